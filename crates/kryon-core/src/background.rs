@@ -0,0 +1,311 @@
+// crates/kryon-core/src/background.rs
+//! `background_image` and how it fills an element's box, as in CSS:
+//! `background_repeat` (`repeat`, `repeat-x`, `repeat-y`, `no-repeat`),
+//! `background_size` (`auto`, `cover`, `contain`, or one or two lengths)
+//! and `background_position` (keywords, lengths or percentages).
+//!
+//! One tile is sized and placed against the box, then repeated from there
+//! along the axes that repeat. Percentages in `background_position` move the
+//! tile's own point of that percentage onto the box's, so `50% 50%` centers
+//! it and `100% 0` puts it in the top-right corner.
+
+use crate::{css::parse_length, CSSUnit, CSSUnitValue, LengthContext};
+use glam::Vec2;
+
+/// Most tiles a backend that draws them one by one draws for one box
+pub const MAX_TILES: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackgroundRepeat {
+    #[default]
+    Repeat,
+    RepeatX,
+    RepeatY,
+    NoRepeat,
+}
+
+impl BackgroundRepeat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "repeat" => Some(Self::Repeat),
+            "repeat-x" => Some(Self::RepeatX),
+            "repeat-y" => Some(Self::RepeatY),
+            "no-repeat" => Some(Self::NoRepeat),
+            _ => None,
+        }
+    }
+
+    pub fn repeats_x(self) -> bool {
+        matches!(self, Self::Repeat | Self::RepeatX)
+    }
+
+    pub fn repeats_y(self) -> bool {
+        matches!(self, Self::Repeat | Self::RepeatY)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BackgroundSize {
+    /// The image's own size
+    #[default]
+    Auto,
+    /// Scaled to cover the whole box, keeping its aspect ratio
+    Cover,
+    /// Scaled to fit inside the box, keeping its aspect ratio
+    Contain,
+    /// Width and height; `None` for `auto`, which keeps the aspect ratio
+    Explicit(Option<CSSUnitValue>, Option<CSSUnitValue>),
+}
+
+impl BackgroundSize {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "auto" | "auto auto" => return Some(Self::Auto),
+            "cover" => return Some(Self::Cover),
+            "contain" => return Some(Self::Contain),
+            _ => {}
+        }
+        let length = |token: &str| match token {
+            "auto" => Some(None),
+            token => parse_length(token).filter(|length| length.value >= 0.0 && is_length(length)).map(Some),
+        };
+        let mut tokens = value.split_whitespace();
+        let width = length(tokens.next()?)?;
+        // One value sets the width; the height follows the aspect ratio
+        let height = tokens.next().map_or(Some(None), length)?;
+        if tokens.next().is_some() {
+            return None;
+        }
+        Some(Self::Explicit(width, height))
+    }
+
+    /// `em` and `rem` lengths as pixels
+    pub fn resolve_font_relative(&self, context: &LengthContext) -> Self {
+        match self {
+            Self::Explicit(width, height) => Self::Explicit(
+                width.map(|width| width.resolve_font_relative(context)),
+                height.map(|height| height.resolve_font_relative(context)),
+            ),
+            other => *other,
+        }
+    }
+
+    /// The size of one tile in a box of `box_size`, for an image of
+    /// `image_size`. `None` while an image size it depends on is unknown.
+    pub fn tile_size(&self, box_size: Vec2, image_size: Option<Vec2>) -> Option<Vec2> {
+        let image_size = image_size.filter(|size| size.x > 0.0 && size.y > 0.0);
+        match *self {
+            Self::Auto => image_size,
+            Self::Cover | Self::Contain => {
+                let image_size = image_size?;
+                let scales = box_size / image_size;
+                let scale = if *self == Self::Cover { scales.max_element() } else { scales.min_element() };
+                Some(image_size * scale)
+            }
+            Self::Explicit(width, height) => {
+                let resolve = |length: Option<CSSUnitValue>, base: f32| {
+                    length.and_then(|length| length.to_pixels(&LengthContext { percent_base: base, ..LengthContext::default() }))
+                };
+                match (resolve(width, box_size.x), resolve(height, box_size.y)) {
+                    (Some(width), Some(height)) => Some(Vec2::new(width, height)),
+                    (Some(width), None) => image_size.map(|size| Vec2::new(width, width * size.y / size.x)),
+                    (None, Some(height)) => image_size.map(|size| Vec2::new(height * size.x / size.y, height)),
+                    (None, None) => image_size,
+                }
+            }
+        }
+    }
+}
+
+/// Where the first tile goes, each axis a length or a percentage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundPosition {
+    pub x: CSSUnitValue,
+    pub y: CSSUnitValue,
+}
+
+impl Default for BackgroundPosition {
+    /// The top-left corner
+    fn default() -> Self {
+        let zero = CSSUnitValue::new(0.0, CSSUnit::Percentage);
+        Self { x: zero, y: zero }
+    }
+}
+
+impl BackgroundPosition {
+    /// Parses one or two keywords or lengths, e.g. `center`, `right top`,
+    /// `bottom left`, `25% 75%` or `10px 2em`. One value leaves the other
+    /// axis centered.
+    pub fn parse(value: &str) -> Option<Self> {
+        let percent = |value: f64| CSSUnitValue::new(value, CSSUnit::Percentage);
+        let center = percent(50.0);
+        let tokens: Vec<String> = value.split_whitespace().map(str::to_ascii_lowercase).collect();
+        let horizontal = |token: &str| match token {
+            "left" => Some(percent(0.0)),
+            "center" => Some(center),
+            "right" => Some(percent(100.0)),
+            token => parse_length(token).filter(is_length),
+        };
+        let vertical = |token: &str| match token {
+            "top" => Some(percent(0.0)),
+            "center" => Some(center),
+            "bottom" => Some(percent(100.0)),
+            token => parse_length(token).filter(is_length),
+        };
+        let (x, y) = match tokens.as_slice() {
+            [only] if matches!(only.as_str(), "top" | "bottom") => (center, vertical(only)?),
+            [only] => (horizontal(only)?, center),
+            // Keywords may come vertical first: `top right`
+            [first, second] if matches!(first.as_str(), "top" | "bottom") || matches!(second.as_str(), "left" | "right") => {
+                (horizontal(second)?, vertical(first)?)
+            }
+            [first, second] => (horizontal(first)?, vertical(second)?),
+            _ => return None,
+        };
+        Some(Self { x, y })
+    }
+
+    /// `em` and `rem` lengths as pixels
+    pub fn resolve_font_relative(&self, context: &LengthContext) -> Self {
+        Self { x: self.x.resolve_font_relative(context), y: self.y.resolve_font_relative(context) }
+    }
+
+    /// Top-left of a tile of `tile_size` placed in a box of `box_size`,
+    /// relative to the box
+    pub fn tile_offset(&self, box_size: Vec2, tile_size: Vec2) -> Vec2 {
+        let free = box_size - tile_size;
+        let resolve = |length: CSSUnitValue, base: f32| {
+            length.to_pixels(&LengthContext { percent_base: base, ..LengthContext::default() }).unwrap_or(0.0)
+        };
+        Vec2::new(resolve(self.x, free.x), resolve(self.y, free.y))
+    }
+}
+
+/// One tile clipped to the box: where it's drawn, relative to the box, and
+/// the part of the image it shows as texture coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilePiece {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+}
+
+/// The part of a box of `box_size` the tiles cover, as its top-left and
+/// bottom-right corners relative to the box. `None` if they miss it.
+pub fn painted_area(box_size: Vec2, tile_offset: Vec2, tile_size: Vec2, repeat: BackgroundRepeat) -> Option<(Vec2, Vec2)> {
+    let span = |repeats: bool, offset: f32, tile: f32, extent: f32| {
+        if repeats { (0.0, extent) } else { (offset.max(0.0), (offset + tile).min(extent)) }
+    };
+    let (left, right) = span(repeat.repeats_x(), tile_offset.x, tile_size.x, box_size.x);
+    let (top, bottom) = span(repeat.repeats_y(), tile_offset.y, tile_size.y, box_size.y);
+    (right > left && bottom > top).then(|| (Vec2::new(left, top), Vec2::new(right, bottom)))
+}
+
+/// The tiles covering a box of `box_size`, each clipped to it, for
+/// backends that can't wrap texture coordinates. At most `MAX_TILES`.
+pub fn tile_pieces(box_size: Vec2, tile_offset: Vec2, tile_size: Vec2, repeat: BackgroundRepeat) -> Vec<TilePiece> {
+    let mut pieces = Vec::new();
+    if tile_size.x <= 0.0 || tile_size.y <= 0.0 {
+        return pieces;
+    }
+    let Some((area_min, area_max)) = painted_area(box_size, tile_offset, tile_size, repeat) else {
+        return pieces;
+    };
+    // The tile at or before the area's top-left corner, in tile steps from the placed one
+    let first = ((area_min - tile_offset) / tile_size).floor();
+    let mut y = tile_offset.y + first.y * tile_size.y;
+    while y < area_max.y {
+        let mut x = tile_offset.x + first.x * tile_size.x;
+        while x < area_max.x {
+            let min = Vec2::new(x, y).max(area_min);
+            let max = (Vec2::new(x, y) + tile_size).min(area_max);
+            if max.x > min.x && max.y > min.y {
+                if pieces.len() == MAX_TILES {
+                    return pieces;
+                }
+                pieces.push(TilePiece {
+                    position: min,
+                    size: max - min,
+                    uv_min: (min - Vec2::new(x, y)) / tile_size,
+                    uv_max: (max - Vec2::new(x, y)) / tile_size,
+                });
+            }
+            x += tile_size.x;
+        }
+        y += tile_size.y;
+    }
+    pieces
+}
+
+fn is_length(length: &CSSUnitValue) -> bool {
+    !matches!(length.unit, CSSUnit::Degrees | CSSUnit::Radians | CSSUnit::Turns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_css_keywords_and_lengths() {
+        assert_eq!(BackgroundRepeat::parse("Repeat-X"), Some(BackgroundRepeat::RepeatX));
+        assert_eq!(BackgroundRepeat::parse("space"), None);
+
+        let px = |value: f64| Some(CSSUnitValue::new(value, CSSUnit::Pixels));
+        assert_eq!(BackgroundSize::parse("cover"), Some(BackgroundSize::Cover));
+        assert_eq!(BackgroundSize::parse("32px"), Some(BackgroundSize::Explicit(px(32.0), None)));
+        assert_eq!(BackgroundSize::parse("auto 10px"), Some(BackgroundSize::Explicit(None, px(10.0))));
+        assert_eq!(BackgroundSize::parse("10deg"), None);
+
+        let percent = |value: f64| CSSUnitValue::new(value, CSSUnit::Percentage);
+        let position = |value: &str| BackgroundPosition::parse(value).map(|position| (position.x, position.y));
+        assert_eq!(position("center"), Some((percent(50.0), percent(50.0))));
+        assert_eq!(position("top right"), Some((percent(100.0), percent(0.0))));
+        assert_eq!(position("bottom"), Some((percent(50.0), percent(100.0))));
+        assert_eq!(position("10px 25%"), Some((px(10.0).unwrap(), percent(25.0))));
+        assert_eq!(position("left right"), None);
+    }
+
+    #[test]
+    fn sizes_and_places_the_first_tile() {
+        let box_size = Vec2::new(200.0, 100.0);
+        let image = Some(Vec2::new(50.0, 25.0));
+        assert_eq!(BackgroundSize::Auto.tile_size(box_size, image), image);
+        assert_eq!(BackgroundSize::Auto.tile_size(box_size, None), None);
+        assert_eq!(BackgroundSize::Cover.tile_size(box_size, image), Some(Vec2::new(200.0, 100.0)));
+        assert_eq!(BackgroundSize::Contain.tile_size(Vec2::new(100.0, 100.0), image), Some(Vec2::new(100.0, 50.0)));
+        assert_eq!(BackgroundSize::parse("50% auto").unwrap().tile_size(box_size, image), Some(Vec2::new(100.0, 50.0)));
+        // Both lengths given: the image's size isn't needed
+        assert_eq!(BackgroundSize::parse("16px 16px").unwrap().tile_size(box_size, None), Some(Vec2::splat(16.0)));
+
+        let tile = Vec2::new(50.0, 25.0);
+        assert_eq!(BackgroundPosition::default().tile_offset(box_size, tile), Vec2::ZERO);
+        assert_eq!(BackgroundPosition::parse("center").unwrap().tile_offset(box_size, tile), Vec2::new(75.0, 37.5));
+        assert_eq!(BackgroundPosition::parse("right 10px").unwrap().tile_offset(box_size, tile), Vec2::new(150.0, 10.0));
+    }
+
+    #[test]
+    fn tiles_cover_the_repeating_axes_clipped_to_the_box() {
+        let box_size = Vec2::new(100.0, 40.0);
+        let tile = Vec2::new(30.0, 30.0);
+
+        let pieces = tile_pieces(box_size, Vec2::new(10.0, 5.0), tile, BackgroundRepeat::RepeatX);
+        let rects: Vec<(Vec2, Vec2)> = pieces.iter().map(|piece| (piece.position, piece.size)).collect();
+        assert_eq!(rects, vec![
+            (Vec2::new(0.0, 5.0), Vec2::new(10.0, 30.0)),
+            (Vec2::new(10.0, 5.0), Vec2::new(30.0, 30.0)),
+            (Vec2::new(40.0, 5.0), Vec2::new(30.0, 30.0)),
+            (Vec2::new(70.0, 5.0), Vec2::new(30.0, 30.0)),
+        ]);
+        // The clipped first tile shows the right third of the image
+        assert_eq!(pieces[0].uv_min, Vec2::new(2.0 / 3.0, 0.0));
+        assert_eq!(pieces[0].uv_max, Vec2::ONE);
+
+        assert_eq!(tile_pieces(box_size, Vec2::ZERO, tile, BackgroundRepeat::Repeat).len(), 4 * 2);
+        assert_eq!(painted_area(box_size, Vec2::new(80.0, 20.0), tile, BackgroundRepeat::NoRepeat),
+            Some((Vec2::new(80.0, 20.0), Vec2::new(100.0, 40.0))));
+        assert_eq!(painted_area(box_size, Vec2::new(120.0, 0.0), tile, BackgroundRepeat::NoRepeat), None);
+        assert_eq!(tile_pieces(box_size, Vec2::ZERO, Vec2::splat(0.01), BackgroundRepeat::Repeat).len(), MAX_TILES);
+    }
+}
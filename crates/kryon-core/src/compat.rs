@@ -0,0 +1,223 @@
+// crates/kryon-core/src/compat.rs
+//! KRB versions, and reading files written for other versions.
+//!
+//! A KRB's `version` holds its major version in the high byte and its minor
+//! version in the low one: `0x0500` is 5.0, [`CURRENT_VERSION`], the newest
+//! this renderer reads in full.
+//!
+//! Some parts of the format came with later versions; [`KrbFeature`] lists
+//! them with the version each came in. Older files have reserved bytes
+//! where the header now keeps those parts' counts and offsets, so the
+//! parser doesn't read parts a file's version predates, and warns if their
+//! count isn't zero.
+//!
+//! A file from a newer minor version of the same major version loads. What
+//! this renderer doesn't know in it, such as new properties, value types
+//! and event types, is skipped. A newer major version may have changed what
+//! the known parts mean, so those files are refused with
+//! `UnsupportedVersion` unless they're parsed with
+//! [`ParseOptions::tolerant`], which reads them the same way as a newer
+//! minor version, best effort. Either way the file's
+//! [`CompatibilityReport`] records the versions and everything that was
+//! skipped.
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// The newest KRB version this renderer reads in full
+pub const CURRENT_VERSION: KrbVersion = KrbVersion(0x0500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KrbVersion(pub u16);
+
+impl KrbVersion {
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self(((major as u16) << 8) | minor as u16)
+    }
+
+    pub fn major(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    pub fn minor(self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Whether files of this version can have `feature`
+    pub fn has(self, feature: KrbFeature) -> bool {
+        self >= feature.since()
+    }
+}
+
+impl fmt::Display for KrbVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major(), self.minor())
+    }
+}
+
+impl Serialize for KrbVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A part of the format that came after the first versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KrbFeature {
+    TemplateVariables,
+    TemplateBindings,
+    Transforms,
+}
+
+impl KrbFeature {
+    pub const ALL: [KrbFeature; 3] = [KrbFeature::TemplateVariables, KrbFeature::TemplateBindings, KrbFeature::Transforms];
+
+    /// The version that brought it
+    pub fn since(self) -> KrbVersion {
+        match self {
+            KrbFeature::TemplateVariables | KrbFeature::TemplateBindings => KrbVersion::new(4, 0),
+            KrbFeature::Transforms => KrbVersion::new(5, 0),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KrbFeature::TemplateVariables => "template variables",
+            KrbFeature::TemplateBindings => "template bindings",
+            KrbFeature::Transforms => "transforms",
+        }
+    }
+}
+
+impl fmt::Display for KrbFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Something in a file that this renderer read differently than the
+/// file's version means, or skipped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CompatWarning {
+    /// A newer minor version: anything added in it is skipped
+    NewerMinorVersion,
+    /// A newer major version, read with [`ParseOptions::tolerant`]
+    NewerMajorVersion,
+    /// The header counts entries of a part the file's version predates;
+    /// they weren't read
+    IgnoredSection { feature: KrbFeature, count: u16 },
+    /// A property id this renderer doesn't know, in `table` (`style` or
+    /// `element`)
+    UnknownProperty { table: &'static str, property_id: u8 },
+    /// A value type this renderer doesn't know, in `table` (`custom
+    /// property` or `transform`)
+    UnknownValueType { table: &'static str, value_type: u8 },
+    UnknownEventType { event_id: u8 },
+}
+
+impl fmt::Display for CompatWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatWarning::NewerMinorVersion => write!(f, "newer than {}; anything added since is skipped", CURRENT_VERSION),
+            CompatWarning::NewerMajorVersion => write!(f, "a newer major version than {}, read as far as it can be", CURRENT_VERSION),
+            CompatWarning::IgnoredSection { feature, count } => {
+                write!(f, "ignored {} {} the file's version predates", count, feature)
+            }
+            CompatWarning::UnknownProperty { table, property_id } => write!(f, "skipped unknown {} property 0x{:02X}", table, property_id),
+            CompatWarning::UnknownValueType { table, value_type } => write!(f, "skipped {} values of unknown type 0x{:02X}", table, value_type),
+            CompatWarning::UnknownEventType { event_id } => write!(f, "skipped handlers for unknown event 0x{:02X}", event_id),
+        }
+    }
+}
+
+/// How a file's version compares to this renderer's, and what reading it
+/// skipped
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompatibilityReport {
+    pub file_version: KrbVersion,
+    pub renderer_version: KrbVersion,
+    /// The versioned parts of the format the file can have
+    pub features: Vec<KrbFeature>,
+    /// Each once, in the order they were first met
+    pub warnings: Vec<CompatWarning>,
+}
+
+impl CompatibilityReport {
+    pub fn new(file_version: KrbVersion) -> Self {
+        Self {
+            file_version,
+            renderer_version: CURRENT_VERSION,
+            features: KrbFeature::ALL.into_iter().filter(|&feature| file_version.has(feature)).collect(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Whether the file is from a newer version than this renderer's
+    pub fn is_newer(&self) -> bool {
+        self.file_version > self.renderer_version
+    }
+
+    /// Whether the file was read exactly as its version means
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub(crate) fn warn(&mut self, warning: CompatWarning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+}
+
+impl Default for CompatibilityReport {
+    fn default() -> Self {
+        Self::new(CURRENT_VERSION)
+    }
+}
+
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KRB {} read by a {} renderer", self.file_version, self.renderer_version)?;
+        for warning in &self.warnings {
+            write!(f, "\n  {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Read files from newer major versions too, best effort, rather than
+    /// refuse them
+    pub tolerant: bool,
+}
+
+impl ParseOptions {
+    pub fn tolerant() -> Self {
+        Self { tolerant: true }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_have_the_features_they_came_after() {
+        let legacy = KrbVersion(0x0302);
+        assert_eq!((legacy.major(), legacy.minor(), legacy.to_string()), (3, 2, "3.2".to_string()));
+        assert_eq!(KrbVersion::new(5, 1), KrbVersion(0x0501));
+        assert!(!legacy.has(KrbFeature::TemplateVariables));
+        assert_eq!(CompatibilityReport::new(KrbVersion::new(4, 7)).features, vec![KrbFeature::TemplateVariables, KrbFeature::TemplateBindings]);
+        assert_eq!(CompatibilityReport::default().features, KrbFeature::ALL.to_vec());
+
+        let mut report = CompatibilityReport::new(KrbVersion::new(5, 1));
+        assert!(report.is_newer() && report.is_clean());
+        report.warn(CompatWarning::NewerMinorVersion);
+        report.warn(CompatWarning::UnknownProperty { table: "element", property_id: 0xA0 });
+        report.warn(CompatWarning::UnknownProperty { table: "element", property_id: 0xA0 });
+        assert_eq!(report.to_string(), "KRB 5.1 read by a 5.0 renderer\n  newer than 5.0; anything added since is skipped\n  skipped unknown element property 0xA0");
+    }
+}
@@ -0,0 +1,462 @@
+// crates/kryon-core/src/css.rs
+//! CSS color and length parsing shared by the parser, layout and backends.
+//!
+//! Colors: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`/`rgba()`,
+//! `hsl()`/`hsla()` (comma or space separated, optional `/ alpha`), the CSS
+//! named colors and `transparent`. Lengths: a number followed by one of `px`,
+//! `em`, `rem`, `vw`, `vh`, `%`, `deg`, `rad`, `grad`, `turn`, or nothing.
+//! Keywords and function names are case-insensitive.
+
+use crate::{CSSUnit, CSSUnitValue};
+use glam::{Vec2, Vec4};
+
+/// Parses any supported CSS color into RGBA components in `0.0..=1.0`.
+pub fn parse_color(value: &str) -> Option<Vec4> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+
+    let lower = value.to_ascii_lowercase();
+    if let Some((name, args)) = lower.strip_suffix(')').and_then(|v| v.split_once('(')) {
+        return match name.trim() {
+            "rgb" | "rgba" => parse_rgb_args(args),
+            "hsl" | "hsla" => parse_hsl_args(args),
+            _ => None,
+        };
+    }
+
+    named_color(&lower)
+}
+
+/// Parses only the hex forms, for values that must be written as `#...`.
+pub fn parse_hex_color(value: &str) -> Option<Vec4> {
+    parse_hex(value.trim().strip_prefix('#')?)
+}
+
+/// Formats a color as `#rrggbb`, or `#rrggbbaa` when it isn't opaque.
+pub fn format_hex_color(color: Vec4) -> String {
+    let [r, g, b, a] = color.to_array().map(to_byte);
+    if a == 255 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+fn to_byte(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn parse_hex(hex: &str) -> Option<Vec4> {
+    if !hex.is_ascii() {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+    let pair = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let [r, g, b, a] = match hex.len() {
+        3 | 4 => {
+            let short = |i: usize| digit(i).map(|d| d * 17);
+            [short(0)?, short(1)?, short(2)?, if hex.len() == 4 { short(3)? } else { 255 }]
+        }
+        6 | 8 => [pair(0)?, pair(2)?, pair(4)?, if hex.len() == 8 { pair(6)? } else { 255 }],
+        _ => return None,
+    };
+    Some(Vec4::new(r as f32, g as f32, b as f32, a as f32) / 255.0)
+}
+
+/// Splits `a, b, c / d`, `a b c / d` or `a, b, c, d` into components and an
+/// optional alpha.
+fn split_args(args: &str) -> Option<(Vec<&str>, Option<&str>)> {
+    let (components, alpha) = match args.split_once('/') {
+        Some((components, alpha)) => (components, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let mut parts: Vec<&str> = if components.contains(',') {
+        components.split(',').map(str::trim).collect()
+    } else {
+        components.split_whitespace().collect()
+    };
+    match (parts.len(), alpha) {
+        (3, _) => Some((parts, alpha)),
+        (4, None) => {
+            let alpha = parts.pop();
+            Some((parts, alpha))
+        }
+        _ => None,
+    }
+}
+
+/// `0.5` or `50%`, clamped to `0.0..=1.0`.
+fn parse_alpha(value: Option<&str>) -> Option<f32> {
+    let Some(value) = value else {
+        return Some(1.0);
+    };
+    let alpha = match value.strip_suffix('%') {
+        Some(percent) => parse_number(percent)? / 100.0,
+        None => parse_number(value)?,
+    };
+    Some(alpha.clamp(0.0, 1.0))
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+    let number: f32 = value.trim().parse().ok()?;
+    number.is_finite().then_some(number)
+}
+
+fn parse_rgb_args(args: &str) -> Option<Vec4> {
+    let (parts, alpha) = split_args(args)?;
+    let channel = |part: &str| match part.strip_suffix('%') {
+        Some(percent) => parse_number(percent).map(|p| p / 100.0),
+        None => parse_number(part).map(|n| n / 255.0),
+    };
+    Some(Vec4::new(
+        channel(parts[0])?.clamp(0.0, 1.0),
+        channel(parts[1])?.clamp(0.0, 1.0),
+        channel(parts[2])?.clamp(0.0, 1.0),
+        parse_alpha(alpha)?,
+    ))
+}
+
+fn parse_hsl_args(args: &str) -> Option<Vec4> {
+    let (parts, alpha) = split_args(args)?;
+    let hue = match parse_length(parts[0])? {
+        CSSUnitValue { value, unit: CSSUnit::Number } => value as f32,
+        angle => angle.to_degrees()?,
+    };
+    let percent = |part: &str| parse_number(part.strip_suffix('%').unwrap_or(part)).map(|p| (p / 100.0).clamp(0.0, 1.0));
+    let rgb = hsl_to_rgb(hue, percent(parts[1])?, percent(parts[2])?);
+    Some(rgb.extend(parse_alpha(alpha)?))
+}
+
+/// `hue` in degrees, `saturation` and `lightness` in `0.0..=1.0`.
+pub fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> glam::Vec3 {
+    let hue = hue.rem_euclid(360.0) / 30.0;
+    let a = saturation * lightness.min(1.0 - lightness);
+    let channel = |n: f32| {
+        let k = (n + hue) % 12.0;
+        lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    glam::Vec3::new(channel(0.0), channel(8.0), channel(4.0))
+}
+
+/// Parses a number with an optional unit. Unitless numbers are `CSSUnit::Number`.
+pub fn parse_length(value: &str) -> Option<CSSUnitValue> {
+    let value = value.trim();
+    let split = value
+        .rfind(|c: char| c.is_ascii_digit() || c == '.')
+        .map_or(0, |i| i + 1);
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    if !number.is_finite() {
+        return None;
+    }
+
+    let (value, unit) = match unit.to_ascii_lowercase().as_str() {
+        "" => (number, CSSUnit::Number),
+        "px" => (number, CSSUnit::Pixels),
+        "em" => (number, CSSUnit::Em),
+        "rem" => (number, CSSUnit::Rem),
+        "vw" => (number, CSSUnit::ViewportWidth),
+        "vh" => (number, CSSUnit::ViewportHeight),
+        "%" => (number, CSSUnit::Percentage),
+        "deg" => (number, CSSUnit::Degrees),
+        "rad" => (number, CSSUnit::Radians),
+        "grad" => (number * 0.9, CSSUnit::Degrees),
+        "turn" => (number, CSSUnit::Turns),
+        _ => return None,
+    };
+    Some(CSSUnitValue { value, unit })
+}
+
+/// What relative lengths are resolved against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthContext {
+    pub font_size: f32,
+    pub root_font_size: f32,
+    pub viewport: Vec2,
+    /// The size `%` refers to, e.g. the parent's width
+    pub percent_base: f32,
+}
+
+impl Default for LengthContext {
+    fn default() -> Self {
+        Self {
+            font_size: crate::DEFAULT_FONT_SIZE,
+            root_font_size: crate::DEFAULT_FONT_SIZE,
+            viewport: Vec2::new(800.0, 600.0),
+            percent_base: 0.0,
+        }
+    }
+}
+
+impl LengthContext {
+    /// `em` resolves against `font_size`, `rem` against `root_font_size`.
+    pub fn for_fonts(font_size: f32, root_font_size: f32) -> Self {
+        Self { font_size, root_font_size, ..Self::default() }
+    }
+}
+
+/// Rewrites the `em` and `rem` lengths in a space-separated value, such as
+/// a box shadow, as pixels. Other tokens are kept as they are.
+pub fn resolve_font_relative(value: &str, context: &LengthContext) -> String {
+    let font_relative = |token: &str| parse_length(token).filter(|length| matches!(length.unit, CSSUnit::Em | CSSUnit::Rem));
+    if !value.split_whitespace().any(|token| font_relative(token).is_some()) {
+        return value.to_string();
+    }
+    value.split_whitespace()
+        .map(|token| match font_relative(token).and_then(|length| length.to_pixels(context)) {
+            Some(pixels) => format!("{}px", pixels),
+            None => token.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl CSSUnitValue {
+    pub fn new(value: f64, unit: CSSUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Resolves a length to pixels; unitless numbers are taken as pixels.
+    /// `None` for angles.
+    pub fn to_pixels(&self, context: &LengthContext) -> Option<f32> {
+        let value = self.value as f32;
+        Some(match self.unit {
+            CSSUnit::Pixels | CSSUnit::Number => value,
+            CSSUnit::Em => value * context.font_size,
+            CSSUnit::Rem => value * context.root_font_size,
+            CSSUnit::ViewportWidth => value * context.viewport.x / 100.0,
+            CSSUnit::ViewportHeight => value * context.viewport.y / 100.0,
+            CSSUnit::Percentage => value * context.percent_base / 100.0,
+            CSSUnit::Degrees | CSSUnit::Radians | CSSUnit::Turns => return None,
+        })
+    }
+
+    /// `em` and `rem` as pixels; other units are returned unchanged.
+    pub fn resolve_font_relative(&self, context: &LengthContext) -> Self {
+        match self.unit {
+            CSSUnit::Em | CSSUnit::Rem => Self::new(self.to_pixels(context).unwrap_or(0.0) as f64, CSSUnit::Pixels),
+            _ => *self,
+        }
+    }
+
+    /// Angles in degrees. `None` for lengths and plain numbers.
+    pub fn to_degrees(&self) -> Option<f32> {
+        let value = self.value as f32;
+        match self.unit {
+            CSSUnit::Degrees => Some(value),
+            CSSUnit::Radians => Some(value.to_degrees()),
+            CSSUnit::Turns => Some(value * 360.0),
+            _ => None,
+        }
+    }
+
+    /// Angles in radians. `None` for lengths and plain numbers.
+    pub fn to_radians(&self) -> Option<f32> {
+        self.to_degrees().map(f32::to_radians)
+    }
+
+    /// A plain number or a percentage as a fraction (`150%` is `1.5`), for
+    /// factors such as scale.
+    pub fn to_factor(&self) -> Option<f32> {
+        match self.unit {
+            CSSUnit::Number => Some(self.value as f32),
+            CSSUnit::Percentage => Some(self.value as f32 / 100.0),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CSSUnitValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = match self.unit {
+            CSSUnit::Number => "",
+            CSSUnit::Pixels => "px",
+            CSSUnit::Em => "em",
+            CSSUnit::Rem => "rem",
+            CSSUnit::ViewportWidth => "vw",
+            CSSUnit::ViewportHeight => "vh",
+            CSSUnit::Percentage => "%",
+            CSSUnit::Degrees => "deg",
+            CSSUnit::Radians => "rad",
+            CSSUnit::Turns => "turn",
+        };
+        write!(f, "{}{}", self.value, unit)
+    }
+}
+
+fn named_color(name: &str) -> Option<Vec4> {
+    if name == "transparent" {
+        return Some(Vec4::ZERO);
+    }
+    let index = NAMED_COLORS.binary_search_by_key(&name, |&(n, _)| n).ok()?;
+    let rgb = NAMED_COLORS[index].1;
+    let channel = |shift: u32| ((rgb >> shift) & 0xff) as f32 / 255.0;
+    Some(Vec4::new(channel(16), channel(8), channel(0), 1.0))
+}
+
+/// CSS Color Module Level 4 named colors, sorted for binary search.
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff), ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4), ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd), ("blue", 0x0000ff), ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887), ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00), ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed), ("cornsilk", 0xfff8dc), ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff), ("darkblue", 0x00008b), ("darkcyan", 0x008b8b), ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400), ("darkgrey", 0xa9a9a9), ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b), ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00), ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000), ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f), ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f), ("darkturquoise", 0x00ced1), ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493), ("deepskyblue", 0x00bfff), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222), ("floralwhite", 0xfffaf0), ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc), ("ghostwhite", 0xf8f8ff), ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520), ("gray", 0x808080), ("green", 0x008000), ("greenyellow", 0xadff2f),
+    ("grey", 0x808080), ("honeydew", 0xf0fff0), ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082), ("ivory", 0xfffff0), ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5), ("lawngreen", 0x7cfc00), ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080), ("lightcyan", 0xe0ffff), ("lightgoldenrodyellow", 0xfafad2), ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90), ("lightgrey", 0xd3d3d3), ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa), ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00), ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000), ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3), ("mediumpurple", 0x9370db), ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee), ("mediumspringgreen", 0x00fa9a), ("mediumturquoise", 0x48d1cc), ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970), ("mintcream", 0xf5fffa), ("mistyrose", 0xffe4e1), ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead), ("navy", 0x000080), ("oldlace", 0xfdf5e6), ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23), ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa), ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee), ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9), ("peru", 0xcd853f), ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd), ("powderblue", 0xb0e0e6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+    ("red", 0xff0000), ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072), ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57), ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d), ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb), ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xfffafa), ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4), ("tan", 0xd2b48c), ("teal", 0x008080), ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347), ("turquoise", 0x40e0d0), ("violet", 0xee82ee), ("wheat", 0xf5deb3),
+    ("white", 0xffffff), ("whitesmoke", 0xf5f5f5), ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn bytes(color: Vec4) -> [u8; 4] {
+        color.to_array().map(to_byte)
+    }
+
+    #[test]
+    fn parses_each_color_syntax() {
+        let red = Some([255, 0, 0, 255]);
+        for input in [
+            "#f00", "#F00F", "#ff0000", "#ff0000ff", "red", "RED", "rgb(255, 0, 0)", "rgb(255 0 0)",
+            "rgba(255,0,0,1)", "rgb(100% 0% 0% / 100%)", "hsl(0, 100%, 50%)", "hsl(360deg 100% 50%)",
+            "hsla(1turn, 100%, 50%, 1)",
+        ] {
+            assert_eq!(parse_color(input).map(bytes), red, "{}", input);
+        }
+        assert_eq!(parse_color("rgba(0, 0, 255, 0.5)").map(bytes), Some([0, 0, 255, 128]));
+        assert_eq!(parse_color("hsl(120 100% 25%)").map(bytes), Some([0, 128, 0, 255]));
+        assert_eq!(parse_color("transparent"), Some(Vec4::ZERO));
+        assert_eq!(parse_color("rebeccapurple").map(bytes), Some([0x66, 0x33, 0x99, 255]));
+    }
+
+    #[test]
+    fn parses_hex_ramp() {
+        assert_eq!(parse_hex_color("#ff000080"), Some(Vec4::new(1.0, 0.0, 0.0, 128.0 / 255.0)));
+        assert_eq!(parse_hex_color("#00ff00"), Some(Vec4::new(0.0, 1.0, 0.0, 1.0)));
+        assert_eq!(parse_hex_color("red"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_colors() {
+        for input in ["", "#", "#ff", "#fffff", "#gggggg", "rgb(1, 2)", "rgb(1, 2, x)", "hsl()", "notacolor", "#ÿÿÿ"] {
+            assert_eq!(parse_color(input), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn named_colors_are_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn parses_lengths_and_converts_them() {
+        let context = LengthContext { percent_base: 200.0, ..LengthContext::for_fonts(16.0, 20.0) };
+        let px = |value: &str| parse_length(value).and_then(|length| length.to_pixels(&context));
+        assert_eq!(px("12px"), Some(12.0));
+        assert_eq!(px("1.5em"), Some(24.0));
+        assert_eq!(px("2REM"), Some(40.0));
+        assert_eq!(px("50%"), Some(100.0));
+        assert_eq!(px("10vw"), Some(80.0));
+        assert_eq!(px("-4"), Some(-4.0));
+        assert_eq!(px("90deg"), None);
+
+        assert_eq!(parse_length("0.25turn").and_then(|a| a.to_degrees()), Some(90.0));
+        assert_eq!(parse_length("100grad").and_then(|a| a.to_degrees()), Some(90.0));
+        assert_eq!(parse_length("150%").and_then(|f| f.to_factor()), Some(1.5));
+        assert_eq!(parse_length("12pt"), None);
+        assert_eq!(parse_length("px"), None);
+    }
+
+    #[test]
+    fn resolves_font_relative_lengths_in_values() {
+        let context = LengthContext::for_fonts(20.0, 10.0);
+        assert_eq!(resolve_font_relative("0 0.2em 1rem rgba(0, 0, 0, 0.5)", &context), "0 4px 10px rgba(0, 0, 0, 0.5)");
+        assert_eq!(resolve_font_relative("0  4px #000", &context), "0  4px #000");
+        assert_eq!(parse_length("-1em").unwrap().resolve_font_relative(&context), CSSUnitValue::new(-20.0, CSSUnit::Pixels));
+    }
+
+    proptest! {
+        #[test]
+        fn hex_round_trips(r: u8, g: u8, b: u8, a: u8) {
+            let color = Vec4::new(r as f32, g as f32, b as f32, a as f32) / 255.0;
+            let parsed = parse_color(&format_hex_color(color)).unwrap();
+            prop_assert_eq!(bytes(parsed), [r, g, b, a]);
+        }
+
+        #[test]
+        fn shorthand_hex_matches_long_form(r in 0u8..16, g in 0u8..16, b in 0u8..16) {
+            let short = parse_color(&format!("#{:x}{:x}{:x}", r, g, b));
+            let long = parse_color(&format!("#{0:x}{0:x}{1:x}{1:x}{2:x}{2:x}", r, g, b));
+            prop_assert_eq!(short, long);
+        }
+
+        #[test]
+        fn rgb_function_matches_hex(r: u8, g: u8, b: u8, a in 0u8..=100) {
+            let hex = parse_color(&format!("#{:02x}{:02x}{:02x}", r, g, b)).unwrap();
+            let commas = parse_color(&format!("rgba({}, {}, {}, {})", r, g, b, a as f32 / 100.0)).unwrap();
+            let spaces = parse_color(&format!("RGB({} {} {} / {}%)", r, g, b, a)).unwrap();
+            prop_assert_eq!(bytes(commas), bytes(hex.truncate().extend(a as f32 / 100.0)));
+            prop_assert_eq!(bytes(commas), bytes(spaces));
+        }
+
+        #[test]
+        fn hsl_stays_in_gamut(h in -720.0f32..720.0, s in 0.0f32..=100.0, l in 0.0f32..=100.0) {
+            let color = parse_color(&format!("hsl({} {}% {}%)", h, s, l)).unwrap();
+            prop_assert!(color.cmpge(Vec4::ZERO).all() && color.cmple(Vec4::ONE + 1e-5).all(), "{:?}", color);
+            // Zero saturation is a gray at the given lightness
+            let gray = parse_color(&format!("hsl({}, 0%, {}%)", h, l)).unwrap();
+            prop_assert!((gray.x - l / 100.0).abs() < 1e-5 && gray.x == gray.y && gray.y == gray.z);
+        }
+
+        #[test]
+        fn lengths_round_trip_through_display(
+            value in -1.0e6f64..1.0e6,
+            unit in prop::sample::select(vec![
+                CSSUnit::Number, CSSUnit::Pixels, CSSUnit::Em, CSSUnit::Rem, CSSUnit::ViewportWidth,
+                CSSUnit::ViewportHeight, CSSUnit::Percentage, CSSUnit::Degrees, CSSUnit::Radians, CSSUnit::Turns,
+            ]),
+        ) {
+            let length = CSSUnitValue::new(value, unit);
+            prop_assert_eq!(parse_length(&length.to_string()), Some(length));
+        }
+
+        #[test]
+        fn parsers_never_panic(input in "\\PC{0,24}") {
+            let _ = parse_color(&input);
+            let _ = parse_length(&input);
+        }
+    }
+}
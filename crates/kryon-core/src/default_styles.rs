@@ -0,0 +1,320 @@
+// crates/kryon-core/src/default_styles.rs
+//! The default stylesheet: how elements look before the KRB styles them.
+//!
+//! Like a browser's user-agent stylesheet, it gives buttons, inputs, links
+//! and scrollbars a look of their own, in a light and a dark color scheme,
+//! and sits below everything the KRB sets: an element's style block and
+//! inline properties override it property by property. The built-in one is
+//! `default_styles.css`; hosts can replace it with their own, read with
+//! [`DefaultStyles::parse`].
+//!
+//! The format is a small subset of CSS. Selectors name an element type
+//! (`app`, `container`, `text`, `link`, `image`, `button`, `input`,
+//! `table`) or `scrollbar`, optionally followed by an input type such as
+//! `input[type=checkbox]`, then `:light` or `:dark` to apply in one scheme
+//! only; several can share a rule, separated by commas. Declarations take
+//! `background-color`, `color`, `border-color`, `border-width`,
+//! `border-radius`, `font-size`, `font-weight`, `font-family`,
+//! `text-align`, `opacity` and `cursor`. For `scrollbar`, the background is
+//! the track's, the color the thumb's. Rules for one scheme come after
+//! those for both, and input type rules after those for the whole element
+//! type; otherwise later rules win.
+
+use crate::{parse_color, Element, ElementType, PropertyId, PropertyRegistry, PropertyValue, PropertyValueType};
+use glam::Vec4;
+use std::fmt;
+
+const BUILTIN: &str = include_str!("default_styles.css");
+
+/// Properties the default stylesheet can set
+const PROPERTIES: [PropertyId; 11] = [
+    PropertyId::BackgroundColor, PropertyId::TextColor, PropertyId::BorderColor, PropertyId::BorderWidth,
+    PropertyId::BorderRadius, PropertyId::FontSize, PropertyId::FontWeight, PropertyId::FontFamily,
+    PropertyId::TextAlignment, PropertyId::Opacity, PropertyId::Cursor,
+];
+
+/// Light or dark, as CSS's `prefers-color-scheme`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    /// `light` or `dark`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct DefaultStylesError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Colors of a scroll container's scrollbar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollbarColors {
+    pub track: Vec4,
+    pub thumb: Vec4,
+    pub border: Vec4,
+}
+
+impl Default for ScrollbarColors {
+    fn default() -> Self {
+        Self {
+            track: Vec4::new(0.9, 0.9, 0.9, 1.0),
+            thumb: Vec4::new(0.6, 0.6, 0.6, 1.0),
+            border: Vec4::new(0.8, 0.8, 0.8, 1.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Selector {
+    /// An element type's name, or `scrollbar`
+    target: String,
+    input_type: Option<String>,
+    scheme: Option<ColorScheme>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    selector: Selector,
+    /// Property ids with their values, in the order written
+    properties: Vec<(u8, PropertyValue)>,
+}
+
+/// A parsed default stylesheet. [`Default`] is an empty one, which styles
+/// nothing.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DefaultStyles {
+    /// In the order they apply
+    rules: Vec<Rule>,
+}
+
+impl DefaultStyles {
+    /// The stylesheet built into the runtime
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("the built-in default stylesheet parses")
+    }
+
+    pub fn parse(source: &str) -> Result<Self, DefaultStylesError> {
+        let source = strip_comments(source);
+        let registry = PropertyRegistry::new();
+        let mut rules = Vec::new();
+        let mut rest = source.as_str();
+        let mut consumed = 0;
+        while let Some(open) = rest.find('{') {
+            let line = line_at(&source, consumed + open);
+            let close = rest[open..].find('}').map(|close| open + close)
+                .ok_or_else(|| DefaultStylesError { line, message: "'{' without a closing '}'".to_string() })?;
+            let properties = parse_declarations(&rest[open + 1..close], &registry)
+                .map_err(|message| DefaultStylesError { line, message })?;
+            for selector in rest[..open].split(',') {
+                let selector = parse_selector(selector).map_err(|message| DefaultStylesError { line, message })?;
+                rules.push(Rule { selector, properties: properties.clone() });
+            }
+            consumed += close + 1;
+            rest = &rest[close + 1..];
+        }
+        if !rest.trim().is_empty() {
+            return Err(DefaultStylesError { line: line_at(&source, consumed), message: format!("'{}' without a '{{' block", rest.trim()) });
+        }
+        // Stable, so source order decides within each group
+        rules.sort_by_key(|rule| (rule.selector.scheme.is_some(), rule.selector.input_type.is_some()));
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The properties that apply to `element` in `scheme`, in the order to
+    /// apply them
+    pub fn properties_for<'a>(&'a self, element: &'a Element, scheme: ColorScheme) -> impl Iterator<Item = &'a (u8, PropertyValue)> + 'a {
+        let target = element_type_name(element.element_type);
+        let input_type = (element.element_type == ElementType::Input).then(|| {
+            element.custom_properties.get("input_type").and_then(PropertyValue::as_string).unwrap_or("text")
+        });
+        self.matching(target, input_type, scheme)
+    }
+
+    /// The scrollbar colors for `scheme`
+    pub fn scrollbar_colors(&self, scheme: ColorScheme) -> ScrollbarColors {
+        let mut colors = ScrollbarColors::default();
+        for (property_id, value) in self.matching(Some("scrollbar"), None, scheme) {
+            let Some(color) = value.as_color() else { continue };
+            match PropertyId::from(*property_id) {
+                PropertyId::BackgroundColor => colors.track = color,
+                PropertyId::TextColor => colors.thumb = color,
+                PropertyId::BorderColor => colors.border = color,
+                _ => {}
+            }
+        }
+        colors
+    }
+
+    fn matching<'a>(&'a self, target: Option<&'a str>, input_type: Option<&'a str>, scheme: ColorScheme) -> impl Iterator<Item = &'a (u8, PropertyValue)> + 'a {
+        self.rules.iter()
+            .filter(move |rule| {
+                let selector = &rule.selector;
+                Some(selector.target.as_str()) == target
+                    && selector.input_type.as_deref().is_none_or(|wanted| Some(wanted) == input_type)
+                    && selector.scheme.is_none_or(|wanted| wanted == scheme)
+            })
+            .flat_map(|rule| rule.properties.iter())
+    }
+}
+
+fn element_type_name(element_type: ElementType) -> Option<&'static str> {
+    match element_type {
+        ElementType::App => Some("app"),
+        ElementType::Container => Some("container"),
+        ElementType::Text => Some("text"),
+        ElementType::Link => Some("link"),
+        ElementType::Image => Some("image"),
+        ElementType::Button => Some("button"),
+        ElementType::Input => Some("input"),
+        ElementType::Table => Some("table"),
+        _ => None,
+    }
+}
+
+/// Blanks out `/* */` comments, keeping line breaks so errors point at the
+/// right line
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        let end = rest[start..].find("*/").map_or(rest.len(), |end| start + end + 2);
+        stripped.extend(rest[start..end].chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+        rest = &rest[end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn line_at(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+fn parse_selector(selector: &str) -> Result<Selector, String> {
+    let selector = selector.trim();
+    let (selector, scheme) = match selector.rsplit_once(':') {
+        Some((rest, scheme)) => {
+            (rest, Some(ColorScheme::parse(scheme).ok_or_else(|| format!("'{}' isn't light or dark", scheme))?))
+        }
+        None => (selector, None),
+    };
+    let (target, input_type) = match selector.split_once('[') {
+        Some((target, attribute)) => {
+            let input_type = attribute.strip_suffix(']')
+                .and_then(|attribute| attribute.trim().strip_prefix("type="))
+                .ok_or_else(|| format!("'[{}' isn't [type=...]", attribute))?;
+            (target.trim(), Some(input_type.trim().trim_matches(|c| c == '"' || c == '\'').to_string()))
+        }
+        None => (selector.trim(), None),
+    };
+    let known = target == "scrollbar" || [
+        ElementType::App, ElementType::Container, ElementType::Text, ElementType::Link,
+        ElementType::Image, ElementType::Button, ElementType::Input, ElementType::Table,
+    ].into_iter().any(|element_type| element_type_name(element_type) == Some(target));
+    if !known {
+        return Err(format!("unknown element type '{}'", target));
+    }
+    if input_type.is_some() && target != "input" {
+        return Err(format!("only input takes a [type=...], not {}", target));
+    }
+    Ok(Selector { target: target.to_string(), input_type, scheme })
+}
+
+fn parse_declarations(block: &str, registry: &PropertyRegistry) -> Result<Vec<(u8, PropertyValue)>, String> {
+    let mut properties = Vec::new();
+    for declaration in block.split(';').map(str::trim).filter(|declaration| !declaration.is_empty()) {
+        let (name, value) = declaration.split_once(':')
+            .ok_or_else(|| format!("'{}' isn't name: value", declaration))?;
+        let (name, value) = (name.trim(), value.trim());
+        let metadata = registry.all_properties().iter()
+            .find(|metadata| metadata.name == name && PROPERTIES.contains(&metadata.id))
+            .ok_or_else(|| format!("'{}' can't be set in the default stylesheet", name))?;
+        let parsed = match metadata.value_type {
+            PropertyValueType::Color => parse_color(value).map(PropertyValue::Color),
+            PropertyValueType::Float => value.strip_suffix("px").unwrap_or(value).trim().parse().ok().map(PropertyValue::Float),
+            PropertyValueType::Int => value.parse().ok().map(PropertyValue::Int),
+            _ => Some(PropertyValue::String(value.to_string())),
+        };
+        let parsed = parsed.ok_or_else(|| format!("'{}' isn't a valid {}", value, name))?;
+        properties.push((metadata.id.as_u8(), parsed));
+    }
+    Ok(properties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_apply_by_scheme_then_input_type_then_order() {
+        let styles = DefaultStyles::parse("
+            input:dark { color: white }
+            /* checkboxes { are: special } */
+            input[type=checkbox] { color: blue; border-width: 2px }
+            input, button { color: black; border-radius: 3 }
+        ").unwrap();
+        let checkbox = Element {
+            element_type: ElementType::Input,
+            custom_properties: [("input_type".to_string(), PropertyValue::String("checkbox".into()))].into_iter().collect(),
+            ..Default::default()
+        };
+        let text_color = |element: &Element, scheme| styles.properties_for(element, scheme)
+            .filter(|(id, _)| *id == PropertyId::TextColor.as_u8())
+            .filter_map(|(_, value)| value.as_color())
+            .last();
+        assert_eq!(text_color(&checkbox, ColorScheme::Light), Some(Vec4::new(0.0, 0.0, 1.0, 1.0)));
+        assert_eq!(text_color(&checkbox, ColorScheme::Dark), Some(Vec4::ONE));
+        let text_input = Element { element_type: ElementType::Input, ..Default::default() };
+        assert_eq!(styles.properties_for(&text_input, ColorScheme::Light).count(), 2);
+        assert_eq!(styles.properties_for(&Element::default(), ColorScheme::Dark).count(), 0);
+
+        let error = DefaultStyles::parse("button {\n  colour: red;\n}").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: 'colour' can't be set in the default stylesheet");
+        assert!(DefaultStyles::parse("\n\nslider { color: red }").unwrap_err().to_string().starts_with("line 3: unknown element type"));
+        assert!(DefaultStyles::parse("button:dim { color: red }").is_err());
+        assert!(DefaultStyles::parse("button { color: red").is_err());
+    }
+
+    #[test]
+    fn the_builtin_stylesheet_covers_interactive_elements_in_both_schemes() {
+        let styles = DefaultStyles::builtin();
+        for element_type in [ElementType::Button, ElementType::Input, ElementType::Link] {
+            let element = Element { element_type, ..Default::default() };
+            let light: Vec<_> = styles.properties_for(&element, ColorScheme::Light).collect();
+            let dark: Vec<_> = styles.properties_for(&element, ColorScheme::Dark).collect();
+            assert!(!light.is_empty() && dark.len() > light.len(), "{:?}", element_type);
+        }
+        assert_ne!(styles.scrollbar_colors(ColorScheme::Light), styles.scrollbar_colors(ColorScheme::Dark));
+        assert_eq!(DefaultStyles::default().scrollbar_colors(ColorScheme::Dark), ScrollbarColors::default());
+    }
+}
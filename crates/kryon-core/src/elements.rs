@@ -14,6 +14,8 @@ pub enum ElementType {
     Canvas = 0x05,
     WasmView = 0x06,
     NativeRendererView = 0x07,
+    ParticleEmitter = 0x08,
+    Table = 0x09,
     Button = 0x10,
     Input = 0x11,
     Custom(u8),
@@ -30,6 +32,8 @@ impl From<u8> for ElementType {
             0x05 => ElementType::Canvas,
             0x06 => ElementType::WasmView,
             0x07 => ElementType::NativeRendererView,
+            0x08 => ElementType::ParticleEmitter,
+            0x09 => ElementType::Table,
             0x10 => ElementType::Button,
             0x11 => ElementType::Input,
             other => ElementType::Custom(other),
@@ -156,6 +160,20 @@ pub enum EventType {
     Blur,
     Change,
     Submit,
+    /// The pointer moved onto the element or one of its descendants
+    PointerEnter,
+    /// The pointer moved off the element and all its descendants
+    PointerLeave,
+    /// The pointer moved within the element; bubbles to ancestors
+    PointerMove,
+    /// A text input failed its validation
+    Invalid,
+    /// A `draggable` element started being dragged
+    DragStart,
+    /// Something being dragged moved over a `drop_target`
+    DragOver,
+    /// Something being dragged was released over a `drop_target`
+    Drop,
 }
 
 impl Default for Element {
@@ -185,7 +203,7 @@ impl Default for Element {
             visible: true,
             z_index: 0,
             text: String::new(),
-            font_size: 14.0,
+            font_size: crate::DEFAULT_FONT_SIZE,
             font_weight: FontWeight::Normal,
             font_family: "default".to_string(),
             text_alignment: TextAlignment::Start,
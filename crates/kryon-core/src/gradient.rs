@@ -0,0 +1,333 @@
+// crates/kryon-core/src/gradient.rs
+//! Gradient backgrounds: `linear-gradient(...)` and `radial-gradient(...)`
+//! given as a `background_image`, as in CSS.
+//!
+//! A linear gradient runs along an angle (`90deg`, `0.25turn`) or towards a
+//! side or corner (`to right`, `to bottom left`); without one it runs top to
+//! bottom. Angles follow CSS: `0deg` points up and they turn clockwise. The
+//! gradient line is as long as it needs to be for the first and last stops
+//! to reach the box's corners. A radial gradient is centred on the box and
+//! reaches its farthest corner, as an ellipse with the box's proportions or,
+//! after the `circle` keyword, a circle.
+//!
+//! Stops are a color and an optional offset (`#f00 40%`). Stops without one
+//! are spread evenly between their neighbours, the first at 0% and the last
+//! at 100%, and an offset before an earlier stop's is moved up to it.
+
+use crate::css::{parse_color, parse_length};
+use crate::CSSUnit;
+use glam::{Vec2, Vec4};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientDirection {
+    /// Radians, `0` pointing up and turning clockwise
+    Angle(f32),
+    /// Towards a corner: `x` is `-1` (left) or `1` (right), `y` is `-1`
+    /// (top) or `1` (bottom). The angle depends on the box's proportions.
+    Corner(Vec2),
+}
+
+impl GradientDirection {
+    /// The direction's angle in a box of `size`
+    pub fn angle(&self, size: Vec2) -> f32 {
+        match *self {
+            Self::Angle(angle) => angle,
+            // Perpendicular to the diagonal between the other two corners
+            Self::Corner(corner) => (corner.x * size.y).atan2(-corner.y * size.x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    Linear(GradientDirection),
+    /// `circle` is false for an ellipse with the box's proportions
+    Radial { circle: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    pub color: Vec4,
+    /// `0.0` at the start of the gradient, `1.0` at its end
+    pub offset: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    /// At least two, in order of offset
+    pub stops: Vec<ColorStop>,
+}
+
+impl Gradient {
+    /// Parses `linear-gradient(...)` or `radial-gradient(...)`; `None` for
+    /// anything else, including gradients with fewer than two stops
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (name, args) = value.strip_suffix(')')?.split_once('(')?;
+        let radial = match name.trim().to_ascii_lowercase().as_str() {
+            "linear-gradient" => false,
+            "radial-gradient" => true,
+            _ => return None,
+        };
+        let mut args = split_arguments(args);
+        let kind = if radial {
+            let circle = radial_shape(args.first()?);
+            if circle.is_some() {
+                args.remove(0);
+            }
+            GradientKind::Radial { circle: circle.unwrap_or(false) }
+        } else {
+            let direction = linear_direction(args.first()?);
+            if direction.is_some() {
+                args.remove(0);
+            }
+            GradientKind::Linear(direction.unwrap_or(GradientDirection::Angle(std::f32::consts::PI)))
+        };
+
+        let stops = args.iter().map(|arg| color_stop(arg)).collect::<Option<Vec<_>>>()?;
+        if stops.len() < 2 {
+            return None;
+        }
+        Some(Self { kind, stops: place_stops(&stops) })
+    }
+
+    /// Where `point`, relative to the box's top-left corner, falls along
+    /// the gradient in a box of `size`: `0.0` at the first stop's end,
+    /// `1.0` at the last's. Not clamped.
+    pub fn offset_at(&self, point: Vec2, size: Vec2) -> f32 {
+        let centered = point - size / 2.0;
+        match self.kind {
+            GradientKind::Linear(direction) => {
+                let angle = direction.angle(size);
+                let along = Vec2::new(angle.sin(), -angle.cos());
+                let length = (size.x * angle.sin()).abs() + (size.y * angle.cos()).abs();
+                if length <= 0.0 {
+                    return 0.0;
+                }
+                centered.dot(along) / length + 0.5
+            }
+            GradientKind::Radial { circle: true } => {
+                let radius = (size / 2.0).length();
+                if radius <= 0.0 { 0.0 } else { centered.length() / radius }
+            }
+            GradientKind::Radial { circle: false } => {
+                if size.x <= 0.0 || size.y <= 0.0 {
+                    return 0.0;
+                }
+                (centered / (size / 2.0)).length() / std::f32::consts::SQRT_2
+            }
+        }
+    }
+
+    /// The color at `offset` along the gradient; before the first stop and
+    /// past the last the end colors carry on
+    pub fn color_at(&self, offset: f32) -> Vec4 {
+        color_between(&self.stops, offset)
+    }
+
+    /// The color at `point` in a box of `size`
+    pub fn color_at_point(&self, point: Vec2, size: Vec2) -> Vec4 {
+        self.color_at(self.offset_at(point, size))
+    }
+
+    /// The gradient with every stop's alpha multiplied by `opacity`
+    pub fn with_opacity(&self, opacity: f32) -> Self {
+        let stops = self.stops.iter().map(|stop| ColorStop { color: stop.color * Vec4::new(1.0, 1.0, 1.0, opacity), ..*stop }).collect();
+        Self { kind: self.kind, stops }
+    }
+
+    /// At most `max` stops, for backends with a fixed number of them:
+    /// gradients with more are sampled at `max` evenly spaced offsets
+    pub fn limited_stops(&self, max: usize) -> Vec<ColorStop> {
+        if self.stops.len() <= max || max < 2 {
+            return self.stops.clone();
+        }
+        (0..max).map(|i| {
+            let offset = i as f32 / (max - 1) as f32;
+            ColorStop { color: self.color_at(offset), offset }
+        }).collect()
+    }
+}
+
+fn color_between(stops: &[ColorStop], offset: f32) -> Vec4 {
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Vec4::ZERO;
+    };
+    if offset <= first.offset {
+        return first.color;
+    }
+    for pair in stops.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if offset <= to.offset {
+            let span = to.offset - from.offset;
+            if span <= 0.0 {
+                return to.color;
+            }
+            return from.color.lerp(to.color, (offset - from.offset) / span);
+        }
+    }
+    last.color
+}
+
+/// Splits on the commas that aren't inside a color's parentheses
+fn split_arguments(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(args[start..].trim());
+    parts
+}
+
+fn linear_direction(arg: &str) -> Option<GradientDirection> {
+    let arg = arg.trim().to_ascii_lowercase();
+    if let Some(sides) = arg.strip_prefix("to ") {
+        let mut corner = Vec2::ZERO;
+        for side in sides.split_whitespace() {
+            match side {
+                "left" if corner.x == 0.0 => corner.x = -1.0,
+                "right" if corner.x == 0.0 => corner.x = 1.0,
+                "top" if corner.y == 0.0 => corner.y = -1.0,
+                "bottom" if corner.y == 0.0 => corner.y = 1.0,
+                _ => return None,
+            }
+        }
+        return match (corner.x, corner.y) {
+            (0.0, 0.0) => None,
+            (0.0, y) => Some(GradientDirection::Angle(if y < 0.0 { 0.0 } else { std::f32::consts::PI })),
+            (x, 0.0) => Some(GradientDirection::Angle(x * std::f32::consts::FRAC_PI_2)),
+            _ => Some(GradientDirection::Corner(corner)),
+        };
+    }
+    parse_length(&arg).and_then(|angle| angle.to_radians()).map(GradientDirection::Angle)
+}
+
+/// `Some(true)` for `circle`, `Some(false)` for `ellipse`, with an optional
+/// `at center` that's the only position supported
+fn radial_shape(arg: &str) -> Option<bool> {
+    let arg = arg.trim().to_ascii_lowercase();
+    let shape = match arg.split_once(" at ") {
+        Some((shape, "center")) => shape.trim(),
+        Some(_) => return None,
+        None => arg.as_str(),
+    };
+    match shape {
+        "circle" | "circle farthest-corner" => Some(true),
+        "ellipse" | "ellipse farthest-corner" | "farthest-corner" => Some(false),
+        _ => None,
+    }
+}
+
+/// A color and its offset, if it has one
+fn color_stop(arg: &str) -> Option<(Vec4, Option<f32>)> {
+    if let Some(color) = parse_color(arg) {
+        return Some((color, None));
+    }
+    let (color, offset) = arg.rsplit_once(char::is_whitespace)?;
+    let offset = parse_length(offset).filter(|length| length.unit == CSSUnit::Percentage)?;
+    Some((parse_color(color)?, Some(offset.value as f32 / 100.0)))
+}
+
+fn place_stops(stops: &[(Vec4, Option<f32>)]) -> Vec<ColorStop> {
+    let mut offsets: Vec<Option<f32>> = stops.iter().map(|&(_, offset)| offset).collect();
+    let last = offsets.len() - 1;
+    offsets[0].get_or_insert(0.0);
+    offsets[last].get_or_insert(1.0);
+    // No stop goes before an earlier one
+    let mut highest = f32::MIN;
+    for offset in offsets.iter_mut().flatten() {
+        highest = highest.max(*offset);
+        *offset = highest;
+    }
+    // Runs of stops without offsets share the space between their neighbours
+    let mut i = 1;
+    while i < last {
+        if offsets[i].is_some() {
+            i += 1;
+            continue;
+        }
+        let start = i - 1;
+        let end = (i..=last).find(|&j| offsets[j].is_some()).unwrap_or(last);
+        let (from, to) = (offsets[start].unwrap_or(0.0), offsets[end].unwrap_or(1.0));
+        for (j, offset) in offsets.iter_mut().enumerate().take(end).skip(i) {
+            *offset = Some(from + (to - from) * (j - start) as f32 / (end - start) as f32);
+        }
+        i = end;
+    }
+    stops.iter().zip(offsets).map(|(&(color, _), offset)| ColorStop { color, offset: offset.unwrap_or(0.0) }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn parses_directions_shapes_and_stops() {
+        let gradient = Gradient::parse("linear-gradient(to right, #f00, rgb(0, 0, 255) 80%, #0f0)").unwrap();
+        assert_eq!(gradient.kind, GradientKind::Linear(GradientDirection::Angle(std::f32::consts::FRAC_PI_2)));
+        let offsets: Vec<f32> = gradient.stops.iter().map(|stop| stop.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.8, 1.0]);
+        assert_eq!(gradient.stops[1].color, Vec4::new(0.0, 0.0, 1.0, 1.0));
+
+        let default = Gradient::parse("linear-gradient(red, white, blue)").unwrap();
+        assert_eq!(default.kind, GradientKind::Linear(GradientDirection::Angle(std::f32::consts::PI)), "top to bottom");
+        assert!(close(default.stops[1].offset, 0.5));
+        let turned = Gradient::parse("LINEAR-GRADIENT(0.25turn, red 60%, blue 20%)").unwrap();
+        assert!(matches!(turned.kind, GradientKind::Linear(GradientDirection::Angle(angle)) if close(angle, std::f32::consts::FRAC_PI_2)));
+        assert_eq!(turned.stops[1].offset, 0.6, "stops don't go backwards");
+
+        assert_eq!(Gradient::parse("radial-gradient(circle at center, red, blue)").unwrap().kind, GradientKind::Radial { circle: true });
+        assert_eq!(Gradient::parse("radial-gradient(red, blue)").unwrap().kind, GradientKind::Radial { circle: false });
+        assert_eq!(Gradient::parse("linear-gradient(red)"), None);
+        assert_eq!(Gradient::parse("conic-gradient(red, blue)"), None);
+        assert_eq!(Gradient::parse("tiles/brick.png"), None);
+    }
+
+    #[test]
+    fn samples_colors_across_the_box() {
+        let size = Vec2::new(200.0, 100.0);
+        let horizontal = Gradient::parse("linear-gradient(90deg, black, white)").unwrap();
+        assert!(close(horizontal.offset_at(Vec2::new(0.0, 50.0), size), 0.0));
+        assert!(close(horizontal.offset_at(Vec2::new(50.0, 0.0), size), 0.25));
+        assert_eq!(horizontal.color_at_point(Vec2::new(100.0, 80.0), size), Vec4::new(0.5, 0.5, 0.5, 1.0));
+        assert_eq!(horizontal.color_at(-1.0), Vec4::new(0.0, 0.0, 0.0, 1.0), "the end colors carry on");
+
+        // A corner gradient puts the two other corners on its midline
+        let corner = Gradient::parse("linear-gradient(to top right, black, white)").unwrap();
+        assert!(close(corner.offset_at(Vec2::new(0.0, 0.0), size), 0.5));
+        assert!(close(corner.offset_at(Vec2::new(200.0, 100.0), size), 0.5));
+        assert!(close(corner.offset_at(Vec2::new(200.0, 0.0), size), 1.0));
+
+        let ellipse = Gradient::parse("radial-gradient(black, white)").unwrap();
+        assert!(close(ellipse.offset_at(size / 2.0, size), 0.0));
+        assert!(close(ellipse.offset_at(size, size), 1.0), "the farthest corner is the end");
+        assert!(close(ellipse.offset_at(Vec2::new(200.0, 50.0), size), ellipse.offset_at(Vec2::new(100.0, 100.0), size)));
+    }
+
+    #[test]
+    fn limits_stops_by_resampling() {
+        let gradient = Gradient::parse("linear-gradient(red, orange, yellow, green, blue, indigo, violet)").unwrap();
+        assert_eq!(gradient.limited_stops(8), gradient.stops);
+        let limited = gradient.limited_stops(3);
+        assert_eq!(limited.len(), 3);
+        for (sample, stop) in limited.iter().zip([0, 3, 6]) {
+            assert!(sample.color.abs_diff_eq(gradient.stops[stop].color, 1e-5));
+        }
+        assert_eq!(gradient.with_opacity(0.5).stops[0].color.w, 0.5);
+    }
+}
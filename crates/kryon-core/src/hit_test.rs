@@ -0,0 +1,124 @@
+// crates/kryon-core/src/hit_test.rs
+//! Whether a pointer position lands on an element's visible shape.
+//!
+//! Points are mapped back through the element's transform and tested
+//! against its rounded rect with the same signed distance function a
+//! rounded-rect shader evaluates, so clicks in the transparent corners of
+//! a rounded button or beside rotated content miss it.
+
+use crate::{Element, ElementId, ResolvedTransform};
+use glam::Vec2;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitRegion {
+    /// Top-left corner before the transform
+    pub position: Vec2,
+    pub size: Vec2,
+    pub border_radius: f32,
+    pub transform: Option<ResolvedTransform>,
+}
+
+impl HitRegion {
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self { position, size, border_radius: 0.0, transform: None }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        let local = match &self.transform {
+            Some(transform) => {
+                // Cheap reject on the transformed bounds before inverting the matrix
+                let in_bounds = transform.bounding_box(self.position, self.size).is_some_and(|(min, size)| {
+                    let max = min + size;
+                    point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
+                });
+                if !in_bounds {
+                    return false;
+                }
+                match transform.inverse_transform_point(point, self.position) {
+                    Some(local) => local,
+                    None => return false,
+                }
+            }
+            None => point,
+        };
+
+        let half_size = self.size / 2.0;
+        rounded_rect_sdf(local - (self.position + half_size), half_size, self.border_radius) <= 0.0
+    }
+}
+
+/// Signed distance from `point` to a rect centered on the origin, negative
+/// inside. `radius` is clamped to fit the rect.
+pub fn rounded_rect_sdf(point: Vec2, half_size: Vec2, radius: f32) -> f32 {
+    let radius = radius.clamp(0.0, half_size.x.min(half_size.y).max(0.0));
+    let q = point.abs() - half_size + Vec2::splat(radius);
+    q.max(Vec2::ZERO).length() + q.x.max(q.y).min(0.0) - radius
+}
+
+/// Whether the element receives pointer input. `pointer_events: none` on the
+/// element or an ancestor lets clicks through to whatever is beneath it; a
+/// descendant can opt back in with `pointer_events: auto`, as in CSS.
+pub fn receives_pointer_events(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> bool {
+    inherited_pointer_events(elements, element_id).unwrap_or(true)
+}
+
+/// The nearest `pointer_events` set on the element or an ancestor: `true`
+/// for `auto`, `false` for `none`, `None` if nothing in the chain sets it.
+/// Overlay windows only take input for elements that explicitly opt in.
+pub fn inherited_pointer_events(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> Option<bool> {
+    let mut current = elements.get(&element_id);
+    while let Some(element) = current {
+        match element.custom_properties.get("pointer_events").and_then(|v| v.as_string()) {
+            Some("none") => return Some(false),
+            Some("auto") => return Some(true),
+            _ => current = element.parent.and_then(|parent| elements.get(&parent)),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PropertyValue;
+
+    #[test]
+    fn rounded_corners_and_transforms_shape_the_region() {
+        let mut region = HitRegion { border_radius: 20.0, ..HitRegion::new(Vec2::ZERO, Vec2::new(100.0, 40.0)) };
+        assert!(region.contains(Vec2::new(50.0, 20.0)));
+        assert!(region.contains(Vec2::new(20.0, 1.0)));
+        assert!(!region.contains(Vec2::new(2.0, 2.0)), "transparent corner");
+        assert!(!region.contains(Vec2::new(101.0, 20.0)));
+
+        region.border_radius = 0.0;
+        region.transform = Some(ResolvedTransform { rotation: std::f32::consts::FRAC_PI_2, origin: Vec2::new(50.0, 20.0), ..ResolvedTransform::IDENTITY });
+        // Turned upright around its center: now 40 wide and 100 tall
+        assert!(region.contains(Vec2::new(50.0, -25.0)));
+        assert!(!region.contains(Vec2::new(5.0, 20.0)));
+    }
+
+    #[test]
+    fn pointer_events_none_is_inherited_until_overridden() {
+        let with_pointer_events = |parent: Option<ElementId>, value: Option<&str>| {
+            let mut element = Element { parent, ..Default::default() };
+            if let Some(value) = value {
+                element.custom_properties.insert("pointer_events".to_string(), PropertyValue::String(value.to_string()));
+            }
+            element
+        };
+        let elements: HashMap<ElementId, Element> = [
+            (0, with_pointer_events(None, None)),
+            (1, with_pointer_events(Some(0), Some("none"))),
+            (2, with_pointer_events(Some(1), None)),
+            (3, with_pointer_events(Some(1), Some("auto"))),
+        ].into_iter().collect();
+
+        assert!(receives_pointer_events(&elements, 0));
+        assert!(!receives_pointer_events(&elements, 1));
+        assert!(!receives_pointer_events(&elements, 2));
+        assert!(receives_pointer_events(&elements, 3));
+        assert_eq!(inherited_pointer_events(&elements, 0), None);
+        assert_eq!(inherited_pointer_events(&elements, 3), Some(true));
+    }
+}
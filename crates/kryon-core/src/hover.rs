@@ -0,0 +1,84 @@
+// crates/kryon-core/src/hover.rs
+//! Which elements the pointer is over, and which it entered or left.
+//!
+//! As with CSS `:hover`, the pointer is over the hit element and every
+//! ancestor. Comparing the previous path with the current one gives the
+//! elements that get `PointerLeave` and `PointerEnter`; elements on both
+//! paths get neither, so moving from a child back to its parent doesn't
+//! re-enter the parent.
+
+use crate::{Element, ElementId};
+use std::collections::HashMap;
+
+/// `target` followed by its ancestors, innermost first. Empty for `None`.
+pub fn hover_path(elements: &HashMap<ElementId, Element>, target: Option<ElementId>) -> Vec<ElementId> {
+    let mut path = Vec::new();
+    let mut current = target.filter(|id| elements.contains_key(id));
+    while let Some(id) = current {
+        // Guard against malformed trees that loop
+        if path.contains(&id) {
+            break;
+        }
+        path.push(id);
+        current = elements.get(&id).and_then(|element| element.parent);
+    }
+    path
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HoverTransition {
+    /// No longer under the pointer, innermost first
+    pub left: Vec<ElementId>,
+    /// Newly under the pointer, outermost first
+    pub entered: Vec<ElementId>,
+}
+
+impl HoverTransition {
+    /// Compares two paths from `hover_path`.
+    pub fn between(previous: &[ElementId], current: &[ElementId]) -> Self {
+        let left = previous.iter().copied().filter(|id| !current.contains(id)).collect();
+        let entered = current.iter().rev().copied().filter(|id| !previous.contains(id)).collect();
+        Self { left, entered }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.entered.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 is the root with children 1 and 2; 3 is a child of 1.
+    fn tree() -> HashMap<ElementId, Element> {
+        let child = |parent| Element { parent: Some(parent), ..Default::default() };
+        [(0, Element::default()), (1, child(0)), (2, child(0)), (3, child(1))].into_iter().collect()
+    }
+
+    #[test]
+    fn path_runs_from_the_target_to_the_root() {
+        let elements = tree();
+        assert_eq!(hover_path(&elements, Some(3)), vec![3, 1, 0]);
+        assert_eq!(hover_path(&elements, None), Vec::<ElementId>::new());
+        assert_eq!(hover_path(&elements, Some(42)), Vec::<ElementId>::new());
+    }
+
+    #[test]
+    fn transitions_skip_shared_ancestors() {
+        let elements = tree();
+        let inner = hover_path(&elements, Some(3));
+        let sibling = hover_path(&elements, Some(2));
+
+        let moved = HoverTransition::between(&inner, &sibling);
+        assert_eq!(moved, HoverTransition { left: vec![3, 1], entered: vec![2] });
+
+        let entered = HoverTransition::between(&[], &inner);
+        assert_eq!(entered.entered, vec![0, 1, 3]);
+
+        // Back out from the child to its parent: only the child is left
+        let to_parent = HoverTransition::between(&inner, &hover_path(&elements, Some(1)));
+        assert_eq!(to_parent, HoverTransition { left: vec![3], entered: vec![] });
+        assert!(HoverTransition::between(&inner, &inner).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 // crates/kryon-core/src/krb.rs
-use crate::{Element, ElementId, ElementType, PropertyValue, Result, KryonError, TextAlignment, Style, CursorType, InteractionState, EventType, TransformData, TransformType, TransformProperty, TransformPropertyType, CSSUnitValue, CSSUnit, LayoutSize, LayoutPosition, LayoutDimension, OverflowType}; 
+use crate::{Element, ElementId, ElementType, PropertyValue, Result, KryonError, TextAlignment, Style, CursorType, InteractionState, EventType, TransformData, TransformOrigin, TransformType, TransformProperty, TransformPropertyType, CSSUnitValue, CSSUnit, LayoutSize, LayoutPosition, LayoutDimension, OverflowType}; 
+use crate::compat::{CompatWarning, CompatibilityReport, KrbFeature, KrbVersion, ParseOptions, CURRENT_VERSION};
 use std::collections::HashMap;
 use glam::{Vec2, Vec4};
 
@@ -16,6 +17,9 @@ pub struct KRBFile {
     pub template_bindings: Vec<TemplateBinding>,
     pub transforms: Vec<TransformData>,
     pub fonts: HashMap<String, String>, // font_family -> font_path
+    /// How the file's version compares to the renderer's, and what reading
+    /// it skipped
+    pub compatibility: CompatibilityReport,
 }
 
 #[derive(Debug)]
@@ -49,6 +53,13 @@ pub struct TemplateVariable {
     pub default_value: String,
 }
 
+impl TemplateVariable {
+    /// The declared type, from `value_type`
+    pub fn template_type(&self) -> crate::TemplateValueType {
+        crate::TemplateValueType::from_code(self.value_type)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TemplateBinding {
     pub element_index: u16,
@@ -60,35 +71,61 @@ pub struct TemplateBinding {
 pub struct KRBParser {
     data: Vec<u8>,
     position: usize,
+    /// Offset of the first read past the end of `data`. Reads there return
+    /// zeros; `parse` turns this into an error once the current table is done.
+    overrun_at: Option<usize>,
+    options: ParseOptions,
+    compatibility: CompatibilityReport,
 }
 
 impl KRBParser {
     pub fn new(data: Vec<u8>) -> Self {
-        Self { data, position: 0 }
+        Self::with_options(data, ParseOptions::default())
+    }
+    
+    pub fn with_options(data: Vec<u8>, options: ParseOptions) -> Self {
+        Self { data, position: 0, overrun_at: None, options, compatibility: CompatibilityReport::default() }
     }
     
     pub fn parse(&mut self) -> Result<KRBFile> {
-        let header = self.parse_header()?;
+        let mut header = self.parse_header()?;
         
         if &header.magic != b"KRB1" {
             return Err(KryonError::InvalidKRB("Invalid magic number".to_string()));
         }
         
-        if header.version > 0x0500 {
-            return Err(KryonError::UnsupportedVersion(header.version));
+        let version = KrbVersion(header.version);
+        self.compatibility = CompatibilityReport::new(version);
+        if version.major() > CURRENT_VERSION.major() {
+            if !self.options.tolerant {
+                return Err(KryonError::UnsupportedVersion(header.version));
+            }
+            self.compatibility.warn(CompatWarning::NewerMajorVersion);
+        } else if version > CURRENT_VERSION {
+            self.compatibility.warn(CompatWarning::NewerMinorVersion);
         }
+        self.skip_sections_before(version, &mut header);
         
         let strings = self.parse_string_table(&header)?;
+        self.check_overrun("string table")?;
         let styles = self.parse_style_table(&header, &strings)?;
+        self.check_overrun("style table")?;
         let mut elements = self.parse_element_tree(&header, &strings)?;
+        self.check_overrun("element tree")?;
         let resources = self.parse_resource_table(&header)?;
+        self.check_overrun("resource table")?;
         let scripts = self.parse_script_table(&header, &strings)?;
+        self.check_overrun("script table")?;
         let template_variables = self.parse_template_variables(&header, &strings)?;
+        self.check_overrun("template variables")?;
         let template_bindings = self.parse_template_bindings(&header, &strings)?;
+        self.check_overrun("template bindings")?;
         let transforms = self.parse_transforms(&header)?;
+        self.check_overrun("transforms")?;
         
         // Apply style-based layout flags to elements
         self.apply_style_layout_flags(&mut elements, &styles)?;
+        Self::attach_transforms(&mut elements, &transforms);
         
         // Find root element (App type) or create default App wrapper
         let root_element_id = if let Some((id, _)) = elements.iter()
@@ -165,9 +202,29 @@ impl KRBParser {
             template_bindings,
             transforms,
             fonts,
+            compatibility: std::mem::take(&mut self.compatibility),
         })
     }
     
+    /// Zeroes the counts of the parts `version` predates; older headers
+    /// have reserved bytes there
+    fn skip_sections_before(&mut self, version: KrbVersion, header: &mut KRBHeader) {
+        for feature in KrbFeature::ALL {
+            if version.has(feature) {
+                continue;
+            }
+            let count = match feature {
+                KrbFeature::TemplateVariables => &mut header.template_variable_count,
+                KrbFeature::TemplateBindings => &mut header.template_binding_count,
+                KrbFeature::Transforms => &mut header.transform_count,
+            };
+            if *count != 0 {
+                self.compatibility.warn(CompatWarning::IgnoredSection { feature, count: *count });
+                *count = 0;
+            }
+        }
+    }
+    
     fn parse_style_table(&mut self, header: &KRBHeader, strings: &[String]) -> Result<HashMap<u8, Style>> {
         let style_offset = self.read_u32_at(32) as usize;
         let mut styles = HashMap::new();
@@ -192,11 +249,23 @@ impl KRBParser {
             let mut properties = HashMap::new();
             for j in 0..property_count {
                 let prop_id = self.read_u8();
-                let _value_type = self.read_u8(); // We can use this for more robust parsing later
+                let value_type = self.read_u8(); // We can use this for more robust parsing later
                 let size = self.read_u8();
                 
                 eprintln!("[STYLE]   Property {}: id=0x{:02X}, size={}", j, prop_id, size);
                 
+                if crate::style::inheritable_property_name(prop_id).is_some() && self.read_inherit_keyword(value_type, size, strings) {
+                    properties.insert(prop_id, PropertyValue::String(crate::style::INHERIT.to_string()));
+                    continue;
+                }
+                
+                if matches!(prop_id, 0x19 | 0x1A) {
+                    if let Some(keyword) = self.read_keyword(value_type, size, strings, crate::SIZING_KEYWORDS) {
+                        properties.insert(prop_id, PropertyValue::String(keyword.to_string()));
+                        continue;
+                    }
+                }
+                
                 let value = match prop_id {
                     0x01 | 0x02 | 0x03 => PropertyValue::Color(self.read_color()),
                     0x04 | 0x05 => PropertyValue::Float(self.read_u8() as f32), // BorderWidth, BorderRadius
@@ -280,6 +349,18 @@ impl KRBParser {
                             continue;
                         }
                     }
+                    0x11 | 0x6D | 0x6E | 0x6F => { // Gap, GridGap, GridColumnGap, GridRowGap
+                        match self.read_gap(value_type, size, strings) {
+                            Some(gap) => gap,
+                            None => continue,
+                        }
+                    }
+                    0x30..=0x33 => { // BackgroundImage, BackgroundRepeat, BackgroundSize, BackgroundPosition
+                        match self.read_string(size, strings) {
+                            Some(value) => PropertyValue::String(value),
+                            None => continue,
+                        }
+                    }
                     // Modern Taffy layout properties (0x40-0x4F range)
                     0x40 => { // Display
                         if size == 1 {
@@ -439,6 +520,12 @@ impl KRBParser {
                             continue;
                         }
                     }
+                    0x4C => { // Order
+                        match self.read_order(size) {
+                            Some(order) => PropertyValue::Int(order),
+                            None => continue,
+                        }
+                    }
                     0x50 => { // Position
                         if size == 1 {
                             let string_index = self.read_u8() as usize;
@@ -475,6 +562,7 @@ impl KRBParser {
                     _ => {
                         // For unknown properties, read the raw bytes and display them
                         eprintln!("[STYLE]     Unknown property 0x{:02X}, size={}, reading raw bytes...", prop_id, size);
+                        self.compatibility.warn(CompatWarning::UnknownProperty { table: "style", property_id: prop_id });
                         let mut raw_bytes = Vec::new();
                         for i in 0..size {
                             let byte = self.read_u8();
@@ -545,10 +633,8 @@ impl KRBParser {
         
         for _ in 0..header.string_count {
             let length = self.read_u8() as usize;
-            let string_data = &self.data[self.position..self.position + length];
-            let string = String::from_utf8_lossy(string_data).to_string();
+            let string = String::from_utf8_lossy(self.read_bytes(length)).to_string();
             strings.push(string);
-            self.position += length;
         }
         
         Ok(strings)
@@ -666,6 +752,8 @@ impl KRBParser {
                     element.event_handlers.insert(event_type, callback_name);
                     eprintln!("[EVENT] Added {} event handler: {}", self.event_type_name(event_type), strings[callback_string_index]);
                 }
+            } else {
+                self.compatibility.warn(CompatWarning::UnknownEventType { event_id: event_type_id });
             }
         }
         
@@ -740,6 +828,28 @@ impl KRBParser {
         
         eprintln!("[PROP] Property ID: 0x{:02X}, value_type: 0x{:02X}, size: {}", property_id, value_type, size);
         
+        if let Some(name) = crate::style::inheritable_property_name(property_id) {
+            if self.read_inherit_keyword(value_type, size, strings) {
+                element.custom_properties.insert(name.to_string(), PropertyValue::String(crate::style::INHERIT.to_string()));
+                eprintln!("[PROP] {}: inherit", name);
+                return Ok(());
+            }
+        }
+        
+        if matches!(property_id, 0x19 | 0x1A) {
+            if let Some(keyword) = self.read_keyword(value_type, size, strings, crate::SIZING_KEYWORDS) {
+                let dimension = LayoutDimension::from_string(keyword);
+                if property_id == 0x19 {
+                    element.layout_size.width = dimension;
+                    eprintln!("[PROP] Width: {}", keyword);
+                } else {
+                    element.layout_size.height = dimension;
+                    eprintln!("[PROP] Height: {}", keyword);
+                }
+                return Ok(());
+            }
+        }
+        
         match property_id {
             0x01 => { // BackgroundColor
                 if size == 4 {
@@ -918,14 +1028,13 @@ impl KRBParser {
                     for _ in 0..size { self.read_u8(); }
                 }
             }
-            0x11 => { // Gap
-                if size == 1 {
-                    let gap = self.read_u8() as f32;
-                    element.custom_properties.insert("gap".to_string(), PropertyValue::Float(gap));
-                    eprintln!("[PROP] Gap: {}", gap);
+            0x11 | 0x6D | 0x6E | 0x6F => { // Gap, GridGap, GridColumnGap, GridRowGap
+                let name = gap_property_name(property_id);
+                if let Some(gap) = self.read_gap(value_type, size, strings) {
+                    eprintln!("[PROP] {}: {:?}", name, gap);
+                    element.custom_properties.insert(name.to_string(), gap);
                 } else {
-                    eprintln!("[PROP] Gap: size mismatch, expected 1, got {}, skipping", size);
-                    for _ in 0..size { self.read_u8(); }
+                    eprintln!("[PROP] {}: unsupported value (type 0x{:02X}, size {}), skipping", name, value_type, size);
                 }
             }
             0x12 => { // MinWidth
@@ -1084,6 +1193,18 @@ impl KRBParser {
                     for _ in 0..size { self.read_u8(); }
                 }
             }
+            0x30..=0x33 => { // BackgroundImage, BackgroundRepeat, BackgroundSize, BackgroundPosition
+                let name = match property_id {
+                    0x30 => "background_image",
+                    0x31 => "background_repeat",
+                    0x32 => "background_size",
+                    _ => "background_position",
+                };
+                if let Some(value) = self.read_string(size, strings) {
+                    eprintln!("[PROP] {}: '{}'", name, value);
+                    element.custom_properties.insert(name.to_string(), PropertyValue::String(value));
+                }
+            }
             0x1A => { // Height
                 if size == 2 {
                     let height = self.read_u16() as f32;
@@ -1366,6 +1487,14 @@ impl KRBParser {
                     for _ in 0..size { self.read_u8(); }
                 }
             }
+            0x4C => { // Order
+                if let Some(order) = self.read_order(size) {
+                    element.custom_properties.insert("order".to_string(), PropertyValue::Int(order));
+                    eprintln!("[PROP] Order: {}", order);
+                } else {
+                    eprintln!("[PROP] Order: size mismatch, expected 1 or 2, got {}, skipping", size);
+                }
+            }
             0x50 => { // Position
                 if size == 1 {
                     let string_index = self.read_u8() as usize;
@@ -1415,6 +1544,7 @@ impl KRBParser {
             }
             _ => {
                 eprintln!("[PROP] Unknown property 0x{:02X}, skipping {} bytes...", property_id, size);
+                self.compatibility.warn(CompatWarning::UnknownProperty { table: "element", property_id });
                 // Skip unknown property using size field
                 for _ in 0..size {
                     self.read_u8();
@@ -1454,6 +1584,9 @@ impl KRBParser {
             }
             _ => {
                 // Unknown value type or size mismatch - consume bytes and skip
+                if !(0x01..=0x04).contains(&value_type) {
+                    self.compatibility.warn(CompatWarning::UnknownValueType { table: "custom property", value_type });
+                }
                 for _ in 0..size {
                     self.read_u8();
                 }
@@ -1604,10 +1737,8 @@ impl KRBParser {
         
         for _ in 0..header.resource_count {
             let length = self.read_u8() as usize;
-            let resource_data = &self.data[self.position..self.position + length];
-            let resource = String::from_utf8_lossy(resource_data).to_string();
+            let resource = String::from_utf8_lossy(self.read_bytes(length)).to_string();
             resources.push(resource);
-            self.position += length;
         }
         
         Ok(resources)
@@ -1651,18 +1782,13 @@ impl KRBParser {
             
             // Parse script bytecode
             let bytecode = if storage_format == 0 { // Inline
-                let bytecode_data = &self.data[self.position..self.position + data_size];
-                bytecode_data.to_vec()
+                self.read_bytes(data_size).to_vec()
             } else { // External
                 // For external storage, we would need to load from resource
                 // For now, return empty vec - this should be handled by the compiler
                 Vec::new()
             };
             
-            if storage_format == 0 {
-                self.position += data_size;
-            }
-            
             scripts.push(ScriptEntry {
                 language,
                 name,
@@ -1678,7 +1804,7 @@ impl KRBParser {
         let template_var_offset = self.read_u32_at(56) as usize;
         let mut template_variables = Vec::new();
         
-        println!("PARSE: template_variable_count = {}, offset = 0x{:X}", header.template_variable_count, template_var_offset);
+        eprintln!("PARSE: template_variable_count = {}, offset = 0x{:X}", header.template_variable_count, template_var_offset);
         
         self.position = template_var_offset;
         
@@ -1699,7 +1825,7 @@ impl KRBParser {
                 String::new()
             };
             
-            println!("PARSE: template_variable[{}]: name='{}' (idx={}), type={}, default='{}' (idx={})", 
+            eprintln!("PARSE: template_variable[{}]: name='{}' (idx={}), type={}, default='{}' (idx={})", 
                 i, name, name_index, value_type, default_value, default_value_index);
             
             template_variables.push(TemplateVariable {
@@ -1716,7 +1842,7 @@ impl KRBParser {
         let template_binding_offset = self.read_u32_at(60) as usize;
         let mut template_bindings = Vec::new();
         
-        println!("PARSE: template_binding_count = {}, offset = 0x{:X}", header.template_binding_count, template_binding_offset);
+        eprintln!("PARSE: template_binding_count = {}, offset = 0x{:X}", header.template_binding_count, template_binding_offset);
         
         self.position = template_binding_offset;
         
@@ -1737,7 +1863,7 @@ impl KRBParser {
                 variable_indices.push(self.read_u8());
             }
             
-            println!("PARSE: template_binding[{}]: element={}, property=0x{:02X}, expr='{}' (idx={}), vars={:?}", 
+            eprintln!("PARSE: template_binding[{}]: element={}, property=0x{:02X}, expr='{}' (idx={}), vars={:?}", 
                 i, element_index, property_id, template_expression, template_expression_index, variable_indices);
             
             template_bindings.push(TemplateBinding {
@@ -1755,7 +1881,7 @@ impl KRBParser {
         let transform_offset = self.read_u32_at(64) as usize;
         let mut transforms = Vec::new();
         
-        println!("PARSE: transform_count = {}, offset = 0x{:X}", header.transform_count, transform_offset);
+        eprintln!("PARSE: transform_count = {}, offset = 0x{:X}", header.transform_count, transform_offset);
         
         self.position = transform_offset;
         
@@ -1830,6 +1956,7 @@ impl KRBParser {
                     }
                     _ => {
                         // Skip unknown value types
+                        self.compatibility.warn(CompatWarning::UnknownValueType { table: "transform", value_type });
                         for _ in 0..size {
                             self.read_u8();
                         }
@@ -1842,16 +1969,17 @@ impl KRBParser {
                     value: css_unit_value,
                 });
                 
-                println!("PARSE: transform[{}].property[{}]: type={:?}, value={:?}", 
+                eprintln!("PARSE: transform[{}].property[{}]: type={:?}, value={:?}", 
                     i, j, property_type_enum, properties.last().unwrap().value);
             }
             
             transforms.push(TransformData {
                 transform_type: transform_type_enum,
                 properties,
+                origin: TransformOrigin::default(),
             });
             
-            println!("PARSE: transform[{}]: type={:?}, properties={}", 
+            eprintln!("PARSE: transform[{}]: type={:?}, properties={}", 
                 i, transform_type_enum, transforms.last().unwrap().properties.len());
         }
         
@@ -1936,6 +2064,27 @@ impl KRBParser {
         Some(app_id)
     }
     
+    /// Copies each element's entry from the transform table into its
+    /// `transform` property, with the origin from its `transform_origin`.
+    fn attach_transforms(elements: &mut HashMap<ElementId, Element>, transforms: &[TransformData]) {
+        for element in elements.values_mut() {
+            let Some(index) = element.custom_properties.get("transform_index").and_then(|v| v.as_int()) else {
+                continue;
+            };
+            let Some(mut transform) = transforms.get(index as usize).cloned() else {
+                eprintln!("[TRANSFORM] Element '{}' refers to missing transform {}", element.id, index);
+                continue;
+            };
+            if let Some(origin) = element.custom_properties.get("transform_origin").and_then(|v| v.as_string()) {
+                match TransformOrigin::parse(origin) {
+                    Some(parsed) => transform.origin = parsed,
+                    None => eprintln!("[TRANSFORM] Element '{}' has invalid transform_origin '{}'", element.id, origin),
+                }
+            }
+            element.custom_properties.insert("transform".to_string(), PropertyValue::Transform(transform));
+        }
+    }
+    
     fn apply_style_layout_flags(&self, elements: &mut HashMap<ElementId, Element>, styles: &HashMap<u8, Style>) -> Result<()> {
         for (_element_id, element) in elements.iter_mut() {
             if element.style_id > 0 {
@@ -1959,6 +2108,11 @@ impl KRBParser {
                             eprintln!("[STYLE_LAYOUT] Applying width {} from style '{}' to element", 
                                 width, style_block.name);
                             element.size.x = width;
+                        } else if let Some(keyword) = width_prop.as_string() {
+                            // A sizing keyword such as fit-content; an inline width still wins
+                            if element.layout_size.width == LayoutDimension::Auto {
+                                element.layout_size.width = LayoutDimension::from_string(keyword);
+                            }
                         }
                     }
                     
@@ -1968,6 +2122,10 @@ impl KRBParser {
                             eprintln!("[STYLE_LAYOUT] Applying height {} from style '{}' to element", 
                                 height, style_block.name);
                             element.size.y = height;
+                        } else if let Some(keyword) = height_prop.as_string() {
+                            if element.layout_size.height == LayoutDimension::Auto {
+                                element.layout_size.height = LayoutDimension::from_string(keyword);
+                            }
                         }
                     }
                     
@@ -2067,6 +2225,11 @@ impl KRBParser {
                         (0x49, "justify_content"),
                         (0x4A, "justify_items"),
                         (0x4B, "justify_self"),
+                        (0x4C, "order"),
+                        (0x11, "gap"),
+                        (0x6D, "gap"),
+                        (0x6E, "column_gap"),
+                        (0x6F, "row_gap"),
                         (0x50, "position"),
                         (0x51, "left"),
                         (0x52, "top"),
@@ -2093,24 +2256,97 @@ impl KRBParser {
     }
     
     // Helper methods for reading binary data
-    fn read_u8(&mut self) -> u8 {
-        if self.position >= self.data.len() {
-            eprintln!("DEBUG: Attempted to read u8 at position {} but file is only {} bytes", self.position, self.data.len());
-            eprintln!("DEBUG: This suggests the KRB file is truncated or the parser is reading more than expected");
-            panic!("KRB parsing error: trying to read u8 at position {} but data length is {}", self.position, self.data.len());
+    fn check_overrun(&self, table: &str) -> Result<()> {
+        match self.overrun_at {
+            Some(offset) => Err(KryonError::InvalidKRB(format!(
+                "{} runs past the end of the file (offset {}, length {})",
+                table, offset, self.data.len()
+            ))),
+            None => Ok(()),
+        }
+    }
+    
+    /// Consumes an inheritable property's value if it is the `inherit`
+    /// keyword, which the compiler writes as a string in place of the usual value.
+    fn read_inherit_keyword(&mut self, value_type: u8, size: u8, strings: &[String]) -> bool {
+        self.read_keyword(value_type, size, strings, &[crate::style::INHERIT]).is_some()
+    }
+    
+    /// Consumes a value that is one of `keywords` written as a string, such
+    /// as `fit-content` for a width, and leaves any other value unread.
+    fn read_keyword(&mut self, value_type: u8, size: u8, strings: &[String], keywords: &[&'static str]) -> Option<&'static str> {
+        if value_type != 0x04 || size != 1 {
+            return None;
         }
-        let value = self.data[self.position];
+        let value = self.data.get(self.position).and_then(|&index| strings.get(index as usize))?;
+        let keyword = keywords.iter().copied().find(|keyword| value.eq_ignore_ascii_case(keyword))?;
         self.position += 1;
-        value
+        Some(keyword)
     }
     
-    fn read_u16(&mut self) -> u16 {
-        if self.position + 1 >= self.data.len() {
-            panic!("KRB parsing error: trying to read u16 at position {} but data length is {}", self.position, self.data.len());
+    /// Reads a string-table index. Skips any other size, or an index past
+    /// the table.
+    fn read_string(&mut self, size: u8, strings: &[String]) -> Option<String> {
+        if size != 1 {
+            for _ in 0..size { self.read_u8(); }
+            return None;
+        }
+        strings.get(self.read_u8() as usize).cloned()
+    }
+    
+    /// Reads a gap: a byte or u16 of pixels, or a string such as `1em`,
+    /// `5%` or `8 16` (row then column). Skips any other encoding.
+    fn read_gap(&mut self, value_type: u8, size: u8, strings: &[String]) -> Option<PropertyValue> {
+        match (value_type, size) {
+            (0x04, 1) => strings.get(self.read_u8() as usize).map(|value| PropertyValue::String(value.clone())),
+            (_, 1) => Some(PropertyValue::Float(self.read_u8() as f32)),
+            (_, 2) => Some(PropertyValue::Float(self.read_u16() as f32)),
+            _ => {
+                for _ in 0..size { self.read_u8(); }
+                None
+            }
         }
-        let value = u16::from_le_bytes([self.data[self.position], self.data[self.position + 1]]);
-        self.position += 2;
-        value
+    }
+    
+    /// Reads a flex `order`, a signed byte or i16 since items can be moved
+    /// before their siblings. Skips any other size.
+    fn read_order(&mut self, size: u8) -> Option<i32> {
+        match size {
+            1 => Some(self.read_u8() as i8 as i32),
+            2 => Some(self.read_u16() as i16 as i32),
+            _ => {
+                for _ in 0..size { self.read_u8(); }
+                None
+            }
+        }
+    }
+    
+    /// Reads `length` bytes, or as many as remain if the data is truncated.
+    fn read_bytes(&mut self, length: usize) -> &[u8] {
+        let start = self.position.min(self.data.len());
+        let end = self.position.saturating_add(length);
+        if end > self.data.len() {
+            self.overrun_at.get_or_insert(self.position);
+        }
+        self.position = end;
+        &self.data[start..end.min(self.data.len())]
+    }
+    
+    fn read_u8(&mut self) -> u8 {
+        match self.data.get(self.position) {
+            Some(&value) => {
+                self.position += 1;
+                value
+            }
+            None => {
+                self.overrun_at.get_or_insert(self.position);
+                0
+            }
+        }
+    }
+    
+    fn read_u16(&mut self) -> u16 {
+        u16::from_le_bytes([self.read_u8(), self.read_u8()])
     }
     
     fn read_u16_at(&self, offset: usize) -> u16 {
@@ -2118,17 +2354,7 @@ impl KRBParser {
     }
     
     fn _read_u32(&mut self) -> u32 {
-        if self.position + 3 >= self.data.len() {
-            panic!("KRB parsing error: trying to read u32 at position {} but data length is {}", self.position, self.data.len());
-        }
-        let value = u32::from_le_bytes([
-            self.data[self.position],
-            self.data[self.position + 1],
-            self.data[self.position + 2],
-            self.data[self.position + 3],
-        ]);
-        self.position += 4;
-        value
+        u32::from_le_bytes([self.read_u8(), self.read_u8(), self.read_u8(), self.read_u8()])
     }
     
     fn read_u32_at(&self, offset: usize) -> u32 {
@@ -2186,6 +2412,13 @@ impl KRBParser {
             0x06 => Some(EventType::Blur),
             0x07 => Some(EventType::Change),
             0x08 => Some(EventType::Submit),
+            0x09 => Some(EventType::PointerEnter),
+            0x0A => Some(EventType::PointerLeave),
+            0x0B => Some(EventType::PointerMove),
+            0x0C => Some(EventType::Invalid),
+            0x0D => Some(EventType::DragStart),
+            0x0E => Some(EventType::DragOver),
+            0x0F => Some(EventType::Drop),
             _ => None, // Safely ignore unknown event types
         }
     }
@@ -2199,6 +2432,13 @@ impl KRBParser {
             EventType::Blur => "Blur",
             EventType::Change => "Change",
             EventType::Submit => "Submit",
+            EventType::PointerEnter => "PointerEnter",
+            EventType::PointerLeave => "PointerLeave",
+            EventType::PointerMove => "PointerMove",
+            EventType::Invalid => "Invalid",
+            EventType::DragStart => "DragStart",
+            EventType::DragOver => "DragOver",
+            EventType::Drop => "Drop",
         }
     }
     
@@ -2211,14 +2451,35 @@ impl KRBParser {
     }
 }
 
+/// The custom property a gap is stored under; the grid gap ids apply to
+/// flex containers as well.
+fn gap_property_name(property_id: u8) -> &'static str {
+    match property_id {
+        0x6E => "column_gap",
+        0x6F => "row_gap",
+        _ => "gap",
+    }
+}
+
 pub fn load_krb_file(path: &str) -> Result<KRBFile> {
+    load_krb_file_with(path, ParseOptions::default())
+}
+
+pub fn load_krb_file_with(path: &str, options: ParseOptions) -> Result<KRBFile> {
     let data = std::fs::read(path)?;
-    load_krb_from_bytes(&data)
+    load_krb_from_bytes_with(&data, options)
 }
 
 pub fn load_krb_from_bytes(data: &[u8]) -> Result<KRBFile> {
-    let mut parser = KRBParser::new(data.to_vec());
+    load_krb_from_bytes_with(data, ParseOptions::default())
+}
+
+pub fn load_krb_from_bytes_with(data: &[u8], options: ParseOptions) -> Result<KRBFile> {
+    let mut parser = KRBParser::with_options(data.to_vec(), options);
     let krb_file = parser.parse()?;
+    if !krb_file.compatibility.is_clean() {
+        eprintln!("[KRB_COMPAT] {}", krb_file.compatibility);
+    }
     
     // DEBUG: Print everything we parsed
     eprintln!("=== KRB FILE DEBUG ===");
@@ -1,6 +1,10 @@
 // crates/kryon-core/src/layout_units.rs
+use crate::{CSSUnit, CSSUnitValue};
 use glam::Vec2;
 
+/// Keywords a width or height can take instead of a length.
+pub const SIZING_KEYWORDS: &[&str] = &["auto", "min-content", "max-content", "fit-content"];
+
 /// Represents a dimension that can be pixels, percentage, or auto
 #[derive(Debug, Clone, PartialEq)]
 pub enum LayoutDimension {
@@ -14,6 +18,12 @@ pub enum LayoutDimension {
     MinPixels(f32),
     /// Fixed maximum size in pixels
     MaxPixels(f32),
+    /// As narrow as the content allows, e.g. the longest word of a label
+    MinContent,
+    /// As wide as the content wants, e.g. a label on one line
+    MaxContent,
+    /// The available space, but no wider than `MaxContent` and no narrower than `MinContent`
+    FitContent,
 }
 
 impl LayoutDimension {
@@ -25,12 +35,19 @@ impl LayoutDimension {
             LayoutDimension::Auto => parent_size, // For now, auto fills parent
             LayoutDimension::MinPixels(px) => *px,
             LayoutDimension::MaxPixels(px) => *px,
+            // Content sizes are only known to the layout engine
+            LayoutDimension::MinContent | LayoutDimension::MaxContent | LayoutDimension::FitContent => parent_size,
         }
     }
     
     /// Check if this dimension is definite (not auto)
     pub fn is_definite(&self) -> bool {
-        !matches!(self, LayoutDimension::Auto)
+        !matches!(self, LayoutDimension::Auto) && !self.is_intrinsic()
+    }
+    
+    /// Whether the size comes from the content: `min-content`, `max-content` or `fit-content`
+    pub fn is_intrinsic(&self) -> bool {
+        matches!(self, LayoutDimension::MinContent | LayoutDimension::MaxContent | LayoutDimension::FitContent)
     }
     
     /// Check if this dimension depends on parent size
@@ -38,33 +55,25 @@ impl LayoutDimension {
         matches!(self, LayoutDimension::Percentage(_) | LayoutDimension::Auto)
     }
     
-    /// Create from a string value (like "50%", "100px", "auto")
+    /// Create from a string value (like "50%", "100px", "auto", "fit-content")
     pub fn from_string(value: &str) -> Self {
         let value = value.trim();
         
-        if value == "auto" {
-            return LayoutDimension::Auto;
-        }
-        
-        if value.ends_with('%') {
-            if let Ok(pct) = value[..value.len()-1].parse::<f32>() {
-                return LayoutDimension::Percentage(pct / 100.0);
-            }
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => return LayoutDimension::Auto,
+            "min-content" => return LayoutDimension::MinContent,
+            "max-content" => return LayoutDimension::MaxContent,
+            "fit-content" => return LayoutDimension::FitContent,
+            _ => {}
         }
         
-        if value.ends_with("px") {
-            if let Ok(px) = value[..value.len()-2].parse::<f32>() {
-                return LayoutDimension::Pixels(px);
-            }
+        match crate::css::parse_length(value) {
+            Some(CSSUnitValue { value, unit: CSSUnit::Percentage }) => LayoutDimension::Percentage(value as f32 / 100.0),
+            // Plain numbers are pixels
+            Some(CSSUnitValue { value, unit: CSSUnit::Pixels | CSSUnit::Number }) => LayoutDimension::Pixels(value as f32),
+            // Default to auto if parsing fails
+            _ => LayoutDimension::Auto,
         }
-        
-        // Try parsing as plain number (assume pixels)
-        if let Ok(px) = value.parse::<f32>() {
-            return LayoutDimension::Pixels(px);
-        }
-        
-        // Default to auto if parsing fails
-        LayoutDimension::Auto
     }
 }
 
@@ -196,6 +205,9 @@ mod tests {
         assert_eq!(LayoutDimension::from_string("100px"), LayoutDimension::Pixels(100.0));
         assert_eq!(LayoutDimension::from_string("auto"), LayoutDimension::Auto);
         assert_eq!(LayoutDimension::from_string("200"), LayoutDimension::Pixels(200.0));
+        assert_eq!(LayoutDimension::from_string("fit-content"), LayoutDimension::FitContent);
+        assert_eq!(LayoutDimension::from_string("Min-Content"), LayoutDimension::MinContent);
+        assert!(SIZING_KEYWORDS.iter().all(|keyword| !LayoutDimension::from_string(keyword).is_definite()));
     }
     
     #[test]
@@ -1,5 +1,6 @@
 // crates/kryon-core/src/lib.rs
 pub mod krb;
+pub mod compat;
 pub mod elements;
 pub mod properties;
 pub mod property_registry;
@@ -9,6 +10,17 @@ pub mod events;
 pub mod style;
 pub mod layout_units;
 pub mod text;
+pub mod particles;
+pub mod table;
+pub mod css;
+pub mod transform;
+pub mod hit_test;
+pub mod hover;
+pub mod orientation;
+pub mod background;
+pub mod gradient;
+pub mod template_value;
+pub mod default_styles;
 
 
 pub use elements::*;
@@ -16,11 +28,23 @@ pub use properties::*;
 pub use property_registry::*;
 pub use property_cache::*;
 pub use krb::*;
+pub use compat::{CompatWarning, CompatibilityReport, KrbFeature, KrbVersion, ParseOptions, CURRENT_VERSION};
 pub use resources::*;
 pub use events::*;
 pub use style::*;
 pub use layout_units::*;
 pub use text::*; 
+pub use particles::*;
+pub use table::*;
+pub use css::{parse_color, parse_hex_color, parse_length, format_hex_color, resolve_font_relative, LengthContext};
+pub use transform::{ResolvedTransform, TransformOrigin};
+pub use hit_test::{HitRegion, inherited_pointer_events, receives_pointer_events};
+pub use hover::{hover_path, HoverTransition};
+pub use orientation::{Orientation, OrientationVariants, OutputRotation};
+pub use background::{painted_area, tile_pieces, BackgroundPosition, BackgroundRepeat, BackgroundSize, TilePiece};
+pub use gradient::{ColorStop, Gradient, GradientDirection, GradientKind};
+pub use template_value::{TemplateValue, TemplateValueError, TemplateValueType};
+pub use default_styles::{ColorScheme, DefaultStyles, DefaultStylesError, ScrollbarColors};
 
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,297 @@
+// crates/kryon-core/src/orientation.rs
+//! Portrait/landscape variants and rotated output for fixed-mount displays.
+//!
+//! An element property prefixed with an orientation replaces the plain
+//! property while the viewport has that orientation, e.g.
+//! `landscape:flex_direction: "row"` next to `flex_direction: "column"`.
+//! Style blocks work the same way by name: `card:portrait` is applied on top
+//! of `card` in portrait (see `StyleComputer::set_orientation`).
+
+use crate::{Element, ElementId, PropertyValue};
+use glam::{Affine2, Vec2};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+impl Orientation {
+    /// Taller than wide is portrait; a square viewport counts as landscape.
+    pub fn from_size(size: Vec2) -> Self {
+        if size.y > size.x {
+            Orientation::Portrait
+        } else {
+            Orientation::Landscape
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Orientation::Portrait => "portrait",
+            Orientation::Landscape => "landscape",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "portrait" => Some(Orientation::Portrait),
+            "landscape" => Some(Orientation::Landscape),
+            _ => None,
+        }
+    }
+
+    /// Splits `landscape:width` into the orientation and `width`.
+    pub fn split_variant(name: &str) -> Option<(Self, &str)> {
+        let (orientation, property) = name.split_once(':')?;
+        Some((Self::parse(orientation)?, property))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Variant {
+    element: ElementId,
+    property: String,
+    /// The plain property as loaded, restored when no variant applies
+    base: Option<PropertyValue>,
+    portrait: Option<PropertyValue>,
+    landscape: Option<PropertyValue>,
+}
+
+/// The orientation-prefixed properties of a set of elements.
+#[derive(Debug, Clone, Default)]
+pub struct OrientationVariants {
+    variants: Vec<Variant>,
+}
+
+impl OrientationVariants {
+    pub fn from_elements(elements: &HashMap<ElementId, Element>) -> Self {
+        let mut variants: Vec<Variant> = Vec::new();
+        for (&element_id, element) in elements {
+            for (name, value) in &element.custom_properties {
+                let Some((orientation, property)) = Orientation::split_variant(name) else {
+                    continue;
+                };
+                let index = match variants.iter().position(|v| v.element == element_id && v.property == property) {
+                    Some(index) => index,
+                    None => {
+                        variants.push(Variant {
+                            element: element_id,
+                            property: property.to_string(),
+                            base: base_value(element, property),
+                            portrait: None,
+                            landscape: None,
+                        });
+                        variants.len() - 1
+                    }
+                };
+                match orientation {
+                    Orientation::Portrait => variants[index].portrait = Some(value.clone()),
+                    Orientation::Landscape => variants[index].landscape = Some(value.clone()),
+                }
+            }
+        }
+        variants.sort_by(|a, b| (a.element, &a.property).cmp(&(b.element, &b.property)));
+        Self { variants }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    /// Sets each varying property to its value for `orientation`, or back to
+    /// the plain value if it has none.
+    pub fn apply(&self, elements: &mut HashMap<ElementId, Element>, orientation: Orientation) {
+        for variant in &self.variants {
+            let Some(element) = elements.get_mut(&variant.element) else {
+                continue;
+            };
+            let value = match orientation {
+                Orientation::Portrait => variant.portrait.as_ref(),
+                Orientation::Landscape => variant.landscape.as_ref(),
+            };
+            set_value(element, &variant.property, value.or(variant.base.as_ref()));
+        }
+    }
+}
+
+/// `visible` is an element field; everything else is a custom property.
+fn base_value(element: &Element, property: &str) -> Option<PropertyValue> {
+    match property {
+        "visible" => Some(PropertyValue::Bool(element.visible)),
+        _ => element.custom_properties.get(property).cloned(),
+    }
+}
+
+fn set_value(element: &mut Element, property: &str, value: Option<&PropertyValue>) {
+    match (property, value) {
+        ("visible", Some(value)) => {
+            element.visible = value.as_bool().or_else(|| value.as_string().map(|s| s == "true")).unwrap_or(element.visible);
+        }
+        (_, Some(value)) => {
+            element.custom_properties.insert(property.to_string(), value.clone());
+        }
+        (_, None) => {
+            element.custom_properties.remove(property);
+        }
+    }
+}
+
+/// Clockwise rotation of the whole output, for displays mounted sideways or
+/// upside down. Layout and input use the rotated (logical) size; backends
+/// map the frame onto the physical surface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    Clockwise270,
+}
+
+impl OutputRotation {
+    pub fn from_degrees(degrees: u32) -> Option<Self> {
+        match degrees {
+            0 => Some(OutputRotation::None),
+            90 => Some(OutputRotation::Clockwise90),
+            180 => Some(OutputRotation::Clockwise180),
+            270 => Some(OutputRotation::Clockwise270),
+            _ => None,
+        }
+    }
+
+    pub fn degrees(self) -> f32 {
+        match self {
+            OutputRotation::None => 0.0,
+            OutputRotation::Clockwise90 => 90.0,
+            OutputRotation::Clockwise180 => 180.0,
+            OutputRotation::Clockwise270 => 270.0,
+        }
+    }
+
+    fn is_quarter_turn(self) -> bool {
+        matches!(self, OutputRotation::Clockwise90 | OutputRotation::Clockwise270)
+    }
+
+    /// The size layout sees on a surface of `physical` size
+    pub fn logical_size(self, physical: Vec2) -> Vec2 {
+        if self.is_quarter_turn() {
+            Vec2::new(physical.y, physical.x)
+        } else {
+            physical
+        }
+    }
+
+    /// Maps logical coordinates onto the physical surface, in the y-down
+    /// screen space every backend uses.
+    pub fn to_physical(self, logical_size: Vec2) -> Affine2 {
+        let (width, height) = (logical_size.x, logical_size.y);
+        match self {
+            OutputRotation::None => Affine2::IDENTITY,
+            OutputRotation::Clockwise90 => Affine2::from_cols(Vec2::Y, Vec2::NEG_X, Vec2::new(height, 0.0)),
+            OutputRotation::Clockwise180 => Affine2::from_cols(Vec2::NEG_X, Vec2::NEG_Y, Vec2::new(width, height)),
+            OutputRotation::Clockwise270 => Affine2::from_cols(Vec2::NEG_Y, Vec2::X, Vec2::new(0.0, width)),
+        }
+    }
+
+    /// Maps a point on a surface of `physical` size, such as the pointer,
+    /// back to logical coordinates.
+    pub fn to_logical(self, point: Vec2, physical: Vec2) -> Vec2 {
+        self.to_physical(self.logical_size(physical)).inverse().transform_point2(point)
+    }
+
+    /// The physical rect covering a logical rect, e.g. for scissor clips
+    pub fn rect_to_physical(self, position: Vec2, size: Vec2, logical_size: Vec2) -> (Vec2, Vec2) {
+        let transform = self.to_physical(logical_size);
+        let a = transform.transform_point2(position);
+        let b = transform.transform_point2(position + size);
+        (a.min(b), (a - b).abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_orientation_from_aspect() {
+        assert_eq!(Orientation::from_size(Vec2::new(768.0, 1024.0)), Orientation::Portrait);
+        assert_eq!(Orientation::from_size(Vec2::new(1024.0, 768.0)), Orientation::Landscape);
+        assert_eq!(Orientation::from_size(Vec2::new(500.0, 500.0)), Orientation::Landscape);
+        assert_eq!(Orientation::split_variant("portrait:width"), Some((Orientation::Portrait, "width")));
+        assert_eq!(Orientation::split_variant("hover:width"), None);
+    }
+
+    #[test]
+    fn applies_and_restores_variants() {
+        let mut row = Element::default();
+        row.custom_properties.insert("flex_direction".to_string(), PropertyValue::String("column".to_string()));
+        row.custom_properties.insert("landscape:flex_direction".to_string(), PropertyValue::String("row".to_string()));
+        let mut sidebar = Element::default();
+        sidebar.custom_properties.insert("portrait:visible".to_string(), PropertyValue::Bool(false));
+        sidebar.custom_properties.insert("landscape:gap".to_string(), PropertyValue::Float(16.0));
+        let mut elements: HashMap<ElementId, Element> = [(1, row), (2, sidebar)].into_iter().collect();
+
+        let variants = OrientationVariants::from_elements(&elements);
+        variants.apply(&mut elements, Orientation::Landscape);
+        assert_eq!(elements[&1].custom_properties["flex_direction"].as_string(), Some("row"));
+        assert_eq!(elements[&2].custom_properties["gap"].as_float(), Some(16.0));
+        assert!(elements[&2].visible);
+
+        variants.apply(&mut elements, Orientation::Portrait);
+        assert_eq!(elements[&1].custom_properties["flex_direction"].as_string(), Some("column"));
+        assert!(!elements[&2].custom_properties.contains_key("gap"));
+        assert!(!elements[&2].visible);
+
+        variants.apply(&mut elements, Orientation::Landscape);
+        assert!(elements[&2].visible);
+    }
+
+    #[test]
+    fn style_blocks_follow_orientation() {
+        use crate::{Style, StyleComputer};
+        use glam::Vec4;
+
+        let block = |name: &str, color: Vec4| Style {
+            name: name.to_string(),
+            properties: [(0x01, PropertyValue::Color(color))].into_iter().collect(),
+        };
+        let styles: HashMap<u8, Style> = [
+            (1, block("card", Vec4::ONE)),
+            (2, block("card:portrait", Vec4::W)),
+        ].into_iter().collect();
+        let elements: HashMap<ElementId, Element> = [(0, Element { style_id: 1, ..Default::default() })].into_iter().collect();
+
+        let mut styles = StyleComputer::new(&elements, &styles);
+        assert_eq!(styles.compute(0).background_color, Vec4::ONE);
+        styles.set_orientation(Orientation::Portrait);
+        assert_eq!(styles.compute(0).background_color, Vec4::W);
+        styles.set_orientation(Orientation::Landscape);
+        assert_eq!(styles.compute(0).background_color, Vec4::ONE);
+    }
+
+    #[test]
+    fn rotation_maps_corners_and_back() {
+        let physical = Vec2::new(1080.0, 1920.0);
+        let rotation = OutputRotation::Clockwise90;
+        let logical = rotation.logical_size(physical);
+        assert_eq!(logical, Vec2::new(1920.0, 1080.0));
+
+        // The logical top-left lands in the physical top-right corner
+        let transform = rotation.to_physical(logical);
+        assert_eq!(transform.transform_point2(Vec2::ZERO), Vec2::new(1080.0, 0.0));
+        assert_eq!(transform.transform_point2(logical), Vec2::new(0.0, 1920.0));
+        assert_eq!(rotation.to_logical(Vec2::new(1080.0, 0.0), physical), Vec2::ZERO);
+
+        for rotation in [OutputRotation::None, OutputRotation::Clockwise180, OutputRotation::Clockwise270] {
+            let point = Vec2::new(100.0, 40.0);
+            let logical = rotation.logical_size(physical);
+            let there = rotation.to_physical(logical).transform_point2(point);
+            assert!(rotation.to_logical(there, physical).abs_diff_eq(point, 1e-3), "{:?}", rotation);
+        }
+
+        let (position, size) = rotation.rect_to_physical(Vec2::new(0.0, 0.0), Vec2::new(200.0, 100.0), logical);
+        assert_eq!((position, size), (Vec2::new(980.0, 0.0), Vec2::new(100.0, 200.0)));
+    }
+}
@@ -0,0 +1,347 @@
+// crates/kryon-core/src/particles.rs
+use glam::{Vec2, Vec4};
+use crate::{Element, PropertyValue};
+
+/// Emitter configuration read from a ParticleEmitter element's custom properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleEmitterConfig {
+    /// Particles spawned per second (`emission_rate`)
+    pub rate: f32,
+    /// Particles spawned immediately when the emitter starts (`burst`)
+    pub burst: u32,
+    /// Seconds each particle lives (`particle_lifetime`)
+    pub lifetime: f32,
+    /// Seconds the emitter keeps spawning, 0 = forever (`duration`)
+    pub duration: f32,
+    /// Initial speed in px/s (`speed`) and random +/- variation (`speed_variance`)
+    pub speed: f32,
+    pub speed_variance: f32,
+    /// Emission direction in degrees, 0 = right, 90 = down (`direction`)
+    pub direction: f32,
+    /// Random deviation from `direction` in degrees (`spread`)
+    pub spread: f32,
+    /// Acceleration in px/s² (`gravity_x`, `gravity`)
+    pub gravity: Vec2,
+    /// Particle size in px at birth and death (`particle_size`, `particle_size_end`)
+    pub size: f32,
+    pub size_end: f32,
+    /// Rotation speed in degrees per second (`spin`)
+    pub spin: f32,
+    /// Colors sampled evenly over a particle's life (`color_ramp`)
+    pub color_ramp: Vec<Vec4>,
+    /// Optional image drawn for each particle (`sprite`)
+    pub sprite: Option<String>,
+    /// Upper bound on live particles (`max_particles`)
+    pub max_particles: usize,
+}
+
+impl Default for ParticleEmitterConfig {
+    fn default() -> Self {
+        Self {
+            rate: 20.0,
+            burst: 0,
+            lifetime: 1.5,
+            duration: 0.0,
+            speed: 80.0,
+            speed_variance: 20.0,
+            direction: -90.0,
+            spread: 30.0,
+            gravity: Vec2::new(0.0, 98.0),
+            size: 6.0,
+            size_end: 2.0,
+            spin: 0.0,
+            color_ramp: vec![Vec4::new(1.0, 1.0, 1.0, 1.0), Vec4::new(1.0, 1.0, 1.0, 0.0)],
+            sprite: None,
+            max_particles: 500,
+        }
+    }
+}
+
+impl ParticleEmitterConfig {
+    pub fn from_element(element: &Element) -> Self {
+        let mut config = Self::default();
+        let props = &element.custom_properties;
+        let float = |key: &str| props.get(key).and_then(|v| v.as_float());
+
+        if let Some(v) = float("emission_rate") { config.rate = v.max(0.0); }
+        if let Some(v) = float("burst") { config.burst = v.max(0.0) as u32; }
+        if let Some(v) = float("particle_lifetime") { config.lifetime = v.max(0.01); }
+        if let Some(v) = float("duration") { config.duration = v.max(0.0); }
+        if let Some(v) = float("speed") { config.speed = v; }
+        if let Some(v) = float("speed_variance") { config.speed_variance = v.abs(); }
+        if let Some(v) = float("direction") { config.direction = v; }
+        if let Some(v) = float("spread") { config.spread = v.abs(); }
+        if let Some(v) = float("gravity_x") { config.gravity.x = v; }
+        if let Some(v) = float("gravity") { config.gravity.y = v; }
+        if let Some(v) = float("particle_size") {
+            config.size = v.max(0.0);
+            config.size_end = config.size;
+        }
+        if let Some(v) = float("particle_size_end") { config.size_end = v.max(0.0); }
+        if let Some(v) = float("spin") { config.spin = v; }
+        if let Some(v) = float("max_particles") { config.max_particles = v.max(0.0) as usize; }
+
+        match props.get("color_ramp") {
+            Some(PropertyValue::String(ramp)) => {
+                let colors: Vec<Vec4> = ramp
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(crate::css::parse_color)
+                    .collect();
+                if !colors.is_empty() {
+                    config.color_ramp = colors;
+                }
+            }
+            Some(PropertyValue::Color(color)) => config.color_ramp = vec![*color],
+            _ => {
+                // Default ramp: the element's text color fading out
+                let color = element.text_color;
+                config.color_ramp = vec![color, Vec4::new(color.x, color.y, color.z, 0.0)];
+            }
+        }
+
+        config.sprite = props.get("sprite")
+            .and_then(|v| v.as_string())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        config
+    }
+
+    /// Samples the color ramp at `t` in 0..=1.
+    pub fn color_at(&self, t: f32) -> Vec4 {
+        match self.color_ramp.len() {
+            0 => Vec4::ONE,
+            1 => self.color_ramp[0],
+            len => {
+                let scaled = t.clamp(0.0, 1.0) * (len - 1) as f32;
+                let index = (scaled.floor() as usize).min(len - 2);
+                self.color_ramp[index].lerp(self.color_ramp[index + 1], scaled - index as f32)
+            }
+        }
+    }
+
+    /// Farthest distance a particle can travel from the emitter, used for culling.
+    pub fn reach(&self) -> f32 {
+        let t = self.lifetime;
+        (self.speed.abs() + self.speed_variance) * t + 0.5 * self.gravity.length() * t * t + self.size.max(self.size_end)
+    }
+}
+
+/// A particle ready to be drawn, in window coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleInstance {
+    pub position: Vec2,
+    pub size: f32,
+    pub rotation: f32, // degrees
+    pub color: Vec4,
+}
+
+#[derive(Debug, Clone)]
+struct Particle {
+    position: Vec2, // Relative to the emitter origin
+    velocity: Vec2,
+    age: f32,
+    rotation: f32,
+}
+
+/// CPU particle simulation for a single ParticleEmitter element.
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    pub config: ParticleEmitterConfig,
+    particles: Vec<Particle>,
+    elapsed: f32,
+    spawn_accumulator: f32,
+    burst_pending: bool,
+    rng_state: u64,
+}
+
+impl ParticleEmitter {
+    pub fn new(config: ParticleEmitterConfig, seed: u64) -> Self {
+        Self {
+            config,
+            particles: Vec::new(),
+            elapsed: 0.0,
+            spawn_accumulator: 0.0,
+            burst_pending: true,
+            // xorshift must never be seeded with zero
+            rng_state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+
+    pub fn from_element(element: &Element, seed: u64) -> Self {
+        Self::new(ParticleEmitterConfig::from_element(element), seed)
+    }
+
+    /// Advances the simulation by `dt` seconds.
+    pub fn tick(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        let lifetime = self.config.lifetime;
+        let gravity = self.config.gravity;
+        let spin = self.config.spin;
+        self.particles.retain_mut(|p| {
+            p.age += dt;
+            if p.age >= lifetime {
+                return false;
+            }
+            p.velocity += gravity * dt;
+            p.position += p.velocity * dt;
+            p.rotation += spin * dt;
+            true
+        });
+
+        if self.burst_pending {
+            self.burst_pending = false;
+            for _ in 0..self.config.burst {
+                self.spawn();
+            }
+        }
+
+        if self.is_emitting() {
+            self.spawn_accumulator += self.config.rate * dt;
+            while self.spawn_accumulator >= 1.0 {
+                self.spawn_accumulator -= 1.0;
+                self.spawn();
+            }
+        }
+
+        self.elapsed += dt;
+    }
+
+    /// True while the emitter is still spawning new particles.
+    pub fn is_emitting(&self) -> bool {
+        self.config.duration <= 0.0 || self.elapsed < self.config.duration
+    }
+
+    /// True once the emitter has stopped spawning and every particle has died.
+    pub fn is_finished(&self) -> bool {
+        !self.is_emitting() && !self.burst_pending && self.particles.is_empty()
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Returns drawable particles positioned around `origin`.
+    pub fn instances(&self, origin: Vec2) -> Vec<ParticleInstance> {
+        self.particles.iter().map(|p| {
+            let t = p.age / self.config.lifetime;
+            ParticleInstance {
+                position: origin + p.position,
+                size: self.config.size + (self.config.size_end - self.config.size) * t,
+                rotation: p.rotation,
+                color: self.config.color_at(t),
+            }
+        }).collect()
+    }
+
+    fn spawn(&mut self) {
+        if self.particles.len() >= self.config.max_particles {
+            return;
+        }
+        let angle = (self.config.direction + self.config.spread * self.random_signed()).to_radians();
+        let speed = self.config.speed + self.config.speed_variance * self.random_signed();
+        let rotation = 360.0 * self.random_unit();
+        self.particles.push(Particle {
+            position: Vec2::ZERO,
+            velocity: Vec2::new(angle.cos(), angle.sin()) * speed,
+            age: 0.0,
+            rotation,
+        });
+    }
+
+    fn random_unit(&mut self) -> f32 {
+        // xorshift64* - deterministic per seed, no external RNG needed
+        self.rng_state ^= self.rng_state >> 12;
+        self.rng_state ^= self.rng_state << 25;
+        self.rng_state ^= self.rng_state >> 27;
+        let value = self.rng_state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        (value >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn random_signed(&mut self) -> f32 {
+        self.random_unit() * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ParticleEmitterConfig {
+        ParticleEmitterConfig {
+            rate: 10.0,
+            lifetime: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spawns_at_configured_rate() {
+        let mut emitter = ParticleEmitter::new(config(), 1);
+        for _ in 0..5 {
+            emitter.tick(0.1);
+        }
+        assert_eq!(emitter.particle_count(), 5);
+    }
+
+    #[test]
+    fn burst_then_finish() {
+        let mut emitter = ParticleEmitter::new(
+            ParticleEmitterConfig { rate: 0.0, burst: 8, duration: 0.1, ..config() },
+            7,
+        );
+        emitter.tick(0.016);
+        assert_eq!(emitter.particle_count(), 8);
+        assert!(!emitter.is_finished());
+
+        for _ in 0..100 {
+            emitter.tick(0.016);
+        }
+        assert_eq!(emitter.particle_count(), 0);
+        assert!(emitter.is_finished());
+    }
+
+    #[test]
+    fn respects_max_particles() {
+        let mut emitter = ParticleEmitter::new(
+            ParticleEmitterConfig { rate: 1000.0, max_particles: 16, ..config() },
+            3,
+        );
+        emitter.tick(0.5);
+        assert_eq!(emitter.particle_count(), 16);
+    }
+
+    #[test]
+    fn gravity_pulls_particles_down() {
+        let mut emitter = ParticleEmitter::new(
+            ParticleEmitterConfig {
+                rate: 0.0,
+                burst: 1,
+                speed: 0.0,
+                speed_variance: 0.0,
+                gravity: Vec2::new(0.0, 100.0),
+                ..config()
+            },
+            5,
+        );
+        emitter.tick(0.01);
+        emitter.tick(0.5);
+        let instance = emitter.instances(Vec2::new(10.0, 10.0))[0];
+        assert!(instance.position.y > 10.0);
+        assert!((instance.position.x - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn color_ramp_interpolates() {
+        let config = ParticleEmitterConfig {
+            color_ramp: vec![Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 0.0, 1.0, 0.0)],
+            ..config()
+        };
+        assert_eq!(config.color_at(0.0), Vec4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(config.color_at(0.5), Vec4::new(0.5, 0.0, 0.5, 0.5));
+        assert_eq!(config.color_at(2.0), Vec4::new(0.0, 0.0, 1.0, 0.0));
+    }
+}
@@ -1,7 +1,7 @@
 // crates/kryon-core/src/properties.rs
 use glam::Vec4;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PropertyValue {
     String(String),
     Int(i32),
@@ -15,10 +15,12 @@ pub enum PropertyValue {
     RichText(crate::text::RichText),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TransformData {
     pub transform_type: TransformType,
     pub properties: Vec<TransformProperty>,
+    /// From the element's `transform_origin`; the KRB transform table doesn't store one
+    pub origin: crate::TransformOrigin,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,7 +31,7 @@ pub enum TransformType {
     Matrix3D = 0x04,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TransformProperty {
     pub property_type: TransformPropertyType,
     pub value: CSSUnitValue,
@@ -59,7 +61,7 @@ pub enum TransformPropertyType {
     Matrix = 0x0F,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CSSUnitValue {
     pub value: f64,
     pub unit: CSSUnit,
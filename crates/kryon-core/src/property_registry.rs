@@ -60,6 +60,12 @@ pub enum PropertyId {
     WindowIcon = 0x28,
     Cursor = 0x29,
     
+    // Background Properties (0x30-0x3F)
+    BackgroundImage = 0x30,
+    BackgroundRepeat = 0x31,
+    BackgroundSize = 0x32,
+    BackgroundPosition = 0x33,
+    
     // Flexbox Properties (0x40-0x4F)
     Display = 0x40,
     FlexDirection = 0x41,
@@ -73,6 +79,7 @@ pub enum PropertyId {
     JustifyContent = 0x49,
     JustifyItems = 0x4A,
     JustifySelf = 0x4B,
+    Order = 0x4C,
     
     // Position Properties (0x50-0x5F)
     Position = 0x50,
@@ -191,6 +198,10 @@ impl From<u8> for PropertyId {
             0x27 => PropertyId::WindowAntialiasing,
             0x28 => PropertyId::WindowIcon,
             0x29 => PropertyId::Cursor,
+            0x30 => PropertyId::BackgroundImage,
+            0x31 => PropertyId::BackgroundRepeat,
+            0x32 => PropertyId::BackgroundSize,
+            0x33 => PropertyId::BackgroundPosition,
             0x40 => PropertyId::Display,
             0x41 => PropertyId::FlexDirection,
             0x42 => PropertyId::FlexWrap,
@@ -203,6 +214,7 @@ impl From<u8> for PropertyId {
             0x49 => PropertyId::JustifyContent,
             0x4A => PropertyId::JustifyItems,
             0x4B => PropertyId::JustifySelf,
+            0x4C => PropertyId::Order,
             0x50 => PropertyId::Position,
             0x51 => PropertyId::Left,
             0x52 => PropertyId::Top,
@@ -301,6 +313,10 @@ impl PropertyId {
             PropertyId::WindowAntialiasing => 0x27,
             PropertyId::WindowIcon => 0x28,
             PropertyId::Cursor => 0x29,
+            PropertyId::BackgroundImage => 0x30,
+            PropertyId::BackgroundRepeat => 0x31,
+            PropertyId::BackgroundSize => 0x32,
+            PropertyId::BackgroundPosition => 0x33,
             PropertyId::Display => 0x40,
             PropertyId::FlexDirection => 0x41,
             PropertyId::FlexWrap => 0x42,
@@ -313,6 +329,7 @@ impl PropertyId {
             PropertyId::JustifyContent => 0x49,
             PropertyId::JustifyItems => 0x4A,
             PropertyId::JustifySelf => 0x4B,
+            PropertyId::Order => 0x4C,
             PropertyId::Position => 0x50,
             PropertyId::Left => 0x51,
             PropertyId::Top => 0x52,
@@ -560,6 +577,39 @@ impl PropertyRegistry {
             value_type: PropertyValueType::String,
         });
         
+        // Background Properties
+        self.register_property(PropertyMetadata {
+            id: PropertyId::BackgroundImage,
+            name: "background-image",
+            inheritable: false,
+            default_value: PropertyValue::String("none".to_string()),
+            value_type: PropertyValueType::String,
+        });
+        
+        self.register_property(PropertyMetadata {
+            id: PropertyId::BackgroundRepeat,
+            name: "background-repeat",
+            inheritable: false,
+            default_value: PropertyValue::String("repeat".to_string()),
+            value_type: PropertyValueType::String,
+        });
+        
+        self.register_property(PropertyMetadata {
+            id: PropertyId::BackgroundSize,
+            name: "background-size",
+            inheritable: false,
+            default_value: PropertyValue::String("auto".to_string()),
+            value_type: PropertyValueType::String,
+        });
+        
+        self.register_property(PropertyMetadata {
+            id: PropertyId::BackgroundPosition,
+            name: "background-position",
+            inheritable: false,
+            default_value: PropertyValue::String("0% 0%".to_string()),
+            value_type: PropertyValueType::String,
+        });
+        
         // Layout Properties
         self.register_property(PropertyMetadata {
             id: PropertyId::Width,
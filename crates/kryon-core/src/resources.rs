@@ -1,5 +1,6 @@
 // crates/kryon-core/src/resources.rs
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct ResourceManager {
@@ -45,4 +46,78 @@ impl ResourceManager {
     pub fn get_resource(&self, id: &str) -> Option<&ResourceEntry> {
         self.resources.get(id)
     }
-}
\ No newline at end of file
+}
+/// Finds asset files referenced by a KRB. Paths are tried as given, then
+/// relative to the KRB's directory, then in the conventional asset folders
+/// for the resource type.
+#[derive(Debug, Clone, Default)]
+pub struct AssetResolver {
+    krb_dir: Option<PathBuf>,
+}
+
+impl AssetResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves relative to the directory containing `krb_path`.
+    pub fn for_krb(krb_path: impl AsRef<Path>) -> Self {
+        Self {
+            krb_dir: krb_path.as_ref().parent().map(Path::to_path_buf),
+        }
+    }
+
+    /// Uses the first `.krb` path on the command line, as the renderers do
+    /// when they are not handed a resolver explicitly.
+    pub fn from_args() -> Self {
+        std::env::args()
+            .find(|arg| arg.ends_with(".krb"))
+            .map(Self::for_krb)
+            .unwrap_or_default()
+    }
+
+    /// Every location `resolve` checks, in order.
+    pub fn candidates(&self, path: &str, resource_type: &ResourceType) -> Vec<PathBuf> {
+        let mut candidates = vec![PathBuf::from(path)];
+        if let Some(krb_dir) = &self.krb_dir {
+            candidates.push(krb_dir.join(path));
+        }
+        let folders: &[&str] = match resource_type {
+            ResourceType::Font => &["assets/fonts", "fonts", "resources/fonts", "assets"],
+            ResourceType::Image => &["assets", "images", "resources"],
+            _ => &["assets", "resources"],
+        };
+        candidates.extend(folders.iter().map(|folder| Path::new(folder).join(path)));
+        candidates
+    }
+
+    pub fn resolve(&self, path: &str, resource_type: &ResourceType) -> Option<PathBuf> {
+        if path.is_empty() {
+            return None;
+        }
+        self.candidates(path, resource_type).into_iter().find(|candidate| candidate.exists())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_relative_to_krb_directory() {
+        let dir = std::env::temp_dir().join(format!("kryon-assets-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("img")).unwrap();
+        std::fs::write(dir.join("img/logo.png"), b"png").unwrap();
+
+        let resolver = AssetResolver::for_krb(dir.join("app.krb"));
+        assert_eq!(resolver.resolve("img/logo.png", &ResourceType::Image), Some(dir.join("img/logo.png")));
+        assert_eq!(resolver.resolve("img/missing.png", &ResourceType::Image), None);
+        assert_eq!(AssetResolver::new().resolve("", &ResourceType::Image), None);
+
+        let fonts = resolver.candidates("Inter.ttf", &ResourceType::Font);
+        assert_eq!(fonts[1], dir.join("Inter.ttf"));
+        assert_eq!(fonts[2], Path::new("assets/fonts/Inter.ttf"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -2,8 +2,8 @@
 
 use crate::{Element, ElementId, PropertyValue};
 use glam::Vec4;
-use std::collections::HashMap;
-use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::cell::{Cell, RefCell};
 
 /// Represents a single style block from the .krb file, like "appstyle".
 #[derive(Debug, Clone)]
@@ -13,21 +13,106 @@ pub struct Style {
     pub properties: HashMap<u8, PropertyValue>,
 }
 
+/// Font size of an element that neither sets nor inherits one; `em` and
+/// `rem` resolve against it when nothing else applies.
+pub const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+/// Keyword that makes a property take its parent's computed value, as in CSS.
+pub const INHERIT: &str = "inherit";
+
+/// Properties that accept [`INHERIT`], by their custom-property name.
+/// The text ones among them inherit by default.
+pub const INHERITABLE_PROPERTIES: &[&str] = &["text_color", "font_size", "font_weight", "text_alignment", "font_family", "line_height"];
+
+/// Border of an element marked `invalid` whose style has no `name:invalid`
+/// block, and the color its error message is drawn in
+pub const INVALID_COLOR: Vec4 = Vec4::new(0.86, 0.2, 0.2, 1.0);
+
+/// Custom-property names of the background image properties an element
+/// can set inline, over its style block's
+const BACKGROUND_PROPERTIES: [(&str, crate::PropertyId); 4] = [
+    ("background_image", crate::PropertyId::BackgroundImage),
+    ("background_repeat", crate::PropertyId::BackgroundRepeat),
+    ("background_size", crate::PropertyId::BackgroundSize),
+    ("background_position", crate::PropertyId::BackgroundPosition),
+];
+
+/// Custom-property name of a standard property that accepts [`INHERIT`].
+pub fn inheritable_property_name(property_id: u8) -> Option<&'static str> {
+    match crate::PropertyId::from(property_id) {
+        crate::PropertyId::TextColor => Some("text_color"),
+        crate::PropertyId::FontSize => Some("font_size"),
+        crate::PropertyId::FontWeight => Some("font_weight"),
+        crate::PropertyId::TextAlignment => Some("text_alignment"),
+        crate::PropertyId::FontFamily => Some("font_family"),
+        _ => None,
+    }
+}
+
+/// CSS `line-height`: a unitless number scales with the font size of each
+/// descendant it's inherited by, a length doesn't.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineHeight {
+    Multiple(f32),
+    Pixels(f32),
+}
+
+impl LineHeight {
+    /// Parses `1.5`, `24px` or `24`. Bare integers from the KRB's integer
+    /// properties are taken as multiples too, as CSS does.
+    pub fn from_property(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Float(multiple) => Some(LineHeight::Multiple(*multiple)),
+            PropertyValue::Int(multiple) => Some(LineHeight::Multiple(*multiple as f32)),
+            PropertyValue::String(text) => {
+                let text = text.trim();
+                match text.strip_suffix("px") {
+                    Some(pixels) => pixels.trim().parse().ok().map(LineHeight::Pixels),
+                    None => text.parse().ok().map(LineHeight::Multiple),
+                }
+            }
+            _ => None,
+        }
+        .filter(|line_height| match line_height {
+            LineHeight::Multiple(value) | LineHeight::Pixels(value) => value.is_finite() && *value > 0.0,
+        })
+    }
+
+    pub fn to_pixels(self, font_size: f32) -> f32 {
+        match self {
+            LineHeight::Multiple(multiple) => multiple * font_size,
+            LineHeight::Pixels(pixels) => pixels,
+        }
+    }
+}
+
 /// Holds the final, calculated style values for a single element after inheritance.
 /// This is the "single source of truth" for the renderer.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ComputedStyle {
     // Non-inheritable visual properties
     pub background_color: Vec4,
     pub border_color: Vec4,
     pub border_width: f32,
     pub border_radius: f32,
+    /// `None` without a `background_image`
+    pub background_image: Option<String>,
+    /// A `background_image` of `linear-gradient(...)` or `radial-gradient(...)`,
+    /// which leaves `background_image` as `None`
+    pub background_gradient: Option<crate::Gradient>,
+    pub background_repeat: crate::BackgroundRepeat,
+    pub background_size: crate::BackgroundSize,
+    pub background_position: crate::BackgroundPosition,
     
     // Inheritable text properties
     pub text_color: Vec4,
     pub font_size: f32,
     pub font_weight: crate::FontWeight,
     pub text_alignment: crate::TextAlignment,
+    /// `None` for the backend's default font
+    pub font_family: Option<String>,
+    /// `None` for the backend's default spacing
+    pub line_height: Option<LineHeight>,
     
     // Inheritable display properties  
     pub opacity: f32,
@@ -43,12 +128,19 @@ impl Default for ComputedStyle {
             border_color: Vec4::ZERO, // Transparent
             border_width: 0.0,
             border_radius: 0.0,
+            background_image: None,
+            background_gradient: None,
+            background_repeat: crate::BackgroundRepeat::default(),
+            background_size: crate::BackgroundSize::default(),
+            background_position: crate::BackgroundPosition::default(),
             
             // Inheritable text properties
             text_color: Vec4::new(0.0, 0.0, 0.0, 1.0), // Black
-            font_size: 14.0, // Default font size
+            font_size: DEFAULT_FONT_SIZE,
             font_weight: crate::FontWeight::Normal,
             text_alignment: crate::TextAlignment::Start,
+            font_family: None,
+            line_height: None,
             
             // Inheritable display properties
             opacity: 1.0, // Fully opaque
@@ -58,21 +150,93 @@ impl Default for ComputedStyle {
     }
 }
 
+fn root_of(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> ElementId {
+    let mut root_id = element_id;
+    while let Some(parent_id) = elements.get(&root_id).and_then(|element| element.parent) {
+        root_id = parent_id;
+    }
+    root_id
+}
+
+/// Font size from the element's and its ancestors' inline `font_size`, as
+/// `StyleComputer` inherits it, for code without the style blocks such as layout.
+pub fn inline_font_size(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> f32 {
+    let mut current = Some(element_id);
+    while let Some(element) = current.and_then(|id| elements.get(&id)) {
+        if element.font_size != DEFAULT_FONT_SIZE {
+            return element.font_size;
+        }
+        current = element.parent;
+    }
+    DEFAULT_FONT_SIZE
+}
+
+/// [`StyleComputer::length_context`] from inline font sizes only.
+pub fn inline_length_context(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> crate::LengthContext {
+    let root_font_size = inline_font_size(elements, root_of(elements, element_id));
+    crate::LengthContext::for_fonts(inline_font_size(elements, element_id), root_font_size)
+}
+
+/// `default` and an empty family select the backend's built-in font.
+fn font_family(family: &str) -> Option<String> {
+    (!family.is_empty() && family != "default").then(|| family.to_string())
+}
+
 #[derive(Clone)] // Add Clone here for easier use later
 pub struct StyleComputer {
+    /// The elements as of the last `sync_elements`
     elements: HashMap<ElementId, Element>,
     styles: HashMap<u8, Style>,
+    /// Computed styles by element and state. An entry depends on the
+    /// element's style inputs, its ancestors' and which of them are
+    /// hovered, and the orientation; changing any of those drops it.
     cache: RefCell<HashMap<(ElementId, crate::InteractionState), ComputedStyle>>,
+    cache_hits: Cell<u64>,
+    cache_misses: Cell<u64>,
+    /// Elements under the pointer; see `set_hover_path`
+    hovered: HashSet<ElementId>,
+    /// Selects `name:portrait` / `name:landscape` style blocks; see `set_orientation`
+    orientation: Option<crate::Orientation>,
+    /// Style id and orientation -> id of the variant block
+    orientation_styles: HashMap<(u8, crate::Orientation), u8>,
+    /// Style id -> id of its `name:invalid` block, for elements that failed validation
+    invalid_styles: HashMap<u8, u8>,
+    /// Applied below the style blocks; empty unless set
+    default_styles: crate::DefaultStyles,
+    color_scheme: crate::ColorScheme,
     #[allow(dead_code)]
     property_registry: crate::PropertyRegistry,
 }
 
 impl StyleComputer {
     pub fn new(elements: &HashMap<ElementId, Element>, styles: &HashMap<u8, Style>) -> Self {
+        let orientation_styles = styles.iter()
+            .filter_map(|(&variant_id, variant)| {
+                let (base, orientation) = variant.name.rsplit_once(':')?;
+                let orientation = crate::Orientation::parse(orientation)?;
+                let (&base_id, _) = styles.iter().find(|(_, style)| style.name == base)?;
+                Some(((base_id, orientation), variant_id))
+            })
+            .collect();
+        let invalid_styles = styles.iter()
+            .filter_map(|(&variant_id, variant)| {
+                let base = variant.name.strip_suffix(":invalid")?;
+                let (&base_id, _) = styles.iter().find(|(_, style)| style.name == base)?;
+                Some((base_id, variant_id))
+            })
+            .collect();
         Self {
             elements: elements.clone(),
             styles: styles.clone(),
             cache: RefCell::new(HashMap::new()),
+            cache_hits: Cell::new(0),
+            cache_misses: Cell::new(0),
+            hovered: HashSet::new(),
+            orientation: None,
+            orientation_styles,
+            invalid_styles,
+            default_styles: crate::DefaultStyles::default(),
+            color_scheme: crate::ColorScheme::default(),
             property_registry: crate::PropertyRegistry::new(),
         }
     }
@@ -141,6 +305,38 @@ impl StyleComputer {
                     };
                 }
             }
+            crate::PropertyId::FontFamily => {
+                if let Some(family) = prop_value.as_string() {
+                    computed_style.font_family = font_family(family);
+                }
+            }
+            crate::PropertyId::BackgroundImage => {
+                if let Some(source) = prop_value.as_string() {
+                    let source = source.trim();
+                    computed_style.background_gradient = crate::Gradient::parse(source);
+                    // CSS's `url(...)` form is accepted too
+                    let source = source.strip_prefix("url(").and_then(|s| s.strip_suffix(')'))
+                        .map(|s| s.trim().trim_matches(|c| c == '"' || c == '\''))
+                        .unwrap_or(source);
+                    computed_style.background_image = (computed_style.background_gradient.is_none() && !source.is_empty() && source != "none")
+                        .then(|| source.to_string());
+                }
+            }
+            crate::PropertyId::BackgroundRepeat => {
+                if let Some(repeat) = prop_value.as_string().and_then(crate::BackgroundRepeat::parse) {
+                    computed_style.background_repeat = repeat;
+                }
+            }
+            crate::PropertyId::BackgroundSize => {
+                if let Some(size) = prop_value.as_string().and_then(crate::BackgroundSize::parse) {
+                    computed_style.background_size = size;
+                }
+            }
+            crate::PropertyId::BackgroundPosition => {
+                if let Some(position) = prop_value.as_string().and_then(crate::BackgroundPosition::parse) {
+                    computed_style.background_position = position;
+                }
+            }
             crate::PropertyId::TextAlignment => {
                 if let Some(s) = prop_value.as_string() {
                     computed_style.text_alignment = match s {
@@ -150,6 +346,14 @@ impl StyleComputer {
                         "justify" => crate::TextAlignment::Justify,
                         _ => crate::TextAlignment::Start,
                     };
+                } else if let Some(i) = prop_value.as_int() {
+                    // Style blocks store the same codes as inline TextAlignment
+                    computed_style.text_alignment = match i {
+                        1 => crate::TextAlignment::Center,
+                        2 => crate::TextAlignment::End,
+                        3 => crate::TextAlignment::Justify,
+                        _ => crate::TextAlignment::Start,
+                    };
                 }
             }
             crate::PropertyId::Opacity => {
@@ -184,6 +388,11 @@ impl StyleComputer {
             }
         }
     }
+    /// Returns style cache `(hits, misses)` since the last call and resets both counters.
+    pub fn take_cache_stats(&self) -> (u64, u64) {
+        (self.cache_hits.replace(0), self.cache_misses.replace(0))
+    }
+
     /// Computes the final style for a given element, using caching for performance.
     pub fn compute(&self, element_id: ElementId) -> ComputedStyle {
         self.compute_with_state(element_id, crate::InteractionState::Normal)
@@ -191,31 +400,46 @@ impl StyleComputer {
     
     /// Computes the final style for a given element in a specific interaction state.
     pub fn compute_with_state(&self, element_id: ElementId, state: crate::InteractionState) -> ComputedStyle {
-        // Temporarily disable cache to debug state changes
         let cache_key = (element_id, state);
-        // if let Some(cached_style) = self.cache.borrow().get(&cache_key) {
-        //     return *cached_style;
-        // }
+        if let Some(cached_style) = self.cache.borrow().get(&cache_key) {
+            self.cache_hits.set(self.cache_hits.get() + 1);
+            return cached_style.clone();
+        }
+        self.cache_misses.set(self.cache_misses.get() + 1);
 
         let element = self.elements.get(&element_id)
             .expect("Element ID must exist");
 
         // STEP 1: Get Parent's Computed Style (Inheritance)
         // If the element has a parent, compute its style first and inherit inheritable properties
-        let mut computed_style = if let Some(parent_id) = element.parent {
-            let parent_style = self.compute(parent_id);
+        let parent_style = element.parent.map(|parent_id| {
+            let parent_state = if self.hovered.contains(&parent_id) {
+                crate::InteractionState::Hover
+            } else {
+                crate::InteractionState::Normal
+            };
+            self.compute_with_state(parent_id, parent_state)
+        }).unwrap_or_default();
+        let mut computed_style = if element.parent.is_some() {
             ComputedStyle {
                 // Non-inheritable properties - always reset to defaults
                 background_color: Vec4::ZERO,
                 border_color: Vec4::ZERO,
                 border_width: 0.0,
                 border_radius: 0.0,
+                background_image: None,
+                background_gradient: None,
+                background_repeat: crate::BackgroundRepeat::default(),
+                background_size: crate::BackgroundSize::default(),
+                background_position: crate::BackgroundPosition::default(),
                 
                 // Inheritable properties - inherit from parent
                 text_color: parent_style.text_color,
                 font_size: parent_style.font_size,
                 font_weight: parent_style.font_weight,
                 text_alignment: parent_style.text_alignment,
+                font_family: parent_style.font_family.clone(),
+                line_height: parent_style.line_height,
                 opacity: parent_style.opacity,
                 visible: parent_style.visible,
                 cursor: parent_style.cursor,
@@ -225,12 +449,29 @@ impl StyleComputer {
         };
 
 
+        // STEP 2a: The default stylesheet, below everything the KRB sets
+        for (prop_id, prop_value) in self.default_styles.properties_for(element, self.color_scheme) {
+            self.apply_property_to_computed_style(&mut computed_style, *prop_id, prop_value, state);
+        }
+
         // STEP 2: Apply Its Own Style Block (but only for non-interactive states)
         if element.style_id > 0 {
             if let Some(style_block) = self.styles.get(&element.style_id) {
                 // Apply all properties from the referenced style block using PropertyRegistry
                 for (prop_id, prop_value) in &style_block.properties {
-                    self.apply_property_to_computed_style(&mut computed_style, *prop_id, prop_value, state);
+                    self.apply_block_property(&mut computed_style, &parent_style, *prop_id, prop_value, state);
+                }
+            }
+            let variant = self.orientation.and_then(|orientation| self.orientation_styles.get(&(element.style_id, orientation)));
+            if let Some(variant_block) = variant.and_then(|id| self.styles.get(id)) {
+                for (prop_id, prop_value) in &variant_block.properties {
+                    self.apply_block_property(&mut computed_style, &parent_style, *prop_id, prop_value, state);
+                }
+            }
+            let invalid = is_invalid(element).then(|| self.invalid_styles.get(&element.style_id)).flatten();
+            if let Some(invalid_block) = invalid.and_then(|id| self.styles.get(id)) {
+                for (prop_id, prop_value) in &invalid_block.properties {
+                    self.apply_block_property(&mut computed_style, &parent_style, *prop_id, prop_value, state);
                 }
             }
         }
@@ -245,12 +486,27 @@ impl StyleComputer {
         if element.border_color != Vec4::ZERO { computed_style.border_color = element.border_color; }
         if element.border_width != 0.0 { computed_style.border_width = element.border_width; }
         if element.border_radius != 0.0 { computed_style.border_radius = element.border_radius; }
+        for (name, property_id) in BACKGROUND_PROPERTIES {
+            if let Some(value) = element.custom_properties.get(name) {
+                self.apply_property_to_computed_style(&mut computed_style, property_id.as_u8(), value, state);
+            }
+        }
         
         // Inheritable text properties
         if element.text_color != Vec4::new(0.0, 0.0, 0.0, 1.0) { computed_style.text_color = element.text_color; }
-        if element.font_size != 14.0 { computed_style.font_size = element.font_size; }
+        if element.font_size != DEFAULT_FONT_SIZE { computed_style.font_size = element.font_size; }
         if element.font_weight != crate::FontWeight::Normal { computed_style.font_weight = element.font_weight; }
         if element.text_alignment != crate::TextAlignment::Start { computed_style.text_alignment = element.text_alignment; }
+        if element.font_family != "default" && !element.font_family.is_empty() { computed_style.font_family = font_family(&element.font_family); }
+        if let Some(line_height) = element.custom_properties.get("line_height").and_then(LineHeight::from_property) {
+            computed_style.line_height = Some(line_height);
+        }
+        // An explicit `inherit` wins over the style block
+        for &name in INHERITABLE_PROPERTIES {
+            if element.custom_properties.get(name).and_then(|value| value.as_string()) == Some(INHERIT) {
+                Self::inherit_property(&mut computed_style, &parent_style, name);
+            }
+        }
         
         // Inheritable display properties
         if element.opacity != 1.0 { computed_style.opacity = element.opacity; }
@@ -261,6 +517,11 @@ impl StyleComputer {
         if computed_style.border_color.w > 0.0 && computed_style.border_width == 0.0 {
             computed_style.border_width = 1.0;
         }
+        // Invalid elements without an invalid block of their own get a red border
+        if is_invalid(element) && !self.invalid_styles.contains_key(&element.style_id) {
+            computed_style.border_color = INVALID_COLOR;
+            computed_style.border_width = computed_style.border_width.max(1.0);
+        }
 
         // STEP 5: Apply intelligent default interaction effects for buttons
         if element.element_type == crate::ElementType::Button {
@@ -272,10 +533,143 @@ impl StyleComputer {
         }
 
         // Store the final computed style in the cache and return it.
-        self.cache.borrow_mut().insert(cache_key, computed_style);
+        self.cache.borrow_mut().insert(cache_key, computed_style.clone());
         computed_style
     }
     
+    /// Applies one property of a style block, where any inheritable
+    /// property may be the [`INHERIT`] keyword.
+    fn apply_block_property(
+        &self,
+        computed_style: &mut ComputedStyle,
+        parent_style: &ComputedStyle,
+        property_id: u8,
+        prop_value: &PropertyValue,
+        state: crate::InteractionState,
+    ) {
+        match inheritable_property_name(property_id) {
+            Some(name) if prop_value.as_string() == Some(INHERIT) => Self::inherit_property(computed_style, parent_style, name),
+            _ => self.apply_property_to_computed_style(computed_style, property_id, prop_value, state),
+        }
+    }
+    
+    /// Copies the property named by one of [`INHERITABLE_PROPERTIES`] from the parent.
+    fn inherit_property(computed_style: &mut ComputedStyle, parent_style: &ComputedStyle, name: &str) {
+        match name {
+            "text_color" => computed_style.text_color = parent_style.text_color,
+            "font_size" => computed_style.font_size = parent_style.font_size,
+            "font_weight" => computed_style.font_weight = parent_style.font_weight,
+            "text_alignment" => computed_style.text_alignment = parent_style.text_alignment,
+            "font_family" => computed_style.font_family = parent_style.font_family.clone(),
+            "line_height" => computed_style.line_height = parent_style.line_height,
+            _ => {}
+        }
+    }
+    
+    /// Sets the elements under the pointer, as computed by
+    /// [`crate::hover::hover_path`]. Descendants inherit from a hovered
+    /// ancestor's hover style, as with CSS `:hover`.
+    pub fn set_hover_path(&mut self, path: &[ElementId]) {
+        let hovered: HashSet<ElementId> = path.iter().copied().collect();
+        if hovered != self.hovered {
+            // Only the descendants of elements that started or stopped being
+            // hovered inherit anything different
+            let changed: Vec<ElementId> = hovered.symmetric_difference(&self.hovered).copied().collect();
+            self.hovered = hovered;
+            self.invalidate_subtrees(changed);
+        }
+    }
+    
+    /// Catches up with changes made to the elements since they were passed
+    /// to `new` or the last call, e.g. by scripts or template variables.
+    /// Elements whose style block, inline style or place in the tree
+    /// changed are recomputed, along with their descendants.
+    pub fn sync_elements(&mut self, elements: &HashMap<ElementId, Element>) {
+        let mut changed = Vec::new();
+        for (&element_id, element) in elements {
+            let stale = self.elements.get(&element_id).is_none_or(|known| style_inputs_differ(known, element));
+            if stale {
+                self.elements.insert(element_id, element.clone());
+                changed.push(element_id);
+            }
+        }
+        let removed: Vec<ElementId> = self.elements.keys().filter(|id| !elements.contains_key(id)).copied().collect();
+        if changed.is_empty() && removed.is_empty() {
+            return;
+        }
+        // Removed elements' descendants are found through the old tree
+        self.invalidate_subtrees(removed.iter().copied());
+        for element_id in removed {
+            self.elements.remove(&element_id);
+        }
+        self.invalidate_subtrees(changed);
+    }
+    
+    /// Drops the cached styles of `roots` and everything below them.
+    fn invalidate_subtrees(&self, roots: impl IntoIterator<Item = ElementId>) {
+        let mut cache = self.cache.borrow_mut();
+        if cache.is_empty() {
+            return;
+        }
+        let mut stale = HashSet::new();
+        let mut pending: Vec<ElementId> = roots.into_iter().collect();
+        while let Some(element_id) = pending.pop() {
+            if stale.insert(element_id) {
+                if let Some(element) = self.elements.get(&element_id) {
+                    pending.extend(element.children.iter().copied());
+                }
+            }
+        }
+        cache.retain(|(element_id, _), _| !stale.contains(element_id));
+    }
+    
+    /// Sets the stylesheet applied below the KRB's styles, such as
+    /// [`crate::DefaultStyles::builtin`]. None is applied until this is called.
+    pub fn set_default_styles(&mut self, default_styles: crate::DefaultStyles) {
+        if default_styles != self.default_styles {
+            self.default_styles = default_styles;
+            self.cache.borrow_mut().clear();
+        }
+    }
+
+    pub fn default_styles(&self) -> &crate::DefaultStyles {
+        &self.default_styles
+    }
+
+    /// Picks the default stylesheet's light or dark rules
+    pub fn set_color_scheme(&mut self, color_scheme: crate::ColorScheme) {
+        if color_scheme != self.color_scheme {
+            self.color_scheme = color_scheme;
+            self.cache.borrow_mut().clear();
+        }
+    }
+
+    pub fn color_scheme(&self) -> crate::ColorScheme {
+        self.color_scheme
+    }
+
+    /// Scrollbar colors from the default stylesheet
+    pub fn scrollbar_colors(&self) -> crate::ScrollbarColors {
+        self.default_styles.scrollbar_colors(self.color_scheme)
+    }
+
+    /// Applies `name:portrait` or `name:landscape` style blocks on top of
+    /// the `name` block, like the orientation media query in CSS.
+    pub fn set_orientation(&mut self, orientation: crate::Orientation) {
+        if self.orientation != Some(orientation) {
+            self.orientation = Some(orientation);
+            self.cache.borrow_mut().clear();
+        }
+    }
+    
+    /// What lengths on `element_id`, whose computed style is `style`,
+    /// resolve against: `em` its font size, `rem` the root element's.
+    pub fn length_context(&self, element_id: ElementId, style: &ComputedStyle) -> crate::LengthContext {
+        let root_id = root_of(&self.elements, element_id);
+        let root_font_size = if root_id == element_id { style.font_size } else { self.compute(root_id).font_size };
+        crate::LengthContext::for_fonts(style.font_size, root_font_size)
+    }
+    
     /// Get an element by ID
     pub fn get_element(&self, element_id: ElementId) -> Option<&Element> {
         self.elements.get(&element_id)
@@ -444,4 +838,215 @@ impl StyleComputer {
             color1.w * (1.0 - factor) + color2.w * factor,
         )
     }
-}
\ No newline at end of file
+}
+/// Whether anything `compute_with_state` reads from the element differs
+/// Whether the runtime marked an element as failing its validation
+pub fn is_invalid(element: &Element) -> bool {
+    matches!(element.custom_properties.get("invalid"), Some(PropertyValue::Bool(true)))
+}
+
+fn style_inputs_differ(old: &Element, new: &Element) -> bool {
+    // Of the custom properties, only `inherit` keywords, line heights and
+    // the background image properties are read
+    let custom = |element: &Element, name: &str| element.custom_properties.get(name)
+        .map(|value| (value.as_string() == Some(INHERIT), LineHeight::from_property(value)));
+    old.style_id != new.style_id
+        || old.parent != new.parent
+        || old.children != new.children
+        || old.element_type != new.element_type
+        || old.background_color != new.background_color
+        || old.border_color != new.border_color
+        || old.border_width != new.border_width
+        || old.border_radius != new.border_radius
+        || old.text_color != new.text_color
+        || old.font_size != new.font_size
+        || old.font_weight != new.font_weight
+        || old.text_alignment != new.text_alignment
+        || old.font_family != new.font_family
+        || old.opacity != new.opacity
+        || old.visible != new.visible
+        || old.cursor != new.cursor
+        || is_invalid(old) != is_invalid(new)
+        || INHERITABLE_PROPERTIES.iter().any(|name| custom(old, name) != custom(new, name))
+        || BACKGROUND_PROPERTIES.iter().any(|(name, _)| {
+            let value = |element: &Element| element.custom_properties.get(*name).and_then(|value| value.as_string().map(str::to_string));
+            value(old) != value(new)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_properties_inherit_until_overridden() {
+        let mut root = Element { font_family: "Inter".into(), font_size: 20.0, text_alignment: crate::TextAlignment::Center, ..Default::default() };
+        root.custom_properties.insert("line_height".into(), PropertyValue::String("1.5".into()));
+        let mut heading = Element { parent: Some(0), font_size: 32.0, style_id: 1, ..Default::default() };
+        heading.custom_properties.insert("font_size".into(), PropertyValue::String(INHERIT.into()));
+        let body = Element { parent: Some(1), font_family: "Georgia".into(), ..Default::default() };
+        let elements: HashMap<ElementId, Element> = [(0, root), (1, heading), (2, body)].into_iter().collect();
+        let styles: HashMap<u8, Style> = [(1, Style {
+            name: "heading".into(),
+            properties: [
+                (0x02, PropertyValue::Color(Vec4::new(1.0, 0.0, 0.0, 1.0))),
+                (0x0B, PropertyValue::String(INHERIT.into())),
+            ].into_iter().collect(),
+        })].into_iter().collect();
+        let computer = StyleComputer::new(&elements, &styles);
+
+        let heading = computer.compute(1);
+        assert_eq!(heading.font_size, 20.0, "explicit inherit beats the inline size");
+        assert_eq!(heading.font_family.as_deref(), Some("Inter"));
+        assert_eq!(heading.text_alignment, crate::TextAlignment::Center);
+
+        let body = computer.compute(2);
+        assert_eq!(body.font_family.as_deref(), Some("Georgia"));
+        assert_eq!(body.text_color, Vec4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(body.line_height.map(|line_height| line_height.to_pixels(body.font_size)), Some(30.0));
+    }
+
+    #[test]
+    fn em_tracks_nested_font_sizes_and_rem_the_root() {
+        let root = Element { font_size: 20.0, ..Default::default() };
+        let card = Element { parent: Some(0), font_size: 10.0, ..Default::default() };
+        let label = Element { parent: Some(1), ..Default::default() };
+        let elements: HashMap<ElementId, Element> = [(0, root), (1, card), (2, label)].into_iter().collect();
+        let computer = StyleComputer::new(&elements, &HashMap::new());
+
+        for (element_id, em, rem) in [(0, 40.0, 40.0), (1, 20.0, 40.0), (2, 20.0, 40.0)] {
+            let computed = computer.length_context(element_id, &computer.compute(element_id));
+            let inline = inline_length_context(&elements, element_id);
+            for context in [computed, inline] {
+                assert_eq!(crate::parse_length("2em").and_then(|length| length.to_pixels(&context)), Some(em));
+                assert_eq!(crate::parse_length("2rem").and_then(|length| length.to_pixels(&context)), Some(rem));
+            }
+        }
+        assert_eq!(crate::resolve_font_relative("1em 0.5rem", &inline_length_context(&elements, 2)), "10px 10px");
+    }
+
+    #[test]
+    fn background_images_come_from_style_blocks_and_inline_properties() {
+        let mut panel = Element { style_id: 1, ..Default::default() };
+        panel.custom_properties.insert("background_repeat".into(), PropertyValue::String("repeat-y".into()));
+        let child = Element { parent: Some(0), ..Default::default() };
+        let elements: HashMap<ElementId, Element> = [(0, panel), (1, child)].into_iter().collect();
+        let styles: HashMap<u8, Style> = [(1, Style {
+            name: "panel".into(),
+            properties: [
+                (0x30, PropertyValue::String("url('tiles/brick.png')".into())),
+                (0x31, PropertyValue::String("no-repeat".into())),
+                (0x32, PropertyValue::String("cover".into())),
+            ].into_iter().collect(),
+        })].into_iter().collect();
+        let computer = StyleComputer::new(&elements, &styles);
+
+        let panel = computer.compute(0);
+        assert_eq!(panel.background_image.as_deref(), Some("tiles/brick.png"));
+        assert_eq!(panel.background_repeat, crate::BackgroundRepeat::RepeatY, "inline wins over the style block");
+        assert_eq!(panel.background_size, crate::BackgroundSize::Cover);
+        assert_eq!(computer.compute(1).background_image, None, "backgrounds aren't inherited");
+
+        let mut elements = elements;
+        elements.get_mut(&1).unwrap().custom_properties.insert("background_image".into(), PropertyValue::String("linear-gradient(to right, #000, #fff)".into()));
+        let child = StyleComputer::new(&elements, &styles).compute(1);
+        assert_eq!(child.background_image, None, "gradients aren't image files");
+        assert_eq!(child.background_gradient.map(|gradient| gradient.stops.len()), Some(2));
+    }
+
+    #[test]
+    fn parses_line_heights() {
+        assert_eq!(LineHeight::from_property(&PropertyValue::String("24px".into())), Some(LineHeight::Pixels(24.0)));
+        assert_eq!(LineHeight::from_property(&PropertyValue::Float(1.2)), Some(LineHeight::Multiple(1.2)));
+        assert_eq!(LineHeight::from_property(&PropertyValue::String(INHERIT.into())), None);
+        assert_eq!(LineHeight::from_property(&PropertyValue::Int(0)), None);
+    }
+
+    #[test]
+    fn cached_styles_are_dropped_when_their_inputs_change() {
+        let root = Element { children: vec![1], ..Default::default() };
+        let card = Element { parent: Some(0), children: vec![2], ..Default::default() };
+        let label = Element { parent: Some(1), ..Default::default() };
+        let mut elements: HashMap<ElementId, Element> = [(0, root), (1, card), (2, label)].into_iter().collect();
+        let red = Vec4::new(1.0, 0.0, 0.0, 1.0);
+        let styles: HashMap<u8, Style> = [(1, Style {
+            name: "alert".into(),
+            properties: [(0x02, PropertyValue::Color(red))].into_iter().collect(),
+        })].into_iter().collect();
+        let mut computer = StyleComputer::new(&elements, &styles);
+
+        computer.compute(2);
+        computer.take_cache_stats();
+        computer.compute(2);
+        assert_eq!(computer.take_cache_stats(), (1, 0));
+
+        // Unrelated changes keep the cache
+        elements.get_mut(&2).unwrap().text = "Saved".into();
+        computer.sync_elements(&elements);
+        computer.compute(2);
+        assert_eq!(computer.take_cache_stats(), (1, 0));
+
+        // A script switching the card's style block reaches the label through inheritance
+        elements.get_mut(&1).unwrap().style_id = 1;
+        computer.sync_elements(&elements);
+        assert_eq!(computer.compute(2).text_color, red);
+        assert_eq!(computer.take_cache_stats(), (1, 2), "the root is still cached");
+
+        // Hovering only changes what the hovered element's subtree inherits
+        computer.set_hover_path(&[2]);
+        computer.compute(1);
+        computer.compute(2);
+        assert_eq!(computer.take_cache_stats(), (2, 1));
+    }
+
+    #[test]
+    fn default_styles_sit_below_style_blocks_and_inline_properties() {
+        let green = Vec4::new(0.0, 1.0, 0.0, 1.0);
+        let styled = Element { element_type: crate::ElementType::Button, style_id: 1, ..Default::default() };
+        let plain = Element { element_type: crate::ElementType::Button, ..Default::default() };
+        let inline = Element { element_type: crate::ElementType::Button, border_radius: 9.0, ..Default::default() };
+        let elements: HashMap<ElementId, Element> = [(0, styled), (1, plain), (2, inline)].into_iter().collect();
+        let styles: HashMap<u8, Style> = [
+            (1, Style { name: "go".into(), properties: [(0x01, PropertyValue::Color(green))].into_iter().collect() }),
+        ].into_iter().collect();
+        let mut computer = StyleComputer::new(&elements, &styles);
+        assert_eq!(computer.compute(1).border_radius, 0.0, "no defaults until they're set");
+
+        computer.set_default_styles(crate::DefaultStyles::parse("
+            button { background-color: #ffffff; border-radius: 4px }
+            button:dark { background-color: #000000 }
+        ").unwrap());
+        assert_eq!(computer.compute(0).background_color, green);
+        assert_eq!(computer.compute(0).border_radius, 4.0);
+        assert_eq!(computer.compute(1).background_color, Vec4::ONE);
+        assert_eq!(computer.compute(2).border_radius, 9.0);
+
+        computer.set_color_scheme(crate::ColorScheme::Dark);
+        assert_eq!(computer.compute(1).background_color, Vec4::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(computer.compute(0).background_color, green);
+    }
+
+    #[test]
+    fn invalid_elements_take_their_invalid_block_or_a_red_border() {
+        let green = Vec4::new(0.0, 1.0, 0.0, 1.0);
+        let orange = Vec4::new(1.0, 0.5, 0.0, 1.0);
+        let field = Element { element_type: crate::ElementType::Input, style_id: 1, ..Default::default() };
+        let plain = Element { element_type: crate::ElementType::Input, ..Default::default() };
+        let mut elements: HashMap<ElementId, Element> = [(0, field), (1, plain)].into_iter().collect();
+        let styles: HashMap<u8, Style> = [
+            (1, Style { name: "field".into(), properties: [(0x03, PropertyValue::Color(green))].into_iter().collect() }),
+            (2, Style { name: "field:invalid".into(), properties: [(0x03, PropertyValue::Color(orange))].into_iter().collect() }),
+        ].into_iter().collect();
+        let mut computer = StyleComputer::new(&elements, &styles);
+        assert_eq!(computer.compute(0).border_color, green);
+        assert_eq!(computer.compute(1).border_width, 0.0);
+
+        for element in elements.values_mut() {
+            element.custom_properties.insert("invalid".into(), PropertyValue::Bool(true));
+        }
+        computer.sync_elements(&elements);
+        assert_eq!(computer.compute(0).border_color, orange);
+        let plain = computer.compute(1);
+        assert_eq!((plain.border_color, plain.border_width), (INVALID_COLOR, 1.0));
+    }
+}
@@ -0,0 +1,336 @@
+// crates/kryon-core/src/table.rs
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// A typed cell of a loaded dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl CellValue {
+    /// Display form used for rendering and filtering.
+    pub fn display(&self) -> String {
+        match self {
+            CellValue::Null => String::new(),
+            CellValue::Bool(b) => b.to_string(),
+            CellValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => format!("{}", *n as i64),
+            CellValue::Number(n) => n.to_string(),
+            CellValue::Text(text) => text.clone(),
+        }
+    }
+
+    /// Orders values of one column; nulls sort last.
+    fn compare(&self, other: &CellValue) -> Ordering {
+        match (self, other) {
+            (CellValue::Null, CellValue::Null) => Ordering::Equal,
+            (CellValue::Null, _) => Ordering::Greater,
+            (_, CellValue::Null) => Ordering::Less,
+            (CellValue::Number(a), CellValue::Number(b)) => a.total_cmp(b),
+            (CellValue::Bool(a), CellValue::Bool(b)) => a.cmp(b),
+            (a, b) => a.display().to_lowercase().cmp(&b.display().to_lowercase()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Text,
+    Number,
+    Bool,
+}
+
+impl ColumnType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnType::Text => "text",
+            ColumnType::Number => "number",
+            ColumnType::Bool => "bool",
+        }
+    }
+
+    /// The narrowest type every non-empty value parses as.
+    fn detect<'a>(values: impl Iterator<Item = &'a str>) -> Self {
+        let mut detected: Option<ColumnType> = None;
+        for value in values.map(str::trim).filter(|v| !v.is_empty()) {
+            let this = if parse_bool(value).is_some() {
+                ColumnType::Bool
+            } else if value.parse::<f64>().is_ok_and(f64::is_finite) {
+                ColumnType::Number
+            } else {
+                return ColumnType::Text;
+            };
+            match detected {
+                Some(previous) if previous != this => return ColumnType::Text,
+                _ => detected = Some(this),
+            }
+        }
+        detected.unwrap_or(ColumnType::Text)
+    }
+
+    fn parse(&self, raw: &str) -> CellValue {
+        let value = raw.trim();
+        if value.is_empty() {
+            return CellValue::Null;
+        }
+        match self {
+            ColumnType::Number => value.parse().map(CellValue::Number).unwrap_or(CellValue::Null),
+            ColumnType::Bool => parse_bool(value).map(CellValue::Bool).unwrap_or(CellValue::Null),
+            ColumnType::Text => CellValue::Text(raw.to_string()),
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub column_type: ColumnType,
+}
+
+/// Rows of typed cells with named columns, as loaded from CSV or JSON.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dataset {
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<CellValue>>,
+}
+
+impl Dataset {
+    /// Builds a dataset from raw text cells, detecting each column's type.
+    /// Short rows are padded with nulls.
+    pub fn from_text_rows(headers: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        let columns: Vec<Column> = headers.into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let column_type = ColumnType::detect(rows.iter().filter_map(|row| row.get(index).map(String::as_str)));
+                Column { name, column_type }
+            })
+            .collect();
+
+        let rows = rows.iter()
+            .map(|row| {
+                columns.iter()
+                    .enumerate()
+                    .map(|(index, column)| row.get(index).map_or(CellValue::Null, |raw| column.column_type.parse(raw)))
+                    .collect()
+            })
+            .collect();
+
+        Self { columns, rows }
+    }
+
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+    pub column: usize,
+    pub ascending: bool,
+}
+
+/// A dataset as shown by a Table element: sorted and filtered row order.
+#[derive(Debug, Clone)]
+pub struct TableView {
+    dataset: Arc<Dataset>,
+    sort: Option<TableSort>,
+    filter: String,
+    rows: Vec<usize>,
+}
+
+impl TableView {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        let rows = (0..dataset.rows.len()).collect();
+        Self { dataset, sort: None, filter: String::new(), rows }
+    }
+
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    pub fn sort(&self) -> Option<TableSort> {
+        self.sort
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Indices into `dataset().rows` in display order.
+    pub fn visible_rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    pub fn set_sort(&mut self, sort: Option<TableSort>) {
+        self.sort = sort.filter(|sort| sort.column < self.dataset.columns.len());
+        self.refresh();
+    }
+
+    /// Header click behaviour: ascending, then descending, then unsorted.
+    pub fn toggle_sort(&mut self, column: usize) {
+        let next = match self.sort {
+            Some(TableSort { column: current, ascending: true }) if current == column => {
+                Some(TableSort { column, ascending: false })
+            }
+            Some(TableSort { column: current, ascending: false }) if current == column => None,
+            _ => Some(TableSort { column, ascending: true }),
+        };
+        self.set_sort(next);
+    }
+
+    /// Keeps rows where any cell contains `filter` (case-insensitive).
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = filter.trim().to_lowercase();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let dataset = &self.dataset;
+        let filter = &self.filter;
+        self.rows = (0..dataset.rows.len())
+            .filter(|&index| {
+                filter.is_empty()
+                    || dataset.rows[index].iter().any(|cell| cell.display().to_lowercase().contains(filter.as_str()))
+            })
+            .collect();
+
+        if let Some(TableSort { column, ascending }) = self.sort {
+            self.rows.sort_by(|&a, &b| {
+                let (a, b) = (&dataset.rows[a][column], &dataset.rows[b][column]);
+                match (a, b) {
+                    // Nulls stay last in both directions
+                    (CellValue::Null, _) | (_, CellValue::Null) => a.compare(b),
+                    _ if ascending => a.compare(b),
+                    _ => b.compare(a),
+                }
+            });
+        }
+    }
+
+    /// Height of the header and of each row for a font size.
+    pub fn row_height(font_size: f32) -> f32 {
+        (font_size.max(1.0) * 1.8).round()
+    }
+
+    /// The column whose header is at `point`, relative to the table's top-left corner.
+    pub fn header_column_at(&self, point: glam::Vec2, width: f32, font_size: f32) -> Option<usize> {
+        if point.y < 0.0 || point.y > Self::row_height(font_size) {
+            return None;
+        }
+        self.column_at(point.x, width)
+    }
+
+    /// Column widths for a table `width` wide, proportional to the longest
+    /// value in each column (header included), clamped to 1..=40 characters.
+    pub fn column_widths(&self, width: f32) -> Vec<f32> {
+        let weights: Vec<f32> = self.dataset.columns.iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let longest = self.dataset.rows.iter()
+                    .map(|row| row[index].display().chars().count())
+                    .chain(std::iter::once(column.name.chars().count() + 2))
+                    .max()
+                    .unwrap_or(1);
+                longest.clamp(1, 40) as f32
+            })
+            .collect();
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+        weights.iter().map(|weight| width * weight / total).collect()
+    }
+
+    /// The column under `x`, measured from the table's left edge.
+    pub fn column_at(&self, x: f32, width: f32) -> Option<usize> {
+        if x < 0.0 {
+            return None;
+        }
+        let mut right = 0.0;
+        for (index, column_width) in self.column_widths(width).into_iter().enumerate() {
+            right += column_width;
+            if x < right {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> Arc<Dataset> {
+        let rows = [
+            ["Pump A", "12.5", "true"],
+            ["valve", "3", "false"],
+            ["Boiler", "", "TRUE"],
+            ["fan", "40", ""],
+        ];
+        Arc::new(Dataset::from_text_rows(
+            vec!["name".into(), "flow".into(), "running".into()],
+            rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect(),
+        ))
+    }
+
+    #[test]
+    fn detects_column_types() {
+        let dataset = dataset();
+        let types: Vec<ColumnType> = dataset.columns.iter().map(|c| c.column_type).collect();
+        assert_eq!(types, vec![ColumnType::Text, ColumnType::Number, ColumnType::Bool]);
+        assert_eq!(dataset.rows[0][1], CellValue::Number(12.5));
+        assert_eq!(dataset.rows[2][1], CellValue::Null);
+        assert_eq!(dataset.rows[2][2], CellValue::Bool(true));
+
+        let mixed = Dataset::from_text_rows(vec!["code".into()], vec![vec!["1".into()], vec!["A2".into()]]);
+        assert_eq!(mixed.columns[0].column_type, ColumnType::Text);
+    }
+
+    #[test]
+    fn sorts_numbers_numerically_with_nulls_last() {
+        let mut view = TableView::new(dataset());
+        view.toggle_sort(1);
+        assert_eq!(view.visible_rows(), &[1, 0, 3, 2]);
+        view.toggle_sort(1);
+        assert_eq!(view.visible_rows(), &[3, 0, 1, 2]);
+        view.toggle_sort(1);
+        assert_eq!(view.sort(), None);
+        assert_eq!(view.visible_rows(), &[0, 1, 2, 3]);
+
+        view.toggle_sort(0);
+        assert_eq!(view.visible_rows(), &[2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn filters_case_insensitively_and_keeps_sort() {
+        let mut view = TableView::new(dataset());
+        view.toggle_sort(0);
+        view.set_filter("A");
+        assert_eq!(view.visible_rows(), &[3, 0, 1]);
+        view.set_filter("");
+        assert_eq!(view.visible_rows().len(), 4);
+    }
+
+    #[test]
+    fn maps_x_to_columns() {
+        let view = TableView::new(dataset());
+        let widths = view.column_widths(300.0);
+        assert!((widths.iter().sum::<f32>() - 300.0).abs() < 0.01);
+        assert_eq!(view.column_at(1.0, 300.0), Some(0));
+        assert_eq!(view.column_at(299.0, 300.0), Some(2));
+        assert_eq!(view.column_at(301.0, 300.0), None);
+        assert_eq!(CellValue::Number(3.0).display(), "3");
+    }
+}
@@ -0,0 +1,317 @@
+// crates/kryon-core/src/template_value.rs
+//! Typed template variables.
+//!
+//! A template variable's KRB `value_type` declares what it holds: a bool, an
+//! int, a float, a string, a color or a list. Values are kept as text, the
+//! form bindings substitute, and a value set from anywhere (scripts, `--var`,
+//! inputs, data sources) is coerced to the declared type first:
+//!
+//! - Anything becomes a string; a list's items are joined with `, `.
+//! - Text is parsed as the type: `true`/`false`/`yes`/`no`/`on`/`off`/`1`/`0`
+//!   for bools, any CSS color for colors, and `a, b, c` or `[a, b, c]` for
+//!   lists, whose items are read as bools, numbers or strings.
+//! - An int becomes a float, and a float with no fraction an int.
+//!
+//! Anything else, such as `"abc"` for an int or `true` for a float, is an
+//! [`TemplateValueError`] and the variable keeps its value.
+
+use crate::{format_hex_color, parse_color};
+use glam::Vec4;
+use std::fmt;
+
+/// What a template variable holds, from its KRB `value_type` code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TemplateValueType {
+    Bool,
+    Int,
+    Float,
+    /// Code 1, and any code this runtime doesn't know
+    #[default]
+    String,
+    Color,
+    List,
+}
+
+impl TemplateValueType {
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0x02 => Self::Int,
+            0x03 => Self::Float,
+            0x04 => Self::Bool,
+            0x05 => Self::Color,
+            0x06 => Self::List,
+            _ => Self::String,
+        }
+    }
+
+    pub fn code(self) -> u8 {
+        match self {
+            Self::String => 0x01,
+            Self::Int => 0x02,
+            Self::Float => 0x03,
+            Self::Bool => 0x04,
+            Self::Color => 0x05,
+            Self::List => 0x06,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Color => "color",
+            Self::List => "list",
+        }
+    }
+}
+
+impl fmt::Display for TemplateValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("expected {expected}, got {value:?}")]
+pub struct TemplateValueError {
+    pub expected: TemplateValueType,
+    /// The value as text
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Color(Vec4),
+    List(Vec<TemplateValue>),
+}
+
+impl TemplateValue {
+    pub fn value_type(&self) -> TemplateValueType {
+        match self {
+            Self::Bool(_) => TemplateValueType::Bool,
+            Self::Int(_) => TemplateValueType::Int,
+            Self::Float(_) => TemplateValueType::Float,
+            Self::String(_) => TemplateValueType::String,
+            Self::Color(_) => TemplateValueType::Color,
+            Self::List(_) => TemplateValueType::List,
+        }
+    }
+
+    /// Reads `text` as a value of type `value_type`
+    pub fn parse(text: &str, value_type: TemplateValueType) -> Result<Self, TemplateValueError> {
+        Self::String(text.to_string()).coerce(value_type)
+    }
+
+    /// A bool, an int, a float or else a string, whichever `text` reads as
+    pub fn infer(text: &str) -> Self {
+        let trimmed = text.trim();
+        match trimmed {
+            "true" => Self::Bool(true),
+            "false" => Self::Bool(false),
+            _ => trimmed.parse().map(Self::Int).ok()
+                .or_else(|| trimmed.parse::<f64>().ok().filter(|value| value.is_finite()).map(Self::Float))
+                .unwrap_or_else(|| Self::String(text.to_string())),
+        }
+    }
+
+    /// This value as `value_type`, by the rules in the module docs
+    pub fn coerce(self, value_type: TemplateValueType) -> Result<Self, TemplateValueError> {
+        if self.value_type() == value_type {
+            return Ok(self);
+        }
+        let coerced = match (&self, value_type) {
+            (_, TemplateValueType::String) => Some(Self::String(self.to_string())),
+            (Self::Int(value), TemplateValueType::Float) => Some(Self::Float(*value as f64)),
+            (Self::Float(value), TemplateValueType::Int) => whole(*value).map(Self::Int),
+            (Self::String(text), expected) => parse_text(text.trim(), expected),
+            _ => None,
+        };
+        coerced.ok_or_else(|| TemplateValueError { expected: value_type, value: self.to_string() })
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Ints too
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            Self::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_color(&self) -> Option<Vec4> {
+        match self {
+            Self::Color(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[TemplateValue]> {
+        match self {
+            Self::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// The text bindings substitute: colors as hex, lists joined with `, `
+impl fmt::Display for TemplateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::String(value) => f.write_str(value),
+            Self::Color(value) => f.write_str(&format_hex_color(*value)),
+            Self::List(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<bool> for TemplateValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for TemplateValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for TemplateValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for TemplateValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for TemplateValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+fn whole(value: f64) -> Option<i64> {
+    (value.fract() == 0.0 && value.abs() < i64::MAX as f64).then_some(value as i64)
+}
+
+fn parse_text(text: &str, value_type: TemplateValueType) -> Option<TemplateValue> {
+    match value_type {
+        TemplateValueType::Bool => parse_bool(text).map(TemplateValue::Bool),
+        TemplateValueType::Int => text.parse().ok()
+            .or_else(|| text.parse().ok().and_then(whole))
+            .map(TemplateValue::Int),
+        TemplateValueType::Float => text.parse::<f64>().ok().filter(|value| value.is_finite()).map(TemplateValue::Float),
+        TemplateValueType::String => Some(TemplateValue::String(text.to_string())),
+        TemplateValueType::Color => parse_color(text).map(TemplateValue::Color),
+        TemplateValueType::List => Some(TemplateValue::List(parse_list(text))),
+    }
+}
+
+fn parse_bool(text: &str) -> Option<bool> {
+    match text.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_list(text: &str) -> Vec<TemplateValue> {
+    let inner = text.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')).unwrap_or(text).trim();
+    if inner.is_empty() {
+        return Vec::new();
+    }
+    inner.split(',')
+        .map(|item| {
+            let item = item.trim();
+            let quoted = ['"', '\''].into_iter()
+                .find_map(|quote| item.strip_prefix(quote).and_then(|rest| rest.strip_suffix(quote)));
+            match quoted {
+                Some(text) => TemplateValue::String(text.to_string()),
+                None => TemplateValue::infer(item),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_parsed_as_the_declared_type() {
+        assert_eq!(TemplateValue::parse(" yes ", TemplateValueType::Bool), Ok(TemplateValue::Bool(true)));
+        assert_eq!(TemplateValue::parse("42", TemplateValueType::Int), Ok(TemplateValue::Int(42)));
+        assert_eq!(TemplateValue::parse("3.0", TemplateValueType::Int), Ok(TemplateValue::Int(3)));
+        assert_eq!(TemplateValue::parse("2.5", TemplateValueType::Float), Ok(TemplateValue::Float(2.5)));
+        assert_eq!(TemplateValue::parse("red", TemplateValueType::Color), Ok(TemplateValue::Color(Vec4::new(1.0, 0.0, 0.0, 1.0))));
+        assert_eq!(
+            TemplateValue::parse("[1, 'two', true, 4.5]", TemplateValueType::List),
+            Ok(TemplateValue::List(vec![TemplateValue::Int(1), "two".into(), true.into(), 4.5.into()]))
+        );
+        assert_eq!(TemplateValue::parse("", TemplateValueType::List), Ok(TemplateValue::List(Vec::new())));
+
+        let error = TemplateValue::parse("abc", TemplateValueType::Int).unwrap_err();
+        assert_eq!(error.to_string(), "expected int, got \"abc\"");
+        assert!(TemplateValue::parse("2.5", TemplateValueType::Int).is_err());
+        assert!(TemplateValue::parse("nope", TemplateValueType::Color).is_err());
+    }
+
+    #[test]
+    fn values_coerce_between_compatible_types() {
+        assert_eq!(TemplateValue::Int(3).coerce(TemplateValueType::Float), Ok(TemplateValue::Float(3.0)));
+        assert_eq!(TemplateValue::Float(4.0).coerce(TemplateValueType::Int), Ok(TemplateValue::Int(4)));
+        assert_eq!(TemplateValue::Bool(true).coerce(TemplateValueType::String), Ok(TemplateValue::String("true".into())));
+        assert!(TemplateValue::Bool(true).coerce(TemplateValueType::Float).is_err());
+        assert!(TemplateValue::Float(0.5).coerce(TemplateValueType::Int).is_err());
+
+        // Text round-trips through each type's display form
+        let list = TemplateValue::List(vec![TemplateValue::Int(1), TemplateValue::String("b".into())]);
+        assert_eq!(list.to_string(), "1, b");
+        assert_eq!(TemplateValue::parse(&list.to_string(), TemplateValueType::List), Ok(list));
+        assert_eq!(TemplateValue::parse("#F00", TemplateValueType::Color).unwrap().to_string(), "#ff0000");
+        assert_eq!(TemplateValueType::from_code(TemplateValueType::Color.code()), TemplateValueType::Color);
+        assert_eq!(TemplateValueType::from_code(0x7f), TemplateValueType::String);
+    }
+}
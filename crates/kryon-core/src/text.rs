@@ -80,6 +80,51 @@ pub struct RichText {
     pub max_width: Option<f32>,
 }
 
+/// Styling for a byte range of a plain string, as `DrawText` carries
+/// inline markup. Unset fields take the command's defaults.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextRun {
+    pub range: std::ops::Range<usize>,
+    pub color: Option<Vec4>,
+    pub font_weight: Option<RichFontWeight>,
+    pub font_style: Option<RichFontStyle>,
+    pub text_decoration: Option<RichTextDecoration>,
+}
+
+impl TextRun {
+    pub fn is_bold(&self) -> bool {
+        self.font_weight.is_some_and(|weight| weight as u16 >= RichFontWeight::SemiBold as u16)
+    }
+
+    pub fn is_italic(&self) -> bool {
+        matches!(self.font_style, Some(RichFontStyle::Italic | RichFontStyle::Oblique))
+    }
+}
+
+/// Splits `text` at the edges of `runs`, pairing each piece with the run
+/// styling it, if any. Runs must be in order and not overlap.
+pub fn text_segments<'a>(text: &'a str, runs: &'a [TextRun]) -> Vec<(&'a str, Option<&'a TextRun>)> {
+    let mut segments = Vec::with_capacity(runs.len() * 2 + 1);
+    let mut start = 0;
+    for run in runs {
+        let range = run.range.start.clamp(start, text.len())..run.range.end.clamp(start, text.len());
+        if !text.is_char_boundary(range.start) || !text.is_char_boundary(range.end) {
+            continue;
+        }
+        if range.start > start {
+            segments.push((&text[start..range.start], None));
+        }
+        if !range.is_empty() {
+            segments.push((&text[range.clone()], Some(run)));
+        }
+        start = range.end;
+    }
+    if start < text.len() {
+        segments.push((&text[start..], None));
+    }
+    segments
+}
+
 /// Text alignment options for rich text  
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RichTextAlignment {
@@ -237,6 +282,134 @@ impl RichText {
     }
 }
 
+impl RichText {
+    /// Parses inline markup in an element's text: `<b>`, `<i>`, `<u>`, `<s>`
+    /// and `<color=...>` (any CSS color), each closed by its `</...>` tag.
+    /// Tags may nest; one left open runs to the end. Anything else in angle
+    /// brackets is kept as text. `None` when there's no markup at all.
+    pub fn parse_markup(text: &str) -> Option<RichText> {
+        #[derive(Clone)]
+        enum Tag {
+            Bold,
+            Italic,
+            Underline,
+            Strike,
+            Color(Vec4),
+        }
+
+        fn tag_name(tag: &Tag) -> &'static str {
+            match tag {
+                Tag::Bold => "b",
+                Tag::Italic => "i",
+                Tag::Underline => "u",
+                Tag::Strike => "s",
+                Tag::Color(_) => "color",
+            }
+        }
+
+        fn styled(text: String, open: &[Tag]) -> TextSpan {
+            let mut span = TextSpan::new(text);
+            for tag in open {
+                match tag {
+                    Tag::Bold => span.font_weight = Some(RichFontWeight::Bold),
+                    Tag::Italic => span.font_style = Some(RichFontStyle::Italic),
+                    Tag::Underline => span.text_decoration = Some(RichTextDecoration::Underline),
+                    Tag::Strike => span.text_decoration = Some(RichTextDecoration::LineThrough),
+                    Tag::Color(color) => span.color = Some(*color),
+                }
+            }
+            span
+        }
+
+        if !text.contains('<') {
+            return None;
+        }
+
+        let mut spans: Vec<TextSpan> = Vec::new();
+        let mut open: Vec<Tag> = Vec::new();
+        let mut current = String::new();
+        let mut found_markup = false;
+        let mut rest = text;
+
+        while let Some(tag_start) = rest.find('<') {
+            current.push_str(&rest[..tag_start]);
+            rest = &rest[tag_start..];
+            let Some(tag_end) = rest.find('>') else {
+                break;
+            };
+            let body = &rest[1..tag_end];
+
+            let change = if let Some(name) = body.strip_prefix('/') {
+                open.iter().rposition(|tag| tag_name(tag) == name.trim()).map(|index| (None, Some(index)))
+            } else {
+                let (name, value) = match body.split_once('=') {
+                    Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                    None => (body.trim(), None),
+                };
+                match (name, value) {
+                    ("b", None) => Some(Tag::Bold),
+                    ("i", None) => Some(Tag::Italic),
+                    ("u", None) => Some(Tag::Underline),
+                    ("s", None) => Some(Tag::Strike),
+                    ("color", Some(value)) => crate::parse_color(value).map(Tag::Color),
+                    _ => None,
+                }
+                .map(|tag| (Some(tag), None))
+            };
+
+            let Some((opened, closed)) = change else {
+                // Not markup, keep the bracket as text
+                current.push('<');
+                rest = &rest[1..];
+                continue;
+            };
+            found_markup = true;
+            if !current.is_empty() {
+                spans.push(styled(std::mem::take(&mut current), &open));
+            }
+            if let Some(tag) = opened {
+                open.push(tag);
+            }
+            if let Some(index) = closed {
+                open.remove(index);
+            }
+            rest = &rest[tag_end + 1..];
+        }
+        current.push_str(rest);
+
+        if !found_markup {
+            return None;
+        }
+        if !current.is_empty() {
+            spans.push(styled(current, &open));
+        }
+        Some(RichText::from_spans(spans))
+    }
+
+    /// The plain text and the runs styling it, for `DrawText`. Spans that
+    /// set none of color, weight, style or decoration get no run.
+    pub fn to_runs(&self) -> (String, Vec<TextRun>) {
+        let mut text = String::new();
+        let mut runs = Vec::new();
+        for span in &self.spans {
+            let start = text.len();
+            text.push_str(&span.text);
+            let run = TextRun {
+                range: start..text.len(),
+                color: span.color,
+                font_weight: span.font_weight,
+                font_style: span.font_style,
+                text_decoration: span.text_decoration,
+            };
+            let styled = run.color.is_some() || run.font_weight.is_some() || run.font_style.is_some() || run.text_decoration.is_some();
+            if styled && !run.range.is_empty() {
+                runs.push(run);
+            }
+        }
+        (text, runs)
+    }
+}
+
 impl From<String> for RichText {
     fn from(text: String) -> Self {
         Self::from_span(TextSpan::new(text))
@@ -295,6 +468,33 @@ mod tests {
         assert_eq!(rich_text.to_plain_text(), text);
     }
     
+    #[test]
+    fn parses_inline_markup_into_runs() {
+        let rich_text = RichText::parse_markup("Save <b>now</b> or <color=#ff0000><i>lose</i> it</color> <x> 1 < 2").unwrap();
+        let (text, runs) = rich_text.to_runs();
+        assert_eq!(text, "Save now or lose it <x> 1 < 2");
+        assert_eq!(runs.len(), 3);
+        assert_eq!((&text[runs[0].range.clone()], runs[0].is_bold()), ("now", true));
+        assert_eq!(&text[runs[1].range.clone()], "lose");
+        assert!(runs[1].is_italic() && runs[1].color == Some(Vec4::new(1.0, 0.0, 0.0, 1.0)));
+        assert_eq!((&text[runs[2].range.clone()], runs[2].is_italic()), (" it", false));
+
+        let unclosed = RichText::parse_markup("<u>all of it").unwrap().to_runs().1;
+        assert_eq!(unclosed[0].text_decoration, Some(RichTextDecoration::Underline));
+        assert!(RichText::parse_markup("a <b and c").is_none());
+        assert!(RichText::parse_markup("1 < 2 > 0").is_none(), "unknown tags are text");
+    }
+
+    #[test]
+    fn segments_cover_the_whole_text() {
+        let (text, runs) = RichText::parse_markup("a<b>bc</b>d").unwrap().to_runs();
+        let segments: Vec<(&str, bool)> = text_segments(&text, &runs).into_iter()
+            .map(|(piece, run)| (piece, run.is_some()))
+            .collect();
+        assert_eq!(segments, vec![("a", false), ("bc", true), ("d", false)]);
+        assert_eq!(text_segments("plain", &[]), vec![("plain", None)]);
+    }
+    
     #[test]
     fn test_multiline_text_conversion() {
         let lines = vec!["Line 1".to_string(), "Line 2".to_string()];
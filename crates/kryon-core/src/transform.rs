@@ -0,0 +1,535 @@
+// crates/kryon-core/src/transform.rs
+//! Resolving `TransformData` into something a backend can draw with.
+//!
+//! Every backend goes through [`ResolvedTransform`], so `scale: 1.5` or
+//! `translateX: 50%` means the same thing in raylib, wgpu and the terminal.
+//! Properties are combined independently of their order: scales multiply,
+//! rotations and translations add up. Scaling and rotation pivot around the
+//! element's [`TransformOrigin`], its center unless the element sets
+//! `transform_origin` (CSS `transform-origin` syntax, e.g. `top left` or
+//! `25% 0`). Lengths are turned into pixels by the renderer with
+//! [`TransformData::resolve_lengths`] when it builds the draw commands, since
+//! only it knows the element's font size and laid-out size, so backends
+//! never see `em`, `rem` or a percentage translation.
+//!
+//! 3D transforms (`rotateX`, `rotateY`, `translateZ`, `scaleZ`,
+//! `perspective`) are part of the matrix from [`ResolvedTransform::to_mat4`],
+//! which the WGPU backend draws with. Backends that can only draw 2D affine
+//! shapes (raylib, the web canvas, the terminal) use
+//! [`ResolvedTransform::flatten`] instead: it projects the element's center
+//! and axes through the full matrix and keeps the 2D transform that moves
+//! them to the same places. A card flip then shows up as the element
+//! narrowing and, past 90 degrees, mirroring; foreshortening is lost.
+
+use crate::{CSSUnit, CSSUnitValue, LengthContext, TransformData, TransformPropertyType};
+use glam::{Mat3, Mat4, Vec2, Vec3, Vec4};
+
+impl TransformData {
+    /// The transform with its lengths, in its properties and origin, turned
+    /// into pixels: `em` and `rem` from `context`, percentages of `size`,
+    /// the element's own size. Scale percentages stay factors.
+    pub fn resolve_lengths(&self, context: &LengthContext, size: Vec2) -> Self {
+        let resolve = |value: CSSUnitValue, percent_base: f32| match value.unit {
+            CSSUnit::Percentage => CSSUnitValue::new((value.value as f32 * percent_base / 100.0) as f64, CSSUnit::Pixels),
+            _ => value.resolve_font_relative(context),
+        };
+        let mut resolved = self.clone();
+        for property in &mut resolved.properties {
+            property.value = match property.property_type {
+                TransformPropertyType::TranslateX => resolve(property.value, size.x),
+                TransformPropertyType::TranslateY => resolve(property.value, size.y),
+                _ => property.value.resolve_font_relative(context),
+            };
+        }
+        resolved.origin.x = resolve(resolved.origin.x, size.x);
+        resolved.origin.y = resolve(resolved.origin.y, size.y);
+        resolved
+    }
+}
+
+/// The point an element scales and rotates about, relative to its top-left
+/// corner. Percentages refer to the element's own size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformOrigin {
+    pub x: CSSUnitValue,
+    pub y: CSSUnitValue,
+}
+
+impl Default for TransformOrigin {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+impl TransformOrigin {
+    pub const CENTER: Self = Self {
+        x: CSSUnitValue { value: 50.0, unit: CSSUnit::Percentage },
+        y: CSSUnitValue { value: 50.0, unit: CSSUnit::Percentage },
+    };
+
+    /// Parses one or two CSS `transform-origin` values: lengths,
+    /// percentages, or `left`/`center`/`right`/`top`/`bottom` in either order.
+    pub fn parse(value: &str) -> Option<Self> {
+        let percent = |value: f64| CSSUnitValue::new(value, CSSUnit::Percentage);
+        // (value, fits x, fits y)
+        let component = |token: &str| -> Option<(CSSUnitValue, bool, bool)> {
+            Some(match token.to_ascii_lowercase().as_str() {
+                "left" => (percent(0.0), true, false),
+                "right" => (percent(100.0), true, false),
+                "top" => (percent(0.0), false, true),
+                "bottom" => (percent(100.0), false, true),
+                "center" => (percent(50.0), true, true),
+                _ => {
+                    let length = crate::css::parse_length(token)?;
+                    length.to_pixels(&LengthContext::default())?;
+                    (length, true, true)
+                }
+            })
+        };
+
+        let tokens: Vec<&str> = value.split_whitespace().collect();
+        let (x, y) = match tokens.as_slice() {
+            [only] => match component(only)? {
+                (value, false, true) => (percent(50.0), value),
+                (value, _, _) => (value, percent(50.0)),
+            },
+            [first, second] => {
+                let (first, second) = (component(first)?, component(second)?);
+                if first.1 && second.2 {
+                    (first.0, second.0)
+                } else if first.2 && second.1 {
+                    (second.0, first.0)
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+        Some(Self { x, y })
+    }
+
+    /// The origin in pixels from the top-left corner of an element of `size`.
+    pub fn resolve(&self, size: Vec2) -> Vec2 {
+        Vec2::new(length(&self.x, size.x), length(&self.y, size.y))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedTransform {
+    pub scale: Vec2,
+    /// Clockwise rotation about the z axis, in radians
+    pub rotation: f32,
+    /// Offset in pixels
+    pub translation: Vec2,
+    /// `skewX` and `skewY` angles, in radians
+    pub skew: Vec2,
+    pub scale_z: f32,
+    /// Rotation about the x axis, in radians; positive tilts the top away
+    pub rotation_x: f32,
+    /// Rotation about the y axis, in radians; positive turns the right edge away
+    pub rotation_y: f32,
+    /// Offset towards the viewer, in pixels
+    pub translation_z: f32,
+    /// Distance from the viewer to the z = 0 plane, in pixels
+    pub perspective: Option<f32>,
+    /// Pivot for scaling and rotation, in pixels from the element's top-left
+    pub origin: Vec2,
+}
+
+impl Default for ResolvedTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ResolvedTransform {
+    pub const IDENTITY: Self = Self {
+        scale: Vec2::ONE,
+        rotation: 0.0,
+        translation: Vec2::ZERO,
+        skew: Vec2::ZERO,
+        scale_z: 1.0,
+        rotation_x: 0.0,
+        rotation_y: 0.0,
+        translation_z: 0.0,
+        perspective: None,
+        origin: Vec2::ZERO,
+    };
+
+    /// `size` is the element's own size, which percentage translations and
+    /// origins refer to.
+    pub fn resolve(transform: &TransformData, size: Vec2) -> Self {
+        let mut resolved = Self { origin: transform.origin.resolve(size), ..Self::IDENTITY };
+
+        for property in &transform.properties {
+            let value = &property.value;
+            match property.property_type {
+                TransformPropertyType::Scale => resolved.scale *= scale_factor(value),
+                TransformPropertyType::ScaleX => resolved.scale.x *= scale_factor(value),
+                TransformPropertyType::ScaleY => resolved.scale.y *= scale_factor(value),
+                TransformPropertyType::TranslateX => resolved.translation.x += length(value, size.x),
+                TransformPropertyType::TranslateY => resolved.translation.y += length(value, size.y),
+                TransformPropertyType::Rotate | TransformPropertyType::RotateZ => resolved.rotation += angle(value),
+                TransformPropertyType::SkewX => resolved.skew.x += angle(value),
+                TransformPropertyType::SkewY => resolved.skew.y += angle(value),
+                TransformPropertyType::ScaleZ => resolved.scale_z *= scale_factor(value),
+                TransformPropertyType::TranslateZ => resolved.translation_z += length(value, 0.0),
+                TransformPropertyType::RotateX => resolved.rotation_x += angle(value),
+                TransformPropertyType::RotateY => resolved.rotation_y += angle(value),
+                TransformPropertyType::Perspective => {
+                    let distance = length(value, 0.0);
+                    resolved.perspective = (distance > 0.0).then_some(distance);
+                }
+                other => {
+                    tracing::debug!("Unsupported transform property: {:?}", other);
+                }
+            }
+        }
+
+        resolved
+    }
+
+    /// [`Self::resolve`] followed by [`Self::flatten`], for 2D backends.
+    pub fn resolve_2d(transform: &TransformData, size: Vec2) -> Self {
+        Self::resolve(transform, size).flatten(size)
+    }
+
+    /// Whether drawing with the transform changes nothing. The origin doesn't matter then.
+    pub fn is_identity(&self) -> bool {
+        Self { origin: Vec2::ZERO, ..*self } == Self::IDENTITY
+    }
+
+    /// Whether anything moves out of the z = 0 plane or is foreshortened.
+    pub fn is_3d(&self) -> bool {
+        self.rotation_x != 0.0
+            || self.rotation_y != 0.0
+            || self.translation_z != 0.0
+            || self.scale_z != 1.0
+            || self.perspective.is_some()
+    }
+
+    /// The `(scale, rotation, translation)` triple. Skew and 3D parts are
+    /// ignored; call [`Self::flatten`] first to account for 3D.
+    pub fn decompose(&self) -> (Vec2, f32, Vec2) {
+        (self.scale, self.rotation, self.translation)
+    }
+
+    /// Scales, skews, then rotates about x, y and z in that order,
+    /// translates, and finally applies perspective. All about the coordinate
+    /// origin.
+    pub fn to_mat4(&self) -> Mat4 {
+        let mut perspective = Mat4::IDENTITY;
+        if let Some(distance) = self.perspective {
+            perspective.z_axis.w = -1.0 / distance;
+        }
+        let mut skew = Mat4::IDENTITY;
+        skew.x_axis.y = self.skew.y.tan();
+        skew.y_axis.x = self.skew.x.tan();
+
+        perspective
+            * Mat4::from_translation(self.translation.extend(self.translation_z))
+            * Mat4::from_rotation_z(self.rotation)
+            * Mat4::from_rotation_y(self.rotation_y)
+            * Mat4::from_rotation_x(self.rotation_x)
+            * skew
+            * Mat4::from_scale(self.scale.extend(self.scale_z))
+    }
+
+    /// Like [`Self::to_mat4`], but scales and rotates about `origin`.
+    pub fn to_mat4_about(&self, origin: Vec2) -> Mat4 {
+        Mat4::from_translation(origin.extend(0.0))
+            * self.to_mat4()
+            * Mat4::from_translation(-origin.extend(0.0))
+    }
+
+    /// The matrix for an element whose top-left corner is at `position`,
+    /// pivoting around [`Self::origin`].
+    pub fn to_mat4_at(&self, position: Vec2) -> Mat4 {
+        self.to_mat4_about(position + self.origin)
+    }
+
+    /// Maps a point in the plane of the element at `position` through
+    /// [`Self::to_mat4_at`], including the perspective divide. `None` when
+    /// the point ends up behind the viewer.
+    pub fn transform_point(&self, point: Vec2, position: Vec2) -> Option<Vec2> {
+        project(&self.to_mat4_at(position), point)
+    }
+
+    /// The inverse of [`Self::transform_point`]: where in the element's own
+    /// plane a point on screen lands, for hit testing. `None` when the
+    /// element is edge-on or the point misses its plane.
+    pub fn inverse_transform_point(&self, point: Vec2, position: Vec2) -> Option<Vec2> {
+        // Points in the z = 0 plane map to the screen through a 3x3 homography
+        let matrix = self.to_mat4_at(position);
+        let homography = Mat3::from_cols(
+            Vec3::new(matrix.x_axis.x, matrix.x_axis.y, matrix.x_axis.w),
+            Vec3::new(matrix.y_axis.x, matrix.y_axis.y, matrix.y_axis.w),
+            Vec3::new(matrix.w_axis.x, matrix.w_axis.y, matrix.w_axis.w),
+        );
+        if homography.determinant().abs() < f32::EPSILON {
+            return None;
+        }
+        let local = homography.inverse() * point.extend(1.0);
+        (local.z.abs() > f32::EPSILON).then(|| local.truncate() / local.z)
+    }
+
+    /// The 2D transform that maps the origin and axes of an element of
+    /// `size` to where the full 3D transform puts them. Unchanged when the
+    /// transform is already 2D. Mirroring, e.g. from `rotateY(180deg)`,
+    /// comes out as a negative `scale.y`; an element turned edge-on or
+    /// behind the viewer gets a zero scale.
+    pub fn flatten(&self, size: Vec2) -> Self {
+        if !self.is_3d() {
+            return *self;
+        }
+
+        let origin = self.origin;
+        let half = Vec2::new(
+            if size.x > 0.0 { size.x / 2.0 } else { 1.0 },
+            if size.y > 0.0 { size.y / 2.0 } else { 1.0 },
+        );
+        let matrix = self.to_mat4_about(origin);
+        let points = (
+            project(&matrix, origin),
+            project(&matrix, origin + Vec2::new(half.x, 0.0)),
+            project(&matrix, origin + Vec2::new(0.0, half.y)),
+        );
+        let (Some(moved_origin), Some(x_end), Some(y_end)) = points else {
+            return Self { scale: Vec2::ZERO, origin, ..Self::IDENTITY };
+        };
+
+        let x_axis = (x_end - moved_origin) / half.x;
+        let y_axis = (y_end - moved_origin) / half.y;
+        let rotation = x_axis.y.atan2(x_axis.x);
+        // The y axis in the rotated frame: any lean along x is skew
+        let y_axis = Vec2::from_angle(-rotation).rotate(y_axis);
+        let skew_x = if y_axis.y.abs() > f32::EPSILON { (y_axis.x / y_axis.y).atan() } else { 0.0 };
+        Self {
+            scale: Vec2::new(x_axis.length(), y_axis.y),
+            rotation,
+            translation: moved_origin - origin,
+            skew: Vec2::new(skew_x, 0.0),
+            origin,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// The corners of the element at `position` once transformed, clockwise
+    /// from its top-left. `None` when part of it is behind the viewer.
+    pub fn corners(&self, position: Vec2, size: Vec2) -> Option<[Vec2; 4]> {
+        let matrix = self.to_mat4_at(position);
+        Some([
+            project(&matrix, position)?,
+            project(&matrix, position + Vec2::new(size.x, 0.0))?,
+            project(&matrix, position + size)?,
+            project(&matrix, position + Vec2::new(0.0, size.y))?,
+        ])
+    }
+
+    /// The axis-aligned `(position, size)` around [`Self::corners`], for
+    /// culling and coarse hit tests.
+    pub fn bounding_box(&self, position: Vec2, size: Vec2) -> Option<(Vec2, Vec2)> {
+        let corners = self.corners(position, size)?;
+        let min = corners.iter().copied().reduce(Vec2::min)?;
+        let max = corners.iter().copied().reduce(Vec2::max)?;
+        Some((min, max - min))
+    }
+
+    /// The rect after scaling about the origin and translating, as
+    /// `(position, size)`. Rotation and skew are left to the caller.
+    pub fn apply_to_rect(&self, position: Vec2, size: Vec2) -> (Vec2, Vec2) {
+        let pivot = position + self.origin + self.translation;
+        (pivot - self.origin * self.scale, size * self.scale)
+    }
+}
+
+fn project(matrix: &Mat4, point: Vec2) -> Option<Vec2> {
+    let projected = *matrix * Vec4::new(point.x, point.y, 0.0, 1.0);
+    (projected.w > f32::EPSILON).then(|| projected.truncate().truncate() / projected.w)
+}
+
+/// Scale factors are plain numbers or percentages; anything else is taken
+/// at face value.
+fn scale_factor(value: &CSSUnitValue) -> f32 {
+    value.to_factor().unwrap_or(value.value as f32)
+}
+
+fn length(value: &CSSUnitValue, percent_base: f32) -> f32 {
+    let context = LengthContext { percent_base, ..LengthContext::default() };
+    value.to_pixels(&context).unwrap_or(0.0)
+}
+
+/// Angles without a unit are degrees.
+fn angle(value: &CSSUnitValue) -> f32 {
+    match value.unit {
+        CSSUnit::Number => (value.value as f32).to_radians(),
+        _ => value.to_radians().unwrap_or(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransformProperty, TransformType};
+    use std::f32::consts::FRAC_PI_2;
+
+    fn transform(properties: &[(TransformPropertyType, f64, CSSUnit)]) -> TransformData {
+        TransformData {
+            transform_type: TransformType::Transform2D,
+            properties: properties.iter()
+                .map(|&(property_type, value, unit)| TransformProperty { property_type, value: CSSUnitValue::new(value, unit) })
+                .collect(),
+            origin: TransformOrigin::default(),
+        }
+    }
+
+    #[test]
+    fn resolves_units_and_combines_properties() {
+        let data = transform(&[
+            (TransformPropertyType::Scale, 2.0, CSSUnit::Number),
+            (TransformPropertyType::ScaleY, 50.0, CSSUnit::Percentage),
+            (TransformPropertyType::TranslateX, 50.0, CSSUnit::Percentage),
+            (TransformPropertyType::TranslateY, 1.0, CSSUnit::Em),
+            (TransformPropertyType::Rotate, 0.25, CSSUnit::Turns),
+        ]);
+        let size = Vec2::new(200.0, 100.0);
+        let data = data.resolve_lengths(&LengthContext::for_fonts(16.0, 20.0), size);
+        assert!(data.properties.iter().all(|property| property.value.unit != CSSUnit::Em));
+        // Resolved once, a percentage no longer depends on the size the backend passes
+        let resolved = ResolvedTransform::resolve(&data, Vec2::ZERO);
+
+        assert_eq!(resolved.scale, Vec2::new(2.0, 1.0));
+        assert_eq!(resolved.translation, Vec2::new(100.0, 16.0));
+        assert!((resolved.rotation - FRAC_PI_2).abs() < 1e-6);
+        assert!(ResolvedTransform::resolve(&transform(&[]), Vec2::ONE).is_identity());
+    }
+
+    #[test]
+    fn matrix_and_rect_views_agree() {
+        let resolved = ResolvedTransform {
+            scale: Vec2::new(2.0, 0.5),
+            translation: Vec2::new(5.0, -5.0),
+            origin: Vec2::new(10.0, 20.0),
+            ..ResolvedTransform::IDENTITY
+        };
+        let element = Vec2::new(10.0, 10.0);
+        let (position, size) = resolved.apply_to_rect(element, Vec2::new(20.0, 40.0));
+        assert_eq!((position, size), (Vec2::new(5.0, 15.0), Vec2::new(40.0, 20.0)));
+
+        let center = Vec2::new(20.0, 30.0);
+        assert_eq!(resolved.transform_point(element, element), Some(position));
+        assert_eq!(resolved.transform_point(center, element), Some(center + resolved.translation));
+
+        let quarter_turn = ResolvedTransform { rotation: FRAC_PI_2, ..ResolvedTransform::IDENTITY };
+        let rotated = quarter_turn.transform_point(Vec2::new(1.0, 0.0), Vec2::ZERO).unwrap();
+        assert!((rotated - Vec2::new(0.0, 1.0)).length() < 1e-6);
+    }
+
+    #[test]
+    fn perspective_foreshortens_rotated_edges() {
+        let data = transform(&[
+            (TransformPropertyType::Perspective, 400.0, CSSUnit::Pixels),
+            (TransformPropertyType::RotateY, 60.0, CSSUnit::Degrees),
+        ]);
+        let resolved = ResolvedTransform::resolve(&data, Vec2::new(200.0, 100.0));
+        assert!(resolved.is_3d());
+
+        // The right edge turns away from the viewer and shrinks; the left comes closer and grows
+        let right = resolved.transform_point(Vec2::new(200.0, 100.0), Vec2::ZERO).unwrap();
+        let left = resolved.transform_point(Vec2::new(0.0, 100.0), Vec2::ZERO).unwrap();
+        assert!(right.x < 150.0 && right.y < 100.0, "{:?}", right);
+        assert!(left.x < 50.0 && left.y > 100.0, "{:?}", left);
+
+        // Pushed behind the viewer
+        let behind = ResolvedTransform { translation_z: 500.0, perspective: Some(400.0), ..ResolvedTransform::IDENTITY };
+        assert_eq!(behind.transform_point(Vec2::new(100.0, 50.0), Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn flattens_3d_transforms_for_2d_backends() {
+        let size = Vec2::new(200.0, 100.0);
+        let turned = ResolvedTransform::resolve(&transform(&[(TransformPropertyType::RotateY, 60.0, CSSUnit::Degrees)]), size).flatten(size);
+        assert!(!turned.is_3d());
+        assert!((turned.scale - Vec2::new(0.5, 1.0)).length() < 1e-5, "{:?}", turned);
+        assert!(turned.rotation.abs() < 1e-5 && turned.translation.length() < 1e-4);
+
+        let flipped = ResolvedTransform { rotation_x: std::f32::consts::PI, ..ResolvedTransform::IDENTITY }.flatten(size);
+        assert!((flipped.scale - Vec2::new(1.0, -1.0)).length() < 1e-5, "{:?}", flipped);
+
+        let flat = ResolvedTransform { rotation: 1.0, ..ResolvedTransform::IDENTITY };
+        assert_eq!(flat.flatten(size), flat);
+    }
+
+    #[test]
+    fn parses_transform_origins() {
+        let pixels = |value| CSSUnitValue::new(value, CSSUnit::Pixels);
+        let percent = |value| CSSUnitValue::new(value, CSSUnit::Percentage);
+        let parse = |value: &str| TransformOrigin::parse(value).map(|origin| (origin.x, origin.y));
+
+        assert_eq!(parse("top left"), Some((percent(0.0), percent(0.0))));
+        assert_eq!(parse("left top"), parse("top left"));
+        assert_eq!(parse("bottom"), Some((percent(50.0), percent(100.0))));
+        assert_eq!(parse("10px"), Some((pixels(10.0), percent(50.0))));
+        assert_eq!(parse("25% 4px"), Some((percent(25.0), pixels(4.0))));
+        assert_eq!(parse("center"), Some((percent(50.0), percent(50.0))));
+        assert_eq!(parse("left right"), None);
+        assert_eq!(parse("45deg"), None);
+        assert_eq!(parse(""), None);
+
+        let origin = TransformOrigin::parse("right 25%").unwrap();
+        assert_eq!(origin.resolve(Vec2::new(200.0, 100.0)), Vec2::new(200.0, 25.0));
+    }
+
+    #[test]
+    fn pivots_around_the_origin_in_every_view() {
+        let mut data = transform(&[
+            (TransformPropertyType::Scale, 2.0, CSSUnit::Number),
+            (TransformPropertyType::Rotate, 90.0, CSSUnit::Degrees),
+        ]);
+        data.origin = TransformOrigin::parse("top left").unwrap();
+        let position = Vec2::new(10.0, 20.0);
+        let size = Vec2::new(40.0, 30.0);
+        let resolved = ResolvedTransform::resolve(&data, size);
+
+        // The origin stays put; everything else scales and turns around it
+        assert_eq!(resolved.transform_point(position, position), Some(position));
+        let corner = resolved.transform_point(position + Vec2::new(40.0, 0.0), position).unwrap();
+        assert!((corner - Vec2::new(10.0, 100.0)).length() < 1e-4, "{:?}", corner);
+        assert_eq!(resolved.apply_to_rect(position, size), (position, Vec2::new(80.0, 60.0)));
+
+        // Hit testing maps screen points back into the element
+        let local = resolved.inverse_transform_point(corner, position).unwrap();
+        assert!((local - Vec2::new(50.0, 20.0)).length() < 1e-4, "{:?}", local);
+
+        let tilted = ResolvedTransform { rotation_y: 1.0, perspective: Some(300.0), ..resolved };
+        let on_screen = tilted.transform_point(Vec2::new(30.0, 35.0), position).unwrap();
+        let back = tilted.inverse_transform_point(on_screen, position).unwrap();
+        assert!((back - Vec2::new(30.0, 35.0)).length() < 1e-3, "{:?}", back);
+        assert_eq!(tilted.flatten(size).origin, resolved.origin);
+    }
+
+    #[test]
+    fn skews_corners_and_bounds() {
+        let mut data = transform(&[(TransformPropertyType::SkewX, 45.0, CSSUnit::Degrees)]);
+        data.origin = TransformOrigin::parse("top left").unwrap();
+        let resolved = ResolvedTransform::resolve(&data, Vec2::new(100.0, 50.0));
+        assert!((resolved.skew.x - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+
+        // Lower edge slides right by its distance from the origin
+        let corners = resolved.corners(Vec2::ZERO, Vec2::new(100.0, 50.0)).unwrap();
+        let expected = [Vec2::ZERO, Vec2::new(100.0, 0.0), Vec2::new(150.0, 50.0), Vec2::new(50.0, 50.0)];
+        for (corner, expected) in corners.iter().zip(expected) {
+            assert!((*corner - expected).length() < 1e-4, "{:?}", corners);
+        }
+        let (position, size) = resolved.bounding_box(Vec2::ZERO, Vec2::new(100.0, 50.0)).unwrap();
+        assert!(position.length() < 1e-4 && (size - Vec2::new(150.0, 50.0)).length() < 1e-4);
+
+        // Inside the bounding box but outside the parallelogram
+        let local = resolved.inverse_transform_point(Vec2::new(10.0, 45.0), Vec2::ZERO).unwrap();
+        assert!(local.x < 0.0, "{:?}", local);
+
+        // Flattening a 3D transform keeps the skew
+        let tilted = ResolvedTransform { rotation_x: 0.5, ..resolved }.flatten(Vec2::new(100.0, 50.0));
+        assert!(tilted.skew.x > 0.0 && tilted.scale.y < 1.0, "{:?}", tilted);
+    }
+}
@@ -0,0 +1,35 @@
+// crates/kryon-core/tests/krb_regressions.rs
+//! Inputs that once panicked the KRB parser, mostly found by the targets in
+//! `fuzz/`. Each must now load or fail with an error. To add one, copy the
+//! crash artifact into `tests/krb_corpus` with a name describing the cause.
+
+use kryon_core::{load_krb_from_bytes, KryonError};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn corpus_inputs_do_not_panic() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/krb_corpus");
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "empty corpus");
+
+    for path in paths {
+        let data = fs::read(&path).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let result = std::panic::catch_unwind(|| load_krb_from_bytes(&data).map(|_| ()));
+        assert!(result.is_ok(), "{} panicked the parser", name);
+    }
+}
+
+#[test]
+fn truncated_file_is_an_error() {
+    let data = fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/krb_corpus/element_tree_past_end.krb")).unwrap();
+    match load_krb_from_bytes(&data) {
+        Err(KryonError::InvalidKRB(message)) => assert!(message.contains("past the end"), "{}", message),
+        other => panic!("expected InvalidKRB, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,48 @@
+// crates/kryon-core/tests/krb_versions.rs
+//! Loading files written for other KRB versions, by patching the version of
+//! a file the current compiler wrote.
+
+use kryon_core::{load_krb_from_bytes, load_krb_from_bytes_with, CompatWarning, KrbFeature, KrbVersion, KryonError, ParseOptions};
+use std::path::Path;
+
+fn with_version(version: u16) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../kryon-layout/tests/fixtures/column_padding.krb");
+    let mut data = std::fs::read(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    data[4..6].copy_from_slice(&version.to_le_bytes());
+    data
+}
+
+#[test]
+fn current_version_loads_clean() {
+    let file = load_krb_from_bytes(&with_version(0x0500)).unwrap();
+    assert!(file.compatibility.is_clean(), "{}", file.compatibility);
+}
+
+#[test]
+fn newer_minor_version_loads_with_a_warning() {
+    let file = load_krb_from_bytes(&with_version(0x0501)).unwrap();
+    assert_eq!(file.compatibility.file_version, KrbVersion::new(5, 1));
+    assert_eq!(file.compatibility.warnings, vec![CompatWarning::NewerMinorVersion]);
+    assert!(!file.elements.is_empty());
+}
+
+#[test]
+fn newer_major_version_needs_tolerant_parsing() {
+    let data = with_version(0x0600);
+    match load_krb_from_bytes(&data) {
+        Err(KryonError::UnsupportedVersion(0x0600)) => {}
+        other => panic!("expected UnsupportedVersion, got {:?}", other.map(|_| ())),
+    }
+    let file = load_krb_from_bytes_with(&data, ParseOptions::tolerant()).unwrap();
+    assert_eq!(file.compatibility.warnings, vec![CompatWarning::NewerMajorVersion]);
+}
+
+#[test]
+fn older_version_skips_sections_it_predates() {
+    let mut data = with_version(0x0300);
+    data[22..24].copy_from_slice(&3u16.to_le_bytes());
+    let file = load_krb_from_bytes(&data).unwrap();
+    assert!(file.template_variables.is_empty());
+    assert!(file.compatibility.features.is_empty());
+    assert_eq!(file.compatibility.warnings, vec![CompatWarning::IgnoredSection { feature: KrbFeature::TemplateVariables, count: 3 }]);
+}
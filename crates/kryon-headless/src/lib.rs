@@ -0,0 +1,446 @@
+// crates/kryon-headless/src/lib.rs
+//! Offscreen rendering without a window, for CI screenshot tests and
+//! server-side render farms.
+//!
+//! `HeadlessRenderer` draws with the wgpu pipelines into a texture, on a
+//! software adapter when there is no GPU, and reads each finished frame
+//! back as RGBA pixels or a PNG.
+
+use glam::{Vec2, Vec4};
+use image::RgbaImage;
+use kryon_core::{ElementId, OutputRotation};
+use kryon_layout::LayoutResult;
+use kryon_render::{
+    BackendMemoryStats, CommandRenderer, RenderCommand, RenderError, RenderResult, Renderer, Unsupported,
+};
+use kryon_wgpu::{WgpuRenderContext, WgpuRenderer};
+use std::io::Cursor;
+use std::path::Path;
+
+pub struct HeadlessRenderer {
+    gpu: WgpuRenderer,
+}
+
+impl HeadlessRenderer {
+    /// A renderer drawing `size` pixel frames.
+    pub fn new(size: Vec2) -> RenderResult<Self> {
+        Self::initialize(size)
+    }
+
+    /// Renders as a display with `scale` device pixels per logical pixel
+    /// would: the frame keeps its size in pixels, and the app lays out in
+    /// `size / scale` logical pixels. Set it before the app is created.
+    pub fn set_viewport_scale(&mut self, scale: f32) -> RenderResult<()> {
+        self.gpu.set_viewport_scale(scale)
+    }
+
+    /// The last finished frame as RGBA pixels.
+    pub fn frame_rgba(&self) -> RenderResult<RgbaImage> {
+        self.gpu
+            .read_frame()?
+            .ok_or_else(|| RenderError::RenderFailed("No offscreen frame to read".to_string()))
+    }
+
+    /// The last finished frame encoded as a PNG.
+    pub fn frame_png(&self) -> RenderResult<Vec<u8>> {
+        let mut png = Vec::new();
+        self.frame_rgba()?
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .map_err(|e| RenderError::RenderFailed(format!("Failed to encode PNG: {}", e)))?;
+        Ok(png)
+    }
+
+    /// Writes the last finished frame to `filename`, in the format its
+    /// extension names.
+    pub fn take_screenshot(&mut self, filename: &str) -> RenderResult<()> {
+        self.frame_rgba()?
+            .save(Path::new(filename))
+            .map_err(|e| RenderError::RenderFailed(format!("Failed to save {}: {}", filename, e)))
+    }
+}
+
+impl Renderer for HeadlessRenderer {
+    /// Frame size in pixels
+    type Surface = Vec2;
+    type Context = WgpuRenderContext;
+
+    fn initialize(size: Self::Surface) -> RenderResult<Self> {
+        Ok(Self { gpu: WgpuRenderer::new_offscreen(size)? })
+    }
+
+    fn begin_frame(&mut self, clear_color: Vec4) -> RenderResult<Self::Context> {
+        self.gpu.begin_frame(clear_color)
+    }
+
+    fn end_frame(&mut self, context: Self::Context) -> RenderResult<()> {
+        self.gpu.end_frame(context)
+    }
+
+    fn render_element(
+        &mut self,
+        context: &mut Self::Context,
+        element: &kryon_core::Element,
+        layout: &LayoutResult,
+        element_id: ElementId,
+    ) -> RenderResult<()> {
+        self.gpu.render_element(context, element, layout, element_id)
+    }
+
+    fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
+        self.gpu.resize(new_size)
+    }
+
+    fn viewport_size(&self) -> Vec2 {
+        self.gpu.viewport_size()
+    }
+
+    fn viewport_scale(&self) -> f32 {
+        self.gpu.viewport_scale()
+    }
+}
+
+impl CommandRenderer for HeadlessRenderer {
+    fn execute_commands(&mut self, context: &mut Self::Context, commands: &[RenderCommand]) -> RenderResult<()> {
+        self.gpu.execute_commands(context, commands)
+    }
+
+    fn memory_stats(&self) -> BackendMemoryStats {
+        self.gpu.memory_stats()
+    }
+
+    fn set_output_rotation(&mut self, rotation: OutputRotation) -> bool {
+        self.gpu.set_output_rotation(rotation)
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "headless"
+    }
+
+    fn take_unsupported(&mut self) -> Vec<Unsupported> {
+        self.gpu.take_unsupported()
+    }
+
+    fn take_decoded_images(&mut self) -> Vec<(String, Vec2)> {
+        self.gpu.take_decoded_images()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::{BackgroundRepeat, Gradient};
+    use kryon_render::{ImageFilter, ImageRendering};
+
+    #[test]
+    fn frames_are_read_back_without_a_window() {
+        // CI machines without any adapter, not even a software one, can't run this
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 48.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 1.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[RenderCommand::DrawRect {
+            position: Vec2::ZERO,
+            size: Vec2::new(32.0, 48.0),
+            color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: 0,
+        }]).unwrap();
+        renderer.end_frame(context).unwrap();
+
+        let frame = renderer.frame_rgba().unwrap();
+        assert_eq!(frame.dimensions(), (64, 48));
+        assert_eq!(frame.get_pixel(8, 24).0, [255, 0, 0, 255]);
+        assert_eq!(frame.get_pixel(56, 24).0, [0, 0, 255, 255]);
+        assert!(renderer.frame_png().unwrap().starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn images_are_drawn_on_the_first_frame() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 48.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let path = std::env::temp_dir().join(format!("kryon-headless-{}-green.png", std::process::id()));
+        image::RgbaImage::from_pixel(4, 4, image::Rgba([0, 255, 0, 255])).save(&path).unwrap();
+        let source = path.to_string_lossy().to_string();
+
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[
+            RenderCommand::DrawImage { position: Vec2::ZERO, size: Vec2::new(32.0, 48.0), source: source.clone(), opacity: 1.0, transform: None, image_rendering: ImageRendering::Auto, filter: ImageFilter::default() },
+            RenderCommand::DrawImage { position: Vec2::new(32.0, 0.0), size: Vec2::new(32.0, 48.0), source: source.clone(), opacity: 0.5, transform: None, image_rendering: ImageRendering::Auto, filter: ImageFilter::default() },
+        ]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let frame = renderer.frame_rgba().unwrap();
+        assert_eq!(frame.get_pixel(16, 24).0, [0, 255, 0, 255]);
+        let [_, green, _, _] = frame.get_pixel(48, 24).0;
+        assert!((100..=200).contains(&green), "half-transparent image over black: {}", green);
+        assert_eq!(renderer.take_decoded_images(), vec![(source, Vec2::new(4.0, 4.0))]);
+    }
+
+    #[test]
+    fn pixelated_images_keep_hard_texel_edges() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let path = std::env::temp_dir().join(format!("kryon-headless-{}-checker.png", std::process::id()));
+        let mut checker = image::RgbaImage::from_pixel(2, 1, image::Rgba([255, 0, 0, 255]));
+        checker.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+        checker.save(&path).unwrap();
+        let source = path.to_string_lossy().to_string();
+
+        let draw = |renderer: &mut HeadlessRenderer, image_rendering| {
+            let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+            renderer.execute_commands(&mut context, &[
+                RenderCommand::DrawImage { position: Vec2::ZERO, size: Vec2::new(64.0, 32.0), source: source.clone(), opacity: 1.0, transform: None, image_rendering, filter: ImageFilter::default() },
+            ]).unwrap();
+            renderer.end_frame(context).unwrap();
+            renderer.frame_rgba().unwrap()
+        };
+
+        // Just left of the boundary between the two texels
+        let pixelated = draw(&mut renderer, ImageRendering::Pixelated);
+        assert_eq!(pixelated.get_pixel(30, 16).0, [255, 0, 0, 255]);
+        let smooth = draw(&mut renderer, ImageRendering::Smooth);
+        let [red, _, blue, _] = smooth.get_pixel(30, 16).0;
+        assert!(red < 255 && blue > 0, "bilinear blend across the texel edge: {:?}", smooth.get_pixel(30, 16).0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn image_filters_match_their_cpu_definition() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let path = std::env::temp_dir().join(format!("kryon-headless-{}-filtered.png", std::process::id()));
+        image::RgbaImage::from_pixel(4, 4, image::Rgba([200, 60, 20, 255])).save(&path).unwrap();
+        let source = path.to_string_lossy().to_string();
+
+        let disabled = ImageFilter { grayscale: 1.0, brightness: 0.8, contrast: 1.2, ..Default::default() };
+        let tinted = ImageFilter { tint: Vec4::new(0.5, 0.5, 1.0, 1.0), ..Default::default() };
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[
+            RenderCommand::DrawImage { position: Vec2::ZERO, size: Vec2::new(32.0, 32.0), source: source.clone(), opacity: 1.0, transform: None, image_rendering: ImageRendering::Auto, filter: disabled },
+            RenderCommand::DrawImage { position: Vec2::new(32.0, 0.0), size: Vec2::new(32.0, 32.0), source: source.clone(), opacity: 1.0, transform: None, image_rendering: ImageRendering::Auto, filter: tinted },
+        ]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let frame = renderer.frame_rgba().unwrap();
+
+        let texel = Vec4::new(200.0, 60.0, 20.0, 255.0) / 255.0;
+        for (x, filter) in [(16, disabled), (48, tinted)] {
+            let expected = (filter.apply(texel) * 255.0).round();
+            let pixel = Vec4::from_array(frame.get_pixel(x, 16).0.map(f32::from));
+            assert!((pixel - expected).abs().max_element() <= 3.0, "{:?} filtered to {:?}, expected {:?}", filter, pixel, expected);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn icons_are_rasterized_at_their_size_and_tinted() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(32.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let path = std::env::temp_dir().join(format!("kryon-headless-{}-icon.svg", std::process::id()));
+        std::fs::write(&path, r#"<svg viewBox="0 0 24 24"><circle cx="12" cy="12" r="10"/></svg>"#).unwrap();
+
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[RenderCommand::DrawImage {
+            position: Vec2::ZERO,
+            size: Vec2::new(32.0, 32.0),
+            source: kryon_render::icon::icon_source(&path.to_string_lossy(), 32),
+            opacity: 1.0,
+            transform: None,
+            image_rendering: ImageRendering::Auto,
+            filter: ImageFilter { tint: Vec4::new(0.0, 1.0, 0.0, 1.0), ..Default::default() },
+        }]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let frame = renderer.frame_rgba().unwrap();
+
+        assert_eq!(frame.get_pixel(16, 16).0, [0, 255, 0, 255], "the circle takes the tint");
+        assert_eq!(frame.get_pixel(1, 1).0, [0, 0, 0, 255], "outside the circle");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn tiled_backgrounds_repeat_along_the_allowed_axis() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let path = std::env::temp_dir().join(format!("kryon-headless-{}-tile.png", std::process::id()));
+        let mut tile = image::RgbaImage::from_pixel(2, 1, image::Rgba([255, 0, 0, 255]));
+        tile.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+        tile.save(&path).unwrap();
+
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[RenderCommand::DrawTiledImage {
+            position: Vec2::ZERO,
+            size: Vec2::new(64.0, 32.0),
+            source: path.to_string_lossy().to_string(),
+            tile_offset: Vec2::new(4.0, 8.0),
+            tile_size: Vec2::new(8.0, 8.0),
+            repeat: BackgroundRepeat::RepeatX,
+            opacity: 1.0,
+            image_rendering: ImageRendering::Pixelated,
+            transform: None,
+            z_index: 0,
+        }]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let frame = renderer.frame_rgba().unwrap();
+
+        // Tiles start 4px in, so the one before shows its blue half at the left edge
+        assert_eq!(frame.get_pixel(1, 12).0, [0, 0, 255, 255]);
+        assert_eq!(frame.get_pixel(5, 12).0, [255, 0, 0, 255]);
+        assert_eq!(frame.get_pixel(9, 12).0, [0, 0, 255, 255]);
+        assert_eq!(frame.get_pixel(61, 12).0, [255, 0, 0, 255]);
+        // Only one row of tiles
+        assert_eq!(frame.get_pixel(5, 4).0, [0, 0, 0, 255]);
+        assert_eq!(frame.get_pixel(5, 20).0, [0, 0, 0, 255]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn gradients_blend_their_stops_inside_rounded_corners() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 1.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[RenderCommand::DrawGradientRect {
+            position: Vec2::ZERO,
+            size: Vec2::new(64.0, 32.0),
+            gradient: Gradient::parse("linear-gradient(to right, #f00, #00f)").unwrap(),
+            border_radius: 12.0,
+            transform: None,
+            z_index: 0,
+        }]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let frame = renderer.frame_rgba().unwrap();
+
+        let [left_red, _, left_blue, _] = frame.get_pixel(2, 16).0;
+        let [middle_red, _, middle_blue, _] = frame.get_pixel(32, 16).0;
+        let [right_red, _, right_blue, _] = frame.get_pixel(61, 16).0;
+        // The target is sRGB, so the blend is brighter than halfway
+        assert!(left_red > 240 && left_blue < 80, "starts red");
+        assert!(right_blue > 240 && right_red < 80, "ends blue");
+        assert!(middle_red > 150 && middle_blue > 150, "blends in between");
+        assert_eq!(frame.get_pixel(0, 0).0, [0, 255, 0, 255], "the corner is cut off");
+    }
+
+    #[test]
+    fn text_runs_draw_in_their_own_colors() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(128.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        let (text, runs) = kryon_core::RichText::parse_markup("MMM<color=#ff0000><b><u>MMM</u></b></color>").unwrap().to_runs();
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[RenderCommand::DrawText {
+            position: Vec2::new(4.0, 4.0),
+            text,
+            font_size: 20.0,
+            color: Vec4::ONE,
+            alignment: kryon_core::TextAlignment::Start,
+            max_width: Some(120.0),
+            max_height: Some(24.0),
+            transform: None,
+            font_family: None,
+            runs,
+            z_index: 0,
+        }]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let frame = renderer.frame_rgba().unwrap();
+
+        let lit = |columns: std::ops::Range<u32>, test: &dyn Fn([u8; 4]) -> bool| {
+            columns.flat_map(|x| (0..32).map(move |y| (x, y))).filter(|&(x, y)| test(frame.get_pixel(x, y).0)).count()
+        };
+        let white = |[r, g, b, _]: [u8; 4]| r > 200 && g > 200 && b > 200;
+        let red = |[r, g, b, _]: [u8; 4]| r > 200 && g < 60 && b < 60;
+        assert!(lit(0..40, &white) > 20, "the plain run is white");
+        assert_eq!(lit(0..40, &red), 0);
+        assert!(lit(60..128, &red) > lit(0..40, &white), "the bold, underlined run is red and heavier");
+        assert_eq!(lit(60..128, &white), 0);
+    }
+
+    #[test]
+    fn scaled_frames_draw_logical_pixels_at_device_size() {
+        let mut renderer = match HeadlessRenderer::new(Vec2::new(64.0, 32.0)) {
+            Ok(renderer) => renderer,
+            Err(e) => {
+                eprintln!("No wgpu adapter ({}); skipping", e);
+                return;
+            }
+        };
+        renderer.set_viewport_scale(2.0).unwrap();
+        assert_eq!(renderer.viewport_scale(), 2.0);
+        let mut context = renderer.begin_frame(Vec4::new(0.0, 0.0, 0.0, 1.0)).unwrap();
+        renderer.execute_commands(&mut context, &[
+            RenderCommand::DrawRect {
+                position: Vec2::ZERO,
+                size: Vec2::new(8.0, 16.0),
+                color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+                border_radius: 0.0,
+                border_width: 0.0,
+                border_color: Vec4::ZERO,
+                transform: None,
+                shadow: None,
+                z_index: 0,
+            },
+            RenderCommand::DrawText {
+                position: Vec2::new(12.0, 0.0),
+                text: "MM".to_string(),
+                font_size: 12.0,
+                color: Vec4::ONE,
+                alignment: kryon_core::TextAlignment::Start,
+                max_width: None,
+                max_height: None,
+                transform: None,
+                font_family: None,
+                runs: Vec::new(),
+                z_index: 0,
+            },
+        ]).unwrap();
+        renderer.end_frame(context).unwrap();
+        let frame = renderer.frame_rgba().unwrap();
+
+        assert_eq!(frame.dimensions(), (64, 32));
+        assert_eq!(frame.get_pixel(15, 31).0, [255, 0, 0, 255], "an 8x16 rect covers 16x32 pixels");
+        assert_eq!(frame.get_pixel(17, 16).0, [0, 0, 0, 255]);
+        // 12px text rasterized at 24px: its glyphs reach well below 12 pixels down
+        let lit_rows: Vec<u32> = (0..32).filter(|&y| (24..64).any(|x| frame.get_pixel(x, y).0[0] > 200)).collect();
+        assert!(lit_rows.last().is_some_and(|&y| y > 14), "text is drawn at twice its size: rows {:?}", lit_rows);
+    }
+}
@@ -0,0 +1,27 @@
+// crates/kryon-html/src/lib.rs
+//! HTML translation of Kryon element trees.
+//!
+//! The web DOM backend and server-side rendering build the same markup:
+//! semantic tags from `semantics`, classes from a compiled `stylesheet`, and
+//! each node positioned from the layout. `ssr` writes it out as a static page.
+
+pub mod semantics;
+pub mod stylesheet;
+pub mod ssr;
+
+pub use semantics::{describe, Semantics};
+pub use stylesheet::{StyleSheet, BASE_CLASS};
+pub use ssr::{render_krb, render_to_html, SsrError, SsrOptions, HYDRATION_ATTRIBUTE};
+
+use glam::Vec2;
+
+/// Inline style for a node: its layout box relative to its parent's.
+pub fn node_style(position: Vec2, parent_position: Vec2, size: Vec2) -> String {
+    format!(
+        "left: {}px; top: {}px; width: {}px; height: {}px",
+        position.x - parent_position.x,
+        position.y - parent_position.y,
+        size.x,
+        size.y
+    )
+}
@@ -0,0 +1,268 @@
+// crates/kryon-html/src/semantics.rs
+//! Semantic HTML for Kryon elements
+//!
+//! Maps each element to the tag and ARIA attributes browsers, screen readers
+//! and crawlers expect, so the DOM backend and server-side rendering emit
+//! `<button>`, `<a>` and `<input type="range">` instead of positioned divs.
+//! Properties that only matter on the web:
+//!
+//! - `heading: 1`-`6` turns text into `<h1>`-`<h6>`
+//! - `list: true` turns a container into `<ul>` and its children into `<li>`
+//! - `href` on a Link, `alt` on an Image
+//! - `role` and `aria_label` describe custom widgets; `role: "slider"` takes
+//!   its value from `min`/`max`/`value`, and `"checkbox"`/`"switch"` their
+//!   state from the checked interaction state
+
+use kryon_core::{Element, ElementId, ElementType, InteractionState};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Semantics {
+    pub tag: &'static str,
+    pub attributes: Vec<(&'static str, String)>,
+    /// Text content, for elements that carry text
+    pub text: Option<String>,
+}
+
+impl Semantics {
+    fn new(tag: &'static str) -> Self {
+        Self { tag, attributes: Vec::new(), text: None }
+    }
+
+    fn attribute(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.attributes.push((name, value.into()));
+        self
+    }
+
+    /// Elements that can't have children or text.
+    pub fn is_void(&self) -> bool {
+        matches!(self.tag, "input" | "img")
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| *key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+fn string_property<'a>(element: &'a Element, name: &str) -> Option<&'a str> {
+    element.custom_properties.get(name).and_then(|v| v.as_string()).filter(|s| !s.is_empty())
+}
+
+fn number_property(element: &Element, name: &str) -> Option<f32> {
+    element.custom_properties.get(name).and_then(|v| v.as_float())
+}
+
+fn heading_tag(level: i32) -> Option<&'static str> {
+    Some(match level {
+        1 => "h1",
+        2 => "h2",
+        3 => "h3",
+        4 => "h4",
+        5 => "h5",
+        6 => "h6",
+        _ => return None,
+    })
+}
+
+fn is_list(element: &Element) -> bool {
+    element.custom_properties.get("list").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// The semantic tag and attributes for `element_id`.
+pub fn describe(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> Option<Semantics> {
+    let element = elements.get(&element_id)?;
+    let checked = element.current_state == InteractionState::Checked;
+    let text = (!element.text.is_empty()).then(|| element.text.clone());
+    let heading = element.custom_properties.get("heading").and_then(|v| v.as_int()).and_then(heading_tag);
+
+    let mut semantics = match element.element_type {
+        ElementType::App => Semantics::new("main"),
+        ElementType::Button => Semantics { text, ..Semantics::new("button").attribute("type", "button") },
+        ElementType::Link => {
+            let link = Semantics { text, ..Semantics::new("a") };
+            match string_property(element, "href") {
+                Some(href) => link.attribute("href", href),
+                None => link,
+            }
+        }
+        ElementType::Image => {
+            let image = Semantics::new("img").attribute("alt", string_property(element, "alt").unwrap_or_default());
+            match string_property(element, "src") {
+                Some(src) => image.attribute("src", src),
+                None => image,
+            }
+        }
+        ElementType::Input => describe_input(element, checked),
+        ElementType::Text => Semantics { text, ..Semantics::new(heading.unwrap_or("span")) },
+        ElementType::Canvas => Semantics::new("canvas"),
+        ElementType::Table => Semantics::new("div").attribute("role", "table"),
+        _ if is_list(element) => Semantics::new("ul"),
+        _ => Semantics { text, ..Semantics::new(heading.unwrap_or("div")) },
+    };
+
+    let in_list = element.parent.and_then(|parent| elements.get(&parent)).is_some_and(is_list);
+    if in_list {
+        if matches!(semantics.tag, "div" | "span") {
+            semantics.tag = "li";
+        } else {
+            semantics = semantics.attribute("role", "listitem");
+        }
+    }
+
+    if let Some(role) = string_property(element, "role") {
+        semantics.attributes.retain(|(name, _)| *name != "role");
+        semantics = semantics.attribute("role", role);
+        match role {
+            "slider" | "progressbar" | "spinbutton" => {
+                let min = number_property(element, "min").unwrap_or(0.0);
+                let max = number_property(element, "max").unwrap_or(100.0);
+                let value = number_property(element, "value").unwrap_or(min);
+                semantics = semantics
+                    .attribute("aria-valuemin", min.to_string())
+                    .attribute("aria-valuemax", max.to_string())
+                    .attribute("aria-valuenow", value.to_string());
+            }
+            "checkbox" | "switch" | "radio" => semantics = semantics.attribute("aria-checked", checked.to_string()),
+            _ => {}
+        }
+        // Custom widgets drawn from divs need to be reachable by keyboard
+        if !matches!(semantics.tag, "button" | "a" | "input") {
+            semantics = semantics.attribute("tabindex", "0");
+        }
+    }
+    if let Some(label) = string_property(element, "aria_label") {
+        semantics.attributes.retain(|(name, _)| *name != "aria-label");
+        semantics = semantics.attribute("aria-label", label);
+    }
+    if element.disabled {
+        semantics = match semantics.tag {
+            "button" | "input" => semantics.attribute("disabled", ""),
+            _ => semantics.attribute("aria-disabled", "true"),
+        };
+    }
+    if !element.id.is_empty() {
+        semantics = semantics.attribute("data-kryon-id", element.id.as_str());
+    }
+    Some(semantics)
+}
+
+fn describe_input(element: &Element, checked: bool) -> Semantics {
+    let input_type = string_property(element, "input_type").unwrap_or("text");
+    let input = Semantics::new("input").attribute("type", input_type);
+    let input = match input_type {
+        "checkbox" | "radio" if checked => input.attribute("checked", ""),
+        "checkbox" | "radio" => input,
+        "range" => {
+            let min = number_property(element, "min").unwrap_or(0.0);
+            let max = number_property(element, "max").unwrap_or(100.0);
+            let value = number_property(element, "value").unwrap_or(min);
+            input.attribute("min", min.to_string()).attribute("max", max.to_string()).attribute("value", value.to_string())
+        }
+        // A password never goes into the markup
+        "password" => input,
+        "date" => ["min", "max"].into_iter().fold(input.attribute("value", element.text.as_str()), |input, bound| {
+            match string_property(element, bound) {
+                Some(date) => input.attribute(bound, date),
+                None => input,
+            }
+        }),
+        _ => input.attribute("value", element.text.as_str()),
+    };
+    // The checkbox label is drawn next to the box rather than in the DOM
+    let input = match string_property(element, "text") {
+        Some(label) if matches!(input_type, "checkbox" | "radio") => input.attribute("aria-label", label),
+        _ => input,
+    };
+    let input = match string_property(element, "placeholder") {
+        Some(placeholder) => input.attribute("placeholder", placeholder),
+        None => input,
+    };
+    let input = match element.custom_properties.get("readonly").and_then(|v| v.as_bool()) {
+        Some(true) => input.attribute("readonly", ""),
+        _ => input,
+    };
+    describe_constraints(element, input)
+}
+
+/// A text input's validation constraints, and whether it failed them
+fn describe_constraints(element: &Element, mut input: Semantics) -> Semantics {
+    if element.custom_properties.get("required").and_then(|v| v.as_bool()) == Some(true) {
+        input = input.attribute("required", "");
+    }
+    if let Some(pattern) = string_property(element, "pattern") {
+        input = input.attribute("pattern", pattern);
+    }
+    for (name, attribute) in [("min_length", "minlength"), ("max_length", "maxlength")] {
+        if let Some(length) = number_property(element, name) {
+            input = input.attribute(attribute, (length.max(0.0) as usize).to_string());
+        }
+    }
+    if kryon_core::is_invalid(element) {
+        input = input.attribute("aria-invalid", "true");
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::PropertyValue;
+
+    #[test]
+    fn maps_elements_to_semantic_tags() {
+        let element = |element_type: ElementType, parent: Option<u32>, properties: &[(&str, PropertyValue)]| Element {
+            element_type,
+            parent,
+            custom_properties: properties.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+            ..Default::default()
+        };
+        let mut elements: HashMap<u32, Element> = HashMap::new();
+        elements.insert(0, element(ElementType::Container, None, &[("list", PropertyValue::Bool(true))]));
+        elements.insert(1, element(ElementType::Text, Some(0), &[]));
+        elements.insert(2, element(ElementType::Text, None, &[("heading", PropertyValue::Int(2))]));
+        elements.insert(3, element(ElementType::Input, None, &[
+            ("input_type", PropertyValue::String("range".to_string())),
+            ("value", PropertyValue::Float(30.0)),
+        ]));
+        let mut toggle = element(ElementType::Container, None, &[("role", PropertyValue::String("switch".to_string()))]);
+        toggle.current_state = InteractionState::Checked;
+        elements.insert(4, toggle);
+        let mut password = element(ElementType::Input, None, &[("input_type", PropertyValue::String("password".to_string()))]);
+        password.text = "hunter2".to_string();
+        elements.insert(5, password);
+        elements.insert(6, element(ElementType::Input, None, &[
+            ("required", PropertyValue::Bool(true)),
+            ("min_length", PropertyValue::Int(3)),
+            ("invalid", PropertyValue::Bool(true)),
+        ]));
+        let mut due = element(ElementType::Input, None, &[
+            ("input_type", PropertyValue::String("date".to_string())),
+            ("min", PropertyValue::String("2026-01-01".to_string())),
+        ]);
+        due.text = "2026-10-16".to_string();
+        elements.insert(7, due);
+
+        let describe = |id| describe(&elements, id).expect("element exists");
+        assert_eq!(describe(0).tag, "ul");
+        assert_eq!(describe(1).tag, "li");
+        assert_eq!(describe(2).tag, "h2");
+
+        let slider = describe(3);
+        assert_eq!((slider.tag, slider.get("type"), slider.get("value")), ("input", Some("range"), Some("30")));
+        assert!(slider.is_void());
+
+        let switch = describe(4);
+        assert_eq!(switch.get("role"), Some("switch"));
+        assert_eq!(switch.get("aria-checked"), Some("true"));
+        assert_eq!(switch.get("tabindex"), Some("0"));
+
+        let password = describe(5);
+        assert_eq!((password.get("type"), password.get("value")), (Some("password"), None));
+
+        let name = describe(6);
+        assert_eq!((name.get("required"), name.get("minlength"), name.get("aria-invalid")), (Some(""), Some("3"), Some("true")));
+
+        let due = describe(7);
+        assert_eq!((due.get("value"), due.get("min"), due.get("max")), (Some("2026-10-16"), Some("2026-01-01"), None));
+    }
+}
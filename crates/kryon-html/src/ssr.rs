@@ -0,0 +1,208 @@
+// crates/kryon-html/src/ssr.rs
+//! Server-side rendering of a KRB to a static HTML page.
+//!
+//! Runs Taffy layout for a fixed viewport and writes the same markup the DOM
+//! backend builds, with the compiled stylesheet in the head. No browser or
+//! wasm is involved, so docs and marketing sites can pre-render at build time.
+//!
+//! With hydration enabled every node carries `data-kryon-node` with its
+//! element id, and a JSON block records the root and viewport, so the wasm
+//! runtime can adopt the existing nodes instead of rebuilding them.
+
+use crate::{describe, node_style, StyleSheet, BASE_CLASS};
+use glam::Vec2;
+use kryon_core::{Element, ElementId, KRBFile, StyleComputer};
+use kryon_layout::{LayoutEngine, LayoutResult, TaffyLayoutEngine};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Attribute holding each node's element id in hydratable output.
+pub const HYDRATION_ATTRIBUTE: &str = "data-kryon-node";
+
+#[derive(Error, Debug)]
+pub enum SsrError {
+    #[error("KRB file has no root element")]
+    NoRootElement,
+    #[error("Viewport must be positive, got {0}x{1}")]
+    InvalidViewport(f32, f32),
+}
+
+#[derive(Debug, Clone)]
+pub struct SsrOptions {
+    pub viewport: Vec2,
+    pub title: String,
+    /// Tag nodes with element ids and embed hydration metadata
+    pub hydrate: bool,
+}
+
+impl Default for SsrOptions {
+    fn default() -> Self {
+        Self { viewport: Vec2::new(800.0, 600.0), title: "Kryon".to_string(), hydrate: false }
+    }
+}
+
+/// Lays out `krb` at the requested viewport and renders a complete page.
+pub fn render_krb(krb: &KRBFile, options: &SsrOptions) -> Result<String, SsrError> {
+    let root_id = krb.root_element_id.ok_or(SsrError::NoRootElement)?;
+    if !(options.viewport.x > 0.0 && options.viewport.y > 0.0) {
+        return Err(SsrError::InvalidViewport(options.viewport.x, options.viewport.y));
+    }
+    let layout = TaffyLayoutEngine::new().compute_layout(&krb.elements, root_id, options.viewport);
+    let style_computer = StyleComputer::new(&krb.elements, &krb.styles);
+    Ok(render_to_html(&krb.elements, &layout, &style_computer, root_id, options))
+}
+
+/// Renders an already laid out tree as a complete page.
+pub fn render_to_html(
+    elements: &HashMap<ElementId, Element>,
+    layout: &LayoutResult,
+    style_computer: &StyleComputer,
+    root_id: ElementId,
+    options: &SsrOptions,
+) -> String {
+    let mut stylesheet = StyleSheet::new();
+    let mut body = String::new();
+    let mut nodes = 0;
+    write_element(&mut body, &mut stylesheet, &mut nodes, elements, layout, style_computer, root_id, Vec2::ZERO, options.hydrate, 1);
+
+    let viewport = options.viewport;
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(&options.title)));
+    html.push_str("<style id=\"kryon-styles\">\n");
+    html.push_str(&format!(".kryon-root {{ position: relative; width: {}px; height: {}px; overflow: hidden; }}\n", viewport.x, viewport.y));
+    html.push_str(&stylesheet.css());
+    html.push_str("\n</style>\n</head>\n<body>\n<div id=\"kryon-root\" class=\"kryon-root\">\n");
+    html.push_str(&body);
+    html.push_str("</div>\n");
+    if options.hydrate {
+        html.push_str(&format!(
+            "<script type=\"application/json\" id=\"kryon-hydration\">{{\"root\":{},\"viewport\":[{},{}],\"nodes\":{}}}</script>\n",
+            root_id, viewport.x, viewport.y, nodes
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_element(
+    html: &mut String,
+    stylesheet: &mut StyleSheet,
+    nodes: &mut usize,
+    elements: &HashMap<ElementId, Element>,
+    layout: &LayoutResult,
+    style_computer: &StyleComputer,
+    element_id: ElementId,
+    parent_position: Vec2,
+    hydrate: bool,
+    depth: usize,
+) {
+    let (Some(element), Some(semantics)) = (elements.get(&element_id), describe(elements, element_id)) else {
+        return;
+    };
+    if !element.visible {
+        return;
+    }
+    *nodes += 1;
+
+    let position = layout.computed_positions.get(&element_id).copied().unwrap_or(element.position);
+    let size = layout.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+    let class = stylesheet.class_for(style_computer, element, element_id);
+    let indent = "  ".repeat(depth);
+
+    html.push_str(&format!("{}<{} class=\"{} {}\"", indent, semantics.tag, BASE_CLASS, class));
+    for (name, value) in &semantics.attributes {
+        html.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+    }
+    if hydrate {
+        html.push_str(&format!(" {}=\"{}\"", HYDRATION_ATTRIBUTE, element_id));
+    }
+    html.push_str(&format!(" style=\"{}\">", node_style(position, parent_position, size)));
+    if semantics.is_void() {
+        html.push('\n');
+        return;
+    }
+
+    if let Some(text) = &semantics.text {
+        html.push_str(&escape(text));
+    }
+    let children: Vec<_> = element.children.iter().filter(|id| elements.contains_key(id)).collect();
+    if children.is_empty() {
+        html.push_str(&format!("</{}>\n", semantics.tag));
+        return;
+    }
+    html.push('\n');
+    for &child_id in children {
+        write_element(html, stylesheet, nodes, elements, layout, style_computer, child_id, position, hydrate, depth + 1);
+    }
+    html.push_str(&format!("{}</{}>\n", indent, semantics.tag));
+}
+
+/// Escapes text and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::{ElementType, PropertyValue};
+
+    fn page() -> (HashMap<ElementId, Element>, LayoutResult) {
+        let mut elements = HashMap::new();
+        elements.insert(0, Element { element_type: ElementType::App, children: vec![1, 2], ..Default::default() });
+        let mut title = Element { element_type: ElementType::Text, parent: Some(0), text: "Fish & <Chips>".to_string(), ..Default::default() };
+        title.custom_properties.insert("heading".to_string(), PropertyValue::Int(1));
+        elements.insert(1, title);
+        elements.insert(2, Element { element_type: ElementType::Button, parent: Some(0), text: "Order".to_string(), ..Default::default() });
+
+        let positions = [(0, Vec2::ZERO), (1, Vec2::new(10.0, 10.0)), (2, Vec2::new(10.0, 60.0))];
+        let layout = LayoutResult {
+            computed_positions: positions.into_iter().collect(),
+            computed_sizes: [(0, Vec2::new(320.0, 200.0)), (1, Vec2::new(300.0, 40.0)), (2, Vec2::new(100.0, 30.0))].into_iter().collect(),
+        };
+        (elements, layout)
+    }
+
+    #[test]
+    fn renders_semantic_markup_with_a_stylesheet() {
+        let (elements, layout) = page();
+        let style_computer = StyleComputer::new(&elements, &HashMap::new());
+        let options = SsrOptions { viewport: Vec2::new(320.0, 200.0), title: "Menu".to_string(), hydrate: false };
+        let html = render_to_html(&elements, &layout, &style_computer, 0, &options);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Menu</title>"));
+        assert!(html.contains("<main class=\"kryon kryon-s"));
+        assert!(html.contains("Fish &amp; &lt;Chips&gt;</h1>"));
+        assert!(html.contains("<button class=\"kryon kryon-s"));
+        assert!(html.contains("style=\"left: 10px; top: 60px; width: 100px; height: 30px\">Order</button>"));
+        assert!(html.contains(".kryon {"));
+        assert!(!html.contains(HYDRATION_ATTRIBUTE));
+        assert!(!html.contains("kryon-hydration"));
+    }
+
+    #[test]
+    fn hydration_tags_nodes_with_element_ids() {
+        let (elements, layout) = page();
+        let style_computer = StyleComputer::new(&elements, &HashMap::new());
+        let options = SsrOptions { viewport: Vec2::new(320.0, 200.0), hydrate: true, ..Default::default() };
+        let html = render_to_html(&elements, &layout, &style_computer, 0, &options);
+
+        assert!(html.contains("data-kryon-node=\"2\""));
+        assert!(html.contains("{\"root\":0,\"viewport\":[320,200],\"nodes\":3}"));
+    }
+}
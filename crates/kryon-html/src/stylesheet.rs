@@ -0,0 +1,220 @@
+// crates/kryon-html/src/stylesheet.rs
+//! Compiles computed styles into a CSS stylesheet
+//!
+//! Instead of writing every style property inline on every DOM node, each
+//! element's computed style (including its hover, active and focus variants)
+//! becomes a class. Elements that look alike share one, so a list of fifty
+//! identical rows adds a single rule. Only the layout geometry stays inline.
+//!
+//! A `transition` property on an element, in CSS syntax with Kryon property
+//! names (`"background_color 200ms ease-out"`), is carried over to the class.
+
+use kryon_core::{ComputedStyle, CursorType, Element, ElementId, InteractionState, LineHeight, StyleComputer, TextAlignment};
+use glam::Vec4;
+use std::collections::HashMap;
+
+/// Class every node carries. It resets the user agent styles of headings,
+/// lists and buttons so the layout matches the other backends.
+pub const BASE_CLASS: &str = "kryon";
+
+const BASE_RULE: &str = ".kryon { position: absolute; box-sizing: border-box; margin: 0; padding: 0; list-style: none; border: none; background: none; font: inherit; }";
+
+const STATES: [(InteractionState, &str); 3] = [
+    (InteractionState::Hover, ":hover"),
+    (InteractionState::Active, ":active"),
+    (InteractionState::Focus, ":focus-visible"),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct StyleSheet {
+    /// Class name and rule text, keyed by the class's full declaration text
+    classes: HashMap<String, (String, String)>,
+    /// Keys in the order their classes were created, so output is stable
+    order: Vec<String>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The class for the element's current style, creating it the first
+    /// time that combination of declarations is seen.
+    pub fn class_for(&mut self, style_computer: &StyleComputer, element: &Element, element_id: ElementId) -> String {
+        let mut normal = declarations(&style_computer.compute(element_id));
+        if !element.font_family.is_empty() && element.font_family != "default" {
+            normal.push(("font-family", element.font_family.clone()));
+        }
+        let mut key = join(&normal);
+        let mut states = Vec::new();
+        for (state, selector) in STATES {
+            let changed: Vec<_> = declarations(&style_computer.compute_with_state(element_id, state))
+                .into_iter()
+                .filter(|declaration| !normal.contains(declaration))
+                .collect();
+            if !changed.is_empty() {
+                key.push_str(selector);
+                key.push_str(&join(&changed));
+                states.push((selector, changed));
+            }
+        }
+        let transition = element.custom_properties.get("transition")
+            .and_then(|v| v.as_string())
+            .map(css_transition);
+        if let Some(transition) = &transition {
+            key.push_str(transition);
+        }
+
+        if let Some((class, _)) = self.classes.get(&key) {
+            return class.clone();
+        }
+        let class = format!("kryon-s{}", self.order.len());
+        let mut rules = format!(".{} {{ {}", class, join(&normal));
+        if let Some(transition) = &transition {
+            rules.push_str(&format!(" transition: {};", transition));
+        }
+        rules.push_str(" }");
+        for (selector, changed) in states {
+            rules.push_str(&format!("\n.{}{} {{ {} }}", class, selector, join(&changed)));
+        }
+        self.order.push(key.clone());
+        self.classes.insert(key, (class.clone(), rules));
+        class
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// The complete stylesheet, base rule first.
+    pub fn css(&self) -> String {
+        let mut css = String::from(BASE_RULE);
+        for key in &self.order {
+            css.push('\n');
+            css.push_str(&self.classes[key].1);
+        }
+        css
+    }
+}
+
+fn join(declarations: &[(&'static str, String)]) -> String {
+    declarations.iter().map(|(name, value)| format!("{}: {};", name, value)).collect::<Vec<_>>().join(" ")
+}
+
+pub fn css_color(color: Vec4) -> String {
+    format!(
+        "rgba({}, {}, {}, {})",
+        (color.x * 255.0) as u8,
+        (color.y * 255.0) as u8,
+        (color.z * 255.0) as u8,
+        color.w
+    )
+}
+
+/// CSS declarations for a computed style. Values equal to the CSS initial
+/// value are left out to keep the rules short.
+pub fn declarations(style: &ComputedStyle) -> Vec<(&'static str, String)> {
+    let mut declarations = Vec::new();
+    if style.background_color.w > 0.0 {
+        declarations.push(("background-color", css_color(style.background_color)));
+    }
+    if style.border_width > 0.0 {
+        declarations.push(("border", format!("{}px solid {}", style.border_width, css_color(style.border_color))));
+    }
+    if style.border_radius > 0.0 {
+        declarations.push(("border-radius", format!("{}px", style.border_radius)));
+    }
+    declarations.push(("color", css_color(style.text_color)));
+    declarations.push(("font-size", format!("{}px", style.font_size)));
+    declarations.push(("font-weight", (style.font_weight as u16).to_string()));
+    if let Some(family) = &style.font_family {
+        declarations.push(("font-family", format!("\"{}\"", family)));
+    }
+    match style.line_height {
+        Some(LineHeight::Multiple(multiple)) => declarations.push(("line-height", multiple.to_string())),
+        Some(LineHeight::Pixels(pixels)) => declarations.push(("line-height", format!("{}px", pixels))),
+        None => {}
+    }
+    let text_align = match style.text_alignment {
+        TextAlignment::Start => None,
+        TextAlignment::Center => Some("center"),
+        TextAlignment::End => Some("end"),
+        TextAlignment::Justify => Some("justify"),
+    };
+    if let Some(text_align) = text_align {
+        declarations.push(("text-align", text_align.to_string()));
+    }
+    if style.opacity < 1.0 {
+        declarations.push(("opacity", style.opacity.to_string()));
+    }
+    if !style.visible {
+        declarations.push(("visibility", "hidden".to_string()));
+    }
+    let cursor = match style.cursor {
+        CursorType::Default => None,
+        CursorType::Pointer => Some("pointer"),
+        CursorType::Text => Some("text"),
+        CursorType::Move => Some("move"),
+        CursorType::NotAllowed => Some("not-allowed"),
+    };
+    if let Some(cursor) = cursor {
+        declarations.push(("cursor", cursor.to_string()));
+    }
+    declarations
+}
+
+/// Rewrites Kryon property names in a transition to their CSS names.
+fn css_transition(transition: &str) -> String {
+    transition.split(',')
+        .map(|part| {
+            let part = part.trim();
+            let (property, rest) = part.split_once(char::is_whitespace).unwrap_or((part, ""));
+            let property = match property {
+                "text_color" => "color".to_string(),
+                other => other.replace('_', "-"),
+            };
+            format!("{} {}", property, rest.trim()).trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::{ElementType, PropertyValue};
+
+    #[test]
+    fn elements_that_look_alike_share_a_class() {
+        let button = |text: &str| Element {
+            element_type: ElementType::Button,
+            text: text.to_string(),
+            background_color: Vec4::new(0.2, 0.4, 0.8, 1.0),
+            ..Default::default()
+        };
+        let mut elements: HashMap<u32, Element> = HashMap::new();
+        elements.insert(0, button("Save"));
+        elements.insert(1, button("Cancel"));
+        let mut fading = Element { background_color: Vec4::ONE, ..Default::default() };
+        fading.custom_properties.insert("transition".to_string(), PropertyValue::String("background_color 200ms ease-out".to_string()));
+        elements.insert(2, fading);
+        let style_computer = StyleComputer::new(&elements, &HashMap::new());
+
+        let mut stylesheet = StyleSheet::new();
+        let save = stylesheet.class_for(&style_computer, &elements[&0], 0);
+        let cancel = stylesheet.class_for(&style_computer, &elements[&1], 1);
+        let panel = stylesheet.class_for(&style_computer, &elements[&2], 2);
+        assert_eq!(save, cancel);
+        assert_ne!(save, panel);
+        assert_eq!(stylesheet.len(), 2);
+
+        let css = stylesheet.css();
+        assert!(css.starts_with(".kryon {"));
+        assert!(css.contains(&format!(".{}:hover {{ background-color:", save)));
+        assert!(css.contains("transition: background-color 200ms ease-out;"));
+    }
+}
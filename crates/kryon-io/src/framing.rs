@@ -0,0 +1,192 @@
+// crates/kryon-io/src/framing.rs
+//! Splitting a byte stream into frames and encoding frames for writing.
+
+use crate::{IoError, IoResult};
+
+/// Frames longer than this are dropped so a noisy line can't grow the buffer forever.
+pub const MAX_FRAME_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// Whatever bytes each read returned
+    Raw,
+    /// `\n`-terminated lines; a trailing `\r` is stripped
+    Line,
+    /// Frames terminated by a custom byte (e.g. `0x00` or `0x03`)
+    Delimiter(u8),
+    /// Big-endian `u16` length header followed by the payload
+    LengthPrefixed,
+}
+
+impl Framing {
+    /// Parses the script-facing names: `raw`, `line`, `length`, or a
+    /// delimiter byte such as `0x03`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "raw" | "binary" => Some(Framing::Raw),
+            "line" | "lines" => Some(Framing::Line),
+            "length" | "length_prefixed" => Some(Framing::LengthPrefixed),
+            other => {
+                let hex = other.strip_prefix("0x")?;
+                u8::from_str_radix(hex, 16).ok().map(Framing::Delimiter)
+            }
+        }
+    }
+
+    /// Encodes one outgoing frame.
+    pub fn encode(&self, payload: &[u8]) -> IoResult<Vec<u8>> {
+        let mut out = Vec::with_capacity(payload.len() + 2);
+        match self {
+            Framing::Raw => out.extend_from_slice(payload),
+            Framing::Line => {
+                out.extend_from_slice(payload);
+                if !payload.ends_with(b"\n") {
+                    out.push(b'\n');
+                }
+            }
+            Framing::Delimiter(byte) => {
+                out.extend_from_slice(payload);
+                out.push(*byte);
+            }
+            Framing::LengthPrefixed => {
+                let len = u16::try_from(payload.len()).map_err(|_| IoError::FrameTooLarge(payload.len()))?;
+                out.extend_from_slice(&len.to_be_bytes());
+                out.extend_from_slice(payload);
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Accumulates received bytes and yields complete frames.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    framing: Framing,
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new(framing: Framing) -> Self {
+        Self { framing, buffer: Vec::new() }
+    }
+
+    pub fn framing(&self) -> Framing {
+        self.framing
+    }
+
+    /// Bytes received but not yet part of a complete frame.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<Vec<u8>> {
+        if self.framing == Framing::Raw {
+            return if bytes.is_empty() { Vec::new() } else { vec![bytes.to_vec()] };
+        }
+
+        self.buffer.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+        loop {
+            let frame = match self.framing {
+                Framing::Raw => unreachable!(),
+                Framing::Line => self.take_until(b'\n').map(|mut line| {
+                    if line.ends_with(b"\r") {
+                        line.pop();
+                    }
+                    line
+                }),
+                Framing::Delimiter(byte) => self.take_until(byte),
+                Framing::LengthPrefixed => self.take_length_prefixed(),
+            };
+            match frame {
+                Some(frame) => frames.push(frame),
+                None => break,
+            }
+        }
+
+        if self.buffer.len() > MAX_FRAME_SIZE {
+            tracing::warn!("Dropping {} bytes without a frame boundary", self.buffer.len());
+            self.buffer.clear();
+        }
+        frames
+    }
+
+    fn take_until(&mut self, delimiter: u8) -> Option<Vec<u8>> {
+        let end = self.buffer.iter().position(|&b| b == delimiter)?;
+        let mut frame: Vec<u8> = self.buffer.drain(..=end).collect();
+        frame.pop();
+        Some(frame)
+    }
+
+    fn take_length_prefixed(&mut self) -> Option<Vec<u8>> {
+        if self.buffer.len() < 2 {
+            return None;
+        }
+        let len = u16::from_be_bytes([self.buffer[0], self.buffer[1]]) as usize;
+        if self.buffer.len() < 2 + len {
+            return None;
+        }
+        let frame = self.buffer[2..2 + len].to_vec();
+        self.buffer.drain(..2 + len);
+        Some(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_split_across_reads() {
+        let mut decoder = FrameDecoder::new(Framing::Line);
+        assert!(decoder.push(b"TEMP=2").is_empty());
+        assert_eq!(decoder.push(b"1.5\r\nHUM=40\nPRE"), vec![b"TEMP=21.5".to_vec(), b"HUM=40".to_vec()]);
+        assert_eq!(decoder.pending(), 3);
+    }
+
+    #[test]
+    fn custom_delimiter_frames() {
+        let mut decoder = FrameDecoder::new(Framing::Delimiter(0x03));
+        assert_eq!(decoder.push(&[1, 2, 3, 4, 3]), vec![vec![1, 2], vec![4]]);
+    }
+
+    #[test]
+    fn length_prefixed_round_trip() {
+        let framing = Framing::LengthPrefixed;
+        let mut wire = framing.encode(&[0xAA, 0xBB, 0xCC]).unwrap();
+        wire.extend(framing.encode(&[]).unwrap());
+
+        let mut decoder = FrameDecoder::new(framing);
+        assert!(decoder.push(&wire[..3]).is_empty());
+        assert_eq!(decoder.push(&wire[3..]), vec![vec![0xAA, 0xBB, 0xCC], vec![]]);
+        assert!(framing.encode(&vec![0; 70_000]).is_err());
+    }
+
+    #[test]
+    fn raw_passes_reads_through() {
+        let mut decoder = FrameDecoder::new(Framing::Raw);
+        assert_eq!(decoder.push(&[9, 8]), vec![vec![9, 8]]);
+        assert!(decoder.push(&[]).is_empty());
+    }
+
+    #[test]
+    fn oversized_garbage_is_dropped() {
+        let mut decoder = FrameDecoder::new(Framing::Line);
+        decoder.push(&vec![b'x'; MAX_FRAME_SIZE + 1]);
+        assert_eq!(decoder.pending(), 0);
+    }
+
+    #[test]
+    fn parses_framing_names() {
+        assert_eq!(Framing::parse("line"), Some(Framing::Line));
+        assert_eq!(Framing::parse("RAW"), Some(Framing::Raw));
+        assert_eq!(Framing::parse("length"), Some(Framing::LengthPrefixed));
+        assert_eq!(Framing::parse("0x03"), Some(Framing::Delimiter(3)));
+        assert_eq!(Framing::parse("slip"), None);
+        assert_eq!(Framing::Line.encode(b"AT").unwrap(), b"AT\n");
+    }
+}
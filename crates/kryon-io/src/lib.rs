@@ -0,0 +1,26 @@
+// crates/kryon-io/src/lib.rs
+//! Device IO for embedded deployments: serial ports with framing helpers and
+//! automatic reconnection. Connections run on worker threads and report
+//! [`SerialEvent`]s through a channel that the runtime polls each frame.
+pub mod framing;
+pub mod serial;
+
+pub use framing::*;
+pub use serial::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IoError {
+    #[error("Serial port error: {0}")]
+    Serial(#[from] serialport::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Unknown connection: {0}")]
+    UnknownConnection(u64),
+
+    #[error("Frame too large: {0} bytes")]
+    FrameTooLarge(usize),
+}
+
+pub type IoResult<T> = Result<T, IoError>;
@@ -0,0 +1,271 @@
+// crates/kryon-io/src/serial.rs
+//! Serial port connections on worker threads.
+//!
+//! Each connection owns a thread that opens the port, decodes incoming bytes
+//! with its [`Framing`] and forwards frames as [`SerialEvent`]s. If the port
+//! disappears (cable pulled, device reset) the thread keeps retrying with
+//! exponential backoff until the connection is closed.
+
+use crate::{FrameDecoder, Framing, IoError, IoResult};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+
+const READ_TIMEOUT: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerialConfig {
+    pub path: String,
+    pub baud_rate: u32,
+    pub data_bits: serialport::DataBits,
+    pub parity: serialport::Parity,
+    pub stop_bits: serialport::StopBits,
+    pub framing: Framing,
+    /// Keep reopening the port after errors
+    pub reconnect: bool,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl SerialConfig {
+    pub fn new(path: impl Into<String>, baud_rate: u32) -> Self {
+        Self {
+            path: path.into(),
+            baud_rate,
+            data_bits: serialport::DataBits::Eight,
+            parity: serialport::Parity::None,
+            stop_bits: serialport::StopBits::One,
+            framing: Framing::Line,
+            reconnect: true,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    pub fn with_reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialEvent {
+    /// The port was opened (again, after a reconnect)
+    Opened { id: u64 },
+    Frame { id: u64, data: Vec<u8> },
+    /// A recoverable error; with reconnect enabled the port is retried
+    Error { id: u64, message: String },
+    /// The connection ended and will not reconnect
+    Closed { id: u64 },
+}
+
+enum Command {
+    Write(Vec<u8>),
+    Close,
+}
+
+/// Lists serial ports present on the system.
+pub fn available_ports() -> Vec<String> {
+    serialport::available_ports()
+        .map(|ports| ports.into_iter().map(|port| port.port_name).collect())
+        .unwrap_or_default()
+}
+
+/// Owns all open serial connections.
+pub struct SerialManager {
+    sender: Sender<SerialEvent>,
+    receiver: Receiver<SerialEvent>,
+    connections: HashMap<u64, Sender<Command>>,
+}
+
+impl SerialManager {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver, connections: HashMap::new() }
+    }
+
+    /// Starts a connection under the caller-chosen `id`.
+    pub fn open(&mut self, id: u64, config: SerialConfig) {
+        if let Some(existing) = self.connections.remove(&id) {
+            let _ = existing.send(Command::Close);
+        }
+        let (commands, command_receiver) = channel();
+        let events = self.sender.clone();
+        std::thread::spawn(move || run_connection(id, config, command_receiver, events));
+        self.connections.insert(id, commands);
+    }
+
+    /// Queues a frame; it is encoded with the connection's framing.
+    pub fn write(&self, id: u64, payload: Vec<u8>) -> IoResult<()> {
+        let commands = self.connections.get(&id).ok_or(IoError::UnknownConnection(id))?;
+        commands.send(Command::Write(payload)).map_err(|_| IoError::UnknownConnection(id))
+    }
+
+    pub fn close(&mut self, id: u64) {
+        if let Some(commands) = self.connections.remove(&id) {
+            let _ = commands.send(Command::Close);
+        }
+    }
+
+    pub fn is_open(&self, id: u64) -> bool {
+        self.connections.contains_key(&id)
+    }
+
+    /// Events since the last poll.
+    pub fn poll(&mut self) -> Vec<SerialEvent> {
+        let events: Vec<SerialEvent> = self.receiver.try_iter().collect();
+        for event in &events {
+            if let SerialEvent::Closed { id } = event {
+                self.connections.remove(id);
+            }
+        }
+        events
+    }
+}
+
+impl Default for SerialManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SerialManager {
+    fn drop(&mut self) {
+        for commands in self.connections.values() {
+            let _ = commands.send(Command::Close);
+        }
+    }
+}
+
+fn run_connection(id: u64, config: SerialConfig, commands: Receiver<Command>, events: Sender<SerialEvent>) {
+    let mut backoff = config.initial_backoff;
+    let mut decoder = FrameDecoder::new(config.framing);
+
+    loop {
+        let port = serialport::new(&config.path, config.baud_rate)
+            .data_bits(config.data_bits)
+            .parity(config.parity)
+            .stop_bits(config.stop_bits)
+            .timeout(READ_TIMEOUT)
+            .open();
+
+        let reason = match port {
+            Ok(mut port) => {
+                tracing::info!("Serial port {} opened", config.path);
+                backoff = config.initial_backoff;
+                decoder.reset();
+                let _ = events.send(SerialEvent::Opened { id });
+                match pump(id, port.as_mut(), &config, &mut decoder, &commands, &events) {
+                    Ok(()) => break, // closed by the app
+                    Err(e) => e.to_string(),
+                }
+            }
+            Err(e) => IoError::from(e).to_string(),
+        };
+
+        let _ = events.send(SerialEvent::Error { id, message: reason });
+        if !config.reconnect {
+            break;
+        }
+
+        // Wait before retrying, but stay responsive to close requests
+        tracing::debug!("Reconnecting to {} in {:?}", config.path, backoff);
+        match commands.recv_timeout(backoff) {
+            Ok(Command::Close) | Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            Ok(Command::Write(_)) => {
+                let _ = events.send(SerialEvent::Error { id, message: "write dropped: port not open".to_string() });
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+        }
+        backoff = (backoff * 2).min(config.max_backoff);
+    }
+
+    let _ = events.send(SerialEvent::Closed { id });
+}
+
+/// Reads and writes until the app closes the connection (`Ok`) or the port fails.
+fn pump(
+    id: u64,
+    port: &mut dyn serialport::SerialPort,
+    config: &SerialConfig,
+    decoder: &mut FrameDecoder,
+    commands: &Receiver<Command>,
+    events: &Sender<SerialEvent>,
+) -> IoResult<()> {
+    let mut buf = [0u8; 1024];
+    loop {
+        loop {
+            match commands.try_recv() {
+                Ok(Command::Write(payload)) => {
+                    match config.framing.encode(&payload) {
+                        Ok(bytes) => port.write_all(&bytes)?,
+                        Err(e) => {
+                            let _ = events.send(SerialEvent::Error { id, message: e.to_string() });
+                        }
+                    }
+                }
+                Ok(Command::Close) | Err(TryRecvError::Disconnected) => return Ok(()),
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+
+        match port.read(&mut buf) {
+            Ok(0) => {}
+            Ok(n) => {
+                for data in decoder.push(&buf[..n]) {
+                    if events.send(SerialEvent::Frame { id, data }).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::TimedOut || e.kind() == ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn wait_for(manager: &mut SerialManager, mut done: impl FnMut(&[SerialEvent]) -> bool) -> Vec<SerialEvent> {
+        let mut events = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline && !done(&events) {
+            events.extend(manager.poll());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        events
+    }
+
+    #[test]
+    fn missing_port_reports_error_then_closes_without_reconnect() {
+        let mut manager = SerialManager::new();
+        let config = SerialConfig::new("/dev/kryon-test-missing", 9600).with_reconnect(false);
+        manager.open(1, config);
+
+        let events = wait_for(&mut manager, |events| events.contains(&SerialEvent::Closed { id: 1 }));
+        assert!(matches!(events.first(), Some(SerialEvent::Error { id: 1, .. })));
+        assert_eq!(events.last(), Some(&SerialEvent::Closed { id: 1 }));
+        assert!(!manager.is_open(1));
+    }
+
+    #[test]
+    fn close_stops_reconnect_loop() {
+        let mut manager = SerialManager::new();
+        manager.open(2, SerialConfig::new("/dev/kryon-test-missing", 9600));
+        wait_for(&mut manager, |events| !events.is_empty());
+
+        manager.close(2);
+        assert!(manager.write(2, b"x".to_vec()).is_err());
+        let events = wait_for(&mut manager, |events| events.contains(&SerialEvent::Closed { id: 2 }));
+        assert!(events.contains(&SerialEvent::Closed { id: 2 }));
+    }
+}
@@ -0,0 +1,316 @@
+// crates/kryon-layout/src/intrinsic.rs
+//! Content-based sizes of leaf elements, which Taffy asks for through its
+//! measure function when a box sizes to its content: the text of Text,
+//! Link and Button elements and the natural size of images.
+//!
+//! These sizes back the `min-content`, `max-content` and `fit-content`
+//! width and height keywords, so a button can be as wide as its label
+//! without a hard-coded width.
+//!
+//! Text is measured by a [`TextMeasurer`]. Backends that shape text supply
+//! one with their font metrics; otherwise widths are estimated from an
+//! average glyph width.
+
+use crate::text_wrap::{wrap_text, WrappedText, ELLIPSIS};
+use glam::Vec2;
+use kryon_core::{Element, ElementId, ElementType};
+use std::collections::HashMap;
+use taffy::prelude::*;
+
+/// Width of an average glyph relative to the font size, as the renderer's
+/// simple text shaper estimates it
+const AVERAGE_GLYPH_WIDTH: f32 = 0.6;
+const LINE_HEIGHT: f32 = 1.2;
+/// First baseline below the top of a text box, relative to the font size,
+/// when the backend doesn't report one: a typical ascent, as text is drawn
+/// from the top of a box as tall as its font size
+pub const DEFAULT_TEXT_BASELINE: f32 = 0.8;
+
+/// Measures text for layout with the metrics of the fonts it's drawn in.
+/// A backend hands one to the layout engine through
+/// `LayoutEngine::set_text_measurer`.
+pub trait TextMeasurer: Send {
+    /// Width of `text` on one line, in `font_family` or the default font
+    fn measure(&mut self, text: &str, font_size: f32, font_family: Option<&str>) -> f32;
+
+    /// Distance from the top of one line to the top of the next
+    fn line_height(&mut self, font_size: f32) -> f32 {
+        font_size * LINE_HEIGHT
+    }
+}
+
+/// Widths from an average glyph width, for backends that don't measure
+/// their text
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EstimatedTextMeasurer;
+
+impl TextMeasurer for EstimatedTextMeasurer {
+    fn measure(&mut self, text: &str, font_size: f32, _font_family: Option<&str>) -> f32 {
+        text.chars().count() as f32 * font_size * AVERAGE_GLYPH_WIDTH
+    }
+}
+
+/// Size of a text run, laid out in lines no wider than its width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMeasurement {
+    /// Width of the longest word; the text can't be narrower without overflowing
+    pub min_content_width: f32,
+    /// Width of the longest line when only `\n` breaks lines
+    pub max_content_width: f32,
+    pub line_height: f32,
+    font_size: f32,
+    font_family: Option<String>,
+}
+
+impl TextMeasurement {
+    /// Estimated from an average glyph width
+    pub fn new(text: &str, font_size: f32) -> Self {
+        Self::measure(text, font_size, None, &mut EstimatedTextMeasurer)
+    }
+
+    pub fn measure(text: &str, font_size: f32, font_family: Option<&str>, measurer: &mut dyn TextMeasurer) -> Self {
+        let mut width = |piece: &str| measurer.measure(piece, font_size, font_family);
+        let min_content_width = text.split_whitespace().map(&mut width).fold(0.0, f32::max);
+        let max_content_width = text.split('\n').map(|line| width(line.trim_end_matches('\r'))).fold(0.0, f32::max);
+        Self {
+            min_content_width,
+            max_content_width,
+            line_height: measurer.line_height(font_size),
+            font_size,
+            font_family: font_family.map(str::to_string),
+        }
+    }
+
+    /// The text's width and height when given `available` width: the width
+    /// wraps between the min- and max-content widths.
+    pub fn size(&self, text: &str, available: AvailableSpace, measurer: &mut dyn TextMeasurer) -> Vec2 {
+        let width = match available {
+            AvailableSpace::MinContent => self.min_content_width,
+            AvailableSpace::MaxContent => self.max_content_width,
+            AvailableSpace::Definite(width) => width.min(self.max_content_width).max(self.min_content_width),
+        };
+        Vec2::new(width, self.lines(text, width, measurer) as f32 * self.line_height)
+    }
+
+    /// The text's lines within `width`, as the renderers break them
+    pub fn wrap(&self, text: &str, width: Option<f32>, measurer: &mut dyn TextMeasurer) -> WrappedText {
+        let font_family = self.font_family.as_deref();
+        wrap_text(text, width, None, self.line_height, ELLIPSIS, |piece| measurer.measure(piece, self.font_size, font_family))
+    }
+
+    /// Lines the text breaks into at word boundaries within `width`
+    fn lines(&self, text: &str, width: f32, measurer: &mut dyn TextMeasurer) -> usize {
+        // Rounding must not push a line that fits exactly onto the next
+        self.wrap(text, Some(width + 0.01), measurer).lines.len()
+    }
+}
+
+/// Whether the element lays out text from its own `text`, rather than
+/// from children
+pub(crate) fn is_text_leaf(element: &Element) -> bool {
+    matches!(element.element_type, ElementType::Text | ElementType::Link | ElementType::Button)
+        && !element.text.is_empty()
+        && element.children.is_empty()
+}
+
+/// Measures a node of a Taffy tree whose node contexts are element ids.
+pub(crate) fn measure_node(
+    elements: &HashMap<ElementId, Element>,
+    intrinsic_sizes: &HashMap<ElementId, Vec2>,
+    measurer: &mut dyn TextMeasurer,
+    element_id: Option<&mut ElementId>,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+) -> Size<f32> {
+    match element_id.and_then(|element_id| elements.get(element_id).map(|element| (*element_id, element))) {
+        Some((element_id, element)) => measure_leaf(element, intrinsic_sizes.get(&element_id).copied(), measurer, known_dimensions, available_space),
+        None => Size::ZERO,
+    }
+}
+
+/// Taffy measure function for a leaf element. `image_size` is the natural
+/// size of an Image element's bitmap, when it is known.
+pub(crate) fn measure_leaf(
+    element: &Element,
+    image_size: Option<Vec2>,
+    measurer: &mut dyn TextMeasurer,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+) -> Size<f32> {
+    let content = match element.element_type {
+        ElementType::Text | ElementType::Link | ElementType::Button if !element.text.is_empty() => {
+            let available = known_dimensions.width.map_or(available_space.width, AvailableSpace::Definite);
+            let font_family = Some(element.font_family.as_str()).filter(|family| !family.is_empty());
+            TextMeasurement::measure(&element.text, element.font_size, font_family, measurer).size(&element.text, available, measurer)
+        }
+        ElementType::Image => match image_size {
+            // Scale to a known width or height, keeping the aspect ratio
+            Some(natural) if natural.x > 0.0 && natural.y > 0.0 => match (known_dimensions.width, known_dimensions.height) {
+                (Some(width), None) => Vec2::new(width, width * natural.y / natural.x),
+                (None, Some(height)) => Vec2::new(height * natural.x / natural.y, height),
+                _ => natural,
+            },
+            _ => Vec2::ZERO,
+        },
+        _ => Vec2::ZERO,
+    };
+    Size {
+        width: known_dimensions.width.unwrap_or(content.x),
+        height: known_dimensions.height.unwrap_or(content.y),
+    }
+}
+
+/// Sets a width or height from a sizing keyword, given the content's
+/// min- and max-content sizes along that axis.
+pub(crate) fn apply_keyword(
+    keyword: &kryon_core::LayoutDimension,
+    (size, min_size, max_size): (&mut Dimension, &mut Dimension, &mut Dimension),
+    min_content: f32,
+    max_content: f32,
+) {
+    match keyword {
+        kryon_core::LayoutDimension::MinContent => *size = Dimension::Length(min_content),
+        kryon_core::LayoutDimension::MaxContent => *size = Dimension::Length(max_content),
+        kryon_core::LayoutDimension::FitContent => {
+            // Left auto, the box takes the space it's given, clamped to its content
+            *size = Dimension::Auto;
+            if *min_size == Dimension::Auto {
+                *min_size = Dimension::Length(min_content);
+            }
+            if *max_size == Dimension::Auto {
+                *max_size = Dimension::Length(max_content.max(min_content));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_wraps_between_its_content_widths() {
+        let text = "Save changes";
+        let measurement = TextMeasurement::new(text, 10.0);
+        assert_eq!(measurement.min_content_width, 42.0);
+        assert_eq!(measurement.max_content_width, 72.0);
+
+        let estimate = &mut EstimatedTextMeasurer;
+        assert_eq!(measurement.size(text, AvailableSpace::MaxContent, estimate), Vec2::new(72.0, 12.0));
+        assert_eq!(measurement.size(text, AvailableSpace::MinContent, estimate), Vec2::new(42.0, 24.0));
+        assert_eq!(measurement.size(text, AvailableSpace::Definite(500.0), estimate), Vec2::new(72.0, 12.0));
+        assert_eq!(measurement.size(text, AvailableSpace::Definite(10.0), estimate), Vec2::new(42.0, 24.0));
+    }
+
+    #[test]
+    fn images_keep_their_aspect_ratio() {
+        let image = Element { element_type: ElementType::Image, ..Default::default() };
+        let natural = Some(Vec2::new(200.0, 100.0));
+        let unknown = Size { width: None, height: None };
+        let space = Size { width: AvailableSpace::MaxContent, height: AvailableSpace::MaxContent };
+
+        assert_eq!(measure_leaf(&image, natural, &mut EstimatedTextMeasurer, unknown, space), Size { width: 200.0, height: 100.0 });
+        assert_eq!(measure_leaf(&image, natural, &mut EstimatedTextMeasurer, Size { width: Some(50.0), height: None }, space), Size { width: 50.0, height: 25.0 });
+        assert_eq!(measure_leaf(&image, None, &mut EstimatedTextMeasurer, unknown, space), Size { width: 0.0, height: 0.0 });
+    }
+
+    #[test]
+    fn sizing_keywords_follow_the_label() {
+        use crate::{LayoutEngine, TaffyLayoutEngine};
+        use kryon_core::LayoutDimension;
+
+        let sized = |element_type, text: &str, width| Element {
+            element_type,
+            text: text.into(),
+            font_size: 10.0,
+            parent: Some(0),
+            layout_size: kryon_core::LayoutSize::new(width, LayoutDimension::Auto),
+            ..Default::default()
+        };
+        let row = Element { element_type: ElementType::Container, children: vec![1, 2, 3], ..Default::default() };
+        let elements: HashMap<ElementId, Element> = [
+            (0, row),
+            (1, sized(ElementType::Button, "Save all changes now", LayoutDimension::FitContent)),
+            (2, sized(ElementType::Text, "Hello world", LayoutDimension::MaxContent)),
+            (3, sized(ElementType::Text, "Hello world", LayoutDimension::MinContent)),
+        ].into_iter().collect();
+
+        let layout = TaffyLayoutEngine::new().compute_layout(&elements, 0, Vec2::new(800.0, 600.0));
+        assert_eq!(layout.computed_sizes[&1].x, 120.0);
+        assert_eq!(layout.computed_sizes[&2].x, 66.0);
+        assert_eq!(layout.computed_sizes[&3].x, 30.0);
+    }
+
+    #[test]
+    fn a_backend_measurer_replaces_the_estimate() {
+        use crate::{LayoutEngine, TaffyLayoutEngine};
+        use kryon_core::LayoutDimension;
+
+        /// A quarter of the font size per character, twice that in "Mono"
+        struct Fixed;
+        impl TextMeasurer for Fixed {
+            fn measure(&mut self, text: &str, font_size: f32, font_family: Option<&str>) -> f32 {
+                let scale = if font_family == Some("Mono") { 2.0 } else { 1.0 };
+                text.chars().count() as f32 * font_size * 0.25 * scale
+            }
+            fn line_height(&mut self, font_size: f32) -> f32 {
+                font_size * 1.5
+            }
+        }
+
+        let label = |text: &str, font_family: &str| Element {
+            element_type: ElementType::Text,
+            text: text.into(),
+            font_size: 20.0,
+            font_family: font_family.into(),
+            parent: Some(0),
+            layout_size: kryon_core::LayoutSize::new(LayoutDimension::MaxContent, LayoutDimension::Auto),
+            ..Default::default()
+        };
+        let mut column = Element { element_type: ElementType::Container, children: vec![1, 2], ..Default::default() };
+        column.custom_properties.insert("align_items".into(), kryon_core::PropertyValue::String("start".into()));
+        let elements: HashMap<ElementId, Element> = [
+            (0, column),
+            (1, label("Hello world", "")),
+            (2, label("Hello", "Mono")),
+        ].into_iter().collect();
+
+        let mut engine = TaffyLayoutEngine::new();
+        engine.set_text_measurer(Box::new(Fixed));
+        let layout = engine.compute_layout(&elements, 0, Vec2::new(800.0, 600.0));
+        assert_eq!(layout.computed_sizes[&1].x, 55.0);
+        assert_eq!(layout.computed_sizes[&2].x, 50.0);
+
+        let measurement = TextMeasurement::measure("Hello world", 20.0, None, &mut Fixed);
+        assert_eq!(measurement.size("Hello world", AvailableSpace::Definite(30.0), &mut Fixed), Vec2::new(30.0, 60.0));
+    }
+
+    #[test]
+    fn images_size_from_their_bitmap_once_decoded() {
+        use crate::{LayoutEngine, TaffyLayoutEngine};
+
+        let image = |size: Vec2| Element { element_type: ElementType::Image, parent: Some(0), size, ..Default::default() };
+        let mut banner = Element { element_type: ElementType::Container, parent: Some(0), size: Vec2::new(320.0, 0.0), ..Default::default() };
+        banner.custom_properties.insert("aspect_ratio".into(), kryon_core::PropertyValue::String("16/9".into()));
+        let mut row = Element { element_type: ElementType::Container, children: vec![1, 2, 3], ..Default::default() };
+        row.custom_properties.insert("align_items".into(), kryon_core::PropertyValue::String("start".into()));
+        let elements: HashMap<ElementId, Element> = [
+            (0, row),
+            (1, image(Vec2::ZERO)),
+            (2, image(Vec2::new(100.0, 0.0))),
+            (3, banner),
+        ].into_iter().collect();
+
+        let mut engine = TaffyLayoutEngine::new();
+        let viewport = Vec2::new(800.0, 600.0);
+        assert_eq!(engine.compute_layout(&elements, 0, viewport).computed_sizes[&1], Vec2::ZERO);
+
+        engine.set_intrinsic_size(1, Vec2::new(200.0, 100.0));
+        engine.set_intrinsic_size(2, Vec2::new(200.0, 100.0));
+        let layout = engine.compute_layout(&elements, 0, viewport);
+        assert_eq!(layout.computed_sizes[&1], Vec2::new(200.0, 100.0));
+        assert_eq!(layout.computed_sizes[&2], Vec2::new(100.0, 50.0));
+        assert_eq!(layout.computed_sizes[&3], Vec2::new(320.0, 180.0));
+    }
+}
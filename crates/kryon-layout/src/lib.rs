@@ -7,10 +7,21 @@ use std::collections::HashMap;
 pub mod flexbox;
 pub mod constraints;
 pub mod taffy_engine;
+pub mod snapshot;
+pub mod safe_area;
+pub mod intrinsic;
+pub mod text_wrap;
 
 pub use flexbox::{LayoutFlags, LayoutDirection, LayoutAlignment};
 pub use constraints::*;
 pub use taffy_engine::TaffyLayoutEngine;
+pub use safe_area::SafeAreaInsets;
+pub use intrinsic::{EstimatedTextMeasurer, TextMeasurement, TextMeasurer};
+pub use text_wrap::{wrap_text, TextLine, WrappedText};
+
+/// Width of the scrollbar drawn inside a scroll container, which its
+/// content is laid out beside
+pub const SCROLLBAR_WIDTH: f32 = 15.0;
 
 #[derive(Debug, Clone)]
 pub struct LayoutResult {
@@ -25,6 +36,33 @@ pub trait LayoutEngine {
         root_id: ElementId,
         viewport_size: Vec2,
     ) -> LayoutResult;
+    
+    /// Insets from the screen edges that content should avoid, for `safe_area`
+    /// and `env(safe-area-inset-*)`. Engines that don't support them ignore it.
+    fn set_safe_area(&mut self, _insets: SafeAreaInsets) {}
+    
+    /// The natural size of an Image element's bitmap, once it's decoded.
+    /// The element takes it when it sets no width or height.
+    fn set_intrinsic_size(&mut self, _element_id: ElementId, _size: Vec2) {}
+    
+    /// How far below the top of a text box its first baseline sits, as a
+    /// fraction of the font size, for `align_items: baseline`. Engines
+    /// without baseline alignment ignore it.
+    fn set_text_baseline(&mut self, _ratio: f32) {}
+    
+    /// Measures the text of content-sized elements, in place of the
+    /// engine's estimate. Engines that don't size text ignore it.
+    fn set_text_measurer(&mut self, _measurer: Box<dyn TextMeasurer>) {}
+    
+    /// Elements whose contents are left out of layout, for
+    /// `content_visibility: auto`, with the size each keeps instead.
+    /// Their descendants get no position or size.
+    fn set_skipped_subtrees(&mut self, _skipped: &HashMap<ElementId, Vec2>) {}
+    
+    /// Nodes kept between layouts and an estimate of their size in bytes
+    fn memory_usage(&self) -> (usize, usize) {
+        (0, 0)
+    }
 }
 
 // Legacy FlexboxLayoutEngine removed - use TaffyLayoutEngine instead
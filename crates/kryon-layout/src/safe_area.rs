@@ -0,0 +1,109 @@
+// crates/kryon-layout/src/safe_area.rs
+//! Safe-area insets: the parts of the screen hidden by notches, rounded
+//! display corners or a kiosk bezel.
+//!
+//! Padding properties can refer to them with the CSS names, e.g.
+//! `padding_top: "env(safe-area-inset-top)"`, and an App element with
+//! `safe_area: true` is inset on every side automatically. `safe_area` also
+//! takes a list of sides such as `"top bottom"`.
+
+use kryon_core::{Element, PropertyValue};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SafeAreaInsets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl SafeAreaInsets {
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self { top, right, bottom, left }
+    }
+
+    /// Parses CSS-style shorthand: one value for all sides, `vertical
+    /// horizontal`, or `top right bottom left`, separated by commas or spaces.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let values = spec
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.trim_end_matches("px").parse::<f32>().map_err(|_| format!("Invalid inset '{}'", part)))
+            .collect::<Result<Vec<_>, _>>()?;
+        match values[..] {
+            [all] => Ok(Self::new(all, all, all, all)),
+            [vertical, horizontal] => Ok(Self::new(vertical, horizontal, vertical, horizontal)),
+            [top, right, bottom, left] => Ok(Self::new(top, right, bottom, left)),
+            _ => Err(format!("Expected 1, 2 or 4 insets, got '{}'", spec)),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Resolves `env(safe-area-inset-<side>)`, returning `None` for anything else.
+    pub fn resolve(&self, value: &str) -> Option<f32> {
+        let name = value.trim().strip_prefix("env(")?.strip_suffix(')')?.trim();
+        match name.strip_prefix("safe-area-inset-")? {
+            "top" => Some(self.top),
+            "right" => Some(self.right),
+            "bottom" => Some(self.bottom),
+            "left" => Some(self.left),
+            _ => None,
+        }
+    }
+
+    /// The insets an element's `safe_area` property asks for: all of them for
+    /// `true`, only the listed sides for a list, none if it's unset.
+    pub fn for_element(&self, element: &Element) -> Self {
+        match element.custom_properties.get("safe_area") {
+            Some(PropertyValue::Bool(true)) => *self,
+            Some(PropertyValue::String(sides)) if sides == "true" => *self,
+            Some(PropertyValue::String(sides)) => {
+                let has = |side: &str| sides.split(|c: char| c == ',' || c.is_whitespace()).any(|s| s == side);
+                Self {
+                    top: if has("top") { self.top } else { 0.0 },
+                    right: if has("right") { self.right } else { 0.0 },
+                    bottom: if has("bottom") { self.bottom } else { 0.0 },
+                    left: if has("left") { self.left } else { 0.0 },
+                }
+            }
+            _ => Self::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shorthand() {
+        assert_eq!(SafeAreaInsets::parse("44"), Ok(SafeAreaInsets::new(44.0, 44.0, 44.0, 44.0)));
+        assert_eq!(SafeAreaInsets::parse("44px 0"), Ok(SafeAreaInsets::new(44.0, 0.0, 44.0, 0.0)));
+        assert_eq!(SafeAreaInsets::parse("47,0,34,0"), Ok(SafeAreaInsets::new(47.0, 0.0, 34.0, 0.0)));
+        assert!(SafeAreaInsets::parse("1 2 3").is_err());
+        assert!(SafeAreaInsets::parse("top").is_err());
+    }
+
+    #[test]
+    fn resolves_env_names() {
+        let insets = SafeAreaInsets::new(47.0, 0.0, 34.0, 8.0);
+        assert_eq!(insets.resolve("env(safe-area-inset-top)"), Some(47.0));
+        assert_eq!(insets.resolve(" env( safe-area-inset-left ) "), Some(8.0));
+        assert_eq!(insets.resolve("env(safe-area-inset-middle)"), None);
+        assert_eq!(insets.resolve("12px"), None);
+    }
+
+    #[test]
+    fn element_selects_sides() {
+        let insets = SafeAreaInsets::new(47.0, 4.0, 34.0, 8.0);
+        let mut app = Element::default();
+        assert!(insets.for_element(&app).is_zero());
+        app.custom_properties.insert("safe_area".to_string(), PropertyValue::Bool(true));
+        assert_eq!(insets.for_element(&app), insets);
+        app.custom_properties.insert("safe_area".to_string(), PropertyValue::String("top bottom".to_string()));
+        assert_eq!(insets.for_element(&app), SafeAreaInsets::new(47.0, 0.0, 34.0, 0.0));
+    }
+}
@@ -0,0 +1,159 @@
+// crates/kryon-layout/src/snapshot.rs
+//! Stable text form of a `LayoutResult`, for golden layout tests.
+//!
+//! One line per laid-out element, sorted by element id:
+//!
+//! ```text
+//! 2 toolbar Container x=0.00 y=0.00 w=800.00 h=64.00
+//! ```
+//!
+//! Lines starting with `#` are comments. Goldens are compared with
+//! [`diff`], which allows a small tolerance on every coordinate so that
+//! float noise from the layout engine doesn't fail a test.
+
+use crate::LayoutResult;
+use glam::Vec2;
+use kryon_core::{Element, ElementId};
+use std::collections::{BTreeMap, HashMap};
+
+/// Largest difference, in pixels, that [`diff`] treats as equal.
+pub const DEFAULT_TOLERANCE: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotEntry {
+    pub element_id: ElementId,
+    pub name: String,
+    pub element_type: String,
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+pub fn serialize(elements: &HashMap<ElementId, Element>, layout: &LayoutResult) -> String {
+    let mut ids: Vec<&ElementId> = layout.computed_positions.keys().collect();
+    ids.sort();
+
+    let mut out = String::new();
+    for &id in ids {
+        let position = layout.computed_positions[&id];
+        let size = layout.computed_sizes.get(&id).copied().unwrap_or(Vec2::ZERO);
+        let (name, element_type) = match elements.get(&id) {
+            Some(element) => (element.id.replace(char::is_whitespace, "_"), format!("{:?}", element.element_type)),
+            None => ("?".to_string(), "?".to_string()),
+        };
+        out.push_str(&format!(
+            "{} {} {} x={:.2} y={:.2} w={:.2} h={:.2}\n",
+            id, name, element_type, position.x, position.y, size.x, size.y
+        ));
+    }
+    out
+}
+
+pub fn parse(text: &str) -> Result<Vec<SnapshotEntry>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| parse_line(line).ok_or_else(|| format!("line {}: malformed entry '{}'", index + 1, line)))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<SnapshotEntry> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [id, name, element_type, x, y, w, h] = fields.as_slice() else {
+        return None;
+    };
+    let number = |field: &str, key: &str| field.strip_prefix(key)?.parse::<f32>().ok();
+
+    Some(SnapshotEntry {
+        element_id: id.parse().ok()?,
+        name: name.to_string(),
+        element_type: element_type.to_string(),
+        position: Vec2::new(number(x, "x=")?, number(y, "y=")?),
+        size: Vec2::new(number(w, "w=")?, number(h, "h=")?),
+    })
+}
+
+/// Differences between two snapshots, one description per element.
+/// Empty when they match within `tolerance`.
+pub fn diff(expected: &str, actual: &str, tolerance: f32) -> Result<Vec<String>, String> {
+    let by_id = |entries: Vec<SnapshotEntry>| -> BTreeMap<ElementId, SnapshotEntry> {
+        entries.into_iter().map(|entry| (entry.element_id, entry)).collect()
+    };
+    let expected = by_id(parse(expected).map_err(|e| format!("golden: {}", e))?);
+    let actual = by_id(parse(actual).map_err(|e| format!("actual: {}", e))?);
+
+    let mut differences = Vec::new();
+    for (id, want) in &expected {
+        let Some(got) = actual.get(id) else {
+            differences.push(format!("{} {}: missing from layout", id, want.name));
+            continue;
+        };
+        if got.name != want.name || got.element_type != want.element_type {
+            differences.push(format!(
+                "{}: expected {} {}, got {} {}",
+                id, want.name, want.element_type, got.name, got.element_type
+            ));
+            continue;
+        }
+
+        let close = |a: Vec2, b: Vec2| (a - b).abs().max_element() <= tolerance;
+        if !close(got.position, want.position) || !close(got.size, want.size) {
+            differences.push(format!(
+                "{} {}: expected x={:.2} y={:.2} w={:.2} h={:.2}, got x={:.2} y={:.2} w={:.2} h={:.2}",
+                id, want.name,
+                want.position.x, want.position.y, want.size.x, want.size.y,
+                got.position.x, got.position.y, got.size.x, got.size.y,
+            ));
+        }
+    }
+    for (id, got) in &actual {
+        if !expected.contains_key(id) {
+            differences.push(format!("{} {}: not in golden", id, got.name));
+        }
+    }
+    Ok(differences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::ElementType;
+
+    fn layout(entries: &[(ElementId, Vec2, Vec2)]) -> LayoutResult {
+        LayoutResult {
+            computed_positions: entries.iter().map(|&(id, position, _)| (id, position)).collect(),
+            computed_sizes: entries.iter().map(|&(id, _, size)| (id, size)).collect(),
+        }
+    }
+
+    #[test]
+    fn serializes_in_id_order_and_round_trips() {
+        let elements: HashMap<ElementId, Element> = [
+            (0, Element { id: "app".into(), element_type: ElementType::App, ..Default::default() }),
+            (1, Element { id: "ok button".into(), element_type: ElementType::Button, ..Default::default() }),
+        ].into_iter().collect();
+        let text = serialize(&elements, &layout(&[
+            (1, Vec2::new(10.0, 20.5), Vec2::new(80.0, 40.0)),
+            (0, Vec2::ZERO, Vec2::new(800.0, 600.0)),
+        ]));
+
+        assert_eq!(
+            text,
+            "0 app App x=0.00 y=0.00 w=800.00 h=600.00\n1 ok_button Button x=10.00 y=20.50 w=80.00 h=40.00\n"
+        );
+        let entries = parse(&format!("# comment\n{}", text)).unwrap();
+        assert_eq!(entries[1].position, Vec2::new(10.0, 20.5));
+    }
+
+    #[test]
+    fn diff_allows_tolerance_and_reports_changes() {
+        let golden = "0 app App x=0.00 y=0.00 w=800.00 h=600.00\n1 side Container x=0.00 y=0.00 w=160.00 h=600.00\n";
+        let nudged = "0 app App x=0.00 y=0.00 w=800.30 h=600.00\n1 side Container x=0.00 y=0.00 w=160.00 h=600.00\n";
+        assert!(diff(golden, nudged, DEFAULT_TOLERANCE).unwrap().is_empty());
+
+        let changed = "0 app App x=0.00 y=0.00 w=800.00 h=600.00\n2 extra Text x=0.00 y=0.00 w=1.00 h=1.00\n";
+        let differences = diff(golden, changed, DEFAULT_TOLERANCE).unwrap();
+        assert_eq!(differences, vec!["1 side: missing from layout", "2 extra: not in golden"]);
+
+        assert!(diff("0 app App x=0", golden, DEFAULT_TOLERANCE).is_err());
+    }
+}
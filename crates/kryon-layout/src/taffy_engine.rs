@@ -3,11 +3,12 @@
 //! This module provides modern Grid and Flexbox layout capabilities using Taffy,
 //! implementing Kryon's own styling system while maintaining KRB binary compatibility.
 
-use kryon_core::{Element, ElementId};
+use crate::{intrinsic, SafeAreaInsets, TextMeasurer};
+use kryon_core::{CSSUnit, CSSUnitValue, Element, ElementId, LengthContext, OverflowType};
 use glam::Vec2;
 use std::collections::HashMap;
 use taffy::prelude::*;
-use taffy::ResolveOrZero;
+use taffy::{Overflow, Point, ResolveOrZero};
 use tracing::debug;
 
 /// Taffy-based layout engine that replaces the legacy flex layout system
@@ -20,6 +21,16 @@ pub struct TaffyLayoutEngine {
     node_to_element: HashMap<taffy::NodeId, ElementId>,
     /// Cached final layout results
     layout_cache: HashMap<ElementId, Layout>,
+    /// Resolves `env(safe-area-inset-*)` padding and `safe_area` on the root
+    safe_area: SafeAreaInsets,
+    /// Natural sizes of Image elements' bitmaps; see `LayoutEngine::set_intrinsic_size`
+    intrinsic_sizes: HashMap<ElementId, Vec2>,
+    /// See `LayoutEngine::set_text_baseline`
+    text_baseline: f32,
+    /// See `LayoutEngine::set_text_measurer`
+    text_measurer: Box<dyn TextMeasurer>,
+    /// See `LayoutEngine::set_skipped_subtrees`
+    skipped: HashMap<ElementId, Vec2>,
 }
 
 impl TaffyLayoutEngine {
@@ -30,6 +41,11 @@ impl TaffyLayoutEngine {
             element_to_node: HashMap::new(),
             node_to_element: HashMap::new(),
             layout_cache: HashMap::new(),
+            safe_area: SafeAreaInsets::default(),
+            intrinsic_sizes: HashMap::new(),
+            text_baseline: intrinsic::DEFAULT_TEXT_BASELINE,
+            text_measurer: Box::new(intrinsic::EstimatedTextMeasurer),
+            skipped: HashMap::new(),
         }
     }
 
@@ -45,6 +61,7 @@ impl TaffyLayoutEngine {
 
         // Build Taffy tree from KRB elements in deterministic order
         let root_node = self.build_taffy_tree_deterministic(elements, root_element_id)?;
+        self.apply_intrinsic_sizes(elements)?;
         
         // Compute layout with Taffy
         let available_space = Size {
@@ -52,7 +69,11 @@ impl TaffyLayoutEngine {
             height: AvailableSpace::Definite(available_space.height),
         };
         
-        self.taffy.compute_layout(root_node, available_space)?;
+        let intrinsic_sizes = &self.intrinsic_sizes;
+        let text_measurer = &mut *self.text_measurer;
+        self.taffy.compute_layout_with_measure(root_node, available_space, |known, available, _, element_id, _| {
+            intrinsic::measure_node(elements, intrinsic_sizes, text_measurer, element_id, known, available)
+        })?;
 
         // Cache layout results
         self.cache_layouts(elements)?;
@@ -68,6 +89,70 @@ impl TaffyLayoutEngine {
         Ok(())
     }
 
+    /// Sizes elements whose width or height is `min-content`, `max-content`
+    /// or `fit-content` from their measured content. Deepest elements go
+    /// first, so an outer box measures the final size of an inner one.
+    fn apply_intrinsic_sizes(&mut self, elements: &HashMap<ElementId, Element>) -> Result<(), taffy::TaffyError> {
+        let depth = |mut element_id: ElementId| {
+            let mut depth = 0;
+            while let Some(parent_id) = elements.get(&element_id).and_then(|element| element.parent) {
+                element_id = parent_id;
+                depth += 1;
+            }
+            depth
+        };
+        let mut sized: Vec<(ElementId, &Element)> = elements.iter()
+            .filter(|(_, element)| element.layout_size.width.is_intrinsic() || element.layout_size.height.is_intrinsic())
+            .map(|(&element_id, element)| (element_id, element))
+            .collect();
+        sized.sort_by_key(|&(element_id, _)| (std::cmp::Reverse(depth(element_id)), element_id));
+
+        for (element_id, element) in sized {
+            let Some(&node) = self.element_to_node.get(&element_id) else { continue };
+            let min_content = self.content_size(elements, node, AvailableSpace::MinContent)?;
+            let max_content = self.content_size(elements, node, AvailableSpace::MaxContent)?;
+            // The height of the content at the width it ends up with
+            let height = match element.layout_size.width {
+                kryon_core::LayoutDimension::MinContent => min_content.height,
+                _ => max_content.height,
+            };
+
+            let mut style = self.taffy.style(node)?.clone();
+            intrinsic::apply_keyword(
+                &element.layout_size.width,
+                (&mut style.size.width, &mut style.min_size.width, &mut style.max_size.width),
+                min_content.width,
+                max_content.width,
+            );
+            intrinsic::apply_keyword(
+                &element.layout_size.height,
+                (&mut style.size.height, &mut style.min_size.height, &mut style.max_size.height),
+                height,
+                height,
+            );
+            eprintln!("[TAFFY_INTRINSIC] Element '{}': {:?} x {:?} from content {}..{} wide",
+                element.id, element.layout_size.width, element.layout_size.height, min_content.width, max_content.width);
+            self.taffy.set_style(node, style)?;
+        }
+        Ok(())
+    }
+
+    /// The size of the subtree at `node` laid out on its own in `width`.
+    fn content_size(
+        &mut self,
+        elements: &HashMap<ElementId, Element>,
+        node: taffy::NodeId,
+        width: AvailableSpace,
+    ) -> Result<Size<f32>, taffy::TaffyError> {
+        let available_space = Size { width, height: AvailableSpace::MaxContent };
+        let intrinsic_sizes = &self.intrinsic_sizes;
+        let text_measurer = &mut *self.text_measurer;
+        self.taffy.compute_layout_with_measure(node, available_space, |known, available, _, element_id, _| {
+            intrinsic::measure_node(elements, intrinsic_sizes, text_measurer, element_id, known, available)
+        })?;
+        Ok(self.taffy.layout(node)?.size)
+    }
+
     /// Get the computed layout for an element
     pub fn get_layout(&self, element_id: ElementId) -> Option<&Layout> {
         self.layout_cache.get(&element_id)
@@ -89,12 +174,48 @@ impl TaffyLayoutEngine {
         root_element_id: ElementId,
     ) -> Result<taffy::NodeId, taffy::TaffyError> {
         // First pass: Create all nodes in sorted order by element ID
-        let mut sorted_elements: Vec<_> = elements.iter().collect();
+        // Leaving out the contents of skipped elements
+        let mut sorted_elements: Vec<_> = elements.iter()
+            .filter(|&(&element_id, _)| !self.inside_skipped(elements, element_id))
+            .collect();
         sorted_elements.sort_by_key(|(id, _)| *id);
         
         for (&element_id, element) in sorted_elements {
-            let style = self.krb_to_taffy_style(element);
-            let node = self.taffy.new_leaf(style)?;
+            let lengths = kryon_core::inline_length_context(elements, element_id);
+            let mut style = self.krb_to_taffy_style(element, &lengths);
+            if element_id == root_element_id {
+                let insets = self.safe_area.for_element(element);
+                let inset = |padding: LengthPercentage, inset: f32| match padding {
+                    LengthPercentage::Length(length) => LengthPercentage::Length(length + inset),
+                    percent => percent,
+                };
+                style.padding = Rect {
+                    top: inset(style.padding.top, insets.top),
+                    right: inset(style.padding.right, insets.right),
+                    bottom: inset(style.padding.bottom, insets.bottom),
+                    left: inset(style.padding.left, insets.left),
+                };
+            }
+            // Stretching a scroll container's children across the axis it
+            // scrolls would cut them to the viewport; they keep their content size
+            if style.align_self.is_none() && element.parent.and_then(|id| elements.get(&id)).is_some_and(scrolls_across) {
+                style.align_self = Some(AlignSelf::Start);
+            }
+            // Images keep their bitmap's proportions unless they set aspect_ratio
+            if element.element_type == kryon_core::ElementType::Image && style.aspect_ratio.is_none() {
+                style.aspect_ratio = self.intrinsic_sizes.get(&element_id)
+                    .filter(|size| size.x > 0.0 && size.y > 0.0)
+                    .map(|size| size.x / size.y);
+            }
+            if let Some(size) = self.skipped.get(&element_id) {
+                style.size = Size { width: Dimension::Length(size.x), height: Dimension::Length(size.y) };
+                eprintln!("[TAFFY_SKIP] Element {}: contents skipped, keeping {}x{}", element_id, size.x, size.y);
+            }
+            let node = if intrinsic::is_text_leaf(element) && in_baseline_context(elements, element_id) {
+                self.new_text_with_baseline(style, element_id, element.font_size)?
+            } else {
+                self.taffy.new_leaf_with_context(style, element_id)?
+            };
             
             self.element_to_node.insert(element_id, node);
             self.node_to_element.insert(node, element_id);
@@ -102,15 +223,18 @@ impl TaffyLayoutEngine {
             eprintln!("[TAFFY_NODE] Element {} -> Taffy Node {:?}", element_id, node);
         }
         
-        // Second pass: Set up parent-child relationships
+        // Second pass: Set up parent-child relationships, laying children
+        // out by their `order` (Taffy has no order of its own)
         for (&element_id, element) in elements {
             if let Some(&parent_node) = self.element_to_node.get(&element_id) {
-                let mut child_nodes = Vec::new();
+                let mut ordered_children = Vec::new();
                 for &child_id in &element.children {
                     if let Some(&child_node) = self.element_to_node.get(&child_id) {
-                        child_nodes.push(child_node);
+                        ordered_children.push((elements.get(&child_id).map_or(0, flex_order), child_node));
                     }
                 }
+                ordered_children.sort_by_key(|(order, _)| *order);
+                let child_nodes: Vec<_> = ordered_children.into_iter().map(|(_, node)| node).collect();
                 
                 if !child_nodes.is_empty() {
                     eprintln!("[TAFFY_TREE] Element {} (Node {:?}) has children: {:?}", 
@@ -129,56 +253,91 @@ impl TaffyLayoutEngine {
             .ok_or_else(|| taffy::TaffyError::InvalidChildNode(taffy::NodeId::new(0)))
     }
 
+    /// Whether an ancestor of the element has its contents skipped
+    fn inside_skipped(&self, elements: &HashMap<ElementId, Element>, element_id: ElementId) -> bool {
+        if self.skipped.is_empty() {
+            return false;
+        }
+        let mut parent = elements.get(&element_id).and_then(|element| element.parent);
+        while let Some(parent_id) = parent {
+            if self.skipped.contains_key(&parent_id) {
+                return true;
+            }
+            parent = elements.get(&parent_id).and_then(|element| element.parent);
+        }
+        false
+    }
+
+    /// A node for a text element taking part in baseline alignment. Taffy
+    /// can't ask a measured leaf for its baseline, so the text goes in a
+    /// column behind a zero-width strut reaching down to its first baseline,
+    /// which Taffy takes as the column's baseline. A negative margin keeps
+    /// the strut from adding to the height.
+    fn new_text_with_baseline(&mut self, mut style: Style, element_id: ElementId, font_size: f32) -> Result<taffy::NodeId, taffy::TaffyError> {
+        let baseline = font_size * self.text_baseline;
+        let strut = self.taffy.new_leaf(Style {
+            size: Size { width: Dimension::Length(0.0), height: Dimension::Length(baseline) },
+            margin: Rect { bottom: LengthPercentageAuto::Length(-baseline), ..Rect::zero() },
+            flex_shrink: 0.0,
+            ..Default::default()
+        })?;
+        let text = self.taffy.new_leaf_with_context(Style::default(), element_id)?;
+        style.display = Display::Flex;
+        style.flex_direction = FlexDirection::Column;
+        style.align_items = Some(AlignItems::Stretch);
+        style.justify_content = Some(JustifyContent::Start);
+        eprintln!("[TAFFY_BASELINE] Element {}: first baseline {} below its content box", element_id, baseline);
+        self.taffy.new_with_children(style, &[strut, text])
+    }
 
     /// Convert kryon-core Element to Taffy Style
-    fn krb_to_taffy_style(&self, element: &Element) -> Style {
+    /// `lengths` resolves `em` and `rem` for this element.
+    fn krb_to_taffy_style(&self, element: &Element, lengths: &LengthContext) -> Style {
         let mut style = Style::default();
 
         // Apply default flex layout for containers first (provides defaults)
         self.apply_default_container_layout(&mut style, element);
         
         // Apply modern CSS properties (these override defaults)
-        self.apply_custom_properties(&mut style, element);
+        self.apply_custom_properties(&mut style, element, lengths);
 
-        // Apply size constraints from element - check both size and layout_size for width
-        let explicit_width = if let kryon_core::LayoutDimension::Pixels(width) = element.layout_size.width {
-            if width > 0.0 { Some(width) } else { None }
-        } else { 
-            if element.size.x > 0.0 { Some(element.size.x) } else { None }
-        };
-        
-        // Apply size constraints from element - check both size and layout_size for height  
-        let explicit_height = if let kryon_core::LayoutDimension::Pixels(height) = element.layout_size.height {
-            if height > 0.0 { Some(height) } else { None }
-        } else { 
-            if element.size.y > 0.0 { Some(element.size.y) } else { None }
-        };
+        // Scroll containers can be smaller than their content, which keeps its
+        // own size; a scrollbar takes its width from the content box
+        style.overflow = Point { x: taffy_overflow(element.overflow_x), y: taffy_overflow(element.overflow_y) };
+        if element.overflow_x == OverflowType::Scroll || element.overflow_y == OverflowType::Scroll {
+            style.scrollbar_width = crate::SCROLLBAR_WIDTH;
+        }
+
+        // Apply size constraints from element - check both size and layout_size.
+        // Percentages resolve against the parent's content box in Taffy.
+        let explicit_width = explicit_dimension(&element.layout_size.width, element.size.x);
+        let explicit_height = explicit_dimension(&element.layout_size.height, element.size.y);
         
         if let Some(width) = explicit_width {
-            style.size.width = Dimension::Length(width);
+            style.size.width = width;
             // Only prevent flex grow/shrink if both dimensions are explicitly set
             // This allows buttons with explicit height to still grow horizontally
             if explicit_height.is_some() {
                 style.flex_grow = 0.0;
                 style.flex_shrink = 0.0;
-                eprintln!("[TAFFY_SIZE] Element '{}': Using width {}px from layout_size (both dimensions set, flex-shrink: 0)", element.id, width);
+                eprintln!("[TAFFY_SIZE] Element '{}': Using width {:?} from layout_size (both dimensions set, flex-shrink: 0)", element.id, width);
             } else {
-                eprintln!("[TAFFY_SIZE] Element '{}': Using width {}px from layout_size (height flexible)", element.id, width);
+                eprintln!("[TAFFY_SIZE] Element '{}': Using width {:?} from layout_size (height flexible)", element.id, width);
             }
         } else if element.element_type == kryon_core::ElementType::Container {
             eprintln!("[TAFFY_CONTAINER] Container '{}': no explicit width, using intrinsic sizing", element.id);
         }
         
         if let Some(height) = explicit_height {
-            style.size.height = Dimension::Length(height);
+            style.size.height = height;
             // Only prevent flex grow/shrink if both dimensions are explicitly set
             // This allows buttons with explicit height to still grow horizontally
             if explicit_width.is_some() {
                 style.flex_grow = 0.0;
                 style.flex_shrink = 0.0;
-                eprintln!("[TAFFY_SIZE] Element '{}': Using height {}px from layout_size (both dimensions set, flex-shrink: 0)", element.id, height);
+                eprintln!("[TAFFY_SIZE] Element '{}': Using height {:?} from layout_size (both dimensions set, flex-shrink: 0)", element.id, height);
             } else {
-                eprintln!("[TAFFY_SIZE] Element '{}': Using height {}px from layout_size (width flexible)", element.id, height);
+                eprintln!("[TAFFY_SIZE] Element '{}': Using height {:?} from layout_size (width flexible)", element.id, height);
             }
         }
 
@@ -218,7 +377,7 @@ impl TaffyLayoutEngine {
             }
             
             // Calculate intrinsic text height if not explicitly set
-            if element.size.y == 0.0 {
+            if explicit_height.is_none() {
                 let text_height = element.font_size.max(16.0);
                 style.size.height = Dimension::Length(text_height);
             }
@@ -311,7 +470,7 @@ impl TaffyLayoutEngine {
     }
 
     /// Apply custom properties from element to Taffy style
-    fn apply_custom_properties(&self, style: &mut Style, element: &Element) {
+    fn apply_custom_properties(&self, style: &mut Style, element: &Element, lengths: &LengthContext) {
         use kryon_core::PropertyValue;
         
         // Parse Kryon Grid properties
@@ -327,10 +486,10 @@ impl TaffyLayoutEngine {
 
         // Grid template columns/rows
         if let Some(PropertyValue::String(value)) = element.custom_properties.get("grid_template_columns") {
-            style.grid_template_columns = self.parse_grid_track_list(value);
+            style.grid_template_columns = self.parse_grid_track_list(value, lengths);
         }
         if let Some(PropertyValue::String(value)) = element.custom_properties.get("grid_template_rows") {
-            style.grid_template_rows = self.parse_grid_track_list(value);
+            style.grid_template_rows = self.parse_grid_track_list(value, lengths);
         }
         
         // Grid template areas (Note: Taffy 0.5 may not have this field yet)
@@ -405,7 +564,7 @@ impl TaffyLayoutEngine {
             style.flex_wrap = match value.as_str() {
                 "nowrap" => FlexWrap::NoWrap,
                 "wrap" => FlexWrap::Wrap,
-                "wrap-reverse" => FlexWrap::WrapReverse,
+                "wrap-reverse" | "wrap_reverse" => FlexWrap::WrapReverse,
                 _ => FlexWrap::NoWrap,
             };
         }
@@ -416,23 +575,11 @@ impl TaffyLayoutEngine {
                 PropertyValue::String(s) => match s.as_str() {
                     "auto" => Dimension::Auto,
                     "content" => Dimension::Auto, // Taffy doesn't have content, use auto
-                    _ => {
-                        if s.ends_with("px") {
-                            if let Ok(px_value) = s.trim_end_matches("px").parse::<f32>() {
-                                Dimension::Length(px_value)
-                            } else {
-                                Dimension::Auto
-                            }
-                        } else if s.ends_with("%") {
-                            if let Ok(percent_value) = s.trim_end_matches("%").parse::<f32>() {
-                                Dimension::Percent(percent_value / 100.0)
-                            } else {
-                                Dimension::Auto
-                            }
-                        } else {
-                            Dimension::Auto
-                        }
-                    }
+                    _ => match kryon_core::parse_length(s) {
+                        Some(CSSUnitValue { value, unit: CSSUnit::Percentage }) => Dimension::Percent(value as f32 / 100.0),
+                        Some(length) => length.to_pixels(lengths).map_or(Dimension::Auto, Dimension::Length),
+                        None => Dimension::Auto,
+                    },
                 }
                 PropertyValue::Float(f) => Dimension::Length(*f),
                 PropertyValue::Int(i) => Dimension::Length(*i as f32),
@@ -440,6 +587,11 @@ impl TaffyLayoutEngine {
             };
         }
 
+        // Width over height, e.g. `16/9`: a box with only one of them set gets the other
+        if let Some(value) = element.custom_properties.get("aspect_ratio") {
+            style.aspect_ratio = aspect_ratio(value);
+        }
+
         if let Some(PropertyValue::String(value)) = element.custom_properties.get("align_items") {
             style.align_items = Some(match value.as_str() {
                 "start" | "flex-start" | "flex_start" => AlignItems::Start,
@@ -466,13 +618,13 @@ impl TaffyLayoutEngine {
         // Align content (for wrapped flex lines)
         if let Some(PropertyValue::String(value)) = element.custom_properties.get("align_content") {
             style.align_content = Some(match value.as_str() {
-                "start" | "flex-start" => AlignContent::Start,
+                "start" | "flex-start" | "flex_start" => AlignContent::Start,
                 "center" => AlignContent::Center,
-                "end" | "flex-end" => AlignContent::End,
+                "end" | "flex-end" | "flex_end" => AlignContent::End,
                 "stretch" => AlignContent::Stretch,
-                "space-between" => AlignContent::SpaceBetween,
-                "space-around" => AlignContent::SpaceAround,
-                "space-evenly" => AlignContent::SpaceEvenly,
+                "space-between" | "space_between" => AlignContent::SpaceBetween,
+                "space-around" | "space_around" => AlignContent::SpaceAround,
+                "space-evenly" | "space_evenly" => AlignContent::SpaceEvenly,
                 _ => AlignContent::Start,
             });
         }
@@ -513,18 +665,6 @@ impl TaffyLayoutEngine {
             style.flex_shrink = shrink_value;
         }
         
-        // Flex order - TODO: Check if this is available in current Taffy version
-        if let Some(value) = element.custom_properties.get("order") {
-            let _order_value = match value {
-                PropertyValue::String(s) => s.parse::<i32>().unwrap_or(0),
-                PropertyValue::Int(i) => *i,
-                PropertyValue::Float(f) => *f as i32,
-                _ => 0,
-            };
-            // style.order = order_value; // TODO: Uncomment when Taffy supports this
-            eprintln!("[TAFFY_ORDER] Order property not yet supported in this Taffy version");
-        }
-
         // Position properties
         if let Some(PropertyValue::String(value)) = element.custom_properties.get("position") {
             style.position = match value.as_str() {
@@ -534,21 +674,40 @@ impl TaffyLayoutEngine {
             };
         }
 
-        // Gap property
+        // Anchored popups are placed by the runtime and take no space in flow
+        if element.custom_properties.contains_key("popup_anchor") {
+            style.position = Position::Absolute;
+        }
+
+        // Gaps between rows and columns: `gap` takes one value for both or
+        // `row column`, and `row_gap`/`column_gap` override either axis
         if let Some(value) = element.custom_properties.get("gap") {
-            if let Some(gap_value) = value.as_float() {
-                style.gap = Size {
-                    width: LengthPercentage::Length(gap_value),
-                    height: LengthPercentage::Length(gap_value),
-                };
+            match value.as_string().map(|text| text.split_whitespace().collect::<Vec<_>>()) {
+                Some(parts) if parts.len() == 2 => {
+                    if let Some(row_gap) = gap_length(&PropertyValue::String(parts[0].into()), lengths) {
+                        style.gap.height = row_gap;
+                    }
+                    if let Some(column_gap) = gap_length(&PropertyValue::String(parts[1].into()), lengths) {
+                        style.gap.width = column_gap;
+                    }
+                }
+                _ => if let Some(gap) = gap_length(value, lengths) {
+                    style.gap = Size { width: gap, height: gap };
+                },
             }
         }
+        if let Some(row_gap) = element.custom_properties.get("row_gap").and_then(|value| gap_length(value, lengths)) {
+            style.gap.height = row_gap;
+        }
+        if let Some(column_gap) = element.custom_properties.get("column_gap").and_then(|value| gap_length(value, lengths)) {
+            style.gap.width = column_gap;
+        }
 
         // Box Model Properties
 
         // Padding properties
         if let Some(value) = element.custom_properties.get("padding") {
-            if let Some(padding_value) = value.as_float() {
+            if let Some(padding_value) = self.padding_length(value, lengths) {
                 let padding = LengthPercentage::Length(padding_value);
                 style.padding = Rect {
                     left: padding,
@@ -561,29 +720,29 @@ impl TaffyLayoutEngine {
 
         // Individual padding sides
         if let Some(value) = element.custom_properties.get("padding_top") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = self.padding_length(value, lengths) {
                 style.padding.top = LengthPercentage::Length(val);
             }
         }
         if let Some(value) = element.custom_properties.get("padding_right") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = self.padding_length(value, lengths) {
                 style.padding.right = LengthPercentage::Length(val);
             }
         }
         if let Some(value) = element.custom_properties.get("padding_bottom") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = self.padding_length(value, lengths) {
                 style.padding.bottom = LengthPercentage::Length(val);
             }
         }
         if let Some(value) = element.custom_properties.get("padding_left") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = self.padding_length(value, lengths) {
                 style.padding.left = LengthPercentage::Length(val);
             }
         }
 
         // Margin properties
         if let Some(value) = element.custom_properties.get("margin") {
-            if let Some(margin_value) = value.as_float() {
+            if let Some(margin_value) = length_value(value, lengths) {
                 let margin = LengthPercentage::Length(margin_value).into();
                 style.margin = Rect {
                     left: margin,
@@ -596,22 +755,22 @@ impl TaffyLayoutEngine {
 
         // Individual margin sides
         if let Some(value) = element.custom_properties.get("margin_top") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = length_value(value, lengths) {
                 style.margin.top = LengthPercentage::Length(val).into();
             }
         }
         if let Some(value) = element.custom_properties.get("margin_right") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = length_value(value, lengths) {
                 style.margin.right = LengthPercentage::Length(val).into();
             }
         }
         if let Some(value) = element.custom_properties.get("margin_bottom") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = length_value(value, lengths) {
                 style.margin.bottom = LengthPercentage::Length(val).into();
             }
         }
         if let Some(value) = element.custom_properties.get("margin_left") {
-            if let Some(val) = value.as_float() {
+            if let Some(val) = length_value(value, lengths) {
                 style.margin.left = LengthPercentage::Length(val).into();
             }
         }
@@ -695,6 +854,11 @@ impl TaffyLayoutEngine {
     }
 
     /// Apply default container layout behavior (replaces legacy layout flags)
+    /// A padding in pixels, which may name a safe-area inset
+    fn padding_length(&self, value: &kryon_core::PropertyValue, lengths: &LengthContext) -> Option<f32> {
+        value.as_string().and_then(|name| self.safe_area.resolve(name)).or_else(|| length_value(value, lengths))
+    }
+
     fn apply_default_container_layout(&self, style: &mut Style, element: &Element) {
         // Set default display behavior based on element type and CSS properties
         match element.element_type {
@@ -739,6 +903,14 @@ impl TaffyLayoutEngine {
                 // WasmView elements are block-level containers for WASM module output
                 // They should maintain their specified width and height for the WASM viewport
             }
+            kryon_core::ElementType::ParticleEmitter => {
+                style.display = Display::Block;
+                // The emitter box is the spawn origin; particles are drawn outside it freely
+            }
+            kryon_core::ElementType::Table => {
+                style.display = Display::Block;
+                // Rows are drawn by the renderer inside the element's box; it has no child nodes
+            }
             _ => {
                 style.display = Display::Block;
             }
@@ -773,7 +945,7 @@ impl TaffyLayoutEngine {
     }
 
     /// Parse Kryon Grid track list (e.g., "1fr 2fr 100px")
-    fn parse_grid_track_list(&self, value: &str) -> Vec<TrackSizingFunction> {
+    fn parse_grid_track_list(&self, value: &str, lengths: &LengthContext) -> Vec<TrackSizingFunction> {
         let mut tracks = Vec::new();
         
         // Parser for Kryon grid track syntax
@@ -782,13 +954,12 @@ impl TaffyLayoutEngine {
                 if let Ok(fr_value) = token.trim_end_matches("fr").parse::<f32>() {
                     tracks.push(fr(fr_value));
                 }
-            } else if token.ends_with("px") {
-                if let Ok(px_value) = token.trim_end_matches("px").parse::<f32>() {
-                    tracks.push(length(px_value));
-                }
-            } else if token.ends_with("%") {
-                if let Ok(percent_value) = token.trim_end_matches("%").parse::<f32>() {
-                    tracks.push(percent(percent_value / 100.0));
+            } else if let Some(value) = kryon_core::parse_length(token) {
+                match value.unit {
+                    CSSUnit::Percentage => tracks.push(percent(value.value as f32 / 100.0)),
+                    _ => if let Some(px_value) = value.to_pixels(lengths) {
+                        tracks.push(length(px_value));
+                    },
                 }
             } else if token == "auto" {
                 tracks.push(auto());
@@ -999,6 +1170,35 @@ impl Default for TaffyLayoutEngine {
 }
 
 impl crate::LayoutEngine for TaffyLayoutEngine {
+    fn set_safe_area(&mut self, insets: SafeAreaInsets) {
+        self.safe_area = insets;
+    }
+    
+    fn set_intrinsic_size(&mut self, element_id: ElementId, size: Vec2) {
+        self.intrinsic_sizes.insert(element_id, size);
+    }
+    
+    fn set_text_baseline(&mut self, ratio: f32) {
+        self.text_baseline = ratio;
+    }
+    
+    fn set_text_measurer(&mut self, measurer: Box<dyn TextMeasurer>) {
+        self.text_measurer = measurer;
+    }
+    
+    fn set_skipped_subtrees(&mut self, skipped: &HashMap<ElementId, Vec2>) {
+        self.skipped.clone_from(skipped);
+    }
+    
+    fn memory_usage(&self) -> (usize, usize) {
+        let nodes = self.element_to_node.len();
+        // Style and layout per Taffy node, plus the id maps and cached layouts
+        let per_node = std::mem::size_of::<Style>()
+            + 2 * std::mem::size_of::<Layout>()
+            + 2 * (std::mem::size_of::<ElementId>() + std::mem::size_of::<NodeId>());
+        (nodes, nodes * per_node)
+    }
+    
     fn compute_layout(
         &mut self,
         elements: &HashMap<ElementId, Element>,
@@ -1069,6 +1269,278 @@ impl TaffyLayoutEngine {
     }
 }
 
+/// A width or height from `layout_size`, else from `size` when that's set.
+fn explicit_dimension(layout: &kryon_core::LayoutDimension, size: f32) -> Option<Dimension> {
+    match *layout {
+        kryon_core::LayoutDimension::Pixels(pixels) => (pixels > 0.0).then_some(Dimension::Length(pixels)),
+        kryon_core::LayoutDimension::Percentage(fraction) => (fraction > 0.0).then_some(Dimension::Percent(fraction)),
+        // `size` holds the last layout's result; the keyword is measured afresh
+        ref keyword if keyword.is_intrinsic() => None,
+        _ => (size > 0.0).then_some(Dimension::Length(size)),
+    }
+}
+
+/// An `aspect_ratio` given as a number or as `width/height`.
+fn aspect_ratio(value: &kryon_core::PropertyValue) -> Option<f32> {
+    let ratio = match value.as_string() {
+        Some(text) => match text.split_once('/') {
+            Some((width, height)) => width.trim().parse::<f32>().ok()? / height.trim().parse::<f32>().ok()?,
+            None => text.trim().parse().ok()?,
+        },
+        None => value.as_float()?,
+    };
+    (ratio.is_finite() && ratio > 0.0).then_some(ratio)
+}
+
+/// A length in pixels from a number or a CSS length such as `1.5em`.
+/// Percentages and angles are `None`.
+fn length_value(value: &kryon_core::PropertyValue, lengths: &LengthContext) -> Option<f32> {
+    match value.as_string() {
+        Some(text) => kryon_core::parse_length(text)
+            .filter(|length| length.unit != CSSUnit::Percentage)
+            .and_then(|length| length.to_pixels(lengths)),
+        None => value.as_float(),
+    }
+}
+
+/// An element's `order` among its siblings, 0 by default.
+fn flex_order(element: &Element) -> i32 {
+    match element.custom_properties.get("order") {
+        Some(kryon_core::PropertyValue::String(s)) => s.trim().parse().unwrap_or(0),
+        Some(kryon_core::PropertyValue::Int(i)) => *i,
+        Some(kryon_core::PropertyValue::Float(f)) => *f as i32,
+        _ => 0,
+    }
+}
+
+/// Whether a flex container scrolls along its cross axis.
+/// Whether the element or an ancestor is aligned to its parent's baseline,
+/// which Taffy takes from the box's first item
+fn in_baseline_context(elements: &HashMap<ElementId, Element>, mut element_id: ElementId) -> bool {
+    let property = |element: &Element, name: &str| match element.custom_properties.get(name) {
+        Some(kryon_core::PropertyValue::String(value)) => Some(value.clone()),
+        _ => None,
+    };
+    while let Some(element) = elements.get(&element_id) {
+        let Some(parent_id) = element.parent else {
+            return false;
+        };
+        let align_self = property(element, "align_self");
+        if align_self.as_deref() == Some("baseline")
+            || (align_self.is_none() && elements.get(&parent_id).and_then(|parent| property(parent, "align_items")).as_deref() == Some("baseline"))
+        {
+            return true;
+        }
+        element_id = parent_id;
+    }
+    false
+}
+
+fn scrolls_across(container: &Element) -> bool {
+    let property = |name| match container.custom_properties.get(name) {
+        Some(kryon_core::PropertyValue::String(value)) => Some(value.as_str()),
+        _ => None,
+    };
+    if property("display").is_some_and(|display| display != "flex") {
+        return false;
+    }
+    let cross_overflow = match property("flex_direction") {
+        Some(direction) if direction.starts_with("column") => container.overflow_x,
+        _ => container.overflow_y,
+    };
+    matches!(cross_overflow, OverflowType::Scroll | OverflowType::Auto)
+}
+
+fn taffy_overflow(overflow: OverflowType) -> Overflow {
+    match overflow {
+        OverflowType::Visible => Overflow::Visible,
+        OverflowType::Hidden => Overflow::Hidden,
+        OverflowType::Scroll | OverflowType::Auto => Overflow::Scroll,
+    }
+}
+
+/// A gap from a number, a CSS length or a percentage of the container.
+fn gap_length(value: &kryon_core::PropertyValue, lengths: &LengthContext) -> Option<LengthPercentage> {
+    match value.as_string().and_then(kryon_core::parse_length) {
+        Some(CSSUnitValue { value, unit: CSSUnit::Percentage }) => Some(LengthPercentage::Percent(value as f32 / 100.0)),
+        _ => length_value(value, lengths).filter(|gap| *gap >= 0.0).map(LengthPercentage::Length),
+    }
+}
+
 // TODO: Future extension for CSS Grid and modern Flexbox properties
 // When kryon-compiler supports generating these properties in KRB,
-// we can parse them from element.custom_properties and apply to Taffy styles
\ No newline at end of file
+// we can parse them from element.custom_properties and apply to Taffy styles
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LayoutEngine;
+    use kryon_core::{ElementType, PropertyValue};
+
+    fn container(children: Vec<ElementId>, properties: &[(&str, &str)]) -> Element {
+        let mut element = Element { element_type: ElementType::Container, children, ..Default::default() };
+        for (name, value) in properties {
+            element.custom_properties.insert(name.to_string(), PropertyValue::String(value.to_string()));
+        }
+        element
+    }
+
+    fn item(parent: ElementId) -> Element {
+        Element { element_type: ElementType::Container, parent: Some(parent), size: Vec2::new(40.0, 30.0), ..Default::default() }
+    }
+
+    fn positions(elements: &HashMap<ElementId, Element>, ids: &[ElementId]) -> Vec<Vec2> {
+        let layout = TaffyLayoutEngine::new().compute_layout(elements, 0, Vec2::new(800.0, 600.0));
+        ids.iter().map(|id| layout.computed_positions[id]).collect()
+    }
+
+    #[test]
+    fn wrapped_lines_are_spaced_by_row_gap_and_items_by_column_gap() {
+        let mut wrap = container(vec![2, 3, 4, 5], &[("flex_wrap", "wrap"), ("gap", "10 20")]);
+        wrap.parent = Some(0);
+        wrap.size = Vec2::new(100.0, 0.0);
+        let elements: HashMap<ElementId, Element> = [
+            (0, container(vec![1], &[("align_items", "start")])),
+            (1, wrap),
+            (2, item(1)),
+            (3, item(1)),
+            (4, item(1)),
+            (5, item(1)),
+        ].into_iter().collect();
+
+        let origin = positions(&elements, &[1])[0];
+        let items: Vec<Vec2> = positions(&elements, &[2, 3, 4, 5]).into_iter().map(|position| position - origin).collect();
+        assert_eq!(items, vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(60.0, 0.0),
+            Vec2::new(0.0, 40.0),
+            Vec2::new(60.0, 40.0),
+        ]);
+    }
+
+    #[test]
+    fn row_and_column_gaps_override_gap_and_accept_percentages() {
+        let mut wrap = container(vec![2, 3, 4], &[("flex_wrap", "wrap"), ("gap", "5"), ("column_gap", "10%"), ("row_gap", "1em")]);
+        wrap.parent = Some(0);
+        wrap.size = Vec2::new(120.0, 0.0);
+        wrap.font_size = 16.0;
+        let elements: HashMap<ElementId, Element> = [
+            (0, container(vec![1], &[("align_items", "start")])),
+            (1, wrap),
+            (2, item(1)),
+            (3, item(1)),
+            (4, item(1)),
+        ].into_iter().collect();
+
+        let origin = positions(&elements, &[1])[0];
+        let items: Vec<Vec2> = positions(&elements, &[2, 3, 4]).into_iter().map(|position| position - origin).collect();
+        // 12px columns fit two 40px items in 120px; the third wraps 16px below
+        assert_eq!(items, vec![Vec2::new(0.0, 0.0), Vec2::new(52.0, 0.0), Vec2::new(0.0, 46.0)]);
+    }
+
+    #[test]
+    fn order_moves_items_and_wrap_reverse_stacks_lines_upwards() {
+        let mut wrap = container(vec![2, 3, 4], &[("flex_wrap", "wrap-reverse"), ("align_content", "end")]);
+        wrap.parent = Some(0);
+        wrap.size = Vec2::new(100.0, 100.0);
+        let mut first = item(1);
+        first.custom_properties.insert("order".into(), PropertyValue::Int(1));
+        let mut last = item(1);
+        last.custom_properties.insert("order".into(), PropertyValue::Int(-1));
+        let elements: HashMap<ElementId, Element> = [
+            (0, container(vec![1], &[("align_items", "start")])),
+            (1, wrap),
+            (2, first),
+            (3, item(1)),
+            (4, last),
+        ].into_iter().collect();
+
+        let origin = positions(&elements, &[1])[0];
+        let items: Vec<Vec2> = positions(&elements, &[2, 3, 4]).into_iter().map(|position| position - origin).collect();
+        // Laid out as 4, 3, 2: two per line, the first line at the bottom
+        assert_eq!(items, vec![
+            Vec2::new(0.0, 40.0),
+            Vec2::new(40.0, 70.0),
+            Vec2::new(0.0, 70.0),
+        ]);
+    }
+
+    #[test]
+    fn wrapped_content_keeps_its_height_in_a_scroll_container() {
+        let mut scroller = container(vec![2], &[]);
+        scroller.parent = Some(0);
+        scroller.size = Vec2::new(200.0, 40.0);
+        scroller.overflow_y = OverflowType::Scroll;
+        let mut wrap = container((3..9).collect(), &[("flex_wrap", "wrap")]);
+        wrap.parent = Some(1);
+        let mut elements: HashMap<ElementId, Element> = [
+            (0, container(vec![1], &[("align_items", "start")])),
+            (1, scroller),
+            (2, wrap),
+        ].into_iter().collect();
+        elements.extend((3..9).map(|id| (id, item(2))));
+
+        let layout = TaffyLayoutEngine::new().compute_layout(&elements, 0, Vec2::new(800.0, 600.0));
+        // The scrollbar leaves 185px, four items a line, and the lines overflow
+        assert_eq!(layout.computed_sizes[&1], Vec2::new(200.0, 40.0));
+        assert_eq!(layout.computed_sizes[&2], Vec2::new(185.0, 60.0));
+        assert_eq!(layout.computed_positions[&7] - layout.computed_positions[&2], Vec2::new(0.0, 30.0));
+    }
+
+    #[test]
+    fn baseline_alignment_lines_up_text_of_different_sizes() {
+        let text = |parent, font_size| Element {
+            element_type: ElementType::Text,
+            parent: Some(parent),
+            text: "42".into(),
+            font_size,
+            ..Default::default()
+        };
+        let mut row = container(vec![2, 3, 4], &[("align_items", "baseline")]);
+        row.parent = Some(0);
+        let mut card = container(vec![5], &[("padding", "6")]);
+        card.parent = Some(1);
+        let elements: HashMap<ElementId, Element> = [
+            (0, container(vec![1], &[("align_items", "start")])),
+            (1, row),
+            (2, text(1, 20.0)),
+            (3, text(1, 40.0)),
+            (4, card),
+            (5, text(4, 20.0)),
+        ].into_iter().collect();
+
+        // Baselines 0.8 of the font size down: 16px and 32px into the boxes
+        let tops: Vec<f32> = positions(&elements, &[2, 3, 5]).iter().map(|position| position.y).collect();
+        assert_eq!(tops, vec![16.0, 0.0, 16.0]);
+
+        // A font with its baseline lower in the box
+        let mut engine = TaffyLayoutEngine::new();
+        engine.set_text_baseline(0.9);
+        let layout = engine.compute_layout(&elements, 0, Vec2::new(800.0, 600.0));
+        assert_eq!(layout.computed_positions[&2].y, 18.0);
+    }
+
+    #[test]
+    fn skipped_subtrees_keep_their_size_and_leave_out_their_contents() {
+        let mut panel = container(vec![3], &[]);
+        panel.parent = Some(0);
+        let elements: HashMap<ElementId, Element> = [
+            (0, container(vec![1, 2], &[("align_items", "start"), ("flex_direction", "column")])),
+            (1, item(0)),
+            (2, panel),
+            (3, item(2)),
+        ].into_iter().collect();
+
+        let mut engine = TaffyLayoutEngine::new();
+        engine.set_skipped_subtrees(&[(2, Vec2::new(120.0, 300.0))].into_iter().collect());
+        let layout = engine.compute_layout(&elements, 0, Vec2::new(800.0, 600.0));
+        assert_eq!(layout.computed_sizes[&2], Vec2::new(120.0, 300.0));
+        assert_eq!(layout.computed_positions[&2], Vec2::new(0.0, 30.0));
+        assert!(!layout.computed_positions.contains_key(&3));
+
+        engine.set_skipped_subtrees(&HashMap::new());
+        let layout = engine.compute_layout(&elements, 0, Vec2::new(800.0, 600.0));
+        assert_eq!(layout.computed_sizes[&2], Vec2::new(40.0, 30.0));
+        assert!(layout.computed_positions.contains_key(&3));
+    }
+}
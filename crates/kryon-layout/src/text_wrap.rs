@@ -0,0 +1,207 @@
+// crates/kryon-layout/src/text_wrap.rs
+//! Breaks text into lines no wider than a box, shared by the layout
+//! engine's intrinsic sizes and the backends that draw text line by line,
+//! so a text element is laid out as tall as the lines it's drawn in.
+//!
+//! Lines break at whitespace, and inside a word only when the word alone is
+//! wider than the box. `\n` always starts a new line. Lines that don't fit
+//! the box's height are dropped and the last one kept ends in an ellipsis.
+//! Widths come from a measure function, so each caller wraps with its own
+//! font metrics.
+
+use std::ops::Range;
+
+/// Ellipsis for fonts that have the character
+pub const ELLIPSIS: &str = "\u{2026}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// Bytes of the source text on this line, without the whitespace it
+    /// broke at
+    pub range: Range<usize>,
+    /// What to draw: the source bytes, plus an ellipsis on a truncated
+    /// last line
+    pub text: String,
+    pub width: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappedText {
+    pub lines: Vec<TextLine>,
+    pub line_height: f32,
+    /// Lines were dropped to fit the height
+    pub truncated: bool,
+}
+
+impl WrappedText {
+    /// Width of the widest line
+    pub fn width(&self) -> f32 {
+        self.lines.iter().map(|line| line.width).fold(0.0, f32::max)
+    }
+
+    pub fn height(&self) -> f32 {
+        self.lines.len() as f32 * self.line_height
+    }
+
+    /// Top of line `index` below the top of the text
+    pub fn line_top(&self, index: usize) -> f32 {
+        index as f32 * self.line_height
+    }
+}
+
+/// Wraps `text` to `max_width`, then keeps the lines that fit `max_height`
+/// (always at least one). `measure` gives the width of a piece of text.
+pub fn wrap_text(
+    text: &str,
+    max_width: Option<f32>,
+    max_height: Option<f32>,
+    line_height: f32,
+    ellipsis: &str,
+    mut measure: impl FnMut(&str) -> f32,
+) -> WrappedText {
+    let mut ranges = Vec::new();
+    let mut paragraph_start = 0;
+    for paragraph in text.split('\n') {
+        let end = paragraph_start + paragraph.trim_end_matches('\r').len();
+        match max_width {
+            Some(width) => break_paragraph(text, paragraph_start..end, width, &mut measure, &mut ranges),
+            None => ranges.push(paragraph_start..end),
+        }
+        paragraph_start += paragraph.len() + 1;
+    }
+
+    let max_lines = max_height
+        .filter(|_| line_height > 0.0)
+        .map_or(usize::MAX, |height| ((height + 0.01) / line_height).floor().max(1.0) as usize);
+    let truncated = ranges.len() > max_lines;
+    ranges.truncate(max_lines);
+
+    let last = ranges.len().saturating_sub(1);
+    let lines = ranges.into_iter()
+        .enumerate()
+        .map(|(index, range)| {
+            let line = if truncated && index == last {
+                ellipsize(&text[range.clone()], max_width, ellipsis, &mut measure)
+            } else {
+                text[range.clone()].to_string()
+            };
+            TextLine { range, width: measure(&line), text: line }
+        })
+        .collect();
+    WrappedText { lines, line_height, truncated }
+}
+
+/// Greedily fills lines from the words of `paragraph`
+fn break_paragraph(
+    text: &str,
+    paragraph: Range<usize>,
+    max_width: f32,
+    measure: &mut impl FnMut(&str) -> f32,
+    lines: &mut Vec<Range<usize>>,
+) {
+    // The line being filled, up to the end of its last word
+    let mut line: Option<Range<usize>> = None;
+    for word in words(text, paragraph.clone()) {
+        if let Some(current) = &line {
+            if measure(&text[current.start..word.end]) <= max_width {
+                line = Some(current.start..word.end);
+                continue;
+            }
+            lines.push(current.clone());
+        }
+        line = Some(break_word(text, word, max_width, measure, lines));
+    }
+    // Blank lines keep their place
+    lines.push(line.unwrap_or(paragraph.start..paragraph.start));
+}
+
+/// Pushes the pieces of a word too wide for a line of its own, returning
+/// the piece left to start the next line with
+fn break_word(
+    text: &str,
+    word: Range<usize>,
+    max_width: f32,
+    measure: &mut impl FnMut(&str) -> f32,
+    lines: &mut Vec<Range<usize>>,
+) -> Range<usize> {
+    let mut start = word.start;
+    let mut end = start;
+    for (offset, character) in text[word.clone()].char_indices() {
+        let next = word.start + offset + character.len_utf8();
+        // Every piece keeps at least one character
+        if end > start && measure(&text[start..next]) > max_width {
+            lines.push(start..end);
+            start = end;
+        }
+        end = next;
+    }
+    start..end
+}
+
+/// Byte ranges of the whitespace-separated words in `range`
+fn words(text: &str, range: Range<usize>) -> impl Iterator<Item = Range<usize>> + '_ {
+    let base = range.start;
+    let slice = &text[range];
+    slice.split_whitespace().map(move |word| {
+        let start = word.as_ptr() as usize - slice.as_ptr() as usize;
+        base + start..base + start + word.len()
+    })
+}
+
+/// `line` shortened until it fits `max_width` with the ellipsis after it
+fn ellipsize(line: &str, max_width: Option<f32>, ellipsis: &str, measure: &mut impl FnMut(&str) -> f32) -> String {
+    let mut kept = line.trim_end();
+    if let Some(width) = max_width {
+        while !kept.is_empty() && measure(&format!("{}{}", kept, ellipsis)) > width {
+            let last = kept.chars().next_back().map_or(0, char::len_utf8);
+            kept = kept[..kept.len() - last].trim_end();
+        }
+    }
+    format!("{}{}", kept, ellipsis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ten units per character
+    fn measure(text: &str) -> f32 {
+        text.chars().count() as f32 * 10.0
+    }
+
+    fn lines(wrapped: &WrappedText) -> Vec<&str> {
+        wrapped.lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn breaks_at_words_and_newlines() {
+        let text = "the quick brown fox\n\njumps";
+        let wrapped = wrap_text(text, Some(100.0), None, 12.0, ELLIPSIS, measure);
+        assert_eq!(lines(&wrapped), ["the quick", "brown fox", "", "jumps"]);
+        assert_eq!(wrapped.lines[1].range, 10..19);
+        assert_eq!(wrapped.width(), 90.0);
+        assert_eq!(wrapped.height(), 48.0);
+        assert!(!wrapped.truncated);
+
+        let unbounded = wrap_text(text, None, None, 12.0, ELLIPSIS, measure);
+        assert_eq!(lines(&unbounded), ["the quick brown fox", "", "jumps"]);
+    }
+
+    #[test]
+    fn words_wider_than_the_line_break_between_characters() {
+        let wrapped = wrap_text("a supercalifragilistic day", Some(60.0), None, 12.0, ELLIPSIS, measure);
+        assert_eq!(lines(&wrapped), ["a", "superc", "alifra", "gilist", "ic day"]);
+    }
+
+    #[test]
+    fn overflowing_lines_end_in_an_ellipsis() {
+        let wrapped = wrap_text("one two three four five", Some(90.0), Some(30.0), 12.0, "...", measure);
+        assert!(wrapped.truncated);
+        assert_eq!(lines(&wrapped), ["one two", "three..."]);
+        assert!(wrapped.lines.iter().all(|line| line.width <= 90.0));
+
+        // Too short for any line still shows the first
+        let wrapped = wrap_text("one two three", Some(90.0), Some(5.0), 12.0, "...", measure);
+        assert_eq!(lines(&wrapped), ["one tw..."]);
+    }
+}
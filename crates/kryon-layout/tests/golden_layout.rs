@@ -0,0 +1,76 @@
+// crates/kryon-layout/tests/golden_layout.rs
+//! Lays out every KRB in `tests/fixtures` at several viewport sizes and
+//! compares the result with `tests/golden_layouts/<fixture>_<W>x<H>.txt`.
+//!
+//! After an intentional layout change, regenerate the goldens with
+//! `KRYON_UPDATE_GOLDENS=1 cargo test -p kryon-layout --test golden_layout`
+//! and review the diff.
+
+use glam::Vec2;
+use kryon_core::load_krb_file;
+use kryon_layout::snapshot::{self, DEFAULT_TOLERANCE};
+use kryon_layout::{LayoutEngine, TaffyLayoutEngine};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const VIEWPORTS: [(u32, u32); 3] = [(800, 600), (1280, 720), (375, 667)];
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "krb"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn layout_snapshot(krb_path: &Path, (width, height): (u32, u32)) -> String {
+    let krb = load_krb_file(krb_path.to_str().unwrap())
+        .unwrap_or_else(|e| panic!("Failed to load {}: {}", krb_path.display(), e));
+    let root_id = krb.root_element_id.expect("fixture has no root element");
+
+    let mut engine = TaffyLayoutEngine::new();
+    let layout = engine.compute_layout(&krb.elements, root_id, Vec2::new(width as f32, height as f32));
+    snapshot::serialize(&krb.elements, &layout)
+}
+
+#[test]
+fn layouts_match_goldens() {
+    let golden_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden_layouts");
+    let update = std::env::var_os("KRYON_UPDATE_GOLDENS").is_some();
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixture KRBs found");
+
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        let stem = fixture.file_stem().unwrap().to_string_lossy();
+        for viewport in VIEWPORTS {
+            let name = format!("{}_{}x{}.txt", stem, viewport.0, viewport.1);
+            let golden_path = golden_dir.join(&name);
+            let actual = format!(
+                "# {} at {}x{}\n{}",
+                fixture.file_name().unwrap().to_string_lossy(), viewport.0, viewport.1,
+                layout_snapshot(fixture, viewport)
+            );
+
+            if update {
+                fs::create_dir_all(&golden_dir).unwrap();
+                fs::write(&golden_path, &actual).unwrap();
+                continue;
+            }
+
+            let Ok(expected) = fs::read_to_string(&golden_path) else {
+                failures.push(format!("{}: no golden (run with KRYON_UPDATE_GOLDENS=1 to create it)", name));
+                continue;
+            };
+            match snapshot::diff(&expected, &actual, DEFAULT_TOLERANCE) {
+                Ok(differences) => failures.extend(differences.into_iter().map(|d| format!("{}: {}", name, d))),
+                Err(e) => failures.push(format!("{}: {}", name, e)),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "layout differs from goldens:\n{}", failures.join("\n"));
+}
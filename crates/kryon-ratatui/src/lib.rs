@@ -1,18 +1,25 @@
 use glam::{Vec2, Vec4};
 use ratatui::{
     backend::Backend,
+    buffer::Buffer,
     layout::{Alignment, Rect},
-    style::{Color, Style},
-    widgets::{Block, Clear, Paragraph},
-    Frame, Terminal,
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Clear, Paragraph, Widget},
+    Terminal,
 };
 
 use kryon_core::TextAlignment;
-use kryon_render::{CommandRenderer, RenderCommand, RenderError, RenderResult, Renderer};
+use kryon_render::{CommandRenderer, DirtyRegion, RenderCommand, RenderError, RenderResult, Renderer, Unsupported};
+use kryon_render::dirty::command_bounds;
+use kryon_render::warnings::command_feature;
 
 pub struct RatatuiRenderer<B: Backend> {
     pub terminal: Terminal<B>,
     source_size: Vec2,
+    unsupported: Vec<Unsupported>,
+    /// The cells the last frame drew, for `execute_dirty` to draw over
+    last_buffer: Option<Buffer>,
 }
 
 pub struct RatatuiContext; // A simple marker context
@@ -27,6 +34,8 @@ impl<B: Backend> Renderer for RatatuiRenderer<B> {
         Ok(Self {
             terminal,
             source_size: Vec2::new(800.0, 600.0), // Default, will be updated
+            unsupported: Vec::new(),
+            last_buffer: None,
         })
     }
 
@@ -41,6 +50,7 @@ impl<B: Backend> Renderer for RatatuiRenderer<B> {
     fn render_element(&mut self, _c: &mut Self::Context, _: &kryon_core::Element, _: &kryon_layout::LayoutResult, _: kryon_core::ElementId) -> RenderResult<()> { Ok(()) }
     
     fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
+        self.last_buffer = None;
         self.terminal
             .resize(Rect::new(0, 0, new_size.x as u16, new_size.y as u16))
             .map_err(|e| RenderError::RenderFailed(format!("Terminal resize failed: {}", e)))
@@ -58,7 +68,7 @@ impl<B: Backend> CommandRenderer for RatatuiRenderer<B> {
         _context: &mut Self::Context,
         commands: &[RenderCommand],
     ) -> RenderResult<()> {
-        self.terminal.draw(|frame| {
+        let completed = self.terminal.draw(|frame| {
             // First Pass: Configuration
             for command in commands {
                 if let RenderCommand::SetCanvasSize(size) = command {
@@ -69,20 +79,102 @@ impl<B: Backend> CommandRenderer for RatatuiRenderer<B> {
             }
 
             // Second Pass: Drawing
-            render_commands_to_frame(commands, frame, self.source_size);
+            let area = frame.size();
+            render_commands_to_buffer(commands.iter().enumerate(), frame.buffer_mut(), area, self.source_size, &mut self.unsupported);
 
         }).map_err(|e| RenderError::RenderFailed(e.to_string()))?;
+        self.last_buffer = Some(completed.buffer.clone());
 
         Ok(())
     }
-}
 
-fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_canvas_size: Vec2) {
-    let terminal_area = frame.size();
+    /// Draws the commands that reach a dirty cell into an empty buffer and
+    /// copies just the dirty cells from it over the last frame's, so cells
+    /// outside keep what was drawn above them. Ratatui then writes only the
+    /// cells that differ to the terminal.
+    fn execute_dirty(
+        &mut self,
+        _context: &mut Self::Context,
+        commands: &[RenderCommand],
+        dirty: &DirtyRegion,
+    ) -> RenderResult<bool> {
+        let area = self.terminal.size().map_err(|e| RenderError::RenderFailed(e.to_string()))?;
+        let Some(last_buffer) = self.last_buffer.as_ref().filter(|buffer| buffer.area == area) else {
+            return Ok(false);
+        };
+        let source_size = self.source_size;
+        let dirty_cells: Vec<Rect> = dirty.rects.iter()
+            .filter_map(|&(position, size)| translate_rect(position, size, source_size, area))
+            .collect();
+
+        let mut redrawn = Buffer::empty(area);
+        let reaching = commands.iter().enumerate().filter(|(_, command)| {
+            // Unbounded commands didn't change, or the frame would be a full one
+            command_bounds(command).is_none_or(|(position, size)| {
+                translate_rect(position, size, source_size, area)
+                    .is_some_and(|cells| dirty_cells.iter().any(|dirty| dirty.intersects(cells)))
+            })
+        });
+        render_commands_to_buffer(reaching, &mut redrawn, area, source_size, &mut self.unsupported);
+
+        let completed = self.terminal.draw(|frame| {
+            let buffer = frame.buffer_mut();
+            buffer.clone_from(last_buffer);
+            for cells in &dirty_cells {
+                for y in cells.top()..cells.bottom() {
+                    for x in cells.left()..cells.right() {
+                        *buffer.get_mut(x, y) = redrawn.get(x, y).clone();
+                    }
+                }
+            }
+        }).map_err(|e| RenderError::RenderFailed(e.to_string()))?;
+        self.last_buffer = Some(completed.buffer.clone());
+
+        Ok(true)
+    }
+
+    /// Commands are in canvas coordinates scaled to the terminal, so its
+    /// size in cells says nothing about what's in view
+    fn needs_full_command_list(&self) -> bool {
+        true
+    }
+
+    /// A month grid squeezed into terminal cells is unreadable
+    fn prefers_list_popups(&self) -> bool {
+        true
+    }
+
+    /// Ratatui keeps the terminal as it was drawn
+    fn skips_unchanged_frames(&self) -> bool {
+        true
+    }
 
-    for command in commands {
+    fn backend_name(&self) -> &'static str {
+        "ratatui"
+    }
+
+    fn take_unsupported(&mut self) -> Vec<Unsupported> {
+        std::mem::take(&mut self.unsupported)
+    }
+}
+
+/// Draws `commands`, each with its index in the frame's command list
+fn render_commands_to_buffer<'a>(
+    commands: impl Iterator<Item = (usize, &'a RenderCommand)>,
+    buffer: &mut Buffer,
+    terminal_area: Rect,
+    app_canvas_size: Vec2,
+    unsupported: &mut Vec<Unsupported>,
+) {
+    for (index, command) in commands {
         match command {
-            RenderCommand::DrawRect { position, size, color, border_width, border_color, transform, .. } => {
+            RenderCommand::DrawRect { position, size, color, border_width, border_color, transform, shadow, .. } => {
+                if shadow.as_deref().is_some_and(|s| !s.is_empty() && s != "none") {
+                    unsupported.push(Unsupported::new(index, "shadows"));
+                }
+                if is_rotated(transform, *size) {
+                    unsupported.push(Unsupported::new(index, "rotation"));
+                }
                 let (final_position, final_size) = apply_transform_ratatui(*position, *size, transform);
                 if let Some(area) = translate_rect(final_position, final_size, app_canvas_size, terminal_area) {
                     let mut block = Block::default().style(Style::default().bg(vec4_to_ratatui_color(*color)));
@@ -90,17 +182,34 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                         block = block.borders(ratatui::widgets::Borders::ALL)
                                      .border_style(Style::default().fg(vec4_to_ratatui_color(*border_color)));
                     }
-                    frame.render_widget(Clear, area);
-                    frame.render_widget(block, area);
+                    Clear.render(area, buffer);
+                    block.render(area, buffer);
                 }
             }
-            RenderCommand::DrawText { position, text, alignment, color, max_width, transform, .. } => {
+            RenderCommand::DrawGradientRect { position, size, gradient, transform, .. } => {
+                if is_rotated(transform, *size) {
+                    unsupported.push(Unsupported::new(index, "rotation"));
+                }
+                // A strip per column or row of cells; corners are too small to round
+                let (final_position, final_size) = apply_transform_ratatui(*position, *size, transform);
+                let cell = app_canvas_size / Vec2::new(terminal_area.width.max(1) as f32, terminal_area.height.max(1) as f32);
+                for strip in kryon_render::gradient::strips(gradient, final_position, final_size, 0.0, cell.min_element()) {
+                    if let Some(area) = translate_rect(strip.position, strip.size, app_canvas_size, terminal_area) {
+                        Block::default().style(Style::default().bg(vec4_to_ratatui_color(strip.color))).render(area, buffer);
+                    }
+                }
+            }
+            RenderCommand::DrawText { position, text, alignment, color, max_width, max_height, transform, runs, .. } => {
                 let text_width = max_width.unwrap_or(text.len() as f32 * 8.0);
-                let text_size = Vec2::new(text_width, 16.0); 
+                // The text's box where layout gave one, which `command_bounds` reports too
+                let text_size = Vec2::new(text_width, max_height.unwrap_or(16.0));
+                if is_rotated(transform, text_size) {
+                    unsupported.push(Unsupported::new(index, "rotation"));
+                }
 
                 let (final_position, final_size) = apply_transform_ratatui(*position, text_size, transform);
                 if let Some(area) = translate_rect(final_position, final_size, app_canvas_size, terminal_area) {
-                    let paragraph = Paragraph::new(text.as_str())
+                    let paragraph = Paragraph::new(styled_text(text, runs))
                         .style(Style::default().fg(vec4_to_ratatui_color(*color)))
                         .alignment(match alignment {
                             TextAlignment::Start => Alignment::Left,
@@ -108,7 +217,7 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                             TextAlignment::End => Alignment::Right,
                             TextAlignment::Justify => Alignment::Left,
                         });
-                    frame.render_widget(paragraph, area);
+                    paragraph.render(area, buffer);
                 }
             }
             RenderCommand::SetCanvasSize(_) => {},
@@ -121,7 +230,7 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                         .borders(ratatui::widgets::Borders::ALL)
                         .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
                         .title("Canvas");
-                    frame.render_widget(block, area);
+                    block.render(area, buffer);
                 }
             }
             RenderCommand::EndCanvas => {
@@ -139,15 +248,17 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                         );
                         let block = ratatui::widgets::Block::default()
                             .style(ratatui::style::Style::default().bg(color));
-                        frame.render_widget(block, area);
+                        block.render(area, buffer);
                     }
                 }
             }
             RenderCommand::DrawCanvasCircle { center: _, radius: _, fill_color: _, stroke_color: _, stroke_width: _ } => {
                 // Terminal circles are difficult - skip for now
+                unsupported.push(Unsupported::new(index, "canvas circles and lines"));
             }
             RenderCommand::DrawCanvasLine { start: _, end: _, color: _, width: _ } => {
                 // Terminal lines are difficult - skip for now
+                unsupported.push(Unsupported::new(index, "canvas circles and lines"));
             }
             RenderCommand::DrawCanvasText { position, text, font_size: _, color, font_family: _, alignment: _ } => {
                 // Draw text within the canvas area
@@ -160,7 +271,7 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                     );
                     let paragraph = ratatui::widgets::Paragraph::new(text.as_str())
                         .style(ratatui::style::Style::default().fg(color));
-                    frame.render_widget(paragraph, area);
+                    paragraph.render(area, buffer);
                 }
             }
             // WASM View rendering commands
@@ -172,7 +283,7 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                         .borders(ratatui::widgets::Borders::ALL)
                         .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
                         .title("WASM View");
-                    frame.render_widget(block, area);
+                    block.render(area, buffer);
                 }
             }
             RenderCommand::EndWasmView => {
@@ -182,7 +293,11 @@ fn render_commands_to_frame(commands: &[RenderCommand], frame: &mut Frame, app_c
                 // In terminal mode, WASM execution is limited - just log it
                 // The actual WASM execution would happen elsewhere
             }
-            _ => {} 
+            _ => {
+                if let Some(feature) = command_feature(command) {
+                    unsupported.push(Unsupported::new(index, feature));
+                }
+            }
         }
     }
 }
@@ -213,64 +328,162 @@ fn translate_rect(source_pos: Vec2, source_size: Vec2, app_canvas_size: Vec2, te
     if final_rect.width > 0 && final_rect.height > 0 { Some(final_rect) } else { None }
 }
 
+/// `DrawText` runs as styled spans: their color, bold, italic, underline
+/// and strike-through. Lines break at newlines, as a plain string's do.
+fn styled_text<'a>(text: &'a str, runs: &'a [kryon_core::TextRun]) -> Text<'a> {
+    let mut lines = vec![Vec::new()];
+    for (piece, run) in kryon_core::text_segments(text, runs) {
+        let style = run.map_or_else(Style::default, |run| {
+            let mut style = Style::default();
+            if let Some(color) = run.color {
+                style = style.fg(vec4_to_ratatui_color(color));
+            }
+            if run.is_bold() {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            if run.is_italic() {
+                style = style.add_modifier(Modifier::ITALIC);
+            }
+            match run.text_decoration {
+                Some(kryon_core::RichTextDecoration::Underline) => style = style.add_modifier(Modifier::UNDERLINED),
+                Some(kryon_core::RichTextDecoration::LineThrough) => style = style.add_modifier(Modifier::CROSSED_OUT),
+                _ => {}
+            }
+            style
+        });
+        for (i, part) in piece.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !part.is_empty() {
+                lines.last_mut().expect("starts with a line").push(Span::styled(part, style));
+            }
+        }
+    }
+    Text::from(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+}
+
 fn vec4_to_ratatui_color(color: Vec4) -> Color {
     if color.w < 0.1 { return Color::Reset; }
     Color::Rgb((color.x * 255.0) as u8, (color.y * 255.0) as u8, (color.z * 255.0) as u8)
 }
 
-/// Apply basic transform to position and size for ratatui (text-based rendering)
-/// Note: ratatui has limited transform capabilities, so we only handle basic translation and scaling
+/// Whether `transform` rotates or skews, which `apply_transform_ratatui` drops
+fn is_rotated(transform: &Option<kryon_core::TransformData>, size: Vec2) -> bool {
+    transform.as_ref().is_some_and(|transform_data| {
+        let resolved = kryon_core::ResolvedTransform::resolve_2d(transform_data, size);
+        resolved.rotation != 0.0 || resolved.skew != Vec2::ZERO
+    })
+}
+
+/// Apply transform to position and size for ratatui (text-based rendering)
+/// Note: ratatui has limited transform capabilities, so rotation, skew and mirroring are ignored
+/// and 3D transforms are flattened
 fn apply_transform_ratatui(position: Vec2, size: Vec2, transform: &Option<kryon_core::TransformData>) -> (Vec2, Vec2) {
     let Some(transform_data) = transform else {
         return (position, size);
     };
-    
-    let mut final_position = position;
-    let mut final_size = size;
-    
-    // Apply transform properties
-    for property in &transform_data.properties {
-        match property.property_type {
-            kryon_core::TransformPropertyType::Scale => {
-                let scale_value = css_unit_to_value(&property.value);
-                final_size.x *= scale_value;
-                final_size.y *= scale_value;
-            }
-            kryon_core::TransformPropertyType::ScaleX => {
-                let scale_value = css_unit_to_value(&property.value);
-                final_size.x *= scale_value;
-            }
-            kryon_core::TransformPropertyType::ScaleY => {
-                let scale_value = css_unit_to_value(&property.value);
-                final_size.y *= scale_value;
-            }
-            kryon_core::TransformPropertyType::TranslateX => {
-                let translate_value = css_unit_to_value(&property.value);
-                final_position.x += translate_value;
-            }
-            kryon_core::TransformPropertyType::TranslateY => {
-                let translate_value = css_unit_to_value(&property.value);
-                final_position.y += translate_value;
-            }
-            // Note: Rotation and skew are not well-supported in text-based rendering
-            // We'll ignore them for now
-            _ => {
-                // Ignore unsupported transform properties in text-based rendering
-            }
+    let mut resolved = kryon_core::ResolvedTransform::resolve_2d(transform_data, size);
+    resolved.scale = resolved.scale.abs();
+    resolved.apply_to_rect(position, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_render::{FrameChange, FrameDiff};
+    use ratatui::backend::TestBackend;
+
+    fn rect(x: f32, color: Vec4, z_index: i32) -> RenderCommand {
+        RenderCommand::DrawRect {
+            position: Vec2::new(x, 0.0),
+            size: Vec2::new(40.0, 40.0),
+            color,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index,
         }
     }
-    
-    (final_position, final_size)
-}
 
-/// Convert CSS unit value to a simple float value for ratatui
-fn css_unit_to_value(unit_value: &kryon_core::CSSUnitValue) -> f32 {
-    match unit_value.unit {
-        kryon_core::CSSUnit::Pixels => unit_value.value as f32,
-        kryon_core::CSSUnit::Number => unit_value.value as f32,
-        kryon_core::CSSUnit::Em => unit_value.value as f32 * 16.0, // Assume 16px base
-        kryon_core::CSSUnit::Rem => unit_value.value as f32 * 16.0, // Assume 16px base
-        kryon_core::CSSUnit::Percentage => unit_value.value as f32 / 100.0,
-        _ => unit_value.value as f32, // Default fallback
+    fn drawn(commands: &[RenderCommand], renderer: Option<RatatuiRenderer<TestBackend>>, dirty: Option<&DirtyRegion>) -> Buffer {
+        let mut renderer = renderer.unwrap_or_else(|| RatatuiRenderer::initialize(TestBackend::new(10, 10)).unwrap());
+        let mut context = renderer.begin_frame(Vec4::ZERO).unwrap();
+        match dirty {
+            Some(dirty) => assert!(renderer.execute_dirty(&mut context, commands, dirty).unwrap()),
+            None => renderer.execute_commands(&mut context, commands).unwrap(),
+        }
+        renderer.terminal.backend().buffer().clone()
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn redrawing_the_dirty_region_matches_a_full_redraw() {
+        let (red, green, blue) = (Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 1.0, 0.0, 1.0), Vec4::new(0.0, 0.0, 1.0, 1.0));
+        let canvas = RenderCommand::SetCanvasSize(Vec2::new(100.0, 100.0));
+        // The green rect overlaps the red one and is drawn above it
+        let before = [canvas.clone(), rect(0.0, red, 0), rect(30.0, green, 1)];
+        let after = [canvas, rect(10.0, blue, 0), rect(30.0, green, 1)];
+        let origins = [None, Some(1), Some(2)];
+
+        let mut diff = FrameDiff::new();
+        diff.update(Vec2::splat(10.0), Vec4::ZERO, &before, &origins);
+        let FrameChange::Partial(dirty) = diff.update(Vec2::splat(10.0), Vec4::ZERO, &after, &origins) else {
+            panic!("only the first rect changed");
+        };
+
+        let mut renderer = RatatuiRenderer::initialize(TestBackend::new(10, 10)).unwrap();
+        let mut context = renderer.begin_frame(Vec4::ZERO).unwrap();
+        renderer.execute_commands(&mut context, &before).unwrap();
+        assert_eq!(drawn(&after, Some(renderer), Some(&dirty)), drawn(&after, None, None));
+    }
+
+    #[test]
+    fn gradients_are_drawn_as_strips_of_cells() {
+        let commands = [
+            RenderCommand::SetCanvasSize(Vec2::new(100.0, 100.0)),
+            RenderCommand::DrawGradientRect {
+                position: Vec2::ZERO,
+                size: Vec2::new(100.0, 50.0),
+                gradient: kryon_core::Gradient::parse("linear-gradient(to right, #f00, #00f)").unwrap(),
+                border_radius: 0.0,
+                transform: None,
+                z_index: 0,
+            },
+        ];
+        let buffer = drawn(&commands, None, None);
+        let background = |x: u16, y: u16| buffer.get(x, y).bg;
+        assert_eq!(background(0, 2), Color::Rgb(242, 0, 12));
+        assert_eq!(background(9, 2), Color::Rgb(12, 0, 242));
+        assert_eq!(background(0, 2), background(0, 4), "columns are one color");
+        assert_eq!(background(0, 7), Color::Reset, "below the box");
+    }
+
+    #[test]
+    fn text_runs_are_styled_cells() {
+        let (text, runs) = kryon_core::RichText::parse_markup("a<b><color=#00ff00>bc</color></b><u>d</u>").unwrap().to_runs();
+        let commands = [
+            RenderCommand::SetCanvasSize(Vec2::new(100.0, 100.0)),
+            RenderCommand::DrawText {
+                position: Vec2::ZERO,
+                text,
+                font_size: 16.0,
+                color: Vec4::ONE,
+                alignment: TextAlignment::Start,
+                max_width: Some(100.0),
+                max_height: Some(20.0),
+                transform: None,
+                font_family: None,
+                runs,
+                z_index: 0,
+            },
+        ];
+        let buffer = drawn(&commands, None, None);
+        let cell = |x: u16| buffer.get(x, 0).clone();
+        assert_eq!((cell(0).symbol(), cell(0).fg, cell(0).modifier), ("a", Color::Rgb(255, 255, 255), Modifier::empty()));
+        assert_eq!((cell(1).symbol(), cell(1).fg, cell(1).modifier), ("b", Color::Rgb(0, 255, 0), Modifier::BOLD));
+        assert_eq!(cell(2).modifier, Modifier::BOLD);
+        assert_eq!((cell(3).symbol(), cell(3).modifier), ("d", Modifier::UNDERLINED));
+    }
+}
@@ -1,26 +1,159 @@
 // crates/kryon-raylib/src/lib.rs
 use kryon_render::{
-    Renderer, CommandRenderer, RenderCommand, RenderResult, InputEvent, MouseButton, KeyCode, KeyModifiers, TextManager
+    Renderer, CommandRenderer, RenderCommand, RenderResult, InputEvent, MouseButton, KeyCode, KeyModifiers, TextManager,
+    BackendMemoryStats, MonitorInfo, WindowGeometry, WindowPlacement, Unsupported, BoxShadow, WarmUp,
+    ImageFilter, ImageRendering, MIPMAP_MIN_SIZE, wheel_scroll_delta, AnimatedImage, FrameClock,
+    GlyphAtlas, GlyphQuad, PositionedGlyph, RenderedText,
 };
-use kryon_core::{CursorType, TransformData, TransformPropertyType, CSSUnit};
-use kryon_layout::LayoutResult;
+use kryon_core::{tile_pieces, AssetResolver, CursorType, OutputRotation, ResourceType, ResolvedTransform};
+use kryon_layout::{wrap_text, LayoutResult};
 use glam::{Vec2, Vec4};
 use raylib::prelude::*;
 use raylib::ffi;
 use std::collections::HashMap;
+use std::time::Duration;
 use kryon_render::RenderError;
 
+/// The buttons polled each frame. GLFW reports a mouse's thumb buttons as
+/// its fourth and fifth (raylib's side and extra), and only some mice
+/// have the separate back and forward ones.
+const MOUSE_BUTTONS: [(raylib::consts::MouseButton, MouseButton); 7] = [
+    (raylib::consts::MouseButton::MOUSE_BUTTON_LEFT, MouseButton::Left),
+    (raylib::consts::MouseButton::MOUSE_BUTTON_RIGHT, MouseButton::Right),
+    (raylib::consts::MouseButton::MOUSE_BUTTON_MIDDLE, MouseButton::Middle),
+    (raylib::consts::MouseButton::MOUSE_BUTTON_SIDE, MouseButton::Back),
+    (raylib::consts::MouseButton::MOUSE_BUTTON_EXTRA, MouseButton::Forward),
+    (raylib::consts::MouseButton::MOUSE_BUTTON_BACK, MouseButton::Back),
+    (raylib::consts::MouseButton::MOUSE_BUTTON_FORWARD, MouseButton::Forward),
+];
+
+/// Width of the flat strips gradients are drawn as, in pixels
+const GRADIENT_STRIP_WIDTH: f32 = 2.0;
+/// Glyph quads sent to rlgl between checks that its batch has room
+const GLYPH_QUADS_PER_BATCH: usize = 1024;
+/// Distance between wrapped lines relative to the font size, as layout
+/// measures them
+const TEXT_LINE_HEIGHT: f32 = 1.2;
+
 pub struct RaylibRenderer {
     handle: RaylibHandle,
     thread: RaylibThread,
     size: Vec2,
     textures: HashMap<String, Texture2D>,
+    /// Filtering each texture was last set up for
+    texture_filters: HashMap<String, ImageRendering>,
     fonts: HashMap<String, Font>,  // Font cache: font_family_name -> Font
     font_paths: HashMap<String, String>,  // Font mappings: font_family_name -> file_path
     text_manager: TextManager,  // Cosmic-text integration
+    glyphs: GlyphRenderer,
     pending_commands: Vec<RenderCommand>,
     prev_mouse_pos: Vec2,
     current_cursor: CursorType,
+    clear_color: Vec4,
+    rotation: OutputRotation,
+    /// The window's DPI scale when input was last polled
+    scale: f32,
+    unsupported: Vec<Unsupported>,
+    /// Textures loaded since `take_decoded_images`, with their sizes
+    decoded_images: Vec<(String, Vec2)>,
+    /// Animated image sources, whose textures show the clock's frame
+    animations: HashMap<String, PlayingAnimation>,
+}
+
+/// An animated image and the frame its texture shows
+struct PlayingAnimation {
+    image: AnimatedImage,
+    clock: FrameClock,
+}
+
+/// Text shaped by cosmic-text, drawn as quads from one glyph atlas texture
+#[derive(Default)]
+struct GlyphRenderer {
+    atlas: GlyphAtlas,
+    /// Created on the first upload
+    texture: Option<ffi::Texture2D>,
+    /// The family names cosmic-text knows loaded fonts by, by the font
+    /// family they were registered as
+    families: HashMap<String, String>,
+}
+
+impl GlyphRenderer {
+    fn draw(&mut self, text_manager: &mut TextManager, text: &RenderedText, origin: Vec2) {
+        let quads = self.atlas.quads(text_manager, text, origin);
+        self.draw_quads(&quads);
+    }
+
+    /// Draws every quad in as few rlgl batches as fit them
+    fn draw_quads(&mut self, quads: &[GlyphQuad]) {
+        if quads.is_empty() {
+            return;
+        }
+        self.upload();
+        let Some(texture) = self.texture else {
+            return;
+        };
+        unsafe {
+            ffi::rlSetTexture(texture.id);
+            for batch in quads.chunks(GLYPH_QUADS_PER_BATCH) {
+                ffi::rlCheckRenderBatchLimit(batch.len() as i32 * 4);
+                ffi::rlBegin(ffi::RL_QUADS as i32);
+                ffi::rlNormal3f(0.0, 0.0, 1.0);
+                for quad in batch {
+                    let color = vec4_to_raylib_color(quad.color);
+                    let (min, max) = (quad.position, quad.position + quad.size);
+                    ffi::rlColor4ub(color.r, color.g, color.b, color.a);
+                    ffi::rlTexCoord2f(quad.uv_min.x, quad.uv_min.y);
+                    ffi::rlVertex2f(min.x, min.y);
+                    ffi::rlTexCoord2f(quad.uv_min.x, quad.uv_max.y);
+                    ffi::rlVertex2f(min.x, max.y);
+                    ffi::rlTexCoord2f(quad.uv_max.x, quad.uv_max.y);
+                    ffi::rlVertex2f(max.x, max.y);
+                    ffi::rlTexCoord2f(quad.uv_max.x, quad.uv_min.y);
+                    ffi::rlVertex2f(max.x, min.y);
+                }
+                ffi::rlEnd();
+            }
+            ffi::rlSetTexture(0);
+        }
+    }
+
+    /// Sends the atlas to the GPU if glyphs were packed since the last time
+    fn upload(&mut self) {
+        if !self.atlas.take_dirty() {
+            return;
+        }
+        let size = self.atlas.size() as i32;
+        let pixels = self.atlas.pixels().as_ptr() as *mut std::ffi::c_void;
+        unsafe {
+            match self.texture {
+                Some(texture) => {
+                    // Quads already batched keep the texels they were made for
+                    ffi::rlDrawRenderBatchActive();
+                    ffi::UpdateTexture(texture, pixels);
+                }
+                None => {
+                    let image = ffi::Image {
+                        data: pixels,
+                        width: size,
+                        height: size,
+                        mipmaps: 1,
+                        format: ffi::PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 as i32,
+                    };
+                    let texture = ffi::LoadTextureFromImage(image);
+                    ffi::SetTextureFilter(texture, ffi::TextureFilter::TEXTURE_FILTER_BILINEAR as i32);
+                    self.texture = Some(texture);
+                }
+            }
+        }
+    }
+
+    /// Frees the texture; it must go before the window does
+    fn release(&mut self) {
+        if let Some(texture) = self.texture.take() {
+            unsafe { ffi::UnloadTexture(texture) };
+        }
+        self.atlas.clear();
+    }
 }
 
 pub struct RaylibRenderContext {
@@ -32,36 +165,12 @@ impl Renderer for RaylibRenderer {
     type Context = RaylibRenderContext;
     
     fn initialize(surface: Self::Surface) -> RenderResult<Self> where Self: Sized {
-        let (width, height, title) = surface;
-        let (mut rl, thread) = raylib::init()
-            .size(width, height)
-            .title(&title)
-            .build();
-        
-        rl.set_target_fps(60);
-        
-        // Enable mouse cursor and ensure window can receive input
-        rl.show_cursor();
-        
-        eprintln!("[RAYLIB_INIT] Window initialized: {}x{}, cursor visible: {}", 
-            width, height, !rl.is_cursor_hidden());
-        
-        Ok(Self {
-            handle: rl,
-            thread,
-            size: Vec2::new(width as f32, height as f32),
-            textures: HashMap::new(),
-            fonts: HashMap::new(),
-            font_paths: HashMap::new(),
-            text_manager: TextManager::new(),
-            pending_commands: Vec::new(),
-            prev_mouse_pos: Vec2::new(-1.0, -1.0), // Initialize to invalid position
-            current_cursor: CursorType::Default,
-        })
+        Self::open(surface, false)
     }
     
-    fn begin_frame(&mut self, _clear_color: Vec4) -> RenderResult<Self::Context> {
+    fn begin_frame(&mut self, clear_color: Vec4) -> RenderResult<Self::Context> {
         self.pending_commands.clear();
+        self.clear_color = clear_color;
         Ok(RaylibRenderContext {})
     }
     
@@ -74,15 +183,34 @@ impl Renderer for RaylibRenderer {
         {
             let mut d = self.handle.begin_drawing(&self.thread);
             
-            // Clear with stored color if needed
-            let clear_color = Vec4::new(0.1, 0.1, 0.1, 1.0); // Default dark gray
-            let raylib_color = vec4_to_raylib_color(clear_color);
-            d.clear_background(raylib_color);
+            d.clear_background(vec4_to_raylib_color(self.clear_color));
+            // Raylib scales what's drawn to the HiDPI framebuffer; glyphs are
+            // rasterized at that size so text stays sharp
+            self.glyphs.atlas.set_scale(self.scale);
+            
+            // A rotated output draws through a 2D camera that maps logical
+            // coordinates onto the screen
+            let rotated = self.rotation != OutputRotation::None;
+            if rotated {
+                let screen = Vec2::new(d.get_screen_width() as f32, d.get_screen_height() as f32);
+                let offset = self.rotation.to_physical(self.rotation.logical_size(screen)).translation;
+                let camera = ffi::Camera2D {
+                    offset: ffi::Vector2 { x: offset.x, y: offset.y },
+                    target: ffi::Vector2 { x: 0.0, y: 0.0 },
+                    rotation: self.rotation.degrees(),
+                    zoom: 1.0,
+                };
+                unsafe { ffi::BeginMode2D(camera) };
+            }
             
             // Execute all commands without borrowing self
             for command in &commands {
 
-                Self::execute_single_command_impl(&mut d, &mut self.textures, &self.fonts, &mut self.text_manager, command)?;
+                Self::execute_single_command_impl(&mut d, &mut self.textures, &self.fonts, &mut self.text_manager, &mut self.glyphs, command)?;
+            }
+            
+            if rotated {
+                unsafe { ffi::EndMode2D() };
             }
         }
         
@@ -107,8 +235,13 @@ impl Renderer for RaylibRenderer {
         Ok(())
     }
     
+    /// The framebuffer's size, which is the window's times its DPI scale
     fn viewport_size(&self) -> Vec2 {
-        Vec2::new(self.handle.get_screen_width() as f32, self.handle.get_screen_height() as f32)
+        render_size()
+    }
+    
+    fn viewport_scale(&self) -> f32 {
+        self.scale
     }
 }
 
@@ -119,10 +252,27 @@ impl CommandRenderer for RaylibRenderer {
         commands: &[RenderCommand],
     ) -> RenderResult<()> {
         // Pre-load any textures we might need before adding to pending commands
-        for command in commands {
-            if let RenderCommand::DrawImage { source, .. } = command {
+        for (index, command) in commands.iter().enumerate() {
+            if let RenderCommand::DrawImage { source, image_rendering, filter, .. } = command {
                 // Try to load the texture (will cache it if successful)
                 let _ = self.load_texture(source); // Ignore errors here, will handle in drawing
+                self.set_texture_filter(source, *image_rendering);
+                // Filters a tint can't do are baked into a copy of the texture
+                if let Some(adjustments) = TexelAdjustments::of(filter) {
+                    let key = adjustments.texture_key(source);
+                    let _ = self.load_texture_as(&key, source, Some(adjustments));
+                    self.set_texture_filter(&key, *image_rendering);
+                }
+            }
+            if let RenderCommand::DrawTiledImage { source, image_rendering, .. } = command {
+                let _ = self.load_texture(source);
+                self.set_texture_filter(source, *image_rendering);
+            }
+            if let RenderCommand::DrawParticles { sprite: Some(sprite), .. } = command {
+                let _ = self.load_texture(sprite);
+            }
+            if let Some(feature) = Self::unsupported_feature(command) {
+                self.unsupported.push(Unsupported::new(index, feature));
             }
         }
         
@@ -134,9 +284,154 @@ impl CommandRenderer for RaylibRenderer {
     fn set_cursor(&mut self, cursor_type: CursorType) {
         self.set_cursor_internal(cursor_type);
     }
+    
+    fn memory_stats(&self) -> BackendMemoryStats {
+        // RGBA8 textures; each loaded font keeps its glyphs in one atlas texture,
+        // as does cosmic-text's shaped text, and animations keep every frame
+        // decoded to upload when it's due
+        let texture_bytes = |width: i32, height: i32| width.max(0) as usize * height.max(0) as usize * 4;
+        let shaped_atlas_bytes = self.glyphs.texture.map_or(0, |texture| texture_bytes(texture.width, texture.height));
+        BackendMemoryStats {
+            textures: self.textures.len(),
+            texture_bytes: self.textures.values().map(|texture| texture_bytes(texture.width, texture.height)).sum::<usize>()
+                + self.animations.values().map(|animation| animation.image.byte_size()).sum::<usize>(),
+            glyph_atlases: self.fonts.len() + self.glyphs.texture.is_some() as usize,
+            glyph_atlas_bytes: self.fonts.values().map(|font| texture_bytes(font.texture.width, font.texture.height)).sum::<usize>()
+                + shaped_atlas_bytes,
+            cached_glyphs: self.fonts.values().map(|font| font.glyphCount.max(0) as usize).sum::<usize>()
+                + self.glyphs.atlas.glyph_count(),
+        }
+    }
+    
+    fn set_output_rotation(&mut self, rotation: OutputRotation) -> bool {
+        self.rotation = rotation;
+        true
+    }
+    
+    fn backend_name(&self) -> &'static str {
+        "raylib"
+    }
+    
+    fn take_unsupported(&mut self) -> Vec<Unsupported> {
+        std::mem::take(&mut self.unsupported)
+    }
+    
+    fn take_decoded_images(&mut self) -> Vec<(String, Vec2)> {
+        std::mem::take(&mut self.decoded_images)
+    }
+    
+    fn text_baseline(&mut self) -> Option<f32> {
+        // Measured large so rounding to whole pixels doesn't skew the ratio
+        self.text_manager.first_baseline("Hg", 100.0).map(|baseline| baseline / 100.0)
+    }
+    
+    fn text_measurer(&mut self) -> Option<Box<dyn kryon_layout::TextMeasurer>> {
+        Some(Box::new(kryon_render::CosmicTextMeasurer::new()))
+    }
+    
+    fn warm_up(&mut self, work: &WarmUp) -> bool {
+        match work {
+            // Raylib rasterizes a font's glyphs when it loads it, at one size
+            WarmUp::Glyphs { font_family: Some(font_family), .. } => {
+                let _ = self.load_font(font_family);
+                true
+            }
+            WarmUp::Image { source } => {
+                let _ = self.load_texture(source);
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    fn clipboard_text(&mut self) -> Option<String> {
+        self.handle.get_clipboard_text().ok()
+    }
+    
+    fn set_clipboard_text(&mut self, text: &str) -> bool {
+        self.handle.set_clipboard_text(text).is_ok()
+    }
+    
+    /// Uploads each animation's frame over its texture when the frame is due
+    fn advance_animations(&mut self, delta: Duration) -> bool {
+        let mut changed = false;
+        for (source, animation) in &mut self.animations {
+            if !animation.clock.advance(delta) {
+                continue;
+            }
+            if let Some(texture) = self.textures.get_mut(source) {
+                texture.update_texture(&animation.image.frames[animation.clock.current()].pixels);
+                changed = true;
+            }
+        }
+        changed
+    }
 }
 
 impl RaylibRenderer {
+    /// Opens a transparent, undecorated, always-on-top window for HUDs.
+    /// Clicks pass through while `set_click_through` is on.
+    pub fn initialize_overlay(surface: (i32, i32, String)) -> RenderResult<Self> {
+        Self::open(surface, true)
+    }
+    
+    fn open((width, height, title): (i32, i32, String), overlay: bool) -> RenderResult<Self> {
+        // Draw at the display's pixel density; the window keeps its size in
+        // screen coordinates and raylib scales everything drawn
+        unsafe { ffi::SetConfigFlags(ffi::ConfigFlags::FLAG_WINDOW_HIGHDPI as u32) };
+        let mut builder = raylib::init();
+        builder.size(width, height).title(&title);
+        if overlay {
+            builder.transparent().undecorated();
+        }
+        let (mut rl, thread) = builder.build();
+        if overlay {
+            unsafe { ffi::SetWindowState(ffi::ConfigFlags::FLAG_WINDOW_TOPMOST as u32) };
+        }
+        
+        rl.set_target_fps(60);
+        
+        // Enable mouse cursor and ensure window can receive input
+        rl.show_cursor();
+        
+        eprintln!("[RAYLIB_INIT] Window initialized: {}x{}, cursor visible: {}", 
+            width, height, !rl.is_cursor_hidden());
+        
+        Ok(Self {
+            handle: rl,
+            thread,
+            size: Vec2::new(width as f32, height as f32),
+            textures: HashMap::new(),
+            texture_filters: HashMap::new(),
+            fonts: HashMap::new(),
+            font_paths: HashMap::new(),
+            text_manager: TextManager::new(),
+            glyphs: GlyphRenderer::default(),
+            pending_commands: Vec::new(),
+            prev_mouse_pos: Vec2::new(-1.0, -1.0), // Initialize to invalid position
+            current_cursor: CursorType::Default,
+            clear_color: Vec4::new(0.1, 0.1, 0.1, 1.0),
+            rotation: OutputRotation::None,
+            scale: window_scale(),
+            unsupported: Vec::new(),
+            decoded_images: Vec::new(),
+            animations: HashMap::new(),
+        })
+    }
+    
+    /// OS-level click-through: while on, the window ignores the mouse and
+    /// clicks reach whatever is underneath.
+    pub fn set_click_through(&mut self, enabled: bool) {
+        let flag = ffi::ConfigFlags::FLAG_WINDOW_MOUSE_PASSTHROUGH as u32;
+        unsafe {
+            if enabled {
+                ffi::SetWindowState(flag);
+            } else {
+                ffi::ClearWindowState(flag);
+            }
+        }
+    }
+    
     pub fn should_close(&self) -> bool {
         self.handle.window_should_close()
     }
@@ -153,15 +448,57 @@ impl RaylibRenderer {
     /// Load a texture from file and cache it for future use
     /// Tries multiple locations: current dir, relative to KRB file, etc.
     pub fn load_texture(&mut self, path: &str) -> RenderResult<()> {
-        if !self.textures.contains_key(path) {
-            let resolved_path = self.resolve_image_path(path);
+        self.load_texture_as(path, path, None)
+    }
+    
+    /// Loads the image at `path` as the texture cached under `key`, with
+    /// `adjustments` applied to its texels first
+    fn load_texture_as(&mut self, key: &str, path: &str, adjustments: Option<TexelAdjustments>) -> RenderResult<()> {
+        if !self.textures.contains_key(key) {
+            // The color picker's ramps are generated rather than loaded
+            if let Some(bitmap) = kryon_render::color_picker::builtin_texture(path) {
+                let texture = self.handle.load_texture_from_image(&self.thread, &image_from_rgba(bitmap.width, bitmap.height, &bitmap.pixels))
+                    .map_err(|e| RenderError::RenderFailed(format!("Failed to create texture: {}", e)))?;
+                texture.set_texture_filter(&self.thread, TextureFilter::TEXTURE_FILTER_BILINEAR);
+                self.texture_filters.insert(key.to_string(), ImageRendering::Smooth);
+                self.textures.insert(key.to_string(), texture);
+                return Ok(());
+            }
+            let resolved_path = self.resolve_image_path(kryon_render::icon::source_file(path));
             if let Some(actual_path) = resolved_path {
-                match raylib::texture::Image::load_image(&actual_path) {
-                    Ok(image) => {
-                        let texture = self.handle.load_texture_from_image(&self.thread, &image)
+                // Adjusted copies of an animation are of its first frame
+                let animation = match adjustments {
+                    None => load_animation(path, &actual_path),
+                    Some(_) => None,
+                };
+                let image = match &animation {
+                    Some(animation) => Ok(image_from_rgba(animation.width, animation.height, &animation.frames[0].pixels)),
+                    None => load_image(path, &actual_path),
+                };
+                match image {
+                    Ok(mut image) => {
+                        if let Some(adjustments) = adjustments {
+                            adjustments.apply(&mut image);
+                        }
+                        let mut texture = self.handle.load_texture_from_image(&self.thread, &image)
                             .map_err(|e| RenderError::RenderFailed(format!("Failed to create texture: {}", e)))?;
-                        self.textures.insert(path.to_string(), texture);
-                        eprintln!("[RAYLIB] Loaded and cached texture: {} (found at: {})", path, actual_path);
+                        // Large images are drawn trilinear between mip levels so they don't shimmer
+                        // when shrunk; an animation's frames only replace the full-size level
+                        if texture.width.max(texture.height) as u32 >= MIPMAP_MIN_SIZE && animation.is_none() {
+                            texture.gen_texture_mipmaps();
+                        }
+                        texture.set_texture_filter(&self.thread, texture_filter(&texture, ImageRendering::Auto));
+                        self.texture_filters.insert(key.to_string(), ImageRendering::Auto);
+                        if adjustments.is_none() {
+                            self.decoded_images.push((path.to_string(), Vec2::new(texture.width as f32, texture.height as f32)));
+                        }
+                        self.textures.insert(key.to_string(), texture);
+                        if let Some(animation) = animation {
+                            eprintln!("[RAYLIB] Playing {} frames of {}", animation.frames.len(), key);
+                            let clock = FrameClock::new(&animation);
+                            self.animations.insert(key.to_string(), PlayingAnimation { image: animation, clock });
+                        }
+                        eprintln!("[RAYLIB] Loaded and cached texture: {} (found at: {})", key, actual_path);
                     }
                     Err(e) => {
                         return Err(RenderError::ResourceNotFound(format!("Failed to load image {}: {}", actual_path, e)));
@@ -174,6 +511,17 @@ impl RaylibRenderer {
         Ok(())
     }
     
+    /// Sets the sampling of a loaded texture for how it's drawn next. A
+    /// texture drawn both pixelated and smooth in one frame is drawn the
+    /// way its last draw asks for.
+    fn set_texture_filter(&mut self, source: &str, image_rendering: ImageRendering) {
+        let Some(texture) = self.textures.get(source) else { return };
+        if self.texture_filters.get(source) != Some(&image_rendering) {
+            texture.set_texture_filter(&self.thread, texture_filter(texture, image_rendering));
+            self.texture_filters.insert(source.to_string(), image_rendering);
+        }
+    }
+    
     /// Resolve image path by checking multiple locations
     fn resolve_image_path(&self, path: &str) -> Option<String> {
         resolve_image_path_static(path)
@@ -194,6 +542,11 @@ impl RaylibRenderer {
                     match self.handle.load_font(&self.thread, &actual_path) {
                         Ok(font) => {
                             self.fonts.insert(font_family.to_string(), font);
+                            // Unrotated text in the font is shaped by cosmic-text
+                            // and drawn from the shared glyph atlas
+                            if let Some(family) = std::fs::read(&actual_path).ok().and_then(|data| self.text_manager.load_font_data(data)) {
+                                self.glyphs.families.insert(font_family.to_string(), family);
+                            }
                             eprintln!("[RAYLIB_FONT] Loaded and cached font '{}' from: {}", font_family, actual_path);
                         }
                         Err(e) => {
@@ -219,6 +572,54 @@ impl RaylibRenderer {
         self.fonts.get(font_family)
     }
     
+    /// Connected monitors. Raylib only reports the scale of the monitor the
+    /// window is on, so the others are listed at 1.0.
+    pub fn monitors(&self) -> Vec<MonitorInfo> {
+        unsafe {
+            let current = ffi::GetCurrentMonitor();
+            let window_scale = ffi::GetWindowScaleDPI();
+            (0..ffi::GetMonitorCount())
+                .map(|index| {
+                    let position = ffi::GetMonitorPosition(index);
+                    let name = ffi::GetMonitorName(index);
+                    MonitorInfo {
+                        name: if name.is_null() {
+                            format!("Monitor {}", index)
+                        } else {
+                            std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned()
+                        },
+                        position: Vec2::new(position.x, position.y),
+                        size: Vec2::new(ffi::GetMonitorWidth(index) as f32, ffi::GetMonitorHeight(index) as f32),
+                        scale_factor: if index == current { window_scale.x } else { 1.0 },
+                    }
+                })
+                .collect()
+        }
+    }
+    
+    /// Moves and maximizes the window as `placement` asks.
+    pub fn apply_placement(&mut self, placement: &WindowPlacement) {
+        if let Some(position) = placement.resolve(&self.monitors(), self.size) {
+            eprintln!("[RAYLIB_INIT] Placing window at {:?}", position);
+            unsafe { ffi::SetWindowPosition(position.x as i32, position.y as i32) };
+        }
+        if placement.maximized {
+            unsafe { ffi::MaximizeWindow() };
+        }
+    }
+    
+    /// The window's current position and size, for `--window-state`.
+    pub fn window_geometry(&self) -> WindowGeometry {
+        unsafe {
+            let position = ffi::GetWindowPosition();
+            WindowGeometry {
+                position: Vec2::new(position.x, position.y),
+                size: Vec2::new(ffi::GetScreenWidth() as f32, ffi::GetScreenHeight() as f32),
+                maximized: ffi::IsWindowMaximized(),
+            }
+        }
+    }
+    
     /// Manually poll input events from the OS - this is what EndDrawing() normally does
     pub fn poll_input_events_from_os(&mut self) {
         unsafe {
@@ -241,6 +642,16 @@ impl RaylibRenderer {
         }
     }
     
+    /// Modifier keys currently held down
+    fn modifiers(&self) -> KeyModifiers {
+        KeyModifiers {
+            shift: self.handle.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT),
+            ctrl: self.handle.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL),
+            alt: self.handle.is_key_down(KeyboardKey::KEY_LEFT_ALT) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_ALT),
+            meta: self.handle.is_key_down(KeyboardKey::KEY_LEFT_SUPER) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_SUPER),
+        }
+    }
+    
     pub fn poll_input_events(&mut self) -> Vec<InputEvent> {
         // CRITICAL: Poll input events from OS FIRST before querying any input state
         self.poll_input_events_from_os();
@@ -248,20 +659,19 @@ impl RaylibRenderer {
         let mut events = Vec::new();
         
         
-        // Handle window resize
-        if self.handle.is_window_resized() {
-            let new_size = Vec2::new(
-                self.handle.get_screen_width() as f32,
-                self.handle.get_screen_height() as f32
-            );
-            events.push(InputEvent::Resize { size: new_size });
+        // Handle window resize, or a move to a display with another scale
+        let scale = window_scale();
+        if self.handle.is_window_resized() || scale != self.scale {
+            self.scale = scale;
+            events.push(InputEvent::Resize { size: render_size() });
         }
         
-        // Handle mouse position - read fresh every frame
+        // Handle mouse position - read fresh every frame. Raylib reports it
+        // in screen coordinates; events carry framebuffer pixels.
         let mouse_pos = Vec2::new(
             self.handle.get_mouse_x() as f32,
             self.handle.get_mouse_y() as f32
-        );
+        ) * self.scale;
         
         // Generate mouse move events if position changed OR if this is the first time reading mouse position
         let is_first_mouse_read = self.prev_mouse_pos.x < 0.0; // Initial position is (-1, -1)
@@ -271,31 +681,30 @@ impl RaylibRenderer {
         }
         
         // Mouse button events
-        if self.handle.is_mouse_button_pressed(raylib::consts::MouseButton::MOUSE_BUTTON_LEFT) {
-            events.push(InputEvent::MousePress {
-                position: mouse_pos,
-                button: MouseButton::Left,
-            });
-        }
-        
-        if self.handle.is_mouse_button_released(raylib::consts::MouseButton::MOUSE_BUTTON_LEFT) {
-            events.push(InputEvent::MouseRelease {
-                position: mouse_pos,
-                button: MouseButton::Left,
-            });
-        }
-        
-        if self.handle.is_mouse_button_pressed(raylib::consts::MouseButton::MOUSE_BUTTON_RIGHT) {
-            events.push(InputEvent::MousePress {
-                position: mouse_pos,
-                button: MouseButton::Right,
-            });
+        for (raylib_button, button) in MOUSE_BUTTONS {
+            if self.handle.is_mouse_button_pressed(raylib_button) {
+                events.push(InputEvent::MousePress {
+                    position: mouse_pos,
+                    button,
+                    modifiers: self.modifiers(),
+                });
+            }
+            
+            if self.handle.is_mouse_button_released(raylib_button) {
+                events.push(InputEvent::MouseRelease {
+                    position: mouse_pos,
+                    button,
+                    modifiers: self.modifiers(),
+                });
+            }
         }
         
-        if self.handle.is_mouse_button_released(raylib::consts::MouseButton::MOUSE_BUTTON_RIGHT) {
-            events.push(InputEvent::MouseRelease {
-                position: mouse_pos,
-                button: MouseButton::Right,
+        // Wheel and trackpad scrolling; the runtime scrolls whatever the
+        // pointer from the move above is over
+        let wheel = unsafe { ffi::GetMouseWheelMoveV() };
+        if wheel.x != 0.0 || wheel.y != 0.0 {
+            events.push(InputEvent::Scroll {
+                delta: wheel_scroll_delta(Vec2::new(wheel.x, wheel.y), self.modifiers()),
             });
         }
         
@@ -304,24 +713,33 @@ impl RaylibRenderer {
             if let Some(kryon_key) = raylib_key_to_kryon_key(key) {
                 events.push(InputEvent::KeyPress {
                     key: kryon_key,
-                    modifiers: KeyModifiers {
-                        shift: self.handle.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT),
-                        ctrl: self.handle.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL),
-                        alt: self.handle.is_key_down(KeyboardKey::KEY_LEFT_ALT) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_ALT),
-                        meta: self.handle.is_key_down(KeyboardKey::KEY_LEFT_SUPER) || self.handle.is_key_down(KeyboardKey::KEY_RIGHT_SUPER),
-                    },
+                    modifiers: self.modifiers(),
                 });
             }
         }
         
         events
     }
-    
+
+    /// What `execute_single_command_impl` can't draw of `command`, if anything.
+    fn unsupported_feature(command: &RenderCommand) -> Option<&'static str> {
+        match command {
+            RenderCommand::DrawRect { size, border_radius, transform: Some(transform), .. }
+                if *border_radius > 0.0 && !is_axis_aligned(&flat_transform(transform, *size)) => Some("rounded corners on rotated rectangles"),
+            RenderCommand::DrawShaderEffect { .. } => Some("shader effects"),
+            RenderCommand::DrawTiledImage { transform: Some(_), .. } => Some("transformed background images"),
+            RenderCommand::DrawGradientRect { transform: Some(_), .. } => Some("transformed gradients"),
+            RenderCommand::NativeRendererView { backend, .. } if backend != "raylib" => Some("native renderer views"),
+            _ => None,
+        }
+    }
+
     fn execute_single_command_impl(
         d: &mut RaylibDrawHandle,
         textures: &mut HashMap<String, Texture2D>,
         fonts: &HashMap<String, Font>,
         text_manager: &mut TextManager,
+        glyphs: &mut GlyphRenderer,
         command: &RenderCommand,
     ) -> RenderResult<()> {
         match command {
@@ -329,7 +747,7 @@ impl RaylibRenderer {
                 position,
                 size,
                 color,
-                border_radius: _,
+                border_radius,
                 border_width,
                 border_color,
                 transform,
@@ -338,25 +756,14 @@ impl RaylibRenderer {
             } => {
                 let rect = Rectangle::new(position.x, position.y, size.x, size.y);
                 let raylib_color = vec4_to_raylib_color(*color);
+                let fill = (color.w > 0.0).then_some(raylib_color);
+                let border = (*border_width > 0.0).then(|| (*border_width, vec4_to_raylib_color(*border_color)));
                 
-                // Draw shadow if specified
-                if let Some(shadow_str) = shadow {
-                    if !shadow_str.is_empty() && shadow_str != "none" {
-                        // Parse shadow string: "offset-x offset-y blur-radius spread-radius color"
-                        // Example: "0 4px 6px rgba(0, 0, 0, 0.1)"
-                        if let Some(shadow_values) = parse_box_shadow(shadow_str) {
-                            let shadow_rect = Rectangle::new(
-                                rect.x + shadow_values.offset_x,
-                                rect.y + shadow_values.offset_y,
-                                rect.width + shadow_values.spread_radius * 2.0,
-                                rect.height + shadow_values.spread_radius * 2.0,
-                            );
-                            
-                            // For now, draw a simple shadow without blur (Raylib limitation)
-                            // In a real implementation, you'd render multiple offset rectangles with decreasing opacity for blur
-                            let shadow_color = vec4_to_raylib_color(shadow_values.color);
-                            d.draw_rectangle_rec(shadow_rect, shadow_color);
-                        }
+                // Blurred shadows are drawn as stacked translucent layers
+                if let Some(box_shadow) = shadow.as_deref().and_then(BoxShadow::parse) {
+                    for layer in box_shadow.layers(*position, *size, *border_radius) {
+                        let layer_rect = Rectangle::new(layer.position.x, layer.position.y, layer.size.x, layer.size.y);
+                        draw_rounded_rect(d, layer_rect, layer.border_radius, Some(vec4_to_raylib_color(layer.color)), None);
                     }
                 }
                 
@@ -364,60 +771,52 @@ impl RaylibRenderer {
                 eprintln!("[RAYLIB_DRAW] DrawRect pos=({}, {}), size=({}, {}), color=({}, {}, {}, {})", 
                     position.x, position.y, size.x, size.y, color.x, color.y, color.z, color.w);
                 
+                // raylib has no call for skewed rectangles, so those are drawn as quads
+                let skewed = transform.as_ref()
+                    .map(|transform_data| flat_transform(transform_data, *size))
+                    .filter(|transform| transform.skew != Vec2::ZERO);
+                
                 // Apply transform if present
-                if let Some(transform_data) = transform {
-                    let (scale, rotation, translation) = extract_transform_values(transform_data);
-                    
-                    // Apply transformations manually (modern Raylib API)
-                    let center_x = position.x + size.x / 2.0;
-                    let center_y = position.y + size.y / 2.0;
-                    
-                    // Calculate transformed rectangle
-                    let transformed_rect = Rectangle::new(
-                        center_x - (size.x * scale.x) / 2.0 + translation.x,
-                        center_y - (size.y * scale.y) / 2.0 + translation.y,
-                        size.x * scale.x,
-                        size.y * scale.y
-                    );
-                    
-                    // Draw filled rectangle with transform
-                    if color.w > 0.0 {
-                        if rotation != 0.0 {
-                            // Use draw_rectangle_pro for rotation
-                            d.draw_rectangle_pro(
-                                transformed_rect,
-                                Vector2::new(transformed_rect.width / 2.0, transformed_rect.height / 2.0),
-                                rotation.to_degrees(),
-                                raylib_color
-                            );
-                        } else {
-                            d.draw_rectangle_rec(transformed_rect, raylib_color);
+                if let Some(skewed) = skewed {
+                    if let Some(corners) = skewed.corners(*position, *size) {
+                        if color.w > 0.0 {
+                            draw_quad(d, &corners, raylib_color);
+                        }
+                        if *border_width > 0.0 {
+                            let border_raylib_color = vec4_to_raylib_color(*border_color);
+                            for (index, corner) in corners.iter().enumerate() {
+                                let next = corners[(index + 1) % corners.len()];
+                                d.draw_line_ex(
+                                    Vector2::new(corner.x, corner.y),
+                                    Vector2::new(next.x, next.y),
+                                    *border_width,
+                                    border_raylib_color
+                                );
+                            }
                         }
                     }
+                } else if let Some(transform_data) = transform {
+                    let transform = flat_transform(transform_data, *size);
                     
-                    // Draw border
-                    if *border_width > 0.0 {
-                        let border_raylib_color = vec4_to_raylib_color(*border_color);
-                        d.draw_rectangle_lines_ex(
-                            transformed_rect, 
-                            *border_width, 
-                            border_raylib_color
-                        );
-                    }
-                } else {
-                    // Draw without transform (original behavior)
-                    if color.w > 0.0 {
-                        d.draw_rectangle_rec(rect, raylib_color);
-                    }
+                    // Calculate transformed rectangle (scaled about the origin, not yet rotated)
+                    let (rect_position, rect_size) = transform.apply_to_rect(*position, *size);
+                    let transformed_rect = Rectangle::new(rect_position.x, rect_position.y, rect_size.x, rect_size.y);
                     
-                    if *border_width > 0.0 {
-                        let border_raylib_color = vec4_to_raylib_color(*border_color);
-                        d.draw_rectangle_lines_ex(
-                            rect, 
-                            *border_width, 
-                            border_raylib_color
-                        );
+                    if transform.rotation == 0.0 {
+                        // Corners scale with the rectangle
+                        draw_rounded_rect(d, transformed_rect, *border_radius * transform.scale.min_element(), fill, border);
+                    } else {
+                        // draw_rectangle_pro puts the pivot at the rect's x/y and rotates about it
+                        if let Some(color) = fill {
+                            let (pivot_rect, pivot) = rotation_pivot(&transform, transformed_rect);
+                            d.draw_rectangle_pro(pivot_rect, pivot, transform.rotation.to_degrees(), color);
+                        }
+                        if let Some((width, color)) = border {
+                            d.draw_rectangle_lines_ex(transformed_rect, width, color);
+                        }
                     }
+                } else {
+                    draw_rounded_rect(d, rect, *border_radius, fill, border);
                 }
             }
             RenderCommand::DrawRichText {
@@ -439,31 +838,13 @@ impl RaylibRenderer {
                 
                 // Apply transform offset if present
                 let base_offset = if let Some(transform_data) = transform {
-                    let (_, _, translation) = extract_transform_values(transform_data);
+                    let translation = ResolvedTransform::resolve_2d(transform_data, rendered.bounds).translation;
                     Vec2::new(position.x + translation.x, position.y + translation.y)
                 } else {
                     *position
                 };
                 
-                // Render each positioned glyph
-                for glyph in &rendered.glyphs {
-                    let glyph_pos = base_offset + glyph.position;
-                    let raylib_color = vec4_to_raylib_color(glyph.color);
-                    
-                    // For now, render each character as individual text
-                    // TODO: Implement proper glyph texture atlas rendering
-                    let char_str = glyph.character.to_string();
-                    d.draw_text(
-                        &char_str,
-                        glyph_pos.x as i32,
-                        glyph_pos.y as i32,
-                        glyph.font_size as i32,
-                        raylib_color,
-                    );
-                }
-                
-                eprintln!("[RAYLIB_RICH_TEXT] Rendered {} glyphs, bounds: {:?}", 
-                    rendered.glyphs.len(), rendered.bounds);
+                glyphs.draw(text_manager, &rendered, base_offset);
             }
             RenderCommand::DrawText {
                 position,
@@ -475,8 +856,29 @@ impl RaylibRenderer {
                 max_height,
                 transform,
                 font_family,
+                runs,
                 z_index: _,
             } => {
+                // Styled runs are shaped by cosmic-text and drawn glyph by
+                // glyph, like DrawRichText
+                if !runs.is_empty() {
+                    draw_text_runs(d, text_manager, glyphs, text, runs, *position, *font_size, *color, *max_width);
+                    return Ok(());
+                }
+                // So is unrotated text in a font cosmic-text has loaded
+                let shaped_family = font_family.as_ref().filter(|_| transform.is_none()).and_then(|name| glyphs.families.get(name)).cloned();
+                if let Some(family) = shaped_family {
+                    let wrapped = text_manager.wrap_text(text, *font_size, Some(&family), *max_width, *max_height);
+                    let top = wrapped_text_top(*alignment, position.y, *max_height, wrapped.height());
+                    for (index, line) in wrapped.lines.iter().enumerate() {
+                        let mut span = kryon_core::TextSpan::new(line.text.as_str()).with_font_size(*font_size);
+                        span.font_family = Some(family.clone());
+                        let rendered = text_manager.render_rich_text(&kryon_core::RichText::from_spans(vec![span]), None, *color);
+                        let x = wrapped_line_x(*alignment, position.x, *max_width, line.width);
+                        glyphs.draw(text_manager, &rendered, Vec2::new(x, top + wrapped.line_top(index)));
+                    }
+                    return Ok(());
+                }
                 let raylib_color = vec4_to_raylib_color(*color);
                 
                 // Determine which font to use
@@ -521,27 +923,29 @@ impl RaylibRenderer {
                 
                 // Apply transform if present
                 if let Some(transform_data) = transform {
-                    let (scale, rotation, translation) = extract_transform_values(transform_data);
-                    
-                    // Apply transformations using raylib's transformation matrix
-                    let center_x = text_x + text_width / 2.0;
-                    let center_y = text_y + text_height / 2.0;
+                    let text_size = Vec2::new(text_width, text_height);
+                    let transform = flat_transform(transform_data, text_size);
+                    let scale = transform.scale;
                     
-                    // Apply transformations manually (modern Raylib API)
-                    let transformed_x = center_x - (text_width * scale.x) / 2.0 + translation.x;
-                    let transformed_y = center_y - (text_height * scale.y) / 2.0 + translation.y;
+                    // Scale about the transform origin
+                    let (text_position, scaled_size) = transform.apply_to_rect(Vec2::new(text_x, text_y), text_size);
+                    let transformed_x = text_position.x;
+                    let transformed_y = text_position.y;
                     
                     // Draw text with transform
-                    if rotation != 0.0 {
-                        // For rotation, use draw_text_pro if available, otherwise fall back to basic draw_text
-                        // Note: draw_text_pro may not be available in all Raylib versions
+                    if transform.rotation != 0.0 {
+                        // Only custom fonts can be drawn rotated; the default font falls back to basic draw_text
                         if let Some(font) = custom_font {
+                            let (pivot_rect, pivot) = rotation_pivot(
+                                &transform,
+                                Rectangle::new(transformed_x, transformed_y, scaled_size.x, scaled_size.y),
+                            );
                             d.draw_text_pro(
                                 font,
                                 text,
-                                Vector2::new(transformed_x, transformed_y),
-                                Vector2::zero(),
-                                0.0, // rotation
+                                Vector2::new(pivot_rect.x, pivot_rect.y),
+                                pivot,
+                                transform.rotation.to_degrees(),
                                 *font_size as f32 * scale.y,
                                 1.0, // spacing
                                 raylib_color,
@@ -578,26 +982,38 @@ impl RaylibRenderer {
                         }
                     }
                 } else {
-                    // Draw without transform (original behavior)
-                    if let Some(font) = custom_font {
-                        d.draw_text_pro(
-                            font,
-                            text,
-                            Vector2::new(text_x, text_y),
-                            Vector2::zero(),
-                            0.0, // rotation
-                            *font_size,
-                            1.0, // spacing
-                            raylib_color,
-                        );
-                    } else {
-                        d.draw_text(
-                            text,
-                            text_x as i32,
-                            text_y as i32,
-                            *font_size as i32,
-                            raylib_color,
-                        );
+                    // Wrapped to the box, each line aligned on its own. Raylib
+                    // fonts only have ASCII, so overflow ends in "..."
+                    let wrapped = wrap_text(text, *max_width, *max_height, *font_size * TEXT_LINE_HEIGHT, "...", |piece| match custom_font {
+                        Some(font) => d.measure_text(piece, font.base_size()) as f32 * *font_size / font.base_size() as f32,
+                        None => d.measure_text(piece, *font_size as i32) as f32,
+                    });
+                    // The last line is only as tall as the font, as a single line always was
+                    let block_height = wrapped.height() - wrapped.line_height + text_height;
+                    let top = if wrapped.lines.len() == 1 { text_y } else { wrapped_text_top(*alignment, position.y, *max_height, block_height) };
+                    for (index, line) in wrapped.lines.iter().enumerate() {
+                        let x = wrapped_line_x(*alignment, position.x, *max_width, line.width);
+                        let y = top + wrapped.line_top(index);
+                        if let Some(font) = custom_font {
+                            d.draw_text_pro(
+                                font,
+                                &line.text,
+                                Vector2::new(x, y),
+                                Vector2::zero(),
+                                0.0, // rotation
+                                *font_size,
+                                1.0, // spacing
+                                raylib_color,
+                            );
+                        } else {
+                            d.draw_text(
+                                &line.text,
+                                x as i32,
+                                y as i32,
+                                *font_size as i32,
+                                raylib_color,
+                            );
+                        }
                     }
                 }
             }
@@ -607,30 +1023,28 @@ impl RaylibRenderer {
                 source,
                 opacity,
                 transform,
+                filter,
+                ..
             } => {
                 eprintln!("[RAYLIB] DrawImage match arm reached for: {}", source);
                 
-                // Check if we have a cached texture
-                if let Some(texture) = textures.get(source) {
+                // Check if we have a cached texture, preferring the copy with the filter baked in
+                let filtered = TexelAdjustments::of(filter).map(|adjustments| adjustments.texture_key(source));
+                if let Some(texture) = filtered.and_then(|key| textures.get(&key)).or_else(|| textures.get(source)) {
                     // Draw the actual texture
                     let dest_rect = Rectangle::new(position.x, position.y, size.x, size.y);
                     let source_rect = Rectangle::new(0.0, 0.0, texture.width as f32, texture.height as f32);
-                    let tint = Color::new(255, 255, 255, (*opacity * 255.0) as u8);
+                    let tint = filter_tint(filter, *opacity);
                     
                     // Apply transform if present
                     if let Some(transform_data) = transform {
-                        let (scale, rotation, translation) = extract_transform_values(transform_data);
-                        
-                        // Apply transformations manually (modern Raylib API)
-                        let center_x = position.x + size.x / 2.0;
-                        let center_y = position.y + size.y / 2.0;
+                        let transform = flat_transform(transform_data, *size);
                         
-                        // Calculate transformed rectangle
-                        let transformed_dest = Rectangle::new(
-                            center_x - (size.x * scale.x) / 2.0 + translation.x,
-                            center_y - (size.y * scale.y) / 2.0 + translation.y,
-                            size.x * scale.x,
-                            size.y * scale.y
+                        // Scale about the transform origin, then rotate about it
+                        let (dest_position, dest_size) = transform.apply_to_rect(*position, *size);
+                        let (transformed_dest, pivot) = rotation_pivot(
+                            &transform,
+                            Rectangle::new(dest_position.x, dest_position.y, dest_size.x, dest_size.y),
                         );
                         
                         // Draw texture with transform
@@ -638,8 +1052,8 @@ impl RaylibRenderer {
                             texture,
                             source_rect,
                             transformed_dest,
-                            Vector2::new(transformed_dest.width / 2.0, transformed_dest.height / 2.0),
-                            rotation.to_degrees(),
+                            pivot,
+                            transform.rotation.to_degrees(),
                             tint,
                         );
                     } else {
@@ -658,7 +1072,7 @@ impl RaylibRenderer {
                         source, position.x, position.y, size.x, size.y);
                 } else {
                     // No cached texture - draw appropriate placeholder
-                    let resolved_path = resolve_image_path_static(source);
+                    let resolved_path = resolve_image_path_static(kryon_render::icon::source_file(source));
                     if resolved_path.is_some() {
                         // File exists but failed to load or wasn't cached
                         let error_color = Color::new(150, 50, 50, (*opacity * 255.0) as u8);
@@ -716,6 +1130,44 @@ impl RaylibRenderer {
                     }
                 }
             }
+            RenderCommand::DrawTiledImage {
+                position,
+                size,
+                source,
+                tile_offset,
+                tile_size,
+                repeat,
+                opacity,
+                ..
+            } => {
+                // Raylib has no wrapping texture draw, so each tile is drawn
+                // clipped to the box; transforms aren't applied
+                if let Some(texture) = textures.get(source) {
+                    let texture_size = Vec2::new(texture.width as f32, texture.height as f32);
+                    let tint = Color::new(255, 255, 255, (*opacity * 255.0) as u8);
+                    for piece in tile_pieces(*size, *tile_offset, *tile_size, *repeat) {
+                        let uv_min = piece.uv_min * texture_size;
+                        let uv_size = (piece.uv_max - piece.uv_min) * texture_size;
+                        d.draw_texture_pro(
+                            texture,
+                            Rectangle::new(uv_min.x, uv_min.y, uv_size.x, uv_size.y),
+                            Rectangle::new(position.x + piece.position.x, position.y + piece.position.y, piece.size.x, piece.size.y),
+                            Vector2::zero(),
+                            0.0,
+                            tint,
+                        );
+                    }
+                }
+            }
+            RenderCommand::DrawGradientRect { position, size, gradient, border_radius, .. } => {
+                // Flat strips a couple of pixels wide; transforms aren't applied
+                for strip in kryon_render::gradient::strips(gradient, *position, *size, *border_radius, GRADIENT_STRIP_WIDTH) {
+                    d.draw_rectangle_rec(
+                        Rectangle::new(strip.position.x, strip.position.y, strip.size.x, strip.size.y),
+                        vec4_to_raylib_color(strip.color),
+                    );
+                }
+            }
             RenderCommand::SetClip { position, size } => {
                 let _scissor = d.begin_scissor_mode(
                     position.x as i32,
@@ -738,26 +1190,24 @@ impl RaylibRenderer {
                 background_color,
                 border_color,
                 border_width,
-                border_radius: _,
+                border_radius,
                 is_focused,
                 is_readonly: _,
+                caret,
+                scroll_offset,
+                blink_phase,
                 transform: _,
             } => {
-                // Draw background
+                // Draw background and border
                 let rect = Rectangle::new(position.x, position.y, size.x, size.y);
                 let bg_color = vec4_to_raylib_color(*background_color);
-                d.draw_rectangle_rec(rect, bg_color);
-                
-                // Draw border
-                if *border_width > 0.0 {
-                    let border_raylib_color = vec4_to_raylib_color(*border_color);
-                    d.draw_rectangle_lines_ex(rect, *border_width, border_raylib_color);
-                }
+                let border = (*border_width > 0.0).then(|| (*border_width, vec4_to_raylib_color(*border_color)));
+                draw_rounded_rect(d, rect, *border_radius, Some(bg_color), border);
                 
                 // Draw focus indicator if focused
                 if *is_focused {
                     let focus_color = Color::BLUE;
-                    d.draw_rectangle_lines_ex(rect, 2.0, focus_color);
+                    draw_rounded_rect(d, rect, *border_radius, None, Some((2.0, focus_color)));
                 }
                 
                 // Draw text or placeholder
@@ -767,12 +1217,25 @@ impl RaylibRenderer {
                     text
                 };
                 
+                // The value scrolls within the input, so it's cut to its box
+                let mut s = d.begin_scissor_mode(position.x as i32, position.y as i32, size.x as i32, size.y as i32);
+                if let Some(caret) = caret {
+                    let rects = kryon_render::text_input::caret_rects(*position, *size, text, *font_size, *caret, *scroll_offset, *blink_phase);
+                    if let Some((selection_position, selection_size)) = rects.selection {
+                        let selection_color = vec4_to_raylib_color(kryon_render::text_input::SELECTION_COLOR);
+                        s.draw_rectangle_rec(Rectangle::new(selection_position.x, selection_position.y, selection_size.x, selection_size.y), selection_color);
+                    }
+                    if let Some((caret_position, caret_size)) = rects.caret {
+                        let caret_color = vec4_to_raylib_color(*text_color);
+                        s.draw_rectangle_rec(Rectangle::new(caret_position.x, caret_position.y, caret_size.x, caret_size.y), caret_color);
+                    }
+                }
                 if !display_text.is_empty() {
                     let text_raylib_color = vec4_to_raylib_color(*text_color);
-                    let text_x = position.x + 5.0; // Small padding
+                    let text_x = position.x + kryon_render::text_input::TEXT_INPUT_PADDING - *scroll_offset;
                     let text_y = position.y + (size.y - *font_size) / 2.0; // Vertically center
                     
-                    d.draw_text(display_text, text_x as i32, text_y as i32, *font_size as i32, text_raylib_color);
+                    s.draw_text(display_text, text_x as i32, text_y as i32, *font_size as i32, text_raylib_color);
                 }
             },
             RenderCommand::DrawCheckbox {
@@ -1145,6 +1608,26 @@ impl RaylibRenderer {
                 // WASM function execution would be handled by a separate WASM runtime
                 // This command is just a marker for the rendering pipeline
             }
+            RenderCommand::DrawParticles { particles, sprite, z_index: _ } => {
+                // Raylib batches consecutive quads internally, so drawing each particle
+                // back-to-back with the same texture/shape stays a single batch.
+                let sprite_texture = sprite.as_ref().and_then(|s| textures.get(s));
+                for particle in particles {
+                    let half = particle.size / 2.0;
+                    let dest = Rectangle::new(particle.position.x, particle.position.y, particle.size, particle.size);
+                    let color = vec4_to_raylib_color(particle.color);
+                    if let Some(texture) = sprite_texture {
+                        let source_rect = Rectangle::new(0.0, 0.0, texture.width as f32, texture.height as f32);
+                        d.draw_texture_pro(texture, source_rect, dest, Vector2::new(half, half), particle.rotation, color);
+                    } else {
+                        d.draw_rectangle_pro(dest, Vector2::new(half, half), particle.rotation, color);
+                    }
+                }
+            }
+            RenderCommand::DrawShaderEffect { .. } => {
+                // Raylib has no WGSL pipeline; the element's background rect
+                // (already drawn) is the static-color fallback.
+            }
             RenderCommand::NativeRendererView { position, size, backend, script_name, element_id, config: _, z_index: _ } => {
                 // Handle NativeRendererView rendering for Raylib backend
                 if backend == "raylib" {
@@ -1202,6 +1685,79 @@ impl RaylibRenderer {
     }
 }
 
+/// Left edge of a wrapped line in a box `max_width` wide
+fn wrapped_line_x(alignment: kryon_core::TextAlignment, x: f32, max_width: Option<f32>, line_width: f32) -> f32 {
+    match alignment {
+        kryon_core::TextAlignment::Center => x + (max_width.unwrap_or(line_width) - line_width) / 2.0,
+        kryon_core::TextAlignment::End => x + max_width.unwrap_or(line_width) - line_width,
+        kryon_core::TextAlignment::Start | kryon_core::TextAlignment::Justify => x,
+    }
+}
+
+/// Top of the first wrapped line; centered text is centered as a block
+fn wrapped_text_top(alignment: kryon_core::TextAlignment, y: f32, max_height: Option<f32>, height: f32) -> f32 {
+    match alignment {
+        kryon_core::TextAlignment::Center => y + (max_height.unwrap_or(height) - height) / 2.0,
+        _ => y,
+    }
+}
+
+/// Draws `text` with its styled runs from the glyph atlas: bold glyphs
+/// are drawn twice a pixel apart, and underlines and strike-throughs are
+/// bars under or through the glyphs of their runs
+#[allow(clippy::too_many_arguments)]
+fn draw_text_runs(
+    d: &mut RaylibDrawHandle,
+    text_manager: &mut TextManager,
+    glyphs: &mut GlyphRenderer,
+    text: &str,
+    runs: &[kryon_core::TextRun],
+    position: Vec2,
+    font_size: f32,
+    color: Vec4,
+    max_width: Option<f32>,
+) {
+    let rendered = text_manager.render_runs(text, runs, font_size, color, max_width);
+    let run_of = |glyph: &PositionedGlyph| runs.iter().find(|run| run.range.contains(&glyph.text_range.start));
+
+    let mut quads = glyphs.atlas.quads(text_manager, &rendered, position);
+    let bold: Vec<GlyphQuad> = quads.iter()
+        .filter(|quad| run_of(&rendered.glyphs[quad.glyph]).is_some_and(|run| run.is_bold()))
+        .map(|quad| GlyphQuad { position: quad.position + Vec2::X, ..*quad })
+        .collect();
+    quads.extend(bold);
+    glyphs.draw_quads(&quads);
+
+    let thickness = (font_size / 14.0).max(1.0);
+    for glyph in &rendered.glyphs {
+        let bar_y = match run_of(glyph).and_then(|run| run.text_decoration) {
+            Some(kryon_core::RichTextDecoration::Underline) => Some(glyph.font_size * 0.95),
+            Some(kryon_core::RichTextDecoration::LineThrough) => Some(glyph.font_size * 0.55),
+            Some(kryon_core::RichTextDecoration::Overline) => Some(0.0),
+            _ => None,
+        };
+        if let Some(bar_y) = bar_y {
+            let glyph_pos = position + glyph.position;
+            d.draw_rectangle_v(
+                Vector2::new(glyph_pos.x, glyph_pos.y + bar_y),
+                Vector2::new(glyph.size.x.max(1.0), thickness),
+                vec4_to_raylib_color(glyph.color),
+            );
+        }
+    }
+}
+
+/// Framebuffer pixels per screen coordinate, 1 where raylib reports none
+fn window_scale() -> f32 {
+    let dpi = unsafe { ffi::GetWindowScaleDPI() };
+    if dpi.x.is_finite() && dpi.x > 0.0 { dpi.x } else { 1.0 }
+}
+
+/// The framebuffer's size in pixels
+fn render_size() -> Vec2 {
+    unsafe { Vec2::new(ffi::GetRenderWidth() as f32, ffi::GetRenderHeight() as f32) }
+}
+
 fn vec4_to_raylib_color(color: Vec4) -> Color {
     let r = (color.x * 255.0) as u8;
     let g = (color.y * 255.0) as u8;
@@ -1211,72 +1767,81 @@ fn vec4_to_raylib_color(color: Vec4) -> Color {
     Color::new(r, g, b, a)
 }
 
-/// Extract transform values from TransformData
-fn extract_transform_values(transform: &TransformData) -> (Vec2, f32, Vec2) {
-    let mut scale = Vec2::new(1.0, 1.0);
-    let mut rotation = 0.0f32;
-    let mut translation = Vec2::new(0.0, 0.0);
-    
-    for property in &transform.properties {
-        match property.property_type {
-            TransformPropertyType::Scale => {
-                let value = css_unit_to_pixels(&property.value);
-                scale = Vec2::new(value, value);
-            }
-            TransformPropertyType::ScaleX => {
-                scale.x = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::ScaleY => {
-                scale.y = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::TranslateX => {
-                translation.x = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::TranslateY => {
-                translation.y = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::Rotate => {
-                rotation = css_unit_to_radians(&property.value);
-            }
-            // Add other transform properties as needed
-            _ => {
-                eprintln!("[RAYLIB_TRANSFORM] Unsupported transform property: {:?}", property.property_type);
-            }
-        }
-    }
-    
-    (scale, rotation, translation)
+/// The transform for drawing with raylib's 2D calls. 3D transforms are
+/// flattened (see `kryon_core::transform`); mirroring is dropped, since
+/// raylib won't draw shapes with a negative size.
+fn flat_transform(transform: &kryon_core::TransformData, size: Vec2) -> ResolvedTransform {
+    let mut resolved = ResolvedTransform::resolve_2d(transform, size);
+    resolved.scale = resolved.scale.abs();
+    resolved
 }
 
-/// Convert CSS unit value to pixels (simplified)
-fn css_unit_to_pixels(unit_value: &kryon_core::CSSUnitValue) -> f32 {
-    match unit_value.unit {
-        CSSUnit::Pixels => unit_value.value as f32,
-        CSSUnit::Number => unit_value.value as f32,
-        CSSUnit::Em => unit_value.value as f32 * 16.0, // Assume 16px base
-        CSSUnit::Rem => unit_value.value as f32 * 16.0, // Assume 16px base
-        CSSUnit::Percentage => unit_value.value as f32 / 100.0,
-        _ => {
-            eprintln!("[RAYLIB_TRANSFORM] Unsupported CSS unit for size: {:?}", unit_value.unit);
-            unit_value.value as f32
-        }
+/// Whether raylib's axis-aligned calls can draw a shape under `transform`
+fn is_axis_aligned(transform: &ResolvedTransform) -> bool {
+    transform.rotation == 0.0 && transform.skew == Vec2::ZERO
+}
+
+/// raylib's roundness for a corner `radius`: the share of half the shorter
+/// side that each corner curves over. A radius too large for the rectangle
+/// is clamped, so the short sides become semicircles.
+fn roundness(rect: Rectangle, radius: f32) -> f32 {
+    let shorter = rect.width.min(rect.height);
+    if shorter <= 0.0 || radius <= 0.0 {
+        0.0
+    } else {
+        (2.0 * radius / shorter).min(1.0)
     }
 }
 
-/// Convert CSS unit value to radians for rotation
-fn css_unit_to_radians(unit_value: &kryon_core::CSSUnitValue) -> f32 {
-    match unit_value.unit {
-        CSSUnit::Degrees => unit_value.value as f32 * std::f32::consts::PI / 180.0,
-        CSSUnit::Radians => unit_value.value as f32,
-        CSSUnit::Turns => unit_value.value as f32 * 2.0 * std::f32::consts::PI,
-        _ => {
-            eprintln!("[RAYLIB_TRANSFORM] Unsupported CSS unit for rotation: {:?}", unit_value.unit);
-            unit_value.value as f32
+/// Fills and outlines a rectangle whose corners are rounded by `radius`,
+/// falling back to the plain calls when the radius is 0. The border is
+/// drawn inside the rectangle, as `draw_rectangle_lines_ex` draws it.
+fn draw_rounded_rect(d: &mut RaylibDrawHandle, rect: Rectangle, radius: f32, fill: Option<Color>, border: Option<(f32, Color)>) {
+    let radius = radius.min(rect.width.min(rect.height) / 2.0);
+    if radius <= 0.0 {
+        if let Some(color) = fill {
+            d.draw_rectangle_rec(rect, color);
+        }
+        if let Some((width, color)) = border {
+            d.draw_rectangle_lines_ex(rect, width, color);
+        }
+        return;
+    }
+    
+    // 0 segments lets raylib pick enough for the radius
+    if let Some(color) = fill {
+        d.draw_rectangle_rounded(rect, roundness(rect, radius), 0, color);
+    }
+    if let Some((width, color)) = border {
+        // raylib strokes outwards from the rectangle it's given, so stroke
+        // from one inset by the border width, with the radius inset to match
+        let width = width.min(rect.width.min(rect.height) / 2.0);
+        let inner = Rectangle::new(rect.x + width, rect.y + width, rect.width - 2.0 * width, rect.height - 2.0 * width);
+        if inner.width <= 0.0 || inner.height <= 0.0 {
+            d.draw_rectangle_rounded(rect, roundness(rect, radius), 0, color);
+        } else {
+            d.draw_rectangle_rounded_lines(inner, roundness(inner, (radius - width).max(0.0)), 0, width, color);
         }
     }
 }
 
+/// Fills a quad given clockwise or counter-clockwise as two triangles.
+/// raylib culls triangles that aren't counter-clockwise on screen, and
+/// mirrored transforms flip the winding, so each triangle is ordered here.
+fn draw_quad(d: &mut RaylibDrawHandle, corners: &[Vec2; 4], color: Color) {
+    for [a, b, c] in [[corners[0], corners[1], corners[2]], [corners[0], corners[2], corners[3]]] {
+        let (b, c) = if (b - a).perp_dot(c - a) > 0.0 { (c, b) } else { (b, c) };
+        d.draw_triangle(Vector2::new(a.x, a.y), Vector2::new(b.x, b.y), Vector2::new(c.x, c.y), color);
+    }
+}
 
+/// The `*_pro` calls take the rotation pivot relative to the destination
+/// rect and place that pivot at the rect's x/y. Converts an unrotated,
+/// already scaled rect into that form.
+fn rotation_pivot(transform: &ResolvedTransform, rect: Rectangle) -> (Rectangle, Vector2) {
+    let pivot = transform.origin * transform.scale;
+    (Rectangle::new(rect.x + pivot.x, rect.y + pivot.y, rect.width, rect.height), Vector2::new(pivot.x, pivot.y))
+}
 
 fn raylib_key_to_kryon_key(key: KeyboardKey) -> Option<KeyCode> {
     match key {
@@ -1286,6 +1851,14 @@ fn raylib_key_to_kryon_key(key: KeyboardKey) -> Option<KeyCode> {
         KeyboardKey::KEY_TAB => Some(KeyCode::Tab),
         KeyboardKey::KEY_BACKSPACE => Some(KeyCode::Backspace),
         KeyboardKey::KEY_DELETE => Some(KeyCode::Delete),
+        KeyboardKey::KEY_LEFT => Some(KeyCode::ArrowLeft),
+        KeyboardKey::KEY_RIGHT => Some(KeyCode::ArrowRight),
+        KeyboardKey::KEY_UP => Some(KeyCode::ArrowUp),
+        KeyboardKey::KEY_DOWN => Some(KeyCode::ArrowDown),
+        KeyboardKey::KEY_HOME => Some(KeyCode::Home),
+        KeyboardKey::KEY_END => Some(KeyCode::End),
+        KeyboardKey::KEY_PAGE_UP => Some(KeyCode::PageUp),
+        KeyboardKey::KEY_PAGE_DOWN => Some(KeyCode::PageDown),
         
         // Convert letters to characters
         KeyboardKey::KEY_A => Some(KeyCode::Character('a')),
@@ -1346,194 +1919,123 @@ fn raylib_key_to_kryon_key(key: KeyboardKey) -> Option<KeyCode> {
 
 /// Resolve font path by checking multiple locations  
 fn resolve_font_path_static(path: &str) -> Option<String> {
-    // Try the path as-is first (relative to current working directory)
-    if std::path::Path::new(path).exists() {
-        eprintln!("[RAYLIB_FONT] Found font at current path: {}", path);
-        return Some(path.to_string());
-    }
-    
-    // Get the KRB file path from command line args if available
-    let args: Vec<String> = std::env::args().collect();
-    if let Some(krb_arg) = args.iter().find(|arg| arg.ends_with(".krb")) {
-        if let Some(krb_dir) = std::path::Path::new(krb_arg).parent() {
-            let krb_relative_path = krb_dir.join(path);
-            if krb_relative_path.exists() {
-                let resolved = krb_relative_path.to_string_lossy().to_string();
-                eprintln!("[RAYLIB_FONT] Found font relative to KRB: {}", resolved);
-                return Some(resolved);
-            }
+    match AssetResolver::from_args().resolve(path, &ResourceType::Font) {
+        Some(resolved) => {
+            eprintln!("[RAYLIB_FONT] Found font: {}", resolved.display());
+            Some(resolved.to_string_lossy().into_owned())
         }
-    }
-    
-    // Try some common relative paths for fonts
-    let common_paths = [
-        format!("assets/fonts/{}", path),
-        format!("fonts/{}", path),
-        format!("resources/fonts/{}", path),
-        format!("assets/{}", path),
-    ];
-    
-    for test_path in &common_paths {
-        if std::path::Path::new(test_path).exists() {
-            eprintln!("[RAYLIB_FONT] Found font at common path: {}", test_path);
-            return Some(test_path.clone());
+        None => {
+            eprintln!("[RAYLIB_FONT] Font not found in any location: {}", path);
+            None
         }
     }
-    
-    eprintln!("[RAYLIB_FONT] Font not found in any location: {}", path);
-    None
 }
 
-/// Resolve image path by checking multiple locations
-fn resolve_image_path_static(path: &str) -> Option<String> {
-    // Try the path as-is first (relative to current working directory)
-    if std::path::Path::new(path).exists() {
-        eprintln!("[RAYLIB] Found image at current path: {}", path);
-        return Some(path.to_string());
+/// The parts of an `ImageFilter` a tint can't do, as raylib's image color
+/// operations do them: grayscale, brightening and contrast. They're applied
+/// to a copy of the texture's image when it's loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TexelAdjustments {
+    grayscale: bool,
+    /// Added to each channel, out of 255
+    brightness: i32,
+    /// `ImageColorContrast`'s -100..=100, which scales by `((100 + c) / 100)²`
+    contrast: i32,
+}
+
+impl TexelAdjustments {
+    /// `None` if the tint does all of `filter`. Grayscale rounds to none or
+    /// full, and brightening adds rather than multiplies; darkening is left
+    /// to the tint, which does it exactly.
+    fn of(filter: &ImageFilter) -> Option<Self> {
+        let adjustments = Self {
+            grayscale: filter.grayscale >= 0.5,
+            brightness: ((filter.brightness - 1.0).max(0.0) * 255.0).round().min(255.0) as i32,
+            contrast: ((filter.contrast.sqrt() - 1.0) * 100.0).round().clamp(-100.0, 100.0) as i32,
+        };
+        (adjustments != Self { grayscale: false, brightness: 0, contrast: 0 }).then_some(adjustments)
     }
-    
-    // Get the KRB file path from command line args if available
-    let args: Vec<String> = std::env::args().collect();
-    if let Some(krb_arg) = args.iter().find(|arg| arg.ends_with(".krb")) {
-        if let Some(krb_dir) = std::path::Path::new(krb_arg).parent() {
-            let krb_relative_path = krb_dir.join(path);
-            if krb_relative_path.exists() {
-                let resolved = krb_relative_path.to_string_lossy().to_string();
-                eprintln!("[RAYLIB] Found image relative to KRB: {}", resolved);
-                return Some(resolved);
-            }
-        }
+
+    /// Where the adjusted copy of `source` is cached among the textures
+    fn texture_key(&self, source: &str) -> String {
+        format!("{}#grayscale={},brightness={},contrast={}", source, self.grayscale, self.brightness, self.contrast)
     }
-    
-    // Try some common relative paths
-    let common_paths = [
-        format!("assets/{}", path),
-        format!("images/{}", path),
-        format!("resources/{}", path),
-    ];
-    
-    for test_path in &common_paths {
-        if std::path::Path::new(test_path).exists() {
-            eprintln!("[RAYLIB] Found image at common path: {}", test_path);
-            return Some(test_path.clone());
+
+    fn apply(&self, image: &mut raylib::texture::Image) {
+        if self.grayscale {
+            image.color_grayscale();
+        }
+        if self.brightness != 0 {
+            image.color_brightness(self.brightness);
+        }
+        if self.contrast != 0 {
+            image.color_contrast(self.contrast as f32);
         }
     }
-    
-    eprintln!("[RAYLIB] Image not found in any location: {}", path);
-    None
 }
 
-#[derive(Debug)]
-struct BoxShadowValues {
-    offset_x: f32,
-    offset_y: f32,
-    blur_radius: f32,
-    spread_radius: f32,
-    color: Vec4,
+/// The tint that does the rest of `filter` on a texture drawn at `opacity`:
+/// its tint color, and any darkening
+fn filter_tint(filter: &ImageFilter, opacity: f32) -> Color {
+    let darken = filter.brightness.min(1.0);
+    vec4_to_raylib_color((filter.tint * Vec4::new(darken, darken, darken, opacity)).clamp(Vec4::ZERO, Vec4::ONE))
 }
 
-/// Parse CSS box-shadow string
-/// Format: "offset-x offset-y blur-radius spread-radius color"
-/// Example: "0 4px 6px rgba(0, 0, 0, 0.1)" or "2px 2px 4px #00000040"
-fn parse_box_shadow(shadow_str: &str) -> Option<BoxShadowValues> {
-    let parts: Vec<&str> = shadow_str.trim().split_whitespace().collect();
-    
-    if parts.len() < 3 {
-        return None; // Need at least offset-x, offset-y, and color
+/// Point sampling for pixelated images; otherwise bilinear, or trilinear
+/// if the texture has mipmaps
+fn texture_filter(texture: &Texture2D, image_rendering: ImageRendering) -> TextureFilter {
+    match image_rendering {
+        ImageRendering::Pixelated => TextureFilter::TEXTURE_FILTER_POINT,
+        ImageRendering::Auto | ImageRendering::Smooth if texture.mipmaps > 1 => TextureFilter::TEXTURE_FILTER_TRILINEAR,
+        ImageRendering::Auto | ImageRendering::Smooth => TextureFilter::TEXTURE_FILTER_BILINEAR,
     }
-    
-    // Parse offset-x and offset-y (required)
-    let offset_x = parse_pixel_value(parts.get(0)?);
-    let offset_y = parse_pixel_value(parts.get(1)?);
-    
-    // Parse optional blur-radius and spread-radius
-    let mut blur_radius = 0.0;
-    let mut spread_radius = 0.0;
-    let mut color_index = 2;
-    
-    // Check if we have more numeric values before the color
-    if parts.len() > 3 {
-        // Try to parse as numeric value
-        if let Ok(_) = parts[2].trim_end_matches("px").parse::<f32>() {
-            blur_radius = parse_pixel_value(parts[2]);
-            color_index = 3;
-            
-            if parts.len() > 4 {
-                if let Ok(_) = parts[3].trim_end_matches("px").parse::<f32>() {
-                    spread_radius = parse_pixel_value(parts[3]);
-                    color_index = 4;
-                }
-            }
-        }
-    }
-    
-    // Parse color (could be rgba() function or hex)
-    let color_str = if color_index < parts.len() {
-        if parts[color_index].starts_with("rgba(") {
-            // Reconstruct rgba() function from remaining parts
-            parts[color_index..].join(" ")
-        } else {
-            parts[color_index].to_string()
-        }
-    } else {
-        "#00000040".to_string() // Default shadow color
+}
+
+/// Loads the image for `source` from the file at `path`, rasterizing it
+/// for an icon source and decoding it otherwise
+fn load_image(source: &str, path: &str) -> Result<raylib::texture::Image, String> {
+    let Some((_, size)) = kryon_render::icon::parse_icon_source(source) else {
+        return raylib::texture::Image::load_image(path).map_err(|e| e.to_string());
     };
-    
-    let color = parse_color_string(&color_str)?;
-    
-    Some(BoxShadowValues {
-        offset_x,
-        offset_y,
-        blur_radius,
-        spread_radius,
-        color,
-    })
+    let icon = kryon_render::icon::load_icon(std::path::Path::new(path), size).map_err(|e| e.to_string())?;
+    Ok(image_from_rgba(icon.width, icon.height, &icon.pixels))
 }
 
-/// Simple pixel value parser for shadow values
-fn parse_pixel_value(value: &str) -> f32 {
-    if value.ends_with("px") {
-        value.trim_end_matches("px").parse().unwrap_or(0.0)
-    } else {
-        value.parse().unwrap_or(0.0)
+/// The animation in the file at `path`, if it holds more than one frame.
+/// Icons and files that fail to decode as animations load as still images.
+fn load_animation(source: &str, path: &str) -> Option<AnimatedImage> {
+    if kryon_render::icon::parse_icon_source(source).is_some() {
+        return None;
     }
+    kryon_render::animated_image::decode(std::path::Path::new(path))
+        .unwrap_or_else(|e| {
+            eprintln!("[RAYLIB] Loading {} as a still image: {}", path, e);
+            None
+        })
 }
 
-/// Parse color string (hex or rgba)
-fn parse_color_string(color_str: &str) -> Option<Vec4> {
-    let color_str = color_str.trim();
-    
-    if color_str.starts_with("rgba(") && color_str.ends_with(")") {
-        // Parse rgba(r, g, b, a)
-        let inner = &color_str[5..color_str.len()-1];
-        let parts: Vec<&str> = inner.split(',').collect();
-        if parts.len() == 4 {
-            let r = parts[0].trim().parse::<f32>().ok()? / 255.0;
-            let g = parts[1].trim().parse::<f32>().ok()? / 255.0;
-            let b = parts[2].trim().parse::<f32>().ok()? / 255.0;
-            let a = parts[3].trim().parse::<f32>().ok()?;
-            return Some(Vec4::new(r, g, b, a));
+/// An RGBA8 image of straight RGBA rows
+fn image_from_rgba(width: u32, height: u32, pixels: &[u8]) -> raylib::texture::Image {
+    let mut image = raylib::texture::Image::gen_image_color(width as i32, height as i32, Color::BLANK);
+    for (index, texel) in pixels.chunks_exact(4).enumerate() {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        image.draw_pixel(x as i32, y as i32, Color::new(texel[0], texel[1], texel[2], texel[3]));
+    }
+    image
+}
+
+/// Resolve image path by checking multiple locations
+fn resolve_image_path_static(path: &str) -> Option<String> {
+    match AssetResolver::from_args().resolve(path, &ResourceType::Image) {
+        Some(resolved) => {
+            eprintln!("[RAYLIB] Found image: {}", resolved.display());
+            Some(resolved.to_string_lossy().into_owned())
         }
-    } else if color_str.starts_with("#") {
-        // Parse hex color
-        let hex = &color_str[1..];
-        if hex.len() == 6 {
-            // #RRGGBB
-            let r = u8::from_str_radix(&hex[0..2], 16).ok()? as f32 / 255.0;
-            let g = u8::from_str_radix(&hex[2..4], 16).ok()? as f32 / 255.0;
-            let b = u8::from_str_radix(&hex[4..6], 16).ok()? as f32 / 255.0;
-            return Some(Vec4::new(r, g, b, 1.0));
-        } else if hex.len() == 8 {
-            // #RRGGBBAA
-            let r = u8::from_str_radix(&hex[0..2], 16).ok()? as f32 / 255.0;
-            let g = u8::from_str_radix(&hex[2..4], 16).ok()? as f32 / 255.0;
-            let b = u8::from_str_radix(&hex[4..6], 16).ok()? as f32 / 255.0;
-            let a = u8::from_str_radix(&hex[6..8], 16).ok()? as f32 / 255.0;
-            return Some(Vec4::new(r, g, b, a));
+        None => {
+            eprintln!("[RAYLIB] Image not found in any location: {}", path);
+            None
         }
     }
-    
-    None
 }
 
 impl Drop for RaylibRenderer {
@@ -1541,10 +2043,12 @@ impl Drop for RaylibRenderer {
         // Clean up all loaded textures before raylib context is destroyed
         eprintln!("[RAYLIB] Cleaning up {} textures", self.textures.len());
         self.textures.clear();
+        self.animations.clear();
         
         // Clean up all loaded fonts before raylib context is destroyed
         eprintln!("[RAYLIB] Cleaning up {} fonts", self.fonts.len());
         self.fonts.clear();
+        self.glyphs.release();
         
         eprintln!("[RAYLIB] Resource cleanup complete");
     }
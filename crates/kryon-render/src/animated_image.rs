@@ -0,0 +1,219 @@
+// crates/kryon-render/src/animated_image.rs
+//! Animated images: GIF, animated WebP and APNG.
+//!
+//! Backends try `decode` on an image source's file before their own loader.
+//! A file with more than one frame comes back as an [`AnimatedImage`], each
+//! frame composited onto the full canvas as straight RGBA rows, so drawing
+//! a frame is uploading it over the texture of the one before. Still files,
+//! including single-frame GIFs and plain PNGs, come back as `None` and load
+//! as usual.
+//!
+//! [`FrameClock`] plays an animation on the app's delta time. Animations
+//! loop forever; the loop counts files carry are ignored. Delays under
+//! 20 ms play at 100 ms, as browsers do, since encoders write 0 to mean
+//! "as fast as possible". Decoding stops at `MAX_ANIMATION_BYTES` of frames
+//! and plays the ones decoded so far.
+
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, Frames};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Frame delays shorter than this are treated as `DEFAULT_FRAME_DELAY`
+pub const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+pub const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+/// The most decoded frame data one animation keeps
+pub const MAX_ANIMATION_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum AnimationError {
+    #[error("failed to read image: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to decode image: {0}")]
+    Decode(#[from] image::ImageError),
+}
+
+/// One frame of an animation
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationFrame {
+    /// Straight (not premultiplied) RGBA rows, `width * height * 4` bytes
+    pub pixels: Vec<u8>,
+    /// How long the frame shows
+    pub delay: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatedImage {
+    pub width: u32,
+    pub height: u32,
+    /// At least two
+    pub frames: Vec<AnimationFrame>,
+}
+
+impl AnimatedImage {
+    /// Builds an animation from decoded frames; `None` for fewer than two
+    /// or frames of different sizes
+    pub fn from_frames(width: u32, height: u32, frames: Vec<AnimationFrame>) -> Option<Self> {
+        let frame_bytes = width as usize * height as usize * 4;
+        (frames.len() > 1 && frames.iter().all(|frame| frame.pixels.len() == frame_bytes)).then_some(Self { width, height, frames })
+    }
+
+    /// Decoded bytes held by the frames
+    pub fn byte_size(&self) -> usize {
+        self.frames.iter().map(|frame| frame.pixels.len()).sum()
+    }
+}
+
+/// Whether `path` names a format that can hold an animation
+pub fn may_be_animated(path: &Path) -> bool {
+    matches!(extension(path).as_deref(), Some("gif" | "webp" | "png" | "apng"))
+}
+
+/// The animation in the image file at `path`; `None` if it's a still image
+/// or not a GIF, WebP or PNG
+pub fn decode(path: &Path) -> Result<Option<AnimatedImage>, AnimationError> {
+    if !may_be_animated(path) {
+        return Ok(None);
+    }
+    let reader = BufReader::new(File::open(path)?);
+    let frames = match extension(path).as_deref() {
+        Some("gif") => GifDecoder::new(reader)?.into_frames(),
+        Some("webp") => {
+            let decoder = WebPDecoder::new(reader)?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => {
+            let decoder = PngDecoder::new(reader)?;
+            if !decoder.is_apng() {
+                return Ok(None);
+            }
+            decoder.apng().into_frames()
+        }
+    };
+    collect_frames(frames)
+}
+
+fn collect_frames(frames: Frames<'_>) -> Result<Option<AnimatedImage>, AnimationError> {
+    let mut size = None;
+    let mut decoded = Vec::new();
+    let mut bytes = 0;
+    for frame in frames {
+        let frame = frame?;
+        let delay = Duration::from(frame.delay());
+        let buffer = frame.into_buffer();
+        let (width, height) = *size.get_or_insert(buffer.dimensions());
+        if buffer.dimensions() != (width, height) {
+            continue;
+        }
+        bytes += buffer.len();
+        if bytes > MAX_ANIMATION_BYTES {
+            eprintln!("[ANIMATION] Keeping the first {} frames of an animation over {} MB", decoded.len(), MAX_ANIMATION_BYTES >> 20);
+            break;
+        }
+        decoded.push(AnimationFrame {
+            pixels: buffer.into_raw(),
+            delay: if delay < MIN_FRAME_DELAY { DEFAULT_FRAME_DELAY } else { delay },
+        });
+    }
+    Ok(size.and_then(|(width, height)| AnimatedImage::from_frames(width, height, decoded)))
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase)
+}
+
+/// Which frame of an animation shows, moved on by the app's delta time
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameClock {
+    delays: Vec<Duration>,
+    current: usize,
+    /// Time the current frame has shown
+    elapsed: Duration,
+}
+
+impl FrameClock {
+    pub fn new(animation: &AnimatedImage) -> Self {
+        Self { delays: animation.frames.iter().map(|frame| frame.delay).collect(), current: 0, elapsed: Duration::ZERO }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Moves the clock on by `delta`, skipping frames a long delta passed
+    /// over. Returns true if a different frame shows now.
+    pub fn advance(&mut self, delta: Duration) -> bool {
+        let total: Duration = self.delays.iter().sum();
+        if total.is_zero() {
+            return false;
+        }
+        let previous = self.current;
+        self.elapsed += delta;
+        // Whole loops change nothing
+        if self.elapsed >= total {
+            self.elapsed = Duration::from_nanos((self.elapsed.as_nanos() % total.as_nanos()) as u64);
+        }
+        while self.elapsed >= self.delays[self.current] {
+            self.elapsed -= self.delays[self.current];
+            self.current = (self.current + 1) % self.delays.len();
+        }
+        self.current != previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, RgbaImage};
+
+    fn animation(delays: &[u64]) -> AnimatedImage {
+        let frames = delays.iter().map(|&ms| AnimationFrame { pixels: vec![0; 4], delay: Duration::from_millis(ms) }).collect();
+        AnimatedImage::from_frames(1, 1, frames).unwrap()
+    }
+
+    #[test]
+    fn clocks_step_through_frames_and_loop() {
+        let mut clock = FrameClock::new(&animation(&[100, 50, 200]));
+        assert!(!clock.advance(Duration::from_millis(99)));
+        assert!(clock.advance(Duration::from_millis(1)));
+        assert_eq!(clock.current(), 1);
+        assert!(clock.advance(Duration::from_millis(60)));
+        assert_eq!(clock.current(), 2);
+        // 190 ms into the last frame, then a long stall of several loops
+        clock.advance(Duration::from_millis(180));
+        assert!(clock.advance(Duration::from_millis(3 * 350 + 20)));
+        assert_eq!(clock.current(), 0, "whole loops are skipped");
+
+        assert!(AnimatedImage::from_frames(1, 1, vec![AnimationFrame { pixels: vec![0; 4], delay: DEFAULT_FRAME_DELAY }]).is_none());
+    }
+
+    #[test]
+    fn decodes_gif_frames_with_their_delays() {
+        let path = std::env::temp_dir().join(format!("kryon-animation-{}.gif", std::process::id()));
+        {
+            let mut encoder = GifEncoder::new(File::create(&path).unwrap());
+            for (color, ms) in [([255, 0, 0, 255], 40), ([0, 0, 255, 255], 0)] {
+                let buffer = RgbaImage::from_pixel(2, 2, image::Rgba(color));
+                encoder.encode_frame(Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(ms, 1))).unwrap();
+            }
+        }
+        let decoded = decode(&path).unwrap().expect("two frames animate");
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!((decoded.width, decoded.height, decoded.frames.len()), (2, 2, 2));
+        assert_eq!(&decoded.frames[0].pixels[..4], &[255, 0, 0, 255]);
+        assert_eq!(&decoded.frames[1].pixels[..4], &[0, 0, 255, 255]);
+        assert_eq!(decoded.frames[0].delay, Duration::from_millis(40));
+        assert_eq!(decoded.frames[1].delay, DEFAULT_FRAME_DELAY, "zero delays play at the default");
+        assert!(decode(Path::new("photo.jpg")).unwrap().is_none());
+    }
+}
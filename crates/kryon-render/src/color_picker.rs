@@ -0,0 +1,427 @@
+// crates/kryon-render/src/color_picker.rs
+//! Color inputs (`input_type: "color"`): a saturation/value square with a
+//! hue slider below it and, unless `alpha_slider` is false, an alpha slider
+//! below that, all filling the element's box.
+//!
+//! The input's value is its text, a hex color (`#rrggbb`, or `#rrggbbaa`
+//! while it isn't opaque). Hue and saturation can't be told from every
+//! color (black has neither), so the runtime keeps the picked HSV in the
+//! `color_hue`, `color_saturation` and `color_value` properties, and they
+//! are used for as long as they still make the value.
+//!
+//! Backends with shader effects draw the square and sliders, thumbs
+//! included, as `DrawShaderEffect`s. The others draw them from precomputed
+//! ramps under the `kryon:color-picker/` sources, which `builtin_texture`
+//! rasterizes in place of loading a file, with rects for the thumbs.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{format_hex_color, parse_color, Element, PropertyValue, TransformData};
+
+use crate::{IconBitmap, ImageFilter, ImageRendering, RenderCommand};
+
+/// Height of the hue and alpha sliders
+pub const SLIDER_HEIGHT: f32 = 12.0;
+/// Space above each slider
+pub const SLIDER_GAP: f32 = 8.0;
+/// Diameter of the saturation/value thumb
+pub const THUMB_SIZE: f32 = 12.0;
+/// Width of a slider's thumb
+const SLIDER_THUMB_WIDTH: f32 = 4.0;
+
+/// Prefix of the sources `builtin_texture` knows
+pub const BUILTIN_SOURCE_PREFIX: &str = "kryon:color-picker/";
+/// White fading to transparent from left to right
+pub const SATURATION_SOURCE: &str = "kryon:color-picker/saturation";
+/// Transparent darkening to black from top to bottom
+pub const VALUE_SOURCE: &str = "kryon:color-picker/value";
+/// Every hue from left to right
+pub const HUE_SOURCE: &str = "kryon:color-picker/hue";
+/// Transparent to opaque white from left to right, tinted with the color
+pub const ALPHA_SOURCE: &str = "kryon:color-picker/alpha";
+/// The checkerboard under the alpha slider
+pub const CHECKER_SOURCE: &str = "kryon:color-picker/checker";
+
+/// Side of the checkerboard's squares, in texels
+const CHECKER_CELL: u32 = 4;
+
+macro_rules! thumb_wgsl {
+    () => {
+        "
+fn ring(color: vec4<f32>, offset: vec2<f32>) -> vec4<f32> {
+    let distance = abs(length(offset) - 5.0);
+    if distance < 1.0 { return vec4<f32>(1.0, 1.0, 1.0, 1.0); }
+    if distance < 2.0 { return vec4<f32>(0.0, 0.0, 0.0, 1.0); }
+    return color;
+}
+
+fn bar(color: vec4<f32>, offset: f32) -> vec4<f32> {
+    if abs(offset) < 1.5 { return vec4<f32>(1.0, 1.0, 1.0, 1.0); }
+    if abs(offset) < 2.5 { return vec4<f32>(0.0, 0.0, 0.0, 1.0); }
+    return color;
+}
+
+fn hue_rgb(hue: f32) -> vec3<f32> {
+    return clamp(abs(fract(hue + vec3<f32>(0.0, 2.0 / 3.0, 1.0 / 3.0)) * 6.0 - 3.0) - 1.0, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+"
+    };
+}
+
+/// The square: `kryon.color_a` is the hue at full saturation and value,
+/// `kryon.color_b.xy` where the thumb is, as a fraction of the square
+pub const SATURATION_VALUE_EFFECT: &str = concat!(
+    thumb_wgsl!(),
+    "
+fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+    let color = mix(vec3<f32>(1.0), kryon.color_a.rgb, uv.x) * (1.0 - uv.y);
+    return ring(vec4<f32>(color, 1.0), (uv - kryon.color_b.xy) * kryon.rect.zw);
+}
+"
+);
+
+/// The hue slider: `kryon.color_b.x` is the thumb, as a fraction of its width
+pub const HUE_EFFECT: &str = concat!(
+    thumb_wgsl!(),
+    "
+fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+    return bar(vec4<f32>(hue_rgb(uv.x), 1.0), (uv.x - kryon.color_b.x) * kryon.rect.z);
+}
+"
+);
+
+/// The alpha slider: `kryon.color_a` is the opaque color, `kryon.color_b.x`
+/// the thumb
+pub const ALPHA_EFFECT: &str = concat!(
+    thumb_wgsl!(),
+    "
+fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+    let cell = floor(frag_coord / 6.0);
+    let checker = select(0.8, 1.0, (cell.x + cell.y) % 2.0 < 1.0);
+    let color = mix(vec3<f32>(checker), kryon.color_a.rgb, uv.x);
+    return bar(vec4<f32>(color, 1.0), (uv.x - kryon.color_b.x) * kryon.rect.z);
+}
+"
+);
+
+/// A color as hue, saturation, value and alpha
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsva {
+    /// Degrees, `0.0..=360.0`
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+}
+
+impl Hsva {
+    /// `rgba`'s hue, saturation and value; gray has hue 0, black
+    /// saturation 0 too
+    pub fn from_rgba(rgba: Vec4) -> Self {
+        let (r, g, b) = (rgba.x, rgba.y, rgba.z);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let hue = if delta <= 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let saturation = if max > 0.0 { delta / max } else { 0.0 };
+        Self { hue, saturation, value: max, alpha: rgba.w }
+    }
+
+    pub fn to_rgba(self) -> Vec4 {
+        let sector = self.hue.rem_euclid(360.0) / 60.0;
+        let chroma = self.value * self.saturation;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let lightest = self.value - chroma;
+        Vec4::new(r + lightest, g + lightest, b + lightest, self.alpha)
+    }
+
+    /// The hue at full saturation and value, opaque
+    pub fn hue_color(self) -> Vec4 {
+        Self { saturation: 1.0, value: 1.0, alpha: 1.0, ..self }.to_rgba()
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` while it isn't opaque
+    pub fn hex(self) -> String {
+        format_hex_color(self.to_rgba())
+    }
+}
+
+/// Whether `element` has an alpha slider
+pub fn shows_alpha(element: &Element) -> bool {
+    !matches!(element.custom_properties.get("alpha_slider"), Some(PropertyValue::Bool(false)))
+}
+
+/// The color a color input shows: its value, as remembered in its HSV
+/// properties while they still make it. Values that don't parse are black.
+pub fn element_color(element: &Element) -> Hsva {
+    let mut rgba = parse_color(&element.text).unwrap_or(Vec4::W);
+    if !shows_alpha(element) {
+        rgba.w = 1.0;
+    }
+    let float = |name: &str| element.custom_properties.get(name).and_then(|value| value.as_float());
+    let remembered = match (float("color_hue"), float("color_saturation"), float("color_value")) {
+        (Some(hue), Some(saturation), Some(value)) => Some(Hsva { hue, saturation, value, alpha: rgba.w }),
+        _ => None,
+    };
+    remembered
+        .filter(|remembered| remembered.hex() == format_hex_color(rgba))
+        .unwrap_or_else(|| Hsva::from_rgba(rgba))
+}
+
+/// The part of a color input a point is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerRegion {
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+/// Where the parts of a color input are, as (position, size) pairs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickerLayout {
+    pub square: (Vec2, Vec2),
+    pub hue: (Vec2, Vec2),
+    pub alpha: Option<(Vec2, Vec2)>,
+}
+
+impl PickerLayout {
+    /// The parts of an input at `position` of `size`
+    pub fn new(position: Vec2, size: Vec2, alpha: bool) -> Self {
+        let sliders = if alpha { 2.0 } else { 1.0 };
+        let square_height = (size.y - sliders * (SLIDER_GAP + SLIDER_HEIGHT)).max(0.0);
+        let slider = |index: f32| {
+            let y = square_height + SLIDER_GAP + index * (SLIDER_GAP + SLIDER_HEIGHT);
+            (position + Vec2::new(0.0, y), Vec2::new(size.x, SLIDER_HEIGHT))
+        };
+        Self {
+            square: (position, Vec2::new(size.x, square_height)),
+            hue: slider(0.0),
+            alpha: alpha.then(|| slider(1.0)),
+        }
+    }
+
+    pub fn region_at(&self, point: Vec2) -> Option<PickerRegion> {
+        let inside = |(position, size): (Vec2, Vec2)| point.cmpge(position).all() && point.cmplt(position + size).all();
+        if inside(self.square) {
+            Some(PickerRegion::SaturationValue)
+        } else if inside(self.hue) {
+            Some(PickerRegion::Hue)
+        } else if self.alpha.is_some_and(inside) {
+            Some(PickerRegion::Alpha)
+        } else {
+            None
+        }
+    }
+
+    /// `color` with the part `region` sets picked at `point`, which may be
+    /// outside it while the pointer drags
+    pub fn pick(&self, region: PickerRegion, point: Vec2, color: Hsva) -> Hsva {
+        let fraction = |(position, size): (Vec2, Vec2)| ((point - position) / size.max(Vec2::ONE)).clamp(Vec2::ZERO, Vec2::ONE);
+        match region {
+            PickerRegion::SaturationValue => {
+                let at = fraction(self.square);
+                Hsva { saturation: at.x, value: 1.0 - at.y, ..color }
+            }
+            PickerRegion::Hue => Hsva { hue: fraction(self.hue).x * 360.0, ..color },
+            PickerRegion::Alpha => match self.alpha {
+                Some(alpha) => Hsva { alpha: fraction(alpha).x, ..color },
+                None => color,
+            },
+        }
+    }
+}
+
+/// Draws a color input showing `color`, with shader effects if
+/// `shader_effects`, else with the precomputed ramps
+pub fn commands(layout: &PickerLayout, color: Hsva, shader_effects: bool, transform: Option<TransformData>, z_index: i32) -> Vec<RenderCommand> {
+    let opaque = Hsva { alpha: 1.0, ..color }.to_rgba();
+    let thumb = Vec2::new(color.saturation, 1.0 - color.value);
+    let mut commands = Vec::new();
+    if shader_effects {
+        let effect = |(position, size): (Vec2, Vec2), shader: &str, primary_color: Vec4, thumb: Vec2| RenderCommand::DrawShaderEffect {
+            position,
+            size,
+            shader: shader.to_string(),
+            primary_color,
+            secondary_color: thumb.extend(0.0).extend(0.0),
+            z_index,
+        };
+        commands.push(effect(layout.square, SATURATION_VALUE_EFFECT, color.hue_color(), thumb));
+        commands.push(effect(layout.hue, HUE_EFFECT, color.hue_color(), Vec2::new(color.hue / 360.0, 0.0)));
+        if let Some(alpha) = layout.alpha {
+            commands.push(effect(alpha, ALPHA_EFFECT, opaque, Vec2::new(color.alpha, 0.0)));
+        }
+        return commands;
+    }
+
+    let image = |(position, size): (Vec2, Vec2), source: &str, tint: Vec4| RenderCommand::DrawImage {
+        position,
+        size,
+        source: source.to_string(),
+        opacity: 1.0,
+        transform: transform.clone(),
+        image_rendering: ImageRendering::Smooth,
+        filter: ImageFilter { tint, ..ImageFilter::default() },
+    };
+    let rect = |position: Vec2, size: Vec2, color: Vec4, border_radius: f32| RenderCommand::DrawRect {
+        position,
+        size,
+        color,
+        border_radius,
+        border_width: 1.0,
+        border_color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        transform: transform.clone(),
+        shadow: None,
+        z_index,
+    };
+    let slider_thumb = |(position, size): (Vec2, Vec2), at: f32| {
+        let x = position.x + at * size.x - SLIDER_THUMB_WIDTH / 2.0;
+        rect(Vec2::new(x, position.y - 1.0), Vec2::new(SLIDER_THUMB_WIDTH, size.y + 2.0), Vec4::ONE, 1.0)
+    };
+
+    let (square_position, square_size) = layout.square;
+    commands.push(rect(square_position, square_size, color.hue_color(), 0.0));
+    commands.push(image(layout.square, SATURATION_SOURCE, Vec4::ONE));
+    commands.push(image(layout.square, VALUE_SOURCE, Vec4::ONE));
+    commands.push(image(layout.hue, HUE_SOURCE, Vec4::ONE));
+    if let Some(alpha) = layout.alpha {
+        commands.push(image(alpha, CHECKER_SOURCE, Vec4::ONE));
+        commands.push(image(alpha, ALPHA_SOURCE, opaque));
+    }
+    // A white ring with a dark edge shows on light and dark colors alike
+    let ring = RenderCommand::DrawRect {
+        position: square_position + thumb * square_size - Vec2::splat(THUMB_SIZE / 2.0),
+        size: Vec2::splat(THUMB_SIZE),
+        color: Vec4::ZERO,
+        border_radius: THUMB_SIZE / 2.0,
+        border_width: 2.0,
+        border_color: Vec4::ONE,
+        transform: transform.clone(),
+        shadow: None,
+        z_index,
+    };
+    commands.push(ring);
+    commands.push(slider_thumb(layout.hue, color.hue / 360.0));
+    if let Some(alpha) = layout.alpha {
+        commands.push(slider_thumb(alpha, color.alpha));
+    }
+    commands
+}
+
+/// The ramp a `kryon:color-picker/` source names; `None` for other sources
+pub fn builtin_texture(source: &str) -> Option<IconBitmap> {
+    let (width, height) = match source.strip_prefix(BUILTIN_SOURCE_PREFIX)? {
+        "saturation" | "hue" | "alpha" => (256, 1),
+        "value" => (1, 256),
+        "checker" => (16 * CHECKER_CELL, 2 * CHECKER_CELL),
+        _ => return None,
+    };
+    let texel = |x: u32, y: u32| -> [u8; 4] {
+        // Ramps run along their one long side
+        let ramp = x.max(y) as u8;
+        match source {
+            SATURATION_SOURCE => [255, 255, 255, 255 - ramp],
+            VALUE_SOURCE => [0, 0, 0, ramp],
+            HUE_SOURCE => {
+                let rgba = Hsva { hue: x as f32 / 256.0 * 360.0, saturation: 1.0, value: 1.0, alpha: 1.0 }.to_rgba();
+                rgba.to_array().map(|channel| (channel * 255.0).round() as u8)
+            }
+            ALPHA_SOURCE => [255, 255, 255, ramp],
+            _ if (x / CHECKER_CELL + y / CHECKER_CELL).is_multiple_of(2) => [255, 255, 255, 255],
+            _ => [204, 204, 204, 255],
+        }
+    };
+    let pixels = (0..height).flat_map(|y| (0..width).flat_map(move |x| texel(x, y))).collect();
+    Some(IconBitmap { width, height, pixels })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vec4, b: Vec4) -> bool {
+        (a - b).abs().max_element() < 1e-4
+    }
+
+    #[test]
+    fn converts_between_rgb_and_hsv() {
+        for rgba in [Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.2, 0.6, 0.4, 0.5), Vec4::new(0.3, 0.1, 0.9, 1.0), Vec4::new(0.5, 0.5, 0.5, 1.0)] {
+            assert!(close(Hsva::from_rgba(rgba).to_rgba(), rgba), "{:?} round trips", rgba);
+        }
+        let green = Hsva::from_rgba(Vec4::new(0.0, 1.0, 0.0, 1.0));
+        assert_eq!((green.hue, green.saturation, green.value), (120.0, 1.0, 1.0));
+        assert_eq!(green.hex(), "#00ff00");
+        assert_eq!(Hsva { alpha: 0.5, ..green }.hex(), "#00ff0080");
+    }
+
+    #[test]
+    fn remembered_hue_survives_colors_without_one() {
+        let mut element = Element { element_type: kryon_core::ElementType::Input, ..Element::default() };
+        element.text = "#000000".to_string();
+        let mut remember = |hue: f32, saturation: f32, value: f32| {
+            for (name, amount) in [("color_hue", hue), ("color_saturation", saturation), ("color_value", value)] {
+                element.custom_properties.insert(name.to_string(), PropertyValue::Float(amount));
+            }
+        };
+        remember(200.0, 0.7, 0.0);
+        let black = element_color(&element);
+        assert_eq!((black.hue, black.saturation), (200.0, 0.7), "black keeps the hue it was picked with");
+
+        element.text = "#ff0000".to_string();
+        assert_eq!(element_color(&element).hue, 0.0, "a value set elsewhere wins");
+    }
+
+    #[test]
+    fn picks_by_region_and_clamps_drags() {
+        let layout = PickerLayout::new(Vec2::new(10.0, 10.0), Vec2::new(200.0, 240.0), true);
+        assert_eq!(layout.square.1, Vec2::new(200.0, 200.0));
+        assert_eq!(layout.hue.0.y, 218.0);
+        assert_eq!(layout.alpha.unwrap().0.y, 238.0);
+        assert_eq!(layout.region_at(Vec2::new(60.0, 60.0)), Some(PickerRegion::SaturationValue));
+        assert_eq!(layout.region_at(Vec2::new(60.0, 222.0)), Some(PickerRegion::Hue));
+        assert_eq!(layout.region_at(Vec2::new(60.0, 214.0)), None, "the gap belongs to nothing");
+
+        let color = Hsva { hue: 30.0, saturation: 0.0, value: 0.0, alpha: 1.0 };
+        let picked = layout.pick(PickerRegion::SaturationValue, Vec2::new(60.0, 60.0), color);
+        assert_eq!((picked.saturation, picked.value), (0.25, 0.75));
+        let dragged = layout.pick(PickerRegion::Hue, Vec2::new(500.0, 0.0), color);
+        assert_eq!(dragged.hue, 360.0);
+        assert_eq!(layout.pick(PickerRegion::Alpha, Vec2::new(110.0, 0.0), color).alpha, 0.5);
+    }
+
+    #[test]
+    fn draws_with_effects_or_ramps() {
+        let layout = PickerLayout::new(Vec2::ZERO, Vec2::new(100.0, 140.0), false);
+        let color = Hsva { hue: 120.0, saturation: 0.5, value: 0.5, alpha: 1.0 };
+        let effects = commands(&layout, color, true, None, 0);
+        assert_eq!(effects.len(), 2);
+        assert!(matches!(&effects[0], RenderCommand::DrawShaderEffect { secondary_color, .. } if secondary_color.x == 0.5));
+
+        let ramps = commands(&layout, color, false, None, 0);
+        let sources: Vec<&str> = ramps.iter().filter_map(|command| match command {
+            RenderCommand::DrawImage { source, .. } => Some(source.as_str()),
+            _ => None,
+        }).collect();
+        assert_eq!(sources, [SATURATION_SOURCE, VALUE_SOURCE, HUE_SOURCE]);
+        for source in [SATURATION_SOURCE, VALUE_SOURCE, HUE_SOURCE, ALPHA_SOURCE, CHECKER_SOURCE] {
+            let bitmap = builtin_texture(source).unwrap();
+            assert_eq!(bitmap.pixels.len(), (bitmap.width * bitmap.height * 4) as usize);
+        }
+        assert_eq!(&builtin_texture(HUE_SOURCE).unwrap().pixels[..4], &[255, 0, 0, 255]);
+        assert!(builtin_texture("icons/home.svg").is_none());
+    }
+}
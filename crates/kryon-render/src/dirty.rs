@@ -0,0 +1,224 @@
+// crates/kryon-render/src/dirty.rs
+//! What changed between one frame's render commands and the next.
+//!
+//! `ElementRenderer` keeps the commands it last drew, with the element each
+//! was drawn for, and compares every new frame against them. A frame
+//! identical to the last isn't drawn at all by backends that keep their
+//! output (`CommandRenderer::skips_unchanged_frames`). Otherwise the
+//! elements whose commands changed, and the rectangles those commands
+//! covered before and after, are handed to `CommandRenderer::execute_dirty`,
+//! so a backend that can redraw part of its output need not redraw it all.
+//!
+//! The comparison is exact rather than clever: a frame whose commands were
+//! only reordered, or whose changes include a clip, a transform, a shadow
+//! or anything else without a simple bounding box, is redrawn in full.
+
+use crate::RenderCommand;
+use glam::{Vec2, Vec4};
+use kryon_core::ElementId;
+use std::collections::HashMap;
+
+/// The parts of a frame that changed since the last one drawn
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirtyRegion {
+    /// Elements whose commands changed, appeared or disappeared
+    pub elements: Vec<ElementId>,
+    /// Rectangles (position, size) covering those commands, where they
+    /// were drawn last frame and where they are drawn now
+    pub rects: Vec<(Vec2, Vec2)>,
+}
+
+impl DirtyRegion {
+    /// Whether the rectangle at `position` of `size` overlaps a dirty one
+    pub fn intersects(&self, position: Vec2, size: Vec2) -> bool {
+        self.rects.iter().any(|&(dirty_position, dirty_size)| {
+            position.x < dirty_position.x + dirty_size.x && dirty_position.x < position.x + size.x
+                && position.y < dirty_position.y + dirty_size.y && dirty_position.y < position.y + size.y
+        })
+    }
+}
+
+/// How a frame differs from the last one drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameChange {
+    Unchanged,
+    /// Only the commands inside `DirtyRegion::rects` changed
+    Partial(DirtyRegion),
+    /// The whole frame has to be redrawn
+    Full,
+}
+
+#[derive(Debug, Clone)]
+struct DrawnFrame {
+    viewport_size: Vec2,
+    clear_color: Vec4,
+    commands: Vec<RenderCommand>,
+    origins: Vec<Option<ElementId>>,
+}
+
+/// The last frame drawn, to compare the next one against
+#[derive(Debug, Clone, Default)]
+pub struct FrameDiff {
+    last: Option<DrawnFrame>,
+}
+
+impl FrameDiff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the next frame a full redraw, e.g. after the backend lost what
+    /// it drew
+    pub fn invalidate(&mut self) {
+        self.last = None;
+    }
+
+    /// Compares a frame against the last and keeps it as the new last.
+    /// `origins[i]` is the element `commands[i]` was drawn for; commands
+    /// past its end, such as the overlay's, belong to none.
+    pub fn update(
+        &mut self,
+        viewport_size: Vec2,
+        clear_color: Vec4,
+        commands: &[RenderCommand],
+        origins: &[Option<ElementId>],
+    ) -> FrameChange {
+        let frame = DrawnFrame {
+            viewport_size,
+            clear_color,
+            commands: commands.to_vec(),
+            origins: (0..commands.len()).map(|index| origins.get(index).copied().flatten()).collect(),
+        };
+        let change = match &self.last {
+            Some(last) => diff(last, &frame),
+            None => FrameChange::Full,
+        };
+        self.last = Some(frame);
+        change
+    }
+}
+
+fn diff(last: &DrawnFrame, frame: &DrawnFrame) -> FrameChange {
+    if last.viewport_size != frame.viewport_size || last.clear_color != frame.clear_color {
+        return FrameChange::Full;
+    }
+    if last.commands == frame.commands {
+        return FrameChange::Unchanged;
+    }
+
+    let before = by_origin(last);
+    let after = by_origin(frame);
+    let mut dirty = DirtyRegion::default();
+    let mut origins: Vec<Option<ElementId>> = before.keys().chain(after.keys()).copied().collect();
+    origins.sort();
+    origins.dedup();
+    for origin in origins {
+        let (old, new) = (before.get(&origin), after.get(&origin));
+        if old == new {
+            continue;
+        }
+        for command in old.into_iter().chain(new).flatten() {
+            match command_bounds(command) {
+                Some(rect) => dirty.rects.push(rect),
+                None => return FrameChange::Full,
+            }
+        }
+        match origin {
+            Some(element) => dirty.elements.push(element),
+            None => return FrameChange::Full,
+        }
+    }
+    // Every element drew the same commands, so they were only reordered
+    if dirty.rects.is_empty() {
+        return FrameChange::Full;
+    }
+    FrameChange::Partial(dirty)
+}
+
+fn by_origin(frame: &DrawnFrame) -> HashMap<Option<ElementId>, Vec<&RenderCommand>> {
+    let mut groups: HashMap<Option<ElementId>, Vec<&RenderCommand>> = HashMap::new();
+    for (command, origin) in frame.commands.iter().zip(&frame.origins) {
+        groups.entry(*origin).or_default().push(command);
+    }
+    groups
+}
+
+/// The rectangle a command draws within, if it's confined to its box:
+/// `None` for transformed, shadowed or unbounded commands
+pub fn command_bounds(command: &RenderCommand) -> Option<(Vec2, Vec2)> {
+    match command {
+        RenderCommand::DrawRect { position, size, transform: None, shadow, .. }
+            if shadow.as_deref().is_none_or(|shadow| shadow.is_empty() || shadow == "none") => Some((*position, *size)),
+        RenderCommand::DrawText { position, max_width: Some(width), max_height: Some(height), transform: None, .. }
+        | RenderCommand::DrawRichText { position, max_width: Some(width), max_height: Some(height), transform: None, .. } => {
+            Some((*position, Vec2::new(*width, *height)))
+        }
+        RenderCommand::DrawImage { position, size, transform: None, .. }
+        | RenderCommand::DrawTiledImage { position, size, transform: None, .. }
+        | RenderCommand::DrawGradientRect { position, size, transform: None, .. }
+        | RenderCommand::DrawTextInput { position, size, transform: None, .. }
+        | RenderCommand::DrawCheckbox { position, size, transform: None, .. }
+        | RenderCommand::DrawSlider { position, size, transform: None, .. }
+        | RenderCommand::DrawScrollbar { position, size, .. } => Some((*position, *size)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, color: Vec4) -> RenderCommand {
+        RenderCommand::DrawRect {
+            position: Vec2::new(x, 0.0),
+            size: Vec2::new(10.0, 10.0),
+            color,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: 0,
+        }
+    }
+
+    #[test]
+    fn reports_the_elements_and_areas_that_changed() {
+        let (red, blue) = (Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 0.0, 1.0, 1.0));
+        let viewport = Vec2::new(100.0, 100.0);
+        let origins = [Some(1), Some(2)];
+        let mut diff = FrameDiff::new();
+        assert_eq!(diff.update(viewport, Vec4::ONE, &[rect(0.0, red), rect(20.0, red)], &origins), FrameChange::Full);
+        assert_eq!(diff.update(viewport, Vec4::ONE, &[rect(0.0, red), rect(20.0, red)], &origins), FrameChange::Unchanged);
+
+        let FrameChange::Partial(dirty) = diff.update(viewport, Vec4::ONE, &[rect(0.0, red), rect(30.0, blue)], &origins) else {
+            panic!("one element moved");
+        };
+        assert_eq!(dirty.elements, vec![2]);
+        assert_eq!(dirty.rects, vec![(Vec2::new(20.0, 0.0), Vec2::new(10.0, 10.0)), (Vec2::new(30.0, 0.0), Vec2::new(10.0, 10.0))]);
+        assert!(dirty.intersects(Vec2::new(25.0, 5.0), Vec2::ONE));
+        assert!(!dirty.intersects(Vec2::ZERO, Vec2::new(10.0, 10.0)));
+
+        // Removing an element dirties where it was
+        let FrameChange::Partial(dirty) = diff.update(viewport, Vec4::ONE, &[rect(0.0, red)], &origins[..1]) else {
+            panic!("one element removed");
+        };
+        assert_eq!(dirty.rects, vec![(Vec2::new(30.0, 0.0), Vec2::new(10.0, 10.0))]);
+    }
+
+    #[test]
+    fn unbounded_or_reordered_changes_redraw_everything() {
+        let viewport = Vec2::new(100.0, 100.0);
+        let (a, b) = (rect(0.0, Vec4::ONE), rect(50.0, Vec4::ONE));
+        let mut diff = FrameDiff::new();
+        diff.update(viewport, Vec4::ONE, &[a.clone(), b.clone()], &[Some(1), Some(2)]);
+        assert_eq!(diff.update(viewport, Vec4::ONE, &[b.clone(), a.clone()], &[Some(2), Some(1)]), FrameChange::Full, "reordered");
+        assert_eq!(diff.update(Vec2::new(50.0, 50.0), Vec4::ONE, &[b.clone(), a.clone()], &[Some(2), Some(1)]), FrameChange::Full, "resized");
+
+        let clipped = [b.clone(), RenderCommand::SetClip { position: Vec2::ZERO, size: Vec2::ONE }, a.clone()];
+        assert_eq!(diff.update(Vec2::new(50.0, 50.0), Vec4::ONE, &clipped, &[Some(2), Some(1), Some(1)]), FrameChange::Full, "clip changed");
+
+        diff.invalidate();
+        assert_eq!(diff.update(Vec2::new(50.0, 50.0), Vec4::ONE, &clipped, &[Some(2), Some(1), Some(1)]), FrameChange::Full);
+    }
+}
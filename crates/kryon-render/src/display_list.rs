@@ -0,0 +1,135 @@
+// crates/kryon-render/src/display_list.rs
+//! A frame's render commands grouped into items, for backends that keep
+//! what they build from commands between frames.
+//!
+//! Each item is a run of consecutive commands drawn for one element (or
+//! for none, such as the overlay's), with a hash of those commands. A
+//! backend that retains geometry, vertex buffers say, keys it by
+//! [`DisplayItem::key`] and rebuilds only the items whose key it hasn't
+//! seen: an element whose commands changed gets a new hash, and so a new
+//! key, while every other element's geometry is reused as it is. An
+//! element split into several runs by the draw order has an item per run.
+
+use crate::RenderCommand;
+use kryon_core::ElementId;
+use std::fmt::Write as _;
+use std::hash::{DefaultHasher, Hasher};
+use std::ops::Range;
+
+/// Identifies an item's commands: the element they were drawn for and
+/// their hash
+pub type DisplayKey = (Option<ElementId>, u64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayItem {
+    pub element: Option<ElementId>,
+    /// Hash of the item's commands, equal for equal commands
+    pub hash: u64,
+    /// The item's commands, in the frame's command list
+    pub commands: Range<usize>,
+}
+
+impl DisplayItem {
+    pub fn key(&self) -> DisplayKey {
+        (self.element, self.hash)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisplayList {
+    items: Vec<DisplayItem>,
+}
+
+impl DisplayList {
+    /// Groups `commands` into items. `origins[i]` is the element
+    /// `commands[i]` was drawn for; commands past its end belong to none.
+    pub fn build(commands: &[RenderCommand], origins: &[Option<ElementId>]) -> Self {
+        let mut items: Vec<DisplayItem> = Vec::new();
+        let mut hasher = CommandHasher::default();
+        for (index, command) in commands.iter().enumerate() {
+            let element = origins.get(index).copied().flatten();
+            match items.last_mut() {
+                Some(item) if item.element == element => item.commands.end = index + 1,
+                _ => {
+                    if let Some(item) = items.last_mut() {
+                        item.hash = std::mem::take(&mut hasher).finish();
+                    }
+                    items.push(DisplayItem { element, hash: 0, commands: index..index + 1 });
+                }
+            }
+            hasher.add(command);
+        }
+        if let Some(item) = items.last_mut() {
+            item.hash = hasher.finish();
+        }
+        Self { items }
+    }
+
+    pub fn items(&self) -> &[DisplayItem] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Hashes commands through their `Debug` form, since their floats have no
+/// `Hash`, without building the string
+#[derive(Default)]
+struct CommandHasher(DefaultHasher);
+
+impl CommandHasher {
+    fn add(&mut self, command: &RenderCommand) {
+        let _ = write!(self, "{:?};", command);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+}
+
+impl std::fmt::Write for CommandHasher {
+    fn write_str(&mut self, text: &str) -> std::fmt::Result {
+        self.0.write(text.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Vec2, Vec4};
+
+    fn rect(x: f32) -> RenderCommand {
+        RenderCommand::DrawRect {
+            position: Vec2::new(x, 0.0),
+            size: Vec2::new(10.0, 10.0),
+            color: Vec4::ONE,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: 0,
+        }
+    }
+
+    #[test]
+    fn items_are_runs_of_one_element_keyed_by_their_commands() {
+        let commands = [rect(0.0), rect(10.0), rect(20.0), rect(30.0), rect(40.0)];
+        let list = DisplayList::build(&commands, &[Some(1), Some(1), Some(2), Some(1)]);
+        let ranges: Vec<_> = list.items().iter().map(|item| (item.element, item.commands.clone())).collect();
+        assert_eq!(ranges, vec![(Some(1), 0..2), (Some(2), 2..3), (Some(1), 3..4), (None, 4..5)]);
+
+        // Only the item whose commands changed gets a new key
+        let moved = [rect(0.0), rect(10.0), rect(25.0), rect(30.0), rect(40.0)];
+        let next = DisplayList::build(&moved, &[Some(1), Some(1), Some(2), Some(1)]);
+        let same: Vec<bool> = list.items().iter().zip(next.items()).map(|(a, b)| a.key() == b.key()).collect();
+        assert_eq!(same, vec![true, false, true, true]);
+    }
+}
@@ -0,0 +1,127 @@
+// crates/kryon-render/src/drag_ghost.rs
+//! The drag ghost: a see-through copy of the element being dragged.
+//!
+//! While a drag is under way the runtime puts a [`DragGhost`] in each
+//! snapshot. The renderer draws the dragged element and its descendants a
+//! second time, moved by the ghost's offset so they follow the pointer and
+//! faded to its opacity, as a layer of their own above the overlay. The
+//! element itself stays where it is in the tree.
+
+use crate::RenderCommand;
+use glam::{Vec2, Vec4};
+use kryon_core::ElementId;
+
+/// How see-through a ghost is unless the element sets `drag_ghost_opacity`
+pub const DEFAULT_GHOST_OPACITY: f32 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragGhost {
+    /// The dragged element
+    pub element: ElementId,
+    /// From where the element is laid out to where the ghost is drawn
+    pub offset: Vec2,
+    pub opacity: f32,
+}
+
+impl DragGhost {
+    /// `command`, drawn for the dragged element, as the ghost draws it
+    pub fn ghost_command(&self, command: &RenderCommand) -> RenderCommand {
+        let mut command = crate::pagination::translated(command, self.offset);
+        fade(&mut command, self.opacity);
+        command
+    }
+}
+
+/// Multiplies the alpha of everything `command` draws by `opacity`
+pub fn fade(command: &mut RenderCommand, opacity: f32) {
+    let fade_color = |color: &mut Vec4| color.w *= opacity;
+    match command {
+        RenderCommand::DrawRect { color, border_color, .. } => {
+            fade_color(color);
+            fade_color(border_color);
+        }
+        RenderCommand::DrawText { color, .. } | RenderCommand::DrawCanvasText { color, .. } | RenderCommand::DrawCanvasLine { color, .. } => {
+            fade_color(color)
+        }
+        RenderCommand::DrawRichText { default_color, .. } => fade_color(default_color),
+        RenderCommand::DrawImage { opacity: image_opacity, .. }
+        | RenderCommand::DrawTiledImage { opacity: image_opacity, .. }
+        | RenderCommand::DrawCanvasImage { opacity: image_opacity, .. } => *image_opacity *= opacity,
+        RenderCommand::DrawGradientRect { gradient, .. } => *gradient = gradient.with_opacity(opacity),
+        RenderCommand::DrawShaderEffect { primary_color, secondary_color, .. } => {
+            fade_color(primary_color);
+            fade_color(secondary_color);
+        }
+        RenderCommand::DrawParticles { particles, .. } => particles.iter_mut().for_each(|particle| fade_color(&mut particle.color)),
+        RenderCommand::DrawTextInput { text_color, background_color, border_color, .. } => {
+            fade_color(text_color);
+            fade_color(background_color);
+            fade_color(border_color);
+        }
+        RenderCommand::DrawCheckbox { text_color, background_color, border_color, check_color, .. } => {
+            fade_color(text_color);
+            fade_color(background_color);
+            fade_color(border_color);
+            fade_color(check_color);
+        }
+        RenderCommand::DrawSlider { track_color, thumb_color, border_color, .. }
+        | RenderCommand::DrawScrollbar { track_color, thumb_color, border_color, .. } => {
+            fade_color(track_color);
+            fade_color(thumb_color);
+            fade_color(border_color);
+        }
+        RenderCommand::DrawCanvasRect { fill_color, stroke_color, .. }
+        | RenderCommand::DrawCanvasCircle { fill_color, stroke_color, .. }
+        | RenderCommand::DrawCanvasEllipse { fill_color, stroke_color, .. }
+        | RenderCommand::DrawCanvasPolygon { fill_color, stroke_color, .. }
+        | RenderCommand::DrawCanvasPath { fill_color, stroke_color, .. } => {
+            fill_color.iter_mut().chain(stroke_color.iter_mut()).for_each(fade_color);
+        }
+        // Native and WASM views draw themselves
+        RenderCommand::SetClip { .. }
+        | RenderCommand::ClearClip
+        | RenderCommand::SetCanvasSize(_)
+        | RenderCommand::NativeRendererView { .. }
+        | RenderCommand::BeginCanvas { .. }
+        | RenderCommand::EndCanvas
+        | RenderCommand::BeginWasmView { .. }
+        | RenderCommand::EndWasmView
+        | RenderCommand::ExecuteWasmFunction { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ghost_commands_follow_the_offset_at_the_ghost_opacity() {
+        let ghost = DragGhost { element: 3, offset: Vec2::new(40.0, -10.0), opacity: 0.5 };
+        let rect = RenderCommand::DrawRect {
+            position: Vec2::new(10.0, 20.0),
+            size: Vec2::new(100.0, 30.0),
+            color: Vec4::new(1.0, 0.0, 0.0, 0.8),
+            border_radius: 4.0,
+            border_width: 1.0,
+            border_color: Vec4::ONE,
+            transform: None,
+            shadow: None,
+            z_index: 2,
+        };
+        let RenderCommand::DrawRect { position, color, border_color, size, z_index, .. } = ghost.ghost_command(&rect) else {
+            panic!("a rect stays a rect");
+        };
+        assert_eq!((position, size, z_index), (Vec2::new(50.0, 10.0), Vec2::new(100.0, 30.0), 2));
+        assert_eq!((color.w, border_color.w), (0.4, 0.5));
+
+        let mut circle = RenderCommand::DrawCanvasCircle {
+            center: Vec2::ZERO,
+            radius: 5.0,
+            fill_color: Some(Vec4::ONE),
+            stroke_color: None,
+            stroke_width: 1.0,
+        };
+        fade(&mut circle, 0.25);
+        assert!(matches!(circle, RenderCommand::DrawCanvasCircle { fill_color: Some(fill), stroke_color: None, .. } if fill.w == 0.25));
+    }
+}
@@ -1,15 +1,34 @@
 // crates/kryon-render/src/events.rs
 use glam::Vec2;
 
-#[derive(Debug, Clone)]
+/// Pointer positions and sizes are in the surface's device pixels; scroll
+/// deltas are in logical pixels
+#[derive(Debug, Clone, PartialEq)]
 pub enum InputEvent {
     MouseMove { position: Vec2 },
-    MousePress { position: Vec2, button: MouseButton },
-    MouseRelease { position: Vec2, button: MouseButton },
+    MousePress { position: Vec2, button: MouseButton, modifiers: KeyModifiers },
+    MouseRelease { position: Vec2, button: MouseButton, modifiers: KeyModifiers },
     KeyPress { key: KeyCode, modifiers: KeyModifiers },
     KeyRelease { key: KeyCode, modifiers: KeyModifiers },
+    /// Wheel or touchpad scroll in pixels; positive y reveals content further down
     Scroll { delta: Vec2 },
     Resize { size: Vec2 },
+    /// Text committed by an input method, inserted as typed
+    TextInput { text: String },
+    /// Text an input method is still composing; empty when it's done or cancelled
+    ImePreedit { text: String },
+}
+
+/// Pixels scrolled per wheel notch, for mice that report lines
+pub const SCROLL_LINE_HEIGHT: f32 = 40.0;
+
+/// The `Scroll` delta for a wheel turned by `notches`, counted positive
+/// upward and to the left as the platforms report them. With Shift held a
+/// vertical wheel scrolls sideways, for mice without a horizontal wheel.
+pub fn wheel_scroll_delta(notches: Vec2, modifiers: KeyModifiers) -> Vec2 {
+    let notches = if modifiers.shift && notches.x == 0.0 { Vec2::new(notches.y, 0.0) } else { notches };
+    // Wheel up reveals content above, so positive notches become upward scrolls
+    -notches * SCROLL_LINE_HEIGHT
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +36,10 @@ pub enum MouseButton {
     Left,
     Right,
     Middle,
+    /// The thumb button browsers use to go back
+    Back,
+    /// The thumb button browsers use to go forward
+    Forward,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -27,6 +50,14 @@ pub enum KeyCode {
     Backspace,
     Delete,
     Tab,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Home,
+    End,
+    PageUp,
+    PageDown,
     Character(char),
     // Add more as needed
 }
@@ -48,4 +79,17 @@ impl KeyModifiers {
             meta: false,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_turns_the_wheel_sideways() {
+        let shift = KeyModifiers { shift: true, ..KeyModifiers::none() };
+        assert_eq!(wheel_scroll_delta(Vec2::new(0.0, -1.0), KeyModifiers::none()), Vec2::new(0.0, SCROLL_LINE_HEIGHT));
+        assert_eq!(wheel_scroll_delta(Vec2::new(0.0, -1.0), shift), Vec2::new(SCROLL_LINE_HEIGHT, 0.0));
+        // A trackpad already reports both axes
+        assert_eq!(wheel_scroll_delta(Vec2::new(0.5, 0.25), shift), Vec2::new(-20.0, -10.0));
+    }
+}
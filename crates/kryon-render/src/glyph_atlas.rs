@@ -0,0 +1,301 @@
+// crates/kryon-render/src/glyph_atlas.rs
+//! A glyph atlas for backends that draw text as textured quads: glyphs
+//! shaped by [`TextManager`] are rasterized by swash once and packed into
+//! one RGBA bitmap, which the backend uploads whenever it changes.
+//!
+//! Coverage masks are stored white with the coverage as alpha, so a quad's
+//! color tints them; color glyphs such as emoji keep their own colors and
+//! are drawn with a white tint. Glyphs are packed left to right in shelves
+//! as tall as the tallest glyph on them. When the atlas fills up it is
+//! cleared and the text that overflowed it packed again, so one text never
+//! mixes glyphs from before and after.
+//!
+//! On a scaled surface (see `Renderer::viewport_scale`) glyphs are
+//! rasterized at the device size and their quads given in logical pixels,
+//! so text stays sharp where the backend scales everything it draws.
+
+use crate::text_manager::{RenderedText, TextManager};
+use cosmic_text::{CacheKey, SwashContent};
+use glam::{Vec2, Vec4};
+use std::collections::HashMap;
+
+/// Width and height of the atlas bitmap
+pub const GLYPH_ATLAS_SIZE: u32 = 1024;
+/// Transparent texels kept around each glyph so filtering doesn't bleed
+const PADDING: u32 = 1;
+
+/// Where a glyph's image is in the atlas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasGlyph {
+    /// Top-left texel
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// From the glyph's origin on the baseline to the image's top-left
+    /// corner
+    pub offset: Vec2,
+    /// A color glyph, drawn untinted
+    pub colored: bool,
+}
+
+/// One glyph to draw: a rectangle of the screen and the part of the atlas
+/// that fills it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphQuad {
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Atlas coordinates from `0.0` to `1.0`
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+    pub color: Vec4,
+    /// Index of the glyph in the text it was made for
+    pub glyph: usize,
+}
+
+pub struct GlyphAtlas {
+    size: u32,
+    pixels: Vec<u8>,
+    /// `None` for glyphs with no image, such as spaces
+    glyphs: HashMap<CacheKey, Option<AtlasGlyph>>,
+    /// Next free texel on the current shelf, and the shelf's height
+    cursor: (u32, u32),
+    shelf_height: u32,
+    dirty: bool,
+    /// Device pixels per logical pixel glyphs are rasterized for
+    scale: f32,
+}
+
+impl GlyphAtlas {
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            pixels: vec![0; size as usize * size as usize * 4],
+            glyphs: HashMap::new(),
+            cursor: (0, 0),
+            shelf_height: 0,
+            dirty: true,
+            scale: 1.0,
+        }
+    }
+
+    /// Rasterizes glyphs for a surface with `scale` device pixels per
+    /// logical pixel, dropping those rasterized for another scale
+    pub fn set_scale(&mut self, scale: f32) {
+        if scale != self.scale && scale.is_finite() && scale > 0.0 {
+            self.scale = scale;
+            self.clear();
+        }
+    }
+
+    /// Width and height of the bitmap
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// RGBA texels, row by row
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Glyphs with an image in the atlas
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.values().flatten().count()
+    }
+
+    /// Whether the bitmap changed since the last call, so it needs uploading
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::replace(&mut self.dirty, false)
+    }
+
+    /// Drops every glyph
+    pub fn clear(&mut self) {
+        self.pixels.fill(0);
+        self.glyphs.clear();
+        self.cursor = (0, 0);
+        self.shelf_height = 0;
+        self.dirty = true;
+    }
+
+    /// Quads drawing `text` with its top-left corner at `origin`, packing
+    /// any glyphs not in the atlas yet
+    pub fn quads(&mut self, text_manager: &mut TextManager, text: &RenderedText, origin: Vec2) -> Vec<GlyphQuad> {
+        match self.try_quads(text_manager, text, origin) {
+            Some(quads) => quads,
+            None => {
+                eprintln!("[GLYPH_ATLAS] Atlas full with {} glyphs; starting over", self.glyph_count());
+                self.clear();
+                // Glyphs that don't fit an empty atlas are left out
+                self.try_quads(text_manager, text, origin).unwrap_or_default()
+            }
+        }
+    }
+
+    fn try_quads(&mut self, text_manager: &mut TextManager, text: &RenderedText, origin: Vec2) -> Option<Vec<GlyphQuad>> {
+        let texel_size = 1.0 / self.size as f32;
+        let mut quads = Vec::with_capacity(text.glyphs.len());
+        for (index, glyph) in text.glyphs.iter().enumerate() {
+            // Shaped in logical pixels, rasterized in device pixels
+            let key = CacheKey {
+                font_size_bits: (f32::from_bits(glyph.raster_key.font_size_bits) * self.scale).to_bits(),
+                ..glyph.raster_key
+            };
+            let placed = match self.glyphs.get(&key) {
+                Some(placed) => *placed,
+                None => {
+                    let placed = self.pack(text_manager, key)?;
+                    self.glyphs.insert(key, placed);
+                    placed
+                }
+            };
+            let Some(placed) = placed else {
+                continue;
+            };
+            let texel = Vec2::new(placed.x as f32, placed.y as f32);
+            let size = Vec2::new(placed.width as f32, placed.height as f32);
+            quads.push(GlyphQuad {
+                position: origin + glyph.raster_origin + placed.offset / self.scale,
+                size: size / self.scale,
+                uv_min: texel * texel_size,
+                uv_max: (texel + size) * texel_size,
+                color: if placed.colored { Vec4::new(1.0, 1.0, 1.0, glyph.color.w) } else { glyph.color },
+                glyph: index,
+            });
+        }
+        Some(quads)
+    }
+
+    /// Rasterizes a glyph into the atlas: `Some(None)` for glyphs without
+    /// an image, `None` when there's no room left
+    fn pack(&mut self, text_manager: &mut TextManager, key: CacheKey) -> Option<Option<AtlasGlyph>> {
+        let Some(image) = text_manager.glyph_image(key) else {
+            return Some(None);
+        };
+        let (width, height) = (image.placement.width, image.placement.height);
+        if width == 0 || height == 0 {
+            return Some(None);
+        }
+        let (x, y) = self.allocate(width, height)?;
+
+        let colored = image.content == SwashContent::Color;
+        for row in 0..height {
+            for column in 0..width {
+                let source = (row * width + column) as usize;
+                let texel = match image.content {
+                    SwashContent::Mask => [255, 255, 255, image.data[source]],
+                    SwashContent::SubpixelMask => {
+                        let rgb = &image.data[source * 4..source * 4 + 3];
+                        [255, 255, 255, ((rgb[0] as u16 + rgb[1] as u16 + rgb[2] as u16) / 3) as u8]
+                    }
+                    SwashContent::Color => {
+                        let rgba = &image.data[source * 4..source * 4 + 4];
+                        [rgba[0], rgba[1], rgba[2], rgba[3]]
+                    }
+                };
+                let target = (((y + row) * self.size + x + column) * 4) as usize;
+                self.pixels[target..target + 4].copy_from_slice(&texel);
+            }
+        }
+        self.dirty = true;
+
+        let offset = Vec2::new(image.placement.left as f32, -image.placement.top as f32);
+        Some(Some(AtlasGlyph { x, y, width, height, offset, colored }))
+    }
+
+    /// Top-left texel of a free `width` by `height` area
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let (padded_width, padded_height) = (width + PADDING, height + PADDING);
+        if padded_width > self.size {
+            return None;
+        }
+        if self.cursor.0 + padded_width > self.size {
+            self.cursor = (0, self.cursor.1 + self.shelf_height);
+            self.shelf_height = 0;
+        }
+        if self.cursor.1 + padded_height > self.size {
+            return None;
+        }
+        let position = self.cursor;
+        self.cursor.0 += padded_width;
+        self.shelf_height = self.shelf_height.max(padded_height);
+        Some(position)
+    }
+}
+
+impl Default for GlyphAtlas {
+    fn default() -> Self {
+        Self::new(GLYPH_ATLAS_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_each_glyph_once_and_places_quads_on_the_baseline() {
+        let mut text_manager = TextManager::new();
+        let text = text_manager.render_simple_text("abba", 24.0, Vec4::new(1.0, 0.0, 0.0, 1.0), None);
+        if text.glyphs.is_empty() {
+            eprintln!("No system fonts; skipping");
+            return;
+        }
+        let mut atlas = GlyphAtlas::new(256);
+        assert!(atlas.take_dirty(), "a new atlas needs its first upload");
+
+        let quads = atlas.quads(&mut text_manager, &text, Vec2::new(10.0, 20.0));
+        assert_eq!(quads.len(), 4);
+        assert!(atlas.take_dirty());
+        assert!(quads.iter().all(|quad| quad.color == Vec4::new(1.0, 0.0, 0.0, 1.0)));
+        assert!(quads.windows(2).all(|pair| pair[0].position.x < pair[1].position.x));
+        // Ascenders reach above x-height letters that sit on the same line
+        assert!(quads[1].position.y < quads[0].position.y);
+        assert!((quads[1].position.y + quads[1].size.y - quads[0].position.y - quads[0].size.y).abs() <= 1.0);
+        let coverage: u32 = atlas.pixels().chunks(4).map(|texel| texel[3] as u32).sum();
+        assert!(coverage > 0);
+
+        // The same glyph at the same subpixel offset shares its image
+        let packed = atlas.glyph_count();
+        let single = text_manager.render_simple_text("a", 24.0, Vec4::ONE, None);
+        let again = atlas.quads(&mut text_manager, &single, Vec2::new(10.0, 20.0));
+        assert_eq!((again[0].uv_min, again[0].uv_max), (quads[0].uv_min, quads[0].uv_max));
+        assert_eq!(atlas.glyph_count(), packed);
+        assert!(!atlas.take_dirty(), "nothing new to upload");
+    }
+
+    #[test]
+    fn starts_over_when_full() {
+        let mut text_manager = TextManager::new();
+        let first = text_manager.render_simple_text("W", 60.0, Vec4::ONE, None);
+        if first.glyphs.is_empty() {
+            eprintln!("No system fonts; skipping");
+            return;
+        }
+        // Room for one of the two glyphs at a time
+        let mut atlas = GlyphAtlas::new(64);
+        assert_eq!(atlas.quads(&mut text_manager, &first, Vec2::ZERO).len(), 1);
+        let second = text_manager.render_simple_text("M", 60.0, Vec4::ONE, None);
+        assert_eq!(atlas.quads(&mut text_manager, &second, Vec2::ZERO).len(), 1);
+        assert_eq!(atlas.glyph_count(), 1, "the first glyph was dropped to make room");
+    }
+
+    #[test]
+    fn scaled_glyphs_are_rasterized_larger_and_drawn_at_their_logical_size() {
+        let mut text_manager = TextManager::new();
+        let text = text_manager.render_simple_text("M", 24.0, Vec4::ONE, None);
+        if text.glyphs.is_empty() {
+            eprintln!("No system fonts; skipping");
+            return;
+        }
+        let mut atlas = GlyphAtlas::new(256);
+        let plain = atlas.quads(&mut text_manager, &text, Vec2::ZERO)[0];
+        atlas.set_scale(2.0);
+        assert_eq!(atlas.glyph_count(), 0, "glyphs of the old scale are dropped");
+        let scaled = atlas.quads(&mut text_manager, &text, Vec2::ZERO)[0];
+
+        let texels = |quad: &GlyphQuad| (quad.uv_max - quad.uv_min) * 256.0;
+        assert!(texels(&scaled).x >= texels(&plain).x * 1.8, "{:?} vs {:?}", texels(&scaled), texels(&plain));
+        assert!((scaled.size - plain.size).abs().max_element() <= 1.0);
+        assert!((scaled.position - plain.position).abs().max_element() <= 1.0);
+    }
+}
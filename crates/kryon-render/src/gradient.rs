@@ -0,0 +1,162 @@
+// crates/kryon-render/src/gradient.rs
+//! `DrawGradientRect` for backends without shaders: the box is cut into
+//! flat-colored strips, each the gradient's color at its centre.
+//!
+//! Gradients running mostly sideways are cut into vertical strips, ones
+//! running mostly up or down into horizontal ones, and radial gradients
+//! into a grid. Strips are trimmed to the box's rounded corners, so a
+//! gradient stays inside the rounded background drawn under it.
+//!
+//! GPU backends draw it with the shared gradient shader instead, from the
+//! [`ShaderGradient`] each of its vertices carries.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{Gradient, GradientKind};
+
+/// Most strips one linear gradient is cut into
+pub const MAX_GRADIENT_STRIPS: usize = 512;
+/// Most cells along each side of a radial gradient's grid
+pub const MAX_RADIAL_CELLS: usize = 64;
+/// Stops the gradient shader takes; `kryon_shaders::MAX_GRADIENT_STOPS`
+pub const SHADER_GRADIENT_STOPS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStrip {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub color: Vec4,
+}
+
+/// A gradient as a vertex of the gradient shader carries it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShaderGradient {
+    /// Kind (0 linear, 1 ellipse, 2 circle), angle, corner radius and
+    /// stop count
+    pub params: [f32; 4],
+    pub offsets: [f32; SHADER_GRADIENT_STOPS],
+    pub colors: [[f32; 4]; SHADER_GRADIENT_STOPS],
+}
+
+impl ShaderGradient {
+    /// `gradient` filling a box of `size`, which fixes corner angles
+    pub fn new(gradient: &Gradient, size: Vec2, border_radius: f32) -> Self {
+        let (kind, angle) = match gradient.kind {
+            GradientKind::Linear(direction) => (0.0, direction.angle(size)),
+            GradientKind::Radial { circle: false } => (1.0, 0.0),
+            GradientKind::Radial { circle: true } => (2.0, 0.0),
+        };
+        let stops = gradient.limited_stops(SHADER_GRADIENT_STOPS);
+        let mut offsets = [0.0; SHADER_GRADIENT_STOPS];
+        let mut colors = [[0.0; 4]; SHADER_GRADIENT_STOPS];
+        for (i, stop) in stops.iter().enumerate() {
+            offsets[i] = stop.offset;
+            colors[i] = stop.color.to_array();
+        }
+        Self { params: [kind, angle, border_radius, stops.len() as f32], offsets, colors }
+    }
+}
+
+/// Cuts a gradient filling `size` at `position` into strips about
+/// `strip_width` wide, or fewer for large boxes
+pub fn strips(gradient: &Gradient, position: Vec2, size: Vec2, border_radius: f32, strip_width: f32) -> Vec<GradientStrip> {
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return Vec::new();
+    }
+    let radius = border_radius.clamp(0.0, size.min_element() / 2.0);
+    let count = |extent: f32, max: usize| ((extent / strip_width.max(1.0)).ceil() as usize).clamp(1, max);
+    let (columns, rows) = match gradient.kind {
+        GradientKind::Linear(direction) => {
+            let angle = direction.angle(size);
+            if angle.sin().abs() >= angle.cos().abs() {
+                (count(size.x, MAX_GRADIENT_STRIPS), 1)
+            } else {
+                (1, count(size.y, MAX_GRADIENT_STRIPS))
+            }
+        }
+        GradientKind::Radial { .. } => (count(size.x, MAX_RADIAL_CELLS), count(size.y, MAX_RADIAL_CELLS)),
+    };
+
+    let cell = size / Vec2::new(columns as f32, rows as f32);
+    let mut strips = Vec::with_capacity(columns * rows);
+    for row in 0..rows {
+        for column in 0..columns {
+            let mut start = Vec2::new(column as f32, row as f32) * cell;
+            let mut end = start + cell;
+            let center = (start + end) / 2.0;
+            // Full-length strips are shortened at the corners; grid cells
+            // outside them are dropped
+            if rows == 1 {
+                let inset = corner_inset(center.x, size.x, radius);
+                (start.y, end.y) = (inset, size.y - inset);
+            } else if columns == 1 {
+                let inset = corner_inset(center.y, size.y, radius);
+                (start.x, end.x) = (inset, size.x - inset);
+            } else if !inside_rounded_box(center, size, radius) {
+                continue;
+            }
+            strips.push(GradientStrip {
+                position: position + start,
+                size: end - start,
+                color: gradient.color_at_point(center, size),
+            });
+        }
+    }
+    strips
+}
+
+/// How far a rounded corner of `radius` cuts into the box `along` an edge
+/// of length `extent`
+fn corner_inset(along: f32, extent: f32, radius: f32) -> f32 {
+    let from_end = along.min(extent - along);
+    if from_end >= radius {
+        return 0.0;
+    }
+    let into_corner = radius - from_end;
+    radius - (radius * radius - into_corner * into_corner).max(0.0).sqrt()
+}
+
+fn inside_rounded_box(point: Vec2, size: Vec2, radius: f32) -> bool {
+    let corner = Vec2::splat(radius);
+    let nearest = point.clamp(corner, size - corner);
+    point.distance_squared(nearest) <= radius * radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_along_the_gradient_and_trims_rounded_corners() {
+        let horizontal = Gradient::parse("linear-gradient(to right, #000, #fff)").unwrap();
+        let cut = strips(&horizontal, Vec2::new(10.0, 20.0), Vec2::new(100.0, 40.0), 0.0, 10.0);
+        assert_eq!(cut.len(), 10);
+        assert_eq!((cut[0].position, cut[0].size), (Vec2::new(10.0, 20.0), Vec2::new(10.0, 40.0)));
+        assert!(cut[0].color.abs_diff_eq(Vec4::new(0.05, 0.05, 0.05, 1.0), 1e-6), "each strip is the color at its centre");
+        assert!(cut[9].color.x > 0.9);
+
+        let vertical = Gradient::parse("linear-gradient(#000, #fff)").unwrap();
+        let rounded = strips(&vertical, Vec2::ZERO, Vec2::new(100.0, 40.0), 10.0, 10.0);
+        assert_eq!(rounded.len(), 4, "rows for a top to bottom gradient");
+        assert!(rounded[0].position.x > 0.0 && rounded[0].size.x < 100.0, "the corner rows are shortened");
+        assert_eq!((rounded[1].position.x, rounded[1].size.x), (0.0, 100.0));
+
+        let radial = Gradient::parse("radial-gradient(#000, #fff)").unwrap();
+        let grid = strips(&radial, Vec2::ZERO, Vec2::new(40.0, 40.0), 20.0, 10.0);
+        assert_eq!(grid.len(), 12, "cells outside the circle are dropped");
+        assert!(strips(&radial, Vec2::ZERO, Vec2::new(0.0, 40.0), 0.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn packs_gradients_for_the_shader() {
+        let corner = Gradient::parse("linear-gradient(to bottom right, red, lime 25%, blue)").unwrap();
+        let packed = ShaderGradient::new(&corner, Vec2::new(100.0, 100.0), 6.0);
+        assert!((packed.params[1] - std::f32::consts::FRAC_PI_4 * 3.0).abs() < 1e-5, "a square's corner is at 135deg");
+        assert_eq!((packed.params[2], packed.params[3]), (6.0, 3.0));
+        assert_eq!(&packed.offsets[..3], &[0.0, 0.25, 1.0]);
+        assert_eq!(packed.colors[2], [0.0, 0.0, 1.0, 1.0]);
+
+        let rainbow = Gradient::parse("radial-gradient(circle, red, orange, yellow, green, blue, indigo, violet, white, black)").unwrap();
+        let packed = ShaderGradient::new(&rainbow, Vec2::ONE, 0.0);
+        assert_eq!((packed.params[0], packed.params[3]), (2.0, SHADER_GRADIENT_STOPS as f32));
+    }
+}
@@ -0,0 +1,711 @@
+// crates/kryon-render/src/icon.rs
+//! Icons drawn from SVG files and tinted like text.
+//!
+//! An element's `icon` property names an SVG file. The icon is drawn
+//! `icon_size` pixels square, its font size by default, in the middle of
+//! the element's box. Shapes without a fill of their own, and those filled
+//! or stroked with `currentColor`, take the element's `icon_color`, or its
+//! text color when that isn't set.
+//!
+//! Backends load icons through their image caches, under a source that
+//! carries the size (`icons/home.svg@24px`), by rasterizing the SVG with
+//! `load_icon` instead of decoding it. Each size is rasterized once, at the
+//! size it's drawn. `currentColor` is drawn white and the `DrawImage`
+//! filter's tint colors it, so one bitmap serves every color.
+//!
+//! The rasterizer covers what icon sets use: paths, the basic shapes,
+//! groups and transforms, with fill and stroke given as attributes or in
+//! `style`. Gradients, text, masks, clip paths and `<use>` are ignored.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{Element, PropertyValue};
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::path::Path;
+use thiserror::Error;
+use tiny_skia::{FillRule, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+/// The largest icon rasterized, in pixels; larger sizes are clamped
+pub const MAX_ICON_SIZE: u32 = 1024;
+
+#[derive(Error, Debug)]
+pub enum IconError {
+    #[error("failed to read icon: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid SVG: {0}")]
+    Parse(#[from] roxmltree::Error),
+    #[error("SVG has no viewBox, width or height to size it by")]
+    NoSize,
+}
+
+/// A rasterized icon as straight (not premultiplied) RGBA rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconBitmap {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// The icon an element's properties ask for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Icon {
+    /// The SVG file
+    pub path: String,
+    /// Width and height, in pixels
+    pub size: f32,
+    pub color: Vec4,
+}
+
+impl Icon {
+    /// The icon `element` shows, if any. `font_size` and `text_color` are
+    /// from its computed style and are the defaults for `icon_size` and
+    /// `icon_color`.
+    pub fn from_element(element: &Element, font_size: f32, text_color: Vec4) -> Option<Self> {
+        let property = |name: &str| element.custom_properties.get(name);
+        let path = property("icon")?.as_string()?.trim();
+        if path.is_empty() {
+            return None;
+        }
+        let size = property("icon_size")
+            .and_then(|value| match value {
+                PropertyValue::String(text) => number(text),
+                other => other.as_float(),
+            })
+            .filter(|size| *size > 0.0)
+            .unwrap_or(font_size);
+        let color = property("icon_color")
+            .and_then(|value| match value {
+                PropertyValue::String(text) => kryon_core::parse_color(text),
+                other => other.as_color(),
+            })
+            .unwrap_or(text_color);
+        Some(Self { path: path.to_string(), size, color })
+    }
+
+    /// The image source backends load the icon under
+    pub fn source(&self) -> String {
+        icon_source(&self.path, self.size.round() as u32)
+    }
+}
+
+/// The image source for the SVG at `path` rasterized `size` pixels square
+pub fn icon_source(path: &str, size: u32) -> String {
+    format!("{}@{}px", path, size.clamp(1, MAX_ICON_SIZE))
+}
+
+/// The SVG file and size in an icon's image source; `None` for other images
+pub fn parse_icon_source(source: &str) -> Option<(&str, u32)> {
+    let (path, size) = source.rsplit_once('@')?;
+    let size = size.strip_suffix("px")?.parse().ok()?;
+    path.to_ascii_lowercase().ends_with(".svg").then_some((path, size))
+}
+
+/// The file an image source is loaded from: an icon's SVG, or the source
+/// itself for other images
+pub fn source_file(source: &str) -> &str {
+    parse_icon_source(source).map_or(source, |(path, _)| path)
+}
+
+/// Reads the SVG at `path` and rasterizes it `size` pixels square
+pub fn load_icon(path: &Path, size: u32) -> Result<IconBitmap, IconError> {
+    rasterize_svg(&std::fs::read_to_string(path)?, size)
+}
+
+/// Rasterizes `svg` into a square of `size` pixels, its viewBox scaled to
+/// fit and centered as `preserveAspectRatio` does by default
+pub fn rasterize_svg(svg: &str, size: u32) -> Result<IconBitmap, IconError> {
+    let size = size.clamp(1, MAX_ICON_SIZE);
+    let document = roxmltree::Document::parse(svg)?;
+    let root = document.root_element();
+    let (origin, extent) = view_box(root).ok_or(IconError::NoSize)?;
+
+    let scale = size as f32 / extent.max_element();
+    let offset = (Vec2::splat(size as f32) - extent * scale) / 2.0 - origin * scale;
+    let transform = Transform::from_row(scale, 0.0, 0.0, scale, offset.x, offset.y);
+    let mut pixmap = Pixmap::new(size, size).expect("icon size is at least one pixel");
+    draw_children(&mut pixmap, root, &Style::default().inherit(root), transform);
+
+    let pixels = pixmap.pixels().iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Ok(IconBitmap { width: size, height: size, pixels })
+}
+
+/// The viewBox's origin and size, or the width and height when it has none
+fn view_box(root: roxmltree::Node) -> Option<(Vec2, Vec2)> {
+    if let Some(view_box) = root.attribute("viewBox") {
+        if let [x, y, width, height] = numbers(view_box)[..] {
+            if width > 0.0 && height > 0.0 {
+                return Some((Vec2::new(x, y), Vec2::new(width, height)));
+            }
+        }
+    }
+    let width = root.attribute("width").and_then(number)?;
+    let height = root.attribute("height").and_then(number)?;
+    (width > 0.0 && height > 0.0).then(|| (Vec2::ZERO, Vec2::new(width, height)))
+}
+
+/// Presentation attributes, as inherited down the tree
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    /// `None` for `none`
+    fill: Option<Vec4>,
+    stroke: Option<Vec4>,
+    fill_opacity: f32,
+    stroke_opacity: f32,
+    /// The product of the element's and its ancestors' `opacity`
+    opacity: f32,
+    stroke_width: f32,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    fill_rule: FillRule,
+}
+
+impl Default for Style {
+    /// SVG's initial values, except that shapes are filled with
+    /// `currentColor` rather than black, so unstyled icons take the tint
+    fn default() -> Self {
+        Self {
+            fill: Some(Vec4::ONE),
+            stroke: None,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            opacity: 1.0,
+            stroke_width: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            fill_rule: FillRule::Winding,
+        }
+    }
+}
+
+impl Style {
+    /// This style with `node`'s presentation attributes applied
+    fn inherit(&self, node: roxmltree::Node) -> Self {
+        let mut style = *self;
+        if let Some(fill) = presentation(node, "fill").and_then(paint) {
+            style.fill = fill;
+        }
+        if let Some(stroke) = presentation(node, "stroke").and_then(paint) {
+            style.stroke = stroke;
+        }
+        if let Some(opacity) = presentation(node, "fill-opacity").and_then(number) {
+            style.fill_opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(opacity) = presentation(node, "stroke-opacity").and_then(number) {
+            style.stroke_opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(opacity) = presentation(node, "opacity").and_then(number) {
+            style.opacity *= opacity.clamp(0.0, 1.0);
+        }
+        if let Some(width) = presentation(node, "stroke-width").and_then(number) {
+            style.stroke_width = width.max(0.0);
+        }
+        match presentation(node, "stroke-linecap") {
+            Some("butt") => style.line_cap = LineCap::Butt,
+            Some("round") => style.line_cap = LineCap::Round,
+            Some("square") => style.line_cap = LineCap::Square,
+            _ => {}
+        }
+        match presentation(node, "stroke-linejoin") {
+            Some("miter") => style.line_join = LineJoin::Miter,
+            Some("round") => style.line_join = LineJoin::Round,
+            Some("bevel") => style.line_join = LineJoin::Bevel,
+            _ => {}
+        }
+        match presentation(node, "fill-rule") {
+            Some("nonzero") => style.fill_rule = FillRule::Winding,
+            Some("evenodd") => style.fill_rule = FillRule::EvenOdd,
+            _ => {}
+        }
+        style
+    }
+}
+
+/// A presentation attribute, from the `style` attribute if it's set there
+fn presentation<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    let declared = node.attribute("style").and_then(|style| {
+        style.split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+            .find(|(property, _)| property.trim() == name)
+            .map(|(_, value)| value)
+    });
+    declared.or_else(|| node.attribute(name)).map(str::trim).filter(|value| *value != "inherit")
+}
+
+/// A fill or stroke: `Some(None)` for `none`, `None` for what isn't
+/// understood, such as gradients, which leaves the inherited paint
+fn paint(value: &str) -> Option<Option<Vec4>> {
+    match value {
+        "none" => Some(None),
+        "currentColor" => Some(Some(Vec4::ONE)),
+        _ => kryon_core::parse_color(value).map(Some),
+    }
+}
+
+/// A length in user units, with or without `px`
+fn number(value: &str) -> Option<f32> {
+    let value = value.trim();
+    value.strip_suffix("px").unwrap_or(value).trim().parse().ok()
+}
+
+/// Every number in a list such as `points` or `viewBox`
+fn numbers(value: &str) -> Vec<f32> {
+    let mut lexer = Lexer::new(value);
+    std::iter::from_fn(|| lexer.number()).collect()
+}
+
+fn draw_children(pixmap: &mut Pixmap, node: roxmltree::Node, style: &Style, transform: Transform) {
+    for child in node.children().filter(|child| child.is_element()) {
+        if presentation(child, "display") == Some("none") {
+            continue;
+        }
+        let style = style.inherit(child);
+        let transform = match child.attribute("transform") {
+            Some(local) => transform.pre_concat(parse_transform(local)),
+            None => transform,
+        };
+        match child.tag_name().name() {
+            "svg" | "g" | "a" => draw_children(pixmap, child, &style, transform),
+            name => {
+                if let Some(path) = shape(child, name) {
+                    draw_shape(pixmap, &path, &style, transform);
+                }
+            }
+        }
+    }
+}
+
+fn draw_shape(pixmap: &mut Pixmap, path: &tiny_skia::Path, style: &Style, transform: Transform) {
+    let paint_with = |color: Vec4, opacity: f32| {
+        let [r, g, b, a] = (color * Vec4::new(1.0, 1.0, 1.0, opacity)).clamp(Vec4::ZERO, Vec4::ONE).to_array()
+            .map(|channel| (channel * 255.0).round() as u8);
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(r, g, b, a);
+        paint.anti_alias = true;
+        paint
+    };
+    if let Some(fill) = style.fill {
+        let paint = paint_with(fill, style.fill_opacity * style.opacity);
+        pixmap.fill_path(path, &paint, style.fill_rule, transform, None);
+    }
+    if let Some(stroke) = style.stroke.filter(|_| style.stroke_width > 0.0) {
+        let paint = paint_with(stroke, style.stroke_opacity * style.opacity);
+        let stroke = Stroke {
+            width: style.stroke_width,
+            line_cap: style.line_cap,
+            line_join: style.line_join,
+            ..Stroke::default()
+        };
+        pixmap.stroke_path(path, &paint, &stroke, transform, None);
+    }
+}
+
+/// The outline of a shape element, `None` for other elements and for
+/// shapes with nothing to draw
+fn shape(node: roxmltree::Node, name: &str) -> Option<tiny_skia::Path> {
+    let length = |attribute: &str| node.attribute(attribute).and_then(number);
+    match name {
+        "path" => parse_path(node.attribute("d")?),
+        "rect" => {
+            let (x, y) = (length("x").unwrap_or(0.0), length("y").unwrap_or(0.0));
+            let (width, height) = (length("width")?, length("height")?);
+            // A radius given on one axis applies to both
+            let (rx, ry) = match (length("rx"), length("ry")) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(radius), None) | (None, Some(radius)) => (radius, radius),
+                (None, None) => (0.0, 0.0),
+            };
+            rect_path(Rect::from_xywh(x, y, width, height)?, Vec2::new(rx, ry))
+        }
+        "circle" => {
+            let radius = length("r")?;
+            PathBuilder::from_circle(length("cx").unwrap_or(0.0), length("cy").unwrap_or(0.0), radius)
+        }
+        "ellipse" => {
+            let center = Vec2::new(length("cx").unwrap_or(0.0), length("cy").unwrap_or(0.0));
+            let radii = Vec2::new(length("rx")?, length("ry")?);
+            PathBuilder::from_oval(Rect::from_xywh(center.x - radii.x, center.y - radii.y, radii.x * 2.0, radii.y * 2.0)?)
+        }
+        "line" => {
+            let mut builder = PathBuilder::new();
+            builder.move_to(length("x1").unwrap_or(0.0), length("y1").unwrap_or(0.0));
+            builder.line_to(length("x2").unwrap_or(0.0), length("y2").unwrap_or(0.0));
+            builder.finish()
+        }
+        "polyline" | "polygon" => {
+            let points = numbers(node.attribute("points")?);
+            let mut pairs = points.chunks_exact(2);
+            let mut builder = PathBuilder::new();
+            let first = pairs.next()?;
+            builder.move_to(first[0], first[1]);
+            for point in pairs {
+                builder.line_to(point[0], point[1]);
+            }
+            if name == "polygon" {
+                builder.close();
+            }
+            builder.finish()
+        }
+        _ => None,
+    }
+}
+
+/// A rectangle with corners rounded by `radii`, clamped to half its size
+fn rect_path(rect: Rect, radii: Vec2) -> Option<tiny_skia::Path> {
+    let radii = radii.max(Vec2::ZERO).min(Vec2::new(rect.width(), rect.height()) / 2.0);
+    if radii.x == 0.0 || radii.y == 0.0 {
+        return Some(PathBuilder::from_rect(rect));
+    }
+    let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+    let mut builder = PathBuilder::new();
+    builder.move_to(left + radii.x, top);
+    let corner = |builder: &mut PathBuilder, from: Vec2, to: Vec2| {
+        builder.line_to(from.x, from.y);
+        arc_to(builder, from, radii, 0.0, false, true, to);
+    };
+    corner(&mut builder, Vec2::new(right - radii.x, top), Vec2::new(right, top + radii.y));
+    corner(&mut builder, Vec2::new(right, bottom - radii.y), Vec2::new(right - radii.x, bottom));
+    corner(&mut builder, Vec2::new(left + radii.x, bottom), Vec2::new(left, bottom - radii.y));
+    corner(&mut builder, Vec2::new(left, top + radii.y), Vec2::new(left + radii.x, top));
+    builder.close();
+    builder.finish()
+}
+
+/// A `transform` attribute's list of functions, applied right to left
+fn parse_transform(value: &str) -> Transform {
+    let mut transform = Transform::identity();
+    for function in value.split(')') {
+        let Some((name, arguments)) = function.split_once('(') else { continue };
+        let name = name.trim_matches(|c: char| c.is_whitespace() || c == ',');
+        let local = match (name, numbers(arguments).as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Transform::from_row(a, b, c, d, e, f),
+            ("translate", &[x]) => Transform::from_translate(x, 0.0),
+            ("translate", &[x, y]) => Transform::from_translate(x, y),
+            ("scale", &[scale]) => Transform::from_scale(scale, scale),
+            ("scale", &[x, y]) => Transform::from_scale(x, y),
+            ("rotate", &[angle]) => Transform::from_rotate(angle),
+            ("rotate", &[angle, x, y]) => Transform::from_rotate_at(angle, x, y),
+            ("skewX", &[angle]) => Transform::from_skew(angle.to_radians().tan(), 0.0),
+            ("skewY", &[angle]) => Transform::from_skew(0.0, angle.to_radians().tan()),
+            _ => continue,
+        };
+        transform = transform.pre_concat(local);
+    }
+    transform
+}
+
+/// The outline in SVG path data. Data after an error is ignored, as
+/// browsers do.
+fn parse_path(data: &str) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    let _ = build_path(data, &mut builder);
+    builder.finish()
+}
+
+/// The control point the previous segment ended with, which `S` and `T`
+/// reflect
+#[derive(Clone, Copy)]
+enum Control {
+    None,
+    Cubic(Vec2),
+    Quad(Vec2),
+}
+
+fn build_path(data: &str, builder: &mut PathBuilder) -> Option<()> {
+    let mut lexer = Lexer::new(data);
+    let mut current = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    let mut previous = Control::None;
+    while let Some(mut command) = lexer.command() {
+        // A command's arguments may repeat without repeating the letter
+        loop {
+            let relative = command.is_ascii_lowercase();
+            let origin = if relative { current } else { Vec2::ZERO };
+            let mut control = Control::None;
+            match command.to_ascii_uppercase() {
+                b'M' => {
+                    current = origin + lexer.point()?;
+                    start = current;
+                    builder.move_to(current.x, current.y);
+                    // Further pairs after a move are lines
+                    command = if relative { b'l' } else { b'L' };
+                }
+                b'L' => {
+                    current = origin + lexer.point()?;
+                    builder.line_to(current.x, current.y);
+                }
+                b'H' => {
+                    current.x = origin.x + lexer.number()?;
+                    builder.line_to(current.x, current.y);
+                }
+                b'V' => {
+                    current.y = origin.y + lexer.number()?;
+                    builder.line_to(current.x, current.y);
+                }
+                b'C' | b'S' => {
+                    let first = match command.to_ascii_uppercase() {
+                        b'C' => origin + lexer.point()?,
+                        _ => match previous {
+                            Control::Cubic(point) => current * 2.0 - point,
+                            _ => current,
+                        },
+                    };
+                    let second = origin + lexer.point()?;
+                    current = origin + lexer.point()?;
+                    builder.cubic_to(first.x, first.y, second.x, second.y, current.x, current.y);
+                    control = Control::Cubic(second);
+                }
+                b'Q' | b'T' => {
+                    let point = match command.to_ascii_uppercase() {
+                        b'Q' => origin + lexer.point()?,
+                        _ => match previous {
+                            Control::Quad(point) => current * 2.0 - point,
+                            _ => current,
+                        },
+                    };
+                    current = origin + lexer.point()?;
+                    builder.quad_to(point.x, point.y, current.x, current.y);
+                    control = Control::Quad(point);
+                }
+                b'A' => {
+                    let radii = lexer.point()?;
+                    let rotation = lexer.number()?;
+                    let (large_arc, sweep) = (lexer.flag()?, lexer.flag()?);
+                    let to = origin + lexer.point()?;
+                    arc_to(builder, current, radii, rotation, large_arc, sweep, to);
+                    current = to;
+                }
+                b'Z' => {
+                    builder.close();
+                    current = start;
+                }
+                _ => return None,
+            }
+            previous = control;
+            if command.eq_ignore_ascii_case(&b'Z') || !lexer.at_number() {
+                break;
+            }
+        }
+    }
+    Some(())
+}
+
+/// Appends an SVG elliptical arc from `from` to `to` as cubic curves of at
+/// most a quarter turn each, after the SVG spec's implementation notes on
+/// converting endpoints to a center
+fn arc_to(builder: &mut PathBuilder, from: Vec2, radii: Vec2, rotation: f32, large_arc: bool, sweep: bool, to: Vec2) {
+    let mut radii = radii.abs();
+    if from == to {
+        return;
+    }
+    if radii.x == 0.0 || radii.y == 0.0 {
+        builder.line_to(to.x, to.y);
+        return;
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let rotate = |v: Vec2| Vec2::new(cos * v.x - sin * v.y, sin * v.x + cos * v.y);
+    let unrotate = |v: Vec2| Vec2::new(cos * v.x + sin * v.y, cos * v.y - sin * v.x);
+
+    // The half chord in the ellipse's axes; radii too small to span it are
+    // scaled up until they do
+    let half = unrotate((from - to) / 2.0);
+    let overshoot = (half / radii).length_squared();
+    if overshoot > 1.0 {
+        radii *= overshoot.sqrt();
+    }
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let numerator = rx2 * ry2 - rx2 * half.y * half.y - ry2 * half.x * half.x;
+    let denominator = rx2 * half.y * half.y + ry2 * half.x * half.x;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center_local = Vec2::new(radii.x * half.y / radii.y, -radii.y * half.x / radii.x) * coefficient;
+    let center = rotate(center_local) + (from + to) / 2.0;
+
+    let angle = |v: Vec2| v.y.atan2(v.x);
+    let start_angle = angle((half - center_local) / radii);
+    let mut sweep_angle = angle((-half - center_local) / radii) - start_angle;
+    if sweep && sweep_angle < 0.0 {
+        sweep_angle += TAU;
+    } else if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= TAU;
+    }
+
+    let segments = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep_angle / segments as f32;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    let point = |theta: f32| center + rotate(Vec2::new(radii.x * theta.cos(), radii.y * theta.sin()));
+    let tangent = |theta: f32| rotate(Vec2::new(-radii.x * theta.sin(), radii.y * theta.cos()));
+    for segment in 0..segments {
+        let theta = start_angle + step * segment as f32;
+        let next = theta + step;
+        let end = if segment + 1 == segments { to } else { point(next) };
+        let first = point(theta) + tangent(theta) * handle;
+        let second = end - tangent(next) * handle;
+        builder.cubic_to(first.x, first.y, second.x, second.y, end.x, end.y);
+    }
+}
+
+/// Reads the numbers, flags and command letters of path data and number
+/// lists, where separators may be left out when the next number's sign or
+/// decimal point makes them unambiguous (`M10-5.5.5`)
+struct Lexer<'a> {
+    text: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text: text.as_bytes(), position: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.position).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace() || byte == b',') {
+            self.position += 1;
+        }
+    }
+
+    fn skip_digits(&mut self) -> bool {
+        let start = self.position;
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position > start
+    }
+
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+        self.peek().is_some_and(|byte| byte.is_ascii_digit() || matches!(byte, b'-' | b'+' | b'.'))
+    }
+
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let command = self.peek().filter(u8::is_ascii_alphabetic)?;
+        self.position += 1;
+        Some(command)
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.position;
+        if matches!(self.peek(), Some(b'-' | b'+')) {
+            self.position += 1;
+        }
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits |= self.skip_digits();
+        }
+        if !digits {
+            self.position = start;
+            return None;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'-' | b'+')) {
+                self.position += 1;
+            }
+            if !self.skip_digits() {
+                self.position = mantissa_end;
+            }
+        }
+        std::str::from_utf8(&self.text[start..self.position]).ok()?.parse().ok()
+    }
+
+    fn point(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.number()?, self.number()?))
+    }
+
+    /// An arc flag, which is a single digit even without a separator after it
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.position += 1;
+        Some(flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The RGBA of the pixel at `(x, y)`
+    fn pixel(bitmap: &IconBitmap, x: u32, y: u32) -> [u8; 4] {
+        let index = ((y * bitmap.width + x) * 4) as usize;
+        bitmap.pixels[index..index + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn icon_sources_carry_the_svg_and_its_size() {
+        assert_eq!(icon_source("icons/home.svg", 24), "icons/home.svg@24px");
+        assert_eq!(parse_icon_source("icons/home.svg@24px"), Some(("icons/home.svg", 24)));
+        assert_eq!(parse_icon_source("photos/me@2x.png"), None);
+        assert_eq!(parse_icon_source("icons/home.svg"), None);
+        assert_eq!(source_file("icons/home.svg@24px"), "icons/home.svg");
+        assert_eq!(source_file("photos/me@2x.png"), "photos/me@2x.png");
+
+        let mut element = Element::default();
+        assert_eq!(Icon::from_element(&element, 16.0, Vec4::ONE), None);
+        element.custom_properties.insert("icon".into(), PropertyValue::String("icons/home.svg".into()));
+        let icon = Icon::from_element(&element, 16.0, Vec4::new(0.2, 0.2, 0.2, 1.0)).unwrap();
+        assert_eq!((icon.size, icon.color), (16.0, Vec4::new(0.2, 0.2, 0.2, 1.0)), "font size and text color by default");
+
+        element.custom_properties.insert("icon_size".into(), PropertyValue::String("23.6px".into()));
+        element.custom_properties.insert("icon_color".into(), PropertyValue::String("#ff0000".into()));
+        let icon = Icon::from_element(&element, 16.0, Vec4::ONE).unwrap();
+        assert_eq!(icon.color, Vec4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(icon.source(), "icons/home.svg@24px");
+    }
+
+    #[test]
+    fn unstyled_shapes_are_drawn_white_to_be_tinted() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><path d="M4 4h16v16H4z"/></svg>"#;
+        let bitmap = rasterize_svg(svg, 48).unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (48, 48));
+        assert_eq!(pixel(&bitmap, 24, 24), [255, 255, 255, 255]);
+        assert_eq!(pixel(&bitmap, 4, 4), [0, 0, 0, 0], "outside the square, scaled by two");
+
+        let styled = r#"<svg viewBox="0 0 24 24" fill="none" stroke="currentColor">
+            <g style="stroke: #00ff00; stroke-width: 4"><line x1="0" y1="12" x2="24" y2="12"/></g>
+            <rect x="2" y="2" width="4" height="4" fill="rgb(255, 0, 0)" stroke="none"/>
+        </svg>"#;
+        let bitmap = rasterize_svg(styled, 24).unwrap();
+        assert_eq!(pixel(&bitmap, 12, 12), [0, 255, 0, 255]);
+        assert_eq!(pixel(&bitmap, 3, 3), [255, 0, 0, 255]);
+        assert_eq!(pixel(&bitmap, 12, 4), [0, 0, 0, 0], "fill none is inherited");
+    }
+
+    #[test]
+    fn arcs_transforms_and_view_boxes() {
+        // A circle of radius 10 from two arcs with compact flags, moved
+        // right by a group transform
+        let svg = r##"<svg viewBox="0 0 48 24"><g transform="translate(12, 0)">
+            <path d="M12 2a10 10 0 110 20 10 10 0 010-20z" fill="#0000ff"/>
+        </g></svg>"##;
+        let bitmap = rasterize_svg(svg, 48).unwrap();
+        // The 48x24 view box is centered in the 48 pixel square, so the
+        // circle's center (24, 12) lands at (24, 24)
+        assert_eq!(pixel(&bitmap, 24, 24), [0, 0, 255, 255]);
+        assert_eq!(pixel(&bitmap, 24, 8), [0, 0, 0, 0], "above the view box");
+        assert_eq!(pixel(&bitmap, 24, 15), [0, 0, 255, 255], "inside the top of the circle");
+        assert_eq!(pixel(&bitmap, 15, 15), [0, 0, 0, 0], "outside the circle, inside its bounding box");
+
+        assert!(matches!(rasterize_svg("<svg/>", 24), Err(IconError::NoSize)));
+        assert!(matches!(rasterize_svg("<svg", 24), Err(IconError::Parse(_))));
+        assert_eq!(numbers("M10-5.5.5e1,2"), vec![]);
+        assert_eq!(numbers("10-5.5.5e1,2"), vec![10.0, -5.5, 5.0, 2.0]);
+    }
+}
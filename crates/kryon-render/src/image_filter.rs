@@ -0,0 +1,166 @@
+// crates/kryon-render/src/image_filter.rs
+//! Color filters on `Image` elements, for disabled icons and hover tints.
+//!
+//! An element's `grayscale`, `brightness`, `contrast` and `tint` properties
+//! become an `ImageFilter` on its `DrawImage`. They apply to each texel in
+//! that order, as the CSS `filter` functions of the same names do, and the
+//! tint multiplies the result. WGPU filters in its image shader; `apply` is
+//! the same math for backends and tests that filter on the CPU.
+
+use glam::{Vec3, Vec4};
+use kryon_core::{Element, PropertyValue};
+
+/// Luma weights CSS `grayscale()` uses
+const LUMA: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageFilter {
+    /// How far each texel moves toward its luma, 0 (none) to 1 (gray)
+    pub grayscale: f32,
+    /// Multiplies each channel; 1 leaves the image as it is
+    pub brightness: f32,
+    /// Scales each channel's distance from mid-gray; 1 leaves it
+    pub contrast: f32,
+    /// Multiplies the filtered texel, alpha included
+    pub tint: Vec4,
+}
+
+impl Default for ImageFilter {
+    fn default() -> Self {
+        Self { grayscale: 0.0, brightness: 1.0, contrast: 1.0, tint: Vec4::ONE }
+    }
+}
+
+impl ImageFilter {
+    /// The filter an element's properties ask for. Amounts take numbers or
+    /// percentages (`grayscale: "100%"`), `tint` any CSS color; values that
+    /// don't parse are left at their defaults.
+    pub fn from_element(element: &Element) -> Self {
+        let property = |name: &str| element.custom_properties.get(name);
+        let mut filter = Self::default();
+        if let Some(grayscale) = property("grayscale").and_then(amount) {
+            filter.grayscale = grayscale.clamp(0.0, 1.0);
+        }
+        if let Some(brightness) = property("brightness").and_then(amount) {
+            filter.brightness = brightness.max(0.0);
+        }
+        if let Some(contrast) = property("contrast").and_then(amount) {
+            filter.contrast = contrast.max(0.0);
+        }
+        if let Some(tint) = property("tint").and_then(color) {
+            filter.tint = tint;
+        }
+        filter
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether anything but the tint changes the image, which a backend
+    /// can't do with a vertex color alone
+    pub fn adjusts_colors(&self) -> bool {
+        Self { tint: Vec4::ONE, ..*self } != Self::default()
+    }
+
+    /// `color` (straight RGBA in `0..=1`) filtered
+    pub fn apply(&self, color: Vec4) -> Vec4 {
+        let rgb = color.truncate();
+        let rgb = rgb.lerp(Vec3::splat(rgb.dot(LUMA)), self.grayscale);
+        let rgb = (rgb * self.brightness).clamp(Vec3::ZERO, Vec3::ONE);
+        let rgb = ((rgb - 0.5) * self.contrast + 0.5).clamp(Vec3::ZERO, Vec3::ONE);
+        rgb.extend(color.w) * self.tint
+    }
+
+    /// The grayscale, brightness and contrast as a CSS `filter` value, e.g.
+    /// for a 2D canvas; `None` if they leave the image as it is. The tint
+    /// has no CSS filter equivalent.
+    pub fn css_filter(&self) -> Option<String> {
+        let mut functions = Vec::new();
+        if self.grayscale != 0.0 {
+            functions.push(format!("grayscale({})", self.grayscale));
+        }
+        if self.brightness != 1.0 {
+            functions.push(format!("brightness({})", self.brightness));
+        }
+        if self.contrast != 1.0 {
+            functions.push(format!("contrast({})", self.contrast));
+        }
+        (!functions.is_empty()).then(|| functions.join(" "))
+    }
+
+    /// `grayscale`, `brightness`, `contrast` and an unused fourth value, as
+    /// the WGPU image shader reads them per vertex
+    pub fn to_array(&self) -> [f32; 4] {
+        [self.grayscale, self.brightness, self.contrast, 0.0]
+    }
+}
+
+/// A number, or a string holding a number or a percentage
+fn amount(value: &PropertyValue) -> Option<f32> {
+    match value {
+        PropertyValue::Percentage(percent) => Some(percent / 100.0),
+        PropertyValue::String(text) => {
+            let text = text.trim();
+            match text.strip_suffix('%') {
+                Some(percent) => percent.trim().parse::<f32>().ok().map(|percent| percent / 100.0),
+                None => text.parse().ok(),
+            }
+        }
+        other => other.as_float(),
+    }
+}
+
+fn color(value: &PropertyValue) -> Option<Vec4> {
+    match value {
+        PropertyValue::String(text) => kryon_core::parse_color(text),
+        other => other.as_color(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_amounts_and_tint_from_element_properties() {
+        let mut element = Element { element_type: kryon_core::ElementType::Image, ..Default::default() };
+        assert!(ImageFilter::from_element(&element).is_identity());
+
+        element.custom_properties.insert("grayscale".into(), PropertyValue::String("100%".into()));
+        element.custom_properties.insert("brightness".into(), PropertyValue::String("0.5".into()));
+        element.custom_properties.insert("contrast".into(), PropertyValue::Int(2));
+        element.custom_properties.insert("tint".into(), PropertyValue::String("#ff000080".into()));
+        let filter = ImageFilter::from_element(&element);
+        assert_eq!(filter.grayscale, 1.0);
+        assert_eq!(filter.brightness, 0.5);
+        assert_eq!(filter.contrast, 2.0);
+        assert!((filter.tint - Vec4::new(1.0, 0.0, 0.0, 128.0 / 255.0)).abs().max_element() < 1e-6);
+        assert!(filter.adjusts_colors());
+        assert_eq!(filter.css_filter().as_deref(), Some("grayscale(1) brightness(0.5) contrast(2)"));
+
+        let tint_only = ImageFilter { tint: Vec4::new(0.5, 0.5, 1.0, 1.0), ..Default::default() };
+        assert!(!tint_only.adjusts_colors());
+        assert_eq!(tint_only.css_filter(), None);
+    }
+
+    #[test]
+    fn filters_apply_in_css_order_then_tint() {
+        let close = |a: Vec4, b: Vec4| (a - b).abs().max_element() < 1e-4;
+        let red = Vec4::new(1.0, 0.0, 0.0, 1.0);
+
+        let gray = ImageFilter { grayscale: 1.0, ..Default::default() }.apply(red);
+        assert!(close(gray, Vec4::new(0.2126, 0.2126, 0.2126, 1.0)), "{:?}", gray);
+
+        let dimmed = ImageFilter { brightness: 0.5, ..Default::default() }.apply(Vec4::new(0.8, 0.4, 0.2, 0.5));
+        assert!(close(dimmed, Vec4::new(0.4, 0.2, 0.1, 0.5)), "{:?}", dimmed);
+
+        let flat = ImageFilter { contrast: 0.0, ..Default::default() }.apply(red);
+        assert!(close(flat, Vec4::new(0.5, 0.5, 0.5, 1.0)), "{:?}", flat);
+
+        let tinted = ImageFilter { tint: Vec4::new(0.0, 0.0, 1.0, 0.5), ..Default::default() }.apply(Vec4::ONE);
+        assert!(close(tinted, Vec4::new(0.0, 0.0, 1.0, 0.5)), "{:?}", tinted);
+
+        assert_eq!(ImageFilter::default().apply(red), red);
+    }
+}
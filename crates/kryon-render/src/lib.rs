@@ -1,8 +1,9 @@
 use glam::{Vec2, Vec4};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 // use tracing::info; // No longer needed
 
-use kryon_core::{Element, ElementId, ElementType, PropertyValue, StyleComputer, TextAlignment, TransformData};
+use kryon_core::{BackgroundRepeat, CellValue, ColorScheme, ComputedStyle, DefaultStyles, Element, ElementId, ElementType, Orientation, OutputRotation, ParticleInstance, PropertyValue, StyleComputer, TableView, TextAlignment, TransformData};
 use kryon_layout::LayoutResult;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -17,6 +18,55 @@ pub use events::*;
 pub mod text_manager;
 pub use text_manager::*;
 
+pub mod glyph_atlas;
+pub use glyph_atlas::{AtlasGlyph, GlyphAtlas, GlyphQuad};
+
+pub mod stats;
+pub use stats::{RenderStats, RenderStatsSummary};
+
+pub mod warnings;
+pub use warnings::{RendererWarning, Unsupported};
+
+pub mod shadow;
+pub use shadow::{BoxShadow, ShadowLayer};
+
+pub mod snapshot;
+pub use snapshot::{FrameSnapshot, SnapshotSlot};
+
+pub mod dirty;
+pub use dirty::{DirtyRegion, FrameChange, FrameDiff};
+
+pub mod display_list;
+pub use display_list::{DisplayItem, DisplayKey, DisplayList};
+
+pub mod threaded;
+pub use threaded::{Backpressure, ThreadedRenderer};
+
+pub mod text_input;
+
+pub mod image_filter;
+pub use image_filter::ImageFilter;
+
+pub mod icon;
+pub use icon::{Icon, IconBitmap, IconError};
+
+pub mod animated_image;
+pub use animated_image::{AnimatedImage, AnimationError, AnimationFrame, FrameClock};
+
+pub mod color_picker;
+
+pub mod gradient;
+pub use gradient::{GradientStrip, ShaderGradient};
+
+pub mod pagination;
+pub use pagination::{Page, PageMargins, PageSetup};
+
+pub mod drag_ghost;
+pub use drag_ghost::DragGhost;
+
+pub mod window;
+pub use window::{MonitorInfo, WindowGeometry, WindowPlacement};
+
 #[cfg(feature = "wasm")]
 pub mod wasm;
 #[cfg(feature = "wasm")]
@@ -54,11 +104,18 @@ pub trait Renderer {
         element_id: ElementId,
     ) -> RenderResult<()>;
     fn resize(&mut self, new_size: Vec2) -> RenderResult<()>;
+    /// Size of the surface in device pixels
     fn viewport_size(&self) -> Vec2;
+    /// Device pixels per logical pixel, e.g. 2 on a HiDPI display. Layout
+    /// and commands are in logical pixels; the backend scales them to the
+    /// surface as it draws, and rasterizes text at the device size.
+    fn viewport_scale(&self) -> f32 {
+        1.0
+    }
 }
 
 /// High-level rendering commands for backends that use them.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RenderCommand {
     DrawRect {
         position: Vec2,
@@ -81,6 +138,9 @@ pub enum RenderCommand {
         max_height: Option<f32>,
         transform: Option<TransformData>,
         font_family: Option<String>,
+        /// Styled byte ranges of `text` from inline markup; empty for plain
+        /// text. Backends that can't style runs draw `text` as it is.
+        runs: Vec<kryon_core::TextRun>,
         z_index: i32,
     },
     DrawRichText {
@@ -99,6 +159,59 @@ pub enum RenderCommand {
         source: String,
         opacity: f32,
         transform: Option<TransformData>,
+        image_rendering: ImageRendering,
+        /// Color adjustments from the element's filter properties
+        filter: ImageFilter,
+    },
+    /// `background_image` tiled over the element's box inside its border,
+    /// above its background color. One tile of `tile_size` sits at
+    /// `tile_offset` from `position` and repeats from there along the axes
+    /// `repeat` allows, clipped to the box. `tile_size` is zero while the
+    /// image's size is unknown and the `background_size` depends on it;
+    /// backends then just start loading the image.
+    DrawTiledImage {
+        position: Vec2,
+        size: Vec2,
+        source: String,
+        tile_offset: Vec2,
+        tile_size: Vec2,
+        repeat: BackgroundRepeat,
+        opacity: f32,
+        image_rendering: ImageRendering,
+        transform: Option<TransformData>,
+        z_index: i32,
+    },
+    /// A `linear-gradient(...)` or `radial-gradient(...)` background over
+    /// the element's box inside its border, above its background color.
+    /// The element's opacity is already in the stops' alpha.
+    DrawGradientRect {
+        position: Vec2,
+        size: Vec2,
+        gradient: kryon_core::Gradient,
+        border_radius: f32,
+        transform: Option<TransformData>,
+        z_index: i32,
+    },
+    /// Custom WGSL effect filling the element rect (`shader_effect` property).
+    /// The element's regular background `DrawRect` is still emitted underneath,
+    /// so backends without a shader pipeline, or snippets that fail validation,
+    /// fall back to the static background color.
+    DrawShaderEffect {
+        position: Vec2,
+        size: Vec2,
+        /// KRB resource path of the WGSL snippet, or the inline snippet itself.
+        shader: String,
+        /// Exposed to the shader as `kryon.color_a` / `kryon.color_b`.
+        primary_color: Vec4,
+        secondary_color: Vec4,
+        z_index: i32,
+    },
+    /// Live particles of a ParticleEmitter element, already culled to the viewport.
+    /// Backends draw each particle as a quad (or `sprite` image) centered on its position.
+    DrawParticles {
+        particles: Vec<ParticleInstance>,
+        sprite: Option<String>,
+        z_index: i32,
     },
     SetClip {
         position: Vec2,
@@ -131,6 +244,13 @@ pub enum RenderCommand {
         border_radius: f32,
         is_focused: bool,
         is_readonly: bool,
+        /// Caret and selection of the focused input, as character indices
+        /// into `text`; `None` when it isn't focused
+        caret: Option<text_input::Caret>,
+        /// How far `text` is moved left to keep the caret in view
+        scroll_offset: f32,
+        /// How far through its blink the caret is; see `text_input::blink_phase`
+        blink_phase: f32,
         transform: Option<TransformData>,
     },
     DrawCheckbox {
@@ -251,6 +371,43 @@ pub enum RenderCommand {
     },
 }
 
+impl RenderCommand {
+    /// The variant name, used to group commands in render statistics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RenderCommand::DrawRect { .. } => "DrawRect",
+            RenderCommand::DrawText { .. } => "DrawText",
+            RenderCommand::DrawRichText { .. } => "DrawRichText",
+            RenderCommand::DrawImage { .. } => "DrawImage",
+            RenderCommand::DrawTiledImage { .. } => "DrawTiledImage",
+            RenderCommand::DrawGradientRect { .. } => "DrawGradientRect",
+            RenderCommand::DrawShaderEffect { .. } => "DrawShaderEffect",
+            RenderCommand::DrawParticles { .. } => "DrawParticles",
+            RenderCommand::SetClip { .. } => "SetClip",
+            RenderCommand::ClearClip => "ClearClip",
+            RenderCommand::SetCanvasSize(_) => "SetCanvasSize",
+            RenderCommand::NativeRendererView { .. } => "NativeRendererView",
+            RenderCommand::DrawTextInput { .. } => "DrawTextInput",
+            RenderCommand::DrawCheckbox { .. } => "DrawCheckbox",
+            RenderCommand::DrawSlider { .. } => "DrawSlider",
+            RenderCommand::DrawScrollbar { .. } => "DrawScrollbar",
+            RenderCommand::BeginCanvas { .. } => "BeginCanvas",
+            RenderCommand::EndCanvas => "EndCanvas",
+            RenderCommand::DrawCanvasLine { .. } => "DrawCanvasLine",
+            RenderCommand::DrawCanvasRect { .. } => "DrawCanvasRect",
+            RenderCommand::DrawCanvasCircle { .. } => "DrawCanvasCircle",
+            RenderCommand::DrawCanvasText { .. } => "DrawCanvasText",
+            RenderCommand::DrawCanvasEllipse { .. } => "DrawCanvasEllipse",
+            RenderCommand::DrawCanvasPolygon { .. } => "DrawCanvasPolygon",
+            RenderCommand::DrawCanvasPath { .. } => "DrawCanvasPath",
+            RenderCommand::DrawCanvasImage { .. } => "DrawCanvasImage",
+            RenderCommand::BeginWasmView { .. } => "BeginWasmView",
+            RenderCommand::EndWasmView => "EndWasmView",
+            RenderCommand::ExecuteWasmFunction { .. } => "ExecuteWasmFunction",
+        }
+    }
+}
+
 /// Trait for backends that use command-based rendering.
 pub trait CommandRenderer: Renderer {
     fn execute_commands(
@@ -263,6 +420,254 @@ pub trait CommandRenderer: Renderer {
     fn set_cursor(&mut self, _cursor_type: kryon_core::CursorType) {
         // Default implementation does nothing
     }
+    
+    /// GPU/CPU memory held by the backend's caches (optional - reported as empty by default)
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats::default()
+    }
+    
+    /// Draws every following frame rotated onto the surface. Commands stay
+    /// in logical coordinates (`OutputRotation::logical_size` of the surface).
+    /// Returns false if the backend can't rotate its output.
+    fn set_output_rotation(&mut self, rotation: OutputRotation) -> bool {
+        rotation == OutputRotation::None
+    }
+    
+    /// Name used in warnings, e.g. `wgpu`
+    fn backend_name(&self) -> &'static str {
+        "unknown"
+    }
+    
+    /// What the last `execute_commands` had to skip; see `warnings`.
+    fn take_unsupported(&mut self) -> Vec<Unsupported> {
+        Vec::new()
+    }
+    
+    /// Image sources decoded since the last call, with their natural size
+    /// in pixels, so layout can size Image elements that set no size.
+    fn take_decoded_images(&mut self) -> Vec<(String, Vec2)> {
+        Vec::new()
+    }
+    
+    /// Where the first baseline of the backend's text sits below the top of
+    /// its box, as a fraction of the font size, for baseline alignment.
+    /// `None` leaves layout to its estimate.
+    fn text_baseline(&mut self) -> Option<f32> {
+        None
+    }
+    
+    /// Measures text as the backend draws it, for layout to size text
+    /// elements by. Asked once, when the app starts; `None` leaves layout
+    /// to its estimate.
+    fn text_measurer(&mut self) -> Option<Box<dyn kryon_layout::TextMeasurer>> {
+        None
+    }
+    
+    /// Does `work` now, ahead of the first frame that needs it. The runtime
+    /// calls this on idle frames, within a time budget. Returns false if the
+    /// backend has no cache the work would fill.
+    fn warm_up(&mut self, _work: &WarmUp) -> bool {
+        false
+    }
+    
+    /// Text on the system clipboard, for pasting into inputs. `None` if the
+    /// backend has no access to it; the runtime keeps a clipboard of its own.
+    fn clipboard_text(&mut self) -> Option<String> {
+        None
+    }
+    
+    /// Puts copied text on the system clipboard. Returns false if the
+    /// backend has no access to it.
+    fn set_clipboard_text(&mut self, _text: &str) -> bool {
+        false
+    }
+    
+    /// Whether every element's commands have to be sent, even for elements
+    /// entirely outside the viewport or a clip, e.g. because the backend
+    /// draws onto a surface larger than its `viewport_size`
+    fn needs_full_command_list(&self) -> bool {
+        false
+    }
+    
+    /// Whether popups such as the date picker's calendar are drawn as a
+    /// list of lines, because the backend puts text in cells too coarse for
+    /// a grid
+    fn prefers_list_popups(&self) -> bool {
+        false
+    }
+    
+    /// Moves animated images on by `delta`, the app's frame time, and
+    /// updates the textures whose frame changed. Returns true if any did,
+    /// so the next frame has to be drawn even if nothing else changed.
+    fn advance_animations(&mut self, _delta: std::time::Duration) -> bool {
+        false
+    }
+    
+    /// Whether `DrawShaderEffect` is drawn, so widgets such as the color
+    /// picker can draw with shaders instead of precomputed textures
+    fn supports_shader_effects(&self) -> bool {
+        false
+    }
+    
+    /// Whether `sample_screen_color` can read the screen, for color
+    /// inputs' eyedropper
+    fn supports_eyedropper(&self) -> bool {
+        false
+    }
+    
+    /// The color on screen at `position`, in window coordinates, including
+    /// what other windows show there. `None` if the backend can't read it.
+    fn sample_screen_color(&mut self, _position: Vec2) -> Option<Vec4> {
+        None
+    }
+    
+    /// Whether a frame whose commands are the same as the last one's can go
+    /// undrawn, `begin_frame` and `end_frame` included, because the output
+    /// still shows it. False for backends whose `end_frame` does more than
+    /// present, such as raylib's, which polls input there.
+    fn skips_unchanged_frames(&self) -> bool {
+        false
+    }
+    
+    /// Redraws only `dirty` over what the last frame drew, given the whole
+    /// frame's `commands`. Returns false, having drawn nothing, if the
+    /// backend can't, and the frame goes to `execute_commands` instead.
+    fn execute_dirty(
+        &mut self,
+        _context: &mut Self::Context,
+        _commands: &[RenderCommand],
+        _dirty: &DirtyRegion,
+    ) -> RenderResult<bool> {
+        Ok(false)
+    }
+    
+    /// Whether the backend keeps what it builds from each display list
+    /// item between frames, so frames should go to `execute_display_list`
+    fn retains_display_list(&self) -> bool {
+        false
+    }
+    
+    /// Draws the frame's `commands`, grouped into `list`'s items, rebuilding
+    /// only the items whose key wasn't in the last frame drawn. Returns how
+    /// many items were rebuilt; by default all of them, through
+    /// `execute_commands`.
+    fn execute_display_list(
+        &mut self,
+        context: &mut Self::Context,
+        commands: &[RenderCommand],
+        list: &DisplayList,
+    ) -> RenderResult<usize> {
+        self.execute_commands(context, commands)?;
+        Ok(list.len())
+    }
+}
+
+/// Memory held by a backend's texture and glyph caches. Byte counts are
+/// computed from dimensions and pixel formats, not queried from the driver.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackendMemoryStats {
+    pub textures: usize,
+    pub texture_bytes: usize,
+    pub glyph_atlases: usize,
+    pub glyph_atlas_bytes: usize,
+    pub cached_glyphs: usize,
+}
+
+/// How an image is filtered when it's drawn at a size other than its own,
+/// from the element's `image_rendering` property (CSS `image-rendering`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImageRendering {
+    /// Smooth, as browsers draw images by default
+    #[default]
+    Auto,
+    /// Nearest-neighbour, so the texels of pixel art stay sharp squares
+    Pixelated,
+    /// Bilinear, and trilinear between mipmaps where the backend has them
+    Smooth,
+}
+
+/// Images whose larger side is at least this many pixels are given
+/// mipmaps by the backends that support them, so they don't shimmer when
+/// drawn much smaller
+pub const MIPMAP_MIN_SIZE: u32 = 256;
+
+impl ImageRendering {
+    /// Parses an `image_rendering` value; `None` for anything unknown.
+    /// `crisp-edges` is drawn pixelated, as most browsers do.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "pixelated" | "crisp-edges" => Some(Self::Pixelated),
+            "smooth" | "high-quality" => Some(Self::Smooth),
+            _ => None,
+        }
+    }
+}
+
+/// An element's `image_rendering`, `Auto` if it's unset or unknown
+fn image_rendering(element: &Element) -> ImageRendering {
+    element.custom_properties.get("image_rendering")
+        .and_then(|v| v.as_string())
+        .and_then(ImageRendering::parse)
+        .unwrap_or_default()
+}
+
+/// Whether two (position, size) rectangles overlap or touch
+fn rects_overlap(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> bool {
+    a.0.x <= b.0.x + b.1.x && b.0.x <= a.0.x + a.1.x && a.0.y <= b.0.y + b.1.y && b.0.y <= a.0.y + a.1.y
+}
+
+/// The part of rectangle `a` inside `b`, empty if they don't overlap
+/// The layer a command is drawn in; commands are stably sorted by it
+fn draw_order(command: &RenderCommand) -> i32 {
+    match command {
+        RenderCommand::DrawRect { z_index, .. } => *z_index,
+        RenderCommand::DrawText { z_index, .. } => *z_index,
+        RenderCommand::DrawRichText { z_index, .. } => *z_index,
+        RenderCommand::DrawScrollbar { z_index, .. } => *z_index,
+        RenderCommand::DrawShaderEffect { z_index, .. } => *z_index,
+        RenderCommand::DrawTiledImage { z_index, .. } => *z_index,
+        RenderCommand::DrawGradientRect { z_index, .. } => *z_index,
+        RenderCommand::DrawParticles { z_index, .. } => *z_index,
+        RenderCommand::DrawImage { .. } => 0,
+        RenderCommand::DrawTextInput { .. } => 1,
+        RenderCommand::DrawCheckbox { .. } => 1,
+        RenderCommand::DrawSlider { .. } => 1,
+        _ => 0,
+    }
+}
+
+fn rect_intersection(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> (Vec2, Vec2) {
+    let min = a.0.max(b.0);
+    let max = (a.0 + a.1).min(b.0 + b.1);
+    (min, (max - min).max(Vec2::ZERO))
+}
+
+/// A backend's scale, or 1 if it reports nothing usable
+fn usable_scale(scale: f32) -> f32 {
+    if scale.is_finite() && scale > 0.0 { scale } else { 1.0 }
+}
+
+/// Whether an element can draw outside its layout box: particles fly free
+/// of it, and transforms and shadows reach past it
+fn draws_outside_box(element: &Element) -> bool {
+    element.element_type == ElementType::ParticleEmitter
+        || element.custom_properties.contains_key("transform")
+        || element.custom_properties.contains_key("shadow")
+        || element.custom_properties.contains_key("error_message")
+}
+
+/// Cache-filling work a backend would otherwise do lazily, on the first
+/// frame that draws the glyph, image or effect; see `CommandRenderer::warm_up`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WarmUp {
+    /// Rasterize `characters` at `font_size` pixels; `None` for the
+    /// backend's default font
+    Glyphs { font_family: Option<String>, font_size: u32, characters: String },
+    /// Load and decode the image at `source`
+    Image { source: String },
+    /// Compile the pipeline for a `shader_effect`
+    ShaderEffect { shader: String },
 }
 
 /// The bridge between the scene graph and the rendering backend.
@@ -271,19 +676,202 @@ pub struct ElementRenderer<R: CommandRenderer> {
     backend: R,
     style_computer: StyleComputer,
     viewport_size: Vec2,
+    particles: Arc<HashMap<ElementId, Vec<ParticleInstance>>>,
+    tables: Arc<HashMap<ElementId, TableView>>,
+    overlay: Arc<Vec<RenderCommand>>,
+    /// The element being dragged, drawn again above the overlay
+    drag_ghost: Option<DragGhost>,
+    stats: RenderStats,
+    rotation: OutputRotation,
+    /// The backend's `viewport_scale` as of its last resize
+    scale: f32,
+    /// (feature, element) pairs already warned about
+    reported: HashSet<(&'static str, Option<ElementId>)>,
+    warnings: Vec<RendererWarning>,
+    /// How far each scroll container's content is scrolled
+    scroll_offsets: HashMap<ElementId, Vec2>,
+    /// Elements whose contents aren't laid out, so aren't drawn
+    skipped_subtrees: HashSet<ElementId>,
+    /// Natural sizes of the images the backend has decoded, to size
+    /// background tiles by
+    image_sizes: HashMap<String, Vec2>,
+    /// The last frame's commands, to find what the next one changed
+    frame_diff: FrameDiff,
 }
 
 impl<R: CommandRenderer> ElementRenderer<R> {
     pub fn new(backend: R, style_computer: StyleComputer) -> Self {
-        let viewport_size = backend.viewport_size();
+        let scale = usable_scale(backend.viewport_scale());
+        let viewport_size = backend.viewport_size() / scale;
         Self {
             backend,
             style_computer,
             viewport_size,
+            particles: Arc::default(),
+            tables: Arc::default(),
+            overlay: Arc::default(),
+            drag_ghost: None,
+            stats: RenderStats::default(),
+            rotation: OutputRotation::None,
+            scale,
+            reported: HashSet::new(),
+            warnings: Vec::new(),
+            scroll_offsets: HashMap::new(),
+            skipped_subtrees: HashSet::new(),
+            image_sizes: HashMap::new(),
+            frame_diff: FrameDiff::new(),
         }
     }
 
+    /// Starts over with a new document's styles, dropping its particles and
+    /// tables. The backend, overlay and output rotation are kept.
+    pub fn reset_scene(&mut self, mut style_computer: StyleComputer) {
+        style_computer.set_default_styles(self.style_computer.default_styles().clone());
+        style_computer.set_color_scheme(self.style_computer.color_scheme());
+        self.style_computer = style_computer;
+        self.particles = Arc::default();
+        self.tables = Arc::default();
+        self.reported.clear();
+        self.scroll_offsets.clear();
+        self.skipped_subtrees.clear();
+        self.drag_ghost = None;
+        self.frame_diff.invalidate();
+    }
+    
+    /// Features the backend couldn't draw since the last call, each
+    /// (feature, element) pair reported once.
+    pub fn take_warnings(&mut self) -> Vec<RendererWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+    
+    /// See `CommandRenderer::take_decoded_images`.
+    pub fn take_decoded_images(&mut self) -> Vec<(String, Vec2)> {
+        let decoded = self.backend.take_decoded_images();
+        self.image_sizes.extend(decoded.iter().cloned());
+        decoded
+    }
+    
+    /// See `CommandRenderer::text_baseline`.
+    pub fn text_baseline(&mut self) -> Option<f32> {
+        self.backend.text_baseline()
+    }
+    
+    /// See `CommandRenderer::text_measurer`.
+    pub fn text_measurer(&mut self) -> Option<Box<dyn kryon_layout::TextMeasurer>> {
+        self.backend.text_measurer()
+    }
+    
+    /// See `CommandRenderer::advance_animations`.
+    pub fn advance_animations(&mut self, delta: std::time::Duration) -> bool {
+        self.backend.advance_animations(delta)
+    }
+    
+    /// See `CommandRenderer::sample_screen_color`.
+    pub fn sample_screen_color(&mut self, position: Vec2) -> Option<Vec4> {
+        self.backend.sample_screen_color(position)
+    }
+    
+    /// See `CommandRenderer::warm_up`.
+    pub fn warm_up(&mut self, work: &WarmUp) -> bool {
+        self.backend.warm_up(work)
+    }
+    
+    /// See `CommandRenderer::clipboard_text`.
+    pub fn clipboard_text(&mut self) -> Option<String> {
+        self.backend.clipboard_text()
+    }
+    
+    /// See `CommandRenderer::set_clipboard_text`.
+    pub fn set_clipboard_text(&mut self, text: &str) -> bool {
+        self.backend.set_clipboard_text(text)
+    }
+
+    /// Elements under the pointer, innermost first; see
+    /// `StyleComputer::set_hover_path`.
+    pub fn set_hover_path(&mut self, path: &[ElementId]) {
+        self.style_computer.set_hover_path(path);
+    }
+
+    /// Scroll offsets of scroll containers, in pixels. The layout passed to
+    /// `collect_render_commands` already has their content moved; these
+    /// only place the scrollbar thumbs.
+    pub fn set_scroll_offsets(&mut self, offsets: &HashMap<ElementId, Vec2>) {
+        self.scroll_offsets.clone_from(offsets);
+    }
+
+    /// Elements whose children are left undrawn, for `content_visibility: auto`;
+    /// see `LayoutEngine::set_skipped_subtrees`.
+    pub fn set_skipped_subtrees(&mut self, skipped: impl IntoIterator<Item = ElementId>) {
+        self.skipped_subtrees = skipped.into_iter().collect();
+    }
+
+    /// Computes styles for drawing; also used for hit testing, so both
+    /// share one cache.
+    pub fn style_computer(&self) -> &StyleComputer {
+        &self.style_computer
+    }
+
+    /// See `StyleComputer::set_orientation`.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.style_computer.set_orientation(orientation);
+    }
+
+    /// See `StyleComputer::set_default_styles`.
+    pub fn set_default_styles(&mut self, default_styles: DefaultStyles) {
+        self.style_computer.set_default_styles(default_styles);
+    }
+
+    /// See `StyleComputer::set_color_scheme`.
+    pub fn set_color_scheme(&mut self, color_scheme: ColorScheme) {
+        self.style_computer.set_color_scheme(color_scheme);
+    }
+
+    /// Rotates the output for a display mounted sideways or upside down.
+    /// `viewport_size` becomes the rotated size. Returns false, changing
+    /// nothing, if the backend can't rotate.
+    pub fn set_output_rotation(&mut self, rotation: OutputRotation) -> bool {
+        let surface = self.surface_size();
+        if !self.backend.set_output_rotation(rotation) {
+            return false;
+        }
+        self.rotation = rotation;
+        self.viewport_size = rotation.logical_size(surface) / self.scale;
+        true
+    }
+
+    pub fn output_rotation(&self) -> OutputRotation {
+        self.rotation
+    }
+
+    /// The backend's unrotated size, in device pixels
+    pub fn surface_size(&self) -> Vec2 {
+        self.rotation.logical_size(self.viewport_size * self.scale)
+    }
+
+    /// Device pixels per logical pixel; see `Renderer::viewport_scale`
+    pub fn viewport_scale(&self) -> f32 {
+        self.scale
+    }
+
     /// Renders a complete frame by generating and executing a single batch of commands.
+    /// Draws a published snapshot, taking its hover path, scroll offsets,
+    /// particles, tables, overlay and drag ghost in place of any set before.
+    pub fn render_snapshot(&mut self, snapshot: &FrameSnapshot) -> RenderResult<()> {
+        self.style_computer.set_orientation(snapshot.orientation);
+        self.style_computer.set_color_scheme(snapshot.color_scheme);
+        self.style_computer.set_hover_path(&snapshot.hover_path);
+        self.scroll_offsets.clone_from(&snapshot.scroll_offsets);
+        self.skipped_subtrees.clone_from(&snapshot.skipped_subtrees);
+        self.particles = snapshot.particles.clone();
+        self.tables = snapshot.tables.clone();
+        self.overlay = snapshot.overlay.clone();
+        self.drag_ghost = snapshot.drag_ghost;
+        match snapshot.root_id {
+            Some(root_id) => self.render_frame(&snapshot.elements, &snapshot.layout, root_id, snapshot.clear_color),
+            None => Ok(()),
+        }
+    }
+
     pub fn render_frame(
         &mut self,
         elements: &HashMap<ElementId, Element>,
@@ -291,53 +879,198 @@ impl<R: CommandRenderer> ElementRenderer<R> {
         root_id: ElementId,
         clear_color: Vec4,
     ) -> RenderResult<()> {
-        let mut context = self.backend.begin_frame(clear_color)?;
+        let mut stats = RenderStats::default();
+        // Styles cached from the last frame stay valid unless their inputs changed
+        self.style_computer.sync_elements(elements);
 
-        if let Some(root_element) = elements.get(&root_id) {
-            let mut all_commands = Vec::new();
+        let Some(root_element) = elements.get(&root_id) else {
+            self.frame_diff.invalidate();
+            let context = self.backend.begin_frame(clear_color)?;
+            (stats.style_cache_hits, stats.style_cache_misses) = self.style_computer.take_cache_stats();
+            self.stats = stats;
+            return self.backend.end_frame(context);
+        };
 
-            // Use the root element's size as defined in the KRB file for the canvas.
-            let canvas_size = root_element.size;
-            if canvas_size.x > 0.0 && canvas_size.y > 0.0 {
-                all_commands.push(RenderCommand::SetCanvasSize(canvas_size));
-            }
+        let mut all_commands = Vec::new();
 
-            // Recursively fill the command list from the element tree.
-            self.collect_render_commands(&mut all_commands, elements, layout, root_id, root_element)?;
-
-            // Sort all commands by z_index to ensure proper layering
-            all_commands.sort_by_key(|cmd| {
-                match cmd {
-                    RenderCommand::DrawRect { z_index, .. } => *z_index,
-                    RenderCommand::DrawText { z_index, .. } => *z_index,
-                    RenderCommand::DrawRichText { z_index, .. } => *z_index,
-                    RenderCommand::DrawScrollbar { z_index, .. } => *z_index,
-                    RenderCommand::DrawImage { .. } => 0,
-                    RenderCommand::DrawTextInput { .. } => 1,
-                    RenderCommand::DrawCheckbox { .. } => 1,
-                    RenderCommand::DrawSlider { .. } => 1,
-                    _ => 0,
-                }
-            });
+        // Use the root element's size as defined in the KRB file for the canvas.
+        let canvas_size = root_element.size;
+        if canvas_size.x > 0.0 && canvas_size.y > 0.0 {
+            all_commands.push(RenderCommand::SetCanvasSize(canvas_size));
+        }
+        // The element each command was drawn for, to attribute warnings
+        let mut origins = vec![None; all_commands.len()];
+
+        // Elements are culled against the viewport, or the canvas where that's
+        // larger, since some backends scale the canvas to fit
+        let visible = (!self.backend.needs_full_command_list())
+            .then(|| (Vec2::ZERO, self.viewport_size.max(canvas_size)));
+
+        // Recursively fill the command list from the element tree.
+        self.collect_render_commands(&mut all_commands, &mut origins, &mut stats, elements, layout, root_id, root_element, visible)?;
 
-            self.backend.execute_commands(&mut context, &all_commands)?;
+        // Sort all commands by z_index to ensure proper layering
+        let mut tagged: Vec<(RenderCommand, Option<ElementId>)> = all_commands.into_iter().zip(origins).collect();
+        tagged.sort_by_key(|(cmd, _)| draw_order(cmd));
+        let (mut all_commands, origins): (Vec<RenderCommand>, Vec<Option<ElementId>>) = tagged.into_iter().unzip();
+
+        // The overlay layer is drawn above every element, and a drag ghost
+        // above that
+        all_commands.extend(self.overlay.iter().cloned());
+        if let Some(ghost) = self.drag_ghost {
+            all_commands.extend(self.drag_ghost_commands(elements, layout, &ghost)?);
         }
 
+        stats.count_commands(&all_commands);
+        (stats.style_cache_hits, stats.style_cache_misses) = self.style_computer.take_cache_stats();
+
+        let change = self.frame_diff.update(self.viewport_size, clear_color, &all_commands, &origins);
+        if change == FrameChange::Unchanged && self.backend.skips_unchanged_frames() {
+            stats.skipped = true;
+            tracing::debug!(target: "kryon::render_stats", "{}", stats.summary());
+            self.stats = stats;
+            return Ok(());
+        }
+
+        let mut context = self.backend.begin_frame(clear_color)?;
+        let drawn = match &change {
+            FrameChange::Partial(dirty) => self.backend.execute_dirty(&mut context, &all_commands, dirty).and_then(|drawn| {
+                if drawn {
+                    stats.dirty_elements = dirty.elements.len();
+                    Ok(())
+                } else {
+                    self.execute_all(&mut context, &all_commands, &origins, &mut stats)
+                }
+            }),
+            _ => self.execute_all(&mut context, &all_commands, &origins, &mut stats),
+        };
+        if drawn.is_err() {
+            // What the backend shows no longer matches the commands kept
+            self.frame_diff.invalidate();
+        }
+        drawn?;
+        self.record_unsupported(&origins);
+
+        tracing::debug!(target: "kryon::render_stats", "{}", stats.summary());
+        self.stats = stats;
+
         self.backend.end_frame(context)?;
         Ok(())
     }
 
-    /// Recursively traverses the element tree and appends drawing commands to a list.
+    /// Sends the whole frame, as a display list if the backend retains one
+    fn execute_all(
+        &mut self,
+        context: &mut R::Context,
+        commands: &[RenderCommand],
+        origins: &[Option<ElementId>],
+        stats: &mut RenderStats,
+    ) -> RenderResult<()> {
+        if !self.backend.retains_display_list() {
+            return self.backend.execute_commands(context, commands);
+        }
+        let list = DisplayList::build(commands, origins);
+        stats.display_items = list.len();
+        stats.rebuilt_items = self.backend.execute_display_list(context, commands, &list)?;
+        Ok(())
+    }
+
+    /// The contents of `container_id`, everything its children draw, cut
+    /// into pages as [`pagination`] describes. The contents are taken from
+    /// their start however far the container is scrolled, and blocks are
+    /// kept whole by `break_inside: avoid` on the elements. The container's
+    /// own background and border aren't part of its contents.
+    pub fn paginate(
+        &self,
+        elements: &HashMap<ElementId, Element>,
+        layout: &LayoutResult,
+        container_id: ElementId,
+        setup: &PageSetup,
+    ) -> RenderResult<Vec<Page>> {
+        let Some(container) = elements.get(&container_id) else {
+            return Ok(Vec::new());
+        };
+        let mut commands = Vec::new();
+        let mut origins = Vec::new();
+        let mut stats = RenderStats::default();
+        let children: &[ElementId] = if self.skipped_subtrees.contains(&container_id) { &[] } else { &container.children };
+        for child_id in children {
+            if let Some(child) = elements.get(child_id) {
+                self.collect_render_commands(&mut commands, &mut origins, &mut stats, elements, layout, *child_id, child, None)?;
+            }
+        }
+        commands.sort_by_key(draw_order);
+
+        // Children are laid out already scrolled
+        let position = layout.computed_positions.get(&container_id).copied().unwrap_or(container.position);
+        let origin = position - self.scroll_offsets.get(&container_id).copied().unwrap_or_default();
+        let mut height: f32 = 0.0;
+        let mut avoid = Vec::new();
+        let mut pending: Vec<ElementId> = children.to_vec();
+        while let Some(element_id) = pending.pop() {
+            let Some(element) = elements.get(&element_id) else {
+                continue;
+            };
+            let top = layout.computed_positions.get(&element_id).copied().unwrap_or(element.position).y - origin.y;
+            let bottom = top + layout.computed_sizes.get(&element_id).copied().unwrap_or(element.size).y;
+            height = height.max(bottom);
+            if element.custom_properties.get("break_inside").and_then(|value| value.as_string()) == Some("avoid") {
+                avoid.push((top, bottom));
+            }
+            if !self.skipped_subtrees.contains(&element_id) {
+                pending.extend(&element.children);
+            }
+        }
+        Ok(pagination::paginate(&commands, origin, height, setup, &avoid))
+    }
+
+    /// What `ghost`'s element and its descendants draw, moved and faded as
+    /// the ghost. Parts of it out of view are drawn all the same, since the
+    /// ghost is somewhere else.
+    fn drag_ghost_commands(&self, elements: &HashMap<ElementId, Element>, layout: &LayoutResult, ghost: &DragGhost) -> RenderResult<Vec<RenderCommand>> {
+        let Some(element) = elements.get(&ghost.element) else {
+            return Ok(Vec::new());
+        };
+        let mut commands = Vec::new();
+        let mut origins = Vec::new();
+        let mut stats = RenderStats::default();
+        self.collect_render_commands(&mut commands, &mut origins, &mut stats, elements, layout, ghost.element, element, None)?;
+        commands.sort_by_key(draw_order);
+        Ok(commands.iter().map(|command| ghost.ghost_command(command)).collect())
+    }
+
+    fn record_unsupported(&mut self, origins: &[Option<ElementId>]) {
+        let backend = self.backend.backend_name();
+        for unsupported in self.backend.take_unsupported() {
+            let element = origins.get(unsupported.command).copied().flatten();
+            if self.reported.insert((unsupported.feature, element)) {
+                self.warnings.push(RendererWarning { backend, feature: unsupported.feature, element });
+            }
+        }
+    }
+
+    /// Recursively traverses the element tree and appends drawing commands to
+    /// a list, and the element each was drawn for to `origins`. `visible`
+    /// (position, size) is the part of the canvas that can show, the
+    /// viewport cut by the clips of the element's ancestors; elements
+    /// outside it are left out. `None` draws every element.
+    #[allow(clippy::too_many_arguments)]
     fn collect_render_commands(
         &self,
         all_commands: &mut Vec<RenderCommand>,
+        origins: &mut Vec<Option<ElementId>>,
+        stats: &mut RenderStats,
         elements: &HashMap<ElementId, Element>,
         layout: &LayoutResult,
         element_id: ElementId,
         element: &Element,
+        visible: Option<(Vec2, Vec2)>,
     ) -> RenderResult<()> {
+        stats.elements_visited += 1;
+        
         // Check if element or any parent is invisible
         if !self.is_element_visible(elements, element_id) {
+            stats.elements_culled += 1;
             eprintln!("🚫 [RENDER_SKIP] Element {} ('{}') skipped - not visible", element_id, element.id);
             return Ok(());
         }
@@ -352,8 +1085,30 @@ impl<R: CommandRenderer> ElementRenderer<R> {
         let position = layout.computed_positions.get(&element_id).copied();
         let size = layout.computed_sizes.get(&element_id).copied();
         
+        // Cull elements out of view. One that clips takes everything it
+        // contains along; otherwise only its own commands are left out, as
+        // its children can overflow into view.
+        let bounds = position.zip(size);
+        let offscreen = match (visible, bounds) {
+            (Some(visible), Some(bounds)) => !rects_overlap(visible, bounds),
+            _ => false,
+        };
+        if offscreen && needs_clip {
+            stats.elements_offscreen += 1;
+            return Ok(());
+        }
+        let draws_self = !offscreen || draws_outside_box(element);
+        if !draws_self {
+            stats.elements_offscreen += 1;
+        }
+        let children_visible = match (visible, bounds) {
+            (Some(visible), Some(bounds)) if needs_clip => Some(rect_intersection(visible, bounds)),
+            _ => visible,
+        };
+        
         // Apply clipping if needed
         if needs_clip && position.is_some() && size.is_some() {
+            stats.elements_clipped += 1;
             all_commands.push(RenderCommand::SetClip {
                 position: position.unwrap(),
                 size: size.unwrap(),
@@ -361,8 +1116,10 @@ impl<R: CommandRenderer> ElementRenderer<R> {
         }
         
         // Generate commands for the current element and append them.
-        let mut element_commands = self.element_to_commands(element, layout, element_id)?;
-        all_commands.append(&mut element_commands);
+        if draws_self {
+            let mut element_commands = self.element_to_commands(element, layout, element_id)?;
+            all_commands.append(&mut element_commands);
+        }
 
         // Check if we need to add scrollbar for overflow
         if (element.overflow_x == kryon_core::OverflowType::Scroll || 
@@ -377,12 +1134,13 @@ impl<R: CommandRenderer> ElementRenderer<R> {
             
             // Add vertical scrollbar if needed
             if element.overflow_y == kryon_core::OverflowType::Scroll {
-                // Calculate content height (sum of children heights)
+                // Children are laid out already scrolled, so add back the offset
+                let scroll_y = self.scroll_offsets.get(&element_id).map_or(0.0, |offset| offset.y);
                 let mut content_height: f32 = 0.0;
                 for &child_id in &element.children {
                     if let Some(child_size) = layout.computed_sizes.get(&child_id) {
                         if let Some(child_pos) = layout.computed_positions.get(&child_id) {
-                            let child_bottom = child_pos.y + child_size.y - pos.y;
+                            let child_bottom = child_pos.y + child_size.y - pos.y + scroll_y;
                             content_height = content_height.max(child_bottom);
                         }
                     }
@@ -390,16 +1148,17 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                 
                 // Only show scrollbar if content exceeds container
                 if content_height > sz.y {
+                    let colors = self.style_computer.scrollbar_colors();
                     all_commands.push(RenderCommand::DrawScrollbar {
-                        position: Vec2::new(pos.x + sz.x - 15.0, pos.y), // Right side
-                        size: Vec2::new(15.0, sz.y), // Standard scrollbar width
+                        position: Vec2::new(pos.x + sz.x - kryon_layout::SCROLLBAR_WIDTH, pos.y), // Right side
+                        size: Vec2::new(kryon_layout::SCROLLBAR_WIDTH, sz.y), // Space layout leaves for it
                         orientation: ScrollbarOrientation::Vertical,
-                        scroll_position: 0.0, // TODO: Track actual scroll position
+                        scroll_position: scroll_y,
                         content_size: content_height,
                         viewport_size: sz.y,
-                        track_color: Vec4::new(0.9, 0.9, 0.9, 1.0),
-                        thumb_color: Vec4::new(0.6, 0.6, 0.6, 1.0),
-                        border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
+                        track_color: colors.track,
+                        thumb_color: colors.thumb,
+                        border_color: colors.border,
                         border_width: 1.0,
                         z_index,
                     });
@@ -407,16 +1166,20 @@ impl<R: CommandRenderer> ElementRenderer<R> {
             }
         }
 
-        // Recurse for children.
-        for &child_id in &element.children {
+        origins.resize(all_commands.len(), Some(element_id));
+
+        // Recurse for children, unless they were left out of layout
+        let children: &[ElementId] = if self.skipped_subtrees.contains(&element_id) { &[] } else { &element.children };
+        for &child_id in children {
             if let Some(child_element) = elements.get(&child_id) {
-                self.collect_render_commands(all_commands, elements, layout, child_id, child_element)?;
+                self.collect_render_commands(all_commands, origins, stats, elements, layout, child_id, child_element, children_visible)?;
             }
         }
         
         // Clear clipping after rendering children
         if needs_clip {
             all_commands.push(RenderCommand::ClearClip);
+            origins.push(Some(element_id));
         }
         
         Ok(())
@@ -485,20 +1248,21 @@ impl<R: CommandRenderer> ElementRenderer<R> {
         let mut border_color = style.border_color;
         border_color.w *= element.opacity;
 
-        // Check if element has transform data
-        let transform = element.custom_properties.get("transform_index")
-            .and_then(|v| v.as_int())
-            .and_then(|_index| {
-                // TODO: Get transform data from KRB file transforms array
-                // For now, return None until we have access to the transforms
-                None
-            });
+        // Backends only see pixels: em and rem are resolved here, where the
+        // element's font size is known, and percentage translations against
+        // its laid-out size
+        let lengths = self.style_computer.length_context(element_id, &style);
+        
+        // Transform data is attached to the element when the KRB is loaded
+        let transform = element.custom_properties.get("transform")
+            .and_then(|v| v.as_transform())
+            .map(|transform| transform.resolve_lengths(&lengths, size));
         
         if bg_color.w > 0.0 || border_width > 0.0 {
             // Extract shadow information from element properties
             let shadow = element.custom_properties.get("shadow")
                 .and_then(|v| v.as_string())
-                .map(|s| s.to_string());
+                .map(|s| kryon_core::resolve_font_relative(s, &lengths));
                 
             // Extract z_index from element properties
             let z_index = element.z_index;
@@ -516,6 +1280,57 @@ impl<R: CommandRenderer> ElementRenderer<R> {
             });
         }
 
+        // Gradient background, over the background color and inside the border
+        if let Some(gradient) = &style.background_gradient {
+            let inset = border_width.min(size.min_element() / 2.0);
+            commands.push(RenderCommand::DrawGradientRect {
+                position: position + Vec2::splat(inset),
+                size: size - Vec2::splat(inset * 2.0),
+                gradient: gradient.with_opacity(element.opacity),
+                border_radius: (style.border_radius - inset).max(0.0),
+                transform: transform.clone(),
+                z_index: element.z_index,
+            });
+        }
+
+        // Background image, over the background color and inside the border
+        if let Some(source) = &style.background_image {
+            let inset = Vec2::splat(border_width.min(size.min_element() / 2.0));
+            let box_size = size - inset * 2.0;
+            let tile_size = style.background_size.resolve_font_relative(&lengths)
+                .tile_size(box_size, self.image_sizes.get(source).copied())
+                .unwrap_or(Vec2::ZERO);
+            let tile_offset = style.background_position.resolve_font_relative(&lengths)
+                .tile_offset(box_size, tile_size);
+            commands.push(RenderCommand::DrawTiledImage {
+                position: position + inset,
+                size: box_size,
+                source: source.clone(),
+                tile_offset,
+                tile_size,
+                repeat: style.background_repeat,
+                opacity: element.opacity,
+                image_rendering: image_rendering(element),
+                transform: transform.clone(),
+                z_index: element.z_index,
+            });
+        }
+
+        // Custom shader effect, drawn over the background rect emitted above
+        if let Some(PropertyValue::String(shader)) = element.custom_properties.get("shader_effect") {
+            let mut secondary_color = style.text_color;
+            secondary_color.w *= element.opacity;
+
+            commands.push(RenderCommand::DrawShaderEffect {
+                position,
+                size,
+                shader: shader.clone(),
+                primary_color: bg_color,
+                secondary_color,
+                z_index: element.z_index,
+            });
+        }
+
         // Check for rich text spans first
         if let Some(spans_property) = element.custom_properties.get("spans") {
             if let PropertyValue::RichText(rich_text) = spans_property {
@@ -525,13 +1340,17 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                 if text_color.w > 0.0 {
                     let text_z_index = element.z_index;
                     
+                    let mut rich_text = rich_text.clone();
+                    if rich_text.line_height.is_none() {
+                        rich_text.line_height = style.line_height.map(|line_height| line_height.to_pixels(style.font_size));
+                    }
                     commands.push(RenderCommand::DrawRichText {
                         position,
-                        rich_text: rich_text.clone(),
+                        rich_text,
                         max_width: Some(size.x),
                         max_height: Some(size.y),
                         default_color: text_color,
-                        alignment: Some(element.text_alignment),
+                        alignment: Some(style.text_alignment),
                         transform: transform.clone(),
                         z_index: text_z_index,
                     });
@@ -551,20 +1370,33 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                 // The renderer backend (e.g., Ratatui) will handle alignment within that box.
                 eprintln!("[RENDER_TEXT] Element {}: text='{}', alignment={:?}, size={:?}", 
                     element.id, element.text, element.text_alignment, size);
+                
+                // Inline markup becomes styled runs of the plain text. An
+                // input's value is what was typed, never markup.
+                let markup = (element.element_type != ElementType::Input)
+                    .then(|| kryon_core::RichText::parse_markup(&element.text))
+                    .flatten();
+                let (text, runs) = match markup {
+                    Some(rich_text) => {
+                        let (text, mut runs) = rich_text.to_runs();
+                        for color in runs.iter_mut().filter_map(|run| run.color.as_mut()) {
+                            color.w *= element.opacity;
+                        }
+                        (text, runs)
+                    }
+                    None => (element.text.clone(), Vec::new()),
+                };
                 commands.push(RenderCommand::DrawText {
                     position, // Use the element's top-left corner.
-                    text: element.text.clone(),
-                    font_size: element.font_size,
+                    text,
+                    font_size: style.font_size,
                     color: text_color,
-                    alignment: element.text_alignment,
+                    alignment: style.text_alignment,
                     max_width: Some(size.x), // The max width is the element's full width.
                     max_height: Some(size.y), // The max height is the element's full height.
                     transform: transform.clone(),
-                    font_family: if element.font_family.is_empty() || element.font_family == "default" {
-                        None
-                    } else {
-                        Some(element.font_family.clone())
-                    },
+                    font_family: style.font_family.clone(),
+                    runs,
                     z_index: text_z_index,
                 });
             }
@@ -580,11 +1412,29 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                         source: image_source.clone(),
                         opacity: element.opacity,
                         transform: transform.clone(),
+                        image_rendering: image_rendering(element),
+                        filter: ImageFilter::from_element(element),
                     });
                 }
             }
         }
         
+        // Icons, centered in the element's box and tinted with its icon color
+        if let Some(icon) = Icon::from_element(element, style.font_size, style.text_color) {
+            let icon_size = Vec2::splat(icon.size);
+            let mut filter = ImageFilter::from_element(element);
+            filter.tint *= icon.color;
+            commands.push(RenderCommand::DrawImage {
+                position: position + (size - icon_size) / 2.0,
+                size: icon_size,
+                source: icon.source(),
+                opacity: element.opacity,
+                transform: transform.clone(),
+                image_rendering: image_rendering(element),
+                filter,
+            });
+        }
+        
         // Handle Link elements - render similar to Text but with link styling
         if element.element_type == ElementType::Link {
             // Draw the background/border if specified (already done above)
@@ -619,17 +1469,14 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                     commands.push(RenderCommand::DrawText {
                         position,
                         text: element.text.clone(),
-                        font_size: element.font_size,
+                        font_size: style.font_size,
                         color: link_color,
-                        alignment: element.text_alignment,
+                        alignment: style.text_alignment,
                         max_width: Some(size.x),
                         max_height: Some(size.y),
                         transform: transform.clone(),
-                        font_family: if element.font_family.is_empty() || element.font_family == "default" {
-                            None
-                        } else {
-                            Some(element.font_family.clone())
-                        },
+                        font_family: style.font_family.clone(),
+                        runs: Vec::new(),
                         z_index: link_z_index,
                     });
                 }
@@ -657,23 +1504,108 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                         .unwrap_or(false);
                     
                     // Use text content as the input value
-                    let input_text = element.text.clone();
+                    let mut input_text = element.text.clone();
+                    let is_focused = element.current_state == kryon_core::InteractionState::Focus;
+                    
+                    // The runtime's text editor keeps the caret of the focused input in
+                    // its properties, along with text an input method is composing, how
+                    // far the value is scrolled and how far the caret is through its blink
+                    let caret = is_focused.then(|| {
+                        let index = |name: &str| element.custom_properties.get(name)
+                            .and_then(|v| if let PropertyValue::Int(i) = v { Some((*i).max(0) as usize) } else { None });
+                        let end = input_text.chars().count();
+                        let mut caret = text_input::Caret {
+                            index: index("caret").unwrap_or(end),
+                            selection: (index("selection_start").unwrap_or(end), index("selection_end").unwrap_or(end)),
+                        };
+                        if let Some(PropertyValue::String(preedit)) = element.custom_properties.get("preedit") {
+                            let at = input_text.char_indices().nth(caret.index).map_or(input_text.len(), |(byte, _)| byte);
+                            input_text.insert_str(at, preedit);
+                            // The composition shows as selected, with the caret after it
+                            caret.selection = (caret.index, caret.index + preedit.chars().count());
+                            caret.index = caret.selection.1;
+                        }
+                        caret
+                    });
+                    // Masking keeps one character per character, so the caret still lines up
+                    if text_input::is_masked(element) {
+                        input_text = text_input::masked(element, &input_text);
+                    }
+                    let reveal_toggle = text_input::has_reveal_toggle(element);
+                    let float = |name: &str| element.custom_properties.get(name).and_then(|v| v.as_float()).unwrap_or(0.0);
+                    // A layout since the runtime scrolled may have widened the input
+                    let text_width = text_input::text_area_width(size, reveal_toggle);
+                    let scroll_offset = float("text_scroll").clamp(0.0, text_input::max_scroll(&input_text, style.font_size, text_width));
                     
                     commands.push(RenderCommand::DrawTextInput {
                         position,
                         size,
                         text: input_text,
                         placeholder,
-                        font_size: element.font_size,
+                        font_size: style.font_size,
                         text_color: style.text_color,
                         background_color: bg_color,
                         border_color,
                         border_width,
                         border_radius: style.border_radius,
-                        is_focused: element.current_state == kryon_core::InteractionState::Focus,
+                        is_focused,
                         is_readonly,
+                        caret,
+                        scroll_offset,
+                        blink_phase: float("caret_blink"),
                         transform: transform.clone(),
                     });
+                    if reveal_toggle {
+                        let flag = |name: &str| matches!(element.custom_properties.get(name), Some(PropertyValue::Bool(true)));
+                        commands.extend(text_input::reveal_toggle_commands(
+                            position,
+                            size,
+                            border_width,
+                            flag("revealed"),
+                            flag("reveal_hover"),
+                            style.text_color,
+                            bg_color,
+                            transform.clone(),
+                        ));
+                    }
+                    commands.extend(text_input::error_message_command(element, position, size, style.font_size, transform.clone()));
+                }
+                "date" => {
+                    // The runtime draws the calendar on the overlay while the
+                    // input shows as focused; the field itself shows the ISO value
+                    let placeholder = element.custom_properties.get("placeholder")
+                        .and_then(|v| v.as_string())
+                        .unwrap_or("YYYY-MM-DD")
+                        .to_string();
+                    commands.push(RenderCommand::DrawTextInput {
+                        position,
+                        size,
+                        text: element.text.clone(),
+                        placeholder,
+                        font_size: style.font_size,
+                        text_color: style.text_color,
+                        background_color: bg_color,
+                        border_color,
+                        border_width,
+                        border_radius: style.border_radius,
+                        is_focused: element.current_state == kryon_core::InteractionState::Focus,
+                        is_readonly: true,
+                        caret: None,
+                        scroll_offset: 0.0,
+                        blink_phase: 0.0,
+                        transform: transform.clone(),
+                    });
+                }
+                "color" => {
+                    let layout = color_picker::PickerLayout::new(position, size, color_picker::shows_alpha(element));
+                    let color = color_picker::element_color(element);
+                    commands.extend(color_picker::commands(
+                        &layout,
+                        color,
+                        self.backend.supports_shader_effects(),
+                        transform.clone(),
+                        element.z_index,
+                    ));
                 }
                 "checkbox" | "radio" => {
                     let check_text = element.custom_properties.get("text")
@@ -685,7 +1617,7 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                         size,
                         is_checked: element.current_state == kryon_core::InteractionState::Checked,
                         text: check_text,
-                        font_size: element.font_size,
+                        font_size: style.font_size,
                         text_color: style.text_color,
                         background_color: bg_color,
                         border_color,
@@ -728,7 +1660,7 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                         size,
                         text: element.text.clone(),
                         placeholder: String::new(),
-                        font_size: element.font_size,
+                        font_size: style.font_size,
                         text_color: style.text_color,
                         background_color: bg_color,
                         border_color,
@@ -736,6 +1668,9 @@ impl<R: CommandRenderer> ElementRenderer<R> {
                         border_radius: style.border_radius,
                         is_focused: false,
                         is_readonly: false,
+                        caret: None,
+                        scroll_offset: 0.0,
+                        blink_phase: 0.0,
                         transform: transform.clone(),
                     });
                 }
@@ -746,6 +1681,43 @@ impl<R: CommandRenderer> ElementRenderer<R> {
             return Ok(commands);
         }
         
+        // Handle ParticleEmitter elements
+        if element.element_type == ElementType::ParticleEmitter {
+            if let Some(particles) = self.particles.get(&element_id) {
+                let viewport = self.viewport_size;
+                let visible: Vec<ParticleInstance> = particles.iter()
+                    .filter(|p| {
+                        let half = p.size * 0.5;
+                        p.position.x + half >= 0.0 && p.position.y + half >= 0.0 &&
+                        p.position.x - half <= viewport.x && p.position.y - half <= viewport.y
+                    })
+                    .copied()
+                    .collect();
+
+                if !visible.is_empty() {
+                    let sprite = element.custom_properties.get("sprite")
+                        .and_then(|v| v.as_string())
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string());
+
+                    commands.push(RenderCommand::DrawParticles {
+                        particles: visible,
+                        sprite,
+                        z_index: element.z_index,
+                    });
+                }
+            }
+            return Ok(commands);
+        }
+
+        // Handle Table elements bound to a dataset
+        if element.element_type == ElementType::Table {
+            if let Some(view) = self.tables.get(&element_id) {
+                commands.extend(Self::table_to_commands(view, element, &style, position, size));
+            }
+            return Ok(commands);
+        }
+
         // Handle NativeRendererView elements
         if element.element_type == ElementType::NativeRendererView {
             if let (Some(backend), Some(script_name)) = (
@@ -901,9 +1873,123 @@ impl<R: CommandRenderer> ElementRenderer<R> {
         Ok(commands)
     }
 
+    /// Replaces the particles drawn for a ParticleEmitter element.
+    pub fn set_particles(&mut self, element_id: ElementId, particles: Vec<ParticleInstance>) {
+        Arc::make_mut(&mut self.particles).insert(element_id, particles);
+    }
+
+    /// Stops drawing particles for an element (emitter finished or removed).
+    pub fn clear_particles(&mut self, element_id: ElementId) {
+        Arc::make_mut(&mut self.particles).remove(&element_id);
+    }
+
+    /// Replaces the rows drawn for a Table element.
+    pub fn set_table(&mut self, element_id: ElementId, view: TableView) {
+        Arc::make_mut(&mut self.tables).insert(element_id, view);
+    }
+
+    pub fn clear_table(&mut self, element_id: ElementId) {
+        Arc::make_mut(&mut self.tables).remove(&element_id);
+    }
+
+    /// Header plus as many rows as fit in the element; numbers are right-aligned.
+    fn table_to_commands(view: &TableView, element: &Element, style: &ComputedStyle, position: Vec2, size: Vec2) -> Vec<RenderCommand> {
+        let mut commands = Vec::new();
+        let font_size = style.font_size.max(1.0);
+        let row_height = TableView::row_height(font_size);
+        let padding = (font_size * 0.5).round();
+        let widths = view.column_widths(size.x);
+
+        let mut text_color = style.text_color;
+        text_color.w *= element.opacity;
+        let tint = |key: &str, alpha: f32| {
+            element.custom_properties.get(key)
+                .and_then(|v| v.as_color())
+                .unwrap_or(Vec4::new(text_color.x, text_color.y, text_color.z, text_color.w * alpha))
+        };
+        let header_color = tint("table_header_color", 0.12);
+        let stripe_color = tint("table_stripe_color", 0.05);
+
+        let rect = |position: Vec2, size: Vec2, color: Vec4| RenderCommand::DrawRect {
+            position,
+            size,
+            color,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: element.z_index,
+        };
+        let cell = |text: String, x: f32, y: f32, width: f32, alignment: TextAlignment| RenderCommand::DrawText {
+            position: Vec2::new(x + padding, y + (row_height - font_size) * 0.5),
+            text,
+            font_size,
+            color: text_color,
+            alignment,
+            max_width: Some((width - padding * 2.0).max(0.0)),
+            max_height: Some(row_height),
+            transform: None,
+            font_family: None,
+            runs: Vec::new(),
+            z_index: element.z_index,
+        };
+
+        let dataset = view.dataset();
+        commands.push(rect(position, Vec2::new(size.x, row_height), header_color));
+        let mut x = position.x;
+        for (index, (column, width)) in dataset.columns.iter().zip(&widths).enumerate() {
+            let marker = match view.sort() {
+                Some(sort) if sort.column == index => if sort.ascending { " ^" } else { " v" },
+                _ => "",
+            };
+            commands.push(cell(format!("{}{}", column.name, marker), x, position.y, *width, TextAlignment::Start));
+            x += width;
+        }
+
+        let mut y = position.y + row_height;
+        for (stripe, &row_index) in view.visible_rows().iter().enumerate() {
+            if y + row_height > position.y + size.y {
+                break;
+            }
+            if stripe % 2 == 1 {
+                commands.push(rect(Vec2::new(position.x, y), Vec2::new(size.x, row_height), stripe_color));
+            }
+            let mut x = position.x;
+            for (value, width) in dataset.rows[row_index].iter().zip(&widths) {
+                let alignment = if matches!(value, CellValue::Number(_)) { TextAlignment::End } else { TextAlignment::Start };
+                commands.push(cell(value.display(), x, y, *width, alignment));
+                x += width;
+            }
+            y += row_height;
+        }
+
+        commands
+    }
+
+    /// Replaces the overlay layer: commands drawn on top of the element tree
+    /// in window coordinates (toasts, debug HUDs).
+    pub fn set_overlay(&mut self, commands: Vec<RenderCommand>) {
+        self.overlay = Arc::new(commands);
+    }
+
+    pub fn clear_overlay(&mut self) {
+        self.overlay = Arc::default();
+    }
+
+    /// Draws `ghost`'s element again above the overlay, or stops with `None`
+    pub fn set_drag_ghost(&mut self, ghost: Option<DragGhost>) {
+        self.drag_ghost = ghost;
+    }
+
+    /// `new_size` is the backend's surface size in device pixels; see
+    /// `set_output_rotation`. The backend's scale is read again, since a
+    /// window moved to another display resizes with it.
     pub fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
-        self.viewport_size = new_size;
-        self.backend.resize(new_size)
+        let resized = self.backend.resize(new_size);
+        self.scale = usable_scale(self.backend.viewport_scale());
+        self.viewport_size = self.rotation.logical_size(new_size) / self.scale;
+        resized
     }
 
     pub fn viewport_size(&self) -> Vec2 {
@@ -917,4 +2003,208 @@ impl<R: CommandRenderer> ElementRenderer<R> {
     pub fn backend_mut(&mut self) -> &mut R {
         &mut self.backend
     }
-}
\ No newline at end of file
+
+    /// Number of commands sent to the backend for the last rendered frame.
+    pub fn last_command_count(&self) -> usize {
+        self.stats.total_commands()
+    }
+
+    /// Statistics for the last rendered frame.
+    pub fn last_frame_stats(&self) -> &RenderStats {
+        &self.stats
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::OverflowType;
+
+    /// Keeps the commands of the last frame
+    struct Recording {
+        full_list: bool,
+        commands: Vec<RenderCommand>,
+        scale: f32,
+        size: Vec2,
+    }
+
+    impl Renderer for Recording {
+        type Surface = bool;
+        type Context = ();
+
+        fn initialize(full_list: bool) -> RenderResult<Self> {
+            Ok(Self { full_list, commands: Vec::new(), scale: 1.0, size: Vec2::new(200.0, 200.0) })
+        }
+
+        fn begin_frame(&mut self, _clear_color: Vec4) -> RenderResult<()> {
+            self.commands.clear();
+            Ok(())
+        }
+
+        fn end_frame(&mut self, _context: ()) -> RenderResult<()> {
+            Ok(())
+        }
+
+        fn render_element(&mut self, _context: &mut (), _element: &Element, _layout: &LayoutResult, _element_id: ElementId) -> RenderResult<()> {
+            Ok(())
+        }
+
+        fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
+            self.size = new_size;
+            Ok(())
+        }
+
+        fn viewport_size(&self) -> Vec2 {
+            self.size
+        }
+
+        fn viewport_scale(&self) -> f32 {
+            self.scale
+        }
+    }
+
+    impl CommandRenderer for Recording {
+        fn execute_commands(&mut self, _context: &mut (), commands: &[RenderCommand]) -> RenderResult<()> {
+            self.commands.extend_from_slice(commands);
+            Ok(())
+        }
+
+        fn needs_full_command_list(&self) -> bool {
+            self.full_list
+        }
+    }
+
+    /// The y of each rect drawn, with `full_list` telling the renderer not to cull
+    fn drawn_rects(full_list: bool) -> (Vec<f32>, usize) {
+        // id, parent, y, height, clips
+        let boxes = [(0, None, 0.0, 200.0, false), (1, Some(0), 10.0, 10.0, false), (2, Some(0), 5000.0, 10.0, false),
+            (3, Some(0), 100.0, 50.0, true), (4, Some(3), 120.0, 10.0, false), (5, Some(3), 400.0, 10.0, false),
+            (6, Some(0), 900.0, 50.0, true), (7, Some(6), 10.0, 10.0, false), (8, Some(2), 20.0, 10.0, false)];
+        let mut elements = HashMap::new();
+        let mut layout = LayoutResult { computed_positions: HashMap::new(), computed_sizes: HashMap::new() };
+        for (id, parent, y, height, clips) in boxes {
+            let overflow = if clips { OverflowType::Hidden } else { OverflowType::Visible };
+            let element = Element {
+                background_color: Vec4::ONE,
+                overflow_x: overflow,
+                overflow_y: overflow,
+                children: boxes.iter().filter(|b| b.1 == Some(id)).map(|b| b.0).collect(),
+                ..Default::default()
+            };
+            elements.insert(id, element);
+            layout.computed_positions.insert(id, Vec2::new(0.0, y));
+            layout.computed_sizes.insert(id, Vec2::new(100.0, height));
+        }
+        let style_computer = StyleComputer::new(&elements, &HashMap::new());
+        let mut renderer = ElementRenderer::new(Recording::initialize(full_list).unwrap(), style_computer);
+        renderer.render_frame(&elements, &layout, 0, Vec4::ZERO).unwrap();
+        let rects = renderer.backend.commands.iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawRect { position, .. } => Some(position.y),
+                _ => None,
+            })
+            .collect();
+        (rects, renderer.last_frame_stats().elements_offscreen)
+    }
+
+    #[test]
+    fn elements_out_of_view_or_clipped_away_are_culled() {
+        let (mut culled, offscreen) = drawn_rects(false);
+        culled.sort_by(f32::total_cmp);
+        // The child of the far-off element 2 overflows back into view; the
+        // clipping element 6 takes its child along
+        assert_eq!(culled, vec![0.0, 10.0, 20.0, 100.0, 120.0]);
+        assert_eq!(offscreen, 3);
+
+        let (everything, offscreen) = drawn_rects(true);
+        assert_eq!(everything.len(), 9);
+        assert_eq!(offscreen, 0);
+    }
+
+    #[test]
+    fn text_markup_becomes_styled_runs() {
+        let mut elements = HashMap::new();
+        elements.insert(0, Element {
+            text: "Hello <b>world</b>".to_string(),
+            opacity: 0.5,
+            ..Default::default()
+        });
+        elements.insert(1, Element {
+            element_type: ElementType::Input,
+            text: "<b>typed</b>".to_string(),
+            ..Default::default()
+        });
+        elements.get_mut(&0).unwrap().children.push(1);
+        let mut layout = LayoutResult { computed_positions: HashMap::new(), computed_sizes: HashMap::new() };
+        for id in [0, 1] {
+            layout.computed_positions.insert(id, Vec2::ZERO);
+            layout.computed_sizes.insert(id, Vec2::new(100.0, 20.0));
+        }
+        let style_computer = StyleComputer::new(&elements, &HashMap::new());
+        let mut renderer = ElementRenderer::new(Recording::initialize(true).unwrap(), style_computer);
+        renderer.render_frame(&elements, &layout, 0, Vec4::ZERO).unwrap();
+
+        let texts: Vec<(&str, &[kryon_core::TextRun])> = renderer.backend.commands.iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawText { text, runs, .. } => Some((text.as_str(), runs.as_slice())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts[0].0, "Hello world");
+        assert_eq!(texts[0].1.len(), 1);
+        assert!(texts[0].1[0].is_bold() && texts[0].1[0].range == (6..11));
+        assert!(texts.iter().any(|(text, runs)| *text == "<b>typed</b>" && runs.is_empty()), "input values aren't markup");
+    }
+
+    #[test]
+    fn scrolled_contents_paginate_from_their_start() {
+        // Four 60px blocks in a 100px container at y 100, scrolled 50px down
+        let mut elements = HashMap::new();
+        let mut layout = LayoutResult { computed_positions: HashMap::new(), computed_sizes: HashMap::new() };
+        elements.insert(0, Element { children: vec![1, 2, 3, 4], ..Default::default() });
+        layout.computed_positions.insert(0, Vec2::new(0.0, 100.0));
+        layout.computed_sizes.insert(0, Vec2::new(100.0, 100.0));
+        for id in 1..=4 {
+            let mut element = Element { parent: Some(0), background_color: Vec4::ONE, ..Default::default() };
+            if id == 2 {
+                element.custom_properties.insert("break_inside".to_string(), PropertyValue::String("avoid".to_string()));
+            }
+            elements.insert(id, element);
+            layout.computed_positions.insert(id, Vec2::new(0.0, 100.0 + 60.0 * (id - 1) as f32 - 50.0));
+            layout.computed_sizes.insert(id, Vec2::new(100.0, 60.0));
+        }
+        let style_computer = StyleComputer::new(&elements, &HashMap::new());
+        let mut renderer = ElementRenderer::new(Recording::initialize(true).unwrap(), style_computer);
+        renderer.set_scroll_offsets(&HashMap::from([(0, Vec2::new(0.0, 50.0))]));
+
+        let setup = PageSetup::new(Vec2::new(100.0, 100.0), PageMargins::new(0.0, 0.0, 0.0, 0.0));
+        let pages = renderer.paginate(&elements, &layout, 0, &setup).unwrap();
+        let rects = |page: &Page| -> Vec<f32> {
+            page.commands.iter().filter_map(|command| match command {
+                RenderCommand::DrawRect { position, .. } => Some(position.y),
+                _ => None,
+            }).collect()
+        };
+        // The first cut would split the second block, so it moves up to it
+        assert_eq!(pages.iter().map(|page| page.content_top).collect::<Vec<_>>(), vec![0.0, 60.0, 160.0]);
+        assert_eq!(rects(&pages[0]), vec![0.0]);
+        assert_eq!(rects(&pages[1]), vec![0.0, 60.0]);
+        assert_eq!(rects(&pages[2]), vec![-40.0, 20.0], "the third block ends on the last page");
+    }
+
+    #[test]
+    fn layout_is_in_logical_pixels_on_a_scaled_surface() {
+        let backend = Recording { scale: 2.0, ..Recording::initialize(true).unwrap() };
+        let mut renderer = ElementRenderer::new(backend, StyleComputer::new(&HashMap::new(), &HashMap::new()));
+        assert_eq!(renderer.viewport_size(), Vec2::new(100.0, 100.0));
+        assert_eq!(renderer.surface_size(), Vec2::new(200.0, 200.0));
+
+        renderer.resize(Vec2::new(400.0, 200.0)).unwrap();
+        assert_eq!(renderer.viewport_size(), Vec2::new(200.0, 100.0));
+        assert_eq!(renderer.surface_size(), Vec2::new(400.0, 200.0));
+
+        // A backend reporting no scale is taken as unscaled
+        renderer.backend.scale = f32::NAN;
+        renderer.resize(Vec2::new(300.0, 100.0)).unwrap();
+        assert_eq!((renderer.viewport_scale(), renderer.viewport_size()), (1.0, Vec2::new(300.0, 100.0)));
+    }
+}
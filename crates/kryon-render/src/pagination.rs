@@ -0,0 +1,369 @@
+// crates/kryon-render/src/pagination.rs
+//! Cutting long content into pages for print-style export.
+//!
+//! A container's contents are one tall strip of commands; pagination cuts
+//! the strip into slices as tall as a page's area inside its margins, and
+//! moves each slice's commands onto a page of its own, clipped to that
+//! area. A cut that would pass through a `break_inside: avoid` block moves
+//! up to the block's top, leaving the rest of the page blank, unless the
+//! block is taller than a page and has to be split anyway. Commands
+//! crossing a cut are drawn on both pages, each showing its part.
+
+use crate::RenderCommand;
+use glam::Vec2;
+
+/// Page sizes in CSS pixels, at 96 per inch
+pub const A4: Vec2 = Vec2::new(794.0, 1123.0);
+pub const A5: Vec2 = Vec2::new(559.0, 794.0);
+pub const LETTER: Vec2 = Vec2::new(816.0, 1056.0);
+pub const LEGAL: Vec2 = Vec2::new(816.0, 1344.0);
+
+/// Blank space around a page's content, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageMargins {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl PageMargins {
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self { top, right, bottom, left }
+    }
+
+    /// Parses CSS-style shorthand: one value for all sides, `vertical
+    /// horizontal`, or `top right bottom left`, separated by commas or spaces.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let values = spec
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.trim_end_matches("px").parse::<f32>().map_err(|_| format!("Invalid margin '{}'", part)))
+            .collect::<Result<Vec<_>, _>>()?;
+        match values[..] {
+            [all] => Ok(Self::new(all, all, all, all)),
+            [vertical, horizontal] => Ok(Self::new(vertical, horizontal, vertical, horizontal)),
+            [top, right, bottom, left] => Ok(Self::new(top, right, bottom, left)),
+            _ => Err(format!("Expected 1, 2 or 4 margins, got '{}'", spec)),
+        }
+    }
+}
+
+impl Default for PageMargins {
+    /// Half an inch on every side
+    fn default() -> Self {
+        Self::new(48.0, 48.0, 48.0, 48.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageSetup {
+    pub size: Vec2,
+    pub margins: PageMargins,
+}
+
+impl PageSetup {
+    pub fn new(size: Vec2, margins: PageMargins) -> Self {
+        Self { size, margins }
+    }
+
+    /// Parses a page size: `a4`, `a5`, `letter`, `legal` or
+    /// `WIDTHxHEIGHT` pixels, with ` landscape` after a name to turn it
+    /// on its side
+    pub fn parse_size(spec: &str) -> Result<Vec2, String> {
+        let spec = spec.trim().to_ascii_lowercase();
+        let (name, landscape) = match spec.strip_suffix("landscape") {
+            Some(name) => (name.trim(), true),
+            None => (spec.as_str(), false),
+        };
+        let size = match name {
+            "a4" => A4,
+            "a5" => A5,
+            "letter" => LETTER,
+            "legal" => LEGAL,
+            _ => {
+                let (width, height) = name.split_once('x').ok_or_else(|| format!("Unknown page size '{}'", spec))?;
+                let parse = |value: &str| value.trim().parse::<f32>().ok().filter(|value| *value > 0.0);
+                match (parse(width), parse(height)) {
+                    (Some(width), Some(height)) => Vec2::new(width, height),
+                    _ => return Err(format!("Invalid page size '{}'", spec)),
+                }
+            }
+        };
+        Ok(if landscape { Vec2::new(size.y, size.x) } else { size })
+    }
+
+    /// Top-left corner of the page's content area
+    pub fn content_origin(&self) -> Vec2 {
+        Vec2::new(self.margins.left, self.margins.top)
+    }
+
+    /// Size of the page inside its margins
+    pub fn content_size(&self) -> Vec2 {
+        let margins = Vec2::new(self.margins.left + self.margins.right, self.margins.top + self.margins.bottom);
+        (self.size - margins).max(Vec2::ONE)
+    }
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        Self::new(A4, PageMargins::default())
+    }
+}
+
+/// One page's commands, in page coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub commands: Vec<RenderCommand>,
+    /// Where the page's slice starts in the content
+    pub content_top: f32,
+    /// How much of the content the page shows
+    pub content_height: f32,
+}
+
+/// Where each page's slice starts in content `content_height` tall, given
+/// the (top, bottom) extents of blocks that shouldn't be split
+pub fn page_starts(content_height: f32, page_height: f32, avoid: &[(f32, f32)]) -> Vec<f32> {
+    let mut starts = vec![0.0];
+    let mut start = 0.0;
+    while start + page_height < content_height {
+        let mut cut = start + page_height;
+        // Moving a cut up can land it in another block, so repeat until it
+        // splits none that would fit a page
+        while let Some(top) = avoid.iter()
+            .filter(|&&(top, bottom)| top > start && top < cut && bottom > cut && bottom - top <= page_height)
+            .map(|&(top, _)| top)
+            .reduce(f32::min)
+        {
+            cut = top;
+        }
+        starts.push(cut);
+        start = cut;
+    }
+    starts
+}
+
+/// Cuts `commands`, drawn over content whose top-left corner is at
+/// `content_origin` and which is `content_height` tall, into pages
+pub fn paginate(
+    commands: &[RenderCommand],
+    content_origin: Vec2,
+    content_height: f32,
+    setup: &PageSetup,
+    avoid: &[(f32, f32)],
+) -> Vec<Page> {
+    let page_area = (setup.content_origin(), setup.content_size());
+    let units = units(commands, content_origin.y);
+    let starts = page_starts(content_height, page_area.1.y, avoid);
+
+    starts.iter().enumerate().map(|(index, &start)| {
+        let end = starts.get(index + 1).copied().unwrap_or(start + page_area.1.y);
+        let clip = (page_area.0, Vec2::new(page_area.1.x, end - start));
+        let offset = page_area.0 - content_origin - Vec2::new(0.0, start);
+
+        let mut page = vec![RenderCommand::SetClip { position: clip.0, size: clip.1 }];
+        for unit in &units {
+            if unit.extent.is_some_and(|(top, bottom)| bottom <= start || top >= end) {
+                continue;
+            }
+            for command in &commands[unit.commands.clone()] {
+                page.push(match command {
+                    // Clips inside the content only ever narrow the page's
+                    RenderCommand::SetClip { position, size } => {
+                        let (position, size) = crate::rect_intersection((*position + offset, *size), clip);
+                        RenderCommand::SetClip { position, size }
+                    }
+                    RenderCommand::ClearClip => RenderCommand::SetClip { position: clip.0, size: clip.1 },
+                    RenderCommand::SetCanvasSize(_) => continue,
+                    command => translated(command, offset),
+                });
+            }
+        }
+        page.push(RenderCommand::ClearClip);
+        Page { commands: page, content_top: start, content_height: end - start }
+    }).collect()
+}
+
+/// Commands that go onto a page together
+struct Unit {
+    commands: std::ops::Range<usize>,
+    /// Top and bottom in the content, if known
+    extent: Option<(f32, f32)>,
+}
+
+/// Groups commands into units: one for each command, except a canvas or
+/// WASM view, which is one with everything drawn in it
+fn units(commands: &[RenderCommand], content_top: f32) -> Vec<Unit> {
+    let mut units = Vec::new();
+    let mut index = 0;
+    while index < commands.len() {
+        let end = match &commands[index] {
+            RenderCommand::BeginCanvas { .. } => group_end(commands, index, |command| matches!(command, RenderCommand::EndCanvas)),
+            RenderCommand::BeginWasmView { .. } => group_end(commands, index, |command| matches!(command, RenderCommand::EndWasmView)),
+            _ => index + 1,
+        };
+        let extent = vertical_extent(&commands[index]).map(|(top, bottom)| (top - content_top, bottom - content_top));
+        units.push(Unit { commands: index..end, extent });
+        index = end;
+    }
+    units
+}
+
+fn group_end(commands: &[RenderCommand], start: usize, is_end: impl Fn(&RenderCommand) -> bool) -> usize {
+    commands[start..].iter().position(is_end).map_or(commands.len(), |offset| start + offset + 1)
+}
+
+/// Top and bottom of what a command draws, where that's known without
+/// drawing it; transformed commands can reach anywhere
+fn vertical_extent(command: &RenderCommand) -> Option<(f32, f32)> {
+    let span = |position: Vec2, height: f32| Some((position.y, position.y + height));
+    match command {
+        RenderCommand::DrawRect { position, size, transform: None, shadow, .. }
+            if shadow.as_deref().is_none_or(|shadow| shadow.is_empty() || shadow == "none") => span(*position, size.y),
+        RenderCommand::DrawText { position, max_height, font_size, transform: None, .. } => {
+            span(*position, max_height.unwrap_or(font_size * 1.2))
+        }
+        RenderCommand::DrawRichText { position, max_height: Some(height), transform: None, .. } => span(*position, *height),
+        RenderCommand::DrawImage { position, size, transform: None, .. }
+        | RenderCommand::DrawTiledImage { position, size, transform: None, .. }
+        | RenderCommand::DrawGradientRect { position, size, transform: None, .. }
+        | RenderCommand::DrawShaderEffect { position, size, .. }
+        | RenderCommand::NativeRendererView { position, size, .. }
+        | RenderCommand::DrawTextInput { position, size, transform: None, .. }
+        | RenderCommand::DrawCheckbox { position, size, transform: None, .. }
+        | RenderCommand::DrawSlider { position, size, transform: None, .. }
+        | RenderCommand::DrawScrollbar { position, size, .. }
+        | RenderCommand::BeginCanvas { position, size, .. }
+        | RenderCommand::BeginWasmView { position, size, .. } => span(*position, size.y),
+        _ => None,
+    }
+}
+
+/// `command` moved by `offset`. Canvas paths are drawn from their path
+/// data as it is.
+pub fn translated(command: &RenderCommand, offset: Vec2) -> RenderCommand {
+    let mut command = command.clone();
+    match &mut command {
+        RenderCommand::DrawRect { position, .. }
+        | RenderCommand::DrawText { position, .. }
+        | RenderCommand::DrawRichText { position, .. }
+        | RenderCommand::DrawImage { position, .. }
+        | RenderCommand::DrawTiledImage { position, .. }
+        | RenderCommand::DrawGradientRect { position, .. }
+        | RenderCommand::DrawShaderEffect { position, .. }
+        | RenderCommand::SetClip { position, .. }
+        | RenderCommand::NativeRendererView { position, .. }
+        | RenderCommand::DrawTextInput { position, .. }
+        | RenderCommand::DrawCheckbox { position, .. }
+        | RenderCommand::DrawSlider { position, .. }
+        | RenderCommand::DrawScrollbar { position, .. }
+        | RenderCommand::BeginCanvas { position, .. }
+        | RenderCommand::DrawCanvasRect { position, .. }
+        | RenderCommand::DrawCanvasText { position, .. }
+        | RenderCommand::DrawCanvasImage { position, .. }
+        | RenderCommand::BeginWasmView { position, .. } => *position += offset,
+        RenderCommand::DrawCanvasLine { start, end, .. } => {
+            *start += offset;
+            *end += offset;
+        }
+        RenderCommand::DrawCanvasCircle { center, .. } | RenderCommand::DrawCanvasEllipse { center, .. } => *center += offset,
+        RenderCommand::DrawCanvasPolygon { points, .. } => points.iter_mut().for_each(|point| *point += offset),
+        RenderCommand::DrawParticles { particles, .. } => particles.iter_mut().for_each(|particle| particle.position += offset),
+        RenderCommand::ClearClip
+        | RenderCommand::SetCanvasSize(_)
+        | RenderCommand::EndCanvas
+        | RenderCommand::DrawCanvasPath { .. }
+        | RenderCommand::EndWasmView
+        | RenderCommand::ExecuteWasmFunction { .. } => {}
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec4;
+
+    fn rect(y: f32, height: f32) -> RenderCommand {
+        RenderCommand::DrawRect {
+            position: Vec2::new(10.0, y),
+            size: Vec2::new(100.0, height),
+            color: Vec4::ONE,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: 0,
+        }
+    }
+
+    fn rect_y(command: &RenderCommand) -> f32 {
+        match command {
+            RenderCommand::DrawRect { position, .. } => position.y,
+            other => panic!("expected a rect, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn cuts_move_up_to_blocks_that_avoid_breaks() {
+        assert_eq!(page_starts(250.0, 100.0, &[]), vec![0.0, 100.0, 200.0]);
+        assert_eq!(page_starts(100.0, 100.0, &[]), vec![0.0], "content that fits is one page");
+        // 80..130 would be split, so the second page starts with it
+        assert_eq!(page_starts(250.0, 100.0, &[(80.0, 130.0)]), vec![0.0, 80.0, 180.0]);
+        // Nested blocks: moving up to 90 would split 60..120 too
+        assert_eq!(page_starts(200.0, 100.0, &[(90.0, 110.0), (60.0, 120.0)]), vec![0.0, 60.0, 160.0]);
+        // A block taller than a page is split where it must be
+        assert_eq!(page_starts(300.0, 100.0, &[(50.0, 200.0)]), vec![0.0, 100.0, 200.0]);
+    }
+
+    #[test]
+    fn pages_hold_their_slice_in_page_coordinates() {
+        let setup = PageSetup::new(Vec2::new(200.0, 140.0), PageMargins::new(20.0, 10.0, 20.0, 10.0));
+        assert_eq!(setup.content_size(), Vec2::new(180.0, 100.0));
+        // Content starts at y 500 on screen
+        let commands = vec![
+            RenderCommand::SetCanvasSize(Vec2::new(800.0, 600.0)),
+            rect(500.0, 40.0),
+            rect(580.0, 40.0),
+            rect(660.0, 30.0),
+        ];
+        let pages = paginate(&commands, Vec2::new(10.0, 500.0), 190.0, &setup, &[]);
+        assert_eq!(pages.len(), 2);
+
+        let first = &pages[0].commands;
+        assert_eq!(first.first(), Some(&RenderCommand::SetClip { position: Vec2::new(10.0, 20.0), size: Vec2::new(180.0, 100.0) }));
+        assert_eq!(first.last(), Some(&RenderCommand::ClearClip));
+        let ys: Vec<f32> = first[1..first.len() - 1].iter().map(rect_y).collect();
+        assert_eq!(ys, vec![20.0, 100.0], "the rect crossing the cut is on both pages");
+
+        let second = &pages[1];
+        assert_eq!(second.content_top, 100.0);
+        let ys: Vec<f32> = second.commands[1..second.commands.len() - 1].iter().map(rect_y).collect();
+        assert_eq!(ys, vec![0.0, 80.0]);
+    }
+
+    #[test]
+    fn content_clips_stay_inside_the_page() {
+        let setup = PageSetup::new(Vec2::new(100.0, 100.0), PageMargins::new(0.0, 0.0, 0.0, 0.0));
+        let commands = vec![
+            RenderCommand::SetClip { position: Vec2::new(0.0, 50.0), size: Vec2::new(100.0, 100.0) },
+            rect(60.0, 80.0),
+            RenderCommand::ClearClip,
+        ];
+        let pages = paginate(&commands, Vec2::ZERO, 150.0, &setup, &[]);
+        assert_eq!(pages[0].commands[1], RenderCommand::SetClip { position: Vec2::new(0.0, 50.0), size: Vec2::new(100.0, 50.0) });
+        assert_eq!(pages[0].commands[3], RenderCommand::SetClip { position: Vec2::ZERO, size: Vec2::new(100.0, 100.0) }, "clearing goes back to the page's clip");
+    }
+
+    #[test]
+    fn parses_page_sizes_and_margins() {
+        assert_eq!(PageSetup::parse_size("A4"), Ok(A4));
+        assert_eq!(PageSetup::parse_size("letter landscape"), Ok(Vec2::new(1056.0, 816.0)));
+        assert_eq!(PageSetup::parse_size("600x800"), Ok(Vec2::new(600.0, 800.0)));
+        assert!(PageSetup::parse_size("tabloid").is_err());
+        assert!(PageSetup::parse_size("0x800").is_err());
+        assert_eq!(PageMargins::parse("24, 36"), Ok(PageMargins::new(24.0, 36.0, 24.0, 36.0)));
+        assert!(PageMargins::parse("1 2 3").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+// crates/kryon-render/src/shadow.rs
+//! `box-shadow` parsing and rasterization shared by the backends.
+//!
+//! A blurred shadow is drawn as a stack of translucent rectangles, from the
+//! shape grown by the blur radius down to the shape shrunk by it. Where
+//! more layers overlap the shadow is darker, so its alpha falls off across
+//! the edge roughly like the Gaussian blur CSS asks for. A backend only has
+//! to fill (rounded) rectangles with alpha blending to draw one.
+
+use glam::{Vec2, Vec4};
+
+/// Most layers one blurred shadow is drawn with
+pub const MAX_SHADOW_LAYERS: usize = 16;
+
+/// Color of a shadow that doesn't name one
+pub const DEFAULT_SHADOW_COLOR: Vec4 = Vec4::new(0.0, 0.0, 0.0, 0.25);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxShadow {
+    pub offset: Vec2,
+    pub blur_radius: f32,
+    pub spread_radius: f32,
+    pub color: Vec4,
+}
+
+/// One rectangle of a rasterized shadow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShadowLayer {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub border_radius: f32,
+    pub color: Vec4,
+}
+
+impl BoxShadow {
+    /// Parses a CSS box-shadow string:
+    /// "offset-x offset-y [blur-radius [spread-radius]] [color]", e.g.
+    /// "0 4px 6px rgba(0, 0, 0, 0.1)" or "2px 2px 4px #00000040".
+    /// `None` for "none", an empty string or anything unparsable.
+    pub fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.split_whitespace().collect();
+
+        let lengths: Vec<f32> = parts.iter().take(4).map_while(|part| parse_pixel_value(part)).collect();
+        if lengths.len() < 2 {
+            return None;
+        }
+
+        // The rest is the color, which may contain spaces (rgba(0, 0, 0, 0.1))
+        let color = parts[lengths.len()..].join(" ");
+        let color = if color.is_empty() { DEFAULT_SHADOW_COLOR } else { kryon_core::parse_color(&color)? };
+
+        Some(Self {
+            offset: Vec2::new(lengths[0], lengths[1]),
+            blur_radius: lengths.get(2).copied().unwrap_or(0.0).max(0.0),
+            spread_radius: lengths.get(3).copied().unwrap_or(0.0),
+            color,
+        })
+    }
+
+    /// The rectangles that draw this shadow under a box at `position` of
+    /// `size` with corners of `border_radius`, outermost first
+    pub fn layers(&self, position: Vec2, size: Vec2, border_radius: f32) -> Vec<ShadowLayer> {
+        let position = position + self.offset - Vec2::splat(self.spread_radius);
+        let size = size + Vec2::splat(self.spread_radius * 2.0);
+        let border_radius = if border_radius > 0.0 { (border_radius + self.spread_radius).max(0.0) } else { 0.0 };
+
+        let count = (self.blur_radius.ceil() as usize).clamp(1, MAX_SHADOW_LAYERS);
+        if count == 1 || self.blur_radius <= 0.0 {
+            return grown(position, size, border_radius, 0.0, self.color).into_iter().collect();
+        }
+
+        // Alpha of each layer, such that all of them together give the full color
+        let alpha = 1.0 - (1.0 - self.color.w.clamp(0.0, 1.0)).powf(1.0 / count as f32);
+        let color = self.color.truncate().extend(alpha);
+        (0..count)
+            .filter_map(|layer| {
+                let grow = self.blur_radius * (1.0 - 2.0 * (layer as f32 + 0.5) / count as f32);
+                grown(position, size, border_radius, grow, color)
+            })
+            .collect()
+    }
+}
+
+/// The box grown on every side by `grow` (or shrunk, if negative); `None`
+/// once it has shrunk away
+fn grown(position: Vec2, size: Vec2, border_radius: f32, grow: f32, color: Vec4) -> Option<ShadowLayer> {
+    let size = size + Vec2::splat(grow * 2.0);
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return None;
+    }
+    Some(ShadowLayer {
+        position: position - Vec2::splat(grow),
+        size,
+        border_radius: (border_radius + grow).max(0.0),
+        color,
+    })
+}
+
+/// Pixel value of a shadow length such as `4px`, `0` or `0.5em`
+fn parse_pixel_value(value: &str) -> Option<f32> {
+    kryon_core::parse_length(value)?.to_pixels(&kryon_core::LengthContext::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lengths_and_color() {
+        let shadow = BoxShadow::parse("0 4px 6px rgba(0, 0, 0, 0.5)").unwrap();
+        assert_eq!(shadow.offset, Vec2::new(0.0, 4.0));
+        assert_eq!(shadow.blur_radius, 6.0);
+        assert_eq!(shadow.spread_radius, 0.0);
+        assert_eq!(shadow.color, Vec4::new(0.0, 0.0, 0.0, 0.5));
+
+        assert_eq!(BoxShadow::parse("2px 2px").unwrap().color, DEFAULT_SHADOW_COLOR);
+        assert_eq!(BoxShadow::parse("none"), None);
+        assert_eq!(BoxShadow::parse(""), None);
+    }
+
+    #[test]
+    fn sharp_shadow_is_one_offset_and_spread_rectangle() {
+        let shadow = BoxShadow::parse("3px 5px 0 2px #000000").unwrap();
+        let layers = shadow.layers(Vec2::new(10.0, 10.0), Vec2::new(100.0, 50.0), 4.0);
+        assert_eq!(layers, vec![ShadowLayer {
+            position: Vec2::new(11.0, 13.0),
+            size: Vec2::new(104.0, 54.0),
+            border_radius: 6.0,
+            color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        }]);
+    }
+
+    #[test]
+    fn blur_fades_out_across_the_edge() {
+        let shadow = BoxShadow::parse("0 0 8px rgba(0, 0, 0, 0.5)").unwrap();
+        let layers = shadow.layers(Vec2::ZERO, Vec2::new(100.0, 100.0), 0.0);
+        assert_eq!(layers.len(), 8);
+
+        // Outermost reaches almost the whole blur radius out, innermost as far in
+        assert_eq!(layers[0].position, Vec2::splat(-7.0));
+        assert_eq!(layers[7].position, Vec2::splat(7.0));
+        assert!(layers.windows(2).all(|pair| pair[0].size.x > pair[1].size.x));
+
+        // The middle, covered by every layer, gets the full shadow color
+        let alpha = layers.iter().fold(0.0, |alpha, layer| alpha + layer.color.w * (1.0 - alpha));
+        assert!((alpha - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn layers_that_shrink_away_are_dropped() {
+        let shadow = BoxShadow::parse("0 0 10px #000000").unwrap();
+        let layers = shadow.layers(Vec2::ZERO, Vec2::new(4.0, 4.0), 0.0);
+        assert!(!layers.is_empty() && layers.len() < 10);
+        assert!(layers.iter().all(|layer| layer.size.x > 0.0 && layer.size.y > 0.0));
+    }
+}
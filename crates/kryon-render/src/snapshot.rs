@@ -0,0 +1,155 @@
+// crates/kryon-render/src/snapshot.rs
+//! Read-only frame snapshots, handed from the update side to the render side.
+//!
+//! The runtime builds a [`FrameSnapshot`] at the end of each update that
+//! changed what's on screen: the element tree, its layout, and the state
+//! the renderer needs on top of them (hover path, scroll offsets,
+//! particles, tables, the overlay and any drag ghost). It is published to a
+//! [`SnapshotSlot`], which swaps in the new `Arc` and wakes anyone waiting.
+//! The render side only ever reads a snapshot, so an `ElementRenderer` can
+//! run on a thread of its own while the next update builds the next one.
+//!
+//! Styles are not copied into the snapshot: the renderer's `StyleComputer`
+//! catches up with each snapshot's elements and keeps the styles whose
+//! inputs didn't change, so only changed elements are restyled.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{ColorScheme, Element, ElementId, Orientation, ParticleInstance, TableView};
+use kryon_layout::LayoutResult;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use crate::{DragGhost, RenderCommand};
+
+/// Everything needed to draw one frame
+#[derive(Debug, Clone)]
+pub struct FrameSnapshot {
+    /// Counts up from 1 with each published snapshot; 0 before the first
+    pub frame: u64,
+    pub root_id: Option<ElementId>,
+    pub clear_color: Vec4,
+    pub elements: Arc<HashMap<ElementId, Element>>,
+    pub layout: Arc<LayoutResult>,
+    /// Elements under the pointer, innermost first
+    pub hover_path: Vec<ElementId>,
+    pub orientation: Orientation,
+    /// Picks the default stylesheet's light or dark rules
+    pub color_scheme: ColorScheme,
+    /// How far each scroll container's content is scrolled
+    pub scroll_offsets: HashMap<ElementId, Vec2>,
+    /// Elements whose contents aren't laid out, so aren't drawn
+    pub skipped_subtrees: HashSet<ElementId>,
+    pub particles: Arc<HashMap<ElementId, Vec<ParticleInstance>>>,
+    pub tables: Arc<HashMap<ElementId, TableView>>,
+    /// Drawn on top of the element tree, in window coordinates
+    pub overlay: Arc<Vec<RenderCommand>>,
+    /// The element being dragged, drawn again where the pointer is
+    pub drag_ghost: Option<DragGhost>,
+}
+
+impl Default for FrameSnapshot {
+    fn default() -> Self {
+        Self {
+            frame: 0,
+            root_id: None,
+            clear_color: Vec4::ZERO,
+            elements: Arc::default(),
+            layout: Arc::new(LayoutResult {
+                computed_positions: HashMap::new(),
+                computed_sizes: HashMap::new(),
+            }),
+            hover_path: Vec::new(),
+            orientation: Orientation::Landscape,
+            color_scheme: ColorScheme::Light,
+            scroll_offsets: HashMap::new(),
+            skipped_subtrees: HashSet::new(),
+            particles: Arc::default(),
+            tables: Arc::default(),
+            overlay: Arc::default(),
+            drag_ghost: None,
+        }
+    }
+}
+
+/// The latest published snapshot, shared between the update and render
+/// sides. Cloning gives another handle to the same slot.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotSlot {
+    shared: Arc<(Mutex<Arc<FrameSnapshot>>, Condvar)>,
+}
+
+impl SnapshotSlot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the latest snapshot. Frames being drawn from the previous
+    /// one keep it until they finish.
+    pub fn publish(&self, snapshot: FrameSnapshot) {
+        let (latest, published) = &*self.shared;
+        *latest.lock().unwrap_or_else(|e| e.into_inner()) = Arc::new(snapshot);
+        published.notify_all();
+    }
+
+    /// The latest snapshot
+    pub fn latest(&self) -> Arc<FrameSnapshot> {
+        self.shared.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Waits up to `timeout` for a snapshot newer than frame `after`; `None`
+    /// if none was published in time
+    pub fn wait_newer(&self, after: u64, timeout: Duration) -> Option<Arc<FrameSnapshot>> {
+        let (latest, published) = &*self.shared;
+        let guard = latest.lock().unwrap_or_else(|e| e.into_inner());
+        let (guard, _) = published
+            .wait_timeout_while(guard, timeout, |snapshot| snapshot.frame <= after)
+            .unwrap_or_else(|e| e.into_inner());
+        (guard.frame > after).then(|| guard.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(frame: u64) -> FrameSnapshot {
+        FrameSnapshot { frame, ..Default::default() }
+    }
+
+    #[test]
+    fn readers_keep_their_snapshot_across_a_publish() {
+        let slot = SnapshotSlot::new();
+        slot.publish(frame(1));
+        let drawing = slot.latest();
+        slot.publish(frame(2));
+        assert_eq!(drawing.frame, 1);
+        assert_eq!(slot.latest().frame, 2);
+    }
+
+    #[test]
+    fn render_thread_wakes_for_each_new_snapshot() {
+        let slot = SnapshotSlot::new();
+        let render_side = slot.clone();
+        let render_thread = std::thread::spawn(move || {
+            let mut drawn = Vec::new();
+            let mut last = 0;
+            while last < 3 {
+                if let Some(snapshot) = render_side.wait_newer(last, Duration::from_secs(10)) {
+                    last = snapshot.frame;
+                    drawn.push(last);
+                }
+            }
+            drawn
+        });
+        for n in 1..=3 {
+            slot.publish(frame(n));
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let drawn = render_thread.join().unwrap();
+        assert_eq!(drawn.last(), Some(&3));
+        assert!(drawn.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert!(slot.wait_newer(3, Duration::from_millis(1)).is_none());
+    }
+}
@@ -0,0 +1,186 @@
+// crates/kryon-render/src/stats.rs
+//! Per-frame statistics collected by `ElementRenderer`.
+//!
+//! Each frame's stats are logged at debug level under the
+//! `kryon::render_stats` target (e.g. `RUST_LOG=kryon::render_stats=debug`).
+
+use crate::RenderCommand;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderStats {
+    /// Commands sent to the backend, by `RenderCommand::kind`
+    pub commands: BTreeMap<&'static str, usize>,
+    /// Elements the traversal reached
+    pub elements_visited: usize,
+    /// Elements skipped along with their subtrees (hidden)
+    pub elements_culled: usize,
+    /// Elements that clip their children (overflow other than visible)
+    pub elements_clipped: usize,
+    /// Elements left undrawn because they're outside the viewport or a clip
+    pub elements_offscreen: usize,
+    pub style_cache_hits: u64,
+    pub style_cache_misses: u64,
+    /// Nothing changed since the last frame, so the backend didn't draw
+    pub skipped: bool,
+    /// Elements the backend redrew on their own, when it redrew only the
+    /// dirty region; 0 for a full redraw
+    pub dirty_elements: usize,
+    /// Items of the display list, for backends that retain one; 0 otherwise
+    pub display_items: usize,
+    /// Display list items the backend rebuilt rather than reused
+    pub rebuilt_items: usize,
+}
+
+impl RenderStats {
+    pub fn count_commands(&mut self, commands: &[RenderCommand]) {
+        for command in commands {
+            *self.commands.entry(command.kind()).or_insert(0) += 1;
+        }
+    }
+
+    pub fn total_commands(&self) -> usize {
+        self.commands.values().sum()
+    }
+
+    /// One-line form used for the debug log.
+    pub fn summary(&self) -> String {
+        let by_kind: Vec<String> = self.commands.iter()
+            .map(|(kind, count)| format!("{}={}", kind, count))
+            .collect();
+        let offscreen = if self.elements_offscreen > 0 {
+            format!(" offscreen={}", self.elements_offscreen)
+        } else {
+            String::new()
+        };
+        let redraw = if self.skipped {
+            " skipped".to_string()
+        } else if self.dirty_elements > 0 {
+            format!(" dirty={}", self.dirty_elements)
+        } else {
+            String::new()
+        };
+        let retained = if self.display_items > 0 {
+            format!(" rebuilt={}/{}", self.rebuilt_items, self.display_items)
+        } else {
+            String::new()
+        };
+        format!(
+            "commands={} [{}] visited={} culled={} clipped={}{} style_cache={}/{}{}{}",
+            self.total_commands(),
+            by_kind.join(" "),
+            self.elements_visited,
+            self.elements_culled,
+            self.elements_clipped,
+            offscreen,
+            self.style_cache_hits,
+            self.style_cache_hits + self.style_cache_misses,
+            redraw,
+            retained,
+        )
+    }
+
+    /// Lines for the profiler overlay.
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut kinds: Vec<(&&str, &usize)> = self.commands.iter().collect();
+        kinds.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let top: Vec<String> = kinds.iter().take(3).map(|(kind, count)| format!("{} {}", kind, count)).collect();
+
+        vec![
+            format!("Commands    {} ({})", self.total_commands(), top.join(", ")),
+            format!(
+                "Elements    {} visited, {} culled, {} clipped, {} offscreen",
+                self.elements_visited, self.elements_culled, self.elements_clipped, self.elements_offscreen
+            ),
+            format!("Style cache {} hits, {} misses", self.style_cache_hits, self.style_cache_misses),
+        ]
+    }
+}
+
+/// Stats summed over several frames, for periodic summaries.
+#[derive(Debug, Clone, Default)]
+pub struct RenderStatsSummary {
+    frames: usize,
+    totals: RenderStats,
+    max_commands: usize,
+}
+
+impl RenderStatsSummary {
+    pub fn add(&mut self, stats: &RenderStats) {
+        self.frames += 1;
+        self.max_commands = self.max_commands.max(stats.total_commands());
+        for (kind, count) in &stats.commands {
+            *self.totals.commands.entry(kind).or_insert(0) += count;
+        }
+        self.totals.elements_visited += stats.elements_visited;
+        self.totals.elements_culled += stats.elements_culled;
+        self.totals.elements_clipped += stats.elements_clipped;
+        self.totals.elements_offscreen += stats.elements_offscreen;
+        self.totals.style_cache_hits += stats.style_cache_hits;
+        self.totals.style_cache_misses += stats.style_cache_misses;
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Per-frame averages over `elapsed`, with the busiest command kinds.
+    pub fn report(&self, elapsed: Duration) -> String {
+        let frames = self.frames.max(1) as f64;
+        let average = |total: usize| total as f64 / frames;
+        let mut kinds: Vec<(&&str, &usize)> = self.totals.commands.iter().collect();
+        kinds.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let by_kind: Vec<String> = kinds.iter()
+            .map(|(kind, count)| format!("{} {:.1}", kind, average(**count)))
+            .collect();
+        let lookups = self.totals.style_cache_hits + self.totals.style_cache_misses;
+
+        format!(
+            "{} frames in {:.1}s ({:.1} fps); per frame: {:.1} commands (max {}) [{}], \
+             {:.1} visited, {:.1} culled, {:.1} clipped, {:.1} offscreen; style cache hit rate {:.0}%",
+            self.frames,
+            elapsed.as_secs_f64(),
+            self.frames as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            average(self.totals.total_commands()),
+            self.max_commands,
+            by_kind.join(", "),
+            average(self.totals.elements_visited),
+            average(self.totals.elements_culled),
+            average(self.totals.elements_clipped),
+            average(self.totals.elements_offscreen),
+            if lookups == 0 { 0.0 } else { self.totals.style_cache_hits as f64 * 100.0 / lookups as f64 },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+
+    #[test]
+    fn counts_commands_by_kind_and_summarizes_frames() {
+        let mut stats = RenderStats::default();
+        stats.count_commands(&[
+            RenderCommand::SetCanvasSize(Vec2::ONE),
+            RenderCommand::ClearClip,
+            RenderCommand::ClearClip,
+        ]);
+        stats.elements_visited = 4;
+        stats.style_cache_misses = 4;
+        assert_eq!(stats.total_commands(), 3);
+        assert_eq!(
+            stats.summary(),
+            "commands=3 [ClearClip=2 SetCanvasSize=1] visited=4 culled=0 clipped=0 style_cache=0/4"
+        );
+        assert!(RenderStats { dirty_elements: 2, ..stats.clone() }.summary().ends_with("style_cache=0/4 dirty=2"));
+
+        let mut summary = RenderStatsSummary::default();
+        summary.add(&stats);
+        summary.add(&RenderStats::default());
+        assert_eq!(summary.frames(), 2);
+        let report = summary.report(Duration::from_secs(1));
+        assert!(report.starts_with("2 frames in 1.0s (2.0 fps); per frame: 1.5 commands (max 3) [ClearClip 1.0, SetCanvasSize 0.5]"), "{}", report);
+    }
+}
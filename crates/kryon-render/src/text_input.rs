@@ -0,0 +1,354 @@
+// crates/kryon-render/src/text_input.rs
+//! Where the characters of a `DrawTextInput` value sit, for drawing the
+//! caret and selection of a focused input and for placing the caret where
+//! it's clicked.
+//!
+//! Backends draw the value from `TEXT_INPUT_PADDING` in from the left edge,
+//! moved left by the command's `scroll_offset` once it's wider than the
+//! input, and clipped to the input. Positions along it are estimated from
+//! an average glyph width, the same way layout sizes text it can't measure,
+//! so the caret can drift a little from the glyphs in proportional fonts.
+//!
+//! The caret blinks on the runtime's animation clock: `blink_phase` runs
+//! from 0 to 1 over `CARET_BLINK_PERIOD`, showing the caret for the first
+//! half, and starts over whenever the caret moves.
+//!
+//! A `password` input's value is drawn as one `mask_character` per
+//! character until it's `revealed`. With `reveal_toggle: true` it shows an
+//! eye at its right end that reveals and hides the value when clicked.
+//!
+//! An input that failed its validation has an `error_message`, drawn below
+//! it in `INVALID_COLOR`.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{Element, PropertyValue, TextAlignment, TransformData, INVALID_COLOR};
+use std::time::Duration;
+
+use crate::RenderCommand;
+
+/// `input_type`s drawn as a `DrawTextInput` and edited as text
+pub const TEXT_INPUT_TYPES: &[&str] = &["text", "password", "email", "number", "tel", "url", "search"];
+
+/// Space between an input's left edge and its text
+pub const TEXT_INPUT_PADDING: f32 = 5.0;
+
+/// Average glyph width as a fraction of the font size
+const AVERAGE_CHAR_WIDTH: f32 = 0.6;
+
+/// Width of the caret bar
+const CARET_WIDTH: f32 = 1.5;
+
+/// Fill behind selected text
+pub const SELECTION_COLOR: Vec4 = Vec4::new(0.2, 0.45, 0.95, 0.35);
+
+/// One blink of the caret, shown then hidden
+pub const CARET_BLINK_PERIOD: Duration = Duration::from_millis(1060);
+
+/// Drawn in place of each character of a password until one is set with
+/// the `mask_character` property
+pub const DEFAULT_MASK_CHARACTER: char = '\u{2022}';
+
+/// Space between an input and the error message below it
+pub const ERROR_MESSAGE_GAP: f32 = 4.0;
+
+/// Size of an error message's text, relative to its input's
+const ERROR_MESSAGE_SCALE: f32 = 0.85;
+
+/// Where the caret of a focused input is, as character indices into its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Caret {
+    pub index: usize,
+    /// Start and end of the selected characters; empty if they're equal
+    pub selection: (usize, usize),
+}
+
+/// Distance from the input's left edge to the boundary before the
+/// `index`th character of `text`, unscrolled
+pub fn caret_x(text: &str, index: usize, font_size: f32) -> f32 {
+    TEXT_INPUT_PADDING + index.min(text.chars().count()) as f32 * font_size * AVERAGE_CHAR_WIDTH
+}
+
+/// The character boundary nearest to `x`, measured from the input's left
+/// edge of the unscrolled text
+pub fn index_at(text: &str, x: f32, font_size: f32) -> usize {
+    let width = font_size * AVERAGE_CHAR_WIDTH;
+    if width <= 0.0 {
+        return 0;
+    }
+    let index = ((x - TEXT_INPUT_PADDING) / width).round().max(0.0) as usize;
+    index.min(text.chars().count())
+}
+
+/// How far `text` can scroll in an input `width` wide: until its end meets
+/// the right padding
+pub fn max_scroll(text: &str, font_size: f32, width: f32) -> f32 {
+    (caret_x(text, usize::MAX, font_size) + TEXT_INPUT_PADDING - width).max(0.0)
+}
+
+/// The scroll offset that keeps the caret at `index` in view, moving
+/// `scroll` no further than it has to, so the text stays put while the
+/// caret moves within the visible part
+pub fn scroll_to_caret(text: &str, index: usize, font_size: f32, width: f32, scroll: f32) -> f32 {
+    let x = caret_x(text, index, font_size);
+    let scroll = scroll.min(x - TEXT_INPUT_PADDING).max(x + CARET_WIDTH + TEXT_INPUT_PADDING - width);
+    scroll.clamp(0.0, max_scroll(text, font_size, width))
+}
+
+/// How far through its blink the caret is, `time` after it last moved
+pub fn blink_phase(time: Duration) -> f32 {
+    let period = CARET_BLINK_PERIOD.as_secs_f32();
+    (time.as_secs_f32() % period) / period
+}
+
+/// Whether the caret shows at `blink_phase`
+pub fn caret_shown(blink_phase: f32) -> bool {
+    blink_phase < 0.5
+}
+
+/// Whether an input holds a password, whose value scripts only see
+/// masked, however it's drawn
+pub fn is_password(element: &Element) -> bool {
+    matches!(element.custom_properties.get("input_type"), Some(PropertyValue::String(input_type)) if input_type == "password")
+}
+
+/// Whether an input's value is drawn masked: a password that isn't `revealed`
+pub fn is_masked(element: &Element) -> bool {
+    is_password(element) && !matches!(element.custom_properties.get("revealed"), Some(PropertyValue::Bool(true)))
+}
+
+/// Whether a password input shows the eye that reveals its value
+pub fn has_reveal_toggle(element: &Element) -> bool {
+    is_password(element) && matches!(element.custom_properties.get("reveal_toggle"), Some(PropertyValue::Bool(true)))
+}
+
+/// `text` as a masked input shows it: the element's `mask_character`, or
+/// `DEFAULT_MASK_CHARACTER`, once per character
+pub fn masked(element: &Element, text: &str) -> String {
+    let mask = element.custom_properties.get("mask_character")
+        .and_then(|value| value.as_string())
+        .and_then(|mask| mask.chars().next())
+        .unwrap_or(DEFAULT_MASK_CHARACTER);
+    std::iter::repeat_n(mask, text.chars().count()).collect()
+}
+
+/// An input's value as it's drawn, masked or not, for measuring where its
+/// characters are
+pub fn shown_value(element: &Element) -> String {
+    if is_masked(element) {
+        masked(element, &element.text)
+    } else {
+        element.text.clone()
+    }
+}
+
+/// The eye of an input at `position` of `size` with a reveal toggle: a
+/// square at its right end, as tall as the input and at most half as wide
+pub fn reveal_toggle_area(position: Vec2, size: Vec2) -> (Vec2, Vec2) {
+    let side = size.y.min(size.x / 2.0).max(0.0);
+    (Vec2::new(position.x + size.x - side, position.y), Vec2::new(side, size.y))
+}
+
+/// How much of an input `size` wide its value has to scroll in: all of it,
+/// less the eye of a reveal toggle
+pub fn text_area_width(size: Vec2, reveal_toggle: bool) -> f32 {
+    if reveal_toggle {
+        size.x - reveal_toggle_area(Vec2::ZERO, size).1.x
+    } else {
+        size.x
+    }
+}
+
+/// The eye of a reveal toggle, drawn over the end of the value in the
+/// input's `background` inside its border: open while the value shows,
+/// shut while it's masked, and brighter under the pointer
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_toggle_commands(
+    position: Vec2,
+    size: Vec2,
+    border_width: f32,
+    revealed: bool,
+    hovered: bool,
+    color: Vec4,
+    background: Vec4,
+    transform: Option<TransformData>,
+) -> Vec<RenderCommand> {
+    let (area_position, area_size) = reveal_toggle_area(position, size);
+    let side = area_size.x.min(area_size.y);
+    let center = area_position + area_size / 2.0;
+    let color = Vec4::new(color.x, color.y, color.z, color.w * if hovered { 1.0 } else { 0.6 });
+    // Same z as the input, so the stable sort keeps them right after it
+    let rect = |position: Vec2, size: Vec2, radius: f32, color: Vec4, border: f32, border_color: Vec4| RenderCommand::DrawRect {
+        position,
+        size,
+        color,
+        border_radius: radius,
+        border_width: border,
+        border_color,
+        transform: transform.clone(),
+        shadow: None,
+        z_index: 1,
+    };
+
+    let inset = Vec2::new(0.0, border_width);
+    let cover = (area_size - inset - Vec2::new(border_width, border_width)).max(Vec2::ZERO);
+    let mut commands = vec![rect(area_position + inset, cover, 0.0, background, 0.0, Vec4::ZERO)];
+    let eye = Vec2::new(side * 0.6, side * 0.36);
+    if revealed {
+        commands.push(rect(center - eye / 2.0, eye, eye.y / 2.0, Vec4::ZERO, CARET_WIDTH, color));
+        let pupil = Vec2::splat(side * 0.16);
+        commands.push(rect(center - pupil / 2.0, pupil, pupil.x / 2.0, color, 0.0, Vec4::ZERO));
+    } else {
+        let lid = Vec2::new(eye.x, CARET_WIDTH);
+        commands.push(rect(center - lid / 2.0, lid, lid.y / 2.0, color, 0.0, Vec4::ZERO));
+    }
+    commands
+}
+
+/// The `error_message` of an input at `position` of `size`, as a line of
+/// text below it as wide as the input; `None` while it has none
+pub fn error_message_command(element: &Element, position: Vec2, size: Vec2, font_size: f32, transform: Option<TransformData>) -> Option<RenderCommand> {
+    let message = element.custom_properties.get("error_message")?.as_string().filter(|message| !message.is_empty())?;
+    let font_size = font_size * ERROR_MESSAGE_SCALE;
+    Some(RenderCommand::DrawText {
+        position: Vec2::new(position.x, position.y + size.y + ERROR_MESSAGE_GAP),
+        text: message.to_string(),
+        font_size,
+        color: INVALID_COLOR,
+        alignment: TextAlignment::Start,
+        max_width: Some(size.x),
+        max_height: Some(font_size * 1.2),
+        transform,
+        font_family: None,
+        runs: Vec::new(),
+        z_index: 1,
+    })
+}
+
+/// The selection highlight and caret bar of a focused input, each as a
+/// position and size. Both are scrolled with the text and cut to the
+/// input's box; either is `None` if there's nothing of it to draw.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CaretRects {
+    pub selection: Option<(Vec2, Vec2)>,
+    pub caret: Option<(Vec2, Vec2)>,
+}
+
+/// The selection and caret of an input at `position` of `size`, over its
+/// value scrolled by `scroll_offset`. The caret is left out in the hidden
+/// half of its blink.
+pub fn caret_rects(
+    position: Vec2,
+    size: Vec2,
+    text: &str,
+    font_size: f32,
+    caret: Caret,
+    scroll_offset: f32,
+    blink_phase: f32,
+) -> CaretRects {
+    let line_height = font_size.min(size.y);
+    let top = position.y + (size.y - line_height) / 2.0;
+    let bar = |left: f32, right: f32| {
+        let (left, right) = ((left - scroll_offset).max(0.0), (right - scroll_offset).min(size.x));
+        (right > left).then(|| (Vec2::new(position.x + left, top), Vec2::new(right - left, line_height)))
+    };
+
+    let (start, end) = caret.selection;
+    let selection = (start != end)
+        .then(|| bar(caret_x(text, start, font_size), caret_x(text, end, font_size)))
+        .flatten();
+    let x = caret_x(text, caret.index, font_size);
+    let caret = caret_shown(blink_phase).then(|| bar(x, x + CARET_WIDTH)).flatten();
+    CaretRects { selection, caret }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clicks_land_on_the_nearest_character_boundary() {
+        assert_eq!(caret_x("hello", 0, 20.0), TEXT_INPUT_PADDING);
+        assert_eq!(caret_x("hello", 2, 20.0), TEXT_INPUT_PADDING + 24.0);
+        assert_eq!(caret_x("hello", 99, 20.0), TEXT_INPUT_PADDING + 60.0);
+
+        assert_eq!(index_at("hello", TEXT_INPUT_PADDING + 25.0, 20.0), 2);
+        assert_eq!(index_at("hello", TEXT_INPUT_PADDING + 31.0, 20.0), 3);
+        assert_eq!(index_at("hello", 0.0, 20.0), 0);
+        assert_eq!(index_at("hello", 500.0, 20.0), 5);
+        assert_eq!(index_at("héllo", TEXT_INPUT_PADDING + 12.0, 20.0), 1);
+    }
+
+    #[test]
+    fn selection_and_caret_stay_inside_the_input() {
+        let rects = caret_rects(Vec2::ZERO, Vec2::new(40.0, 30.0), "abcdef", 20.0, Caret { index: 4, selection: (1, 4) }, 0.0, 0.0);
+        assert_eq!(rects.selection, Some((Vec2::new(17.0, 5.0), Vec2::new(23.0, 20.0))));
+        assert_eq!(rects.caret, None);
+
+        let caret_only = caret_rects(Vec2::ZERO, Vec2::new(100.0, 30.0), "abc", 20.0, Caret { index: 1, selection: (1, 1) }, 0.0, 0.0);
+        assert_eq!(caret_only.selection, None);
+        assert_eq!(caret_only.caret, Some((Vec2::new(17.0, 5.0), Vec2::new(1.5, 20.0))));
+
+        let blinked_off = caret_rects(Vec2::ZERO, Vec2::new(100.0, 30.0), "abc", 20.0, Caret { index: 1, selection: (1, 1) }, 0.0, 0.6);
+        assert_eq!(blinked_off.caret, None);
+    }
+
+    #[test]
+    fn password_inputs_are_masked_until_revealed() {
+        let mut element = Element { element_type: kryon_core::ElementType::Input, ..Default::default() };
+        assert!(!is_masked(&element));
+        element.custom_properties.insert("input_type".into(), PropertyValue::String("password".into()));
+        assert!(is_masked(&element));
+        assert_eq!(masked(&element, "héllo"), "\u{2022}".repeat(5));
+        element.text = "pw".into();
+        assert_eq!(shown_value(&element), "\u{2022}\u{2022}");
+        element.custom_properties.insert("mask_character".into(), PropertyValue::String("*".into()));
+        assert_eq!(masked(&element, "abc"), "***");
+        element.custom_properties.insert("revealed".into(), PropertyValue::Bool(true));
+        assert!(!is_masked(&element));
+
+        assert!(!has_reveal_toggle(&element));
+        element.custom_properties.insert("reveal_toggle".into(), PropertyValue::Bool(true));
+        assert!(has_reveal_toggle(&element));
+        assert_eq!(reveal_toggle_area(Vec2::new(10.0, 0.0), Vec2::new(200.0, 30.0)), (Vec2::new(180.0, 0.0), Vec2::new(30.0, 30.0)));
+        assert_eq!(text_area_width(Vec2::new(200.0, 30.0), true), 170.0);
+        assert_eq!(reveal_toggle_commands(Vec2::ZERO, Vec2::new(200.0, 30.0), 1.0, true, false, Vec4::ONE, Vec4::ZERO, None).len(), 3);
+    }
+
+    #[test]
+    fn error_messages_are_drawn_below_the_input() {
+        let mut element = Element { element_type: kryon_core::ElementType::Input, ..Default::default() };
+        let (position, size) = (Vec2::new(10.0, 20.0), Vec2::new(200.0, 30.0));
+        assert!(error_message_command(&element, position, size, 20.0, None).is_none());
+
+        element.custom_properties.insert("error_message".into(), PropertyValue::String("Please fill in this field.".into()));
+        let Some(RenderCommand::DrawText { position, text, font_size, color, max_width, .. }) = error_message_command(&element, position, size, 20.0, None) else {
+            panic!("expected a line of text");
+        };
+        assert_eq!(position, Vec2::new(10.0, 54.0));
+        assert_eq!((text.as_str(), font_size, color, max_width), ("Please fill in this field.", 17.0, INVALID_COLOR, Some(200.0)));
+    }
+
+    #[test]
+    fn long_values_scroll_to_keep_the_caret_in_view() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        // 20 characters 12 wide in an input 100 wide
+        let text = "abcdefghijklmnopqrst";
+        assert!(close(max_scroll(text, 20.0, 100.0), 150.0));
+        assert_eq!(scroll_to_caret(text, 3, 20.0, 100.0, 0.0), 0.0);
+
+        // The caret at the end scrolls the text as far as it goes
+        let scroll = scroll_to_caret(text, 20, 20.0, 100.0, 0.0);
+        assert!(close(scroll, 150.0), "{}", scroll);
+        let rects = caret_rects(Vec2::ZERO, Vec2::new(100.0, 30.0), text, 20.0, Caret { index: 20, selection: (20, 20) }, scroll, 0.0);
+        let (caret_position, caret_size) = rects.caret.expect("the caret is in view");
+        assert!(close(caret_position.x, 95.0) && close(caret_size.x, 1.5), "{:?}", rects);
+
+        // Moving back within view leaves the text where it is; past the left edge scrolls back
+        assert_eq!(scroll_to_caret(text, 15, 20.0, 100.0, scroll), scroll);
+        assert!(close(scroll_to_caret(text, 5, 20.0, 100.0, scroll), 60.0));
+
+        assert!(caret_shown(blink_phase(Duration::from_millis(100))));
+        assert!(!caret_shown(blink_phase(Duration::from_millis(600))));
+        assert!(caret_shown(blink_phase(CARET_BLINK_PERIOD + Duration::from_millis(100))));
+    }
+}
@@ -1,9 +1,11 @@
 //! TextManager for cosmic-text integration
 
 use cosmic_text::{
-    Attrs, Buffer, Color as CosmicColor, Family, FontSystem, Metrics, Shaping, SwashCache, Weight, Style as CosmicStyle
+    Attrs, Buffer, CacheKey, Color as CosmicColor, Family, FontSystem, Metrics, Shaping, SwashCache, SwashImage, Weight, Style as CosmicStyle
 };
-use kryon_core::{RichText, TextSpan, RichFontWeight, RichFontStyle, RichTextAlignment};
+use kryon_core::{RichText, TextRun, TextSpan, RichFontWeight, RichFontStyle, RichTextAlignment};
+use kryon_layout::text_wrap::{wrap_text, WrappedText, ELLIPSIS};
+use kryon_layout::TextMeasurer;
 use glam::{Vec2, Vec4};
 use std::collections::HashMap;
 
@@ -61,6 +63,16 @@ pub struct PositionedGlyph {
     
     /// Font cache key for identifying which font this glyph belongs to
     pub font_cache_key: String,
+    
+    /// Byte range of the glyph's cluster in the shaped text
+    pub text_range: std::ops::Range<usize>,
+    
+    /// Key of the glyph's rasterized image, for [`TextManager::glyph_image`]
+    pub raster_key: CacheKey,
+    
+    /// Where the rasterized image is placed from: the glyph's origin on
+    /// the baseline, at whole pixels
+    pub raster_origin: Vec2,
 }
 
 impl TextManager {
@@ -98,6 +110,31 @@ impl TextManager {
         self.extract_glyphs_from_buffer(&buffer, default_color)
     }
     
+    /// Render `text` styled by `DrawText` runs. Unstyled parts take
+    /// `font_size` and `default_color`.
+    pub fn render_runs(
+        &mut self,
+        text: &str,
+        runs: &[TextRun],
+        font_size: f32,
+        default_color: Vec4,
+        max_width: Option<f32>,
+    ) -> RenderedText {
+        let spans = kryon_core::text_segments(text, runs).into_iter()
+            .map(|(piece, run)| {
+                let mut span = TextSpan::new(piece).with_font_size(font_size);
+                if let Some(run) = run {
+                    span.color = run.color;
+                    span.font_weight = run.font_weight;
+                    span.font_style = run.font_style;
+                    span.text_decoration = run.text_decoration;
+                }
+                span
+            })
+            .collect();
+        self.render_rich_text(&RichText::from_spans(spans), max_width, default_color)
+    }
+    
     /// Render simple text (backward compatibility)
     pub fn render_simple_text(
         &mut self,
@@ -115,6 +152,45 @@ impl TextManager {
         self.render_rich_text(&rich_text, max_width, color)
     }
     
+    /// Width of `text` on one line, shaped in `font_family` or the default
+    /// sans-serif
+    pub fn measure_line(&mut self, text: &str, font_size: f32, font_family: Option<&str>) -> f32 {
+        let mut buffer = Buffer::new(&mut self.font_system, Metrics::new(font_size, font_size * 1.2));
+        let family = font_family.map_or(Family::SansSerif, Family::Name);
+        buffer.set_text(&mut self.font_system, text, Attrs::new().family(family), Shaping::Advanced);
+        buffer.shape_until_scroll(&mut self.font_system, false);
+        buffer.layout_runs().map(|run| run.line_w).fold(0.0, f32::max)
+    }
+    
+    /// `text` broken into lines within `max_width` and `max_height`, measured
+    /// by cosmic-text. Lines are `font_size * 1.2` apart, as
+    /// `render_rich_text` spaces them.
+    pub fn wrap_text(
+        &mut self,
+        text: &str,
+        font_size: f32,
+        font_family: Option<&str>,
+        max_width: Option<f32>,
+        max_height: Option<f32>,
+    ) -> WrappedText {
+        wrap_text(text, max_width, max_height, font_size * 1.2, ELLIPSIS, |piece| {
+            self.measure_line(piece, font_size, font_family)
+        })
+    }
+    
+    /// How far below the top of a box as tall as `font_size` the first line's
+    /// baseline sits, as the renderers place text. `None` when no font can
+    /// shape the text.
+    pub fn first_baseline(&mut self, text: &str, font_size: f32) -> Option<f32> {
+        let mut buffer = Buffer::new(&mut self.font_system, Metrics::new(font_size, font_size));
+        buffer.set_text(&mut self.font_system, text, Attrs::new().family(Family::SansSerif), Shaping::Advanced);
+        buffer.shape_until_scroll(&mut self.font_system, false);
+        buffer.layout_runs()
+            .next()
+            .filter(|run| !run.glyphs.is_empty())
+            .map(|run| run.line_y - run.line_top)
+    }
+    
     /// Create a cosmic-text Buffer from RichText
     fn create_text_buffer(
         &mut self,
@@ -122,10 +198,10 @@ impl TextManager {
         max_width: Option<f32>,
         default_color: Vec4,
     ) -> Buffer {
-        let metrics = Metrics::new(
-            rich_text.line_height.unwrap_or(self.default_font_size),
-            rich_text.line_height.unwrap_or(self.default_font_size * 1.2),
-        );
+        let font_size = rich_text.spans.iter()
+            .find_map(|span| span.font_size)
+            .unwrap_or(self.default_font_size);
+        let metrics = Metrics::new(font_size, rich_text.line_height.unwrap_or(font_size * 1.2));
         let mut buffer = Buffer::new(&mut self.font_system, metrics);
         
         // Set buffer width if specified
@@ -133,28 +209,15 @@ impl TextManager {
             buffer.set_size(&mut self.font_system, Some(width), None);
         }
         
-        // Build the text content with proper attributes
         if rich_text.spans.is_empty() {
             return buffer;
         }
         
-        // For cosmic-text, we need to set the entire text at once with proper spans
-        let mut full_text = String::new();
-        for span in &rich_text.spans {
-            full_text.push_str(&span.text);
-        }
-        
-        // Set the text with default attributes
-        buffer.set_text(&mut self.font_system, &full_text, Attrs::new(), Shaping::Advanced);
-        
-        // For cosmic-text 0.13, we'll use a simplified approach
-        // Apply the first span's attributes to the entire text
-        if let Some(first_span) = rich_text.spans.first() {
-            let attrs = self.span_to_attrs(first_span, default_color);
-            if let Some(line) = buffer.lines.get_mut(0) {
-                line.set_attrs_list(cosmic_text::AttrsList::new(attrs));
-            }
-        }
+        // Each span shapes with its own attributes
+        let spans: Vec<(&str, Attrs)> = rich_text.spans.iter()
+            .map(|span| (span.text.as_str(), self.span_to_attrs(span, default_color)))
+            .collect();
+        buffer.set_rich_text(&mut self.font_system, spans, Attrs::new(), Shaping::Advanced, None);
         
         // Set text alignment
         if let Some(alignment) = rich_text.alignment {
@@ -188,8 +251,10 @@ impl TextManager {
             attrs = attrs.family(Family::SansSerif);
         }
         
-        // Note: cosmic-text Attrs doesn't have a size() method
-        // Font size is handled at the metrics level for the entire buffer
+        // Spans sized differently from the buffer carry their own metrics
+        if let Some(size) = span.font_size {
+            attrs = attrs.metrics(Metrics::new(size, size * 1.2));
+        }
         
         // Font weight
         if let Some(weight) = span.font_weight {
@@ -236,22 +301,36 @@ impl TextManager {
         let mut max_x = 0.0_f32;
         let mut max_y = 0.0_f32;
         
+        // Glyph ranges are within their line; offset them into the whole text
+        let mut line_starts = Vec::with_capacity(buffer.lines.len());
+        let mut line_start = 0;
+        for line in &buffer.lines {
+            line_starts.push(line_start);
+            line_start += line.text().len() + line.ending().as_str().len();
+        }
+        
         for run in buffer.layout_runs() {
+            let line_start = line_starts.get(run.line_i).copied().unwrap_or(0);
             for glyph in run.glyphs.iter() {
-                let position = Vec2::new(glyph.x, glyph.y);
-                let size = Vec2::new(glyph.w as f32, run.line_height);
+                let position = Vec2::new(glyph.x, glyph.y + run.line_top);
+                let physical = glyph.physical((0.0, run.line_y), 1.0);
+                let size = Vec2::new(glyph.w, run.line_height);
                 
-                // For now, use default color since extracting color from runs is complex
-                let color = default_color;
+                let color = glyph.color_opt
+                    .map(|color| Vec4::new(color.r() as f32, color.g() as f32, color.b() as f32, color.a() as f32) / 255.0)
+                    .unwrap_or(default_color);
                 
                 let positioned_glyph = PositionedGlyph {
                     position,
                     size,
                     color,
-                    font_size: run.line_height / 1.2, // Approximate font size from line height
-                    character: ' ', // Would need to track this from the original text
+                    font_size: glyph.font_size,
+                    character: run.text.get(glyph.start..glyph.end).and_then(|cluster| cluster.chars().next()).unwrap_or(' '),
                     glyph_id: glyph.glyph_id as u32,
                     font_cache_key: format!("font_{}", 0), // Simplified
+                    text_range: line_start + glyph.start..line_start + glyph.end,
+                    raster_key: physical.cache_key,
+                    raster_origin: Vec2::new(physical.x as f32, physical.y as f32),
                 };
                 
                 max_x = max_x.max(position.x + size.x);
@@ -306,6 +385,23 @@ impl TextManager {
         self.buffer_cache.clear();
     }
     
+    /// A glyph rasterized by swash, cached after the first time; `None`
+    /// for glyphs with nothing to draw
+    pub fn glyph_image(&mut self, key: CacheKey) -> Option<SwashImage> {
+        self.swash_cache.get_image(&mut self.font_system, key).clone()
+    }
+    
+    /// Loads a font file's faces for shaping and returns the family name
+    /// spans should ask for to use them
+    pub fn load_font_data(&mut self, data: Vec<u8>) -> Option<String> {
+        let database = self.font_system.db_mut();
+        let ids = database.load_font_source(cosmic_text::fontdb::Source::Binary(std::sync::Arc::new(data)));
+        ids.first()
+            .and_then(|id| database.face(*id))
+            .and_then(|face| face.families.first())
+            .map(|(family, _)| family.clone())
+    }
+    
     /// Get a reference to the SwashCache for glyph rasterization
     pub fn swash_cache(&mut self) -> &mut SwashCache {
         &mut self.swash_cache
@@ -323,6 +419,51 @@ impl Default for TextManager {
     }
 }
 
+/// Widths remembered by a [`CosmicTextMeasurer`] before it starts over
+const MEASURED_WIDTHS_LIMIT: usize = 4096;
+
+/// Measures text for layout with cosmic-text, as the backends that shape
+/// with it draw the text. Layout asks for the same words and lines many
+/// times a pass, so widths are remembered.
+pub struct CosmicTextMeasurer {
+    text_manager: TextManager,
+    /// By text, font size bits and font family
+    widths: HashMap<(String, u32, Option<String>), f32>,
+}
+
+impl CosmicTextMeasurer {
+    pub fn new() -> Self {
+        Self { text_manager: TextManager::new(), widths: HashMap::new() }
+    }
+    
+    /// See [`TextManager::load_font_data`]
+    pub fn load_font_data(&mut self, data: Vec<u8>) -> Option<String> {
+        self.widths.clear();
+        self.text_manager.load_font_data(data)
+    }
+}
+
+impl Default for CosmicTextMeasurer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextMeasurer for CosmicTextMeasurer {
+    fn measure(&mut self, text: &str, font_size: f32, font_family: Option<&str>) -> f32 {
+        let key = (text.to_string(), font_size.to_bits(), font_family.map(str::to_string));
+        if let Some(&width) = self.widths.get(&key) {
+            return width;
+        }
+        if self.widths.len() >= MEASURED_WIDTHS_LIMIT {
+            self.widths.clear();
+        }
+        let width = self.text_manager.measure_line(text, font_size, font_family);
+        self.widths.insert(key, width);
+        width
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,6 +492,69 @@ mod tests {
         assert!(rendered.bounds.y > 0.0);
     }
     
+    #[test]
+    fn first_baseline_scales_with_font_size() {
+        let mut text_manager = TextManager::new();
+        let Some(small) = text_manager.first_baseline("Hg", 20.0) else {
+            return; // No system fonts to shape with
+        };
+        let large = text_manager.first_baseline("Hg", 40.0).unwrap();
+        assert!(small > 10.0 && small < 20.0, "baseline {} outside the box", small);
+        assert!((large - 2.0 * small).abs() < 1.0);
+    }
+    
+    #[test]
+    fn wraps_to_measured_widths() {
+        let mut text_manager = TextManager::new();
+        let word = text_manager.measure_line("wrap", 16.0, None);
+        if word == 0.0 {
+            return; // No system fonts to shape with
+        }
+        let text = "wrap wrap wrap wrap";
+        let wrapped = text_manager.wrap_text(text, 16.0, None, Some(word * 2.5), None);
+        assert_eq!(wrapped.lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>(), ["wrap wrap", "wrap wrap"]);
+        assert_eq!(wrapped.height(), 2.0 * 16.0 * 1.2);
+        
+        let clipped = text_manager.wrap_text(text, 16.0, None, Some(word * 2.5), Some(20.0));
+        assert!(clipped.truncated);
+        assert!(clipped.lines[0].text.ends_with(ELLIPSIS));
+        assert!(clipped.lines[0].width <= word * 2.5);
+    }
+    
+    #[test]
+    fn runs_color_their_glyphs() {
+        let mut text_manager = TextManager::new();
+        let red = Vec4::new(1.0, 0.0, 0.0, 1.0);
+        let runs = [TextRun { range: 3..5, color: Some(red), font_weight: Some(RichFontWeight::Bold), ..Default::default() }];
+        let rendered = text_manager.render_runs("ab\ncd", &runs, 16.0, Vec4::new(0.0, 0.0, 0.0, 1.0), None);
+        if rendered.glyphs.is_empty() {
+            return; // No system fonts to shape with
+        }
+        
+        let glyphs: Vec<(char, std::ops::Range<usize>, bool)> = rendered.glyphs.iter()
+            .map(|glyph| (glyph.character, glyph.text_range.clone(), glyph.color == red))
+            .collect();
+        assert_eq!(glyphs, vec![('a', 0..1, false), ('b', 1..2, false), ('c', 3..4, true), ('d', 4..5, true)]);
+        assert!(rendered.glyphs[2].position.y > rendered.glyphs[0].position.y, "the second line is below the first");
+        assert_eq!(rendered.glyphs[2].font_size, 16.0);
+    }
+    
+    #[test]
+    fn cosmic_measurer_sizes_text_for_layout() {
+        let mut measurer = CosmicTextMeasurer::new();
+        let word = measurer.measure("layout", 16.0, None);
+        if word == 0.0 {
+            return; // No system fonts to shape with
+        }
+        assert_eq!(measurer.measure("layout", 16.0, None), word);
+        assert!(measurer.measure("layout", 32.0, None) > word * 1.5);
+        
+        let measurement = kryon_layout::TextMeasurement::measure("layout layout", 16.0, None, &mut measurer);
+        assert_eq!(measurement.min_content_width, word);
+        assert!(measurement.max_content_width > 2.0 * word);
+        assert_eq!(measurement.line_height, 16.0 * 1.2);
+    }
+    
     #[test]
     fn test_rich_text_rendering() {
         let mut text_manager = TextManager::new();
@@ -0,0 +1,662 @@
+// crates/kryon-render/src/threaded.rs
+//! Running a backend on a render thread of its own.
+//!
+//! [`ThreadedRenderer`] wraps a backend and is itself a `CommandRenderer`,
+//! so `ElementRenderer` and the runtime use it like any other. The main
+//! thread keeps running input, scripts, layout and command generation;
+//! each finished command list goes into a triple buffer: the list being
+//! recorded, the newest complete one and the one being drawn. The render
+//! thread draws the newest list whenever one is ready, at the backend's
+//! own pace (usually vsync), and neither side waits on the other.
+//!
+//! When the render thread falls behind, a frame it hasn't started yet is
+//! replaced by the next one and counted in [`ThreadedRenderer::dropped_frames`];
+//! [`Backpressure::Wait`] makes the main thread wait instead, for captures
+//! where every frame counts. Backends tied to the main thread's GL context
+//! or window, such as raylib, aren't `Send` and can't be moved; they, and
+//! any backend when spawning fails, run inline through the same type.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{CursorType, Element, ElementId, OutputRotation};
+use kryon_layout::LayoutResult;
+use std::sync::{mpsc, Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+
+use crate::{BackendMemoryStats, CommandRenderer, DirtyRegion, DisplayList, RenderCommand, RenderError, RenderResult, Renderer, Unsupported, WarmUp};
+
+/// What `end_frame` does when the render thread hasn't taken the last frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backpressure {
+    /// Replace it: the screen shows the newest state, some frames are skipped
+    #[default]
+    DropStale,
+    /// Wait until the render thread has taken it
+    Wait,
+}
+
+/// Work for the render thread, a closure run on the backend
+type Job<R> = Box<dyn FnOnce(&mut R) + Send>;
+
+#[derive(Default)]
+struct Frame {
+    clear_color: Vec4,
+    commands: Vec<RenderCommand>,
+    /// The commands' display list, for backends that retain one
+    display_list: Option<DisplayList>,
+}
+
+/// State behind the lock both threads take
+struct Queue<R> {
+    /// The middle buffer: the newest complete frame
+    frame: Frame,
+    /// `frame` hasn't been taken by the render thread yet
+    fresh: bool,
+    jobs: Vec<Job<R>>,
+    shutdown: bool,
+    dropped: u64,
+}
+
+/// What the backend reported after drawing, for the main thread to collect
+#[derive(Default)]
+struct Feedback {
+    unsupported: Vec<Unsupported>,
+    decoded_images: Vec<(String, Vec2)>,
+    /// An animation advanced to a new frame since the main thread last asked
+    animated: bool,
+    /// Display list items rebuilt in the last frame drawn
+    rebuilt_items: usize,
+    memory: BackendMemoryStats,
+    error: Option<String>,
+}
+
+struct Shared<R> {
+    queue: Mutex<Queue<R>>,
+    /// Signalled when a frame or job arrives, and when a frame is taken
+    changed: Condvar,
+    feedback: Mutex<Feedback>,
+}
+
+impl<R> Shared<R> {
+    fn queue(&self) -> MutexGuard<'_, Queue<R>> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn feedback(&self) -> MutexGuard<'_, Feedback> {
+        self.feedback.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+enum Mode<R: CommandRenderer> {
+    Inline(R),
+    Threaded { shared: Arc<Shared<R>>, thread: JoinHandle<Option<R>> },
+    /// Shut down, or the backend was lost
+    Stopped,
+}
+
+pub struct ThreadedRenderer<R: CommandRenderer + Send + 'static> {
+    mode: Mode<R>,
+    /// The back buffer, recorded between `begin_frame` and `end_frame`
+    recording: Frame,
+    size: Vec2,
+    /// The backend's scale as of its last resize
+    scale: f32,
+    backend_name: &'static str,
+    text_baseline: Option<f32>,
+    /// Taken from the backend before it moves to its thread
+    text_measurer: Option<Box<dyn kryon_layout::TextMeasurer>>,
+    needs_full_command_list: bool,
+    skips_unchanged_frames: bool,
+    retains_display_list: bool,
+    supports_shader_effects: bool,
+    supports_eyedropper: bool,
+    backpressure: Backpressure,
+}
+
+impl<R: CommandRenderer + Send + 'static> ThreadedRenderer<R> {
+    /// Moves `backend` to a new render thread
+    pub fn spawn(backend: R) -> RenderResult<Self> {
+        Self::try_spawn(backend).map_err(|(_, e)| e)
+    }
+
+    /// Keeps `backend` on the calling thread; frames are drawn in `end_frame`
+    pub fn inline(mut backend: R) -> Self {
+        let mut renderer = Self::describing(&mut backend);
+        renderer.mode = Mode::Inline(backend);
+        renderer
+    }
+
+    /// `spawn`, or `inline` if the thread can't be started
+    pub fn spawn_or_inline(backend: R) -> Self {
+        Self::try_spawn(backend).unwrap_or_else(|(backend, e)| {
+            eprintln!("[RENDER_THREAD] {}; drawing on the main thread", e);
+            Self::inline(backend)
+        })
+    }
+
+    fn try_spawn(mut backend: R) -> Result<Self, (R, RenderError)> {
+        let mut renderer = Self::describing(&mut backend);
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue { frame: Frame::default(), fresh: false, jobs: Vec::new(), shutdown: false, dropped: 0 }),
+            changed: Condvar::new(),
+            feedback: Mutex::new(Feedback::default()),
+        });
+        // Handed over once the thread runs, so a failed spawn leaves it here
+        let (hand_over, received) = mpsc::channel();
+        let render_side = shared.clone();
+        let spawned = std::thread::Builder::new()
+            .name("kryon-render".to_string())
+            .spawn(move || received.recv().ok().map(|backend| render_loop(backend, render_side)));
+        let thread = match spawned {
+            Ok(thread) => thread,
+            Err(e) => return Err((backend, RenderError::InitializationFailed(format!("Can't start a render thread: {}", e)))),
+        };
+        if let Err(mpsc::SendError(backend)) = hand_over.send(backend) {
+            return Err((backend, RenderError::InitializationFailed("The render thread exited on start".to_string())));
+        }
+        eprintln!("[RENDER_THREAD] Drawing with {} on a render thread", renderer.backend_name);
+        renderer.mode = Mode::Threaded { shared, thread };
+        Ok(renderer)
+    }
+
+    /// A renderer with what the main thread needs to know about `backend`
+    fn describing(backend: &mut R) -> Self {
+        Self {
+            mode: Mode::Stopped,
+            recording: Frame::default(),
+            size: backend.viewport_size(),
+            scale: backend.viewport_scale(),
+            backend_name: backend.backend_name(),
+            text_baseline: backend.text_baseline(),
+            text_measurer: backend.text_measurer(),
+            needs_full_command_list: backend.needs_full_command_list(),
+            skips_unchanged_frames: backend.skips_unchanged_frames(),
+            retains_display_list: backend.retains_display_list(),
+            supports_shader_effects: backend.supports_shader_effects(),
+            supports_eyedropper: backend.supports_eyedropper(),
+            backpressure: Backpressure::default(),
+        }
+    }
+
+    pub fn is_threaded(&self) -> bool {
+        matches!(self.mode, Mode::Threaded { .. })
+    }
+
+    pub fn set_backpressure(&mut self, backpressure: Backpressure) {
+        self.backpressure = backpressure;
+    }
+
+    /// Frames replaced before the render thread started drawing them
+    pub fn dropped_frames(&self) -> u64 {
+        match &self.mode {
+            Mode::Threaded { shared, .. } => shared.queue().dropped,
+            _ => 0,
+        }
+    }
+
+    /// Runs `job` on the backend, on the render thread between frames,
+    /// and waits for its result
+    pub fn with_backend<T: Send + 'static>(&mut self, job: impl FnOnce(&mut R) -> T + Send + 'static) -> RenderResult<T> {
+        match &mut self.mode {
+            Mode::Inline(backend) => Ok(job(backend)),
+            Mode::Threaded { .. } => {
+                let (sender, receiver) = mpsc::channel();
+                self.send(Box::new(move |backend: &mut R| {
+                    let _ = sender.send(job(backend));
+                }));
+                receiver.recv().map_err(|_| stopped())
+            }
+            Mode::Stopped => Err(stopped()),
+        }
+    }
+
+    /// Stops the render thread after the frame it is drawing and gives the
+    /// backend back. `None` if the render thread panicked.
+    pub fn shutdown(mut self) -> Option<R> {
+        self.stop()
+    }
+
+    fn stop(&mut self) -> Option<R> {
+        match std::mem::replace(&mut self.mode, Mode::Stopped) {
+            Mode::Inline(backend) => Some(backend),
+            Mode::Threaded { shared, thread } => {
+                shared.queue().shutdown = true;
+                shared.changed.notify_all();
+                let backend = thread.join().ok().flatten();
+                if backend.is_none() {
+                    eprintln!("[RENDER_THREAD] The render thread panicked");
+                }
+                backend
+            }
+            Mode::Stopped => None,
+        }
+    }
+
+    fn send(&self, job: Job<R>) {
+        if let Mode::Threaded { shared, .. } = &self.mode {
+            shared.queue().jobs.push(job);
+            shared.changed.notify_all();
+        }
+    }
+
+    /// Hands the recorded frame to the render thread
+    fn publish(&mut self, shared: &Shared<R>) -> RenderResult<()> {
+        if let Some(error) = shared.feedback().error.take() {
+            return Err(RenderError::RenderFailed(error));
+        }
+        let mut queue = shared.queue();
+        if self.backpressure == Backpressure::Wait {
+            while queue.fresh && !queue.shutdown {
+                queue = shared.changed.wait(queue).unwrap_or_else(|e| e.into_inner());
+            }
+        }
+        if queue.shutdown {
+            return Err(stopped());
+        }
+        if queue.fresh {
+            queue.dropped += 1;
+        }
+        // The stale or already drawn list comes back to be recorded into
+        std::mem::swap(&mut queue.frame, &mut self.recording);
+        queue.fresh = true;
+        drop(queue);
+        shared.changed.notify_all();
+        Ok(())
+    }
+}
+
+fn stopped() -> RenderError {
+    RenderError::RenderFailed("The render thread has stopped".to_string())
+}
+
+impl<R: CommandRenderer + Send + 'static> Drop for ThreadedRenderer<R> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Draws each frame as it arrives and runs jobs between frames, until shut down
+fn render_loop<R: CommandRenderer>(mut backend: R, shared: Arc<Shared<R>>) -> R {
+    let mut front = Frame::default();
+    loop {
+        let (jobs, draw, shutdown) = {
+            let mut queue = shared.queue();
+            while !queue.fresh && queue.jobs.is_empty() && !queue.shutdown {
+                queue = shared.changed.wait(queue).unwrap_or_else(|e| e.into_inner());
+            }
+            let draw = std::mem::take(&mut queue.fresh);
+            if draw {
+                std::mem::swap(&mut front, &mut queue.frame);
+            }
+            (std::mem::take(&mut queue.jobs), draw, queue.shutdown)
+        };
+        // A main thread waiting under `Backpressure::Wait` can go on
+        shared.changed.notify_all();
+
+        for job in jobs {
+            job(&mut backend);
+        }
+        if shutdown {
+            return backend;
+        }
+        if draw {
+            let drawn = backend.begin_frame(front.clear_color)
+                .and_then(|mut context| {
+                    let rebuilt = match &front.display_list {
+                        Some(list) => backend.execute_display_list(&mut context, &front.commands, list)?,
+                        None => {
+                            backend.execute_commands(&mut context, &front.commands)?;
+                            0
+                        }
+                    };
+                    backend.end_frame(context)?;
+                    Ok(rebuilt)
+                });
+            let mut feedback = shared.feedback();
+            match drawn {
+                Ok(rebuilt) => feedback.rebuilt_items = rebuilt,
+                Err(e) => {
+                    eprintln!("[RENDER_THREAD] Frame failed: {}", e);
+                    feedback.error = Some(e.to_string());
+                }
+            }
+            // Commands are indexed per frame; these can't be traced to elements
+            feedback.unsupported.extend(backend.take_unsupported().into_iter().map(|unsupported| Unsupported::new(usize::MAX, unsupported.feature)));
+            feedback.decoded_images.extend(backend.take_decoded_images());
+            feedback.memory = backend.memory_stats();
+        }
+    }
+}
+
+impl<R: CommandRenderer + Send + 'static> Renderer for ThreadedRenderer<R> {
+    type Surface = R;
+    /// The backend's context when drawing inline
+    type Context = Option<R::Context>;
+
+    fn initialize(backend: R) -> RenderResult<Self> {
+        Self::spawn(backend)
+    }
+
+    fn begin_frame(&mut self, clear_color: Vec4) -> RenderResult<Self::Context> {
+        match &mut self.mode {
+            Mode::Inline(backend) => Ok(Some(backend.begin_frame(clear_color)?)),
+            Mode::Threaded { .. } => {
+                self.recording.clear_color = clear_color;
+                self.recording.commands.clear();
+                self.recording.display_list = None;
+                Ok(None)
+            }
+            Mode::Stopped => Err(stopped()),
+        }
+    }
+
+    fn end_frame(&mut self, context: Self::Context) -> RenderResult<()> {
+        match &mut self.mode {
+            Mode::Inline(backend) => match context {
+                Some(context) => backend.end_frame(context),
+                None => Ok(()),
+            },
+            Mode::Threaded { shared, .. } => {
+                let shared = shared.clone();
+                self.publish(&shared)
+            }
+            Mode::Stopped => Err(stopped()),
+        }
+    }
+
+    fn render_element(&mut self, context: &mut Self::Context, element: &Element, layout: &LayoutResult, element_id: ElementId) -> RenderResult<()> {
+        match (&mut self.mode, context) {
+            (Mode::Inline(backend), Some(context)) => backend.render_element(context, element, layout, element_id),
+            _ => Ok(()),
+        }
+    }
+
+    fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
+        self.size = new_size;
+        if matches!(self.mode, Mode::Stopped) {
+            return Ok(());
+        }
+        // Waits for the render thread: the scale can change with the size,
+        // and layout needs the new one
+        let (resized, scale) = self.with_backend(move |backend: &mut R| (backend.resize(new_size), backend.viewport_scale()))?;
+        self.scale = scale;
+        resized
+    }
+
+    fn viewport_size(&self) -> Vec2 {
+        self.size
+    }
+
+    fn viewport_scale(&self) -> f32 {
+        self.scale
+    }
+}
+
+impl<R: CommandRenderer + Send + 'static> CommandRenderer for ThreadedRenderer<R> {
+    fn execute_commands(&mut self, context: &mut Self::Context, commands: &[RenderCommand]) -> RenderResult<()> {
+        match (&mut self.mode, context) {
+            (Mode::Inline(backend), Some(context)) => backend.execute_commands(context, commands),
+            _ => {
+                self.recording.commands.extend_from_slice(commands);
+                // The list no longer covers every command
+                self.recording.display_list = None;
+                Ok(())
+            }
+        }
+    }
+
+    fn set_cursor(&mut self, cursor_type: CursorType) {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.set_cursor(cursor_type),
+            _ => self.send(Box::new(move |backend: &mut R| backend.set_cursor(cursor_type))),
+        }
+    }
+
+    fn memory_stats(&self) -> BackendMemoryStats {
+        match &self.mode {
+            Mode::Inline(backend) => backend.memory_stats(),
+            Mode::Threaded { shared, .. } => shared.feedback().memory.clone(),
+            Mode::Stopped => BackendMemoryStats::default(),
+        }
+    }
+
+    fn set_output_rotation(&mut self, rotation: OutputRotation) -> bool {
+        self.with_backend(move |backend| backend.set_output_rotation(rotation)).unwrap_or(false)
+    }
+
+    fn backend_name(&self) -> &'static str {
+        self.backend_name
+    }
+
+    fn take_unsupported(&mut self) -> Vec<Unsupported> {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.take_unsupported(),
+            Mode::Threaded { shared, .. } => std::mem::take(&mut shared.feedback().unsupported),
+            Mode::Stopped => Vec::new(),
+        }
+    }
+
+    fn take_decoded_images(&mut self) -> Vec<(String, Vec2)> {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.take_decoded_images(),
+            Mode::Threaded { shared, .. } => std::mem::take(&mut shared.feedback().decoded_images),
+            Mode::Stopped => Vec::new(),
+        }
+    }
+
+    /// Advanced on the render thread between frames, so a new frame is
+    /// reported on the next call
+    fn advance_animations(&mut self, delta: std::time::Duration) -> bool {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.advance_animations(delta),
+            Mode::Threaded { shared, .. } => {
+                let render_side = shared.clone();
+                let animated = std::mem::take(&mut shared.feedback().animated);
+                self.send(Box::new(move |backend: &mut R| {
+                    if backend.advance_animations(delta) {
+                        render_side.feedback().animated = true;
+                    }
+                }));
+                animated
+            }
+            Mode::Stopped => false,
+        }
+    }
+
+    fn text_baseline(&mut self) -> Option<f32> {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.text_baseline(),
+            _ => self.text_baseline,
+        }
+    }
+
+    fn text_measurer(&mut self) -> Option<Box<dyn kryon_layout::TextMeasurer>> {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.text_measurer(),
+            _ => self.text_measurer.take(),
+        }
+    }
+
+    fn warm_up(&mut self, work: &WarmUp) -> bool {
+        match &mut self.mode {
+            Mode::Inline(backend) => backend.warm_up(work),
+            _ => {
+                // Waits for the render thread, so the runtime's idle budget covers the work
+                let work = work.clone();
+                self.with_backend(move |backend| backend.warm_up(&work)).unwrap_or(false)
+            }
+        }
+    }
+
+    fn clipboard_text(&mut self) -> Option<String> {
+        self.with_backend(|backend| backend.clipboard_text()).ok().flatten()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> bool {
+        let text = text.to_string();
+        self.with_backend(move |backend| backend.set_clipboard_text(&text)).unwrap_or(false)
+    }
+
+    fn needs_full_command_list(&self) -> bool {
+        self.needs_full_command_list
+    }
+
+    fn supports_shader_effects(&self) -> bool {
+        self.supports_shader_effects
+    }
+
+    fn supports_eyedropper(&self) -> bool {
+        self.supports_eyedropper
+    }
+
+    fn sample_screen_color(&mut self, position: Vec2) -> Option<Vec4> {
+        self.with_backend(move |backend| backend.sample_screen_color(position)).ok().flatten()
+    }
+
+    fn skips_unchanged_frames(&self) -> bool {
+        self.skips_unchanged_frames
+    }
+
+    /// Only inline; the render thread is always sent whole frames
+    fn execute_dirty(&mut self, context: &mut Self::Context, commands: &[RenderCommand], dirty: &DirtyRegion) -> RenderResult<bool> {
+        match (&mut self.mode, context) {
+            (Mode::Inline(backend), Some(context)) => backend.execute_dirty(context, commands, dirty),
+            _ => Ok(false),
+        }
+    }
+
+    fn retains_display_list(&self) -> bool {
+        self.retains_display_list
+    }
+
+    /// On the render thread the items are rebuilt a frame later, so the
+    /// count returned is the last drawn frame's
+    fn execute_display_list(&mut self, context: &mut Self::Context, commands: &[RenderCommand], list: &DisplayList) -> RenderResult<usize> {
+        match (&mut self.mode, context) {
+            (Mode::Inline(backend), Some(context)) => backend.execute_display_list(context, commands, list),
+            (Mode::Threaded { shared, .. }, _) => {
+                self.recording.display_list = self.recording.commands.is_empty().then(|| list.clone());
+                self.recording.commands.extend_from_slice(commands);
+                Ok(shared.feedback().rebuilt_items)
+            }
+            _ => {
+                self.recording.commands.extend_from_slice(commands);
+                Ok(0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::ThreadId;
+
+    /// Remembers how many commands each drawn frame had, and on which thread
+    #[derive(Default)]
+    struct Counting {
+        size: Vec2,
+        frames: Arc<Mutex<Vec<(usize, ThreadId)>>>,
+        pending: usize,
+    }
+
+    impl Renderer for Counting {
+        type Surface = ();
+        type Context = ();
+
+        fn initialize(_surface: ()) -> RenderResult<Self> {
+            Ok(Self::default())
+        }
+
+        fn begin_frame(&mut self, _clear_color: Vec4) -> RenderResult<()> {
+            self.pending = 0;
+            Ok(())
+        }
+
+        fn end_frame(&mut self, _context: ()) -> RenderResult<()> {
+            self.frames.lock().unwrap().push((self.pending, std::thread::current().id()));
+            Ok(())
+        }
+
+        fn render_element(&mut self, _context: &mut (), _element: &Element, _layout: &LayoutResult, _element_id: ElementId) -> RenderResult<()> {
+            Ok(())
+        }
+
+        fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
+            self.size = new_size;
+            Ok(())
+        }
+
+        fn viewport_size(&self) -> Vec2 {
+            self.size
+        }
+    }
+
+    impl CommandRenderer for Counting {
+        fn execute_commands(&mut self, _context: &mut (), commands: &[RenderCommand]) -> RenderResult<()> {
+            self.pending += commands.len();
+            Ok(())
+        }
+    }
+
+    fn draw(renderer: &mut ThreadedRenderer<Counting>, commands: usize) {
+        let mut context = renderer.begin_frame(Vec4::ONE).unwrap();
+        renderer.execute_commands(&mut context, &vec![RenderCommand::ClearClip; commands]).unwrap();
+        renderer.end_frame(context).unwrap();
+    }
+
+    #[test]
+    fn frames_are_drawn_on_the_render_thread_in_order() {
+        let backend = Counting::default();
+        let frames = backend.frames.clone();
+        let mut renderer = ThreadedRenderer::spawn(backend).unwrap();
+        renderer.set_backpressure(Backpressure::Wait);
+        for commands in 1..=5 {
+            draw(&mut renderer, commands);
+        }
+        renderer.resize(Vec2::new(320.0, 200.0)).unwrap();
+        assert_eq!(renderer.viewport_size(), Vec2::new(320.0, 200.0));
+
+        let backend = renderer.shutdown().unwrap();
+        assert_eq!(backend.size, Vec2::new(320.0, 200.0));
+        // Waiting drops nothing, though the last frame may still be pending at shutdown
+        let frames = frames.lock().unwrap();
+        let counts: Vec<usize> = frames.iter().map(|&(commands, _)| commands).collect();
+        assert!(counts.len() >= 4 && counts.iter().copied().eq(1..=counts.len()));
+        assert!(frames.iter().all(|&(_, thread)| thread != std::thread::current().id()));
+    }
+
+    #[test]
+    fn a_busy_render_thread_gets_the_newest_frame() {
+        let backend = Counting::default();
+        let frames = backend.frames.clone();
+        let mut renderer = ThreadedRenderer::spawn(backend).unwrap();
+        // Holding the backend keeps the render thread from taking frames
+        let (started, blocked) = mpsc::channel();
+        let (release, wait) = mpsc::channel::<()>();
+        renderer.send(Box::new(move |_backend: &mut Counting| {
+            started.send(()).unwrap();
+            wait.recv().unwrap();
+        }));
+        blocked.recv().unwrap();
+        for commands in 1..=3 {
+            draw(&mut renderer, commands);
+        }
+        release.send(()).unwrap();
+        assert_eq!(renderer.dropped_frames(), 2);
+        let started = std::time::Instant::now();
+        while frames.lock().unwrap().is_empty() && started.elapsed() < std::time::Duration::from_secs(10) {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(frames.lock().unwrap().iter().map(|&(commands, _)| commands).collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn inline_draws_on_the_calling_thread() {
+        let backend = Counting::default();
+        let frames = backend.frames.clone();
+        let mut renderer = ThreadedRenderer::inline(backend);
+        draw(&mut renderer, 2);
+        assert!(!renderer.is_threaded());
+        assert_eq!(*frames.lock().unwrap(), vec![(2, std::thread::current().id())]);
+    }
+}
@@ -0,0 +1,92 @@
+// crates/kryon-render/src/warnings.rs
+//! Features a backend can't draw, reported instead of silently dropped.
+//!
+//! Backends note each command aspect they skip as an [`Unsupported`] with
+//! the command's index in the batch. `ElementRenderer` maps the index back
+//! to the element that produced the command and keeps one
+//! [`RendererWarning`] per (feature, element), so a shadow repeated every
+//! frame is reported once. Feature names match `--validate`'s.
+
+use crate::RenderCommand;
+use kryon_core::ElementId;
+
+/// A skipped aspect of the command at `command` in the last batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsupported {
+    pub command: usize,
+    pub feature: &'static str,
+}
+
+impl Unsupported {
+    pub fn new(command: usize, feature: &'static str) -> Self {
+        Self { command, feature }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RendererWarning {
+    pub backend: &'static str,
+    pub feature: &'static str,
+    /// `None` for commands not drawn for an element, such as the overlay
+    pub element: Option<ElementId>,
+}
+
+impl RendererWarning {
+    pub fn message(&self) -> String {
+        format!("{} are not supported by the {} backend", self.feature, self.backend)
+    }
+}
+
+/// The feature a whole command stands for, for backends that skip it
+/// entirely. Markers that draw nothing (`EndCanvas`, `ClearClip`, ...) have none.
+pub fn command_feature(command: &RenderCommand) -> Option<&'static str> {
+    match command {
+        RenderCommand::DrawRect { .. } => Some("rectangles"),
+        RenderCommand::DrawText { .. } => Some("text"),
+        RenderCommand::DrawRichText { .. } => Some("rich text spans"),
+        RenderCommand::DrawImage { .. } => Some("images"),
+        RenderCommand::DrawTiledImage { .. } => Some("background images"),
+        RenderCommand::DrawGradientRect { .. } => Some("gradients"),
+        RenderCommand::DrawShaderEffect { .. } => Some("shader effects"),
+        RenderCommand::DrawParticles { .. } => Some("particles"),
+        RenderCommand::SetClip { .. } => Some("overflow clipping"),
+        RenderCommand::NativeRendererView { .. } => Some("native renderer views"),
+        RenderCommand::DrawTextInput { .. } => Some("input elements"),
+        RenderCommand::DrawCheckbox { .. } | RenderCommand::DrawSlider { .. } => Some("form controls"),
+        RenderCommand::DrawScrollbar { .. } => Some("scrollbars"),
+        RenderCommand::BeginCanvas { .. }
+        | RenderCommand::DrawCanvasLine { .. }
+        | RenderCommand::DrawCanvasRect { .. }
+        | RenderCommand::DrawCanvasCircle { .. }
+        | RenderCommand::DrawCanvasText { .. }
+        | RenderCommand::DrawCanvasEllipse { .. }
+        | RenderCommand::DrawCanvasPolygon { .. }
+        | RenderCommand::DrawCanvasPath { .. }
+        | RenderCommand::DrawCanvasImage { .. } => Some("canvas"),
+        RenderCommand::BeginWasmView { .. } | RenderCommand::ExecuteWasmFunction { .. } => Some("wasm views"),
+        RenderCommand::ClearClip | RenderCommand::SetCanvasSize(_) | RenderCommand::EndCanvas | RenderCommand::EndWasmView => None,
+    }
+}
+
+/// Families every backend maps to its built-in font.
+pub const GENERIC_FONTS: &[&str] = &["", "default", "sans-serif", "serif", "monospace", "system-ui"];
+
+pub fn is_generic_font(family: &str) -> bool {
+    GENERIC_FONTS.contains(&family)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_skipped_commands() {
+        let clip = RenderCommand::SetClip { position: glam::Vec2::ZERO, size: glam::Vec2::ONE };
+        assert_eq!(command_feature(&clip), Some("overflow clipping"));
+        assert_eq!(command_feature(&RenderCommand::ClearClip), None);
+
+        let warning = RendererWarning { backend: "ratatui", feature: "shadows", element: Some(3) };
+        assert_eq!(warning.message(), "shadows are not supported by the ratatui backend");
+        assert!(is_generic_font("sans-serif") && !is_generic_font("Inter"));
+    }
+}
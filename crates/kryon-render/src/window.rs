@@ -0,0 +1,166 @@
+// crates/kryon-render/src/window.rs
+//! Monitor information and window placement for the windowed backends.
+//!
+//! Backends enumerate their monitors into `MonitorInfo`; the binaries turn
+//! `--monitor`/`--position`/`--maximized` into a `WindowPlacement` and keep
+//! the last window geometry in a small `key=value` file between runs.
+
+use glam::Vec2;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub name: String,
+    /// Top-left corner on the virtual desktop, in pixels
+    pub position: Vec2,
+    /// Resolution in pixels
+    pub size: Vec2,
+    pub scale_factor: f32,
+}
+
+impl MonitorInfo {
+    pub fn dpi(&self) -> f32 {
+        96.0 * self.scale_factor
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        let max = self.position + self.size;
+        point.x >= self.position.x && point.y >= self.position.y && point.x < max.x && point.y < max.y
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowPlacement {
+    /// Index into the backend's monitor list
+    pub monitor: Option<usize>,
+    /// Window top-left, relative to the monitor when one is chosen
+    pub position: Option<Vec2>,
+    pub maximized: bool,
+}
+
+impl WindowPlacement {
+    /// Parses `--position X,Y`.
+    pub fn parse_position(value: &str) -> Option<Vec2> {
+        let (x, y) = value.split_once(',')?;
+        Some(Vec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+    }
+
+    /// Falls back to where the window was last closed when neither a
+    /// monitor nor a position was asked for and that spot is still on screen.
+    pub fn or_saved(mut self, saved: Option<&WindowGeometry>, monitors: &[MonitorInfo]) -> Self {
+        if let Some(saved) = saved.filter(|saved| saved.is_on_screen(monitors)) {
+            if self.monitor.is_none() && self.position.is_none() {
+                self.position = Some(saved.position);
+                self.maximized |= saved.maximized;
+            }
+        }
+        self
+    }
+
+    /// Desktop position for the top-left of a window of `window_size`, or
+    /// `None` to leave it to the window manager. A monitor without a position
+    /// centers the window on it; an unknown monitor index is ignored.
+    pub fn resolve(&self, monitors: &[MonitorInfo], window_size: Vec2) -> Option<Vec2> {
+        let monitor = self.monitor.and_then(|index| monitors.get(index));
+        match (monitor, self.position) {
+            (Some(monitor), Some(position)) => Some(monitor.position + position),
+            (Some(monitor), None) => Some(monitor.position + ((monitor.size - window_size) / 2.0).max(Vec2::ZERO)),
+            (None, position) => position,
+        }
+    }
+}
+
+/// Window position and size saved when the app closes (`--window-state`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub maximized: bool,
+}
+
+impl WindowGeometry {
+    /// The saved geometry, or `None` if the file is missing or unreadable.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        Self::parse(&std::fs::read_to_string(path).ok()?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.serialize())
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut values = std::collections::HashMap::new();
+        for line in text.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                values.insert(key.trim(), value.trim());
+            }
+        }
+        let number = |key: &str| values.get(key)?.parse::<f32>().ok().filter(|v| v.is_finite());
+        let size = Vec2::new(number("width")?, number("height")?);
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            position: Vec2::new(number("x")?, number("y")?),
+            size,
+            maximized: values.get("maximized").is_some_and(|v| *v == "true"),
+        })
+    }
+
+    pub fn serialize(&self) -> String {
+        format!(
+            "x={}\ny={}\nwidth={}\nheight={}\nmaximized={}\n",
+            self.position.x, self.position.y, self.size.x, self.size.y, self.maximized
+        )
+    }
+
+    /// Whether the window's top-left still lands on a connected monitor, so
+    /// a window saved on an unplugged display isn't restored offscreen.
+    /// Always true when the backend can't list monitors.
+    pub fn is_on_screen(&self, monitors: &[MonitorInfo]) -> bool {
+        monitors.is_empty() || monitors.iter().any(|monitor| monitor.contains(self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitors() -> Vec<MonitorInfo> {
+        vec![
+            MonitorInfo { name: "DP-1".to_string(), position: Vec2::ZERO, size: Vec2::new(1920.0, 1080.0), scale_factor: 1.0 },
+            MonitorInfo { name: "HDMI-1".to_string(), position: Vec2::new(1920.0, 0.0), size: Vec2::new(1280.0, 720.0), scale_factor: 2.0 },
+        ]
+    }
+
+    #[test]
+    fn placement_is_relative_to_the_chosen_monitor() {
+        let window = Vec2::new(800.0, 600.0);
+        let on_second = WindowPlacement { monitor: Some(1), ..Default::default() };
+        assert_eq!(on_second.resolve(&monitors(), window), Some(Vec2::new(2160.0, 60.0)));
+
+        let offset = WindowPlacement { monitor: Some(1), position: Some(Vec2::new(10.0, 20.0)), maximized: false };
+        assert_eq!(offset.resolve(&monitors(), window), Some(Vec2::new(1930.0, 20.0)));
+
+        let saved = WindowGeometry { position: Vec2::new(100.0, 50.0), size: window, maximized: true };
+        let restored = WindowPlacement::default().or_saved(Some(&saved), &monitors());
+        assert_eq!((restored.position, restored.maximized), (Some(saved.position), true));
+        assert_eq!(on_second.clone().or_saved(Some(&saved), &monitors()), on_second);
+
+        let unknown = WindowPlacement { monitor: Some(5), ..Default::default() };
+        assert_eq!(unknown.resolve(&monitors(), window), None);
+        assert_eq!(WindowPlacement::parse_position(" 40, -8"), Some(Vec2::new(40.0, -8.0)));
+        assert_eq!(monitors()[1].dpi(), 192.0);
+    }
+
+    #[test]
+    fn geometry_round_trips_and_rejects_offscreen_positions() {
+        let geometry = WindowGeometry { position: Vec2::new(2000.0, 100.0), size: Vec2::new(640.0, 480.0), maximized: true };
+        assert_eq!(WindowGeometry::parse(&geometry.serialize()), Some(geometry));
+        assert!(geometry.is_on_screen(&monitors()));
+        assert!(!geometry.is_on_screen(&monitors()[..1]));
+
+        assert_eq!(WindowGeometry::parse("x=1\ny=2\nwidth=0\nheight=10"), None);
+        assert_eq!(WindowGeometry::parse("garbage"), None);
+    }
+}
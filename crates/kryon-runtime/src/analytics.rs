@@ -0,0 +1,438 @@
+// crates/kryon-runtime/src/analytics.rs
+//! Opt-in interaction analytics for kiosk deployments: heatmaps from where
+//! elements are clicked, funnels from the screens and fields people pass
+//! through.
+//!
+//! Nothing is recorded unless the host attaches an [`AnalyticsRecorder`].
+//! Events name the element by its id and never carry what it shows or what
+//! was typed: no text, no field values, no key presses. Password inputs
+//! and everything inside an element with `analytics: false` are left out
+//! entirely, and scripts can withdraw consent with
+//! `kryon.analytics.set_consent(false)`, which also discards what was kept.
+//!
+//! Events wait in a ring buffer, dropping the oldest when it's full, and
+//! are handed to the uploader in batches. A batch the uploader fails to
+//! send stays in the buffer for the next attempt.
+
+use crate::script::engine_trait::ScriptValue;
+use glam::Vec2;
+use kryon_core::{Element, ElementId, PropertyValue};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Events kept before the oldest are dropped
+pub const DEFAULT_ANALYTICS_CAPACITY: usize = 1000;
+/// Events handed to the uploader at once
+pub const DEFAULT_BATCH_SIZE: usize = 50;
+/// Longest an event waits for a batch to fill before it's uploaded anyway
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Custom event properties longer than this are cut, so scripts can't
+/// smuggle whole documents into the log
+const MAX_PROPERTY_LENGTH: usize = 200;
+
+/// Sends a batch of events somewhere, returning whether it got there.
+pub type Uploader = Box<dyn FnMut(&[AnalyticsEvent]) -> bool + Send>;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnalyticsEvent {
+    /// `click`, `focus`, `blur`, `change`, `submit`, `invalid`, or the name
+    /// of a custom event
+    pub kind: String,
+    /// Set by `kryon.analytics.track`
+    pub custom: bool,
+    /// The element's id; elements without one are recorded anonymously
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element: Option<String>,
+    /// The screen or route set by `kryon.analytics.set_screen`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Where a click landed, in logical pixels, for heatmaps
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<[f32; 2]>,
+    /// Anonymous id of this run of the app, for funnels
+    pub session: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, serde_json::Value>,
+}
+
+/// Requests queued by `kryon.analytics.*`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalyticsCommand {
+    Track { name: String, properties: BTreeMap<String, serde_json::Value> },
+    Screen(Option<String>),
+    Consent(bool),
+}
+
+impl AnalyticsCommand {
+    /// Parses `{op, ...}` as queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+        let name = match fields.get("name") {
+            Some(ScriptValue::String(name)) if !name.is_empty() => Some(name.clone()),
+            _ => None,
+        };
+        match fields.get("op")? {
+            ScriptValue::String(op) if op == "track" => Some(Self::Track {
+                name: name?,
+                properties: match fields.get("properties") {
+                    Some(ScriptValue::Object(properties)) => scalar_properties(properties),
+                    _ => BTreeMap::new(),
+                },
+            }),
+            ScriptValue::String(op) if op == "screen" => Some(Self::Screen(name)),
+            ScriptValue::String(op) if op == "consent" => Some(Self::Consent(fields.get("granted")?.to_bool())),
+            _ => None,
+        }
+    }
+}
+
+/// Keeps numbers, booleans and short strings; tables and functions are
+/// dropped.
+fn scalar_properties(properties: &HashMap<String, ScriptValue>) -> BTreeMap<String, serde_json::Value> {
+    properties.iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                ScriptValue::Boolean(b) => serde_json::Value::from(*b),
+                ScriptValue::Integer(i) => serde_json::Value::from(*i),
+                ScriptValue::Number(n) => serde_json::Value::from(*n),
+                ScriptValue::String(s) => serde_json::Value::from(s.chars().take(MAX_PROPERTY_LENGTH).collect::<String>()),
+                _ => return None,
+            };
+            Some((key.clone(), value))
+        })
+        .collect()
+}
+
+/// Whether interactions with the element may be recorded: not a password
+/// input, and not inside an element with `analytics: false`.
+pub fn is_recordable(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> bool {
+    let mut current = Some(element_id);
+    while let Some(id) = current {
+        let Some(element) = elements.get(&id) else {
+            break;
+        };
+        if kryon_render::text_input::is_password(element) {
+            return false;
+        }
+        match element.custom_properties.get("analytics") {
+            Some(PropertyValue::Bool(false)) => return false,
+            Some(PropertyValue::String(value)) if value == "false" || value == "off" => return false,
+            _ => {}
+        }
+        current = element.parent;
+    }
+    true
+}
+
+pub struct AnalyticsRecorder {
+    events: VecDeque<AnalyticsEvent>,
+    capacity: usize,
+    batch_size: usize,
+    flush_interval: Duration,
+    since_flush: Duration,
+    uploader: Option<Uploader>,
+    screen: Option<String>,
+    consent: bool,
+    session: String,
+    dropped: u64,
+}
+
+impl AnalyticsRecorder {
+    /// A recorder keeping up to `capacity` events
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity.min(DEFAULT_ANALYTICS_CAPACITY)),
+            capacity: capacity.max(1),
+            batch_size: DEFAULT_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            since_flush: Duration::ZERO,
+            uploader: None,
+            screen: None,
+            consent: true,
+            session: session_id(),
+            dropped: 0,
+        }
+    }
+
+    /// Uploads once `batch_size` events are waiting, or every
+    /// `flush_interval` whatever is waiting.
+    pub fn with_batching(mut self, batch_size: usize, flush_interval: Duration) -> Self {
+        self.batch_size = batch_size.max(1);
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    pub fn with_uploader(mut self, uploader: impl FnMut(&[AnalyticsEvent]) -> bool + Send + 'static) -> Self {
+        self.uploader = Some(Box::new(uploader));
+        self
+    }
+
+    /// Events waiting to be uploaded, oldest first
+    pub fn events(&self) -> impl Iterator<Item = &AnalyticsEvent> {
+        self.events.iter()
+    }
+
+    /// Takes the waiting events, for hosts that upload on their own schedule
+    pub fn take_events(&mut self) -> Vec<AnalyticsEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Events dropped because the buffer was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn screen(&self) -> Option<&str> {
+        self.screen.as_deref()
+    }
+
+    pub fn has_consent(&self) -> bool {
+        self.consent
+    }
+
+    /// Records an interaction with an element. `position` is only kept for
+    /// clicks.
+    pub fn record(&mut self, kind: &str, element: Option<&str>, position: Option<Vec2>) {
+        self.push(kind, false, element, position, BTreeMap::new());
+    }
+
+    /// Records a custom event from a script
+    pub fn track(&mut self, name: &str, properties: BTreeMap<String, serde_json::Value>) {
+        self.push(name, true, None, None, properties);
+    }
+
+    fn push(
+        &mut self,
+        kind: &str,
+        custom: bool,
+        element: Option<&str>,
+        position: Option<Vec2>,
+        properties: BTreeMap<String, serde_json::Value>,
+    ) {
+        if !self.consent {
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(AnalyticsEvent {
+            kind: kind.to_string(),
+            custom,
+            element: element.filter(|id| !id.is_empty()).map(str::to_string),
+            screen: self.screen.clone(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_millis() as u64),
+            position: position.map(|position| [position.x, position.y]),
+            session: self.session.clone(),
+            properties,
+        });
+    }
+
+    pub fn apply(&mut self, command: AnalyticsCommand) {
+        match command {
+            AnalyticsCommand::Track { name, properties } => self.track(&name, properties),
+            AnalyticsCommand::Screen(screen) => self.screen = screen,
+            AnalyticsCommand::Consent(granted) => {
+                self.consent = granted;
+                if !granted {
+                    self.events.clear();
+                }
+            }
+        }
+    }
+
+    /// Uploads full batches as they fill, and whatever is waiting once the
+    /// flush interval has passed.
+    pub fn update(&mut self, delta_time: Duration) {
+        self.since_flush += delta_time;
+        if self.since_flush >= self.flush_interval {
+            self.flush();
+        } else if self.events.len() >= self.batch_size {
+            self.upload(self.batch_size);
+        }
+    }
+
+    /// Uploads every waiting event, a batch at a time.
+    pub fn flush(&mut self) {
+        self.since_flush = Duration::ZERO;
+        self.upload(1);
+    }
+
+    /// Uploads batches while at least `minimum` events wait, stopping at the
+    /// first batch that fails
+    fn upload(&mut self, minimum: usize) {
+        let Some(uploader) = self.uploader.as_mut() else {
+            return;
+        };
+        while self.events.len() >= minimum && !self.events.is_empty() {
+            let count = self.batch_size.min(self.events.len());
+            let batch: Vec<AnalyticsEvent> = self.events.iter().take(count).cloned().collect();
+            if !uploader(&batch) {
+                eprintln!("[ANALYTICS] Upload of {} event(s) failed; keeping them for the next attempt", count);
+                return;
+            }
+            self.events.drain(..count);
+        }
+    }
+}
+
+impl Default for AnalyticsRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_ANALYTICS_CAPACITY)
+    }
+}
+
+impl Drop for AnalyticsRecorder {
+    fn drop(&mut self) {
+        // Last chance for events recorded since the previous batch
+        self.flush();
+    }
+}
+
+/// One JSON object per line
+pub fn json_lines(events: &[AnalyticsEvent]) -> String {
+    events.iter()
+        .filter_map(|event| serde_json::to_string(event).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// An uploader appending batches to a JSON Lines file
+pub fn file_uploader(path: impl Into<PathBuf>) -> impl FnMut(&[AnalyticsEvent]) -> bool + Send + 'static {
+    let path = path.into();
+    move |events| {
+        let result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(json_lines(events).as_bytes()));
+        if let Err(e) = &result {
+            eprintln!("[ANALYTICS] Failed to write {}: {}", path.display(), e);
+        }
+        result.is_ok()
+    }
+}
+
+/// An id for this run that says nothing about the device or person
+fn session_id() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_nanos()));
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn command(fields: Vec<(&str, ScriptValue)>) -> ScriptValue {
+        ScriptValue::Object(fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    #[test]
+    fn ring_buffer_keeps_the_newest_events() {
+        let mut recorder = AnalyticsRecorder::new(2);
+        recorder.record("click", Some("start"), Some(Vec2::new(10.0, 20.0)));
+        recorder.record("focus", Some(""), None);
+        recorder.record("submit", Some("form"), None);
+
+        let kinds: Vec<&str> = recorder.events().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, ["focus", "submit"]);
+        assert_eq!(recorder.dropped(), 1);
+        assert_eq!(recorder.events().next().unwrap().element, None, "elements without an id stay anonymous");
+    }
+
+    #[test]
+    fn uploads_in_batches_and_keeps_failed_ones() {
+        let uploaded = Arc::new(Mutex::new(Vec::new()));
+        let online = Arc::new(Mutex::new(false));
+        let mut recorder = AnalyticsRecorder::default()
+            .with_batching(2, Duration::from_secs(10))
+            .with_uploader({
+                let (uploaded, online) = (uploaded.clone(), online.clone());
+                move |batch| {
+                    if !*online.lock().unwrap() {
+                        return false;
+                    }
+                    uploaded.lock().unwrap().push(batch.len());
+                    true
+                }
+            });
+
+        recorder.record("click", Some("a"), None);
+        recorder.update(Duration::from_secs(1));
+        assert_eq!(recorder.events().count(), 1, "waits for a full batch");
+
+        recorder.record("click", Some("b"), None);
+        recorder.update(Duration::from_secs(1));
+        assert_eq!(recorder.events().count(), 2, "a failed upload keeps its events");
+
+        *online.lock().unwrap() = true;
+        recorder.record("click", Some("c"), None);
+        recorder.update(Duration::from_secs(1));
+        assert_eq!(*uploaded.lock().unwrap(), [2]);
+        assert_eq!(recorder.events().count(), 1);
+
+        recorder.update(Duration::from_secs(10));
+        assert_eq!(*uploaded.lock().unwrap(), [2, 1], "the interval flushes a partial batch");
+    }
+
+    #[test]
+    fn script_commands_tag_screens_and_withdraw_consent() {
+        let mut recorder = AnalyticsRecorder::default();
+        let screen = command(vec![("op", "screen".into()), ("name", "checkout".into())]);
+        recorder.apply(AnalyticsCommand::from_script_value(&screen).unwrap());
+
+        let properties = ScriptValue::Object([
+            ("step".to_string(), ScriptValue::Integer(2)),
+            ("note".to_string(), ScriptValue::String("x".repeat(500))),
+            ("nested".to_string(), ScriptValue::Array(vec![])),
+        ].into_iter().collect());
+        let track = command(vec![("op", "track".into()), ("name", "coupon_applied".into()), ("properties", properties)]);
+        recorder.apply(AnalyticsCommand::from_script_value(&track).unwrap());
+
+        let event = recorder.events().next().unwrap();
+        assert!(event.custom);
+        assert_eq!(event.screen.as_deref(), Some("checkout"));
+        assert_eq!(event.properties.len(), 2, "tables are dropped");
+        assert_eq!(event.properties["note"].as_str().unwrap().len(), MAX_PROPERTY_LENGTH);
+        let line = json_lines(&recorder.take_events());
+        assert!(line.contains("\"kind\":\"coupon_applied\"") && line.ends_with('\n'));
+
+        recorder.record("click", Some("pay"), None);
+        recorder.apply(AnalyticsCommand::from_script_value(&command(vec![("op", "consent".into()), ("granted", false.into())])).unwrap());
+        recorder.record("click", Some("pay"), None);
+        assert_eq!(recorder.events().count(), 0, "withdrawing consent discards and stops recording");
+    }
+
+    #[test]
+    fn passwords_and_opted_out_subtrees_are_not_recorded() {
+        let element = |parent: Option<ElementId>, properties: Vec<(&str, PropertyValue)>| Element {
+            parent,
+            custom_properties: properties.into_iter().map(|(key, value)| (key.to_string(), value)).collect(),
+            ..Default::default()
+        };
+        let elements: HashMap<ElementId, Element> = [
+            (0, element(None, vec![])),
+            (1, element(Some(0), vec![("analytics", PropertyValue::Bool(false))])),
+            (2, element(Some(1), vec![])),
+            (3, element(Some(0), vec![("input_type", PropertyValue::String("password".into()))])),
+            (4, element(Some(0), vec![])),
+        ].into_iter().collect();
+
+        assert!(!is_recordable(&elements, 2));
+        assert!(!is_recordable(&elements, 3));
+        assert!(is_recordable(&elements, 4));
+    }
+}
@@ -2,6 +2,9 @@
 #[cfg(feature = "wgpu")]
 pub use kryon_wgpu::WgpuRenderer;
 
+#[cfg(feature = "headless")]
+pub use kryon_headless::HeadlessRenderer;
+
 #[cfg(feature = "ratatui")]
 pub use kryon_ratatui::RatatuiRenderer;
 
@@ -13,11 +16,13 @@ pub use kryon_raylib::RaylibRenderer;
 pub enum RendererBackend {
     #[cfg(feature = "wgpu")]
     Wgpu,
+    #[cfg(feature = "headless")]
+    Headless,
     #[cfg(feature = "ratatui")]
     Ratatui,
     #[cfg(feature = "raylib")]
     Raylib,
-    #[cfg(not(any(feature = "wgpu", feature = "ratatui", feature = "raylib")))]
+    #[cfg(not(any(feature = "wgpu", feature = "headless", feature = "ratatui", feature = "raylib")))]
     None,
 }
 
@@ -26,11 +31,13 @@ impl RendererBackend {
         match self {
             #[cfg(feature = "wgpu")]
             RendererBackend::Wgpu => "wgpu",
+            #[cfg(feature = "headless")]
+            RendererBackend::Headless => "headless",
             #[cfg(feature = "ratatui")]
             RendererBackend::Ratatui => "ratatui",
             #[cfg(feature = "raylib")]
             RendererBackend::Raylib => "raylib",
-            #[cfg(not(any(feature = "wgpu", feature = "ratatui", feature = "raylib")))]
+            #[cfg(not(any(feature = "wgpu", feature = "headless", feature = "ratatui", feature = "raylib")))]
             RendererBackend::None => "none",
         }
     }
@@ -41,13 +48,16 @@ impl RendererBackend {
         #[cfg(feature = "wgpu")]
         backends.push(RendererBackend::Wgpu);
         
+        #[cfg(feature = "headless")]
+        backends.push(RendererBackend::Headless);
+
         #[cfg(feature = "ratatui")]
         backends.push(RendererBackend::Ratatui);
 
         #[cfg(feature = "raylib")]
         backends.push(RendererBackend::Raylib);
         
-        #[cfg(not(any(feature = "wgpu", feature = "ratatui", feature = "raylib")))]
+        #[cfg(not(any(feature = "wgpu", feature = "headless", feature = "ratatui", feature = "raylib")))]
         backends.push(RendererBackend::None);
         
         backends
@@ -0,0 +1,141 @@
+// crates/kryon-runtime/src/content_visibility.rs
+//! `content_visibility: auto`: the contents of an element that is hidden,
+//! or far enough offscreen that a little scrolling won't reveal it, are
+//! left out of layout and rendering.
+//!
+//! A skipped element keeps the size it last had with its contents laid
+//! out, so what comes after it doesn't move and scroll positions don't
+//! jump. Each frame the elements are checked against the last layout; one
+//! that is shown again or comes near the viewport gets its contents back
+//! on the next layout.
+
+use glam::Vec2;
+use kryon_core::{Element, ElementId, PropertyValue};
+use kryon_layout::LayoutResult;
+use std::collections::HashMap;
+
+/// How far outside the viewport an element still counts as near, as a
+/// fraction of the viewport size
+pub const NEAR_VIEWPORT: f32 = 0.5;
+
+#[derive(Debug, Default)]
+pub struct ContentVisibility {
+    /// Elements whose contents are skipped, with the size each keeps
+    skipped: HashMap<ElementId, Vec2>,
+}
+
+impl ContentVisibility {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Elements whose contents are skipped, with the size each keeps
+    pub fn skipped(&self) -> &HashMap<ElementId, Vec2> {
+        &self.skipped
+    }
+
+    /// Whether the element is inside a skipped element, and so has no layout
+    pub fn is_skipped_content(&self, elements: &HashMap<ElementId, Element>, element_id: ElementId) -> bool {
+        let mut parent = elements.get(&element_id).and_then(|element| element.parent);
+        while let Some(parent_id) = parent {
+            if self.skipped.contains_key(&parent_id) {
+                return true;
+            }
+            parent = elements.get(&parent_id).and_then(|element| element.parent);
+        }
+        false
+    }
+
+    /// Decides again which elements to skip, from where the last layout put
+    /// them. Returns whether that changed, in which case layout is stale.
+    pub fn update(&mut self, elements: &HashMap<ElementId, Element>, layout: &LayoutResult, viewport: Vec2) -> bool {
+        let near_min = -viewport * NEAR_VIEWPORT;
+        let near_max = viewport * (1.0 + NEAR_VIEWPORT);
+        let mut skipped = HashMap::new();
+        let auto = elements.iter().filter(|(_, element)| is_auto(element)).map(|(&element_id, _)| element_id);
+        for element_id in auto {
+            // Inside a skipped element: decided once that one is laid out again
+            let (Some(&position), Some(&size)) = (layout.computed_positions.get(&element_id), layout.computed_sizes.get(&element_id)) else {
+                continue;
+            };
+            let far = position.x > near_max.x || position.y > near_max.y
+                || position.x + size.x < near_min.x || position.y + size.y < near_min.y;
+            if far || !is_shown(elements, element_id) {
+                skipped.insert(element_id, size);
+            }
+        }
+
+        let changed = skipped.len() != self.skipped.len() || skipped.keys().any(|element_id| !self.skipped.contains_key(element_id));
+        if changed {
+            eprintln!("[CONTENT_VISIBILITY] Skipping the contents of {} element(s)", skipped.len());
+        }
+        self.skipped = skipped;
+        changed
+    }
+}
+
+fn is_auto(element: &Element) -> bool {
+    matches!(element.custom_properties.get("content_visibility"), Some(PropertyValue::String(value)) if value == "auto")
+}
+
+/// Whether the element and all its ancestors are visible
+fn is_shown(elements: &HashMap<ElementId, Element>, element_id: ElementId) -> bool {
+    kryon_core::hover_path(elements, Some(element_id)).iter().all(|id| elements[id].visible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A list in an 800x600 viewport: item 1 on screen, item 2 just below
+    /// it, item 3 far below, each with one child
+    fn list() -> (HashMap<ElementId, Element>, LayoutResult) {
+        let mut elements: HashMap<ElementId, Element> = HashMap::new();
+        elements.insert(0, Element { children: vec![1, 2, 3], ..Default::default() });
+        for id in 1..=3 {
+            let mut item = Element { parent: Some(0), children: vec![id + 10], ..Default::default() };
+            item.custom_properties.insert("content_visibility".into(), PropertyValue::String("auto".into()));
+            elements.insert(id, item);
+            elements.insert(id + 10, Element { parent: Some(id), ..Default::default() });
+        }
+        let layout = LayoutResult {
+            computed_positions: [(0, Vec2::ZERO), (1, Vec2::new(0.0, 100.0)), (2, Vec2::new(0.0, 700.0)), (3, Vec2::new(0.0, 2000.0))].into_iter().collect(),
+            computed_sizes: [(0, Vec2::new(800.0, 2200.0)), (1, Vec2::new(800.0, 200.0)), (2, Vec2::new(800.0, 200.0)), (3, Vec2::new(800.0, 200.0))].into_iter().collect(),
+        };
+        (elements, layout)
+    }
+
+    #[test]
+    fn skips_only_far_offscreen_and_hidden_contents() {
+        let (mut elements, layout) = list();
+        let mut visibility = ContentVisibility::new();
+        let viewport = Vec2::new(800.0, 600.0);
+
+        assert!(visibility.update(&elements, &layout, viewport));
+        assert_eq!(visibility.skipped().keys().copied().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(visibility.skipped()[&3], Vec2::new(800.0, 200.0));
+        assert!(visibility.is_skipped_content(&elements, 13));
+        assert!(!visibility.is_skipped_content(&elements, 3));
+        assert!(!visibility.update(&elements, &layout, viewport));
+
+        // Collapsing the first item skips its contents too
+        elements.get_mut(&1).unwrap().visible = false;
+        assert!(visibility.update(&elements, &layout, viewport));
+        assert!(visibility.skipped().contains_key(&1));
+    }
+
+    #[test]
+    fn scrolling_near_brings_contents_back() {
+        let (elements, mut layout) = list();
+        let mut visibility = ContentVisibility::new();
+        let viewport = Vec2::new(800.0, 600.0);
+        visibility.update(&elements, &layout, viewport);
+
+        // Scrolled down 1200px: item 3 is near the bottom, item 1 far above
+        for position in layout.computed_positions.values_mut() {
+            position.y -= 1200.0;
+        }
+        assert!(visibility.update(&elements, &layout, viewport));
+        assert_eq!(visibility.skipped().keys().copied().collect::<Vec<_>>(), vec![1]);
+    }
+}
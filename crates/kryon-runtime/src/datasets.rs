@@ -0,0 +1,339 @@
+// crates/kryon-runtime/src/datasets.rs
+//! CSV/JSON loading for `kryon.data.load_csv` / `load_json` and Table elements.
+//!
+//! A Table element can load its rows without any script:
+//!
+//! ```text
+//! Table {
+//!     table_source: "data/pumps.csv"     # .json files are read as JSON
+//!     table_sort: "-flow"                 # optional, `-` for descending
+//!     table_filter: "boiler"              # optional initial filter
+//! }
+//! ```
+//!
+//! Clicking a header cycles ascending, descending and unsorted. Scripts can
+//! bind loaded data with `{ bind = "table_id" }` and call
+//! `kryon.data.sort_table` / `kryon.data.filter_table`.
+
+use crate::script::engine_trait::ScriptValue;
+use kryon_core::{CellValue, Column, ColumnType, Dataset, Element, TableSort, TableView};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DatasetError {
+    #[error("failed to read {path}: {source}")]
+    Io { path: String, source: std::io::Error },
+
+    #[error("invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unsupported data shape: {0}")]
+    Shape(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    /// First record holds column names; otherwise columns are `column1`, `column2`, ...
+    pub header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: b',', header: true }
+    }
+}
+
+pub fn parse_csv(text: &str, options: &CsvOptions) -> Result<Dataset, DatasetError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::Headers)
+        .from_reader(text.as_bytes());
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        records.push(record?.iter().map(str::to_string).collect::<Vec<String>>());
+    }
+
+    let headers = if options.header && !records.is_empty() {
+        records.remove(0)
+    } else {
+        let width = records.iter().map(Vec::len).max().unwrap_or(0);
+        (1..=width).map(|index| format!("column{}", index)).collect()
+    };
+    Ok(Dataset::from_text_rows(headers, records))
+}
+
+/// Parses an array of objects (columns in first-seen key order) or an array
+/// of arrays (first array is the header). `pointer` selects the array inside
+/// a larger document, e.g. `/data/items`.
+pub fn parse_json(text: &str, pointer: Option<&str>) -> Result<Dataset, DatasetError> {
+    let document: Value = serde_json::from_str(text)?;
+    let value = match pointer {
+        Some(pointer) if !pointer.is_empty() => document.pointer(pointer)
+            .ok_or_else(|| DatasetError::Shape(format!("nothing at {}", pointer)))?,
+        _ => &document,
+    };
+    let Value::Array(items) = value else {
+        return Err(DatasetError::Shape("expected an array of rows".to_string()));
+    };
+
+    let (names, rows): (Vec<String>, Vec<Vec<Value>>) = match items.first() {
+        None => (Vec::new(), Vec::new()),
+        Some(Value::Array(header)) => {
+            let names = header.iter().map(json_text).collect();
+            let rows = items[1..].iter()
+                .map(|row| row.as_array().cloned().unwrap_or_default())
+                .collect();
+            (names, rows)
+        }
+        Some(Value::Object(_)) => {
+            let mut names: Vec<String> = Vec::new();
+            for item in items {
+                for key in item.as_object().into_iter().flat_map(|object| object.keys()) {
+                    if !names.contains(key) {
+                        names.push(key.clone());
+                    }
+                }
+            }
+            let rows = items.iter()
+                .map(|item| names.iter().map(|name| item.get(name).cloned().unwrap_or(Value::Null)).collect())
+                .collect();
+            (names, rows)
+        }
+        Some(_) => return Err(DatasetError::Shape("rows must be objects or arrays".to_string())),
+    };
+
+    // A column keeps JSON's types when they agree; mixed columns become text
+    let columns: Vec<Column> = names.into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let mut column_type = None;
+            for value in rows.iter().filter_map(|row| row.get(index)) {
+                let this = match value {
+                    Value::Null => continue,
+                    Value::Bool(_) => ColumnType::Bool,
+                    Value::Number(_) => ColumnType::Number,
+                    _ => ColumnType::Text,
+                };
+                if column_type.is_some_and(|previous| previous != this) {
+                    column_type = Some(ColumnType::Text);
+                    break;
+                }
+                column_type = Some(this);
+            }
+            Column { name, column_type: column_type.unwrap_or(ColumnType::Text) }
+        })
+        .collect();
+
+    let rows = rows.iter()
+        .map(|row| {
+            columns.iter()
+                .enumerate()
+                .map(|(index, column)| match (row.get(index), column.column_type) {
+                    (None | Some(Value::Null), _) => CellValue::Null,
+                    (Some(Value::Bool(b)), ColumnType::Bool) => CellValue::Bool(*b),
+                    (Some(Value::Number(n)), ColumnType::Number) => n.as_f64().map_or(CellValue::Null, CellValue::Number),
+                    (Some(value), _) => CellValue::Text(json_text(value)),
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Dataset { columns, rows })
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn read(path: &str) -> Result<String, DatasetError> {
+    std::fs::read_to_string(path).map_err(|source| DatasetError::Io { path: path.to_string(), source })
+}
+
+pub fn load_csv(path: &str, options: &CsvOptions) -> Result<Dataset, DatasetError> {
+    parse_csv(&read(path)?, options)
+}
+
+pub fn load_json(path: &str, pointer: Option<&str>) -> Result<Dataset, DatasetError> {
+    parse_json(&read(path)?, pointer)
+}
+
+/// Columns and rows for scripts:
+/// `{ columns = { {name=, type=}, ... }, rows = { {col = value, ...}, ... } }`.
+pub fn dataset_to_script_value(dataset: &Dataset) -> ScriptValue {
+    let columns = dataset.columns.iter()
+        .map(|column| {
+            let mut entry = HashMap::new();
+            entry.insert("name".to_string(), ScriptValue::String(column.name.clone()));
+            entry.insert("type".to_string(), ScriptValue::from(column.column_type.as_str()));
+            ScriptValue::Object(entry)
+        })
+        .collect();
+
+    let rows = dataset.rows.iter()
+        .map(|row| {
+            let fields = dataset.columns.iter()
+                .zip(row)
+                .filter_map(|(column, cell)| {
+                    let value = match cell {
+                        CellValue::Null => return None,
+                        CellValue::Bool(b) => ScriptValue::Boolean(*b),
+                        CellValue::Number(n) => ScriptValue::Number(*n),
+                        CellValue::Text(text) => ScriptValue::String(text.clone()),
+                    };
+                    Some((column.name.clone(), value))
+                })
+                .collect();
+            ScriptValue::Object(fields)
+        })
+        .collect();
+
+    let mut result = HashMap::new();
+    result.insert("columns".to_string(), ScriptValue::Array(columns));
+    result.insert("rows".to_string(), ScriptValue::Array(rows));
+    ScriptValue::Object(result)
+}
+
+/// `name` sorts ascending, `-name` descending.
+pub fn parse_sort(dataset: &Dataset, spec: &str) -> Option<TableSort> {
+    let spec = spec.trim();
+    let (name, ascending) = match spec.strip_prefix('-') {
+        Some(name) => (name, false),
+        None => (spec, true),
+    };
+    dataset.column_index(name).map(|column| TableSort { column, ascending })
+}
+
+/// Loads the `table_source` of a Table element and applies its initial sort and filter.
+pub fn table_view_for_element(element: &Element) -> Option<Result<TableView, DatasetError>> {
+    let props = &element.custom_properties;
+    let source = props.get("table_source")?.as_string()?;
+    let dataset = if source.to_ascii_lowercase().ends_with(".json") {
+        load_json(source, props.get("table_pointer").and_then(|v| v.as_string()))
+    } else {
+        load_csv(source, &CsvOptions::default())
+    };
+
+    Some(dataset.map(|dataset| {
+        let sort = props.get("table_sort").and_then(|v| v.as_string()).and_then(|spec| parse_sort(&dataset, spec));
+        let mut view = TableView::new(Arc::new(dataset));
+        view.set_sort(sort);
+        if let Some(filter) = props.get("table_filter").and_then(|v| v.as_string()) {
+            view.set_filter(filter);
+        }
+        view
+    }))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataRequest {
+    LoadCsv { id: u64, path: String, options: CsvOptions, bind: Option<String> },
+    LoadJson { id: u64, path: String, pointer: Option<String>, bind: Option<String> },
+    SortTable { table: String, column: Option<String>, ascending: bool },
+    FilterTable { table: String, filter: String },
+}
+
+impl DataRequest {
+    /// Parses a request queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+        let string = |key: &str| match fields.get(key) {
+            Some(ScriptValue::String(s)) if !s.is_empty() => Some(s.clone()),
+            _ => None,
+        };
+        let id = match fields.get("id") {
+            Some(ScriptValue::Integer(id)) if *id > 0 => *id as u64,
+            Some(ScriptValue::Number(id)) if *id > 0.0 => *id as u64,
+            _ => 0,
+        };
+        let ScriptValue::String(op) = fields.get("op")? else {
+            return None;
+        };
+
+        match op.as_str() {
+            "load_csv" if id > 0 => {
+                let mut options = CsvOptions::default();
+                if let Some(delimiter) = string("delimiter") {
+                    options.delimiter = *delimiter.as_bytes().first()?;
+                }
+                if let Some(header) = fields.get("header") {
+                    options.header = header.to_bool();
+                }
+                Some(DataRequest::LoadCsv { id, path: string("path")?, options, bind: string("bind") })
+            }
+            "load_json" if id > 0 => Some(DataRequest::LoadJson {
+                id,
+                path: string("path")?,
+                pointer: string("pointer"),
+                bind: string("bind"),
+            }),
+            "sort_table" => Some(DataRequest::SortTable {
+                table: string("table")?,
+                column: string("column"),
+                ascending: fields.get("ascending").map(|v| v.to_bool()).unwrap_or(true),
+            }),
+            "filter_table" => Some(DataRequest::FilterTable {
+                table: string("table")?,
+                filter: fields.get("filter").map(|v| v.to_string()).unwrap_or_default(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_csv_with_types() {
+        let dataset = parse_csv("name,flow,ok\n\"Pump, A\",12.5,true\nValve,,false\n", &CsvOptions::default()).unwrap();
+        assert_eq!(dataset.columns.iter().map(|c| c.column_type).collect::<Vec<_>>(),
+            vec![ColumnType::Text, ColumnType::Number, ColumnType::Bool]);
+        assert_eq!(dataset.rows[0][0], CellValue::Text("Pump, A".into()));
+        assert_eq!(dataset.rows[1][1], CellValue::Null);
+
+        let headerless = parse_csv("1;2\n3;4", &CsvOptions { delimiter: b';', header: false }).unwrap();
+        assert_eq!(headerless.columns[1].name, "column2");
+        assert_eq!(headerless.rows.len(), 2);
+    }
+
+    #[test]
+    fn parses_json_objects_and_arrays() {
+        let dataset = parse_json(r#"{"items": [{"id": 1, "tag": "a"}, {"id": 2, "extra": true}]}"#, Some("/items")).unwrap();
+        let names: Vec<&str> = dataset.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "tag", "extra"]);
+        assert_eq!(dataset.rows[1], vec![CellValue::Number(2.0), CellValue::Null, CellValue::Bool(true)]);
+
+        let arrays = parse_json(r#"[["x", "y"], [1, "one"], ["two", 2]]"#, None).unwrap();
+        assert_eq!(arrays.columns[0].column_type, ColumnType::Text);
+        assert_eq!(arrays.rows[1][0], CellValue::Text("two".into()));
+
+        assert!(parse_json(r#"{"a": 1}"#, None).is_err());
+    }
+
+    #[test]
+    fn script_rows_omit_nulls() {
+        let dataset = parse_csv("a,b\n1,\n", &CsvOptions::default()).unwrap();
+        let ScriptValue::Object(result) = dataset_to_script_value(&dataset) else { panic!() };
+        let Some(ScriptValue::Array(rows)) = result.get("rows") else { panic!() };
+        let ScriptValue::Object(row) = &rows[0] else { panic!() };
+        assert_eq!(row.get("a"), Some(&ScriptValue::Number(1.0)));
+        assert!(!row.contains_key("b"));
+        assert_eq!(parse_sort(&dataset, "-b"), Some(TableSort { column: 1, ascending: false }));
+    }
+}
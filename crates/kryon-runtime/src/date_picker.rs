@@ -0,0 +1,426 @@
+// crates/kryon-runtime/src/date_picker.rs
+//! The calendar popup of `Input` elements with `input_type: "date"`.
+//!
+//! Clicking a date input opens a month grid below it on the overlay layer,
+//! with weekday and month names from the active locale and weeks starting
+//! on its first weekday. The arrow keys move the highlighted day, Page Up
+//! and Page Down the month (a year with Shift), Home and End go to the ends
+//! of the month, Enter or Space picks the highlighted day and Escape closes
+//! the popup. Days before the input's `min` or after its `max` can't be
+//! highlighted or picked.
+//!
+//! Values are ISO dates, `YYYY-MM-DD`, in the element's `text` and in the
+//! template variable its `value_variable` names. Backends whose text cells
+//! are too coarse for a grid get a list of the days around the highlighted
+//! one instead.
+
+use crate::i18n::Locale;
+use crate::popups::{self, Placement, Rect, POPUP_Z_INDEX};
+use glam::{Vec2, Vec4};
+use kryon_core::{Element, ElementId, TextAlignment};
+use kryon_render::{KeyCode, KeyModifiers, RenderCommand};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PADDING: f32 = 8.0;
+const HEADER_HEIGHT: f32 = 32.0;
+const WEEKDAY_HEIGHT: f32 = 24.0;
+const CELL_SIZE: Vec2 = Vec2::new(32.0, 28.0);
+const GRID_ROWS: usize = 6;
+const LIST_WIDTH: f32 = 200.0;
+const LIST_ROW_HEIGHT: f32 = 20.0;
+/// Days listed on each side of the highlighted one
+const LIST_RADIUS: i64 = 3;
+const OFFSET: f32 = 4.0;
+const ACCENT: Vec4 = Vec4::new(0.2, 0.45, 0.9, 1.0);
+
+/// A day in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    /// 1 to 12
+    pub month: u32,
+    /// 1 to the length of the month
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        ((1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)).then_some(Self { year, month, day })
+    }
+
+    /// Parses `YYYY-MM-DD`
+    pub fn parse_iso(value: &str) -> Option<Self> {
+        let mut parts = value.trim().splitn(3, '-');
+        let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return None;
+        }
+        Self::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+    }
+
+    /// The current day in UTC
+    pub fn today() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        Self::from_days((seconds / 86_400) as i64)
+    }
+
+    /// The day `days` after 1970-01-01
+    pub fn from_days(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days, with eras of 400 years from March
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+        let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01
+    pub fn days(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month_from_march = (i64::from(self.month) + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// 0 for Sunday
+    pub fn weekday(self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days() + 4).rem_euclid(7) as u32
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.days() + days)
+    }
+
+    /// The same day `months` later, or the month's last day if it's shorter
+    pub fn add_months(self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+        Self { year, month, day: self.day.min(days_in_month(year, month)) }
+    }
+
+    fn first_of_month(self) -> Self {
+        Self { day: 1, ..self }
+    }
+
+    fn last_of_month(self) -> Self {
+        Self { day: days_in_month(self.year, self.month), ..self }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// What a key or click did to an open picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerAction {
+    /// The highlighted day or shown month changed
+    Moved,
+    /// The picker stays as it was
+    Handled,
+    Picked(Date),
+    Closed,
+}
+
+/// Colors taken from the input's computed style
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickerColors {
+    pub background: Vec4,
+    pub text: Vec4,
+    pub border: Vec4,
+}
+
+/// An open date picker
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatePicker {
+    element: ElementId,
+    /// The keyboard's day; its month is the one shown
+    highlighted: Date,
+    min: Option<Date>,
+    max: Option<Date>,
+    /// A list of days rather than a month grid
+    list: bool,
+}
+
+impl DatePicker {
+    /// A picker for `element` showing its value, or `today` if it has none,
+    /// kept within its `min` and `max`
+    pub fn open(element_id: ElementId, element: &Element, today: Date, list: bool) -> Self {
+        let bound = |name: &str| element.custom_properties.get(name).and_then(|value| value.as_string()).and_then(Date::parse_iso);
+        let mut picker = Self { element: element_id, highlighted: today, min: bound("min"), max: bound("max"), list };
+        picker.highlighted = picker.clamp(Date::parse_iso(&element.text).unwrap_or(today));
+        picker
+    }
+
+    pub fn element(&self) -> ElementId {
+        self.element
+    }
+
+    pub fn highlighted(&self) -> Date {
+        self.highlighted
+    }
+
+    pub fn allows(&self, date: Date) -> bool {
+        self.min.is_none_or(|min| date >= min) && self.max.is_none_or(|max| date <= max)
+    }
+
+    fn clamp(&self, date: Date) -> Date {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+
+    fn highlight(&mut self, date: Date) -> PickerAction {
+        let date = self.clamp(date);
+        if date == self.highlighted {
+            return PickerAction::Handled;
+        }
+        self.highlighted = date;
+        PickerAction::Moved
+    }
+
+    pub fn key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> PickerAction {
+        let date = self.highlighted;
+        let week = if self.list { 1 } else { 7 };
+        let months = if modifiers.shift { 12 } else { 1 };
+        match key {
+            KeyCode::ArrowLeft => self.highlight(date.add_days(-1)),
+            KeyCode::ArrowRight => self.highlight(date.add_days(1)),
+            KeyCode::ArrowUp => self.highlight(date.add_days(-week)),
+            KeyCode::ArrowDown => self.highlight(date.add_days(week)),
+            KeyCode::PageUp => self.highlight(date.add_months(-months)),
+            KeyCode::PageDown => self.highlight(date.add_months(months)),
+            KeyCode::Home => self.highlight(date.first_of_month()),
+            KeyCode::End => self.highlight(date.last_of_month()),
+            KeyCode::Enter | KeyCode::Space if self.allows(date) => PickerAction::Picked(date),
+            KeyCode::Escape | KeyCode::Tab => PickerAction::Closed,
+            // Typing goes nowhere while the picker is open
+            _ => PickerAction::Handled,
+        }
+    }
+
+    /// Where the picker goes below the input at `anchor`, or above it if
+    /// there's more room there
+    pub fn bounds(&self, anchor: Rect, viewport: Vec2) -> Rect {
+        let size = match self.list {
+            true => Vec2::new(LIST_WIDTH, LIST_ROW_HEIGHT * (2 * LIST_RADIUS + 2) as f32 + 2.0 * PADDING),
+            false => Vec2::new(7.0 * CELL_SIZE.x, HEADER_HEIGHT + WEEKDAY_HEIGHT + GRID_ROWS as f32 * CELL_SIZE.y) + 2.0 * PADDING,
+        };
+        let (position, _) = popups::place(anchor, size, viewport, Placement::Bottom, OFFSET);
+        Rect { position, size }
+    }
+
+    /// A press at `position`: the header's arrows change the month, a day
+    /// is picked, and a press outside closes the picker
+    pub fn click(&mut self, position: Vec2, bounds: Rect, locale: &Locale) -> PickerAction {
+        let local = position - bounds.position;
+        if local.cmplt(Vec2::ZERO).any() || local.cmpge(bounds.size).any() {
+            return PickerAction::Closed;
+        }
+        let header = if self.list { LIST_ROW_HEIGHT } else { HEADER_HEIGHT };
+        if local.y < PADDING + header {
+            return match local.x {
+                x if x < bounds.size.x / 4.0 => self.highlight(self.highlighted.add_months(-1)),
+                x if x > bounds.size.x * 3.0 / 4.0 => self.highlight(self.highlighted.add_months(1)),
+                _ => PickerAction::Handled,
+            };
+        }
+        let day = match self.list {
+            true => {
+                let row = ((local.y - PADDING - header) / LIST_ROW_HEIGHT).floor() as i64;
+                (row <= 2 * LIST_RADIUS).then(|| self.highlighted.add_days(row - LIST_RADIUS))
+            }
+            false => {
+                let grid = local - Vec2::new(PADDING, PADDING + header + WEEKDAY_HEIGHT);
+                let (column, row) = ((grid.x / CELL_SIZE.x).floor(), (grid.y / CELL_SIZE.y).floor());
+                ((0.0..7.0).contains(&column) && (0.0..GRID_ROWS as f32).contains(&row))
+                    .then(|| self.grid_start(locale).add_days(row as i64 * 7 + column as i64))
+            }
+        };
+        match day {
+            Some(day) if self.allows(day) => PickerAction::Picked(day),
+            _ => PickerAction::Handled,
+        }
+    }
+
+    /// The day in the grid's top left cell: the first of the month, or the
+    /// last days of the month before, back to the locale's first weekday
+    fn grid_start(&self, locale: &Locale) -> Date {
+        let first = self.highlighted.first_of_month();
+        first.add_days(-(((first.weekday() + 7 - locale.first_weekday) % 7) as i64))
+    }
+
+    /// The picker's overlay commands, `selected` being the input's value
+    pub fn commands(&self, bounds: Rect, locale: &Locale, selected: Option<Date>, font_size: f32, colors: PickerColors) -> Vec<RenderCommand> {
+        let dimmed = colors.text * Vec4::new(1.0, 1.0, 1.0, 0.4);
+        let mut commands = vec![rect(bounds.position, bounds.size, colors.background, 4.0, colors.border)];
+        let origin = bounds.position + PADDING;
+        let inner_width = bounds.size.x - 2.0 * PADDING;
+        let title = format!("{} {}", locale.month_name(self.highlighted.month), self.highlighted.year);
+        let header = if self.list { LIST_ROW_HEIGHT } else { HEADER_HEIGHT };
+        for (text, alignment) in [("<", TextAlignment::Start), (title.as_str(), TextAlignment::Center), (">", TextAlignment::End)] {
+            commands.push(text_command(text, origin, Vec2::new(inner_width, header), font_size, colors.text, alignment));
+        }
+
+        if self.list {
+            for (row, offset) in (-LIST_RADIUS..=LIST_RADIUS).enumerate() {
+                let day = self.highlighted.add_days(offset);
+                let position = origin + Vec2::new(0.0, header + row as f32 * LIST_ROW_HEIGHT);
+                let marker = if offset == 0 { ">" } else { " " };
+                let label = format!("{} {} {} {}", marker, locale.weekday_name(day.weekday()), day.day, locale.month_name(day.month));
+                let color = if self.allows(day) { colors.text } else { dimmed };
+                let color = if Some(day) == selected { ACCENT } else { color };
+                commands.push(text_command(&label, position, Vec2::new(inner_width, LIST_ROW_HEIGHT), font_size, color, TextAlignment::Start));
+            }
+            return commands;
+        }
+
+        for (column, weekday) in locale.week().enumerate() {
+            let position = origin + Vec2::new(column as f32 * CELL_SIZE.x, header);
+            let size = Vec2::new(CELL_SIZE.x, WEEKDAY_HEIGHT);
+            commands.push(text_command(locale.short_weekday_name(weekday), position, size, font_size * 0.8, dimmed, TextAlignment::Center));
+        }
+        let start = self.grid_start(locale);
+        for cell in 0..GRID_ROWS * 7 {
+            let day = start.add_days(cell as i64);
+            let position = origin + Vec2::new((cell % 7) as f32 * CELL_SIZE.x, header + WEEKDAY_HEIGHT + (cell / 7) as f32 * CELL_SIZE.y);
+            let mut color = if day.month == self.highlighted.month && self.allows(day) { colors.text } else { dimmed };
+            if Some(day) == selected {
+                commands.push(rect(position + 1.0, CELL_SIZE - 2.0, ACCENT, 4.0, Vec4::ZERO));
+                color = Vec4::ONE;
+            }
+            if day == self.highlighted {
+                commands.push(rect(position + 1.0, CELL_SIZE - 2.0, Vec4::ZERO, 4.0, ACCENT));
+            }
+            commands.push(text_command(&day.day.to_string(), position, CELL_SIZE, font_size, color, TextAlignment::Center));
+        }
+        commands
+    }
+}
+
+fn rect(position: Vec2, size: Vec2, color: Vec4, border_radius: f32, border_color: Vec4) -> RenderCommand {
+    RenderCommand::DrawRect {
+        position,
+        size,
+        color,
+        border_radius,
+        border_width: if border_color.w > 0.0 { 1.0 } else { 0.0 },
+        border_color,
+        transform: None,
+        shadow: None,
+        z_index: POPUP_Z_INDEX,
+    }
+}
+
+/// `text` centered vertically in the box at `position`
+fn text_command(text: &str, position: Vec2, size: Vec2, font_size: f32, color: Vec4, alignment: TextAlignment) -> RenderCommand {
+    RenderCommand::DrawText {
+        position: Vec2::new(position.x, position.y + (size.y - font_size * 1.2).max(0.0) / 2.0),
+        text: text.to_string(),
+        font_size,
+        color,
+        alignment,
+        max_width: Some(size.x),
+        max_height: Some(font_size * 1.2),
+        transform: None,
+        font_family: None,
+        runs: Vec::new(),
+        z_index: POPUP_Z_INDEX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::I18n;
+    use kryon_core::PropertyValue;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    fn date_input(value: &str, min: &str, max: &str) -> Element {
+        let mut element = Element { text: value.to_string(), ..Default::default() };
+        element.custom_properties.insert("min".into(), PropertyValue::String(min.into()));
+        element.custom_properties.insert("max".into(), PropertyValue::String(max.into()));
+        element
+    }
+
+    #[test]
+    fn dates_count_days_and_months_like_the_calendar() {
+        assert_eq!(Date::from_days(0), date(1970, 1, 1));
+        assert_eq!(date(2000, 3, 1).days() - date(2000, 2, 28).days(), 2, "2000 was a leap year");
+        assert_eq!(date(2026, 10, 16).weekday(), 5, "a Friday");
+        assert_eq!(Date::from_days(date(1969, 12, 31).days()), date(1969, 12, 31));
+        assert_eq!(date(2024, 1, 31).add_months(1), date(2024, 2, 29));
+        assert_eq!(date(2024, 1, 15).add_months(-13), date(2022, 12, 15));
+        assert_eq!(Date::parse_iso("2026-02-29"), None);
+        assert_eq!(Date::parse_iso("2026-2-1"), None);
+        assert_eq!(date(5, 3, 9).to_string(), "0005-03-09");
+    }
+
+    #[test]
+    fn keys_move_within_min_and_max_and_pick() {
+        let element = date_input("2026-10-16", "2026-10-03", "2026-11-20");
+        let mut picker = DatePicker::open(1, &element, date(2020, 1, 1), false);
+        let none = KeyModifiers::none();
+        assert_eq!(picker.key(KeyCode::ArrowDown, none), PickerAction::Moved);
+        assert_eq!(picker.highlighted(), date(2026, 10, 23));
+        picker.key(KeyCode::PageDown, none);
+        assert_eq!(picker.highlighted(), date(2026, 11, 20), "clamped to max");
+        picker.key(KeyCode::PageUp, KeyModifiers { shift: true, ..none });
+        assert_eq!(picker.highlighted(), date(2026, 10, 3), "clamped to min");
+        assert_eq!(picker.key(KeyCode::ArrowLeft, none), PickerAction::Handled);
+        assert_eq!(picker.key(KeyCode::Enter, none), PickerAction::Picked(date(2026, 10, 3)));
+        assert_eq!(picker.key(KeyCode::Escape, none), PickerAction::Closed);
+
+        let empty = DatePicker::open(1, &date_input("", "2030-01-01", ""), date(2026, 10, 16), true);
+        assert_eq!(empty.highlighted(), date(2030, 1, 1), "today, kept after min");
+    }
+
+    #[test]
+    fn grids_start_on_the_locales_first_weekday() {
+        let mut i18n = I18n::new();
+        let element = date_input("2026-10-16", "", "");
+        let mut picker = DatePicker::open(1, &element, date(2026, 1, 1), false);
+        // October 2026 starts on a Thursday
+        assert_eq!(picker.grid_start(i18n.locale()), date(2026, 9, 27));
+        i18n.set_locale("de");
+        assert_eq!(picker.grid_start(i18n.locale()), date(2026, 9, 28));
+
+        let bounds = picker.bounds(Rect { position: Vec2::new(100.0, 100.0), size: Vec2::new(240.0, 30.0) }, Vec2::new(800.0, 600.0));
+        let cell = |column: f32, row: f32| bounds.position + PADDING + Vec2::new(column * CELL_SIZE.x, HEADER_HEIGHT + WEEKDAY_HEIGHT + row * CELL_SIZE.y) + 4.0;
+        assert_eq!(picker.click(cell(0.0, 0.0), bounds, i18n.locale()), PickerAction::Picked(date(2026, 9, 28)));
+        assert_eq!(picker.click(cell(4.0, 2.0), bounds, i18n.locale()), PickerAction::Picked(date(2026, 10, 16)));
+        assert_eq!(picker.click(bounds.position + Vec2::new(PADDING + 2.0, PADDING + 2.0), bounds, i18n.locale()), PickerAction::Moved);
+        assert_eq!(picker.highlighted(), date(2026, 9, 16), "the header's left arrow goes back a month");
+        assert_eq!(picker.click(Vec2::ZERO, bounds, i18n.locale()), PickerAction::Closed);
+
+        let commands = picker.commands(bounds, i18n.locale(), Date::parse_iso(&element.text), 14.0, PickerColors { background: Vec4::ONE, text: Vec4::W, border: Vec4::W });
+        let texts: Vec<&str> = commands.iter().filter_map(|command| match command {
+            RenderCommand::DrawText { text, .. } => Some(text.as_str()),
+            _ => None,
+        }).collect();
+        assert!(texts.contains(&"September 2026") && texts.contains(&"Mo"));
+    }
+}
@@ -0,0 +1,365 @@
+// crates/kryon-runtime/src/diagnostics.rs
+//! Crash reporting and diagnostic bundles.
+//!
+//! Nothing is recorded until a renderer calls [`install`] (its
+//! `--diagnostics-dir` flag). From then on the app keeps the last frames'
+//! command counts and timings plus recent log lines, and a panic hook writes
+//! a zipped bundle with the backtrace, those frames, the loaded KRB's
+//! metadata and the backend/adapter info. Scripts can write the same bundle
+//! with `kryon.diagnostics.dump()`.
+
+use crate::script::engine_trait::ScriptValue;
+use kryon_core::KRBFile;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock, TryLockError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Frames kept for the bundle's `frames.csv`.
+pub const FRAME_HISTORY: usize = 120;
+/// Log lines kept for the bundle's `log.txt`.
+pub const LOG_HISTORY: usize = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameRecord {
+    pub frame: u64,
+    pub command_count: usize,
+    pub update_ms: f32,
+    pub render_ms: f32,
+}
+
+/// What goes into a bundle. The process-wide instance behind [`install`] is
+/// fed by the app and the renderers; a standalone one is useful in tests.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    frames: VecDeque<FrameRecord>,
+    log: VecDeque<String>,
+    partial_line: String,
+    krb: Option<serde_json::Value>,
+    backend: Option<String>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_frame(&mut self, frame: FrameRecord) {
+        if self.frames.len() == FRAME_HISTORY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = &FrameRecord> {
+        self.frames.iter()
+    }
+
+    /// Appends log output, which may hold several lines or part of one.
+    pub fn record_log(&mut self, text: &str) {
+        self.partial_line.push_str(text);
+        while let Some(end) = self.partial_line.find('\n') {
+            let line: String = self.partial_line.drain(..=end).collect();
+            if self.log.len() == LOG_HISTORY {
+                self.log.pop_front();
+            }
+            self.log.push_back(line.trim_end().to_string());
+        }
+    }
+
+    pub fn log_lines(&self) -> impl Iterator<Item = &String> {
+        self.log.iter()
+    }
+
+    pub fn set_krb(&mut self, path: &str, krb_file: &KRBFile) {
+        self.krb = Some(krb_metadata(path, krb_file));
+    }
+
+    pub fn set_backend(&mut self, info: impl Into<String>) {
+        self.backend = Some(info.into());
+    }
+
+    /// Writes `kryon-<reason>-<unix time>-<pid>.zip` into `dir`.
+    pub fn write_bundle(&self, dir: &Path, reason: &str, panic_report: Option<&str>) -> io::Result<PathBuf> {
+        let created = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let file_reason: String = reason.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .take(32)
+            .collect();
+        let path = dir.join(format!("kryon-{}-{}-{}.zip", file_reason, created, std::process::id()));
+
+        let summary = serde_json::json!({
+            "reason": reason,
+            "created_unix": created,
+            "kryon_version": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "backend": self.backend,
+            "krb": self.krb,
+            "frames_recorded": self.frames.len(),
+        });
+
+        let mut frames = String::from("frame,commands,update_ms,render_ms\n");
+        for frame in &self.frames {
+            frames.push_str(&format!(
+                "{},{},{:.3},{:.3}\n",
+                frame.frame, frame.command_count, frame.update_ms, frame.render_ms
+            ));
+        }
+
+        let mut log: Vec<&str> = self.log.iter().map(String::as_str).collect();
+        if !self.partial_line.is_empty() {
+            log.push(&self.partial_line);
+        }
+
+        let mut files = vec![
+            ("summary.json", serde_json::to_string_pretty(&summary).unwrap_or_default()),
+            ("frames.csv", frames),
+            ("log.txt", log.join("\n")),
+        ];
+        if let Some(report) = panic_report {
+            files.push(("panic.txt", report.to_string()));
+        }
+
+        zip_files(&path, &files)?;
+        Ok(path)
+    }
+}
+
+/// A `kryon.diagnostics.dump()` call queued by the script bridge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpRequest {
+    pub id: u64,
+    pub reason: String,
+}
+
+impl DumpRequest {
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+        let id = match fields.get("id") {
+            Some(ScriptValue::Integer(id)) if *id > 0 => *id as u64,
+            Some(ScriptValue::Number(id)) if *id > 0.0 => *id as u64,
+            _ => return None,
+        };
+        let reason = match fields.get("reason") {
+            Some(ScriptValue::String(reason)) if !reason.is_empty() => reason.clone(),
+            _ => "script".to_string(),
+        };
+        Some(Self { id, reason })
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static BUNDLE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static RECORDER: OnceLock<Mutex<Recorder>> = OnceLock::new();
+
+/// Starts recording and installs the panic hook, which writes a bundle into
+/// `dir` and then runs the previous hook. Only the first call takes effect.
+pub fn install(dir: impl Into<PathBuf>) -> io::Result<()> {
+    let dir = dir.into();
+    std::fs::create_dir_all(&dir)?;
+    if BUNDLE_DIR.set(dir.clone()).is_err() {
+        return Ok(());
+    }
+    RECORDER.get_or_init(Default::default);
+    ENABLED.store(true, Ordering::Release);
+    eprintln!("[DIAG] Diagnostics enabled; bundles go to {}", dir.display());
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let report = format!(
+            "thread '{}' {}\n\n{}",
+            thread.name().unwrap_or("<unnamed>"),
+            info,
+            std::backtrace::Backtrace::force_capture()
+        );
+        // The panicking thread may hold the recorder; a bundle without
+        // frames and log still carries the backtrace.
+        let result = match RECORDER.get().map(Mutex::try_lock) {
+            Some(Ok(recorder)) => recorder.write_bundle(&dir, "crash", Some(&report)),
+            Some(Err(TryLockError::Poisoned(poisoned))) => poisoned.into_inner().write_bundle(&dir, "crash", Some(&report)),
+            _ => Recorder::new().write_bundle(&dir, "crash", Some(&report)),
+        };
+        match result {
+            Ok(path) => eprintln!("[DIAG] Crash report written to {}", path.display()),
+            Err(e) => eprintln!("[DIAG] Failed to write crash report: {}", e),
+        }
+        previous(info);
+    }));
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+fn recorder() -> Option<MutexGuard<'static, Recorder>> {
+    if !is_enabled() {
+        return None;
+    }
+    RECORDER.get().map(|recorder| recorder.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+pub fn record_frame(frame: FrameRecord) {
+    if let Some(mut recorder) = recorder() {
+        recorder.record_frame(frame);
+    }
+}
+
+/// Records the KRB being rendered; call after template overrides are applied.
+pub fn set_krb(path: &str, krb_file: &KRBFile) {
+    if let Some(mut recorder) = recorder() {
+        recorder.set_krb(path, krb_file);
+    }
+}
+
+/// Records the backend and adapter, e.g. `wgpu: NVIDIA GeForce (Vulkan)`.
+pub fn set_backend(info: impl Into<String>) {
+    if let Some(mut recorder) = recorder() {
+        recorder.set_backend(info);
+    }
+}
+
+/// Writes a bundle now, for `kryon.diagnostics.dump()`.
+pub fn dump(reason: &str) -> io::Result<PathBuf> {
+    let (Some(dir), Some(recorder)) = (BUNDLE_DIR.get(), recorder()) else {
+        return Err(io::Error::other(
+            "diagnostics are not enabled (start the renderer with --diagnostics-dir)",
+        ));
+    };
+    let path = recorder.write_bundle(dir, reason, None)?;
+    eprintln!("[DIAG] Diagnostic bundle written to {}", path.display());
+    Ok(path)
+}
+
+/// Passes log output through to `inner` and keeps a copy for bundles, e.g.
+/// `tracing_subscriber::fmt().with_writer(|| diagnostics::tee(io::stdout()))`.
+pub fn tee<W: Write>(inner: W) -> LogTee<W> {
+    LogTee { inner }
+}
+
+pub struct LogTee<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for LogTee<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if let Some(mut recorder) = recorder() {
+            recorder.record_log(&String::from_utf8_lossy(&buf[..written]));
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn krb_metadata(path: &str, krb_file: &KRBFile) -> serde_json::Value {
+    let header = &krb_file.header;
+    let mut fonts: Vec<&String> = krb_file.fonts.keys().collect();
+    fonts.sort();
+    let scripts: Vec<serde_json::Value> = krb_file.scripts.iter()
+        .map(|script| serde_json::json!({
+            "name": script.name,
+            "language": script.language,
+            "bytes": script.bytecode.len(),
+        }))
+        .collect();
+
+    serde_json::json!({
+        "path": path,
+        "version": format!("{}.{}", header.version >> 8, header.version & 0xFF),
+        "elements": krb_file.elements.len(),
+        "styles": krb_file.styles.len(),
+        "components": header.component_count,
+        "resources": krb_file.resources.len(),
+        "template_variables": krb_file.template_variables.len(),
+        "fonts": fonts,
+        "scripts": scripts,
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn zip_files(path: &Path, files: &[(&str, String)]) -> io::Result<()> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(*name, options).map_err(io::Error::other)?;
+        zip.write_all(contents.as_bytes())?;
+    }
+    zip.finish().map_err(io::Error::other)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn zip_files(_path: &Path, _files: &[(&str, String)]) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "diagnostic bundles need a filesystem"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn keeps_recent_frames_and_whole_log_lines() {
+        let mut recorder = Recorder::new();
+        for frame in 0..FRAME_HISTORY as u64 + 5 {
+            recorder.record_frame(FrameRecord { frame, ..Default::default() });
+        }
+        assert_eq!(recorder.frames().count(), FRAME_HISTORY);
+        assert_eq!(recorder.frames().next().unwrap().frame, 5);
+
+        recorder.record_log("INFO starting\nWARN par");
+        recorder.record_log("tial\n");
+        let lines: Vec<&String> = recorder.log_lines().collect();
+        assert_eq!(lines, vec!["INFO starting", "WARN partial"]);
+    }
+
+    #[test]
+    fn bundle_contains_report_frames_and_log() {
+        let dir = std::env::temp_dir().join(format!("kryon-diag-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut recorder = Recorder::new();
+        recorder.set_backend("ratatui");
+        recorder.record_frame(FrameRecord { frame: 7, command_count: 42, update_ms: 1.5, render_ms: 2.25 });
+        recorder.record_log("last words\n");
+        let path = recorder.write_bundle(&dir, "crash", Some("thread 'main' panicked")).unwrap();
+        assert!(path.file_name().unwrap().to_string_lossy().starts_with("kryon-crash-"));
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let mut read = |name: &str| {
+            let mut contents = String::new();
+            archive.by_name(name).unwrap().read_to_string(&mut contents).unwrap();
+            contents
+        };
+        assert_eq!(read("frames.csv"), "frame,commands,update_ms,render_ms\n7,42,1.500,2.250\n");
+        assert_eq!(read("log.txt"), "last words");
+        assert_eq!(read("panic.txt"), "thread 'main' panicked");
+        let summary: serde_json::Value = serde_json::from_str(&read("summary.json")).unwrap();
+        assert_eq!(summary["backend"], "ratatui");
+        assert_eq!(summary["reason"], "crash");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parses_dump_requests() {
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("id".to_string(), ScriptValue::Integer(3));
+        assert_eq!(
+            DumpRequest::from_script_value(&ScriptValue::Object(fields.clone())),
+            Some(DumpRequest { id: 3, reason: "script".into() })
+        );
+        fields.insert("id".to_string(), ScriptValue::Nil);
+        assert_eq!(DumpRequest::from_script_value(&ScriptValue::Object(fields)), None);
+    }
+}
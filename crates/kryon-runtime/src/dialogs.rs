@@ -0,0 +1,484 @@
+// crates/kryon-runtime/src/dialogs.rs
+//! System file open/save dialogs for scripts.
+//!
+//! Scripts call `kryon.dialog.open_file(options, callback)` or
+//! `kryon.dialog.save_file(options, callback)`. The bridge queues a
+//! [`DialogRequest`]; [`DialogManager`] shows it without blocking the frame
+//! loop and the [`DialogResult`] is delivered back to the script callback on a
+//! later `update`.
+//!
+//! Backends:
+//! - desktop (`dialogs` feature): native dialogs through `rfd`, run on a worker thread
+//! - web (`web` feature on wasm32): a hidden `<input type=file>` for opening and
+//!   a download link for saving; file contents are always delivered as bytes
+//! - otherwise every request completes immediately as cancelled
+
+use crate::script::engine_trait::ScriptValue;
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogKind {
+    OpenFile,
+    SaveFile,
+}
+
+/// A named set of extensions, e.g. `Images: png, jpg`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFilter {
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// `accept` attribute value for an HTML file input.
+    pub fn to_accept(&self) -> String {
+        self.extensions.iter()
+            .map(|ext| if ext == "*" { "*/*".to_string() } else { format!(".{}", ext) })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogRequest {
+    /// Script-side callback id the result is delivered to
+    pub callback_id: i64,
+    pub kind: DialogKind,
+    pub title: Option<String>,
+    pub filters: Vec<FileFilter>,
+    /// Allow selecting several files (open only)
+    pub multiple: bool,
+    pub default_name: Option<String>,
+    pub directory: Option<String>,
+    /// Read the selected files and deliver their bytes (always on for web)
+    pub read: bool,
+    /// Contents written to the chosen path (save only, required on web)
+    pub data: Option<Vec<u8>>,
+}
+
+impl DialogRequest {
+    /// Parses a request queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+
+        let callback_id = match fields.get("id")? {
+            ScriptValue::Integer(id) => *id,
+            ScriptValue::Number(id) => *id as i64,
+            _ => return None,
+        };
+        let kind = match string_field(fields, "kind")?.as_str() {
+            "open" => DialogKind::OpenFile,
+            "save" => DialogKind::SaveFile,
+            _ => return None,
+        };
+
+        let filters = match fields.get("filters") {
+            Some(ScriptValue::Array(items)) => items.iter().filter_map(parse_filter).collect(),
+            _ => Vec::new(),
+        };
+
+        let data = match fields.get("data") {
+            Some(ScriptValue::String(text)) => Some(text.clone().into_bytes()),
+            Some(ScriptValue::Bytes(bytes)) => Some(bytes.clone()),
+            _ => None,
+        };
+
+        Some(Self {
+            callback_id,
+            kind,
+            title: string_field(fields, "title"),
+            filters,
+            multiple: kind == DialogKind::OpenFile && bool_field(fields, "multiple"),
+            default_name: string_field(fields, "default_name"),
+            directory: string_field(fields, "directory"),
+            read: bool_field(fields, "read"),
+            data,
+        })
+    }
+}
+
+fn string_field(fields: &HashMap<String, ScriptValue>, key: &str) -> Option<String> {
+    match fields.get(key) {
+        Some(ScriptValue::String(s)) if !s.is_empty() => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn bool_field(fields: &HashMap<String, ScriptValue>, key: &str) -> bool {
+    fields.get(key).map(|v| v.to_bool()).unwrap_or(false)
+}
+
+fn parse_filter(value: &ScriptValue) -> Option<FileFilter> {
+    let ScriptValue::Object(fields) = value else {
+        return None;
+    };
+    let extensions: Vec<String> = match fields.get("extensions")? {
+        ScriptValue::Array(items) => items.iter()
+            .filter_map(|ext| match ext {
+                ScriptValue::String(s) => Some(s.trim_start_matches('.').to_string()),
+                _ => None,
+            })
+            .collect(),
+        ScriptValue::String(s) => s.split(',').map(|ext| ext.trim().trim_start_matches('.').to_string()).collect(),
+        _ => return None,
+    };
+    if extensions.is_empty() {
+        return None;
+    }
+    let name = string_field(fields, "name").unwrap_or_else(|| extensions.join(", "));
+    Some(FileFilter { name, extensions })
+}
+
+/// A file chosen by the user. On web `path` is just the file name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedFile {
+    pub path: String,
+    pub data: Option<Vec<u8>>,
+}
+
+impl SelectedFile {
+    pub fn name(&self) -> &str {
+        self.path.rsplit(['/', '\\']).next().unwrap_or(&self.path)
+    }
+
+    fn to_script_value(&self) -> ScriptValue {
+        let mut fields = HashMap::new();
+        fields.insert("path".to_string(), ScriptValue::String(self.path.clone()));
+        fields.insert("name".to_string(), ScriptValue::String(self.name().to_string()));
+        if let Some(data) = &self.data {
+            fields.insert("data".to_string(), ScriptValue::Bytes(data.clone()));
+        }
+        ScriptValue::Object(fields)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogResult {
+    pub callback_id: i64,
+    /// Empty when the dialog was cancelled
+    pub files: Vec<SelectedFile>,
+    pub error: Option<String>,
+}
+
+impl DialogResult {
+    pub fn cancelled(callback_id: i64) -> Self {
+        Self { callback_id, files: Vec::new(), error: None }
+    }
+
+    pub fn failed(callback_id: i64, error: impl Into<String>) -> Self {
+        Self { callback_id, files: Vec::new(), error: Some(error.into()) }
+    }
+
+    /// Arguments for the bridge's `_deliver_dialog_result(id, files, error)`.
+    pub fn to_script_args(&self) -> Vec<ScriptValue> {
+        let files = if self.files.is_empty() {
+            ScriptValue::Nil
+        } else {
+            ScriptValue::Array(self.files.iter().map(SelectedFile::to_script_value).collect())
+        };
+        let error = self.error.clone().map(ScriptValue::String).unwrap_or(ScriptValue::Nil);
+        vec![ScriptValue::Integer(self.callback_id), files, error]
+    }
+}
+
+/// Shows dialogs off the frame loop and collects their results.
+pub struct DialogManager {
+    sender: Sender<DialogResult>,
+    receiver: Receiver<DialogResult>,
+    in_flight: usize,
+}
+
+impl DialogManager {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver, in_flight: 0 }
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.in_flight > 0
+    }
+
+    pub fn show(&mut self, request: DialogRequest) {
+        eprintln!("[DIALOG] {:?} requested (callback {})", request.kind, request.callback_id);
+        self.in_flight += 1;
+        backend::show(request, self.sender.clone());
+    }
+
+    /// Results that completed since the last poll.
+    pub fn poll(&mut self) -> Vec<DialogResult> {
+        if self.in_flight == 0 {
+            return Vec::new();
+        }
+        let results: Vec<DialogResult> = self.receiver.try_iter().collect();
+        self.in_flight = self.in_flight.saturating_sub(results.len());
+        results
+    }
+}
+
+impl Default for DialogManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(feature = "dialogs", not(target_arch = "wasm32")))]
+mod backend {
+    use super::*;
+
+    pub fn show(request: DialogRequest, sender: Sender<DialogResult>) {
+        std::thread::spawn(move || {
+            let result = pollster::block_on(run(request));
+            let _ = sender.send(result);
+        });
+    }
+
+    async fn run(request: DialogRequest) -> DialogResult {
+        let mut dialog = rfd::AsyncFileDialog::new();
+        if let Some(title) = &request.title {
+            dialog = dialog.set_title(title);
+        }
+        if let Some(directory) = &request.directory {
+            dialog = dialog.set_directory(directory);
+        }
+        if let Some(name) = &request.default_name {
+            dialog = dialog.set_file_name(name);
+        }
+        for filter in &request.filters {
+            dialog = dialog.add_filter(&filter.name, &filter.extensions);
+        }
+
+        let id = request.callback_id;
+        match request.kind {
+            DialogKind::OpenFile => {
+                let handles = if request.multiple {
+                    dialog.pick_files().await.unwrap_or_default()
+                } else {
+                    dialog.pick_file().await.into_iter().collect()
+                };
+
+                let mut files = Vec::with_capacity(handles.len());
+                for handle in handles {
+                    let data = if request.read { Some(handle.read().await) } else { None };
+                    files.push(SelectedFile { path: handle.path().display().to_string(), data });
+                }
+                DialogResult { callback_id: id, files, error: None }
+            }
+            DialogKind::SaveFile => {
+                let Some(handle) = dialog.save_file().await else {
+                    return DialogResult::cancelled(id);
+                };
+                if let Some(data) = &request.data {
+                    if let Err(e) = handle.write(data).await {
+                        return DialogResult::failed(id, format!("Failed to write {}: {}", handle.path().display(), e));
+                    }
+                }
+                DialogResult {
+                    callback_id: id,
+                    files: vec![SelectedFile { path: handle.path().display().to_string(), data: None }],
+                    error: None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use wasm_bindgen_futures::JsFuture;
+
+    pub fn show(request: DialogRequest, sender: Sender<DialogResult>) {
+        let id = request.callback_id;
+        let result = match request.kind {
+            DialogKind::OpenFile => open_file(request, sender.clone()),
+            DialogKind::SaveFile => save_file(&request).map(|result| {
+                let _ = sender.send(result);
+            }),
+        };
+        if let Err(e) = result {
+            let _ = sender.send(DialogResult::failed(id, format!("{:?}", e)));
+        }
+    }
+
+    fn document() -> Result<web_sys::Document, wasm_bindgen::JsValue> {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| wasm_bindgen::JsValue::from_str("no document"))
+    }
+
+    fn open_file(request: DialogRequest, sender: Sender<DialogResult>) -> Result<(), wasm_bindgen::JsValue> {
+        let input: web_sys::HtmlInputElement = document()?.create_element("input")?.dyn_into()?;
+        input.set_type("file");
+        input.set_multiple(request.multiple);
+        let accept: Vec<String> = request.filters.iter().map(FileFilter::to_accept).collect();
+        input.set_accept(&accept.join(","));
+
+        let id = request.callback_id;
+        let cancel_sender = sender.clone();
+        let target = input.clone();
+        let on_change = Closure::once(move || {
+            let Some(list) = target.files() else {
+                let _ = sender.send(DialogResult::cancelled(id));
+                return;
+            };
+            let chosen: Vec<web_sys::File> = (0..list.length()).filter_map(|i| list.get(i)).collect();
+            wasm_bindgen_futures::spawn_local(async move {
+                let mut files = Vec::with_capacity(chosen.len());
+                for file in chosen {
+                    let data = match JsFuture::from(file.array_buffer()).await {
+                        Ok(buffer) => js_sys::Uint8Array::new(&buffer).to_vec(),
+                        Err(e) => {
+                            let _ = sender.send(DialogResult::failed(id, format!("{:?}", e)));
+                            return;
+                        }
+                    };
+                    files.push(SelectedFile { path: file.name(), data: Some(data) });
+                }
+                let _ = sender.send(DialogResult { callback_id: id, files, error: None });
+            });
+        });
+        input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+        on_change.forget();
+
+        // Browsers report dismissal through `cancel`; older ones never fire it and
+        // the request simply stays in flight.
+        let on_cancel = Closure::once(move || {
+            let _ = cancel_sender.send(DialogResult::cancelled(id));
+        });
+        input.add_event_listener_with_callback("cancel", on_cancel.as_ref().unchecked_ref())?;
+        on_cancel.forget();
+
+        input.click();
+        Ok(())
+    }
+
+    fn save_file(request: &DialogRequest) -> Result<DialogResult, wasm_bindgen::JsValue> {
+        let id = request.callback_id;
+        let Some(data) = &request.data else {
+            return Ok(DialogResult::failed(id, "save_file on web requires `data` to download"));
+        };
+        let name = request.default_name.clone().unwrap_or_else(|| "download".to_string());
+
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data.as_slice()));
+        let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
+        let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+        let anchor: web_sys::HtmlAnchorElement = document()?.create_element("a")?.dyn_into()?;
+        anchor.set_href(&url);
+        anchor.set_download(&name);
+        anchor.click();
+        web_sys::Url::revoke_object_url(&url)?;
+
+        Ok(DialogResult {
+            callback_id: id,
+            files: vec![SelectedFile { path: name, data: None }],
+            error: None,
+        })
+    }
+}
+
+#[cfg(not(any(
+    all(feature = "dialogs", not(target_arch = "wasm32")),
+    all(feature = "web", target_arch = "wasm32"),
+)))]
+mod backend {
+    use super::*;
+
+    pub fn show(request: DialogRequest, sender: Sender<DialogResult>) {
+        eprintln!("[DIALOG] No dialog backend enabled (build with the `dialogs` feature); treating as cancelled");
+        let _ = sender.send(DialogResult::cancelled(request.callback_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(fields: Vec<(&str, ScriptValue)>) -> ScriptValue {
+        ScriptValue::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    fn strings(items: &[&str]) -> ScriptValue {
+        ScriptValue::Array(items.iter().map(|s| ScriptValue::from(*s)).collect())
+    }
+
+    #[test]
+    fn parses_open_request_with_filters() {
+        let value = object(vec![
+            ("id", ScriptValue::Integer(3)),
+            ("kind", "open".into()),
+            ("title", "Pick images".into()),
+            ("multiple", true.into()),
+            ("filters", ScriptValue::Array(vec![
+                object(vec![("name", "Images".into()), ("extensions", strings(&["png", ".jpg"]))]),
+                object(vec![("extensions", "csv, tsv".into())]),
+                object(vec![("name", "Empty".into()), ("extensions", strings(&[]))]),
+            ])),
+        ]);
+
+        let request = DialogRequest::from_script_value(&value).unwrap();
+        assert_eq!(request.callback_id, 3);
+        assert_eq!(request.kind, DialogKind::OpenFile);
+        assert_eq!(request.title.as_deref(), Some("Pick images"));
+        assert!(request.multiple);
+        assert_eq!(request.filters.len(), 2);
+        assert_eq!(request.filters[0].extensions, vec!["png", "jpg"]);
+        assert_eq!(request.filters[1].name, "csv, tsv");
+    }
+
+    #[test]
+    fn save_requests_ignore_multiple_and_keep_data() {
+        let value = object(vec![
+            ("id", ScriptValue::Number(7.0)),
+            ("kind", "save".into()),
+            ("multiple", true.into()),
+            ("default_name", "notes.txt".into()),
+            ("data", "hello".into()),
+        ]);
+
+        let request = DialogRequest::from_script_value(&value).unwrap();
+        assert_eq!(request.kind, DialogKind::SaveFile);
+        assert!(!request.multiple);
+        assert_eq!(request.default_name.as_deref(), Some("notes.txt"));
+        assert_eq!(request.data.as_deref(), Some(&b"hello"[..]));
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        assert!(DialogRequest::from_script_value(&ScriptValue::Nil).is_none());
+        assert!(DialogRequest::from_script_value(&object(vec![("kind", "open".into())])).is_none());
+        assert!(DialogRequest::from_script_value(&object(vec![
+            ("id", ScriptValue::Integer(1)),
+            ("kind", "folder".into()),
+        ])).is_none());
+    }
+
+    #[test]
+    fn filters_build_html_accept() {
+        let filter = FileFilter { name: "Images".into(), extensions: vec!["png".into(), "jpg".into()] };
+        assert_eq!(filter.to_accept(), ".png,.jpg");
+        let any = FileFilter { name: "All".into(), extensions: vec!["*".into()] };
+        assert_eq!(any.to_accept(), "*/*");
+    }
+
+    #[test]
+    fn results_convert_to_callback_arguments() {
+        let result = DialogResult {
+            callback_id: 2,
+            files: vec![SelectedFile { path: "/tmp/data.bin".into(), data: Some(vec![0, 159, 255]) }],
+            error: None,
+        };
+        let args = result.to_script_args();
+        assert_eq!(args[0], ScriptValue::Integer(2));
+        let ScriptValue::Array(files) = &args[1] else { panic!("expected file list") };
+        let ScriptValue::Object(file) = &files[0] else { panic!("expected file object") };
+        assert_eq!(file.get("name"), Some(&ScriptValue::from("data.bin")));
+        assert_eq!(file.get("data"), Some(&ScriptValue::Bytes(vec![0, 159, 255])));
+
+        assert_eq!(DialogResult::cancelled(4).to_script_args()[1], ScriptValue::Nil);
+    }
+}
@@ -0,0 +1,242 @@
+// crates/kryon-runtime/src/drag_drop.rs
+//! Dragging elements and dropping them on others.
+//!
+//! Elements take part through their properties:
+//!
+//! - `draggable: true` lets the element be dragged; a press on any of its
+//!   descendants drags it too
+//! - `drag_data`: the payload handed to the drop target, the element's `id`
+//!   without one
+//! - `drag_type`: what kind of thing the element is, for drop targets to
+//!   pick what they take
+//! - `drop_target`: `true` to take any drag, or the `drag_type`s it takes,
+//!   separated by commas
+//! - `drag_ghost_opacity`: how opaque the copy following the pointer is
+//!   (default 0.6)
+//!
+//! A drag starts once the pointer has moved [`DRAG_THRESHOLD`] pixels from
+//! a press on a draggable element, so clicks stay clicks. `onDragStart` runs
+//! on the dragged element then; it can return a value to send instead of
+//! `drag_data`, or `false` to not drag after all. While the button is held,
+//! a ghost of the element follows the pointer and `onDragOver` runs on the
+//! innermost drop target under it with every move; returning `false` turns
+//! the drag away. Releasing over a target that took it runs the target's
+//! `onDrop`. Releasing anywhere else, or pressing Escape, cancels the drag.
+//! A drag never ends in a click.
+
+use crate::script::engine_trait::ScriptValue;
+use glam::Vec2;
+use kryon_core::{Element, ElementId, PropertyValue};
+use kryon_render::drag_ghost::{DragGhost, DEFAULT_GHOST_OPACITY};
+use std::collections::HashMap;
+
+/// How far the pointer moves from a press before it becomes a drag
+pub const DRAG_THRESHOLD: f32 = 4.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drag {
+    pub source: ElementId,
+    pub drag_type: Option<String>,
+    pub data: ScriptValue,
+    /// Where the press that started the drag was
+    pub origin: Vec2,
+    pub position: Vec2,
+    /// The drop target under the pointer, if it took the drag
+    pub target: Option<ElementId>,
+}
+
+impl Drag {
+    /// A drag of `source` from a press at `origin`, carrying its
+    /// `drag_data`
+    pub fn new(source: ElementId, element: &Element, origin: Vec2) -> Self {
+        let data = match element.custom_properties.get("drag_data") {
+            Some(PropertyValue::String(data)) => ScriptValue::String(data.clone()),
+            Some(PropertyValue::Int(data)) => ScriptValue::Integer(*data as i64),
+            Some(PropertyValue::Float(data)) => ScriptValue::Number(*data as f64),
+            _ => ScriptValue::String(element.id.clone()),
+        };
+        let drag_type = element.custom_properties.get("drag_type").and_then(|value| value.as_string()).map(str::to_string);
+        Self { source, drag_type, data, origin, position: origin, target: None }
+    }
+}
+
+#[derive(Debug, Default)]
+enum State {
+    #[default]
+    Idle,
+    /// The button went down on `source` and hasn't moved far yet
+    Pressed { source: ElementId, origin: Vec2 },
+    Dragging(Drag),
+}
+
+#[derive(Debug, Default)]
+pub struct DragDrop {
+    state: State,
+}
+
+impl DragDrop {
+    /// The button went down at `position` on the draggable `source`
+    pub fn press(&mut self, source: ElementId, position: Vec2) {
+        self.state = State::Pressed { source, origin: position };
+    }
+
+    /// The pressed element and where the press was, once the pointer at
+    /// `position` has moved far enough to start dragging it
+    pub fn pending_start(&self, position: Vec2) -> Option<(ElementId, Vec2)> {
+        match self.state {
+            State::Pressed { source, origin } if position.distance(origin) >= DRAG_THRESHOLD => Some((source, origin)),
+            _ => None,
+        }
+    }
+
+    pub fn start(&mut self, drag: Drag) {
+        self.state = State::Dragging(drag);
+    }
+
+    pub fn drag(&self) -> Option<&Drag> {
+        match &self.state {
+            State::Dragging(drag) => Some(drag),
+            _ => None,
+        }
+    }
+
+    pub fn drag_mut(&mut self) -> Option<&mut Drag> {
+        match &mut self.state {
+            State::Dragging(drag) => Some(drag),
+            _ => None,
+        }
+    }
+
+    /// The button went up: the drag, if there was one, to drop
+    pub fn release(&mut self) -> Option<Drag> {
+        match std::mem::take(&mut self.state) {
+            State::Dragging(drag) => Some(drag),
+            _ => None,
+        }
+    }
+
+    /// Drops any press or drag; whether there was a drag
+    pub fn cancel(&mut self) -> bool {
+        matches!(std::mem::take(&mut self.state), State::Dragging(_))
+    }
+
+    /// Cancels what involves one of `removed`; whether a drag was cancelled
+    pub fn forget(&mut self, removed: &[ElementId]) -> bool {
+        let involved = match &mut self.state {
+            State::Idle => false,
+            State::Pressed { source, .. } => removed.contains(source),
+            State::Dragging(drag) => {
+                drag.target = drag.target.filter(|target| !removed.contains(target));
+                removed.contains(&drag.source)
+            }
+        };
+        involved && self.cancel()
+    }
+
+    /// The ghost to draw for the drag under way
+    pub fn ghost(&self, elements: &HashMap<ElementId, Element>) -> Option<DragGhost> {
+        let drag = self.drag()?;
+        let opacity = elements.get(&drag.source)
+            .and_then(|element| element.custom_properties.get("drag_ghost_opacity"))
+            .and_then(|value| value.as_float())
+            .map_or(DEFAULT_GHOST_OPACITY, |opacity| opacity.clamp(0.0, 1.0));
+        Some(DragGhost { element: drag.source, offset: drag.position - drag.origin, opacity })
+    }
+}
+
+/// Whether a property holding `true`, `1` or `"true"` is set
+fn is_set(element: &Element, property: &str) -> bool {
+    match element.custom_properties.get(property) {
+        Some(PropertyValue::Bool(set)) => *set,
+        Some(PropertyValue::Int(value)) => *value != 0,
+        Some(PropertyValue::String(value)) => value == "true",
+        _ => false,
+    }
+}
+
+/// The element a press on `path` (innermost first) drags, if any
+pub fn draggable_in(elements: &HashMap<ElementId, Element>, path: &[ElementId]) -> Option<ElementId> {
+    path.iter().copied().find(|id| elements.get(id).is_some_and(|element| element.visible && is_set(element, "draggable")))
+}
+
+/// Whether `element` is a drop target taking drags of `drag_type`
+pub fn accepts(element: &Element, drag_type: Option<&str>) -> bool {
+    match element.custom_properties.get("drop_target") {
+        Some(PropertyValue::String(types)) if types != "true" && types != "false" => {
+            drag_type.is_some_and(|drag_type| types.split(',').any(|accepted| accepted.trim() == drag_type))
+        }
+        _ => is_set(element, "drop_target"),
+    }
+}
+
+/// The innermost drop target on `path` (innermost first) that takes
+/// `drag`. The dragged element and its descendants are passed over.
+pub fn drop_target_in(elements: &HashMap<ElementId, Element>, path: &[ElementId], drag: &Drag) -> Option<ElementId> {
+    let outside_source = match path.iter().position(|&id| id == drag.source) {
+        Some(index) => &path[index + 1..],
+        None => path,
+    };
+    outside_source.iter().copied()
+        .find(|id| elements.get(id).is_some_and(|element| accepts(element, drag.drag_type.as_deref())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(id: &str, properties: &[(&str, PropertyValue)]) -> Element {
+        Element {
+            id: id.to_string(),
+            custom_properties: properties.iter().map(|(name, value)| (name.to_string(), value.clone())).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn presses_become_drags_past_the_threshold() {
+        let card = element("card", &[("drag_data", PropertyValue::String("task-7".into())), ("drag_ghost_opacity", PropertyValue::Float(0.3))]);
+        let elements = HashMap::from([(4, card.clone())]);
+        let mut drag_drop = DragDrop::default();
+        drag_drop.press(4, Vec2::new(10.0, 10.0));
+        assert_eq!(drag_drop.pending_start(Vec2::new(12.0, 11.0)), None);
+        assert_eq!(drag_drop.pending_start(Vec2::new(14.0, 10.0)), Some((4, Vec2::new(10.0, 10.0))));
+
+        let drag = Drag::new(4, &card, Vec2::new(10.0, 10.0));
+        assert_eq!(drag.data, ScriptValue::from("task-7"));
+        drag_drop.start(drag);
+        drag_drop.drag_mut().unwrap().position = Vec2::new(60.0, 30.0);
+        assert_eq!(drag_drop.ghost(&elements), Some(DragGhost { element: 4, offset: Vec2::new(50.0, 20.0), opacity: 0.3 }));
+        assert_eq!(drag_drop.pending_start(Vec2::new(90.0, 90.0)), None, "a drag under way doesn't start again");
+
+        assert_eq!(drag_drop.release().map(|drag| drag.source), Some(4));
+        assert!(drag_drop.release().is_none() && drag_drop.ghost(&elements).is_none());
+
+        drag_drop.press(4, Vec2::ZERO);
+        assert!(drag_drop.release().is_none(), "a press that never moved is a click");
+    }
+
+    #[test]
+    fn drop_targets_take_their_drag_types_outside_the_source() {
+        let elements = HashMap::from([
+            (1, element("board", &[("drop_target", PropertyValue::String("true".into()))])),
+            (2, element("done", &[("drop_target", PropertyValue::String("task, note".into()))])),
+            (3, element("card", &[("draggable", PropertyValue::Int(1)), ("drag_type", PropertyValue::String("task".into())), ("drop_target", PropertyValue::Bool(true))])),
+            (4, element("label", &[])),
+            (5, element("trash", &[("drop_target", PropertyValue::String("file".into()))])),
+        ]);
+        assert_eq!(draggable_in(&elements, &[4, 3, 1]), Some(3));
+        assert_eq!(draggable_in(&elements, &[2, 1]), None);
+
+        let task = Drag::new(3, &elements[&3], Vec2::ZERO);
+        assert_eq!(drop_target_in(&elements, &[4, 3, 2, 1], &task), Some(2), "the card can't be dropped on itself");
+        assert_eq!(drop_target_in(&elements, &[5, 1], &task), Some(1));
+        assert!(!accepts(&elements[&2], None));
+        assert_eq!(task.data, ScriptValue::from("card"));
+
+        let mut drag_drop = DragDrop::default();
+        drag_drop.start(Drag { target: Some(2), ..task });
+        assert!(!drag_drop.forget(&[2]));
+        assert_eq!(drag_drop.drag().unwrap().target, None);
+        assert!(drag_drop.forget(&[3]) && drag_drop.drag().is_none());
+    }
+}
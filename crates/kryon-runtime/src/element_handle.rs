@@ -0,0 +1,250 @@
+// crates/kryon-runtime/src/element_handle.rs
+//! Changing elements from Rust.
+//!
+//! [`KryonApp::element`] returns an [`ElementHandle`], whose setters change
+//! the element and take care of what has to follow: a new layout or just a
+//! repaint, the scripts' copy of the element tree on the next frame, and
+//! the string id index when elements are added or removed. Embedders use it
+//! instead of editing the element map and marking the app dirty by hand.
+//!
+//! Every change is an [`ElementChange`]. Scripts' queued `setText`,
+//! `setVisible`, `setStyle` and `setChecked` calls and the REPL's `set` are
+//! applied through the same values, so an element changes the same way
+//! whoever changed it.
+
+use crate::KryonApp;
+use anyhow::{anyhow, bail, Result};
+use kryon_core::{Element, ElementId, InteractionState, PropertyValue, TransformOrigin};
+use kryon_render::CommandRenderer;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementChange {
+    Text(String),
+    Visible(bool),
+    /// Checkboxes and radio buttons
+    Checked(bool),
+    /// The style block, by its id in the KRB
+    Style(u8),
+    /// A property by name, from its text: the fields the REPL's `set` knows,
+    /// such as `font_size` or `background_color`, or else a custom property
+    /// holding the text
+    Property { name: String, value: String },
+}
+
+impl ElementChange {
+    /// Makes the change to `element`. A property value that can't be read
+    /// as the property's type fails and leaves the element as it was.
+    pub fn apply(&self, element: &mut Element) -> Result<()> {
+        match self {
+            Self::Text(text) => element.text = text.clone(),
+            Self::Visible(visible) => element.visible = *visible,
+            Self::Checked(checked) => {
+                element.current_state = if *checked { InteractionState::Checked } else { InteractionState::Normal };
+            }
+            Self::Style(style_id) => element.style_id = *style_id,
+            Self::Property { name, value } => set_property(element, name, value)?,
+        }
+        Ok(())
+    }
+
+    /// Whether the change can move or resize elements, rather than only
+    /// repaint this one
+    pub fn affects_layout(&self) -> bool {
+        match self {
+            Self::Checked(_) => false,
+            Self::Property { name, .. } => !matches!(
+                name.as_str(),
+                "opacity" | "background_color" | "text_color" | "border_color" | "transform_origin"
+            ),
+            _ => true,
+        }
+    }
+}
+
+fn set_property(element: &mut Element, property: &str, value: &str) -> Result<()> {
+    let number = || value.parse::<f32>().map_err(|_| anyhow!("'{}' is not a number", value));
+    let color = || kryon_core::parse_color(value).ok_or_else(|| anyhow!("'{}' is not a CSS color", value));
+
+    match property {
+        "text" => element.text = value.to_string(),
+        "visible" => element.visible = value.parse().map_err(|_| anyhow!("'{}' is not true or false", value))?,
+        "font_size" => element.font_size = number()?,
+        "opacity" => element.opacity = number()?,
+        "background_color" => element.background_color = color()?,
+        "text_color" => element.text_color = color()?,
+        "border_color" => element.border_color = color()?,
+        "transform_origin" => {
+            let origin = TransformOrigin::parse(value)
+                .ok_or_else(|| anyhow!("'{}' is not a transform origin", value))?;
+            if let Some(PropertyValue::Transform(transform)) = element.custom_properties.get_mut("transform") {
+                transform.origin = origin;
+            }
+            element.custom_properties.insert(property.to_string(), PropertyValue::String(value.to_string()));
+        }
+        _ => {
+            element.custom_properties.insert(property.to_string(), PropertyValue::String(value.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// An element of a [`KryonApp`], for changing it. Setters return the
+/// handle so calls chain:
+///
+/// ```ignore
+/// app.element("status").unwrap().set_text("Saved").set_visible(true);
+/// ```
+pub struct ElementHandle<'a, R: CommandRenderer> {
+    app: &'a mut KryonApp<R>,
+    element_id: ElementId,
+}
+
+impl<'a, R: CommandRenderer> ElementHandle<'a, R> {
+    /// `element_id` must be in the app's elements
+    pub(crate) fn new(app: &'a mut KryonApp<R>, element_id: ElementId) -> Self {
+        Self { app, element_id }
+    }
+
+    pub fn id(&self) -> ElementId {
+        self.element_id
+    }
+
+    pub fn element(&self) -> &Element {
+        &self.app.elements[&self.element_id]
+    }
+
+    /// Makes `change`, then marks the app for a layout or a repaint as it
+    /// needs
+    pub fn apply(&mut self, change: ElementChange) -> Result<&mut Self> {
+        let element = self.app.elements.get_mut(&self.element_id).expect("handles are made for elements that exist");
+        change.apply(element)?;
+        self.app.element_changed(change.affects_layout());
+        Ok(self)
+    }
+
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.apply(ElementChange::Text(text.into())).expect("only property changes can fail")
+    }
+
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.apply(ElementChange::Visible(visible)).expect("only property changes can fail")
+    }
+
+    pub fn set_checked(&mut self, checked: bool) -> &mut Self {
+        self.apply(ElementChange::Checked(checked)).expect("only property changes can fail")
+    }
+
+    /// Switches the element to the KRB's style block named `name`
+    pub fn set_style_class(&mut self, name: &str) -> Result<&mut Self> {
+        let style_id = self.app.krb_file.styles.iter()
+            .find(|(_, style)| style.name == name)
+            .map(|(&style_id, _)| style_id)
+            .ok_or_else(|| anyhow!("No style named '{}'", name))?;
+        self.apply(ElementChange::Style(style_id))
+    }
+
+    /// See [`ElementChange::Property`]
+    pub fn set_property(&mut self, name: &str, value: &str) -> Result<&mut Self> {
+        self.apply(ElementChange::Property { name: name.to_string(), value: value.to_string() })
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = (ElementId, &Element)> + '_ {
+        self.element().children.iter().filter_map(|id| Some((*id, self.app.elements.get(id)?)))
+    }
+
+    pub fn child(&mut self, index: usize) -> Option<ElementHandle<'_, R>> {
+        let child_id = *self.element().children.get(index)?;
+        Some(ElementHandle::new(self.app, child_id))
+    }
+
+    pub fn parent(self) -> Option<ElementHandle<'a, R>> {
+        let parent_id = self.element().parent?;
+        Some(ElementHandle::new(self.app, parent_id))
+    }
+
+    /// Adds `element` as this one's last child, returning a handle to it.
+    /// Its `parent` and `children` are set to fit; add its own children
+    /// through the handle.
+    pub fn append_child(&mut self, element: Element) -> ElementHandle<'_, R> {
+        let child_id = self.app.allocate_element_id();
+        let indexed = !element.id.is_empty();
+        insert_child(&mut self.app.elements, self.element_id, child_id, element);
+        if indexed {
+            self.app.reindex_elements();
+        }
+        self.app.element_changed(true);
+        ElementHandle::new(self.app, child_id)
+    }
+
+    /// Removes the element and everything below it. The root can't be
+    /// removed.
+    pub fn remove(self) -> Result<()> {
+        if self.app.krb_file.root_element_id == Some(self.element_id) {
+            bail!("The root element can't be removed");
+        }
+        let removed = remove_subtree(&mut self.app.elements, self.element_id);
+        self.app.forget_elements(&removed);
+        Ok(())
+    }
+}
+
+/// Adds `element` to the tree as `parent`'s last child, numbered `element_id`
+pub(crate) fn insert_child(elements: &mut HashMap<ElementId, Element>, parent: ElementId, element_id: ElementId, mut element: Element) {
+    element.parent = Some(parent);
+    element.children.clear();
+    elements.insert(element_id, element);
+    if let Some(parent) = elements.get_mut(&parent) {
+        parent.children.push(element_id);
+    }
+}
+
+/// Takes `element_id` and its descendants out of the tree, returning their
+/// ids
+pub(crate) fn remove_subtree(elements: &mut HashMap<ElementId, Element>, element_id: ElementId) -> Vec<ElementId> {
+    if let Some(parent) = elements.get(&element_id).and_then(|element| element.parent) {
+        if let Some(parent) = elements.get_mut(&parent) {
+            parent.children.retain(|&child| child != element_id);
+        }
+    }
+    let mut removed = Vec::new();
+    let mut pending = vec![element_id];
+    while let Some(id) = pending.pop() {
+        if let Some(element) = elements.remove(&id) {
+            pending.extend(element.children);
+            removed.push(id);
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_apply_to_the_element_and_subtrees_come_and_go() {
+        let mut element = Element::default();
+        ElementChange::Text("Saved".into()).apply(&mut element).unwrap();
+        ElementChange::Checked(true).apply(&mut element).unwrap();
+        ElementChange::Property { name: "font_size".into(), value: "18".into() }.apply(&mut element).unwrap();
+        assert_eq!((element.text.as_str(), element.current_state, element.font_size), ("Saved", InteractionState::Checked, 18.0));
+
+        let bad = ElementChange::Property { name: "opacity".into(), value: "half".into() };
+        assert_eq!(bad.apply(&mut element).unwrap_err().to_string(), "'half' is not a number");
+        assert!(!bad.affects_layout() && !ElementChange::Checked(false).affects_layout());
+        assert!(ElementChange::Visible(false).affects_layout());
+
+        let mut elements = HashMap::from([(0, Element::default())]);
+        insert_child(&mut elements, 0, 1, Element { children: vec![7], ..Default::default() });
+        insert_child(&mut elements, 1, 2, Element::default());
+        insert_child(&mut elements, 0, 3, Element::default());
+        assert_eq!((elements[&0].children.clone(), elements[&1].children.clone(), elements[&2].parent), (vec![1, 3], vec![2], Some(1)));
+
+        let mut removed = remove_subtree(&mut elements, 1);
+        removed.sort_unstable();
+        assert_eq!(removed, vec![1, 2]);
+        assert_eq!(elements[&0].children, vec![3]);
+        assert_eq!(elements.len(), 2);
+    }
+}
@@ -0,0 +1,116 @@
+// crates/kryon-runtime/src/element_index.rs
+//! Lookup of elements by their string `id`.
+//!
+//! Numeric [`ElementId`]s are positions in the KRB's element table: they
+//! follow document order, so they are stable for one build of a KRB but
+//! shift whenever elements are added or removed before an element in the
+//! source. String ids are what stays stable across builds, and what
+//! scripts, the console and hosts should hold on to.
+//!
+//! The runtime rebuilds the index whenever the element tree is replaced,
+//! on start and on hot reload, so lookups after a reload resolve to the
+//! new tree's elements. Elements from included `.kry` files are compiled
+//! into the same KRB and numbered with the rest. When several elements
+//! share an id, the first in document order (the lowest `ElementId`) is
+//! the one found, every time.
+
+use kryon_core::{Element, ElementId};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Clone)]
+pub struct ElementIndex {
+    by_id: HashMap<String, ElementId>,
+    /// Elements whose id an earlier element already has
+    shadowed: Vec<(String, ElementId)>,
+}
+
+impl ElementIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_elements(elements: &HashMap<ElementId, Element>) -> Self {
+        let mut index = Self::new();
+        index.rebuild(elements);
+        index
+    }
+
+    /// Indexes `elements` from scratch, after the tree changed shape
+    pub fn rebuild(&mut self, elements: &HashMap<ElementId, Element>) {
+        let mut named: Vec<(ElementId, &str)> = elements.iter()
+            .filter(|(_, element)| !element.id.is_empty())
+            .map(|(&element_id, element)| (element_id, element.id.as_str()))
+            .collect();
+        named.sort_unstable_by_key(|&(element_id, _)| element_id);
+
+        self.by_id.clear();
+        self.shadowed.clear();
+        for (element_id, id) in named {
+            if self.by_id.contains_key(id) {
+                self.shadowed.push((id.to_string(), element_id));
+            } else {
+                self.by_id.insert(id.to_string(), element_id);
+            }
+        }
+    }
+
+    /// The element with string id `id`
+    pub fn get(&self, id: &str) -> Option<ElementId> {
+        self.by_id.get(id).copied()
+    }
+
+    /// Every indexed string id with its element
+    pub fn ids(&self) -> &HashMap<String, ElementId> {
+        &self.by_id
+    }
+
+    /// Elements that can't be found by id because an earlier element has
+    /// the same one, in document order
+    pub fn shadowed(&self) -> &[(String, ElementId)] {
+        &self.shadowed
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(id: &str) -> Element {
+        Element { id: id.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn finds_elements_by_id_and_skips_unnamed_ones() {
+        let elements = [(0, named("app")), (1, named("")), (2, named("save"))].into_iter().collect();
+        let index = ElementIndex::from_elements(&elements);
+        assert_eq!(index.get("save"), Some(2));
+        assert_eq!(index.get("app"), Some(0));
+        assert_eq!(index.get(""), None);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn duplicate_ids_resolve_to_the_first_in_document_order() {
+        let elements: HashMap<ElementId, Element> = (0..20).map(|element_id| (element_id, named("row"))).collect();
+        let index = ElementIndex::from_elements(&elements);
+        assert_eq!(index.get("row"), Some(0));
+        assert_eq!(index.shadowed().len(), 19);
+        assert_eq!(index.shadowed()[0], ("row".to_string(), 1));
+    }
+
+    #[test]
+    fn rebuild_follows_a_reloaded_tree() {
+        let mut index = ElementIndex::from_elements(&[(3, named("title"))].into_iter().collect());
+        index.rebuild(&[(0, named("banner")), (4, named("title"))].into_iter().collect());
+        assert_eq!(index.get("title"), Some(4));
+        assert_eq!(index.get("banner"), Some(0));
+    }
+}
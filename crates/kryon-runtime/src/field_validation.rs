@@ -0,0 +1,143 @@
+// crates/kryon-runtime/src/field_validation.rs
+//! Constraint validation for text `Input` elements.
+//!
+//! An input's `required`, `pattern`, `min_length` and `max_length`
+//! properties constrain its value as the HTML attributes of the same names
+//! do: an empty value only fails `required`, `pattern` has to match all of
+//! it, and lengths count characters. The runtime checks an input when it
+//! loses focus, then after every change once it has been checked, so a
+//! field isn't flagged while it's first typed into. A failing input gets
+//! the `invalid` property, which `StyleComputer` styles with the input's
+//! `name:invalid` block, and an `error_message` drawn below it: the
+//! input's `validation_message` if it has one, else what failed.
+
+use kryon_core::{Element, ElementType, PropertyValue};
+use kryon_render::text_input::TEXT_INPUT_TYPES;
+use regex::Regex;
+use std::fmt;
+
+/// The first constraint a value fails, in the order they're checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidityError {
+    ValueMissing,
+    TooShort { min: usize },
+    TooLong { max: usize },
+    PatternMismatch,
+}
+
+impl fmt::Display for ValidityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidityError::ValueMissing => write!(f, "Please fill in this field."),
+            ValidityError::TooShort { min } => write!(f, "Please use at least {} characters.", min),
+            ValidityError::TooLong { max } => write!(f, "Please use at most {} characters.", max),
+            ValidityError::PatternMismatch => write!(f, "Please match the requested format."),
+        }
+    }
+}
+
+/// The constraints on an input's value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    pub required: bool,
+    /// A regular expression the whole value has to match
+    pub pattern: Option<String>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+}
+
+impl Constraints {
+    /// The constraints a text input's properties set; none for other elements
+    pub fn from_element(element: &Element) -> Self {
+        let input_type = element.custom_properties.get("input_type").and_then(|value| value.as_string()).unwrap_or("text");
+        if element.element_type != ElementType::Input || !TEXT_INPUT_TYPES.contains(&input_type) {
+            return Self::default();
+        }
+        let property = |name: &str| element.custom_properties.get(name);
+        let length = |name: &str| property(name).and_then(|value| value.as_float()).filter(|length| *length >= 0.0).map(|length| length as usize);
+        Self {
+            required: matches!(property("required"), Some(PropertyValue::Bool(true))),
+            pattern: property("pattern").and_then(|value| value.as_string()).filter(|pattern| !pattern.is_empty()).map(str::to_string),
+            min_length: length("min_length"),
+            max_length: length("max_length"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks `value` against each constraint. A pattern that isn't a valid
+    /// regular expression is ignored, as browsers do.
+    pub fn check(&self, value: &str) -> Result<(), ValidityError> {
+        if value.is_empty() {
+            return if self.required { Err(ValidityError::ValueMissing) } else { Ok(()) };
+        }
+        let length = value.chars().count();
+        if let Some(min) = self.min_length.filter(|&min| length < min) {
+            return Err(ValidityError::TooShort { min });
+        }
+        if let Some(max) = self.max_length.filter(|&max| length > max) {
+            return Err(ValidityError::TooLong { max });
+        }
+        let pattern = self.pattern.as_ref().and_then(|pattern| Regex::new(&format!("^(?:{})$", pattern)).ok());
+        if pattern.is_some_and(|pattern| !pattern.is_match(value)) {
+            return Err(ValidityError::PatternMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Why an element's value fails its constraints, as shown below it; `None`
+/// if it passes or has none
+pub fn validation_message(element: &Element) -> Option<String> {
+    let error = Constraints::from_element(element).check(&element.text).err()?;
+    let custom = element.custom_properties.get("validation_message").and_then(|value| value.as_string()).filter(|message| !message.is_empty());
+    Some(custom.map_or_else(|| error.to_string(), str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(properties: &[(&str, PropertyValue)]) -> Element {
+        Element {
+            element_type: ElementType::Input,
+            custom_properties: properties.iter().map(|(name, value)| (name.to_string(), value.clone())).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn checks_values_like_html_constraint_validation() {
+        let constraints = Constraints::from_element(&input(&[
+            ("required", PropertyValue::Bool(true)),
+            ("pattern", PropertyValue::String("[a-z]+\\d".into())),
+            ("min_length", PropertyValue::Int(3)),
+            ("max_length", PropertyValue::Int(6)),
+        ]));
+        assert_eq!(constraints.check(""), Err(ValidityError::ValueMissing));
+        assert_eq!(constraints.check("a1"), Err(ValidityError::TooShort { min: 3 }));
+        assert_eq!(constraints.check("abcdef1"), Err(ValidityError::TooLong { max: 6 }));
+        assert_eq!(constraints.check("abc1x"), Err(ValidityError::PatternMismatch), "the pattern has to match the whole value");
+        assert_eq!(constraints.check("abc1"), Ok(()));
+
+        let optional = Constraints { required: false, ..constraints };
+        assert_eq!(optional.check(""), Ok(()), "empty values only fail required");
+        let broken = Constraints { pattern: Some("[".into()), ..Default::default() };
+        assert_eq!(broken.check("anything"), Ok(()));
+    }
+
+    #[test]
+    fn messages_come_from_the_error_or_the_input() {
+        let mut element = input(&[("required", PropertyValue::Bool(true))]);
+        assert_eq!(validation_message(&element).as_deref(), Some("Please fill in this field."));
+        element.custom_properties.insert("validation_message".into(), PropertyValue::String("Name is required".into()));
+        assert_eq!(validation_message(&element).as_deref(), Some("Name is required"));
+        element.text = "Ada".into();
+        assert_eq!(validation_message(&element), None);
+
+        let checkbox = input(&[("input_type", PropertyValue::String("checkbox".into())), ("required", PropertyValue::Bool(true))]);
+        assert!(Constraints::from_element(&checkbox).is_empty());
+    }
+}
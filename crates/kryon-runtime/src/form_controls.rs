@@ -0,0 +1,169 @@
+// crates/kryon-runtime/src/form_controls.rs
+//! Checkboxes, radio buttons and sliders: Input elements whose
+//! `input_type` is `checkbox`, `radio` or `range`.
+//!
+//! A click toggles a checkbox. A click checks a radio button and unchecks
+//! the other radio buttons with the same `name`; clicking a checked one
+//! does nothing. A press on a slider moves its thumb under the pointer,
+//! and the thumb follows the pointer until the button is released. A
+//! slider's value stays between its `min` and `max` (0 and 100 unless
+//! set), on a whole number of `step`s from `min` when it has a `step`.
+//!
+//! Whether a checkbox or radio button is checked is its `Checked`
+//! interaction state; a slider's value is its `value` property. A change
+//! goes into the template variable the element's `value_variable` names
+//! (`true` or `false` for a checkbox, the checked radio button's `value`
+//! or `id` for a radio group, the number for a slider) and the element's
+//! `onChange` runs. Controls that are `disabled` or `readonly` don't
+//! change.
+
+use kryon_core::{Element, ElementType, InteractionState, PropertyValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Checkbox,
+    Radio,
+    Slider,
+}
+
+/// What kind of control `element` is, if it's one that can be changed
+pub fn control(element: &Element) -> Option<Control> {
+    if element.element_type != ElementType::Input {
+        return None;
+    }
+    let flag = |name: &str| matches!(element.custom_properties.get(name), Some(PropertyValue::Bool(true)));
+    if flag("disabled") || flag("readonly") {
+        return None;
+    }
+    match element.custom_properties.get("input_type").and_then(|value| value.as_string())? {
+        "checkbox" => Some(Control::Checkbox),
+        "radio" => Some(Control::Radio),
+        "range" => Some(Control::Slider),
+        _ => None,
+    }
+}
+
+pub fn is_checked(element: &Element) -> bool {
+    element.current_state == InteractionState::Checked
+}
+
+/// The `name` shared by the radio buttons of `element`'s group
+pub fn radio_group(element: &Element) -> Option<&str> {
+    element.custom_properties.get("name").and_then(|value| value.as_string()).filter(|name| !name.is_empty())
+}
+
+/// What a checked radio button puts in its group's `value_variable`
+pub fn radio_value(element: &Element) -> String {
+    match element.custom_properties.get("value") {
+        Some(PropertyValue::String(value)) => value.clone(),
+        Some(value) => value.as_float().map(format_value).unwrap_or_else(|| element.id.clone()),
+        None => element.id.clone(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliderRange {
+    pub min: f32,
+    pub max: f32,
+    pub step: Option<f32>,
+}
+
+impl SliderRange {
+    /// The range of the slider `element`, with the defaults the renderer
+    /// draws it with
+    pub fn of(element: &Element) -> Self {
+        let number = |name: &str| element.custom_properties.get(name).and_then(number);
+        let min = number("min").unwrap_or(0.0);
+        let max = number("max").unwrap_or(100.0).max(min);
+        let step = number("step").filter(|step| *step > 0.0);
+        Self { min, max, step }
+    }
+
+    /// `value` moved onto the range and its steps
+    pub fn clamp(&self, value: f32) -> f32 {
+        let value = value.clamp(self.min, self.max);
+        let Some(step) = self.step else {
+            return value;
+        };
+        let steps = ((value - self.min) / step).round();
+        let max_steps = ((self.max - self.min) / step).floor();
+        self.min + steps.min(max_steps) * step
+    }
+
+    /// The value `fraction` of the way along the track
+    pub fn value_at(&self, fraction: f32) -> f32 {
+        self.clamp(self.min + fraction.clamp(0.0, 1.0) * (self.max - self.min))
+    }
+}
+
+/// The value of the slider `element`, kept in its range
+pub fn slider_value(element: &Element) -> f32 {
+    let range = SliderRange::of(element);
+    let value = element.custom_properties.get("value").and_then(number).unwrap_or(range.min);
+    range.clamp(value)
+}
+
+/// A slider value as scripts and template variables see it: `40`, `0.25`
+pub fn format_value(value: f32) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// Numbers written as numbers or as strings, e.g. `value: "40"`
+fn number(value: &PropertyValue) -> Option<f32> {
+    match value {
+        PropertyValue::String(text) => text.trim().parse().ok(),
+        value => value.as_float(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(input_type: &str, properties: &[(&str, PropertyValue)]) -> Element {
+        let mut element = Element { id: "control".into(), element_type: ElementType::Input, ..Default::default() };
+        element.custom_properties.insert("input_type".into(), PropertyValue::String(input_type.into()));
+        element.custom_properties.extend(properties.iter().map(|(name, value)| (name.to_string(), value.clone())));
+        element
+    }
+
+    #[test]
+    fn sliders_keep_to_their_range_and_steps() {
+        let slider = input("range", &[("min", PropertyValue::Int(10)), ("max", PropertyValue::Float(20.0)), ("step", PropertyValue::String("3".into()))]);
+        let range = SliderRange::of(&slider);
+        assert_eq!(control(&slider), Some(Control::Slider));
+        assert_eq!(range.value_at(0.0), 10.0);
+        assert_eq!(range.value_at(0.45), 16.0);
+        assert_eq!(range.value_at(1.0), 19.0, "20 isn't a whole number of steps from 10");
+        assert_eq!(range.clamp(-5.0), 10.0);
+        assert_eq!(slider_value(&slider), 10.0, "no value starts at min");
+
+        let fine = input("range", &[("max", PropertyValue::Int(1)), ("step", PropertyValue::Float(0.05)), ("value", PropertyValue::String("0.3".into()))]);
+        assert_eq!(format_value(slider_value(&fine)), "0.3");
+        assert_eq!(format_value(SliderRange::of(&fine).value_at(0.5)), "0.5");
+        assert_eq!(format_value(40.0), "40");
+
+        let unstepped = input("range", &[]);
+        assert_eq!(SliderRange::of(&unstepped).value_at(0.25), 25.0);
+    }
+
+    #[test]
+    fn checkboxes_and_radios_are_controls_unless_disabled() {
+        let checkbox = input("checkbox", &[]);
+        assert_eq!(control(&checkbox), Some(Control::Checkbox));
+        assert!(!is_checked(&checkbox));
+
+        let radio = input("radio", &[("name", PropertyValue::String("size".into())), ("value", PropertyValue::String("large".into()))]);
+        assert_eq!((control(&radio), radio_group(&radio), radio_value(&radio).as_str()), (Some(Control::Radio), Some("size"), "large"));
+        assert_eq!(radio_value(&input("radio", &[])), "control");
+
+        assert_eq!(control(&input("checkbox", &[("disabled", PropertyValue::Bool(true))])), None);
+        assert_eq!(control(&input("text", &[])), None);
+        assert_eq!(control(&Element { element_type: ElementType::Container, ..checkbox }), None);
+    }
+}
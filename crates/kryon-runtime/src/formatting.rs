@@ -0,0 +1,245 @@
+// crates/kryon-runtime/src/formatting.rs
+//! Locale-aware formatting of numbers, currency amounts, dates and times
+//! relative to now, with the conventions of the active [`Locale`].
+//!
+//! Templates apply these as filters after a variable, e.g.
+//! `$total|currency(EUR)`, `$count|number`, `$ratio|number(2)`,
+//! `$due|date(long)` or `$sent_at|relative`. Scripts get the same through
+//! `kryon.format`, which the runtime hands the active locale's data.
+//!
+//! Dates and times are ISO dates (`YYYY-MM-DD`, optionally followed by
+//! `THH:MM[:SS]`, taken as UTC) or Unix timestamps in seconds.
+
+use crate::date_picker::Date;
+use crate::i18n::Locale;
+use crate::script::engine_trait::ScriptValue;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Fraction digits shown at most when a number's aren't given
+const DEFAULT_MAX_DECIMALS: usize = 3;
+
+/// Length of each relative time unit in seconds, smallest first, in the
+/// order of `RelativeTime::units`
+const RELATIVE_UNITS: [f64; 7] = [1.0, 60.0, 3_600.0, 86_400.0, 604_800.0, 2_592_000.0, 31_536_000.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateStyle {
+    /// Numeric, e.g. 3/14/2025
+    Short,
+    /// With the month's name, e.g. March 14, 2025
+    Long,
+}
+
+impl DateStyle {
+    /// `short` or `long`; anything else is short
+    pub fn parse(value: &str) -> Self {
+        if value.trim().eq_ignore_ascii_case("long") { Self::Long } else { Self::Short }
+    }
+}
+
+/// `value` with grouped digits. Without `decimals`, up to three fraction
+/// digits are shown and trailing zeros dropped.
+pub fn number(locale: &Locale, value: f64, decimals: Option<usize>) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let shown = decimals.unwrap_or(DEFAULT_MAX_DECIMALS);
+    // Halves round away from zero, where `format!` would round to even
+    let scale = 10f64.powi(shown.min(15) as i32);
+    let mut digits = format!("{:.*}", shown, (value.abs() * scale).round() / scale);
+    if decimals.is_none() && digits.contains('.') {
+        digits = digits.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    let (whole, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+
+    let mut text = String::new();
+    // No minus sign on a value that rounds to zero
+    if value < 0.0 && digits.bytes().any(|digit| matches!(digit, b'1'..=b'9')) {
+        text.push('-');
+    }
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            text.push_str(&locale.group_separator);
+        }
+        text.push(digit);
+    }
+    if !fraction.is_empty() {
+        text.push_str(&locale.decimal_separator);
+        text.push_str(fraction);
+    }
+    text
+}
+
+/// `value` in the currency with ISO 4217 `code`, e.g. `USD` or `EUR`,
+/// with the currency's usual number of decimals
+pub fn currency(locale: &Locale, value: f64, code: &str) -> String {
+    let code = code.trim().to_ascii_uppercase();
+    let (symbol, decimals) = match code.as_str() {
+        "USD" => ("$", 2),
+        "EUR" => ("€", 2),
+        "GBP" => ("£", 2),
+        "JPY" => ("¥", 0),
+        "KRW" => ("₩", 0),
+        "INR" => ("₹", 2),
+        "CHF" => ("CHF", 2),
+        _ => (code.as_str(), 2),
+    };
+    let amount = number(locale, value.abs(), Some(decimals));
+    let formatted = locale.currency_pattern.replace('¤', symbol).replace("{}", &amount);
+    if value < 0.0 && amount.bytes().any(|digit| matches!(digit, b'1'..=b'9')) {
+        format!("-{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+pub fn date(locale: &Locale, date: Date, style: DateStyle) -> String {
+    let pattern = match style {
+        DateStyle::Short => &locale.short_date,
+        DateStyle::Long => &locale.long_date,
+    };
+    pattern
+        .replace("{month}", locale.month_name(date.month))
+        .replace("{dd}", &format!("{:02}", date.day))
+        .replace("{mm}", &format!("{:02}", date.month))
+        .replace("{d}", &date.day.to_string())
+        .replace("{m}", &date.month.to_string())
+        .replace("{y}", &date.year.to_string())
+}
+
+/// A time `seconds` from now (negative for the past) in the largest whole
+/// unit, e.g. "3 days ago" or "in 2 hours"
+pub fn relative_time(locale: &Locale, seconds: f64) -> String {
+    let words = &locale.relative_time;
+    let distance = seconds.abs();
+    if distance < 1.0 {
+        return words.now.clone();
+    }
+    let unit = RELATIVE_UNITS.iter().rposition(|&length| distance >= length).unwrap_or(0);
+    let amount = (distance / RELATIVE_UNITS[unit]).floor() as u64;
+    let (one, many) = &words.units[unit];
+    let phrase = format!("{} {}", amount, if amount == 1 { one } else { many });
+    let pattern = if seconds < 0.0 { &words.past } else { &words.future };
+    pattern.replace("{}", &phrase)
+}
+
+/// Seconds since the Unix epoch of an ISO date or a timestamp
+pub fn parse_timestamp(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return seconds.is_finite().then_some(seconds);
+    }
+    let (day, time) = value.split_once(['T', ' ']).unwrap_or((value, ""));
+    let days = Date::parse_iso(day)?.days() as f64;
+    let mut parts = time.trim_end_matches('Z').split(':').map(|part| part.parse::<f64>().ok());
+    let hours = parts.next().flatten().unwrap_or(0.0);
+    let minutes = parts.next().flatten().unwrap_or(0.0);
+    let seconds = parts.next().flatten().unwrap_or(0.0);
+    Some(days * 86_400.0 + hours * 3_600.0 + minutes * 60.0 + seconds)
+}
+
+/// The day of an ISO date or a timestamp, in UTC
+pub fn parse_date(value: &str) -> Option<Date> {
+    parse_timestamp(value).map(|seconds| Date::from_days((seconds / 86_400.0).floor() as i64))
+}
+
+/// Seconds since the Unix epoch
+pub fn now() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |elapsed| elapsed.as_secs_f64())
+}
+
+/// Applies a template filter to a variable's value. `None` when the value
+/// isn't a number or date the filter can format, or the filter is unknown.
+pub fn apply_filter(locale: &Locale, value: &str, filter: &str, argument: Option<&str>) -> Option<String> {
+    let argument = argument.map(str::trim).filter(|argument| !argument.is_empty());
+    match filter {
+        "number" => Some(number(locale, value.trim().parse().ok()?, argument.and_then(|decimals| decimals.parse().ok()))),
+        "currency" => Some(currency(locale, value.trim().parse().ok()?, argument.unwrap_or("USD"))),
+        "date" => Some(date(locale, parse_date(value)?, DateStyle::parse(argument.unwrap_or("short")))),
+        "relative" => Some(relative_time(locale, parse_timestamp(value)? - now())),
+        _ => None,
+    }
+}
+
+/// The locale's conventions as `kryon.format` reads them
+pub fn locale_script_value(locale: &Locale) -> ScriptValue {
+    let strings = |values: &[String]| ScriptValue::Array(values.iter().map(|value| value.as_str().into()).collect());
+    let words = &locale.relative_time;
+    let units = words.units.iter()
+        .map(|(one, many)| ScriptValue::Array(vec![one.as_str().into(), many.as_str().into()]))
+        .collect();
+    let relative: HashMap<String, ScriptValue> = [
+        ("now", words.now.as_str().into()),
+        ("past", words.past.as_str().into()),
+        ("future", words.future.as_str().into()),
+        ("units", ScriptValue::Array(units)),
+    ].into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+    let fields: HashMap<String, ScriptValue> = [
+        ("tag", locale.tag.as_str().into()),
+        ("months", strings(&locale.months)),
+        ("decimal", locale.decimal_separator.as_str().into()),
+        ("group", locale.group_separator.as_str().into()),
+        ("currency", locale.currency_pattern.as_str().into()),
+        ("short_date", locale.short_date.as_str().into()),
+        ("long_date", locale.long_date.as_str().into()),
+        ("relative", ScriptValue::Object(relative)),
+    ].into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+    ScriptValue::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::I18n;
+
+    fn locale(tag: &str) -> Locale {
+        let mut i18n = I18n::new();
+        assert!(i18n.set_locale(tag));
+        i18n.locale().clone()
+    }
+
+    #[test]
+    fn numbers_and_currencies_follow_the_locale() {
+        let (en, de, fr) = (locale("en"), locale("de"), locale("fr"));
+        assert_eq!(number(&en, 1234.56, None), "1,234.56");
+        assert_eq!(number(&de, 1234.56, None), "1.234,56");
+        assert_eq!(number(&fr, 1234567.0, None), "1\u{202F}234\u{202F}567");
+        assert_eq!(number(&en, 0.125, Some(2)), "0.13");
+        assert_eq!(number(&en, -0.001, Some(1)), "0.0");
+        assert_eq!(number(&en, 2.5000, None), "2.5");
+
+        assert_eq!(currency(&en, -1234.5, "usd"), "-$1,234.50");
+        assert_eq!(currency(&de, 1234.5, "EUR"), "1.234,50 €");
+        assert_eq!(currency(&en, 1500.0, "JPY"), "¥1,500");
+        assert_eq!(currency(&en, 3.0, "SEK"), "SEK3.00");
+    }
+
+    #[test]
+    fn dates_and_relative_times_follow_the_locale() {
+        let day = Date::new(2025, 3, 4).unwrap();
+        assert_eq!(date(&locale("en"), day, DateStyle::Short), "3/4/2025");
+        assert_eq!(date(&locale("en"), day, DateStyle::Long), "March 4, 2025");
+        assert_eq!(date(&locale("de"), day, DateStyle::Short), "04.03.2025");
+        assert_eq!(date(&locale("es"), day, DateStyle::Long), "4 de marzo de 2025");
+
+        assert_eq!(relative_time(&locale("en"), -3.0 * 86_400.0 - 10.0), "3 days ago");
+        assert_eq!(relative_time(&locale("en"), 3_600.0), "in 1 hour");
+        assert_eq!(relative_time(&locale("de"), -120.0), "vor 2 Minuten");
+        assert_eq!(relative_time(&locale("fr"), 0.2), "maintenant");
+
+        assert_eq!(parse_date("2025-03-04T23:30:00Z"), Some(day));
+        assert_eq!(parse_date("1741046400"), Some(day));
+        assert_eq!(parse_timestamp("2025-03-04T01:02:03"), Some(1_741_046_400.0 + 3_723.0));
+    }
+
+    #[test]
+    fn filters_leave_values_they_cannot_read() {
+        let en = locale("en");
+        assert_eq!(apply_filter(&en, "42000", "number", None).as_deref(), Some("42,000"));
+        assert_eq!(apply_filter(&en, "9.5", "currency", Some("EUR")).as_deref(), Some("€9.50"));
+        assert_eq!(apply_filter(&en, "2025-03-04", "date", Some("long")).as_deref(), Some("March 4, 2025"));
+        assert_eq!(apply_filter(&en, "soon", "number", None), None);
+        assert_eq!(apply_filter(&en, "1", "shout", None), None);
+    }
+}
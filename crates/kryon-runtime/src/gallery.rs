@@ -0,0 +1,420 @@
+// crates/kryon-runtime/src/gallery.rs
+//! Batch screenshots of many KRB files, for design galleries.
+//!
+//! A [`GalleryPlan`] expands a directory of `.krb` files, or a JSON manifest,
+//! into one job per file, size and theme. A theme is a set of template
+//! variable overrides. The renderers run the jobs in parallel child processes
+//! (see [`run`]) and [`write_index`] records the results as `index.json` and
+//! a browsable `index.html`.
+//!
+//! Manifest format, with paths relative to the manifest:
+//!
+//! ```json
+//! {
+//!   "files": ["screens/login.krb", "screens/settings"],
+//!   "sizes": ["390x844", "1280x800"],
+//!   "themes": { "light": {}, "dark": { "theme": "dark" } }
+//! }
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GalleryError {
+    #[error("failed to read {path}: {source}")]
+    Io { path: String, source: std::io::Error },
+    #[error("invalid gallery manifest {path}: {message}")]
+    Manifest { path: String, message: String },
+    #[error("no .krb files found in {0}")]
+    Empty(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GallerySize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl GallerySize {
+    /// Parses `WIDTHxHEIGHT`, e.g. `390x844`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (width, height) = spec.trim().split_once(['x', 'X'])
+            .ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", spec))?;
+        match (width.parse(), height.parse()) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => Ok(Self { width, height }),
+            _ => Err(format!("expected WIDTHxHEIGHT, got '{}'", spec)),
+        }
+    }
+}
+
+impl fmt::Display for GallerySize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// A named set of template variable overrides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GalleryTheme {
+    pub name: String,
+    pub vars: Vec<(String, String)>,
+}
+
+impl GalleryTheme {
+    /// Parses `NAME` or `NAME:VAR=VALUE,VAR=VALUE`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (name, vars) = spec.split_once(':').unwrap_or((spec, ""));
+        if name.is_empty() {
+            return Err(format!("expected NAME[:VAR=VALUE,...], got '{}'", spec));
+        }
+        let vars = vars.split(',')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=')
+                .map(|(var, value)| (var.to_string(), value.to_string()))
+                .ok_or_else(|| format!("invalid theme variable '{}': expected VAR=VALUE", pair)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { name: name.to_string(), vars })
+    }
+}
+
+/// One screenshot to take.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GalleryJob {
+    pub krb_file: PathBuf,
+    /// The screen's name, from its path below the gallery root
+    pub screen: String,
+    /// `None` renders at the size in the KRB
+    pub size: Option<GallerySize>,
+    pub theme: Option<String>,
+    pub vars: Vec<(String, String)>,
+    /// PNG file name inside the output directory
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GalleryPlan {
+    pub jobs: Vec<GalleryJob>,
+}
+
+impl GalleryPlan {
+    /// Plans a directory (searched recursively) or a JSON manifest. Sizes and
+    /// themes given here replace the manifest's.
+    pub fn from_path(path: &Path, sizes: &[GallerySize], themes: &[GalleryTheme]) -> Result<Self, GalleryError> {
+        let mut screens = Vec::new();
+        let (mut manifest_sizes, mut manifest_themes) = (Vec::new(), Vec::new());
+        if path.is_dir() {
+            collect_krb_files(path, path, &mut screens)?;
+        } else {
+            let manifest = Manifest::load(path)?;
+            let base = path.parent().unwrap_or(Path::new("."));
+            for file in &manifest.files {
+                let file = base.join(file);
+                if file.is_dir() {
+                    collect_krb_files(&file, &file, &mut screens)?;
+                } else {
+                    screens.push((screen_name(&file, file.parent().unwrap_or(base)), file));
+                }
+            }
+            manifest_sizes = manifest.sizes;
+            manifest_themes = manifest.themes;
+        }
+        if screens.is_empty() {
+            return Err(GalleryError::Empty(path.display().to_string()));
+        }
+        let sizes = if sizes.is_empty() { &manifest_sizes } else { sizes };
+        let themes = if themes.is_empty() { &manifest_themes } else { themes };
+        Ok(Self::new(screens, sizes, themes))
+    }
+
+    /// One job per screen, theme and size, in that order.
+    pub fn new(screens: Vec<(String, PathBuf)>, sizes: &[GallerySize], themes: &[GalleryTheme]) -> Self {
+        let sizes: Vec<Option<GallerySize>> = if sizes.is_empty() { vec![None] } else { sizes.iter().copied().map(Some).collect() };
+        let themes: Vec<Option<&GalleryTheme>> = if themes.is_empty() { vec![None] } else { themes.iter().map(Some).collect() };
+        let mut jobs = Vec::new();
+        for (screen, krb_file) in screens {
+            for theme in &themes {
+                for size in &sizes {
+                    let mut output = screen.clone();
+                    if let Some(theme) = theme {
+                        output.push('-');
+                        output.push_str(&file_safe(&theme.name));
+                    }
+                    if let Some(size) = size {
+                        output.push_str(&format!("-{}", size));
+                    }
+                    output.push_str(".png");
+                    jobs.push(GalleryJob {
+                        krb_file: krb_file.clone(),
+                        screen: screen.clone(),
+                        size: *size,
+                        theme: theme.map(|theme| theme.name.clone()),
+                        vars: theme.map(|theme| theme.vars.clone()).unwrap_or_default(),
+                        output,
+                    });
+                }
+            }
+        }
+        Self { jobs }
+    }
+}
+
+struct Manifest {
+    files: Vec<String>,
+    sizes: Vec<GallerySize>,
+    themes: Vec<GalleryTheme>,
+}
+
+impl Manifest {
+    fn load(path: &Path) -> Result<Self, GalleryError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|source| GalleryError::Io { path: path.display().to_string(), source })?;
+        let invalid = |message: String| GalleryError::Manifest { path: path.display().to_string(), message };
+        let value: Value = serde_json::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+
+        let files = value["files"].as_array()
+            .ok_or_else(|| invalid("\"files\" must be an array of paths".to_string()))?
+            .iter()
+            .map(|file| file.as_str().map(str::to_string).ok_or_else(|| invalid(format!("invalid file {}", file))))
+            .collect::<Result<_, _>>()?;
+        let sizes = value["sizes"].as_array().map(Vec::as_slice).unwrap_or_default()
+            .iter()
+            .map(|size| size.as_str().ok_or_else(|| format!("invalid size {}", size)).and_then(GallerySize::parse).map_err(&invalid))
+            .collect::<Result<_, _>>()?;
+        let themes = value["themes"].as_object()
+            .map(|themes| themes.iter()
+                .map(|(name, vars)| GalleryTheme {
+                    name: name.clone(),
+                    vars: vars.as_object().into_iter().flatten()
+                        .map(|(var, value)| (var.clone(), value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())))
+                        .collect(),
+                })
+                .collect())
+            .unwrap_or_default();
+        Ok(Self { files, sizes, themes })
+    }
+}
+
+fn collect_krb_files(dir: &Path, root: &Path, screens: &mut Vec<(String, PathBuf)>) -> Result<(), GalleryError> {
+    let read_error = |source| GalleryError::Io { path: dir.display().to_string(), source };
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir).map_err(read_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()
+        .map_err(read_error)?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_krb_files(&path, root, screens)?;
+        } else if path.extension().is_some_and(|ext| ext == "krb") {
+            screens.push((screen_name(&path, root), path));
+        }
+    }
+    Ok(())
+}
+
+/// `root/settings/profile.krb` is the screen `settings_profile`.
+fn screen_name(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path).with_extension("");
+    let parts: Vec<String> = relative.components()
+        .map(|part| file_safe(&part.as_os_str().to_string_lossy()))
+        .collect();
+    parts.join("_")
+}
+
+fn file_safe(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+/// The outcome of one job.
+#[derive(Debug, Clone)]
+pub struct GalleryEntry {
+    pub job: GalleryJob,
+    pub result: Result<(), String>,
+    pub elapsed: Duration,
+}
+
+/// Runs `render` for every job on up to `parallelism` threads, returning
+/// the entries in job order.
+pub fn run<F>(jobs: Vec<GalleryJob>, parallelism: usize, render: F) -> Vec<GalleryEntry>
+where
+    F: Fn(&GalleryJob) -> Result<(), String> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let entries: Mutex<Vec<Option<GalleryEntry>>> = Mutex::new(vec![None; jobs.len()]);
+    std::thread::scope(|scope| {
+        for _ in 0..parallelism.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else { break };
+                let start = Instant::now();
+                let result = render(job);
+                entries.lock().unwrap()[index] = Some(GalleryEntry { job: job.clone(), result, elapsed: start.elapsed() });
+            });
+        }
+    });
+    entries.into_inner().unwrap().into_iter().flatten().collect()
+}
+
+/// A renderer invocation that saves the job's screenshot into `out_dir`.
+/// Raylib writes screenshots to the working directory, so the child runs
+/// there and gets an absolute KRB path.
+pub fn screenshot_command(exe: &Path, job: &GalleryJob, out_dir: &Path, extra_args: &[String]) -> Command {
+    let krb_file = std::fs::canonicalize(&job.krb_file).unwrap_or_else(|_| job.krb_file.clone());
+    let mut command = Command::new(exe);
+    command.current_dir(out_dir).arg(krb_file).arg("--screenshot").arg(&job.output);
+    if let Some(size) = job.size {
+        command.arg("--width").arg(size.width.to_string()).arg("--height").arg(size.height.to_string());
+    }
+    command.args(extra_args);
+    for (var, value) in &job.vars {
+        command.arg("--var").arg(format!("{}={}", var, value));
+    }
+    command
+}
+
+/// Writes `index.json` and `index.html` describing `entries` into `out_dir`.
+pub fn write_index(out_dir: &Path, entries: &[GalleryEntry]) -> std::io::Result<()> {
+    let screens: Vec<Value> = entries.iter()
+        .map(|entry| json!({
+            "screen": entry.job.screen,
+            "file": entry.job.output,
+            "source": entry.job.krb_file.display().to_string(),
+            "theme": entry.job.theme,
+            "width": entry.job.size.map(|size| size.width),
+            "height": entry.job.size.map(|size| size.height),
+            "ok": entry.result.is_ok(),
+            "error": entry.result.as_ref().err(),
+            "elapsed_ms": entry.elapsed.as_millis() as u64,
+        }))
+        .collect();
+    let index = serde_json::to_string_pretty(&json!({ "screens": screens }))?;
+    std::fs::write(out_dir.join("index.json"), index)?;
+    std::fs::write(out_dir.join("index.html"), index_html(entries))
+}
+
+fn index_html(entries: &[GalleryEntry]) -> String {
+    let mut html = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Kryon gallery</title>\n<style>\n",
+        "body { font-family: sans-serif; margin: 24px; background: #f4f4f5; }\n",
+        "section { margin-bottom: 32px; }\n",
+        ".shots { display: flex; flex-wrap: wrap; gap: 16px; align-items: flex-start; }\n",
+        "figure { margin: 0; background: #fff; padding: 8px; border-radius: 6px; }\n",
+        "figure img { display: block; max-width: 360px; height: auto; }\n",
+        "figcaption { font-size: 12px; color: #52525b; margin-top: 6px; }\n",
+        ".failed { color: #b91c1c; }\n",
+        "</style>\n</head>\n<body>\n<h1>Kryon gallery</h1>\n",
+    ));
+    let mut current_screen: Option<&str> = None;
+    for entry in entries {
+        if current_screen != Some(entry.job.screen.as_str()) {
+            if current_screen.is_some() {
+                html.push_str("</div>\n</section>\n");
+            }
+            html.push_str(&format!("<section>\n<h2>{}</h2>\n<div class=\"shots\">\n", escape_html(&entry.job.screen)));
+            current_screen = Some(&entry.job.screen);
+        }
+        let caption = [entry.job.theme.clone(), entry.job.size.map(|size| size.to_string())]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" · ");
+        match &entry.result {
+            Ok(()) => html.push_str(&format!(
+                "<figure><a href=\"{file}\"><img src=\"{file}\" alt=\"{alt}\"></a><figcaption>{caption}</figcaption></figure>\n",
+                file = escape_html(&entry.job.output),
+                alt = escape_html(&entry.job.screen),
+                caption = escape_html(&caption),
+            )),
+            Err(error) => html.push_str(&format!(
+                "<figure><figcaption>{}</figcaption><p class=\"failed\">{}</p></figure>\n",
+                escape_html(&caption),
+                escape_html(error),
+            )),
+        }
+    }
+    if current_screen.is_some() {
+        html.push_str("</div>\n</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes_and_themes() {
+        assert_eq!(GallerySize::parse("390x844"), Ok(GallerySize { width: 390, height: 844 }));
+        assert!(GallerySize::parse("390").is_err());
+        assert!(GallerySize::parse("0x10").is_err());
+        assert_eq!(GalleryTheme::parse("light").unwrap(), GalleryTheme { name: "light".to_string(), vars: vec![] });
+        assert_eq!(
+            GalleryTheme::parse("dark:theme=dark,accent=#0af").unwrap().vars,
+            vec![("theme".to_string(), "dark".to_string()), ("accent".to_string(), "#0af".to_string())],
+        );
+        assert!(GalleryTheme::parse("dark:theme").is_err());
+    }
+
+    #[test]
+    fn plans_directories_and_manifests() {
+        let dir = std::env::temp_dir().join(format!("kryon-gallery-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("settings")).unwrap();
+        for file in ["login.krb", "settings/profile.krb", "notes.txt"] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+
+        let sizes = [GallerySize { width: 390, height: 844 }, GallerySize { width: 1280, height: 800 }];
+        let plan = GalleryPlan::from_path(&dir, &sizes, &[]).unwrap();
+        let outputs: Vec<&str> = plan.jobs.iter().map(|job| job.output.as_str()).collect();
+        assert_eq!(outputs, ["login-390x844.png", "login-1280x800.png", "settings_profile-390x844.png", "settings_profile-1280x800.png"]);
+
+        std::fs::write(dir.join("gallery.json"), r#"{
+            "files": ["login.krb"],
+            "sizes": ["800x600"],
+            "themes": { "dark": { "theme": "dark" } }
+        }"#).unwrap();
+        let plan = GalleryPlan::from_path(&dir.join("gallery.json"), &[], &[]).unwrap();
+        assert_eq!(plan.jobs.len(), 1);
+        assert_eq!(plan.jobs[0].output, "login-dark-800x600.png");
+        assert_eq!(plan.jobs[0].vars, vec![("theme".to_string(), "dark".to_string())]);
+
+        std::fs::write(dir.join("bad.json"), r#"{ "files": "login.krb" }"#).unwrap();
+        assert!(matches!(GalleryPlan::from_path(&dir.join("bad.json"), &[], &[]), Err(GalleryError::Manifest { .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn runs_jobs_in_parallel_and_indexes_results() {
+        let screens = (0..6).map(|i| (format!("screen{}", i), PathBuf::from(format!("screen{}.krb", i)))).collect();
+        let plan = GalleryPlan::new(screens, &[], &[]);
+        let entries = run(plan.jobs, 3, |job| {
+            if job.screen == "screen4" { Err("renderer crashed".to_string()) } else { Ok(()) }
+        });
+        assert_eq!(entries.len(), 6);
+        assert!(entries.iter().enumerate().all(|(i, entry)| entry.job.screen == format!("screen{}", i)));
+        assert_eq!(entries[4].result, Err("renderer crashed".to_string()));
+
+        let dir = std::env::temp_dir().join(format!("kryon-gallery-index-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        write_index(&dir, &entries).unwrap();
+        let index: Value = serde_json::from_str(&std::fs::read_to_string(dir.join("index.json")).unwrap()).unwrap();
+        assert_eq!(index["screens"][0]["file"], "screen0.png");
+        assert_eq!(index["screens"][4]["ok"], false);
+        let html = std::fs::read_to_string(dir.join("index.html")).unwrap();
+        assert!(html.contains("<img src=\"screen0.png\""));
+        assert!(html.contains("renderer crashed"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,283 @@
+// crates/kryon-runtime/src/i18n.rs
+//! Locale data for the text the runtime generates itself, such as the
+//! weekday and month names of the date picker, and for the number, currency
+//! and date formatting of templates and `kryon.format` (see `formatting`).
+//!
+//! The active locale is the App element's `locale` property if it names
+//! one, else the first of `LC_ALL`, `LC_TIME` and `LANG` that does, else
+//! `en`. Tags match exactly, then by language, so `de_AT.UTF-8` and `de-CH`
+//! both get `de`. Hosts can register locales of their own.
+
+use std::collections::HashMap;
+
+const DEFAULT_LOCALE: &str = "en";
+
+/// Names and conventions for one locale
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    /// A BCP 47 tag, e.g. `en` or `pt-BR`
+    pub tag: String,
+    /// January first
+    pub months: [String; 12],
+    /// Sunday first
+    pub weekdays: [String; 7],
+    /// Two or three letters each, Sunday first, for calendar headers
+    pub short_weekdays: [String; 7],
+    /// The day weeks start on: 0 for Sunday, 1 for Monday
+    pub first_weekday: u32,
+    /// Between a number's whole part and its fraction
+    pub decimal_separator: String,
+    /// Between groups of three digits of the whole part
+    pub group_separator: String,
+    /// A currency amount, with `{}` for the number and `¤` for the symbol
+    pub currency_pattern: String,
+    /// Numeric dates: `{d}`, `{m}` and `{y}`, or `{dd}` and `{mm}` zero-padded
+    pub short_date: String,
+    /// Dates with the month's name as `{month}`
+    pub long_date: String,
+    pub relative_time: RelativeTime,
+}
+
+/// Words for times relative to now, such as "3 days ago"
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeTime {
+    pub now: String,
+    /// `{}` is the amount with its unit
+    pub past: String,
+    pub future: String,
+    /// Singular and plural of seconds, minutes, hours, days, weeks, months
+    /// and years
+    pub units: [(String, String); 7],
+}
+
+impl RelativeTime {
+    pub fn new(now: &str, past: &str, future: &str, units: [(&str, &str); 7]) -> Self {
+        Self {
+            now: now.to_string(),
+            past: past.to_string(),
+            future: future.to_string(),
+            units: units.map(|(one, many)| (one.to_string(), many.to_string())),
+        }
+    }
+
+    fn english() -> Self {
+        Self::new("now", "{} ago", "in {}", [
+            ("second", "seconds"), ("minute", "minutes"), ("hour", "hours"), ("day", "days"),
+            ("week", "weeks"), ("month", "months"), ("year", "years"),
+        ])
+    }
+}
+
+impl Locale {
+    /// A locale with English number, date and relative time conventions
+    /// until the `with_*` methods set its own
+    pub fn new(tag: &str, months: [&str; 12], weekdays: [&str; 7], short_weekdays: [&str; 7], first_weekday: u32) -> Self {
+        Self {
+            tag: tag.to_string(),
+            months: months.map(str::to_string),
+            weekdays: weekdays.map(str::to_string),
+            short_weekdays: short_weekdays.map(str::to_string),
+            first_weekday: first_weekday % 7,
+            decimal_separator: ".".to_string(),
+            group_separator: ",".to_string(),
+            currency_pattern: "¤{}".to_string(),
+            short_date: "{m}/{d}/{y}".to_string(),
+            long_date: "{month} {d}, {y}".to_string(),
+            relative_time: RelativeTime::english(),
+        }
+    }
+
+    pub fn with_separators(mut self, decimal: &str, group: &str) -> Self {
+        self.decimal_separator = decimal.to_string();
+        self.group_separator = group.to_string();
+        self
+    }
+
+    pub fn with_currency_pattern(mut self, pattern: &str) -> Self {
+        self.currency_pattern = pattern.to_string();
+        self
+    }
+
+    pub fn with_date_patterns(mut self, short: &str, long: &str) -> Self {
+        self.short_date = short.to_string();
+        self.long_date = long.to_string();
+        self
+    }
+
+    pub fn with_relative_time(mut self, relative_time: RelativeTime) -> Self {
+        self.relative_time = relative_time;
+        self
+    }
+
+    /// `month` counts from 1
+    pub fn month_name(&self, month: u32) -> &str {
+        &self.months[(month.clamp(1, 12) - 1) as usize]
+    }
+
+    /// `weekday` counts from 0 for Sunday
+    pub fn weekday_name(&self, weekday: u32) -> &str {
+        &self.weekdays[(weekday % 7) as usize]
+    }
+
+    pub fn short_weekday_name(&self, weekday: u32) -> &str {
+        &self.short_weekdays[(weekday % 7) as usize]
+    }
+
+    /// Weekdays in the order a calendar's columns show them
+    pub fn week(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..7).map(|column| (self.first_weekday + column) % 7)
+    }
+
+    fn english() -> Self {
+        Self::new(
+            "en",
+            ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"],
+            ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"],
+            ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"],
+            0,
+        )
+    }
+
+    fn built_in() -> Vec<Self> {
+        vec![
+            Self::english(),
+            Self::new(
+                "en-GB",
+                ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"],
+                ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"],
+                ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"],
+                1,
+            )
+            .with_date_patterns("{dd}/{mm}/{y}", "{d} {month} {y}"),
+            Self::new(
+                "de",
+                ["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember"],
+                ["Sonntag", "Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag"],
+                ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+                1,
+            )
+            .with_separators(",", ".")
+            .with_currency_pattern("{} ¤")
+            .with_date_patterns("{dd}.{mm}.{y}", "{d}. {month} {y}")
+            .with_relative_time(RelativeTime::new("jetzt", "vor {}", "in {}", [
+                ("Sekunde", "Sekunden"), ("Minute", "Minuten"), ("Stunde", "Stunden"), ("Tag", "Tagen"),
+                ("Woche", "Wochen"), ("Monat", "Monaten"), ("Jahr", "Jahren"),
+            ])),
+            Self::new(
+                "fr",
+                ["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre"],
+                ["dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi"],
+                ["di", "lu", "ma", "me", "je", "ve", "sa"],
+                1,
+            )
+            // Digits are grouped with a narrow no-break space
+            .with_separators(",", "\u{202F}")
+            .with_currency_pattern("{} ¤")
+            .with_date_patterns("{dd}/{mm}/{y}", "{d} {month} {y}")
+            .with_relative_time(RelativeTime::new("maintenant", "il y a {}", "dans {}", [
+                ("seconde", "secondes"), ("minute", "minutes"), ("heure", "heures"), ("jour", "jours"),
+                ("semaine", "semaines"), ("mois", "mois"), ("an", "ans"),
+            ])),
+            Self::new(
+                "es",
+                ["enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre"],
+                ["domingo", "lunes", "martes", "miércoles", "jueves", "viernes", "sábado"],
+                ["do", "lu", "ma", "mi", "ju", "vi", "sá"],
+                1,
+            )
+            .with_separators(",", ".")
+            .with_currency_pattern("{} ¤")
+            .with_date_patterns("{d}/{m}/{y}", "{d} de {month} de {y}")
+            .with_relative_time(RelativeTime::new("ahora", "hace {}", "dentro de {}", [
+                ("segundo", "segundos"), ("minuto", "minutos"), ("hora", "horas"), ("día", "días"),
+                ("semana", "semanas"), ("mes", "meses"), ("año", "años"),
+            ])),
+        ]
+    }
+}
+
+/// The locales the runtime knows and the one in use
+#[derive(Debug, Clone)]
+pub struct I18n {
+    locales: HashMap<String, Locale>,
+    active: String,
+}
+
+impl I18n {
+    /// The built-in locales, with `en` active
+    pub fn new() -> Self {
+        Self {
+            locales: Locale::built_in().into_iter().map(|locale| (locale.tag.clone(), locale)).collect(),
+            active: DEFAULT_LOCALE.to_string(),
+        }
+    }
+
+    /// The built-in locales, with the environment's active
+    pub fn from_environment() -> Self {
+        let mut i18n = Self::new();
+        let from_env = ["LC_ALL", "LC_TIME", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| i18n.resolve(value).is_some());
+        if let Some(tag) = from_env {
+            i18n.set_locale(&tag);
+        }
+        i18n
+    }
+
+    /// Adds `locale`, or replaces the one with its tag
+    pub fn register(&mut self, locale: Locale) {
+        self.locales.insert(locale.tag.clone(), locale);
+    }
+
+    /// Makes the locale `tag` resolves to active. Returns false, leaving
+    /// the active locale as it was, if none does.
+    pub fn set_locale(&mut self, tag: &str) -> bool {
+        match self.resolve(tag) {
+            Some(resolved) => {
+                self.active = resolved;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn locale(&self) -> &Locale {
+        self.locales.get(&self.active).unwrap_or_else(|| &self.locales[DEFAULT_LOCALE])
+    }
+
+    /// The registered tag for `tag`, which may be a POSIX locale such as
+    /// `de_DE.UTF-8`: the same tag, else its language
+    fn resolve(&self, tag: &str) -> Option<String> {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default().replace('_', "-");
+        let find = |tag: &str| self.locales.keys().find(|known| known.eq_ignore_ascii_case(tag)).cloned();
+        find(&tag).or_else(|| find(tag.split('-').next()?))
+    }
+}
+
+impl Default for I18n {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_tags_by_region_then_language() {
+        let mut i18n = I18n::new();
+        assert_eq!(i18n.locale().tag, "en");
+        assert!(i18n.set_locale("de_AT.UTF-8"));
+        assert_eq!(i18n.locale().tag, "de");
+        assert!(i18n.set_locale("en_GB"));
+        assert_eq!(i18n.locale().tag, "en-GB");
+        assert!(!i18n.set_locale("C"));
+        assert_eq!(i18n.locale().tag, "en-GB", "unknown tags leave the locale as it was");
+
+        assert_eq!(i18n.locale().week().next(), Some(1), "British weeks start on Monday");
+        assert_eq!(i18n.locale().month_name(3), "March");
+        assert_eq!(i18n.locale().short_weekday_name(0), "Su");
+    }
+}
@@ -0,0 +1,212 @@
+// crates/kryon-runtime/src/idle_work.rs
+//! Cache warm-up on idle frames.
+//!
+//! Backends fill their glyph, texture and pipeline caches the first time a
+//! frame needs an entry, so the first hover that changes a font size or the
+//! first scroll that brings an image into view stutters. After each layout
+//! the runtime plans the work that would avoid that: glyphs for the
+//! printable ASCII range at every font size in use (in every registered
+//! font), the images of elements that aren't on screen yet, and the
+//! pipelines of every `shader_effect`. Frames where nothing changed then
+//! hand it to the backend a piece at a time, until the frame's budget is
+//! spent. Each piece is only done once.
+
+use glam::Vec2;
+use kryon_core::{Element, ElementId, ElementType, DEFAULT_FONT_SIZE};
+use kryon_layout::LayoutResult;
+use kryon_render::WarmUp;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// How long an idle frame may spend warming caches by default
+pub const DEFAULT_IDLE_BUDGET: Duration = Duration::from_millis(2);
+
+/// Characters rasterized for each font size: printable ASCII
+const GLYPH_RANGE: std::ops::RangeInclusive<char> = ' '..='~';
+
+#[derive(Debug)]
+pub struct IdleWork {
+    queue: VecDeque<WarmUp>,
+    /// Handed to the backend already, or found to be needless
+    done: HashSet<WarmUp>,
+    budget: Duration,
+}
+
+impl Default for IdleWork {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDLE_BUDGET)
+    }
+}
+
+impl IdleWork {
+    pub fn new(budget: Duration) -> Self {
+        Self { queue: VecDeque::new(), done: HashSet::new(), budget }
+    }
+
+    /// Time an idle frame may spend; zero turns warm-up off
+    pub fn set_budget(&mut self, budget: Duration) {
+        self.budget = budget;
+    }
+
+    /// Work planned and not yet done
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Replaces the planned work with what `elements`, laid out in a
+    /// `viewport`, would still need. `fonts` are the registered font families.
+    pub fn plan<'a>(
+        &mut self,
+        elements: &HashMap<ElementId, Element>,
+        layout: &LayoutResult,
+        viewport: Vec2,
+        fonts: impl IntoIterator<Item = &'a str>,
+    ) {
+        let mut font_sizes = BTreeSet::from([DEFAULT_FONT_SIZE.round() as u32]);
+        let mut images = BTreeSet::new();
+        let mut shaders = BTreeSet::new();
+        for (&element_id, element) in elements {
+            if !element.text.is_empty() {
+                font_sizes.insert(kryon_core::inline_font_size(elements, element_id).round() as u32);
+            }
+            if let Some(shader) = element.custom_properties.get("shader_effect").and_then(|value| value.as_string()) {
+                shaders.insert(shader.to_string());
+            }
+            let source = (element.element_type == ElementType::Image)
+                .then(|| element.custom_properties.get("src").and_then(|src| src.as_string()))
+                .flatten();
+            if let Some(source) = source {
+                if !on_screen(element, element_id, layout, viewport) {
+                    images.insert(source.to_string());
+                }
+            }
+        }
+
+        let characters: String = GLYPH_RANGE.collect();
+        let families: Vec<Option<String>> = std::iter::once(None)
+            .chain(fonts.into_iter().map(|family| Some(family.to_string())))
+            .collect();
+        // Glyphs first: a hover that changes font size stalls on them synchronously
+        let glyphs = families.iter().flat_map(|font_family| {
+            font_sizes.iter().map(|&font_size| WarmUp::Glyphs {
+                font_family: font_family.clone(),
+                font_size,
+                characters: characters.clone(),
+            })
+        });
+        let shaders = shaders.into_iter().map(|shader| WarmUp::ShaderEffect { shader });
+        let images = images.into_iter().map(|source| WarmUp::Image { source });
+
+        self.queue = glyphs.chain(shaders).chain(images)
+            .filter(|work| !self.done.contains(work))
+            .collect();
+    }
+
+    /// Hands planned work to `warm_up` until the budget is spent, and
+    /// returns how many pieces were done. A piece that starts within the
+    /// budget is finished even if it runs over.
+    pub fn run(&mut self, mut warm_up: impl FnMut(&WarmUp) -> bool) -> usize {
+        let started = Instant::now();
+        let mut done = 0;
+        while started.elapsed() < self.budget {
+            let Some(work) = self.queue.pop_front() else { break };
+            warm_up(&work);
+            self.done.insert(work);
+            done += 1;
+        }
+        done
+    }
+}
+
+/// Whether the element is laid out somewhere inside the viewport, so its
+/// own frame already loads what it draws
+fn on_screen(element: &Element, element_id: ElementId, layout: &LayoutResult, viewport: Vec2) -> bool {
+    if !element.visible {
+        return false;
+    }
+    let (Some(&position), Some(&size)) = (layout.computed_positions.get(&element_id), layout.computed_sizes.get(&element_id)) else {
+        return false;
+    };
+    position.x < viewport.x && position.y < viewport.y && position.x + size.x > 0.0 && position.y + size.y > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::PropertyValue;
+
+    fn image(source: &str) -> Element {
+        let mut element = Element { element_type: ElementType::Image, ..Default::default() };
+        element.custom_properties.insert("src".to_string(), PropertyValue::String(source.to_string()));
+        element
+    }
+
+    fn laid_out(boxes: &[(ElementId, Vec2, Vec2)]) -> LayoutResult {
+        LayoutResult {
+            computed_positions: boxes.iter().map(|&(element_id, position, _)| (element_id, position)).collect(),
+            computed_sizes: boxes.iter().map(|&(element_id, _, size)| (element_id, size)).collect(),
+        }
+    }
+
+    #[test]
+    fn plans_glyphs_per_font_and_size_and_only_offscreen_images() {
+        let elements: HashMap<ElementId, Element> = [
+            (0, Element { text: "Title".to_string(), font_size: 24.0, ..Default::default() }),
+            (1, image("visible.png")),
+            (2, image("below.png")),
+            (3, Element { visible: false, ..image("hidden.png") }),
+        ].into_iter().collect();
+        let layout = laid_out(&[
+            (1, Vec2::ZERO, Vec2::splat(100.0)),
+            (2, Vec2::new(0.0, 900.0), Vec2::splat(100.0)),
+            (3, Vec2::ZERO, Vec2::splat(100.0)),
+        ]);
+
+        let mut idle = IdleWork::new(Duration::from_secs(60));
+        idle.plan(&elements, &layout, Vec2::new(800.0, 600.0), ["Inter"]);
+
+        let mut work = Vec::new();
+        idle.run(|piece| {
+            work.push(piece.clone());
+            true
+        });
+        let glyphs: Vec<_> = work.iter()
+            .filter_map(|piece| match piece {
+                WarmUp::Glyphs { font_family, font_size, characters } => {
+                    assert!(characters.contains('A') && characters.contains('~'));
+                    Some((font_family.as_deref(), *font_size))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(glyphs, vec![(None, 14), (None, 24), (Some("Inter"), 14), (Some("Inter"), 24)]);
+        assert_eq!(&work[4..], &[
+            WarmUp::Image { source: "below.png".to_string() },
+            WarmUp::Image { source: "hidden.png".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn stops_at_the_budget_and_never_repeats_work() {
+        let elements: HashMap<ElementId, Element> =
+            (0..4).map(|n| (n, image(&format!("{}.png", n)))).collect();
+        let layout = laid_out(&[]);
+        let mut idle = IdleWork::new(Duration::from_millis(5));
+        idle.plan(&elements, &layout, Vec2::splat(100.0), []);
+        let planned = idle.pending();
+
+        let slow = |_: &WarmUp| {
+            std::thread::sleep(Duration::from_millis(10));
+            true
+        };
+        assert_eq!(idle.run(slow), 1);
+        assert_eq!(idle.pending(), planned - 1);
+
+        // A later layout doesn't bring back what was already done
+        idle.plan(&elements, &layout, Vec2::splat(100.0), []);
+        assert_eq!(idle.pending(), planned - 1);
+
+        idle.set_budget(Duration::ZERO);
+        assert_eq!(idle.run(|_| true), 0);
+    }
+}
@@ -0,0 +1,158 @@
+// crates/kryon-runtime/src/input.rs
+//! Coalescing of high-frequency pointer input.
+//!
+//! A 1000 Hz mouse delivers many moves per frame, and each one used to run a
+//! full hover pass. With coalescing on, `KryonApp::handle_input` only keeps
+//! the latest pointer position and the summed wheel delta. They are processed
+//! once per tick, or just before the next press, release or key event so the
+//! order of events is preserved.
+
+use glam::Vec2;
+use kryon_core::ElementId;
+use kryon_render::InputEvent;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputPolicy {
+    /// Process only the latest mouse position per tick
+    pub coalesce_moves: bool,
+    /// Sum wheel deltas per tick
+    pub coalesce_scroll: bool,
+    /// Minimum time between two calls of an element's hover handler
+    pub hover_handler_interval: Duration,
+}
+
+impl InputPolicy {
+    /// Every event is processed as it arrives, for latency-sensitive apps
+    /// such as drawing or games.
+    pub fn immediate() -> Self {
+        Self {
+            coalesce_moves: false,
+            coalesce_scroll: false,
+            hover_handler_interval: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for InputPolicy {
+    fn default() -> Self {
+        Self {
+            coalesce_moves: true,
+            coalesce_scroll: true,
+            hover_handler_interval: Duration::from_millis(50),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InputCoalescer {
+    policy: InputPolicy,
+    pending_move: Option<Vec2>,
+    pending_scroll: Option<Vec2>,
+    /// When each element's hover handler last ran, as time since app start
+    hover_handler_calls: HashMap<ElementId, Duration>,
+}
+
+impl InputCoalescer {
+    pub fn new(policy: InputPolicy) -> Self {
+        Self { policy, ..Default::default() }
+    }
+
+    pub fn policy(&self) -> InputPolicy {
+        self.policy
+    }
+
+    /// Takes effect for the next event; call `flush` first to process what
+    /// the old policy held back.
+    pub fn set_policy(&mut self, policy: InputPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the events to process now, in order.
+    pub fn push(&mut self, event: InputEvent) -> Vec<InputEvent> {
+        match event {
+            InputEvent::MouseMove { position } if self.policy.coalesce_moves => {
+                self.pending_move = Some(position);
+                Vec::new()
+            }
+            InputEvent::Scroll { delta } if self.policy.coalesce_scroll => {
+                *self.pending_scroll.get_or_insert(Vec2::ZERO) += delta;
+                Vec::new()
+            }
+            event => {
+                let mut events = self.flush();
+                events.push(event);
+                events
+            }
+        }
+    }
+
+    /// Takes the held-back move and scroll, move first so the wheel applies
+    /// at the latest pointer position.
+    pub fn flush(&mut self) -> Vec<InputEvent> {
+        let moved = self.pending_move.take().map(|position| InputEvent::MouseMove { position });
+        let scrolled = self.pending_scroll.take().map(|delta| InputEvent::Scroll { delta });
+        moved.into_iter().chain(scrolled).collect()
+    }
+
+    /// Whether `element`'s hover handler may run at `now` (time since app
+    /// start), recording the call if so.
+    pub fn allow_hover_handler(&mut self, element: ElementId, now: Duration) -> bool {
+        if let Some(&last) = self.hover_handler_calls.get(&element) {
+            if now.saturating_sub(last) < self.policy.hover_handler_interval {
+                return false;
+            }
+        }
+        self.hover_handler_calls.insert(element, now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_render::{KeyModifiers, MouseButton};
+
+    fn moved(x: f32) -> InputEvent {
+        InputEvent::MouseMove { position: Vec2::new(x, 0.0) }
+    }
+
+    #[test]
+    fn keeps_the_latest_move_and_sums_scrolls() {
+        let mut input = InputCoalescer::default();
+        for x in 0..10 {
+            assert!(input.push(moved(x as f32)).is_empty());
+        }
+        input.push(InputEvent::Scroll { delta: Vec2::new(0.0, 1.0) });
+        input.push(InputEvent::Scroll { delta: Vec2::new(0.0, 2.0) });
+        assert_eq!(input.flush(), vec![moved(9.0), InputEvent::Scroll { delta: Vec2::new(0.0, 3.0) }]);
+        assert!(input.flush().is_empty());
+    }
+
+    #[test]
+    fn discrete_events_flush_pending_input_first() {
+        let mut input = InputCoalescer::default();
+        input.push(moved(1.0));
+        input.push(moved(2.0));
+        let press = InputEvent::MousePress { position: Vec2::new(2.0, 0.0), button: MouseButton::Left, modifiers: KeyModifiers::none() };
+        assert_eq!(input.push(press.clone()), vec![moved(2.0), press]);
+    }
+
+    #[test]
+    fn immediate_policy_passes_everything_through() {
+        let mut input = InputCoalescer::new(InputPolicy::immediate());
+        assert_eq!(input.push(moved(1.0)), vec![moved(1.0)]);
+        assert!(input.allow_hover_handler(1, Duration::ZERO));
+        assert!(input.allow_hover_handler(1, Duration::ZERO));
+    }
+
+    #[test]
+    fn rate_limits_hover_handlers_per_element() {
+        let mut input = InputCoalescer::default();
+        assert!(input.allow_hover_handler(1, Duration::from_millis(100)));
+        assert!(!input.allow_hover_handler(1, Duration::from_millis(120)));
+        assert!(input.allow_hover_handler(2, Duration::from_millis(120)));
+        assert!(input.allow_hover_handler(1, Duration::from_millis(150)));
+    }
+}
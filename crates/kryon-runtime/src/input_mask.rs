@@ -0,0 +1,221 @@
+// crates/kryon-runtime/src/input_mask.rs
+//! Formatting masks for text inputs, such as phone numbers and dates.
+//!
+//! An input's `input_mask` property is a pattern in which `9` takes a
+//! digit, `a` a letter and `*` either; every other character, or one
+//! escaped with `\`, is a literal the mask puts in by itself. With
+//! `"(999) 999-9999"`, typing `5551234567` shows `(555) 123-4567`.
+//!
+//! The value is the formatted text; the raw value is just the characters
+//! typed into slots. The text editor edits the raw value and formats it
+//! again after every key, so literals are never typed or deleted, and the
+//! caret only stops next to slots. Literals after the last filled slot
+//! aren't shown until something follows them.
+
+use kryon_core::Element;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Literal(char),
+}
+
+impl Slot {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Slot::Digit => c.is_ascii_digit(),
+            Slot::Letter => c.is_alphabetic(),
+            Slot::Alphanumeric => c.is_alphanumeric(),
+            Slot::Literal(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMask {
+    slots: Vec<Slot>,
+}
+
+impl InputMask {
+    /// Parses a mask pattern; `None` if it has no slots to type into
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let mut slots = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '9' => Slot::Digit,
+                'a' => Slot::Letter,
+                '*' => Slot::Alphanumeric,
+                '\\' => Slot::Literal(chars.next().unwrap_or('\\')),
+                literal => Slot::Literal(literal),
+            });
+        }
+        slots.iter().any(|slot| !matches!(slot, Slot::Literal(_))).then_some(Self { slots })
+    }
+
+    /// The mask an element's `input_mask` property sets
+    pub fn from_element(element: &Element) -> Option<Self> {
+        Self::parse(element.custom_properties.get("input_mask")?.as_string()?)
+    }
+
+    /// How many characters the raw value holds at most
+    pub fn capacity(&self) -> usize {
+        self.slots.iter().filter(|slot| !matches!(slot, Slot::Literal(_))).count()
+    }
+
+    /// `raw` laid out in the mask. Characters a slot doesn't take are
+    /// dropped, as is anything past the last slot.
+    pub fn format(&self, raw: &str) -> String {
+        let mut value = String::new();
+        let mut pending_literals = String::new();
+        let mut raw = raw.chars();
+        for &slot in &self.slots {
+            match slot {
+                Slot::Literal(literal) => pending_literals.push(literal),
+                slot => {
+                    let Some(c) = (loop {
+                        match raw.next() {
+                            Some(c) if slot.accepts(c) => break Some(c),
+                            Some(_) => continue,
+                            None => break None,
+                        }
+                    }) else {
+                        break;
+                    };
+                    value.push_str(&pending_literals);
+                    pending_literals.clear();
+                    value.push(c);
+                }
+            }
+        }
+        value
+    }
+
+    /// The characters of `value` that fill slots, whether `value` is
+    /// formatted or was set without the literals
+    pub fn unmask(&self, value: &str) -> String {
+        let mut raw = String::new();
+        let mut slots = self.slots.iter().copied().peekable();
+        for c in value.chars() {
+            // Skip the literals `c` isn't, up to the slot it may fill
+            while let Some(Slot::Literal(literal)) = slots.peek().copied() {
+                if literal == c {
+                    break;
+                }
+                slots.next();
+            }
+            match slots.peek().copied() {
+                Some(Slot::Literal(_)) => {
+                    slots.next();
+                }
+                Some(slot) if slot.accepts(c) => {
+                    raw.push(c);
+                    slots.next();
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+        raw
+    }
+
+    /// How many slots come before character `index` of a formatted value
+    pub fn raw_index(&self, index: usize) -> usize {
+        self.slots.iter().take(index).filter(|slot| !matches!(slot, Slot::Literal(_))).count()
+    }
+
+    /// Where the caret goes in a formatted value after `raw_index` raw
+    /// characters: just after the last of them, or before the first slot
+    pub fn value_index(&self, raw_index: usize) -> usize {
+        let mut slots = self.slots.iter().enumerate().filter(|(_, slot)| !matches!(slot, Slot::Literal(_)));
+        match raw_index {
+            0 => slots.next().map_or(0, |(index, _)| index),
+            raw_index => slots.nth(raw_index - 1).map_or(self.slots.len(), |(index, _)| index + 1),
+        }
+    }
+
+    /// The caret positions in a formatted value, in order: next to its
+    /// slots, never between two literals
+    pub fn caret_stops(&self, value: &str) -> Vec<usize> {
+        let len = value.chars().count();
+        let mut stops: Vec<usize> = (0..=self.unmask(value).chars().count())
+            .map(|raw_index| self.value_index(raw_index).min(len))
+            .collect();
+        stops.dedup();
+        stops
+    }
+
+    /// Replaces raw characters `start..end` of a formatted value with
+    /// `text`, keeping what the slots from `start` on take. Returns the new
+    /// value and the raw index after the inserted text.
+    pub fn splice(&self, value: &str, start: usize, end: usize, text: &str) -> (String, usize) {
+        let mut raw: Vec<char> = self.unmask(value).chars().collect();
+        let (start, end) = (start.min(raw.len()), end.min(raw.len()));
+        raw.drain(start..end);
+        let mut at = start;
+        for c in text.chars() {
+            if raw.len() >= self.capacity() {
+                break;
+            }
+            if self.slot(at).is_some_and(|slot| slot.accepts(c)) {
+                raw.insert(at, c);
+                at += 1;
+            }
+        }
+        let value = self.format(&raw.into_iter().collect::<String>());
+        let kept = self.unmask(&value).chars().count();
+        (value, at.min(kept))
+    }
+
+    /// The slot raw character `raw_index` goes in
+    fn slot(&self, raw_index: usize) -> Option<Slot> {
+        self.slots.iter().copied().filter(|slot| !matches!(slot, Slot::Literal(_))).nth(raw_index)
+    }
+}
+
+/// An element's value without its mask's literals; the value itself if it
+/// has no mask
+pub fn raw_value(element: &Element) -> String {
+    match InputMask::from_element(element) {
+        Some(mask) => mask.unmask(&element.text),
+        None => element.text.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_and_unmasks_values() {
+        let phone = InputMask::parse("(999) 999-9999").unwrap();
+        assert_eq!(phone.capacity(), 10);
+        assert_eq!(phone.format("5551234567"), "(555) 123-4567");
+        assert_eq!(phone.format("555"), "(555", "trailing literals wait for the next character");
+        assert_eq!(phone.format("55x51"), "(555) 1");
+        assert_eq!(phone.format(""), "");
+        assert_eq!(phone.unmask("(555) 123-4567"), "5551234567");
+        assert_eq!(phone.unmask("555-1234"), "5551234");
+
+        let escaped = InputMask::parse("\\a-aa").unwrap();
+        assert_eq!(escaped.format("xy"), "a-xy");
+        assert!(InputMask::parse("--").is_none());
+    }
+
+    #[test]
+    fn carets_stop_next_to_slots() {
+        let date = InputMask::parse("99/99/9999").unwrap();
+        assert_eq!((date.raw_index(3), date.raw_index(4)), (2, 3));
+        assert_eq!((date.value_index(0), date.value_index(2), date.value_index(3)), (0, 2, 4));
+        assert_eq!(date.caret_stops("12/3"), vec![0, 1, 2, 4]);
+
+        let (value, caret) = date.splice("12/3", 2, 2, "x5");
+        assert_eq!((value.as_str(), caret), ("12/53", 3), "rejected characters are skipped");
+        let (value, caret) = date.splice("12/31/2024", 1, 2, "");
+        assert_eq!((value.as_str(), caret), ("13/12/024", 1));
+        let (value, _) = date.splice("12/31/2024", 8, 8, "9");
+        assert_eq!(value, "12/31/2024", "full masks take nothing more");
+    }
+}
@@ -1,51 +1,191 @@
 // crates/kryon-runtime/src/lib.rs
 
 use kryon_core::{
-    KRBFile, Element, ElementId, InteractionState, EventType, load_krb_file,
-    StyleComputer,
+    KRBFile, Element, ElementId, ElementType, InteractionState, EventType, load_krb_file,
+    StyleComputer, ParticleEmitter, ParticleInstance, TableView, HitRegion, HoverTransition, OrientationVariants,
 };
 use kryon_layout::{LayoutEngine, TaffyLayoutEngine, LayoutResult};
-use kryon_render::{ElementRenderer, CommandRenderer, InputEvent, MouseButton, KeyCode};
+pub use kryon_layout::{SafeAreaInsets, TextMeasurer};
+pub use kryon_core::{ColorScheme, CompatibilityReport, DefaultStyles, DefaultStylesError, Orientation, OutputRotation, TemplateValue, TemplateValueError, TemplateValueType};
+use kryon_render::{ElementRenderer, CommandRenderer, InputEvent, MouseButton, KeyCode, RenderCommand, RenderStats, RenderStatsSummary, color_picker, text_input};
+use text_editing::{Clipboard, Edit, TextEditor};
+pub use kryon_render::{FrameSnapshot, SnapshotSlot};
+pub use kryon_render::RendererWarning;
+pub use kryon_render::{Page, PageMargins, PageSetup};
 use glam::Vec2;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+pub mod analytics;
 pub mod backends;
+pub mod content_visibility;
+pub mod datasets;
+pub mod date_picker;
+pub mod diagnostics;
+pub mod dialogs;
+pub mod drag_drop;
+pub mod element_handle;
+pub mod element_index;
 pub mod event_system;
+pub mod field_validation;
+pub mod form_controls;
+pub mod formatting;
+pub mod gallery;
+pub mod i18n;
+pub mod idle_work;
+pub mod input;
+pub mod input_mask;
+pub mod live_compile;
+pub mod memory;
+pub mod metrics;
+pub mod net;
+pub mod notifications;
+pub mod popups;
+pub mod process;
+pub mod profiler;
+pub mod repl;
+pub mod sandbox;
+pub mod scroll;
+pub mod serial;
 pub mod script;
 pub mod template_engine;
+pub mod text_editing;
+pub mod toasts;
 pub mod shared_data;
+pub mod validation;
+pub mod variable_changes;
+pub mod variable_overrides;
 
 pub use backends::*;
+pub use analytics::AnalyticsRecorder;
+pub use dialogs::{DialogManager, DialogRequest, DialogResult};
+pub use element_handle::{ElementChange, ElementHandle};
+pub use gallery::{GalleryPlan, GallerySize, GalleryTheme};
+pub use input::InputPolicy;
+pub use live_compile::{CompileCommand, CompileOutcome, CompileWatcher};
+pub use memory::MemoryReport;
+pub use notifications::{NotificationManager, NotificationRequest};
+pub use popups::{Placement, PopupCommand, PopupManager, PopupTrigger};
+pub use process::{ProcessManager, ProcessPolicy};
+pub use toasts::{ToastCommand, ToastCorner, ToastKind, ToastManager, ToastRequest};
+pub use repl::Repl;
+pub use sandbox::{Capability, SandboxReport};
+pub use metrics::MetricsExporter;
 pub use event_system::*;
 pub use script::ScriptSystem;
+pub use script::event::AppEvent;
 pub use template_engine::*;
 pub use shared_data::*;
+pub use variable_overrides::{OverrideError, VariableOverrides};
 
 pub struct KryonApp<R: CommandRenderer> {
     // Core data
     krb_file: KRBFile,
     elements: HashMap<ElementId, Element>,
+    /// String id to `ElementId`, rebuilt when `elements` is replaced
+    element_index: element_index::ElementIndex,
+    /// No element added through an `ElementHandle` is numbered below this,
+    /// so a removed element's id isn't handed out again
+    next_element_id: ElementId,
+    /// Elements changed through an `ElementHandle` since the scripts were
+    /// last given them
+    elements_changed: bool,
     
     // Systems
-    _style_computer: StyleComputer, 
     layout_engine: Box<dyn LayoutEngine>,
     renderer: ElementRenderer<R>,
     event_system: EventSystem,
     script_system: ScriptSystem,
     template_engine: TemplateEngine,
+    /// Applied since the watchers were last told, for `kryon.watch`
+    variable_changes: variable_changes::VariableChanges,
+    particle_emitters: HashMap<ElementId, ParticleEmitter>,
+    /// Shared with published snapshots; copied on write while one holds them
+    particles: Arc<HashMap<ElementId, Vec<ParticleInstance>>>,
+    tables: Arc<HashMap<ElementId, TableView>>,
+    overlay: Arc<Vec<RenderCommand>>,
+    dialogs: DialogManager,
+    notifications: NotificationManager,
+    toasts: ToastManager,
+    popups: PopupManager,
+    scroll: scroll::ScrollState,
+    content_visibility: content_visibility::ContentVisibility,
+    idle_work: idle_work::IdleWork,
+    processes: ProcessManager,
+    serial: serial::SerialBridge,
+    websockets: net::WebSocketManager,
+    mqtt: net::MqttManager,
+    data_sources: net::DataSourceManager,
+    repl: Option<Repl>,
+    metrics: Option<MetricsExporter>,
+    analytics: Option<AnalyticsRecorder>,
+    profiler: profiler::ProfilerOverlay,
+    build_error: live_compile::BuildErrorOverlay,
+    /// Published to `kryon.monitors()`, again after a reload
+    monitors: Vec<kryon_render::MonitorInfo>,
+    /// Raised by `kryon.emit`, until the host takes them
+    app_events: Vec<AppEvent>,
+    /// Features the backend skipped, once per (feature, element)
+    renderer_warnings: Vec<RendererWarning>,
+    /// Print a `[WARN]` summary after the first frame, then each new warning
+    warning_summary: bool,
+    warnings_summarized: bool,
+    /// Natural sizes of the images the backend has decoded, by source
+    image_sizes: HashMap<String, Vec2>,
     
     // State
     layout_result: LayoutResult,
     viewport_size: Vec2,
     needs_layout: bool,
+    /// The last published snapshot no longer shows the current state
     needs_render: bool,
+    snapshots: SnapshotSlot,
+    published_frame: u64,
+    rendered_frame: u64,
+    /// Modifier keys held at the last mouse or key event
+    modifiers: kryon_render::KeyModifiers,
+    /// Transparent HUD window: only elements with `pointer_events: auto` take input
+    overlay_mode: bool,
+    input: input::InputCoalescer,
+    /// Elements under the pointer, innermost first
+    hover_path: Vec<ElementId>,
+    text_editor: TextEditor,
+    /// Time since the focused input's caret last moved, on the animation clock
+    caret_blink: Duration,
+    /// Password input whose reveal toggle the pointer is over
+    reveal_hover: Option<ElementId>,
+    /// Copied text, for backends without a system clipboard
+    clipboard: Option<String>,
+    /// The calendar of the date input that opened it
+    date_picker: Option<date_picker::DatePicker>,
+    /// The color input being dragged on, and the part of it the drag began on
+    color_drag: Option<(ElementId, color_picker::PickerRegion)>,
+    /// The slider whose thumb is being dragged
+    slider_drag: Option<ElementId>,
+    /// The color input whose eyedropper the next press samples for
+    eyedropper: Option<ElementId>,
+    drag_drop: drag_drop::DragDrop,
+    /// What was stripped from the document, when it runs sandboxed
+    sandbox: Option<SandboxReport>,
+    i18n: i18n::I18n,
+    orientation: Orientation,
+    orientation_variants: OrientationVariants,
     
     // Timing
+    started_at: Instant,
     last_frame_time: Instant,
+    last_update_time: Duration,
+    memory_report_age: Duration,
+    stats_interval: Option<Duration>,
+    stats_summary: RenderStatsSummary,
+    stats_window_start: Instant,
     frame_count: u64,
 }
 
+/// How often the profiler overlay and `kryon.memory_report()` are refreshed.
+const MEMORY_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
 impl<R: CommandRenderer> KryonApp<R> {
     pub fn new(krb_path: &str, renderer: R) -> anyhow::Result<Self> {
         Self::new_with_layout_engine(krb_path, renderer, None)
@@ -66,25 +206,73 @@ impl<R: CommandRenderer> KryonApp<R> {
         
         
         // Use TaffyLayoutEngine as the core layout system
-        let layout_engine: Box<dyn LayoutEngine> = layout_engine.unwrap_or_else(|| {
+        let mut layout_engine: Box<dyn LayoutEngine> = layout_engine.unwrap_or_else(|| {
             Box::new(TaffyLayoutEngine::new())
         });
-        let renderer = ElementRenderer::new(renderer, style_computer.clone());
+        let mut renderer = ElementRenderer::new(renderer, style_computer);
+        renderer.set_default_styles(DefaultStyles::builtin());
+        if let Some(baseline) = renderer.text_baseline() {
+            eprintln!("[LAYOUT] Text baseline at {:.3} of the font size", baseline);
+            layout_engine.set_text_baseline(baseline);
+        }
+        if let Some(measurer) = renderer.text_measurer() {
+            eprintln!("[LAYOUT] Measuring text with the {} backend's fonts", renderer.backend().backend_name());
+            layout_engine.set_text_measurer(measurer);
+        }
         let viewport_size = renderer.viewport_size();
         
         let event_system = EventSystem::new();
         let script_system = ScriptSystem::new()?;
         let template_engine = TemplateEngine::new(&krb_file);
         
+        let particle_emitters = elements.iter()
+            .filter(|(_, element)| element.element_type == ElementType::ParticleEmitter)
+            .map(|(&id, element)| (id, ParticleEmitter::from_element(element, id as u64)))
+            .collect();
+        
+        let data_sources = net::DataSourceManager::from_elements(elements.values());
+        let popups = PopupManager::from_elements(&mut elements);
+        let orientation_variants = OrientationVariants::from_elements(&elements);
+        
         let mut app = Self {
             krb_file,
+            element_index: element_index::ElementIndex::new(),
+            next_element_id: 0,
+            elements_changed: false,
             elements,
-            _style_computer: style_computer,
             layout_engine,
             renderer,
             event_system,
             script_system,
             template_engine,
+            variable_changes: variable_changes::VariableChanges::default(),
+            particle_emitters,
+            particles: Arc::default(),
+            tables: Arc::default(),
+            overlay: Arc::default(),
+            dialogs: DialogManager::new(),
+            notifications: NotificationManager::new(),
+            toasts: ToastManager::new(),
+            popups,
+            scroll: scroll::ScrollState::new(),
+            content_visibility: content_visibility::ContentVisibility::new(),
+            idle_work: idle_work::IdleWork::default(),
+            processes: ProcessManager::default(),
+            serial: serial::SerialBridge::new(),
+            websockets: net::WebSocketManager::new(),
+            mqtt: net::MqttManager::new(),
+            data_sources,
+            repl: None,
+            metrics: None,
+            analytics: None,
+            profiler: profiler::ProfilerOverlay::new(),
+            build_error: live_compile::BuildErrorOverlay::default(),
+            monitors: Vec::new(),
+            app_events: Vec::new(),
+            renderer_warnings: Vec::new(),
+            warning_summary: false,
+            warnings_summarized: false,
+            image_sizes: HashMap::new(),
             layout_result: LayoutResult {
                 computed_positions: HashMap::new(),
                 computed_sizes: HashMap::new(),
@@ -92,49 +280,190 @@ impl<R: CommandRenderer> KryonApp<R> {
             viewport_size,
             needs_layout: true,
             needs_render: true,
+            snapshots: SnapshotSlot::new(),
+            published_frame: 0,
+            rendered_frame: 0,
+            modifiers: kryon_render::KeyModifiers::none(),
+            overlay_mode: false,
+            input: input::InputCoalescer::default(),
+            hover_path: Vec::new(),
+            text_editor: TextEditor::new(),
+            caret_blink: Duration::ZERO,
+            reveal_hover: None,
+            clipboard: None,
+            date_picker: None,
+            color_drag: None,
+            slider_drag: None,
+            eyedropper: None,
+            drag_drop: drag_drop::DragDrop::default(),
+            sandbox: None,
+            i18n: i18n::I18n::from_environment(),
+            orientation: Orientation::from_size(viewport_size),
+            orientation_variants,
+            started_at: Instant::now(),
             last_frame_time: Instant::now(),
+            last_update_time: Duration::ZERO,
+            memory_report_age: MEMORY_REPORT_INTERVAL,
+            stats_interval: None,
+            stats_summary: RenderStatsSummary::default(),
+            stats_window_start: Instant::now(),
             frame_count: 0,
         };
+        app.start()?;
+        Ok(app)
+    }
+    
+    /// Runs `krb_file` as static UI, for files from sources that aren't
+    /// trusted: see [`sandbox`]. Nothing can take it out of the sandbox, a
+    /// reload included.
+    pub fn new_sandboxed(mut krb_file: KRBFile, renderer: R, layout_engine: Option<Box<dyn LayoutEngine>>) -> anyhow::Result<Self> {
+        let report = Self::strip_for_sandbox(&mut krb_file);
+        let mut app = Self::new_with_krb(krb_file, renderer, layout_engine)?;
+        app.sandbox = Some(report);
+        Ok(app)
+    }
+    
+    fn strip_for_sandbox(krb_file: &mut KRBFile) -> SandboxReport {
+        let report = sandbox::strip(krb_file);
+        eprintln!("[SANDBOX] Running sandboxed: {}", report);
+        report
+    }
+    
+    /// Replaces the document with `krb_file`, e.g. after a recompile. The
+    /// renderer, layout engine and host settings (input policy, overlay
+    /// mode, profiler, console, process policy, monitors) carry over;
+    /// scripts, connections and element state start fresh.
+    pub fn reload(&mut self, mut krb_file: KRBFile) -> anyhow::Result<()> {
+        if self.sandbox.is_some() {
+            self.sandbox = Some(Self::strip_for_sandbox(&mut krb_file));
+        }
+        let script_system = ScriptSystem::new()?;
+        let mut elements = krb_file.elements.clone();
+        let style_computer = StyleComputer::new(&elements, &krb_file.styles);
+        Self::link_element_hierarchy(&mut elements, &krb_file)?;
+        
+        self.renderer.reset_scene(style_computer);
+        self.template_engine = TemplateEngine::new(&krb_file);
+        self.variable_changes = variable_changes::VariableChanges::default();
+        self.next_element_id = 0;
+        self.particle_emitters = elements.iter()
+            .filter(|(_, element)| element.element_type == ElementType::ParticleEmitter)
+            .map(|(&id, element)| (id, ParticleEmitter::from_element(element, id as u64)))
+            .collect();
+        self.data_sources = net::DataSourceManager::from_elements(elements.values());
+        self.popups = PopupManager::from_elements(&mut elements);
+        self.scroll = scroll::ScrollState::new();
+        self.content_visibility = content_visibility::ContentVisibility::new();
+        self.orientation_variants = OrientationVariants::from_elements(&elements);
+        self.krb_file = krb_file;
+        self.elements = elements;
+        self.event_system = EventSystem::new();
+        self.script_system = script_system;
+        self.particles = Arc::default();
+        self.tables = Arc::default();
+        self.dialogs = DialogManager::new();
+        self.processes = ProcessManager::new(self.processes.policy().clone());
+        self.serial = serial::SerialBridge::new();
+        self.websockets = net::WebSocketManager::new();
+        self.mqtt = net::MqttManager::new();
+        self.app_events.clear();
+        self.renderer_warnings.clear();
+        self.warnings_summarized = false;
+        self.layout_result = LayoutResult {
+            computed_positions: HashMap::new(),
+            computed_sizes: HashMap::new(),
+        };
+        self.hover_path.clear();
+        self.text_editor.blur();
+        self.date_picker = None;
+        self.color_drag = None;
+        self.slider_drag = None;
+        self.eyedropper = None;
+        self.drag_drop = drag_drop::DragDrop::default();
+        self.toasts = ToastManager::new();
+        self.needs_render = true;
+        
+        let monitors = std::mem::take(&mut self.monitors);
+        self.set_monitors(&monitors);
+        self.start()
+    }
+    
+    /// Loads tables, initializes scripts and computes the first layout.
+    fn start(&mut self) -> anyhow::Result<()> {
+        self.reindex_elements();
+        
+        // Load Table elements that declare a table_source
+        for (&element_id, element) in self.elements.iter().filter(|(_, e)| e.element_type == ElementType::Table) {
+            match datasets::table_view_for_element(element) {
+                Some(Ok(view)) => {
+                    Arc::make_mut(&mut self.tables).insert(element_id, view);
+                }
+                Some(Err(e)) => eprintln!("[DATA] Table '{}' failed to load: {}", element.id, e),
+                None => {}
+            }
+        }
         
         // Initialize the script system with KRB file data
-        app.script_system.initialize(&app.krb_file, &app.elements)?;
+        self.script_system.initialize(&self.krb_file, &self.elements)?;
         
         // Load compiled scripts from KRB file
-        app.script_system.load_compiled_scripts(&app.krb_file.scripts)?;
+        self.script_system.load_compiled_scripts(&self.krb_file.scripts)?;
         
         // Initialize template variables in the script system
         // Always initialize template variables from KRB data to ensure script access
-        if app.template_engine.has_bindings() {
+        if self.template_engine.has_bindings() {
             tracing::info!("🔍 [INIT_DEBUG] Template engine has bindings, using template variables");
-            let template_vars = app.template_engine.get_variables().clone();
-            app.script_system.initialize_template_variables(&template_vars)?;
+            let template_vars = self.template_engine.get_variables().clone();
+            self.script_system.initialize_template_variables(&template_vars)?;
         } else {
             tracing::info!("🔍 [INIT_DEBUG] Template engine has no bindings, extracting variables from KRB");
             // Extract template variables directly from KRB data
             let mut vars = std::collections::HashMap::new();
-            for var in &app.krb_file.template_variables {
+            for var in &self.krb_file.template_variables {
                 vars.insert(var.name.clone(), var.default_value.clone());
             }
-            app.script_system.initialize_template_variables(&vars)?;
+            self.script_system.initialize_template_variables(&vars)?;
         }
+        self.script_system.set_template_variable_types(self.template_engine.variable_types())?;
         
         // Apply any initial changes set by scripts during initialization
-        let changes_applied = app.script_system.apply_pending_changes(&mut app.elements)?;
+        let changes_applied = self.script_system.apply_pending_changes(&mut self.elements)?;
         if changes_applied {
             tracing::info!("Applied initial changes from scripts");
         }
         
+        // The App element's locale formats template filters and `kryon.format`
+        let app_locale = self.krb_file.root_element_id
+            .and_then(|id| self.elements.get(&id))
+            .and_then(|root| root.custom_properties.get("locale"))
+            .and_then(|value| value.as_string())
+            .map(str::to_string);
+        if let Some(locale) = app_locale {
+            if !self.i18n.set_locale(&locale) {
+                eprintln!("[I18N] No locale data for '{}', keeping '{}'", locale, self.i18n.locale().tag);
+            }
+        }
+        self.apply_locale()?;
+        
         // Initialize template variables (apply default values to elements)
-        app.initialize_template_variables()?;
+        self.initialize_template_variables()?;
+        
+        // Orientation variants and kryon.orientation() are in place before init functions run
+        self.apply_orientation(self.orientation)?;
         
         // Execute script initialization functions now that template variables are ready
-        app.script_system.execute_init_functions()?;
+        self.script_system.execute_init_functions()?;
+        
+        // Connect to an MQTT broker declared on the App element (mqtt_broker / mqtt_bind)
+        if let Some(root) = self.krb_file.root_element_id.and_then(|id| self.elements.get(&id)) {
+            self.mqtt.connect_declared(root);
+        }
         
         // Force initial layout computation
-        app.update_layout()?;
-        app.needs_layout = false; // Reset after initial layout
+        self.update_layout()?;
+        self.needs_layout = false; // Reset after initial layout
         
-        Ok(app)
+        Ok(())
     }
     
     fn link_element_hierarchy(
@@ -147,7 +476,33 @@ impl<R: CommandRenderer> KryonApp<R> {
         Ok(())
     }
     
-    pub fn update(&mut self, _delta_time: Duration) -> anyhow::Result<()> {
+    pub fn update(&mut self, delta_time: Duration) -> anyhow::Result<()> {
+        let update_started = Instant::now();
+        
+        // One hover pass per tick for the moves coalesced since the last one
+        self.flush_input()?;
+        
+        // Show newly requested dialogs and run callbacks for finished ones, so
+        // their DOM changes are picked up below in the same frame
+        self.update_dialogs()?;
+        self.update_popups();
+        self.update_toasts(delta_time)?;
+        self.update_analytics(delta_time);
+        self.update_eyedropper();
+        self.update_processes()?;
+        self.update_serial()?;
+        self.update_websockets()?;
+        self.update_mqtt()?;
+        self.update_data_sources(delta_time)?;
+        self.update_datasets()?;
+        self.update_repl();
+        self.update_diagnostics()?;
+        
+        // Scripts see what embedders changed through element handles
+        if std::mem::take(&mut self.elements_changed) {
+            self.script_system.refresh_elements(&self.elements)?;
+        }
+        
         // Get pending changes from scripts and apply both DOM and template variable changes
         let pending_changes = self.script_system.get_pending_changes()?;
         
@@ -169,9 +524,21 @@ impl<R: CommandRenderer> KryonApp<R> {
             self.needs_render = true;
         }
         
+        // Watchers hear about the frame's changes once they're all applied;
+        // what they change in turn is delivered next frame
+        self.deliver_variable_changes()?;
+        
         // Process events
         self.event_system.update(&mut self.elements)?;
         
+        // Images decoded in the background since the last frame
+        self.collect_decoded_images();
+        
+        // Skipped contents that came into view, or new ones to skip, need a layout
+        if self.content_visibility.update(&self.elements, &self.layout_result, self.viewport_size) {
+            self.needs_layout = true;
+        }
+        
         // Update layout if needed
         if self.needs_layout {
             self.update_layout()?;
@@ -179,294 +546,2293 @@ impl<R: CommandRenderer> KryonApp<R> {
             self.needs_render = true;
         }
         
-        Ok(())
-    }
-    
-    pub fn render(&mut self) -> anyhow::Result<()> {
-        if !self.needs_render {
-            return Ok(());
-        }
-        
-        if let Some(root_id) = self.krb_file.root_element_id {
-            let clear_color = glam::Vec4::new(0.1, 0.1, 0.1, 1.0); // Dark gray
-            
-            self.renderer.render_frame(
-                &self.elements,
-                &self.layout_result,
-                root_id,
-                clear_color,
-            )?;
+        // Advance particle simulations and animated images on the animation clock
+        self.update_particles(delta_time);
+        if self.renderer.advance_animations(delta_time) {
+            self.needs_render = true;
         }
+        self.update_caret_blink(delta_time);
         
-        self.needs_render = false;
-        self.frame_count += 1;
-        
-        // Note: Forced hover test removed - hover system confirmed working
-        
-        // Update timing
-        let now = Instant::now();
-        let frame_time = now.duration_since(self.last_frame_time);
-        self.last_frame_time = now;
+        self.update_notifications(delta_time);
+        self.update_memory_report(delta_time);
+        self.app_events.extend(self.script_system.take_app_events());
         
-        // Log FPS occasionally
-        if self.frame_count % 60 == 0 {
-            let fps = 1.0 / frame_time.as_secs_f32();
-            tracing::debug!("FPS: {:.1}", fps);
+        if self.needs_render {
+            self.publish_snapshot();
+        } else {
+            // Nothing to draw: warm the backend's caches for what comes next
+            let renderer = &mut self.renderer;
+            self.idle_work.run(|work| renderer.warm_up(work));
         }
         
+        self.last_update_time = update_started.elapsed();
         Ok(())
     }
     
-    pub fn handle_input(&mut self, event: InputEvent) -> anyhow::Result<()> {
-        match event {
-            InputEvent::Resize { size } => {
-                self.viewport_size = size;
-                self.renderer.resize(size)?;
-                self.needs_layout = true;
-            }
-            InputEvent::MouseMove { position } => {
-                self.handle_mouse_move(position)?;
-            }
-            InputEvent::MousePress { position, button } => {
-                self.handle_mouse_press(position, button)?;
-            }
-            InputEvent::MouseRelease { position, button } => {
-                self.handle_mouse_release(position, button)?;
-            }
-            InputEvent::KeyPress { key, modifiers } => {
-                self.handle_key_press(key, modifiers)?;
+    /// Publishes what's on screen now to `frame_snapshots()`, for `render`
+    /// or a render thread to draw.
+    fn publish_snapshot(&mut self) {
+        self.published_frame += 1;
+        let clear_color = if self.overlay_mode {
+            glam::Vec4::ZERO // Let the desktop show through
+        } else {
+            glam::Vec4::new(0.1, 0.1, 0.1, 1.0) // Dark gray
+        };
+        self.snapshots.publish(FrameSnapshot {
+            frame: self.published_frame,
+            root_id: self.krb_file.root_element_id,
+            clear_color,
+            elements: Arc::new(self.elements.clone()),
+            layout: Arc::new(self.layout_result.clone()),
+            hover_path: self.hover_path.clone(),
+            orientation: self.orientation,
+            color_scheme: self.color_scheme(),
+            scroll_offsets: self.scroll.offsets().clone(),
+            skipped_subtrees: self.content_visibility.skipped().keys().copied().collect(),
+            particles: self.particles.clone(),
+            tables: self.tables.clone(),
+            overlay: self.overlay.clone(),
+            drag_ghost: self.drag_drop.ghost(&self.elements),
+        });
+        self.needs_render = false;
+    }
+    
+    /// Snapshots published at the end of each update that changed what's on
+    /// screen. A host can draw them on a render thread of its own, with its
+    /// own `ElementRenderer`, instead of calling `render`.
+    pub fn frame_snapshots(&self) -> SnapshotSlot {
+        self.snapshots.clone()
+    }
+    
+    fn update_dialogs(&mut self) -> anyhow::Result<()> {
+        for request in self.script_system.take_dialog_requests() {
+            self.dialogs.show(request);
+        }
+        
+        for result in self.dialogs.poll() {
+            if let Some(error) = &result.error {
+                eprintln!("[DIALOG] Dialog {} failed: {}", result.callback_id, error);
             }
-            _ => {}
+            self.script_system.deliver_dialog_result(&result)?;
         }
         
         Ok(())
     }
     
-    // In fn update_layout(&mut self) -> anyhow::Result<()>
-
-fn update_layout(&mut self) -> anyhow::Result<()> {
-    if let Some(root_id) = self.krb_file.root_element_id {
-        self.layout_result = self.layout_engine.compute_layout(
-            &self.elements,
-            root_id,
-            self.viewport_size,
-        );
-        
-        // Apply computed layout results back to element positions and sizes
-        for (&element_id, computed_position) in &self.layout_result.computed_positions {
-            if let Some(element) = self.elements.get_mut(&element_id) {
-                eprintln!("[LAYOUT_APPLY] Element {}: applying computed position {:?} (was {:?})", 
-                    element_id, computed_position, element.position);
-                element.position = *computed_position;
-            }
+    fn update_popups(&mut self) {
+        let mut changed = false;
+        for command in self.script_system.take_popup_commands() {
+            let (PopupCommand::Open(id) | PopupCommand::Close(id) | PopupCommand::Toggle(id)) = &command;
+            let Some(element_id) = self.element_index.get(id) else {
+                eprintln!("[POPUP] No popup element with id '{}'", id);
+                continue;
+            };
+            let open = match command {
+                PopupCommand::Open(_) => true,
+                PopupCommand::Close(_) => false,
+                PopupCommand::Toggle(_) => !self.popups.is_open(element_id),
+            };
+            changed |= self.popups.set_open(element_id, open);
         }
-        
-        for (&element_id, computed_size) in &self.layout_result.computed_sizes {
-            if let Some(element) = self.elements.get_mut(&element_id) {
-                // Debug: Log size application
-                eprintln!("[LAYOUT_APPLY] Element {}: applying computed size {:?} (was {:?})", 
-                    element_id, computed_size, element.size);
-                element.size = *computed_size;
-            }
+        if changed {
+            self.place_popups();
         }
     }
-    Ok(())
-}
-
-    fn handle_mouse_move(&mut self, position: Vec2) -> anyhow::Result<()> {
-        let hovered_element = self.find_element_at_position(position);
-        
-        // Determine the cursor type for the hovered element
-        let cursor_type = if let Some(element_id) = hovered_element {
-            if let Some(element) = self.elements.get(&element_id) {
-                element.cursor
-            } else {
-                kryon_core::CursorType::Default
+    
+    /// Shows open popups next to their anchors and hides the others. Called
+    /// after layout, which puts popups where they'd be in flow.
+    fn place_popups(&mut self) {
+        for popup in self.popups.popups() {
+            if let Some(element) = self.elements.get_mut(&popup.element) {
+                element.visible = popup.open;
             }
-        } else {
-            kryon_core::CursorType::Default
-        };
-        
-        // Update the cursor through the renderer
-        self.renderer.backend_mut().set_cursor(cursor_type);
+        }
         
-        // Update hover states (but preserve checked state)
-        for (element_id, element) in self.elements.iter_mut() {
-            let should_hover = Some(*element_id) == hovered_element;
-            let was_hovering = element.current_state == InteractionState::Hover;
-            let is_checked = element.current_state == InteractionState::Checked;
-            
-            if should_hover && !was_hovering && !is_checked {
-                // Only set hover if not already in checked state
-                element.current_state = InteractionState::Hover;
-                self.needs_render = true;
-                
-                // Trigger hover event
-                if let Some(handler) = element.event_handlers.get(&EventType::Hover) {
-                    use kryon_core::PropertyValue;
-                    self.script_system.call_function(handler, vec![])?;
+        let offsets = self.popups.offsets(&self.layout_result.computed_positions, &self.layout_result.computed_sizes, self.viewport_size);
+        for (popup, offset) in offsets {
+            if offset == Vec2::ZERO {
+                continue;
+            }
+            for element_id in popups::subtree(&self.elements, popup) {
+                if let Some(position) = self.layout_result.computed_positions.get_mut(&element_id) {
+                    *position += offset;
+                }
+                if let Some(element) = self.elements.get_mut(&element_id) {
+                    element.position += offset;
                 }
-            } else if !should_hover && was_hovering && !is_checked {
-                // Only reset to normal if not in checked state
-                element.current_state = InteractionState::Normal;
-                self.needs_render = true;
             }
-            // If element is checked, preserve the checked state regardless of hover
         }
-        
-        Ok(())
+        self.needs_render = true;
     }
     
-    fn handle_mouse_press(&mut self, position: Vec2, button: MouseButton) -> anyhow::Result<()> {
-        if button == MouseButton::Left {
-            if let Some(element_id) = self.find_element_at_position(position) {
-                if let Some(element) = self.elements.get_mut(&element_id) {
-                    element.current_state = InteractionState::Active;
-                    self.needs_render = true;
-                }
-            }
+    fn update_processes(&mut self) -> anyhow::Result<()> {
+        for request in self.script_system.take_process_requests() {
+            self.processes.spawn(request);
+        }
+        
+        for event in self.processes.poll() {
+            self.script_system.deliver_process_event(&event)?;
         }
+        
         Ok(())
     }
     
-    fn handle_mouse_release(&mut self, position: Vec2, button: MouseButton) -> anyhow::Result<()> {
-        if button == MouseButton::Left {
-            if let Some(element_id) = self.find_element_at_position(position) {
-                // Trigger click event first, before changing any states
-                if let Some(element) = self.elements.get(&element_id) {
-                    if let Some(handler) = element.event_handlers.get(&EventType::Click) {
-                        // Call the click handler function
-                        use kryon_core::PropertyValue;
-                        self.script_system.call_function(handler, vec![])?;
-                        
-                        // Apply any pending changes from scripts
-                        let changes_applied = self.script_system.apply_pending_changes(&mut self.elements)?;
-                        
-                        // Apply template variable changes from scripts
-                        let pending_changes = self.script_system.get_pending_changes()?;
-                        let template_variable_changes = if let Some(template_changes) = pending_changes.get("template_variables") {
-                            for (name, value) in &template_changes.data {
-                                self.set_template_variable(name, value)?;
-                            }
-                            !template_changes.data.is_empty()
-                        } else {
-                            false
-                        };
-                        
-                        if changes_applied || template_variable_changes {
-                            tracing::info!("Changes applied, triggering re-render");
-                            self.needs_render = true;
-                            
-                            // Force layout update for visibility changes and template variable changes
-                            // This ensures that elements become visible/invisible immediately and template variables update
-                            if template_variable_changes {
-                                self.update_layout()?;
-                                self.needs_layout = false;
-                                tracing::info!("🚀 [SCRIPT_IMMEDIATE] Immediate layout update applied for template changes");
-                            }
-                        }
-                        
-                        // After script changes are applied, set hover state only for non-checked elements
-                        if let Some(element) = self.elements.get_mut(&element_id) {
-                            if element.current_state != InteractionState::Checked {
-                                element.current_state = InteractionState::Hover;
-                                self.needs_render = true;
-                            }
-                        }
-                    } else {
-                        // No click handler, just set hover state
-                        if let Some(element) = self.elements.get_mut(&element_id) {
-                            element.current_state = InteractionState::Hover;
-                            self.needs_render = true;
-                        }
-                    }
-                }
-            }
+    fn update_serial(&mut self) -> anyhow::Result<()> {
+        for command in self.script_system.take_serial_commands() {
+            self.serial.handle(command);
         }
+        
+        for args in self.serial.poll() {
+            self.script_system.deliver_serial_event(args)?;
+        }
+        
         Ok(())
     }
     
-    fn handle_key_press(&mut self, key: KeyCode, _modifiers: kryon_render::KeyModifiers) -> anyhow::Result<()> {
-        // Handle global key events
-        match key {
-            KeyCode::Escape => {
-                // Could trigger app exit
+    fn update_websockets(&mut self) -> anyhow::Result<()> {
+        for command in self.script_system.take_ws_commands() {
+            self.websockets.handle(command);
+        }
+        
+        for event in self.websockets.poll() {
+            if let net::WsEvent::Error { id, message } = &event {
+                eprintln!("[WEBSOCKET] Socket {} error: {}", id, message);
             }
-            _ => {}
+            self.script_system.deliver_ws_event(&event)?;
         }
+        
         Ok(())
     }
     
-    fn find_element_at_position(&self, position: Vec2) -> Option<ElementId> {
-        // Find the topmost element at the given position
-        let mut found_elements = Vec::new();
+    fn update_mqtt(&mut self) -> anyhow::Result<()> {
+        for command in self.script_system.take_mqtt_commands() {
+            self.mqtt.handle(command);
+        }
         
-        for (element_id, element) in &self.elements {
-            if !element.visible {
-                continue;
+        for event in self.mqtt.poll() {
+            if let net::MqttEvent::Error { id, message } = &event {
+                eprintln!("[MQTT] Connection {} error: {}", id, message);
             }
-            
-            let element_pos = self.layout_result.computed_positions
-                .get(element_id)
-                .copied()
-                .unwrap_or(element.position);
-            let element_size = self.layout_result.computed_sizes
-                .get(element_id)
-                .copied()
-                .unwrap_or(element.size);
-            
-            if position.x >= element_pos.x
-                && position.x <= element_pos.x + element_size.x
-                && position.y >= element_pos.y
-                && position.y <= element_pos.y + element_size.y
-            {
-                found_elements.push(*element_id);
+            // Bound topics update template variables without going through scripts
+            for (name, value) in self.mqtt.bound_values(&event) {
+                self.set_template_variable(&name, &value)?;
             }
+            self.script_system.deliver_mqtt_event(&event)?;
         }
         
-        // Return the highest element ID (topmost)
-        found_elements.into_iter().max()
+        Ok(())
     }
     
-    pub fn get_element(&self, id: &str) -> Option<&Element> {
-        self.elements.iter()
-            .find(|(_, element)| element.id == id)
-            .map(|(_, element)| element)
+    fn update_diagnostics(&mut self) -> anyhow::Result<()> {
+        for request in self.script_system.take_diagnostic_dumps() {
+            let result = diagnostics::dump(&request.reason).map_err(|e| e.to_string());
+            if let Err(e) = &result {
+                eprintln!("[DIAG] Dump requested by script failed: {}", e);
+            }
+            self.script_system.deliver_diagnostic_dump(request.id, result)?;
+        }
+        
+        Ok(())
     }
     
-    pub fn get_element_mut(&mut self, id: &str) -> Option<&mut Element> {
-        self.elements.iter_mut()
-            .find(|(_, element)| element.id == id)
-            .map(|(_, element)| element)
+    /// Attaches a script console; its input is handled between frames.
+    pub fn attach_repl(&mut self, repl: Repl) {
+        if self.sandbox.is_some() {
+            eprintln!("[SANDBOX] Not attaching a console to a sandboxed app");
+            return;
+        }
+        self.repl = Some(repl);
     }
     
-    pub fn viewport_size(&self) -> Vec2 {
-        self.viewport_size
+    /// Records each rendered frame into `exporter` and publishes a new
+    /// snapshot whenever the memory report is refreshed.
+    pub fn attach_metrics(&mut self, mut exporter: MetricsExporter) {
+        exporter.set_backend(self.renderer.backend().backend_name());
+        self.metrics = Some(exporter);
+        self.memory_report_age = MEMORY_REPORT_INTERVAL;
     }
     
-    pub fn mark_needs_layout(&mut self) {
-        self.needs_layout = true;
+    /// Records clicks and form field events into `recorder`, along with the
+    /// events scripts track through `kryon.analytics`.
+    pub fn enable_analytics(&mut self, recorder: AnalyticsRecorder) {
+        self.analytics = Some(recorder);
     }
     
-    pub fn mark_needs_render(&mut self) {
-        self.needs_render = true;
+    pub fn analytics_mut(&mut self) -> Option<&mut AnalyticsRecorder> {
+        self.analytics.as_mut()
     }
     
-    pub fn renderer(&self) -> &ElementRenderer<R> {
-        &self.renderer
+    /// Applies `kryon.analytics` calls and uploads waiting events. Calls
+    /// made without a recorder are dropped.
+    fn update_analytics(&mut self, delta_time: Duration) {
+        let commands = self.script_system.take_analytics_commands();
+        let Some(recorder) = self.analytics.as_mut() else { return };
+        for command in commands {
+            recorder.apply(command);
+        }
+        recorder.update(delta_time);
     }
     
-    pub fn renderer_mut(&mut self) -> &mut ElementRenderer<R> {
-        &mut self.renderer
+    fn record_interaction(&mut self, kind: &str, element_id: ElementId, position: Option<Vec2>) {
+        let Some(recorder) = self.analytics.as_mut() else { return };
+        if !analytics::is_recordable(&self.elements, element_id) {
+            return;
+        }
+        let id = self.elements.get(&element_id).map(|element| element.id.as_str()).filter(|id| !id.is_empty());
+        recorder.record(kind, id, position);
     }
     
-    // Template variable methods
+    fn update_repl(&mut self) {
+        let Some(requests) = self.repl.as_mut().map(Repl::poll) else { return };
+        for (command, request) in requests {
+            let output = match command {
+                Ok(command) => self.run_repl_command(command),
+                Err(e) => format!("error: {}", e),
+            };
+            request.respond(output);
+        }
+    }
+    
+    fn run_repl_command(&mut self, command: repl::ReplCommand) -> String {
+        use repl::ReplCommand;
+        
+        match command {
+            ReplCommand::Eval(code) => match self.script_system.evaluate("lua", &code) {
+                Ok(value) => repl::format_value(&value),
+                Err(e) => format!("error: {}", e),
+            },
+            ReplCommand::Vars => {
+                let mut variables: Vec<_> = self.template_engine.get_variables().iter().collect();
+                variables.sort();
+                variables.iter()
+                    .map(|(name, value)| format!("{} = {:?}", name, value))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            ReplCommand::Get(name) => match self.get_template_variable(&name) {
+                Some(value) => format!("{:?}", value),
+                None => format!("error: no template variable '{}'", name),
+            },
+            ReplCommand::Set { name, value } => {
+                match self.set_typed_template_variable(&name, TemplateValue::String(value.clone())) {
+                    Ok(()) => format!("{} = {:?}", name, self.get_template_variable(&name).unwrap_or(&value)),
+                    Err(e) => format!("error: {}", e),
+                }
+            }
+            ReplCommand::Element(id) => match self.element_by_string_id(&id) {
+                Some(element_id) => self.describe_element(element_id),
+                None => format!("error: no element with id '{}'", id),
+            },
+            ReplCommand::SetProperty { element, property, value } => {
+                let Some(element_id) = self.element_by_string_id(&element) else {
+                    return format!("error: no element with id '{}'", element);
+                };
+                match ElementHandle::new(self, element_id).set_property(&property, &value) {
+                    Ok(_) => format!("{}.{} = {}", element, property, value),
+                    Err(e) => format!("error: {}", e),
+                }
+            }
+            ReplCommand::History => self.repl.as_ref().map(Repl::format_history).unwrap_or_default(),
+            ReplCommand::Memory => self.memory_report().summary_lines().join("\n"),
+            ReplCommand::Warnings if self.renderer_warnings.is_empty() => "no renderer warnings".to_string(),
+            ReplCommand::Warnings => self.renderer_warnings.iter()
+                .map(|warning| self.describe_warning(warning))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ReplCommand::Help => repl::HELP.to_string(),
+        }
+    }
+    
+    fn element_by_string_id(&self, id: &str) -> Option<ElementId> {
+        self.element_index.get(id)
+            .or_else(|| id.strip_prefix('#').and_then(|n| n.parse().ok()).filter(|n| self.elements.contains_key(n)))
+    }
+    
+    fn describe_element(&self, element_id: ElementId) -> String {
+        let element = &self.elements[&element_id];
+        let position = self.layout_result.computed_positions.get(&element_id).copied().unwrap_or(element.position);
+        let size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        let color = |c: glam::Vec4| format!("rgba({:.2}, {:.2}, {:.2}, {:.2})", c.x, c.y, c.z, c.w);
+        
+        let mut lines = vec![
+            format!("#{} {:?} '{}'", element_id, element.element_type, element.id),
+            format!("  position = ({}, {})  size = ({}, {})", position.x, position.y, size.x, size.y),
+            format!("  text = {:?}", element.text),
+            format!("  visible = {}  opacity = {}  z_index = {}", element.visible, element.opacity, element.z_index),
+            format!("  font_size = {}  font_family = {:?}", element.font_size, element.font_family),
+            format!("  background_color = {}", color(element.background_color)),
+            format!("  text_color = {}", color(element.text_color)),
+            format!("  border_color = {}  border_width = {}", color(element.border_color), element.border_width),
+            format!("  children = {:?}", element.children),
+        ];
+        let mut handlers: Vec<String> = element.event_handlers.iter()
+            .map(|(event, handler)| format!("  on {:?} -> {}", event, handler))
+            .collect();
+        handlers.sort();
+        lines.extend(handlers);
+        let mut properties: Vec<String> = element.custom_properties.iter()
+            .map(|(name, value)| format!("  {} = {:?}", name, value))
+            .collect();
+        properties.sort();
+        lines.extend(properties);
+        lines.join("\n")
+    }
+    
+    /// Loads datasets requested by scripts and applies table sort/filter requests.
+    fn update_datasets(&mut self) -> anyhow::Result<()> {
+        for request in self.script_system.take_data_requests() {
+            match request {
+                datasets::DataRequest::LoadCsv { id, path, options, bind } => {
+                    let result = datasets::load_csv(&path, &options);
+                    self.finish_data_load(id, result, bind)?;
+                }
+                datasets::DataRequest::LoadJson { id, path, pointer, bind } => {
+                    let result = datasets::load_json(&path, pointer.as_deref());
+                    self.finish_data_load(id, result, bind)?;
+                }
+                datasets::DataRequest::SortTable { table, column, ascending } => {
+                    self.update_table(&table, |view| {
+                        let sort = column.as_deref()
+                            .and_then(|name| datasets::parse_sort(view.dataset(), name))
+                            .map(|sort| kryon_core::TableSort { ascending, ..sort });
+                        view.set_sort(sort);
+                    });
+                }
+                datasets::DataRequest::FilterTable { table, filter } => {
+                    self.update_table(&table, |view| view.set_filter(&filter));
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    fn finish_data_load(
+        &mut self,
+        id: u64,
+        result: Result<kryon_core::Dataset, datasets::DatasetError>,
+        bind: Option<String>,
+    ) -> anyhow::Result<()> {
+        match result {
+            Ok(dataset) => {
+                let value = datasets::dataset_to_script_value(&dataset);
+                if let Some(table) = bind {
+                    match self.table_element_id(&table) {
+                        Some(element_id) => self.set_table_view(element_id, TableView::new(Arc::new(dataset))),
+                        None => eprintln!("[DATA] No Table element with id '{}' to bind", table),
+                    }
+                }
+                self.script_system.deliver_data_result(id, value, None)
+            }
+            Err(e) => self.script_system.deliver_data_result(id, script::engine_trait::ScriptValue::Nil, Some(e.to_string())),
+        }
+    }
+    
+    fn table_element_id(&self, id: &str) -> Option<ElementId> {
+        self.element_index.get(id).filter(|element_id| self.elements[element_id].element_type == ElementType::Table)
+    }
+    
+    fn set_table_view(&mut self, element_id: ElementId, view: TableView) {
+        Arc::make_mut(&mut self.tables).insert(element_id, view);
+        self.needs_render = true;
+    }
+    
+    fn update_table(&mut self, id: &str, change: impl FnOnce(&mut TableView)) {
+        let Some(element_id) = self.table_element_id(id) else {
+            eprintln!("[DATA] No Table element with id '{}'", id);
+            return;
+        };
+        let Some(mut view) = self.tables.get(&element_id).cloned() else {
+            eprintln!("[DATA] Table '{}' has no data bound", id);
+            return;
+        };
+        change(&mut view);
+        self.set_table_view(element_id, view);
+    }
+    
+    /// Fetches declared data sources when due and writes their values into template variables.
+    fn update_data_sources(&mut self, delta_time: Duration) -> anyhow::Result<()> {
+        for name in self.script_system.take_data_refreshes() {
+            if !self.data_sources.refresh(name.as_deref()) {
+                eprintln!("[DATA] kryon.data.refresh: unknown source '{}'", name.unwrap_or_default());
+            }
+        }
+        
+        if self.data_sources.is_empty() {
+            return Ok(());
+        }
+        for (name, value) in self.data_sources.update(delta_time) {
+            self.set_template_variable(&name, &value)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Sends script notifications to the OS and keeps fallback toasts on the overlay.
+    fn update_notifications(&mut self, delta_time: Duration) {
+        for request in self.script_system.take_notification_requests() {
+            self.notifications.notify(request);
+        }
+        
+        if self.notifications.update(delta_time) {
+            self.refresh_overlay();
+        }
+    }
+    
+    /// Shows, dismisses and ages script toasts. Toasts with an action that
+    /// left unpressed let the script forget their callback.
+    fn update_toasts(&mut self, delta_time: Duration) -> anyhow::Result<()> {
+        for command in self.script_system.take_toast_commands() {
+            self.toasts.apply(command);
+        }
+        if !self.toasts.update(delta_time) {
+            return Ok(());
+        }
+        for id in self.toasts.take_expired_actions() {
+            self.script_system.finish_toast(id, false)?;
+        }
+        self.refresh_overlay();
+        Ok(())
+    }
+    
+    /// Room notification toasts take when script toasts share their corner
+    fn toast_reserved(&self) -> f32 {
+        if self.toasts.corner() == ToastCorner::BottomRight {
+            self.notifications.stack_height()
+        } else {
+            0.0
+        }
+    }
+    
+    /// Rebuilds the overlay layer: notification and script toasts, the
+    /// profiler panel, any build error and an open date picker.
+    fn refresh_overlay(&mut self) {
+        let mut commands = self.notifications.overlay_commands(self.viewport_size);
+        commands.extend(self.toasts.overlay_commands(self.viewport_size, self.toast_reserved()));
+        commands.extend(self.date_picker_commands());
+        commands.extend(self.profiler.overlay_commands());
+        commands.extend(self.build_error.overlay_commands(self.viewport_size));
+        self.overlay = Arc::new(commands);
+        self.needs_render = true;
+    }
+    
+    /// Memory held by textures, glyph atlases, elements, layout, tables and
+    /// script engines.
+    pub fn memory_report(&self) -> MemoryReport {
+        let mut report = MemoryReport::default();
+        report.set_backend(&self.renderer.backend().memory_stats());
+        report.elements = memory::element_memory(self.elements.values());
+        
+        let (nodes, node_bytes) = self.layout_engine.memory_usage();
+        let results = self.layout_result.computed_positions.len() + self.layout_result.computed_sizes.len();
+        report.layout = memory::SubsystemMemory {
+            count: nodes.max(self.layout_result.computed_positions.len()),
+            bytes: node_bytes + results * std::mem::size_of::<(ElementId, Vec2)>(),
+        };
+        
+        report.tables = memory::table_memory(self.tables.values());
+        report.scripts = self.script_system.memory_stats().iter()
+            .map(|(language, stats)| memory::ScriptMemory::new(language.as_str(), stats))
+            .collect();
+        report
+    }
+    
+    /// Publishes the backend's monitors to `kryon.monitors()`.
+    pub fn set_monitors(&mut self, monitors: &[kryon_render::MonitorInfo]) {
+        use script::engine_trait::ScriptValue;
+        
+        self.monitors = monitors.to_vec();
+        let monitors = monitors.iter().enumerate()
+            .map(|(index, monitor)| {
+                let fields = [
+                    ("index", ScriptValue::Integer(index as i64)),
+                    ("name", ScriptValue::String(monitor.name.clone())),
+                    ("x", ScriptValue::from(monitor.position.x)),
+                    ("y", ScriptValue::from(monitor.position.y)),
+                    ("width", ScriptValue::from(monitor.size.x)),
+                    ("height", ScriptValue::from(monitor.size.y)),
+                    ("scale_factor", ScriptValue::from(monitor.scale_factor)),
+                    ("dpi", ScriptValue::from(monitor.dpi())),
+                ];
+                ScriptValue::Object(fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+            })
+            .collect();
+        if let Err(e) = self.script_system.set_monitors(ScriptValue::Array(monitors)) {
+            tracing::warn!("Failed to publish monitors to scripts: {}", e);
+        }
+    }
+    
+    /// Covers the UI with a compile or reload error until called with `None`.
+    pub fn set_build_error(&mut self, error: Option<String>) {
+        self.build_error.set_error(error);
+        self.refresh_overlay();
+    }
+    
+    pub fn set_profiler_overlay(&mut self, enabled: bool) {
+        self.profiler.set_enabled(enabled);
+        self.memory_report_age = MEMORY_REPORT_INTERVAL;
+        self.refresh_overlay();
+    }
+    
+    /// Statistics for the last rendered frame: commands by type, elements
+    /// visited, culled and clipped, and style cache lookups.
+    pub fn render_stats(&self) -> &RenderStats {
+        self.renderer.last_frame_stats()
+    }
+    
+    /// Prints a `[STATS]` summary of the frames rendered in each `interval`.
+    /// `None` stops the summaries.
+    pub fn set_stats_interval(&mut self, interval: Option<Duration>) {
+        self.stats_interval = interval;
+        self.stats_summary = RenderStatsSummary::default();
+        self.stats_window_start = Instant::now();
+    }
+    
+    fn record_render_stats(&mut self) {
+        let Some(interval) = self.stats_interval else {
+            return;
+        };
+        self.stats_summary.add(self.renderer.last_frame_stats());
+        
+        let elapsed = self.stats_window_start.elapsed();
+        if elapsed >= interval {
+            eprintln!("[STATS] {}", self.stats_summary.report(elapsed));
+            self.stats_summary = RenderStatsSummary::default();
+            self.stats_window_start = Instant::now();
+        }
+    }
+    
+    /// Features the backend couldn't draw so far, in the order first seen.
+    pub fn renderer_warnings(&self) -> &[RendererWarning] {
+        &self.renderer_warnings
+    }
+    
+    /// Prints the renderer warnings as a `[WARN]` summary after the first
+    /// frame, and each one found later as it turns up.
+    pub fn set_warning_summary(&mut self, enabled: bool) {
+        self.warning_summary = enabled;
+    }
+    
+    /// How long each idle frame may spend warming the backend's caches;
+    /// see `idle_work`. Zero turns warm-up off.
+    pub fn set_idle_budget(&mut self, budget: Duration) {
+        self.idle_work.set_budget(budget);
+    }
+    
+    /// Image elements without a size collapse until their bitmap is
+    /// decoded; lays them out again once the backend reports its size.
+    /// Picks up images the backend finished decoding: a new size needs a
+    /// layout, and an image decoded in the background needs a frame to show.
+    /// Any image may be a `background_image`, whose tiles are sized when
+    /// the commands are built, so every one gets a frame.
+    fn collect_decoded_images(&mut self) {
+        for (source, size) in self.renderer.take_decoded_images() {
+            let in_use = self.elements.values().any(|element| image_source(element) == Some(source.as_str()));
+            if self.image_sizes.insert(source, size) != Some(size) && in_use {
+                self.needs_layout = true;
+            }
+            self.needs_render = true;
+        }
+    }
+    
+    fn collect_renderer_warnings(&mut self) {
+        let new_warnings = self.renderer.take_warnings();
+        let first = self.renderer_warnings.len();
+        self.renderer_warnings.extend(new_warnings);
+        if !self.warning_summary {
+            return;
+        }
+        
+        if !self.warnings_summarized {
+            self.warnings_summarized = true;
+            if self.renderer_warnings.is_empty() {
+                return;
+            }
+            eprintln!("[WARN] {} renderer warning(s) on the first frame:", self.renderer_warnings.len());
+        }
+        for warning in &self.renderer_warnings[first..] {
+            eprintln!("[WARN] {}", self.describe_warning(warning));
+        }
+    }
+    
+    fn describe_warning(&self, warning: &RendererWarning) -> String {
+        let element = warning.element
+            .and_then(|id| self.elements.get(&id).map(|element| validation::element_label(id, element)));
+        match element {
+            Some(label) => format!("{}: {}", label, warning.message()),
+            None => warning.message(),
+        }
+    }
+    
+    /// Refreshes the snapshot behind `kryon.memory_report()`, the profiler's
+    /// render and memory sections, and the metrics export.
+    fn update_memory_report(&mut self, delta_time: Duration) {
+        self.memory_report_age += delta_time;
+        if self.memory_report_age < MEMORY_REPORT_INTERVAL {
+            return;
+        }
+        self.memory_report_age = Duration::ZERO;
+        
+        let report = self.memory_report();
+        if let Err(e) = self.script_system.set_memory_report(report.to_script_value()) {
+            tracing::warn!("Failed to publish memory report to scripts: {}", e);
+        }
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.publish(&report, self.script_system.error_count(), self.image_sizes.len());
+        }
+        if self.profiler.is_enabled() {
+            self.profiler.set_section("render", self.renderer.last_frame_stats().summary_lines());
+            self.profiler.set_section("memory", report.summary_lines());
+            self.refresh_overlay();
+        }
+    }
+    
+    /// Ticks every ParticleEmitter and hands its particles to the renderer.
+    /// Emitters whose reach lies entirely offscreen are paused, and finished
+    /// emitters are dropped.
+    fn update_particles(&mut self, delta_time: Duration) {
+        if self.particle_emitters.is_empty() {
+            return;
+        }
+        
+        let dt = delta_time.as_secs_f32();
+        let mut finished = Vec::new();
+        
+        for (&element_id, emitter) in self.particle_emitters.iter_mut() {
+            let Some(element) = self.elements.get(&element_id) else {
+                finished.push(element_id);
+                continue;
+            };
+            if !element.visible {
+                continue;
+            }
+            
+            let position = self.layout_result.computed_positions.get(&element_id).copied().unwrap_or(element.position);
+            let size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+            let reach = emitter.config.reach();
+            let onscreen = position.x + size.x + reach >= 0.0
+                && position.y + size.y + reach >= 0.0
+                && position.x - reach <= self.viewport_size.x
+                && position.y - reach <= self.viewport_size.y;
+            if !onscreen {
+                continue;
+            }
+            
+            emitter.tick(dt);
+            if emitter.is_finished() {
+                finished.push(element_id);
+                continue;
+            }
+            
+            let origin = position + size * 0.5;
+            Arc::make_mut(&mut self.particles).insert(element_id, emitter.instances(origin));
+            self.needs_render = true;
+        }
+        
+        for element_id in finished {
+            self.particle_emitters.remove(&element_id);
+            Arc::make_mut(&mut self.particles).remove(&element_id);
+            self.needs_render = true;
+        }
+    }
+    
+    pub fn render(&mut self) -> anyhow::Result<()> {
+        self.flush_input()?;
+        // Input handled since the last update
+        if self.needs_render {
+            self.publish_snapshot();
+        }
+        let snapshot = self.snapshots.latest();
+        if snapshot.frame == self.rendered_frame {
+            return Ok(());
+        }
+        
+        let render_started = Instant::now();
+        self.renderer.render_snapshot(&snapshot)?;
+        self.rendered_frame = snapshot.frame;
+        self.frame_count += 1;
+        self.collect_renderer_warnings();
+        self.collect_decoded_images();
+        
+        let update_ms = self.last_update_time.as_secs_f32() * 1000.0;
+        let render_ms = render_started.elapsed().as_secs_f32() * 1000.0;
+        diagnostics::record_frame(diagnostics::FrameRecord {
+            frame: self.frame_count,
+            command_count: self.renderer.last_command_count(),
+            update_ms,
+            render_ms,
+        });
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.record_frame(update_ms + render_ms, self.renderer.last_frame_stats());
+        }
+        self.record_render_stats();
+        
+        // Note: Forced hover test removed - hover system confirmed working
+        
+        // Update timing
+        let now = Instant::now();
+        let frame_time = now.duration_since(self.last_frame_time);
+        self.last_frame_time = now;
+        
+        // Log FPS occasionally
+        if self.frame_count % 60 == 0 {
+            let fps = 1.0 / frame_time.as_secs_f32();
+            tracing::debug!("FPS: {:.1}", fps);
+        }
+        
+        Ok(())
+    }
+    
+    /// Queues `event` under the input policy; mouse moves and wheel deltas
+    /// are coalesced and processed by the next `update` or `render`.
+    pub fn handle_input(&mut self, event: InputEvent) -> anyhow::Result<()> {
+        for event in self.input.push(event) {
+            self.process_input(event)?;
+        }
+        Ok(())
+    }
+    
+    /// How mouse moves, wheel events and hover handlers are batched. The
+    /// default coalesces them; `InputPolicy::immediate()` processes every
+    /// event as it arrives.
+    pub fn set_input_policy(&mut self, policy: InputPolicy) -> anyhow::Result<()> {
+        self.flush_input()?;
+        self.input.set_policy(policy);
+        Ok(())
+    }
+    
+    /// Screen edges hidden by notches, rounded corners or a bezel. They pad
+    /// an App element with `safe_area` set and resolve
+    /// `env(safe-area-inset-*)` in padding.
+    pub fn set_safe_area_insets(&mut self, insets: SafeAreaInsets) {
+        self.layout_engine.set_safe_area(insets);
+        self.needs_layout = true;
+    }
+    
+    /// Switches the locale used by the date picker, template format filters
+    /// and `kryon.format`. Returns false, keeping the current one, if no
+    /// registered locale matches `tag`.
+    pub fn set_locale(&mut self, tag: &str) -> anyhow::Result<bool> {
+        if !self.i18n.set_locale(tag) {
+            return Ok(false);
+        }
+        self.apply_locale()?;
+        self.initialize_template_variables()?;
+        Ok(true)
+    }
+    
+    /// Adds locale data, or replaces a built-in locale's. Takes effect
+    /// through `set_locale` or the App element's `locale`.
+    pub fn register_locale(&mut self, locale: i18n::Locale) {
+        self.i18n.register(locale);
+    }
+    
+    /// Hands the active locale to template filters and scripts
+    fn apply_locale(&mut self) -> anyhow::Result<()> {
+        let locale = self.i18n.locale();
+        self.template_engine.set_locale(locale.clone());
+        self.script_system.set_locale(formatting::locale_script_value(locale))?;
+        self.needs_render = true;
+        Ok(())
+    }
+    
+    /// Measures text for layout with `measurer` instead of what the backend
+    /// supplied, e.g. the fonts an embedder draws with.
+    pub fn set_text_measurer(&mut self, measurer: Box<dyn TextMeasurer>) {
+        self.layout_engine.set_text_measurer(measurer);
+        self.needs_layout = true;
+    }
+    
+    /// Rotates the output for a display mounted sideways or upside down.
+    /// Layout uses the rotated size and pointer input is rotated back.
+    pub fn set_output_rotation(&mut self, rotation: OutputRotation) -> anyhow::Result<()> {
+        if !self.renderer.set_output_rotation(rotation) {
+            anyhow::bail!("This backend can't rotate its output");
+        }
+        self.viewport_size = self.renderer.viewport_size();
+        self.needs_layout = true;
+        self.needs_render = true;
+        self.update_orientation()
+    }
+    
+    /// Replaces the built-in default stylesheet, which styles buttons,
+    /// inputs, links and scrollbars below the KRB's own styles.
+    /// `DefaultStyles::default()` styles nothing.
+    pub fn set_default_styles(&mut self, default_styles: DefaultStyles) {
+        self.renderer.set_default_styles(default_styles);
+        self.needs_layout = true;
+        self.needs_render = true;
+    }
+    
+    /// Reads a default stylesheet for [`set_default_styles`](Self::set_default_styles)
+    /// from `path`
+    pub fn load_default_styles(&mut self, path: &str) -> anyhow::Result<()> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read default styles {}: {}", path, e))?;
+        let default_styles = DefaultStyles::parse(&source)
+            .map_err(|e| anyhow::anyhow!("Invalid default styles {}: {}", path, e))?;
+        self.set_default_styles(default_styles);
+        Ok(())
+    }
+    
+    /// Picks the default stylesheet's light or dark rules, e.g. from the
+    /// system's appearance setting
+    pub fn set_color_scheme(&mut self, color_scheme: ColorScheme) {
+        if color_scheme != self.color_scheme() {
+            self.renderer.set_color_scheme(color_scheme);
+            self.needs_layout = true;
+            self.needs_render = true;
+        }
+    }
+    
+    pub fn color_scheme(&self) -> ColorScheme {
+        self.renderer.style_computer().color_scheme()
+    }
+    
+    /// Portrait or landscape, from the (rotated) viewport's aspect
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+    
+    fn update_orientation(&mut self) -> anyhow::Result<()> {
+        let orientation = Orientation::from_size(self.viewport_size);
+        if orientation != self.orientation {
+            self.apply_orientation(orientation)?;
+        }
+        Ok(())
+    }
+    
+    /// Applies orientation variants of element properties and style blocks,
+    /// then tells scripts.
+    fn apply_orientation(&mut self, orientation: Orientation) -> anyhow::Result<()> {
+        self.orientation = orientation;
+        if !self.orientation_variants.is_empty() {
+            self.orientation_variants.apply(&mut self.elements, orientation);
+            self.script_system.refresh_elements(&self.elements)?;
+        }
+        self.renderer.set_orientation(orientation);
+        self.script_system.set_orientation(orientation)?;
+        self.needs_layout = true;
+        self.needs_render = true;
+        Ok(())
+    }
+    
+    /// Pointer positions arrive in surface coordinates, device pixels
+    /// unrotated; see `set_output_rotation` and `viewport_scale`
+    fn logical_position(&self, position: Vec2) -> Vec2 {
+        self.renderer.output_rotation().to_logical(position, self.renderer.surface_size()) / self.renderer.viewport_scale()
+    }
+    
+    fn flush_input(&mut self) -> anyhow::Result<()> {
+        for event in self.input.flush() {
+            self.process_input(event)?;
+        }
+        Ok(())
+    }
+    
+    fn process_input(&mut self, event: InputEvent) -> anyhow::Result<()> {
+        match event {
+            InputEvent::Resize { size } => {
+                self.renderer.resize(size)?;
+                self.viewport_size = self.renderer.viewport_size();
+                self.needs_layout = true;
+                self.update_orientation()?;
+            }
+            InputEvent::MouseMove { position } => {
+                self.handle_mouse_move(self.logical_position(position))?;
+            }
+            InputEvent::MousePress { position, button, modifiers } => {
+                self.modifiers = modifiers;
+                self.handle_mouse_press(self.logical_position(position), button)?;
+            }
+            InputEvent::MouseRelease { position, button, modifiers } => {
+                self.modifiers = modifiers;
+                self.handle_mouse_release(self.logical_position(position), button)?;
+            }
+            InputEvent::KeyPress { key, modifiers } => {
+                self.modifiers = modifiers;
+                self.handle_key_press(key, modifiers)?;
+            }
+            InputEvent::KeyRelease { modifiers, .. } => {
+                self.modifiers = modifiers;
+            }
+            InputEvent::Scroll { delta } => {
+                self.handle_scroll(delta);
+            }
+            InputEvent::TextInput { text } => {
+                let edit = match self.text_editor.focused().and_then(|element_id| self.elements.get_mut(&element_id)) {
+                    Some(element) => self.text_editor.insert(&mut element.text, &text),
+                    None => Edit::Ignored,
+                };
+                self.apply_edit(edit)?;
+            }
+            InputEvent::ImePreedit { text } => {
+                let edit = self.text_editor.set_preedit(&text);
+                self.apply_edit(edit)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    // In fn update_layout(&mut self) -> anyhow::Result<()>
+
+fn update_layout(&mut self) -> anyhow::Result<()> {
+    if let Some(root_id) = self.krb_file.root_element_id {
+        for (&element_id, element) in &self.elements {
+            if let Some(&size) = image_source(element).and_then(|source| self.image_sizes.get(source)) {
+                self.layout_engine.set_intrinsic_size(element_id, size);
+            }
+        }
+        
+        self.layout_engine.set_skipped_subtrees(self.content_visibility.skipped());
+        self.layout_result = self.layout_engine.compute_layout(
+            &self.elements,
+            root_id,
+            self.viewport_size,
+        );
+        
+        // Apply computed layout results back to element positions and sizes
+        for (&element_id, computed_position) in &self.layout_result.computed_positions {
+            if let Some(element) = self.elements.get_mut(&element_id) {
+                eprintln!("[LAYOUT_APPLY] Element {}: applying computed position {:?} (was {:?})", 
+                    element_id, computed_position, element.position);
+                element.position = *computed_position;
+            }
+        }
+        
+        for (&element_id, computed_size) in &self.layout_result.computed_sizes {
+            if let Some(element) = self.elements.get_mut(&element_id) {
+                // Debug: Log size application
+                eprintln!("[LAYOUT_APPLY] Element {}: applying computed size {:?} (was {:?})", 
+                    element_id, computed_size, element.size);
+                element.size = *computed_size;
+            }
+        }
+        
+        self.scroll.apply_layout(&mut self.elements, &mut self.layout_result);
+        
+        if !self.popups.is_empty() {
+            self.place_popups();
+        }
+        if self.date_picker.is_some() {
+            self.refresh_overlay();
+        }
+        
+        self.idle_work.plan(
+            &self.elements,
+            &self.layout_result,
+            self.viewport_size,
+            self.krb_file.fonts.keys().map(String::as_str),
+        );
+    }
+    Ok(())
+}
+
+    /// Scrolls the innermost scroll container under the pointer that can
+    /// still move.
+    fn handle_scroll(&mut self, delta: Vec2) {
+        if !self.scroll.scroll(&self.hover_path, delta, &mut self.elements, &mut self.layout_result) {
+            return;
+        }
+        eprintln!("[SCROLL] Scrolled by {:?}", delta);
+        if !self.popups.is_empty() {
+            self.place_popups();
+        }
+        if self.date_picker.is_some() {
+            self.refresh_overlay();
+        }
+        self.needs_render = true;
+    }
+
+    fn handle_mouse_move(&mut self, position: Vec2) -> anyhow::Result<()> {
+        if self.toasts.set_pointer(position, self.viewport_size, self.toast_reserved()) {
+            self.refresh_overlay();
+        }
+        if self.color_drag.is_some() {
+            self.drag_color(position)?;
+        }
+        if self.slider_drag.is_some() {
+            self.drag_slider(position)?;
+        }
+        // A drag takes the moves while it lasts
+        if self.update_drag(position)? {
+            return Ok(());
+        }
+        
+        let hovered_element = self.find_element_at_position(position);
+        let previous_target = self.hover_path.first().copied();
+        let path = kryon_core::hover_path(&self.elements, hovered_element);
+        let transition = HoverTransition::between(&self.hover_path, &path);
+        
+        // Determine the cursor type for the hovered element
+        let reveal_hover = hovered_element.filter(|&element_id| self.reveal_toggle_at(element_id, position));
+        let cursor_type = if reveal_hover.is_some() {
+            kryon_core::CursorType::Pointer
+        } else if let Some(element_id) = hovered_element {
+            if let Some(element) = self.elements.get(&element_id) {
+                element.cursor
+            } else {
+                kryon_core::CursorType::Default
+            }
+        } else {
+            kryon_core::CursorType::Default
+        };
+        if reveal_hover != self.reveal_hover {
+            for (element_id, hovered) in [(self.reveal_hover, false), (reveal_hover, true)] {
+                if let Some(element) = element_id.and_then(|element_id| self.elements.get_mut(&element_id)) {
+                    element.custom_properties.insert("reveal_hover".to_string(), kryon_core::PropertyValue::Bool(hovered));
+                }
+            }
+            self.reveal_hover = reveal_hover;
+            self.needs_render = true;
+        }
+        
+        // Update the cursor through the renderer
+        self.renderer.backend_mut().set_cursor(cursor_type);
+        
+        if !transition.is_empty() {
+            // Update hover states along the path (but preserve checked state)
+            for element_id in &transition.left {
+                if let Some(element) = self.elements.get_mut(element_id) {
+                    if element.current_state == InteractionState::Hover {
+                        element.current_state = InteractionState::Normal;
+                    }
+                }
+            }
+            for element_id in &transition.entered {
+                if let Some(element) = self.elements.get_mut(element_id) {
+                    if !matches!(element.current_state, InteractionState::Checked | InteractionState::Focus) {
+                        element.current_state = InteractionState::Hover;
+                    }
+                }
+            }
+            self.renderer.set_hover_path(&path);
+            self.needs_render = true;
+        }
+        self.hover_path = path;
+        
+        if self.popups.update_hover(&self.hover_path, position) {
+            self.place_popups();
+        }
+        
+        // Leave innermost first, then enter outermost first, as in the DOM.
+        // The related target is the element the pointer came from or went to.
+        for &element_id in &transition.left {
+            self.call_pointer_handler(element_id, EventType::PointerLeave, |app| {
+                let related_target = hovered_element.and_then(|id| app.elements.get(&id)).map(|element| element.id.clone());
+                script::event::PointerEvent { related_target, ..app.pointer_event("pointerleave", element_id, position, None) }
+            })?;
+        }
+        for &element_id in &transition.entered {
+            self.call_pointer_handler(element_id, EventType::PointerEnter, |app| {
+                let related_target = previous_target.and_then(|id| app.elements.get(&id)).map(|element| element.id.clone());
+                script::event::PointerEvent { related_target, ..app.pointer_event("pointerenter", element_id, position, None) }
+            })?;
+        }
+        
+        let Some(target) = hovered_element else {
+            return Ok(());
+        };
+        
+        // Trigger hover event, unless this element's handler ran moments ago
+        let now = self.started_at.elapsed();
+        if transition.entered.contains(&target) && self.has_handler(target, EventType::Hover) && self.input.allow_hover_handler(target, now) {
+            self.call_pointer_handler(target, EventType::Hover, |app| app.pointer_event("hover", target, position, None))?;
+        }
+        
+        // Moves bubble from the target through its ancestors
+        for element_id in self.hover_path.clone() {
+            self.call_pointer_handler(element_id, EventType::PointerMove, |app| {
+                let target_id = app.elements[&target].id.clone();
+                script::event::PointerEvent { target: target_id, ..app.pointer_event("pointermove", element_id, position, None) }
+            })?;
+        }
+        
+        Ok(())
+    }
+    
+    fn has_handler(&self, element_id: ElementId, event_type: EventType) -> bool {
+        self.elements.get(&element_id).is_some_and(|element| element.event_handlers.contains_key(&event_type))
+    }
+    
+    /// Calls the element's handler for `event_type` with the event from
+    /// `event`, which is only built if there is a handler.
+    fn call_pointer_handler(
+        &mut self,
+        element_id: ElementId,
+        event_type: EventType,
+        event: impl FnOnce(&Self) -> script::event::PointerEvent,
+    ) -> anyhow::Result<()> {
+        let Some(handler) = self.elements.get(&element_id).and_then(|element| element.event_handlers.get(&event_type)).cloned() else {
+            return Ok(());
+        };
+        let event = event(self).to_script_value();
+        self.script_system.call_function_with_values(&handler, vec![event])?;
+        Ok(())
+    }
+    
+    /// Runs `Press` or `Release` handlers for any button, bubbling from the
+    /// element under the pointer through its ancestors like pointer moves.
+    fn dispatch_button_event(&mut self, event_type: EventType, kind: &'static str, position: Vec2, button: MouseButton) -> anyhow::Result<()> {
+        let Some(target) = self.find_element_at_position(position) else {
+            return Ok(());
+        };
+        for element_id in kryon_core::hover_path(&self.elements, Some(target)) {
+            self.call_pointer_handler(element_id, event_type, |app| {
+                let target_id = app.elements[&target].id.clone();
+                script::event::PointerEvent { target: target_id, ..app.pointer_event(kind, element_id, position, Some(button)) }
+            })?;
+        }
+        Ok(())
+    }
+    
+    fn handle_mouse_press(&mut self, position: Vec2, button: MouseButton) -> anyhow::Result<()> {
+        // Toasts sit above everything; presses on them go no further
+        let reserved = self.toast_reserved();
+        if button == MouseButton::Left {
+            let (on_toast, action) = self.toasts.press(position, self.viewport_size, reserved);
+            if let Some(id) = action {
+                self.script_system.finish_toast(id, true)?;
+                self.refresh_overlay();
+            }
+            if on_toast {
+                return Ok(());
+            }
+        } else if self.toasts.hit(position, self.viewport_size, reserved) {
+            return Ok(());
+        }
+        self.dispatch_button_event(EventType::Press, "press", position, button)?;
+        
+        if button == MouseButton::Left {
+            let target = self.find_element_at_position(position);
+            
+            // An open date picker takes presses on it; one anywhere else
+            // closes it, and one on its own input does nothing more
+            if let Some(opened_by) = self.date_picker.as_ref().map(date_picker::DatePicker::element) {
+                let action = self.date_picker_click(position);
+                let closed = action == date_picker::PickerAction::Closed;
+                self.apply_picker_action(action)?;
+                if !closed || target == Some(opened_by) {
+                    return Ok(());
+                }
+            }
+            if let Some(element_id) = target.filter(|&element_id| self.date_input(element_id)) {
+                self.set_focus(None)?;
+                self.open_date_picker(element_id);
+                return Ok(());
+            }
+            // An eyedropper takes the color under the press, wherever it is
+            if let Some(element_id) = self.eyedropper.take() {
+                self.set_focus(None)?;
+                match self.renderer.sample_screen_color(position) {
+                    Some(rgba) => self.set_input_color(element_id, color_picker::Hsva::from_rgba(rgba), true)?,
+                    None => eprintln!("[COLOR] Couldn't sample the screen at {:?}", position),
+                }
+                return Ok(());
+            }
+            // A press on a color input's square or sliders picks there and
+            // starts a drag that keeps picking until the button is released
+            if let Some(element_id) = target.filter(|&element_id| self.color_input(element_id)) {
+                let local = self.local_position(element_id, position);
+                if let Some(region) = self.color_picker_layout(element_id).region_at(local) {
+                    self.set_focus(None)?;
+                    self.color_drag = Some((element_id, region));
+                    self.drag_color(position)?;
+                    return Ok(());
+                }
+            }
+            // Checkboxes and radio buttons change on release, so the press
+            // leaves them as they are. A press on a slider moves its thumb
+            // there and starts a drag that keeps moving it until release.
+            match target.and_then(|element_id| Some((element_id, self.form_control(element_id)?))) {
+                Some((_, form_controls::Control::Checkbox | form_controls::Control::Radio)) => {
+                    self.set_focus(None)?;
+                    return Ok(());
+                }
+                Some((element_id, form_controls::Control::Slider)) => {
+                    self.set_focus(None)?;
+                    self.slider_drag = Some(element_id);
+                    self.drag_slider(position)?;
+                    return Ok(());
+                }
+                None => {}
+            }
+            
+            // Pressing a text input focuses it and puts the caret under the
+            // pointer; pressing anywhere else takes focus away
+            let input = target.filter(|&element_id| self.text_input(element_id).is_some());
+            self.set_focus(input)?;
+            if let Some(element_id) = input {
+                // The eye of a password's reveal toggle shows or hides it
+                // and leaves the caret where it was
+                if self.reveal_toggle_at(element_id, position) {
+                    let element = self.elements.get_mut(&element_id).expect("text inputs are elements");
+                    let revealed = text_input::is_masked(element);
+                    element.custom_properties.insert("revealed".to_string(), kryon_core::PropertyValue::Bool(revealed));
+                    self.text_editor.set_masked(!revealed);
+                    self.sync_caret();
+                    return Ok(());
+                }
+                let element = &self.elements[&element_id];
+                let local = self.local_position(element_id, position);
+                let font_size = self.renderer.style_computer().compute(element_id).font_size;
+                let scroll = element.custom_properties.get("text_scroll").and_then(|value| value.as_float()).unwrap_or(0.0);
+                let index = text_input::index_at(&text_input::shown_value(element), local.x + scroll, font_size);
+                let edit = self.text_editor.place_caret(&element.text, index, self.modifiers.shift);
+                self.apply_edit(edit)?;
+                return Ok(());
+            }
+            
+            if let Some(element_id) = target {
+                if let Some(element) = self.elements.get_mut(&element_id) {
+                    element.current_state = InteractionState::Active;
+                    self.needs_render = true;
+                }
+                let path = kryon_core::hover_path(&self.elements, target);
+                if let Some(source) = drag_drop::draggable_in(&self.elements, &path) {
+                    self.drag_drop.press(source, position);
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    fn handle_mouse_release(&mut self, position: Vec2, button: MouseButton) -> anyhow::Result<()> {
+        self.dispatch_button_event(EventType::Release, "release", position, button)?;
+        
+        if button == MouseButton::Left {
+            if let Some(drag) = self.drag_drop.release() {
+                return self.finish_drag(drag, position);
+            }
+            self.color_drag = None;
+            self.slider_drag = None;
+            let target = self.find_element_at_position(position);
+            if self.popups.handle_click(&kryon_core::hover_path(&self.elements, target)) {
+                self.place_popups();
+            }
+            
+            if let Some(element_id) = target {
+                if matches!(self.form_control(element_id), Some(form_controls::Control::Checkbox | form_controls::Control::Radio)) {
+                    self.toggle_control(element_id)?;
+                }
+                self.handle_table_header_click(element_id, position);
+                self.record_interaction("click", element_id, Some(position));
+                
+                // Trigger click event first, before changing any states
+                if let Some(element) = self.elements.get(&element_id) {
+                    if let Some(handler) = element.event_handlers.get(&EventType::Click) {
+                        // Call the click handler function with the event object
+                        let event = self.pointer_event("click", element_id, position, Some(button)).to_script_value();
+                        self.script_system.call_function_with_values(handler, vec![event])?;
+                        
+                        // Apply any pending changes from scripts
+                        let changes_applied = self.script_system.apply_pending_changes(&mut self.elements)?;
+                        
+                        // Apply template variable changes from scripts
+                        let pending_changes = self.script_system.get_pending_changes()?;
+                        let template_variable_changes = if let Some(template_changes) = pending_changes.get("template_variables") {
+                            for (name, value) in &template_changes.data {
+                                self.set_template_variable(name, value)?;
+                            }
+                            !template_changes.data.is_empty()
+                        } else {
+                            false
+                        };
+                        
+                        if changes_applied || template_variable_changes {
+                            tracing::info!("Changes applied, triggering re-render");
+                            self.needs_render = true;
+                            
+                            // Force layout update for visibility changes and template variable changes
+                            // This ensures that elements become visible/invisible immediately and template variables update
+                            if template_variable_changes {
+                                self.update_layout()?;
+                                self.needs_layout = false;
+                                tracing::info!("🚀 [SCRIPT_IMMEDIATE] Immediate layout update applied for template changes");
+                            }
+                        }
+                        
+                        // After script changes are applied, set hover state only for non-checked elements
+                        if let Some(element) = self.elements.get_mut(&element_id) {
+                            if !matches!(element.current_state, InteractionState::Checked | InteractionState::Focus) {
+                                element.current_state = InteractionState::Hover;
+                                self.needs_render = true;
+                            }
+                        }
+                    } else {
+                        // No click handler, just set hover state
+                        if let Some(element) = self.elements.get_mut(&element_id) {
+                            if !matches!(element.current_state, InteractionState::Checked | InteractionState::Focus) {
+                                element.current_state = InteractionState::Hover;
+                                self.needs_render = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Starts a drag once the pointer is far enough from a press on a
+    /// draggable element, and follows it with the ghost, the drop target
+    /// and the cursor. Whether a drag is under way.
+    fn update_drag(&mut self, position: Vec2) -> anyhow::Result<bool> {
+        if let Some((source, origin)) = self.drag_drop.pending_start(position) {
+            self.start_drag(source, origin, position)?;
+        }
+        let Some(drag) = self.drag_drop.drag().cloned() else {
+            return Ok(false);
+        };
+        
+        let path = kryon_core::hover_path(&self.elements, self.find_element_at_position(position));
+        let over = drag_drop::drop_target_in(&self.elements, &path, &drag);
+        let mut target = None;
+        if let Some(element_id) = over {
+            // onDragOver can turn the drag away
+            let answer = self.call_drag_handler(element_id, EventType::DragOver, "dragover", &drag, position)?;
+            if answer != Some(script::engine_trait::ScriptValue::Boolean(false)) {
+                target = Some(element_id);
+            }
+        }
+        if let Some(drag) = self.drag_drop.drag_mut() {
+            drag.position = position;
+            drag.target = target;
+        }
+        let cursor = if target.is_some() { kryon_core::CursorType::Move } else { kryon_core::CursorType::NotAllowed };
+        self.renderer.backend_mut().set_cursor(cursor);
+        self.needs_render = true;
+        Ok(true)
+    }
+    
+    /// Runs the source's `onDragStart`, which can replace the payload or
+    /// refuse the drag
+    fn start_drag(&mut self, source: ElementId, origin: Vec2, position: Vec2) -> anyhow::Result<()> {
+        let Some(element) = self.elements.get(&source) else {
+            self.drag_drop.cancel();
+            return Ok(());
+        };
+        let mut drag = drag_drop::Drag::new(source, element, origin);
+        match self.call_drag_handler(source, EventType::DragStart, "dragstart", &drag, position)? {
+            Some(script::engine_trait::ScriptValue::Boolean(false)) => {
+                self.drag_drop.cancel();
+                return Ok(());
+            }
+            None | Some(script::engine_trait::ScriptValue::Nil) => {}
+            Some(data) => drag.data = data,
+        }
+        eprintln!("[DRAG] Dragging '{}'", self.elements[&source].id);
+        self.drag_drop.start(drag);
+        self.record_interaction("drag", source, Some(origin));
+        Ok(())
+    }
+    
+    /// Drops `drag` on the target that took it, if any, and puts the
+    /// dragged element and the hover state back as they were
+    fn finish_drag(&mut self, drag: drag_drop::Drag, position: Vec2) -> anyhow::Result<()> {
+        if let Some(target) = drag.target.filter(|target| self.elements.contains_key(target)) {
+            eprintln!("[DRAG] Dropped '{}' on '{}'", self.elements.get(&drag.source).map_or("", |element| element.id.as_str()), self.elements[&target].id);
+            self.call_drag_handler(target, EventType::Drop, "drop", &drag, position)?;
+            self.record_interaction("drop", target, Some(position));
+        }
+        if let Some(element) = self.elements.get_mut(&drag.source) {
+            if element.current_state == InteractionState::Active {
+                element.current_state = InteractionState::Normal;
+            }
+        }
+        self.needs_render = true;
+        // The hover path and cursor weren't followed during the drag
+        self.handle_mouse_move(position)
+    }
+    
+    /// Calls the element's drag handler, returning what it returned, or
+    /// `None` if it has none
+    fn call_drag_handler(
+        &mut self,
+        element_id: ElementId,
+        event_type: EventType,
+        kind: &'static str,
+        drag: &drag_drop::Drag,
+        position: Vec2,
+    ) -> anyhow::Result<Option<script::engine_trait::ScriptValue>> {
+        let Some(handler) = self.elements.get(&element_id).and_then(|element| element.event_handlers.get(&event_type)).cloned() else {
+            return Ok(None);
+        };
+        let event = script::event::DragEvent {
+            kind,
+            target: self.elements[&element_id].id.clone(),
+            source: self.elements.get(&drag.source).map(|element| element.id.clone()).unwrap_or_default(),
+            data: drag.data.clone(),
+            drag_type: drag.drag_type.clone(),
+            client: position,
+            local: self.local_position(element_id, position),
+            modifiers: self.modifiers,
+            timestamp: self.started_at.elapsed().as_secs_f64() * 1000.0,
+        };
+        let answer = self.script_system.call_function_with_values(&handler, vec![event.to_script_value()])?;
+        Ok(Some(answer))
+    }
+    
+    /// Clicking a Table header cycles the clicked column's sort.
+    fn handle_table_header_click(&mut self, element_id: ElementId, position: Vec2) {
+        let (Some(view), Some(element)) = (self.tables.get(&element_id), self.elements.get(&element_id)) else {
+            return;
+        };
+        let size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        
+        if let Some(column) = view.header_column_at(self.local_position(element_id, position), size.x, element.font_size) {
+            let mut view = view.clone();
+            view.toggle_sort(column);
+            self.set_table_view(element_id, view);
+        }
+    }
+    
+    fn handle_key_press(&mut self, key: KeyCode, modifiers: kryon_render::KeyModifiers) -> anyhow::Result<()> {
+        if let Some(picker) = &mut self.date_picker {
+            let action = picker.key(key, modifiers);
+            return self.apply_picker_action(action);
+        }
+        
+        if key == KeyCode::Escape && self.eyedropper.take().is_some() {
+            eprintln!("[COLOR] Eyedropper cancelled");
+            return Ok(());
+        }
+        
+        if key == KeyCode::Escape {
+            if let Some(drag) = self.drag_drop.release() {
+                eprintln!("[DRAG] Drag cancelled");
+                let position = drag.position;
+                return self.finish_drag(drag_drop::Drag { target: None, ..drag }, position);
+            }
+        }
+        
+        if key == KeyCode::Tab && !modifiers.ctrl {
+            return self.focus_next_input(modifiers.shift);
+        }
+        
+        // The focused input gets the key first
+        let Self { text_editor, elements, renderer, clipboard, .. } = self;
+        let edit = match text_editor.focused().and_then(|element_id| elements.get_mut(&element_id)) {
+            Some(element) => text_editor.key(&mut element.text, key, modifiers, &mut AppClipboard { renderer, fallback: clipboard }),
+            None => Edit::Ignored,
+        };
+        if self.apply_edit(edit)? {
+            return Ok(());
+        }
+        
+        // Handle global key events
+        match key {
+            KeyCode::Escape => {
+                // Could trigger app exit
+            }
+            KeyCode::Character(c) if c.eq_ignore_ascii_case(&'p') && modifiers.ctrl && modifiers.shift => {
+                let enabled = !self.profiler.is_enabled();
+                self.set_profiler_overlay(enabled);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    
+    /// Whether a text input at `element_id` is read-only; `None` if the
+    /// element isn't a text input
+    fn text_input(&self, element_id: ElementId) -> Option<bool> {
+        let element = self.elements.get(&element_id)?;
+        let input_type = element.custom_properties.get("input_type").and_then(|value| value.as_string()).unwrap_or("text");
+        if element.element_type != ElementType::Input || !text_input::TEXT_INPUT_TYPES.contains(&input_type) {
+            return None;
+        }
+        Some(matches!(element.custom_properties.get("readonly"), Some(kryon_core::PropertyValue::Bool(true))))
+    }
+    
+    /// Whether `element_id` is a date input that can be changed
+    fn date_input(&self, element_id: ElementId) -> bool {
+        let Some(element) = self.elements.get(&element_id) else {
+            return false;
+        };
+        let flag = |name: &str| matches!(element.custom_properties.get(name), Some(kryon_core::PropertyValue::Bool(true)));
+        element.element_type == ElementType::Input
+            && element.custom_properties.get("input_type").and_then(|value| value.as_string()) == Some("date")
+            && !flag("readonly")
+            && !flag("disabled")
+    }
+    
+    /// Opens the calendar of the date input `element_id` below it
+    fn open_date_picker(&mut self, element_id: ElementId) {
+        let list = self.renderer.backend().prefers_list_popups();
+        let Some(element) = self.elements.get_mut(&element_id) else {
+            return;
+        };
+        self.date_picker = Some(date_picker::DatePicker::open(element_id, element, date_picker::Date::today(), list));
+        element.current_state = InteractionState::Focus;
+        self.refresh_overlay();
+    }
+    
+    /// Where the open date picker is, below its input
+    fn date_picker_bounds(&self) -> Option<popups::Rect> {
+        let picker = self.date_picker.as_ref()?;
+        let element = self.elements.get(&picker.element())?;
+        let anchor = popups::Rect {
+            position: self.layout_result.computed_positions.get(&picker.element()).copied().unwrap_or(element.position),
+            size: self.layout_result.computed_sizes.get(&picker.element()).copied().unwrap_or(element.size),
+        };
+        Some(picker.bounds(anchor, self.viewport_size))
+    }
+    
+    fn date_picker_commands(&self) -> Vec<RenderCommand> {
+        let (Some(picker), Some(bounds)) = (&self.date_picker, self.date_picker_bounds()) else {
+            return Vec::new();
+        };
+        let style = self.renderer.style_computer().compute(picker.element());
+        let colors = date_picker::PickerColors {
+            background: if style.background_color.w > 0.0 { style.background_color } else { glam::Vec4::ONE },
+            text: style.text_color,
+            border: style.border_color,
+        };
+        let selected = self.elements.get(&picker.element()).and_then(|element| date_picker::Date::parse_iso(&element.text));
+        picker.commands(bounds, self.i18n.locale(), selected, style.font_size, colors)
+    }
+    
+    fn date_picker_click(&mut self, position: Vec2) -> date_picker::PickerAction {
+        let Some(bounds) = self.date_picker_bounds() else {
+            return date_picker::PickerAction::Closed;
+        };
+        let Self { date_picker, i18n, .. } = self;
+        match date_picker {
+            Some(picker) => picker.click(position, bounds, i18n.locale()),
+            None => date_picker::PickerAction::Closed,
+        }
+    }
+    
+    /// Redraws, closes or picks a day from the open date picker. A picked
+    /// day becomes the input's value and its `value_variable`, and the
+    /// input's `onChange` is called.
+    fn apply_picker_action(&mut self, action: date_picker::PickerAction) -> anyhow::Result<()> {
+        let Some(element_id) = self.date_picker.as_ref().map(date_picker::DatePicker::element) else {
+            return Ok(());
+        };
+        let picked = match action {
+            date_picker::PickerAction::Handled => return Ok(()),
+            date_picker::PickerAction::Moved => {
+                self.refresh_overlay();
+                return Ok(());
+            }
+            date_picker::PickerAction::Picked(date) => Some(date),
+            date_picker::PickerAction::Closed => None,
+        };
+        self.date_picker = None;
+        self.refresh_overlay();
+        let Some(element) = self.elements.get_mut(&element_id) else {
+            return Ok(());
+        };
+        if element.current_state == InteractionState::Focus {
+            element.current_state = InteractionState::Normal;
+        }
+        let Some(date) = picked else {
+            return Ok(());
+        };
+        let value = date.to_string();
+        element.text = value.clone();
+        let variable = element.custom_properties.get("value_variable").and_then(|value| value.as_string()).map(str::to_string);
+        if let Some(name) = variable {
+            self.assign_template_variable(&name, &value)?;
+        }
+        self.script_system.refresh_elements(&self.elements)?;
+        self.call_field_handler(element_id, EventType::Change, "change")
+    }
+    
+    /// Whether `element_id` is a color input that can be changed
+    fn color_input(&self, element_id: ElementId) -> bool {
+        let Some(element) = self.elements.get(&element_id) else {
+            return false;
+        };
+        let flag = |name: &str| matches!(element.custom_properties.get(name), Some(kryon_core::PropertyValue::Bool(true)));
+        is_color_input(element) && !flag("readonly") && !flag("disabled")
+    }
+    
+    /// Where the parts of the color input `element_id` are, relative to
+    /// its top-left corner
+    fn color_picker_layout(&self, element_id: ElementId) -> color_picker::PickerLayout {
+        let element = &self.elements[&element_id];
+        let size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        color_picker::PickerLayout::new(Vec2::ZERO, size, color_picker::shows_alpha(element))
+    }
+    
+    /// Picks the color under `position` on the part of the color input the
+    /// drag began on
+    fn drag_color(&mut self, position: Vec2) -> anyhow::Result<()> {
+        let Some((element_id, region)) = self.color_drag.filter(|(element_id, _)| self.elements.contains_key(element_id)) else {
+            self.color_drag = None;
+            return Ok(());
+        };
+        let current = color_picker::element_color(&self.elements[&element_id]);
+        let local = self.local_position(element_id, position);
+        let picked = self.color_picker_layout(element_id).pick(region, local, current);
+        if picked == current {
+            return Ok(());
+        }
+        self.set_input_color(element_id, picked, true)
+    }
+    
+    /// Makes `color` the value of the color input `element_id`, and of its
+    /// `value_variable`, and calls its `onChange`. The hex goes into the text
+    /// input its `hex_input` names too, unless that's where it came from.
+    fn set_input_color(&mut self, element_id: ElementId, color: color_picker::Hsva, to_hex_input: bool) -> anyhow::Result<()> {
+        let Some(element) = self.elements.get_mut(&element_id) else {
+            return Ok(());
+        };
+        let color = match color_picker::shows_alpha(element) {
+            true => color,
+            false => color_picker::Hsva { alpha: 1.0, ..color },
+        };
+        let value = color.hex();
+        element.text = value.clone();
+        for (name, amount) in [("color_hue", color.hue), ("color_saturation", color.saturation), ("color_value", color.value)] {
+            element.custom_properties.insert(name.to_string(), kryon_core::PropertyValue::Float(amount));
+        }
+        let string = |name: &str| element.custom_properties.get(name).and_then(|value| value.as_string()).map(str::to_string);
+        let (hex_input, variable) = (string("hex_input"), string("value_variable"));
+        self.needs_render = true;
+        
+        let hex_input = hex_input.filter(|_| to_hex_input).and_then(|id| self.element_index.get(&id));
+        if let Some(input) = hex_input.and_then(|input_id| self.elements.get_mut(&input_id)) {
+            input.text = value.clone();
+        }
+        if let Some(name) = variable {
+            self.assign_template_variable(&name, &value)?;
+        }
+        self.script_system.refresh_elements(&self.elements)?;
+        self.call_field_handler(element_id, EventType::Change, "change")
+    }
+    
+    /// The checkbox, radio button or slider `element_id` is, if it's one
+    /// that can be changed
+    fn form_control(&self, element_id: ElementId) -> Option<form_controls::Control> {
+        self.elements.get(&element_id).and_then(form_controls::control)
+    }
+    
+    /// Toggles the checkbox `element_id`, or checks the radio button and
+    /// unchecks the others in its group, then puts the new value into its
+    /// `value_variable` and calls its `onChange`
+    fn toggle_control(&mut self, element_id: ElementId) -> anyhow::Result<()> {
+        let Some(element) = self.elements.get(&element_id) else {
+            return Ok(());
+        };
+        let checked = !form_controls::is_checked(element);
+        let (value, unchecked) = match form_controls::control(element) {
+            Some(form_controls::Control::Checkbox) => (checked.to_string(), Vec::new()),
+            Some(form_controls::Control::Radio) if checked => {
+                let group = form_controls::radio_group(element);
+                let unchecked: Vec<ElementId> = self.elements.iter()
+                    .filter(|&(&other_id, other)| other_id != element_id && group.is_some() && form_controls::radio_group(other) == group)
+                    .filter(|(_, other)| other.custom_properties.get("input_type").and_then(|value| value.as_string()) == Some("radio"))
+                    .filter(|(_, other)| form_controls::is_checked(other))
+                    .map(|(&other_id, _)| other_id)
+                    .collect();
+                (form_controls::radio_value(element), unchecked)
+            }
+            _ => return Ok(()),
+        };
+        let variable = element.custom_properties.get("value_variable").and_then(|value| value.as_string()).map(str::to_string);
+        
+        for other_id in unchecked {
+            if let Some(other) = self.elements.get_mut(&other_id) {
+                other.current_state = InteractionState::Normal;
+            }
+        }
+        if let Some(element) = self.elements.get_mut(&element_id) {
+            // The pointer is still over it
+            element.current_state = if checked { InteractionState::Checked } else { InteractionState::Hover };
+        }
+        self.needs_render = true;
+        if let Some(name) = variable {
+            self.assign_template_variable(&name, &value)?;
+        }
+        self.script_system.refresh_elements(&self.elements)?;
+        self.call_field_handler(element_id, EventType::Change, "change")
+    }
+    
+    /// Moves the thumb of the slider being dragged to under `position`. A
+    /// new value goes into the slider's `value_variable`, and its
+    /// `onChange` is called.
+    fn drag_slider(&mut self, position: Vec2) -> anyhow::Result<()> {
+        let Some(element_id) = self.slider_drag.filter(|element_id| self.elements.contains_key(element_id)) else {
+            self.slider_drag = None;
+            return Ok(());
+        };
+        let element = &self.elements[&element_id];
+        let width = self.layout_result.computed_sizes.get(&element_id).map_or(element.size.x, |size| size.x);
+        let fraction = if width > 0.0 { self.local_position(element_id, position).x / width } else { 0.0 };
+        let value = form_controls::SliderRange::of(element).value_at(fraction);
+        if element.custom_properties.contains_key("value") && value == form_controls::slider_value(element) {
+            return Ok(());
+        }
+        let variable = element.custom_properties.get("value_variable").and_then(|value| value.as_string()).map(str::to_string);
+        
+        let element = self.elements.get_mut(&element_id).expect("the slider was looked up above");
+        element.custom_properties.insert("value".to_string(), kryon_core::PropertyValue::Float(value));
+        self.needs_render = true;
+        if let Some(name) = variable {
+            self.assign_template_variable(&name, &form_controls::format_value(value))?;
+        }
+        self.script_system.refresh_elements(&self.elements)?;
+        self.call_field_handler(element_id, EventType::Change, "change")
+    }
+    
+    /// Gives the color typed into the text input `element_id` to the color
+    /// inputs whose `hex_input` it is, once it's a whole hex color
+    fn follow_hex_input(&mut self, element_id: ElementId) -> anyhow::Result<()> {
+        let Some(input) = self.elements.get(&element_id) else {
+            return Ok(());
+        };
+        let Some(rgba) = kryon_core::parse_hex_color(&input.text) else {
+            return Ok(());
+        };
+        let pickers: Vec<ElementId> = self.elements.iter()
+            .filter(|(_, element)| is_color_input(element))
+            .filter(|(_, element)| element.custom_properties.get("hex_input").and_then(|value| value.as_string()) == Some(input.id.as_str()))
+            .map(|(&picker, _)| picker)
+            .collect();
+        for picker in pickers {
+            self.set_input_color(picker, color_picker::Hsva::from_rgba(rgba), false)?;
+        }
+        Ok(())
+    }
+    
+    /// Arms the eyedropper of the color inputs `kryon.eyedropper(id)` asked
+    /// for, on backends that can read the screen
+    fn update_eyedropper(&mut self) {
+        for id in self.script_system.take_eyedropper_requests() {
+            let Some(element_id) = self.element_index.get(&id).filter(|&element_id| self.color_input(element_id)) else {
+                eprintln!("[COLOR] No color input with id '{}' for the eyedropper", id);
+                continue;
+            };
+            if !self.renderer.backend().supports_eyedropper() {
+                eprintln!("[COLOR] The {} backend can't sample screen colors; ignoring the eyedropper", self.renderer.backend().backend_name());
+                continue;
+            }
+            self.eyedropper = Some(element_id);
+        }
+    }
+    
+    /// Moves focus to the text input `target`, or away from every input,
+    /// calling `onBlur` and `onFocus`
+    fn set_focus(&mut self, target: Option<ElementId>) -> anyhow::Result<()> {
+        if self.text_editor.focused() == target {
+            return Ok(());
+        }
+        if let Some(previous) = self.text_editor.blur() {
+            if let Some(element) = self.elements.get_mut(&previous) {
+                if element.current_state == InteractionState::Focus {
+                    element.current_state = InteractionState::Normal;
+                }
+                for name in ["caret", "selection_start", "selection_end", "preedit", "text_scroll", "caret_blink"] {
+                    element.custom_properties.remove(name);
+                }
+            }
+            self.needs_render = true;
+            self.validate_input(previous)?;
+            self.call_field_handler(previous, EventType::Blur, "blur")?;
+        }
+        let Some((element_id, readonly)) = target.and_then(|element_id| Some((element_id, self.text_input(element_id)?))) else {
+            return Ok(());
+        };
+        let element = self.elements.get_mut(&element_id).expect("text inputs are elements");
+        element.current_state = InteractionState::Focus;
+        // A value set by a script or template may not be formatted yet
+        let mask = input_mask::InputMask::from_element(element);
+        if let Some(mask) = &mask {
+            element.text = mask.format(&mask.unmask(&element.text));
+        }
+        self.text_editor.focus(element_id, &element.text, readonly);
+        self.text_editor.set_masked(text_input::is_masked(element));
+        self.text_editor.set_input_mask(mask);
+        self.sync_caret();
+        self.call_field_handler(element_id, EventType::Focus, "focus")
+    }
+    
+    /// Tab moves focus through the visible text inputs in document order;
+    /// Shift+Tab moves back
+    fn focus_next_input(&mut self, backwards: bool) -> anyhow::Result<()> {
+        let mut inputs: Vec<ElementId> = self.elements.keys()
+            .copied()
+            .filter(|&element_id| self.text_input(element_id).is_some() && self.is_visible(element_id))
+            .collect();
+        if inputs.is_empty() {
+            return Ok(());
+        }
+        inputs.sort_unstable();
+        let current = self.text_editor.focused().and_then(|focused| inputs.iter().position(|&element_id| element_id == focused));
+        let next = match (current, backwards) {
+            (Some(index), false) => (index + 1) % inputs.len(),
+            (Some(index), true) => (index + inputs.len() - 1) % inputs.len(),
+            (None, false) => 0,
+            (None, true) => inputs.len() - 1,
+        };
+        self.set_focus(Some(inputs[next]))
+    }
+    
+    /// Shows what the text editor did in the focused input and tells its
+    /// handlers. Returns false if the editor didn't use the input.
+    fn apply_edit(&mut self, edit: Edit) -> anyhow::Result<bool> {
+        let Some(element_id) = self.text_editor.focused() else {
+            return Ok(false);
+        };
+        match edit {
+            Edit::Ignored => return Ok(false),
+            Edit::Handled => {}
+            Edit::Moved => self.sync_caret(),
+            Edit::Changed => {
+                self.sync_caret();
+                // An input checked once is checked again as it's edited
+                if self.elements.get(&element_id).is_some_and(|element| element.custom_properties.contains_key("invalid")) {
+                    self.validate_input(element_id)?;
+                }
+                // Masked inputs keep their raw value in the template variable
+                // their `raw_variable` names
+                let raw_variable = self.elements.get(&element_id).and_then(|element| {
+                    let name = element.custom_properties.get("raw_variable")?.as_string()?.to_string();
+                    Some((name, input_mask::raw_value(element)))
+                });
+                if let Some((name, raw_value)) = raw_variable {
+                    self.assign_template_variable(&name, &raw_value)?;
+                }
+                // Scripts reading the input see what was typed
+                self.script_system.refresh_elements(&self.elements)?;
+                self.call_field_handler(element_id, EventType::Change, "change")?;
+                self.follow_hex_input(element_id)?;
+            }
+            Edit::Submit => {
+                self.validate_input(element_id)?;
+                self.call_field_handler(element_id, EventType::Submit, "submit")?;
+            }
+        }
+        Ok(true)
+    }
+    
+    /// Copies the editor's caret, selection and composition into the
+    /// focused input's properties, which the renderer draws them from,
+    /// scrolls its value to keep the caret in view and restarts the blink
+    fn sync_caret(&mut self) {
+        let Some(element_id) = self.text_editor.focused() else {
+            return;
+        };
+        let size = self.layout_result.computed_sizes.get(&element_id).copied();
+        let font_size = self.renderer.style_computer().compute(element_id).font_size;
+        let Some(element) = self.elements.get_mut(&element_id) else {
+            return;
+        };
+        let (start, end) = self.text_editor.selection();
+        let caret = self.text_editor.caret();
+        let preedit = self.text_editor.preedit();
+        // Scrolled as drawn, with the composition in the value and the caret after it
+        let mut shown = element.text.clone();
+        shown.insert_str(shown.char_indices().nth(caret).map_or(shown.len(), |(byte, _)| byte), preedit);
+        if text_input::is_masked(element) {
+            shown = text_input::masked(element, &shown);
+        }
+        let shown_caret = caret + preedit.chars().count();
+        let scroll = element.custom_properties.get("text_scroll").and_then(|value| value.as_float()).unwrap_or(0.0);
+        let width = text_input::text_area_width(size.unwrap_or(element.size), text_input::has_reveal_toggle(element));
+        let scroll = text_input::scroll_to_caret(&shown, shown_caret, font_size, width, scroll);
+        
+        let properties = &mut element.custom_properties;
+        properties.insert("caret".to_string(), kryon_core::PropertyValue::Int(caret as i32));
+        properties.insert("selection_start".to_string(), kryon_core::PropertyValue::Int(start as i32));
+        properties.insert("selection_end".to_string(), kryon_core::PropertyValue::Int(end as i32));
+        match preedit {
+            "" => properties.remove("preedit"),
+            preedit => properties.insert("preedit".to_string(), kryon_core::PropertyValue::String(preedit.to_string())),
+        };
+        properties.insert("text_scroll".to_string(), kryon_core::PropertyValue::Float(scroll));
+        // A moved caret shows solid before it blinks again
+        properties.insert("caret_blink".to_string(), kryon_core::PropertyValue::Float(0.0));
+        self.caret_blink = Duration::ZERO;
+        self.needs_render = true;
+    }
+    
+    /// Blinks the focused input's caret, redrawing only when it shows or hides
+    fn update_caret_blink(&mut self, delta_time: Duration) {
+        let Some(element) = self.text_editor.focused().and_then(|element_id| self.elements.get_mut(&element_id)) else {
+            return;
+        };
+        let shown_before = text_input::caret_shown(text_input::blink_phase(self.caret_blink));
+        self.caret_blink += delta_time;
+        let phase = text_input::blink_phase(self.caret_blink);
+        if text_input::caret_shown(phase) != shown_before {
+            element.custom_properties.insert("caret_blink".to_string(), kryon_core::PropertyValue::Float(phase));
+            self.needs_render = true;
+        }
+    }
+    
+    /// Checks a text input against its constraints, marking it `invalid`
+    /// with an `error_message` or clearing both, and calls `onInvalid` when
+    /// it starts failing. Inputs without constraints are left alone.
+    fn validate_input(&mut self, element_id: ElementId) -> anyhow::Result<()> {
+        let Some(element) = self.elements.get_mut(&element_id) else {
+            return Ok(());
+        };
+        if field_validation::Constraints::from_element(element).is_empty() {
+            return Ok(());
+        }
+        let was_invalid = kryon_core::is_invalid(element);
+        let message = field_validation::validation_message(element);
+        let properties = &mut element.custom_properties;
+        properties.insert("invalid".to_string(), kryon_core::PropertyValue::Bool(message.is_some()));
+        match &message {
+            Some(message) => properties.insert("error_message".to_string(), kryon_core::PropertyValue::String(message.clone())),
+            None => properties.remove("error_message"),
+        };
+        self.needs_render = true;
+        if message.is_some() && !was_invalid {
+            self.call_field_handler(element_id, EventType::Invalid, "invalid")?;
+        }
+        Ok(())
+    }
+    
+    fn call_field_handler(&mut self, element_id: ElementId, event_type: EventType, kind: &'static str) -> anyhow::Result<()> {
+        self.record_interaction(kind, element_id, None);
+        let Some(element) = self.elements.get(&element_id) else {
+            return Ok(());
+        };
+        let Some(handler) = element.event_handlers.get(&event_type).cloned() else {
+            return Ok(());
+        };
+        let control = form_controls::control(element);
+        let (value, raw_value) = match control {
+            Some(form_controls::Control::Checkbox) => (form_controls::is_checked(element).to_string(), None),
+            Some(form_controls::Control::Radio) => (form_controls::radio_value(element), None),
+            Some(form_controls::Control::Slider) => (form_controls::format_value(form_controls::slider_value(element)), None),
+            // A password's value is only for `getSecretValue`
+            None if text_input::is_password(element) => {
+                (text_input::masked(element, &element.text), Some(text_input::masked(element, &input_mask::raw_value(element))))
+            }
+            None => (element.text.clone(), Some(input_mask::raw_value(element))),
+        };
+        let event = script::event::FieldEvent {
+            kind,
+            target: element.id.clone(),
+            raw_value: raw_value.unwrap_or_else(|| value.clone()),
+            value,
+            selection: self.text_editor.selection(),
+            validation_message: element.custom_properties.get("error_message").and_then(|value| value.as_string()).unwrap_or_default().to_string(),
+            color: is_color_input(element).then(|| color_picker::element_color(element).to_rgba()),
+            checked: matches!(control, Some(form_controls::Control::Checkbox | form_controls::Control::Radio)).then(|| form_controls::is_checked(element)),
+            timestamp: self.started_at.elapsed().as_secs_f64() * 1000.0,
+        };
+        self.script_system.call_function_with_values(&handler, vec![event.to_script_value()])?;
+        Ok(())
+    }
+    
+    /// Where the caret of the focused text input is, as a position and size
+    /// in window coordinates (device pixels), for placing an input
+    /// method's candidate window. `None` while no input has focus.
+    pub fn ime_cursor_area(&self) -> Option<(Vec2, Vec2)> {
+        let element_id = self.text_editor.focused()?;
+        let element = self.elements.get(&element_id)?;
+        let position = self.layout_result.computed_positions.get(&element_id).copied().unwrap_or(element.position);
+        let size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        let font_size = self.renderer.style_computer().compute(element_id).font_size;
+        let scroll = element.custom_properties.get("text_scroll").and_then(|value| value.as_float()).unwrap_or(0.0);
+        let x = text_input::caret_x(&text_input::shown_value(element), self.text_editor.caret(), font_size) - scroll;
+        let (top, bottom) = (position + Vec2::new(x, 0.0), position + Vec2::new(x, size.y));
+        // The bounds of the caret as drawn, for a rotated or scaled input
+        let (top, bottom) = match self.resolved_transform(element_id, size) {
+            Some(transform) => (transform.transform_point(top, position)?, transform.transform_point(bottom, position)?),
+            None => (top, bottom),
+        };
+        let min = top.min(bottom);
+        let scale = self.renderer.viewport_scale();
+        Some((min * scale, (top.max(bottom) - min).max(Vec2::ONE) * scale))
+    }
+    
+    /// Whether `position` is on the eye of a password input's reveal toggle
+    fn reveal_toggle_at(&self, element_id: ElementId, position: Vec2) -> bool {
+        let Some(element) = self.elements.get(&element_id).filter(|element| text_input::has_reveal_toggle(element)) else {
+            return false;
+        };
+        let size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        let (area_position, area_size) = text_input::reveal_toggle_area(Vec2::ZERO, size);
+        let local = self.local_position(element_id, position);
+        local.cmpge(area_position).all() && local.cmplt(area_position + area_size).all()
+    }
+    
+    /// The element's transform as drawn, with its lengths resolved against
+    /// its font size and `size`.
+    fn resolved_transform(&self, element_id: ElementId, size: Vec2) -> Option<kryon_core::ResolvedTransform> {
+        let transform = self.elements.get(&element_id)?.custom_properties.get("transform")?.as_transform()?;
+        let style = self.renderer.style_computer().compute(element_id);
+        let lengths = self.renderer.style_computer().length_context(element_id, &style);
+        Some(kryon_core::ResolvedTransform::resolve(&transform.resolve_lengths(&lengths, size), size))
+    }
+    
+    /// Where `position`, in window coordinates, falls in the element's
+    /// untransformed box, relative to its top-left corner, so a click on a
+    /// rotated slider or scaled input still maps onto its track or text.
+    fn local_position(&self, element_id: ElementId, position: Vec2) -> Vec2 {
+        let element = &self.elements[&element_id];
+        let element_pos = self.layout_result.computed_positions.get(&element_id).copied().unwrap_or(element.position);
+        let element_size = self.layout_result.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        let untransformed = self.resolved_transform(element_id, element_size)
+            .and_then(|transform| transform.inverse_transform_point(position, element_pos))
+            .unwrap_or(position);
+        untransformed - element_pos
+    }
+    
+    /// Builds the event object passed to script handlers, targeting
+    /// `element_id`, with `local_x`/`local_y` from `local_position`
+    fn pointer_event(&self, kind: &'static str, element_id: ElementId, position: Vec2, button: Option<MouseButton>) -> script::event::PointerEvent {
+        let element = &self.elements[&element_id];
+        script::event::PointerEvent {
+            kind,
+            target: element.id.clone(),
+            current_target: element.id.clone(),
+            related_target: None,
+            client: position,
+            local: self.local_position(element_id, position),
+            button,
+            modifiers: self.modifiers,
+            timestamp: self.started_at.elapsed().as_secs_f64() * 1000.0,
+        }
+    }
+    
+    fn receives_pointer_events(&self, element_id: ElementId) -> bool {
+        if self.overlay_mode {
+            kryon_core::inherited_pointer_events(&self.elements, element_id) == Some(true)
+        } else {
+            kryon_core::receives_pointer_events(&self.elements, element_id)
+        }
+    }
+    
+    /// Runs the app as a transparent overlay: the frame is cleared to
+    /// transparent and input goes only to elements that opt in with
+    /// `pointer_events: auto`. Everything else should pass through to the
+    /// windows below; see `captures_pointer_at`.
+    pub fn set_overlay_mode(&mut self, enabled: bool) {
+        self.overlay_mode = enabled;
+        self.needs_render = true;
+    }
+    
+    /// Whether a click at `position` lands on an element that takes input.
+    /// Overlay frontends turn OS-level click-through off only while this
+    /// holds.
+    pub fn captures_pointer_at(&self, position: Vec2) -> bool {
+        self.find_element_at_position(self.logical_position(position)).is_some()
+    }
+    
+    /// Whether any visible element takes pointer input at all. Frontends that
+    /// can't track the pointer while clicks pass through use this to decide
+    /// whether click-through can stay on for the whole window.
+    pub fn has_pointer_targets(&self) -> bool {
+        self.elements.iter().any(|(&id, element)| element.visible && self.receives_pointer_events(id))
+    }
+    
+    fn find_element_at_position(&self, position: Vec2) -> Option<ElementId> {
+        // Find the topmost element at the given position
+        let mut found_elements = Vec::new();
+        
+        for (element_id, element) in &self.elements {
+            if !self.is_visible(*element_id) || !self.receives_pointer_events(*element_id)
+                || self.content_visibility.is_skipped_content(&self.elements, *element_id) {
+                continue;
+            }
+            
+            let element_pos = self.layout_result.computed_positions
+                .get(element_id)
+                .copied()
+                .unwrap_or(element.position);
+            let element_size = self.layout_result.computed_sizes
+                .get(element_id)
+                .copied()
+                .unwrap_or(element.size);
+            
+            let mut region = HitRegion {
+                transform: self.resolved_transform(*element_id, element_size),
+                ..HitRegion::new(element_pos, element_size)
+            };
+            if !region.contains(position) || self.is_clipped_at(*element_id, position) {
+                continue;
+            }
+            
+            // Rounded corners only matter once the point is inside the rect
+            region.border_radius = self.renderer.style_computer().compute(*element_id).border_radius;
+            if region.contains(position) {
+                found_elements.push(*element_id);
+            }
+        }
+        
+        // Return the topmost element: highest z-index, then highest ID
+        found_elements.into_iter().max_by_key(|id| (self.elements[id].z_index, *id))
+    }
+    
+    /// Whether an ancestor that clips its overflow, such as a scroll
+    /// container, hides the element at `position`
+    fn is_clipped_at(&self, element_id: ElementId, position: Vec2) -> bool {
+        kryon_core::hover_path(&self.elements, Some(element_id)).iter().skip(1).any(|id| {
+            let ancestor = &self.elements[id];
+            if ancestor.overflow_x == kryon_core::OverflowType::Visible && ancestor.overflow_y == kryon_core::OverflowType::Visible {
+                return false;
+            }
+            let (Some(&ancestor_pos), Some(&ancestor_size)) = (self.layout_result.computed_positions.get(id), self.layout_result.computed_sizes.get(id)) else {
+                return false;
+            };
+            !HitRegion::new(ancestor_pos, ancestor_size).contains(position)
+        })
+    }
+    
+    /// Whether the element and all its ancestors are visible, as for rendering
+    fn is_visible(&self, element_id: ElementId) -> bool {
+        kryon_core::hover_path(&self.elements, Some(element_id)).iter().all(|id| self.elements[id].visible)
+    }
+    
+    /// The element with string id `id`; the first in document order if
+    /// several share it
+    pub fn get_element(&self, id: &str) -> Option<&Element> {
+        self.elements.get(&self.element_index.get(id)?)
+    }
+    
+    /// The contents of the element with string id `container`, as laid out
+    /// last, cut into pages for printing; see `ElementRenderer::paginate`
+    pub fn paginate(&self, container: &str, setup: &PageSetup) -> anyhow::Result<Vec<Page>> {
+        let element_id = self.element_index.get(container)
+            .ok_or_else(|| anyhow::anyhow!("No element with id '{}' to paginate", container))?;
+        Ok(self.renderer.paginate(&self.elements, &self.layout_result, element_id, setup)?)
+    }
+    
+    /// Like [`get_element`](Self::get_element). Changing the element's `id`
+    /// through this needs a [`reindex_elements`](Self::reindex_elements)
+    /// before it can be found by the new one.
+    pub fn get_element_mut(&mut self, id: &str) -> Option<&mut Element> {
+        self.elements.get_mut(&self.element_index.get(id)?)
+    }
+    
+    /// A handle for changing the element with string id `id` (or `#12` for
+    /// element 12), which takes care of layout and repaint
+    pub fn element(&mut self, id: &str) -> Option<ElementHandle<'_, R>> {
+        let element_id = self.element_by_string_id(id)?;
+        Some(ElementHandle::new(self, element_id))
+    }
+    
+    /// Like [`element`](Self::element), by numeric id
+    pub fn element_by_numeric_id(&mut self, element_id: ElementId) -> Option<ElementHandle<'_, R>> {
+        self.elements.contains_key(&element_id).then(|| ElementHandle::new(self, element_id))
+    }
+    
+    pub fn root_element(&mut self) -> Option<ElementHandle<'_, R>> {
+        self.element_by_numeric_id(self.krb_file.root_element_id?)
+    }
+    
+    /// Marks the app for a layout, or just a repaint, after an element
+    /// changed through a handle
+    fn element_changed(&mut self, affects_layout: bool) {
+        self.needs_layout |= affects_layout;
+        self.needs_render = true;
+        self.elements_changed = true;
+    }
+    
+    fn allocate_element_id(&mut self) -> ElementId {
+        let next_free = self.elements.keys().max().map_or(0, |id| id + 1);
+        let element_id = next_free.max(self.next_element_id);
+        self.next_element_id = element_id + 1;
+        element_id
+    }
+    
+    /// Drops what refers to elements that were removed from the tree
+    fn forget_elements(&mut self, removed: &[ElementId]) {
+        self.hover_path.retain(|id| !removed.contains(id));
+        if self.text_editor.focused().is_some_and(|id| removed.contains(&id)) {
+            self.text_editor.blur();
+        }
+        if self.date_picker.as_ref().is_some_and(|picker| removed.contains(&picker.element())) {
+            self.date_picker = None;
+        }
+        self.reveal_hover = self.reveal_hover.filter(|id| !removed.contains(id));
+        self.eyedropper = self.eyedropper.filter(|id| !removed.contains(id));
+        self.color_drag = self.color_drag.filter(|(id, _)| !removed.contains(id));
+        self.slider_drag = self.slider_drag.filter(|id| !removed.contains(id));
+        if self.drag_drop.forget(removed) {
+            eprintln!("[DRAG] The dragged element was removed; drag cancelled");
+        }
+        for element_id in removed {
+            self.particle_emitters.remove(element_id);
+        }
+        self.reindex_elements();
+        self.element_changed(true);
+    }
+    
+    /// Rebuilds the string id index from the current elements
+    pub fn reindex_elements(&mut self) {
+        self.element_index.rebuild(&self.elements);
+        for (id, element_id) in self.element_index.shadowed() {
+            eprintln!("[ELEMENT_INDEX] Element #{} repeats id '{}'; lookups find #{}", element_id, id, self.element_index.get(id).unwrap_or_default());
+        }
+    }
+    
+    /// In logical pixels
+    pub fn viewport_size(&self) -> Vec2 {
+        self.viewport_size
+    }
+    
+    /// Device pixels per logical pixel, as the backend reported at its last
+    /// resize
+    pub fn viewport_scale(&self) -> f32 {
+        self.renderer.viewport_scale()
+    }
+    
+    /// Sets which programs `kryon.process` may run. Nothing is allowed by
+    /// default; keep it that way for untrusted KRB content.
+    pub fn set_process_policy(&mut self, policy: ProcessPolicy) {
+        if self.sandbox.is_some() {
+            eprintln!("[SANDBOX] Ignoring the process policy; a sandboxed app runs no programs");
+            return;
+        }
+        self.processes.set_policy(policy);
+    }
+    
+    /// Whether the app was made with [`KryonApp::new_sandboxed`]
+    pub fn is_sandboxed(&self) -> bool {
+        self.sandbox.is_some()
+    }
+    
+    /// What the sandbox stripped from the document, when sandboxed
+    pub fn sandbox_report(&self) -> Option<&SandboxReport> {
+        self.sandbox.as_ref()
+    }
+    
+    /// How the document's KRB version compares to the renderer's, and what
+    /// loading it skipped
+    pub fn compatibility_report(&self) -> &CompatibilityReport {
+        &self.krb_file.compatibility
+    }
+    
+    pub fn mark_needs_layout(&mut self) {
+        self.needs_layout = true;
+    }
+    
+    pub fn mark_needs_render(&mut self) {
+        self.needs_render = true;
+    }
+    
+    pub fn renderer(&self) -> &ElementRenderer<R> {
+        &self.renderer
+    }
+    
+    pub fn renderer_mut(&mut self) -> &mut ElementRenderer<R> {
+        &mut self.renderer
+    }
+    
+    // Template variable methods
     
     /// Set a template variable and update affected elements
     pub fn set_template_variable(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        let old = self.template_engine.get_variable(name).map(str::to_string);
         
         // Force update the template variable (ignore change detection for now)
-        self.template_engine.set_variable(name, value);
+        if self.template_engine.set_variable(name, value) {
+            let new = self.template_engine.get_variable(name).unwrap_or(value);
+            self.variable_changes.record(name, old.as_deref(), new);
+        }
         
         // Always update elements if we have bindings for this variable
         let bindings_for_var = self.template_engine.get_bindings_for_variable(name);
@@ -487,16 +2853,64 @@ fn update_layout(&mut self) -> anyhow::Result<()> {
         Ok(())
     }
     
+    /// Set a template variable to `value` coerced to its declared type, for
+    /// scripts and bindings both. A value that isn't compatible is an error
+    /// and leaves the variable as it was.
+    pub fn set_typed_template_variable(&mut self, name: &str, value: TemplateValue) -> anyhow::Result<()> {
+        let value = self.template_engine.coerce(name, value)
+            .map_err(|error| anyhow::anyhow!("template variable '{}': {}", name, error))?
+            .to_string();
+        self.script_system.set_template_variable(name, &value)?;
+        self.set_template_variable(name, &value)
+    }
+    
+    /// Calls the `kryon.watch` callbacks with the changes applied since the
+    /// last time
+    fn deliver_variable_changes(&mut self) -> anyhow::Result<()> {
+        if self.variable_changes.is_empty() {
+            return Ok(());
+        }
+        let changes = self.variable_changes.take().iter().map(variable_changes::VariableChange::to_script_value).collect();
+        self.script_system.deliver_variable_changes(script::engine_trait::ScriptValue::Array(changes))
+    }
+    
+    /// Sets a template variable from an input's value, which is dropped
+    /// if it isn't the variable's type
+    fn assign_template_variable(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match self.template_engine.coerce(name, TemplateValue::String(value.to_string())) {
+            Ok(value) => self.set_typed_template_variable(name, value),
+            Err(error) => {
+                eprintln!("[TEMPLATE] Input value not assigned to '{}': {}", name, error);
+                Ok(())
+            }
+        }
+    }
+    
     /// Get a template variable value
     pub fn get_template_variable(&self, name: &str) -> Option<&str> {
         self.template_engine.get_variable(name)
     }
     
+    /// Get a template variable value as its declared type
+    pub fn get_typed_template_variable(&self, name: &str) -> Option<TemplateValue> {
+        self.template_engine.get_typed_variable(name)
+    }
+    
+    /// A template variable's declared type
+    pub fn template_variable_type(&self, name: &str) -> Option<TemplateValueType> {
+        self.template_engine.variable_type(name)
+    }
+    
     /// Get all template variables
     pub fn get_template_variables(&self) -> &HashMap<String, String> {
         self.template_engine.get_variables()
     }
     
+    /// Take the events scripts raised with `kryon.emit` since the last call
+    pub fn take_app_events(&mut self) -> Vec<AppEvent> {
+        std::mem::take(&mut self.app_events)
+    }
+    
     /// Get all template variable names
     pub fn get_template_variable_names(&self) -> Vec<String> {
         self.template_engine.get_variable_names()
@@ -517,4 +2931,34 @@ fn update_layout(&mut self) -> anyhow::Result<()> {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+/// The `src` of an Image element
+/// The backend's system clipboard, or the runtime's own for backends
+/// without one
+struct AppClipboard<'a, R: CommandRenderer> {
+    renderer: &'a mut ElementRenderer<R>,
+    fallback: &'a mut Option<String>,
+}
+
+impl<R: CommandRenderer> Clipboard for AppClipboard<'_, R> {
+    fn read(&mut self) -> Option<String> {
+        self.renderer.clipboard_text().or_else(|| self.fallback.clone())
+    }
+    
+    fn write(&mut self, text: &str) {
+        if !self.renderer.set_clipboard_text(text) {
+            *self.fallback = Some(text.to_string());
+        }
+    }
+}
+
+fn is_color_input(element: &Element) -> bool {
+    element.element_type == ElementType::Input
+        && element.custom_properties.get("input_type").and_then(|value| value.as_string()) == Some("color")
+}
+
+fn image_source(element: &Element) -> Option<&str> {
+    (element.element_type == kryon_core::ElementType::Image)
+        .then(|| element.custom_properties.get("src").and_then(|src| src.as_string()))
+        .flatten()
+}
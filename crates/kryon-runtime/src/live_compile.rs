@@ -0,0 +1,354 @@
+// crates/kryon-runtime/src/live_compile.rs
+//! Watch-mode compile and preview for `.kry` sources.
+//!
+//! A [`CompileWatcher`] polls the `.kry` files next to the entry file, runs
+//! the external Kryon compiler on a background thread when one changes, and
+//! hands back either the new KRB or the compiler's output. The renderers pass
+//! the KRB to `KryonApp::reload` and errors to `KryonApp::set_build_error`,
+//! which covers the stale UI with [`BuildErrorOverlay`] until the next
+//! successful build.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+
+use glam::{Vec2, Vec4};
+use kryon_core::TextAlignment;
+use kryon_render::RenderCommand;
+
+/// The compiler invocation used when none is configured.
+pub const DEFAULT_COMPILE_COMMAND: &str = "kryon compile {input} -o {output}";
+
+/// How often sources are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A compiler command line with `{input}` and `{output}` placeholders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CompileCommand {
+    /// Splits `spec` on whitespace; the first word is the program.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut words = spec.split_whitespace().map(str::to_string);
+        let program = words.next().ok_or_else(|| "the compile command is empty".to_string())?;
+        Ok(Self { program, args: words.collect() })
+    }
+
+    pub fn command(&self, input: &Path, output: &Path) -> Command {
+        let mut command = Command::new(&self.program);
+        for arg in &self.args {
+            command.arg(arg
+                .replace("{input}", &input.to_string_lossy())
+                .replace("{output}", &output.to_string_lossy()));
+        }
+        command
+    }
+
+    /// Runs the compiler, returning its output as the error if it fails.
+    pub fn run(&self, input: &Path, output: &Path) -> Result<(), String> {
+        if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let _ = std::fs::remove_file(output);
+        let result = self.command(input, output).output()
+            .map_err(|e| format!("Failed to run `{}`: {}", self.program, e))?;
+        if result.status.success() && output.exists() {
+            return Ok(());
+        }
+        let mut message = String::from_utf8_lossy(&result.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&result.stdout);
+        if !stdout.trim().is_empty() {
+            if !message.is_empty() {
+                message.push('\n');
+            }
+            message.push_str(stdout.trim());
+        }
+        if message.is_empty() {
+            message = if result.status.success() {
+                format!("`{}` did not write {}", self.program, output.display())
+            } else {
+                format!("`{}` exited with {}", self.program, result.status)
+            };
+        }
+        Err(message)
+    }
+}
+
+impl Default for CompileCommand {
+    fn default() -> Self {
+        Self::parse(DEFAULT_COMPILE_COMMAND).unwrap()
+    }
+}
+
+/// Where the renderers put the KRB compiled from `entry`: a per-process
+/// temporary directory, so concurrent previews don't collide.
+pub fn output_path(entry: &Path) -> PathBuf {
+    let name = entry.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_else(|| "app".to_string());
+    std::env::temp_dir()
+        .join(format!("kryon-watch-{}", std::process::id()))
+        .join(format!("{}.krb", name))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileOutcome {
+    /// The KRB was rebuilt
+    Compiled(PathBuf),
+    /// The compiler's error output
+    Failed(String),
+}
+
+/// Recompiles `entry` whenever a `.kry` file under its directory changes.
+pub struct CompileWatcher {
+    entry: PathBuf,
+    output: PathBuf,
+    command: CompileCommand,
+    stamps: HashMap<PathBuf, SystemTime>,
+    last_poll: Instant,
+    running: Option<Receiver<CompileOutcome>>,
+    /// Sources changed while a build was running
+    stale: bool,
+}
+
+impl CompileWatcher {
+    pub fn new(entry: impl Into<PathBuf>, output: impl Into<PathBuf>, command: CompileCommand) -> Self {
+        let entry = entry.into();
+        let mut watcher = Self {
+            stamps: HashMap::new(),
+            entry,
+            output: output.into(),
+            command,
+            last_poll: Instant::now(),
+            running: None,
+            stale: false,
+        };
+        watcher.stamps = watcher.scan();
+        watcher
+    }
+
+    /// Where the compiled KRB is written
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// Compiles on the calling thread, for the first build before a window opens.
+    pub fn compile_now(&self) -> CompileOutcome {
+        outcome(self.command.run(&self.entry, &self.output), &self.output)
+    }
+
+    /// Checks for changed sources and finished builds. Call once a frame;
+    /// returns the outcome of a build when one completes.
+    pub fn poll(&mut self) -> Option<CompileOutcome> {
+        let finished = match self.running.as_ref().map(Receiver::try_recv) {
+            Some(Ok(outcome)) => Some(outcome),
+            Some(Err(TryRecvError::Disconnected)) => Some(CompileOutcome::Failed("The compiler thread stopped unexpectedly".to_string())),
+            Some(Err(TryRecvError::Empty)) | None => None,
+        };
+        if finished.is_some() {
+            self.running = None;
+        }
+
+        if self.last_poll.elapsed() >= POLL_INTERVAL {
+            self.last_poll = Instant::now();
+            let stamps = self.scan();
+            if stamps != self.stamps {
+                self.stamps = stamps;
+                self.stale = true;
+            }
+        }
+
+        if self.stale && self.running.is_none() {
+            self.stale = false;
+            self.start_build();
+        }
+        finished
+    }
+
+    fn start_build(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        let (command, entry, output) = (self.command.clone(), self.entry.clone(), self.output.clone());
+        eprintln!("[WATCH] Compiling {}", entry.display());
+        std::thread::spawn(move || {
+            let _ = sender.send(outcome(command.run(&entry, &output), &output));
+        });
+        self.running = Some(receiver);
+    }
+
+    /// Modification times of the `.kry` files beside the entry and below
+    fn scan(&self) -> HashMap<PathBuf, SystemTime> {
+        let mut stamps = HashMap::new();
+        let root = self.entry.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        scan_sources(root, &mut stamps);
+        if let Ok(modified) = std::fs::metadata(&self.entry).and_then(|meta| meta.modified()) {
+            stamps.insert(self.entry.clone(), modified);
+        }
+        stamps
+    }
+}
+
+fn outcome(result: Result<(), String>, output: &Path) -> CompileOutcome {
+    match result {
+        Ok(()) => CompileOutcome::Compiled(output.to_path_buf()),
+        Err(message) => CompileOutcome::Failed(message),
+    }
+}
+
+fn scan_sources(dir: &Path, stamps: &mut HashMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden || path.ends_with("target") {
+            continue;
+        }
+        if path.is_dir() {
+            scan_sources(&path, stamps);
+        } else if path.extension().is_some_and(|ext| ext == "kry") {
+            if let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) {
+                stamps.insert(path, modified);
+            }
+        }
+    }
+}
+
+const PANEL_MARGIN: f32 = 24.0;
+const PANEL_PADDING: f32 = 16.0;
+const LINE_HEIGHT: f32 = 18.0;
+const FONT_SIZE: f32 = 14.0;
+const MAX_LINES: usize = 40;
+
+/// A panel covering the UI with the last build error.
+#[derive(Debug, Default)]
+pub struct BuildErrorOverlay {
+    error: Option<String>,
+}
+
+impl BuildErrorOverlay {
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn overlay_commands(&self, viewport_size: Vec2) -> Vec<RenderCommand> {
+        let Some(error) = &self.error else {
+            return Vec::new();
+        };
+        let origin = Vec2::splat(PANEL_MARGIN);
+        let size = (viewport_size - origin * 2.0).max(Vec2::ZERO);
+        let text = |text: &str, y: f32, color: Vec4| RenderCommand::DrawText {
+            position: origin + Vec2::new(PANEL_PADDING, PANEL_PADDING + y),
+            text: text.to_string(),
+            font_size: FONT_SIZE,
+            color,
+            alignment: TextAlignment::Start,
+            max_width: Some(size.x - PANEL_PADDING * 2.0),
+            max_height: None,
+            transform: None,
+            font_family: None,
+            runs: Vec::new(),
+            z_index: i32::MAX,
+        };
+
+        let mut commands = vec![
+            RenderCommand::DrawRect {
+                position: Vec2::ZERO,
+                size: viewport_size,
+                color: Vec4::new(0.0, 0.0, 0.0, 0.6),
+                border_radius: 0.0,
+                border_width: 0.0,
+                border_color: Vec4::ZERO,
+                transform: None,
+                shadow: None,
+                z_index: i32::MAX,
+            },
+            RenderCommand::DrawRect {
+                position: origin,
+                size,
+                color: Vec4::new(0.16, 0.05, 0.06, 0.96),
+                border_radius: 6.0,
+                border_width: 2.0,
+                border_color: Vec4::new(0.94, 0.27, 0.27, 1.0),
+                transform: None,
+                shadow: None,
+                z_index: i32::MAX,
+            },
+            text("Build failed", 0.0, Vec4::new(1.0, 0.45, 0.45, 1.0)),
+        ];
+        let lines: Vec<&str> = error.lines().collect();
+        for (index, line) in lines.iter().take(MAX_LINES).enumerate() {
+            commands.push(text(line, (index + 2) as f32 * LINE_HEIGHT, Vec4::new(0.95, 0.9, 0.9, 1.0)));
+        }
+        if lines.len() > MAX_LINES {
+            let more = format!("... {} more lines", lines.len() - MAX_LINES);
+            commands.push(text(&more, (MAX_LINES + 2) as f32 * LINE_HEIGHT, Vec4::new(0.7, 0.6, 0.6, 1.0)));
+        }
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_placeholders() {
+        let command = CompileCommand::parse("kryc --release {input} --out={output}").unwrap();
+        let command = command.command(Path::new("app.kry"), Path::new("/tmp/app.krb"));
+        assert_eq!(command.get_program(), "kryc");
+        let args: Vec<_> = command.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+        assert_eq!(args, ["--release", "app.kry", "--out=/tmp/app.krb"]);
+        assert!(CompileCommand::parse("  ").is_err());
+    }
+
+    #[test]
+    fn rebuilds_when_sources_change() {
+        let dir = std::env::temp_dir().join(format!("kryon-watch-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("components")).unwrap();
+        std::fs::write(dir.join("app.kry"), "App {}").unwrap();
+        std::fs::write(dir.join("components/button.kry"), "Button {}").unwrap();
+
+        // `cp` stands in for the compiler; a missing input makes it fail
+        let command = CompileCommand::parse("cp {input} {output}").unwrap();
+        let output = dir.join("app.krb");
+        let mut watcher = CompileWatcher::new(dir.join("app.kry"), &output, command);
+        assert_eq!(watcher.compile_now(), CompileOutcome::Compiled(output.clone()));
+
+        let wait = |watcher: &mut CompileWatcher| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while Instant::now() < deadline {
+                if let Some(outcome) = watcher.poll() {
+                    return Some(outcome);
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            None
+        };
+        assert_eq!(watcher.poll(), None);
+        watcher.stamps.clear();
+        assert_eq!(wait(&mut watcher), Some(CompileOutcome::Compiled(output.clone())));
+
+        std::fs::remove_file(dir.join("app.kry")).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        watcher.stamps.clear();
+        assert!(matches!(wait(&mut watcher), Some(CompileOutcome::Failed(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn overlay_shows_errors_until_cleared() {
+        let mut overlay = BuildErrorOverlay::default();
+        assert!(overlay.overlay_commands(Vec2::new(800.0, 600.0)).is_empty());
+        overlay.set_error(Some("app.kry:3:5: expected '}'".to_string()));
+        let commands = overlay.overlay_commands(Vec2::new(800.0, 600.0));
+        assert!(commands.iter().any(|command| matches!(command, RenderCommand::DrawText { text, .. } if text.contains("expected '}'"))));
+        overlay.set_error(None);
+        assert!(overlay.overlay_commands(Vec2::new(800.0, 600.0)).is_empty());
+    }
+}
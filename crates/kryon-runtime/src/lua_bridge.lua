@@ -62,6 +62,16 @@ function getElementById(element_id)
                 print("Queuing checked state change for element '" .. self.id .. "' to " .. tostring(checked))
             end,
 
+            -- Checks whether a checkbox or radio button is checked, considering
+            -- pending changes.
+            isChecked = function(self)
+                if _pending_state_changes[self.numeric_id] ~= nil then
+                    return _pending_state_changes[self.numeric_id]
+                end
+                local data = _elements_data and _elements_data[self.numeric_id]
+                return data ~= nil and data.checked == true
+            end,
+
             -- Queues a text content change.
             setText = function(self, text)
                 _pending_text_changes[self.numeric_id] = tostring(text)
@@ -80,6 +90,59 @@ function getElementById(element_id)
                 return ""
             end,
 
+            -- Gets the real value of a password input, which getText masks.
+            -- Other elements return their text.
+            getSecretValue = function(self)
+                if _pending_text_changes[self.numeric_id] ~= nil then
+                    return _pending_text_changes[self.numeric_id]
+                end
+                return _secret_value(self.numeric_id) or self:getText()
+            end,
+
+            -- Gets a text input's value without the literals of its input_mask,
+            -- e.g. 5551234567 for (555) 123-4567. Other elements return their text.
+            getRawValue = function(self)
+                if _pending_text_changes[self.numeric_id] ~= nil then
+                    return _pending_text_changes[self.numeric_id]
+                end
+                local data = _elements_data and _elements_data[self.numeric_id]
+                return data and data.raw_value or self:getText()
+            end,
+
+            -- Gets a slider's value as a number. Other elements return their text.
+            getValue = function(self)
+                local data = _elements_data and _elements_data[self.numeric_id]
+                if data and data.value ~= nil then
+                    return data.value
+                end
+                return self:getText()
+            end,
+
+            -- Gets why a text input's value fails its required, pattern,
+            -- min_length or max_length, or "" if it passes.
+            getValidationMessage = function(self)
+                local data = _elements_data and _elements_data[self.numeric_id]
+                return data and data.validation_message or ""
+            end,
+
+            -- Checks whether every text input in this element, such as a form,
+            -- passes its validation, the element itself included.
+            checkValidity = function(self)
+                local pending = { self.numeric_id }
+                while #pending > 0 do
+                    local data = _elements_data and _elements_data[table.remove(pending)]
+                    if data then
+                        if data.validation_message then
+                            return false
+                        end
+                        for _, child_id in ipairs(data.children or {}) do
+                            table.insert(pending, child_id)
+                        end
+                    end
+                end
+                return true
+            end,
+
             -- Queues a visibility change.
             setVisible = function(self, visible)
                 _pending_visibility_changes[self.numeric_id] = visible
@@ -338,6 +401,1191 @@ function onResize(callback) addEventListener('resize', callback) end
 function onLoad(callback) addEventListener('load', callback) end
 
 
+-- =============================================================================
+--  4b. Host APIs (kryon.*)
+-- =============================================================================
+-- Services provided by the host platform. Requests are queued here and picked
+-- up by the Rust runtime; results arrive later through callbacks.
+
+kryon = kryon or {}
+kryon.dialog = {}
+
+_pending_dialog_requests    = {} -- { {id=, kind="open"|"save", title=, filters=, ...}, ... }
+_dialog_callbacks           = {} -- { [id] = { callback = fn, multiple = bool } }
+
+local _next_dialog_id = 1
+
+_pending_notifications      = {} -- { {title=, body=, options=}, ... }
+_pending_toast_commands     = {} -- { {op="show"|"dismiss"|"configure", id=, ...}, ... }
+_toast_actions              = {} -- { [id] = fn }
+
+local _next_toast_id = 1
+
+_pending_app_events         = {} -- { {name=, payload=}, ... }
+
+kryon.popup = {}
+_pending_popup_commands     = {} -- { {op="open"|"close"|"toggle", id=}, ... }
+
+_pending_eyedropper_requests = {} -- { {id=}, ... }
+
+kryon.process = {}
+_pending_process_requests   = {} -- { {id=, program=, args=, cwd=, env=, stdin=}, ... }
+_process_handlers           = {} -- { [id] = { on_stdout, on_stderr, on_exit } }
+
+local _next_process_id = 1
+
+kryon.serial = {}
+_pending_serial_commands    = {} -- { {op="open"|"write"|"close", id=, ...}, ... }
+_serial_handlers            = {} -- { [id] = { on_open, on_data, on_error, on_close } }
+
+local _next_serial_id = 1
+
+kryon.ws = {}
+_pending_ws_commands        = {} -- { {op="connect"|"send"|"close", id=, ...}, ... }
+_ws_sockets                 = {} -- { [id] = socket }
+
+local _next_ws_id = 1
+
+kryon.mqtt = {}
+_pending_mqtt_commands      = {} -- { {op="connect"|"subscribe"|"publish"|..., id=, ...}, ... }
+_mqtt_clients               = {} -- { [id] = { handlers = {...}, subscriptions = { [filter] = callback } } }
+
+local _next_mqtt_id = 1
+
+kryon.data = {}
+_pending_data_refreshes     = {} -- { name_or_false, ... }
+_pending_data_requests      = {} -- { {op="load_csv"|"load_json"|"sort_table"|"filter_table", ...}, ... }
+_data_callbacks             = {} -- { [id] = callback }
+
+local _next_data_id = 1
+
+kryon.diagnostics = {}
+_pending_diagnostic_dumps   = {} -- { {id=, reason=}, ... }
+_diagnostic_callbacks       = {} -- { [id] = callback }
+
+local _next_diagnostic_id = 1
+
+kryon.analytics = {}
+_pending_analytics_commands = {} -- { {op="track"|"screen"|"consent", ...}, ... }
+
+_memory_report              = nil -- latest snapshot, refreshed by the runtime about once a second
+_monitors                   = {} -- set by the runtime when the window opens
+_orientation                = nil -- "portrait" or "landscape", set by the runtime
+_orientation_listeners      = {} -- { callback, ... }
+_variable_watchers          = {} -- { [name or "*"] = { {id=, callback=}, ... } }
+
+local _next_watch_id = 1
+
+kryon.format = {}
+-- The active locale's conventions, set by the runtime; English until then
+_locale = {
+    tag = "en", decimal = ".", group = ",", currency = "¤{}",
+    short_date = "{m}/{d}/{y}", long_date = "{month} {d}, {y}",
+    months = { "January", "February", "March", "April", "May", "June", "July",
+               "August", "September", "October", "November", "December" },
+    relative = {
+        now = "now", past = "{} ago", future = "in {}",
+        units = { {"second", "seconds"}, {"minute", "minutes"}, {"hour", "hours"}, {"day", "days"},
+                  {"week", "weeks"}, {"month", "months"}, {"year", "years"} },
+    },
+}
+
+local function _queue_dialog(kind, options, callback)
+    -- Allow kryon.dialog.open_file(callback) without options.
+    if type(options) == "function" and callback == nil then
+        options, callback = {}, options
+    end
+    options = options or {}
+    if type(callback) ~= "function" then
+        print("Error: kryon.dialog." .. kind .. "_file(options, callback) - callback must be a function.")
+        return nil
+    end
+
+    local id = _next_dialog_id
+    _next_dialog_id = id + 1
+
+    local request = { id = id, kind = kind }
+    for key, value in pairs(options) do
+        request[key] = value
+    end
+    table.insert(_pending_dialog_requests, request)
+    _dialog_callbacks[id] = { callback = callback, multiple = options.multiple == true }
+    return id
+end
+
+---
+-- Shows a file open dialog. The callback receives a file table
+-- `{ path, name, data }` (or a list of them with `multiple = true`), or nil
+-- if the user cancelled, plus an error message if the dialog failed.
+-- `data` holds the file bytes when `read = true` and always on the web.
+---@param options table|nil { title, filters = { {name=, extensions={...}} }, multiple, directory, read }
+---@param callback function
+---@return number|nil The request id.
+--
+function kryon.dialog.open_file(options, callback)
+    return _queue_dialog("open", options, callback)
+end
+
+---
+-- Shows a file save dialog. If `data` is given it is written to the chosen
+-- path (downloaded on the web). The callback receives `{ path, name }` or nil.
+---@param options table|nil { title, filters, default_name, directory, data }
+---@param callback function
+---@return number|nil The request id.
+--
+function kryon.dialog.save_file(options, callback)
+    return _queue_dialog("save", options, callback)
+end
+
+---
+-- Raises an OS notification. Falls back to an in-app toast when the platform
+-- has no notification service or permission was refused.
+---@param title string
+---@param body string|nil
+---@param options table|nil { icon = path, timeout = milliseconds }
+--
+function kryon.notify(title, body, options)
+    if title == nil then
+        print("Error: kryon.notify(title, body, options) - title is required.")
+        return
+    end
+    table.insert(_pending_notifications, { title = tostring(title), body = body, options = options })
+end
+
+---
+-- Shows an in-app toast on the overlay layer. Toasts past the visible limit
+-- wait until one leaves.
+---@param message string
+---@param options table|nil { kind = "info"|"success"|"warning"|"error",
+--   duration = milliseconds (0 stays until dismissed),
+--   action = { label = string, callback = fn(id) } }
+---@return number|nil The toast id, for `kryon.dismiss_toast`.
+--
+function kryon.toast(message, options)
+    if message == nil then
+        print("Error: kryon.toast(message, options) - message is required.")
+        return nil
+    end
+    options = options or {}
+
+    local id = _next_toast_id
+    _next_toast_id = id + 1
+
+    local command = { op = "show", id = id, message = tostring(message), kind = options.kind, duration = options.duration }
+    local action = options.action
+    if type(action) == "table" and action.label ~= nil then
+        if type(action.callback) ~= "function" then
+            print("Error: kryon.toast(message, options) - action.callback must be a function.")
+            return nil
+        end
+        command.action = tostring(action.label)
+        _toast_actions[id] = action.callback
+    end
+    table.insert(_pending_toast_commands, command)
+    return id
+end
+
+---
+-- Dismisses a toast shown or queued by `kryon.toast`.
+---@param id number
+--
+function kryon.dismiss_toast(id)
+    table.insert(_pending_toast_commands, { op = "dismiss", id = id })
+end
+
+---
+-- Sets where toasts stack and how many show at once.
+---@param options table { corner = "top-left"|"top-right"|"bottom-left"|"bottom-right", max_visible = number }
+--
+function kryon.configure_toasts(options)
+    options = options or {}
+    table.insert(_pending_toast_commands, { op = "configure", corner = options.corner, max_visible = options.max_visible })
+end
+
+---
+-- Raises an app-level event for the embedding host, such as listeners
+-- registered with `KryonWebApp.on(name, callback)` on the web.
+---@param name string
+---@param payload any|nil Passed to the host's listeners as-is.
+--
+function kryon.emit(name, payload)
+    if name == nil then
+        print("Error: kryon.emit(name, payload) - name is required.")
+        return
+    end
+    table.insert(_pending_app_events, { name = tostring(name), payload = payload })
+end
+
+local function _queue_popup(op, id)
+    if id == nil then
+        print("Error: kryon.popup." .. op .. "(id) - id is required.")
+        return
+    end
+    table.insert(_pending_popup_commands, { op = op, id = tostring(id) })
+end
+
+---
+-- Opens an anchored popup, an element with a `popup_anchor` property, next
+-- to its anchor.
+---@param id string The popup element's id.
+--
+function kryon.popup.open(id)
+    _queue_popup("open", id)
+end
+
+---
+-- Closes an anchored popup.
+---@param id string The popup element's id.
+--
+function kryon.popup.close(id)
+    _queue_popup("close", id)
+end
+
+---
+-- Opens an anchored popup if it is closed, and closes it otherwise.
+---@param id string The popup element's id.
+--
+function kryon.popup.toggle(id)
+    _queue_popup("toggle", id)
+end
+
+---
+-- Arms a color input's eyedropper: the next press anywhere picks the color
+-- on screen under it, and Escape cancels. Backends that can't read the
+-- screen ignore it.
+---@param id string The color input's id.
+--
+function kryon.eyedropper(id)
+    if id == nil then
+        print("Error: kryon.eyedropper(id) - id is required.")
+        return
+    end
+    table.insert(_pending_eyedropper_requests, { id = tostring(id) })
+end
+
+---
+-- Starts a local process. Only programs on the host's allowlist can run;
+-- anything else reports an error through on_exit.
+---@param program string Program name or path.
+---@param args table|nil List of arguments.
+---@param options table|nil { cwd, env = {KEY=value}, stdin, on_stdout(line), on_stderr(line), on_exit(code, error) }
+---@return number The process request id.
+--
+function kryon.process.spawn(program, args, options)
+    options = options or {}
+    local id = _next_process_id
+    _next_process_id = id + 1
+
+    table.insert(_pending_process_requests, {
+        id = id,
+        program = program,
+        args = args or {},
+        cwd = options.cwd,
+        env = options.env,
+        stdin = options.stdin,
+    })
+    _process_handlers[id] = {
+        on_stdout = options.on_stdout,
+        on_stderr = options.on_stderr,
+        on_exit = options.on_exit,
+    }
+    return id
+end
+
+---
+-- Runs a process to completion and collects its output.
+---@param callback function Receives { code, stdout, stderr, error }.
+--
+function kryon.process.run(program, args, callback)
+    local stdout, stderr = {}, {}
+    return kryon.process.spawn(program, args, {
+        on_stdout = function(line) table.insert(stdout, line) end,
+        on_stderr = function(line) table.insert(stderr, line) end,
+        on_exit = function(code, error)
+            if callback then
+                callback({
+                    code = code,
+                    stdout = table.concat(stdout, "\n"),
+                    stderr = table.concat(stderr, "\n"),
+                    error = error,
+                })
+            end
+        end,
+    })
+end
+
+---
+-- Opens a serial device. Frames arrive in on_data as strings for "line" and
+-- delimiter framing, or as raw byte strings for "raw" and "length" framing.
+-- The port reconnects automatically unless `reconnect = false`.
+---@param path string Device path, e.g. "/dev/ttyUSB0" or "COM3".
+---@param options table|nil { baud = 9600, framing = "line"|"raw"|"length"|"0x03", reconnect = true }
+---@param handlers table|nil { on_open(), on_data(frame), on_error(message), on_close() }
+---@return table A port object with :write(data) and :close().
+--
+function kryon.serial.open(path, options, handlers)
+    options = options or {}
+    local id = _next_serial_id
+    _next_serial_id = id + 1
+
+    table.insert(_pending_serial_commands, {
+        op = "open",
+        id = id,
+        path = path,
+        baud = options.baud,
+        framing = options.framing,
+        reconnect = options.reconnect ~= false,
+    })
+    _serial_handlers[id] = handlers or {}
+
+    local port = { id = id }
+    function port:write(data)
+        table.insert(_pending_serial_commands, { op = "write", id = self.id, data = data })
+    end
+    function port:close()
+        table.insert(_pending_serial_commands, { op = "close", id = self.id })
+    end
+    return port
+end
+
+---
+-- Opens a WebSocket. Assign handlers on the returned socket:
+--   socket.onOpen(), socket.onMessage(data, is_binary),
+--   socket.onClose(code, reason, reconnecting), socket.onError(message)
+-- Text frames arrive as strings, binary frames as byte strings. Dropped
+-- connections are retried with exponential backoff unless `reconnect = false`.
+---@param url string A ws:// or wss:// URL.
+---@param options table|nil { reconnect = true, max_backoff = 30000 (ms) }
+---@return table A socket object with :send(data), :send_binary(data) and :close().
+--
+function kryon.ws.connect(url, options)
+    options = options or {}
+    local id = _next_ws_id
+    _next_ws_id = id + 1
+
+    table.insert(_pending_ws_commands, {
+        op = "connect",
+        id = id,
+        url = url,
+        reconnect = options.reconnect ~= false,
+        max_backoff = options.max_backoff,
+    })
+
+    local socket = { id = id, url = url }
+    function socket:send(data)
+        table.insert(_pending_ws_commands, { op = "send", id = self.id, data = data })
+    end
+    function socket:send_binary(data)
+        table.insert(_pending_ws_commands, { op = "send", id = self.id, data = data, binary = true })
+    end
+    function socket:close()
+        table.insert(_pending_ws_commands, { op = "close", id = self.id })
+    end
+    _ws_sockets[id] = socket
+    return socket
+end
+
+---
+-- Connects to an MQTT broker. Message callbacks are registered per topic
+-- filter and may use the `+` and `#` wildcards. Payloads arrive as strings
+-- (or byte strings when not valid UTF-8).
+---@param url string "mqtt://host[:1883]" or "mqtts://host[:8883]".
+---@param options table|nil { client_id, username, password, keep_alive, clean_session,
+--        reconnect = true, tls = { ca_file, cert_file, key_file },
+--        on_connect(), on_disconnect(), on_error(message) }
+---@return table A client with :subscribe, :unsubscribe, :publish, :bind and :disconnect.
+--
+function kryon.mqtt.connect(url, options)
+    options = options or {}
+    local id = _next_mqtt_id
+    _next_mqtt_id = id + 1
+
+    local settings = {}
+    for key, value in pairs(options) do
+        if type(value) ~= "function" then
+            settings[key] = value
+        end
+    end
+    table.insert(_pending_mqtt_commands, { op = "connect", id = id, url = url, options = settings })
+    _mqtt_clients[id] = { handlers = options, subscriptions = {} }
+
+    local client = { id = id, url = url }
+    -- client:subscribe(filter, [options,] callback(topic, payload)); options = { qos = 0|1|2 }
+    function client:subscribe(filter, sub_options, callback)
+        if type(sub_options) == "function" and callback == nil then
+            sub_options, callback = {}, sub_options
+        end
+        sub_options = sub_options or {}
+        local entry = _mqtt_clients[self.id]
+        if entry then
+            entry.subscriptions[filter] = callback
+        end
+        table.insert(_pending_mqtt_commands, { op = "subscribe", id = self.id, filter = filter, qos = sub_options.qos })
+    end
+    function client:unsubscribe(filter)
+        local entry = _mqtt_clients[self.id]
+        if entry then
+            entry.subscriptions[filter] = nil
+        end
+        table.insert(_pending_mqtt_commands, { op = "unsubscribe", id = self.id, filter = filter })
+    end
+    -- client:publish(topic, payload, { qos = 0|1|2, retain = false })
+    function client:publish(topic, payload, pub_options)
+        pub_options = pub_options or {}
+        table.insert(_pending_mqtt_commands, {
+            op = "publish", id = self.id, topic = topic, payload = payload,
+            qos = pub_options.qos, retain = pub_options.retain,
+        })
+    end
+    -- Writes every payload on `filter` into the template variable `variable`.
+    function client:bind(filter, variable)
+        table.insert(_pending_mqtt_commands, { op = "bind", id = self.id, filter = filter, variable = variable })
+    end
+    function client:disconnect()
+        table.insert(_pending_mqtt_commands, { op = "disconnect", id = self.id })
+    end
+    return client
+end
+
+---
+-- Refetches a data source declared with `data_source` properties right away.
+-- Without a name every source is refreshed.
+---@param name string|nil The source's `data_name` (or element id).
+--
+function kryon.data.refresh(name)
+    table.insert(_pending_data_refreshes, name or false)
+end
+
+local function _queue_data_load(op, path, options, callback)
+    -- Allow kryon.data.load_csv(path, callback) without options.
+    if type(options) == "function" and callback == nil then
+        options, callback = {}, options
+    end
+    options = options or {}
+    local id = _next_data_id
+    _next_data_id = id + 1
+
+    local request = { op = op, id = id, path = path }
+    for key, value in pairs(options) do
+        request[key] = value
+    end
+    table.insert(_pending_data_requests, request)
+    _data_callbacks[id] = callback or false
+end
+
+---
+-- Loads a CSV file. Column types (number, bool, text) are detected and the
+-- callback receives `{ columns = { {name, type}, ... }, rows = { {col = value}, ... } }`
+-- or `nil, error`. Empty cells are left out of their row.
+---@param path string
+---@param options table|nil { delimiter = ",", header = true, bind = "table_element_id" }
+---@param callback function|nil function(dataset, error)
+--
+function kryon.data.load_csv(path, options, callback)
+    _queue_data_load("load_csv", path, options, callback)
+end
+
+---
+-- Loads a JSON array of objects (or of arrays, header first).
+---@param path string
+---@param options table|nil { pointer = "/items", bind = "table_element_id" }
+---@param callback function|nil function(dataset, error)
+--
+function kryon.data.load_json(path, options, callback)
+    _queue_data_load("load_json", path, options, callback)
+end
+
+-- Sorts a bound Table by column name; a nil column clears the sort.
+function kryon.data.sort_table(table_id, column, ascending)
+    table.insert(_pending_data_requests, {
+        op = "sort_table", table = table_id, column = column, ascending = ascending ~= false,
+    })
+end
+
+-- Shows only rows of a bound Table containing `text` (case-insensitive).
+function kryon.data.filter_table(table_id, text)
+    table.insert(_pending_data_requests, { op = "filter_table", table = table_id, filter = text or "" })
+end
+
+---
+-- Writes a diagnostic bundle (recent frames, log, KRB and backend info) into
+-- the renderer's --diagnostics-dir. The callback receives the bundle path,
+-- or `nil, error` when diagnostics are not enabled.
+---@param reason string|nil Included in the file name and summary
+---@param callback function|nil function(path, error)
+--
+function kryon.diagnostics.dump(reason, callback)
+    if type(reason) == "function" and callback == nil then
+        reason, callback = nil, reason
+    end
+    local id = _next_diagnostic_id
+    _next_diagnostic_id = id + 1
+    table.insert(_pending_diagnostic_dumps, { id = id, reason = reason })
+    _diagnostic_callbacks[id] = callback or false
+end
+
+---
+-- Records a custom analytics event when the app runs with analytics enabled.
+-- Property values longer than 200 characters are cut short; don't pass
+-- anything the user typed.
+---@param name string Event name, e.g. "checkout_started"
+---@param properties table|nil Flat table of strings, numbers and booleans
+--
+function kryon.analytics.track(name, properties)
+    if name == nil then
+        print("Error: kryon.analytics.track(name, properties) - name is required.")
+        return
+    end
+    table.insert(_pending_analytics_commands, { op = "track", name = tostring(name), properties = properties })
+end
+
+---
+-- Sets the screen or route recorded with later analytics events.
+---@param name string|nil nil clears it
+--
+function kryon.analytics.set_screen(name)
+    table.insert(_pending_analytics_commands, { op = "screen", name = name })
+end
+
+---
+-- Grants or withdraws the user's consent to analytics. Withdrawing it
+-- discards events not yet uploaded and stops recording until granted again.
+---@param granted boolean
+--
+function kryon.analytics.set_consent(granted)
+    table.insert(_pending_analytics_commands, { op = "consent", granted = granted and true or false })
+end
+
+---
+-- Memory use by subsystem, refreshed about once a second:
+-- `{ total_bytes, textures = {count, bytes}, glyph_atlases, cached_glyphs,
+--    elements, layout, tables, scripts = { lua = {bytes, peak_bytes, objects, limit} } }`.
+-- Byte counts are estimates; nil until the first refresh.
+--
+function kryon.memory_report()
+    return _memory_report
+end
+
+-- Internal: called by the Rust runtime with a fresh memory report.
+function _set_memory_report(report)
+    _memory_report = report
+end
+
+-- Connected monitors, in the order `--monitor` indexes them (from 0):
+-- `{ {index, name, x, y, width, height, scale_factor, dpi}, ... }`.
+-- Positions and sizes are in pixels on the virtual desktop. Empty on
+-- backends without monitors, such as the terminal.
+--
+function kryon.monitors()
+    return _monitors
+end
+
+-- Internal: called by the Rust runtime with the backend's monitors.
+function _set_monitors(monitors)
+    _monitors = monitors
+end
+
+-- The viewport's orientation, "portrait" (taller than wide) or "landscape".
+-- With a rotated output this is the orientation after rotation.
+--
+function kryon.orientation()
+    return _orientation
+end
+
+---
+-- Calls `callback(orientation)` whenever the viewport switches between
+-- portrait and landscape, after orientation variants have been applied.
+---@param callback function
+--
+function kryon.on_orientation_change(callback)
+    if type(callback) ~= "function" then
+        print("Error: kryon.on_orientation_change(callback) - callback must be a function.")
+        return
+    end
+    table.insert(_orientation_listeners, callback)
+end
+
+-- Internal: called by the Rust runtime on load and when the orientation changes.
+function _set_orientation(orientation)
+    local previous = _orientation
+    _orientation = orientation
+    if previous == nil or previous == orientation then
+        return
+    end
+    for _, callback in ipairs(_orientation_listeners) do
+        local success, err = pcall(callback, orientation)
+        if not success then
+            print("Error in orientation change listener: " .. tostring(err))
+        end
+    end
+end
+
+-- A template variable's value as its declared type: a boolean, a number, a
+-- string (colors as "#rrggbb") or a list table. nil for unknown variables.
+--
+function kryon.get_variable(name)
+    local variable = _reactive_variables and _reactive_variables[name]
+    if variable then
+        return variable.get()
+    end
+    return nil
+end
+
+---
+-- Sets a template variable, like assigning the global of the same name.
+-- The value is coerced to the variable's type (see kryon.variable_type);
+-- one that can't be, such as "abc" for an int, raises an error.
+---@param name string
+---@param value any
+--
+function kryon.set_variable(name, value)
+    local variable = _reactive_variables and _reactive_variables[name]
+    if not variable then
+        error("kryon.set_variable: no template variable '" .. tostring(name) .. "'", 2)
+    end
+    variable.set(value)
+end
+
+-- "bool", "int", "float", "string", "color" or "list"; nil for unknown variables.
+--
+function kryon.variable_type(name)
+    if not (_reactive_variables and _reactive_variables[name]) then
+        return nil
+    end
+    return _template_variable_types and _template_variable_types[name] or "string"
+end
+
+---
+-- Calls `callback(new, old, name)` after a template variable changes, with
+-- the values as their declared type. Changes are delivered once a frame,
+-- after they're all applied: a variable set several times in a frame is
+-- reported once, with its value before the first change and after the last.
+-- Watching "*" calls `callback(changes)` with the frame's changes to every
+-- variable, a list of `{ name =, old =, new = }`. Returns a handle whose
+-- `unwatch()` stops the calls.
+---@param name string
+---@param callback function
+---@return table|nil
+--
+function kryon.watch(name, callback)
+    if type(name) ~= "string" or type(callback) ~= "function" then
+        print("Error: kryon.watch(name, callback) - name must be a string and callback a function.")
+        return nil
+    end
+    if name ~= "*" and not (_reactive_variables and _reactive_variables[name]) then
+        print("Warning: kryon.watch - no template variable '" .. name .. "'")
+    end
+    local id = _next_watch_id
+    _next_watch_id = _next_watch_id + 1
+    _variable_watchers[name] = _variable_watchers[name] or {}
+    table.insert(_variable_watchers[name], { id = id, callback = callback })
+    return {
+        id = id,
+        name = name,
+        unwatch = function() kryon.unwatch(id) end,
+    }
+end
+
+-- Stops a kryon.watch callback, by its handle or the handle's id.
+--
+function kryon.unwatch(handle)
+    local id = type(handle) == "table" and handle.id or handle
+    for name, watchers in pairs(_variable_watchers) do
+        for index, watcher in ipairs(watchers) do
+            if watcher.id == id then
+                table.remove(watchers, index)
+                if #watchers == 0 then
+                    _variable_watchers[name] = nil
+                end
+                return true
+            end
+        end
+    end
+    return false
+end
+
+local function _typed_change_value(name, value)
+    if value ~= nil and _typed_template_value then
+        return _typed_template_value(name, value)
+    end
+    return value
+end
+
+-- Internal: called by the Rust runtime each frame a template variable changed.
+-- Callbacks added or removed during delivery take effect from the next frame.
+function _deliver_variable_changes(changes)
+    local typed = {}
+    for _, change in ipairs(changes) do
+        table.insert(typed, {
+            name = change.name,
+            old = _typed_change_value(change.name, change.old),
+            new = _typed_change_value(change.name, change.new),
+        })
+    end
+    local function call(watchers, ...)
+        local snapshot = {}
+        for index, watcher in ipairs(watchers or {}) do
+            snapshot[index] = watcher
+        end
+        for _, watcher in ipairs(snapshot) do
+            local success, err = pcall(watcher.callback, ...)
+            if not success then
+                print("Error in variable watcher: " .. tostring(err))
+            end
+        end
+    end
+    for _, change in ipairs(typed) do
+        call(_variable_watchers[change.name], change.new, change.old, change.name)
+    end
+    if #typed > 0 then
+        call(_variable_watchers["*"], typed)
+    end
+end
+
+-- Symbols and decimals of the currencies kryon.format.currency knows by symbol
+local _currencies = {
+    USD = { "$", 2 }, EUR = { "€", 2 }, GBP = { "£", 2 }, JPY = { "¥", 0 },
+    KRW = { "₩", 0 }, INR = { "₹", 2 }, CHF = { "CHF", 2 },
+}
+
+-- Seconds in each relative time unit, in the order of `_locale.relative.units`
+local _relative_units = { 1, 60, 3600, 86400, 604800, 2592000, 31536000 }
+
+-- Replaces `{}`-style placeholders literally, without pattern magic
+local function _fill(pattern, placeholder, value)
+    local start, finish = pattern:find(placeholder, 1, true)
+    while start do
+        pattern = pattern:sub(1, start - 1) .. value .. pattern:sub(finish + 1)
+        start, finish = pattern:find(placeholder, start + #value, true)
+    end
+    return pattern
+end
+
+-- Seconds since the Unix epoch of a timestamp or an ISO date (`YYYY-MM-DD`,
+-- optionally with `THH:MM[:SS]`, taken as UTC)
+local function _timestamp(value)
+    if type(value) == "number" then
+        return value
+    end
+    if type(value) ~= "string" then
+        return nil
+    end
+    local seconds = tonumber(value)
+    if seconds then
+        return seconds
+    end
+    local year, month, day = value:match("^%s*(%d%d%d%d)%-(%d%d)%-(%d%d)")
+    if not year then
+        return nil
+    end
+    -- Days from civil (Howard Hinnant), as the runtime's Date does
+    year, month, day = tonumber(year), tonumber(month), tonumber(day)
+    if month <= 2 then year = year - 1 end
+    local era = math.floor(year / 400)
+    local year_of_era = year - era * 400
+    local day_of_year = math.floor((153 * ((month + 9) % 12) + 2) / 5) + day - 1
+    local day_of_era = year_of_era * 365 + math.floor(year_of_era / 4) - math.floor(year_of_era / 100) + day_of_year
+    local days = era * 146097 + day_of_era - 719468
+    local hours, minutes, secs = value:match("[T ](%d%d):(%d%d):?(%d*)")
+    return days * 86400 + (tonumber(hours) or 0) * 3600 + (tonumber(minutes) or 0) * 60 + (tonumber(secs) or 0)
+end
+
+---
+-- A number in the active locale, e.g. "1,234.56" or "1.234,56". Without
+-- `decimals`, up to three fraction digits are shown, trailing zeros dropped.
+---@param value number
+---@param decimals number|nil Fraction digits to show
+---@return string|nil nil if value isn't a number
+--
+function kryon.format.number(value, decimals)
+    value = tonumber(value)
+    if value == nil then
+        return nil
+    end
+    if value ~= value or value == math.huge or value == -math.huge then
+        return tostring(value)
+    end
+    local shown = decimals or 3
+    local scale = 10 ^ shown
+    -- Halves round away from zero
+    local digits = string.format("%." .. shown .. "f", math.floor(math.abs(value) * scale + 0.5) / scale)
+    if decimals == nil and digits:find(".", 1, true) then
+        digits = digits:gsub("0+$", ""):gsub("%.$", "")
+    end
+    local whole, fraction = digits:match("^(%d+)%.?(%d*)$")
+    local groups = {}
+    local first = #whole % 3
+    if first > 0 then
+        table.insert(groups, whole:sub(1, first))
+    end
+    for i = first + 1, #whole, 3 do
+        table.insert(groups, whole:sub(i, i + 2))
+    end
+    local text = table.concat(groups, _locale.group)
+    if fraction ~= "" then
+        text = text .. _locale.decimal .. fraction
+    end
+    if value < 0 and digits:find("[1-9]") then
+        text = "-" .. text
+    end
+    return text
+end
+
+---
+-- An amount of money in the active locale, e.g. "$1,234.50" or "1.234,50 €".
+---@param value number
+---@param code string|nil ISO 4217 code, "USD" by default
+---@return string|nil nil if value isn't a number
+--
+function kryon.format.currency(value, code)
+    value = tonumber(value)
+    if value == nil then
+        return nil
+    end
+    code = string.upper(code or "USD")
+    local currency = _currencies[code] or { code, 2 }
+    local amount = kryon.format.number(math.abs(value), currency[2])
+    local text = _fill(_fill(_locale.currency, "¤", currency[1]), "{}", amount)
+    if value < 0 and amount:find("[1-9]") then
+        text = "-" .. text
+    end
+    return text
+end
+
+---
+-- A date in the active locale: "short" is numeric (3/14/2025), "long" names
+-- the month (March 14, 2025).
+---@param value number|string A Unix timestamp or an ISO date
+---@param style string|nil "short" (the default) or "long"
+---@return string|nil nil if value isn't a date
+--
+function kryon.format.date(value, style)
+    local seconds = _timestamp(value)
+    if seconds == nil then
+        return nil
+    end
+    local date = os.date("!*t", math.floor(seconds))
+    local text = style == "long" and _locale.long_date or _locale.short_date
+    text = _fill(text, "{month}", _locale.months[date.month])
+    text = _fill(text, "{dd}", string.format("%02d", date.day))
+    text = _fill(text, "{mm}", string.format("%02d", date.month))
+    text = _fill(text, "{d}", tostring(date.day))
+    text = _fill(text, "{m}", tostring(date.month))
+    return _fill(text, "{y}", tostring(date.year))
+end
+
+---
+-- How long ago or from now a time is, in the largest whole unit, e.g.
+-- "3 days ago" or "in 2 hours".
+---@param value number|string A Unix timestamp or an ISO date
+---@return string|nil nil if value isn't a time
+--
+function kryon.format.relative(value)
+    local seconds = _timestamp(value)
+    if seconds == nil then
+        return nil
+    end
+    local offset = seconds - os.time()
+    local distance = math.abs(offset)
+    local words = _locale.relative
+    if distance < 1 then
+        return words.now
+    end
+    local unit = 1
+    for index, length in ipairs(_relative_units) do
+        if distance >= length then
+            unit = index
+        end
+    end
+    local amount = math.floor(distance / _relative_units[unit])
+    local names = words.units[unit]
+    local phrase = string.format("%d %s", amount, amount == 1 and names[1] or names[2])
+    return _fill(offset < 0 and words.past or words.future, "{}", phrase)
+end
+
+-- The tag of the active locale, e.g. "de" or "en-GB"
+function kryon.format.locale()
+    return _locale.tag
+end
+
+-- Internal: called by the Rust runtime with the active locale's conventions.
+function _set_locale(locale)
+    _locale = locale
+end
+
+-- Internal: called by the Rust runtime to take queued dialog requests.
+function _take_pending_dialog_requests()
+    local requests = _copy_table(_pending_dialog_requests)
+    _clear_table_in_place(_pending_dialog_requests)
+    return requests
+end
+
+-- Internal: called by the Rust runtime to take queued notifications.
+function _take_pending_notifications()
+    local notifications = _copy_table(_pending_notifications)
+    _clear_table_in_place(_pending_notifications)
+    return notifications
+end
+
+-- Internal: called by the Rust runtime to take queued toast commands.
+function _take_pending_toast_commands()
+    local commands = _copy_table(_pending_toast_commands)
+    _clear_table_in_place(_pending_toast_commands)
+    return commands
+end
+
+-- Internal: called by the Rust runtime to take queued app events.
+function _take_pending_app_events()
+    local events = _copy_table(_pending_app_events)
+    _clear_table_in_place(_pending_app_events)
+    return events
+end
+
+-- Internal: called by the Rust runtime to take queued popup commands.
+function _take_pending_popup_commands()
+    local commands = _copy_table(_pending_popup_commands)
+    _clear_table_in_place(_pending_popup_commands)
+    return commands
+end
+
+-- Internal: called by the Rust runtime to take queued eyedropper requests.
+function _take_pending_eyedropper_requests()
+    local requests = _copy_table(_pending_eyedropper_requests)
+    _clear_table_in_place(_pending_eyedropper_requests)
+    return requests
+end
+
+-- Internal: called by the Rust runtime to take queued process launches.
+function _take_pending_process_requests()
+    local requests = _copy_table(_pending_process_requests)
+    _clear_table_in_place(_pending_process_requests)
+    return requests
+end
+
+-- Internal: called by the Rust runtime with process output and exit status.
+function _deliver_process_event(id, kind, value)
+    local handlers = _process_handlers[id]
+    if not handlers then
+        return
+    end
+
+    local callback, args
+    if kind == "stdout" then
+        callback, args = handlers.on_stdout, { value }
+    elseif kind == "stderr" then
+        callback, args = handlers.on_stderr, { value }
+    else
+        -- "exit" delivers the code, "error" a message; both end the process
+        _process_handlers[id] = nil
+        callback = handlers.on_exit
+        if kind == "exit" then args = { value, nil } else args = { nil, value } end
+    end
+
+    if callback then
+        local success, err = pcall(callback, args[1], args[2])
+        if not success then
+            print("Error in process " .. kind .. " callback: " .. tostring(err))
+        end
+    end
+end
+
+-- Internal: called by the Rust runtime to take queued serial port commands.
+function _take_pending_serial_commands()
+    local commands = _copy_table(_pending_serial_commands)
+    _clear_table_in_place(_pending_serial_commands)
+    return commands
+end
+
+-- Internal: called by the Rust runtime with serial port events.
+function _deliver_serial_event(id, kind, value)
+    local handlers = _serial_handlers[id]
+    if not handlers then
+        return
+    end
+    if kind == "close" then
+        _serial_handlers[id] = nil
+    end
+
+    local callback = handlers["on_" .. kind]
+    if callback then
+        local success, err = pcall(callback, value)
+        if not success then
+            print("Error in serial " .. kind .. " callback: " .. tostring(err))
+        end
+    end
+end
+
+-- Internal: called by the Rust runtime to take queued WebSocket commands.
+function _take_pending_ws_commands()
+    local commands = _copy_table(_pending_ws_commands)
+    _clear_table_in_place(_pending_ws_commands)
+    return commands
+end
+
+-- Internal: called by the Rust runtime with WebSocket events.
+function _deliver_ws_event(id, kind, value, extra)
+    local socket = _ws_sockets[id]
+    if not socket then
+        return
+    end
+
+    local callback, a, b, c
+    if kind == "open" then
+        callback = socket.onOpen
+    elseif kind == "message" then
+        callback, a, b = socket.onMessage, value, extra
+    elseif kind == "error" then
+        callback, a = socket.onError, value
+    elseif kind == "close" then
+        if not value.reconnecting then
+            _ws_sockets[id] = nil
+        end
+        callback, a, b, c = socket.onClose, value.code, value.reason, value.reconnecting
+    end
+
+    if callback then
+        local success, err = pcall(callback, a, b, c)
+        if not success then
+            print("Error in WebSocket " .. kind .. " callback: " .. tostring(err))
+        end
+    end
+end
+
+-- Internal: called by the Rust runtime to take queued MQTT commands.
+function _take_pending_mqtt_commands()
+    local commands = _copy_table(_pending_mqtt_commands)
+    _clear_table_in_place(_pending_mqtt_commands)
+    return commands
+end
+
+-- Internal: called by the Rust runtime with MQTT events.
+function _deliver_mqtt_event(id, kind, value)
+    local entry = _mqtt_clients[id]
+    if not entry then
+        return
+    end
+
+    if kind == "message" then
+        for _, filter in ipairs(value.filters or {}) do
+            local callback = entry.subscriptions[filter]
+            if callback then
+                local success, err = pcall(callback, value.topic, value.payload)
+                if not success then
+                    print("Error in MQTT callback for '" .. filter .. "': " .. tostring(err))
+                end
+            end
+        end
+        return
+    end
+
+    if kind == "disconnect" then
+        _mqtt_clients[id] = nil
+    end
+    local callback = entry.handlers["on_" .. kind]
+    if callback then
+        local success, err = pcall(callback, value)
+        if not success then
+            print("Error in MQTT " .. kind .. " callback: " .. tostring(err))
+        end
+    end
+end
+
+-- Internal: called by the Rust runtime to take queued data source refreshes.
+function _take_pending_data_refreshes()
+    local names = _copy_table(_pending_data_refreshes)
+    _clear_table_in_place(_pending_data_refreshes)
+    return names
+end
+
+-- Internal: called by the Rust runtime to take queued dataset loads and table updates.
+function _take_pending_data_requests()
+    local requests = _copy_table(_pending_data_requests)
+    _clear_table_in_place(_pending_data_requests)
+    return requests
+end
+
+-- Internal: called by the Rust runtime to take queued diagnostic dumps.
+function _take_pending_diagnostic_dumps()
+    local requests = _copy_table(_pending_diagnostic_dumps)
+    _clear_table_in_place(_pending_diagnostic_dumps)
+    return requests
+end
+
+-- Internal: called by the Rust runtime to take queued analytics commands.
+function _take_pending_analytics_commands()
+    local commands = _copy_table(_pending_analytics_commands)
+    _clear_table_in_place(_pending_analytics_commands)
+    return commands
+end
+
+-- Internal: called by the Rust runtime once a diagnostic bundle is written.
+function _deliver_diagnostic_dump(id, path, error)
+    local callback = _diagnostic_callbacks[id]
+    _diagnostic_callbacks[id] = nil
+    if not callback then
+        return
+    end
+
+    local success, err = pcall(callback, path, error)
+    if not success then
+        print("Error in diagnostics callback: " .. tostring(err))
+    end
+end
+
+-- Internal: called by the Rust runtime when a dataset load finishes.
+function _deliver_data_result(id, dataset, error)
+    local callback = _data_callbacks[id]
+    _data_callbacks[id] = nil
+    if not callback then
+        if error then
+            print("Error loading data: " .. tostring(error))
+        end
+        return
+    end
+
+    local success, err = pcall(callback, dataset, error)
+    if not success then
+        print("Error in data load callback: " .. tostring(err))
+    end
+end
+
+-- Internal: called by the Rust runtime when a toast leaves, running its
+-- action callback if the action was pressed.
+function _finish_toast(id, pressed)
+    local callback = _toast_actions[id]
+    if not callback then
+        return
+    end
+    _toast_actions[id] = nil
+
+    if pressed then
+        local success, err = pcall(callback, id)
+        if not success then
+            print("Error in toast action " .. tostring(id) .. ": " .. tostring(err))
+        end
+    end
+end
+
+-- Internal: called by the Rust runtime when a dialog completes.
+function _deliver_dialog_result(id, files, error)
+    local entry = _dialog_callbacks[id]
+    if not entry then
+        return
+    end
+    _dialog_callbacks[id] = nil
+
+    local result = files
+    if files and not entry.multiple then
+        result = files[1]
+    end
+
+    local success, err = pcall(entry.callback, result, error)
+    if not success then
+        print("Error in dialog callback " .. tostring(id) .. ": " .. tostring(err))
+    end
+end
+
+
 -- =============================================================================
 --  5. Internal Getter Functions for the Rust Runtime
 -- =============================================================================
@@ -407,3 +1655,13 @@ end
 function _clear_template_variable_changes()
     _clear_table_in_place(_template_variable_changes)
 end
+
+-- Internal: called by the Rust runtime when a template variable is set from
+-- outside scripts (e.g. the REPL's :set), so script reads see the new value.
+function _set_template_variable(name, value)
+    if _reactive_variables and _reactive_variables[name] then
+        _reactive_variables[name].set(value)
+    elseif _template_variables then
+        _template_variables[name] = value
+    end
+end
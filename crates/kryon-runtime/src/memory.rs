@@ -0,0 +1,249 @@
+// crates/kryon-runtime/src/memory.rs
+//! Where the app's memory goes, gathered from each subsystem.
+//!
+//! Byte counts are estimates. Elements, layout nodes and tables are sized
+//! from their structs plus owned strings and collections. Textures and glyph
+//! atlases are sized from dimensions and pixel formats. Script engines
+//! report their allocator's heap size.
+
+use crate::script::engine_trait::{EngineMemoryStats, ScriptValue};
+use kryon_core::{CellValue, Element, PropertyValue, TableView};
+use kryon_render::BackendMemoryStats;
+use std::collections::HashMap;
+use std::mem::size_of;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SubsystemMemory {
+    pub count: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptMemory {
+    pub language: String,
+    pub current_bytes: usize,
+    pub peak_bytes: usize,
+    pub objects: usize,
+    pub limit: Option<usize>,
+}
+
+impl ScriptMemory {
+    pub fn new(language: impl Into<String>, stats: &EngineMemoryStats) -> Self {
+        Self {
+            language: language.into(),
+            current_bytes: stats.current_usage,
+            peak_bytes: stats.peak_usage,
+            objects: stats.object_count,
+            limit: stats.memory_limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryReport {
+    pub textures: SubsystemMemory,
+    pub glyph_atlases: SubsystemMemory,
+    pub cached_glyphs: usize,
+    /// `count` is elements
+    pub elements: SubsystemMemory,
+    /// `count` is layout nodes
+    pub layout: SubsystemMemory,
+    /// `count` is dataset rows across all tables
+    pub tables: SubsystemMemory,
+    pub scripts: Vec<ScriptMemory>,
+}
+
+impl MemoryReport {
+    pub fn set_backend(&mut self, stats: &BackendMemoryStats) {
+        self.textures = SubsystemMemory { count: stats.textures, bytes: stats.texture_bytes };
+        self.glyph_atlases = SubsystemMemory { count: stats.glyph_atlases, bytes: stats.glyph_atlas_bytes };
+        self.cached_glyphs = stats.cached_glyphs;
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.textures.bytes
+            + self.glyph_atlases.bytes
+            + self.elements.bytes
+            + self.layout.bytes
+            + self.tables.bytes
+            + self.scripts.iter().map(|script| script.current_bytes).sum::<usize>()
+    }
+
+    /// One line per subsystem, for the profiler overlay and the console.
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Memory      {}", format_bytes(self.total_bytes())),
+            format!("Textures    {} ({})", format_bytes(self.textures.bytes), self.textures.count),
+            format!(
+                "Glyphs      {} ({} atlases, {} glyphs)",
+                format_bytes(self.glyph_atlases.bytes), self.glyph_atlases.count, self.cached_glyphs
+            ),
+            format!("Elements    {} ({})", format_bytes(self.elements.bytes), self.elements.count),
+            format!("Layout      {} ({} nodes)", format_bytes(self.layout.bytes), self.layout.count),
+        ];
+        if self.tables.count > 0 {
+            lines.push(format!("Tables      {} ({} rows)", format_bytes(self.tables.bytes), self.tables.count));
+        }
+        for script in &self.scripts {
+            lines.push(format!(
+                "Script {:<4} {} (peak {})",
+                script.language, format_bytes(script.current_bytes), format_bytes(script.peak_bytes)
+            ));
+        }
+        lines
+    }
+
+    /// The table returned by `kryon.memory_report()`.
+    pub fn to_script_value(&self) -> ScriptValue {
+        let subsystem = |memory: &SubsystemMemory| {
+            object([
+                ("count", ScriptValue::Integer(memory.count as i64)),
+                ("bytes", ScriptValue::Integer(memory.bytes as i64)),
+            ])
+        };
+        let scripts = self.scripts.iter()
+            .map(|script| {
+                let limit = script.limit.map_or(ScriptValue::Nil, |limit| ScriptValue::Integer(limit as i64));
+                (script.language.clone(), object([
+                    ("bytes", ScriptValue::Integer(script.current_bytes as i64)),
+                    ("peak_bytes", ScriptValue::Integer(script.peak_bytes as i64)),
+                    ("objects", ScriptValue::Integer(script.objects as i64)),
+                    ("limit", limit),
+                ]))
+            })
+            .collect();
+
+        object([
+            ("total_bytes", ScriptValue::Integer(self.total_bytes() as i64)),
+            ("textures", subsystem(&self.textures)),
+            ("glyph_atlases", subsystem(&self.glyph_atlases)),
+            ("cached_glyphs", ScriptValue::Integer(self.cached_glyphs as i64)),
+            ("elements", subsystem(&self.elements)),
+            ("layout", subsystem(&self.layout)),
+            ("tables", subsystem(&self.tables)),
+            ("scripts", ScriptValue::Object(scripts)),
+        ])
+    }
+}
+
+fn object<const N: usize>(fields: [(&str, ScriptValue); N]) -> ScriptValue {
+    ScriptValue::Object(fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+}
+
+pub fn element_memory<'a>(elements: impl IntoIterator<Item = &'a Element>) -> SubsystemMemory {
+    elements.into_iter().fold(SubsystemMemory::default(), |total, element| SubsystemMemory {
+        count: total.count + 1,
+        bytes: total.bytes + element_bytes(element),
+    })
+}
+
+pub fn table_memory<'a>(tables: impl IntoIterator<Item = &'a TableView>) -> SubsystemMemory {
+    tables.into_iter().fold(SubsystemMemory::default(), |total, view| {
+        let dataset = view.dataset();
+        let cells: usize = dataset.rows.iter()
+            .map(|row| {
+                row.capacity() * size_of::<CellValue>()
+                    + row.iter().map(|cell| match cell {
+                        CellValue::Text(text) => text.capacity(),
+                        _ => 0,
+                    }).sum::<usize>()
+            })
+            .sum();
+        SubsystemMemory {
+            count: total.count + dataset.rows.len(),
+            bytes: total.bytes + cells + std::mem::size_of_val(view.visible_rows()),
+        }
+    })
+}
+
+fn element_bytes(element: &Element) -> usize {
+    size_of::<Element>()
+        + element.id.capacity()
+        + element.text.capacity()
+        + element.font_family.capacity()
+        + element.children.capacity() * size_of::<kryon_core::ElementId>()
+        + element.component_name.as_ref().map_or(0, String::capacity)
+        + properties_bytes(&element.custom_properties)
+        + properties_bytes(&element.native_config)
+        + element.state_properties.values().map(properties_bytes).sum::<usize>()
+        + element.event_handlers.values().map(|handler| size_of::<(kryon_core::EventType, String)>() + handler.capacity()).sum::<usize>()
+}
+
+fn properties_bytes(properties: &HashMap<String, PropertyValue>) -> usize {
+    properties.iter()
+        .map(|(key, value)| {
+            let value_bytes = match value {
+                PropertyValue::String(text) | PropertyValue::Resource(text) => text.capacity(),
+                _ => 0,
+            };
+            size_of::<(String, PropertyValue)>() + key.capacity() + value_bytes
+        })
+        .sum()
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn element_estimate_counts_owned_strings() {
+        let small = Element::default();
+        let mut large = Element::default();
+        large.text = "x".repeat(4096);
+        large.custom_properties.insert("label".into(), PropertyValue::String("y".repeat(1024)));
+
+        let memory = element_memory([&small, &large]);
+        assert_eq!(memory.count, 2);
+        assert!(memory.bytes >= 2 * size_of::<Element>() + 4096 + 1024);
+    }
+
+    #[test]
+    fn report_totals_and_script_table() {
+        let mut report = MemoryReport::default();
+        report.set_backend(&BackendMemoryStats {
+            textures: 2,
+            texture_bytes: 4096,
+            glyph_atlases: 1,
+            glyph_atlas_bytes: 1024,
+            cached_glyphs: 80,
+        });
+        report.elements = SubsystemMemory { count: 3, bytes: 300 };
+        report.scripts.push(ScriptMemory {
+            language: "lua".into(),
+            current_bytes: 2000,
+            peak_bytes: 2500,
+            objects: 4,
+            limit: None,
+        });
+        assert_eq!(report.total_bytes(), 4096 + 1024 + 300 + 2000);
+        assert!(report.summary_lines().iter().any(|line| line.starts_with("Script lua")));
+
+        let ScriptValue::Object(fields) = report.to_script_value() else { panic!("expected a table") };
+        assert!(matches!(fields["total_bytes"], ScriptValue::Integer(7420)));
+        let ScriptValue::Object(scripts) = &fields["scripts"] else { panic!("expected scripts") };
+        let ScriptValue::Object(lua) = &scripts["lua"] else { panic!("expected lua") };
+        assert!(matches!(lua["peak_bytes"], ScriptValue::Integer(2500)));
+    }
+}
@@ -0,0 +1,351 @@
+// crates/kryon-runtime/src/metrics.rs
+//! Runtime metrics in the Prometheus text exposition format, for fleets of
+//! kiosks and dashboards watched from Prometheus or Grafana.
+//!
+//! `KryonApp` records every frame it renders and publishes a fresh text
+//! snapshot each time it refreshes the memory report. The snapshot can be
+//! scraped from a small HTTP server at `/metrics`, or pushed to a
+//! Pushgateway for devices behind NAT that Prometheus can't reach. Both
+//! need the `metrics` feature; the text itself is always available from
+//! [`MetricsExporter::text`].
+//!
+//! Every sample carries `backend` and `app_id` labels so one dashboard can
+//! tell devices and renderers apart.
+
+use crate::memory::MemoryReport;
+use kryon_render::RenderStats;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Frame times kept for the percentiles
+const FRAME_WINDOW: usize = 600;
+
+/// Percentiles reported for frame times
+const QUANTILES: [f64; 3] = [0.5, 0.9, 0.99];
+
+/// Collects frame timings and counters and formats them for Prometheus.
+pub struct MetricsExporter {
+    app_id: String,
+    backend: String,
+    /// Milliseconds spent updating and rendering each of the recent frames
+    frame_times: VecDeque<f32>,
+    frames: u64,
+    frame_ms_total: f64,
+    style_cache_hits: u64,
+    style_cache_misses: u64,
+    /// Frames since the last snapshot, for the frame rate
+    window_frames: u64,
+    window_start: Instant,
+    fps: f32,
+    text: Arc<Mutex<String>>,
+}
+
+impl MetricsExporter {
+    pub fn new(app_id: impl Into<String>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            backend: String::new(),
+            frame_times: VecDeque::with_capacity(FRAME_WINDOW),
+            frames: 0,
+            frame_ms_total: 0.0,
+            style_cache_hits: 0,
+            style_cache_misses: 0,
+            window_frames: 0,
+            window_start: Instant::now(),
+            fps: 0.0,
+            text: Arc::new(Mutex::new(String::new())),
+        }
+    }
+
+    /// Exporter for the renderers' `--metrics-listen` / `--metrics-push`
+    /// flags, or `None` when neither was given. Failures are logged and
+    /// leave the other transport running.
+    pub fn from_args(app_id: &str, listen: Option<&str>, push: Option<&str>, push_interval: Duration) -> Option<Self> {
+        if listen.is_none() && push.is_none() {
+            return None;
+        }
+        let exporter = Self::new(app_id);
+        if let Some(addr) = listen {
+            if let Err(e) = exporter.listen(addr) {
+                eprintln!("[METRICS] Failed to listen on {}: {}", addr, e);
+            }
+        }
+        if let Some(url) = push {
+            if let Err(e) = exporter.push_to(url, push_interval) {
+                eprintln!("[METRICS] Failed to push to {}: {}", url, e);
+            }
+        }
+        Some(exporter)
+    }
+
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    /// Set by the app from its renderer when the exporter is attached
+    pub fn set_backend(&mut self, backend: impl Into<String>) {
+        self.backend = backend.into();
+    }
+
+    pub fn record_frame(&mut self, frame_ms: f32, stats: &RenderStats) {
+        if self.frame_times.len() == FRAME_WINDOW {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_ms);
+        self.frames += 1;
+        self.frame_ms_total += frame_ms as f64;
+        self.style_cache_hits += stats.style_cache_hits;
+        self.style_cache_misses += stats.style_cache_misses;
+        self.window_frames += 1;
+    }
+
+    /// Frame time in milliseconds below which `quantile` of the recent
+    /// frames fall, nearest-rank
+    pub fn frame_time_percentile(&self, quantile: f64) -> f32 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        let mut sorted: Vec<f32> = self.frame_times.iter().copied().collect();
+        sorted.sort_by(f32::total_cmp);
+        let rank = (quantile * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    /// Frames rendered per second since the last snapshot
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+
+    /// Formats a new snapshot for the server and the pushes to send.
+    pub fn publish(&mut self, memory: &MemoryReport, script_errors: u64, decoded_images: usize) {
+        let elapsed = self.window_start.elapsed().as_secs_f32();
+        if elapsed > 0.0 {
+            self.fps = self.window_frames as f32 / elapsed;
+        }
+        self.window_frames = 0;
+        self.window_start = Instant::now();
+
+        let text = self.format(memory, script_errors, decoded_images);
+        *self.text.lock().unwrap() = text;
+    }
+
+    /// The last published snapshot
+    pub fn text(&self) -> String {
+        self.text.lock().unwrap().clone()
+    }
+
+    fn format(&self, memory: &MemoryReport, script_errors: u64, decoded_images: usize) -> String {
+        let labels = format!("backend=\"{}\",app_id=\"{}\"", escape(&self.backend), escape(&self.app_id));
+        let plain = |value: String| Sample { suffix: "", labels: labels.clone(), value };
+        let with = |extra: String, value: String| Sample { suffix: "", labels: format!("{},{}", labels, extra), value };
+
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: Vec<Sample>| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for sample in samples {
+                let _ = writeln!(out, "{}{}{{{}}} {}", name, sample.suffix, sample.labels, sample.value);
+            }
+        };
+
+        let mut frame_times: Vec<Sample> = QUANTILES.iter()
+            .map(|&quantile| with(format!("quantile=\"{}\"", quantile), self.frame_time_percentile(quantile).to_string()))
+            .collect();
+        frame_times.push(Sample { suffix: "_sum", ..plain(self.frame_ms_total.to_string()) });
+        frame_times.push(Sample { suffix: "_count", ..plain(self.frames.to_string()) });
+        metric("kryon_frame_time_milliseconds", "summary", "Time spent updating and rendering a frame", frame_times);
+        metric("kryon_fps", "gauge", "Frames rendered per second", vec![plain(self.fps.to_string())]);
+
+        let mut subsystems = vec![
+            with("subsystem=\"textures\"".to_string(), memory.textures.bytes.to_string()),
+            with("subsystem=\"glyph_atlases\"".to_string(), memory.glyph_atlases.bytes.to_string()),
+            with("subsystem=\"elements\"".to_string(), memory.elements.bytes.to_string()),
+            with("subsystem=\"layout\"".to_string(), memory.layout.bytes.to_string()),
+            with("subsystem=\"tables\"".to_string(), memory.tables.bytes.to_string()),
+        ];
+        subsystems.extend(memory.scripts.iter().map(|script| {
+            with(format!("subsystem=\"script\",language=\"{}\"", escape(&script.language)), script.current_bytes.to_string())
+        }));
+        metric("kryon_memory_bytes", "gauge", "Estimated memory held by each subsystem", subsystems);
+        metric("kryon_memory_total_bytes", "gauge", "Estimated memory held by all subsystems", vec![plain(memory.total_bytes().to_string())]);
+
+        metric("kryon_script_errors_total", "counter", "Script function calls that failed", vec![plain(script_errors.to_string())]);
+
+        metric("kryon_textures", "gauge", "Textures held by the backend", vec![plain(memory.textures.count.to_string())]);
+        metric("kryon_cached_glyphs", "gauge", "Glyphs in the backend's glyph atlases", vec![plain(memory.cached_glyphs.to_string())]);
+        metric("kryon_decoded_images", "gauge", "Images decoded so far", vec![plain(decoded_images.to_string())]);
+        metric("kryon_style_cache_hits_total", "counter", "Styles reused from the style cache", vec![plain(self.style_cache_hits.to_string())]);
+        metric("kryon_style_cache_misses_total", "counter", "Styles computed because the cache had none", vec![plain(self.style_cache_misses.to_string())]);
+        out
+    }
+
+    /// Serves the snapshot at `http://ADDR/metrics`.
+    pub fn listen(&self, addr: &str) -> io::Result<SocketAddr> {
+        transport::listen(addr, self.text.clone())
+    }
+
+    /// Pushes the snapshot to a Pushgateway at `url` every `interval`,
+    /// grouped under the `kryon` job and this app's id.
+    pub fn push_to(&self, url: &str, interval: Duration) -> io::Result<()> {
+        let url = format!("{}/metrics/job/kryon/app_id/{}", url.trim_end_matches('/'), self.app_id);
+        transport::push(url, interval, self.text.clone())
+    }
+}
+
+/// One line of a metric: `name` + `suffix` `{labels}` `value`
+struct Sample {
+    suffix: &'static str,
+    labels: String,
+    value: String,
+}
+
+/// Escapes a label value: backslashes, quotes and newlines
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+mod transport {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+    const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn listen(addr: &str, text: Arc<Mutex<String>>) -> io::Result<SocketAddr> {
+        let listener = TcpListener::bind(addr)?;
+        let local = listener.local_addr()?;
+        eprintln!("[METRICS] Serving metrics on http://{}/metrics", local);
+
+        std::thread::Builder::new()
+            .name("kryon-metrics".to_string())
+            .spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let _ = respond(stream, &text);
+                }
+            })?;
+        Ok(local)
+    }
+
+    fn respond(mut stream: std::net::TcpStream, text: &Mutex<String>) -> io::Result<()> {
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        // Only the request line matters; scrapers send short requests
+        let mut request = [0u8; 1024];
+        let read = stream.read(&mut request)?;
+        let request = String::from_utf8_lossy(&request[..read]);
+        let path = request.split_whitespace().nth(1).unwrap_or("");
+
+        let (status, body) = if path == "/metrics" || path.starts_with("/metrics?") {
+            ("200 OK", text.lock().unwrap().clone())
+        } else {
+            ("404 Not Found", "Metrics are served at /metrics\n".to_string())
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status, CONTENT_TYPE, body.len(), body
+        )
+    }
+
+    pub fn push(url: String, interval: Duration, text: Arc<Mutex<String>>) -> io::Result<()> {
+        eprintln!("[METRICS] Pushing metrics to {} every {:?}", url, interval);
+        std::thread::Builder::new()
+            .name("kryon-metrics-push".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(interval);
+                let body = text.lock().unwrap().clone();
+                if body.is_empty() {
+                    continue;
+                }
+                let result = ureq::put(&url)
+                    .timeout(PUSH_TIMEOUT)
+                    .set("Content-Type", CONTENT_TYPE)
+                    .send_string(&body);
+                if let Err(e) = result {
+                    eprintln!("[METRICS] Push to {} failed: {}", url, e);
+                }
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(not(all(feature = "metrics", not(target_arch = "wasm32"))))]
+mod transport {
+    use super::*;
+
+    fn unsupported() -> io::Error {
+        io::Error::new(io::ErrorKind::Unsupported, "metrics export needs the `metrics` feature")
+    }
+
+    pub fn listen(_addr: &str, _text: Arc<Mutex<String>>) -> io::Result<SocketAddr> {
+        Err(unsupported())
+    }
+
+    pub fn push(_url: String, _interval: Duration, _text: Arc<Mutex<String>>) -> io::Result<()> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{ScriptMemory, SubsystemMemory};
+
+    #[test]
+    fn percentiles_use_the_recent_frames() {
+        let mut exporter = MetricsExporter::new("kiosk");
+        assert_eq!(exporter.frame_time_percentile(0.5), 0.0);
+        for ms in 1..=100 {
+            exporter.record_frame(ms as f32, &RenderStats::default());
+        }
+        assert_eq!(exporter.frame_time_percentile(0.5), 50.0);
+        assert_eq!(exporter.frame_time_percentile(0.9), 90.0);
+        assert_eq!(exporter.frame_time_percentile(0.99), 99.0);
+
+        for _ in 0..FRAME_WINDOW {
+            exporter.record_frame(2.0, &RenderStats::default());
+        }
+        assert_eq!(exporter.frame_time_percentile(0.99), 2.0, "old frames leave the window");
+    }
+
+    #[test]
+    fn snapshot_is_labelled_prometheus_text() {
+        let mut exporter = MetricsExporter::new("lobby \"east\"");
+        exporter.set_backend("raylib");
+        exporter.record_frame(4.0, &RenderStats { style_cache_hits: 3, style_cache_misses: 1, ..Default::default() });
+        let memory = MemoryReport {
+            textures: SubsystemMemory { count: 2, bytes: 4096 },
+            scripts: vec![ScriptMemory {
+                language: "lua".to_string(),
+                current_bytes: 1000,
+                peak_bytes: 2000,
+                objects: 5,
+                limit: None,
+            }],
+            ..Default::default()
+        };
+        assert!(exporter.text().is_empty(), "nothing published yet");
+        exporter.publish(&memory, 7, 1);
+
+        let text = exporter.text();
+        let labels = "backend=\"raylib\",app_id=\"lobby \\\"east\\\"\"";
+        for line in [
+            "# TYPE kryon_frame_time_milliseconds summary".to_string(),
+            format!("kryon_frame_time_milliseconds{{{},quantile=\"0.99\"}} 4", labels),
+            format!("kryon_frame_time_milliseconds_count{{{}}} 1", labels),
+            format!("kryon_memory_bytes{{{},subsystem=\"textures\"}} 4096", labels),
+            format!("kryon_memory_bytes{{{},subsystem=\"script\",language=\"lua\"}} 1000", labels),
+            format!("kryon_memory_total_bytes{{{}}} 5096", labels),
+            format!("kryon_script_errors_total{{{}}} 7", labels),
+            format!("kryon_textures{{{}}} 2", labels),
+            format!("kryon_style_cache_hits_total{{{}}} 3", labels),
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing `{}` in\n{}", line, text);
+        }
+    }
+}
@@ -0,0 +1,345 @@
+// crates/kryon-runtime/src/net/data_source.rs
+//! Declarative JSON data sources bound to template variables.
+//!
+//! Any element can declare a source with properties; the runtime fetches it,
+//! picks values out with JSON pointers and writes them into template
+//! variables:
+//!
+//! ```text
+//! Container {
+//!     data_source: "https://api.example.com/plant/status"
+//!     data_interval: 5          # seconds, 0 = fetch once
+//!     data_bind: "/temperature = temperature; /alarms/0/text = alarm"
+//!     data_name: "plant"        # defaults to the element id
+//! }
+//! ```
+//!
+//! While a request is running `plant_loading` is `"true"`, and `plant_error`
+//! holds the last failure (empty on success). Scripts can force a fetch with
+//! `kryon.data.refresh("plant")`.
+//!
+//! URLs without a scheme (or `file://`) are read from disk. Native HTTP needs
+//! the `http` feature; web builds use `fetch`.
+
+use super::parse_bindings;
+use kryon_core::Element;
+use serde_json::Value;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSource {
+    pub name: String,
+    pub url: String,
+    /// Refetch period; `None` fetches once
+    pub interval: Option<Duration>,
+    /// `(json pointer, template variable)` pairs
+    pub bindings: Vec<(String, String)>,
+}
+
+impl DataSource {
+    /// Reads the `data_*` properties of an element that declares a source.
+    pub fn from_element(element: &Element) -> Option<Self> {
+        let props = &element.custom_properties;
+        let url = props.get("data_source")?.as_string()?.trim().to_string();
+        if url.is_empty() {
+            return None;
+        }
+
+        let name = props.get("data_name")
+            .and_then(|v| v.as_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(element.id.as_str())
+            .to_string();
+        if name.is_empty() {
+            eprintln!("[DATA] Ignoring data source {} without an element id or data_name", url);
+            return None;
+        }
+
+        let interval = props.get("data_interval")
+            .and_then(|v| v.as_float())
+            .filter(|secs| *secs > 0.0)
+            .map(|secs| Duration::from_secs_f32(secs.max(0.1)));
+        let bindings = props.get("data_bind")
+            .and_then(|v| v.as_string())
+            .map(parse_bindings)
+            .unwrap_or_default();
+
+        Some(Self { name, url, interval, bindings })
+    }
+
+    pub fn loading_variable(&self) -> String {
+        format!("{}_loading", self.name)
+    }
+
+    pub fn error_variable(&self) -> String {
+        format!("{}_error", self.name)
+    }
+
+    /// Template variable values for a fetched document. Missing pointers
+    /// leave their variables untouched.
+    pub fn extract(&self, body: &str) -> Result<Vec<(String, String)>, String> {
+        let document: Value = serde_json::from_str(body).map_err(|e| format!("invalid JSON: {}", e))?;
+        Ok(self.bindings.iter()
+            .filter_map(|(pointer, variable)| {
+                document.pointer(pointer).map(|value| (variable.clone(), value_to_string(value)))
+            })
+            .collect())
+    }
+}
+
+/// Strings are used as-is, null becomes empty and structures stay JSON.
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+struct SourceState {
+    source: DataSource,
+    until_next: Option<Duration>,
+    in_flight: bool,
+}
+
+/// Polls every declared data source on the frame clock.
+pub struct DataSourceManager {
+    sources: Vec<SourceState>,
+    sender: Sender<(usize, Result<String, String>)>,
+    receiver: Receiver<(usize, Result<String, String>)>,
+}
+
+impl DataSourceManager {
+    pub fn new(sources: Vec<DataSource>) -> Self {
+        let (sender, receiver) = channel();
+        let sources = sources.into_iter()
+            .map(|source| SourceState { source, until_next: Some(Duration::ZERO), in_flight: false })
+            .collect();
+        Self { sources, sender, receiver }
+    }
+
+    /// Collects the sources declared on `elements`, sorted by name.
+    pub fn from_elements<'a>(elements: impl IntoIterator<Item = &'a Element>) -> Self {
+        let mut sources: Vec<DataSource> = elements.into_iter().filter_map(DataSource::from_element).collect();
+        sources.sort_by(|a, b| a.name.cmp(&b.name));
+        Self::new(sources)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &DataSource> {
+        self.sources.iter().map(|state| &state.source)
+    }
+
+    /// Schedules an immediate fetch of the named source, or of all sources
+    /// when `name` is `None`. Returns false for an unknown name.
+    pub fn refresh(&mut self, name: Option<&str>) -> bool {
+        let mut found = false;
+        for state in &mut self.sources {
+            if name.is_none_or(|name| state.source.name == name) {
+                state.until_next = Some(Duration::ZERO);
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Starts due fetches and applies finished ones. Returns template
+    /// variable updates, including the loading/error state variables.
+    pub fn update(&mut self, delta_time: Duration) -> Vec<(String, String)> {
+        let mut updates = Vec::new();
+
+        for (index, result) in self.receiver.try_iter() {
+            let Some(state) = self.sources.get_mut(index) else { continue };
+            state.in_flight = false;
+            if state.until_next.is_none() {
+                state.until_next = state.source.interval;
+            }
+
+            let outcome = result.and_then(|body| state.source.extract(&body));
+            let error = match outcome {
+                Ok(values) => {
+                    updates.extend(values);
+                    String::new()
+                }
+                Err(error) => {
+                    eprintln!("[DATA] Source '{}' failed: {}", state.source.name, error);
+                    error
+                }
+            };
+            updates.push((state.source.loading_variable(), "false".to_string()));
+            updates.push((state.source.error_variable(), error));
+        }
+
+        for (index, state) in self.sources.iter_mut().enumerate() {
+            let Some(remaining) = state.until_next else { continue };
+            if state.in_flight {
+                continue;
+            }
+            if remaining > delta_time {
+                state.until_next = Some(remaining - delta_time);
+                continue;
+            }
+
+            state.in_flight = true;
+            state.until_next = None; // rescheduled when the result arrives
+            updates.push((state.source.loading_variable(), "true".to_string()));
+            backend::fetch(index, state.source.url.clone(), self.sender.clone());
+        }
+
+        updates
+    }
+}
+
+/// Local paths, with or without a `file://` prefix.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn read_file(url: &str) -> Result<String, String> {
+    let path = url.strip_prefix("file://").unwrap_or(url);
+    std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn is_http(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+
+    #[cfg(feature = "http")]
+    const TIMEOUT: Duration = Duration::from_secs(15);
+
+    pub fn fetch(index: usize, url: String, results: Sender<(usize, Result<String, String>)>) {
+        std::thread::spawn(move || {
+            let result = if is_http(&url) { get(&url) } else { read_file(&url) };
+            let _ = results.send((index, result));
+        });
+    }
+
+    #[cfg(feature = "http")]
+    fn get(url: &str) -> Result<String, String> {
+        ureq::get(url)
+            .timeout(TIMEOUT)
+            .set("Accept", "application/json")
+            .call()
+            .map_err(|e| e.to_string())?
+            .into_string()
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(feature = "http"))]
+    fn get(_url: &str) -> Result<String, String> {
+        Err("HTTP data sources not available (built without the `http` feature)".to_string())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use super::*;
+
+    pub fn fetch(index: usize, url: String, results: Sender<(usize, Result<String, String>)>) {
+        #[cfg(feature = "web")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = results.send((index, get(&url).await));
+        });
+        #[cfg(not(feature = "web"))]
+        let _ = results.send((index, Err(format!("cannot fetch {} without the `web` feature", url))));
+    }
+
+    #[cfg(feature = "web")]
+    async fn get(url: &str) -> Result<String, String> {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+
+        let window = web_sys::window().ok_or("no window")?;
+        let response = JsFuture::from(window.fetch_with_str(url)).await.map_err(|e| format!("{:?}", e))?;
+        let response: web_sys::Response = response.dyn_into().map_err(|_| "fetch did not return a Response")?;
+        if !response.ok() {
+            return Err(format!("HTTP {} {}", response.status(), response.status_text()));
+        }
+        let text = response.text().map_err(|e| format!("{:?}", e))?;
+        JsFuture::from(text).await
+            .map_err(|e| format!("{:?}", e))?
+            .as_string()
+            .ok_or_else(|| "response body is not text".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::{ElementType, PropertyValue};
+
+    fn source(bindings: &str) -> DataSource {
+        DataSource {
+            name: "plant".into(),
+            url: "plant.json".into(),
+            interval: None,
+            bindings: parse_bindings(bindings),
+        }
+    }
+
+    #[test]
+    fn extracts_values_by_pointer() {
+        let source = source("/temperature = temp; /alarms/0/text = alarm; /ok = ok; /missing = gone; /meta = meta");
+        let values = source
+            .extract(r#"{"temperature": 74.5, "alarms": [{"text": "Low oil"}], "ok": true, "meta": {"v": null}}"#)
+            .unwrap();
+        assert_eq!(values, vec![
+            ("temp".to_string(), "74.5".to_string()),
+            ("alarm".to_string(), "Low oil".to_string()),
+            ("ok".to_string(), "true".to_string()),
+            ("meta".to_string(), r#"{"v":null}"#.to_string()),
+        ]);
+        assert!(source.extract("not json").is_err());
+    }
+
+    #[test]
+    fn reads_element_properties() {
+        let mut element = Element { element_type: ElementType::Container, ..Default::default() };
+        element.id = "status_panel".into();
+        element.custom_properties.insert("data_source".into(), PropertyValue::String("https://x/status".into()));
+        element.custom_properties.insert("data_interval".into(), PropertyValue::Float(2.0));
+        element.custom_properties.insert("data_bind".into(), PropertyValue::String("/a = a".into()));
+
+        let source = DataSource::from_element(&element).unwrap();
+        assert_eq!(source.name, "status_panel");
+        assert_eq!(source.interval, Some(Duration::from_secs(2)));
+        assert_eq!(source.error_variable(), "status_panel_error");
+        assert!(DataSource::from_element(&Element::default()).is_none());
+    }
+
+    #[test]
+    fn file_source_round_trip() {
+        let path = std::env::temp_dir().join(format!("kryon-data-source-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"level": 3}"#).unwrap();
+        let mut source = source("/level = level");
+        source.url = path.to_string_lossy().into_owned();
+        let mut manager = DataSourceManager::new(vec![source]);
+
+        assert_eq!(manager.update(Duration::ZERO), vec![("plant_loading".to_string(), "true".to_string())]);
+        let mut updates = Vec::new();
+        for _ in 0..200 {
+            updates = manager.update(Duration::from_millis(10));
+            if !updates.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(updates, vec![
+            ("level".to_string(), "3".to_string()),
+            ("plant_loading".to_string(), "false".to_string()),
+            ("plant_error".to_string(), String::new()),
+        ]);
+        // Fetched once; only a refresh starts another request
+        assert!(manager.update(Duration::from_secs(60)).is_empty());
+        assert!(manager.refresh(Some("plant")));
+        assert!(!manager.refresh(Some("other")));
+    }
+}
@@ -0,0 +1,53 @@
+// crates/kryon-runtime/src/net/mod.rs
+//! Networking for scripts. Connections run off the frame loop and report
+//! events through channels that `KryonApp::update` drains each frame.
+
+pub mod data_source;
+pub mod mqtt;
+pub mod websocket;
+
+pub use data_source::{DataSource, DataSourceManager};
+pub use mqtt::{MqttCommand, MqttConfig, MqttEvent, MqttManager};
+pub use websocket::{ReconnectPolicy, WebSocketManager, WsCommand, WsEvent, WsMessage};
+
+/// Shared tokio runtime for native network clients, started on first use.
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+pub(crate) fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("kryon-net")
+            .enable_all()
+            .build()
+            .expect("Failed to start network runtime")
+    })
+}
+
+/// Parses `key = variable` pairs separated by `;` or newlines, as used by the
+/// `mqtt_bind` and `data_bind` properties.
+pub fn parse_bindings(spec: &str) -> Vec<(String, String)> {
+    spec.split([';', '\n'])
+        .filter_map(|pair| {
+            let (key, variable) = pair.split_once('=')?;
+            let (key, variable) = (key.trim(), variable.trim());
+            (!key.is_empty() && !variable.is_empty()).then(|| (key.to_string(), variable.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_binding_specs() {
+        assert_eq!(
+            parse_bindings("plant/+/temperature = temperature; /status/text=status\n bad ;=x"),
+            vec![
+                ("plant/+/temperature".to_string(), "temperature".to_string()),
+                ("/status/text".to_string(), "status".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,668 @@
+// crates/kryon-runtime/src/net/mqtt.rs
+//! MQTT client behind `kryon.mqtt.connect(url)` (native only, `mqtt` feature).
+//!
+//! Besides script callbacks, topic filters can be bound straight to template
+//! variables, either from a script (`client:bind(filter, name)`) or from the
+//! App element, so a dashboard can show live values without any script:
+//!
+//! ```text
+//! App {
+//!     mqtt_broker: "mqtts://broker.local:8883"
+//!     mqtt_bind: "plant/+/temperature = temperature; plant/status = status"
+//! }
+//! ```
+
+use super::{parse_bindings, ReconnectPolicy};
+use crate::script::engine_trait::ScriptValue;
+use kryon_core::Element;
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+/// Connection id used for the connection declared on the App element.
+/// Script connections start at 1.
+pub const DECLARED_CONNECTION_ID: u64 = 0;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MqttTls {
+    /// PEM file with the broker's CA; the platform roots are used when unset
+    pub ca_file: Option<String>,
+    /// PEM client certificate and key for mutual TLS
+    pub cert_file: Option<String>,
+    pub key_file: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: Option<MqttTls>,
+    pub client_id: String,
+    pub credentials: Option<(String, String)>,
+    pub keep_alive: Duration,
+    pub clean_session: bool,
+    pub reconnect: ReconnectPolicy,
+}
+
+impl MqttConfig {
+    /// Parses `mqtt://host[:port]` or `mqtts://host[:port]` (ports default to
+    /// 1883 and 8883).
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("mqtts://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("mqtt://").or_else(|| url.strip_prefix("tcp://")) {
+            (false, rest)
+        } else {
+            return None;
+        };
+
+        let authority = rest.split('/').next().unwrap_or_default();
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().ok()?),
+            None => (authority, if tls { 8883 } else { 1883 }),
+        };
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            host: host.to_string(),
+            port,
+            tls: tls.then(MqttTls::default),
+            client_id: format!("kryon-{}", std::process::id()),
+            credentials: None,
+            keep_alive: Duration::from_secs(30),
+            clean_session: true,
+            reconnect: ReconnectPolicy::default(),
+        })
+    }
+
+    /// Reads `client_id`, `username`/`password`, `keep_alive` (seconds),
+    /// `clean_session`, `reconnect` and a `tls` table from script options.
+    fn apply_options(&mut self, options: &HashMap<String, ScriptValue>) {
+        if let Some(ScriptValue::String(client_id)) = options.get("client_id") {
+            self.client_id = client_id.clone();
+        }
+        if let Some(ScriptValue::String(username)) = options.get("username") {
+            let password = options.get("password").map(|p| p.to_string()).unwrap_or_default();
+            self.credentials = Some((username.clone(), password));
+        }
+        match options.get("keep_alive") {
+            Some(ScriptValue::Integer(secs)) if *secs > 0 => self.keep_alive = Duration::from_secs(*secs as u64),
+            Some(ScriptValue::Number(secs)) if *secs > 0.0 => self.keep_alive = Duration::from_secs_f64(*secs),
+            _ => {}
+        }
+        if let Some(clean) = options.get("clean_session") {
+            self.clean_session = clean.to_bool();
+        }
+        if let Some(reconnect) = options.get("reconnect") {
+            self.reconnect.enabled = reconnect.to_bool();
+        }
+        if let Some(ScriptValue::Object(tls)) = options.get("tls") {
+            let path = |key: &str| match tls.get(key) {
+                Some(ScriptValue::String(path)) if !path.is_empty() => Some(path.clone()),
+                _ => None,
+            };
+            self.tls = Some(MqttTls {
+                ca_file: path("ca_file"),
+                cert_file: path("cert_file"),
+                key_file: path("key_file"),
+            });
+        }
+    }
+
+    /// The connection declared with `mqtt_*` properties on the App element,
+    /// with its topic-to-variable bindings.
+    pub fn from_element(element: &Element) -> Option<(Self, Vec<(String, String)>)> {
+        let props = &element.custom_properties;
+        let string = |key: &str| props.get(key).and_then(|v| v.as_string()).filter(|s| !s.is_empty()).map(|s| s.to_string());
+
+        let mut config = Self::from_url(&string("mqtt_broker")?)?;
+        if let Some(client_id) = string("mqtt_client_id") {
+            config.client_id = client_id;
+        }
+        if let Some(username) = string("mqtt_username") {
+            config.credentials = Some((username, string("mqtt_password").unwrap_or_default()));
+        }
+        if let Some(ca_file) = string("mqtt_ca_file") {
+            config.tls.get_or_insert_with(MqttTls::default).ca_file = Some(ca_file);
+        }
+        let bindings = string("mqtt_bind").map(|spec| parse_bindings(&spec)).unwrap_or_default();
+        Some((config, bindings))
+    }
+}
+
+/// MQTT topic filter matching with `+` (one level) and `#` (all remaining levels).
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut topic_levels = topic.split('/');
+    for level in filter.split('/') {
+        match (level, topic_levels.next()) {
+            ("#", _) => return !topic.starts_with('$') || !filter.starts_with('#'),
+            ("+", Some(name)) => {
+                if name.starts_with('$') && filter.starts_with('+') {
+                    return false;
+                }
+            }
+            (level, Some(name)) if level == name => {}
+            _ => return false,
+        }
+    }
+    topic_levels.next().is_none()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MqttCommand {
+    Connect { id: u64, config: MqttConfig },
+    Subscribe { id: u64, filter: String, qos: u8 },
+    Unsubscribe { id: u64, filter: String },
+    Publish { id: u64, topic: String, payload: Vec<u8>, qos: u8, retain: bool },
+    Bind { id: u64, filter: String, variable: String },
+    Disconnect { id: u64 },
+}
+
+impl MqttCommand {
+    /// Parses a command queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+
+        let id = match fields.get("id")? {
+            ScriptValue::Integer(id) if *id > 0 => *id as u64,
+            ScriptValue::Number(id) if *id > 0.0 => *id as u64,
+            _ => return None,
+        };
+        let ScriptValue::String(op) = fields.get("op")? else {
+            return None;
+        };
+        let string = |key: &str| match fields.get(key) {
+            Some(ScriptValue::String(s)) if !s.is_empty() => Some(s.clone()),
+            _ => None,
+        };
+        let qos = match fields.get("qos") {
+            Some(ScriptValue::Integer(qos)) => (*qos).clamp(0, 2) as u8,
+            Some(ScriptValue::Number(qos)) => qos.clamp(0.0, 2.0) as u8,
+            _ => 0,
+        };
+
+        match op.as_str() {
+            "connect" => {
+                let mut config = MqttConfig::from_url(&string("url")?)?;
+                if let Some(ScriptValue::Object(options)) = fields.get("options") {
+                    config.apply_options(options);
+                }
+                Some(MqttCommand::Connect { id, config })
+            }
+            "subscribe" => Some(MqttCommand::Subscribe { id, filter: string("filter")?, qos }),
+            "unsubscribe" => Some(MqttCommand::Unsubscribe { id, filter: string("filter")? }),
+            "publish" => {
+                let payload = match fields.get("payload") {
+                    Some(ScriptValue::Bytes(bytes)) => bytes.clone(),
+                    Some(ScriptValue::Nil) | None => Vec::new(),
+                    Some(other) => other.to_string().into_bytes(),
+                };
+                let retain = fields.get("retain").map(|v| v.to_bool()).unwrap_or(false);
+                Some(MqttCommand::Publish { id, topic: string("topic")?, payload, qos, retain })
+            }
+            "bind" => Some(MqttCommand::Bind { id, filter: string("filter")?, variable: string("variable")? }),
+            "disconnect" => Some(MqttCommand::Disconnect { id }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MqttEvent {
+    /// Connected (again, after a reconnect)
+    Connected { id: u64 },
+    /// `filters` lists the subscriptions the topic matched
+    Message { id: u64, topic: String, payload: Vec<u8>, filters: Vec<String> },
+    Error { id: u64, message: String },
+    /// The connection ended and will not reconnect
+    Disconnected { id: u64 },
+}
+
+impl MqttEvent {
+    /// Arguments for the bridge's `_deliver_mqtt_event(id, kind, value)`.
+    pub fn to_script_args(&self) -> Vec<ScriptValue> {
+        let (id, kind, value) = match self {
+            MqttEvent::Connected { id } => (id, "connect", ScriptValue::Nil),
+            MqttEvent::Message { id, topic, payload, filters } => {
+                let mut message = HashMap::new();
+                message.insert("topic".to_string(), ScriptValue::String(topic.clone()));
+                message.insert("payload".to_string(), payload_value(payload));
+                message.insert(
+                    "filters".to_string(),
+                    ScriptValue::Array(filters.iter().cloned().map(ScriptValue::String).collect()),
+                );
+                (id, "message", ScriptValue::Object(message))
+            }
+            MqttEvent::Error { id, message } => (id, "error", ScriptValue::String(message.clone())),
+            MqttEvent::Disconnected { id } => (id, "disconnect", ScriptValue::Nil),
+        };
+        vec![ScriptValue::Integer(*id as i64), ScriptValue::from(kind), value]
+    }
+}
+
+/// Payloads become strings when they are valid UTF-8.
+fn payload_value(payload: &[u8]) -> ScriptValue {
+    match std::str::from_utf8(payload) {
+        Ok(text) => ScriptValue::String(text.to_string()),
+        Err(_) => ScriptValue::Bytes(payload.to_vec()),
+    }
+}
+
+struct ConnectionState {
+    connection: Option<backend::Connection>,
+    subscriptions: Vec<(String, u8)>,
+    bindings: Vec<(String, String)>,
+    connected_before: bool,
+}
+
+/// Owns all MQTT connections and their subscriptions.
+pub struct MqttManager {
+    sender: Sender<MqttEvent>,
+    receiver: Receiver<MqttEvent>,
+    connections: HashMap<u64, ConnectionState>,
+}
+
+impl MqttManager {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver, connections: HashMap::new() }
+    }
+
+    pub fn handle(&mut self, command: MqttCommand) {
+        match command {
+            MqttCommand::Connect { id, config } => {
+                eprintln!("[MQTT] Connecting {} to {}:{}", id, config.host, config.port);
+                if let Some(previous) = self.connections.remove(&id).and_then(|state| state.connection) {
+                    previous.disconnect();
+                }
+                let connection = backend::connect(id, &config, self.sender.clone());
+                self.connections.insert(id, ConnectionState {
+                    connection,
+                    subscriptions: Vec::new(),
+                    bindings: Vec::new(),
+                    connected_before: false,
+                });
+            }
+            MqttCommand::Subscribe { id, filter, qos } => {
+                let Some(state) = self.connections.get_mut(&id) else {
+                    return self.report(id, "not connected");
+                };
+                state.subscriptions.retain(|(existing, _)| *existing != filter);
+                state.subscriptions.push((filter.clone(), qos));
+                if let Some(Err(e)) = state.connection.as_ref().map(|c| c.subscribe(&filter, qos)) {
+                    self.report(id, &e);
+                }
+            }
+            MqttCommand::Unsubscribe { id, filter } => {
+                if let Some(state) = self.connections.get_mut(&id) {
+                    state.subscriptions.retain(|(existing, _)| *existing != filter);
+                    // Keep the broker subscription while a binding still needs it
+                    if !state.bindings.iter().any(|(bound, _)| *bound == filter) {
+                        if let Some(connection) = &state.connection {
+                            let _ = connection.unsubscribe(&filter);
+                        }
+                    }
+                }
+            }
+            MqttCommand::Publish { id, topic, payload, qos, retain } => {
+                let result = match self.connections.get(&id).and_then(|state| state.connection.as_ref()) {
+                    Some(connection) => connection.publish(&topic, payload, qos, retain),
+                    None => Err("not connected".to_string()),
+                };
+                if let Err(e) = result {
+                    self.report(id, &e);
+                }
+            }
+            MqttCommand::Bind { id, filter, variable } => {
+                let Some(state) = self.connections.get_mut(&id) else {
+                    return self.report(id, "not connected");
+                };
+                if !state.subscriptions.iter().any(|(existing, _)| *existing == filter)
+                    && !state.bindings.iter().any(|(bound, _)| *bound == filter)
+                {
+                    if let Some(Err(e)) = state.connection.as_ref().map(|c| c.subscribe(&filter, 0)) {
+                        self.report(id, &e);
+                    }
+                }
+                if let Some(state) = self.connections.get_mut(&id) {
+                    state.bindings.push((filter, variable));
+                }
+            }
+            MqttCommand::Disconnect { id } => {
+                if let Some(connection) = self.connections.remove(&id).and_then(|state| state.connection) {
+                    connection.disconnect();
+                }
+            }
+        }
+    }
+
+    /// Connects to the broker declared on the App element, if any.
+    pub fn connect_declared(&mut self, root: &Element) {
+        let Some((config, bindings)) = MqttConfig::from_element(root) else {
+            return;
+        };
+        let qos = root.custom_properties.get("mqtt_qos").and_then(|v| v.as_float()).unwrap_or(0.0).clamp(0.0, 2.0) as u8;
+        self.handle(MqttCommand::Connect { id: DECLARED_CONNECTION_ID, config });
+        for (filter, variable) in bindings {
+            self.handle(MqttCommand::Subscribe { id: DECLARED_CONNECTION_ID, filter: filter.clone(), qos });
+            self.handle(MqttCommand::Bind { id: DECLARED_CONNECTION_ID, filter, variable });
+        }
+    }
+
+    fn report(&self, id: u64, message: &str) {
+        let _ = self.sender.send(MqttEvent::Error { id, message: message.to_string() });
+    }
+
+    /// Events since the last poll, with matching subscription filters filled in.
+    pub fn poll(&mut self) -> Vec<MqttEvent> {
+        let mut events: Vec<MqttEvent> = self.receiver.try_iter().collect();
+        for event in &mut events {
+            match event {
+                MqttEvent::Connected { id } => {
+                    let Some(state) = self.connections.get_mut(id) else { continue };
+                    // Requests queued before the first connect are sent by the
+                    // client; after a reconnect the broker may have forgotten them
+                    if state.connected_before {
+                        if let Some(connection) = &state.connection {
+                            for (filter, qos) in &state.subscriptions {
+                                let _ = connection.subscribe(filter, *qos);
+                            }
+                            for (filter, _) in &state.bindings {
+                                let _ = connection.subscribe(filter, 0);
+                            }
+                        }
+                    }
+                    state.connected_before = true;
+                }
+                MqttEvent::Message { id, topic, filters, .. } => {
+                    if let Some(state) = self.connections.get(id) {
+                        filters.extend(state.subscriptions.iter()
+                            .filter(|(filter, _)| topic_matches(filter, topic))
+                            .map(|(filter, _)| filter.clone()));
+                    }
+                }
+                MqttEvent::Disconnected { id } => {
+                    self.connections.remove(id);
+                }
+                MqttEvent::Error { .. } => {}
+            }
+        }
+        events
+    }
+
+    /// Template variable updates for a received message: `(variable, value)`
+    /// for every binding whose filter matches the topic.
+    pub fn bound_values(&self, event: &MqttEvent) -> Vec<(String, String)> {
+        let MqttEvent::Message { id, topic, payload, .. } = event else {
+            return Vec::new();
+        };
+        let Some(state) = self.connections.get(id) else {
+            return Vec::new();
+        };
+        let value = String::from_utf8_lossy(payload).trim().to_string();
+        state.bindings.iter()
+            .filter(|(filter, _)| topic_matches(filter, topic))
+            .map(|(_, variable)| (variable.clone(), value.clone()))
+            .collect()
+    }
+}
+
+impl Default for MqttManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MqttManager {
+    fn drop(&mut self) {
+        for (_, state) in self.connections.drain() {
+            if let Some(connection) = state.connection {
+                connection.disconnect();
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "mqtt", not(target_arch = "wasm32")))]
+mod backend {
+    use super::*;
+    use rumqttc::{Client, Event, MqttOptions, Outgoing, Packet, QoS, TlsConfiguration, Transport};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    const MAX_PACKET_SIZE: usize = 256 * 1024;
+
+    pub struct Connection {
+        client: Client,
+        closed: Arc<AtomicBool>,
+    }
+
+    fn qos(level: u8) -> QoS {
+        match level {
+            0 => QoS::AtMostOnce,
+            1 => QoS::AtLeastOnce,
+            _ => QoS::ExactlyOnce,
+        }
+    }
+
+    impl Connection {
+        pub fn subscribe(&self, filter: &str, level: u8) -> Result<(), String> {
+            self.client.try_subscribe(filter, qos(level)).map_err(|e| e.to_string())
+        }
+
+        pub fn unsubscribe(&self, filter: &str) -> Result<(), String> {
+            self.client.try_unsubscribe(filter).map_err(|e| e.to_string())
+        }
+
+        pub fn publish(&self, topic: &str, payload: Vec<u8>, level: u8, retain: bool) -> Result<(), String> {
+            self.client.try_publish(topic, qos(level), retain, payload).map_err(|e| e.to_string())
+        }
+
+        pub fn disconnect(&self) {
+            self.closed.store(true, Ordering::SeqCst);
+            let _ = self.client.try_disconnect();
+        }
+    }
+
+    fn read_pem(path: &Option<String>) -> Result<Option<Vec<u8>>, String> {
+        path.as_ref()
+            .map(|path| std::fs::read(path).map_err(|e| format!("failed to read {}: {}", path, e)))
+            .transpose()
+    }
+
+    fn transport(tls: &MqttTls) -> Result<Transport, String> {
+        let client_auth = match (read_pem(&tls.cert_file)?, read_pem(&tls.key_file)?) {
+            (Some(cert), Some(key)) => Some((cert, key)),
+            (None, None) => None,
+            _ => return Err("TLS client auth needs both cert_file and key_file".to_string()),
+        };
+        let config = match read_pem(&tls.ca_file)? {
+            Some(ca) => TlsConfiguration::Simple { ca, alpn: None, client_auth },
+            None if client_auth.is_some() => return Err("TLS client auth needs a ca_file".to_string()),
+            // Platform roots; loading them panics when the store is unreadable
+            None => std::panic::catch_unwind(TlsConfiguration::default)
+                .map_err(|_| "failed to load platform certificates".to_string())?,
+        };
+        Ok(Transport::tls_with_config(config))
+    }
+
+    pub fn connect(id: u64, config: &MqttConfig, events: Sender<MqttEvent>) -> Option<Connection> {
+        let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+        options.set_keep_alive(config.keep_alive.max(Duration::from_secs(5)));
+        options.set_clean_session(config.clean_session || config.client_id.is_empty());
+        options.set_max_packet_size(MAX_PACKET_SIZE, MAX_PACKET_SIZE);
+        if let Some((username, password)) = &config.credentials {
+            options.set_credentials(username, password);
+        }
+        if let Some(tls) = &config.tls {
+            match transport(tls) {
+                Ok(transport) => {
+                    options.set_transport(transport);
+                }
+                Err(message) => {
+                    let _ = events.send(MqttEvent::Error { id, message });
+                    let _ = events.send(MqttEvent::Disconnected { id });
+                    return None;
+                }
+            }
+        }
+
+        let (client, mut connection) = Client::new(options, 64);
+        let closed = Arc::new(AtomicBool::new(false));
+        let policy = config.reconnect;
+        let thread_closed = closed.clone();
+
+        std::thread::spawn(move || {
+            let mut attempt = 0;
+            while !thread_closed.load(Ordering::SeqCst) {
+                let result = match connection.recv_timeout(POLL_INTERVAL) {
+                    Ok(result) => result,
+                    Err(_) => continue, // nothing happened
+                };
+                let sent = match result {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        attempt = 0;
+                        events.send(MqttEvent::Connected { id })
+                    }
+                    Ok(Event::Incoming(Packet::Publish(publish))) => events.send(MqttEvent::Message {
+                        id,
+                        topic: publish.topic,
+                        payload: publish.payload.to_vec(),
+                        filters: Vec::new(),
+                    }),
+                    Ok(Event::Outgoing(Outgoing::Disconnect)) => break,
+                    Ok(_) => Ok(()),
+                    Err(e) => {
+                        let sent = events.send(MqttEvent::Error { id, message: e.to_string() });
+                        if !policy.enabled {
+                            break;
+                        }
+                        // Back off before the next poll reconnects, staying responsive to disconnect
+                        let deadline = std::time::Instant::now() + policy.delay(attempt);
+                        attempt = attempt.saturating_add(1);
+                        while std::time::Instant::now() < deadline && !thread_closed.load(Ordering::SeqCst) {
+                            std::thread::sleep(POLL_INTERVAL);
+                        }
+                        sent
+                    }
+                };
+                if sent.is_err() {
+                    return; // the app is gone
+                }
+            }
+            let _ = events.send(MqttEvent::Disconnected { id });
+        });
+
+        Some(Connection { client, closed })
+    }
+}
+
+#[cfg(not(all(feature = "mqtt", not(target_arch = "wasm32"))))]
+mod backend {
+    use super::*;
+
+    pub struct Connection;
+
+    impl Connection {
+        pub fn subscribe(&self, _filter: &str, _qos: u8) -> Result<(), String> { Ok(()) }
+        pub fn unsubscribe(&self, _filter: &str) -> Result<(), String> { Ok(()) }
+        pub fn publish(&self, _topic: &str, _payload: Vec<u8>, _qos: u8, _retain: bool) -> Result<(), String> { Ok(()) }
+        pub fn disconnect(&self) {}
+    }
+
+    pub fn connect(id: u64, _config: &MqttConfig, events: Sender<MqttEvent>) -> Option<Connection> {
+        let _ = events.send(MqttEvent::Error {
+            id,
+            message: "MQTT not available (built without the `mqtt` feature)".to_string(),
+        });
+        let _ = events.send(MqttEvent::Disconnected { id });
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_matching() {
+        assert!(topic_matches("plant/+/temperature", "plant/boiler/temperature"));
+        assert!(!topic_matches("plant/+/temperature", "plant/boiler/pressure"));
+        assert!(!topic_matches("plant/+", "plant/boiler/temperature"));
+        assert!(topic_matches("plant/#", "plant/boiler/temperature"));
+        assert!(topic_matches("plant/#", "plant"));
+        assert!(topic_matches("#", "anything/at/all"));
+        assert!(!topic_matches("#", "$SYS/broker/uptime"));
+        assert!(!topic_matches("+/broker/uptime", "$SYS/broker/uptime"));
+        assert!(topic_matches("$SYS/#", "$SYS/broker/uptime"));
+        assert!(!topic_matches("plant/status", "plant/status/extra"));
+    }
+
+    #[test]
+    fn parses_broker_urls() {
+        let plain = MqttConfig::from_url("mqtt://broker.local").unwrap();
+        assert_eq!((plain.host.as_str(), plain.port, plain.tls.is_some()), ("broker.local", 1883, false));
+
+        let secure = MqttConfig::from_url("mqtts://10.0.0.5:9883").unwrap();
+        assert_eq!((secure.host.as_str(), secure.port, secure.tls.is_some()), ("10.0.0.5", 9883, true));
+
+        assert!(MqttConfig::from_url("http://broker.local").is_none());
+        assert!(MqttConfig::from_url("mqtt://:1883").is_none());
+    }
+
+    #[test]
+    fn parses_commands_with_options() {
+        let mut tls = HashMap::new();
+        tls.insert("ca_file".to_string(), ScriptValue::from("ca.pem"));
+        let mut options = HashMap::new();
+        options.insert("client_id".to_string(), ScriptValue::from("panel-1"));
+        options.insert("username".to_string(), ScriptValue::from("hmi"));
+        options.insert("tls".to_string(), ScriptValue::Object(tls));
+        let mut fields = HashMap::new();
+        fields.insert("id".to_string(), ScriptValue::Integer(1));
+        fields.insert("op".to_string(), ScriptValue::from("connect"));
+        fields.insert("url".to_string(), ScriptValue::from("mqtt://broker"));
+        fields.insert("options".to_string(), ScriptValue::Object(options));
+
+        let Some(MqttCommand::Connect { config, .. }) = MqttCommand::from_script_value(&ScriptValue::Object(fields)) else {
+            panic!("expected connect");
+        };
+        assert_eq!(config.client_id, "panel-1");
+        assert_eq!(config.credentials, Some(("hmi".to_string(), String::new())));
+        assert_eq!(config.tls.unwrap().ca_file.as_deref(), Some("ca.pem"));
+
+        let mut fields = HashMap::new();
+        fields.insert("id".to_string(), ScriptValue::Integer(1));
+        fields.insert("op".to_string(), ScriptValue::from("publish"));
+        fields.insert("topic".to_string(), ScriptValue::from("plant/setpoint"));
+        fields.insert("payload".to_string(), ScriptValue::Number(21.5));
+        fields.insert("qos".to_string(), ScriptValue::Integer(7));
+        assert_eq!(
+            MqttCommand::from_script_value(&ScriptValue::Object(fields)),
+            Some(MqttCommand::Publish { id: 1, topic: "plant/setpoint".into(), payload: b"21.5".to_vec(), qos: 2, retain: false })
+        );
+    }
+
+    #[test]
+    fn messages_resolve_filters_and_bindings() {
+        let (sender, receiver) = channel();
+        let mut manager = MqttManager { sender: sender.clone(), receiver, connections: HashMap::new() };
+        manager.connections.insert(3, ConnectionState {
+            connection: None,
+            subscriptions: vec![("plant/#".to_string(), 1), ("other".to_string(), 0)],
+            bindings: vec![("plant/+/temperature".to_string(), "temperature".to_string())],
+            connected_before: true,
+        });
+
+        let _ = sender.send(MqttEvent::Message { id: 3, topic: "plant/boiler/temperature".into(), payload: b" 74.2\n".to_vec(), filters: Vec::new() });
+        let events = manager.poll();
+        let MqttEvent::Message { filters, .. } = &events[0] else { panic!("expected message") };
+        assert_eq!(filters, &vec!["plant/#".to_string()]);
+        assert_eq!(manager.bound_values(&events[0]), vec![("temperature".to_string(), "74.2".to_string())]);
+    }
+}
@@ -0,0 +1,507 @@
+// crates/kryon-runtime/src/net/websocket.rs
+//! WebSocket client behind `kryon.ws.connect(url)`.
+//!
+//! Native builds use tokio-tungstenite (with the `websocket` feature), web
+//! builds use the browser's WebSocket. Dropped connections are retried with
+//! exponential backoff until the script closes the socket.
+
+use crate::script::engine_trait::ScriptValue;
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsMessage {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl WsMessage {
+    pub fn to_script_value(&self) -> ScriptValue {
+        match self {
+            WsMessage::Text(text) => ScriptValue::String(text.clone()),
+            WsMessage::Binary(bytes) => ScriptValue::Bytes(bytes.clone()),
+        }
+    }
+
+    /// Strings are sent as text frames and byte strings as binary frames,
+    /// unless `binary` forces a binary frame.
+    pub fn from_script_value(value: &ScriptValue, binary: bool) -> Self {
+        match value {
+            ScriptValue::Bytes(bytes) => WsMessage::Binary(bytes.clone()),
+            ScriptValue::String(text) if binary => WsMessage::Binary(text.clone().into_bytes()),
+            other if binary => WsMessage::Binary(other.to_string().into_bytes()),
+            ScriptValue::String(text) => WsMessage::Text(text.clone()),
+            other => WsMessage::Text(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    pub enabled: bool,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before reconnect attempt `attempt` (0-based): doubling from
+    /// `initial_delay`, capped at `max_delay`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.min(16));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsCommand {
+    Connect { id: u64, url: String, reconnect: ReconnectPolicy },
+    Send { id: u64, message: WsMessage },
+    Close { id: u64 },
+}
+
+impl WsCommand {
+    /// Parses a command queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+
+        let id = match fields.get("id")? {
+            ScriptValue::Integer(id) if *id > 0 => *id as u64,
+            ScriptValue::Number(id) if *id > 0.0 => *id as u64,
+            _ => return None,
+        };
+        let ScriptValue::String(op) = fields.get("op")? else {
+            return None;
+        };
+
+        match op.as_str() {
+            "connect" => {
+                let url = match fields.get("url")? {
+                    ScriptValue::String(url) if url.starts_with("ws://") || url.starts_with("wss://") => url.clone(),
+                    _ => return None,
+                };
+                let mut reconnect = ReconnectPolicy::default();
+                if let Some(enabled) = fields.get("reconnect") {
+                    reconnect.enabled = enabled.to_bool();
+                }
+                match fields.get("max_backoff") {
+                    Some(ScriptValue::Integer(ms)) if *ms > 0 => reconnect.max_delay = Duration::from_millis(*ms as u64),
+                    Some(ScriptValue::Number(ms)) if *ms > 0.0 => reconnect.max_delay = Duration::from_millis(*ms as u64),
+                    _ => {}
+                }
+                Some(WsCommand::Connect { id, url, reconnect })
+            }
+            "send" => {
+                let binary = fields.get("binary").map(|v| v.to_bool()).unwrap_or(false);
+                let message = WsMessage::from_script_value(fields.get("data")?, binary);
+                Some(WsCommand::Send { id, message })
+            }
+            "close" => Some(WsCommand::Close { id }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsEvent {
+    Open { id: u64 },
+    Message { id: u64, message: WsMessage },
+    Error { id: u64, message: String },
+    /// `reconnecting` is true when another attempt is scheduled
+    Close { id: u64, code: Option<u16>, reason: String, reconnecting: bool },
+}
+
+impl WsEvent {
+    /// Arguments for the bridge's `_deliver_ws_event(id, kind, value, extra)`.
+    pub fn to_script_args(&self) -> Vec<ScriptValue> {
+        let (id, kind, value, extra) = match self {
+            WsEvent::Open { id } => (id, "open", ScriptValue::Nil, ScriptValue::Nil),
+            WsEvent::Message { id, message } => {
+                let binary = matches!(message, WsMessage::Binary(_));
+                (id, "message", message.to_script_value(), ScriptValue::Boolean(binary))
+            }
+            WsEvent::Error { id, message } => (id, "error", ScriptValue::String(message.clone()), ScriptValue::Nil),
+            WsEvent::Close { id, code, reason, reconnecting } => {
+                let mut info = HashMap::new();
+                info.insert("code".to_string(), code.map(|c| ScriptValue::Integer(c as i64)).unwrap_or(ScriptValue::Nil));
+                info.insert("reason".to_string(), ScriptValue::String(reason.clone()));
+                info.insert("reconnecting".to_string(), ScriptValue::Boolean(*reconnecting));
+                (id, "close", ScriptValue::Object(info), ScriptValue::Nil)
+            }
+        };
+        vec![ScriptValue::Integer(*id as i64), ScriptValue::from(kind), value, extra]
+    }
+}
+
+/// Owns the script's WebSocket connections.
+pub struct WebSocketManager {
+    sender: Sender<WsEvent>,
+    receiver: Receiver<WsEvent>,
+    connections: HashMap<u64, backend::Connection>,
+}
+
+impl WebSocketManager {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver, connections: HashMap::new() }
+    }
+
+    pub fn handle(&mut self, command: WsCommand) {
+        match command {
+            WsCommand::Connect { id, url, reconnect } => {
+                eprintln!("[WEBSOCKET] Connecting {} to {}", id, url);
+                if let Some(previous) = self.connections.remove(&id) {
+                    previous.close();
+                }
+                if let Some(connection) = backend::connect(id, url, reconnect, self.sender.clone()) {
+                    self.connections.insert(id, connection);
+                }
+            }
+            WsCommand::Send { id, message } => match self.connections.get(&id) {
+                Some(connection) => connection.send(message),
+                None => {
+                    let _ = self.sender.send(WsEvent::Error { id, message: "socket is not connected".to_string() });
+                }
+            },
+            WsCommand::Close { id } => {
+                if let Some(connection) = self.connections.remove(&id) {
+                    connection.close();
+                }
+            }
+        }
+    }
+
+    /// Events since the last poll.
+    pub fn poll(&mut self) -> Vec<WsEvent> {
+        let events: Vec<WsEvent> = self.receiver.try_iter().collect();
+        for event in &events {
+            if let WsEvent::Close { id, reconnecting: false, .. } = event {
+                self.connections.remove(id);
+            }
+        }
+        events
+    }
+}
+
+impl Default for WebSocketManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for WebSocketManager {
+    fn drop(&mut self) {
+        for (_, connection) in self.connections.drain() {
+            connection.close();
+        }
+    }
+}
+
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+mod backend {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+    use tokio_tungstenite::tungstenite::Message;
+
+    enum Outgoing {
+        Message(WsMessage),
+        Close,
+    }
+
+    pub struct Connection {
+        outgoing: UnboundedSender<Outgoing>,
+    }
+
+    impl Connection {
+        pub fn send(&self, message: WsMessage) {
+            let _ = self.outgoing.send(Outgoing::Message(message));
+        }
+
+        pub fn close(&self) {
+            let _ = self.outgoing.send(Outgoing::Close);
+        }
+    }
+
+    pub fn connect(id: u64, url: String, policy: ReconnectPolicy, events: Sender<WsEvent>) -> Option<Connection> {
+        let (outgoing, receiver) = unbounded_channel();
+        crate::net::runtime().spawn(run(id, url, policy, receiver, events));
+        Some(Connection { outgoing })
+    }
+
+    async fn run(id: u64, url: String, policy: ReconnectPolicy, mut outgoing: UnboundedReceiver<Outgoing>, events: Sender<WsEvent>) {
+        let mut attempt = 0;
+        loop {
+            let (code, reason) = match tokio_tungstenite::connect_async(url.as_str()).await {
+                Ok((stream, _)) => {
+                    attempt = 0;
+                    let _ = events.send(WsEvent::Open { id });
+                    let (mut write, mut read) = stream.split();
+
+                    loop {
+                        tokio::select! {
+                            incoming = read.next() => match incoming {
+                                Some(Ok(Message::Text(text))) => {
+                                    let _ = events.send(WsEvent::Message { id, message: WsMessage::Text(text) });
+                                }
+                                Some(Ok(Message::Binary(bytes))) => {
+                                    let _ = events.send(WsEvent::Message { id, message: WsMessage::Binary(bytes) });
+                                }
+                                Some(Ok(Message::Close(frame))) => {
+                                    break frame.map(|f| (Some(u16::from(f.code)), f.reason.into_owned())).unwrap_or((None, String::new()));
+                                }
+                                Some(Ok(_)) => {} // ping/pong handled by tungstenite
+                                Some(Err(e)) => break (None, e.to_string()),
+                                None => break (None, "connection closed".to_string()),
+                            },
+                            command = outgoing.recv() => match command {
+                                Some(Outgoing::Message(message)) => {
+                                    let frame = match message {
+                                        WsMessage::Text(text) => Message::Text(text),
+                                        WsMessage::Binary(bytes) => Message::Binary(bytes),
+                                    };
+                                    if let Err(e) = write.send(frame).await {
+                                        let _ = events.send(WsEvent::Error { id, message: e.to_string() });
+                                    }
+                                }
+                                Some(Outgoing::Close) | None => {
+                                    let _ = write.send(Message::Close(None)).await;
+                                    let _ = events.send(WsEvent::Close { id, code: Some(1000), reason: String::new(), reconnecting: false });
+                                    return;
+                                }
+                            },
+                        }
+                    }
+                }
+                Err(e) => (None, e.to_string()),
+            };
+
+            if !policy.enabled {
+                let _ = events.send(WsEvent::Close { id, code, reason, reconnecting: false });
+                return;
+            }
+            let _ = events.send(WsEvent::Close { id, code, reason, reconnecting: true });
+
+            // Back off, but give up immediately if the script closes the socket
+            let delay = policy.delay(attempt);
+            attempt = attempt.saturating_add(1);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                command = outgoing.recv() => match command {
+                    Some(Outgoing::Close) | None => {
+                        let _ = events.send(WsEvent::Close { id, code: None, reason: String::new(), reconnecting: false });
+                        return;
+                    }
+                    Some(Outgoing::Message(_)) => {
+                        let _ = events.send(WsEvent::Error { id, message: "message dropped: socket is reconnecting".to_string() });
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{BinaryType, CloseEvent, MessageEvent, WebSocket};
+
+    struct State {
+        socket: RefCell<Option<WebSocket>>,
+        closed: Cell<bool>,
+        attempt: Cell<u32>,
+    }
+
+    pub struct Connection {
+        state: Rc<State>,
+    }
+
+    impl Connection {
+        pub fn send(&self, message: WsMessage) {
+            let socket = self.state.socket.borrow();
+            let Some(socket) = socket.as_ref() else { return };
+            let _ = match message {
+                WsMessage::Text(text) => socket.send_with_str(&text),
+                WsMessage::Binary(bytes) => socket.send_with_u8_array(&bytes),
+            };
+        }
+
+        pub fn close(&self) {
+            self.state.closed.set(true);
+            if let Some(socket) = self.state.socket.borrow_mut().take() {
+                let _ = socket.close();
+            }
+        }
+    }
+
+    pub fn connect(id: u64, url: String, policy: ReconnectPolicy, events: Sender<WsEvent>) -> Option<Connection> {
+        let state = Rc::new(State { socket: RefCell::new(None), closed: Cell::new(false), attempt: Cell::new(0) });
+        open(id, Rc::new(url), policy, events, state.clone());
+        Some(Connection { state })
+    }
+
+    fn open(id: u64, url: Rc<String>, policy: ReconnectPolicy, events: Sender<WsEvent>, state: Rc<State>) {
+        let socket = match WebSocket::new(&url) {
+            Ok(socket) => socket,
+            Err(e) => {
+                let _ = events.send(WsEvent::Error { id, message: format!("{:?}", e) });
+                let _ = events.send(WsEvent::Close { id, code: None, reason: String::new(), reconnecting: false });
+                return;
+            }
+        };
+        socket.set_binary_type(BinaryType::Arraybuffer);
+
+        let on_open = {
+            let (events, state) = (events.clone(), state.clone());
+            Closure::<dyn FnMut()>::new(move || {
+                state.attempt.set(0);
+                let _ = events.send(WsEvent::Open { id });
+            })
+        };
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        on_open.forget();
+
+        let on_message = {
+            let events = events.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                let data = event.data();
+                let message = if let Some(text) = data.as_string() {
+                    WsMessage::Text(text)
+                } else {
+                    WsMessage::Binary(js_sys::Uint8Array::new(&data).to_vec())
+                };
+                let _ = events.send(WsEvent::Message { id, message });
+            })
+        };
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        on_message.forget();
+
+        let on_close = {
+            let (events, state, url) = (events.clone(), state.clone(), url.clone());
+            Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+                let reconnecting = policy.enabled && !state.closed.get();
+                let _ = events.send(WsEvent::Close { id, code: Some(event.code()), reason: event.reason(), reconnecting });
+                if !reconnecting {
+                    return;
+                }
+
+                let attempt = state.attempt.get();
+                state.attempt.set(attempt.saturating_add(1));
+                let (events, state, url) = (events.clone(), state.clone(), url.clone());
+                let retry = Closure::once_into_js(move || {
+                    if !state.closed.get() {
+                        open(id, url, policy, events, state);
+                    }
+                });
+                if let Some(window) = web_sys::window() {
+                    let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                        retry.unchecked_ref(),
+                        policy.delay(attempt).as_millis() as i32,
+                    );
+                }
+            })
+        };
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        on_close.forget();
+
+        *state.socket.borrow_mut() = Some(socket);
+    }
+}
+
+#[cfg(not(any(
+    all(feature = "websocket", not(target_arch = "wasm32")),
+    all(feature = "web", target_arch = "wasm32"),
+)))]
+mod backend {
+    use super::*;
+
+    pub struct Connection;
+
+    impl Connection {
+        pub fn send(&self, _message: WsMessage) {}
+        pub fn close(&self) {}
+    }
+
+    pub fn connect(id: u64, _url: String, _policy: ReconnectPolicy, events: Sender<WsEvent>) -> Option<Connection> {
+        let _ = events.send(WsEvent::Error {
+            id,
+            message: "WebSocket support not available (built without the `websocket` feature)".to_string(),
+        });
+        let _ = events.send(WsEvent::Close { id, code: None, reason: String::new(), reconnecting: false });
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(fields: Vec<(&str, ScriptValue)>) -> Option<WsCommand> {
+        let fields: HashMap<String, ScriptValue> = fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        WsCommand::from_script_value(&ScriptValue::Object(fields))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = ReconnectPolicy {
+            enabled: true,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(500));
+        assert_eq!(policy.delay(1), Duration::from_secs(1));
+        assert_eq!(policy.delay(3), Duration::from_secs(4));
+        assert_eq!(policy.delay(40), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn parses_connect_and_rejects_non_ws_urls() {
+        let connect = command(vec![
+            ("id", ScriptValue::Integer(1)),
+            ("op", "connect".into()),
+            ("url", "wss://example.com/feed".into()),
+            ("reconnect", false.into()),
+        ]);
+        let Some(WsCommand::Connect { url, reconnect, .. }) = connect else { panic!("expected connect") };
+        assert_eq!(url, "wss://example.com/feed");
+        assert!(!reconnect.enabled);
+
+        assert!(command(vec![("id", ScriptValue::Integer(1)), ("op", "connect".into()), ("url", "http://x".into())]).is_none());
+    }
+
+    #[test]
+    fn frames_map_to_script_values() {
+        assert_eq!(WsMessage::from_script_value(&"hi".into(), false), WsMessage::Text("hi".into()));
+        assert_eq!(WsMessage::from_script_value(&"hi".into(), true), WsMessage::Binary(b"hi".to_vec()));
+        assert_eq!(WsMessage::from_script_value(&ScriptValue::Bytes(vec![0xFF]), false), WsMessage::Binary(vec![0xFF]));
+        assert_eq!(WsMessage::from_script_value(&ScriptValue::Integer(3), false), WsMessage::Text("3".into()));
+
+        let args = WsEvent::Message { id: 2, message: WsMessage::Binary(vec![1]) }.to_script_args();
+        assert_eq!(args[2], ScriptValue::Bytes(vec![1]));
+        assert_eq!(args[3], ScriptValue::Boolean(true));
+    }
+
+    #[cfg(not(feature = "websocket"))]
+    #[test]
+    fn connect_without_backend_reports_close() {
+        let mut manager = WebSocketManager::new();
+        manager.handle(WsCommand::Connect { id: 1, url: "ws://localhost".into(), reconnect: ReconnectPolicy::default() });
+        let events = manager.poll();
+        assert!(matches!(events.last(), Some(WsEvent::Close { id: 1, reconnecting: false, .. })));
+    }
+}
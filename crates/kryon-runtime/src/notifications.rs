@@ -0,0 +1,388 @@
+// crates/kryon-runtime/src/notifications.rs
+//! OS notifications for scripts: `kryon.notify(title, body, {icon, timeout})`.
+//!
+//! Backends:
+//! - desktop (`notifications` feature): `notify-rust`, shown from a worker thread
+//! - web (`web` feature on wasm32): the Notification API, asking for permission
+//!   on first use
+//!
+//! When no backend is available, or the OS/browser refuses, the notification
+//! is shown as an in-app toast on the renderer's overlay layer instead.
+
+use crate::script::engine_trait::ScriptValue;
+use glam::{Vec2, Vec4};
+use kryon_core::TextAlignment;
+use kryon_render::RenderCommand;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+/// Toasts stay up this long unless the script passes `timeout`.
+pub const DEFAULT_TOAST_TIMEOUT: Duration = Duration::from_secs(5);
+
+const TOAST_WIDTH: f32 = 320.0;
+const TOAST_HEIGHT: f32 = 64.0;
+const TOAST_MARGIN: f32 = 16.0;
+const TOAST_SPACING: f32 = 8.0;
+const TOAST_FADE: f32 = 0.3;
+const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationRequest {
+    pub title: String,
+    pub body: String,
+    pub icon: Option<String>,
+    pub timeout: Option<Duration>,
+}
+
+impl NotificationRequest {
+    /// Parses `{title, body, options}` as queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+
+        let title = match fields.get("title")? {
+            ScriptValue::Nil => return None,
+            title => title.to_string(),
+        };
+        let body = match fields.get("body") {
+            Some(ScriptValue::Nil) | None => String::new(),
+            Some(body) => body.to_string(),
+        };
+
+        let (icon, timeout) = match fields.get("options") {
+            Some(ScriptValue::Object(options)) => {
+                let icon = match options.get("icon") {
+                    Some(ScriptValue::String(icon)) if !icon.is_empty() => Some(icon.clone()),
+                    _ => None,
+                };
+                // Timeout is given in milliseconds, like the web API
+                let timeout = match options.get("timeout") {
+                    Some(ScriptValue::Integer(ms)) if *ms > 0 => Some(Duration::from_millis(*ms as u64)),
+                    Some(ScriptValue::Number(ms)) if *ms > 0.0 => Some(Duration::from_secs_f64(ms / 1000.0)),
+                    _ => None,
+                };
+                (icon, timeout)
+            }
+            _ => (None, None),
+        };
+
+        Some(Self { title, body, icon, timeout })
+    }
+}
+
+/// What a backend reports back for a request.
+#[derive(Debug)]
+enum Outcome {
+    Shown,
+    /// Not delivered by the OS; show it as a toast instead
+    Fallback(NotificationRequest, String),
+}
+
+/// An in-app notification drawn on the overlay layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub title: String,
+    pub body: String,
+    pub remaining: f32,
+}
+
+impl Toast {
+    fn opacity(&self) -> f32 {
+        (self.remaining / TOAST_FADE).clamp(0.0, 1.0)
+    }
+}
+
+pub struct NotificationManager {
+    sender: Sender<Outcome>,
+    receiver: Receiver<Outcome>,
+    toasts: Vec<Toast>,
+}
+
+impl NotificationManager {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver, toasts: Vec::new() }
+    }
+
+    pub fn notify(&mut self, request: NotificationRequest) {
+        eprintln!("[NOTIFY] '{}'", request.title);
+        backend::show(request, self.sender.clone());
+    }
+
+    /// Shows `request` as a toast without trying the OS first.
+    pub fn push_toast(&mut self, request: &NotificationRequest) {
+        let timeout = request.timeout.unwrap_or(DEFAULT_TOAST_TIMEOUT);
+        self.toasts.push(Toast {
+            title: request.title.clone(),
+            body: request.body.clone(),
+            remaining: timeout.as_secs_f32(),
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    pub fn toasts(&self) -> &[Toast] {
+        &self.toasts
+    }
+
+    /// Collects backend outcomes and ages toasts. Returns true when the
+    /// overlay needs to be redrawn.
+    pub fn update(&mut self, delta_time: Duration) -> bool {
+        let outcomes: Vec<Outcome> = self.receiver.try_iter().collect();
+        let mut changed = false;
+        for outcome in outcomes {
+            if let Outcome::Fallback(request, reason) = outcome {
+                eprintln!("[NOTIFY] OS notification unavailable ({}), showing toast", reason);
+                self.push_toast(&request);
+                changed = true;
+            }
+        }
+
+        if self.toasts.is_empty() {
+            return changed;
+        }
+        let dt = delta_time.as_secs_f32();
+        for toast in &mut self.toasts {
+            toast.remaining -= dt;
+        }
+        self.toasts.retain(|toast| toast.remaining > 0.0);
+        true
+    }
+
+    /// How far the toasts reach up from the bottom-right corner
+    pub fn stack_height(&self) -> f32 {
+        self.toasts.len() as f32 * (TOAST_HEIGHT + TOAST_SPACING)
+    }
+
+    /// Toasts stacked in the bottom-right corner, newest at the bottom.
+    pub fn overlay_commands(&self, viewport_size: Vec2) -> Vec<RenderCommand> {
+        let mut commands = Vec::with_capacity(self.toasts.len() * 3);
+        let x = (viewport_size.x - TOAST_WIDTH - TOAST_MARGIN).max(0.0);
+        let mut y = viewport_size.y - TOAST_MARGIN - TOAST_HEIGHT;
+
+        for toast in self.toasts.iter().rev() {
+            let alpha = toast.opacity();
+            commands.push(RenderCommand::DrawRect {
+                position: Vec2::new(x, y),
+                size: Vec2::new(TOAST_WIDTH, TOAST_HEIGHT),
+                color: Vec4::new(0.12, 0.12, 0.14, 0.92 * alpha),
+                border_radius: 6.0,
+                border_width: 1.0,
+                border_color: Vec4::new(1.0, 1.0, 1.0, 0.15 * alpha),
+                transform: None,
+                shadow: None,
+                z_index: i32::MAX,
+            });
+            commands.push(overlay_text(&toast.title, Vec2::new(x + 12.0, y + 10.0), 15.0, Vec4::new(1.0, 1.0, 1.0, alpha)));
+            if !toast.body.is_empty() {
+                commands.push(overlay_text(&toast.body, Vec2::new(x + 12.0, y + 34.0), 13.0, Vec4::new(0.8, 0.8, 0.85, alpha)));
+            }
+            y -= TOAST_HEIGHT + TOAST_SPACING;
+        }
+
+        commands
+    }
+}
+
+impl Default for NotificationManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn overlay_text(text: &str, position: Vec2, font_size: f32, color: Vec4) -> RenderCommand {
+    RenderCommand::DrawText {
+        position,
+        text: text.to_string(),
+        font_size,
+        color,
+        alignment: TextAlignment::Start,
+        max_width: Some(TOAST_WIDTH - 24.0),
+        max_height: None,
+        transform: None,
+        font_family: None,
+        runs: Vec::new(),
+        z_index: i32::MAX,
+    }
+}
+
+#[cfg(all(feature = "notifications", not(target_arch = "wasm32")))]
+mod backend {
+    use super::*;
+
+    pub fn show(request: NotificationRequest, sender: Sender<Outcome>) {
+        // Talking to the notification daemon can block, keep it off the frame loop
+        std::thread::spawn(move || {
+            let mut notification = notify_rust::Notification::new();
+            notification.summary(&request.title).body(&request.body);
+            if let Some(icon) = &request.icon {
+                notification.icon(icon);
+            }
+            if let Some(timeout) = request.timeout {
+                notification.timeout(notify_rust::Timeout::Milliseconds(timeout.as_millis() as u32));
+            }
+
+            let outcome = match notification.show() {
+                Ok(_) => Outcome::Shown,
+                Err(e) => Outcome::Fallback(request, e.to_string()),
+            };
+            let _ = sender.send(outcome);
+        });
+    }
+}
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use web_sys::{Notification, NotificationOptions, NotificationPermission};
+
+    pub fn show(request: NotificationRequest, sender: Sender<Outcome>) {
+        // Missing in some embedded webviews
+        let supported = web_sys::window()
+            .map(|window| js_sys::Reflect::has(&window, &JsValue::from_str("Notification")).unwrap_or(false))
+            .unwrap_or(false);
+        if !supported {
+            let _ = sender.send(Outcome::Fallback(request, "Notification API not supported".to_string()));
+            return;
+        }
+
+        match Notification::permission() {
+            NotificationPermission::Granted => {
+                let _ = sender.send(create(request));
+            }
+            NotificationPermission::Denied => {
+                let _ = sender.send(Outcome::Fallback(request, "permission denied".to_string()));
+            }
+            _ => match Notification::request_permission() {
+                Ok(promise) => {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let granted = wasm_bindgen_futures::JsFuture::from(promise).await
+                            .ok()
+                            .and_then(|value| value.as_string())
+                            .map(|value| value == "granted")
+                            .unwrap_or(false);
+                        let outcome = if granted {
+                            create(request)
+                        } else {
+                            Outcome::Fallback(request, "permission not granted".to_string())
+                        };
+                        let _ = sender.send(outcome);
+                    });
+                }
+                Err(e) => {
+                    let _ = sender.send(Outcome::Fallback(request, format!("{:?}", e)));
+                }
+            },
+        }
+    }
+
+    fn create(request: NotificationRequest) -> Outcome {
+        let mut options = NotificationOptions::new();
+        options.body(&request.body);
+        if let Some(icon) = &request.icon {
+            options.icon(icon);
+        }
+
+        match Notification::new_with_options(&request.title, &options) {
+            Ok(notification) => {
+                if let Some(timeout) = request.timeout {
+                    let close = Closure::once_into_js(move || notification.close());
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                            close.unchecked_ref(),
+                            timeout.as_millis() as i32,
+                        );
+                    }
+                }
+                Outcome::Shown
+            }
+            Err(e) => Outcome::Fallback(request, format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(not(any(
+    all(feature = "notifications", not(target_arch = "wasm32")),
+    all(feature = "web", target_arch = "wasm32"),
+)))]
+mod backend {
+    use super::*;
+
+    pub fn show(request: NotificationRequest, sender: Sender<Outcome>) {
+        let _ = sender.send(Outcome::Fallback(request, "no notification backend enabled".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn request_value(options: Option<Vec<(&str, ScriptValue)>>) -> ScriptValue {
+        let mut fields = HashMap::new();
+        fields.insert("title".to_string(), ScriptValue::from("Build finished"));
+        fields.insert("body".to_string(), ScriptValue::from("All targets passed"));
+        if let Some(options) = options {
+            let options = options.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+            fields.insert("options".to_string(), ScriptValue::Object(options));
+        }
+        ScriptValue::Object(fields)
+    }
+
+    #[test]
+    fn parses_title_body_and_options() {
+        let value = request_value(Some(vec![
+            ("icon", "assets/icon.png".into()),
+            ("timeout", ScriptValue::Integer(2500)),
+        ]));
+        let request = NotificationRequest::from_script_value(&value).unwrap();
+        assert_eq!(request.title, "Build finished");
+        assert_eq!(request.body, "All targets passed");
+        assert_eq!(request.icon.as_deref(), Some("assets/icon.png"));
+        assert_eq!(request.timeout, Some(Duration::from_millis(2500)));
+    }
+
+    #[test]
+    fn requires_a_title() {
+        let mut fields = HashMap::new();
+        fields.insert("body".to_string(), ScriptValue::from("no title"));
+        assert!(NotificationRequest::from_script_value(&ScriptValue::Object(fields)).is_none());
+    }
+
+    #[test]
+    fn fallback_outcome_becomes_a_toast_that_expires() {
+        let mut manager = NotificationManager::new();
+        let mut request = NotificationRequest::from_script_value(&request_value(None)).unwrap();
+        request.timeout = Some(Duration::from_millis(500));
+        manager.sender.send(Outcome::Fallback(request, "test".to_string())).unwrap();
+
+        assert!(manager.update(Duration::ZERO));
+        assert_eq!(manager.toasts().len(), 1);
+
+        manager.update(Duration::from_millis(300));
+        assert_eq!(manager.toasts().len(), 1);
+        manager.update(Duration::from_millis(300));
+        assert!(manager.toasts().is_empty());
+    }
+
+    #[test]
+    fn toasts_stack_upwards_from_the_bottom_right() {
+        let mut manager = NotificationManager::new();
+        let request = NotificationRequest::from_script_value(&request_value(None)).unwrap();
+        manager.push_toast(&request);
+        manager.push_toast(&request);
+
+        let rects: Vec<Vec2> = manager.overlay_commands(Vec2::new(800.0, 600.0)).iter()
+            .filter_map(|cmd| match cmd {
+                RenderCommand::DrawRect { position, .. } => Some(*position),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[0], Vec2::new(800.0 - TOAST_WIDTH - TOAST_MARGIN, 600.0 - TOAST_MARGIN - TOAST_HEIGHT));
+        assert!(rects[1].y < rects[0].y);
+    }
+}
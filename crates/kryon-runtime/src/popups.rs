@@ -0,0 +1,402 @@
+// crates/kryon-runtime/src/popups.rs
+//! Anchored popups: tooltips, hover cards and dropdowns.
+//!
+//! Any element with a `popup_anchor` property is a popup. It stays hidden
+//! until its trigger opens it, and is then drawn above everything else, next
+//! to the anchor element:
+//!
+//! - `popup_anchor`: `id` of the anchor element
+//! - `popup_placement`: `bottom` (default), `top`, `start`, `end`, or
+//!   `cursor` to follow the pointer like a tooltip
+//! - `popup_trigger`: `hover` (default) opens while the pointer is over the
+//!   anchor or the popup, `click` toggles on anchor clicks and closes on
+//!   clicks elsewhere, `manual` leaves it to scripts
+//! - `popup_offset`: gap to the anchor in pixels (default 4)
+//!
+//! A popup that doesn't fit on its side is flipped to the opposite side if
+//! that fits better, then shifted along the anchor's edge to stay inside the
+//! viewport. Scripts use `kryon.popup.open(id)`, `kryon.popup.close(id)` and
+//! `kryon.popup.toggle(id)`.
+
+use crate::script::engine_trait::ScriptValue;
+use glam::Vec2;
+use kryon_core::{Element, ElementId, PropertyValue};
+use std::collections::HashMap;
+
+/// Added to the z-index of popup subtrees so they draw and hit-test above
+/// the rest of the app.
+pub const POPUP_Z_INDEX: i32 = 10_000;
+
+const DEFAULT_OFFSET: f32 = 4.0;
+/// Distance from the pointer to a `cursor`-placed popup
+const CURSOR_OFFSET: Vec2 = Vec2::new(12.0, 16.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    Top,
+    Bottom,
+    /// Left of the anchor
+    Start,
+    /// Right of the anchor
+    End,
+    Cursor,
+}
+
+impl Placement {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "top" => Some(Placement::Top),
+            "bottom" => Some(Placement::Bottom),
+            "start" | "left" => Some(Placement::Start),
+            "end" | "right" => Some(Placement::End),
+            "cursor" => Some(Placement::Cursor),
+            _ => None,
+        }
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Placement::Top => Placement::Bottom,
+            Placement::Bottom => Placement::Top,
+            Placement::Start => Placement::End,
+            Placement::End => Placement::Start,
+            Placement::Cursor => Placement::Cursor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupTrigger {
+    Hover,
+    Click,
+    Manual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+/// Where a popup of `size` goes next to `anchor`, and the side it ended up
+/// on after flipping. For `Placement::Cursor` the anchor is the pointer.
+pub fn place(anchor: Rect, size: Vec2, viewport: Vec2, placement: Placement, offset: f32) -> (Vec2, Placement) {
+    let position_on = |placement: Placement| {
+        let center = anchor.position + (anchor.size - size) / 2.0;
+        match placement {
+            Placement::Top => Vec2::new(center.x, anchor.position.y - size.y - offset),
+            Placement::Bottom => Vec2::new(center.x, anchor.position.y + anchor.size.y + offset),
+            Placement::Start => Vec2::new(anchor.position.x - size.x - offset, center.y),
+            Placement::End => Vec2::new(anchor.position.x + anchor.size.x + offset, center.y),
+            Placement::Cursor => anchor.position + CURSOR_OFFSET,
+        }
+    };
+    // How far the popup sticks out of the viewport along the placement axis
+    let overflow = |placement: Placement, position: Vec2| {
+        let (start, end, limit) = match placement {
+            Placement::Top | Placement::Bottom => (position.y, position.y + size.y, viewport.y),
+            Placement::Start | Placement::End => (position.x, position.x + size.x, viewport.x),
+            Placement::Cursor => return 0.0,
+        };
+        (-start).max(0.0) + (end - limit).max(0.0)
+    };
+
+    let mut side = placement;
+    let mut position = position_on(side);
+    let current_overflow = overflow(side, position);
+    if current_overflow > 0.0 {
+        let flipped = position_on(side.opposite());
+        if overflow(side.opposite(), flipped) < current_overflow {
+            side = side.opposite();
+            position = flipped;
+        }
+    }
+    if placement == Placement::Cursor {
+        // Beside the pointer: flip to the other side of it on each axis
+        if position.x + size.x > viewport.x {
+            position.x = anchor.position.x - CURSOR_OFFSET.x - size.x;
+        }
+        if position.y + size.y > viewport.y {
+            position.y = anchor.position.y - CURSOR_OFFSET.y / 2.0 - size.y;
+        }
+    }
+
+    // Shift back inside the viewport; the top-left corner wins if it's too big
+    let max = (viewport - size).max(Vec2::ZERO);
+    (position.clamp(Vec2::ZERO, max), side)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Popup {
+    pub element: ElementId,
+    pub anchor: ElementId,
+    pub placement: Placement,
+    pub trigger: PopupTrigger,
+    pub offset: f32,
+    pub open: bool,
+}
+
+/// `kryon.popup.*` calls queued by the script bridge
+#[derive(Debug, Clone, PartialEq)]
+pub enum PopupCommand {
+    Open(String),
+    Close(String),
+    Toggle(String),
+}
+
+impl PopupCommand {
+    /// Parses `{op, id}` as queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+        let Some(ScriptValue::String(id)) = fields.get("id") else {
+            return None;
+        };
+        match fields.get("op")? {
+            ScriptValue::String(op) if op == "open" => Some(PopupCommand::Open(id.clone())),
+            ScriptValue::String(op) if op == "close" => Some(PopupCommand::Close(id.clone())),
+            ScriptValue::String(op) if op == "toggle" => Some(PopupCommand::Toggle(id.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PopupManager {
+    popups: Vec<Popup>,
+    pointer: Vec2,
+}
+
+impl PopupManager {
+    /// Finds popup elements, hides them and raises their subtrees above the
+    /// rest of the app.
+    pub fn from_elements(elements: &mut HashMap<ElementId, Element>) -> Self {
+        let string = |element: &Element, name: &str| match element.custom_properties.get(name) {
+            Some(PropertyValue::String(value)) => Some(value.clone()),
+            _ => None,
+        };
+
+        let mut popups = Vec::new();
+        for (&element_id, element) in elements.iter() {
+            let Some(anchor_id) = string(element, "popup_anchor") else {
+                continue;
+            };
+            let Some(anchor) = elements.iter().find(|(_, e)| e.id == anchor_id).map(|(&id, _)| id) else {
+                eprintln!("[POPUP] Popup '{}' is anchored to unknown element '{}'", element.id, anchor_id);
+                continue;
+            };
+            let placement = string(element, "popup_placement").as_deref().and_then(Placement::parse).unwrap_or(Placement::Bottom);
+            let trigger = match string(element, "popup_trigger").as_deref() {
+                Some("click") => PopupTrigger::Click,
+                Some("manual") => PopupTrigger::Manual,
+                _ => PopupTrigger::Hover,
+            };
+            let offset = element.custom_properties.get("popup_offset").and_then(|v| v.as_float()).unwrap_or(DEFAULT_OFFSET);
+            popups.push(Popup { element: element_id, anchor, placement, trigger, offset, open: false });
+        }
+        popups.sort_by_key(|popup| popup.element);
+
+        for popup in &popups {
+            for id in subtree(elements, popup.element) {
+                if let Some(element) = elements.get_mut(&id) {
+                    element.z_index += POPUP_Z_INDEX;
+                }
+            }
+            if let Some(element) = elements.get_mut(&popup.element) {
+                element.visible = false;
+            }
+        }
+        Self { popups, pointer: Vec2::ZERO }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.popups.is_empty()
+    }
+
+    pub fn popups(&self) -> &[Popup] {
+        &self.popups
+    }
+
+    pub fn is_open(&self, element: ElementId) -> bool {
+        self.popups.iter().any(|popup| popup.element == element && popup.open)
+    }
+
+    /// Returns whether anything changed.
+    pub fn set_open(&mut self, element: ElementId, open: bool) -> bool {
+        match self.popups.iter_mut().find(|popup| popup.element == element) {
+            Some(popup) if popup.open != open => {
+                popup.open = open;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Opens hover popups whose anchor or popup is on `hover_path` and
+    /// closes the others. Returns whether anything changed, including a
+    /// cursor-placed popup that has to follow the pointer.
+    pub fn update_hover(&mut self, hover_path: &[ElementId], pointer: Vec2) -> bool {
+        let moved = self.pointer != pointer;
+        self.pointer = pointer;
+        let mut changed = false;
+        for popup in self.popups.iter_mut().filter(|popup| popup.trigger == PopupTrigger::Hover) {
+            let open = hover_path.contains(&popup.anchor) || hover_path.contains(&popup.element);
+            changed |= popup.open != open || (open && moved && popup.placement == Placement::Cursor);
+            popup.open = open;
+        }
+        changed
+    }
+
+    /// Toggles click popups anchored on `click_path` and closes open ones
+    /// clicked outside of. Returns whether anything changed.
+    pub fn handle_click(&mut self, click_path: &[ElementId]) -> bool {
+        let mut changed = false;
+        for popup in self.popups.iter_mut().filter(|popup| popup.trigger == PopupTrigger::Click) {
+            let open = if click_path.contains(&popup.anchor) {
+                !popup.open
+            } else {
+                popup.open && click_path.contains(&popup.element)
+            };
+            changed |= popup.open != open;
+            popup.open = open;
+        }
+        changed
+    }
+
+    /// How far to move each open popup's subtree from where layout put it.
+    pub fn offsets(&self, positions: &HashMap<ElementId, Vec2>, sizes: &HashMap<ElementId, Vec2>, viewport: Vec2) -> Vec<(ElementId, Vec2)> {
+        self.popups.iter()
+            .filter(|popup| popup.open)
+            .filter_map(|popup| {
+                let current = *positions.get(&popup.element)?;
+                let size = sizes.get(&popup.element).copied().unwrap_or(Vec2::ZERO);
+                let anchor = match popup.placement {
+                    Placement::Cursor => Rect { position: self.pointer, size: Vec2::ZERO },
+                    _ => Rect {
+                        position: *positions.get(&popup.anchor)?,
+                        size: sizes.get(&popup.anchor).copied().unwrap_or(Vec2::ZERO),
+                    },
+                };
+                let (position, _) = place(anchor, size, viewport, popup.placement, popup.offset);
+                Some((popup.element, position - current))
+            })
+            .collect()
+    }
+}
+
+/// `root` and all its descendants
+pub fn subtree(elements: &HashMap<ElementId, Element>, root: ElementId) -> Vec<ElementId> {
+    let mut ids = vec![root];
+    let mut index = 0;
+    while index < ids.len() {
+        if let Some(element) = elements.get(&ids[index]) {
+            for &child in &element.children {
+                if !ids.contains(&child) {
+                    ids.push(child);
+                }
+            }
+        }
+        index += 1;
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn anchor(x: f32, y: f32) -> Rect {
+        Rect { position: Vec2::new(x, y), size: Vec2::new(100.0, 40.0) }
+    }
+
+    #[test]
+    fn places_on_the_requested_side_centered_on_the_anchor() {
+        let size = Vec2::new(200.0, 80.0);
+        let (position, side) = place(anchor(300.0, 200.0), size, VIEWPORT, Placement::Bottom, 4.0);
+        assert_eq!((position, side), (Vec2::new(250.0, 244.0), Placement::Bottom));
+        let (position, _) = place(anchor(300.0, 200.0), size, VIEWPORT, Placement::End, 4.0);
+        assert_eq!(position, Vec2::new(404.0, 180.0));
+    }
+
+    #[test]
+    fn flips_when_the_other_side_fits_and_shifts_into_view() {
+        let size = Vec2::new(200.0, 80.0);
+        // No room below an anchor at the bottom edge
+        let (position, side) = place(anchor(10.0, 550.0), size, VIEWPORT, Placement::Bottom, 4.0);
+        assert_eq!(side, Placement::Top);
+        assert_eq!(position, Vec2::new(0.0, 466.0), "shifted right to the viewport edge");
+
+        // Neither side fits: keep the requested side and clamp
+        let tall = Vec2::new(100.0, 590.0);
+        let (position, side) = place(anchor(300.0, 280.0), tall, VIEWPORT, Placement::Top, 4.0);
+        assert_eq!(side, Placement::Top);
+        assert_eq!(position.y, 0.0);
+    }
+
+    #[test]
+    fn cursor_popups_stay_beside_the_pointer() {
+        let pointer = Rect { position: Vec2::new(100.0, 100.0), size: Vec2::ZERO };
+        let size = Vec2::new(120.0, 30.0);
+        assert_eq!(place(pointer, size, VIEWPORT, Placement::Cursor, 4.0).0, Vec2::new(112.0, 116.0));
+        let corner = Rect { position: Vec2::new(790.0, 590.0), size: Vec2::ZERO };
+        assert_eq!(place(corner, size, VIEWPORT, Placement::Cursor, 4.0).0, Vec2::new(658.0, 552.0));
+    }
+
+    fn app_with_popup(trigger: &str) -> (HashMap<ElementId, Element>, PopupManager) {
+        let mut card = Element { id: "card".to_string(), children: vec![3], ..Default::default() };
+        card.custom_properties.insert("popup_anchor".to_string(), PropertyValue::String("avatar".to_string()));
+        card.custom_properties.insert("popup_trigger".to_string(), PropertyValue::String(trigger.to_string()));
+        let mut elements: HashMap<ElementId, Element> = [
+            (0, Element { children: vec![1, 2], ..Default::default() }),
+            (1, Element { id: "avatar".to_string(), parent: Some(0), ..Default::default() }),
+            (2, Element { parent: Some(0), ..card }),
+            (3, Element { parent: Some(2), ..Default::default() }),
+        ].into_iter().collect();
+        let popups = PopupManager::from_elements(&mut elements);
+        (elements, popups)
+    }
+
+    #[test]
+    fn hover_popups_open_over_the_anchor_or_the_popup() {
+        let (elements, mut popups) = app_with_popup("hover");
+        assert!(!elements[&2].visible);
+        assert_eq!(elements[&3].z_index, POPUP_Z_INDEX);
+        assert_eq!(popups.popups()[0].anchor, 1);
+
+        assert!(popups.update_hover(&[1, 0], Vec2::ZERO));
+        assert!(popups.is_open(2));
+        assert!(!popups.update_hover(&[3, 2, 0], Vec2::ZERO), "moving onto the card keeps it open");
+        assert!(popups.update_hover(&[0], Vec2::ZERO));
+        assert!(!popups.is_open(2));
+    }
+
+    #[test]
+    fn click_popups_toggle_on_the_anchor_and_close_outside() {
+        let (_, mut popups) = app_with_popup("click");
+        assert!(!popups.update_hover(&[1, 0], Vec2::ZERO));
+        assert!(popups.handle_click(&[1, 0]));
+        assert!(popups.is_open(2));
+        assert!(!popups.handle_click(&[3, 2, 0]), "clicks inside keep it open");
+        assert!(popups.handle_click(&[0]));
+        assert!(!popups.is_open(2));
+        popups.handle_click(&[1, 0]);
+        popups.handle_click(&[1, 0]);
+        assert!(!popups.is_open(2));
+    }
+
+    #[test]
+    fn parses_script_commands() {
+        let command = |op: &str| {
+            let mut fields = HashMap::new();
+            fields.insert("op".to_string(), ScriptValue::from(op));
+            fields.insert("id".to_string(), ScriptValue::from("card"));
+            PopupCommand::from_script_value(&ScriptValue::Object(fields))
+        };
+        assert_eq!(command("open"), Some(PopupCommand::Open("card".to_string())));
+        assert_eq!(command("toggle"), Some(PopupCommand::Toggle("card".to_string())));
+        assert_eq!(command("explode"), None);
+    }
+}
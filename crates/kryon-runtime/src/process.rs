@@ -0,0 +1,328 @@
+// crates/kryon-runtime/src/process.rs
+//! Local process execution for scripts (`kryon.process.*`).
+//!
+//! Only compiled in with the `process` feature, and even then every program
+//! must be on the app's [`ProcessPolicy`] allowlist. The default policy allows
+//! nothing, so KRB content cannot run commands unless the host opts in.
+//!
+//! Processes run on worker threads; stdout/stderr lines and the exit code are
+//! delivered back to the script as [`ProcessEvent`]s on later updates.
+
+use crate::script::engine_trait::ScriptValue;
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// Which programs scripts may start.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessPolicy {
+    /// Program names or paths, matched exactly; `"*"` allows anything
+    pub allowed: Vec<String>,
+}
+
+impl ProcessPolicy {
+    /// A policy that rejects every program (the default).
+    pub fn deny_all() -> Self {
+        Self::default()
+    }
+
+    pub fn allow<I, S>(programs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self { allowed: programs.into_iter().map(Into::into).collect() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.allowed.is_empty()
+    }
+
+    pub fn permits(&self, program: &str) -> bool {
+        self.allowed.iter().any(|allowed| allowed == "*" || allowed == program)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessRequest {
+    /// Script-side id the events are delivered to
+    pub id: i64,
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    pub env: Vec<(String, String)>,
+    /// Written to the child's stdin, which is then closed
+    pub stdin: Option<Vec<u8>>,
+}
+
+impl ProcessRequest {
+    /// Parses a request queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+
+        let id = match fields.get("id")? {
+            ScriptValue::Integer(id) => *id,
+            ScriptValue::Number(id) => *id as i64,
+            _ => return None,
+        };
+        let program = match fields.get("program")? {
+            ScriptValue::String(program) if !program.is_empty() => program.clone(),
+            _ => return None,
+        };
+        let args = match fields.get("args") {
+            Some(ScriptValue::Array(args)) => args.iter().map(|arg| arg.to_string()).collect(),
+            _ => Vec::new(),
+        };
+        let cwd = match fields.get("cwd") {
+            Some(ScriptValue::String(cwd)) if !cwd.is_empty() => Some(cwd.clone()),
+            _ => None,
+        };
+        let mut env: Vec<(String, String)> = match fields.get("env") {
+            Some(ScriptValue::Object(env)) => env.iter().map(|(k, v)| (k.clone(), v.to_string())).collect(),
+            _ => Vec::new(),
+        };
+        env.sort();
+        let stdin = match fields.get("stdin") {
+            Some(ScriptValue::String(text)) => Some(text.clone().into_bytes()),
+            Some(ScriptValue::Bytes(bytes)) => Some(bytes.clone()),
+            _ => None,
+        };
+
+        Some(Self { id, program, args, cwd, env, stdin })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessEvent {
+    Stdout { id: i64, line: String },
+    Stderr { id: i64, line: String },
+    /// The process finished; `code` is None if it was killed by a signal
+    Exit { id: i64, code: Option<i32> },
+    /// The process could not be started
+    Error { id: i64, message: String },
+}
+
+impl ProcessEvent {
+    /// Arguments for the bridge's `_deliver_process_event(id, kind, value)`.
+    pub fn to_script_args(&self) -> Vec<ScriptValue> {
+        let (id, kind, value) = match self {
+            ProcessEvent::Stdout { id, line } => (id, "stdout", ScriptValue::String(line.clone())),
+            ProcessEvent::Stderr { id, line } => (id, "stderr", ScriptValue::String(line.clone())),
+            ProcessEvent::Exit { id, code } => (id, "exit", code.map(|c| ScriptValue::Integer(c as i64)).unwrap_or(ScriptValue::Nil)),
+            ProcessEvent::Error { id, message } => (id, "error", ScriptValue::String(message.clone())),
+        };
+        vec![ScriptValue::Integer(*id), ScriptValue::from(kind), value]
+    }
+}
+
+/// Starts allowed processes and collects their output.
+pub struct ProcessManager {
+    policy: ProcessPolicy,
+    sender: Sender<ProcessEvent>,
+    receiver: Receiver<ProcessEvent>,
+    running: HashSet<i64>,
+}
+
+impl ProcessManager {
+    pub fn new(policy: ProcessPolicy) -> Self {
+        let (sender, receiver) = channel();
+        Self { policy, sender, receiver, running: HashSet::new() }
+    }
+
+    pub fn policy(&self) -> &ProcessPolicy {
+        &self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: ProcessPolicy) {
+        self.policy = policy;
+    }
+
+    /// Number of processes that have not exited yet.
+    pub fn running_count(&self) -> usize {
+        self.running.len()
+    }
+
+    pub fn spawn(&mut self, request: ProcessRequest) {
+        let id = request.id;
+        if !cfg!(feature = "process") {
+            let _ = self.sender.send(ProcessEvent::Error {
+                id,
+                message: "process API not available (built without the `process` feature)".to_string(),
+            });
+            return;
+        }
+        if !self.policy.permits(&request.program) {
+            eprintln!("[PROCESS] Refusing to run '{}': not in the allowlist", request.program);
+            let _ = self.sender.send(ProcessEvent::Error {
+                id,
+                message: format!("'{}' is not in the process allowlist", request.program),
+            });
+            return;
+        }
+
+        eprintln!("[PROCESS] Spawning {} {:?}", request.program, request.args);
+        match backend::spawn(request, self.sender.clone()) {
+            Ok(()) => {
+                self.running.insert(id);
+            }
+            Err(message) => {
+                let _ = self.sender.send(ProcessEvent::Error { id, message });
+            }
+        }
+    }
+
+    /// Events received since the last poll, in order per process.
+    pub fn poll(&mut self) -> Vec<ProcessEvent> {
+        let events: Vec<ProcessEvent> = self.receiver.try_iter().collect();
+        for event in &events {
+            if let ProcessEvent::Exit { id, .. } | ProcessEvent::Error { id, .. } = event {
+                self.running.remove(id);
+            }
+        }
+        events
+    }
+}
+
+impl Default for ProcessManager {
+    fn default() -> Self {
+        Self::new(ProcessPolicy::deny_all())
+    }
+}
+
+#[cfg(all(feature = "process", not(target_arch = "wasm32")))]
+mod backend {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::process::{Command, Stdio};
+
+    pub fn spawn(request: ProcessRequest, sender: Sender<ProcessEvent>) -> Result<(), String> {
+        let mut command = Command::new(&request.program);
+        command.args(&request.args)
+            .envs(request.env.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .stdin(if request.stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(cwd) = &request.cwd {
+            command.current_dir(cwd);
+        }
+
+        let mut child = command.spawn()
+            .map_err(|e| format!("Failed to start '{}': {}", request.program, e))?;
+        let id = request.id;
+
+        if let (Some(mut stdin), Some(data)) = (child.stdin.take(), request.stdin) {
+            std::thread::spawn(move || {
+                let _ = stdin.write_all(&data);
+            });
+        }
+
+        let stdout = child.stdout.take().map(|out| forward_lines(out, sender.clone(), move |line| ProcessEvent::Stdout { id, line }));
+        let stderr = child.stderr.take().map(|err| forward_lines(err, sender.clone(), move |line| ProcessEvent::Stderr { id, line }));
+
+        std::thread::spawn(move || {
+            // Drain output first so Exit is always the last event for this id
+            for reader in [stdout, stderr].into_iter().flatten() {
+                let _ = reader.join();
+            }
+            let event = match child.wait() {
+                Ok(status) => ProcessEvent::Exit { id, code: status.code() },
+                Err(e) => ProcessEvent::Error { id, message: e.to_string() },
+            };
+            let _ = sender.send(event);
+        });
+
+        Ok(())
+    }
+
+    fn forward_lines<T, F>(stream: T, sender: Sender<ProcessEvent>, make_event: F) -> std::thread::JoinHandle<()>
+    where
+        T: Read + Send + 'static,
+        F: Fn(String) -> ProcessEvent + Send + 'static,
+    {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(make_event(line)).is_err() {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(not(all(feature = "process", not(target_arch = "wasm32"))))]
+mod backend {
+    use super::*;
+
+    pub fn spawn(_request: ProcessRequest, _sender: Sender<ProcessEvent>) -> Result<(), String> {
+        Err("process API is not supported on this platform".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn request(program: &str) -> ProcessRequest {
+        ProcessRequest { id: 1, program: program.to_string(), args: Vec::new(), cwd: None, env: Vec::new(), stdin: None }
+    }
+
+    #[test]
+    fn default_policy_denies_everything() {
+        let policy = ProcessPolicy::default();
+        assert!(!policy.is_enabled());
+        assert!(!policy.permits("echo"));
+
+        let mut manager = ProcessManager::default();
+        manager.spawn(request("echo"));
+        assert!(matches!(manager.poll().as_slice(), [ProcessEvent::Error { id: 1, .. }]));
+        assert_eq!(manager.running_count(), 0);
+    }
+
+    #[test]
+    fn allowlist_matches_programs_exactly() {
+        let policy = ProcessPolicy::allow(["lp", "/usr/local/bin/sensor"]);
+        assert!(policy.permits("lp"));
+        assert!(policy.permits("/usr/local/bin/sensor"));
+        assert!(!policy.permits("sensor"));
+        assert!(!policy.permits("/usr/bin/lp"));
+        assert!(ProcessPolicy::allow(["*"]).permits("anything"));
+    }
+
+    #[test]
+    fn parses_script_request() {
+        let mut fields = HashMap::new();
+        fields.insert("id".to_string(), ScriptValue::Integer(4));
+        fields.insert("program".to_string(), ScriptValue::from("lp"));
+        fields.insert("args".to_string(), ScriptValue::Array(vec!["-d".into(), ScriptValue::Integer(2)]));
+        fields.insert("stdin".to_string(), ScriptValue::from("ticket"));
+
+        let request = ProcessRequest::from_script_value(&ScriptValue::Object(fields)).unwrap();
+        assert_eq!(request.id, 4);
+        assert_eq!(request.args, vec!["-d", "2"]);
+        assert_eq!(request.stdin.as_deref(), Some(&b"ticket"[..]));
+        assert!(ProcessRequest::from_script_value(&ScriptValue::Nil).is_none());
+    }
+
+    #[cfg(all(feature = "process", unix))]
+    #[test]
+    fn captures_output_before_exit() {
+        let mut manager = ProcessManager::new(ProcessPolicy::allow(["sh"]));
+        let mut req = request("sh");
+        req.args = vec!["-c".into(), "echo one; echo two >&2; exit 3".into()];
+        manager.spawn(req);
+
+        let mut events = Vec::new();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while manager.running_count() > 0 && std::time::Instant::now() < deadline {
+            events.extend(manager.poll());
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert!(events.contains(&ProcessEvent::Stdout { id: 1, line: "one".into() }));
+        assert!(events.contains(&ProcessEvent::Stderr { id: 1, line: "two".into() }));
+        assert_eq!(events.last(), Some(&ProcessEvent::Exit { id: 1, code: Some(3) }));
+    }
+}
@@ -0,0 +1,82 @@
+// crates/kryon-runtime/src/profiler.rs
+//! Profiler panel drawn in the top-left corner of the overlay layer.
+//!
+//! Off by default; toggled with Ctrl+Shift+P, the renderers' `--profiler`
+//! flag or `KryonApp::set_profiler_overlay`. The app refreshes its sections
+//! about once a second.
+
+use glam::{Vec2, Vec4};
+use kryon_core::TextAlignment;
+use kryon_render::RenderCommand;
+
+const PANEL_MARGIN: f32 = 8.0;
+const PANEL_PADDING: f32 = 8.0;
+const PANEL_WIDTH: f32 = 300.0;
+const LINE_HEIGHT: f32 = 16.0;
+const FONT_SIZE: f32 = 13.0;
+
+#[derive(Debug, Default)]
+pub struct ProfilerOverlay {
+    enabled: bool,
+    sections: Vec<(&'static str, Vec<String>)>,
+}
+
+impl ProfilerOverlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Replaces the lines shown under `section`, keeping section order.
+    pub fn set_section(&mut self, section: &'static str, lines: Vec<String>) {
+        match self.sections.iter_mut().find(|(name, _)| *name == section) {
+            Some((_, existing)) => *existing = lines,
+            None => self.sections.push((section, lines)),
+        }
+    }
+
+    pub fn overlay_commands(&self) -> Vec<RenderCommand> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let lines: Vec<&String> = self.sections.iter().flat_map(|(_, lines)| lines).collect();
+        let height = lines.len().max(1) as f32 * LINE_HEIGHT + PANEL_PADDING * 2.0;
+        let origin = Vec2::splat(PANEL_MARGIN);
+
+        let mut commands = vec![RenderCommand::DrawRect {
+            position: origin,
+            size: Vec2::new(PANEL_WIDTH, height),
+            color: Vec4::new(0.0, 0.0, 0.0, 0.75),
+            border_radius: 4.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: i32::MAX,
+        }];
+        for (index, line) in lines.iter().enumerate() {
+            commands.push(RenderCommand::DrawText {
+                position: origin + Vec2::new(PANEL_PADDING, PANEL_PADDING + index as f32 * LINE_HEIGHT),
+                text: line.to_string(),
+                font_size: FONT_SIZE,
+                color: Vec4::new(0.6, 1.0, 0.6, 1.0),
+                alignment: TextAlignment::Start,
+                max_width: Some(PANEL_WIDTH - PANEL_PADDING * 2.0),
+                max_height: None,
+                transform: None,
+                font_family: None,
+                runs: Vec::new(),
+                z_index: i32::MAX,
+            });
+        }
+        commands
+    }
+}
@@ -0,0 +1,384 @@
+// crates/kryon-runtime/src/repl.rs
+//! Interactive script console attached to a running app.
+//!
+//! Lines arrive from stdin (desktop) or from the inspector WebSocket and are
+//! handled by `KryonApp::update` between frames, so code runs in the live
+//! engine with the same host APIs and process allowlist as the app's own
+//! scripts. Lines starting with `:` are console commands; see [`HELP`].
+
+use crate::script::engine_trait::ScriptValue;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+pub const HELP: &str = "\
+<lua code>                 evaluate in the live engine and print the result
+:vars                      list template variables
+:get NAME                  show a template variable
+:set NAME VALUE            set a template variable (updates scripts and bindings)
+:el ID                     show an element's properties
+:prop ID NAME VALUE        set an element property (text, visible, font_size,
+                           opacity, background_color, text_color, border_color,
+                           or any custom property)
+:memory                    show memory use by subsystem
+:warnings                  list features the backend couldn't draw, by element
+:history                   list previous lines; !N re-runs line N, !! the last
+:help                      show this help";
+
+/// History entries kept per session.
+const HISTORY_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplCommand {
+    Eval(String),
+    Vars,
+    Get(String),
+    Set { name: String, value: String },
+    Element(String),
+    SetProperty { element: String, property: String, value: String },
+    Memory,
+    Warnings,
+    History,
+    Help,
+}
+
+impl ReplCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let Some(command) = line.strip_prefix(':') else {
+            return Ok(ReplCommand::Eval(line.to_string()));
+        };
+
+        let (name, rest) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        let rest = rest.trim();
+        let mut words = rest.splitn(3, char::is_whitespace).filter(|word| !word.is_empty());
+        let usage = |usage: &str| format!("usage: {}", usage);
+
+        match name {
+            "vars" => Ok(ReplCommand::Vars),
+            "get" => words.next()
+                .map(|name| ReplCommand::Get(name.to_string()))
+                .ok_or_else(|| usage(":get NAME")),
+            "set" => {
+                let (name, value) = rest.split_once(char::is_whitespace).ok_or_else(|| usage(":set NAME VALUE"))?;
+                Ok(ReplCommand::Set { name: name.to_string(), value: value.trim().to_string() })
+            }
+            "el" => words.next()
+                .map(|id| ReplCommand::Element(id.to_string()))
+                .ok_or_else(|| usage(":el ID")),
+            "prop" => match (words.next(), words.next(), words.next()) {
+                (Some(element), Some(property), Some(value)) => Ok(ReplCommand::SetProperty {
+                    element: element.to_string(),
+                    property: property.to_string(),
+                    value: value.trim().to_string(),
+                }),
+                _ => Err(usage(":prop ID NAME VALUE")),
+            },
+            "memory" => Ok(ReplCommand::Memory),
+            "warnings" => Ok(ReplCommand::Warnings),
+            "history" => Ok(ReplCommand::History),
+            "help" | "h" | "?" => Ok(ReplCommand::Help),
+            other => Err(format!("unknown command ':{}' (try :help)", other)),
+        }
+    }
+}
+
+/// One console line and where its output goes.
+pub struct ReplRequest {
+    pub line: String,
+    reply: Box<dyn FnOnce(String) + Send>,
+}
+
+impl ReplRequest {
+    pub fn new(line: impl Into<String>, reply: impl FnOnce(String) + Send + 'static) -> Self {
+        Self { line: line.into(), reply: Box::new(reply) }
+    }
+
+    pub fn respond(self, output: String) {
+        (self.reply)(output);
+    }
+}
+
+pub struct Repl {
+    sender: Sender<ReplRequest>,
+    receiver: Receiver<ReplRequest>,
+    history: Vec<String>,
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver, history: Vec::new() }
+    }
+
+    /// Console for the renderers' `--repl` / `--repl-listen` flags, or `None`
+    /// when neither was given. A listen failure is logged and leaves stdin
+    /// (if requested) attached.
+    pub fn from_args(stdin: bool, listen: Option<&str>) -> Option<Self> {
+        if !stdin && listen.is_none() {
+            return None;
+        }
+        let repl = Self::new();
+        if let Some(addr) = listen {
+            if let Err(e) = repl.listen(addr) {
+                eprintln!("[REPL] Failed to listen on {}: {}", addr, e);
+            }
+        }
+        if stdin {
+            repl.attach_stdin();
+        }
+        Some(repl)
+    }
+
+    /// For custom front ends; requests sent here are handled like console input.
+    pub fn sender(&self) -> Sender<ReplRequest> {
+        self.sender.clone()
+    }
+
+    /// Reads lines from stdin on a background thread and prints the results.
+    /// Each line waits for its result before the next prompt.
+    pub fn attach_stdin(&self) {
+        use std::io::{BufRead, Write};
+
+        let sender = self.sender.clone();
+        std::thread::Builder::new()
+            .name("kryon-repl".to_string())
+            .spawn(move || {
+                let prompt = || {
+                    print!("kryon> ");
+                    let _ = io::stdout().flush();
+                };
+                prompt();
+                for line in io::stdin().lock().lines() {
+                    let Ok(line) = line else { break };
+                    let (reply, output) = channel();
+                    let request = ReplRequest::new(line, move |text| {
+                        let _ = reply.send(text);
+                    });
+                    if sender.send(request).is_err() {
+                        break; // app shut down
+                    }
+                    if let Ok(text) = output.recv() {
+                        if !text.is_empty() {
+                            println!("{}", text);
+                        }
+                    }
+                    prompt();
+                }
+            })
+            .expect("Failed to start REPL thread");
+    }
+
+    /// Serves the console on the inspector WebSocket: each text frame is one
+    /// line and is answered with one text frame.
+    pub fn listen(&self, addr: &str) -> io::Result<SocketAddr> {
+        remote::listen(addr, self.sender.clone())
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Takes pending lines, expanding `!!` / `!N` from history and recording
+    /// them. Empty lines are answered immediately.
+    pub fn poll(&mut self) -> Vec<(Result<ReplCommand, String>, ReplRequest)> {
+        let mut ready = Vec::new();
+        for mut request in self.receiver.try_iter().collect::<Vec<_>>() {
+            let line = request.line.trim().to_string();
+            if line.is_empty() {
+                request.respond(String::new());
+                continue;
+            }
+
+            let line = match self.expand(&line) {
+                Ok(line) => line,
+                Err(e) => {
+                    ready.push((Err(e), request));
+                    continue;
+                }
+            };
+
+            let command = ReplCommand::parse(&line);
+            if command != Ok(ReplCommand::History) {
+                self.history.push(line.clone());
+                if self.history.len() > HISTORY_LIMIT {
+                    self.history.remove(0);
+                }
+            }
+            request.line = line;
+            ready.push((command, request));
+        }
+        ready
+    }
+
+    fn expand(&self, line: &str) -> Result<String, String> {
+        let Some(reference) = line.strip_prefix('!') else {
+            return Ok(line.to_string());
+        };
+        let index = if reference == "!" {
+            self.history.len()
+        } else {
+            reference.parse::<usize>().map_err(|_| format!("bad history reference '{}'", line))?
+        };
+        index.checked_sub(1)
+            .and_then(|index| self.history.get(index))
+            .cloned()
+            .ok_or_else(|| format!("no history entry {}", line))
+    }
+
+    pub fn format_history(&self) -> String {
+        self.history.iter()
+            .enumerate()
+            .map(|(index, line)| format!("{:>4}  {}", index + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Lua-style rendering of a script value, with object keys sorted.
+pub fn format_value(value: &ScriptValue) -> String {
+    match value {
+        ScriptValue::Nil => "nil".to_string(),
+        ScriptValue::Boolean(b) => b.to_string(),
+        ScriptValue::Integer(i) => i.to_string(),
+        ScriptValue::Number(n) => n.to_string(),
+        ScriptValue::String(s) => format!("{:?}", s),
+        ScriptValue::Bytes(bytes) => format!("<{} bytes>", bytes.len()),
+        ScriptValue::Array(items) => {
+            let items: Vec<String> = items.iter().map(format_value).collect();
+            format!("{{{}}}", items.join(", "))
+        }
+        ScriptValue::Object(map) => {
+            let mut entries: Vec<(&String, &ScriptValue)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let entries: Vec<String> = entries.iter()
+                .map(|(key, value)| format!("{} = {}", key, format_value(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+mod remote {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    pub fn listen(addr: &str, requests: Sender<ReplRequest>) -> io::Result<SocketAddr> {
+        let runtime = crate::net::runtime();
+        let listener = runtime.block_on(TcpListener::bind(addr))?;
+        let local = listener.local_addr()?;
+        eprintln!("[REPL] Inspector console listening on ws://{}", local);
+
+        runtime.spawn(async move {
+            while let Ok((stream, peer)) = listener.accept().await {
+                let requests = requests.clone();
+                tokio::spawn(async move {
+                    let Ok(socket) = tokio_tungstenite::accept_async(stream).await else { return };
+                    eprintln!("[REPL] Inspector client connected from {}", peer);
+                    let (mut write, mut read) = socket.split();
+
+                    while let Some(Ok(message)) = read.next().await {
+                        let line = match message {
+                            Message::Text(text) => text,
+                            Message::Close(_) => break,
+                            _ => continue,
+                        };
+                        let (reply, output) = tokio::sync::oneshot::channel();
+                        let request = ReplRequest::new(line, move |text| {
+                            let _ = reply.send(text);
+                        });
+                        if requests.send(request).is_err() {
+                            break;
+                        }
+                        let Ok(text) = output.await else { break };
+                        if write.send(Message::Text(text)).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(local)
+    }
+}
+
+#[cfg(not(all(feature = "websocket", not(target_arch = "wasm32"))))]
+mod remote {
+    use super::*;
+
+    pub fn listen(_addr: &str, _requests: Sender<ReplRequest>) -> io::Result<SocketAddr> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the remote console needs the `websocket` feature",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(ReplCommand::parse(" 1 + 1 "), Ok(ReplCommand::Eval("1 + 1".into())));
+        assert_eq!(
+            ReplCommand::parse(":set title Hello there"),
+            Ok(ReplCommand::Set { name: "title".into(), value: "Hello there".into() })
+        );
+        assert_eq!(
+            ReplCommand::parse(":prop header background_color #ff0000"),
+            Ok(ReplCommand::SetProperty { element: "header".into(), property: "background_color".into(), value: "#ff0000".into() })
+        );
+        assert_eq!(ReplCommand::parse(":warnings"), Ok(ReplCommand::Warnings));
+        assert_eq!(ReplCommand::parse(":el"), Err("usage: :el ID".into()));
+        assert!(ReplCommand::parse(":bogus").is_err());
+    }
+
+    #[test]
+    fn expands_history() {
+        let mut repl = Repl::new();
+        let replies = Arc::new(Mutex::new(Vec::new()));
+        let send = |repl: &Repl, line: &str| {
+            let replies = replies.clone();
+            repl.sender().send(ReplRequest::new(line, move |text| replies.lock().unwrap().push(text))).unwrap();
+        };
+
+        send(&repl, "x = 1");
+        send(&repl, "");
+        send(&repl, ":vars");
+        send(&repl, ":history");
+        let lines: Vec<String> = repl.poll().into_iter().map(|(_, request)| request.line).collect();
+        assert_eq!(lines, vec!["x = 1", ":vars", ":history"]);
+        assert_eq!(replies.lock().unwrap().len(), 1, "empty line answered immediately");
+
+        send(&repl, "!1");
+        send(&repl, "!!");
+        send(&repl, "!9");
+        let polled = repl.poll();
+        assert_eq!(polled[0].0, Ok(ReplCommand::Eval("x = 1".into())));
+        assert_eq!(polled[1].0, Ok(ReplCommand::Eval("x = 1".into())));
+        assert_eq!(polled[2].0, Err("no history entry !9".into()));
+        assert_eq!(repl.format_history().lines().last(), Some("   4  x = 1"));
+    }
+
+    #[test]
+    fn formats_values() {
+        let mut object = HashMap::new();
+        object.insert("b".to_string(), ScriptValue::Array(vec![ScriptValue::Integer(1), ScriptValue::Nil]));
+        object.insert("a".to_string(), ScriptValue::String("hi".into()));
+        assert_eq!(format_value(&ScriptValue::Object(object)), r#"{a = "hi", b = {1, nil}}"#);
+        assert_eq!(format_value(&ScriptValue::Number(2.5)), "2.5");
+    }
+}
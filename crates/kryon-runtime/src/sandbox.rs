@@ -0,0 +1,246 @@
+// crates/kryon-runtime/src/sandbox.rs
+//! Sandbox mode, for viewing KRB files from untrusted sources.
+//!
+//! A sandboxed app draws the file's UI and runs nothing of its own. Before
+//! the app starts, [`strip`] takes out everything in the file that would
+//! run code or reach outside the app: its scripts, its elements' event
+//! handlers, and the data sources, MQTT brokers and table sources it
+//! declares. The runtime also keeps the process, serial, network and
+//! dialog subsystems switched off for as long as the app is sandboxed, and
+//! a REPL can't be attached.
+//!
+//! What was taken out is kept as a [`SandboxReport`], so galleries and
+//! preview tools can show what the file tried to use. Scripts' calls into
+//! `kryon.process`, `kryon.dialog`, `kryon.ws`, `kryon.mqtt`, `kryon.data`
+//! and `kryon.serial` are found by their names in the script's text.
+
+use kryon_core::{KRBFile, PropertyValue};
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    Scripts,
+    Network,
+    Processes,
+    /// Reading files from disk
+    Storage,
+    FileDialogs,
+    SerialPorts,
+}
+
+impl Capability {
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::Scripts => "scripts",
+            Capability::Network => "network",
+            Capability::Processes => "processes",
+            Capability::Storage => "storage",
+            Capability::FileDialogs => "file dialogs",
+            Capability::SerialPorts => "serial ports",
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Something the file would have used, had it not been sandboxed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub capability: Capability,
+    /// What it was, e.g. `data_source "https://..." on 'plant'`
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxReport {
+    attempts: Vec<Attempt>,
+}
+
+impl SandboxReport {
+    /// In the order they were found
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// The capabilities the file tried to use
+    pub fn capabilities(&self) -> BTreeSet<Capability> {
+        self.attempts.iter().map(|attempt| attempt.capability).collect()
+    }
+
+    pub fn attempted(&self, capability: Capability) -> bool {
+        self.attempts.iter().any(|attempt| attempt.capability == capability)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attempts.is_empty()
+    }
+
+    fn record(&mut self, capability: Capability, detail: String) {
+        self.attempts.push(Attempt { capability, detail });
+    }
+}
+
+impl fmt::Display for SandboxReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.attempts.is_empty() {
+            return f.write_str("the file uses nothing the sandbox disables");
+        }
+        let capabilities: Vec<&str> = self.capabilities().into_iter().map(Capability::name).collect();
+        write!(f, "disabled {}", capabilities.join(", "))?;
+        for attempt in &self.attempts {
+            write!(f, "\n  {}: {}", attempt.capability, attempt.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Script APIs and the capability each needs
+const SCRIPT_APIS: [(&str, Capability); 6] = [
+    ("kryon.process", Capability::Processes),
+    ("kryon.dialog", Capability::FileDialogs),
+    ("kryon.ws", Capability::Network),
+    ("kryon.mqtt", Capability::Network),
+    ("kryon.data", Capability::Network),
+    ("kryon.serial", Capability::SerialPorts),
+];
+
+/// Takes everything the sandbox disables out of `krb_file`, reporting what
+/// it found
+pub fn strip(krb_file: &mut KRBFile) -> SandboxReport {
+    let mut report = SandboxReport::default();
+
+    for script in std::mem::take(&mut krb_file.scripts) {
+        report.record(Capability::Scripts, format!("{} script '{}'", script.language, script.name));
+        for (api, capability) in SCRIPT_APIS {
+            if mentions(&script.bytecode, api) {
+                report.record(capability, format!("{} in script '{}'", api, script.name));
+            }
+        }
+    }
+
+    let mut element_ids: Vec<_> = krb_file.elements.keys().copied().collect();
+    element_ids.sort_unstable();
+    for element_id in element_ids {
+        let element = krb_file.elements.get_mut(&element_id).expect("ids were taken from the map");
+        let name = if element.id.is_empty() { format!("#{}", element_id) } else { format!("'{}'", element.id) };
+        if !element.event_handlers.is_empty() {
+            let mut handlers: Vec<&str> = element.event_handlers.values().map(String::as_str).collect();
+            handlers.sort_unstable();
+            report.record(Capability::Scripts, format!("event handlers {} on {}", handlers.join(", "), name));
+            element.event_handlers.clear();
+        }
+        if let Some(source) = take_string(&mut element.custom_properties, "data_source") {
+            let capability = if is_remote(&source) { Capability::Network } else { Capability::Storage };
+            report.record(capability, format!("data_source \"{}\" on {}", source, name));
+        }
+        if let Some(broker) = take_string(&mut element.custom_properties, "mqtt_broker") {
+            report.record(Capability::Network, format!("mqtt_broker \"{}\" on {}", broker, name));
+        }
+        if let Some(source) = take_string(&mut element.custom_properties, "table_source") {
+            report.record(Capability::Storage, format!("table_source \"{}\" on {}", source, name));
+        }
+    }
+    report
+}
+
+fn take_string(properties: &mut std::collections::HashMap<String, PropertyValue>, name: &str) -> Option<String> {
+    match properties.remove(name)? {
+        PropertyValue::String(value) => Some(value),
+        other => Some(format!("{:?}", other)),
+    }
+}
+
+/// Whether `source` names a URL rather than a file
+fn is_remote(source: &str) -> bool {
+    source.contains("://") && !source.starts_with("file://")
+}
+
+/// Whether `api` appears in `script` as a name, not the start of a longer one
+fn mentions(script: &[u8], api: &str) -> bool {
+    let api = api.as_bytes();
+    script.windows(api.len()).enumerate().any(|(start, window)| {
+        window == api && script.get(start + api.len()).is_none_or(|&next| !(next.is_ascii_alphanumeric() || next == b'_'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::{Element, EventType, KRBHeader, ScriptEntry};
+    use std::collections::HashMap;
+
+    fn krb_file(elements: HashMap<u32, Element>, scripts: Vec<ScriptEntry>) -> KRBFile {
+        KRBFile {
+            header: KRBHeader {
+                magic: *b"KRB1",
+                version: 1,
+                flags: 0,
+                element_count: elements.len() as u16,
+                style_count: 0,
+                component_count: 0,
+                script_count: scripts.len() as u16,
+                string_count: 0,
+                resource_count: 0,
+                template_variable_count: 0,
+                template_binding_count: 0,
+                transform_count: 0,
+            },
+            strings: Vec::new(),
+            elements,
+            styles: HashMap::new(),
+            root_element_id: Some(0),
+            resources: Vec::new(),
+            scripts,
+            template_variables: Vec::new(),
+            template_bindings: Vec::new(),
+            transforms: Vec::new(),
+            fonts: HashMap::new(),
+            compatibility: Default::default(),
+        }
+    }
+
+    #[test]
+    fn strips_scripts_handlers_and_declared_sources() {
+        let mut app = Element { id: "app".into(), ..Default::default() };
+        app.custom_properties.insert("mqtt_broker".into(), PropertyValue::String("mqtts://broker.local".into()));
+        let mut plant = Element { id: "plant".into(), ..Default::default() };
+        plant.custom_properties.insert("data_source".into(), PropertyValue::String("https://api.example.com/plant".into()));
+        plant.event_handlers.insert(EventType::Click, "refresh".into());
+        let mut table = Element::default();
+        table.custom_properties.insert("table_source".into(), PropertyValue::String("data/pumps.csv".into()));
+        let script = ScriptEntry {
+            language: "lua".into(),
+            name: "main".into(),
+            bytecode: b"function refresh() kryon.process.run('rm') kryon.data.refresh() kryon.dialogs = 1 end".to_vec(),
+            entry_points: Vec::new(),
+        };
+        let mut file = krb_file(HashMap::from([(0, app), (1, plant), (2, table)]), vec![script]);
+
+        let report = strip(&mut file);
+        assert!(file.scripts.is_empty());
+        assert!(file.elements.values().all(|element| element.event_handlers.is_empty() && element.custom_properties.is_empty()));
+        assert_eq!(
+            report.capabilities().into_iter().collect::<Vec<_>>(),
+            vec![Capability::Scripts, Capability::Network, Capability::Processes, Capability::Storage],
+            "kryon.dialogs isn't kryon.dialog"
+        );
+        let details: Vec<&str> = report.attempts().iter().map(|attempt| attempt.detail.as_str()).collect();
+        assert_eq!(details, vec![
+            "lua script 'main'",
+            "kryon.process in script 'main'",
+            "kryon.data in script 'main'",
+            "mqtt_broker \"mqtts://broker.local\" on 'app'",
+            "event handlers refresh on 'plant'",
+            "data_source \"https://api.example.com/plant\" on 'plant'",
+            "table_source \"data/pumps.csv\" on #2",
+        ]);
+        assert!(report.to_string().starts_with("disabled scripts, network, processes, storage\n  scripts: lua script 'main'"));
+
+        assert!(strip(&mut file).is_empty(), "a stripped file has nothing left to strip");
+    }
+}
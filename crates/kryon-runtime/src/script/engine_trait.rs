@@ -6,7 +6,7 @@
 
 use std::collections::HashMap;
 use anyhow::Result;
-use kryon_core::{Element, ElementId};
+use kryon_core::{Element, ElementId, TemplateValueType};
 // use crate::script::error::ScriptError;
 
 /// Core value type for inter-language communication
@@ -17,6 +17,8 @@ pub enum ScriptValue {
     Integer(i64),
     Number(f64),
     String(String),
+    /// Raw binary data, e.g. file contents delivered by host APIs
+    Bytes(Vec<u8>),
     Array(Vec<ScriptValue>),
     Object(HashMap<String, ScriptValue>),
 }
@@ -30,6 +32,7 @@ impl ScriptValue {
             ScriptValue::Integer(i) => i.to_string(),
             ScriptValue::Number(f) => f.to_string(),
             ScriptValue::String(s) => s.clone(),
+            ScriptValue::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
             ScriptValue::Array(arr) => {
                 let items: Vec<String> = arr.iter().map(|v| v.to_string()).collect();
                 format!("[{}]", items.join(", "))
@@ -51,6 +54,7 @@ impl ScriptValue {
             ScriptValue::Integer(i) => *i != 0,
             ScriptValue::Number(f) => *f != 0.0 && !f.is_nan(),
             ScriptValue::String(s) => !s.is_empty(),
+            ScriptValue::Bytes(bytes) => !bytes.is_empty(),
             ScriptValue::Array(arr) => !arr.is_empty(),
             ScriptValue::Object(obj) => !obj.is_empty(),
         }
@@ -117,6 +121,13 @@ pub trait ScriptEngine {
     /// Setup reactive template variables
     fn setup_reactive_variables(&mut self, variables: &HashMap<String, String>) -> Result<()>;
     
+    /// Set the declared type of each template variable, which values
+    /// scripts assign are coerced to. Engines without typed variables keep
+    /// assigning text.
+    fn set_template_variable_types(&mut self, _types: &HashMap<String, TemplateValueType>) -> Result<()> {
+        Ok(())
+    }
+    
     /// Execute onReady callbacks
     fn execute_on_ready_callbacks(&mut self) -> Result<()>;
     
@@ -224,6 +235,12 @@ impl From<String> for ScriptValue {
     }
 }
 
+impl From<Vec<u8>> for ScriptValue {
+    fn from(value: Vec<u8>) -> Self {
+        ScriptValue::Bytes(value)
+    }
+}
+
 impl From<&str> for ScriptValue {
     fn from(value: &str) -> Self {
         ScriptValue::String(value.to_string())
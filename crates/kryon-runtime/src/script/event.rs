@@ -0,0 +1,303 @@
+// crates/kryon-runtime/src/script/event.rs
+//! The event object passed to script event handlers.
+//!
+//! It is built once here and converted to a `ScriptValue`, so every engine
+//! sees the same fields:
+//!
+//! ```lua
+//! function onSliderClick(event)
+//!     -- event.type, event.target, event.timestamp
+//!     -- event.button                     "left", "right", "middle", "back"
+//!                                         or "forward"; absent on hover
+//!     -- event.current_target             the element whose handler runs
+//!     -- event.related_target             pointerenter/pointerleave: the
+//!                                         element the pointer came from or
+//!                                         went to, if any
+//!     -- event.client_x, event.client_y   window coordinates
+//!     -- event.local_x, event.local_y     relative to the element's top-left,
+//!     --                                  before its transform
+//!     -- event.modifiers.shift / ctrl / alt / meta
+//! end
+//!
+//! function onNameChange(event)
+//!     -- event.type                       "change", "focus", "blur" or "submit"
+//!     -- event.target, event.value, event.timestamp
+//!     -- event.selection_start, event.selection_end
+//!     -- event.color                      color inputs: {r, g, b, a}, 0 to 1
+//! end
+//!
+//! function onCardDrop(event)
+//!     -- event.type                       "dragstart", "dragover" or "drop"
+//!     -- event.target                     the element whose handler runs
+//!     -- event.source                     the element being dragged
+//!     -- event.data, event.drag_type      its payload and kind
+//!     -- event.client_x, event.client_y, event.local_x, event.local_y
+//!     -- event.modifiers, event.timestamp
+//! end
+//! ```
+
+use super::engine_trait::ScriptValue;
+use glam::{Vec2, Vec4};
+use kryon_render::{KeyModifiers, MouseButton};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+    /// `"click"`, `"press"`, `"release"`, `"hover"`, `"pointerenter"`,
+    /// `"pointerleave"` or `"pointermove"`
+    pub kind: &'static str,
+    /// The target element's `id`, empty if it has none
+    pub target: String,
+    /// The `id` of the element whose handler runs; an ancestor of `target`
+    /// while a `pointermove`, `press` or `release` bubbles
+    pub current_target: String,
+    pub related_target: Option<String>,
+    pub client: Vec2,
+    pub local: Vec2,
+    /// `None` for events not caused by a button, such as hover
+    pub button: Option<MouseButton>,
+    pub modifiers: KeyModifiers,
+    /// Milliseconds since the app started
+    pub timestamp: f64,
+}
+
+impl PointerEvent {
+    pub fn to_script_value(&self) -> ScriptValue {
+        let mut modifiers = HashMap::new();
+        modifiers.insert("shift".to_string(), ScriptValue::from(self.modifiers.shift));
+        modifiers.insert("ctrl".to_string(), ScriptValue::from(self.modifiers.ctrl));
+        modifiers.insert("alt".to_string(), ScriptValue::from(self.modifiers.alt));
+        modifiers.insert("meta".to_string(), ScriptValue::from(self.modifiers.meta));
+
+        let mut fields = HashMap::new();
+        fields.insert("type".to_string(), ScriptValue::from(self.kind));
+        fields.insert("target".to_string(), ScriptValue::from(self.target.as_str()));
+        fields.insert("current_target".to_string(), ScriptValue::from(self.current_target.as_str()));
+        if let Some(related_target) = &self.related_target {
+            fields.insert("related_target".to_string(), ScriptValue::from(related_target.as_str()));
+        }
+        fields.insert("client_x".to_string(), ScriptValue::from(self.client.x));
+        fields.insert("client_y".to_string(), ScriptValue::from(self.client.y));
+        fields.insert("local_x".to_string(), ScriptValue::from(self.local.x));
+        fields.insert("local_y".to_string(), ScriptValue::from(self.local.y));
+        if let Some(button) = self.button {
+            let name = match button {
+                MouseButton::Left => "left",
+                MouseButton::Right => "right",
+                MouseButton::Middle => "middle",
+                MouseButton::Back => "back",
+                MouseButton::Forward => "forward",
+            };
+            fields.insert("button".to_string(), ScriptValue::from(name));
+        }
+        fields.insert("modifiers".to_string(), ScriptValue::Object(modifiers));
+        fields.insert("timestamp".to_string(), ScriptValue::from(self.timestamp));
+        ScriptValue::Object(fields)
+    }
+}
+
+/// The event passed to a text input's `onChange`, `onFocus`, `onBlur`,
+/// `onSubmit` and `onInvalid` handlers, and to the `onChange` of
+/// checkboxes, radio buttons and sliders
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldEvent {
+    /// `"change"`, `"focus"`, `"blur"`, `"submit"` or `"invalid"`
+    pub kind: &'static str,
+    pub target: String,
+    /// The input's value after the edit: `true` or `false` for a
+    /// checkbox, a radio button's `value`, a slider's number
+    pub value: String,
+    /// The value without the literals of its `input_mask`; the same as
+    /// `value` without one
+    pub raw_value: String,
+    /// Character indices; equal when nothing is selected
+    pub selection: (usize, usize),
+    /// Why the input failed its validation when it was last checked;
+    /// empty if it passed
+    pub validation_message: String,
+    /// A color input's value as RGBA components in `0.0..=1.0`; `value`
+    /// is its hex
+    pub color: Option<Vec4>,
+    /// Whether a checkbox or radio button is checked
+    pub checked: Option<bool>,
+    /// Milliseconds since the app started
+    pub timestamp: f64,
+}
+
+impl FieldEvent {
+    pub fn to_script_value(&self) -> ScriptValue {
+        let mut fields = HashMap::new();
+        fields.insert("type".to_string(), ScriptValue::from(self.kind));
+        fields.insert("target".to_string(), ScriptValue::from(self.target.as_str()));
+        fields.insert("value".to_string(), ScriptValue::from(self.value.as_str()));
+        fields.insert("raw_value".to_string(), ScriptValue::from(self.raw_value.as_str()));
+        fields.insert("selection_start".to_string(), ScriptValue::Integer(self.selection.0 as i64));
+        fields.insert("selection_end".to_string(), ScriptValue::Integer(self.selection.1 as i64));
+        fields.insert("validation_message".to_string(), ScriptValue::from(self.validation_message.as_str()));
+        if let Some(color) = self.color {
+            let components = [("r", color.x), ("g", color.y), ("b", color.z), ("a", color.w)];
+            let color = components.into_iter().map(|(name, component)| (name.to_string(), ScriptValue::from(component))).collect();
+            fields.insert("color".to_string(), ScriptValue::Object(color));
+        }
+        if let Some(checked) = self.checked {
+            fields.insert("checked".to_string(), ScriptValue::Boolean(checked));
+        }
+        fields.insert("timestamp".to_string(), ScriptValue::from(self.timestamp));
+        ScriptValue::Object(fields)
+    }
+}
+
+/// The event passed to `onDragStart`, `onDragOver` and `onDrop` handlers
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragEvent {
+    /// `"dragstart"`, `"dragover"` or `"drop"`
+    pub kind: &'static str,
+    pub target: String,
+    /// The `id` of the element being dragged
+    pub source: String,
+    /// The drag's payload: the source's `drag_data`, or what its
+    /// `onDragStart` returned
+    pub data: ScriptValue,
+    pub drag_type: Option<String>,
+    pub client: Vec2,
+    pub local: Vec2,
+    pub modifiers: KeyModifiers,
+    /// Milliseconds since the app started
+    pub timestamp: f64,
+}
+
+impl DragEvent {
+    pub fn to_script_value(&self) -> ScriptValue {
+        let mut modifiers = HashMap::new();
+        modifiers.insert("shift".to_string(), ScriptValue::from(self.modifiers.shift));
+        modifiers.insert("ctrl".to_string(), ScriptValue::from(self.modifiers.ctrl));
+        modifiers.insert("alt".to_string(), ScriptValue::from(self.modifiers.alt));
+        modifiers.insert("meta".to_string(), ScriptValue::from(self.modifiers.meta));
+
+        let mut fields = HashMap::new();
+        fields.insert("type".to_string(), ScriptValue::from(self.kind));
+        fields.insert("target".to_string(), ScriptValue::from(self.target.as_str()));
+        fields.insert("source".to_string(), ScriptValue::from(self.source.as_str()));
+        fields.insert("data".to_string(), self.data.clone());
+        if let Some(drag_type) = &self.drag_type {
+            fields.insert("drag_type".to_string(), ScriptValue::from(drag_type.as_str()));
+        }
+        fields.insert("client_x".to_string(), ScriptValue::from(self.client.x));
+        fields.insert("client_y".to_string(), ScriptValue::from(self.client.y));
+        fields.insert("local_x".to_string(), ScriptValue::from(self.local.x));
+        fields.insert("local_y".to_string(), ScriptValue::from(self.local.y));
+        fields.insert("modifiers".to_string(), ScriptValue::Object(modifiers));
+        fields.insert("timestamp".to_string(), ScriptValue::from(self.timestamp));
+        ScriptValue::Object(fields)
+    }
+}
+
+/// An app-level event raised by `kryon.emit(name, payload)` for the host
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppEvent {
+    pub name: String,
+    pub payload: ScriptValue,
+}
+
+impl AppEvent {
+    /// Parses `{name, payload}` as queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+        let name = match fields.get("name")? {
+            ScriptValue::String(name) => name.clone(),
+            _ => return None,
+        };
+        let payload = fields.get("payload").cloned().unwrap_or(ScriptValue::Nil);
+        Some(Self { name, payload })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_a_script_object() {
+        let event = PointerEvent {
+            kind: "click",
+            target: "volume".to_string(),
+            current_target: "volume".to_string(),
+            related_target: None,
+            client: Vec2::new(130.0, 45.0),
+            local: Vec2::new(30.0, 5.0),
+            button: Some(MouseButton::Left),
+            modifiers: KeyModifiers { shift: true, ..KeyModifiers::none() },
+            timestamp: 1500.0,
+        };
+        let ScriptValue::Object(fields) = event.to_script_value() else {
+            panic!("expected an object");
+        };
+        assert_eq!(fields["type"], ScriptValue::from("click"));
+        assert_eq!(fields["target"], ScriptValue::from("volume"));
+        assert_eq!(fields["local_x"], ScriptValue::Number(30.0));
+        assert_eq!(fields["client_y"], ScriptValue::Number(45.0));
+        assert_eq!(fields["button"], ScriptValue::from("left"));
+        let ScriptValue::Object(modifiers) = &fields["modifiers"] else {
+            panic!("expected modifiers");
+        };
+        assert_eq!(modifiers["shift"], ScriptValue::Boolean(true));
+        assert_eq!(modifiers["ctrl"], ScriptValue::Boolean(false));
+
+        let hover = PointerEvent { kind: "hover", button: None, ..event.clone() };
+        let ScriptValue::Object(fields) = hover.to_script_value() else { unreachable!() };
+        assert!(!fields.contains_key("button"));
+        assert!(!fields.contains_key("related_target"));
+
+        let back = PointerEvent { kind: "press", button: Some(MouseButton::Back), ..event.clone() };
+        let ScriptValue::Object(fields) = back.to_script_value() else { unreachable!() };
+        assert_eq!(fields["type"], ScriptValue::from("press"));
+        assert_eq!(fields["button"], ScriptValue::from("back"));
+
+        let left = PointerEvent { kind: "pointerleave", related_target: Some("mute".to_string()), button: None, ..event };
+        let ScriptValue::Object(fields) = left.to_script_value() else { unreachable!() };
+        assert_eq!(fields["related_target"], ScriptValue::from("mute"));
+        assert_eq!(fields["current_target"], ScriptValue::from("volume"));
+    }
+
+    #[test]
+    fn drag_events_carry_the_source_and_payload() {
+        let event = DragEvent {
+            kind: "drop",
+            target: "done".to_string(),
+            source: "card".to_string(),
+            data: ScriptValue::Integer(7),
+            drag_type: None,
+            client: Vec2::new(200.0, 80.0),
+            local: Vec2::new(20.0, 8.0),
+            modifiers: KeyModifiers::none(),
+            timestamp: 900.0,
+        };
+        let ScriptValue::Object(fields) = event.to_script_value() else {
+            panic!("expected an object");
+        };
+        assert_eq!(fields["type"], ScriptValue::from("drop"));
+        assert_eq!(fields["source"], ScriptValue::from("card"));
+        assert_eq!(fields["data"], ScriptValue::Integer(7));
+        assert_eq!(fields["local_y"], ScriptValue::Number(8.0));
+        assert!(!fields.contains_key("drag_type"));
+    }
+
+    #[test]
+    fn parses_app_events() {
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), ScriptValue::from("saved"));
+        fields.insert("payload".to_string(), ScriptValue::Integer(3));
+        let event = AppEvent::from_script_value(&ScriptValue::Object(fields.clone())).unwrap();
+        assert_eq!(event, AppEvent { name: "saved".to_string(), payload: ScriptValue::Integer(3) });
+
+        fields.remove("payload");
+        let event = AppEvent::from_script_value(&ScriptValue::Object(fields.clone())).unwrap();
+        assert_eq!(event.payload, ScriptValue::Nil);
+
+        fields.remove("name");
+        assert!(AppEvent::from_script_value(&ScriptValue::Object(fields)).is_none());
+        assert!(AppEvent::from_script_value(&ScriptValue::from("saved")).is_none());
+    }
+}
@@ -8,6 +8,9 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use anyhow::Result;
 use mlua::{Lua, Table as LuaTable, Function as LuaFunction};
+use kryon_core::ElementId;
+use kryon_render::text_input;
+use crate::{field_validation, form_controls, input_mask};
 use crate::script::{
     engine_trait::{BridgeData, ChangeSet, ScriptValue},
     error::ScriptError,
@@ -67,14 +70,31 @@ impl LuaBridge {
         }
         globals.set("_component_properties", component_properties_table)?;
         
-        // Create elements data table
+        // Create elements data table. Passwords read masked; their values
+        // are only handed out by `getSecretValue`
         let elements_table = self.lua.create_table()?;
+        let mut secret_values = HashMap::new();
         for (element_id, element) in &bridge_data.elements_data {
             let element_data = self.lua.create_table()?;
             element_data.set("id", element.id.clone())?;
             element_data.set("element_type", format!("{:?}", element.element_type))?;
             element_data.set("visible", element.visible)?;
-            element_data.set("text", element.text.clone())?;
+            if text_input::is_password(element) {
+                element_data.set("text", text_input::masked(element, &element.text))?;
+                element_data.set("raw_value", text_input::masked(element, &input_mask::raw_value(element)))?;
+                secret_values.insert(*element_id, element.text.clone());
+            } else {
+                element_data.set("text", element.text.clone())?;
+                element_data.set("raw_value", input_mask::raw_value(element))?;
+            }
+            if let Some(message) = field_validation::validation_message(element) {
+                element_data.set("validation_message", message)?;
+            }
+            match form_controls::control(element) {
+                Some(form_controls::Control::Slider) => element_data.set("value", form_controls::slider_value(element))?,
+                Some(_) => element_data.set("checked", form_controls::is_checked(element))?,
+                None => {}
+            }
             element_data.set("style_id", element.style_id)?;
             
             // Store parent/children relationships
@@ -91,6 +111,9 @@ impl LuaBridge {
             elements_table.set(*element_id, element_data)?;
         }
         globals.set("_elements_data", elements_table)?;
+        globals.set("_secret_value", self.lua.create_function(move |_, element_id: ElementId| {
+            Ok(secret_values.get(&element_id).cloned())
+        })?)?;
         
         tracing::debug!("Lua bridge setup completed with {} elements and {} styles", 
                        bridge_data.elements_data.len(), bridge_data.style_ids.len());
@@ -245,6 +268,7 @@ impl LuaBridge {
             ScriptValue::Integer(i) => table.set(key, i)?,
             ScriptValue::Number(f) => table.set(key, f)?,
             ScriptValue::String(s) => table.set(key, s)?,
+            ScriptValue::Bytes(bytes) => table.set(key, self.lua.create_string(&bytes)?)?,
             ScriptValue::Array(arr) => {
                 let lua_table = self.lua.create_table()?;
                 for (i, item) in arr.into_iter().enumerate() {
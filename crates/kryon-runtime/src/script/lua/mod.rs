@@ -10,6 +10,7 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 use anyhow::Result;
+use kryon_core::TemplateValueType;
 use mlua::{Lua, Value as LuaValue, Function as LuaFunction};
 use regex;
 
@@ -88,7 +89,10 @@ impl LuaEngine {
             LuaValue::Boolean(b) => ScriptValue::Boolean(b),
             LuaValue::Integer(i) => ScriptValue::Integer(i),
             LuaValue::Number(n) => ScriptValue::Number(n),
-            LuaValue::String(s) => ScriptValue::String(s.to_str().unwrap_or("").to_string()),
+            LuaValue::String(s) => match s.to_str() {
+                Ok(text) => ScriptValue::String(text.to_string()),
+                Err(_) => ScriptValue::Bytes(s.as_bytes().to_vec()),
+            },
             LuaValue::Table(table) => {
                 // Try to determine if it's an array or object
                 let mut map = HashMap::new();
@@ -149,6 +153,7 @@ impl LuaEngine {
             ScriptValue::Integer(i) => Ok(LuaValue::Integer(i)),
             ScriptValue::Number(f) => Ok(LuaValue::Number(f)),
             ScriptValue::String(s) => Ok(LuaValue::String(self.lua.create_string(&s)?)),
+            ScriptValue::Bytes(bytes) => Ok(LuaValue::String(self.lua.create_string(&bytes)?)),
             ScriptValue::Array(arr) => {
                 let table = self.lua.create_table()?;
                 for (i, item) in arr.into_iter().enumerate() {
@@ -168,9 +173,8 @@ impl LuaEngine {
     
     /// Update memory statistics
     fn update_memory_stats(&mut self) {
-        // Estimate memory usage (Lua doesn't provide direct access)
         self.memory_stats.object_count = self.functions.len();
-        self.memory_stats.current_usage = self.memory_stats.object_count * 1024; // Rough estimate
+        self.memory_stats.current_usage = self.lua.used_memory();
         self.memory_stats.peak_usage = self.memory_stats.peak_usage.max(self.memory_stats.current_usage);
     }
     
@@ -278,7 +282,8 @@ impl ScriptEngine for LuaEngine {
             .map(|arg| self.script_value_to_lua_value(arg))
             .collect();
         
-        let lua_args = lua_args?;
+        // Pass as multiple arguments; a Vec would arrive as a single table
+        let lua_args = mlua::MultiValue::from_vec(lua_args?);
         
         // Call the function
         let result: LuaValue = function.call(lua_args).map_err(|e| {
@@ -308,6 +313,10 @@ impl ScriptEngine for LuaEngine {
         self.reactive.setup(variables)
     }
     
+    fn set_template_variable_types(&mut self, types: &HashMap<String, TemplateValueType>) -> Result<()> {
+        self.reactive.set_types(types)
+    }
+    
     fn execute_on_ready_callbacks(&mut self) -> Result<()> {
         self.bridge.execute_on_ready_callbacks()
     }
@@ -390,7 +399,13 @@ impl ScriptEngine for LuaEngine {
     }
     
     fn get_memory_usage(&self) -> EngineMemoryStats {
-        self.memory_stats.clone()
+        // Heap size as reported by the Lua allocator, read live
+        let current_usage = self.lua.used_memory();
+        EngineMemoryStats {
+            current_usage,
+            peak_usage: self.memory_stats.peak_usage.max(current_usage),
+            ..self.memory_stats.clone()
+        }
     }
 }
 
@@ -7,7 +7,8 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 use anyhow::Result;
-use mlua::{Lua, Table as LuaTable, Function as LuaFunction};
+use kryon_core::{TemplateValue, TemplateValueType};
+use mlua::{Lua, Table as LuaTable, Function as LuaFunction, Value as LuaValue};
 use crate::script::{
     engine_trait::{ChangeSet, ScriptValue},
     error::ScriptError,
@@ -53,10 +54,21 @@ impl LuaReactiveSystem {
             local function create_reactive_variable(name, initial_value)
                 local value = initial_value
                 return {
-                    get = function() return value end,
+                    get = function()
+                        if _typed_template_value then
+                            return _typed_template_value(name, value)
+                        end
+                        return value
+                    end,
                     set = function(new_value)
+                        -- Raises an error for values that aren't the variable's type
+                        if _coerce_template_value then
+                            new_value = _coerce_template_value(name, new_value)
+                        else
+                            new_value = tostring(new_value)
+                        end
                         if value ~= new_value then
-                            value = tostring(new_value)
+                            value = new_value
                             _template_variable_changes[name] = value
                             _template_variables[name] = value
                             -- Immediately notify the template engine for instant UI updates
@@ -148,6 +160,45 @@ impl LuaReactiveSystem {
         Ok(())
     }
     
+    /// Set the variables' declared types. Values assigned are coerced to
+    /// them, with an error raised in the script for one that isn't
+    /// compatible, and reads return booleans, numbers, strings (colors as
+    /// hex) or list tables.
+    pub fn set_types(&mut self, types: &HashMap<String, TemplateValueType>) -> Result<()> {
+        let globals = self.lua.globals();
+        
+        let type_names = self.lua.create_table()?;
+        for (name, value_type) in types {
+            type_names.set(name.as_str(), value_type.name())?;
+        }
+        globals.set("_template_variable_types", type_names)?;
+        
+        // `_coerce_template_value(name, value)`: the value's text as the variable's type
+        let coerce_types = types.clone();
+        globals.set("_coerce_template_value", self.lua.create_function(move |_, (name, value): (String, LuaValue)| {
+            let value_type = coerce_types.get(&name).copied().unwrap_or_default();
+            let value = template_value(&value).ok_or_else(|| {
+                mlua::Error::RuntimeError(format!("template variable '{}' can't hold a {}", name, value.type_name()))
+            })?;
+            value.coerce(value_type)
+                .map(|value| value.to_string())
+                .map_err(|error| mlua::Error::RuntimeError(format!("template variable '{}': {}", name, error)))
+        })?)?;
+        
+        // `_typed_template_value(name, text)`: the text read as the variable's type
+        let read_types = types.clone();
+        globals.set("_typed_template_value", self.lua.create_function(move |lua, (name, text): (String, String)| {
+            let value_type = read_types.get(&name).copied().unwrap_or_default();
+            match TemplateValue::parse(&text, value_type) {
+                Ok(value) => lua_value(lua, &value),
+                Err(_) => Ok(LuaValue::String(lua.create_string(&text)?)),
+            }
+        })?)?;
+        
+        tracing::debug!("Lua reactive variables typed for {} variables", types.len());
+        Ok(())
+    }
+    
     /// Get pending reactive variable changes (without clearing them)
     pub fn get_pending_changes(&mut self) -> Result<HashMap<String, ChangeSet>> {
         let mut changes = HashMap::new();
@@ -365,6 +416,37 @@ impl LuaReactiveSystem {
     }
 }
 
+/// A script's value as a template value; lists are sequence tables
+fn template_value(value: &LuaValue) -> Option<TemplateValue> {
+    match value {
+        LuaValue::Boolean(value) => Some(TemplateValue::Bool(*value)),
+        LuaValue::Integer(value) => Some(TemplateValue::Int(*value)),
+        LuaValue::Number(value) => Some(TemplateValue::Float(*value)),
+        LuaValue::String(value) => Some(TemplateValue::String(value.to_str().ok()?.to_string())),
+        LuaValue::Table(table) => table.clone().sequence_values::<LuaValue>()
+            .map(|item| template_value(&item.ok()?))
+            .collect::<Option<Vec<_>>>()
+            .map(TemplateValue::List),
+        _ => None,
+    }
+}
+
+fn lua_value<'lua>(lua: &'lua Lua, value: &TemplateValue) -> mlua::Result<LuaValue<'lua>> {
+    Ok(match value {
+        TemplateValue::Bool(value) => LuaValue::Boolean(*value),
+        TemplateValue::Int(value) => LuaValue::Number(*value as f64),
+        TemplateValue::Float(value) => LuaValue::Number(*value),
+        TemplateValue::List(items) => {
+            let table = lua.create_table()?;
+            for (index, item) in items.iter().enumerate() {
+                table.set(index + 1, lua_value(lua, item)?)?;
+            }
+            LuaValue::Table(table)
+        }
+        other => LuaValue::String(lua.create_string(other.to_string())?),
+    })
+}
+
 /// Statistics about the reactive variable system
 #[derive(Debug, Clone)]
 pub struct ReactiveSystemStats {
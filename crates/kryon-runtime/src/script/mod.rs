@@ -24,14 +24,28 @@
 
 use std::collections::HashMap;
 use anyhow::Result;
-use kryon_core::{ScriptEntry, Element, ElementId, PropertyValue, KRBFile};
+use kryon_core::{ScriptEntry, Element, ElementId, PropertyValue, KRBFile, TemplateValueType};
+use crate::element_handle::ElementChange;
+use crate::element_index::ElementIndex;
+use crate::dialogs::{DialogRequest, DialogResult};
+use crate::notifications::NotificationRequest;
+use crate::popups::PopupCommand;
+use crate::toasts::ToastCommand;
+use crate::analytics::AnalyticsCommand;
+use crate::process::{ProcessEvent, ProcessRequest};
+use crate::serial::SerialCommand;
+use crate::datasets::DataRequest;
+use crate::diagnostics::DumpRequest;
+use crate::net::{MqttCommand, MqttEvent, WsCommand, WsEvent};
 
 pub mod engine_trait;
 pub mod error;
+pub mod event;
 pub mod registry;
 pub mod lua;
 
 use engine_trait::{ScriptValue, BridgeData, ChangeSet};
+use event::AppEvent;
 use error::ScriptError;
 use registry::ScriptRegistry;
 
@@ -51,6 +65,8 @@ pub struct ScriptSystem {
     style_mappings: HashMap<u8, kryon_core::Style>,
     /// Bridge data for setting up new engines
     bridge_data: Option<BridgeData>,
+    /// Script functions that returned an error, for the metrics export
+    error_count: u64,
 }
 
 impl ScriptSystem {
@@ -64,6 +80,7 @@ impl ScriptSystem {
             elements_data: HashMap::new(),
             style_mappings: HashMap::new(),
             bridge_data: None,
+            error_count: 0,
         })
     }
     
@@ -121,12 +138,21 @@ impl ScriptSystem {
         let script_args: Vec<ScriptValue> = args.into_iter()
             .map(|pv| self.property_value_to_script_value(pv))
             .collect();
-        
+        self.call_function_with_values(function_name, script_args)
+    }
+    
+    /// Execute a function with arguments that are already script values, such
+    /// as an event object
+    pub fn call_function_with_values(&mut self, function_name: &str, script_args: Vec<ScriptValue>) -> Result<ScriptValue> {
         // Try to find the function in any of the active engines
         let mut result = None;
         for engine in self.registry.get_all_engines_mut() {
             if engine.has_function(function_name) {
-                result = Some(engine.call_function(function_name, script_args.clone())?);
+                let called = engine.call_function(function_name, script_args.clone());
+                if called.is_err() {
+                    self.error_count += 1;
+                }
+                result = Some(called?);
                 break;
             }
         }
@@ -137,6 +163,11 @@ impl ScriptSystem {
         }.into())
     }
     
+    /// Calls to script functions that failed so far
+    pub fn error_count(&self) -> u64 {
+        self.error_count
+    }
+    
     /// Initialize template variables for reactive updates
     pub fn initialize_template_variables(&mut self, variables: &HashMap<String, String>) -> Result<()> {
         self.template_variables = variables.clone();
@@ -149,6 +180,15 @@ impl ScriptSystem {
         Ok(())
     }
     
+    /// Set the declared type of each template variable in all engines, so
+    /// that values scripts assign are coerced to it
+    pub fn set_template_variable_types(&mut self, types: &HashMap<String, TemplateValueType>) -> Result<()> {
+        for engine in self.registry.get_all_engines_mut() {
+            engine.set_template_variable_types(types)?;
+        }
+        Ok(())
+    }
+    
     /// Execute initialization functions
     pub fn execute_init_functions(&mut self) -> Result<()> {
         // Execute onReady callbacks first
@@ -191,79 +231,312 @@ impl ScriptSystem {
         Ok(())
     }
     
-    /// Apply pending changes to elements
-    pub fn apply_pending_changes(&mut self, elements: &mut HashMap<ElementId, Element>) -> Result<bool> {
-        let changes = self.get_pending_changes()?;
-        self.apply_pending_dom_changes(elements, &changes)
+    /// Take file dialog requests queued by `kryon.dialog.*` in any engine
+    pub fn take_dialog_requests(&mut self) -> Vec<DialogRequest> {
+        self.take_host_queue("_take_pending_dialog_requests")
+            .iter()
+            .filter_map(|item| {
+                let request = DialogRequest::from_script_value(item);
+                if request.is_none() {
+                    tracing::warn!("Ignoring malformed dialog request: {}", item.to_string());
+                }
+                request
+            })
+            .collect()
     }
     
-    /// Apply pending DOM changes from a given change set
-    pub fn apply_pending_dom_changes(&mut self, elements: &mut HashMap<ElementId, Element>, changes: &HashMap<String, ChangeSet>) -> Result<bool> {
-        let mut any_changes = false;
+    /// Take notifications queued by `kryon.notify` in any engine
+    pub fn take_notification_requests(&mut self) -> Vec<NotificationRequest> {
+        self.take_host_queue("_take_pending_notifications")
+            .iter()
+            .filter_map(NotificationRequest::from_script_value)
+            .collect()
+    }
+    
+    /// Take `kryon.toast`, `kryon.dismiss_toast` and `kryon.configure_toasts`
+    /// calls queued in any engine
+    pub fn take_toast_commands(&mut self) -> Vec<ToastCommand> {
+        self.take_host_queue("_take_pending_toast_commands")
+            .iter()
+            .filter_map(ToastCommand::from_script_value)
+            .collect()
+    }
+    
+    /// Take `kryon.analytics` calls queued in any engine
+    pub fn take_analytics_commands(&mut self) -> Vec<AnalyticsCommand> {
+        self.take_host_queue("_take_pending_analytics_commands")
+            .iter()
+            .filter_map(AnalyticsCommand::from_script_value)
+            .collect()
+    }
+    
+    /// Take app-level events raised by `kryon.emit` in any engine
+    pub fn take_app_events(&mut self) -> Vec<AppEvent> {
+        self.take_host_queue("_take_pending_app_events")
+            .iter()
+            .filter_map(AppEvent::from_script_value)
+            .collect()
+    }
+    
+    /// Take `kryon.popup.*` calls queued in any engine
+    pub fn take_popup_commands(&mut self) -> Vec<PopupCommand> {
+        self.take_host_queue("_take_pending_popup_commands")
+            .iter()
+            .filter_map(PopupCommand::from_script_value)
+            .collect()
+    }
+    
+    /// Take the ids of the color inputs `kryon.eyedropper` was called for in any engine
+    pub fn take_eyedropper_requests(&mut self) -> Vec<String> {
+        self.take_host_queue("_take_pending_eyedropper_requests")
+            .iter()
+            .filter_map(|item| match item {
+                ScriptValue::Object(fields) => match fields.get("id") {
+                    Some(ScriptValue::String(id)) => Some(id.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+    
+    /// Drain a host API request queue by calling its bridge function in every engine
+    fn take_host_queue(&mut self, function_name: &str) -> Vec<ScriptValue> {
+        let mut items = Vec::new();
         
-        // Apply style changes
-        if let Some(style_changes) = changes.get("style_changes") {
-            for (element_id_str, style_id_str) in &style_changes.data {
-                if let (Ok(element_id), Ok(style_id)) = (element_id_str.parse::<ElementId>(), style_id_str.parse::<u8>()) {
-                    if let Some(element) = elements.get_mut(&element_id) {
-                        element.style_id = style_id;
-                        any_changes = true;
-                    }
-                }
+        for engine in self.registry.get_all_engines_mut() {
+            if !engine.has_function(function_name) {
+                continue;
+            }
+            match engine.call_function(function_name, vec![]) {
+                Ok(ScriptValue::Array(queued)) => items.extend(queued),
+                Ok(_) => {} // empty table
+                Err(e) => tracing::warn!("Failed to call {}: {}", function_name, e),
             }
         }
         
-        // Apply text changes
-        if let Some(text_changes) = changes.get("text_changes") {
-            for (element_id_str, new_text) in &text_changes.data {
-                if let Ok(element_id) = element_id_str.parse::<ElementId>() {
-                    if let Some(element) = elements.get_mut(&element_id) {
-                        element.text = new_text.clone();
-                        any_changes = true;
-                    }
-                }
+        items
+    }
+    
+    /// Take process launches queued by `kryon.process.*` in any engine
+    pub fn take_process_requests(&mut self) -> Vec<ProcessRequest> {
+        self.take_host_queue("_take_pending_process_requests")
+            .iter()
+            .filter_map(ProcessRequest::from_script_value)
+            .collect()
+    }
+    
+    /// Take serial port commands queued by `kryon.serial.*` in any engine
+    pub fn take_serial_commands(&mut self) -> Vec<SerialCommand> {
+        self.take_host_queue("_take_pending_serial_commands")
+            .iter()
+            .filter_map(SerialCommand::from_script_value)
+            .collect()
+    }
+    
+    /// Deliver a serial port event (`_deliver_serial_event(id, kind, value)` arguments)
+    pub fn deliver_serial_event(&mut self, args: Vec<ScriptValue>) -> Result<()> {
+        self.call_host_callback("_deliver_serial_event", args)
+    }
+    
+    /// Take WebSocket commands queued by `kryon.ws.*` in any engine
+    pub fn take_ws_commands(&mut self) -> Vec<WsCommand> {
+        self.take_host_queue("_take_pending_ws_commands")
+            .iter()
+            .filter_map(WsCommand::from_script_value)
+            .collect()
+    }
+    
+    /// Deliver a WebSocket event to the socket object that owns it
+    pub fn deliver_ws_event(&mut self, event: &WsEvent) -> Result<()> {
+        self.call_host_callback("_deliver_ws_event", event.to_script_args())
+    }
+    
+    /// Take MQTT commands queued by `kryon.mqtt.*` in any engine
+    pub fn take_mqtt_commands(&mut self) -> Vec<MqttCommand> {
+        self.take_host_queue("_take_pending_mqtt_commands")
+            .iter()
+            .filter_map(MqttCommand::from_script_value)
+            .collect()
+    }
+    
+    /// Take data source refreshes queued by `kryon.data.refresh`; `None` means all sources
+    pub fn take_data_refreshes(&mut self) -> Vec<Option<String>> {
+        self.take_host_queue("_take_pending_data_refreshes")
+            .into_iter()
+            .map(|name| match name {
+                ScriptValue::String(name) => Some(name),
+                _ => None,
+            })
+            .collect()
+    }
+    
+    /// Take dataset loads and table sort/filter requests queued by `kryon.data.*`
+    pub fn take_data_requests(&mut self) -> Vec<DataRequest> {
+        self.take_host_queue("_take_pending_data_requests")
+            .iter()
+            .filter_map(DataRequest::from_script_value)
+            .collect()
+    }
+    
+    /// Memory statistics of each active engine, by language
+    pub fn memory_stats(&self) -> Vec<(String, engine_trait::EngineMemoryStats)> {
+        self.registry.engine_memory()
+    }
+    
+    /// Publish the snapshot returned by `kryon.memory_report()`
+    pub fn set_memory_report(&mut self, report: ScriptValue) -> Result<()> {
+        self.call_host_callback("_set_memory_report", vec![report])
+    }
+    
+    pub fn set_monitors(&mut self, monitors: ScriptValue) -> Result<()> {
+        self.call_host_callback("_set_monitors", vec![monitors])
+    }
+    
+    /// Publish the locale `kryon.format` formats with
+    pub fn set_locale(&mut self, locale: ScriptValue) -> Result<()> {
+        self.call_host_callback("_set_locale", vec![locale])
+    }
+    
+    /// Calls the `kryon.watch` callbacks with a frame's template variable
+    /// changes, a list of `{ name =, old =, new = }`
+    pub fn deliver_variable_changes(&mut self, changes: ScriptValue) -> Result<()> {
+        self.call_host_callback("_deliver_variable_changes", vec![changes])
+    }
+    
+    /// Updates `kryon.orientation()`, calling the change listeners if it differs
+    pub fn set_orientation(&mut self, orientation: kryon_core::Orientation) -> Result<()> {
+        self.call_host_callback("_set_orientation", vec![orientation.name().into()])
+    }
+    
+    pub fn take_diagnostic_dumps(&mut self) -> Vec<DumpRequest> {
+        self.take_host_queue("_take_pending_diagnostic_dumps")
+            .iter()
+            .filter_map(DumpRequest::from_script_value)
+            .collect()
+    }
+    
+    /// Deliver the written bundle's path (or the error) to `kryon.diagnostics.dump`'s callback
+    pub fn deliver_diagnostic_dump(&mut self, id: u64, result: std::result::Result<std::path::PathBuf, String>) -> Result<()> {
+        let (path, error) = match result {
+            Ok(path) => (ScriptValue::String(path.display().to_string()), ScriptValue::Nil),
+            Err(e) => (ScriptValue::Nil, ScriptValue::String(e)),
+        };
+        self.call_host_callback("_deliver_diagnostic_dump", vec![ScriptValue::Integer(id as i64), path, error])
+    }
+    
+    /// Deliver a loaded dataset (or the load error) to the script callback
+    pub fn deliver_data_result(&mut self, id: u64, dataset: ScriptValue, error: Option<String>) -> Result<()> {
+        let error = error.map(ScriptValue::String).unwrap_or(ScriptValue::Nil);
+        self.call_host_callback("_deliver_data_result", vec![ScriptValue::Integer(id as i64), dataset, error])
+    }
+    
+    /// Deliver an MQTT connection event or message to the client that owns it
+    pub fn deliver_mqtt_event(&mut self, event: &MqttEvent) -> Result<()> {
+        self.call_host_callback("_deliver_mqtt_event", event.to_script_args())
+    }
+    
+    /// Tell the script a toast with an action left, running the action
+    /// callback if it was `pressed`
+    pub fn finish_toast(&mut self, id: u64, pressed: bool) -> Result<()> {
+        self.call_host_callback("_finish_toast", vec![ScriptValue::Integer(id as i64), ScriptValue::Boolean(pressed)])
+    }
+    
+    /// Deliver a completed dialog to the script callback that requested it
+    pub fn deliver_dialog_result(&mut self, result: &DialogResult) -> Result<()> {
+        self.call_host_callback("_deliver_dialog_result", result.to_script_args())
+    }
+    
+    /// Deliver process output or exit status to the script that started it
+    pub fn deliver_process_event(&mut self, event: &ProcessEvent) -> Result<()> {
+        self.call_host_callback("_deliver_process_event", event.to_script_args())
+    }
+    
+    /// Call a host API delivery function in every engine that defines it.
+    /// Engines ignore ids they did not issue.
+    /// Evaluate code in the live engine for `language`, creating it if needed
+    pub fn evaluate(&mut self, language: &str, code: &str) -> Result<ScriptValue> {
+        let engine_exists = self.registry.get_engine(language).is_some();
+        let engine = self.registry.get_or_create_engine(language)?;
+        if !engine_exists {
+            if let Some(bridge_data) = &self.bridge_data {
+                engine.setup_bridge(bridge_data)?;
             }
         }
-        
-        // Apply visibility changes
-        if let Some(visibility_changes) = changes.get("visibility_changes") {
-            for (element_id_str, visible_str) in &visibility_changes.data {
-                if let (Ok(element_id), Ok(visible)) = (element_id_str.parse::<ElementId>(), visible_str.parse::<bool>()) {
-                    if let Some(element) = elements.get_mut(&element_id) {
-                        element.visible = visible;
-                        any_changes = true;
-                    }
-                }
+        engine.execute_code(code)
+    }
+    
+    /// Set a template variable as a script assignment would, so reactive
+    /// script variables see the new value
+    pub fn set_template_variable(&mut self, name: &str, value: &str) -> Result<()> {
+        self.template_variables.insert(name.to_string(), value.to_string());
+        self.call_host_callback("_set_template_variable", vec![
+            ScriptValue::String(name.to_string()),
+            ScriptValue::String(value.to_string()),
+        ])
+    }
+    
+    fn call_host_callback(&mut self, function_name: &str, args: Vec<ScriptValue>) -> Result<()> {
+        for engine in self.registry.get_all_engines_mut() {
+            if engine.has_function(function_name) {
+                engine.call_function(function_name, args.clone())?;
             }
         }
-        
-        // Apply state changes (checked, etc.)
-        if let Some(state_changes) = changes.get("state_changes") {
-            for (element_id_str, checked_str) in &state_changes.data {
-                if let (Ok(element_id), Ok(checked)) = (element_id_str.parse::<ElementId>(), checked_str.parse::<bool>()) {
-                    if let Some(element) = elements.get_mut(&element_id) {
-                        use kryon_core::InteractionState;
-                        element.current_state = if checked {
-                            InteractionState::Checked
-                        } else {
-                            InteractionState::Normal
-                        };
-                        any_changes = true;
-                    }
-                }
+        Ok(())
+    }
+    
+    /// Apply pending changes to elements
+    pub fn apply_pending_changes(&mut self, elements: &mut HashMap<ElementId, Element>) -> Result<bool> {
+        let changes = self.get_pending_changes()?;
+        self.apply_pending_dom_changes(elements, &changes)
+    }
+    
+    /// Apply pending DOM changes from a given change set
+    pub fn apply_pending_dom_changes(&mut self, elements: &mut HashMap<ElementId, Element>, changes: &HashMap<String, ChangeSet>) -> Result<bool> {
+        let mut any_changes = false;
+        for (element_id, change) in Self::element_changes(changes) {
+            if let Some(element) = elements.get_mut(&element_id) {
+                change.apply(element)?;
+                any_changes = true;
             }
         }
         
         // Refresh elements data in engines if changes were made
         if any_changes {
-            self.elements_data = elements.clone();
-            let bridge_data = self.create_bridge_data_from_stored(elements)?;
-            self.registry.setup_bridge_for_all_engines(&bridge_data)?;
+            self.refresh_elements(elements)?;
         }
         
         Ok(any_changes)
     }
     
+    /// The queued style, text, visibility and checked changes, in that
+    /// order, as the changes `ElementHandle` makes
+    fn element_changes(changes: &HashMap<String, ChangeSet>) -> Vec<(ElementId, ElementChange)> {
+        let mut element_changes = Vec::new();
+        for change_type in ["style_changes", "text_changes", "visibility_changes", "state_changes"] {
+            let Some(change_set) = changes.get(change_type) else { continue };
+            for (element_id, value) in &change_set.data {
+                let change = match change_type {
+                    "style_changes" => value.parse().ok().map(ElementChange::Style),
+                    "text_changes" => Some(ElementChange::Text(value.clone())),
+                    "visibility_changes" => value.parse().ok().map(ElementChange::Visible),
+                    _ => value.parse().ok().map(ElementChange::Checked),
+                };
+                if let (Ok(element_id), Some(change)) = (element_id.parse(), change) {
+                    element_changes.push((element_id, change));
+                }
+            }
+        }
+        element_changes
+    }
+    
+    /// Push element data changed outside of scripts to every engine
+    pub fn refresh_elements(&mut self, elements: &HashMap<ElementId, Element>) -> Result<()> {
+        self.elements_data = elements.clone();
+        let bridge_data = self.create_bridge_data_from_stored(elements)?;
+        self.registry.setup_bridge_for_all_engines(&bridge_data)
+    }
+    
     /// Get all function names from all engines
     fn get_all_function_names(&self) -> Vec<String> {
         let mut all_functions = Vec::new();
@@ -275,13 +548,8 @@ impl ScriptSystem {
     
     /// Create bridge data for DOM API
     fn create_bridge_data(&self, krb_file: &KRBFile, elements: &HashMap<ElementId, Element>) -> Result<BridgeData> {
-        // Create element ID mappings
-        let mut element_ids = HashMap::new();
-        for (element_id, element) in elements {
-            if !element.id.is_empty() {
-                element_ids.insert(element.id.clone(), *element_id);
-            }
-        }
+        // Create element ID mappings; repeated ids resolve to the first element
+        let element_ids = ElementIndex::from_elements(elements).ids().clone();
         
         // Create style ID mappings
         let mut style_ids = HashMap::new();
@@ -317,13 +585,8 @@ impl ScriptSystem {
     
     /// Create bridge data using stored style mappings
     fn create_bridge_data_from_stored(&self, elements: &HashMap<ElementId, Element>) -> Result<BridgeData> {
-        // Create element ID mappings
-        let mut element_ids = HashMap::new();
-        for (element_id, element) in elements {
-            if !element.id.is_empty() {
-                element_ids.insert(element.id.clone(), *element_id);
-            }
-        }
+        // Create element ID mappings; repeated ids resolve to the first element
+        let element_ids = ElementIndex::from_elements(elements).ids().clone();
         
         // Create style ID mappings using stored style mappings
         let mut style_ids = HashMap::new();
@@ -7,7 +7,7 @@
 use std::collections::HashMap;
 use anyhow::Result;
 use crate::script::{
-    engine_trait::{ScriptEngine, ScriptEngineFactory, BridgeData},
+    engine_trait::{ScriptEngine, ScriptEngineFactory, BridgeData, EngineMemoryStats},
     error::ScriptError,
     lua::LuaEngineFactory,
 };
@@ -260,6 +260,15 @@ impl ScriptRegistry {
         }
     }
     
+    /// Memory statistics of each active engine, by language
+    pub fn engine_memory(&self) -> Vec<(String, EngineMemoryStats)> {
+        let mut stats: Vec<(String, EngineMemoryStats)> = self.engines.iter()
+            .map(|(language, engine)| (language.clone(), engine.get_memory_usage()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+    
     /// Validate configuration
     pub fn validate_config(&self) -> Result<()> {
         // Check if default language is supported
@@ -0,0 +1,184 @@
+// crates/kryon-runtime/src/scroll.rs
+//! Scroll offsets of `overflow: scroll` and `overflow: auto` containers.
+//!
+//! Layout places content as if nothing were scrolled. After each layout the
+//! content of every scroll container is measured and its descendants are
+//! moved up and left by the container's offset; a wheel event changes the
+//! offset of the innermost container under the pointer that can still move
+//! and shifts its descendants by the difference. Offsets are clamped so
+//! content never scrolls past its end.
+
+use glam::Vec2;
+use kryon_core::{Element, ElementId, OverflowType};
+use kryon_layout::LayoutResult;
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct ScrollState {
+    offsets: HashMap<ElementId, Vec2>,
+    /// Furthest each scroll container can scroll, from the last layout
+    limits: HashMap<ElementId, Vec2>,
+}
+
+impl ScrollState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How far the container's content is scrolled, in pixels
+    pub fn offset(&self, element_id: ElementId) -> Vec2 {
+        self.offsets.get(&element_id).copied().unwrap_or(Vec2::ZERO)
+    }
+
+    /// Offsets of every scrolled container
+    pub fn offsets(&self) -> &HashMap<ElementId, Vec2> {
+        &self.offsets
+    }
+
+    /// Measures the scroll containers in a fresh, unscrolled layout, clamps
+    /// their offsets to it and moves their content by them.
+    pub fn apply_layout(&mut self, elements: &mut HashMap<ElementId, Element>, layout: &mut LayoutResult) {
+        // A container whose children weren't laid out keeps its last limit
+        let previous = std::mem::take(&mut self.limits);
+        self.limits = elements.iter()
+            .filter(|(_, element)| scrolls(element.overflow_x) || scrolls(element.overflow_y))
+            .filter_map(|(&element_id, element)| {
+                let limit = scroll_limit(element_id, element, layout).or_else(|| previous.get(&element_id).copied())?;
+                Some((element_id, limit))
+            })
+            .collect();
+        let limits = &self.limits;
+        self.offsets.retain(|element_id, offset| match limits.get(element_id) {
+            Some(limit) => {
+                *offset = offset.clamp(Vec2::ZERO, *limit);
+                *offset != Vec2::ZERO
+            }
+            None => false,
+        });
+        for (&element_id, &offset) in &self.offsets {
+            shift_descendants(elements, layout, element_id, -offset);
+        }
+    }
+
+    /// Scrolls by `delta` pixels (positive to reveal content further down or
+    /// right), moving the innermost container on `path` (innermost first)
+    /// that isn't already at its end. Returns whether anything moved.
+    pub fn scroll(
+        &mut self,
+        path: &[ElementId],
+        delta: Vec2,
+        elements: &mut HashMap<ElementId, Element>,
+        layout: &mut LayoutResult,
+    ) -> bool {
+        for &element_id in path {
+            let Some(&limit) = self.limits.get(&element_id) else {
+                continue;
+            };
+            let offset = self.offset(element_id);
+            let scrolled = (offset + delta).clamp(Vec2::ZERO, limit);
+            if scrolled == offset {
+                continue;
+            }
+            shift_descendants(elements, layout, element_id, offset - scrolled);
+            if scrolled == Vec2::ZERO {
+                self.offsets.remove(&element_id);
+            } else {
+                self.offsets.insert(element_id, scrolled);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+fn scrolls(overflow: OverflowType) -> bool {
+    matches!(overflow, OverflowType::Scroll | OverflowType::Auto)
+}
+
+/// How far the container's children reach past its box, on the axes it
+/// scrolls. `None` if it or its children weren't laid out.
+fn scroll_limit(element_id: ElementId, element: &Element, layout: &LayoutResult) -> Option<Vec2> {
+    let position = *layout.computed_positions.get(&element_id)?;
+    let size = *layout.computed_sizes.get(&element_id)?;
+    if !element.children.is_empty() && element.children.iter().all(|child_id| !layout.computed_positions.contains_key(child_id)) {
+        return None;
+    }
+    let content = element.children.iter()
+        .filter_map(|child_id| Some(*layout.computed_positions.get(child_id)? + *layout.computed_sizes.get(child_id)?))
+        .fold(Vec2::ZERO, |extent, child_end| extent.max(child_end - position));
+    let overflow = (content - size).max(Vec2::ZERO);
+    Some(Vec2::new(
+        if scrolls(element.overflow_x) { overflow.x } else { 0.0 },
+        if scrolls(element.overflow_y) { overflow.y } else { 0.0 },
+    ))
+}
+
+fn shift_descendants(elements: &mut HashMap<ElementId, Element>, layout: &mut LayoutResult, element_id: ElementId, shift: Vec2) {
+    let mut pending = elements.get(&element_id).map(|element| element.children.clone()).unwrap_or_default();
+    while let Some(child_id) = pending.pop() {
+        if let Some(position) = layout.computed_positions.get_mut(&child_id) {
+            *position += shift;
+        }
+        if let Some(child) = elements.get_mut(&child_id) {
+            child.position += shift;
+            pending.extend(child.children.iter().copied());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 100px tall scroll container at (0, 50) holding a 300px tall list
+    /// with one row inside
+    fn scrolled_list() -> (HashMap<ElementId, Element>, LayoutResult) {
+        let scroller = Element { children: vec![2], overflow_y: OverflowType::Scroll, ..Default::default() };
+        let list = Element { parent: Some(1), children: vec![3], position: Vec2::new(0.0, 50.0), ..Default::default() };
+        let row = Element { parent: Some(2), ..Default::default() };
+        let elements = [(1, scroller), (2, list), (3, row)].into_iter().collect();
+        let layout = LayoutResult {
+            computed_positions: [(1, Vec2::new(0.0, 50.0)), (2, Vec2::new(0.0, 50.0)), (3, Vec2::new(0.0, 250.0))].into_iter().collect(),
+            computed_sizes: [(1, Vec2::new(200.0, 100.0)), (2, Vec2::new(200.0, 300.0)), (3, Vec2::new(200.0, 20.0))].into_iter().collect(),
+        };
+        (elements, layout)
+    }
+
+    #[test]
+    fn wheel_moves_content_and_stops_at_the_end() {
+        let (mut elements, mut layout) = scrolled_list();
+        let mut scroll = ScrollState::new();
+        scroll.apply_layout(&mut elements, &mut layout);
+
+        assert!(scroll.scroll(&[3, 2, 1], Vec2::new(0.0, 120.0), &mut elements, &mut layout));
+        assert_eq!(scroll.offset(1), Vec2::new(0.0, 120.0));
+        assert_eq!(layout.computed_positions[&3], Vec2::new(0.0, 130.0));
+        assert_eq!(elements[&2].position, Vec2::new(0.0, -70.0));
+
+        // 300px of content in a 100px box scrolls at most 200px, and only vertically
+        assert!(scroll.scroll(&[3, 2, 1], Vec2::new(50.0, 500.0), &mut elements, &mut layout));
+        assert_eq!(scroll.offset(1), Vec2::new(0.0, 200.0));
+        assert!(!scroll.scroll(&[3, 2, 1], Vec2::new(0.0, 10.0), &mut elements, &mut layout));
+        assert_eq!(layout.computed_positions[&1], Vec2::new(0.0, 50.0));
+    }
+
+    #[test]
+    fn offsets_are_reapplied_and_clamped_after_layout() {
+        let (mut elements, mut layout) = scrolled_list();
+        let mut scroll = ScrollState::new();
+        scroll.apply_layout(&mut elements, &mut layout);
+        scroll.scroll(&[1], Vec2::new(0.0, 150.0), &mut elements, &mut layout);
+
+        // The list shrank to 180px: at most 80px of scrolling is left
+        let (mut elements, mut layout) = scrolled_list();
+        layout.computed_sizes.insert(2, Vec2::new(200.0, 180.0));
+        scroll.apply_layout(&mut elements, &mut layout);
+        assert_eq!(scroll.offset(1), Vec2::new(0.0, 80.0));
+        assert_eq!(layout.computed_positions[&3], Vec2::new(0.0, 170.0));
+
+        // Nothing left to scroll drops the offset
+        layout.computed_sizes.insert(2, Vec2::new(200.0, 90.0));
+        scroll.apply_layout(&mut elements, &mut layout);
+        assert!(scroll.offsets().is_empty());
+    }
+}
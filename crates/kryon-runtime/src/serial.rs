@@ -0,0 +1,233 @@
+// crates/kryon-runtime/src/serial.rs
+//! Script bindings for serial devices (`kryon.serial.*`).
+//!
+//! The ports themselves live in `kryon-io` and are only compiled in with the
+//! `embedded-io` feature. Without it, opening a port reports an error and a
+//! close event so scripts can degrade gracefully.
+
+use crate::script::engine_trait::ScriptValue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialCommand {
+    Open {
+        id: u64,
+        path: String,
+        baud_rate: u32,
+        /// `line`, `raw`, `length` or a delimiter byte like `0x03`
+        framing: String,
+        reconnect: bool,
+    },
+    Write { id: u64, data: Vec<u8> },
+    Close { id: u64 },
+}
+
+impl SerialCommand {
+    /// Parses a command queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+
+        let id = match fields.get("id")? {
+            ScriptValue::Integer(id) if *id > 0 => *id as u64,
+            ScriptValue::Number(id) if *id > 0.0 => *id as u64,
+            _ => return None,
+        };
+
+        match fields.get("op")? {
+            ScriptValue::String(op) if op == "open" => {
+                let path = match fields.get("path")? {
+                    ScriptValue::String(path) if !path.is_empty() => path.clone(),
+                    _ => return None,
+                };
+                let baud_rate = match fields.get("baud") {
+                    Some(ScriptValue::Integer(baud)) if *baud > 0 => *baud as u32,
+                    Some(ScriptValue::Number(baud)) if *baud > 0.0 => *baud as u32,
+                    _ => 9600,
+                };
+                let framing = match fields.get("framing") {
+                    Some(ScriptValue::String(framing)) => framing.clone(),
+                    _ => "line".to_string(),
+                };
+                let reconnect = fields.get("reconnect").map(|v| v.to_bool()).unwrap_or(true);
+                Some(SerialCommand::Open { id, path, baud_rate, framing, reconnect })
+            }
+            ScriptValue::String(op) if op == "write" => {
+                let data = match fields.get("data")? {
+                    ScriptValue::Bytes(bytes) => bytes.clone(),
+                    ScriptValue::Array(items) => items.iter()
+                        .filter_map(|item| match item {
+                            ScriptValue::Integer(byte) => u8::try_from(*byte).ok(),
+                            ScriptValue::Number(byte) => Some(*byte as u8),
+                            _ => None,
+                        })
+                        .collect(),
+                    other => other.to_string().into_bytes(),
+                };
+                Some(SerialCommand::Write { id, data })
+            }
+            ScriptValue::String(op) if op == "close" => Some(SerialCommand::Close { id }),
+            _ => None,
+        }
+    }
+}
+
+/// Arguments for the bridge's `_deliver_serial_event(id, kind, value)`.
+fn event_args(id: u64, kind: &str, value: ScriptValue) -> Vec<ScriptValue> {
+    vec![ScriptValue::Integer(id as i64), ScriptValue::from(kind), value]
+}
+
+/// Text frames become strings when they are valid UTF-8.
+#[cfg_attr(not(feature = "embedded-io"), allow(dead_code))]
+fn frame_value(data: Vec<u8>, text: bool) -> ScriptValue {
+    if text {
+        match String::from_utf8(data) {
+            Ok(text) => ScriptValue::String(text),
+            Err(e) => ScriptValue::Bytes(e.into_bytes()),
+        }
+    } else {
+        ScriptValue::Bytes(data)
+    }
+}
+
+pub struct SerialBridge {
+    #[cfg(feature = "embedded-io")]
+    manager: kryon_io::SerialManager,
+    /// Connections whose frames are delivered as strings
+    #[cfg(feature = "embedded-io")]
+    text_frames: std::collections::HashSet<u64>,
+    /// Events generated synchronously (bad commands, missing feature)
+    pending: Vec<Vec<ScriptValue>>,
+}
+
+impl SerialBridge {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "embedded-io")]
+            manager: kryon_io::SerialManager::new(),
+            #[cfg(feature = "embedded-io")]
+            text_frames: std::collections::HashSet::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "embedded-io")]
+    pub fn handle(&mut self, command: SerialCommand) {
+        match command {
+            SerialCommand::Open { id, path, baud_rate, framing, reconnect } => {
+                let Some(framing) = kryon_io::Framing::parse(&framing) else {
+                    self.pending.push(event_args(id, "error", format!("unknown framing '{}'", framing).into()));
+                    self.pending.push(event_args(id, "close", ScriptValue::Nil));
+                    return;
+                };
+                eprintln!("[SERIAL] Opening {} at {} baud ({:?})", path, baud_rate, framing);
+                if matches!(framing, kryon_io::Framing::Line | kryon_io::Framing::Delimiter(_)) {
+                    self.text_frames.insert(id);
+                }
+                let config = kryon_io::SerialConfig::new(path, baud_rate)
+                    .with_framing(framing)
+                    .with_reconnect(reconnect);
+                self.manager.open(id, config);
+            }
+            SerialCommand::Write { id, data } => {
+                if let Err(e) = self.manager.write(id, data) {
+                    self.pending.push(event_args(id, "error", e.to_string().into()));
+                }
+            }
+            SerialCommand::Close { id } => self.manager.close(id),
+        }
+    }
+
+    #[cfg(not(feature = "embedded-io"))]
+    pub fn handle(&mut self, command: SerialCommand) {
+        if let SerialCommand::Open { id, .. } = command {
+            self.pending.push(event_args(id, "error", "serial IO not available (built without the `embedded-io` feature)".into()));
+            self.pending.push(event_args(id, "close", ScriptValue::Nil));
+        }
+    }
+
+    /// Script callback arguments for everything that happened since the last poll.
+    pub fn poll(&mut self) -> Vec<Vec<ScriptValue>> {
+        #[allow(unused_mut)]
+        let mut events = std::mem::take(&mut self.pending);
+
+        #[cfg(feature = "embedded-io")]
+        for event in self.manager.poll() {
+            events.push(match event {
+                kryon_io::SerialEvent::Opened { id } => event_args(id, "open", ScriptValue::Nil),
+                kryon_io::SerialEvent::Frame { id, data } => {
+                    event_args(id, "data", frame_value(data, self.text_frames.contains(&id)))
+                }
+                kryon_io::SerialEvent::Error { id, message } => event_args(id, "error", message.into()),
+                kryon_io::SerialEvent::Closed { id } => {
+                    self.text_frames.remove(&id);
+                    event_args(id, "close", ScriptValue::Nil)
+                }
+            });
+        }
+
+        events
+    }
+}
+
+impl Default for SerialBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn command(fields: Vec<(&str, ScriptValue)>) -> Option<SerialCommand> {
+        let fields: HashMap<String, ScriptValue> = fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        SerialCommand::from_script_value(&ScriptValue::Object(fields))
+    }
+
+    #[test]
+    fn parses_open_with_defaults() {
+        let open = command(vec![("id", ScriptValue::Integer(1)), ("op", "open".into()), ("path", "/dev/ttyUSB0".into())]);
+        assert_eq!(open, Some(SerialCommand::Open {
+            id: 1,
+            path: "/dev/ttyUSB0".into(),
+            baud_rate: 9600,
+            framing: "line".into(),
+            reconnect: true,
+        }));
+    }
+
+    #[test]
+    fn parses_writes_from_strings_bytes_and_byte_lists() {
+        let text = command(vec![("id", ScriptValue::Integer(2)), ("op", "write".into()), ("data", "AT".into())]);
+        assert_eq!(text, Some(SerialCommand::Write { id: 2, data: b"AT".to_vec() }));
+
+        let list = command(vec![
+            ("id", ScriptValue::Integer(2)),
+            ("op", "write".into()),
+            ("data", ScriptValue::Array(vec![ScriptValue::Integer(0x02), ScriptValue::Integer(0xFF)])),
+        ]);
+        assert_eq!(list, Some(SerialCommand::Write { id: 2, data: vec![0x02, 0xFF] }));
+
+        assert!(command(vec![("id", ScriptValue::Integer(2)), ("op", "flush".into())]).is_none());
+    }
+
+    #[test]
+    fn text_frames_fall_back_to_bytes() {
+        assert_eq!(frame_value(b"OK".to_vec(), true), ScriptValue::String("OK".into()));
+        assert_eq!(frame_value(vec![0xFF], true), ScriptValue::Bytes(vec![0xFF]));
+        assert_eq!(frame_value(b"OK".to_vec(), false), ScriptValue::Bytes(b"OK".to_vec()));
+    }
+
+    #[cfg(not(feature = "embedded-io"))]
+    #[test]
+    fn open_without_feature_reports_error_and_close() {
+        let mut bridge = SerialBridge::new();
+        bridge.handle(SerialCommand::Open { id: 5, path: "/dev/null".into(), baud_rate: 9600, framing: "line".into(), reconnect: false });
+        let events = bridge.poll();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0][1], ScriptValue::from("error"));
+        assert_eq!(events[1][1], ScriptValue::from("close"));
+    }
+}
@@ -1,49 +1,99 @@
 // crates/kryon-runtime/src/template_engine.rs
 
-use kryon_core::{KRBFile, Element, ElementId, TemplateVariable, TemplateBinding};
+use crate::formatting;
+use crate::i18n::{I18n, Locale};
+use kryon_core::{KRBFile, Element, ElementId, TemplateVariable, TemplateBinding, TemplateValue, TemplateValueError, TemplateValueType};
 use std::collections::HashMap;
 use regex::Regex;
 
 /// Template evaluation engine that handles variable substitution and reactive updates
 pub struct TemplateEngine {
-    /// Template variables with their current values
+    /// Template variables with their current values, as text in the form
+    /// their type displays
     variables: HashMap<String, String>,
+    /// Each variable's declared type, which values set are coerced to
+    types: HashMap<String, TemplateValueType>,
     /// Template bindings from KRB file
     bindings: Vec<TemplateBinding>,
     /// Template variables from KRB file
     template_variables: Vec<TemplateVariable>,
-    /// Compiled regex for template variable extraction
+    /// Compiled regex for template variable extraction, with an optional
+    /// format filter such as `|currency(EUR)`
     template_regex: Regex,
+    /// Conventions for format filters
+    locale: Locale,
 }
 
 impl TemplateEngine {
     /// Create a new template engine from KRB file data
     pub fn new(krb_file: &KRBFile) -> Self {
-        let template_regex = Regex::new(r"\$([a-zA-Z_][a-zA-Z0-9_]*)").unwrap();
+        let template_regex = Regex::new(r"\$([a-zA-Z_][a-zA-Z0-9_]*)(?:\|(number|currency|date|relative)(?:\(([^)]*)\))?)?").unwrap();
         
         // Initialize variables with their default values
         let mut variables = HashMap::new();
+        let mut types = HashMap::new();
         for template_var in &krb_file.template_variables {
-            variables.insert(template_var.name.clone(), template_var.default_value.clone());
+            let value_type = template_var.template_type();
+            let default_value = match TemplateValue::parse(&template_var.default_value, value_type) {
+                Ok(value) => value.to_string(),
+                Err(error) => {
+                    eprintln!("[TEMPLATE] Default of '{}' kept as it is: {}", template_var.name, error);
+                    template_var.default_value.clone()
+                }
+            };
+            variables.insert(template_var.name.clone(), default_value);
+            types.insert(template_var.name.clone(), value_type);
         }
         
         Self {
             variables,
+            types,
             bindings: krb_file.template_bindings.clone(),
             template_variables: krb_file.template_variables.clone(),
             template_regex,
+            locale: I18n::new().locale().clone(),
         }
     }
     
-    /// Set a template variable value
+    /// Sets the locale format filters use. Bindings are evaluated with it
+    /// from the next `update_elements`.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+    
+    /// Set a template variable value, read as the variable's type. A value
+    /// that isn't one is logged and leaves the variable as it was.
     pub fn set_variable(&mut self, name: &str, value: &str) -> bool {
-        if let Some(var_value) = self.variables.get_mut(name) {
-            if *var_value != value {
-                *var_value = value.to_string();
-                return true; // Variable changed
+        match self.set_typed_variable(name, TemplateValue::String(value.to_string())) {
+            Ok(changed) => changed,
+            Err(error) => {
+                eprintln!("[TEMPLATE] Not setting '{}': {}", name, error);
+                false
             }
         }
-        false // Variable not found or unchanged
+    }
+    
+    /// Set a template variable to `value` coerced to its type. Returns
+    /// whether it changed; unknown variables are left alone.
+    pub fn set_typed_variable(&mut self, name: &str, value: TemplateValue) -> Result<bool, TemplateValueError> {
+        let Some(value_type) = self.types.get(name).copied() else {
+            return Ok(false);
+        };
+        let text = value.coerce(value_type)?.to_string();
+        let var_value = self.variables.get_mut(name).expect("typed variables have values");
+        if *var_value == text {
+            return Ok(false);
+        }
+        *var_value = text;
+        Ok(true)
+    }
+    
+    /// `value` as variable `name`'s type, or as it is for unknown variables
+    pub fn coerce(&self, name: &str, value: TemplateValue) -> Result<TemplateValue, TemplateValueError> {
+        match self.types.get(name) {
+            Some(value_type) => value.coerce(*value_type),
+            None => Ok(value),
+        }
     }
     
     /// Get a template variable value
@@ -51,27 +101,41 @@ impl TemplateEngine {
         self.variables.get(name).map(|s| s.as_str())
     }
     
+    /// Get a template variable value as its type. A default that wasn't
+    /// one comes back as a string.
+    pub fn get_typed_variable(&self, name: &str) -> Option<TemplateValue> {
+        let text = self.variables.get(name)?;
+        Some(TemplateValue::parse(text, self.variable_type(name)?)
+            .unwrap_or_else(|_| TemplateValue::String(text.clone())))
+    }
+    
+    /// A template variable's declared type
+    pub fn variable_type(&self, name: &str) -> Option<TemplateValueType> {
+        self.types.get(name).copied()
+    }
+    
+    /// Every template variable's declared type
+    pub fn variable_types(&self) -> &HashMap<String, TemplateValueType> {
+        &self.types
+    }
+    
     /// Get all template variables
     pub fn get_variables(&self) -> &HashMap<String, String> {
         &self.variables
     }
     
-    /// Evaluate a template expression by substituting variables
+    /// Evaluate a template expression by substituting variables, formatted
+    /// by their filter if they have one. A value the filter can't format is
+    /// substituted as it is; unknown variables are left in place.
     pub fn evaluate_expression(&self, expression: &str) -> String {
-        let mut result = expression.to_string();
-        
-        // Replace all $variable patterns with their values
-        for capture in self.template_regex.captures_iter(expression) {
-            if let Some(var_name) = capture.get(1) {
-                let var_name_str = var_name.as_str();
-                if let Some(value) = self.variables.get(var_name_str) {
-                    let pattern = format!("${}", var_name_str);
-                    result = result.replace(&pattern, value);
-                }
-            }
-        }
-        
-        result
+        self.template_regex.replace_all(expression, |capture: &regex::Captures| {
+            let Some(value) = self.variables.get(&capture[1]) else {
+                return capture[0].to_string();
+            };
+            capture.get(2)
+                .and_then(|filter| formatting::apply_filter(&self.locale, value, filter.as_str(), capture.get(3).map(|argument| argument.as_str())))
+                .unwrap_or_else(|| value.clone())
+        }).into_owned()
     }
     
     /// Update all elements that have template bindings
@@ -185,6 +249,7 @@ mod tests {
                 resource_count: 0,
                 template_variable_count: 1,
                 template_binding_count: 1,
+                transform_count: 0,
             },
             strings: vec![],
             elements: HashMap::new(),
@@ -194,6 +259,9 @@ mod tests {
             scripts: vec![],
             template_variables,
             template_bindings,
+            transforms: vec![],
+            fonts: HashMap::new(),
+            compatibility: Default::default(),
         }
     }
     
@@ -234,6 +302,54 @@ mod tests {
         assert_eq!(result, "Value is 42 items");
     }
     
+    #[test]
+    fn filters_format_with_the_locale() {
+        let mut krb_file = create_test_krb_file();
+        krb_file.template_variables.push(TemplateVariable {
+            name: "total".to_string(),
+            value_type: 1,
+            default_value: "1234.5".to_string(),
+        });
+        let mut engine = TemplateEngine::new(&krb_file);
+        
+        assert_eq!(engine.evaluate_expression("Total: $total|currency(EUR), $total|number"), "Total: €1,234.50, 1,234.5");
+        engine.set_variable("counter_value", "many");
+        assert_eq!(engine.evaluate_expression("$counter_value|number, $missing|number"), "many, $missing|number");
+        
+        let mut i18n = I18n::new();
+        i18n.set_locale("de");
+        engine.set_locale(i18n.locale().clone());
+        assert_eq!(engine.evaluate_expression("$total|currency(EUR)"), "1.234,50 €");
+        assert_eq!(engine.get_bindings_for_variable("counter_value").len(), 1);
+    }
+    
+    #[test]
+    fn values_are_coerced_to_the_declared_type() {
+        let mut krb_file = create_test_krb_file();
+        for (name, value_type, default_value) in [("enabled", 0x04, "yes"), ("ratio", 0x03, "1"), ("accent", 0x05, "red"), ("count", 0x02, "oops")] {
+            krb_file.template_variables.push(TemplateVariable { name: name.to_string(), value_type, default_value: default_value.to_string() });
+        }
+        let mut engine = TemplateEngine::new(&krb_file);
+        
+        // Defaults are canonical, or kept as they are when they don't parse
+        assert_eq!(engine.get_variable("enabled"), Some("true"));
+        assert_eq!(engine.get_variable("accent"), Some("#ff0000"));
+        assert_eq!(engine.get_typed_variable("count"), Some(TemplateValue::String("oops".to_string())));
+        
+        assert!(engine.set_variable("ratio", "2.5"));
+        assert_eq!(engine.get_typed_variable("ratio"), Some(TemplateValue::Float(2.5)));
+        assert_eq!(engine.set_typed_variable("count", TemplateValue::Float(7.0)), Ok(true));
+        assert_eq!(engine.get_variable("count"), Some("7"));
+        assert!(!engine.set_variable("enabled", "on"));
+        
+        // Incompatible values leave the variable as it was
+        let error = engine.set_typed_variable("ratio", TemplateValue::Bool(true)).unwrap_err();
+        assert_eq!(error.expected, TemplateValueType::Float);
+        assert!(!engine.set_variable("count", "seven"));
+        assert_eq!(engine.get_variable("count"), Some("7"));
+        assert_eq!(engine.variable_type("accent"), Some(TemplateValueType::Color));
+    }
+    
     #[test]
     fn test_affected_elements() {
         let krb_file = create_test_krb_file();
@@ -0,0 +1,474 @@
+// crates/kryon-runtime/src/text_editing.rs
+//! Focus and editing for text `Input` elements.
+//!
+//! One input at a time has focus, from a click or from Tab. Its value is
+//! the element's `text`; the editor keeps the caret and selection as
+//! character indices into it and applies each key to both. The runtime
+//! copies them into the element's `caret`, `selection_start` and
+//! `selection_end` properties, where the renderer draws them from and
+//! scripts can read them, along with how far the value is scrolled
+//! (`text_scroll`) and the caret's blink (`caret_blink`), and dispatches
+//! `onChange` when the value changed.
+//!
+//! Text from an input method arrives in two steps: the composition in
+//! progress (`preedit`), shown at the caret but not part of the value, and
+//! the committed text, inserted like typed characters.
+//!
+//! A masked input, a `password` that isn't revealed, never copies its value
+//! out: copy and cut leave the clipboard and the value as they were.
+//!
+//! An input with an `input_mask` is edited through it: typed and pasted
+//! text fills the mask's slots, deleting removes the character typed
+//! before or after the caret rather than a literal, and the arrows step
+//! the caret between slots; see `input_mask`.
+
+use crate::input_mask::InputMask;
+use kryon_core::ElementId;
+use kryon_render::{KeyCode, KeyModifiers};
+
+/// Where copied text goes and pasted text comes from
+pub trait Clipboard {
+    fn read(&mut self) -> Option<String>;
+    fn write(&mut self, text: &str);
+}
+
+/// What a key or text did to the focused input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// Not an editing key, or nothing to do; the app may handle it
+    Ignored,
+    /// Handled without changing anything visible
+    Handled,
+    /// The caret or selection moved
+    Moved,
+    /// The value changed
+    Changed,
+    /// Enter was pressed
+    Submit,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TextEditor {
+    focused: Option<ElementId>,
+    readonly: bool,
+    /// The value is hidden, so it mustn't reach the clipboard
+    masked: bool,
+    /// Formats the value as it's edited
+    mask: Option<InputMask>,
+    caret: usize,
+    /// Where the selection started; the same as `caret` when nothing is selected
+    anchor: usize,
+    preedit: String,
+}
+
+impl TextEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn focused(&self) -> Option<ElementId> {
+        self.focused
+    }
+
+    /// Focuses an input holding `value`, with the caret at its end
+    pub fn focus(&mut self, element_id: ElementId, value: &str, readonly: bool) {
+        let end = value.chars().count();
+        *self = Self { focused: Some(element_id), readonly, masked: false, mask: None, caret: end, anchor: end, preedit: String::new() };
+    }
+
+    /// Drops focus and returns the input that had it
+    pub fn blur(&mut self) -> Option<ElementId> {
+        let focused = self.focused.take();
+        self.preedit.clear();
+        focused
+    }
+
+    /// Keeps the focused input's value off the clipboard while `masked`,
+    /// e.g. until a password is revealed
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
+
+    /// Edits the focused input through `mask`, whose value should already
+    /// be formatted by it
+    pub fn set_input_mask(&mut self, mask: Option<InputMask>) {
+        self.mask = mask;
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Start and end of the selection; equal when nothing is selected
+    pub fn selection(&self) -> (usize, usize) {
+        (self.caret.min(self.anchor), self.caret.max(self.anchor))
+    }
+
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Shows `text` as the composition in progress; empty clears it
+    pub fn set_preedit(&mut self, text: &str) -> Edit {
+        if self.focused.is_none() || self.readonly || self.preedit == text {
+            return Edit::Ignored;
+        }
+        self.preedit = text.to_string();
+        Edit::Moved
+    }
+
+    /// Puts the caret at `index`, extending the selection if `extend`
+    pub fn place_caret(&mut self, value: &str, index: usize, extend: bool) -> Edit {
+        let mut index = index.min(value.chars().count());
+        // Masked values only have carets next to their slots
+        if let Some(mask) = &self.mask {
+            index = mask.caret_stops(value).into_iter().min_by_key(|stop| stop.abs_diff(index)).unwrap_or(index);
+        }
+        let anchor = if extend { self.anchor } else { index };
+        if (self.caret, self.anchor) == (index, anchor) {
+            return Edit::Handled;
+        }
+        self.caret = index;
+        self.anchor = anchor;
+        Edit::Moved
+    }
+
+    /// Replaces the selection with `text`, as if typed
+    pub fn insert(&mut self, value: &mut String, text: &str) -> Edit {
+        if self.focused.is_none() || self.readonly {
+            return Edit::Ignored;
+        }
+        self.clamp(value);
+        self.preedit.clear();
+        // Inputs are a single line
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        let (start, end) = self.selection();
+        if text.is_empty() && start == end {
+            return Edit::Handled;
+        }
+        if let Some(mask) = &self.mask {
+            let (start, end) = (mask.raw_index(start), mask.raw_index(end));
+            return self.splice_masked(value, start, end, &text);
+        }
+        value.replace_range(byte_range(value, start, end), &text);
+        self.caret = start + text.chars().count();
+        self.anchor = self.caret;
+        Edit::Changed
+    }
+
+    /// Replaces raw characters `start..end` of a masked value with `text`,
+    /// leaving the caret after what the mask took of it
+    fn splice_masked(&mut self, value: &mut String, start: usize, end: usize, text: &str) -> Edit {
+        let Some(mask) = &self.mask else {
+            return Edit::Ignored;
+        };
+        let (spliced, raw_caret) = mask.splice(value, start, end, text);
+        if spliced == *value {
+            return Edit::Handled;
+        }
+        self.caret = mask.value_index(raw_caret).min(spliced.chars().count());
+        self.anchor = self.caret;
+        *value = spliced;
+        Edit::Changed
+    }
+
+    /// Where the caret goes one step back or forward: a character over,
+    /// or to the next stop of a mask
+    fn step(&self, value: &str, forward: bool) -> usize {
+        match &self.mask {
+            Some(mask) => {
+                let stops = mask.caret_stops(value);
+                let next = if forward {
+                    stops.into_iter().find(|&stop| stop > self.caret)
+                } else {
+                    stops.into_iter().rev().find(|&stop| stop < self.caret)
+                };
+                next.unwrap_or(self.caret)
+            }
+            None if forward => self.caret + 1,
+            None => self.caret.saturating_sub(1),
+        }
+    }
+
+    /// Applies an editing key. Keys the editor doesn't use come back as
+    /// `Edit::Ignored`, as does everything while nothing is focused.
+    pub fn key(&mut self, value: &mut String, key: KeyCode, modifiers: KeyModifiers, clipboard: &mut dyn Clipboard) -> Edit {
+        if self.focused.is_none() {
+            return Edit::Ignored;
+        }
+        self.clamp(value);
+        let len = value.chars().count();
+        let shortcut = modifiers.ctrl || modifiers.meta;
+        match key {
+            KeyCode::Character(c) if shortcut => match c.to_ascii_lowercase() {
+                'a' => {
+                    self.anchor = 0;
+                    self.caret = len;
+                    Edit::Moved
+                }
+                'c' => {
+                    self.copy(value, clipboard);
+                    Edit::Handled
+                }
+                // Cutting what can't be copied would only lose it
+                'x' if self.masked => Edit::Handled,
+                'x' => {
+                    self.copy(value, clipboard);
+                    self.insert(value, "")
+                }
+                'v' => match clipboard.read() {
+                    Some(text) => self.insert(value, &text),
+                    None => Edit::Handled,
+                },
+                _ => Edit::Ignored,
+            },
+            KeyCode::Character(c) => {
+                // Backends that report the key rather than the character send it unshifted
+                let c = if modifiers.shift { c.to_ascii_uppercase() } else { c };
+                self.insert(value, c.encode_utf8(&mut [0; 4]))
+            }
+            KeyCode::Space if !shortcut => self.insert(value, " "),
+            KeyCode::Backspace | KeyCode::Delete => {
+                if self.readonly {
+                    return Edit::Handled;
+                }
+                if self.caret == self.anchor {
+                    let forward = key == KeyCode::Delete;
+                    // Deletes the typed character next to the caret, past any literals
+                    if let Some(mask) = &self.mask {
+                        let raw = mask.raw_index(self.caret);
+                        let range = match forward {
+                            true => (raw < mask.unmask(value).chars().count()).then_some((raw, raw + 1)),
+                            false => raw.checked_sub(1).map(|start| (start, raw)),
+                        };
+                        return match range {
+                            Some((start, end)) => self.splice_masked(value, start, end, ""),
+                            None => Edit::Handled,
+                        };
+                    }
+                    if (forward && self.caret == len) || (!forward && self.caret == 0) {
+                        return Edit::Handled;
+                    }
+                    self.anchor = if forward { self.caret + 1 } else { self.caret - 1 };
+                }
+                self.insert(value, "")
+            }
+            KeyCode::ArrowLeft | KeyCode::ArrowRight => {
+                let (start, end) = self.selection();
+                let index = match (key == KeyCode::ArrowLeft, modifiers.shift || start == end) {
+                    (true, true) => self.step(value, false),
+                    (false, true) => self.step(value, true),
+                    // Collapses the selection to the side of the arrow
+                    (true, false) => start,
+                    (false, false) => end,
+                };
+                self.place_caret(value, index, modifiers.shift)
+            }
+            KeyCode::Home | KeyCode::ArrowUp => self.place_caret(value, 0, modifiers.shift),
+            KeyCode::End | KeyCode::ArrowDown => self.place_caret(value, len, modifiers.shift),
+            KeyCode::Enter => Edit::Submit,
+            _ => Edit::Ignored,
+        }
+    }
+
+    /// Keeps the caret inside a value a script may have shortened
+    fn clamp(&mut self, value: &str) {
+        let len = value.chars().count();
+        self.caret = self.caret.min(len);
+        self.anchor = self.anchor.min(len);
+    }
+
+    fn copy(&self, value: &str, clipboard: &mut dyn Clipboard) {
+        let (start, end) = self.selection();
+        if start != end && !self.masked {
+            clipboard.write(&value[byte_range(value, start, end)]);
+        }
+    }
+}
+
+/// Byte range of the characters from `start` to `end`
+fn byte_range(value: &str, start: usize, end: usize) -> std::ops::Range<usize> {
+    let byte = |index: usize| value.char_indices().nth(index).map_or(value.len(), |(byte, _)| byte);
+    byte(start)..byte(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Memory(Option<String>);
+
+    impl Clipboard for Memory {
+        fn read(&mut self) -> Option<String> {
+            self.0.clone()
+        }
+
+        fn write(&mut self, text: &str) {
+            self.0 = Some(text.to_string());
+        }
+    }
+
+    fn with(ctrl: bool, shift: bool) -> KeyModifiers {
+        KeyModifiers { ctrl, shift, ..KeyModifiers::none() }
+    }
+
+    fn typed(editor: &mut TextEditor, value: &mut String, keys: &[KeyCode]) {
+        for &key in keys {
+            editor.key(value, key, KeyModifiers::none(), &mut Memory::default());
+        }
+    }
+
+    #[test]
+    fn types_and_deletes_around_the_caret() {
+        let mut editor = TextEditor::new();
+        let mut value = "héllo".to_string();
+        editor.focus(3, &value, false);
+        assert_eq!(editor.caret(), 5);
+
+        typed(&mut editor, &mut value, &[KeyCode::ArrowLeft, KeyCode::ArrowLeft, KeyCode::Backspace, KeyCode::Character('x')]);
+        assert_eq!(value, "héxlo");
+        assert_eq!(editor.caret(), 3);
+
+        typed(&mut editor, &mut value, &[KeyCode::Home, KeyCode::Delete, KeyCode::Delete, KeyCode::Space]);
+        assert_eq!(value, " xlo");
+        assert_eq!(editor.key(&mut value, KeyCode::Character('h'), with(false, true), &mut Memory::default()), Edit::Changed);
+        assert_eq!(value, " Hxlo");
+
+        typed(&mut editor, &mut value, &[KeyCode::End, KeyCode::Delete]);
+        assert_eq!(value, " Hxlo");
+        assert_eq!(editor.key(&mut value, KeyCode::Enter, KeyModifiers::none(), &mut Memory::default()), Edit::Submit);
+    }
+
+    #[test]
+    fn shift_selects_and_typing_replaces_the_selection() {
+        let mut editor = TextEditor::new();
+        let mut value = "kryon".to_string();
+        editor.focus(1, &value, false);
+        for _ in 0..3 {
+            editor.key(&mut value, KeyCode::ArrowLeft, with(false, true), &mut Memory::default());
+        }
+        assert_eq!(editor.selection(), (2, 5));
+
+        // A plain arrow collapses the selection to its side
+        editor.key(&mut value, KeyCode::ArrowRight, KeyModifiers::none(), &mut Memory::default());
+        assert_eq!(editor.selection(), (5, 5));
+
+        editor.place_caret(&value, 2, false);
+        editor.place_caret(&value, 4, true);
+        editor.insert(&mut value, "ZZZ");
+        assert_eq!(value, "krZZZn");
+        assert_eq!(editor.selection(), (5, 5));
+    }
+
+    #[test]
+    fn clipboard_shortcuts_copy_cut_and_paste() {
+        let mut editor = TextEditor::new();
+        let mut clipboard = Memory::default();
+        let mut value = "copy me".to_string();
+        editor.focus(1, &value, false);
+
+        editor.key(&mut value, KeyCode::Character('a'), with(true, false), &mut clipboard);
+        assert_eq!(editor.selection(), (0, 7));
+        editor.key(&mut value, KeyCode::Character('c'), with(true, false), &mut clipboard);
+        assert_eq!(clipboard.0.as_deref(), Some("copy me"));
+
+        editor.place_caret(&value, 5, false);
+        editor.place_caret(&value, 7, true);
+        assert_eq!(editor.key(&mut value, KeyCode::Character('x'), with(true, false), &mut clipboard), Edit::Changed);
+        assert_eq!((value.as_str(), clipboard.0.as_deref()), ("copy ", Some("me")));
+
+        editor.place_caret(&value, 0, false);
+        editor.key(&mut value, KeyCode::Character('v'), with(true, false), &mut clipboard);
+        assert_eq!(value, "mecopy ");
+
+        // Pasted line breaks don't make it into a single-line input
+        clipboard.0 = Some("a\nb".to_string());
+        editor.key(&mut value, KeyCode::Character('v'), with(true, false), &mut clipboard);
+        assert_eq!(value, "meabcopy ");
+    }
+
+    #[test]
+    fn readonly_inputs_select_and_copy_but_never_change() {
+        let mut editor = TextEditor::new();
+        let mut clipboard = Memory::default();
+        let mut value = "fixed".to_string();
+        editor.focus(1, &value, true);
+
+        for key in [KeyCode::Character('q'), KeyCode::Backspace, KeyCode::Space] {
+            assert_ne!(editor.key(&mut value, key, KeyModifiers::none(), &mut clipboard), Edit::Changed);
+        }
+        editor.key(&mut value, KeyCode::Character('a'), with(true, false), &mut clipboard);
+        editor.key(&mut value, KeyCode::Character('x'), with(true, false), &mut clipboard);
+        assert_eq!(value, "fixed");
+        assert_eq!(clipboard.0.as_deref(), Some("fixed"));
+        assert_eq!(editor.set_preedit("か"), Edit::Ignored);
+    }
+
+    #[test]
+    fn masked_inputs_keep_their_value_off_the_clipboard() {
+        let mut editor = TextEditor::new();
+        let mut clipboard = Memory(Some("pasted".to_string()));
+        let mut value = "secret".to_string();
+        editor.focus(1, &value, false);
+        editor.set_masked(true);
+
+        editor.key(&mut value, KeyCode::Character('a'), with(true, false), &mut clipboard);
+        editor.key(&mut value, KeyCode::Character('c'), with(true, false), &mut clipboard);
+        assert_eq!(editor.key(&mut value, KeyCode::Character('x'), with(true, false), &mut clipboard), Edit::Handled);
+        assert_eq!((value.as_str(), clipboard.0.as_deref()), ("secret", Some("pasted")));
+
+        // Pasting into a password still works, and revealing it allows cutting
+        assert_eq!(editor.key(&mut value, KeyCode::Character('v'), with(true, false), &mut clipboard), Edit::Changed);
+        editor.key(&mut value, KeyCode::Character('!'), KeyModifiers::none(), &mut clipboard);
+        editor.set_masked(false);
+        editor.key(&mut value, KeyCode::Character('a'), with(true, false), &mut clipboard);
+        assert_eq!(editor.key(&mut value, KeyCode::Character('x'), with(true, false), &mut clipboard), Edit::Changed);
+        assert_eq!((value.as_str(), clipboard.0.as_deref()), ("", Some("pasted!")));
+    }
+
+    #[test]
+    fn masks_insert_literals_and_skip_them_when_moving_and_deleting() {
+        let mut editor = TextEditor::new();
+        let mut clipboard = Memory(Some("555123".to_string()));
+        let mut value = String::new();
+        editor.focus(1, &value, false);
+        editor.set_input_mask(InputMask::parse("(999) 999-9999"));
+
+        typed(&mut editor, &mut value, &[KeyCode::Character('5'), KeyCode::Character('x'), KeyCode::Character('5')]);
+        assert_eq!((value.as_str(), editor.caret()), ("(55", 3));
+        editor.key(&mut value, KeyCode::Character('a'), with(true, false), &mut clipboard);
+        editor.key(&mut value, KeyCode::Character('v'), with(true, false), &mut clipboard);
+        assert_eq!((value.as_str(), editor.caret()), ("(555) 123", 9));
+
+        // The caret steps over ") " and Backspace deletes the digit before it
+        typed(&mut editor, &mut value, &[KeyCode::ArrowLeft, KeyCode::ArrowLeft, KeyCode::ArrowLeft]);
+        assert_eq!(editor.caret(), 4);
+        typed(&mut editor, &mut value, &[KeyCode::Backspace]);
+        assert_eq!((value.as_str(), editor.caret()), ("(551) 23", 3));
+        typed(&mut editor, &mut value, &[KeyCode::Delete]);
+        assert_eq!((value.as_str(), editor.caret()), ("(552) 3", 3));
+
+        // Clicks between literals land next to a slot
+        editor.place_caret(&value, 5, false);
+        assert_eq!(editor.caret(), 4);
+    }
+
+    #[test]
+    fn committed_composition_is_inserted_at_the_caret() {
+        let mut editor = TextEditor::new();
+        let mut value = "ab".to_string();
+        editor.focus(1, &value, false);
+        editor.place_caret(&value, 1, false);
+
+        assert_eq!(editor.set_preedit("にほ"), Edit::Moved);
+        assert_eq!(value, "ab");
+        assert_eq!(editor.insert(&mut value, "日本"), Edit::Changed);
+        assert_eq!(value, "a日本b");
+        assert_eq!(editor.preedit(), "");
+        assert_eq!(editor.caret(), 3);
+
+        assert_eq!(editor.blur(), Some(1));
+        assert_eq!(editor.insert(&mut value, "x"), Edit::Ignored);
+    }
+}
@@ -0,0 +1,628 @@
+// crates/kryon-runtime/src/toasts.rs
+//! In-app toasts for scripts: `kryon.toast(message, {kind, duration, action})`.
+//!
+//! Toasts stack in one corner of the overlay layer, newest nearest the
+//! corner. They slide and fade in, count down while shown and slide out
+//! again; the pointer resting on the stack pauses every countdown. At most
+//! `max_visible` are up at once, the rest wait their turn in order.
+//!
+//! A toast with an action draws a button. Pressing it dismisses the toast
+//! and the runtime runs the script's action callback.
+
+use crate::script::engine_trait::ScriptValue;
+use glam::{Vec2, Vec4};
+use kryon_core::TextAlignment;
+use kryon_render::RenderCommand;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Toasts stay up this long unless the script passes `duration`.
+pub const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(4);
+/// Toasts shown at once before new ones queue.
+pub const DEFAULT_MAX_VISIBLE_TOASTS: usize = 3;
+
+const TOAST_WIDTH: f32 = 320.0;
+const TOAST_HEIGHT: f32 = 48.0;
+const TOAST_MARGIN: f32 = 16.0;
+const TOAST_SPACING: f32 = 8.0;
+const ACCENT_WIDTH: f32 = 4.0;
+const BUTTON_HEIGHT: f32 = 28.0;
+const BUTTON_PADDING: f32 = 10.0;
+const MESSAGE_SIZE: f32 = 14.0;
+const ENTER_SECONDS: f32 = 0.2;
+const EXIT_SECONDS: f32 = 0.2;
+/// How far a toast slides in from, as a share of its width
+const SLIDE_DISTANCE: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastKind {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "info" => Some(Self::Info),
+            "success" => Some(Self::Success),
+            "warning" => Some(Self::Warning),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    fn accent(self) -> Vec4 {
+        match self {
+            Self::Info => Vec4::new(0.29, 0.56, 0.89, 1.0),
+            Self::Success => Vec4::new(0.30, 0.69, 0.31, 1.0),
+            Self::Warning => Vec4::new(0.96, 0.65, 0.14, 1.0),
+            Self::Error => Vec4::new(0.90, 0.30, 0.26, 1.0),
+        }
+    }
+}
+
+/// The viewport corner toasts stack in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl ToastCorner {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "top-left" => Some(Self::TopLeft),
+            "top-right" => Some(Self::TopRight),
+            "bottom-left" => Some(Self::BottomLeft),
+            "bottom-right" => Some(Self::BottomRight),
+            _ => None,
+        }
+    }
+
+    fn is_top(self) -> bool {
+        matches!(self, Self::TopLeft | Self::TopRight)
+    }
+
+    fn is_left(self) -> bool {
+        matches!(self, Self::TopLeft | Self::BottomLeft)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastRequest {
+    /// Issued by the script bridge; action callbacks are keyed by it
+    pub id: u64,
+    pub message: String,
+    pub kind: ToastKind,
+    /// `None` keeps the toast up until it is dismissed or its action pressed
+    pub duration: Option<Duration>,
+    /// Label of the action button
+    pub action: Option<String>,
+}
+
+/// A `kryon.toast*` call queued by the script bridge
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastCommand {
+    Show(ToastRequest),
+    Dismiss(u64),
+    Configure {
+        corner: Option<ToastCorner>,
+        max_visible: Option<usize>,
+    },
+}
+
+impl ToastCommand {
+    /// Parses `{op, ...}` as queued by the script bridge.
+    pub fn from_script_value(value: &ScriptValue) -> Option<Self> {
+        let ScriptValue::Object(fields) = value else {
+            return None;
+        };
+        let id = match fields.get("id") {
+            Some(ScriptValue::Integer(id)) if *id > 0 => Some(*id as u64),
+            Some(ScriptValue::Number(id)) if *id > 0.0 => Some(*id as u64),
+            _ => None,
+        };
+        let string = |name: &str| match fields.get(name) {
+            Some(ScriptValue::String(value)) if !value.is_empty() => Some(value.clone()),
+            _ => None,
+        };
+
+        match fields.get("op")? {
+            ScriptValue::String(op) if op == "show" => {
+                let kind = match string("kind") {
+                    Some(name) => ToastKind::parse(&name).unwrap_or_else(|| {
+                        eprintln!("[TOAST] Unknown toast kind '{}', using info", name);
+                        ToastKind::Info
+                    }),
+                    None => ToastKind::Info,
+                };
+                // Duration is given in milliseconds; 0 keeps the toast up
+                let duration = match fields.get("duration") {
+                    Some(ScriptValue::Integer(ms)) if *ms <= 0 => None,
+                    Some(ScriptValue::Integer(ms)) => Some(Duration::from_millis(*ms as u64)),
+                    Some(ScriptValue::Number(ms)) if *ms <= 0.0 => None,
+                    Some(ScriptValue::Number(ms)) => Some(Duration::from_secs_f64(ms / 1000.0)),
+                    _ => Some(DEFAULT_TOAST_DURATION),
+                };
+                Some(Self::Show(ToastRequest {
+                    id: id?,
+                    message: fields.get("message")?.to_string(),
+                    kind,
+                    duration,
+                    action: string("action"),
+                }))
+            }
+            ScriptValue::String(op) if op == "dismiss" => Some(Self::Dismiss(id?)),
+            ScriptValue::String(op) if op == "configure" => {
+                let corner = string("corner").and_then(|name| {
+                    let corner = ToastCorner::parse(&name);
+                    if corner.is_none() {
+                        eprintln!("[TOAST] Unknown toast corner '{}'", name);
+                    }
+                    corner
+                });
+                let max_visible = match fields.get("max_visible") {
+                    Some(ScriptValue::Integer(count)) if *count > 0 => Some(*count as usize),
+                    Some(ScriptValue::Number(count)) if *count >= 1.0 => Some(*count as usize),
+                    _ => None,
+                };
+                Some(Self::Configure { corner, max_visible })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Toast {
+    request: ToastRequest,
+    /// Seconds since it started entering
+    age: f32,
+    /// Seconds left to show, paused on hover
+    remaining: Option<f32>,
+    /// Seconds since it started leaving
+    leaving: Option<f32>,
+}
+
+impl Toast {
+    /// How far in it is: 0 while off screen, 1 when fully shown
+    fn presence(&self) -> f32 {
+        let entered = (self.age / ENTER_SECONDS).min(1.0);
+        let left = self.leaving.map_or(0.0, |leaving| (leaving / EXIT_SECONDS).min(1.0));
+        ease_out(entered.min(1.0 - left))
+    }
+}
+
+fn ease_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Where a toast and its action button are drawn this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ToastLayout {
+    position: Vec2,
+    size: Vec2,
+    button: Option<(Vec2, Vec2)>,
+}
+
+#[derive(Debug)]
+pub struct ToastManager {
+    shown: Vec<Toast>,
+    queued: VecDeque<ToastRequest>,
+    corner: ToastCorner,
+    max_visible: usize,
+    hovered: bool,
+    /// Toasts with an action that left without it being pressed
+    expired_actions: Vec<u64>,
+}
+
+impl ToastManager {
+    pub fn new() -> Self {
+        Self {
+            shown: Vec::new(),
+            queued: VecDeque::new(),
+            corner: ToastCorner::default(),
+            max_visible: DEFAULT_MAX_VISIBLE_TOASTS,
+            hovered: false,
+            expired_actions: Vec::new(),
+        }
+    }
+
+    pub fn apply(&mut self, command: ToastCommand) {
+        match command {
+            ToastCommand::Show(request) => self.show(request),
+            ToastCommand::Dismiss(id) => self.dismiss(id),
+            ToastCommand::Configure { corner, max_visible } => {
+                if let Some(corner) = corner {
+                    self.corner = corner;
+                }
+                if let Some(max_visible) = max_visible {
+                    self.max_visible = max_visible.max(1);
+                }
+            }
+        }
+    }
+
+    pub fn show(&mut self, request: ToastRequest) {
+        eprintln!("[TOAST] '{}'", request.message);
+        self.queued.push_back(request);
+        self.promote();
+    }
+
+    /// Starts `id` leaving, or drops it from the queue
+    pub fn dismiss(&mut self, id: u64) {
+        if let Some(toast) = self.shown.iter_mut().find(|toast| toast.request.id == id) {
+            toast.leaving.get_or_insert(0.0);
+        }
+        self.queued.retain(|request| request.id != id);
+    }
+
+    pub fn corner(&self) -> ToastCorner {
+        self.corner
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty() && self.queued.is_empty()
+    }
+
+    /// Ids of the toasts on screen, oldest first
+    pub fn visible(&self) -> Vec<u64> {
+        self.shown.iter().map(|toast| toast.request.id).collect()
+    }
+
+    /// Ids of the toasts waiting for a free slot
+    pub fn queued(&self) -> Vec<u64> {
+        self.queued.iter().map(|request| request.id).collect()
+    }
+
+    /// Ids of toasts with an action that left without it being pressed, so
+    /// the script can forget their callbacks
+    pub fn take_expired_actions(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.expired_actions)
+    }
+
+    /// Moves queued toasts into free slots. Leaving toasts still hold theirs.
+    fn promote(&mut self) {
+        while self.shown.len() < self.max_visible {
+            let Some(request) = self.queued.pop_front() else {
+                break;
+            };
+            let remaining = request.duration.map(|duration| duration.as_secs_f32());
+            self.shown.push(Toast { request, age: 0.0, remaining, leaving: None });
+        }
+    }
+
+    /// Animates and ages toasts. Returns true when the overlay needs to be
+    /// redrawn.
+    pub fn update(&mut self, delta_time: Duration) -> bool {
+        if self.shown.is_empty() {
+            return false;
+        }
+        let dt = delta_time.as_secs_f32();
+        for toast in &mut self.shown {
+            toast.age += dt;
+            match (&mut toast.leaving, &mut toast.remaining) {
+                (Some(leaving), _) => *leaving += dt,
+                (None, Some(remaining)) if !self.hovered => {
+                    *remaining -= dt;
+                    if *remaining <= 0.0 {
+                        toast.leaving = Some(0.0);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let expired_actions = &mut self.expired_actions;
+        self.shown.retain(|toast| {
+            let gone = toast.leaving.is_some_and(|leaving| leaving >= EXIT_SECONDS);
+            if gone && toast.request.action.is_some() {
+                expired_actions.push(toast.request.id);
+            }
+            !gone
+        });
+        self.promote();
+        true
+    }
+
+    /// Tracks the pointer for pause-on-hover. `reserved` is the room another
+    /// stack takes in the same corner. Returns true when hover changed.
+    pub fn set_pointer(&mut self, position: Vec2, viewport_size: Vec2, reserved: f32) -> bool {
+        let hovered = self.hit(position, viewport_size, reserved);
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        changed
+    }
+
+    /// Whether `position` is on a toast
+    pub fn hit(&self, position: Vec2, viewport_size: Vec2, reserved: f32) -> bool {
+        self.layouts(viewport_size, reserved).iter()
+            .any(|layout| contains(layout.position, layout.size, position))
+    }
+
+    /// Takes a press on the stack. Returns whether it landed on a toast and,
+    /// if it pressed an action button, that toast's id; the toast is
+    /// dismissed.
+    pub fn press(&mut self, position: Vec2, viewport_size: Vec2, reserved: f32) -> (bool, Option<u64>) {
+        let layouts = self.layouts(viewport_size, reserved);
+        let Some(index) = layouts.iter().position(|layout| contains(layout.position, layout.size, position)) else {
+            return (false, None);
+        };
+        let on_button = layouts[index].button
+            .is_some_and(|(button_position, button_size)| contains(button_position, button_size, position));
+        let toast = &mut self.shown[index];
+        if !on_button || toast.leaving.is_some() {
+            return (true, None);
+        }
+        toast.leaving = Some(0.0);
+        // Its callback runs now, so it isn't reported as expired
+        toast.request.action = None;
+        (true, Some(toast.request.id))
+    }
+
+    /// How far the stack reaches from its corner, for stacking others past it
+    pub fn stack_height(&self) -> f32 {
+        self.shown.iter().map(|toast| (TOAST_HEIGHT + TOAST_SPACING) * toast.presence()).sum()
+    }
+
+    fn layouts(&self, viewport_size: Vec2, reserved: f32) -> Vec<ToastLayout> {
+        let size = Vec2::new(TOAST_WIDTH.min(viewport_size.x - 2.0 * TOAST_MARGIN).max(0.0), TOAST_HEIGHT);
+        let mut offset = TOAST_MARGIN + reserved;
+        // Newest nearest the corner
+        let mut layouts: Vec<ToastLayout> = self.shown.iter().rev()
+            .map(|toast| {
+                let presence = toast.presence();
+                let slide = (1.0 - presence) * SLIDE_DISTANCE * size.x;
+                let x = if self.corner.is_left() {
+                    TOAST_MARGIN - slide
+                } else {
+                    viewport_size.x - TOAST_MARGIN - size.x + slide
+                };
+                let y = if self.corner.is_top() {
+                    offset
+                } else {
+                    viewport_size.y - offset - size.y
+                };
+                offset += (size.y + TOAST_SPACING) * presence;
+
+                let position = Vec2::new(x, y);
+                let button = toast.request.action.as_ref().map(|label| {
+                    let width = label.chars().count() as f32 * MESSAGE_SIZE * 0.55 + 2.0 * BUTTON_PADDING;
+                    let button_size = Vec2::new(width.min(size.x / 2.0), BUTTON_HEIGHT);
+                    let button_position = position + Vec2::new(size.x - BUTTON_PADDING - button_size.x, (size.y - BUTTON_HEIGHT) / 2.0);
+                    (button_position, button_size)
+                });
+                ToastLayout { position, size, button }
+            })
+            .collect();
+        layouts.reverse();
+        layouts
+    }
+
+    /// The stack in its corner. `reserved` is the room another stack takes
+    /// in the same corner.
+    pub fn overlay_commands(&self, viewport_size: Vec2, reserved: f32) -> Vec<RenderCommand> {
+        let layouts = self.layouts(viewport_size, reserved);
+        let mut commands = Vec::with_capacity(self.shown.len() * 5);
+
+        for (toast, layout) in self.shown.iter().zip(layouts) {
+            let alpha = toast.presence();
+            commands.push(RenderCommand::DrawRect {
+                position: layout.position,
+                size: layout.size,
+                color: Vec4::new(0.12, 0.12, 0.14, 0.94 * alpha),
+                border_radius: 6.0,
+                border_width: 1.0,
+                border_color: Vec4::new(1.0, 1.0, 1.0, 0.15 * alpha),
+                transform: None,
+                shadow: None,
+                z_index: i32::MAX,
+            });
+            commands.push(RenderCommand::DrawRect {
+                position: layout.position + Vec2::new(1.0, 6.0),
+                size: Vec2::new(ACCENT_WIDTH, layout.size.y - 12.0),
+                color: toast.request.kind.accent() * Vec4::new(1.0, 1.0, 1.0, alpha),
+                border_radius: ACCENT_WIDTH / 2.0,
+                border_width: 0.0,
+                border_color: Vec4::ZERO,
+                transform: None,
+                shadow: None,
+                z_index: i32::MAX,
+            });
+
+            let text_left = ACCENT_WIDTH + 12.0;
+            let text_right = layout.button.map_or(layout.position.x + layout.size.x, |(position, _)| position.x) - 8.0;
+            commands.push(overlay_text(
+                &toast.request.message,
+                layout.position + Vec2::new(text_left, (layout.size.y - MESSAGE_SIZE) / 2.0 - 2.0),
+                MESSAGE_SIZE,
+                Vec4::new(1.0, 1.0, 1.0, alpha),
+                TextAlignment::Start,
+                (text_right - layout.position.x - text_left).max(0.0),
+            ));
+
+            if let (Some(label), Some((position, size))) = (&toast.request.action, layout.button) {
+                commands.push(RenderCommand::DrawRect {
+                    position,
+                    size,
+                    color: Vec4::new(1.0, 1.0, 1.0, 0.1 * alpha),
+                    border_radius: 4.0,
+                    border_width: 0.0,
+                    border_color: Vec4::ZERO,
+                    transform: None,
+                    shadow: None,
+                    z_index: i32::MAX,
+                });
+                commands.push(overlay_text(
+                    label,
+                    position + Vec2::new(0.0, (size.y - MESSAGE_SIZE) / 2.0 - 2.0),
+                    MESSAGE_SIZE,
+                    toast.request.kind.accent() * Vec4::new(1.0, 1.0, 1.0, alpha),
+                    TextAlignment::Center,
+                    size.x,
+                ));
+            }
+        }
+
+        commands
+    }
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn contains(position: Vec2, size: Vec2, point: Vec2) -> bool {
+    point.cmpge(position).all() && point.cmplt(position + size).all()
+}
+
+fn overlay_text(text: &str, position: Vec2, font_size: f32, color: Vec4, alignment: TextAlignment, max_width: f32) -> RenderCommand {
+    RenderCommand::DrawText {
+        position,
+        text: text.to_string(),
+        font_size,
+        color,
+        alignment,
+        max_width: Some(max_width),
+        max_height: None,
+        transform: None,
+        font_family: None,
+        runs: Vec::new(),
+        z_index: i32::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn request(id: u64, action: Option<&str>) -> ToastRequest {
+        ToastRequest {
+            id,
+            message: format!("toast {}", id),
+            kind: ToastKind::Info,
+            duration: Some(Duration::from_secs(1)),
+            action: action.map(str::to_string),
+        }
+    }
+
+    fn rects(manager: &ToastManager) -> Vec<Vec2> {
+        manager.overlay_commands(VIEWPORT, 0.0).iter()
+            .filter_map(|cmd| match cmd {
+                RenderCommand::DrawRect { position, size, .. } if size.x == TOAST_WIDTH => Some(*position),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_show_dismiss_and_configure() {
+        let value = |fields: Vec<(&str, ScriptValue)>| {
+            ScriptValue::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect::<HashMap<_, _>>())
+        };
+
+        let show = value(vec![
+            ("op", "show".into()),
+            ("id", ScriptValue::Integer(7)),
+            ("message", "Saved".into()),
+            ("kind", "success".into()),
+            ("duration", ScriptValue::Integer(0)),
+            ("action", "Undo".into()),
+        ]);
+        let Some(ToastCommand::Show(request)) = ToastCommand::from_script_value(&show) else {
+            panic!("expected a toast");
+        };
+        assert_eq!((request.id, request.message.as_str(), request.kind), (7, "Saved", ToastKind::Success));
+        assert_eq!((request.duration, request.action.as_deref()), (None, Some("Undo")));
+
+        let plain = value(vec![("op", "show".into()), ("id", ScriptValue::Integer(8)), ("message", "Hi".into())]);
+        let Some(ToastCommand::Show(request)) = ToastCommand::from_script_value(&plain) else {
+            panic!("expected a toast");
+        };
+        assert_eq!((request.kind, request.duration), (ToastKind::Info, Some(DEFAULT_TOAST_DURATION)));
+
+        let configure = value(vec![("op", "configure".into()), ("corner", "top-left".into()), ("max_visible", ScriptValue::Integer(2))]);
+        assert_eq!(
+            ToastCommand::from_script_value(&configure),
+            Some(ToastCommand::Configure { corner: Some(ToastCorner::TopLeft), max_visible: Some(2) })
+        );
+        let dismiss = value(vec![("op", "dismiss".into()), ("id", ScriptValue::Integer(7))]);
+        assert_eq!(ToastCommand::from_script_value(&dismiss), Some(ToastCommand::Dismiss(7)));
+        assert!(ToastCommand::from_script_value(&value(vec![("op", "show".into())])).is_none());
+    }
+
+    #[test]
+    fn queues_past_the_visible_limit_and_expires_in_order() {
+        let mut manager = ToastManager::new();
+        manager.apply(ToastCommand::Configure { corner: None, max_visible: Some(2) });
+        for id in 1..=3 {
+            manager.show(request(id, None));
+        }
+        assert_eq!((manager.visible(), manager.queued()), (vec![1, 2], vec![3]));
+
+        manager.update(Duration::from_millis(1000));
+        assert_eq!(manager.visible(), vec![1, 2], "leaving toasts keep their slot");
+        manager.update(Duration::from_millis(300));
+        assert_eq!((manager.visible(), manager.queued()), (vec![3], vec![]));
+        manager.update(Duration::from_secs(2));
+        manager.update(Duration::from_millis(300));
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn slides_in_and_stacks_away_from_the_corner() {
+        let mut manager = ToastManager::new();
+        manager.show(request(1, None));
+        let entering = rects(&manager)[0];
+        manager.update(Duration::from_millis(500));
+        let shown = rects(&manager)[0];
+        assert_eq!(shown, Vec2::new(800.0 - TOAST_MARGIN - TOAST_WIDTH, 600.0 - TOAST_MARGIN - TOAST_HEIGHT));
+        assert!(entering.x > shown.x, "toasts slide in from the right edge");
+
+        manager.show(request(2, None));
+        manager.update(Duration::from_millis(500));
+        let stacked = rects(&manager);
+        assert_eq!(stacked[1], shown, "the newest sits in the corner");
+        assert_eq!(stacked[0].y, shown.y - TOAST_HEIGHT - TOAST_SPACING);
+
+        manager.apply(ToastCommand::Configure { corner: Some(ToastCorner::TopLeft), max_visible: None });
+        assert_eq!(rects(&manager)[1], Vec2::splat(TOAST_MARGIN));
+        let reserved = manager.layouts(VIEWPORT, 50.0);
+        assert_eq!(reserved[1].position.y, TOAST_MARGIN + 50.0, "another stack in the corner pushes it along");
+    }
+
+    #[test]
+    fn hovering_pauses_and_actions_dismiss() {
+        let mut manager = ToastManager::new();
+        manager.show(request(1, Some("Undo")));
+        manager.show(request(2, Some("Retry")));
+        manager.update(Duration::from_millis(500));
+
+        let toast = Vec2::new(800.0 - TOAST_MARGIN - 20.0, 600.0 - TOAST_MARGIN - 20.0);
+        assert!(manager.set_pointer(toast, VIEWPORT, 0.0));
+        manager.update(Duration::from_secs(5));
+        assert_eq!(manager.visible(), vec![1, 2], "hover pauses the countdown");
+
+        let button = Vec2::new(800.0 - TOAST_MARGIN - BUTTON_PADDING - 2.0, 600.0 - TOAST_MARGIN - TOAST_HEIGHT / 2.0);
+        assert_eq!(manager.press(button, VIEWPORT, 0.0), (true, Some(2)));
+        assert_eq!(manager.press(Vec2::ZERO, VIEWPORT, 0.0), (false, None));
+
+        manager.set_pointer(Vec2::ZERO, VIEWPORT, 0.0);
+        manager.update(Duration::from_millis(600));
+        manager.update(Duration::from_millis(300));
+        assert!(manager.is_empty());
+        assert_eq!(manager.take_expired_actions(), vec![1], "pressed actions aren't reported");
+    }
+}
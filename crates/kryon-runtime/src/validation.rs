@@ -0,0 +1,551 @@
+// crates/kryon-runtime/src/validation.rs
+//! Headless KRB linting for CI.
+//!
+//! `validate` loads nothing and renders nothing: it inspects a parsed KRB for
+//! missing assets, unknown fonts, event handlers without a matching script
+//! function, features the selected backend cannot draw, and what this
+//! renderer can't read in a file from another KRB version. The report
+//! serializes to JSON for machine consumption.
+
+use kryon_core::{AssetResolver, CompatWarning, CompatibilityReport, Element, ElementId, ElementType, KRBFile, OverflowType, PropertyValue, ResolvedTransform, ResourceType};
+use kryon_render::warnings::is_generic_font;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    Wgpu,
+    Raylib,
+    Ratatui,
+    Web,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 4] = [Backend::Wgpu, Backend::Raylib, Backend::Ratatui, Backend::Web];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Backend::Wgpu => "wgpu",
+            Backend::Raylib => "raylib",
+            Backend::Ratatui => "ratatui",
+            Backend::Web => "web",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Backend::ALL.into_iter()
+            .find(|backend| backend.as_str().eq_ignore_ascii_case(value))
+            .ok_or_else(|| format!("unknown backend '{}' (expected wgpu, raylib, ratatui or web)", value))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Stable identifier: `missing_asset`, `unknown_font`, `dangling_handler`,
+    /// `unsupported_feature`, `script_not_inspectable`, `unsupported_version`,
+    /// `newer_version`, `skipped_content` or `load_failed`
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub file: String,
+    pub backend: Backend,
+    pub valid: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    pub issues: Vec<Issue>,
+    /// How the file's KRB version compares to the renderer's; missing when
+    /// the file didn't load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compatibility: Option<CompatibilityReport>,
+}
+
+impl ValidationReport {
+    fn new(file: &str, backend: Backend, issues: Vec<Issue>) -> Self {
+        let errors = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+        Self {
+            file: file.to_string(),
+            backend,
+            valid: errors == 0,
+            errors,
+            warnings: issues.len() - errors,
+            issues,
+            compatibility: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("validation report is always serializable")
+    }
+
+    /// Process exit code: 0 when valid, 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        if self.valid { 0 } else { 1 }
+    }
+}
+
+/// Loads and validates a KRB file. Load failures are reported, not returned.
+/// Files from newer major KRB versions are read as far as they can be, and
+/// fail validation.
+pub fn validate_file(krb_path: &str, backend: Backend) -> ValidationReport {
+    match kryon_core::load_krb_file_with(krb_path, kryon_core::ParseOptions::tolerant()) {
+        Ok(krb_file) => validate(&krb_file, krb_path, backend, &AssetResolver::for_krb(krb_path)),
+        Err(e) => ValidationReport::new(krb_path, backend, vec![Issue {
+            severity: Severity::Error,
+            code: "load_failed",
+            element: None,
+            message: e.to_string(),
+        }]),
+    }
+}
+
+pub fn validate(krb_file: &KRBFile, krb_path: &str, backend: Backend, resolver: &AssetResolver) -> ValidationReport {
+    let mut issues = Vec::new();
+
+    let mut elements: Vec<(&ElementId, &Element)> = krb_file.elements.iter().collect();
+    elements.sort_by_key(|(id, _)| **id);
+
+    check_assets(krb_file, &elements, resolver, &mut issues);
+    check_fonts(krb_file, &elements, &mut issues);
+    check_handlers(krb_file, &elements, &mut issues);
+    check_features(&elements, backend, &mut issues);
+    check_compatibility(&krb_file.compatibility, &mut issues);
+
+    ValidationReport { compatibility: Some(krb_file.compatibility.clone()), ..ValidationReport::new(krb_path, backend, issues) }
+}
+
+pub(crate) fn element_label(id: ElementId, element: &Element) -> String {
+    if element.id.is_empty() { format!("#{}", id) } else { element.id.clone() }
+}
+
+fn string_property<'a>(element: &'a Element, name: &str) -> Option<&'a str> {
+    match element.custom_properties.get(name) {
+        Some(PropertyValue::String(value)) => Some(value.as_str()),
+        _ => None,
+    }
+}
+
+/// Remote URLs and template expressions are only known at runtime.
+fn is_static_path(path: &str) -> bool {
+    !path.is_empty() && !path.contains("://") && !path.contains('$')
+}
+
+fn resource_type_for(path: &str) -> ResourceType {
+    let extension = path.rsplit('.').next().unwrap_or_default().to_ascii_lowercase();
+    match extension.as_str() {
+        "ttf" | "otf" | "woff" | "woff2" => ResourceType::Font,
+        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" => ResourceType::Image,
+        _ => ResourceType::Data,
+    }
+}
+
+fn check_assets(krb_file: &KRBFile, elements: &[(&ElementId, &Element)], resolver: &AssetResolver, issues: &mut Vec<Issue>) {
+    let mut missing = |path: &str, resource_type: ResourceType, element: Option<String>, what: &str| {
+        let path = path.strip_prefix("file://").unwrap_or(path);
+        if is_static_path(path) && resolver.resolve(path, &resource_type).is_none() {
+            issues.push(Issue {
+                severity: Severity::Error,
+                code: "missing_asset",
+                element,
+                message: format!("{} '{}' not found", what, path),
+            });
+        }
+    };
+
+    for (&id, element) in elements {
+        let label = || Some(element_label(id, element));
+        if element.element_type == ElementType::Image {
+            if let Some(src) = string_property(element, "src") {
+                missing(src, ResourceType::Image, label(), "image");
+            }
+        }
+        if let Some(sprite) = string_property(element, "sprite") {
+            missing(sprite, ResourceType::Image, label(), "sprite");
+        }
+        if let Some(source) = string_property(element, "table_source") {
+            missing(source, ResourceType::Data, label(), "table source");
+        }
+        if let Some(source) = string_property(element, "data_source") {
+            missing(source, ResourceType::Data, label(), "data source");
+        }
+    }
+
+    let mut fonts: Vec<(&String, &String)> = krb_file.fonts.iter().collect();
+    fonts.sort();
+    for (family, path) in fonts {
+        missing(path, ResourceType::Font, None, &format!("font '{}'", family));
+    }
+    for resource in &krb_file.resources {
+        missing(resource, resource_type_for(resource), None, "resource");
+    }
+}
+
+/// Families every backend maps to its built-in font.
+fn check_fonts(krb_file: &KRBFile, elements: &[(&ElementId, &Element)], issues: &mut Vec<Issue>) {
+    let mut reported = HashSet::new();
+    for (&id, element) in elements {
+        let family = element.font_family.as_str();
+        if is_generic_font(family) || krb_file.fonts.contains_key(family) || !reported.insert(family) {
+            continue;
+        }
+        issues.push(Issue {
+            severity: Severity::Warning,
+            code: "unknown_font",
+            element: Some(element_label(id, element)),
+            message: format!("font family '{}' is not declared in the KRB; the default font is used", family),
+        });
+    }
+}
+
+/// Function names defined by the KRB's scripts, or `None` when a script is
+/// precompiled bytecode without declared entry points.
+fn script_functions(krb_file: &KRBFile, issues: &mut Vec<Issue>) -> Option<HashSet<String>> {
+    let definition = Regex::new(
+        r"(?m)(?:^|[^\w.])function\s+([A-Za-z_][\w.:]*)\s*\(|(?:^|[^\w.])([A-Za-z_][\w.]*)\s*=\s*function\b",
+    ).unwrap();
+
+    let mut functions = HashSet::new();
+    let mut complete = true;
+    for script in &krb_file.scripts {
+        functions.extend(script.entry_points.iter().cloned());
+        match std::str::from_utf8(&script.bytecode) {
+            Ok(source) => functions.extend(definition.captures_iter(source).filter_map(|captures| {
+                captures.get(1).or_else(|| captures.get(2)).map(|name| name.as_str().to_string())
+            })),
+            Err(_) if script.entry_points.is_empty() => {
+                complete = false;
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    code: "script_not_inspectable",
+                    element: None,
+                    message: format!("script '{}' is bytecode without declared entry points; handlers were not checked", script.name),
+                });
+            }
+            Err(_) => {}
+        }
+    }
+    complete.then_some(functions)
+}
+
+fn check_handlers(krb_file: &KRBFile, elements: &[(&ElementId, &Element)], issues: &mut Vec<Issue>) {
+    let Some(functions) = script_functions(krb_file, issues) else { return };
+
+    for (&id, element) in elements {
+        let mut handlers: Vec<(String, &str)> = element.event_handlers.iter()
+            .map(|(event, handler)| (format!("{:?}", event).to_lowercase(), handler.as_str()))
+            .collect();
+        handlers.sort();
+        if let Some(script) = element.native_render_script.as_deref() {
+            handlers.push(("native render".to_string(), script));
+        }
+        if let Some(script) = string_property(element, "draw_script") {
+            handlers.push(("draw".to_string(), script));
+        }
+
+        for (event, handler) in handlers {
+            let name = handler.trim().trim_end_matches("()");
+            if name.is_empty() || functions.contains(name) {
+                continue;
+            }
+            issues.push(Issue {
+                severity: Severity::Error,
+                code: "dangling_handler",
+                element: Some(element_label(id, element)),
+                message: format!("{} handler '{}' is not defined by any script", event, name),
+            });
+        }
+    }
+}
+
+/// Renderer features that not every backend implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Feature {
+    Images,
+    Canvas,
+    WasmView,
+    NativeView,
+    Particles,
+    TextInput,
+    ShaderEffect,
+    RichText,
+    CustomFonts,
+    Clipping,
+    Shadows,
+    Rotation,
+}
+
+impl Feature {
+    fn name(&self) -> &'static str {
+        match self {
+            Feature::Images => "images",
+            Feature::Canvas => "canvas",
+            Feature::WasmView => "wasm views",
+            Feature::NativeView => "native renderer views",
+            Feature::Particles => "particles",
+            Feature::TextInput => "input elements",
+            Feature::ShaderEffect => "shader effects",
+            Feature::RichText => "rich text spans",
+            Feature::CustomFonts => "custom fonts",
+            Feature::Clipping => "overflow clipping",
+            Feature::Shadows => "shadows",
+            Feature::Rotation => "rotation",
+        }
+    }
+
+    fn backends(&self) -> &'static [Backend] {
+        match self {
+            Feature::Images => &[Backend::Wgpu, Backend::Raylib, Backend::Web],
+            Feature::Canvas | Feature::WasmView => &[Backend::Raylib, Backend::Ratatui],
+            Feature::Particles | Feature::ShaderEffect => &[Backend::Wgpu, Backend::Raylib],
+            Feature::NativeView | Feature::TextInput | Feature::RichText | Feature::CustomFonts => &[Backend::Raylib],
+            Feature::Clipping => &[Backend::Raylib, Backend::Web],
+            Feature::Shadows => &[Backend::Wgpu, Backend::Raylib, Backend::Web],
+            Feature::Rotation => &[Backend::Wgpu, Backend::Raylib, Backend::Web],
+        }
+    }
+
+    /// Whole elements that fail to draw are errors; lost styling is a warning.
+    fn severity(&self) -> Severity {
+        match self {
+            Feature::Images | Feature::Canvas | Feature::WasmView | Feature::NativeView
+            | Feature::Particles | Feature::TextInput => Severity::Error,
+            Feature::ShaderEffect | Feature::RichText | Feature::CustomFonts | Feature::Clipping
+            | Feature::Shadows | Feature::Rotation => Severity::Warning,
+        }
+    }
+
+    fn used_by(element: &Element) -> Vec<Feature> {
+        let mut features: Vec<Feature> = match element.element_type {
+            ElementType::Image => vec![Feature::Images],
+            ElementType::Canvas => vec![Feature::Canvas],
+            ElementType::WasmView => vec![Feature::WasmView],
+            ElementType::NativeRendererView => vec![Feature::NativeView],
+            ElementType::ParticleEmitter => vec![Feature::Particles],
+            ElementType::Input => vec![Feature::TextInput],
+            _ => Vec::new(),
+        };
+        let props = &element.custom_properties;
+        if props.contains_key("shader_effect") {
+            features.push(Feature::ShaderEffect);
+        }
+        if props.contains_key("spans") {
+            features.push(Feature::RichText);
+        }
+        if !is_generic_font(&element.font_family) {
+            features.push(Feature::CustomFonts);
+        }
+        let clips = |overflow: OverflowType| overflow != OverflowType::Visible;
+        if clips(element.overflow_x) || clips(element.overflow_y) {
+            features.push(Feature::Clipping);
+        }
+        if props.get("shadow").and_then(|v| v.as_string()).is_some_and(|s| !s.is_empty() && s != "none") {
+            features.push(Feature::Shadows);
+        }
+        let rotates = props.get("transform").and_then(|v| v.as_transform()).is_some_and(|transform| {
+            let resolved = ResolvedTransform::resolve_2d(transform, element.size);
+            resolved.rotation != 0.0 || resolved.skew != glam::Vec2::ZERO
+        });
+        if rotates {
+            features.push(Feature::Rotation);
+        }
+        features
+    }
+}
+
+fn check_features(elements: &[(&ElementId, &Element)], backend: Backend, issues: &mut Vec<Issue>) {
+    for (&id, element) in elements {
+        for feature in Feature::used_by(element) {
+            let supported = feature.backends().contains(&backend);
+            // Native views only draw on the backend they were written for
+            let native_mismatch = feature == Feature::NativeView
+                && element.native_backend.as_deref().is_some_and(|native| !native.eq_ignore_ascii_case(backend.as_str()));
+
+            if supported && !native_mismatch {
+                continue;
+            }
+            let message = if native_mismatch {
+                format!("native view targets '{}' and is not drawn by {}", element.native_backend.as_deref().unwrap_or_default(), backend)
+            } else {
+                format!("{} are not supported by the {} backend", feature.name(), backend)
+            };
+            issues.push(Issue {
+                severity: feature.severity(),
+                code: "unsupported_feature",
+                element: Some(element_label(id, element)),
+                message,
+            });
+        }
+    }
+}
+
+/// A newer major version fails: renderers refuse it unless they're told to
+/// read it anyway. Anything else read differently than the file means is a
+/// warning.
+fn check_compatibility(report: &CompatibilityReport, issues: &mut Vec<Issue>) {
+    for warning in &report.warnings {
+        let (severity, code) = match warning {
+            CompatWarning::NewerMajorVersion => (Severity::Error, "unsupported_version"),
+            CompatWarning::NewerMinorVersion => (Severity::Warning, "newer_version"),
+            _ => (Severity::Warning, "skipped_content"),
+        };
+        issues.push(Issue {
+            severity,
+            code,
+            element: None,
+            message: format!("KRB {}: {}", report.file_version, warning),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kryon_core::{EventType, KRBHeader, ScriptEntry};
+    use std::collections::HashMap;
+
+    fn element(id: &str, element_type: ElementType) -> Element {
+        Element { id: id.into(), element_type, ..Default::default() }
+    }
+
+    fn krb(elements: Vec<Element>, scripts: Vec<ScriptEntry>) -> KRBFile {
+        KRBFile {
+            header: KRBHeader {
+                magic: *b"KRB1", version: 1, flags: 0, element_count: elements.len() as u16, style_count: 0,
+                component_count: 0, script_count: scripts.len() as u16, string_count: 0, resource_count: 0,
+                template_variable_count: 0, template_binding_count: 0, transform_count: 0,
+            },
+            strings: Vec::new(),
+            elements: elements.into_iter().enumerate().map(|(index, element)| (index as u32, element)).collect(),
+            styles: HashMap::new(),
+            root_element_id: Some(0),
+            resources: Vec::new(),
+            scripts,
+            template_variables: Vec::new(),
+            template_bindings: Vec::new(),
+            transforms: Vec::new(),
+            fonts: HashMap::new(),
+            compatibility: Default::default(),
+        }
+    }
+
+    fn lua(source: &str) -> ScriptEntry {
+        ScriptEntry { language: "lua".into(), name: "main".into(), bytecode: source.as_bytes().to_vec(), entry_points: Vec::new() }
+    }
+
+    fn codes(report: &ValidationReport) -> Vec<(&str, Option<&str>)> {
+        report.issues.iter().map(|issue| (issue.code, issue.element.as_deref())).collect()
+    }
+
+    #[test]
+    fn reports_missing_assets_fonts_and_dangling_handlers() {
+        let mut image = element("logo", ElementType::Image);
+        image.custom_properties.insert("src".into(), PropertyValue::String("no/such/logo.png".into()));
+        let mut remote = element("avatar", ElementType::Image);
+        remote.custom_properties.insert("src".into(), PropertyValue::String("https://x/avatar.png".into()));
+        let mut button = element("save", ElementType::Button);
+        button.event_handlers.insert(EventType::Click, "save_document".into());
+        button.event_handlers.insert(EventType::Hover, "highlight".into());
+        button.font_family = "Brand Sans".into();
+
+        let file = krb(
+            vec![element("app", ElementType::App), image, remote, button],
+            vec![lua("function highlight()\nend\nlocal helpers = {}\nhelpers.x = function() end")],
+        );
+        let report = validate(&file, "app.krb", Backend::Raylib, &AssetResolver::new());
+
+        assert_eq!(codes(&report), vec![
+            ("missing_asset", Some("logo")),
+            ("unknown_font", Some("save")),
+            ("dangling_handler", Some("save")),
+        ]);
+        assert!(report.issues[2].message.contains("save_document"));
+        assert!(!report.valid);
+        assert_eq!((report.errors, report.warnings, report.exit_code()), (2, 1, 1));
+    }
+
+    #[test]
+    fn reports_features_per_backend() {
+        let mut native = element("chart", ElementType::NativeRendererView);
+        native.native_backend = Some("raylib".into());
+        let mut fancy = element("title", ElementType::Text);
+        fancy.custom_properties.insert("shader_effect".into(), PropertyValue::String("...".into()));
+        fancy.custom_properties.insert("shadow".into(), PropertyValue::String("0 4px 6px #000".into()));
+        let file = krb(vec![element("photo", ElementType::Image), native, fancy], Vec::new());
+
+        let ratatui = validate(&file, "app.krb", Backend::Ratatui, &AssetResolver::new());
+        assert_eq!(codes(&ratatui), vec![
+            ("unsupported_feature", Some("photo")),
+            ("unsupported_feature", Some("chart")),
+            ("unsupported_feature", Some("title")),
+            ("unsupported_feature", Some("title")),
+        ]);
+        assert_eq!((ratatui.errors, ratatui.warnings), (2, 2));
+
+        let raylib = validate(&file, "app.krb", Backend::Raylib, &AssetResolver::new());
+        assert!(raylib.valid, "{:?}", raylib.issues);
+
+        let wgpu = validate(&file, "app.krb", Backend::Wgpu, &AssetResolver::new());
+        assert_eq!((wgpu.errors, wgpu.warnings), (1, 0));
+        assert!(wgpu.issues[0].message.contains("targets 'raylib'"));
+    }
+
+    #[test]
+    fn reports_what_a_newer_krb_version_lost() {
+        let mut file = krb(vec![element("app", ElementType::App)], Vec::new());
+        file.compatibility = CompatibilityReport {
+            warnings: vec![
+                CompatWarning::NewerMajorVersion,
+                CompatWarning::UnknownProperty { table: "element", property_id: 0xC1 },
+            ],
+            ..CompatibilityReport::new(kryon_core::KrbVersion::new(6, 0))
+        };
+        let report = validate(&file, "app.krb", Backend::Wgpu, &AssetResolver::new());
+        assert_eq!(codes(&report), vec![("unsupported_version", None), ("skipped_content", None)]);
+        assert_eq!((report.errors, report.warnings), (1, 1));
+        assert_eq!(report.issues[1].message, "KRB 6.0: skipped unknown element property 0xC1");
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["compatibility"]["file_version"], "6.0");
+        assert_eq!(json["compatibility"]["renderer_version"], "5.0");
+        assert_eq!(json["compatibility"]["warnings"][1], serde_json::json!({ "kind": "unknown_property", "table": "element", "property_id": 193 }));
+    }
+
+    #[test]
+    fn serializes_machine_readable_report() {
+        let report = validate_file("/nonexistent/app.krb", "web".parse().unwrap());
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["backend"], "web");
+        assert_eq!(json["valid"], false);
+        assert_eq!(json["issues"][0]["severity"], "error");
+        assert_eq!(json["issues"][0]["code"], "load_failed");
+        assert!(json["issues"][0].get("element").is_none());
+        assert!(json.get("compatibility").is_none());
+        assert!("gl".parse::<Backend>().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+// crates/kryon-runtime/src/variable_changes.rs
+//! Template variable changes batched for `kryon.watch`.
+//!
+//! Every change the runtime applies to a template variable, whether a
+//! script, a data source, an input or the REPL made it, is recorded here.
+//! Once per frame, after the scripts' changes are applied, the batch is
+//! taken and delivered to the watchers in one call. A variable changed
+//! several times in a frame appears once, with the value it had before the
+//! first change and the value after the last; one changed back to where it
+//! started doesn't appear at all.
+
+use crate::script::engine_trait::ScriptValue;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableChange {
+    pub name: String,
+    /// `None` for a variable that had no value yet
+    pub old: Option<String>,
+    pub new: String,
+}
+
+impl VariableChange {
+    /// `{ name =, old =, new = }` for `_deliver_variable_changes`
+    pub fn to_script_value(&self) -> ScriptValue {
+        ScriptValue::Object(HashMap::from([
+            ("name".to_string(), ScriptValue::String(self.name.clone())),
+            ("old".to_string(), self.old.clone().map_or(ScriptValue::Nil, ScriptValue::String)),
+            ("new".to_string(), ScriptValue::String(self.new.clone())),
+        ]))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct VariableChanges {
+    /// In the order the variables first changed
+    pending: Vec<VariableChange>,
+}
+
+impl VariableChanges {
+    pub fn record(&mut self, name: &str, old: Option<&str>, new: &str) {
+        if old == Some(new) {
+            return;
+        }
+        match self.pending.iter_mut().find(|change| change.name == name) {
+            Some(change) => change.new = new.to_string(),
+            None => self.pending.push(VariableChange {
+                name: name.to_string(),
+                old: old.map(str::to_string),
+                new: new.to_string(),
+            }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// The frame's changes, leaving out variables back at their old value
+    pub fn take(&mut self) -> Vec<VariableChange> {
+        let mut changes = std::mem::take(&mut self.pending);
+        changes.retain(|change| change.old.as_deref() != Some(change.new.as_str()));
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_within_a_frame_are_coalesced_per_variable() {
+        let mut changes = VariableChanges::default();
+        changes.record("count", Some("1"), "2");
+        changes.record("theme", Some("dark"), "light");
+        changes.record("count", Some("2"), "3");
+        changes.record("mode", Some("a"), "b");
+        changes.record("mode", Some("b"), "a");
+        changes.record("unchanged", Some("x"), "x");
+        changes.record("fresh", None, "new");
+
+        let taken = changes.take();
+        let summary: Vec<_> = taken.iter().map(|change| (change.name.as_str(), change.old.as_deref(), change.new.as_str())).collect();
+        assert_eq!(summary, vec![
+            ("count", Some("1"), "3"),
+            ("theme", Some("dark"), "light"),
+            ("fresh", None, "new"),
+        ]);
+        assert!(changes.is_empty() && changes.take().is_empty(), "each change is delivered once");
+    }
+}
@@ -0,0 +1,285 @@
+// crates/kryon-runtime/src/variable_overrides.rs
+//! Deployment-time template variable overrides.
+//!
+//! Values come from `KRYON_VAR_<NAME>` environment variables and
+//! `--var NAME=VALUE` command-line flags and are written into the KRB's
+//! template variable defaults before the app is created, so the
+//! TemplateEngine and script-side reactive variables both start from them.
+
+use kryon_core::{KRBFile, TemplateVariable};
+use thiserror::Error;
+
+/// Prefix of environment variables injected as template variables.
+pub const ENV_PREFIX: &str = "KRYON_VAR_";
+
+/// Precedence rules, printed by the renderers' `--info`.
+pub const PRECEDENCE_HELP: &str = "\
+Template variable precedence (highest first):
+  1. --var NAME=VALUE         command line, repeatable; the last flag for a name wins
+  2. KRYON_VAR_NAME=VALUE     environment
+  3. default compiled into the KRB
+Names match declared variables case-insensitively (KRYON_VAR_API_URL sets api_url).
+Undeclared names are added as new string variables (lowercased when from the environment).
+Overrides are applied before scripts are initialized.";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum OverrideError {
+    #[error("invalid --var '{0}': expected NAME=VALUE")]
+    MissingValue(String),
+    #[error("invalid --var '{0}': variable names use letters, digits and '_'")]
+    InvalidName(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideSource {
+    Environment,
+    CommandLine,
+}
+
+impl OverrideSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverrideSource::Environment => "environment",
+            OverrideSource::CommandLine => "--var",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableOverride {
+    pub name: String,
+    pub value: String,
+    pub source: OverrideSource,
+}
+
+/// Ordered overrides; later entries win over earlier ones for the same name.
+#[derive(Debug, Clone, Default)]
+pub struct VariableOverrides {
+    overrides: Vec<VariableOverride>,
+}
+
+impl VariableOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads `KRYON_VAR_*` from the process environment.
+    pub fn from_env() -> Self {
+        Self::from_env_vars(std::env::vars())
+    }
+
+    pub fn from_env_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut overrides: Vec<VariableOverride> = vars.into_iter()
+            .filter_map(|(key, value)| {
+                let name = key.strip_prefix(ENV_PREFIX)?;
+                is_valid_name(name).then(|| VariableOverride {
+                    name: name.to_string(),
+                    value,
+                    source: OverrideSource::Environment,
+                })
+            })
+            .collect();
+        // Environment order is unspecified; keep --info output stable
+        overrides.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { overrides }
+    }
+
+    /// Parses `--var NAME=VALUE` arguments. The value may contain `=`.
+    pub fn parse_cli(specs: &[String]) -> Result<Self, OverrideError> {
+        let overrides = specs.iter()
+            .map(|spec| {
+                let (name, value) = spec.split_once('=')
+                    .ok_or_else(|| OverrideError::MissingValue(spec.clone()))?;
+                let name = name.trim();
+                if !is_valid_name(name) {
+                    return Err(OverrideError::InvalidName(spec.clone()));
+                }
+                Ok(VariableOverride {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    source: OverrideSource::CommandLine,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { overrides })
+    }
+
+    /// Environment overrides followed by `--var` flags, in precedence order.
+    pub fn from_env_and_cli(specs: &[String]) -> Result<Self, OverrideError> {
+        Ok(Self::from_env().then(Self::parse_cli(specs)?))
+    }
+
+    /// Appends `higher`, whose entries take precedence.
+    pub fn then(mut self, higher: Self) -> Self {
+        self.overrides.extend(higher.overrides);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &VariableOverride> {
+        self.overrides.iter()
+    }
+
+    /// Writes the overrides into the KRB's template variable defaults and
+    /// returns the variable names that were set, with the winning source.
+    pub fn apply(&self, krb_file: &mut KRBFile) -> Vec<(String, OverrideSource)> {
+        let applied = self.apply_to(&mut krb_file.template_variables);
+        krb_file.header.template_variable_count = krb_file.template_variables.len() as u16;
+
+        for (name, source) in &applied {
+            eprintln!("[VARS] {} set from {}", name, source.as_str());
+        }
+        applied
+    }
+
+    /// The `--info` text: precedence rules and each variable's resolved value.
+    pub fn info_report(&self, krb_file: &KRBFile) -> String {
+        let mut variables = krb_file.template_variables.clone();
+        let applied = self.apply_to(&mut variables);
+
+        let mut report = format!("{}\n\nTemplate variables:\n", PRECEDENCE_HELP);
+        if variables.is_empty() {
+            report.push_str("  (none)\n");
+        }
+        for variable in &variables {
+            let source = applied.iter()
+                .find(|(name, _)| *name == variable.name)
+                .map_or("krb default", |(_, source)| source.as_str());
+            report.push_str(&format!("  {} = {:?}  ({})\n", variable.name, variable.default_value, source));
+        }
+        report
+    }
+
+    fn apply_to(&self, variables: &mut Vec<TemplateVariable>) -> Vec<(String, OverrideSource)> {
+        let mut applied: Vec<(String, OverrideSource)> = Vec::new();
+
+        for entry in &self.overrides {
+            let name = resolve_name(variables, entry);
+            match variables.iter_mut().find(|var| var.name == name) {
+                Some(variable) => variable.default_value = entry.value.clone(),
+                None => variables.push(TemplateVariable {
+                    name: name.clone(),
+                    value_type: STRING_VALUE_TYPE,
+                    default_value: entry.value.clone(),
+                }),
+            }
+
+            applied.retain(|(existing, _)| *existing != name);
+            applied.push((name, entry.source));
+        }
+        applied
+    }
+}
+
+/// Value type recorded for variables that only exist as overrides.
+const STRING_VALUE_TYPE: u8 = 1;
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Exact match first, then a case-insensitive match against declared
+/// variables; otherwise the override's own name (lowercased from the environment).
+fn resolve_name(declared: &[TemplateVariable], entry: &VariableOverride) -> String {
+    if declared.iter().any(|var| var.name == entry.name) {
+        return entry.name.clone();
+    }
+    if let Some(var) = declared.iter().find(|var| var.name.eq_ignore_ascii_case(&entry.name)) {
+        return var.name.clone();
+    }
+    match entry.source {
+        OverrideSource::Environment => entry.name.to_ascii_lowercase(),
+        OverrideSource::CommandLine => entry.name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable(name: &str, value: &str) -> TemplateVariable {
+        TemplateVariable { name: name.into(), value_type: STRING_VALUE_TYPE, default_value: value.into() }
+    }
+
+    fn krb(variables: Vec<TemplateVariable>) -> KRBFile {
+        KRBFile {
+            header: kryon_core::KRBHeader {
+                magic: *b"KRB1", version: 1, flags: 0, element_count: 0, style_count: 0,
+                component_count: 0, script_count: 0, string_count: 0, resource_count: 0,
+                template_variable_count: variables.len() as u16, template_binding_count: 0, transform_count: 0,
+            },
+            strings: Vec::new(),
+            elements: Default::default(),
+            styles: Default::default(),
+            root_element_id: None,
+            resources: Vec::new(),
+            scripts: Vec::new(),
+            template_variables: variables,
+            template_bindings: Vec::new(),
+            transforms: Vec::new(),
+            fonts: Default::default(),
+            compatibility: Default::default(),
+        }
+    }
+
+    #[test]
+    fn parses_cli_specs() {
+        let overrides = VariableOverrides::parse_cli(&["api_url=http://x/?a=b".into(), " kiosk = 7".into()]).unwrap();
+        let parsed: Vec<(&str, &str)> = overrides.iter().map(|o| (o.name.as_str(), o.value.as_str())).collect();
+        assert_eq!(parsed, vec![("api_url", "http://x/?a=b"), ("kiosk", " 7")]);
+
+        assert_eq!(
+            VariableOverrides::parse_cli(&["kiosk".into()]).unwrap_err(),
+            OverrideError::MissingValue("kiosk".into())
+        );
+        assert!(VariableOverrides::parse_cli(&["bad-name=1".into()]).is_err());
+    }
+
+    #[test]
+    fn cli_wins_over_environment_and_krb_defaults() {
+        let env = VariableOverrides::from_env_vars(vec![
+            ("KRYON_VAR_API_URL".to_string(), "http://env".to_string()),
+            ("KRYON_VAR_KIOSK_ID".to_string(), "env-kiosk".to_string()),
+            ("KRYON_VAR_TITLE".to_string(), "From env".to_string()),
+            ("PATH".to_string(), "/bin".to_string()),
+        ]);
+        let cli = VariableOverrides::parse_cli(&["api_url=http://cli".into(), "Theme=dark".into()]).unwrap();
+        let mut krb_file = krb(vec![variable("api_url", "http://krb"), variable("Title", "Hi"), variable("count", "0")]);
+
+        let applied = env.then(cli).apply(&mut krb_file);
+
+        let values: Vec<(&str, &str)> = krb_file.template_variables.iter()
+            .map(|var| (var.name.as_str(), var.default_value.as_str()))
+            .collect();
+        assert_eq!(values, vec![
+            ("api_url", "http://cli"),
+            ("Title", "From env"),
+            ("count", "0"),
+            ("kiosk_id", "env-kiosk"),
+            ("Theme", "dark"),
+        ]);
+        assert_eq!(applied, vec![
+            ("kiosk_id".to_string(), OverrideSource::Environment),
+            ("Title".to_string(), OverrideSource::Environment),
+            ("api_url".to_string(), OverrideSource::CommandLine),
+            ("Theme".to_string(), OverrideSource::CommandLine),
+        ]);
+        assert_eq!(krb_file.header.template_variable_count, 5);
+    }
+
+    #[test]
+    fn info_report_lists_sources() {
+        let overrides = VariableOverrides::parse_cli(&["api_url=http://cli".into()]).unwrap();
+        let krb_file = krb(vec![variable("api_url", "http://krb"), variable("title", "Hi")]);
+
+        let report = overrides.info_report(&krb_file);
+        assert!(report.starts_with("Template variable precedence"));
+        assert!(report.contains("  api_url = \"http://cli\"  (--var)\n"));
+        assert!(report.contains("  title = \"Hi\"  (krb default)\n"));
+        // Reporting does not modify the KRB
+        assert_eq!(krb_file.template_variables[0].default_value, "http://krb");
+    }
+}
@@ -0,0 +1,49 @@
+// crates/kryon-shaders/src/effect_prelude.wgsl
+// Prepended to every user shader_effect snippet. The snippet must define:
+//   fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32>
+// `uv` is 0..1 across the element rect, `frag_coord` is in window pixels.
+struct EffectUniforms {
+    rect: vec4<f32>,       // x, y, width, height in pixels
+    color_a: vec4<f32>,    // element background color
+    color_b: vec4<f32>,    // element text/foreground color
+    resolution: vec2<f32>, // viewport size in pixels
+    time: f32,             // seconds since renderer start
+    _padding: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> kryon: EffectUniforms;
+
+struct EffectVertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> EffectVertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let uv = corners[vertex_index];
+    let pixel = kryon.rect.xy + uv * kryon.rect.zw;
+
+    var out: EffectVertexOutput;
+    out.clip_position = vec4<f32>(
+        pixel.x / kryon.resolution.x * 2.0 - 1.0,
+        1.0 - pixel.y / kryon.resolution.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: EffectVertexOutput) -> @location(0) vec4<f32> {
+    return effect(in.uv, in.clip_position.xy);
+}
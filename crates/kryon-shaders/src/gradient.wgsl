@@ -0,0 +1,114 @@
+// crates/kryon-shaders/src/gradient.wgsl
+// Linear and radial gradient fill with rounded corners. Each vertex carries
+// the whole gradient, so any number of them batch into one draw. Offsets
+// along the gradient match kryon_core::Gradient::offset_at.
+
+struct ViewProjection {
+    view_proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> view_projection: ViewProjection;
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    // xy: position in the box, zw: box size
+    @location(1) shape: vec4<f32>,
+    // x: kind (0 linear, 1 ellipse, 2 circle), y: angle, z: corner radius, w: stop count
+    @location(2) params: vec4<f32>,
+    @location(3) offsets_low: vec4<f32>,
+    @location(4) offsets_high: vec4<f32>,
+    @location(5) color_0: vec4<f32>,
+    @location(6) color_1: vec4<f32>,
+    @location(7) color_2: vec4<f32>,
+    @location(8) color_3: vec4<f32>,
+    @location(9) color_4: vec4<f32>,
+    @location(10) color_5: vec4<f32>,
+    @location(11) color_6: vec4<f32>,
+    @location(12) color_7: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) shape: vec4<f32>,
+    @location(1) @interpolate(flat) params: vec4<f32>,
+    @location(2) @interpolate(flat) offsets_low: vec4<f32>,
+    @location(3) @interpolate(flat) offsets_high: vec4<f32>,
+    @location(4) @interpolate(flat) color_0: vec4<f32>,
+    @location(5) @interpolate(flat) color_1: vec4<f32>,
+    @location(6) @interpolate(flat) color_2: vec4<f32>,
+    @location(7) @interpolate(flat) color_3: vec4<f32>,
+    @location(8) @interpolate(flat) color_4: vec4<f32>,
+    @location(9) @interpolate(flat) color_5: vec4<f32>,
+    @location(10) @interpolate(flat) color_6: vec4<f32>,
+    @location(11) @interpolate(flat) color_7: vec4<f32>,
+}
+
+@vertex
+fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = view_projection.view_proj * model.position;
+    out.shape = model.shape;
+    out.params = model.params;
+    out.offsets_low = model.offsets_low;
+    out.offsets_high = model.offsets_high;
+    out.color_0 = model.color_0;
+    out.color_1 = model.color_1;
+    out.color_2 = model.color_2;
+    out.color_3 = model.color_3;
+    out.color_4 = model.color_4;
+    out.color_5 = model.color_5;
+    out.color_6 = model.color_6;
+    out.color_7 = model.color_7;
+    return out;
+}
+
+fn gradient_offset(local: vec2<f32>, size: vec2<f32>, kind: f32, angle: f32) -> f32 {
+    let centered = local - size * 0.5;
+    if (kind < 0.5) {
+        let along = vec2<f32>(sin(angle), -cos(angle));
+        let length = abs(size.x * sin(angle)) + abs(size.y * cos(angle));
+        return dot(centered, along) / max(length, 0.0001) + 0.5;
+    }
+    if (kind < 1.5) {
+        return length(centered / max(size * 0.5, vec2<f32>(0.0001))) / sqrt(2.0);
+    }
+    return length(centered) / max(length(size * 0.5), 0.0001);
+}
+
+// Signed distance to a rounded box centered on the origin
+fn rounded_box(point: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    let q = abs(point) - half_size + vec2<f32>(radius);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let local = in.shape.xy;
+    let size = in.shape.zw;
+    let radius = min(in.params.z, min(size.x, size.y) * 0.5);
+    let count = i32(in.params.w);
+
+    var offsets = array<f32, 8>(
+        in.offsets_low.x, in.offsets_low.y, in.offsets_low.z, in.offsets_low.w,
+        in.offsets_high.x, in.offsets_high.y, in.offsets_high.z, in.offsets_high.w,
+    );
+    var colors = array<vec4<f32>, 8>(
+        in.color_0, in.color_1, in.color_2, in.color_3,
+        in.color_4, in.color_5, in.color_6, in.color_7,
+    );
+
+    let t = gradient_offset(local, size, in.params.x, in.params.y);
+    var color = colors[0];
+    for (var i = 1; i < count; i++) {
+        if (t > offsets[i - 1]) {
+            let span = offsets[i] - offsets[i - 1];
+            let amount = select(1.0, clamp((t - offsets[i - 1]) / span, 0.0, 1.0), span > 0.0);
+            color = mix(colors[i - 1], colors[i], amount);
+        }
+    }
+
+    let distance = rounded_box(local - size * 0.5, size * 0.5, radius);
+    let coverage = clamp(0.5 - distance, 0.0, 1.0);
+    return vec4<f32>(color.rgb, color.a * coverage);
+}
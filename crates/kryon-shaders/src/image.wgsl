@@ -0,0 +1,90 @@
+// crates/kryon-shaders/src/image.wgsl
+// Textured quad. Shares the text vertex layout; the vertex color tints the
+// sampled texel, so white with alpha = opacity draws the image unchanged.
+struct ViewProjection {
+    view_proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> view_projection: ViewProjection;
+
+@group(1) @binding(0)
+var t_image: texture_2d<f32>;
+@group(1) @binding(1)
+var s_image: sampler;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) tex_coords: vec2<f32>,
+    @location(2) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.color = model.color;
+    out.tex_coords = model.tex_coords;
+    out.clip_position = view_projection.view_proj * vec4<f32>(model.position, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(t_image, s_image, in.tex_coords) * in.color;
+}
+
+// Filtered variant used by the native backend: grayscale, brightness and
+// contrast come per vertex and apply in sRGB, as CSS filters do, followed by
+// the vertex color's tint.
+struct FilteredVertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) tex_coords: vec2<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) adjust: vec4<f32>,
+}
+
+struct FilteredVertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) adjust: vec4<f32>,
+}
+
+@vertex
+fn vs_filtered(model: FilteredVertexInput) -> FilteredVertexOutput {
+    var out: FilteredVertexOutput;
+    out.color = model.color;
+    out.tex_coords = model.tex_coords;
+    out.adjust = model.adjust;
+    out.clip_position = view_projection.view_proj * vec4<f32>(model.position, 0.0, 1.0);
+    return out;
+}
+
+fn to_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 12.92;
+    let high = 1.055 * pow(linear, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, linear <= vec3<f32>(0.0031308));
+}
+
+fn to_linear(srgb: vec3<f32>) -> vec3<f32> {
+    let low = srgb / 12.92;
+    let high = pow((srgb + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, srgb <= vec3<f32>(0.04045));
+}
+
+@fragment
+fn fs_filtered(in: FilteredVertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureSample(t_image, s_image, in.tex_coords);
+    var rgb = to_srgb(texel.rgb);
+    // x: grayscale amount, y: brightness, z: contrast
+    rgb = mix(rgb, vec3<f32>(dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722))), in.adjust.x);
+    rgb = clamp(rgb * in.adjust.y, vec3<f32>(0.0), vec3<f32>(1.0));
+    rgb = clamp((rgb - 0.5) * in.adjust.z + 0.5, vec3<f32>(0.0), vec3<f32>(1.0));
+    return vec4<f32>(to_linear(rgb * in.color.rgb), texel.a * in.color.a);
+}
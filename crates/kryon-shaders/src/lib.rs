@@ -0,0 +1,300 @@
+// crates/kryon-shaders/src/lib.rs
+//! WGSL sources and pipeline descriptions shared by the native wgpu backend
+//! and the browser WebGPU backend.
+//!
+//! Each backend turns a [`PipelineDescription`] into its own API's pipeline
+//! objects, so vertex layouts and bind groups are declared once next to the
+//! shader that consumes them instead of being restated per backend.
+
+pub const RECT: &str = include_str!("rect.wgsl");
+pub const TEXT: &str = include_str!("text.wgsl");
+pub const IMAGE: &str = include_str!("image.wgsl");
+pub const PARTICLE: &str = include_str!("particle.wgsl");
+/// Prepended to every `shader_effect` snippet.
+pub const EFFECT_PRELUDE: &str = include_str!("effect_prelude.wgsl");
+/// Linear and radial gradient fill with rounded corners.
+pub const GRADIENT: &str = include_str!("gradient.wgsl");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexFormat {
+    Float32,
+    Float32x2,
+    Float32x4,
+}
+
+impl VertexFormat {
+    pub const fn size(self) -> u64 {
+        match self {
+            VertexFormat::Float32 => 4,
+            VertexFormat::Float32x2 => 8,
+            VertexFormat::Float32x4 => 16,
+        }
+    }
+
+    /// Name used by the WebGPU JS API.
+    pub const fn web_name(self) -> &'static str {
+        match self {
+            VertexFormat::Float32 => "float32",
+            VertexFormat::Float32x2 => "float32x2",
+            VertexFormat::Float32x4 => "float32x4",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepMode {
+    Vertex,
+    Instance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttribute {
+    pub location: u32,
+    pub offset: u64,
+    pub format: VertexFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexLayout {
+    pub stride: u64,
+    pub step_mode: StepMode,
+    pub attributes: &'static [VertexAttribute],
+}
+
+/// A bind group entry; its binding index is its position in the group.
+///
+/// Uniforms are visible to the vertex stage, textures and samplers to the
+/// fragment stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binding {
+    Uniform,
+    /// Filterable float texture
+    Texture,
+    /// Filtering sampler
+    Sampler,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineDescription {
+    pub label: &'static str,
+    pub source: &'static str,
+    pub vertex_entry: &'static str,
+    pub fragment_entry: &'static str,
+    pub vertex: VertexLayout,
+    pub bind_groups: &'static [&'static [Binding]],
+    /// Cull back faces. Off for geometry whose winding can flip, such as
+    /// mirrored transforms, user polygons and rotated particles.
+    pub cull_back_faces: bool,
+}
+
+/// `@group(0)` of every pipeline: the view-projection matrix.
+pub const VIEW_PROJECTION_GROUP: &[Binding] = &[Binding::Uniform];
+/// `@group(1)` of textured pipelines.
+pub const TEXTURE_GROUP: &[Binding] = &[Binding::Texture, Binding::Sampler];
+
+/// Homogeneous `vec4` position and color.
+pub const RECT_VERTEX: VertexLayout = VertexLayout {
+    stride: 32,
+    step_mode: StepMode::Vertex,
+    attributes: &[
+        VertexAttribute { location: 0, offset: 0, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 1, offset: 16, format: VertexFormat::Float32x4 },
+    ],
+};
+
+/// Position, texture coordinates and color; used by text and images.
+pub const TEXTURED_VERTEX: VertexLayout = VertexLayout {
+    stride: 32,
+    step_mode: StepMode::Vertex,
+    attributes: &[
+        VertexAttribute { location: 0, offset: 0, format: VertexFormat::Float32x2 },
+        VertexAttribute { location: 1, offset: 8, format: VertexFormat::Float32x2 },
+        VertexAttribute { location: 2, offset: 16, format: VertexFormat::Float32x4 },
+    ],
+};
+
+/// The textured layout plus grayscale, brightness and contrast; used by
+/// filtered images.
+pub const FILTERED_IMAGE_VERTEX: VertexLayout = VertexLayout {
+    stride: 48,
+    step_mode: StepMode::Vertex,
+    attributes: &[
+        VertexAttribute { location: 0, offset: 0, format: VertexFormat::Float32x2 },
+        VertexAttribute { location: 1, offset: 8, format: VertexFormat::Float32x2 },
+        VertexAttribute { location: 2, offset: 16, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 3, offset: 32, format: VertexFormat::Float32x4 },
+    ],
+};
+
+/// Center, size and rotation, and color per particle instance.
+pub const PARTICLE_INSTANCE: VertexLayout = VertexLayout {
+    stride: 32,
+    step_mode: StepMode::Instance,
+    attributes: &[
+        VertexAttribute { location: 0, offset: 0, format: VertexFormat::Float32x2 },
+        VertexAttribute { location: 1, offset: 8, format: VertexFormat::Float32x2 },
+        VertexAttribute { location: 2, offset: 16, format: VertexFormat::Float32x4 },
+    ],
+};
+
+/// Most color stops one gradient vertex carries; gradients with more are
+/// resampled.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// Homogeneous position, position in the box and box size, gradient
+/// parameters, then the stops' offsets and colors.
+pub const GRADIENT_VERTEX: VertexLayout = VertexLayout {
+    stride: 208,
+    step_mode: StepMode::Vertex,
+    attributes: &[
+        VertexAttribute { location: 0, offset: 0, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 1, offset: 16, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 2, offset: 32, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 3, offset: 48, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 4, offset: 64, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 5, offset: 80, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 6, offset: 96, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 7, offset: 112, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 8, offset: 128, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 9, offset: 144, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 10, offset: 160, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 11, offset: 176, format: VertexFormat::Float32x4 },
+        VertexAttribute { location: 12, offset: 192, format: VertexFormat::Float32x4 },
+    ],
+};
+
+pub const RECT_PIPELINE: PipelineDescription = PipelineDescription {
+    label: "Rectangle Pipeline",
+    source: RECT,
+    vertex_entry: "vs_main",
+    fragment_entry: "fs_main",
+    vertex: RECT_VERTEX,
+    bind_groups: &[VIEW_PROJECTION_GROUP],
+    cull_back_faces: false,
+};
+
+/// Samples the red channel of a coverage atlas as alpha.
+pub const TEXT_PIPELINE: PipelineDescription = PipelineDescription {
+    label: "Text Pipeline",
+    source: TEXT,
+    vertex_entry: "vs_main",
+    fragment_entry: "fs_main",
+    vertex: TEXTURED_VERTEX,
+    bind_groups: &[VIEW_PROJECTION_GROUP, TEXTURE_GROUP],
+    cull_back_faces: false,
+};
+
+pub const IMAGE_PIPELINE: PipelineDescription = PipelineDescription {
+    label: "Image Pipeline",
+    source: IMAGE,
+    vertex_entry: "vs_main",
+    fragment_entry: "fs_main",
+    vertex: TEXTURED_VERTEX,
+    bind_groups: &[VIEW_PROJECTION_GROUP, TEXTURE_GROUP],
+    cull_back_faces: false,
+};
+
+/// `IMAGE_PIPELINE` with color filters applied to each texel.
+pub const FILTERED_IMAGE_PIPELINE: PipelineDescription = PipelineDescription {
+    label: "Filtered Image Pipeline",
+    source: IMAGE,
+    vertex_entry: "vs_filtered",
+    fragment_entry: "fs_filtered",
+    vertex: FILTERED_IMAGE_VERTEX,
+    bind_groups: &[VIEW_PROJECTION_GROUP, TEXTURE_GROUP],
+    cull_back_faces: false,
+};
+
+/// Draws six vertices per instance.
+pub const PARTICLE_PIPELINE: PipelineDescription = PipelineDescription {
+    label: "Particle Pipeline",
+    source: PARTICLE,
+    vertex_entry: "vs_main",
+    fragment_entry: "fs_main",
+    vertex: PARTICLE_INSTANCE,
+    bind_groups: &[VIEW_PROJECTION_GROUP],
+    cull_back_faces: false,
+};
+
+pub const GRADIENT_PIPELINE: PipelineDescription = PipelineDescription {
+    label: "Gradient Pipeline",
+    source: GRADIENT,
+    vertex_entry: "vs_main",
+    fragment_entry: "fs_main",
+    vertex: GRADIENT_VERTEX,
+    bind_groups: &[VIEW_PROJECTION_GROUP],
+    cull_back_faces: false,
+};
+
+pub const PIPELINES: &[PipelineDescription] = &[RECT_PIPELINE, TEXT_PIPELINE, IMAGE_PIPELINE, FILTERED_IMAGE_PIPELINE, PARTICLE_PIPELINE, GRADIENT_PIPELINE];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> naga::Module {
+        naga::front::wgsl::parse_str(source).unwrap_or_else(|e| panic!("{}", e.emit_to_string(source)))
+    }
+
+    fn validate(source: &str) {
+        let module = parse(source);
+        naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+            .validate(&module)
+            .unwrap();
+    }
+
+    #[test]
+    fn every_shader_validates() {
+        for source in [RECT, TEXT, IMAGE, PARTICLE, GRADIENT] {
+            validate(source);
+        }
+    }
+
+    #[test]
+    fn attributes_fit_their_stride() {
+        for pipeline in PIPELINES {
+            for attribute in pipeline.vertex.attributes {
+                assert!(attribute.offset + attribute.format.size() <= pipeline.vertex.stride, "{}", pipeline.label);
+            }
+        }
+    }
+
+    #[test]
+    fn descriptions_match_shader_interfaces() {
+        for pipeline in PIPELINES {
+            let module = parse(pipeline.source);
+            let entry = |name: &str| {
+                module.entry_points.iter().find(|e| e.name == name).unwrap_or_else(|| panic!("{} has no {}", pipeline.label, name))
+            };
+            let vertex = entry(pipeline.vertex_entry);
+            entry(pipeline.fragment_entry);
+
+            // Every location the vertex stage reads is supplied by the layout
+            let mut locations = Vec::new();
+            for argument in &vertex.function.arguments {
+                match (&argument.binding, &module.types[argument.ty].inner) {
+                    (Some(naga::Binding::Location { location, .. }), _) => locations.push(*location),
+                    (None, naga::TypeInner::Struct { members, .. }) => {
+                        locations.extend(members.iter().filter_map(|member| match member.binding {
+                            Some(naga::Binding::Location { location, .. }) => Some(location),
+                            _ => None,
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+            locations.sort_unstable();
+            let mut described: Vec<_> = pipeline.vertex.attributes.iter().map(|a| a.location).collect();
+            described.sort_unstable();
+            assert_eq!(locations, described, "{}", pipeline.label);
+
+            // Every resource binding the shader declares is in the described groups
+            for (_, global) in module.global_variables.iter() {
+                if let Some(binding) = &global.binding {
+                    let group = pipeline.bind_groups.get(binding.group as usize);
+                    assert!(group.and_then(|g| g.get(binding.binding as usize)).is_some(), "{} @group({}) @binding({})", pipeline.label, binding.group, binding.binding);
+                }
+            }
+        }
+    }
+}
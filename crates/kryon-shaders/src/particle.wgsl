@@ -0,0 +1,51 @@
+// crates/kryon-shaders/src/particle.wgsl
+struct ViewProjection {
+    view_proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> view_projection: ViewProjection;
+
+struct InstanceInput {
+    @location(0) center: vec2<f32>,
+    @location(1) size_rotation: vec2<f32>, // size in px, rotation in degrees
+    @location(2) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, instance: InstanceInput) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let local = corners[vertex_index];
+    let angle = radians(instance.size_rotation.y);
+    let rotated = vec2<f32>(
+        local.x * cos(angle) - local.y * sin(angle),
+        local.x * sin(angle) + local.y * cos(angle),
+    );
+    let world = instance.center + rotated * instance.size_rotation.x * 0.5;
+
+    var out: VertexOutput;
+    out.clip_position = view_projection.view_proj * vec4<f32>(world, 0.0, 1.0);
+    out.local = local;
+    out.color = instance.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Soft round particle
+    let edge = 1.0 - smoothstep(0.8, 1.0, length(in.local));
+    return vec4<f32>(in.color.rgb, in.color.a * edge);
+}
@@ -1,4 +1,4 @@
-// crates/kryon-wgpu/src/shaders/rect.wgsl
+// crates/kryon-shaders/src/rect.wgsl
 struct ViewProjection {
     view_proj: mat4x4<f32>,
 }
@@ -7,7 +7,7 @@ struct ViewProjection {
 var<uniform> view_projection: ViewProjection;
 
 struct VertexInput {
-    @location(0) position: vec2<f32>,
+    @location(0) position: vec4<f32>,
     @location(1) color: vec4<f32>,
 }
 
@@ -20,7 +20,7 @@ struct VertexOutput {
 fn vs_main(model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.color = model.color;
-    out.clip_position = view_projection.view_proj * vec4<f32>(model.position, 0.0, 1.0);
+    out.clip_position = view_projection.view_proj * model.position;
     return out;
 }
 
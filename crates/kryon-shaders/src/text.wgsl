@@ -1,4 +1,4 @@
-// crates/kryon-wgpu/src/shaders/text.wgsl
+// crates/kryon-shaders/src/text.wgsl
 struct ViewProjection {
     view_proj: mat4x4<f32>,
 }
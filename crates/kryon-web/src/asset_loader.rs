@@ -2,8 +2,9 @@
 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response};
+use web_sys::{Blob, ImageBitmap, ImageData, Request, RequestCache, RequestInit, RequestMode, Response};
 use std::collections::HashMap;
+use std::future::Future;
 
 #[derive(Debug, Clone)]
 pub enum Asset {
@@ -13,9 +14,38 @@ pub enum Asset {
     Binary(Vec<u8>),
 }
 
+/// HTTP cache behaviour for asset requests, sent as the fetch `cache` mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheControl {
+    /// Follow the response's cache headers
+    #[default]
+    Default,
+    /// Revalidate cached responses with the server before using them
+    NoCache,
+    /// Always fetch from the network and update the cache
+    Reload,
+    /// Use any cached response, however stale
+    ForceCache,
+    /// Always fetch from the network and don't cache the response
+    NoStore,
+}
+
+impl CacheControl {
+    fn request_cache(self) -> RequestCache {
+        match self {
+            CacheControl::Default => RequestCache::Default,
+            CacheControl::NoCache => RequestCache::NoCache,
+            CacheControl::Reload => RequestCache::Reload,
+            CacheControl::ForceCache => RequestCache::ForceCache,
+            CacheControl::NoStore => RequestCache::NoStore,
+        }
+    }
+}
+
 pub struct WebAssetLoader {
     cache: HashMap<String, Asset>,
     loading: HashMap<String, JsFuture>,
+    cache_control: CacheControl,
 }
 
 impl WebAssetLoader {
@@ -23,9 +53,18 @@ impl WebAssetLoader {
         Self {
             cache: HashMap::new(),
             loading: HashMap::new(),
+            cache_control: CacheControl::Default,
         }
     }
     
+    pub fn cache_control(&self) -> CacheControl {
+        self.cache_control
+    }
+    
+    pub fn set_cache_control(&mut self, cache_control: CacheControl) {
+        self.cache_control = cache_control;
+    }
+    
     pub async fn load_asset(&mut self, url: &str) -> Result<Asset, JsValue> {
         // Check cache first
         if let Some(asset) = self.cache.get(url) {
@@ -71,16 +110,36 @@ impl WebAssetLoader {
         }
     }
     
+    /// Fetches and decodes an image off the main thread with
+    /// `createImageBitmap`. Icon sources (`icons/home.svg@24px`) fetch the
+    /// SVG and rasterize it at that size instead, so they can be tinted as
+    /// on the other backends. The future doesn't borrow the loader, so it
+    /// can be spawned.
+    pub fn decode_image(&self, url: &str) -> impl Future<Output = Result<ImageBitmap, JsValue>> + 'static {
+        let icon = kryon_render::icon::parse_icon_source(url).map(|(_, size)| size);
+        let url = kryon_render::icon::source_file(url).to_string();
+        let cache_control = self.cache_control;
+        async move {
+            let resp: Response = JsFuture::from(fetch(&request(&url, cache_control)?)?).await?.dyn_into()?;
+            if !resp.ok() {
+                return Err(JsValue::from_str(&format!("Failed to fetch {}: {}", url, resp.status())));
+            }
+            if let Some(size) = icon {
+                let svg = JsFuture::from(resp.text()?).await?.as_string().unwrap_or_default();
+                let icon = kryon_render::icon::rasterize_svg(&svg, size)
+                    .map_err(|e| JsValue::from_str(&format!("Failed to rasterize {}: {}", url, e)))?;
+                let pixels = ImageData::new_with_u8_clamped_array_and_sh(wasm_bindgen::Clamped(&icon.pixels), icon.width, icon.height)?;
+                return JsFuture::from(create_image_bitmap_from_pixels(&pixels)?).await?.dyn_into();
+            }
+            let blob: Blob = JsFuture::from(resp.blob()?).await?.dyn_into()?;
+            JsFuture::from(create_image_bitmap(&blob)?).await?.dyn_into()
+        }
+    }
+    
     async fn fetch_asset(&self, url: &str) -> Result<Asset, JsValue> {
-        let window = web_sys::window().ok_or("No window object")?;
-        
-        let mut opts = RequestInit::new();
-        opts.method("GET");
-        opts.mode(RequestMode::Cors);
+        let request = request(url, self.cache_control)?;
         
-        let request = Request::new_with_str_and_init(url, &opts)?;
-        
-        let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
+        let resp_value = JsFuture::from(fetch(&request)?).await?;
         let resp: Response = resp_value.dyn_into()?;
         
         if !resp.ok() {
@@ -124,14 +183,8 @@ impl WebAssetLoader {
     }
     
     fn start_loading(&self, url: &str) -> JsFuture {
-        let window = web_sys::window().unwrap();
-        
-        let mut opts = RequestInit::new();
-        opts.method("GET");
-        opts.mode(RequestMode::Cors);
-        
-        let request = Request::new_with_str_and_init(url, &opts).unwrap();
-        JsFuture::from(window.fetch_with_request(&request))
+        let request = request(url, self.cache_control).unwrap();
+        JsFuture::from(fetch(&request).unwrap())
     }
     
     pub fn clear_cache(&mut self) {
@@ -141,4 +194,46 @@ impl WebAssetLoader {
     pub fn cache_size(&self) -> usize {
         self.cache.len()
     }
-}
\ No newline at end of file
+}
+
+fn request(url: &str, cache_control: CacheControl) -> Result<Request, JsValue> {
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_cache(cache_control.request_cache());
+    Request::new_with_str_and_init(url, &opts)
+}
+
+/// `fetch` from the window or, inside a worker, the worker scope
+fn fetch(request: &Request) -> Result<js_sys::Promise, JsValue> {
+    let global = js_sys::global();
+    if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+        Ok(window.fetch_with_request(request))
+    } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+        Ok(worker.fetch_with_request(request))
+    } else {
+        Err(JsValue::from_str("fetch is not available"))
+    }
+}
+
+fn create_image_bitmap(blob: &Blob) -> Result<js_sys::Promise, JsValue> {
+    let global = js_sys::global();
+    if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+        window.create_image_bitmap_with_blob(blob)
+    } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+        worker.create_image_bitmap_with_blob(blob)
+    } else {
+        Err(JsValue::from_str("createImageBitmap is not available"))
+    }
+}
+
+fn create_image_bitmap_from_pixels(pixels: &ImageData) -> Result<js_sys::Promise, JsValue> {
+    let global = js_sys::global();
+    if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+        window.create_image_bitmap_with_image_data(pixels)
+    } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+        worker.create_image_bitmap_with_image_data(pixels)
+    } else {
+        Err(JsValue::from_str("createImageBitmap is not available"))
+    }
+}
@@ -1,9 +1,12 @@
 //! Canvas-based web renderer using HTML5 Canvas and WebGL/WebGPU
 
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlCanvasElement, WebGl2RenderingContext, CanvasRenderingContext2d};
+use web_sys::{
+    HtmlCanvasElement, HtmlImageElement, OffscreenCanvas, OffscreenCanvasRenderingContext2d,
+    WebGl2RenderingContext, CanvasRenderingContext2d,
+};
 use kryon_render::{Renderer, RenderResult, RenderError, RenderCommand};
-use kryon_core::Element;
+use kryon_core::{Element, ResolvedTransform, TransformData};
 use kryon_layout::LayoutResult;
 use glam::{Vec2, Vec4};
 
@@ -37,11 +40,123 @@ fn base64_encode(data: &[u8]) -> String {
     result
 }
 
+/// The canvas a renderer draws to: an element on the page, or an
+/// `OffscreenCanvas` transferred to a worker.
+#[derive(Debug, Clone)]
+pub enum CanvasSurface {
+    Element(HtmlCanvasElement),
+    Offscreen(OffscreenCanvas),
+}
+
+impl CanvasSurface {
+    pub fn size(&self) -> Vec2 {
+        match self {
+            Self::Element(canvas) => Vec2::new(canvas.width() as f32, canvas.height() as f32),
+            Self::Offscreen(canvas) => Vec2::new(canvas.width() as f32, canvas.height() as f32),
+        }
+    }
+
+    /// Canvas pixels per CSS pixel to draw at: the page's `devicePixelRatio`,
+    /// or 1 for a worker's canvas, which has no page to ask
+    pub fn pixel_ratio(&self) -> f32 {
+        let ratio = match self {
+            Self::Element(_) => web_sys::window().map_or(1.0, |window| window.device_pixel_ratio() as f32),
+            Self::Offscreen(_) => 1.0,
+        };
+        if ratio.is_finite() && ratio > 0.0 { ratio } else { 1.0 }
+    }
+
+    /// Sizes the backing store to `size` CSS pixels times `scale`; a page
+    /// canvas keeps showing at `size`
+    pub fn set_size(&self, size: Vec2, scale: f32) {
+        let backing = size * scale;
+        match self {
+            Self::Element(canvas) => {
+                canvas.set_width(backing.x as u32);
+                canvas.set_height(backing.y as u32);
+                let style = canvas.style();
+                let _ = style.set_property("width", &format!("{}px", size.x));
+                let _ = style.set_property("height", &format!("{}px", size.y));
+            }
+            Self::Offscreen(canvas) => {
+                canvas.set_width(backing.x as u32);
+                canvas.set_height(backing.y as u32);
+            }
+        }
+    }
+
+    pub fn get_context(&self, context_id: &str) -> Result<Option<js_sys::Object>, JsValue> {
+        match self {
+            Self::Element(canvas) => canvas.get_context(context_id),
+            Self::Offscreen(canvas) => canvas.get_context(context_id),
+        }
+    }
+
+    /// Whether `canvas` can be handed to a worker with
+    /// `transferControlToOffscreen`. False in browsers without OffscreenCanvas
+    /// and for canvases that already have a context.
+    pub fn offscreen_supported(canvas: &HtmlCanvasElement) -> bool {
+        js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("OffscreenCanvas")).unwrap_or(false)
+            && js_sys::Reflect::has(canvas, &JsValue::from_str("transferControlToOffscreen")).unwrap_or(false)
+    }
+}
+
+macro_rules! context_2d {
+    ($($name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        /// The drawing calls of `CanvasRenderingContext2d`, which web-sys binds
+        /// separately for `OffscreenCanvasRenderingContext2d`.
+        trait Context2d {
+            $(fn $name(&self, $($arg: $ty),*) $(-> $ret)?;)*
+        }
+
+        // Colors are passed as CSS strings through the older `JsValue` setters
+        #[allow(deprecated)]
+        impl Context2d for CanvasRenderingContext2d {
+            $(fn $name(&self, $($arg: $ty),*) $(-> $ret)? { CanvasRenderingContext2d::$name(self, $($arg),*) })*
+        }
+
+        #[allow(deprecated)]
+        impl Context2d for OffscreenCanvasRenderingContext2d {
+            $(fn $name(&self, $($arg: $ty),*) $(-> $ret)? { OffscreenCanvasRenderingContext2d::$name(self, $($arg),*) })*
+        }
+    };
+}
+
+context_2d! {
+    save();
+    restore();
+    set_fill_style(value: &JsValue);
+    set_stroke_style(value: &JsValue);
+    set_line_width(value: f64);
+    set_font(value: &str);
+    fill_rect(x: f64, y: f64, w: f64, h: f64);
+    stroke_rect(x: f64, y: f64, w: f64, h: f64);
+    fill_text(text: &str, x: f64, y: f64) -> Result<(), JsValue>;
+    begin_path();
+    close_path();
+    rect(x: f64, y: f64, w: f64, h: f64);
+    clip();
+    move_to(x: f64, y: f64);
+    line_to(x: f64, y: f64);
+    quadratic_curve_to(cpx: f64, cpy: f64, x: f64, y: f64);
+    arc(x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) -> Result<(), JsValue>;
+    fill();
+    stroke();
+    transform(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Result<(), JsValue>;
+    set_transform(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Result<(), JsValue>;
+    set_filter(value: &str);
+    set_global_alpha(value: f64);
+    draw_image_with_html_image_element_and_dw_and_dh(image: &HtmlImageElement, dx: f64, dy: f64, dw: f64, dh: f64) -> Result<(), JsValue>;
+}
+
 pub struct CanvasRenderer {
-    canvas: HtmlCanvasElement,
-    context_2d: Option<CanvasRenderingContext2d>,
+    canvas: CanvasSurface,
+    context_2d: Option<Box<dyn Context2d>>,
     context_webgl: Option<WebGl2RenderingContext>,
+    /// In CSS pixels, which commands are in
     size: Vec2,
+    /// Canvas pixels per CSS pixel, the page's `devicePixelRatio`
+    scale: f32,
     render_mode: RenderMode,
 }
 
@@ -62,6 +177,8 @@ impl CanvasRenderer {
             .dyn_into::<HtmlCanvasElement>()
             .map_err(|_| "Element is not a canvas")?;
         
+        // The canvas is sized in CSS pixels on the page; its backing store
+        // gets the device's pixels so text and edges stay sharp on HiDPI
         let size = Vec2::new(canvas.width() as f32, canvas.height() as f32);
         
         // Try to get WebGL2 context first, fallback to 2D
@@ -79,18 +196,45 @@ impl CanvasRenderer {
                 .ok_or("No 2D context")?
                 .dyn_into::<CanvasRenderingContext2d>()
                 .map_err(|_| "Context is not 2D")?;
-            (Some(context_2d), None, RenderMode::Canvas2D)
+            (Some(Box::new(context_2d) as Box<dyn Context2d>), None, RenderMode::Canvas2D)
         };
         
+        let canvas = CanvasSurface::Element(canvas);
+        let scale = canvas.pixel_ratio();
+        canvas.set_size(size, scale);
         Ok(Self {
             canvas,
             context_2d,
             context_webgl,
             size,
+            scale,
             render_mode,
         })
     }
     
+    /// Draws onto a canvas transferred from the main thread, typically inside
+    /// the layout worker. Offscreen canvases always use the 2D context.
+    pub fn from_offscreen(canvas: OffscreenCanvas) -> Result<Self, JsValue> {
+        let context_2d = canvas
+            .get_context("2d")?
+            .ok_or("No 2D context")?
+            .dyn_into::<OffscreenCanvasRenderingContext2d>()
+            .map_err(|_| "Context is not 2D")?;
+        
+        Ok(Self {
+            size: Vec2::new(canvas.width() as f32, canvas.height() as f32),
+            canvas: CanvasSurface::Offscreen(canvas),
+            context_2d: Some(Box::new(context_2d)),
+            context_webgl: None,
+            scale: 1.0,
+            render_mode: RenderMode::Canvas2D,
+        })
+    }
+    
+    pub fn surface(&self) -> &CanvasSurface {
+        &self.canvas
+    }
+    
     pub fn execute_render_command(&mut self, command: &RenderCommand) -> Result<(), JsValue> {
         match &self.render_mode {
             RenderMode::Canvas2D => self.execute_2d_command(command),
@@ -100,10 +244,18 @@ impl CanvasRenderer {
     }
     
     fn execute_2d_command(&mut self, command: &RenderCommand) -> Result<(), JsValue> {
-        let ctx = self.context_2d.as_ref().ok_or("No 2D context")?;
+        let ctx = self.context_2d.as_deref().ok_or("No 2D context")?;
         
         match command {
-            RenderCommand::DrawRect { position, size, color, border_radius, border_width, border_color, .. } => {
+            RenderCommand::DrawRect { position, size, color, border_radius, border_width, border_color, transform, .. } => {
+                let transformed = match transform {
+                    Some(transform_data) => {
+                        self.push_transform(ctx, transform_data, *position, *size)?;
+                        true
+                    }
+                    None => false,
+                };
+                
                 // Fill rectangle
                 ctx.set_fill_style(&JsValue::from_str(&format!(
                     "rgba({}, {}, {}, {})", 
@@ -136,6 +288,10 @@ impl CanvasRenderer {
                         ctx.stroke_rect(position.x as f64, position.y as f64, size.x as f64, size.y as f64);
                     }
                 }
+                
+                if transformed {
+                    ctx.restore();
+                }
             }
             
             RenderCommand::DrawText { position, text, font_size, color, .. } => {
@@ -161,23 +317,30 @@ impl CanvasRenderer {
                 ctx.save();
             }
             
-            RenderCommand::DrawImage { position, size, image_data, .. } => {
-                // Create an image element and draw it
-                let img = web_sys::HtmlImageElement::new()?;
+            RenderCommand::DrawImage { position, size, source, opacity, filter, .. } => {
+                // Create an image element and draw it; icons are drawn from
+                // their SVG as the browser renders it, untinted
+                let img = HtmlImageElement::new()?;
+                img.set_src(kryon_render::icon::source_file(source));
                 
-                // Convert image data to data URL (simplified)
-                let base64 = base64_encode(image_data);
-                let data_url = format!("data:image/png;base64,{}", base64);
-                img.set_src(&data_url);
+                // Grayscale, brightness and contrast as a CSS filter; of the
+                // tint, only its alpha has a 2D canvas equivalent
+                ctx.save();
+                if let Some(css_filter) = filter.css_filter() {
+                    ctx.set_filter(&css_filter);
+                }
+                ctx.set_global_alpha((*opacity * filter.tint.w) as f64);
                 
                 // Draw the image (this is async in reality, but simplified here)
-                ctx.draw_image_with_html_image_element_and_dw_and_dh(
+                let drawn = ctx.draw_image_with_html_image_element_and_dw_and_dh(
                     &img,
                     position.x as f64,
                     position.y as f64,
                     size.x as f64,
                     size.y as f64,
-                )?;
+                );
+                ctx.restore();
+                drawn?;
             }
             
             RenderCommand::DrawLine { start, end, color, width } => {
@@ -241,7 +404,22 @@ impl CanvasRenderer {
         Ok(())
     }
     
-    fn draw_rounded_rect(&self, ctx: &CanvasRenderingContext2d, position: Vec2, size: Vec2, radius: f32) -> Result<(), JsValue> {
+    /// Saves the context and applies an element's transform about its origin.
+    /// The canvas is 2D, so 3D transforms are flattened (see `kryon_core::transform`).
+    fn push_transform(&self, ctx: &dyn Context2d, transform: &TransformData, position: Vec2, size: Vec2) -> Result<(), JsValue> {
+        let matrix = ResolvedTransform::resolve_2d(transform, size).to_mat4_at(position);
+        ctx.save();
+        ctx.transform(
+            matrix.x_axis.x as f64,
+            matrix.x_axis.y as f64,
+            matrix.y_axis.x as f64,
+            matrix.y_axis.y as f64,
+            matrix.w_axis.x as f64,
+            matrix.w_axis.y as f64,
+        )
+    }
+    
+    fn draw_rounded_rect(&self, ctx: &dyn Context2d, position: Vec2, size: Vec2, radius: f32) -> Result<(), JsValue> {
         let x = position.x as f64;
         let y = position.y as f64;
         let w = size.x as f64;
@@ -264,7 +442,7 @@ impl CanvasRenderer {
         Ok(())
     }
     
-    fn stroke_rounded_rect(&self, ctx: &CanvasRenderingContext2d, position: Vec2, size: Vec2, radius: f32) -> Result<(), JsValue> {
+    fn stroke_rounded_rect(&self, ctx: &dyn Context2d, position: Vec2, size: Vec2, radius: f32) -> Result<(), JsValue> {
         let x = position.x as f64;
         let y = position.y as f64;
         let w = size.x as f64;
@@ -290,7 +468,10 @@ impl CanvasRenderer {
     pub fn clear(&mut self, color: Vec4) -> Result<(), JsValue> {
         match &self.render_mode {
             RenderMode::Canvas2D => {
-                let ctx = self.context_2d.as_ref().ok_or("No 2D context")?;
+                let ctx = self.context_2d.as_deref().ok_or("No 2D context")?;
+                // Each frame draws CSS pixels scaled onto the backing store
+                let scale = self.scale as f64;
+                ctx.set_transform(scale, 0.0, 0.0, scale, 0.0, 0.0)?;
                 ctx.set_fill_style(&JsValue::from_str(&format!(
                     "rgba({}, {}, {}, {})",
                     (color.x * 255.0) as u8,
@@ -307,10 +488,22 @@ impl CanvasRenderer {
         Ok(())
     }
     
+    /// In CSS pixels
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Canvas pixels per CSS pixel
+    pub fn viewport_scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// `new_size` is in CSS pixels. The device pixel ratio is read again,
+    /// since it changes with the page's zoom and the display.
     pub fn resize(&mut self, new_size: Vec2) -> Result<(), JsValue> {
+        self.scale = self.canvas.pixel_ratio();
         self.size = new_size;
-        self.canvas.set_width(new_size.x as u32);
-        self.canvas.set_height(new_size.y as u32);
+        self.canvas.set_size(new_size, self.scale);
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,447 @@
+//! Compact wire format for render commands sent between threads
+//!
+//! The layout worker encodes each frame's `RenderCommand`s into a flat
+//! `f32` buffer plus a string table. The buffer's `ArrayBuffer` is
+//! transferred with `postMessage`, so a frame crosses to the main thread
+//! without being copied or turned into JS objects.
+//!
+//! Only commands the web backends draw are encoded; the rest are dropped by
+//! the worker rather than shipped to be ignored.
+
+use glam::{Vec2, Vec4};
+use kryon_core::{
+    BackgroundRepeat, CSSUnit, CSSUnitValue, RichFontStyle, RichFontWeight, RichTextDecoration, TextAlignment,
+    TextRun, TransformData, TransformOrigin, TransformProperty, TransformPropertyType, TransformType,
+};
+use kryon_render::{ImageFilter, ImageRendering, RenderCommand};
+
+const OP_SET_CANVAS_SIZE: f32 = 1.0;
+const OP_DRAW_RECT: f32 = 2.0;
+const OP_DRAW_TEXT: f32 = 3.0;
+const OP_DRAW_IMAGE: f32 = 4.0;
+const OP_SET_CLIP: f32 = 5.0;
+const OP_CLEAR_CLIP: f32 = 6.0;
+const OP_DRAW_TILED_IMAGE: f32 = 7.0;
+
+/// One frame of encoded render commands.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisplayList {
+    pub numbers: Vec<f32>,
+    pub strings: Vec<String>,
+}
+
+impl DisplayList {
+    pub fn encode(commands: &[RenderCommand]) -> Self {
+        let mut list = Self::default();
+        for command in commands {
+            list.push(command);
+        }
+        list
+    }
+
+    fn push(&mut self, command: &RenderCommand) {
+        match command {
+            RenderCommand::SetCanvasSize(size) => {
+                self.numbers.push(OP_SET_CANVAS_SIZE);
+                self.vec2(*size);
+            }
+            RenderCommand::DrawRect { position, size, color, border_radius, border_width, border_color, transform, shadow, z_index } => {
+                self.numbers.push(OP_DRAW_RECT);
+                self.vec2(*position);
+                self.vec2(*size);
+                self.vec4(*color);
+                self.numbers.extend([*border_radius, *border_width]);
+                self.vec4(*border_color);
+                self.transform(transform.as_ref());
+                self.optional_string(shadow.as_deref());
+                self.numbers.push(*z_index as f32);
+            }
+            RenderCommand::DrawText { position, text, font_size, color, alignment, max_width, max_height, transform, font_family, runs, z_index } => {
+                self.numbers.push(OP_DRAW_TEXT);
+                self.vec2(*position);
+                self.string(text);
+                self.numbers.push(*font_size);
+                self.vec4(*color);
+                self.numbers.push(match alignment {
+                    TextAlignment::Start => 0.0,
+                    TextAlignment::Center => 1.0,
+                    TextAlignment::End => 2.0,
+                    TextAlignment::Justify => 3.0,
+                });
+                self.optional_number(*max_width);
+                self.optional_number(*max_height);
+                self.transform(transform.as_ref());
+                self.optional_string(font_family.as_deref());
+                self.runs(runs);
+                self.numbers.push(*z_index as f32);
+            }
+            RenderCommand::DrawImage { position, size, source, opacity, transform, image_rendering, filter } => {
+                self.numbers.push(OP_DRAW_IMAGE);
+                self.vec2(*position);
+                self.vec2(*size);
+                self.string(source);
+                self.numbers.push(*opacity);
+                self.transform(transform.as_ref());
+                self.image_rendering(*image_rendering);
+                self.numbers.extend([filter.grayscale, filter.brightness, filter.contrast]);
+                self.vec4(filter.tint);
+            }
+            RenderCommand::DrawTiledImage { position, size, source, tile_offset, tile_size, repeat, opacity, image_rendering, transform, z_index } => {
+                self.numbers.push(OP_DRAW_TILED_IMAGE);
+                self.vec2(*position);
+                self.vec2(*size);
+                self.string(source);
+                self.vec2(*tile_offset);
+                self.vec2(*tile_size);
+                self.numbers.push(match repeat {
+                    BackgroundRepeat::Repeat => 0.0,
+                    BackgroundRepeat::RepeatX => 1.0,
+                    BackgroundRepeat::RepeatY => 2.0,
+                    BackgroundRepeat::NoRepeat => 3.0,
+                });
+                self.numbers.push(*opacity);
+                self.image_rendering(*image_rendering);
+                self.transform(transform.as_ref());
+                self.numbers.push(*z_index as f32);
+            }
+            RenderCommand::SetClip { position, size } => {
+                self.numbers.push(OP_SET_CLIP);
+                self.vec2(*position);
+                self.vec2(*size);
+            }
+            RenderCommand::ClearClip => self.numbers.push(OP_CLEAR_CLIP),
+            _ => {}
+        }
+    }
+
+    fn vec2(&mut self, value: Vec2) {
+        self.numbers.extend(value.to_array());
+    }
+
+    fn image_rendering(&mut self, value: ImageRendering) {
+        self.numbers.push(match value {
+            ImageRendering::Auto => 0.0,
+            ImageRendering::Pixelated => 1.0,
+            ImageRendering::Smooth => 2.0,
+        });
+    }
+
+    fn vec4(&mut self, value: Vec4) {
+        self.numbers.extend(value.to_array());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.numbers.push(self.strings.len() as f32);
+        self.strings.push(value.to_string());
+    }
+
+    fn optional_string(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => self.string(value),
+            None => self.numbers.push(-1.0),
+        }
+    }
+
+    fn optional_number(&mut self, value: Option<f32>) {
+        match value {
+            Some(value) => self.numbers.extend([1.0, value]),
+            None => self.numbers.push(0.0),
+        }
+    }
+
+    fn transform(&mut self, transform: Option<&TransformData>) {
+        let Some(transform) = transform else {
+            self.numbers.push(0.0);
+            return;
+        };
+        self.numbers.extend([1.0, transform.transform_type as u8 as f32, transform.properties.len() as f32]);
+        for property in &transform.properties {
+            self.numbers.push(property.property_type as u8 as f32);
+            self.unit_value(property.value);
+        }
+        self.unit_value(transform.origin.x);
+        self.unit_value(transform.origin.y);
+    }
+
+    fn unit_value(&mut self, value: CSSUnitValue) {
+        self.numbers.extend([value.value as f32, value.unit as u8 as f32]);
+    }
+
+    /// Count, then per run its range, an optional color and weight, style
+    /// and decoration codes (0 when unset)
+    fn runs(&mut self, runs: &[TextRun]) {
+        self.numbers.push(runs.len() as f32);
+        for run in runs {
+            self.numbers.extend([run.range.start as f32, run.range.end as f32]);
+            match run.color {
+                Some(color) => {
+                    self.numbers.push(1.0);
+                    self.vec4(color);
+                }
+                None => self.numbers.push(0.0),
+            }
+            self.numbers.extend([
+                run.font_weight.map_or(0.0, |weight| weight as u16 as f32),
+                match run.font_style {
+                    None => 0.0,
+                    Some(RichFontStyle::Normal) => 1.0,
+                    Some(RichFontStyle::Italic) => 2.0,
+                    Some(RichFontStyle::Oblique) => 3.0,
+                },
+                match run.text_decoration {
+                    None => 0.0,
+                    Some(RichTextDecoration::None) => 1.0,
+                    Some(RichTextDecoration::Underline) => 2.0,
+                    Some(RichTextDecoration::Overline) => 3.0,
+                    Some(RichTextDecoration::LineThrough) => 4.0,
+                },
+            ]);
+        }
+    }
+
+    /// Decodes the commands, or `None` if the buffer is malformed.
+    pub fn decode(&self) -> Option<Vec<RenderCommand>> {
+        let mut reader = Reader { list: self, offset: 0 };
+        let mut commands = Vec::new();
+        while reader.offset < self.numbers.len() {
+            commands.push(reader.command()?);
+        }
+        Some(commands)
+    }
+}
+
+struct Reader<'a> {
+    list: &'a DisplayList,
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn command(&mut self) -> Option<RenderCommand> {
+        let op = self.number()?;
+        Some(if op == OP_SET_CANVAS_SIZE {
+            RenderCommand::SetCanvasSize(self.vec2()?)
+        } else if op == OP_DRAW_RECT {
+            RenderCommand::DrawRect {
+                position: self.vec2()?,
+                size: self.vec2()?,
+                color: self.vec4()?,
+                border_radius: self.number()?,
+                border_width: self.number()?,
+                border_color: self.vec4()?,
+                transform: self.transform()?,
+                shadow: self.optional_string()?,
+                z_index: self.number()? as i32,
+            }
+        } else if op == OP_DRAW_TEXT {
+            RenderCommand::DrawText {
+                position: self.vec2()?,
+                text: self.string()?,
+                font_size: self.number()?,
+                color: self.vec4()?,
+                alignment: match self.number()? as u8 {
+                    0 => TextAlignment::Start,
+                    1 => TextAlignment::Center,
+                    2 => TextAlignment::End,
+                    3 => TextAlignment::Justify,
+                    _ => return None,
+                },
+                max_width: self.optional_number()?,
+                max_height: self.optional_number()?,
+                transform: self.transform()?,
+                font_family: self.optional_string()?,
+                runs: self.runs()?,
+                z_index: self.number()? as i32,
+            }
+        } else if op == OP_DRAW_IMAGE {
+            RenderCommand::DrawImage {
+                position: self.vec2()?,
+                size: self.vec2()?,
+                source: self.string()?,
+                opacity: self.number()?,
+                transform: self.transform()?,
+                image_rendering: self.image_rendering()?,
+                filter: ImageFilter {
+                    grayscale: self.number()?,
+                    brightness: self.number()?,
+                    contrast: self.number()?,
+                    tint: self.vec4()?,
+                },
+            }
+        } else if op == OP_DRAW_TILED_IMAGE {
+            RenderCommand::DrawTiledImage {
+                position: self.vec2()?,
+                size: self.vec2()?,
+                source: self.string()?,
+                tile_offset: self.vec2()?,
+                tile_size: self.vec2()?,
+                repeat: match self.number()? as u8 {
+                    0 => BackgroundRepeat::Repeat,
+                    1 => BackgroundRepeat::RepeatX,
+                    2 => BackgroundRepeat::RepeatY,
+                    3 => BackgroundRepeat::NoRepeat,
+                    _ => return None,
+                },
+                opacity: self.number()?,
+                image_rendering: self.image_rendering()?,
+                transform: self.transform()?,
+                z_index: self.number()? as i32,
+            }
+        } else if op == OP_SET_CLIP {
+            RenderCommand::SetClip { position: self.vec2()?, size: self.vec2()? }
+        } else if op == OP_CLEAR_CLIP {
+            RenderCommand::ClearClip
+        } else {
+            return None;
+        })
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        let value = *self.list.numbers.get(self.offset)?;
+        self.offset += 1;
+        Some(value)
+    }
+
+    fn vec2(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.number()?, self.number()?))
+    }
+
+    fn image_rendering(&mut self) -> Option<ImageRendering> {
+        Some(match self.number()? as u8 {
+            0 => ImageRendering::Auto,
+            1 => ImageRendering::Pixelated,
+            2 => ImageRendering::Smooth,
+            _ => return None,
+        })
+    }
+
+    fn vec4(&mut self) -> Option<Vec4> {
+        Some(Vec4::new(self.number()?, self.number()?, self.number()?, self.number()?))
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let index = self.number()?;
+        if index < 0.0 {
+            return None;
+        }
+        self.list.strings.get(index as usize).cloned()
+    }
+
+    /// `Some(None)` for an absent string, `None` if the buffer is malformed
+    fn optional_string(&mut self) -> Option<Option<String>> {
+        if self.list.numbers.get(self.offset)? < &0.0 {
+            self.offset += 1;
+            return Some(None);
+        }
+        self.string().map(Some)
+    }
+
+    fn optional_number(&mut self) -> Option<Option<f32>> {
+        match self.number()? as u8 {
+            0 => Some(None),
+            _ => self.number().map(Some),
+        }
+    }
+
+    fn transform(&mut self) -> Option<Option<TransformData>> {
+        if self.number()? == 0.0 {
+            return Some(None);
+        }
+        let transform_type = match self.number()? as u8 {
+            0x01 => TransformType::Transform2D,
+            0x02 => TransformType::Transform3D,
+            0x03 => TransformType::Matrix2D,
+            0x04 => TransformType::Matrix3D,
+            _ => return None,
+        };
+        let count = self.number()? as usize;
+        let mut properties = Vec::with_capacity(count.min(16));
+        for _ in 0..count {
+            let property_type = transform_property_type(self.number()? as u8)?;
+            properties.push(TransformProperty { property_type, value: self.unit_value()? });
+        }
+        let origin = TransformOrigin { x: self.unit_value()?, y: self.unit_value()? };
+        Some(Some(TransformData { transform_type, properties, origin }))
+    }
+
+    fn runs(&mut self) -> Option<Vec<TextRun>> {
+        let count = self.number()? as usize;
+        let mut runs = Vec::with_capacity(count.min(self.list.numbers.len()));
+        for _ in 0..count {
+            let range = self.number()? as usize..self.number()? as usize;
+            let color = match self.number()? as u8 {
+                0 => None,
+                _ => Some(self.vec4()?),
+            };
+            let font_weight = match self.number()? as u16 {
+                0 => None,
+                100 => Some(RichFontWeight::Thin),
+                200 => Some(RichFontWeight::ExtraLight),
+                300 => Some(RichFontWeight::Light),
+                400 => Some(RichFontWeight::Normal),
+                500 => Some(RichFontWeight::Medium),
+                600 => Some(RichFontWeight::SemiBold),
+                700 => Some(RichFontWeight::Bold),
+                800 => Some(RichFontWeight::ExtraBold),
+                900 => Some(RichFontWeight::Black),
+                _ => return None,
+            };
+            let font_style = match self.number()? as u8 {
+                0 => None,
+                1 => Some(RichFontStyle::Normal),
+                2 => Some(RichFontStyle::Italic),
+                3 => Some(RichFontStyle::Oblique),
+                _ => return None,
+            };
+            let text_decoration = match self.number()? as u8 {
+                0 => None,
+                1 => Some(RichTextDecoration::None),
+                2 => Some(RichTextDecoration::Underline),
+                3 => Some(RichTextDecoration::Overline),
+                4 => Some(RichTextDecoration::LineThrough),
+                _ => return None,
+            };
+            runs.push(TextRun { range, color, font_weight, font_style, text_decoration });
+        }
+        Some(runs)
+    }
+
+    fn unit_value(&mut self) -> Option<CSSUnitValue> {
+        let value = self.number()? as f64;
+        let unit = match self.number()? as u8 {
+            0x01 => CSSUnit::Pixels,
+            0x02 => CSSUnit::Em,
+            0x03 => CSSUnit::Rem,
+            0x04 => CSSUnit::ViewportWidth,
+            0x05 => CSSUnit::ViewportHeight,
+            0x06 => CSSUnit::Percentage,
+            0x07 => CSSUnit::Degrees,
+            0x08 => CSSUnit::Radians,
+            0x09 => CSSUnit::Turns,
+            0x0A => CSSUnit::Number,
+            _ => return None,
+        };
+        Some(CSSUnitValue { value, unit })
+    }
+}
+
+fn transform_property_type(code: u8) -> Option<TransformPropertyType> {
+    use TransformPropertyType::*;
+    Some(match code {
+        0x01 => Scale,
+        0x02 => ScaleX,
+        0x03 => ScaleY,
+        0x04 => TranslateX,
+        0x05 => TranslateY,
+        0x06 => Rotate,
+        0x07 => SkewX,
+        0x08 => SkewY,
+        0x09 => ScaleZ,
+        0x0A => TranslateZ,
+        0x0B => RotateX,
+        0x0C => RotateY,
+        0x0D => RotateZ,
+        0x0E => Perspective,
+        0x0F => Matrix,
+        _ => return None,
+    })
+}
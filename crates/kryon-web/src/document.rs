@@ -0,0 +1,290 @@
+//! The loaded KRB document behind `KryonWebApp`
+//!
+//! Holds the elements, template variables and scripts of the current app so
+//! the host page can read and drive them through the JS interop API, the
+//! same way native hosts use `KryonApp::set_template_variable` and
+//! `KryonApp::get_element`.
+//!
+//! It also lays the elements out with Taffy and turns them into
+//! `RenderCommand`s with the same `ElementRenderer` the native backends
+//! use, again only after scripts, template variables or the viewport
+//! changed something.
+//!
+//! ```js
+//! await app.load_krb(bytes);
+//! app.on('saved', payload => console.log('saved', payload));
+//! app.set_template_variable('user_name', 'Ada');
+//! app.get_element_property('title', 'text'); // "Hello Ada"
+//! ```
+
+use crate::worker::{RecordingBackend, CLEAR_COLOR};
+use glam::Vec2;
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use kryon_core::{CSSUnit, Element, ElementId, KRBFile, Orientation, OrientationVariants, PropertyValue, StyleComputer};
+use kryon_html::stylesheet::css_color;
+use kryon_layout::{LayoutEngine, LayoutResult, TaffyLayoutEngine};
+use kryon_render::{ElementRenderer, RenderCommand, Renderer};
+use kryon_runtime::script::engine_trait::ScriptValue;
+use kryon_runtime::{AppEvent, ScriptSystem, TemplateEngine};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+pub struct WebDocument {
+    elements: HashMap<ElementId, Element>,
+    template_engine: TemplateEngine,
+    script_system: ScriptSystem,
+    root_id: Option<ElementId>,
+    layout_engine: TaffyLayoutEngine,
+    layout: LayoutResult,
+    renderer: ElementRenderer<RecordingBackend>,
+    orientation_variants: OrientationVariants,
+    viewport: Vec2,
+    needs_layout: bool,
+}
+
+impl WebDocument {
+    pub fn load(data: &[u8], viewport: Vec2) -> Result<Self, JsValue> {
+        let krb = kryon_core::load_krb_from_bytes(data).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Self::from_krb(krb, viewport).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    fn from_krb(krb: KRBFile, viewport: Vec2) -> anyhow::Result<Self> {
+        let mut style_computer = StyleComputer::new(&krb.elements, &krb.styles);
+        style_computer.set_default_styles(kryon_core::DefaultStyles::builtin());
+        let mut document = Self {
+            elements: krb.elements.clone(),
+            template_engine: TemplateEngine::new(&krb),
+            script_system: ScriptSystem::new()?,
+            root_id: krb.root_element_id,
+            layout_engine: TaffyLayoutEngine::new(),
+            layout: LayoutResult { computed_positions: HashMap::new(), computed_sizes: HashMap::new() },
+            renderer: ElementRenderer::new(RecordingBackend::initialize(viewport)?, style_computer),
+            orientation_variants: OrientationVariants::from_elements(&krb.elements),
+            viewport,
+            needs_layout: true,
+        };
+        document.apply_orientation();
+
+        document.script_system.initialize(&krb, &document.elements)?;
+        document.script_system.load_compiled_scripts(&krb.scripts)?;
+        let variables = if document.template_engine.has_bindings() {
+            document.template_engine.get_variables().clone()
+        } else {
+            krb.template_variables.iter().map(|var| (var.name.clone(), var.default_value.clone())).collect()
+        };
+        document.script_system.initialize_template_variables(&variables)?;
+        document.script_system.apply_pending_changes(&mut document.elements)?;
+        document.template_engine.update_elements(&mut document.elements);
+        document.script_system.execute_init_functions()?;
+        Ok(document)
+    }
+
+    /// Sets a template variable for both bindings and scripts
+    pub fn set_template_variable(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        self.script_system.set_template_variable(name, value)?;
+        self.template_engine.set_variable(name, value);
+        self.template_engine.update_elements(&mut self.elements);
+        self.needs_layout = true;
+        Ok(())
+    }
+
+    pub fn template_variable(&self, name: &str) -> Option<&str> {
+        self.template_engine.get_variable(name)
+    }
+
+    pub fn template_variable_names(&self) -> Vec<String> {
+        self.template_engine.get_variable_names()
+    }
+
+    pub fn element(&self, id: &str) -> Option<&Element> {
+        self.elements.values().find(|element| element.id == id)
+    }
+
+    /// `id`s of all elements that have one
+    pub fn element_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.elements.values()
+            .filter(|element| !element.id.is_empty())
+            .map(|element| element.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Applies element and template variable changes made by scripts since
+    /// the last call and returns the events they raised with `kryon.emit`.
+    pub fn update(&mut self) -> anyhow::Result<Vec<AppEvent>> {
+        let changes = self.script_system.get_pending_changes()?;
+        if let Some(template_changes) = changes.get("template_variables") {
+            for (name, value) in &template_changes.data {
+                self.template_engine.set_variable(name, value);
+            }
+            self.template_engine.update_elements(&mut self.elements);
+            self.needs_layout |= !template_changes.data.is_empty();
+        }
+        self.needs_layout |= self.script_system.apply_pending_dom_changes(&mut self.elements, &changes)?;
+        self.script_system.clear_pending_changes()?;
+        Ok(self.script_system.take_app_events())
+    }
+
+    /// Lays the document out again for a new canvas or container size
+    pub fn set_viewport(&mut self, viewport: Vec2) {
+        if viewport == self.viewport {
+            return;
+        }
+        let rotated = Orientation::from_size(viewport) != Orientation::from_size(self.viewport);
+        self.viewport = viewport;
+        let _ = self.renderer.resize(viewport);
+        if rotated {
+            self.apply_orientation();
+        }
+        self.needs_layout = true;
+    }
+
+    fn apply_orientation(&mut self) {
+        let orientation = Orientation::from_size(self.viewport);
+        self.orientation_variants.apply(&mut self.elements, orientation);
+        self.renderer.set_orientation(orientation);
+    }
+
+    /// Lays out and generates the commands for a new frame, if anything
+    /// changed since the last one. `None` when the last frame still holds.
+    pub fn frame(&mut self) -> anyhow::Result<Option<Vec<RenderCommand>>> {
+        let Some(root_id) = self.root_id else {
+            return Ok(None);
+        };
+        if !self.needs_layout {
+            return Ok(None);
+        }
+        self.layout = self.layout_engine.compute_layout(&self.elements, root_id, self.viewport);
+        self.needs_layout = false;
+        self.renderer.render_frame(&self.elements, &self.layout, root_id, CLEAR_COLOR)?;
+        Ok(self.renderer.backend_mut().take_frame())
+    }
+
+    pub fn root_id(&self) -> Option<ElementId> {
+        self.root_id
+    }
+
+    pub fn elements(&self) -> &HashMap<ElementId, Element> {
+        &self.elements
+    }
+
+    /// Where the last `frame` placed each element
+    pub fn layout(&self) -> &LayoutResult {
+        &self.layout
+    }
+
+    pub fn style_computer(&self) -> &StyleComputer {
+        self.renderer.style_computer()
+    }
+}
+
+/// Named listeners for app events, called in registration order
+#[derive(Default)]
+pub struct EventListeners {
+    listeners: HashMap<String, Vec<js_sys::Function>>,
+}
+
+impl EventListeners {
+    pub fn add(&mut self, name: &str, callback: js_sys::Function) {
+        self.listeners.entry(name.to_string()).or_default().push(callback);
+    }
+
+    /// Removes `callback`, or every listener for `name` if it is `None`
+    pub fn remove(&mut self, name: &str, callback: Option<&js_sys::Function>) {
+        match callback {
+            Some(callback) => {
+                if let Some(listeners) = self.listeners.get_mut(name) {
+                    listeners.retain(|listener| listener != callback);
+                }
+            }
+            None => {
+                self.listeners.remove(name);
+            }
+        }
+    }
+
+    /// Calls each listener for `name` with `payload`. A listener that throws
+    /// is reported and doesn't stop the rest.
+    pub fn dispatch(&self, name: &str, payload: &JsValue) {
+        for listener in self.listeners.get(name).into_iter().flatten() {
+            if let Err(e) = listener.call1(&JsValue::NULL, payload) {
+                web_sys::console::error_2(&format!("Listener for '{}' failed:", name).into(), &e);
+            }
+        }
+    }
+}
+
+/// An element field by the name `:prop` and scripts use, falling back to
+/// custom properties. `None` for unknown names.
+pub fn element_property(element: &Element, name: &str) -> Option<JsValue> {
+    let value = match name {
+        "id" => JsValue::from_str(&element.id),
+        "type" => JsValue::from_str(&format!("{:?}", element.element_type)),
+        "text" => JsValue::from_str(&element.text),
+        "visible" => JsValue::from_bool(element.visible),
+        "disabled" => JsValue::from_bool(element.disabled),
+        "opacity" => JsValue::from_f64(element.opacity as f64),
+        "font_size" => JsValue::from_f64(element.font_size as f64),
+        "font_family" => JsValue::from_str(&element.font_family),
+        "z_index" => JsValue::from_f64(element.z_index as f64),
+        "border_width" => JsValue::from_f64(element.border_width as f64),
+        "border_radius" => JsValue::from_f64(element.border_radius as f64),
+        "background_color" => JsValue::from_str(&css_color(element.background_color)),
+        "text_color" => JsValue::from_str(&css_color(element.text_color)),
+        "border_color" => JsValue::from_str(&css_color(element.border_color)),
+        _ => return element.custom_properties.get(name).map(property_to_js),
+    };
+    Some(value)
+}
+
+/// Colors become CSS `rgba()` strings and units `"12px"`-style strings.
+/// Transforms are `undefined`.
+pub fn property_to_js(value: &PropertyValue) -> JsValue {
+    match value {
+        PropertyValue::String(s) | PropertyValue::Resource(s) => JsValue::from_str(s),
+        PropertyValue::Int(i) => JsValue::from_f64(*i as f64),
+        PropertyValue::Float(f) => JsValue::from_f64(*f as f64),
+        PropertyValue::Percentage(p) => JsValue::from_str(&format!("{}%", p)),
+        PropertyValue::Bool(b) => JsValue::from_bool(*b),
+        PropertyValue::Color(c) => JsValue::from_str(&css_color(*c)),
+        PropertyValue::CSSUnit(unit) => JsValue::from_str(&format!("{}{}", unit.value, unit_suffix(unit.unit))),
+        PropertyValue::RichText(text) => JsValue::from_str(&text.to_plain_text()),
+        PropertyValue::Transform(_) => JsValue::UNDEFINED,
+    }
+}
+
+fn unit_suffix(unit: CSSUnit) -> &'static str {
+    match unit {
+        CSSUnit::Pixels => "px",
+        CSSUnit::Em => "em",
+        CSSUnit::Rem => "rem",
+        CSSUnit::ViewportWidth => "vw",
+        CSSUnit::ViewportHeight => "vh",
+        CSSUnit::Percentage => "%",
+        CSSUnit::Degrees => "deg",
+        CSSUnit::Radians => "rad",
+        CSSUnit::Turns => "turn",
+        CSSUnit::Number => "",
+    }
+}
+
+/// Arrays become JS arrays and objects plain JS objects
+pub fn script_value_to_js(value: &ScriptValue) -> JsValue {
+    match value {
+        ScriptValue::Nil => JsValue::NULL,
+        ScriptValue::Boolean(b) => JsValue::from_bool(*b),
+        ScriptValue::Integer(i) => JsValue::from_f64(*i as f64),
+        ScriptValue::Number(n) => JsValue::from_f64(*n),
+        ScriptValue::String(s) => JsValue::from_str(s),
+        ScriptValue::Bytes(bytes) => Uint8Array::from(bytes.as_slice()).into(),
+        ScriptValue::Array(items) => items.iter().map(script_value_to_js).collect::<Array>().into(),
+        ScriptValue::Object(fields) => {
+            let object = Object::new();
+            for (key, value) in fields {
+                let _ = Reflect::set(&object, &JsValue::from_str(key), &script_value_to_js(value));
+            }
+            object.into()
+        }
+    }
+}
@@ -6,7 +6,8 @@
 use wasm_bindgen::prelude::*;
 use web_sys::{Document, Element, HtmlElement, Window};
 use kryon_render::{Renderer, RenderResult, RenderError, RenderCommand};
-use kryon_core::Element as KryonElement;
+use kryon_core::{Element as KryonElement, ElementId, StyleComputer};
+use kryon_html::{StyleSheet, BASE_CLASS, HYDRATION_ATTRIBUTE};
 use kryon_layout::LayoutResult;
 use glam::{Vec2, Vec4};
 use std::collections::HashMap;
@@ -16,10 +17,37 @@ pub struct DomRenderer {
     container: Element,
     element_map: HashMap<String, Element>,
     next_id: u32,
+    stylesheet: StyleSheet,
+    style_element: Option<Element>,
 }
 
 impl DomRenderer {
     pub fn new(container_id: &str) -> Result<Self, JsValue> {
+        Self::open(container_id, true)
+    }
+    
+    /// Attaches to markup pre-rendered by `kryon_html::ssr` with hydration
+    /// enabled, adopting its nodes and stylesheet instead of rebuilding them.
+    /// Pass the id of the SSR root, `kryon-root`.
+    pub fn hydrate(container_id: &str) -> Result<Self, JsValue> {
+        let mut renderer = Self::open(container_id, false)?;
+        
+        let nodes = renderer.container.query_selector_all(&format!("[{}]", HYDRATION_ATTRIBUTE))?;
+        for index in 0..nodes.length() {
+            let Some(node) = nodes.item(index).and_then(|node| node.dyn_into::<Element>().ok()) else {
+                continue;
+            };
+            if let Some(element_id) = node.get_attribute(HYDRATION_ATTRIBUTE) {
+                renderer.element_map.insert(element_id, node);
+            }
+        }
+        renderer.style_element = renderer.document.get_element_by_id("kryon-styles");
+        
+        web_sys::console::log_1(&format!("[DOM] Hydrated {} nodes", renderer.element_map.len()).into());
+        Ok(renderer)
+    }
+    
+    fn open(container_id: &str, clear: bool) -> Result<Self, JsValue> {
         let window = web_sys::window().ok_or("No window object")?;
         let document = window.document().ok_or("No document object")?;
         
@@ -28,7 +56,9 @@ impl DomRenderer {
             .ok_or("Container element not found")?;
         
         // Clear existing content
-        container.set_inner_html("");
+        if clear {
+            container.set_inner_html("");
+        }
         
         // Set up container styles for proper layout
         let container_style = container
@@ -46,9 +76,17 @@ impl DomRenderer {
             container,
             element_map: HashMap::new(),
             next_id: 0,
+            stylesheet: StyleSheet::new(),
+            style_element: None,
         })
     }
     
+    /// The DOM node built for an element by `render_elements` or adopted by
+    /// `hydrate`.
+    pub fn node(&self, element_id: ElementId) -> Option<&Element> {
+        self.element_map.get(&element_id.to_string())
+    }
+    
     pub fn execute_render_command(&mut self, command: &RenderCommand) -> Result<(), JsValue> {
         match command {
             RenderCommand::DrawRect { position, size, color, border_radius, border_width, border_color, .. } => {
@@ -158,6 +196,74 @@ impl DomRenderer {
         Ok(())
     }
     
+    /// Builds the DOM from the element tree rather than from render commands,
+    /// with semantic tags and ARIA attributes (see `semantics`). Styles are
+    /// compiled into a shared `<style>` sheet; each node only carries its
+    /// classes and its layout position, relative to its parent, inline.
+    pub fn render_elements(&mut self, elements: &HashMap<ElementId, KryonElement>, layout: &LayoutResult, style_computer: &StyleComputer, root_id: ElementId) -> Result<(), JsValue> {
+        self.clear()?;
+        let container = self.container.clone();
+        self.append_element(elements, layout, style_computer, root_id, &container, Vec2::ZERO)?;
+        self.update_stylesheet()
+    }
+    
+    fn append_element(&mut self, elements: &HashMap<ElementId, KryonElement>, layout: &LayoutResult, style_computer: &StyleComputer, element_id: ElementId, parent: &Element, parent_position: Vec2) -> Result<(), JsValue> {
+        let (Some(element), Some(semantics)) = (elements.get(&element_id), kryon_html::describe(elements, element_id)) else {
+            return Ok(());
+        };
+        if !element.visible {
+            return Ok(());
+        }
+        
+        let node = self.document.create_element(semantics.tag)?;
+        for (name, value) in &semantics.attributes {
+            node.set_attribute(name, value)?;
+        }
+        let class = self.stylesheet.class_for(style_computer, element, element_id);
+        node.set_class_name(&format!("{} {}", BASE_CLASS, class));
+        
+        let position = layout.computed_positions.get(&element_id).copied().unwrap_or(element.position);
+        let size = layout.computed_sizes.get(&element_id).copied().unwrap_or(element.size);
+        node.set_attribute(HYDRATION_ATTRIBUTE, &element_id.to_string())?;
+        node.set_attribute("style", &kryon_html::node_style(position, parent_position, size))?;
+        
+        if let Some(text) = &semantics.text {
+            node.set_text_content(Some(text));
+        }
+        parent.append_child(&node)?;
+        
+        if !semantics.is_void() {
+            for &child_id in &element.children {
+                self.append_element(elements, layout, style_computer, child_id, &node, position)?;
+            }
+        }
+        
+        self.element_map.insert(element_id.to_string(), node);
+        Ok(())
+    }
+    
+    /// Writes the compiled classes into the `<style>` element, creating it
+    /// in the document head on first use.
+    fn update_stylesheet(&mut self) -> Result<(), JsValue> {
+        let style_element = match &self.style_element {
+            Some(style_element) => style_element.clone(),
+            None => {
+                let style_element = self.document.create_element("style")?;
+                let head = self.document.head().ok_or("No document head")?;
+                head.append_child(&style_element)?;
+                self.style_element = Some(style_element.clone());
+                style_element
+            }
+        };
+        style_element.set_text_content(Some(&self.stylesheet.css()));
+        Ok(())
+    }
+    
+    /// The stylesheet compiled so far.
+    pub fn stylesheet(&self) -> &StyleSheet {
+        &self.stylesheet
+    }
+    
     pub fn clear(&mut self) -> Result<(), JsValue> {
         self.container.set_inner_html("");
         self.element_map.clear();
@@ -182,4 +288,4 @@ impl DomRenderer {
         self.next_id += 1;
         id
     }
-}
\ No newline at end of file
+}
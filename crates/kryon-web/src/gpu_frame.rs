@@ -0,0 +1,562 @@
+//! Turns render commands into vertex data for the WebGPU renderer
+//!
+//! Commands are drawn in the order given. Consecutive commands that use the
+//! same pipeline, texture and clip share a batch, so a frame is a handful of
+//! draw calls over four buffers: rect vertices, textured vertices (text and
+//! images), gradient vertices and particle instances. Everything here is plain geometry, so it
+//! runs without a GPU.
+
+use glam::{Mat4, Vec2, Vec4};
+use kryon_core::{tile_pieces, ResolvedTransform, RichText, TextAlignment, TransformData};
+use kryon_render::{text_input::{self, TEXT_INPUT_PADDING}, RenderCommand, ScrollbarOrientation, ShaderGradient};
+use crate::texture_cache::ImageStatus;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// Floats per vertex in the rect and textured buffers, and per particle instance
+pub const VERTEX_FLOATS: usize = 8;
+/// Floats per vertex in the gradient buffer, which carries the whole gradient
+pub const GRADIENT_VERTEX_FLOATS: usize = (kryon_shaders::GRADIENT_VERTEX.stride / 4) as usize;
+
+const CORNER_SEGMENTS: usize = 8;
+const ELLIPSE_SEGMENTS: usize = 48;
+const LINE_HEIGHT: f32 = 1.2;
+const DEFAULT_FONT: &str = "Arial";
+const PLACEHOLDER_COLOR: Vec4 = Vec4::new(0.88, 0.88, 0.88, 1.0);
+const PLACEHOLDER_MARK_COLOR: Vec4 = Vec4::new(0.6, 0.6, 0.6, 1.0);
+
+/// A glyph's atlas coordinates and metrics at the requested font size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    /// `[u0, v0, u1, v1]`
+    pub uv: [f32; 4],
+    pub size: Vec2,
+    pub advance: f32,
+}
+
+pub trait GlyphSource {
+    /// `None` when the glyph can't be rasterized this frame.
+    fn glyph(&mut self, character: char, font_size: f32, font_family: &str) -> Option<Glyph>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchKind {
+    Rect,
+    Text,
+    Image(String),
+    Gradient,
+    Particles,
+}
+
+/// Scissor rectangle in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clip {
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+impl Clip {
+    pub fn intersect(&self, other: &Clip) -> Clip {
+        let start = self.position.max(other.position);
+        let end = (self.position + self.size).min(other.position + other.size);
+        Clip { position: start, size: (end - start).max(Vec2::ZERO) }
+    }
+}
+
+/// A run of vertices (or particle instances) drawn with one pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch {
+    pub kind: BatchKind,
+    pub first: u32,
+    pub count: u32,
+    pub clip: Option<Clip>,
+}
+
+#[derive(Debug, Default)]
+pub struct GpuFrame {
+    pub rect_vertices: Vec<f32>,
+    pub textured_vertices: Vec<f32>,
+    pub gradient_vertices: Vec<f32>,
+    pub particle_instances: Vec<f32>,
+    pub batches: Vec<Batch>,
+    /// Set by `SetCanvasSize`
+    pub canvas_size: Option<Vec2>,
+    clip: Option<Clip>,
+}
+
+impl GpuFrame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    /// Records `command`. `image_status` reports whether an image source has
+    /// texture; images without one are skipped.
+    pub fn push(&mut self, command: &RenderCommand, glyphs: &mut dyn GlyphSource, image_status: &mut dyn FnMut(&str) -> ImageStatus) {
+        match command {
+            RenderCommand::DrawRect { position, size, color, border_radius, border_width, border_color, transform, .. } => {
+                let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                self.rounded_rect(*position, *size, *border_radius, *color, matrix.as_ref());
+                self.border(*position, *size, *border_radius, *border_width, *border_color, matrix.as_ref());
+            }
+            RenderCommand::DrawText { position, text, font_size, color, alignment, max_width, max_height, transform, font_family, .. } => {
+                let family = font_family.as_deref().unwrap_or(DEFAULT_FONT);
+                let runs = [(text.as_str(), *font_size, *color, family)];
+                self.text(&runs, *position, *alignment, *max_width, *max_height, transform.as_ref(), glyphs);
+            }
+            RenderCommand::DrawRichText { position, rich_text, max_width, max_height, default_color, alignment, transform, .. } => {
+                let runs = rich_text_runs(rich_text, *default_color);
+                let alignment = alignment.unwrap_or(TextAlignment::Start);
+                self.text(&runs, *position, alignment, max_width.or(rich_text.max_width), *max_height, transform.as_ref(), glyphs);
+            }
+            RenderCommand::DrawImage { position, size, source, opacity, transform, filter, .. } => {
+                // Textured vertices only carry a color, so of the filter only
+                // the tint and darkening apply
+                let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                let darken = filter.brightness.min(1.0);
+                let tint = filter.tint * Vec4::new(darken, darken, darken, opacity.clamp(0.0, 1.0));
+                self.image(source, image_status(source), *position, *size, tint, matrix.as_ref());
+            }
+            RenderCommand::DrawTiledImage { position, size, source, tile_offset, tile_size, repeat, opacity, transform, .. } => {
+                // No placeholder: the background color shows until the image loads
+                if image_status(source) == ImageStatus::Ready {
+                    let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                    let color = Vec4::new(1.0, 1.0, 1.0, opacity.clamp(0.0, 1.0));
+                    for piece in tile_pieces(*size, *tile_offset, *tile_size, *repeat) {
+                        let uv = [piece.uv_min.x, piece.uv_min.y, piece.uv_max.x, piece.uv_max.y];
+                        let data = textured_quad(*position + piece.position, piece.size, uv, color, matrix.as_ref());
+                        self.extend(BatchKind::Image(source.clone()), &data);
+                    }
+                }
+            }
+            RenderCommand::DrawGradientRect { position, size, gradient, border_radius, transform, .. } => {
+                let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                self.gradient(*position, *size, &ShaderGradient::new(gradient, *size, *border_radius), matrix.as_ref());
+            }
+            RenderCommand::DrawCanvasImage { source, position, size, opacity } => {
+                self.image(source, image_status(source), *position, *size, Vec4::new(1.0, 1.0, 1.0, opacity.clamp(0.0, 1.0)), None);
+            }
+            RenderCommand::SetClip { position, size } => self.intersect_clip(Clip { position: *position, size: *size }),
+            RenderCommand::ClearClip => self.clip = None,
+            RenderCommand::SetCanvasSize(size) => self.canvas_size = Some(*size),
+            RenderCommand::DrawParticles { particles, .. } => {
+                for particle in particles {
+                    let [r, g, b, a] = particle.color.to_array();
+                    let instance = [particle.position.x, particle.position.y, particle.size, particle.rotation, r, g, b, a];
+                    self.extend(BatchKind::Particles, &instance);
+                }
+            }
+            RenderCommand::DrawTextInput { position, size, text, placeholder, font_size, text_color, background_color, border_color, border_width, border_radius, caret, scroll_offset, blink_phase, transform, .. } => {
+                let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                self.rounded_rect(*position, *size, *border_radius, *background_color, matrix.as_ref());
+                self.border(*position, *size, *border_radius, *border_width, *border_color, matrix.as_ref());
+
+                let saved = self.clip;
+                self.intersect_clip(Clip { position: *position, size: *size });
+                if let Some(caret) = caret {
+                    let rects = text_input::caret_rects(*position, *size, text, *font_size, *caret, *scroll_offset, *blink_phase);
+                    if let Some((selection_position, selection_size)) = rects.selection {
+                        self.rounded_rect(selection_position, selection_size, 0.0, text_input::SELECTION_COLOR, matrix.as_ref());
+                    }
+                    if let Some((caret_position, caret_size)) = rects.caret {
+                        self.rounded_rect(caret_position, caret_size, 0.0, *text_color, matrix.as_ref());
+                    }
+                }
+                let text_position = *position + Vec2::new(TEXT_INPUT_PADDING - scroll_offset, (size.y - font_size * LINE_HEIGHT) / 2.0);
+                let (content, color) = if text.is_empty() {
+                    (placeholder.as_str(), *text_color * Vec4::new(1.0, 1.0, 1.0, 0.5))
+                } else {
+                    (text.as_str(), *text_color)
+                };
+                self.text(&[(content, *font_size, color, DEFAULT_FONT)], text_position, TextAlignment::Start, None, None, transform.as_ref(), glyphs);
+                self.clip = saved;
+            }
+            RenderCommand::DrawCheckbox { position, size, is_checked, text, font_size, text_color, background_color, border_color, border_width, check_color, transform } => {
+                let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                let side = size.y.min(20.0);
+                let box_position = *position + Vec2::new(0.0, (size.y - side) / 2.0);
+                self.rounded_rect(box_position, Vec2::splat(side), 3.0, *background_color, matrix.as_ref());
+                self.border(box_position, Vec2::splat(side), 3.0, *border_width, *border_color, matrix.as_ref());
+                if *is_checked {
+                    let inset = side / 4.0;
+                    self.rounded_rect(box_position + Vec2::splat(inset), Vec2::splat(side - inset * 2.0), 2.0, *check_color, matrix.as_ref());
+                }
+                if !text.is_empty() {
+                    let label = *position + Vec2::new(side + 8.0, (size.y - font_size * LINE_HEIGHT) / 2.0);
+                    self.text(&[(text.as_str(), *font_size, *text_color, DEFAULT_FONT)], label, TextAlignment::Start, None, None, transform.as_ref(), glyphs);
+                }
+            }
+            RenderCommand::DrawSlider { position, size, value, min_value, max_value, track_color, thumb_color, border_color, border_width, transform } => {
+                let matrix = transform_matrix(transform.as_ref(), *position, *size);
+                let track_height = (size.y / 4.0).clamp(2.0, 6.0);
+                let track_position = *position + Vec2::new(0.0, (size.y - track_height) / 2.0);
+                self.rounded_rect(track_position, Vec2::new(size.x, track_height), track_height / 2.0, *track_color, matrix.as_ref());
+
+                let range = max_value - min_value;
+                let fraction = if range > 0.0 { ((value - min_value) / range).clamp(0.0, 1.0) } else { 0.0 };
+                let thumb = size.y.min(18.0);
+                let thumb_position = *position + Vec2::new(fraction * (size.x - thumb), (size.y - thumb) / 2.0);
+                self.rounded_rect(thumb_position, Vec2::splat(thumb), thumb / 2.0, *thumb_color, matrix.as_ref());
+                self.border(thumb_position, Vec2::splat(thumb), thumb / 2.0, *border_width, *border_color, matrix.as_ref());
+            }
+            RenderCommand::DrawScrollbar { position, size, orientation, scroll_position, content_size, viewport_size, track_color, thumb_color, border_color, border_width, .. } => {
+                self.rounded_rect(*position, *size, 0.0, *track_color, None);
+                self.border(*position, *size, 0.0, *border_width, *border_color, None);
+
+                let along = match orientation {
+                    ScrollbarOrientation::Vertical => size.y,
+                    ScrollbarOrientation::Horizontal => size.x,
+                };
+                let visible = if *content_size > 0.0 { (viewport_size / content_size).clamp(0.0, 1.0) } else { 1.0 };
+                let thumb_length = (along * visible).max(16.0).min(along);
+                let scrollable = content_size - viewport_size;
+                let offset = if scrollable > 0.0 { (scroll_position / scrollable).clamp(0.0, 1.0) * (along - thumb_length) } else { 0.0 };
+                let (thumb_position, thumb_size) = match orientation {
+                    ScrollbarOrientation::Vertical => (*position + Vec2::new(0.0, offset), Vec2::new(size.x, thumb_length)),
+                    ScrollbarOrientation::Horizontal => (*position + Vec2::new(offset, 0.0), Vec2::new(thumb_length, size.y)),
+                };
+                let radius = thumb_size.min_element() / 2.0;
+                self.rounded_rect(thumb_position, thumb_size, radius, *thumb_color, None);
+            }
+            RenderCommand::DrawCanvasLine { start, end, color, width } => self.line(*start, *end, *width, *color),
+            RenderCommand::DrawCanvasRect { position, size, fill_color, stroke_color, stroke_width } => {
+                if let Some(fill) = fill_color {
+                    self.rounded_rect(*position, *size, 0.0, *fill, None);
+                }
+                if let Some(stroke) = stroke_color {
+                    self.border(*position, *size, 0.0, *stroke_width, *stroke, None);
+                }
+            }
+            RenderCommand::DrawCanvasCircle { center, radius, fill_color, stroke_color, stroke_width } => {
+                self.shape(&ellipse(*center, Vec2::splat(*radius)), *center, *fill_color, *stroke_color, *stroke_width);
+            }
+            RenderCommand::DrawCanvasEllipse { center, rx, ry, fill_color, stroke_color, stroke_width } => {
+                self.shape(&ellipse(*center, Vec2::new(*rx, *ry)), *center, *fill_color, *stroke_color, *stroke_width);
+            }
+            RenderCommand::DrawCanvasPolygon { points, fill_color, stroke_color, stroke_width } if points.len() >= 3 => {
+                let center = points.iter().copied().sum::<Vec2>() / points.len() as f32;
+                self.shape(points, center, *fill_color, *stroke_color, *stroke_width);
+            }
+            RenderCommand::DrawCanvasText { position, text, font_size, color, font_family, alignment } => {
+                let family = font_family.as_deref().unwrap_or(DEFAULT_FONT);
+                self.text(&[(text.as_str(), *font_size, *color, family)], *position, *alignment, None, None, None, glyphs);
+            }
+            // Shader effects keep the background rect drawn beneath them;
+            // views, canvases and paths have no WebGPU equivalent yet
+            _ => {}
+        }
+    }
+
+    fn intersect_clip(&mut self, clip: Clip) {
+        self.clip = Some(match &self.clip {
+            Some(current) => current.intersect(&clip),
+            None => clip,
+        });
+    }
+
+    /// Appends `data` to the buffer for `kind`, extending the last batch when
+    /// it can be drawn in the same call.
+    fn extend(&mut self, kind: BatchKind, data: &[f32]) {
+        let (buffer, floats) = match kind {
+            BatchKind::Rect => (&mut self.rect_vertices, VERTEX_FLOATS),
+            BatchKind::Text | BatchKind::Image(_) => (&mut self.textured_vertices, VERTEX_FLOATS),
+            BatchKind::Gradient => (&mut self.gradient_vertices, GRADIENT_VERTEX_FLOATS),
+            BatchKind::Particles => (&mut self.particle_instances, VERTEX_FLOATS),
+        };
+        let first = (buffer.len() / floats) as u32;
+        buffer.extend_from_slice(data);
+        let count = (data.len() / floats) as u32;
+
+        if let Some(last) = self.batches.last_mut() {
+            if last.kind == kind && last.clip == self.clip && last.first + last.count == first {
+                last.count += count;
+                return;
+            }
+        }
+        self.batches.push(Batch { kind, first, count, clip: self.clip });
+    }
+
+    fn triangles(&mut self, points: &[Vec2], indices: &[[usize; 3]], color: Vec4, matrix: Option<&Mat4>) {
+        let color = color.to_array();
+        let mut data = Vec::with_capacity(indices.len() * 3 * VERTEX_FLOATS);
+        for triangle in indices {
+            for &index in triangle {
+                let point = points[index];
+                let position = match matrix {
+                    Some(matrix) => {
+                        let transformed = *matrix * point.extend(0.0).extend(1.0);
+                        [transformed.x, transformed.y, 0.0, transformed.w]
+                    }
+                    None => [point.x, point.y, 0.0, 1.0],
+                };
+                data.extend(position);
+                data.extend(color);
+            }
+        }
+        self.extend(BatchKind::Rect, &data);
+    }
+
+    fn rounded_rect(&mut self, position: Vec2, size: Vec2, radius: f32, color: Vec4, matrix: Option<&Mat4>) {
+        if color.w <= 0.0 || size.x <= 0.0 || size.y <= 0.0 {
+            return;
+        }
+        if radius <= 0.0 {
+            let corners = [position, position + Vec2::new(size.x, 0.0), position + size, position + Vec2::new(0.0, size.y)];
+            self.triangles(&corners, &[[0, 1, 2], [0, 2, 3]], color, matrix);
+            return;
+        }
+        let mut points = rounded_outline(position, size, radius, corner_segments(radius));
+        let center = points.len();
+        points.push(position + size / 2.0);
+        self.triangles(&points, &fan(center), color, matrix);
+    }
+
+    /// A quad the gradient shader fills, rounding its corners itself
+    fn gradient(&mut self, position: Vec2, size: Vec2, gradient: &ShaderGradient, matrix: Option<&Mat4>) {
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return;
+        }
+        let corners = [Vec2::ZERO, Vec2::new(size.x, 0.0), size, Vec2::new(0.0, size.y)];
+        let mut data = Vec::with_capacity(6 * GRADIENT_VERTEX_FLOATS);
+        for index in [0, 1, 2, 2, 3, 0] {
+            let local = corners[index];
+            let point = (position + local).extend(0.0).extend(1.0);
+            let point = matrix.map_or(point, |matrix| *matrix * point);
+            data.extend([point.x, point.y, 0.0, point.w]);
+            data.extend([local.x, local.y, size.x, size.y]);
+            data.extend(gradient.params);
+            data.extend(gradient.offsets);
+            data.extend(gradient.colors.iter().flatten());
+        }
+        self.extend(BatchKind::Gradient, &data);
+    }
+
+    /// Border drawn inside the rect, following its rounded corners.
+    fn border(&mut self, position: Vec2, size: Vec2, radius: f32, width: f32, color: Vec4, matrix: Option<&Mat4>) {
+        if width <= 0.0 || color.w <= 0.0 || size.x <= 0.0 || size.y <= 0.0 {
+            return;
+        }
+        let width = width.min(size.min_element() / 2.0);
+        // Both outlines get the same point count, so the ring pairs them up
+        let segments = corner_segments(radius);
+        let outer = rounded_outline(position, size, radius, segments);
+        let inner = rounded_outline(position + Vec2::splat(width), size - Vec2::splat(width * 2.0), radius - width, segments);
+        self.ring(&outer, &inner, color, matrix);
+    }
+
+    fn ring(&mut self, outer: &[Vec2], inner: &[Vec2], color: Vec4, matrix: Option<&Mat4>) {
+        let count = outer.len().min(inner.len());
+        let points: Vec<Vec2> = outer[..count].iter().chain(&inner[..count]).copied().collect();
+        let indices: Vec<[usize; 3]> = (0..count)
+            .flat_map(|i| {
+                let next = (i + 1) % count;
+                [[i, next, count + next], [count + next, count + i, i]]
+            })
+            .collect();
+        self.triangles(&points, &indices, color, matrix);
+    }
+
+    fn line(&mut self, start: Vec2, end: Vec2, width: f32, color: Vec4) {
+        let direction = (end - start).normalize_or_zero();
+        if direction == Vec2::ZERO || width <= 0.0 {
+            return;
+        }
+        let normal = direction.perp() * (width / 2.0);
+        let points = [start - normal, end - normal, end + normal, start + normal];
+        self.triangles(&points, &[[0, 1, 2], [2, 3, 0]], color, None);
+    }
+
+    /// Fills a convex outline from `center` and strokes its edges.
+    fn shape(&mut self, outline: &[Vec2], center: Vec2, fill: Option<Vec4>, stroke: Option<Vec4>, stroke_width: f32) {
+        if let Some(fill) = fill {
+            let mut points = outline.to_vec();
+            points.push(center);
+            self.triangles(&points, &fan(outline.len()), fill, None);
+        }
+        if let Some(stroke) = stroke {
+            for (i, &point) in outline.iter().enumerate() {
+                self.line(point, outline[(i + 1) % outline.len()], stroke_width, stroke);
+            }
+        }
+    }
+
+    /// Draws the image, or a gray placeholder while it loads. Images that
+    /// failed to load get a placeholder crossed out.
+    /// An image multiplied by `tint`, or a placeholder as opaque as the
+    /// tint while it loads or if it failed to
+    fn image(&mut self, source: &str, status: ImageStatus, position: Vec2, size: Vec2, tint: Vec4, matrix: Option<&Mat4>) {
+        let opacity = tint.w;
+        if status == ImageStatus::Ready {
+            let data = textured_quad(position, size, [0.0, 0.0, 1.0, 1.0], tint, matrix);
+            self.extend(BatchKind::Image(source.to_string()), &data);
+            return;
+        }
+
+        self.rounded_rect(position, size, 0.0, PLACEHOLDER_COLOR * Vec4::new(1.0, 1.0, 1.0, opacity), matrix);
+        if status == ImageStatus::Failed {
+            let color = PLACEHOLDER_MARK_COLOR * Vec4::new(1.0, 1.0, 1.0, opacity);
+            let width = (size.min_element() / 40.0).max(1.0);
+            let end = position + size;
+            for (from, to) in [(position, end), (Vec2::new(end.x, position.y), Vec2::new(position.x, end.y))] {
+                let normal = (to - from).normalize_or_zero().perp() * (width / 2.0);
+                let points = [from - normal, to - normal, to + normal, from + normal];
+                self.triangles(&points, &[[0, 1, 2], [2, 3, 0]], color, matrix);
+            }
+        }
+    }
+
+    /// Lays out `runs` line by line and returns the width of the widest line.
+    #[allow(clippy::too_many_arguments)]
+    fn text(
+        &mut self,
+        runs: &[(&str, f32, Vec4, &str)],
+        position: Vec2,
+        alignment: TextAlignment,
+        max_width: Option<f32>,
+        max_height: Option<f32>,
+        transform: Option<&TransformData>,
+        glyphs: &mut dyn GlyphSource,
+    ) -> f32 {
+        // Split the runs into lines of positioned glyphs
+        struct Placed {
+            x: f32,
+            glyph: Glyph,
+            color: Vec4,
+            font_size: f32,
+        }
+        let mut lines: Vec<(Vec<Placed>, f32, f32)> = vec![(Vec::new(), 0.0, 0.0)];
+        for &(text, font_size, color, family) in runs {
+            for character in text.chars() {
+                if character == '\n' {
+                    lines.push((Vec::new(), 0.0, font_size * LINE_HEIGHT));
+                    continue;
+                }
+                let (placed, width, height) = lines.last_mut().expect("at least one line");
+                *height = height.max(font_size * LINE_HEIGHT);
+                if let Some(glyph) = glyphs.glyph(character, font_size, family) {
+                    placed.push(Placed { x: *width, glyph, color, font_size });
+                    *width += glyph.advance;
+                }
+            }
+        }
+
+        let widest = lines.iter().map(|line| line.1).fold(0.0, f32::max);
+        let block_width = max_width.unwrap_or(widest);
+        let block_height: f32 = lines.iter().map(|line| line.2).sum();
+        let matrix = transform_matrix(transform, position, Vec2::new(block_width, block_height));
+
+        let mut data = Vec::new();
+        let mut top = 0.0;
+        for (index, (placed, width, height)) in lines.iter().enumerate() {
+            if index > 0 && max_height.is_some_and(|max| top + height > max) {
+                break;
+            }
+            let offset = match alignment {
+                TextAlignment::Center => (block_width - width) / 2.0,
+                TextAlignment::End => block_width - width,
+                TextAlignment::Start | TextAlignment::Justify => 0.0,
+            };
+            for item in placed {
+                // Runs of different sizes share the line's baseline band
+                let line_offset = height - item.font_size * LINE_HEIGHT;
+                let glyph_position = position + Vec2::new(offset + item.x, top + line_offset);
+                data.extend(textured_quad(glyph_position, item.glyph.size, item.glyph.uv, item.color, matrix.as_ref()));
+            }
+            top += height;
+        }
+        if !data.is_empty() {
+            self.extend(BatchKind::Text, &data);
+        }
+        widest
+    }
+}
+
+fn transform_matrix(transform: Option<&TransformData>, position: Vec2, size: Vec2) -> Option<Mat4> {
+    transform.map(|transform| ResolvedTransform::resolve(transform, size).to_mat4_at(position))
+}
+
+fn textured_quad(position: Vec2, size: Vec2, uv: [f32; 4], color: Vec4, matrix: Option<&Mat4>) -> Vec<f32> {
+    let [u0, v0, u1, v1] = uv;
+    let corners = [
+        (position, [u0, v0]),
+        (position + Vec2::new(size.x, 0.0), [u1, v0]),
+        (position + size, [u1, v1]),
+        (position + Vec2::new(0.0, size.y), [u0, v1]),
+    ];
+    let color = color.to_array();
+    let mut data = Vec::with_capacity(6 * VERTEX_FLOATS);
+    for index in [0, 1, 2, 2, 3, 0] {
+        let (point, tex_coords) = corners[index];
+        // Textured vertices are 2D, so perspective is divided out here
+        let point = match matrix {
+            Some(matrix) => {
+                let transformed = *matrix * point.extend(0.0).extend(1.0);
+                Vec2::new(transformed.x, transformed.y) / transformed.w
+            }
+            None => point,
+        };
+        data.extend([point.x, point.y]);
+        data.extend(tex_coords);
+        data.extend(color);
+    }
+    data
+}
+
+fn corner_segments(radius: f32) -> usize {
+    if radius > 0.0 { CORNER_SEGMENTS } else { 0 }
+}
+
+/// Outline of a rect with circular corners, clockwise from the top-left
+/// corner, with `segments + 1` points per corner.
+fn rounded_outline(position: Vec2, size: Vec2, radius: f32, segments: usize) -> Vec<Vec2> {
+    let radius = radius.min(size.x / 2.0).min(size.y / 2.0).max(0.0);
+    let corners = [
+        (position + Vec2::splat(radius), PI),
+        (position + Vec2::new(size.x - radius, radius), PI + FRAC_PI_2),
+        (position + size - Vec2::splat(radius), 0.0),
+        (position + Vec2::new(radius, size.y - radius), FRAC_PI_2),
+    ];
+    let mut points = Vec::with_capacity(4 * (segments + 1));
+    for (center, start) in corners {
+        for i in 0..=segments {
+            let angle = start + FRAC_PI_2 * i as f32 / segments.max(1) as f32;
+            points.push(center + Vec2::new(angle.cos(), angle.sin()) * radius);
+        }
+    }
+    points
+}
+
+fn ellipse(center: Vec2, radii: Vec2) -> Vec<Vec2> {
+    (0..ELLIPSE_SEGMENTS)
+        .map(|i| {
+            let angle = TAU * i as f32 / ELLIPSE_SEGMENTS as f32;
+            center + Vec2::new(angle.cos(), angle.sin()) * radii
+        })
+        .collect()
+}
+
+/// Triangles fanning from the point at `center` over the `center` points before it.
+fn fan(center: usize) -> Vec<[usize; 3]> {
+    (0..center).map(|i| [center, i, (i + 1) % center]).collect()
+}
+
+fn rich_text_runs(rich_text: &RichText, default_color: Vec4) -> Vec<(&str, f32, Vec4, &str)> {
+    rich_text
+        .spans
+        .iter()
+        .map(|span| {
+            (
+                span.text.as_str(),
+                span.font_size.unwrap_or(16.0),
+                span.color.unwrap_or(default_color),
+                span.font_family.as_deref().unwrap_or(DEFAULT_FONT),
+            )
+        })
+        .collect()
+}
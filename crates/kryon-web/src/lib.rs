@@ -3,13 +3,16 @@
 //! This crate provides WebAssembly-based rendering backends for Kryon applications,
 //! allowing them to run in web browsers.
 
+use kryon_render::RenderCommand;
 use wasm_bindgen::prelude::*;
 use web_sys::console;
+use worker::CLEAR_COLOR;
 
 mod canvas_renderer;
 mod dom_renderer; 
 mod event_handler;
 mod asset_loader;
+mod document;
 mod utils;
 
 #[cfg(feature = "webgpu")]
@@ -20,17 +23,25 @@ mod winit_integration;
 
 #[cfg(feature = "webgpu")]
 mod shaders;
+#[cfg(feature = "webgpu")]
+mod gpu_frame;
+#[cfg(feature = "webgpu")]
+mod texture_cache;
+#[cfg(feature = "webgpu")]
 mod texture_manager;
 mod animation;
 mod profiler;
+mod display_list;
+mod worker;
 
 #[cfg(test)]
 mod tests;
 
-pub use canvas_renderer::CanvasRenderer;
+pub use canvas_renderer::{CanvasRenderer, CanvasSurface};
 pub use dom_renderer::DomRenderer;
 pub use event_handler::WebEventHandler;
-pub use asset_loader::WebAssetLoader;
+pub use asset_loader::{CacheControl, WebAssetLoader};
+pub use document::WebDocument;
 
 #[cfg(feature = "webgpu")]
 pub use webgpu_renderer::WebGpuRenderer;
@@ -38,9 +49,15 @@ pub use webgpu_renderer::WebGpuRenderer;
 #[cfg(feature = "winit")]
 pub use winit_integration::WinitWebBridge;
 
+#[cfg(feature = "webgpu")]
 pub use texture_manager::{TextureManager, TextureDescriptor, TextureFormat, TextureUsage};
+#[cfg(feature = "webgpu")]
+pub use texture_cache::ImageStatus;
 pub use animation::{AnimationSystem, Animation, Transition, AnimationValue, EasingFunction};
 pub use profiler::{PerformanceProfiler, PerformanceMetrics, FrameStats};
+pub use display_list::DisplayList;
+pub use kryon_html::{Semantics, StyleSheet, describe as describe_semantics};
+pub use worker::{LayoutWorkerClient, RecordingBackend, start_layout_worker};
 
 /// Initialize the web renderer with panic hooks and logging
 #[wasm_bindgen(start)]
@@ -63,8 +80,11 @@ pub struct KryonWebApp {
     event_handler: WebEventHandler,
     asset_loader: WebAssetLoader,
     animation_system: AnimationSystem,
-    texture_manager: Option<TextureManager>,
     profiler: PerformanceProfiler,
+    document: Option<WebDocument>,
+    listeners: document::EventListeners,
+    /// Commands of the last laid-out frame, redrawn until something changes
+    commands: Vec<RenderCommand>,
 }
 
 #[wasm_bindgen]
@@ -80,8 +100,10 @@ impl KryonWebApp {
             event_handler: WebEventHandler::new(),
             asset_loader: WebAssetLoader::new(),
             animation_system: AnimationSystem::new(),
-            texture_manager: None,
             profiler: PerformanceProfiler::new(),
+            document: None,
+            listeners: document::EventListeners::default(),
+            commands: Vec::new(),
         }
     }
     
@@ -110,21 +132,171 @@ impl KryonWebApp {
         Ok(())
     }
     
+    /// Initialize the fastest available canvas renderer: WebGPU when the
+    /// browser supports it, otherwise the 2D canvas. Returns the backend used,
+    /// `"webgpu"` or `"canvas"`.
+    #[wasm_bindgen]
+    pub async fn init_gpu(&mut self, canvas_id: &str) -> Result<String, JsValue> {
+        #[cfg(feature = "webgpu")]
+        if WebGpuRenderer::is_available() {
+            match WebGpuRenderer::new(canvas_id).await {
+                Ok(webgpu_renderer) => {
+                    self.webgpu_renderer = Some(webgpu_renderer);
+                    return Ok("webgpu".to_string());
+                }
+                Err(e) => console::warn_2(&"WebGPU initialization failed, falling back to canvas:".into(), &e),
+            }
+        }
+        self.init_canvas(canvas_id)?;
+        Ok("canvas".to_string())
+    }
+    
     /// Check if WebGPU is available
     #[cfg(feature = "webgpu")]
     #[wasm_bindgen]
     pub fn is_webgpu_available() -> bool {
         WebGpuRenderer::is_available()
     }
-    
+
+    /// Call `callback(source)` whenever an image drawn by the WebGPU renderer
+    /// finishes loading, so the page can redraw frames that showed its
+    /// placeholder. Requires `init_webgpu` or `init_gpu` first.
+    #[cfg(feature = "webgpu")]
+    #[wasm_bindgen]
+    pub fn on_image_ready(&mut self, callback: js_sys::Function) -> Result<(), JsValue> {
+        let renderer = self.webgpu_renderer.as_mut().ok_or("WebGPU renderer not initialized")?;
+        renderer.texture_manager().on_texture_ready(move |source| {
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(source));
+        });
+        Ok(())
+    }
+
+    /// HTTP cache mode for image requests: `"default"`, `"no-cache"`,
+    /// `"reload"`, `"force-cache"` or `"no-store"`
+    #[cfg(feature = "webgpu")]
+    #[wasm_bindgen]
+    pub fn set_image_cache(&mut self, mode: &str) -> Result<(), JsValue> {
+        let cache_control = match mode {
+            "default" => CacheControl::Default,
+            "no-cache" => CacheControl::NoCache,
+            "reload" => CacheControl::Reload,
+            "force-cache" => CacheControl::ForceCache,
+            "no-store" => CacheControl::NoStore,
+            _ => return Err(JsValue::from_str(&format!("Unknown cache mode: {}", mode))),
+        };
+        self.asset_loader.set_cache_control(cache_control);
+        if let Some(renderer) = &mut self.webgpu_renderer {
+            renderer.texture_manager().set_cache_control(cache_control);
+        }
+        Ok(())
+    }
+
     /// Load and run a KRB file
     #[wasm_bindgen]
     pub async fn load_krb(&mut self, krb_data: &[u8]) -> Result<(), JsValue> {
         console::log_1(&format!("Loading KRB file, size: {} bytes", krb_data.len()).into());
         
-        // Parse KRB data
-        // TODO: Implement KRB parsing for web
-        
+        self.document = Some(WebDocument::load(krb_data, self.viewport_size())?);
+        self.commands.clear();
+        self.dispatch_app_events()?;
+        Ok(())
+    }
+    
+    /// Resize the renderers and lay the document out for the new size
+    #[wasm_bindgen]
+    pub fn resize(&mut self, width: f32, height: f32) -> Result<(), JsValue> {
+        let size = glam::Vec2::new(width, height);
+        if let Some(canvas_renderer) = &mut self.canvas_renderer {
+            canvas_renderer.resize(size)?;
+        }
+        if let Some(dom_renderer) = &mut self.dom_renderer {
+            dom_renderer.resize(size)?;
+        }
+        #[cfg(feature = "webgpu")]
+        if let Some(webgpu_renderer) = &mut self.webgpu_renderer {
+            webgpu_renderer.resize(size)?;
+        }
+        if let Some(document) = &mut self.document {
+            document.set_viewport(size);
+        }
+        Ok(())
+    }
+    
+    /// Size of the canvas being drawn to, or of the window in DOM mode
+    fn viewport_size(&self) -> glam::Vec2 {
+        #[cfg(feature = "webgpu")]
+        if let Some(webgpu_renderer) = &self.webgpu_renderer {
+            return webgpu_renderer.size();
+        }
+        match &self.canvas_renderer {
+            Some(canvas_renderer) => canvas_renderer.size(),
+            None => utils::get_viewport_size(),
+        }
+    }
+    
+    /// Set a template variable and update the elements bound to it
+    #[wasm_bindgen]
+    pub fn set_template_variable(&mut self, name: &str, value: &str) -> Result<(), JsValue> {
+        self.document_mut()?
+            .set_template_variable(name, value)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+    
+    /// Get a template variable value
+    #[wasm_bindgen]
+    pub fn get_template_variable(&self, name: &str) -> Option<String> {
+        self.document.as_ref()?.template_variable(name).map(str::to_string)
+    }
+    
+    /// Get all template variable names
+    #[wasm_bindgen]
+    pub fn get_template_variable_names(&self) -> Vec<String> {
+        self.document.as_ref().map(WebDocument::template_variable_names).unwrap_or_default()
+    }
+    
+    /// Call `callback(payload)` whenever a script runs `kryon.emit(event_name, payload)`
+    #[wasm_bindgen]
+    pub fn on(&mut self, event_name: &str, callback: js_sys::Function) {
+        self.listeners.add(event_name, callback);
+    }
+    
+    /// Remove a listener added with `on`, or all listeners for the event if
+    /// no callback is given
+    #[wasm_bindgen]
+    pub fn off(&mut self, event_name: &str, callback: Option<js_sys::Function>) {
+        self.listeners.remove(event_name, callback.as_ref());
+    }
+    
+    /// Get an element property by element `id`: `text`, `visible`,
+    /// `opacity`, `background_color` and the other built-in fields, or any
+    /// custom property. `undefined` if the element or property doesn't exist.
+    #[wasm_bindgen]
+    pub fn get_element_property(&self, element_id: &str, property: &str) -> JsValue {
+        self.document.as_ref()
+            .and_then(|document| document.element(element_id))
+            .and_then(|element| document::element_property(element, property))
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+    
+    /// Get the `id` of every element that has one
+    #[wasm_bindgen]
+    pub fn get_element_ids(&self) -> Vec<String> {
+        self.document.as_ref().map(WebDocument::element_ids).unwrap_or_default()
+    }
+    
+    fn document_mut(&mut self) -> Result<&mut WebDocument, JsValue> {
+        self.document.as_mut().ok_or_else(|| JsValue::from_str("No KRB loaded"))
+    }
+    
+    /// Apply script changes and deliver `kryon.emit` events to listeners
+    fn dispatch_app_events(&mut self) -> Result<(), JsValue> {
+        let Some(document) = &mut self.document else {
+            return Ok(());
+        };
+        let events = document.update().map_err(|e| JsValue::from_str(&e.to_string()))?;
+        for event in events {
+            self.listeners.dispatch(&event.name, &document::script_value_to_js(&event.payload));
+        }
         Ok(())
     }
     
@@ -139,27 +311,47 @@ impl KryonWebApp {
         self.animation_system.update(timestamp);
         self.profiler.end_timer("animation");
         
+        self.profiler.begin_timer("scripts");
+        self.dispatch_app_events()?;
+        self.profiler.end_timer("scripts");
+        
+        // Layout and command generation, only when something changed
+        self.profiler.begin_timer("layout");
+        let viewport = self.viewport_size();
+        let changed = match &mut self.document {
+            Some(document) => {
+                document.set_viewport(viewport);
+                let frame = document.frame().map_err(|e| JsValue::from_str(&e.to_string()))?;
+                frame.map(|commands| self.commands = commands).is_some()
+            }
+            None => false,
+        };
+        self.profiler.end_timer("layout");
+        
         // Begin render timing
         self.profiler.begin_timer("render");
         
-        // Clear the renderer
         if let Some(canvas_renderer) = &mut self.canvas_renderer {
-            canvas_renderer.clear(glam::Vec4::new(0.95, 0.95, 0.95, 1.0))?;
-            self.profiler.increment_counter("draw_calls", 1);
+            canvas_renderer.clear(CLEAR_COLOR)?;
+            for command in &self.commands {
+                canvas_renderer.execute_render_command(command)?;
+            }
+            self.profiler.increment_counter("draw_calls", self.commands.len() as u32 + 1);
         }
         
-        if let Some(dom_renderer) = &mut self.dom_renderer {
-            dom_renderer.clear()?;
+        // The DOM is retained: rebuilt only when the layout changed
+        if let (Some(dom_renderer), Some(document), true) = (&mut self.dom_renderer, &self.document, changed) {
+            if let Some(root_id) = document.root_id() {
+                dom_renderer.render_elements(document.elements(), document.layout(), document.style_computer(), root_id)?;
+            }
         }
         
         #[cfg(feature = "webgpu")]
         if let Some(webgpu_renderer) = &mut self.webgpu_renderer {
-            webgpu_renderer.clear(glam::Vec4::new(0.95, 0.95, 0.95, 1.0))?;
+            webgpu_renderer.render_commands(&self.commands, CLEAR_COLOR)?;
             self.profiler.increment_counter("draw_calls", 1);
         }
         
-        // TODO: Render actual content from KRB data with animation values
-        
         // End render timing
         self.profiler.end_timer("render");
         
@@ -0,0 +1,134 @@
+//! WebGPU pipelines built from the `kryon_shaders` descriptions
+//!
+//! The WGSL sources, vertex layouts and bind groups are the ones the native
+//! wgpu backend uses; this module only translates them into the browser's
+//! WebGPU descriptor objects.
+
+use kryon_shaders::{Binding, PipelineDescription, StepMode, VertexFormat};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::sys::JsNullable;
+use web_sys::{
+    GpuBindGroupLayout, GpuBindGroupLayoutDescriptor, GpuBindGroupLayoutEntry, GpuBlendComponent,
+    GpuBlendFactor, GpuBlendState, GpuBufferBindingLayout, GpuBufferBindingType, GpuColorTargetState,
+    GpuCullMode, GpuDevice, GpuFragmentState, GpuPipelineLayoutDescriptor, GpuPrimitiveState,
+    GpuPrimitiveTopology, GpuRenderPipeline, GpuRenderPipelineDescriptor, GpuSamplerBindingLayout,
+    GpuSamplerBindingType, GpuShaderModuleDescriptor, GpuTextureBindingLayout, GpuTextureFormat,
+    GpuTextureSampleType, GpuVertexAttribute, GpuVertexBufferLayout, GpuVertexState, GpuVertexStepMode,
+};
+
+// GPUShaderStage flags
+const VERTEX_STAGE: u32 = 0x1;
+const FRAGMENT_STAGE: u32 = 0x2;
+
+/// The pipelines and shared bind group layouts the WebGPU renderer draws with.
+pub struct ShaderManager {
+    pub view_projection_layout: GpuBindGroupLayout,
+    pub texture_layout: GpuBindGroupLayout,
+    pub rect: GpuRenderPipeline,
+    pub text: GpuRenderPipeline,
+    pub image: GpuRenderPipeline,
+    pub particle: GpuRenderPipeline,
+    pub gradient: GpuRenderPipeline,
+}
+
+impl ShaderManager {
+    pub fn new(device: &GpuDevice, format: GpuTextureFormat) -> Result<Self, JsValue> {
+        let view_projection_layout = create_bind_group_layout(device, kryon_shaders::VIEW_PROJECTION_GROUP)?;
+        let texture_layout = create_bind_group_layout(device, kryon_shaders::TEXTURE_GROUP)?;
+        let textured = [&view_projection_layout, &texture_layout];
+
+        Ok(Self {
+            rect: create_pipeline(device, &kryon_shaders::RECT_PIPELINE, &[&view_projection_layout], format)?,
+            text: create_pipeline(device, &kryon_shaders::TEXT_PIPELINE, &textured, format)?,
+            image: create_pipeline(device, &kryon_shaders::IMAGE_PIPELINE, &textured, format)?,
+            particle: create_pipeline(device, &kryon_shaders::PARTICLE_PIPELINE, &[&view_projection_layout], format)?,
+            gradient: create_pipeline(device, &kryon_shaders::GRADIENT_PIPELINE, &[&view_projection_layout], format)?,
+            view_projection_layout,
+            texture_layout,
+        })
+    }
+}
+
+pub fn create_bind_group_layout(device: &GpuDevice, group: &[Binding]) -> Result<GpuBindGroupLayout, JsValue> {
+    let entries: Vec<_> = group.iter().enumerate().map(|(index, binding)| {
+        let visibility = match binding {
+            Binding::Uniform => VERTEX_STAGE,
+            Binding::Texture | Binding::Sampler => FRAGMENT_STAGE,
+        };
+        let entry = GpuBindGroupLayoutEntry::new(index as u32, visibility);
+        match binding {
+            Binding::Uniform => {
+                let buffer = GpuBufferBindingLayout::new();
+                buffer.set_type(GpuBufferBindingType::Uniform);
+                entry.set_buffer(&buffer);
+            }
+            Binding::Texture => {
+                let texture = GpuTextureBindingLayout::new();
+                texture.set_sample_type(GpuTextureSampleType::Float);
+                entry.set_texture(&texture);
+            }
+            Binding::Sampler => {
+                let sampler = GpuSamplerBindingLayout::new();
+                sampler.set_type(GpuSamplerBindingType::Filtering);
+                entry.set_sampler(&sampler);
+            }
+        }
+        entry
+    }).collect();
+    device.create_bind_group_layout(&GpuBindGroupLayoutDescriptor::new(&entries))
+}
+
+pub fn create_pipeline(
+    device: &GpuDevice,
+    description: &PipelineDescription,
+    bind_group_layouts: &[&GpuBindGroupLayout],
+    format: GpuTextureFormat,
+) -> Result<GpuRenderPipeline, JsValue> {
+    let module_descriptor = GpuShaderModuleDescriptor::new(description.source);
+    module_descriptor.set_label(description.label);
+    let module = device.create_shader_module(&module_descriptor);
+
+    let layouts: Vec<_> = bind_group_layouts.iter().map(|&layout| JsNullable::wrap(layout.clone())).collect();
+    let layout_descriptor = GpuPipelineLayoutDescriptor::new(&layouts);
+    layout_descriptor.set_label(description.label);
+    let layout = device.create_pipeline_layout(&layout_descriptor);
+
+    let attributes: Vec<_> = description.vertex.attributes.iter().map(|attribute| {
+        let format = match attribute.format {
+            VertexFormat::Float32 => web_sys::GpuVertexFormat::Float32,
+            VertexFormat::Float32x2 => web_sys::GpuVertexFormat::Float32x2,
+            VertexFormat::Float32x4 => web_sys::GpuVertexFormat::Float32x4,
+        };
+        GpuVertexAttribute::new(format, attribute.offset as u32, attribute.location)
+    }).collect();
+    let buffer = GpuVertexBufferLayout::new(description.vertex.stride as u32, &attributes);
+    buffer.set_step_mode(match description.vertex.step_mode {
+        StepMode::Vertex => GpuVertexStepMode::Vertex,
+        StepMode::Instance => GpuVertexStepMode::Instance,
+    });
+    let vertex = GpuVertexState::new(&module);
+    vertex.set_entry_point(description.vertex_entry);
+    vertex.set_buffers(&[JsNullable::wrap(buffer)]);
+
+    // Same blending as wgpu's BlendState::ALPHA_BLENDING
+    let color = GpuBlendComponent::new();
+    color.set_src_factor(GpuBlendFactor::SrcAlpha);
+    color.set_dst_factor(GpuBlendFactor::OneMinusSrcAlpha);
+    let alpha = GpuBlendComponent::new();
+    alpha.set_src_factor(GpuBlendFactor::One);
+    alpha.set_dst_factor(GpuBlendFactor::OneMinusSrcAlpha);
+    let target = GpuColorTargetState::new(format);
+    target.set_blend(&GpuBlendState::new(&alpha, &color));
+    let fragment = GpuFragmentState::new(&module, &[JsNullable::wrap(target)]);
+    fragment.set_entry_point(description.fragment_entry);
+
+    let primitive = GpuPrimitiveState::new();
+    primitive.set_topology(GpuPrimitiveTopology::TriangleList);
+    primitive.set_cull_mode(if description.cull_back_faces { GpuCullMode::Back } else { GpuCullMode::None });
+
+    let descriptor = GpuRenderPipelineDescriptor::new(&layout, &vertex);
+    descriptor.set_label(description.label);
+    descriptor.set_fragment(&fragment);
+    descriptor.set_primitive(&primitive);
+    device.create_render_pipeline(&descriptor)
+}
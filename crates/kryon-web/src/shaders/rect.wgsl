@@ -1,32 +0,0 @@
-// Basic rectangle shader for WebGPU
-
-struct VertexInput {
-    @location(0) position: vec2<f32>,
-    @location(1) uv: vec2<f32>,
-}
-
-struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-}
-
-struct Uniforms {
-    view_proj: mat4x4<f32>,
-    color: vec4<f32>,
-}
-
-@group(0) @binding(0)
-var<uniform> uniforms: Uniforms;
-
-@vertex
-fn vs_main(input: VertexInput) -> VertexOutput {
-    var output: VertexOutput;
-    output.clip_position = uniforms.view_proj * vec4<f32>(input.position, 0.0, 1.0);
-    output.uv = input.uv;
-    return output;
-}
-
-@fragment
-fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-    return uniforms.color;
-}
\ No newline at end of file
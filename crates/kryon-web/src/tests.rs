@@ -132,6 +132,7 @@ mod tests {
         assert!((ease_in_out_result - 0.5).abs() < 0.1); // Should be close to middle
     }
     
+    #[cfg(feature = "webgpu")]
     #[wasm_bindgen_test]
     fn test_texture_manager() {
         use crate::texture_manager::*;
@@ -307,6 +308,7 @@ mod tests {
         assert!(custom_bezier >= 0.0 && custom_bezier <= 1.0);
     }
     
+    #[cfg(feature = "webgpu")]
     #[wasm_bindgen_test]
     fn test_texture_atlas() {
         use crate::texture_manager::*;
@@ -414,6 +416,110 @@ mod tests {
         assert!(app.is_animating("test_element", "position"));
         assert!(app.is_animating("test_element", "scale"));
     }
+
+    #[wasm_bindgen_test]
+    fn test_display_list_round_trip() {
+        use kryon_core::{CSSUnit, CSSUnitValue, TextAlignment, TransformData, TransformOrigin, TransformProperty, TransformPropertyType, TransformType};
+        use kryon_render::RenderCommand;
+
+        let commands = vec![
+            RenderCommand::SetCanvasSize(Vec2::new(800.0, 600.0)),
+            RenderCommand::DrawRect {
+                position: Vec2::new(10.0, 20.0),
+                size: Vec2::new(100.0, 40.0),
+                color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+                border_radius: 4.0,
+                border_width: 1.0,
+                border_color: Vec4::ONE,
+                transform: Some(TransformData {
+                    transform_type: TransformType::Transform2D,
+                    properties: vec![TransformProperty {
+                        property_type: TransformPropertyType::Rotate,
+                        value: CSSUnitValue { value: 45.0, unit: CSSUnit::Degrees },
+                    }],
+                    origin: TransformOrigin::default(),
+                }),
+                shadow: Some("2px 2px 4px #000".to_string()),
+                z_index: 3,
+            },
+            RenderCommand::DrawText {
+                position: Vec2::new(12.0, 24.0),
+                text: "Hello".to_string(),
+                font_size: 16.0,
+                color: Vec4::ONE,
+                alignment: TextAlignment::Center,
+                max_width: Some(100.0),
+                max_height: None,
+                transform: None,
+                font_family: None,
+                runs: vec![kryon_core::TextRun {
+                    range: 1..3,
+                    color: Some(Vec4::new(1.0, 0.0, 0.0, 1.0)),
+                    font_weight: Some(kryon_core::RichFontWeight::Bold),
+                    text_decoration: Some(kryon_core::RichTextDecoration::Underline),
+                    ..Default::default()
+                }],
+                z_index: 4,
+            },
+            RenderCommand::DrawTiledImage {
+                position: Vec2::new(10.0, 20.0),
+                size: Vec2::new(100.0, 40.0),
+                source: "tile.png".to_string(),
+                tile_offset: Vec2::new(-8.0, 0.0),
+                tile_size: Vec2::new(16.0, 16.0),
+                repeat: kryon_core::BackgroundRepeat::RepeatX,
+                opacity: 0.5,
+                image_rendering: kryon_render::ImageRendering::Pixelated,
+                transform: None,
+                z_index: 3,
+            },
+            RenderCommand::ClearClip,
+        ];
+
+        let list = crate::DisplayList::encode(&commands);
+        assert_eq!(list.strings, vec!["2px 2px 4px #000", "Hello", "tile.png"]);
+        let decoded = list.decode().expect("valid display list");
+        assert_eq!(decoded.len(), commands.len());
+        assert_eq!(crate::DisplayList::encode(&decoded), list);
+
+        let truncated = crate::DisplayList { numbers: list.numbers[..5].to_vec(), strings: list.strings.clone() };
+        assert_eq!(truncated.decode(), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_app_event_listeners() {
+        use crate::document::{element_property, script_value_to_js, EventListeners};
+        use kryon_core::PropertyValue;
+        use kryon_runtime::script::engine_trait::ScriptValue;
+        use std::collections::HashMap;
+
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), ScriptValue::from("notes.txt"));
+        fields.insert("tags".to_string(), ScriptValue::Array(vec![ScriptValue::Integer(1), ScriptValue::Nil]));
+        let payload = script_value_to_js(&ScriptValue::Object(fields));
+        assert_eq!(js_sys::Reflect::get(&payload, &"name".into()).unwrap(), "notes.txt");
+        let tags: js_sys::Array = js_sys::Reflect::get(&payload, &"tags".into()).unwrap().into();
+        assert_eq!(tags.length(), 2);
+        assert!(tags.get(1).is_null());
+
+        let received = js_sys::Array::new();
+        let push = js_sys::Function::new_with_args("payload", "this.push(payload)").bind(&received);
+        let mut listeners = EventListeners::default();
+        listeners.add("saved", push.clone());
+        listeners.dispatch("saved", &payload);
+        listeners.dispatch("closed", &payload);
+        assert_eq!(received.length(), 1);
+        listeners.remove("saved", Some(&push));
+        listeners.dispatch("saved", &payload);
+        assert_eq!(received.length(), 1);
+
+        let mut element = kryon_core::Element { id: "title".to_string(), text: "Hello".to_string(), ..Default::default() };
+        element.custom_properties.insert("gap".to_string(), PropertyValue::Float(8.0));
+        assert_eq!(element_property(&element, "text").unwrap(), "Hello");
+        assert_eq!(element_property(&element, "gap").unwrap(), 8.0);
+        assert!(element_property(&element, "missing").is_none());
+    }
+
 }
 
 #[cfg(test)]
@@ -512,4 +618,173 @@ mod integration_tests {
         let stats = app.get_performance_stats();
         assert!(!stats.is_undefined());
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "webgpu")]
+    #[wasm_bindgen_test]
+    fn test_gpu_frame_batching() {
+        use crate::gpu_frame::{BatchKind, Clip, Glyph, GlyphSource, GpuFrame, VERTEX_FLOATS};
+        use crate::texture_cache::ImageStatus;
+        use kryon_core::TextAlignment;
+        use kryon_render::RenderCommand;
+
+        struct FixedGlyphs;
+        impl GlyphSource for FixedGlyphs {
+            fn glyph(&mut self, character: char, font_size: f32, _font_family: &str) -> Option<Glyph> {
+                (character != ' ').then_some(Glyph { uv: [0.0, 0.0, 0.1, 0.1], size: Vec2::splat(font_size), advance: font_size })
+            }
+        }
+
+        let rect = |x: f32| RenderCommand::DrawRect {
+            position: Vec2::new(x, 0.0),
+            size: Vec2::new(10.0, 10.0),
+            color: Vec4::ONE,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Vec4::ZERO,
+            transform: None,
+            shadow: None,
+            z_index: 0,
+        };
+        let text = RenderCommand::DrawText {
+            position: Vec2::ZERO,
+            text: "a b".to_string(),
+            font_size: 10.0,
+            color: Vec4::ONE,
+            alignment: TextAlignment::Start,
+            max_width: None,
+            max_height: None,
+            transform: None,
+            font_family: None,
+            runs: Vec::new(),
+            z_index: 0,
+        };
+
+        let mut frame = GpuFrame::new();
+        let mut glyphs = FixedGlyphs;
+        let mut image_status = |_: &str| ImageStatus::Ready;
+        let commands = [
+            rect(0.0),
+            rect(20.0),
+            RenderCommand::SetClip { position: Vec2::new(5.0, 5.0), size: Vec2::new(50.0, 50.0) },
+            RenderCommand::SetClip { position: Vec2::ZERO, size: Vec2::new(20.0, 20.0) },
+            text,
+            RenderCommand::ClearClip,
+            rect(40.0),
+        ];
+        for command in &commands {
+            frame.push(command, &mut glyphs, &mut image_status);
+        }
+
+        // Adjacent rects share a batch; text breaks it, keeping painter's order
+        let kinds: Vec<_> = frame.batches.iter().map(|batch| (batch.kind.clone(), batch.count)).collect();
+        assert_eq!(kinds, vec![(BatchKind::Rect, 12), (BatchKind::Text, 12), (BatchKind::Rect, 6)]);
+        assert_eq!(frame.rect_vertices.len(), 18 * VERTEX_FLOATS);
+
+        // Nested clips intersect
+        assert_eq!(frame.batches[0].clip, None);
+        assert_eq!(frame.batches[1].clip, Some(Clip { position: Vec2::new(5.0, 5.0), size: Vec2::new(15.0, 15.0) }));
+        assert_eq!(frame.batches[2].clip, None);
+
+        frame.clear();
+        assert!(frame.is_empty());
+    }
+
+    #[cfg(feature = "webgpu")]
+    #[wasm_bindgen_test]
+    fn test_gpu_frame_image_placeholders() {
+        use crate::gpu_frame::{BatchKind, Glyph, GlyphSource, GpuFrame};
+        use crate::texture_cache::ImageStatus;
+        use kryon_render::RenderCommand;
+
+        struct NoGlyphs;
+        impl GlyphSource for NoGlyphs {
+            fn glyph(&mut self, _character: char, _font_size: f32, _font_family: &str) -> Option<Glyph> {
+                None
+            }
+        }
+
+        let image = |source: &str| RenderCommand::DrawImage {
+            position: Vec2::ZERO,
+            size: Vec2::new(40.0, 20.0),
+            source: source.to_string(),
+            opacity: 1.0,
+            transform: None,
+            image_rendering: kryon_render::ImageRendering::Auto,
+            filter: kryon_render::ImageFilter::default(),
+        };
+        let mut image_status = |source: &str| match source {
+            "ready.png" => ImageStatus::Ready,
+            "loading.png" => ImageStatus::Loading,
+            _ => ImageStatus::Failed,
+        };
+
+        let mut frame = GpuFrame::new();
+        for source in ["ready.png", "loading.png", "broken.png"] {
+            frame.push(&image(source), &mut NoGlyphs, &mut image_status);
+        }
+
+        // Loading images get a plain placeholder, failed ones a crossed-out one
+        let kinds: Vec<_> = frame.batches.iter().map(|batch| (batch.kind.clone(), batch.count)).collect();
+        assert_eq!(kinds, vec![(BatchKind::Image("ready.png".to_string()), 6), (BatchKind::Rect, 6 + 6 + 12)]);
+    }
+
+    #[cfg(feature = "webgpu")]
+    #[wasm_bindgen_test]
+    fn test_gpu_frame_gradients() {
+        use crate::gpu_frame::{BatchKind, Glyph, GlyphSource, GpuFrame, GRADIENT_VERTEX_FLOATS};
+        use crate::texture_cache::ImageStatus;
+        use kryon_render::RenderCommand;
+
+        struct NoGlyphs;
+        impl GlyphSource for NoGlyphs {
+            fn glyph(&mut self, _character: char, _font_size: f32, _font_family: &str) -> Option<Glyph> {
+                None
+            }
+        }
+
+        let gradient = RenderCommand::DrawGradientRect {
+            position: Vec2::new(10.0, 20.0),
+            size: Vec2::new(40.0, 20.0),
+            gradient: kryon_core::Gradient::parse("linear-gradient(to right, red, blue)").unwrap(),
+            border_radius: 4.0,
+            transform: None,
+            z_index: 0,
+        };
+        let mut image_status = |_: &str| ImageStatus::Ready;
+        let mut frame = GpuFrame::new();
+        frame.push(&gradient, &mut NoGlyphs, &mut image_status);
+        frame.push(&gradient, &mut NoGlyphs, &mut image_status);
+
+        // Both quads share a batch; each vertex carries the box and its stops
+        let kinds: Vec<_> = frame.batches.iter().map(|batch| (batch.kind.clone(), batch.count)).collect();
+        assert_eq!(kinds, vec![(BatchKind::Gradient, 12)]);
+        assert_eq!(frame.gradient_vertices.len(), 12 * GRADIENT_VERTEX_FLOATS);
+        assert_eq!(&frame.gradient_vertices[..8], &[10.0, 20.0, 0.0, 1.0, 0.0, 0.0, 40.0, 20.0]);
+        assert_eq!(frame.gradient_vertices[10..12], [4.0, 2.0], "corner radius and stop count");
+    }
+
+    #[cfg(feature = "webgpu")]
+    #[wasm_bindgen_test]
+    fn test_texture_cache_eviction() {
+        use crate::texture_cache::{ImageStatus, TextureCache};
+
+        let mut cache = TextureCache::new(100);
+        cache.start_loading("a.png");
+        assert_eq!(cache.touch("a.png"), Some(ImageStatus::Loading));
+        assert_eq!(cache.touch("unknown.png"), None);
+
+        cache.loaded("a.png", 60);
+        cache.next_frame();
+        cache.next_frame();
+        cache.loaded("b.png", 60);
+        cache.failed("c.png");
+        assert_eq!(cache.bytes(), 120);
+
+        // b.png was loaded this frame, so only a.png can go
+        assert_eq!(cache.evict(), vec!["a.png".to_string()]);
+        assert_eq!(cache.status("a.png"), None);
+        assert_eq!(cache.status("c.png"), Some(ImageStatus::Failed));
+        assert_eq!(cache.bytes(), 60);
+        assert!(cache.evict().is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+//! Load state and memory accounting for image textures
+//!
+//! Tracks which sources are loading, loaded or failed, how much GPU memory
+//! the loaded ones take, and which to evict when they exceed the budget.
+
+use std::collections::HashMap;
+
+/// Default GPU memory budget for image textures
+pub const DEFAULT_TEXTURE_BUDGET: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageStatus {
+    Ready,
+    Loading,
+    Failed,
+}
+
+#[derive(Debug)]
+struct Entry {
+    status: ImageStatus,
+    bytes: u64,
+    last_used: u64,
+}
+
+#[derive(Debug)]
+pub struct TextureCache {
+    entries: HashMap<String, Entry>,
+    frame: u64,
+    bytes: u64,
+    budget: u64,
+}
+
+impl TextureCache {
+    pub fn new(budget: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            frame: 0,
+            bytes: 0,
+            budget,
+        }
+    }
+
+    pub fn status(&self, source: &str) -> Option<ImageStatus> {
+        self.entries.get(source).map(|entry| entry.status)
+    }
+
+    /// Marks `source` as used this frame. `None` if it was never requested.
+    pub fn touch(&mut self, source: &str) -> Option<ImageStatus> {
+        let entry = self.entries.get_mut(source)?;
+        entry.last_used = self.frame;
+        Some(entry.status)
+    }
+
+    pub fn start_loading(&mut self, source: &str) {
+        self.remove(source);
+        self.entries.insert(source.to_string(), Entry { status: ImageStatus::Loading, bytes: 0, last_used: self.frame });
+    }
+
+    pub fn loaded(&mut self, source: &str, bytes: u64) {
+        self.remove(source);
+        self.bytes += bytes;
+        self.entries.insert(source.to_string(), Entry { status: ImageStatus::Ready, bytes, last_used: self.frame });
+    }
+
+    pub fn failed(&mut self, source: &str) {
+        self.remove(source);
+        self.entries.insert(source.to_string(), Entry { status: ImageStatus::Failed, bytes: 0, last_used: self.frame });
+    }
+
+    pub fn remove(&mut self, source: &str) {
+        if let Some(entry) = self.entries.remove(source) {
+            self.bytes -= entry.bytes;
+        }
+    }
+
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Removes and returns loaded sources, least recently used first, until
+    /// the rest fit the budget. Sources used this frame or the previous one
+    /// are kept even if that leaves the cache over budget.
+    pub fn evict(&mut self) -> Vec<String> {
+        if self.bytes <= self.budget {
+            return Vec::new();
+        }
+        let mut candidates: Vec<_> = self.entries.iter()
+            .filter(|(_, entry)| entry.status == ImageStatus::Ready && entry.last_used + 1 < self.frame)
+            .map(|(source, entry)| (entry.last_used, source.clone()))
+            .collect();
+        candidates.sort();
+
+        let mut evicted = Vec::new();
+        for (_, source) in candidates {
+            if self.bytes <= self.budget {
+                break;
+            }
+            self.remove(&source);
+            evicted.push(source);
+        }
+        evicted
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    pub fn set_budget(&mut self, budget: u64) {
+        self.budget = budget;
+    }
+}
+
+impl Default for TextureCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEXTURE_BUDGET)
+    }
+}
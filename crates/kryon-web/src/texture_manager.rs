@@ -1,13 +1,23 @@
 //! Texture and image management system for web rendering
+//!
+//! Image sources are fetched and decoded asynchronously through the
+//! [`WebAssetLoader`]; decoded bitmaps are uploaded at the start of the next
+//! frame under their source key. Until then [`TextureManager::image_status`]
+//! reports them as loading so draw commands can show a placeholder.
 
 use wasm_bindgen::prelude::*;
 use web_sys::{
-    GpuDevice, GpuQueue, GpuTexture, GpuTextureView, GpuSampler, 
-    HtmlImageElement, HtmlCanvasElement, ImageBitmap, ImageData
+    GpuAddressMode, GpuCopyExternalImageDestInfo, GpuCopyExternalImageSourceInfo, GpuDevice, GpuFilterMode,
+    GpuMipmapFilterMode, GpuOrigin3dDict, GpuQueue, GpuSampler, GpuSamplerDescriptor, GpuTexelCopyBufferLayout,
+    GpuTexelCopyTextureInfo, GpuTexture, GpuTextureDescriptor, GpuTextureFormat, GpuTextureView, HtmlImageElement,
+    ImageBitmap, console,
 };
 use glam::Vec2;
+use std::cell::RefCell;
 use std::collections::HashMap;
-use crate::asset_loader::WebAssetLoader;
+use std::rc::Rc;
+use crate::asset_loader::{CacheControl, WebAssetLoader};
+use crate::texture_cache::{ImageStatus, TextureCache};
 
 #[derive(Debug, Clone)]
 pub struct TextureDescriptor {
@@ -48,103 +58,179 @@ pub struct ManagedTexture {
     pub size: Vec2,
 }
 
+type DecodedImages = Rc<RefCell<Vec<(String, Result<ImageBitmap, JsValue>)>>>;
+type ReadyListeners = Rc<RefCell<Vec<Box<dyn Fn(&str)>>>>;
+
 pub struct TextureManager {
     device: GpuDevice,
     queue: GpuQueue,
     textures: HashMap<String, ManagedTexture>,
     atlas_manager: TextureAtlasManager,
     asset_loader: WebAssetLoader,
+    cache: TextureCache,
+    /// Filled by in-flight loads, drained by `begin_frame`
+    decoded: DecodedImages,
+    listeners: ReadyListeners,
 }
 
 impl TextureManager {
     pub fn new(device: GpuDevice, queue: GpuQueue) -> Self {
         let atlas_manager = TextureAtlasManager::new(device.clone(), queue.clone());
-        
+
         Self {
             device,
             queue,
             textures: HashMap::new(),
             atlas_manager,
             asset_loader: WebAssetLoader::new(),
+            cache: TextureCache::default(),
+            decoded: Rc::new(RefCell::new(Vec::new())),
+            listeners: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// HTTP cache mode used when fetching images
+    pub fn set_cache_control(&mut self, cache_control: CacheControl) {
+        self.asset_loader.set_cache_control(cache_control);
+    }
+
+    /// GPU memory, in bytes, that loaded images may use before the least
+    /// recently drawn ones are evicted. Evicted images reload when drawn again.
+    pub fn set_memory_budget(&mut self, bytes: u64) {
+        self.cache.set_budget(bytes);
+    }
+
+    /// Calls `listener` with the source of every image that finishes decoding,
+    /// e.g. to schedule a redraw for draw commands that showed a placeholder.
+    pub fn on_texture_ready(&mut self, listener: impl Fn(&str) + 'static) {
+        self.listeners.borrow_mut().push(Box::new(listener));
+    }
+
+    /// Status of the image at `source`, starting to load it on first use.
+    /// Textures created directly rather than loaded by source are always
+    /// ready and never evicted.
+    pub fn image_status(&mut self, source: &str) -> ImageStatus {
+        match self.cache.touch(source) {
+            Some(status) => status,
+            None if self.textures.contains_key(source) => ImageStatus::Ready,
+            None => {
+                self.request_texture(source);
+                ImageStatus::Loading
+            }
         }
     }
-    
+
+    /// Starts fetching and decoding `source` unless it is loaded or loading.
+    /// Failed images are retried.
+    pub fn request_texture(&mut self, source: &str) {
+        if matches!(self.cache.status(source), Some(ImageStatus::Ready | ImageStatus::Loading)) || self.textures.contains_key(source) {
+            return;
+        }
+        self.cache.start_loading(source);
+
+        let decode = self.asset_loader.decode_image(source);
+        let decoded = self.decoded.clone();
+        let listeners = self.listeners.clone();
+        let source = source.to_string();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = decode.await;
+            let ready = result.is_ok();
+            decoded.borrow_mut().push((source.clone(), result));
+            if ready {
+                for listener in listeners.borrow().iter() {
+                    listener(&source);
+                }
+            }
+        });
+    }
+
+    /// Uploads images decoded since the last frame and evicts textures over
+    /// the memory budget. Returns the sources that were evicted.
+    pub fn begin_frame(&mut self) -> Vec<String> {
+        self.cache.next_frame();
+
+        let decoded: Vec<_> = self.decoded.borrow_mut().drain(..).collect();
+        for (source, result) in decoded {
+            // Removed or re-requested while in flight
+            if self.cache.status(&source) != Some(ImageStatus::Loading) {
+                continue;
+            }
+            let uploaded = result.and_then(|bitmap| {
+                let uploaded = self.create_texture_from_bitmap(&source, &bitmap);
+                bitmap.close();
+                uploaded
+            });
+            match uploaded {
+                Ok(()) => {
+                    let size = self.textures[&source].size;
+                    self.cache.loaded(&source, size.x as u64 * size.y as u64 * 4);
+                }
+                Err(e) => {
+                    console::warn_2(&format!("Failed to load image {}:", source).into(), &e);
+                    self.cache.failed(&source);
+                }
+            }
+        }
+
+        let evicted = self.cache.evict();
+        for source in &evicted {
+            if let Some(texture) = self.textures.remove(source) {
+                texture.gpu_texture.destroy();
+            }
+        }
+        evicted
+    }
+
     /// Load texture from URL
     pub async fn load_texture(&mut self, name: &str, url: &str) -> Result<(), JsValue> {
-        let image_data = self.asset_loader.load_image(url).await?;
-        
-        // Create image element
-        let img = HtmlImageElement::new()?;
-        let base64_data = base64_encode(&image_data);
-        let data_url = format!("data:image/png;base64,{}", base64_data);
-        img.set_src(&data_url);
-        
-        // Wait for image to load
-        let promise = js_sys::Promise::new(&mut |resolve, reject| {
-            let resolve_clone = resolve.clone();
-            let reject_clone = reject.clone();
-            
-            let onload = Closure::wrap(Box::new(move || {
-                resolve_clone.call0(&JsValue::NULL).unwrap();
-            }) as Box<dyn FnMut()>);
-            
-            let onerror = Closure::wrap(Box::new(move || {
-                reject_clone.call0(&JsValue::NULL).unwrap();
-            }) as Box<dyn FnMut()>);
-            
-            img.set_onload(Some(onload.as_ref().unchecked_ref()));
-            img.set_onerror(Some(onerror.as_ref().unchecked_ref()));
-            
-            onload.forget();
-            onerror.forget();
-        });
-        
-        wasm_bindgen_futures::JsFuture::from(promise).await?;
-        
-        // Create texture from image
-        self.create_texture_from_image(name, &img)?;
-        
+        let bitmap = self.asset_loader.decode_image(url).await?;
+        self.create_texture_from_bitmap(name, &bitmap)?;
+        bitmap.close();
         Ok(())
     }
-    
+
+    /// Create texture from a decoded image bitmap
+    pub fn create_texture_from_bitmap(&mut self, name: &str, bitmap: &ImageBitmap) -> Result<(), JsValue> {
+        let (width, height) = (bitmap.width(), bitmap.height());
+        let texture = self.create_texture(name, &Self::external_image_descriptor(width, height))?.gpu_texture.clone();
+        self.queue.copy_external_image_to_texture_with_u32_sequence(
+            &GpuCopyExternalImageSourceInfo::new(bitmap),
+            &GpuCopyExternalImageDestInfo::new(&texture),
+            &[width.into(), height.into()],
+        )?;
+        Ok(())
+    }
+
     /// Create texture from HTML image element
     pub fn create_texture_from_image(&mut self, name: &str, img: &HtmlImageElement) -> Result<(), JsValue> {
-        let width = img.natural_width();
-        let height = img.natural_height();
-        
-        let descriptor = TextureDescriptor {
+        let (width, height) = (img.natural_width(), img.natural_height());
+        let texture = self.create_texture(name, &Self::external_image_descriptor(width, height))?.gpu_texture.clone();
+        self.queue.copy_external_image_to_texture_with_u32_sequence(
+            &GpuCopyExternalImageSourceInfo::new_with_html_image_element(img),
+            &GpuCopyExternalImageDestInfo::new(&texture),
+            &[width.into(), height.into()],
+        )?;
+        Ok(())
+    }
+
+    /// Copying external images requires a render attachment destination
+    fn external_image_descriptor(width: u32, height: u32) -> TextureDescriptor {
+        TextureDescriptor {
             width,
             height,
-            format: TextureFormat::Rgba8UnormSrgb,
+            format: TextureFormat::Rgba8Unorm,
             usage: TextureUsage {
                 texture_binding: true,
                 storage_binding: false,
-                render_attachment: false,
+                render_attachment: true,
                 copy_src: false,
                 copy_dst: true,
             },
             mip_level_count: 1,
             sample_count: 1,
-        };
-        
-        let texture = self.create_texture(name, &descriptor)?;
-        
-        // Copy image data to texture
-        let source = js_sys::Object::new();
-        js_sys::Reflect::set(&source, &JsValue::from_str("source"), img)?;
-        
-        let destination = js_sys::Object::new();
-        js_sys::Reflect::set(&destination, &JsValue::from_str("texture"), &texture.gpu_texture)?;
-        
-        let size = js_sys::Object::new();
-        js_sys::Reflect::set(&size, &JsValue::from_str("width"), &JsValue::from_f64(width as f64))?;
-        js_sys::Reflect::set(&size, &JsValue::from_str("height"), &JsValue::from_f64(height as f64))?;
-        
-        self.queue.copy_external_image_to_texture(&source, &destination, &size);
-        
-        Ok(())
+        }
     }
-    
+
     /// Create texture from raw data
     pub fn create_texture_from_data(&mut self, name: &str, data: &[u8], width: u32, height: u32, format: TextureFormat) -> Result<(), JsValue> {
         let descriptor = TextureDescriptor {
@@ -161,122 +247,53 @@ impl TextureManager {
             mip_level_count: 1,
             sample_count: 1,
         };
-        
-        let texture = self.create_texture(name, &descriptor)?;
-        
-        // Copy data to texture
-        let destination = js_sys::Object::new();
-        js_sys::Reflect::set(&destination, &JsValue::from_str("texture"), &texture.gpu_texture)?;
-        
-        let size = js_sys::Object::new();
-        js_sys::Reflect::set(&size, &JsValue::from_str("width"), &JsValue::from_f64(width as f64))?;
-        js_sys::Reflect::set(&size, &JsValue::from_str("height"), &JsValue::from_f64(height as f64))?;
-        
-        let data_layout = js_sys::Object::new();
-        js_sys::Reflect::set(&data_layout, &JsValue::from_str("bytesPerRow"), &JsValue::from_f64((width * 4) as f64))?;
-        js_sys::Reflect::set(&data_layout, &JsValue::from_str("rowsPerImage"), &JsValue::from_f64(height as f64))?;
-        
-        let data_array = js_sys::Uint8Array::from(data);
-        self.queue.write_texture(&destination, &data_array, &data_layout, &size);
-        
-        Ok(())
+
+        let texture = self.create_texture(name, &descriptor)?.gpu_texture.clone();
+
+        let data_layout = GpuTexelCopyBufferLayout::new();
+        data_layout.set_bytes_per_row(width * 4);
+        data_layout.set_rows_per_image(height);
+        self.queue.write_texture_with_u8_slice_and_u32_sequence(
+            &GpuTexelCopyTextureInfo::new(&texture),
+            data,
+            &data_layout,
+            &[width.into(), height.into()],
+        )
     }
-    
-    /// Create empty texture
+
+    /// Create empty texture. Replaces any texture with the same name.
     pub fn create_texture(&mut self, name: &str, descriptor: &TextureDescriptor) -> Result<&ManagedTexture, JsValue> {
-        let texture_desc = js_sys::Object::new();
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("label"), &JsValue::from_str(name))?;
-        
-        let size = js_sys::Object::new();
-        js_sys::Reflect::set(&size, &JsValue::from_str("width"), &JsValue::from_f64(descriptor.width as f64))?;
-        js_sys::Reflect::set(&size, &JsValue::from_str("height"), &JsValue::from_f64(descriptor.height as f64))?;
-        
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("size"), &size)?;
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("format"), &JsValue::from_str(&format_to_string(&descriptor.format)))?;
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("usage"), &JsValue::from_f64(usage_to_flags(&descriptor.usage) as f64))?;
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("mipLevelCount"), &JsValue::from_f64(descriptor.mip_level_count as f64))?;
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("sampleCount"), &JsValue::from_f64(descriptor.sample_count as f64))?;
-        
-        let gpu_texture = self.device.create_texture(&texture_desc);
-        
-        // Create texture view
-        let view = gpu_texture.create_view(&js_sys::Object::new());
-        
-        // Create sampler
-        let sampler_desc = js_sys::Object::new();
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("magFilter"), &JsValue::from_str("linear"))?;
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("minFilter"), &JsValue::from_str("linear"))?;
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("mipmapFilter"), &JsValue::from_str("linear"))?;
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("addressModeU"), &JsValue::from_str("clamp-to-edge"))?;
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("addressModeV"), &JsValue::from_str("clamp-to-edge"))?;
-        
-        let sampler = self.device.create_sampler(&sampler_desc);
-        
-        let managed_texture = ManagedTexture {
-            gpu_texture,
-            view,
-            sampler,
-            descriptor: descriptor.clone(),
-            size: Vec2::new(descriptor.width as f32, descriptor.height as f32),
-        };
-        
-        self.textures.insert(name.to_string(), managed_texture);
-        
+        let managed_texture = create_managed_texture(&self.device, name, descriptor)?;
+
+        if let Some(old) = self.textures.insert(name.to_string(), managed_texture) {
+            old.gpu_texture.destroy();
+        }
+
         Ok(self.textures.get(name).unwrap())
     }
-    
+
     /// Get texture by name
     pub fn get_texture(&self, name: &str) -> Option<&ManagedTexture> {
         self.textures.get(name)
     }
-    
+
     /// Remove texture
     pub fn remove_texture(&mut self, name: &str) {
-        self.textures.remove(name);
+        if let Some(texture) = self.textures.remove(name) {
+            texture.gpu_texture.destroy();
+        }
+        self.cache.remove(name);
     }
-    
+
+    /// GPU memory used by images loaded by source, in bytes
+    pub fn memory_usage(&self) -> u64 {
+        self.cache.bytes()
+    }
+
     /// Get texture atlas manager
     pub fn atlas_manager(&mut self) -> &mut TextureAtlasManager {
         &mut self.atlas_manager
     }
-    
-    /// Generate mipmaps for texture
-    pub fn generate_mipmaps(&self, texture_name: &str) -> Result<(), JsValue> {
-        let texture = self.get_texture(texture_name)
-            .ok_or_else(|| JsValue::from_str("Texture not found"))?;
-        
-        // Create command encoder
-        let encoder = self.device.create_command_encoder(&js_sys::Object::new());
-        
-        // Generate mipmaps using compute or render passes
-        // This is a simplified implementation
-        for mip_level in 1..texture.descriptor.mip_level_count {
-            let source_view = texture.gpu_texture.create_view(&js_sys::Object::new());
-            let dest_view = texture.gpu_texture.create_view(&js_sys::Object::new());
-            
-            // Create render pass for mipmap generation
-            let render_pass_desc = js_sys::Object::new();
-            let color_attachments = js_sys::Array::new();
-            
-            let color_attachment = js_sys::Object::new();
-            js_sys::Reflect::set(&color_attachment, &JsValue::from_str("view"), &dest_view)?;
-            js_sys::Reflect::set(&color_attachment, &JsValue::from_str("loadOp"), &JsValue::from_str("clear"))?;
-            js_sys::Reflect::set(&color_attachment, &JsValue::from_str("storeOp"), &JsValue::from_str("store"))?;
-            
-            color_attachments.push(&color_attachment);
-            js_sys::Reflect::set(&render_pass_desc, &JsValue::from_str("colorAttachments"), &color_attachments)?;
-            
-            let render_pass = encoder.begin_render_pass(&render_pass_desc);
-            render_pass.end();
-        }
-        
-        // Submit commands
-        let commands = js_sys::Array::new();
-        commands.push(&encoder.finish(&js_sys::Object::new()));
-        self.queue.submit(&commands);
-        
-        Ok(())
-    }
 }
 
 pub struct TextureAtlasManager {
@@ -303,12 +320,12 @@ pub struct RectPacker {
     free_rects: Vec<Rect>,
 }
 
-#[derive(Debug, Clone)]
-struct Rect {
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
 }
 
 impl TextureAtlasManager {
@@ -319,7 +336,7 @@ impl TextureAtlasManager {
             atlases: HashMap::new(),
         }
     }
-    
+
     pub fn create_atlas(&mut self, name: &str, width: u32, height: u32) -> Result<(), JsValue> {
         let descriptor = TextureDescriptor {
             width,
@@ -335,52 +352,27 @@ impl TextureAtlasManager {
             mip_level_count: 1,
             sample_count: 1,
         };
-        
-        let texture_desc = js_sys::Object::new();
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("label"), &JsValue::from_str(&format!("{}_atlas", name)))?;
-        
-        let size = js_sys::Object::new();
-        js_sys::Reflect::set(&size, &JsValue::from_str("width"), &JsValue::from_f64(width as f64))?;
-        js_sys::Reflect::set(&size, &JsValue::from_str("height"), &JsValue::from_f64(height as f64))?;
-        
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("size"), &size)?;
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("format"), &JsValue::from_str("rgba8unorm-srgb"))?;
-        js_sys::Reflect::set(&texture_desc, &JsValue::from_str("usage"), &JsValue::from_f64(5))?; // TEXTURE_BINDING | COPY_DST
-        
-        let gpu_texture = self.device.create_texture(&texture_desc);
-        let view = gpu_texture.create_view(&js_sys::Object::new());
-        
-        let sampler_desc = js_sys::Object::new();
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("magFilter"), &JsValue::from_str("linear"))?;
-        js_sys::Reflect::set(&sampler_desc, &JsValue::from_str("minFilter"), &JsValue::from_str("linear"))?;
-        let sampler = self.device.create_sampler(&sampler_desc);
-        
-        let managed_texture = ManagedTexture {
-            gpu_texture,
-            view,
-            sampler,
-            descriptor,
-            size: Vec2::new(width as f32, height as f32),
-        };
-        
+
+        let managed_texture = create_managed_texture(&self.device, &format!("{}_atlas", name), &descriptor)?;
+
         let atlas = TextureAtlas {
             texture: managed_texture,
             regions: HashMap::new(),
             packer: RectPacker::new(Vec2::new(width as f32, height as f32)),
         };
-        
+
         self.atlases.insert(name.to_string(), atlas);
-        
+
         Ok(())
     }
-    
+
     pub fn add_region(&mut self, atlas_name: &str, region_name: &str, data: &[u8], width: u32, height: u32) -> Result<AtlasRegion, JsValue> {
         let atlas = self.atlases.get_mut(atlas_name)
             .ok_or_else(|| JsValue::from_str("Atlas not found"))?;
-        
+
         let rect = atlas.packer.pack(width, height)
             .ok_or_else(|| JsValue::from_str("No space in atlas"))?;
-        
+
         // Calculate UV coordinates
         let uv_min = Vec2::new(
             rect.x as f32 / atlas.texture.size.x,
@@ -390,48 +382,40 @@ impl TextureAtlasManager {
             (rect.x + rect.width) as f32 / atlas.texture.size.x,
             (rect.y + rect.height) as f32 / atlas.texture.size.y,
         );
-        
+
         let region = AtlasRegion {
             uv_min,
             uv_max,
             size: Vec2::new(width as f32, height as f32),
         };
-        
+
         // Copy data to atlas texture
-        let destination = js_sys::Object::new();
-        js_sys::Reflect::set(&destination, &JsValue::from_str("texture"), &atlas.texture.gpu_texture)?;
-        
-        let origin = js_sys::Object::new();
-        js_sys::Reflect::set(&origin, &JsValue::from_str("x"), &JsValue::from_f64(rect.x as f64))?;
-        js_sys::Reflect::set(&origin, &JsValue::from_str("y"), &JsValue::from_f64(rect.y as f64))?;
-        js_sys::Reflect::set(&destination, &JsValue::from_str("origin"), &origin)?;
-        
-        let size = js_sys::Object::new();
-        js_sys::Reflect::set(&size, &JsValue::from_str("width"), &JsValue::from_f64(width as f64))?;
-        js_sys::Reflect::set(&size, &JsValue::from_str("height"), &JsValue::from_f64(height as f64))?;
-        
-        let data_layout = js_sys::Object::new();
-        js_sys::Reflect::set(&data_layout, &JsValue::from_str("bytesPerRow"), &JsValue::from_f64((width * 4) as f64))?;
-        
-        let data_array = js_sys::Uint8Array::from(data);
-        self.queue.write_texture(&destination, &data_array, &data_layout, &size);
-        
+        let destination = GpuTexelCopyTextureInfo::new(&atlas.texture.gpu_texture);
+        let origin = GpuOrigin3dDict::new();
+        origin.set_x(rect.x);
+        origin.set_y(rect.y);
+        destination.set_origin_gpu_origin_3d_dict(&origin);
+
+        let data_layout = GpuTexelCopyBufferLayout::new();
+        data_layout.set_bytes_per_row(width * 4);
+        self.queue.write_texture_with_u8_slice_and_u32_sequence(&destination, data, &data_layout, &[width.into(), height.into()])?;
+
         atlas.regions.insert(region_name.to_string(), region.clone());
-        
+
         Ok(region)
     }
-    
+
     pub fn get_atlas(&self, name: &str) -> Option<&TextureAtlas> {
         self.atlases.get(name)
     }
-    
+
     pub fn get_region(&self, atlas_name: &str, region_name: &str) -> Option<&AtlasRegion> {
         self.atlases.get(atlas_name)?.regions.get(region_name)
     }
 }
 
 impl RectPacker {
-    fn new(size: Vec2) -> Self {
+    pub fn new(size: Vec2) -> Self {
         Self {
             size,
             free_rects: vec![Rect {
@@ -442,12 +426,16 @@ impl RectPacker {
             }],
         }
     }
-    
-    fn pack(&mut self, width: u32, height: u32) -> Option<Rect> {
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn pack(&mut self, width: u32, height: u32) -> Option<Rect> {
         // Find best fit rectangle
         let mut best_rect = None;
         let mut best_area = u32::MAX;
-        
+
         for (i, rect) in self.free_rects.iter().enumerate() {
             if rect.width >= width && rect.height >= height {
                 let area = rect.width * rect.height;
@@ -457,91 +445,89 @@ impl RectPacker {
                 }
             }
         }
-        
-        if let Some((index, rect)) = best_rect {
-            self.free_rects.remove(index);
-            
-            // Split remaining space
-            if rect.width > width {
-                self.free_rects.push(Rect {
-                    x: rect.x + width,
-                    y: rect.y,
-                    width: rect.width - width,
-                    height: rect.height,
-                });
-            }
-            
-            if rect.height > height {
-                self.free_rects.push(Rect {
-                    x: rect.x,
-                    y: rect.y + height,
-                    width: rect.width,
-                    height: rect.height - height,
-                });
-            }
-            
-            Some(Rect {
-                x: rect.x,
+
+        let (index, rect) = best_rect?;
+        self.free_rects.remove(index);
+
+        // Split remaining space into the strip to the right and the one below
+        if rect.width > width {
+            self.free_rects.push(Rect {
+                x: rect.x + width,
                 y: rect.y,
-                width,
+                width: rect.width - width,
                 height,
-            })
-        } else {
-            None
+            });
+        }
+
+        if rect.height > height {
+            self.free_rects.push(Rect {
+                x: rect.x,
+                y: rect.y + height,
+                width: rect.width,
+                height: rect.height - height,
+            });
         }
+
+        Some(Rect {
+            x: rect.x,
+            y: rect.y,
+            width,
+            height,
+        })
     }
 }
 
 // Helper functions
 
-fn format_to_string(format: &TextureFormat) -> &'static str {
+fn create_managed_texture(device: &GpuDevice, label: &str, descriptor: &TextureDescriptor) -> Result<ManagedTexture, JsValue> {
+    let texture_desc = GpuTextureDescriptor::new(
+        texture_format(&descriptor.format),
+        &[descriptor.width.into(), descriptor.height.into()],
+        usage_to_flags(&descriptor.usage),
+    );
+    texture_desc.set_label(label);
+    texture_desc.set_mip_level_count(descriptor.mip_level_count);
+    texture_desc.set_sample_count(descriptor.sample_count);
+
+    let gpu_texture = device.create_texture(&texture_desc)?;
+    let view = gpu_texture.create_view()?;
+
+    let sampler_desc = GpuSamplerDescriptor::new();
+    sampler_desc.set_mag_filter(GpuFilterMode::Linear);
+    sampler_desc.set_min_filter(GpuFilterMode::Linear);
+    sampler_desc.set_mipmap_filter(GpuMipmapFilterMode::Linear);
+    sampler_desc.set_address_mode_u(GpuAddressMode::ClampToEdge);
+    sampler_desc.set_address_mode_v(GpuAddressMode::ClampToEdge);
+    let sampler = device.create_sampler_with_descriptor(&sampler_desc);
+
+    Ok(ManagedTexture {
+        gpu_texture,
+        view,
+        sampler,
+        descriptor: descriptor.clone(),
+        size: Vec2::new(descriptor.width as f32, descriptor.height as f32),
+    })
+}
+
+fn texture_format(format: &TextureFormat) -> GpuTextureFormat {
     match format {
-        TextureFormat::Rgba8Unorm => "rgba8unorm",
-        TextureFormat::Rgba8UnormSrgb => "rgba8unorm-srgb",
-        TextureFormat::Bgra8Unorm => "bgra8unorm",
-        TextureFormat::Bgra8UnormSrgb => "bgra8unorm-srgb",
-        TextureFormat::R8Unorm => "r8unorm",
-        TextureFormat::Rg8Unorm => "rg8unorm",
-        TextureFormat::Depth24Plus => "depth24plus",
-        TextureFormat::Depth32Float => "depth32float",
+        TextureFormat::Rgba8Unorm => GpuTextureFormat::Rgba8unorm,
+        TextureFormat::Rgba8UnormSrgb => GpuTextureFormat::Rgba8unormSrgb,
+        TextureFormat::Bgra8Unorm => GpuTextureFormat::Bgra8unorm,
+        TextureFormat::Bgra8UnormSrgb => GpuTextureFormat::Bgra8unormSrgb,
+        TextureFormat::R8Unorm => GpuTextureFormat::R8unorm,
+        TextureFormat::Rg8Unorm => GpuTextureFormat::Rg8unorm,
+        TextureFormat::Depth24Plus => GpuTextureFormat::Depth24plus,
+        TextureFormat::Depth32Float => GpuTextureFormat::Depth32float,
     }
 }
 
 fn usage_to_flags(usage: &TextureUsage) -> u32 {
     let mut flags = 0;
-    if usage.texture_binding { flags |= 1; }
-    if usage.storage_binding { flags |= 2; }
-    if usage.render_attachment { flags |= 4; }
-    if usage.copy_src { flags |= 8; }
-    if usage.copy_dst { flags |= 16; }
+    if usage.copy_src { flags |= web_sys::gpu_texture_usage::COPY_SRC; }
+    if usage.copy_dst { flags |= web_sys::gpu_texture_usage::COPY_DST; }
+    if usage.texture_binding { flags |= web_sys::gpu_texture_usage::TEXTURE_BINDING; }
+    if usage.storage_binding { flags |= web_sys::gpu_texture_usage::STORAGE_BINDING; }
+    if usage.render_attachment { flags |= web_sys::gpu_texture_usage::RENDER_ATTACHMENT; }
     flags
 }
-
-fn base64_encode(data: &[u8]) -> String {
-    // Simple base64 encoding implementation
-    let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut result = String::new();
-    
-    for chunk in data.chunks(3) {
-        let mut buf = [0u8; 3];
-        for (i, &byte) in chunk.iter().enumerate() {
-            buf[i] = byte;
-        }
-        
-        let b0 = buf[0] as usize;
-        let b1 = buf[1] as usize;
-        let b2 = buf[2] as usize;
-        
-        let c0 = b0 >> 2;
-        let c1 = ((b0 & 0x03) << 4) | (b1 >> 4);
-        let c2 = ((b1 & 0x0f) << 2) | (b2 >> 6);
-        let c3 = b2 & 0x3f;
-        
-        result.push(alphabet.chars().nth(c0).unwrap());
-        result.push(alphabet.chars().nth(c1).unwrap());
-        result.push(if chunk.len() > 1 { alphabet.chars().nth(c2).unwrap() } else { '=' });
-        result.push(if chunk.len() > 2 { alphabet.chars().nth(c3).unwrap() } else { '=' });
-    }
-    
-    result
-}
\ No newline at end of file
@@ -35,6 +35,37 @@ pub fn get_viewport_size() -> Vec2 {
     Vec2::new(width as f32, height as f32)
 }
 
+/// The page's CSS `env(safe-area-inset-*)` values in CSS pixels, read from
+/// a hidden probe element. They are zero unless the page's viewport meta tag
+/// has `viewport-fit=cover`.
+pub fn get_safe_area_insets() -> kryon_layout::SafeAreaInsets {
+    let read = || -> Option<kryon_layout::SafeAreaInsets> {
+        let window = web_sys::window()?;
+        let document = window.document()?;
+        let probe = document.create_element("div").ok()?.dyn_into::<web_sys::HtmlElement>().ok()?;
+        let style = probe.style();
+        for (name, value) in [
+            ("position", "fixed"),
+            ("visibility", "hidden"),
+            ("padding-top", "env(safe-area-inset-top)"),
+            ("padding-right", "env(safe-area-inset-right)"),
+            ("padding-bottom", "env(safe-area-inset-bottom)"),
+            ("padding-left", "env(safe-area-inset-left)"),
+        ] {
+            style.set_property(name, value).ok()?;
+        }
+        document.body()?.append_child(&probe).ok()?;
+        let computed = window.get_computed_style(&probe).ok().flatten();
+        probe.remove();
+        let computed = computed?;
+        let side = |name: &str| computed.get_property_value(name).ok()
+            .and_then(|value| value.trim_end_matches("px").parse::<f32>().ok())
+            .unwrap_or(0.0);
+        Some(kryon_layout::SafeAreaInsets::new(side("padding-top"), side("padding-right"), side("padding-bottom"), side("padding-left")))
+    };
+    read().unwrap_or_default()
+}
+
 /// Check if the browser supports WebGL2
 pub fn supports_webgl2() -> bool {
     let window = web_sys::window().unwrap();
@@ -1,18 +1,51 @@
 //! WebGPU renderer for web browsers
+//!
+//! Uses the pipelines shared with kryon-wgpu (see `shaders`). Commands are
+//! recorded between `clear` and `flush` into a [`GpuFrame`] and drawn in
+//! order in a single render pass. Text comes from a glyph atlas rasterized
+//! with an `OffscreenCanvas`, so the renderer also works inside a worker.
 
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlCanvasElement, GpuCanvasContext, GpuDevice, GpuQueue, GpuRenderPassEncoder};
-use kryon_render::{Renderer, RenderResult, RenderError, RenderCommand};
-use kryon_core::Element;
-use kryon_layout::LayoutResult;
-use glam::{Vec2, Vec4};
+use wasm_bindgen::sys::JsNullable;
+use crate::canvas_renderer::CanvasSurface;
+use crate::gpu_frame::{BatchKind, Clip, Glyph, GlyphSource, GpuFrame};
+use crate::shaders::ShaderManager;
+use crate::texture_manager::TextureManager;
+use web_sys::{
+    Gpu, GpuBindGroup, GpuBindGroupDescriptor, GpuBindGroupEntry, GpuBuffer, GpuBufferDescriptor,
+    GpuCanvasAlphaMode, GpuCanvasConfiguration, GpuCanvasContext, GpuColorDict, GpuCopyExternalImageDestInfo,
+    GpuCopyExternalImageSourceInfo, GpuDevice, GpuFilterMode, GpuLoadOp, GpuQueue, GpuRenderPassColorAttachment,
+    GpuRenderPassDescriptor, GpuRenderPassEncoder, GpuSampler, GpuSamplerDescriptor, GpuStoreOp, GpuTexture,
+    GpuTextureDescriptor, GpuTextureFormat, GpuTextureView, HtmlCanvasElement, ImageBitmap, OffscreenCanvas,
+    OffscreenCanvasRenderingContext2d, gpu_buffer_usage, gpu_texture_usage,
+};
+use kryon_render::RenderCommand;
+use glam::{Mat4, Vec2, Vec4};
+use std::collections::HashMap;
+
+const ATLAS_SIZE: u32 = 1024;
+const GLYPH_PADDING: f32 = 2.0;
 
 pub struct WebGpuRenderer {
-    canvas: HtmlCanvasElement,
+    canvas: CanvasSurface,
     device: GpuDevice,
     queue: GpuQueue,
     context: GpuCanvasContext,
+    format: GpuTextureFormat,
     size: Vec2,
+    shaders: ShaderManager,
+    view_projection: GpuBuffer,
+    view_projection_group: GpuBindGroup,
+    glyphs: GlyphAtlas,
+    textures: TextureManager,
+    /// Bind groups for the textures in `textures`, created on first draw
+    images: HashMap<String, GpuBindGroup>,
+    rect_buffer: VertexBuffer,
+    gradient_buffer: VertexBuffer,
+    textured_buffer: VertexBuffer,
+    particle_buffer: VertexBuffer,
+    frame: GpuFrame,
+    clear_color: Vec4,
 }
 
 impl WebGpuRenderer {
@@ -25,152 +58,401 @@ impl WebGpuRenderer {
             .ok_or("Canvas element not found")?
             .dyn_into::<HtmlCanvasElement>()
             .map_err(|_| "Element is not a canvas")?;
-        
-        // Check if WebGPU is available
-        let navigator = window.navigator();
-        let gpu = js_sys::Reflect::get(&navigator, &JsValue::from_str("gpu"))
-            .map_err(|_| "WebGPU not available")?;
-        
-        if gpu.is_undefined() {
-            return Err("WebGPU not supported in this browser".into());
-        }
-        
-        // Get WebGPU adapter
-        let adapter_promise = js_sys::Reflect::get(&gpu, &JsValue::from_str("requestAdapter"))
-            .map_err(|_| "Failed to get requestAdapter")?;
-        
-        let adapter_promise = js_sys::Function::from(adapter_promise)
-            .call0(&gpu)
-            .map_err(|_| "Failed to call requestAdapter")?;
-        
-        let adapter = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::from(adapter_promise))
+
+        Self::from_surface(CanvasSurface::Element(canvas)).await
+    }
+
+    /// Renders into a canvas transferred to a worker.
+    pub async fn from_offscreen(canvas: OffscreenCanvas) -> Result<Self, JsValue> {
+        Self::from_surface(CanvasSurface::Offscreen(canvas)).await
+    }
+
+    async fn from_surface(canvas: CanvasSurface) -> Result<Self, JsValue> {
+        let gpu = gpu().ok_or("WebGPU not supported in this browser")?;
+
+        let adapter = wasm_bindgen_futures::JsFuture::from(gpu.request_adapter())
             .await
             .map_err(|_| "Failed to get WebGPU adapter")?;
-        
-        // Get WebGPU device
-        let device_promise = js_sys::Reflect::get(&adapter, &JsValue::from_str("requestDevice"))
-            .map_err(|_| "Failed to get requestDevice")?;
-        
-        let device_promise = js_sys::Function::from(device_promise)
-            .call0(&adapter)
-            .map_err(|_| "Failed to call requestDevice")?;
-        
-        let device = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::from(device_promise))
+        if adapter.is_null() {
+            return Err("No WebGPU adapter available".into());
+        }
+        let adapter: web_sys::GpuAdapter = adapter.unchecked_into();
+
+        let device = wasm_bindgen_futures::JsFuture::from(adapter.request_device())
             .await
             .map_err(|_| "Failed to get WebGPU device")?;
-        
-        let device = device.dyn_into::<GpuDevice>()
-            .map_err(|_| "Failed to cast to GpuDevice")?;
-        
-        // Get the queue
+        let device: GpuDevice = device.unchecked_into();
         let queue = device.queue();
-        
-        // Get the canvas context
+
         let context = canvas
             .get_context("webgpu")
             .map_err(|_| "Failed to get WebGPU context")?
             .ok_or("WebGPU context not available")?
             .dyn_into::<GpuCanvasContext>()
             .map_err(|_| "Failed to cast to GpuCanvasContext")?;
-        
-        let size = Vec2::new(canvas.width() as f32, canvas.height() as f32);
-        
-        // Configure the context
-        let config = js_sys::Object::new();
-        js_sys::Reflect::set(&config, &JsValue::from_str("device"), &device)?;
-        js_sys::Reflect::set(&config, &JsValue::from_str("format"), &JsValue::from_str("bgra8unorm"))?;
-        js_sys::Reflect::set(&config, &JsValue::from_str("alphaMode"), &JsValue::from_str("premultiplied"))?;
-        
-        context.configure(&config);
-        
+
+        let format = gpu.get_preferred_canvas_format();
+        let size = canvas.size();
+        canvas.set_size(size, canvas.pixel_ratio());
+        configure(&context, &device, format)?;
+
+        let shaders = ShaderManager::new(&device, format)?;
+
+        let view_projection = device.create_buffer(&GpuBufferDescriptor::new(64, gpu_buffer_usage::UNIFORM | gpu_buffer_usage::COPY_DST))?;
+        let view_projection_group = device.create_bind_group(&GpuBindGroupDescriptor::new(
+            &[GpuBindGroupEntry::new_with_gpu_buffer(0, &view_projection)],
+            &shaders.view_projection_layout,
+        ));
+
+        let sampler_descriptor = GpuSamplerDescriptor::new();
+        sampler_descriptor.set_mag_filter(GpuFilterMode::Linear);
+        sampler_descriptor.set_min_filter(GpuFilterMode::Linear);
+        let sampler = device.create_sampler_with_descriptor(&sampler_descriptor);
+
+        let glyphs = GlyphAtlas::new(&device, &shaders, &sampler)?;
+        let textures = TextureManager::new(device.clone(), queue.clone());
+
         Ok(Self {
             canvas,
             device,
             queue,
             context,
+            format,
             size,
+            shaders,
+            view_projection,
+            view_projection_group,
+            glyphs,
+            textures,
+            images: HashMap::new(),
+            rect_buffer: VertexBuffer::default(),
+            gradient_buffer: VertexBuffer::default(),
+            textured_buffer: VertexBuffer::default(),
+            particle_buffer: VertexBuffer::default(),
+            frame: GpuFrame::new(),
+            clear_color: Vec4::ONE,
         })
     }
-    
+
+    /// Records a command into the current frame; drawn by [`Self::flush`].
     pub fn execute_render_command(&mut self, command: &RenderCommand) -> Result<(), JsValue> {
-        match command {
-            RenderCommand::DrawRect { position, size, color, .. } => {
-                // Basic rectangle rendering using WebGPU
-                // This is a simplified implementation
-                web_sys::console::log_1(&format!("WebGPU: Drawing rect at {:?}, size {:?}, color {:?}", position, size, color).into());
+        let textures = &mut self.textures;
+        self.frame.push(command, &mut self.glyphs, &mut |source| textures.image_status(source));
+        Ok(())
+    }
+
+    /// Starts a new frame cleared to `color`.
+    pub fn clear(&mut self, color: Vec4) -> Result<(), JsValue> {
+        self.frame.clear();
+        self.clear_color = color;
+        for source in self.textures.begin_frame() {
+            self.images.remove(&source);
+        }
+        if self.glyphs.full {
+            self.glyphs.reset();
+        }
+        Ok(())
+    }
+
+    /// Draws the commands recorded since [`Self::clear`].
+    pub fn flush(&mut self) -> Result<(), JsValue> {
+        if let Some(canvas_size) = self.frame.canvas_size.take() {
+            if canvas_size != self.size && canvas_size.x > 0.0 && canvas_size.y > 0.0 {
+                self.resize(canvas_size)?;
+            }
+        }
+
+        let projection = Mat4::orthographic_rh(0.0, self.size.x, self.size.y, 0.0, -1.0, 1.0);
+        self.queue.write_buffer_with_u32_and_buffer_source(
+            &self.view_projection,
+            0,
+            &js_sys::Float32Array::from(&projection.to_cols_array()[..]),
+        )?;
+        self.glyphs.upload(&self.queue)?;
+        let rect_buffer = self.rect_buffer.upload(&self.device, &self.queue, &self.frame.rect_vertices)?;
+        let textured_buffer = self.textured_buffer.upload(&self.device, &self.queue, &self.frame.textured_vertices)?;
+        let gradient_buffer = self.gradient_buffer.upload(&self.device, &self.queue, &self.frame.gradient_vertices)?;
+        let particle_buffer = self.particle_buffer.upload(&self.device, &self.queue, &self.frame.particle_instances)?;
+        for batch in &self.frame.batches {
+            if let BatchKind::Image(source) = &batch.kind {
+                if !self.images.contains_key(source) {
+                    if let Some(texture) = self.textures.get_texture(source) {
+                        let group = texture_bind_group(&self.device, &self.shaders, &texture.view, &texture.sampler);
+                        self.images.insert(source.clone(), group);
+                    }
+                }
             }
-            
-            RenderCommand::DrawText { position, text, color, .. } => {
-                // Basic text rendering using WebGPU
-                web_sys::console::log_1(&format!("WebGPU: Drawing text '{}' at {:?}, color {:?}", text, position, color).into());
+        }
+
+        let view = self.context.get_current_texture()?.create_view()?;
+        let attachment = GpuRenderPassColorAttachment::new_with_gpu_texture_view(GpuLoadOp::Clear, GpuStoreOp::Store, &view);
+        let [r, g, b, a] = self.clear_color.to_array().map(f64::from);
+        attachment.set_clear_value_gpu_color_dict(&GpuColorDict::new(a, b, g, r));
+
+        let encoder = self.device.create_command_encoder();
+        let pass = encoder.begin_render_pass(&GpuRenderPassDescriptor::new(&[JsNullable::wrap(attachment)]))?;
+        pass.set_bind_group(0, Some(&self.view_projection_group));
+
+        for batch in &self.frame.batches {
+            let (pipeline, buffer, texture_group) = match &batch.kind {
+                BatchKind::Rect => (&self.shaders.rect, &rect_buffer, None),
+                BatchKind::Text => (&self.shaders.text, &textured_buffer, Some(&self.glyphs.bind_group)),
+                BatchKind::Image(source) => (&self.shaders.image, &textured_buffer, self.images.get(source)),
+                BatchKind::Gradient => (&self.shaders.gradient, &gradient_buffer, None),
+                BatchKind::Particles => (&self.shaders.particle, &particle_buffer, None),
+            };
+            if !self.scissor(&pass, batch.clip) {
+                continue;
+            }
+            pass.set_pipeline(pipeline);
+            pass.set_vertex_buffer(0, buffer.as_ref());
+            if let Some(group) = texture_group {
+                pass.set_bind_group(1, Some(group));
             }
-            
-            _ => {
-                web_sys::console::log_1(&format!("WebGPU: Unimplemented command: {:?}", command).into());
+            match batch.kind {
+                BatchKind::Particles => pass.draw_with_instance_count_and_first_vertex_and_first_instance(6, batch.count, 0, batch.first),
+                _ => pass.draw_with_instance_count_and_first_vertex(batch.count, 1, batch.first),
             }
         }
-        
+
+        pass.end();
+        self.queue.submit(&[encoder.finish()]);
         Ok(())
     }
-    
-    pub fn clear(&mut self, color: Vec4) -> Result<(), JsValue> {
-        // Get the current texture
-        let texture = self.context.get_current_texture();
-        
-        // Create a command encoder
-        let encoder = self.device.create_command_encoder(&js_sys::Object::new());
-        
-        // Create a render pass
-        let color_attachment = js_sys::Object::new();
-        js_sys::Reflect::set(&color_attachment, &JsValue::from_str("view"), &texture.create_view(&js_sys::Object::new()))?;
-        js_sys::Reflect::set(&color_attachment, &JsValue::from_str("clearValue"), &js_sys::Object::new())?;
-        js_sys::Reflect::set(&color_attachment, &JsValue::from_str("loadOp"), &JsValue::from_str("clear"))?;
-        js_sys::Reflect::set(&color_attachment, &JsValue::from_str("storeOp"), &JsValue::from_str("store"))?;
-        
-        let render_pass_desc = js_sys::Object::new();
-        let color_attachments = js_sys::Array::new();
-        color_attachments.push(&color_attachment);
-        js_sys::Reflect::set(&render_pass_desc, &JsValue::from_str("colorAttachments"), &color_attachments)?;
-        
-        let render_pass = encoder.begin_render_pass(&render_pass_desc);
-        render_pass.end();
-        
-        // Submit the command
-        let commands = js_sys::Array::new();
-        commands.push(&encoder.finish(&js_sys::Object::new()));
-        self.queue.submit(&commands);
-        
+
+    /// Clears, draws `commands` and presents them.
+    pub fn render_commands(&mut self, commands: &[RenderCommand], clear_color: Vec4) -> Result<(), JsValue> {
+        self.clear(clear_color)?;
+        for command in commands {
+            self.execute_render_command(command)?;
+        }
+        self.flush()
+    }
+
+    /// Sets the scissor for a batch; `false` when the clip is empty.
+    fn scissor(&self, pass: &GpuRenderPassEncoder, clip: Option<Clip>) -> bool {
+        let viewport = Clip { position: Vec2::ZERO, size: self.size };
+        let clip = clip.map_or(viewport, |clip| clip.intersect(&viewport));
+        let start = clip.position.floor();
+        let end = (clip.position + clip.size).ceil().min(self.size.floor());
+        if end.x <= start.x || end.y <= start.y {
+            return false;
+        }
+        pass.set_scissor_rect(start.x as u32, start.y as u32, (end.x - start.x) as u32, (end.y - start.y) as u32);
+        true
+    }
+
+    /// Uploads a decoded image under `source`, replacing any loaded one.
+    /// Sources drawn without an upload are fetched and decoded automatically.
+    pub fn upload_image(&mut self, source: &str, bitmap: &ImageBitmap) -> Result<(), JsValue> {
+        self.textures.create_texture_from_bitmap(source, bitmap)?;
+        self.images.remove(source);
         Ok(())
     }
-    
+
+    pub fn has_image(&self, source: &str) -> bool {
+        self.textures.get_texture(source).is_some()
+    }
+
+    /// Image loading: cache control, memory budget and ready notifications
+    pub fn texture_manager(&mut self) -> &mut TextureManager {
+        &mut self.textures
+    }
+
+    /// `new_size` is in CSS pixels, which the projection maps onto the
+    /// canvas's device pixels
     pub fn resize(&mut self, new_size: Vec2) -> Result<(), JsValue> {
         self.size = new_size;
-        self.canvas.set_width(new_size.x as u32);
-        self.canvas.set_height(new_size.y as u32);
-        
-        // Reconfigure the context
-        let config = js_sys::Object::new();
-        js_sys::Reflect::set(&config, &JsValue::from_str("device"), &self.device)?;
-        js_sys::Reflect::set(&config, &JsValue::from_str("format"), &JsValue::from_str("bgra8unorm"))?;
-        js_sys::Reflect::set(&config, &JsValue::from_str("alphaMode"), &JsValue::from_str("premultiplied"))?;
-        
-        self.context.configure(&config);
-        
-        Ok(())
+        self.canvas.set_size(new_size, self.canvas.pixel_ratio());
+        configure(&self.context, &self.device, self.format)
     }
-    
+
     pub fn size(&self) -> Vec2 {
         self.size
     }
-    
-    /// Check if WebGPU is available in the browser
+
+    /// Check if WebGPU is available in this context
     pub fn is_available() -> bool {
-        if let Some(window) = web_sys::window() {
-            let navigator = window.navigator();
-            if let Ok(gpu) = js_sys::Reflect::get(&navigator, &JsValue::from_str("gpu")) {
-                return !gpu.is_undefined();
+        gpu().is_some()
+    }
+}
+
+/// `navigator.gpu` from the window or worker global scope
+fn gpu() -> Option<Gpu> {
+    let global = js_sys::global();
+    let gpu = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+        window.navigator().gpu()
+    } else {
+        global.dyn_ref::<web_sys::WorkerGlobalScope>()?.navigator().gpu()
+    };
+    (!JsValue::from(&gpu).is_undefined()).then_some(gpu)
+}
+
+fn configure(context: &GpuCanvasContext, device: &GpuDevice, format: GpuTextureFormat) -> Result<(), JsValue> {
+    let config = GpuCanvasConfiguration::new(device, format);
+    config.set_alpha_mode(GpuCanvasAlphaMode::Premultiplied);
+    context.configure(&config)
+}
+
+fn create_texture(device: &GpuDevice, width: u32, height: u32, label: &str) -> Result<GpuTexture, JsValue> {
+    // copyExternalImageToTexture needs RENDER_ATTACHMENT on the destination
+    let descriptor = GpuTextureDescriptor::new(
+        GpuTextureFormat::Rgba8unorm,
+        &[width.into(), height.into()],
+        gpu_texture_usage::TEXTURE_BINDING | gpu_texture_usage::COPY_DST | gpu_texture_usage::RENDER_ATTACHMENT,
+    );
+    descriptor.set_label(label);
+    device.create_texture(&descriptor)
+}
+
+fn texture_bind_group(device: &GpuDevice, shaders: &ShaderManager, view: &GpuTextureView, sampler: &GpuSampler) -> GpuBindGroup {
+    let entries = [
+        GpuBindGroupEntry::new_with_gpu_texture_view(0, view),
+        GpuBindGroupEntry::new(1, sampler),
+    ];
+    device.create_bind_group(&GpuBindGroupDescriptor::new(&entries, &shaders.texture_layout))
+}
+
+/// A vertex buffer that grows to fit each frame's data.
+#[derive(Default)]
+struct VertexBuffer {
+    buffer: Option<GpuBuffer>,
+    capacity: u32,
+}
+
+impl VertexBuffer {
+    fn upload(&mut self, device: &GpuDevice, queue: &GpuQueue, data: &[f32]) -> Result<Option<GpuBuffer>, JsValue> {
+        if data.is_empty() {
+            return Ok(None);
+        }
+        let bytes = (data.len() * 4) as u32;
+        if self.buffer.is_none() || bytes > self.capacity {
+            if let Some(old) = self.buffer.take() {
+                old.destroy();
             }
+            self.capacity = bytes.next_power_of_two().max(64 * 1024);
+            self.buffer = Some(device.create_buffer(&GpuBufferDescriptor::new(
+                self.capacity,
+                gpu_buffer_usage::VERTEX | gpu_buffer_usage::COPY_DST,
+            ))?);
         }
-        false
+        let buffer = self.buffer.clone().expect("buffer was just created");
+        queue.write_buffer_with_u32_and_buffer_source(&buffer, 0, &js_sys::Float32Array::from(data))?;
+        Ok(Some(buffer))
     }
-}
\ No newline at end of file
+}
+
+/// Glyphs rasterized white-on-black with the 2D canvas API, so the red
+/// channel is coverage as the shared text shader expects. Glyphs are
+/// shelf-packed; when the atlas fills up it is cleared before the next frame.
+struct GlyphAtlas {
+    canvas: OffscreenCanvas,
+    context: OffscreenCanvasRenderingContext2d,
+    texture: GpuTexture,
+    bind_group: GpuBindGroup,
+    glyphs: HashMap<(char, u32, String), Glyph>,
+    cursor: Vec2,
+    row_height: f32,
+    dirty: bool,
+    full: bool,
+}
+
+impl GlyphAtlas {
+    fn new(device: &GpuDevice, shaders: &ShaderManager, sampler: &GpuSampler) -> Result<Self, JsValue> {
+        let canvas = OffscreenCanvas::new(ATLAS_SIZE, ATLAS_SIZE)?;
+        let context = canvas
+            .get_context("2d")?
+            .ok_or("2D context not available")?
+            .dyn_into::<OffscreenCanvasRenderingContext2d>()?;
+        context.set_text_baseline("top");
+        let texture = create_texture(device, ATLAS_SIZE, ATLAS_SIZE, "Glyph Atlas")?;
+        let bind_group = texture_bind_group(device, shaders, &texture.create_view()?, sampler);
+        let mut atlas = Self {
+            canvas,
+            context,
+            texture,
+            bind_group,
+            glyphs: HashMap::new(),
+            cursor: Vec2::ZERO,
+            row_height: 0.0,
+            dirty: false,
+            full: false,
+        };
+        atlas.reset();
+        Ok(atlas)
+    }
+
+    fn reset(&mut self) {
+        self.context.set_fill_style_str("black");
+        self.context.fill_rect(0.0, 0.0, ATLAS_SIZE as f64, ATLAS_SIZE as f64);
+        self.glyphs.clear();
+        self.cursor = Vec2::ZERO;
+        self.row_height = 0.0;
+        self.dirty = true;
+        self.full = false;
+    }
+
+    fn upload(&mut self, queue: &GpuQueue) -> Result<(), JsValue> {
+        if !self.dirty {
+            return Ok(());
+        }
+        queue.copy_external_image_to_texture_with_u32_sequence(
+            &GpuCopyExternalImageSourceInfo::new_with_offscreen_canvas(&self.canvas),
+            &GpuCopyExternalImageDestInfo::new(&self.texture),
+            &[ATLAS_SIZE.into(), ATLAS_SIZE.into()],
+        )?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+impl GlyphSource for GlyphAtlas {
+    fn glyph(&mut self, character: char, font_size: f32, font_family: &str) -> Option<Glyph> {
+        // Rasterize at whole pixel sizes and scale to the requested size
+        let pixels = font_size.ceil().max(1.0) as u32;
+        let scale = font_size / pixels as f32;
+        let key = (character, pixels, font_family.to_string());
+        if let Some(glyph) = self.glyphs.get(&key) {
+            return Some(scale_glyph(*glyph, scale));
+        }
+        if self.full {
+            return None;
+        }
+
+        let text = character.to_string();
+        self.context.set_font(&format!("{}px {}", pixels, font_family));
+        let metrics = self.context.measure_text(&text).ok()?;
+        let advance = metrics.width() as f32;
+        let width = advance.max(metrics.actual_bounding_box_right() as f32).ceil();
+        let height = (pixels as f32 * 1.25).ceil();
+
+        if self.cursor.x + width + GLYPH_PADDING > ATLAS_SIZE as f32 {
+            self.cursor = Vec2::new(0.0, self.cursor.y + self.row_height + GLYPH_PADDING);
+            self.row_height = 0.0;
+        }
+        if self.cursor.y + height > ATLAS_SIZE as f32 {
+            self.full = true;
+            return None;
+        }
+
+        let origin = self.cursor;
+        self.context.set_fill_style_str("white");
+        self.context.fill_text(&text, origin.x as f64, origin.y as f64).ok()?;
+        self.cursor.x += width + GLYPH_PADDING;
+        self.row_height = self.row_height.max(height);
+        self.dirty = true;
+
+        let atlas = ATLAS_SIZE as f32;
+        let glyph = Glyph {
+            uv: [origin.x / atlas, origin.y / atlas, (origin.x + width) / atlas, (origin.y + height) / atlas],
+            size: Vec2::new(width, height),
+            advance,
+        };
+        self.glyphs.insert(key, glyph);
+        Some(scale_glyph(glyph, scale))
+    }
+}
+
+fn scale_glyph(glyph: Glyph, scale: f32) -> Glyph {
+    Glyph { size: glyph.size * scale, advance: glyph.advance * scale, ..glyph }
+}
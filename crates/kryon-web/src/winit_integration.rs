@@ -68,6 +68,8 @@ impl WinitWebBridge {
                                 0 => MouseButton::Left,
                                 1 => MouseButton::Middle,
                                 2 => MouseButton::Right,
+                                3 => MouseButton::Back,
+                                4 => MouseButton::Forward,
                                 _ => MouseButton::Other(button as u16),
                             },
                         },
@@ -92,6 +94,8 @@ impl WinitWebBridge {
                                 0 => MouseButton::Left,
                                 1 => MouseButton::Middle,
                                 2 => MouseButton::Right,
+                                3 => MouseButton::Back,
+                                4 => MouseButton::Forward,
                                 _ => MouseButton::Other(button as u16),
                             },
                         },
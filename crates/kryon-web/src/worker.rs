@@ -0,0 +1,496 @@
+//! Layout and command generation in a dedicated web worker
+//!
+//! `start_layout_worker` runs inside the worker: it parses the KRB, runs
+//! Taffy layout and generates `RenderCommand`s, then posts each frame back
+//! as a `DisplayList`. On the main thread `LayoutWorkerClient` only forwards
+//! input and resizes and draws the latest frame onto a canvas, so a long
+//! layout pass no longer stalls animation or input.
+//!
+//! ```js
+//! // worker.js
+//! import init, { start_layout_worker } from './kryon_web.js';
+//! await init();
+//! start_layout_worker();
+//!
+//! // main thread
+//! const client = new LayoutWorkerClient('./worker.js', 'kryon-canvas');
+//! client.load_krb(new Uint8Array(await (await fetch('app.krb')).arrayBuffer()));
+//! canvas.addEventListener('mousemove', e => client.forward_mouse_event(e));
+//! requestAnimationFrame(function loop(t) { client.frame(t); requestAnimationFrame(loop); });
+//! ```
+//!
+//! Messages are plain objects with a `type` field. Frames travel as a
+//! transferred `Float32Array` plus a string table, so nothing is copied.
+
+use crate::canvas_renderer::{CanvasRenderer, CanvasSurface};
+use crate::display_list::DisplayList;
+use glam::{Vec2, Vec4};
+use kryon_core::{Element, ElementId, HitRegion, InteractionState, Orientation, OrientationVariants, ResolvedTransform, StyleComputer};
+use kryon_layout::{LayoutEngine, LayoutResult, SafeAreaInsets, TaffyLayoutEngine};
+use kryon_render::{CommandRenderer, ElementRenderer, RenderCommand, RenderResult, Renderer};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    DedicatedWorkerGlobalScope, HtmlCanvasElement, MessageEvent, MouseEvent, OffscreenCanvas, Worker, WorkerOptions, WorkerType,
+};
+
+pub(crate) const CLEAR_COLOR: Vec4 = Vec4::new(0.95, 0.95, 0.95, 1.0);
+
+/// Backend that keeps the commands of the last frame instead of drawing them.
+pub struct RecordingBackend {
+    size: Vec2,
+    commands: Vec<RenderCommand>,
+    frame_ready: bool,
+}
+
+impl RecordingBackend {
+    /// The last frame's commands, once per rendered frame.
+    pub fn take_frame(&mut self) -> Option<Vec<RenderCommand>> {
+        std::mem::take(&mut self.frame_ready).then(|| std::mem::take(&mut self.commands))
+    }
+}
+
+impl Renderer for RecordingBackend {
+    type Surface = Vec2;
+    type Context = ();
+
+    fn initialize(size: Self::Surface) -> RenderResult<Self> {
+        Ok(Self { size, commands: Vec::new(), frame_ready: false })
+    }
+
+    fn begin_frame(&mut self, _clear_color: Vec4) -> RenderResult<()> {
+        self.commands.clear();
+        Ok(())
+    }
+
+    fn end_frame(&mut self, _context: ()) -> RenderResult<()> {
+        self.frame_ready = true;
+        Ok(())
+    }
+
+    fn render_element(&mut self, _context: &mut (), _element: &Element, _layout: &LayoutResult, _element_id: ElementId) -> RenderResult<()> {
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: Vec2) -> RenderResult<()> {
+        self.size = new_size;
+        Ok(())
+    }
+
+    fn viewport_size(&self) -> Vec2 {
+        self.size
+    }
+}
+
+impl CommandRenderer for RecordingBackend {
+    fn execute_commands(&mut self, _context: &mut (), commands: &[RenderCommand]) -> RenderResult<()> {
+        self.commands.extend_from_slice(commands);
+        Ok(())
+    }
+
+    /// The main thread's canvas keeps showing the last frame posted
+    fn skips_unchanged_frames(&self) -> bool {
+        true
+    }
+}
+
+/// The scene owned by the worker. Hover and press states are tracked here;
+/// scripts are not run in worker mode.
+struct WorkerScene {
+    elements: HashMap<ElementId, Element>,
+    root_id: ElementId,
+    layout_engine: TaffyLayoutEngine,
+    layout: LayoutResult,
+    renderer: ElementRenderer<RecordingBackend>,
+    viewport: Vec2,
+    orientation_variants: OrientationVariants,
+    needs_layout: bool,
+    needs_render: bool,
+}
+
+impl WorkerScene {
+    fn load(data: &[u8], viewport: Vec2, safe_area: SafeAreaInsets) -> Result<Self, JsValue> {
+        let krb = kryon_core::load_krb_from_bytes(data).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let root_id = krb.root_element_id.ok_or("KRB has no root element")?;
+        let mut style_computer = StyleComputer::new(&krb.elements, &krb.styles);
+        style_computer.set_default_styles(kryon_core::DefaultStyles::builtin());
+        let backend = RecordingBackend::initialize(viewport).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let mut layout_engine = TaffyLayoutEngine::new();
+        layout_engine.set_safe_area(safe_area);
+        let mut scene = Self {
+            orientation_variants: OrientationVariants::from_elements(&krb.elements),
+            elements: krb.elements,
+            root_id,
+            layout_engine,
+            layout: LayoutResult { computed_positions: HashMap::new(), computed_sizes: HashMap::new() },
+            renderer: ElementRenderer::new(backend, style_computer),
+            viewport,
+            needs_layout: true,
+            needs_render: true,
+        };
+        scene.apply_orientation();
+        Ok(scene)
+    }
+
+    fn apply_orientation(&mut self) {
+        let orientation = Orientation::from_size(self.viewport);
+        self.orientation_variants.apply(&mut self.elements, orientation);
+        self.renderer.set_orientation(orientation);
+    }
+
+    /// Safe-area insets usually change with the viewport, on rotation.
+    fn resize(&mut self, viewport: Vec2, safe_area: SafeAreaInsets) {
+        self.layout_engine.set_safe_area(safe_area);
+        let rotated = Orientation::from_size(viewport) != Orientation::from_size(self.viewport);
+        self.viewport = viewport;
+        let _ = self.renderer.resize(viewport);
+        if rotated {
+            self.apply_orientation();
+        }
+        self.needs_layout = true;
+    }
+
+    /// Lays out and renders if anything changed since the last frame.
+    fn frame(&mut self) -> Option<Vec<RenderCommand>> {
+        if self.needs_layout {
+            self.layout = self.layout_engine.compute_layout(&self.elements, self.root_id, self.viewport);
+            self.needs_layout = false;
+            self.needs_render = true;
+        }
+        if !self.needs_render {
+            return None;
+        }
+        self.needs_render = false;
+        if let Err(e) = self.renderer.render_frame(&self.elements, &self.layout, self.root_id, CLEAR_COLOR) {
+            web_sys::console::error_1(&format!("[WORKER] Render failed: {}", e).into());
+            return None;
+        }
+        self.renderer.backend_mut().take_frame()
+    }
+
+    fn handle_mouse(&mut self, kind: &str, position: Vec2) {
+        let target = self.element_at(position);
+        let pressed = kind == "mousedown";
+        let path = kryon_core::hover_path(&self.elements, target);
+        self.renderer.set_hover_path(&path);
+        for (&id, element) in self.elements.iter_mut() {
+            if element.current_state == InteractionState::Checked {
+                continue;
+            }
+            let state = match target {
+                Some(target) if target == id && pressed => InteractionState::Active,
+                _ if path.contains(&id) => InteractionState::Hover,
+                _ => InteractionState::Normal,
+            };
+            if element.current_state != state {
+                element.current_state = state;
+                self.needs_render = true;
+            }
+        }
+    }
+
+    /// The topmost visible element under `position` that takes pointer input.
+    fn element_at(&self, position: Vec2) -> Option<ElementId> {
+        self.elements.iter()
+            .filter(|(&id, element)| element.visible && kryon_core::receives_pointer_events(&self.elements, id))
+            .filter(|(&id, element)| {
+                let (Some(&origin), Some(&size)) = (self.layout.computed_positions.get(&id), self.layout.computed_sizes.get(&id)) else {
+                    return false;
+                };
+                let region = HitRegion {
+                    transform: element.custom_properties.get("transform")
+                        .and_then(|v| v.as_transform())
+                        .map(|transform| {
+                            let lengths = kryon_core::inline_length_context(&self.elements, id);
+                            ResolvedTransform::resolve(&transform.resolve_lengths(&lengths, size), size)
+                        }),
+                    ..HitRegion::new(origin, size)
+                };
+                region.contains(position)
+            })
+            .map(|(&id, _)| id)
+            .max()
+    }
+}
+
+fn field(message: &JsValue, name: &str) -> JsValue {
+    js_sys::Reflect::get(message, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+}
+
+fn number_field(message: &JsValue, name: &str) -> f32 {
+    field(message, name).as_f64().unwrap_or(0.0) as f32
+}
+
+/// Insets sent as `[top, right, bottom, left]` in a `safe_area` field
+fn safe_area_field(message: &JsValue) -> SafeAreaInsets {
+    let side = |index| js_sys::Reflect::get_u32(&field(message, "safe_area"), index).ok().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+    SafeAreaInsets::new(side(0), side(1), side(2), side(3))
+}
+
+fn safe_area_value() -> js_sys::Array {
+    let insets = crate::utils::get_safe_area_insets();
+    [insets.top, insets.right, insets.bottom, insets.left].iter().map(|&inset| JsValue::from_f64(inset as f64)).collect()
+}
+
+fn message(kind: &str, fields: &[(&str, &JsValue)]) -> js_sys::Object {
+    let object = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&object, &"type".into(), &kind.into());
+    for (name, value) in fields {
+        let _ = js_sys::Reflect::set(&object, &(*name).into(), value);
+    }
+    object
+}
+
+#[derive(Default)]
+struct WorkerState {
+    scene: Option<WorkerScene>,
+    /// The page's canvas, once transferred. The worker then draws frames
+    /// itself instead of posting them.
+    canvas: Option<CanvasRenderer>,
+    /// Whether the worker runs its own `requestAnimationFrame` loop, which
+    /// makes `tick` messages redundant
+    self_driven: bool,
+}
+
+impl WorkerState {
+    fn draw(&mut self) {
+        let (Some(scene), Some(canvas)) = (&mut self.scene, &mut self.canvas) else {
+            return;
+        };
+        if let Some(commands) = scene.frame() {
+            if let Err(e) = draw(canvas, &commands) {
+                web_sys::console::error_2(&"[WORKER] Draw failed:".into(), &e);
+            }
+        }
+    }
+}
+
+fn draw(canvas: &mut CanvasRenderer, commands: &[RenderCommand]) -> Result<(), JsValue> {
+    canvas.clear(CLEAR_COLOR)?;
+    for command in commands {
+        canvas.execute_render_command(command)?;
+    }
+    Ok(())
+}
+
+/// Drives rendering from the worker's own animation frames. Returns false
+/// where workers have no `requestAnimationFrame`, leaving it to `tick`.
+fn start_render_loop(scope: &DedicatedWorkerGlobalScope, state: Rc<RefCell<WorkerState>>) -> bool {
+    if !js_sys::Reflect::has(scope, &JsValue::from_str("requestAnimationFrame")).unwrap_or(false) {
+        return false;
+    }
+    // The callback re-arms itself for the life of the worker
+    let callback: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
+    let next = callback.clone();
+    let frame_scope = scope.clone();
+    *callback.borrow_mut() = Some(Closure::new(move |_timestamp: f64| {
+        state.borrow_mut().draw();
+        if let Some(next) = next.borrow().as_ref() {
+            let _ = frame_scope.request_animation_frame(next.as_ref().unchecked_ref());
+        }
+    }));
+    let first = callback.borrow();
+    first.as_ref().is_some_and(|first| scope.request_animation_frame(first.as_ref().unchecked_ref()).is_ok())
+}
+
+/// Entry point for the worker script. Handles `load`, `canvas`, `resize`,
+/// `input` and `tick` messages. Until the page transfers its canvas, each
+/// tick that changed something is answered with a `frame` message.
+#[wasm_bindgen]
+pub fn start_layout_worker() -> Result<(), JsValue> {
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().dyn_into()?;
+    let reply_scope = scope.clone();
+    let state = Rc::new(RefCell::new(WorkerState::default()));
+
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        let data = event.data();
+        let viewport = || Vec2::new(number_field(&data, "width"), number_field(&data, "height"));
+        match field(&data, "type").as_string().as_deref() {
+            Some("load") => {
+                let bytes = js_sys::Uint8Array::new(&field(&data, "krb")).to_vec();
+                match WorkerScene::load(&bytes, viewport(), safe_area_field(&data)) {
+                    Ok(loaded) => state.borrow_mut().scene = Some(loaded),
+                    Err(e) => {
+                        let _ = reply_scope.post_message(&message("error", &[("message", &e)]));
+                    }
+                }
+            }
+            Some("canvas") => {
+                let canvas = field(&data, "canvas").dyn_into::<OffscreenCanvas>().map_err(|_| JsValue::from_str("Not an OffscreenCanvas"));
+                match canvas.and_then(CanvasRenderer::from_offscreen) {
+                    Ok(canvas) => {
+                        state.borrow_mut().canvas = Some(canvas);
+                        let self_driven = start_render_loop(&reply_scope, state.clone());
+                        state.borrow_mut().self_driven = self_driven;
+                    }
+                    Err(e) => {
+                        let _ = reply_scope.post_message(&message("error", &[("message", &e)]));
+                    }
+                }
+            }
+            Some("resize") => {
+                let mut state = state.borrow_mut();
+                if let Some(canvas) = &mut state.canvas {
+                    let _ = canvas.resize(viewport());
+                }
+                if let Some(scene) = &mut state.scene {
+                    scene.resize(viewport(), safe_area_field(&data));
+                }
+            }
+            Some("input") => {
+                if let (Some(scene), Some(kind)) = (&mut state.borrow_mut().scene, field(&data, "event").as_string()) {
+                    scene.handle_mouse(&kind, Vec2::new(number_field(&data, "x"), number_field(&data, "y")));
+                }
+            }
+            Some("tick") => {
+                let mut state = state.borrow_mut();
+                if state.canvas.is_some() {
+                    if !state.self_driven {
+                        state.draw();
+                    }
+                    return;
+                }
+                let Some(commands) = state.scene.as_mut().and_then(WorkerScene::frame) else {
+                    return;
+                };
+                let list = DisplayList::encode(&commands);
+                let numbers = js_sys::Float32Array::from(list.numbers.as_slice());
+                let strings: js_sys::Array = list.strings.iter().map(|s| JsValue::from_str(s)).collect();
+                let reply = message("frame", &[("numbers", &numbers), ("strings", &strings)]);
+                let transfer = js_sys::Array::of1(&numbers.buffer());
+                if let Err(e) = reply_scope.post_message_with_transfer(&reply, &transfer) {
+                    web_sys::console::error_2(&"[WORKER] Failed to post frame:".into(), &e);
+                }
+            }
+            _ => {}
+        }
+    });
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+    Ok(())
+}
+
+/// Main-thread side: owns the worker and, unless it was handed to the
+/// worker, the canvas.
+#[wasm_bindgen]
+pub struct LayoutWorkerClient {
+    worker: Worker,
+    /// `None` once the canvas has been transferred to the worker
+    canvas: Option<CanvasRenderer>,
+    size: Vec2,
+    latest: Rc<RefCell<Option<DisplayList>>>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+}
+
+#[wasm_bindgen]
+impl LayoutWorkerClient {
+    /// Starts `worker_url` as a module worker drawing onto `canvas_id`.
+    /// Where OffscreenCanvas is supported the canvas is transferred and the
+    /// worker renders on its own; otherwise frames come back to be drawn here.
+    #[wasm_bindgen(constructor)]
+    pub fn new(worker_url: &str, canvas_id: &str) -> Result<LayoutWorkerClient, JsValue> {
+        let options = WorkerOptions::new();
+        options.set_type(WorkerType::Module);
+        let worker = Worker::new_with_options(worker_url, &options)?;
+
+        let latest = Rc::new(RefCell::new(None));
+        let inbox = latest.clone();
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let data = event.data();
+            match field(&data, "type").as_string().as_deref() {
+                Some("frame") => {
+                    let numbers = js_sys::Float32Array::new(&field(&data, "numbers")).to_vec();
+                    let strings = js_sys::Array::from(&field(&data, "strings")).iter().filter_map(|s| s.as_string()).collect();
+                    // Frames that arrive between two draws replace each other
+                    *inbox.borrow_mut() = Some(DisplayList { numbers, strings });
+                }
+                Some("error") => web_sys::console::error_2(&"[WORKER]".into(), &field(&data, "message")),
+                _ => {}
+            }
+        });
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let element = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(canvas_id))
+            .ok_or("Canvas element not found")?
+            .dyn_into::<HtmlCanvasElement>()
+            .map_err(|_| "Element is not a canvas")?;
+        let size = Vec2::new(element.width() as f32, element.height() as f32);
+        let offscreen = CanvasSurface::offscreen_supported(&element)
+            .then(|| element.transfer_control_to_offscreen().ok())
+            .flatten();
+        let canvas = match offscreen {
+            Some(offscreen) => {
+                let transfer = message("canvas", &[("canvas", &offscreen)]);
+                worker.post_message_with_transfer(&transfer, &js_sys::Array::of1(&offscreen))?;
+                None
+            }
+            None => Some(CanvasRenderer::new(canvas_id)?),
+        };
+
+        Ok(Self { worker, canvas, size, latest, _onmessage: onmessage })
+    }
+
+    /// Whether the worker draws the canvas itself.
+    #[wasm_bindgen(getter)]
+    pub fn offscreen(&self) -> bool {
+        self.canvas.is_none()
+    }
+
+    /// Sends a KRB file to the worker, which parses and lays it out.
+    pub fn load_krb(&self, krb_data: &[u8]) -> Result<(), JsValue> {
+        let krb = js_sys::Uint8Array::from(krb_data);
+        let load = message("load", &[
+            ("krb", &krb),
+            ("width", &self.size.x.into()),
+            ("height", &self.size.y.into()),
+            ("safe_area", &safe_area_value()),
+        ]);
+        self.worker.post_message_with_transfer(&load, &js_sys::Array::of1(&krb.buffer()))
+    }
+
+    /// Resizes the canvas. A transferred canvas can only be resized by the
+    /// worker, so the new size is always sent along. The page's safe-area
+    /// insets are re-read too, as they change when the device rotates.
+    pub fn resize(&mut self, width: f32, height: f32) -> Result<(), JsValue> {
+        self.size = Vec2::new(width, height);
+        if let Some(canvas) = &mut self.canvas {
+            canvas.resize(self.size)?;
+        }
+        self.worker.post_message(&message("resize", &[("width", &width.into()), ("height", &height.into()), ("safe_area", &safe_area_value())]))
+    }
+
+    /// Forwards `mousemove`, `mousedown` and `mouseup` in canvas coordinates.
+    pub fn forward_mouse_event(&self, event: &MouseEvent) -> Result<(), JsValue> {
+        let input = message("input", &[
+            ("event", &event.type_().into()),
+            ("x", &event.offset_x().into()),
+            ("y", &event.offset_y().into()),
+            ("button", &event.button().into()),
+        ]);
+        self.worker.post_message(&input)
+    }
+
+    /// Call from `requestAnimationFrame`: asks the worker for the next frame
+    /// and draws the newest one it has sent, if any. Harmless but unneeded
+    /// once the worker draws the canvas itself.
+    pub fn frame(&mut self, timestamp: f64) -> Result<(), JsValue> {
+        self.worker.post_message(&message("tick", &[("timestamp", &timestamp.into())]))?;
+
+        let (Some(canvas), Some(list)) = (&mut self.canvas, self.latest.borrow_mut().take()) else {
+            return Ok(());
+        };
+        let commands = list.decode().ok_or("Malformed display list from worker")?;
+        draw(canvas, &commands)
+    }
+}
+
+impl Drop for LayoutWorkerClient {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
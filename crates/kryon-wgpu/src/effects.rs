@@ -0,0 +1,241 @@
+// crates/kryon-wgpu/src/effects.rs
+//! Custom `shader_effect` support.
+//!
+//! An element's `shader_effect` property names a WGSL snippet (a KRB resource
+//! path, or the snippet itself when inlined). The snippet is wrapped with
+//! [`EFFECT_PRELUDE`], validated with naga and compiled into a pipeline that
+//! fills the element rect. Snippets that fail to load or validate are cached
+//! as failures so the element keeps its static background color.
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec4};
+use kryon_render::{RenderError, RenderResult};
+use std::collections::HashMap;
+use wgpu::util::DeviceExt;
+
+pub use kryon_shaders::EFFECT_PRELUDE;
+
+/// Standard uniforms available to every effect as `kryon.*`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct EffectUniforms {
+    pub rect: [f32; 4],
+    pub color_a: [f32; 4],
+    pub color_b: [f32; 4],
+    pub resolution: [f32; 2],
+    pub time: f32,
+    pub _padding: f32,
+}
+
+impl EffectUniforms {
+    pub fn new(position: Vec2, size: Vec2, color_a: Vec4, color_b: Vec4, resolution: Vec2, time: f32) -> Self {
+        Self {
+            rect: [position.x, position.y, size.x, size.y],
+            color_a: color_a.to_array(),
+            color_b: color_b.to_array(),
+            resolution: resolution.to_array(),
+            time,
+            _padding: 0.0,
+        }
+    }
+}
+
+/// Returns the snippet source for a `shader_effect` value: inline WGSL is used
+/// as-is, anything else is treated as a resource path.
+pub fn load_effect_source(shader: &str) -> RenderResult<String> {
+    if shader.contains("fn effect") {
+        return Ok(shader.to_string());
+    }
+    std::fs::read_to_string(shader)
+        .map_err(|e| RenderError::ResourceNotFound(format!("shader_effect '{}': {}", shader, e)))
+}
+
+/// Wraps a user snippet with the standard prelude.
+pub fn compose_effect_shader(snippet: &str) -> String {
+    format!("{}\n// ---- shader_effect snippet ----\n{}\n", EFFECT_PRELUDE, snippet)
+}
+
+/// Parses and validates a snippet, returning the full WGSL module source.
+pub fn validate_effect_shader(snippet: &str) -> RenderResult<String> {
+    if !snippet.contains("fn effect") {
+        return Err(RenderError::RenderFailed(
+            "shader_effect snippet must define `fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32>`".to_string(),
+        ));
+    }
+
+    let source = compose_effect_shader(snippet);
+    let module = naga::front::wgsl::parse_str(&source)
+        .map_err(|e| RenderError::RenderFailed(format!("shader_effect parse error:\n{}", e.emit_to_string(&source))))?;
+
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+        .validate(&module)
+        .map_err(|e| RenderError::RenderFailed(format!("shader_effect validation error:\n{}", e.emit_to_string(&source))))?;
+
+    Ok(source)
+}
+
+/// Compiled effect pipelines keyed by the `shader_effect` property value.
+pub struct EffectPipelines {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    pipelines: HashMap<String, Option<wgpu::RenderPipeline>>,
+}
+
+impl EffectPipelines {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+            label: Some("effect_bind_group_layout"),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Effect Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        Self {
+            bind_group_layout,
+            pipeline_layout,
+            format,
+            pipelines: HashMap::new(),
+        }
+    }
+
+    /// Compiles the pipeline for `shader` on first use. Returns `false` if the
+    /// snippet failed to load or validate.
+    pub fn prepare(&mut self, device: &wgpu::Device, shader: &str) -> bool {
+        if !self.pipelines.contains_key(shader) {
+            let pipeline = match load_effect_source(shader).and_then(|snippet| validate_effect_shader(&snippet)) {
+                Ok(source) => Some(self.create_pipeline(device, &source)),
+                Err(e) => {
+                    eprintln!("[WGPU_EFFECT] Falling back to static color: {}", e);
+                    None
+                }
+            };
+            self.pipelines.insert(shader.to_string(), pipeline);
+        }
+        self.get(shader).is_some()
+    }
+
+    pub fn get(&self, shader: &str) -> Option<&wgpu::RenderPipeline> {
+        self.pipelines.get(shader).and_then(|p| p.as_ref())
+    }
+
+    pub fn create_bind_group(&self, device: &wgpu::Device, uniforms: &EffectUniforms) -> wgpu::BindGroup {
+        // Each effect gets its own small buffer: queue writes are applied before the
+        // encoder runs, so sharing one buffer would leave every draw with the last value.
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Effect Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[*uniforms]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+            label: Some("effect_bind_group"),
+        })
+    }
+
+    fn create_pipeline(&self, device: &wgpu::Device, source: &str) -> wgpu::RenderPipeline {
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Effect Shader"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Effect Pipeline"),
+            layout: Some(&self.pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: self.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRADIENT: &str = r#"
+fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+    let t = 0.5 + 0.5 * sin(kryon.time + uv.x * 6.28);
+    return mix(kryon.color_a, kryon.color_b, t);
+}
+"#;
+
+    #[test]
+    fn valid_snippet_compiles_with_prelude() {
+        let source = validate_effect_shader(GRADIENT).expect("gradient should validate");
+        assert!(source.contains("fn vs_main"));
+        assert!(source.contains("fn effect"));
+    }
+
+    #[test]
+    fn color_picker_snippets_validate() {
+        use kryon_render::color_picker::{ALPHA_EFFECT, HUE_EFFECT, SATURATION_VALUE_EFFECT};
+        for snippet in [SATURATION_VALUE_EFFECT, HUE_EFFECT, ALPHA_EFFECT] {
+            validate_effect_shader(snippet).expect("color picker snippets should validate");
+        }
+    }
+
+    #[test]
+    fn missing_entry_function_is_rejected() {
+        let err = validate_effect_shader("fn main() {}").unwrap_err();
+        assert!(err.to_string().contains("fn effect"));
+    }
+
+    #[test]
+    fn type_errors_are_reported() {
+        let bad = "fn effect(uv: vec2<f32>, frag_coord: vec2<f32>) -> vec4<f32> { return uv; }";
+        assert!(validate_effect_shader(bad).is_err());
+    }
+
+    #[test]
+    fn inline_snippet_is_not_read_from_disk() {
+        assert_eq!(load_effect_source(GRADIENT).unwrap(), GRADIENT);
+        assert!(load_effect_source("missing/effect.wgsl").is_err());
+    }
+
+    #[test]
+    fn uniforms_match_wgsl_layout() {
+        assert_eq!(std::mem::size_of::<EffectUniforms>(), 64);
+    }
+}
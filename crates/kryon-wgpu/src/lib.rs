@@ -1,16 +1,20 @@
 // crates/kryon-wgpu/src/lib.rs
 use kryon_render::{
-    Renderer, CommandRenderer, RenderCommand, RenderResult, RenderError
+    Renderer, CommandRenderer, RenderCommand, RenderResult, RenderError, BackendMemoryStats, Unsupported, BoxShadow, ImageFilter, WarmUp,
+    DisplayList
 };
+use kryon_render::warnings::{command_feature, is_generic_font};
 use kryon_layout::LayoutResult;
 use glam::{Vec2, Vec4, Mat4};
 use winit::window::Window;
-use kryon_core::{TransformData, TransformPropertyType, CSSUnit, CSSUnitValue};
+use kryon_core::{painted_area, AssetResolver, OutputRotation, ResolvedTransform, ResourceType, TransformData};
 
 pub mod shaders;
 pub mod vertex;
 pub mod text;
 pub mod resources;
+pub mod effects;
+pub mod retained;
 
 #[cfg(feature = "web")]
 pub mod web_renderer;
@@ -20,18 +24,45 @@ pub use web_renderer::WebWgpuRenderer;
 
 use vertex::*;
 use text::TextRenderer;
-use resources::ResourceManager;
+use resources::{ImageSamplers, ResourceManager};
+use effects::{EffectPipelines, EffectUniforms};
+use retained::{quad_indices, RetainedVertices};
+use wgpu::util::DeviceExt;
+
+/// Where frames are drawn: a window's surface, or a texture that is read
+/// back instead of presented
+enum RenderTarget {
+    Window {
+        surface: wgpu::Surface<'static>,
+        alpha_modes: Vec<wgpu::CompositeAlphaMode>,
+        /// For its scale factor
+        window: std::sync::Arc<Window>,
+    },
+    Offscreen {
+        texture: wgpu::Texture,
+    },
+}
 
 pub struct WgpuRenderer {
-    surface: wgpu::Surface<'static>,
+    target: RenderTarget,
     device: wgpu::Device,
     queue: wgpu::Queue,
+    /// Format and size of the target, for either kind
     config: wgpu::SurfaceConfiguration,
     size: Vec2,
+    /// Device pixels per logical pixel, the window's scale factor
+    scale: f32,
+    rotation: OutputRotation,
+    unsupported: Vec<Unsupported>,
     
     // Rendering pipeline
     rect_pipeline: wgpu::RenderPipeline,
     text_pipeline: wgpu::RenderPipeline,
+    image_pipeline: wgpu::RenderPipeline,
+    particle_pipeline: wgpu::RenderPipeline,
+    gradient_pipeline: wgpu::RenderPipeline,
+    image_bind_group_layout: wgpu::BindGroupLayout,
+    image_samplers: ImageSamplers,
     
     // Uniform buffers
     view_proj_buffer: wgpu::Buffer,
@@ -40,12 +71,23 @@ pub struct WgpuRenderer {
     // Text rendering
     text_renderer: TextRenderer,
     
+    // Custom shader effects
+    effect_pipelines: EffectPipelines,
+    start_time: std::time::Instant,
+    
     // Resource management
-    _resource_manager: ResourceManager,
+    resource_manager: ResourceManager,
     
     // Vertex buffers (reusable)
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
+    
+    // Rect and text vertices kept between frames, drawing from a display list
+    retained: bool,
+    retained_rects: RetainedVertices<RectVertex>,
+    retained_text: RetainedVertices<TextVertex>,
+    /// Indices for the retained rects' quads, and how many quads they cover
+    quad_index_buffer: Option<(wgpu::Buffer, usize)>,
 }
 
 pub struct WgpuRenderContext {
@@ -61,17 +103,42 @@ impl Renderer for WgpuRenderer {
         pollster::block_on(Self::new_async(surface.0, surface.1))
     }
     
-    fn begin_frame(&mut self, _clear_color: Vec4) -> RenderResult<Self::Context> {
-        let output = self.surface
-            .get_current_texture()
-            .map_err(|e| RenderError::RenderFailed(format!("Failed to get surface texture: {}", e)))?;
-        
-        let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+    fn begin_frame(&mut self, clear_color: Vec4) -> RenderResult<Self::Context> {
+        let view = match &self.target {
+            RenderTarget::Window { surface, .. } => {
+                let output = surface
+                    .get_current_texture()
+                    .map_err(|e| RenderError::RenderFailed(format!("Failed to get surface texture: {}", e)))?;
+                output.texture.create_view(&wgpu::TextureViewDescriptor::default())
+            }
+            RenderTarget::Offscreen { texture } => texture.create_view(&wgpu::TextureViewDescriptor::default()),
+        };
         
-        let encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
         
+        // The command passes all load what is already there, so start from the clear color
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Clear Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: clear_color.x as f64,
+                        g: clear_color.y as f64,
+                        b: clear_color.z as f64,
+                        a: clear_color.w as f64,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        
         Ok(WgpuRenderContext { encoder, view })
     }
     
@@ -96,7 +163,17 @@ impl Renderer for WgpuRenderer {
             self.size = new_size;
             self.config.width = new_size.x as u32;
             self.config.height = new_size.y as u32;
-            self.surface.configure(&self.device, &self.config);
+            match &mut self.target {
+                RenderTarget::Window { surface, window, .. } => {
+                    surface.configure(&self.device, &self.config);
+                    // A window moved to another display resizes with its scale
+                    let scale = window.scale_factor() as f32;
+                    if scale != self.scale {
+                        self.set_text_scale(scale);
+                    }
+                }
+                RenderTarget::Offscreen { texture } => *texture = create_offscreen_texture(&self.device, &self.config),
+            }
             
             // Update projection matrix
             self.update_view_projection()?;
@@ -107,6 +184,10 @@ impl Renderer for WgpuRenderer {
     fn viewport_size(&self) -> Vec2 {
         self.size
     }
+    
+    fn viewport_scale(&self) -> f32 {
+        self.scale
+    }
 }
 
 impl CommandRenderer for WgpuRenderer {
@@ -115,40 +196,72 @@ impl CommandRenderer for WgpuRenderer {
         context: &mut Self::Context,
         commands: &[RenderCommand],
     ) -> RenderResult<()> {
-        if commands.is_empty() {
-            return Ok(());
+        self.draw_commands(context, commands, None).map(|_| ())
+    }
+    
+    fn retains_display_list(&self) -> bool {
+        self.retained
+    }
+    
+    /// Rects and text are drawn from vertices kept for each item; the
+    /// other commands are drawn as `execute_commands` draws them
+    fn execute_display_list(
+        &mut self,
+        context: &mut Self::Context,
+        commands: &[RenderCommand],
+        list: &DisplayList,
+    ) -> RenderResult<usize> {
+        self.draw_commands(context, commands, Some(list))
+    }
+    
+    fn memory_stats(&self) -> BackendMemoryStats {
+        let (textures, texture_bytes) = self.resource_manager.memory_usage();
+        let (cached_glyphs, glyph_atlas_bytes) = self.text_renderer.memory_usage();
+        BackendMemoryStats {
+            textures,
+            texture_bytes,
+            glyph_atlases: 1,
+            glyph_atlas_bytes,
+            cached_glyphs,
         }
-        
-        // Separate commands by type for batching
-        let mut rect_commands = Vec::new();
-        let mut text_commands = Vec::new();
-        let mut image_commands = Vec::new();
-        
-        for command in commands {
-            match command {
-                RenderCommand::DrawRect { .. } => rect_commands.push(command),
-                RenderCommand::DrawText { .. } => text_commands.push(command),
-                RenderCommand::DrawImage { .. } => image_commands.push(command),
-                _ => {} // Handle other commands
+    }
+    
+    fn supports_shader_effects(&self) -> bool {
+        true
+    }
+    
+    fn set_output_rotation(&mut self, rotation: OutputRotation) -> bool {
+        self.rotation = rotation;
+        self.update_view_projection().is_ok()
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "wgpu"
+    }
+
+    fn take_unsupported(&mut self) -> Vec<Unsupported> {
+        std::mem::take(&mut self.unsupported)
+    }
+
+    fn take_decoded_images(&mut self) -> Vec<(String, Vec2)> {
+        self.resource_manager.poll();
+        self.resource_manager.take_decoded()
+    }
+
+    fn warm_up(&mut self, work: &WarmUp) -> bool {
+        match work {
+            // There's one font; every family is drawn with it
+            WarmUp::Glyphs { font_size, characters, .. } => {
+                if let Err(e) = self.text_renderer.prepare_text(&self.device, &self.queue, characters, *font_size as f32) {
+                    eprintln!("[WGPU_TEXT] Failed to rasterize glyphs at {}px: {}", font_size, e);
+                }
+            }
+            WarmUp::Image { source } => self.request_image(source),
+            WarmUp::ShaderEffect { shader } => {
+                self.effect_pipelines.prepare(&self.device, shader);
             }
         }
-        
-        // Render rectangles
-        if !rect_commands.is_empty() {
-            self.render_rects(context, &rect_commands)?;
-        }
-        
-        // Render text
-        if !text_commands.is_empty() {
-            self.render_text(context, &text_commands)?;
-        }
-        
-        // Render images
-        if !image_commands.is_empty() {
-            self.render_images(context, &image_commands)?;
-        }
-        
-        Ok(())
+        true
     }
 }
 
@@ -164,7 +277,7 @@ impl WgpuRenderer {
 
         // The surface is now created *inside* the renderer from the window handle.
         // This resolves the type mismatch and the original ownership panic.
-        let surface = instance.create_surface(window)
+        let surface = instance.create_surface(window.clone())
             .map_err(|e| RenderError::InitializationFailed(format!("Failed to create surface: {}", e)))?;
 
         // Debug: Enumerate all adapters first
@@ -254,6 +367,79 @@ impl WgpuRenderer {
 
         surface.configure(&device, &config);
 
+        let scale = window.scale_factor() as f32;
+        let target = RenderTarget::Window { surface, alpha_modes: surface_caps.alpha_modes, window };
+        let mut renderer = Self::with_target(device, queue, target, config, size)?;
+        renderer.set_text_scale(scale);
+        renderer.update_view_projection()?;
+        Ok(renderer)
+    }
+
+    /// Renders into a texture instead of a window, for screenshots in CI and
+    /// servers without a display. Read each frame back with `read_frame`.
+    pub fn new_offscreen(size: Vec2) -> RenderResult<Self> {
+        pollster::block_on(Self::new_offscreen_async(size))
+    }
+
+    async fn new_offscreen_async(size: Vec2) -> RenderResult<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        // Machines without a GPU still have a software adapter
+        let mut adapter = None;
+        for force_fallback_adapter in [false, true] {
+            adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::default(),
+                    compatible_surface: None,
+                    force_fallback_adapter,
+                })
+                .await;
+            if adapter.is_some() {
+                break;
+            }
+        }
+        let adapter = adapter
+            .ok_or_else(|| RenderError::InitializationFailed("No adapter found for offscreen rendering".to_string()))?;
+        let info = adapter.get_info();
+        eprintln!("[WGPU_OFFSCREEN] Using adapter: {} ({:?})", info.name, info.backend);
+
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::default(),
+                },
+                None,
+            )
+            .await
+            .map_err(|e| RenderError::InitializationFailed(format!("Device request failed: {}", e)))?;
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            width: (size.x as u32).max(1),
+            height: (size.y as u32).max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let texture = create_offscreen_texture(&device, &config);
+
+        Self::with_target(device, queue, RenderTarget::Offscreen { texture }, config, size)
+    }
+
+    fn with_target(
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        target: RenderTarget,
+        config: wgpu::SurfaceConfiguration,
+        size: Vec2,
+    ) -> RenderResult<Self> {
         // Create uniform buffer for view-projection matrix
         let view_proj_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("View Projection Buffer"),
@@ -262,19 +448,11 @@ impl WgpuRenderer {
             mapped_at_creation: false,
         });
 
-        let uniform_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-            label: Some("uniform_bind_group_layout"),
-        });
+        let uniform_bind_group_layout = shaders::create_bind_group_layout(
+            &device,
+            "uniform_bind_group_layout",
+            kryon_shaders::VIEW_PROJECTION_GROUP,
+        );
 
         let view_proj_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: &uniform_bind_group_layout,
@@ -285,96 +463,55 @@ impl WgpuRenderer {
             label: Some("view_proj_bind_group"),
         });
 
-        // Create shaders
-        let rect_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Rectangle Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/rect.wgsl").into()),
-        });
-
-        let text_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Text Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/text.wgsl").into()),
-        });
-
         // Create text rendering pipeline
         let text_renderer = TextRenderer::new(&device, &queue)
             .map_err(|e| RenderError::InitializationFailed(format!("Text renderer creation failed: {}", e)))?;
 
-        // The text pipeline needs the bind group layout from the text atlas
-        let text_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Text Pipeline Layout"),
-            bind_group_layouts: &[&uniform_bind_group_layout, text_renderer.bind_group_layout()],
-            push_constant_ranges: &[],
-        });
+        // Pipelines come from the descriptions shared with the browser
+        // WebGPU backend. The text pipeline needs the bind group layout
+        // from the text atlas.
+        let text_pipeline = shaders::create_pipeline(
+            &device,
+            &kryon_shaders::TEXT_PIPELINE,
+            &[&uniform_bind_group_layout, text_renderer.bind_group_layout()],
+            config.format,
+        );
 
-        let text_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Text Pipeline"),
-            layout: Some(&text_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &text_shader,
-                entry_point: "vs_main",
-                buffers: &[TextVertex::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &text_shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None, // Text can have different winding orders
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        let rect_pipeline = shaders::create_pipeline(
+            &device,
+            &kryon_shaders::RECT_PIPELINE,
+            &[&uniform_bind_group_layout],
+            config.format,
+        );
 
-        // Create rectangle rendering pipeline
-        let rect_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Rectangle Pipeline Layout"),
-            bind_group_layouts: &[&uniform_bind_group_layout],
-            push_constant_ranges: &[],
-        });
+        // Images share the smooth and pixelated samplers; each texture gets a bind group per sampler
+        let image_bind_group_layout = shaders::create_bind_group_layout(
+            &device,
+            "image_bind_group_layout",
+            kryon_shaders::TEXTURE_GROUP,
+        );
+        // Every image goes through the filtered pipeline; an unfiltered one passes through unchanged
+        let image_pipeline = shaders::create_pipeline(
+            &device,
+            &kryon_shaders::FILTERED_IMAGE_PIPELINE,
+            &[&uniform_bind_group_layout, &image_bind_group_layout],
+            config.format,
+        );
+        let image_samplers = ImageSamplers::new(&device);
 
-        let rect_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Rectangle Pipeline"),
-            layout: Some(&rect_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &rect_shader,
-                entry_point: "vs_main",
-                buffers: &[RectVertex::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &rect_shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        // Instanced particle pipeline (shares the view-projection layout)
+        let particle_pipeline = shaders::create_pipeline(
+            &device,
+            &kryon_shaders::PARTICLE_PIPELINE,
+            &[&uniform_bind_group_layout],
+            config.format,
+        );
+        let gradient_pipeline = shaders::create_pipeline(
+            &device,
+            &kryon_shaders::GRADIENT_PIPELINE,
+            &[&uniform_bind_group_layout],
+            config.format,
+        );
         
         // Create vertex and index buffers
         let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -391,20 +528,38 @@ impl WgpuRenderer {
             mapped_at_creation: false,
         });
 
+        let effect_pipelines = EffectPipelines::new(&device, config.format);
+        // Frames read back must show every image, so offscreen ones decode inline
+        let decode_on_threads = matches!(target, RenderTarget::Window { .. });
+
         let mut renderer = Self {
-            surface,
+            target,
             device,
             queue,
             config,
             size,
+            scale: 1.0,
+            rotation: OutputRotation::None,
+            unsupported: Vec::new(),
             rect_pipeline,
             text_pipeline,
+            image_pipeline,
+            particle_pipeline,
+            gradient_pipeline,
+            image_bind_group_layout,
+            image_samplers,
             view_proj_buffer,
             view_proj_bind_group,
             text_renderer,
-            _resource_manager: ResourceManager::new(),
+            effect_pipelines,
+            start_time: std::time::Instant::now(),
+            resource_manager: ResourceManager::new(decode_on_threads),
             vertex_buffer,
             index_buffer,
+            retained: true,
+            retained_rects: RetainedVertices::new("Retained Rect Vertex Buffer", wgpu::BufferUsages::VERTEX),
+            retained_text: RetainedVertices::new("Retained Text Vertex Buffer", wgpu::BufferUsages::VERTEX),
+            quad_index_buffer: None,
         };
 
         renderer.update_view_projection()?;
@@ -412,6 +567,113 @@ impl WgpuRenderer {
         Ok(renderer)
     }
     
+    /// Composites the window with what is behind it, for overlay windows.
+    /// Needs a window created with transparency and a surface that supports a
+    /// non-opaque alpha mode; returns whether one was found.
+    /// Offscreen frames always keep their alpha channel.
+    pub fn set_transparent(&mut self, transparent: bool) -> bool {
+        let RenderTarget::Window { surface, alpha_modes, .. } = &self.target else {
+            return true;
+        };
+        let alpha_mode = if transparent {
+            let Some(mode) = [wgpu::CompositeAlphaMode::PreMultiplied, wgpu::CompositeAlphaMode::PostMultiplied]
+                .into_iter()
+                .find(|mode| alpha_modes.contains(mode))
+            else {
+                return false;
+            };
+            mode
+        } else {
+            alpha_modes[0]
+        };
+        self.config.alpha_mode = alpha_mode;
+        surface.configure(&self.device, &self.config);
+        true
+    }
+    
+    /// Draws offscreen frames at `scale` device pixels per logical pixel,
+    /// as a window on a HiDPI display would be. The frame keeps its size in
+    /// device pixels; a window's scale follows its display instead.
+    pub fn set_viewport_scale(&mut self, scale: f32) -> RenderResult<()> {
+        if matches!(self.target, RenderTarget::Offscreen { .. }) && scale.is_finite() && scale > 0.0 {
+            self.set_text_scale(scale);
+            self.update_view_projection()?;
+        }
+        Ok(())
+    }
+    
+    /// Text kept from the last scale would be drawn from the wrong glyphs
+    fn set_text_scale(&mut self, scale: f32) {
+        self.scale = scale;
+        self.text_renderer.set_scale(scale);
+        self.retained_text.clear();
+    }
+    
+    /// Whether frames are drawn from a display list, keeping each
+    /// element's rect and text vertices on the GPU while its commands stay
+    /// the same. On by default.
+    pub fn set_retained(&mut self, retained: bool) {
+        self.retained = retained;
+        if !retained {
+            self.retained_rects.clear();
+            self.retained_text.clear();
+        }
+    }
+    
+    /// Copies the last frame drawn offscreen back from the GPU. `None` for
+    /// a renderer drawing to a window.
+    pub fn read_frame(&self) -> RenderResult<Option<image::RgbaImage>> {
+        let RenderTarget::Offscreen { texture } = &self.target else {
+            return Ok(None);
+        };
+        let (width, height) = (self.config.width, self.config.height);
+        // Buffer rows are padded to the copy alignment
+        let row_bytes = width * 4;
+        let padded_row_bytes = row_bytes.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Frame Readback Buffer"),
+            size: padded_row_bytes as u64 * height as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Frame Readback Encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+        
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .map_err(|e| RenderError::RenderFailed(format!("Frame readback was dropped: {}", e)))?
+            .map_err(|e| RenderError::RenderFailed(format!("Failed to map frame: {}", e)))?;
+        
+        let pixels = slice
+            .get_mapped_range()
+            .chunks(padded_row_bytes as usize)
+            .flat_map(|row| row[..row_bytes as usize].iter().copied())
+            .collect();
+        buffer.unmap();
+        Ok(image::RgbaImage::from_raw(width, height, pixels))
+    }
+    
     fn update_view_projection(&mut self) -> RenderResult<()> {
         let projection = Mat4::orthographic_rh(
             0.0,
@@ -421,6 +683,16 @@ impl WgpuRenderer {
             -1.0,
             1.0,
         );
+        // Logical coordinates are rotated onto the surface, then scaled to
+        // its device pixels
+        let rotation = self.rotation.to_physical(self.rotation.logical_size(self.size / self.scale));
+        let rotation = Mat4::from_cols(
+            rotation.matrix2.x_axis.extend(0.0).extend(0.0),
+            rotation.matrix2.y_axis.extend(0.0).extend(0.0),
+            Vec4::Z,
+            rotation.translation.extend(0.0).extend(1.0),
+        );
+        let projection = projection * Mat4::from_scale(Vec2::splat(self.scale).extend(1.0)) * rotation;
         
         let uniform = ViewProjectionUniform {
             view_proj: projection.to_cols_array_2d(),
@@ -435,6 +707,104 @@ impl WgpuRenderer {
         Ok(())
     }
     
+    /// Draws `commands` batched by kind, with rects and text from the
+    /// vertices kept for `list`'s items if there is a list. Returns how
+    /// many of its items had to be rebuilt.
+    fn draw_commands(
+        &mut self,
+        context: &mut WgpuRenderContext,
+        commands: &[RenderCommand],
+        list: Option<&DisplayList>,
+    ) -> RenderResult<usize> {
+        if commands.is_empty() {
+            return Ok(0);
+        }
+        
+        // Separate commands by type for batching
+        let mut rect_commands = Vec::new();
+        let mut text_commands = Vec::new();
+        let mut image_commands = Vec::new();
+        let mut background_commands = Vec::new();
+        let mut gradient_commands = Vec::new();
+        let mut effect_commands = Vec::new();
+        let mut particle_commands = Vec::new();
+        
+        for (index, command) in commands.iter().enumerate() {
+            match command {
+                RenderCommand::DrawRect { .. } => rect_commands.push(command),
+                RenderCommand::DrawGradientRect { .. } => gradient_commands.push(command),
+                RenderCommand::DrawShaderEffect { .. } => effect_commands.push(command),
+                RenderCommand::DrawParticles { .. } => particle_commands.push(command),
+                RenderCommand::DrawText { font_family, .. } => {
+                    if font_family.as_deref().is_some_and(|family| !is_generic_font(family)) {
+                        self.unsupported.push(Unsupported::new(index, "custom fonts"));
+                    }
+                    text_commands.push(command);
+                }
+                RenderCommand::DrawImage { source, .. } => {
+                    self.request_image(source);
+                    image_commands.push(command);
+                }
+                RenderCommand::DrawTiledImage { source, .. } => {
+                    self.request_image(source);
+                    background_commands.push(command);
+                }
+                _ => {
+                    if let Some(feature) = command_feature(command) {
+                        self.unsupported.push(Unsupported::new(index, feature));
+                    }
+                }
+            }
+        }
+        
+        // Items rebuilt from a display list
+        let mut rebuilt = vec![false; list.map_or(0, DisplayList::len)];
+        
+        // Render rectangles
+        match list {
+            Some(list) => self.render_retained_rects(context, commands, list, &mut rebuilt)?,
+            None if !rect_commands.is_empty() => self.render_rects(context, &rect_commands)?,
+            None => {}
+        }
+        
+        // Render gradients over their background rects
+        if !gradient_commands.is_empty() {
+            self.render_gradients(context, &gradient_commands)?;
+        }
+        
+        // Render background images over their background rects
+        self.resource_manager.poll();
+        self.resource_manager.upload_pending(&self.device, &self.queue, &self.image_bind_group_layout, &self.image_samplers);
+        if !background_commands.is_empty() {
+            self.render_images(context, &background_commands)?;
+        }
+        
+        // Render shader effects over their background rects
+        if !effect_commands.is_empty() {
+            self.render_effects(context, &effect_commands)?;
+        }
+        
+        // Render particles
+        if !particle_commands.is_empty() {
+            self.render_particles(context, &particle_commands)?;
+        }
+        
+        // Render text
+        match list {
+            Some(list) => self.render_retained_text(context, commands, list, &mut rebuilt)?,
+            None if !text_commands.is_empty() => self.render_text(context, &text_commands)?,
+            None => {}
+        }
+        
+        // Render images
+        if !image_commands.is_empty() {
+            self.render_images(context, &image_commands)?;
+        }
+        
+        Ok(rebuilt.into_iter().filter(|&item| item).count())
+    }
+    
+    
     fn render_rects(
         &mut self,
         context: &mut WgpuRenderContext,
@@ -445,39 +815,8 @@ impl WgpuRenderer {
         let mut index_offset = 0u16;
         
         for command in commands {
-            if let RenderCommand::DrawRect {
-                position,
-                size,
-                color,
-                border_radius,
-                border_width,
-                border_color,
-                transform,
-                shadow: _,
-                z_index: _,
-            } = command {
-                // Generate vertices for rounded rectangle
-                let rect_vertices = generate_rounded_rect_vertices(
-                    *position,
-                    *size,
-                    *color,
-                    *border_radius,
-                    *border_width,
-                    *border_color,
-                );
-                
-                // Apply transform if present
-                let transformed_vertices = if let Some(transform_data) = transform {
-                    apply_transform_to_vertices(rect_vertices, transform_data)
-                } else {
-                    rect_vertices
-                };
-                
-                // Add vertices and indices
-                for vertex in transformed_vertices {
-                    vertices.push(vertex);
-                }
-                
+            let quads = rect_vertices(command);
+            for _ in 0..quads.len() / 4 {
                 // Generate indices for two triangles (quad)
                 indices.extend_from_slice(&[
                     index_offset,
@@ -489,6 +828,7 @@ impl WgpuRenderer {
                 ]);
                 index_offset += 4;
             }
+            vertices.extend(quads);
         }
         
         if vertices.is_empty() {
@@ -533,150 +873,553 @@ impl WgpuRenderer {
         Ok(())
     }
     
+    /// Draws `DrawGradientRect` commands in one draw; the shader shades
+    /// each quad from the gradient its vertices carry
+    fn render_gradients(
+        &mut self,
+        context: &mut WgpuRenderContext,
+        commands: &[&RenderCommand],
+    ) -> RenderResult<()> {
+        let mut vertices = Vec::new();
+        for command in commands {
+            let RenderCommand::DrawGradientRect { position, size, gradient, border_radius, transform, .. } = command else { continue };
+            let packed = kryon_render::ShaderGradient::new(gradient, *size, *border_radius);
+            let matrix = transform.as_ref().map(|transform_data| ResolvedTransform::resolve(transform_data, *size).to_mat4_at(*position));
+            let corner = |local: Vec2| {
+                let point = Vec4::new(position.x + local.x, position.y + local.y, 0.0, 1.0);
+                let point = matrix.map_or(point, |matrix| matrix * point);
+                GradientVertex {
+                    position: [point.x, point.y, 0.0, point.w],
+                    shape: [local.x, local.y, size.x, size.y],
+                    params: packed.params,
+                    offsets: packed.offsets,
+                    colors: packed.colors,
+                }
+            };
+            let [top_left, top_right, bottom_right, bottom_left] = [Vec2::ZERO, Vec2::new(size.x, 0.0), *size, Vec2::new(0.0, size.y)].map(corner);
+            vertices.extend([top_left, top_right, bottom_right, bottom_right, bottom_left, top_left]);
+        }
+        
+        if vertices.is_empty() {
+            return Ok(());
+        }
+        
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Gradient Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        
+        let mut render_pass = context.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Gradient Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &context.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        
+        render_pass.set_pipeline(&self.gradient_pipeline);
+        render_pass.set_bind_group(0, &self.view_proj_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.draw(0..vertices.len() as u32, 0..1);
+        
+        Ok(())
+    }
+    
     fn render_text(
         &mut self,
         context: &mut WgpuRenderContext,
         commands: &[&RenderCommand],
     ) -> RenderResult<()> {
+        // All text shares the glyph atlas, so it's drawn in one pass
+        let mut vertices = Vec::new();
         for command in commands {
-            if let RenderCommand::DrawText {
+            vertices.extend(text_vertices(&mut self.text_renderer, &self.device, &self.queue, command));
+        }
+        
+        if vertices.is_empty() {
+            return Ok(());
+        }
+        
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Text Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        
+        let mut render_pass = context.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Text Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &context.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        
+        render_pass.set_pipeline(&self.text_pipeline);
+        render_pass.set_bind_group(0, &self.view_proj_bind_group, &[]);
+        render_pass.set_bind_group(1, self.text_renderer.bind_group(), &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.draw(0..vertices.len() as u32, 0..1);
+        
+        Ok(())
+    }
+    
+    /// Draws the display list's rects from the vertices kept for its
+    /// items, building those of items that changed
+    fn render_retained_rects(
+        &mut self,
+        context: &mut WgpuRenderContext,
+        commands: &[RenderCommand],
+        list: &DisplayList,
+        rebuilt: &mut [bool],
+    ) -> RenderResult<()> {
+        let items = items_drawing(commands, list, |command| matches!(command, RenderCommand::DrawRect { .. }));
+        let keys: Vec<_> = items.iter().map(|&index| list.items()[index].key()).collect();
+        self.retained_rects.update(&self.device, &self.queue, &keys, |item| {
+            rebuilt[items[item]] = true;
+            commands[list.items()[items[item]].commands.clone()].iter().flat_map(rect_vertices).collect()
+        });
+        let Some(vertex_buffer) = self.retained_rects.buffer() else {
+            return Ok(());
+        };
+        
+        // Every quad's indices are the same, so they're only uploaded when there are more quads
+        let quads = self.retained_rects.vertex_count() / 4;
+        if self.quad_index_buffer.as_ref().is_none_or(|(_, covered)| *covered < quads) {
+            let covered = quads.next_power_of_two();
+            let buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Retained Rect Index Buffer"),
+                contents: bytemuck::cast_slice(&quad_indices(covered)),
+                usage: wgpu::BufferUsages::INDEX,
+            });
+            self.quad_index_buffer = Some((buffer, covered));
+        }
+        let Some((index_buffer, _)) = &self.quad_index_buffer else {
+            return Ok(());
+        };
+        
+        let mut render_pass = begin_load_pass(context, "Retained Rectangle Render Pass");
+        render_pass.set_pipeline(&self.rect_pipeline);
+        render_pass.set_bind_group(0, &self.view_proj_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..(quads * 6) as u32, 0, 0..1);
+        
+        Ok(())
+    }
+    
+    /// Draws the display list's text from the vertices kept for its items,
+    /// laying out the text of items that changed
+    fn render_retained_text(
+        &mut self,
+        context: &mut WgpuRenderContext,
+        commands: &[RenderCommand],
+        list: &DisplayList,
+        rebuilt: &mut [bool],
+    ) -> RenderResult<()> {
+        let items = items_drawing(commands, list, |command| matches!(command, RenderCommand::DrawText { .. }));
+        let keys: Vec<_> = items.iter().map(|&index| list.items()[index].key()).collect();
+        let text_renderer = &mut self.text_renderer;
+        let (device, queue) = (&self.device, &self.queue);
+        self.retained_text.update(device, queue, &keys, |item| {
+            rebuilt[items[item]] = true;
+            commands[list.items()[items[item]].commands.clone()].iter()
+                .flat_map(|command| text_vertices(text_renderer, device, queue, command))
+                .collect()
+        });
+        let Some(vertex_buffer) = self.retained_text.buffer() else {
+            return Ok(());
+        };
+        
+        let mut render_pass = begin_load_pass(context, "Retained Text Render Pass");
+        render_pass.set_pipeline(&self.text_pipeline);
+        render_pass.set_bind_group(0, &self.view_proj_bind_group, &[]);
+        render_pass.set_bind_group(1, self.text_renderer.bind_group(), &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.draw(0..self.retained_text.vertex_count() as u32, 0..1);
+        
+        Ok(())
+    }
+    
+    fn render_effects(
+        &mut self,
+        context: &mut WgpuRenderContext,
+        commands: &[&RenderCommand],
+    ) -> RenderResult<()> {
+        let time = self.start_time.elapsed().as_secs_f32();
+        
+        for command in commands {
+            if let RenderCommand::DrawShaderEffect {
                 position,
-                text,
-                font_size,
-                color,
-                alignment,
-                max_width,
-                max_height: _,
-                transform,
-                font_family: _, // WGPU doesn't support custom fonts yet
+                size,
+                shader,
+                primary_color,
+                secondary_color,
+                z_index: _,
             } = command {
-                // Apply transform to text position if present
-                let final_position = if let Some(transform_data) = transform {
-                    let (scale, rotation, translation) = extract_transform_values(transform_data);
-                    let transform_matrix = create_transform_matrix(scale, rotation, translation);
-                    apply_transform_to_position(*position, &transform_matrix)
-                } else {
-                    *position
-                };
+                if !self.effect_pipelines.prepare(&self.device, shader) {
+                    continue; // Invalid snippet - background rect already drawn as fallback
+                }
                 
-                // For now, use the basic render_text method
-                // TODO: Implement proper transform support in text renderer
-                self.text_renderer.render_text(
-                    &mut context.encoder,
-                    &context.view,
-                    &self.text_pipeline,
-                    &self.view_proj_bind_group,
-                    text,
-                    final_position,
-                    *font_size,
-                    *color,
-                    *alignment,
-                    *max_width,
-                ).map_err(|e| RenderError::RenderFailed(format!("Text rendering failed: {}", e)))?;
+                // Effects draw in the surface's device pixels, unrotated
+                let uniforms = EffectUniforms::new(*position * self.scale, *size * self.scale, *primary_color, *secondary_color, self.size, time);
+                let bind_group = self.effect_pipelines.create_bind_group(&self.device, &uniforms);
+                let Some(pipeline) = self.effect_pipelines.get(shader) else { continue };
+                
+                let mut render_pass = context.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Effect Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &context.view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                });
+                
+                render_pass.set_pipeline(pipeline);
+                render_pass.set_bind_group(0, &bind_group, &[]);
+                render_pass.draw(0..6, 0..1);
             }
         }
         Ok(())
     }
     
-    fn render_images(
+    fn render_particles(
         &mut self,
-        _context: &mut WgpuRenderContext,
-        _commands: &[&RenderCommand],
+        context: &mut WgpuRenderContext,
+        commands: &[&RenderCommand],
     ) -> RenderResult<()> {
-        // TODO: Implement image rendering with transform support
-        // When implementing, handle transform field in RenderCommand::DrawImage
+        // All emitters share one instance buffer and a single instanced draw.
+        // TODO: Draw `sprite` textures once the image pipeline exists
+        let instances: Vec<ParticleInstanceRaw> = commands.iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawParticles { particles, .. } => Some(particles),
+                _ => None,
+            })
+            .flatten()
+            .map(|p| ParticleInstanceRaw {
+                center: p.position.to_array(),
+                size: p.size,
+                rotation: p.rotation,
+                color: p.color.to_array(),
+            })
+            .collect();
+        
+        if instances.is_empty() {
+            return Ok(());
+        }
+        
+        let instance_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle Instance Buffer"),
+            contents: bytemuck::cast_slice(&instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        
+        let mut render_pass = context.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Particle Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &context.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        
+        render_pass.set_pipeline(&self.particle_pipeline);
+        render_pass.set_bind_group(0, &self.view_proj_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, instance_buffer.slice(..));
+        render_pass.draw(0..6, 0..instances.len() as u32);
+        
         Ok(())
     }
-}
-
-/// Extract transform values from TransformData
-fn extract_transform_values(transform: &TransformData) -> (Vec2, f32, Vec2) {
-    let mut scale = Vec2::new(1.0, 1.0);
-    let mut rotation = 0.0f32;
-    let mut translation = Vec2::new(0.0, 0.0);
-    
-    for property in &transform.properties {
-        match property.property_type {
-            TransformPropertyType::Scale => {
-                let value = css_unit_to_pixels(&property.value);
-                scale = Vec2::new(value, value);
-            }
-            TransformPropertyType::ScaleX => {
-                scale.x = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::ScaleY => {
-                scale.y = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::TranslateX => {
-                translation.x = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::TranslateY => {
-                translation.y = css_unit_to_pixels(&property.value);
-            }
-            TransformPropertyType::Rotate => {
-                rotation = css_unit_to_radians(&property.value);
-            }
-            _ => {
-                eprintln!("[WGPU_TRANSFORM] Unsupported transform property: {:?}", property.property_type);
+    
+    /// Draws `DrawImage` commands, and `DrawTiledImage` ones as a single
+    /// quad over the area their tiles cover, with texture coordinates past
+    /// 0..1 wrapped by the repeating sampler
+    fn render_images(
+        &mut self,
+        context: &mut WgpuRenderContext,
+        commands: &[&RenderCommand],
+    ) -> RenderResult<()> {
+        let mut vertices = Vec::new();
+        let mut indices: Vec<u16> = Vec::new();
+        let mut draws = Vec::new();
+        
+        for command in commands {
+            let (source, image_rendering, repeat, quad) = match command {
+                RenderCommand::DrawImage { position, size, source, opacity, transform, image_rendering, filter } => {
+                    let area = (*position, *position + *size);
+                    let quad = image_vertices(*position, *size, area, (Vec2::ZERO, Vec2::ONE), *opacity, filter, transform.as_ref());
+                    (source, *image_rendering, false, quad)
+                }
+                RenderCommand::DrawTiledImage {
+                    position, size, source, tile_offset, tile_size, repeat, opacity, image_rendering, transform, ..
+                } => {
+                    // The image's size isn't known yet
+                    if tile_size.x <= 0.0 || tile_size.y <= 0.0 {
+                        continue;
+                    }
+                    let Some((area_min, area_max)) = painted_area(*size, *tile_offset, *tile_size, *repeat) else {
+                        continue;
+                    };
+                    let uv = ((area_min - *tile_offset) / *tile_size, (area_max - *tile_offset) / *tile_size);
+                    let area = (*position + area_min, *position + area_max);
+                    let quad = image_vertices(*position, *size, area, uv, *opacity, &ImageFilter::default(), transform.as_ref());
+                    (source, *image_rendering, true, quad)
+                }
+                _ => continue,
+            };
+            // Still decoding, or failed to
+            if self.resource_manager.get_bind_group(source, image_rendering, repeat).is_none() {
+                continue;
             }
+            let Some(quad) = quad else {
+                continue;
+            };
+            
+            let first_vertex = vertices.len() as u16;
+            let first_index = indices.len() as u32;
+            vertices.extend_from_slice(&quad);
+            indices.extend([0, 1, 2, 2, 3, 0].map(|corner| first_vertex + corner));
+            draws.push((source.as_str(), image_rendering, repeat, first_index..first_index + 6));
+        }
+        
+        if draws.is_empty() {
+            return Ok(());
         }
+        
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Image Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Image Index Buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        
+        let mut render_pass = context.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Image Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &context.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        
+        render_pass.set_pipeline(&self.image_pipeline);
+        render_pass.set_bind_group(0, &self.view_proj_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        for (source, image_rendering, repeat, index_range) in draws {
+            let Some(bind_group) = self.resource_manager.get_bind_group(source, image_rendering, repeat) else { continue };
+            render_pass.set_bind_group(1, bind_group, &[]);
+            render_pass.draw_indexed(index_range, 0, 0..1);
+        }
+        
+        Ok(())
     }
-    
-    (scale, rotation, translation)
-}
 
-/// Convert CSS unit value to pixels (simplified)
-fn css_unit_to_pixels(unit_value: &CSSUnitValue) -> f32 {
-    match unit_value.unit {
-        CSSUnit::Pixels => unit_value.value as f32,
-        CSSUnit::Number => unit_value.value as f32,
-        CSSUnit::Em => unit_value.value as f32 * 16.0, // Assume 16px base
-        CSSUnit::Rem => unit_value.value as f32 * 16.0, // Assume 16px base
-        CSSUnit::Percentage => unit_value.value as f32 / 100.0,
-        _ => {
-            eprintln!("[WGPU_TRANSFORM] Unsupported CSS unit for size: {:?}", unit_value.unit);
-            unit_value.value as f32
+    /// Starts loading an image the first time it's drawn. Its size goes to
+    /// layout once it's decoded.
+    fn request_image(&mut self, source: &str) {
+        if !self.resource_manager.is_requested(source) {
+            let path = AssetResolver::from_args().resolve(kryon_render::icon::source_file(source), &ResourceType::Image);
+            self.resource_manager.request(source, path);
         }
     }
 }
 
-/// Convert CSS unit value to radians for rotation
-fn css_unit_to_radians(unit_value: &CSSUnitValue) -> f32 {
-    match unit_value.unit {
-        CSSUnit::Degrees => unit_value.value as f32 * std::f32::consts::PI / 180.0,
-        CSSUnit::Radians => unit_value.value as f32,
-        CSSUnit::Turns => unit_value.value as f32 * 2.0 * std::f32::consts::PI,
-        _ => {
-            eprintln!("[WGPU_TRANSFORM] Unsupported CSS unit for rotation: {:?}", unit_value.unit);
-            unit_value.value as f32
+/// A textured quad covering `area` (top-left and bottom-right corners) of
+/// an element at `position` of `size`, showing `uv` of the texture; corners
+/// clockwise from the top-left, filtered by `filter`. The element's
+/// transform is applied to the corners; `None` if one ends up behind the
+/// viewer.
+/// Indices of the display list items with a command `draws` accepts
+fn items_drawing(commands: &[RenderCommand], list: &DisplayList, draws: impl Fn(&RenderCommand) -> bool) -> Vec<usize> {
+    list.items().iter()
+        .enumerate()
+        .filter(|(_, item)| commands[item.commands.clone()].iter().any(&draws))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// A render pass over what the frame has drawn so far
+fn begin_load_pass<'a>(context: &'a mut WgpuRenderContext, label: &'static str) -> wgpu::RenderPass<'a> {
+    context.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &context.view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        occlusion_query_set: None,
+        timestamp_writes: None,
+    })
+}
+
+/// The quads of a `DrawRect`, four vertices each: its shadow's layers, if
+/// it has a shadow, then the rect
+fn rect_vertices(command: &RenderCommand) -> Vec<RectVertex> {
+    let RenderCommand::DrawRect { position, size, color, border_radius, border_width, border_color, transform, shadow, .. } = command else {
+        return Vec::new();
+    };
+    // Blurred shadows are drawn as stacked translucent layers under the rect
+    let shadow_layers = shadow.as_deref().and_then(BoxShadow::parse)
+        .map(|box_shadow| box_shadow.layers(*position, *size, *border_radius))
+        .unwrap_or_default();
+    let quads = shadow_layers.iter()
+        .map(|layer| generate_rounded_rect_vertices(layer.position, layer.size, layer.color, layer.border_radius, 0.0, Vec4::ZERO))
+        .chain(std::iter::once(generate_rounded_rect_vertices(
+            *position,
+            *size,
+            *color,
+            *border_radius,
+            *border_width,
+            *border_color,
+        )));
+    
+    let mut vertices = Vec::new();
+    for rect_vertices in quads {
+        // Apply transform if present; shadows turn with their rect
+        match transform {
+            Some(transform_data) => vertices.extend(apply_transform_to_vertices(rect_vertices, *position, *size, transform_data)),
+            None => vertices.extend(rect_vertices),
         }
     }
+    vertices
 }
 
-/// Create a transformation matrix for WGPU
-fn create_transform_matrix(scale: Vec2, rotation: f32, translation: Vec2) -> Mat4 {
-    let scale_matrix = Mat4::from_scale(scale.extend(1.0));
-    let rotation_matrix = Mat4::from_rotation_z(rotation);
-    let translation_matrix = Mat4::from_translation(translation.extend(0.0));
+/// The glyph quads of a `DrawText`, rasterizing the glyphs it needs
+fn text_vertices(text_renderer: &mut TextRenderer, device: &wgpu::Device, queue: &wgpu::Queue, command: &RenderCommand) -> Vec<TextVertex> {
+    let RenderCommand::DrawText {
+        position,
+        text,
+        font_size,
+        color,
+        alignment,
+        max_width,
+        max_height: _,
+        transform,
+        font_family: _, // WGPU doesn't support custom fonts yet
+        runs,
+        z_index: _,
+    } = command else {
+        return Vec::new();
+    };
+    // Apply transform to text position if present
+    // TODO: Rotate and scale the glyphs too
+    let final_position = if let Some(transform_data) = transform {
+        ResolvedTransform::resolve(transform_data, Vec2::ZERO)
+            .transform_point(*position, *position)
+            .unwrap_or(*position)
+    } else {
+        *position
+    };
     
-    translation_matrix * rotation_matrix * scale_matrix
+    if let Err(e) = text_renderer.prepare_text(device, queue, text, *font_size) {
+        eprintln!("[WGPU_TEXT] Failed to rasterize glyphs at {}px: {}", font_size, e);
+    }
+    text_renderer.generate_text_vertices(
+        text,
+        runs,
+        final_position,
+        *font_size,
+        *color,
+        *alignment,
+        *max_width,
+    )
+}
+
+fn image_vertices(
+    position: Vec2,
+    size: Vec2,
+    area: (Vec2, Vec2),
+    uv: (Vec2, Vec2),
+    opacity: f32,
+    filter: &ImageFilter,
+    transform: Option<&TransformData>,
+) -> Option<[ImageVertex; 4]> {
+    let corners = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+    let resolved = transform.map(|transform_data| ResolvedTransform::resolve(transform_data, size));
+    let color = filter.tint * Vec4::new(1.0, 1.0, 1.0, opacity);
+    let vertex = ImageVertex { position: [0.0; 2], tex_coords: [0.0; 2], color: color.to_array(), adjust: filter.to_array() };
+    let mut vertices = [vertex; 4];
+    for (vertex, corner) in vertices.iter_mut().zip(corners) {
+        let point = area.0 + corner * (area.1 - area.0);
+        let point = match &resolved {
+            Some(resolved) => resolved.transform_point(point, position)?,
+            None => point,
+        };
+        vertex.position = point.to_array();
+        vertex.tex_coords = (uv.0 + corner * (uv.1 - uv.0)).to_array();
+    }
+    Some(vertices)
 }
 
-/// Apply transform to position using transformation matrix
-fn apply_transform_to_position(position: Vec2, transform_matrix: &Mat4) -> Vec2 {
-    let transformed = transform_matrix.transform_point3(position.extend(0.0));
-    Vec2::new(transformed.x, transformed.y)
+fn create_offscreen_texture(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Offscreen Target"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: config.usage,
+        view_formats: &[],
+    })
 }
 
-/// Apply transform to vertices using transformation matrix
-fn apply_transform_to_vertices(vertices: Vec<RectVertex>, transform_data: &TransformData) -> Vec<RectVertex> {
-    let (scale, rotation, translation) = extract_transform_values(transform_data);
-    let transform_matrix = create_transform_matrix(scale, rotation, translation);
+/// Apply transform to a rect's vertices, about the transform origin
+///
+/// 3D transforms keep the homogeneous `w` from the perspective matrix and
+/// flatten `z`, so the element is drawn projected onto the screen plane
+/// without being depth-clipped by the orthographic projection.
+fn apply_transform_to_vertices(vertices: Vec<RectVertex>, position: Vec2, size: Vec2, transform_data: &TransformData) -> Vec<RectVertex> {
+    let transform_matrix = ResolvedTransform::resolve(transform_data, size).to_mat4_at(position);
     
     vertices.into_iter().map(|mut vertex| {
-        let transformed = transform_matrix.transform_point3(Vec2::new(vertex.position[0], vertex.position[1]).extend(0.0));
-        vertex.position[0] = transformed.x;
-        vertex.position[1] = transformed.y;
+        let transformed = transform_matrix * Vec4::from(vertex.position);
+        vertex.position = [transformed.x, transformed.y, 0.0, transformed.w];
         vertex
     }).collect()
 }
\ No newline at end of file
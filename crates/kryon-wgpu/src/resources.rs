@@ -1,101 +1,229 @@
 // crates/kryon-wgpu/src/resources.rs
-use image::GenericImageView;
-use std::collections::HashMap;
+//! Image textures, keyed by the `source` of the `DrawImage` commands.
+//!
+//! Decoding a large PNG or JPEG takes long enough to drop frames, so a
+//! window renderer decodes each image on its own thread the first time it
+//! is drawn and uploads it once it's ready; until then the image is simply
+//! not drawn. An offscreen renderer decodes inline instead, so the first
+//! frame read back already shows every image.
+//!
+//! Images of `MIPMAP_MIN_SIZE` or more are uploaded with a full mip chain,
+//! downsampled on the decoding thread, so photos drawn smaller than they
+//! are don't shimmer. Each texture has one bind group per sampler: the
+//! trilinear one for smooth images and the nearest one for pixelated ones,
+//! each both clamping and wrapping, the latter for tiled backgrounds.
+//!
+//! Icon sources (`icons/home.svg@24px`) are rasterized from their SVG at
+//! that size rather than decoded.
+
+use glam::Vec2;
+use image::imageops::FilterType;
+use image::RgbaImage;
+use kryon_render::{icon, ImageRendering, MIPMAP_MIN_SIZE};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// A decoded image's mip levels, largest first, or why it couldn't be decoded
+type Decoded = (String, Result<Vec<RgbaImage>, String>);
+
+/// The samplers images are drawn with
+pub struct ImageSamplers {
+    /// Bilinear within a mip level and linear between them
+    smooth: wgpu::Sampler,
+    /// Nearest texel of the nearest mip level
+    pixelated: wgpu::Sampler,
+    /// The same, wrapping texture coordinates outside 0..1 so they repeat
+    smooth_repeat: wgpu::Sampler,
+    pixelated_repeat: wgpu::Sampler,
+}
+
+impl ImageSamplers {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let sampler = |label: &str, filter: wgpu::FilterMode, address_mode: wgpu::AddressMode| device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(label),
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            address_mode_w: address_mode,
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter: filter,
+            ..Default::default()
+        });
+        Self {
+            smooth: sampler("image_sampler_smooth", wgpu::FilterMode::Linear, wgpu::AddressMode::ClampToEdge),
+            pixelated: sampler("image_sampler_pixelated", wgpu::FilterMode::Nearest, wgpu::AddressMode::ClampToEdge),
+            smooth_repeat: sampler("image_sampler_smooth_repeat", wgpu::FilterMode::Linear, wgpu::AddressMode::Repeat),
+            pixelated_repeat: sampler("image_sampler_pixelated_repeat", wgpu::FilterMode::Nearest, wgpu::AddressMode::Repeat),
+        }
+    }
+}
+
+/// A texture's bind groups, one per sampler
+struct ImageBindGroups {
+    smooth: wgpu::BindGroup,
+    pixelated: wgpu::BindGroup,
+    smooth_repeat: wgpu::BindGroup,
+    pixelated_repeat: wgpu::BindGroup,
+}
 
 pub struct ResourceManager {
     textures: HashMap<String, wgpu::Texture>,
-    bind_groups: HashMap<String, wgpu::BindGroup>,
+    bind_groups: HashMap<String, ImageBindGroups>,
+    /// Sources being decoded, decoded or failed; each is only tried once
+    requested: HashSet<String>,
+    /// Decoded and waiting for the next frame to upload them
+    pending_uploads: Vec<(String, Vec<RgbaImage>)>,
+    /// Natural sizes of the images decoded since the last `take_decoded`
+    decoded: Vec<(String, Vec2)>,
+    decode_on_threads: bool,
+    sender: Sender<Decoded>,
+    receiver: Receiver<Decoded>,
 }
 
 impl ResourceManager {
-    pub fn new() -> Self {
+    /// `decode_on_threads` decodes images in the background instead of on
+    /// the first frame that draws them
+    pub fn new(decode_on_threads: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
         Self {
             textures: HashMap::new(),
             bind_groups: HashMap::new(),
+            requested: HashSet::new(),
+            pending_uploads: Vec::new(),
+            decoded: Vec::new(),
+            decode_on_threads,
+            sender,
+            receiver,
         }
     }
-    
+
+    /// Whether `source` is being decoded, or was decoded or tried
+    pub fn is_requested(&self, source: &str) -> bool {
+        self.requested.contains(source)
+    }
+
+    /// Starts decoding the image at `path` for `source`, unless that was
+    /// already done or tried. `None` for a source that didn't resolve to a
+    /// file; it isn't looked for again.
+    pub fn request(&mut self, source: &str, path: Option<PathBuf>) {
+        if !self.requested.insert(source.to_string()) {
+            return;
+        }
+        let Some(path) = path else {
+            eprintln!("[WGPU_IMAGE] Image not found: {}", source);
+            return;
+        };
+        let source = source.to_string();
+        let decode = move || {
+            let image = match icon::parse_icon_source(&source) {
+                Some((_, size)) => icon::load_icon(&path, size)
+                    .map(|icon| RgbaImage::from_raw(icon.width, icon.height, icon.pixels).expect("icon bitmaps are RGBA rows"))
+                    .map_err(|e| e.to_string()),
+                None => image::open(&path).map(|image| image.to_rgba8()).map_err(|e| e.to_string()),
+            };
+            (source, image.map(mip_chain).map_err(|e| format!("{}: {}", path.display(), e)))
+        };
+        if self.decode_on_threads {
+            let sender = self.sender.clone();
+            std::thread::spawn(move || {
+                // The renderer may be gone by the time the image is decoded
+                let _ = sender.send(decode());
+            });
+        } else {
+            let decoded = decode();
+            self.finish_decode(decoded);
+        }
+    }
+
+    /// Collects the images decoded in the background since the last call
+    pub fn poll(&mut self) {
+        while let Ok(decoded) = self.receiver.try_recv() {
+            self.finish_decode(decoded);
+        }
+    }
+
+    fn finish_decode(&mut self, (source, levels): Decoded) {
+        match levels {
+            Ok(levels) => {
+                let (width, height) = levels[0].dimensions();
+                eprintln!("[WGPU_IMAGE] Decoded {} ({}x{}, {} mip levels)", source, width, height, levels.len());
+                self.decoded.push((source.clone(), Vec2::new(width as f32, height as f32)));
+                self.pending_uploads.push((source, levels));
+            }
+            Err(e) => eprintln!("[WGPU_IMAGE] Failed to decode image {}", e),
+        }
+    }
+
+    /// Natural sizes of the images decoded since the last call
+    pub fn take_decoded(&mut self) -> Vec<(String, Vec2)> {
+        std::mem::take(&mut self.decoded)
+    }
+
+    /// Creates textures for the decoded images that don't have one yet
+    pub fn upload_pending(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &wgpu::BindGroupLayout,
+        samplers: &ImageSamplers,
+    ) {
+        for (source, levels) in std::mem::take(&mut self.pending_uploads) {
+            self.load_texture(device, queue, layout, samplers, source, &levels);
+        }
+    }
+
+    /// Uploads an image as a texture with `levels` as its mip chain,
+    /// starting from the full-size image
     pub fn load_texture(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
+        layout: &wgpu::BindGroupLayout,
+        samplers: &ImageSamplers,
         name: String,
-        image_data: &[u8],
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let image = image::load_from_memory(image_data)?;
-        let rgba = image.to_rgba8();
-        let dimensions = image.dimensions();
-        
+        levels: &[RgbaImage],
+    ) {
+        let dimensions = levels[0].dimensions();
+        let size = wgpu::Extent3d {
+            width: dimensions.0,
+            height: dimensions.1,
+            depth_or_array_layers: 1,
+        };
+
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some(&name),
-            size: wgpu::Extent3d {
-                width: dimensions.0,
-                height: dimensions.1,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
+            size,
+            mip_level_count: levels.len() as u32,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8UnormSrgb,
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
-        
-        queue.write_texture(
-            wgpu::ImageCopyTexture {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            &rgba,
-            wgpu::ImageDataLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * dimensions.0),
-                rows_per_image: Some(dimensions.1),
-            },
-            wgpu::Extent3d {
-                width: dimensions.0,
-                height: dimensions.1,
-                depth_or_array_layers: 1,
-            },
-        );
-        
-        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-        
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                    },
-                    count: None,
+
+        for (mip_level, level) in levels.iter().enumerate() {
+            let (width, height) = level.dimensions();
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: mip_level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
                 },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
+                level,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * width),
+                    rows_per_image: Some(height),
                 },
-            ],
-            label: Some("texture_bind_group_layout"),
-        });
-        
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bind_group_layout,
+                wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            );
+        }
+
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = |sampler: &wgpu::Sampler| device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
@@ -103,29 +231,130 @@ impl ResourceManager {
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
+                    resource: wgpu::BindingResource::Sampler(sampler),
                 },
             ],
-            label: Some("diffuse_bind_group"),
+            label: Some("image_bind_group"),
         });
-        
+        let bind_groups = ImageBindGroups {
+            smooth: bind_group(&samplers.smooth),
+            pixelated: bind_group(&samplers.pixelated),
+            smooth_repeat: bind_group(&samplers.smooth_repeat),
+            pixelated_repeat: bind_group(&samplers.pixelated_repeat),
+        };
+
         self.textures.insert(name.clone(), texture);
-        self.bind_groups.insert(name, bind_group);
-        
-        Ok(())
+        self.bind_groups.insert(name, bind_groups);
     }
-    
+
     pub fn get_texture(&self, name: &str) -> Option<&wgpu::Texture> {
         self.textures.get(name)
     }
-    
-    pub fn get_bind_group(&self, name: &str) -> Option<&wgpu::BindGroup> {
-        self.bind_groups.get(name)
+
+    /// The bind group that draws `name` filtered as `image_rendering` asks,
+    /// wrapping texture coordinates if it's to `repeat`
+    pub fn get_bind_group(&self, name: &str, image_rendering: ImageRendering, repeat: bool) -> Option<&wgpu::BindGroup> {
+        let bind_groups = self.bind_groups.get(name)?;
+        Some(match (image_rendering, repeat) {
+            (ImageRendering::Pixelated, false) => &bind_groups.pixelated,
+            (ImageRendering::Pixelated, true) => &bind_groups.pixelated_repeat,
+            (ImageRendering::Auto | ImageRendering::Smooth, false) => &bind_groups.smooth,
+            (ImageRendering::Auto | ImageRendering::Smooth, true) => &bind_groups.smooth_repeat,
+        })
+    }
+
+    /// Texture count and their size in bytes (RGBA8, mip levels included).
+    pub fn memory_usage(&self) -> (usize, usize) {
+        let bytes = self.textures.values()
+            .flat_map(|texture| {
+                (0..texture.mip_level_count()).map(move |level| {
+                    let width = (texture.width() >> level).max(1) as usize;
+                    let height = (texture.height() >> level).max(1) as usize;
+                    width * height * 4
+                })
+            })
+            .sum();
+        (self.textures.len(), bytes)
+    }
+}
+
+/// `image` followed by its mip levels, each half the size of the one before
+/// down to 1x1, if it's at least `MIPMAP_MIN_SIZE` on its larger side;
+/// otherwise just `image`
+pub fn mip_chain(image: RgbaImage) -> Vec<RgbaImage> {
+    let mut levels = vec![image];
+    let (width, height) = levels[0].dimensions();
+    if width.max(height) < MIPMAP_MIN_SIZE {
+        return levels;
     }
+    while let Some(previous) = levels.last().filter(|level| level.width() > 1 || level.height() > 1) {
+        let (width, height) = ((previous.width() / 2).max(1), (previous.height() / 2).max(1));
+        let next = image::imageops::resize(previous, width, height, FilterType::Triangle);
+        levels.push(next);
+    }
+    levels
 }
 
-impl Default for ResourceManager {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_png(name: &str, width: u32, height: u32) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("kryon-wgpu-{}-{}.png", std::process::id(), name));
+        RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255])).save(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn decodes_each_source_once_and_reports_its_size() {
+        let path = write_png("inline", 3, 2);
+        let mut resources = ResourceManager::new(false);
+        resources.request("logo.png", Some(path.clone()));
+        resources.request("logo.png", Some(path.clone()));
+        assert_eq!(resources.take_decoded(), vec![("logo.png".to_string(), Vec2::new(3.0, 2.0))]);
+        assert_eq!(resources.pending_uploads.len(), 1);
+        assert!(resources.take_decoded().is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn icon_sources_are_rasterized_at_their_size() {
+        let path = std::env::temp_dir().join(format!("kryon-wgpu-{}-icon.svg", std::process::id()));
+        std::fs::write(&path, r#"<svg viewBox="0 0 24 24"><circle cx="12" cy="12" r="10"/></svg>"#).unwrap();
+        let mut resources = ResourceManager::new(false);
+        resources.request("icon.svg@32px", Some(path.clone()));
+        assert_eq!(resources.take_decoded(), vec![("icon.svg@32px".to_string(), Vec2::new(32.0, 32.0))]);
+        let (_, levels) = &resources.pending_uploads[0];
+        assert_eq!(levels[0].get_pixel(16, 16).0, [255, 255, 255, 255]);
+        let _ = std::fs::remove_file(path);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn background_decodes_arrive_on_poll() {
+        let path = write_png("threaded", 4, 4);
+        let mut resources = ResourceManager::new(true);
+        resources.request("photo.png", Some(path.clone()));
+        resources.request("missing.png", Some(PathBuf::from("/nonexistent/missing.png")));
+        resources.request("unresolved.png", None);
+
+        let started = std::time::Instant::now();
+        while resources.decoded.is_empty() && started.elapsed() < std::time::Duration::from_secs(10) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            resources.poll();
+        }
+        assert_eq!(resources.take_decoded(), vec![("photo.png".to_string(), Vec2::new(4.0, 4.0))]);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn only_large_images_get_a_mip_chain_down_to_one_pixel() {
+        let small = mip_chain(RgbaImage::new(MIPMAP_MIN_SIZE - 1, 16));
+        assert_eq!(small.len(), 1);
+
+        let large = mip_chain(RgbaImage::new(300, 100));
+        let sizes: Vec<(u32, u32)> = large.iter().map(|level| level.dimensions()).collect();
+        assert_eq!(sizes, vec![
+            (300, 100), (150, 50), (75, 25), (37, 12), (18, 6), (9, 3), (4, 1), (2, 1), (1, 1),
+        ]);
+    }
+}
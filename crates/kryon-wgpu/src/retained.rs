@@ -0,0 +1,158 @@
+// crates/kryon-wgpu/src/retained.rs
+//! Vertices kept on the GPU between frames, for drawing from a display
+//! list (`kryon_render::display_list`).
+//!
+//! Each display list item's vertices are built once and kept, keyed by the
+//! item, while the item stays in the frame. The buffer holds the items'
+//! vertices back to back in draw order; when a frame comes, only the items
+//! that aren't already at their place in the buffer are uploaded. An item
+//! whose commands changed has a new key and is rebuilt; if its vertex count
+//! is the same, the items after it stay where they are and aren't uploaded
+//! again.
+
+use bytemuck::Pod;
+use kryon_render::DisplayKey;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Vertices the buffer is first made for
+const INITIAL_CAPACITY: usize = 4096;
+
+pub struct RetainedVertices<V: Pod> {
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+    /// Vertices of the items drawn last frame
+    built: HashMap<DisplayKey, Vec<V>>,
+    /// The items in the buffer, in order, with their vertex counts
+    uploaded: Vec<(DisplayKey, usize)>,
+    buffer: Option<wgpu::Buffer>,
+    /// Vertices the buffer has room for
+    capacity: usize,
+}
+
+impl<V: Pod> RetainedVertices<V> {
+    pub fn new(label: &'static str, usage: wgpu::BufferUsages) -> Self {
+        Self {
+            label,
+            usage: usage | wgpu::BufferUsages::COPY_DST,
+            built: HashMap::new(),
+            uploaded: Vec::new(),
+            buffer: None,
+            capacity: 0,
+        }
+    }
+
+    /// Fills the buffer with the vertices of `items`, in order, calling
+    /// `build` for those of items the last frame didn't have. Items with no
+    /// vertices can be left out. Returns how many items were built.
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        items: &[DisplayKey],
+        mut build: impl FnMut(usize) -> Vec<V>,
+    ) -> usize {
+        let mut built = HashMap::with_capacity(items.len());
+        let mut rebuilt = 0;
+        for (index, key) in items.iter().enumerate() {
+            let vertices = self.built.remove(key).or_else(|| built.remove(key)).unwrap_or_else(|| {
+                rebuilt += 1;
+                build(index)
+            });
+            built.insert(*key, vertices);
+        }
+        let current: Vec<(DisplayKey, usize)> = items.iter().map(|key| (*key, built[key].len())).collect();
+        let total: usize = current.iter().map(|(_, count)| count).sum();
+
+        // A buffer too small is replaced, and everything goes in the new one
+        if total > self.capacity || self.buffer.is_none() {
+            self.capacity = total.max(INITIAL_CAPACITY).next_power_of_two();
+            self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(self.label),
+                size: (self.capacity * std::mem::size_of::<V>()) as u64,
+                usage: self.usage,
+                mapped_at_creation: false,
+            }));
+            self.uploaded.clear();
+        }
+        let buffer = self.buffer.as_ref().expect("made above");
+        for range in stale_ranges(&self.uploaded, &current) {
+            let mut vertices = Vec::new();
+            for (key, _) in &current[range.clone()] {
+                vertices.extend_from_slice(&built[key]);
+            }
+            let offset: usize = current[..range.start].iter().map(|(_, count)| count).sum();
+            queue.write_buffer(buffer, (offset * std::mem::size_of::<V>()) as u64, bytemuck::cast_slice(&vertices));
+        }
+
+        self.built = built;
+        self.uploaded = current;
+        rebuilt
+    }
+
+    /// The buffer, if it holds any vertices
+    pub fn buffer(&self) -> Option<&wgpu::Buffer> {
+        self.buffer.as_ref().filter(|_| self.vertex_count() > 0)
+    }
+
+    /// Vertices in the buffer
+    pub fn vertex_count(&self) -> usize {
+        self.uploaded.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Forgets everything built, e.g. when what vertices are built from changed
+    pub fn clear(&mut self) {
+        self.built.clear();
+        self.uploaded.clear();
+    }
+}
+
+/// Runs of items in `current` that aren't in the buffer where `uploaded`
+/// put them: items that changed, and every item after one whose vertex
+/// count changed
+fn stale_ranges(uploaded: &[(DisplayKey, usize)], current: &[(DisplayKey, usize)]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut shifted = false;
+    for (index, item) in current.iter().enumerate() {
+        let previous = uploaded.get(index);
+        let in_place = !shifted && previous == Some(item);
+        shifted |= previous.map(|(_, count)| count) != Some(&item.1);
+        if in_place {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end = index + 1,
+            _ => ranges.push(index..index + 1),
+        }
+    }
+    ranges
+}
+
+/// Indices of `quads` quads, each four vertices drawn as two triangles
+pub fn quad_indices(quads: usize) -> Vec<u32> {
+    (0..quads as u32)
+        .flat_map(|quad| [0, 1, 2, 2, 3, 0].map(|corner| quad * 4 + corner))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_items_out_of_place_are_uploaded() {
+        let item = |element: u32, hash: u64, count: usize| ((Some(element), hash), count);
+        let last = [item(1, 10, 4), item(2, 20, 8), item(3, 30, 4)];
+
+        assert_eq!(stale_ranges(&last, &last), Vec::<Range<usize>>::new());
+        assert_eq!(stale_ranges(&[], &last), vec![0..3]);
+        // Same size: just the item that changed
+        assert_eq!(stale_ranges(&last, &[item(1, 10, 4), item(2, 21, 8), item(3, 30, 4)]), vec![1..2]);
+        // Grown: it and everything after it
+        assert_eq!(stale_ranges(&last, &[item(1, 11, 8), item(2, 20, 8), item(3, 30, 4)]), vec![0..3]);
+        // Removed from the end: nothing to upload
+        assert_eq!(stale_ranges(&last, &last[..2]), Vec::<Range<usize>>::new());
+
+        assert_eq!(quad_indices(2), vec![0, 1, 2, 2, 3, 0, 4, 5, 6, 6, 7, 4]);
+    }
+}
@@ -1,85 +1,104 @@
 // crates/kryon-wgpu/src/shaders.rs
+//! Builds wgpu pipelines from the shared [`kryon_shaders`] descriptions.
 
-pub const RECT_SHADER: &str = include_str!("shaders/rect.wgsl");
-pub const TEXT_SHADER: &str = include_str!("shaders/text.wgsl");
+use kryon_shaders::{Binding, PipelineDescription, StepMode, VertexAttribute, VertexFormat, VertexLayout};
 
-pub fn create_rect_pipeline(
-    device: &wgpu::Device,
-    surface_format: wgpu::TextureFormat,
-) -> wgpu::RenderPipeline {
-    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-        label: Some("Rect Shader"),
-        source: wgpu::ShaderSource::Wgsl(RECT_SHADER.into()),
-    });
+pub use kryon_shaders::{RECT as RECT_SHADER, TEXT as TEXT_SHADER};
 
-    let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: Some("Rect Pipeline Layout"),
-        bind_group_layouts: &[],
-        push_constant_ranges: &[],
-    });
+const fn vertex_attribute(attribute: &VertexAttribute) -> wgpu::VertexAttribute {
+    wgpu::VertexAttribute {
+        format: match attribute.format {
+            VertexFormat::Float32 => wgpu::VertexFormat::Float32,
+            VertexFormat::Float32x2 => wgpu::VertexFormat::Float32x2,
+            VertexFormat::Float32x4 => wgpu::VertexFormat::Float32x4,
+        },
+        offset: attribute.offset,
+        shader_location: attribute.location,
+    }
+}
 
-    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: Some("Rect Pipeline"),
-        layout: Some(&render_pipeline_layout),
-        vertex: wgpu::VertexState {
-            module: &shader,
-            entry_point: "vs_main",
-            buffers: &[crate::vertex::RectVertex::desc()],
+/// Converts a shared layout's attributes, for use in a `static`.
+pub const fn vertex_attributes<const N: usize>(layout: &VertexLayout) -> [wgpu::VertexAttribute; N] {
+    assert!(layout.attributes.len() == N);
+    let mut attributes = [wgpu::VertexAttribute { format: wgpu::VertexFormat::Float32, offset: 0, shader_location: 0 }; N];
+    let mut i = 0;
+    while i < N {
+        attributes[i] = vertex_attribute(&layout.attributes[i]);
+        i += 1;
+    }
+    attributes
+}
+
+pub fn vertex_buffer_layout<'a>(layout: &VertexLayout, attributes: &'a [wgpu::VertexAttribute]) -> wgpu::VertexBufferLayout<'a> {
+    wgpu::VertexBufferLayout {
+        array_stride: layout.stride,
+        step_mode: match layout.step_mode {
+            StepMode::Vertex => wgpu::VertexStepMode::Vertex,
+            StepMode::Instance => wgpu::VertexStepMode::Instance,
         },
-        fragment: Some(wgpu::FragmentState {
-            module: &shader,
-            entry_point: "fs_main",
-            targets: &[Some(wgpu::ColorTargetState {
-                format: surface_format,
-                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                write_mask: wgpu::ColorWrites::ALL,
-            })],
-        }),
-        primitive: wgpu::PrimitiveState {
-            topology: wgpu::PrimitiveTopology::TriangleList,
-            strip_index_format: None,
-            front_face: wgpu::FrontFace::Ccw,
-            cull_mode: Some(wgpu::Face::Back),
-            polygon_mode: wgpu::PolygonMode::Fill,
-            unclipped_depth: false,
-            conservative: false,
+        attributes,
+    }
+}
+
+pub fn create_bind_group_layout(device: &wgpu::Device, label: &str, group: &[Binding]) -> wgpu::BindGroupLayout {
+    let entries: Vec<_> = group.iter().enumerate().map(|(index, binding)| wgpu::BindGroupLayoutEntry {
+        binding: index as u32,
+        visibility: match binding {
+            Binding::Uniform => wgpu::ShaderStages::VERTEX,
+            Binding::Texture | Binding::Sampler => wgpu::ShaderStages::FRAGMENT,
         },
-        depth_stencil: None,
-        multisample: wgpu::MultisampleState {
-            count: 1,
-            mask: !0,
-            alpha_to_coverage_enabled: false,
+        ty: match binding {
+            Binding::Uniform => wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            Binding::Texture => wgpu::BindingType::Texture {
+                multisampled: false,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            },
+            Binding::Sampler => wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
         },
-        multiview: None,
-    })
+        count: None,
+    }).collect();
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor { label: Some(label), entries: &entries })
 }
 
-pub fn create_text_pipeline(
+/// Creates the pipeline described by `description`.
+///
+/// `bind_group_layouts` must match `description.bind_groups`; layouts are
+/// owned by the caller so pipelines can share them.
+pub fn create_pipeline(
     device: &wgpu::Device,
+    description: &PipelineDescription,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
     surface_format: wgpu::TextureFormat,
 ) -> wgpu::RenderPipeline {
+    debug_assert_eq!(bind_group_layouts.len(), description.bind_groups.len());
+    let attributes: Vec<_> = description.vertex.attributes.iter().map(vertex_attribute).collect();
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-        label: Some("Text Shader"),
-        source: wgpu::ShaderSource::Wgsl(TEXT_SHADER.into()),
+        label: Some(description.label),
+        source: wgpu::ShaderSource::Wgsl(description.source.into()),
     });
 
-    let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: Some("Text Pipeline Layout"),
-        bind_group_layouts: &[],
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(description.label),
+        bind_group_layouts,
         push_constant_ranges: &[],
     });
 
     device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: Some("Text Pipeline"),
-        layout: Some(&render_pipeline_layout),
+        label: Some(description.label),
+        layout: Some(&layout),
         vertex: wgpu::VertexState {
             module: &shader,
-            entry_point: "vs_main",
-            buffers: &[crate::vertex::TextVertex::desc()],
+            entry_point: description.vertex_entry,
+            buffers: &[vertex_buffer_layout(&description.vertex, &attributes)],
         },
         fragment: Some(wgpu::FragmentState {
             module: &shader,
-            entry_point: "fs_main",
+            entry_point: description.fragment_entry,
             targets: &[Some(wgpu::ColorTargetState {
                 format: surface_format,
                 blend: Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -90,17 +109,13 @@ pub fn create_text_pipeline(
             topology: wgpu::PrimitiveTopology::TriangleList,
             strip_index_format: None,
             front_face: wgpu::FrontFace::Ccw,
-            cull_mode: Some(wgpu::Face::Back),
+            cull_mode: description.cull_back_faces.then_some(wgpu::Face::Back),
             polygon_mode: wgpu::PolygonMode::Fill,
             unclipped_depth: false,
             conservative: false,
         },
         depth_stencil: None,
-        multisample: wgpu::MultisampleState {
-            count: 1,
-            mask: !0,
-            alpha_to_coverage_enabled: false,
-        },
+        multisample: wgpu::MultisampleState::default(),
         multiview: None,
     })
-}
\ No newline at end of file
+}
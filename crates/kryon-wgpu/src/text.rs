@@ -7,11 +7,23 @@ pub struct TextRenderer {
     font: Font,
     atlas: TextureAtlas,
     cache: HashMap<TextCacheKey, CachedGlyph>,
+    /// Device pixels per logical pixel: glyphs are rasterized this much
+    /// larger and drawn at their logical size
+    scale: f32,
 }
 
+/// How far a bold run's glyphs are drawn a second time to the right, as a
+/// share of the font size; there's one font and no bold face
+const BOLD_OFFSET: f32 = 1.0 / 24.0;
+/// How far an italic run's glyph tops lean right, per pixel of height
+const ITALIC_SLANT: f32 = 0.2;
+/// Side of the opaque block at the atlas origin that lines sample
+const SOLID_BLOCK: u32 = 4;
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct TextCacheKey {
     character: char,
+    /// The size rasterized at, in device pixels
     font_size: u32,
 }
 
@@ -21,6 +33,17 @@ struct CachedGlyph {
     metrics: fontdue::Metrics,
 }
 
+/// A quad of the text atlas: glyph bitmaps, or the solid block for lines
+#[derive(Debug, Clone, Copy)]
+struct Quad {
+    position: Vec2,
+    size: Vec2,
+    texture_coords: [f32; 4],
+    color: Vec4,
+    /// How far the top edge is shifted right of the bottom one
+    slant: f32,
+}
+
 pub struct TextureAtlas {
     texture: wgpu::Texture,
     _texture_view: wgpu::TextureView,
@@ -46,9 +69,21 @@ impl TextRenderer {
             font,
             atlas,
             cache: HashMap::new(),
+            scale: 1.0,
         })
     }
     
+    /// Rasterizes glyphs for a surface with `scale` device pixels per
+    /// logical pixel. Glyphs already cached at another scale stay cached.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+    
+    /// Font size in device pixels, the glyph cache's key
+    fn raster_size(&self, font_size: f32) -> f32 {
+        (font_size * self.scale).max(1.0)
+    }
+    
     pub fn prepare_text(
         &mut self,
         device: &wgpu::Device,
@@ -56,7 +91,8 @@ impl TextRenderer {
         text: &str,
         font_size: f32,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let font_size_px = font_size as u32;
+        let raster_size = self.raster_size(font_size);
+        let font_size_px = raster_size as u32;
         
         for character in text.chars() {
             let key = TextCacheKey {
@@ -65,129 +101,160 @@ impl TextRenderer {
             };
             
             if !self.cache.contains_key(&key) {
-                let (metrics, bitmap) = self.font.rasterize(character, font_size);
+                let (metrics, bitmap) = self.font.rasterize(character, raster_size);
                 
-                if !bitmap.is_empty() {
-                    let texture_coords = self.atlas.add_glyph(device, queue, &bitmap, metrics.width, metrics.height)?;
-                    
-                    self.cache.insert(key, CachedGlyph {
-                        texture_coords,
-                        metrics,
-                    });
-                }
+                // Blank glyphs, such as spaces, only advance the pen
+                let texture_coords = if bitmap.is_empty() {
+                    [0.0; 4]
+                } else {
+                    self.atlas.add_glyph(device, queue, &bitmap, metrics.width, metrics.height)?
+                };
+                self.cache.insert(key, CachedGlyph {
+                    texture_coords,
+                    metrics,
+                });
             }
         }
         
         Ok(())
     }
     
+    /// Vertices for `text` with its top-left at `position`, styled by
+    /// `runs`. Lines break at newlines and are aligned within `max_width`.
+    /// Glyphs must have been prepared at `font_size`; they were rasterized
+    /// at the device size, so their metrics are scaled back here.
+    #[allow(clippy::too_many_arguments)]
     pub fn generate_text_vertices(
         &self,
         text: &str,
+        runs: &[kryon_core::TextRun],
         position: Vec2,
         font_size: f32,
         color: Vec4,
+        alignment: kryon_core::TextAlignment,
+        max_width: Option<f32>,
     ) -> Vec<crate::vertex::TextVertex> {
-        let mut vertices = Vec::new();
-        let mut cursor_x = position.x;
-        let font_size_px = font_size as u32;
+        let font_size_px = self.raster_size(font_size) as u32;
+        let scale = self.scale;
+        let glyph = |character: char| self.cache.get(&TextCacheKey { character, font_size: font_size_px });
+        let (ascent, line_height) = match self.font.horizontal_line_metrics(font_size) {
+            Some(line) => (line.ascent, line.new_line_size),
+            None => (font_size, font_size * 1.2),
+        };
         
-        for character in text.chars() {
-            let key = TextCacheKey {
-                character,
-                font_size: font_size_px,
+        // Split the styled pieces into lines
+        let mut lines: Vec<Vec<(&str, Option<&kryon_core::TextRun>)>> = vec![Vec::new()];
+        for (piece, run) in kryon_core::text_segments(text, runs) {
+            for (i, part) in piece.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(Vec::new());
+                }
+                lines.last_mut().expect("starts with a line").push((part, run));
+            }
+        }
+        
+        let mut quads = Vec::new();
+        for (line_index, line) in lines.iter().enumerate() {
+            let width: f32 = line.iter()
+                .flat_map(|(part, _)| part.chars())
+                .filter_map(|character| glyph(character).map(|cached| cached.metrics.advance_width / scale))
+                .sum();
+            let free = max_width.map_or(0.0, |max_width| (max_width - width).max(0.0));
+            let mut pen_x = position.x + match alignment {
+                kryon_core::TextAlignment::Center => free / 2.0,
+                kryon_core::TextAlignment::End => free,
+                kryon_core::TextAlignment::Start | kryon_core::TextAlignment::Justify => 0.0,
             };
+            let baseline = position.y + ascent + line_index as f32 * line_height;
             
-            if let Some(cached_glyph) = self.cache.get(&key) {
-                let glyph_pos = Vec2::new(
-                    cursor_x + cached_glyph.metrics.xmin as f32,
-                    position.y + cached_glyph.metrics.ymin as f32,
-                );
-                
-                let glyph_size = Vec2::new(
-                    cached_glyph.metrics.width as f32,
-                    cached_glyph.metrics.height as f32,
-                );
+            for &(part, run) in line {
+                let run_color = run.and_then(|run| run.color).unwrap_or(color);
+                let bold = run.is_some_and(kryon_core::TextRun::is_bold);
+                let slant = if run.is_some_and(kryon_core::TextRun::is_italic) { ITALIC_SLANT } else { 0.0 };
+                let start_x = pen_x;
                 
-                // Generate quad for this glyph
-                let tex_coords = cached_glyph.texture_coords;
-                
-                vertices.extend_from_slice(&[
-                    crate::vertex::TextVertex {
-                        position: [glyph_pos.x, glyph_pos.y],
-                        tex_coords: [tex_coords[0], tex_coords[1]],
-                        color: color.into(),
-                    },
-                    crate::vertex::TextVertex {
-                        position: [glyph_pos.x + glyph_size.x, glyph_pos.y],
-                        tex_coords: [tex_coords[0] + tex_coords[2], tex_coords[1]],
-                        color: color.into(),
-                    },
-                    crate::vertex::TextVertex {
-                        position: [glyph_pos.x + glyph_size.x, glyph_pos.y + glyph_size.y],
-                        tex_coords: [tex_coords[0] + tex_coords[2], tex_coords[1] + tex_coords[3]],
-                        color: color.into(),
-                    },
-                    crate::vertex::TextVertex {
-                        position: [glyph_pos.x, glyph_pos.y],
-                        tex_coords: [tex_coords[0], tex_coords[1]],
-                        color: color.into(),
-                    },
-                    crate::vertex::TextVertex {
-                        position: [glyph_pos.x + glyph_size.x, glyph_pos.y + glyph_size.y],
-                        tex_coords: [tex_coords[0] + tex_coords[2], tex_coords[1] + tex_coords[3]],
-                        color: color.into(),
-                    },
-                    crate::vertex::TextVertex {
-                        position: [glyph_pos.x, glyph_pos.y + glyph_size.y],
-                        tex_coords: [tex_coords[0], tex_coords[1] + tex_coords[3]],
-                        color: color.into(),
-                    },
-                ]);
+                for character in part.chars() {
+                    let Some(cached) = glyph(character) else {
+                        continue;
+                    };
+                    let metrics = cached.metrics;
+                    if metrics.width > 0 && metrics.height > 0 {
+                        let size = Vec2::new(metrics.width as f32, metrics.height as f32) / scale;
+                        let (xmin, ymin) = (metrics.xmin as f32 / scale, metrics.ymin as f32 / scale);
+                        let bottom = baseline - ymin;
+                        // The slant leans about the baseline, so descenders lean left
+                        let lean = slant * ymin;
+                        let quad = Quad {
+                            position: Vec2::new(pen_x + xmin + lean, bottom - size.y),
+                            size,
+                            texture_coords: cached.texture_coords,
+                            color: run_color,
+                            slant: slant * size.y,
+                        };
+                        if bold {
+                            quads.push(Quad { position: quad.position + Vec2::new((font_size * BOLD_OFFSET).max(0.5), 0.0), ..quad });
+                        }
+                        quads.push(quad);
+                    }
+                    pen_x += metrics.advance_width / scale;
+                }
                 
-                cursor_x += cached_glyph.metrics.advance_width;
+                // Decorations are thin solid bars across the run
+                let thickness = (font_size / 14.0).max(1.0);
+                let line_y = match run.and_then(|run| run.text_decoration) {
+                    Some(kryon_core::RichTextDecoration::Underline) => Some(baseline + thickness),
+                    Some(kryon_core::RichTextDecoration::LineThrough) => Some(baseline - ascent * 0.3),
+                    Some(kryon_core::RichTextDecoration::Overline) => Some(baseline - ascent),
+                    _ => None,
+                };
+                if let Some(line_y) = line_y.filter(|_| pen_x > start_x) {
+                    quads.push(Quad {
+                        position: Vec2::new(start_x, line_y),
+                        size: Vec2::new(pen_x - start_x, thickness),
+                        texture_coords: self.atlas.solid_texel(),
+                        color: run_color,
+                        slant: 0.0,
+                    });
+                }
             }
         }
         
-        vertices
+        quads.iter().flat_map(quad_vertices).collect()
     }
 
     pub fn bind_group(&self) -> &wgpu::BindGroup {
         &self.atlas.bind_group
     }
     
+    /// Cached glyph count and the atlas size in bytes (R8, one byte per texel).
+    pub fn memory_usage(&self) -> (usize, usize) {
+        (self.cache.len(), (self.atlas.size * self.atlas.size) as usize)
+    }
+    
     pub fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
         &self.atlas.bind_group_layout
     }
+}
 
-    pub fn render_text(
-        &mut self,
-        _encoder: &mut wgpu::CommandEncoder,
-        _target: &wgpu::TextureView,
-        _pipeline: &wgpu::RenderPipeline,
-        _bind_group: &wgpu::BindGroup,
-        text: &str,
-        position: Vec2,
-        font_size: f32,
-        color: Vec4,
-        _alignment: kryon_core::TextAlignment,
-        _max_width: Option<f32>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // Generate vertices for the text
-        let vertices = self.generate_text_vertices(text, position, font_size, color);
-        
-        if vertices.is_empty() {
-            return Ok(());
-        }
-        
-        // For now, just return Ok - in a real implementation you'd render the vertices
-        // TODO: Implement actual text rendering with the encoder
-        Ok(())
-    }
+/// Two triangles covering `quad`, its top edge shifted by its slant
+fn quad_vertices(quad: &Quad) -> [crate::vertex::TextVertex; 6] {
+    let [u, v, width, height] = quad.texture_coords;
+    let color: [f32; 4] = quad.color.into();
+    let vertex = |x: f32, y: f32, tex_u: f32, tex_v: f32| crate::vertex::TextVertex {
+        position: [x, y],
+        tex_coords: [tex_u, tex_v],
+        color,
+    };
+    let Quad { position, size, slant, .. } = *quad;
+    let top_left = vertex(position.x + slant, position.y, u, v);
+    let top_right = vertex(position.x + size.x + slant, position.y, u + width, v);
+    let bottom_right = vertex(position.x + size.x, position.y + size.y, u + width, v + height);
+    let bottom_left = vertex(position.x, position.y + size.y, u, v + height);
+    [top_left, top_right, bottom_right, top_left, bottom_right, bottom_left]
 }
 
 impl TextureAtlas {
-    fn new(device: &wgpu::Device, _queue: &wgpu::Queue, size: u32) -> Result<Self, Box<dyn std::error::Error>> {
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue, size: u32) -> Result<Self, Box<dyn std::error::Error>> {
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Text Atlas"),
             size: wgpu::Extent3d {
@@ -253,7 +320,7 @@ impl TextureAtlas {
             ],
         });
         
-        Ok(Self {
+        let mut atlas = Self {
             texture,
             _texture_view: texture_view,
             bind_group,
@@ -262,7 +329,17 @@ impl TextureAtlas {
             cursor_x: 0,
             cursor_y: 0,
             row_height: 0,
-        })
+        };
+        // Glyphs are packed after the solid block
+        let solid = vec![u8::MAX; (SOLID_BLOCK * SOLID_BLOCK) as usize];
+        atlas.add_glyph(device, queue, &solid, SOLID_BLOCK as usize, SOLID_BLOCK as usize)?;
+        Ok(atlas)
+    }
+    
+    /// Texture coordinates of one fully opaque texel, for solid quads
+    fn solid_texel(&self) -> [f32; 4] {
+        let center = (SOLID_BLOCK / 2) as f32 / self.size as f32;
+        [center, center, 0.0, 0.0]
     }
     
     fn add_glyph(
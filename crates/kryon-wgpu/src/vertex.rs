@@ -11,28 +11,17 @@ pub struct ViewProjectionUniform {
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct RectVertex {
-    pub position: [f32; 2],
+    /// Homogeneous position in pixels. `w` is 1 except under perspective,
+    /// where keeping it lets the GPU interpolate perspective-correctly.
+    pub position: [f32; 4],
     pub color: [f32; 4],
 }
 
+static RECT_ATTRIBUTES: [wgpu::VertexAttribute; 2] = crate::shaders::vertex_attributes(&kryon_shaders::RECT_VERTEX);
+
 impl RectVertex {
     pub fn desc() -> wgpu::VertexBufferLayout<'static> {
-        wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<RectVertex>() as wgpu::BufferAddress,
-            step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    offset: 0,
-                    shader_location: 0,
-                    format: wgpu::VertexFormat::Float32x2,
-                },
-                wgpu::VertexAttribute {
-                    offset: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
-                    shader_location: 1,
-                    format: wgpu::VertexFormat::Float32x4,
-                },
-            ],
-        }
+        crate::shaders::vertex_buffer_layout(&kryon_shaders::RECT_VERTEX, &RECT_ATTRIBUTES)
     }
 }
 
@@ -44,29 +33,68 @@ pub struct TextVertex {
     pub color: [f32; 4],
 }
 
+static TEXT_ATTRIBUTES: [wgpu::VertexAttribute; 3] = crate::shaders::vertex_attributes(&kryon_shaders::TEXTURED_VERTEX);
+
 impl TextVertex {
     pub fn desc() -> wgpu::VertexBufferLayout<'static> {
-        wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<TextVertex>() as wgpu::BufferAddress,
-            step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    offset: 0,
-                    shader_location: 0,
-                    format: wgpu::VertexFormat::Float32x2,
-                },
-                wgpu::VertexAttribute {
-                    offset: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
-                    shader_location: 1,
-                    format: wgpu::VertexFormat::Float32x2,
-                },
-                wgpu::VertexAttribute {
-                    offset: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
-                    shader_location: 2,
-                    format: wgpu::VertexFormat::Float32x4,
-                },
-            ],
-        }
+        crate::shaders::vertex_buffer_layout(&kryon_shaders::TEXTURED_VERTEX, &TEXT_ATTRIBUTES)
+    }
+}
+
+/// A `TextVertex` plus the image's grayscale, brightness and contrast
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct ImageVertex {
+    pub position: [f32; 2],
+    pub tex_coords: [f32; 2],
+    pub color: [f32; 4],
+    pub adjust: [f32; 4],
+}
+
+static IMAGE_ATTRIBUTES: [wgpu::VertexAttribute; 4] = crate::shaders::vertex_attributes(&kryon_shaders::FILTERED_IMAGE_VERTEX);
+
+impl ImageVertex {
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        crate::shaders::vertex_buffer_layout(&kryon_shaders::FILTERED_IMAGE_VERTEX, &IMAGE_ATTRIBUTES)
+    }
+}
+
+/// One corner of a gradient quad, carrying the whole gradient
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct GradientVertex {
+    /// Homogeneous position in pixels, as in `RectVertex`
+    pub position: [f32; 4],
+    /// Position in the box, then the box's size
+    pub shape: [f32; 4],
+    pub params: [f32; 4],
+    pub offsets: [f32; kryon_shaders::MAX_GRADIENT_STOPS],
+    pub colors: [[f32; 4]; kryon_shaders::MAX_GRADIENT_STOPS],
+}
+
+static GRADIENT_ATTRIBUTES: [wgpu::VertexAttribute; 13] = crate::shaders::vertex_attributes(&kryon_shaders::GRADIENT_VERTEX);
+
+impl GradientVertex {
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        crate::shaders::vertex_buffer_layout(&kryon_shaders::GRADIENT_VERTEX, &GRADIENT_ATTRIBUTES)
+    }
+}
+
+/// Per-instance data for the particle pipeline; one quad is expanded per instance.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct ParticleInstanceRaw {
+    pub center: [f32; 2],
+    pub size: f32,
+    pub rotation: f32,
+    pub color: [f32; 4],
+}
+
+static PARTICLE_ATTRIBUTES: [wgpu::VertexAttribute; 3] = crate::shaders::vertex_attributes(&kryon_shaders::PARTICLE_INSTANCE);
+
+impl ParticleInstanceRaw {
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        crate::shaders::vertex_buffer_layout(&kryon_shaders::PARTICLE_INSTANCE, &PARTICLE_ATTRIBUTES)
     }
 }
 
@@ -86,20 +114,37 @@ pub fn generate_rounded_rect_vertices(
     
     vec![
         RectVertex {
-            position: [x, y],
+            position: [x, y, 0.0, 1.0],
             color: color.into(),
         },
         RectVertex {
-            position: [x + w, y],
+            position: [x + w, y, 0.0, 1.0],
             color: color.into(),
         },
         RectVertex {
-            position: [x + w, y + h],
+            position: [x + w, y + h, 0.0, 1.0],
             color: color.into(),
         },
         RectVertex {
-            position: [x, y + h],
+            position: [x, y + h, 0.0, 1.0],
             color: color.into(),
         },
     ]
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertex_structs_match_shared_layouts() {
+        assert_eq!(std::mem::size_of::<RectVertex>() as u64, kryon_shaders::RECT_VERTEX.stride);
+        assert_eq!(std::mem::size_of::<TextVertex>() as u64, kryon_shaders::TEXTURED_VERTEX.stride);
+        assert_eq!(std::mem::size_of::<ImageVertex>() as u64, kryon_shaders::FILTERED_IMAGE_VERTEX.stride);
+        assert_eq!(std::mem::size_of::<ParticleInstanceRaw>() as u64, kryon_shaders::PARTICLE_INSTANCE.stride);
+        assert_eq!(std::mem::size_of::<GradientVertex>() as u64, kryon_shaders::GRADIENT_VERTEX.stride);
+        assert_eq!(GradientVertex::desc().attributes[5].offset, std::mem::offset_of!(GradientVertex, colors) as u64);
+        assert_eq!(kryon_render::gradient::SHADER_GRADIENT_STOPS, kryon_shaders::MAX_GRADIENT_STOPS);
+        assert_eq!(TextVertex::desc().attributes[2].offset, std::mem::offset_of!(TextVertex, color) as u64);
+        assert_eq!(ImageVertex::desc().attributes[3].offset, std::mem::offset_of!(ImageVertex, adjust) as u64);
+    }
+}
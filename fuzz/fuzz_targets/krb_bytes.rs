@@ -0,0 +1,10 @@
+// fuzz/fuzz_targets/krb_bytes.rs
+//! Arbitrary bytes into the KRB parser. `load_krb_file` is a read followed by
+//! `load_krb_from_bytes`, so this covers it without touching the filesystem.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = kryon_core::load_krb_from_bytes(data);
+});
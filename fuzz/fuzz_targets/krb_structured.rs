@@ -0,0 +1,69 @@
+// fuzz/fuzz_targets/krb_structured.rs
+//! Structural mutations of valid KRBs: header counts, table offsets, single
+//! bytes, truncation and splices. Starting from real files gets past the magic
+//! and header checks that reject almost all random input.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+const SEEDS: [&[u8]; 3] = [
+    include_bytes!("../../crates/kryon-layout/tests/fixtures/column_padding.krb"),
+    include_bytes!("../../crates/kryon-layout/tests/fixtures/flex_row_center.krb"),
+    include_bytes!("../../crates/kryon-layout/tests/fixtures/percent_sizes.krb"),
+];
+
+/// Header layout: u16 counts at 8..28, u32 table offsets at 28..68.
+const COUNT_FIELDS: usize = 10;
+const OFFSET_FIELDS: usize = 10;
+
+#[derive(Debug, Arbitrary)]
+enum Mutation {
+    Count { field: u8, value: u16 },
+    Offset { field: u8, value: u32 },
+    Byte { at: u16, value: u8 },
+    Truncate { len: u16 },
+    Splice { at: u16, bytes: Vec<u8> },
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    seed: u8,
+    mutations: Vec<Mutation>,
+}
+
+fn apply(data: &mut Vec<u8>, mutation: &Mutation) {
+    if data.is_empty() {
+        return;
+    }
+    match *mutation {
+        Mutation::Count { field, value } => {
+            let at = 8 + (field as usize % COUNT_FIELDS) * 2;
+            data[at..at + 2].copy_from_slice(&value.to_le_bytes());
+        }
+        Mutation::Offset { field, value } => {
+            let at = 28 + (field as usize % OFFSET_FIELDS) * 4;
+            data[at..at + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        Mutation::Byte { at, value } => {
+            let at = at as usize % data.len();
+            data[at] = value;
+        }
+        Mutation::Truncate { len } => data.truncate(len as usize),
+        Mutation::Splice { at, ref bytes } => {
+            let at = at as usize % data.len();
+            data.splice(at..at, bytes.iter().copied());
+        }
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let mut data = SEEDS[input.seed as usize % SEEDS.len()].to_vec();
+    for mutation in &input.mutations {
+        if data.len() < 68 {
+            break;
+        }
+        apply(&mut data, mutation);
+    }
+    let _ = kryon_core::load_krb_from_bytes(&data);
+});
@@ -0,0 +1,135 @@
+// fuzz/fuzz_targets/render_commands.rs
+//! Generated `RenderCommand` sequences executed by the ratatui backend on an
+//! in-memory `TestBackend`, which needs no window, GPU or terminal. Geometry is
+//! unconstrained, so NaN, infinite and negative values all show up.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use glam::{Vec2, Vec4};
+use kryon_core::{CSSUnit, CSSUnitValue, TextAlignment, TransformData, TransformProperty, TransformPropertyType, TransformType};
+use kryon_ratatui::RatatuiRenderer;
+use kryon_render::{CommandRenderer, RenderCommand, Renderer};
+use libfuzzer_sys::fuzz_target;
+use ratatui::backend::TestBackend;
+
+#[derive(Debug, Arbitrary)]
+enum Transform {
+    Scale(f64),
+    Translate(f64, f64),
+    Rotate(f64),
+}
+
+#[derive(Debug, Arbitrary)]
+enum Command {
+    Rect { position: (f32, f32), size: (f32, f32), color: [f32; 4], border_width: f32, transform: Option<Transform> },
+    Text { position: (f32, f32), text: String, max_width: Option<f32>, alignment: u8, transform: Option<Transform> },
+    Image { position: (f32, f32), size: (f32, f32), opacity: f32 },
+    Clip { position: (f32, f32), size: (f32, f32) },
+    ClearClip,
+    CanvasSize(f32, f32),
+    BeginCanvas { position: (f32, f32), size: (f32, f32) },
+    CanvasRect { position: (f32, f32), size: (f32, f32), fill: Option<[f32; 4]> },
+    CanvasText { position: (f32, f32), text: String },
+    EndCanvas,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    width: u8,
+    height: u8,
+    commands: Vec<Command>,
+}
+
+fn vec2((x, y): (f32, f32)) -> Vec2 {
+    Vec2::new(x, y)
+}
+
+fn transform(transform: &Option<Transform>) -> Option<TransformData> {
+    let property = |property_type, value, unit| TransformProperty { property_type, value: CSSUnitValue { value, unit } };
+    let properties = match *transform.as_ref()? {
+        Transform::Scale(scale) => vec![property(TransformPropertyType::Scale, scale, CSSUnit::Number)],
+        Transform::Translate(x, y) => vec![
+            property(TransformPropertyType::TranslateX, x, CSSUnit::Pixels),
+            property(TransformPropertyType::TranslateY, y, CSSUnit::Pixels),
+        ],
+        Transform::Rotate(degrees) => vec![property(TransformPropertyType::Rotate, degrees, CSSUnit::Degrees)],
+    };
+    Some(TransformData { transform_type: TransformType::Transform2D, properties, origin: Default::default() })
+}
+
+fn to_render_command(command: &Command) -> RenderCommand {
+    match command {
+        Command::Rect { position, size, color, border_width, transform: t } => RenderCommand::DrawRect {
+            position: vec2(*position),
+            size: vec2(*size),
+            color: Vec4::from_array(*color),
+            border_radius: 0.0,
+            border_width: *border_width,
+            border_color: Vec4::ONE,
+            transform: transform(t),
+            shadow: None,
+            z_index: 0,
+        },
+        Command::Text { position, text, max_width, alignment, transform: t } => RenderCommand::DrawText {
+            position: vec2(*position),
+            text: text.clone(),
+            font_size: 16.0,
+            color: Vec4::ONE,
+            alignment: match alignment % 4 {
+                0 => TextAlignment::Start,
+                1 => TextAlignment::Center,
+                2 => TextAlignment::End,
+                _ => TextAlignment::Justify,
+            },
+            max_width: *max_width,
+            max_height: None,
+            transform: transform(t),
+            font_family: None,
+            runs: Vec::new(),
+            z_index: 0,
+        },
+        Command::Image { position, size, opacity } => RenderCommand::DrawImage {
+            position: vec2(*position),
+            size: vec2(*size),
+            source: "missing.png".to_string(),
+            opacity: *opacity,
+            transform: None,
+        },
+        Command::Clip { position, size } => RenderCommand::SetClip { position: vec2(*position), size: vec2(*size) },
+        Command::ClearClip => RenderCommand::ClearClip,
+        Command::CanvasSize(width, height) => RenderCommand::SetCanvasSize(Vec2::new(*width, *height)),
+        Command::BeginCanvas { position, size } => RenderCommand::BeginCanvas {
+            canvas_id: "fuzz".to_string(),
+            position: vec2(*position),
+            size: vec2(*size),
+        },
+        Command::CanvasRect { position, size, fill } => RenderCommand::DrawCanvasRect {
+            position: vec2(*position),
+            size: vec2(*size),
+            fill_color: fill.map(Vec4::from_array),
+            stroke_color: None,
+            stroke_width: 1.0,
+        },
+        Command::CanvasText { position, text } => RenderCommand::DrawCanvasText {
+            position: vec2(*position),
+            text: text.clone(),
+            font_size: 12.0,
+            color: Vec4::ONE,
+            font_family: None,
+            alignment: TextAlignment::Start,
+        },
+        Command::EndCanvas => RenderCommand::EndCanvas,
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let backend = TestBackend::new(input.width as u16, input.height as u16);
+    let Ok(mut renderer) = RatatuiRenderer::initialize(backend) else {
+        return;
+    };
+    let commands: Vec<RenderCommand> = input.commands.iter().map(to_render_command).collect();
+
+    let mut context = renderer.begin_frame(Vec4::ZERO).unwrap();
+    let _ = renderer.execute_commands(&mut context, &commands);
+    let _ = renderer.end_frame(context);
+});
@@ -65,6 +65,14 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// A gap as written in the source: pixels for numbers, strings as given.
+fn gap_label(value: &kryon_core::PropertyValue) -> String {
+    match value.as_float() {
+        Some(pixels) => format!("{}px", pixels),
+        None => value.as_string().unwrap_or("?").to_string(),
+    }
+}
+
 fn generate_tree_output(krb_file: &kryon_core::KRBFile, args: &Args) -> Result<String> {
     let mut output = String::new();
     
@@ -138,6 +146,15 @@ fn render_element_tree(
         // Show key properties inline
         let mut inline_props = Vec::new();
         
+        // Show the spacing a container leaves between its children
+        if args.show_layout {
+            for name in ["gap", "row_gap", "column_gap"] {
+                if let Some(value) = element.custom_properties.get(name) {
+                    inline_props.push(format!("{}:{}", name.replace('_', "-"), gap_label(value)));
+                }
+            }
+        }
+        
         // Show background color if not transparent
         if element.background_color.w > 0.0 {
             if args.show_colors {
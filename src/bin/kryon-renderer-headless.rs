@@ -0,0 +1,188 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use glam::Vec2;
+use tracing::info;
+
+use kryon_headless::HeadlessRenderer;
+use kryon_render::{CommandRenderer, Renderer};
+use kryon_runtime::{diagnostics, ColorScheme, KryonApp, OutputRotation, PageMargins, PageSetup, SafeAreaInsets, VariableOverrides};
+
+#[derive(Parser)]
+#[command(name = "kryon-renderer-headless")]
+#[command(about = "Renders a Kryon .krb file offscreen, without a window, to an image")]
+struct Args {
+    /// Path to the .krb file to render
+    krb_file: String,
+
+    /// Where to write the frame: a PNG (or other image) path, a `.rgba` path
+    /// for raw RGBA bytes, or `-` for a PNG on stdout
+    #[arg(short, long)]
+    output: String,
+
+    /// Frame width in pixels
+    #[arg(long, default_value_t = 800)]
+    width: u32,
+
+    /// Frame height in pixels
+    #[arg(long, default_value_t = 600)]
+    height: u32,
+
+    /// Device pixels per logical pixel, to render as a HiDPI display would.
+    /// --width and --height stay the frame's size in pixels, so the app
+    /// lays out in WIDTH/SCALE by HEIGHT/SCALE.
+    #[arg(long, default_value_t = 1.0)]
+    scale: f32,
+
+    /// Frames to run before capturing, so images can load and animations
+    /// advance; each frame steps the app by 16ms
+    #[arg(long, default_value_t = 2)]
+    frames: u32,
+
+    /// Enable debug logging
+    #[arg(short, long)]
+    debug: bool,
+
+    /// Set a template variable, e.g. --var theme=dark (repeatable).
+    /// Takes precedence over KRYON_VAR_* environment variables and KRB defaults.
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    vars: Vec<String>,
+
+    /// Insets from the screen edges hidden by a notch, rounded corners or a
+    /// kiosk bezel: ALL, VERTICAL,HORIZONTAL or TOP,RIGHT,BOTTOM,LEFT pixels
+    #[arg(long, value_name = "INSETS", value_parser = SafeAreaInsets::parse)]
+    safe_area_insets: Option<SafeAreaInsets>,
+
+    /// Rotate the output clockwise by 0, 90, 180 or 270 degrees
+    #[arg(long, value_name = "DEGREES", value_parser = parse_rotation)]
+    rotate: Option<OutputRotation>,
+
+    /// Light or dark default styles for buttons, inputs, links and
+    /// scrollbars
+    #[arg(long, value_name = "SCHEME", value_parser = parse_color_scheme)]
+    color_scheme: Option<ColorScheme>,
+
+    /// Default stylesheet to use in place of the built-in one
+    #[arg(long, value_name = "FILE")]
+    default_styles: Option<String>,
+
+    /// Render static UI only, for files that aren't trusted: scripts, event
+    /// handlers and declared data sources are stripped, and processes,
+    /// network, serial ports and file dialogs stay off
+    #[arg(long)]
+    sandbox: bool,
+
+    /// Print the contents of the element with this id as pages, one image
+    /// each, numbered after the output's name (report.png becomes
+    /// report-1.png, report-2.png, ...)
+    #[arg(long, value_name = "ELEMENT_ID")]
+    paginate: Option<String>,
+
+    /// Page size for --paginate: a4, a5, letter, legal or WIDTHxHEIGHT
+    /// pixels, with " landscape" after a name to turn it on its side
+    #[arg(long, value_name = "SIZE", default_value = "a4", value_parser = PageSetup::parse_size)]
+    page_size: Vec2,
+
+    /// Page margins for --paginate: ALL, VERTICAL,HORIZONTAL or
+    /// TOP,RIGHT,BOTTOM,LEFT pixels
+    #[arg(long, value_name = "MARGINS", default_value = "48", value_parser = PageMargins::parse)]
+    page_margins: PageMargins,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // Logs go to stderr so a PNG can be written to stdout
+    tracing_subscriber::fmt()
+        .with_max_level(if args.debug { tracing::Level::DEBUG } else { tracing::Level::INFO })
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .init();
+
+    if !Path::new(&args.krb_file).exists() {
+        anyhow::bail!("KRB file not found: {}", args.krb_file);
+    }
+    let mut krb_file = kryon_core::load_krb_file(&args.krb_file)?;
+    VariableOverrides::from_env_and_cli(&args.vars)?.apply(&mut krb_file);
+    diagnostics::set_krb(&args.krb_file, &krb_file);
+    diagnostics::set_backend("headless (wgpu offscreen)");
+
+    let size = Vec2::new(args.width as f32, args.height as f32);
+    let mut renderer = HeadlessRenderer::new(size).context("Failed to initialize headless renderer")?;
+    renderer.set_viewport_scale(args.scale)?;
+    let mut app = if args.sandbox {
+        KryonApp::new_sandboxed(krb_file, renderer, None)
+    } else {
+        KryonApp::new_with_krb(krb_file, renderer, None)
+    }
+    .context("Failed to create Kryon application")?;
+    if let Some(insets) = args.safe_area_insets {
+        app.set_safe_area_insets(insets);
+    }
+    if let Some(rotation) = args.rotate {
+        app.set_output_rotation(rotation)?;
+    }
+    if let Some(path) = &args.default_styles {
+        app.load_default_styles(path)?;
+    }
+    if let Some(color_scheme) = args.color_scheme {
+        app.set_color_scheme(color_scheme);
+    }
+
+    for _ in 0..args.frames.max(1) {
+        app.update(Duration::from_millis(16))?;
+        app.render()?;
+    }
+
+    if let Some(container) = &args.paginate {
+        return write_pages(&app, container, PageSetup::new(args.page_size, args.page_margins), &args.output);
+    }
+
+    let backend = app.renderer_mut().backend_mut();
+    if args.output == "-" {
+        std::io::stdout().write_all(&backend.frame_png()?)?;
+    } else if args.output.ends_with(".rgba") {
+        std::fs::write(&args.output, backend.frame_rgba()?.into_raw())
+            .with_context(|| format!("Failed to write {}", args.output))?;
+    } else {
+        backend.take_screenshot(&args.output)?;
+    }
+    info!("Rendered {} at {}x{} to {}", args.krb_file, args.width, args.height, args.output);
+
+    Ok(())
+}
+
+/// Draws each page of `container` on a white page of its own and writes
+/// it next to `output`, numbered
+fn write_pages(app: &KryonApp<HeadlessRenderer>, container: &str, setup: PageSetup, output: &str) -> Result<()> {
+    if output == "-" {
+        anyhow::bail!("Pages are written to numbered files; give --output a file name");
+    }
+    let pages = app.paginate(container, &setup)?;
+    let mut printer = HeadlessRenderer::new(setup.size).context("Failed to initialize page renderer")?;
+    let path = Path::new(output);
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("page");
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("png");
+    for (index, page) in pages.iter().enumerate() {
+        let mut context = printer.begin_frame(glam::Vec4::ONE)?;
+        printer.execute_commands(&mut context, &page.commands)?;
+        printer.end_frame(context)?;
+        let page_path = path.with_file_name(format!("{}-{}.{}", stem, index + 1, extension));
+        printer.take_screenshot(&page_path.to_string_lossy())?;
+    }
+    info!("Printed '{}' as {} pages of {}x{} to {}", container, pages.len(), setup.size.x, setup.size.y, output);
+    Ok(())
+}
+
+fn parse_rotation(value: &str) -> Result<OutputRotation, String> {
+    value.parse().ok()
+        .and_then(OutputRotation::from_degrees)
+        .ok_or_else(|| format!("expected 0, 90, 180 or 270, got '{}'", value))
+}
+
+fn parse_color_scheme(value: &str) -> Result<ColorScheme, String> {
+    ColorScheme::parse(value).ok_or_else(|| format!("expected light or dark, got '{}'", value))
+}
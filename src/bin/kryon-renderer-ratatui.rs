@@ -18,7 +18,7 @@ use ratatui::prelude::CrosstermBackend;
 use kryon_core::load_krb_file; // Assuming you might want this for inspect
 use kryon_render::{InputEvent, Renderer}; // Keep Renderer for trait bounds
 use kryon_ratatui::RatatuiRenderer;
-use kryon_runtime::KryonApp;
+use kryon_runtime::{analytics, diagnostics, AnalyticsRecorder, KryonApp, MetricsExporter, Repl, VariableOverrides};
 
 #[derive(Parser)]
 #[command(name = "kryon-renderer-ratatui")]
@@ -33,14 +33,68 @@ struct Args {
     /// Enable standalone rendering mode (auto-wrap non-App elements)
     #[arg(long)]
     standalone: bool,
+
+    /// Set a template variable, e.g. --var api_url=https://host (repeatable).
+    /// Takes precedence over KRYON_VAR_* environment variables and KRB defaults.
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    vars: Vec<String>,
+
+    /// Print template variable precedence and resolved values, then exit
+    #[arg(long)]
+    info: bool,
+
+    /// Serve the script console over WebSocket, e.g. 127.0.0.1:9230
+    /// (needs the kryon-runtime/websocket feature). Stdin belongs to the terminal UI.
+    #[arg(long, value_name = "ADDR")]
+    repl_listen: Option<String>,
+    /// On panic, write a crash bundle (backtrace, recent frames, log, KRB and
+    /// backend info) into DIR. Also enables kryon.diagnostics.dump().
+    #[arg(long, value_name = "DIR")]
+    diagnostics_dir: Option<String>,
+
+    /// Print a summary of render statistics (commands by type, elements
+    /// visited/culled/clipped, style cache hit rate) every SECONDS
+    #[arg(long, value_name = "SECONDS")]
+    stats_interval: Option<f64>,
+
+    /// Serve Prometheus metrics (frame times, FPS, memory, script errors,
+    /// caches) at http://ADDR/metrics, e.g. 0.0.0.0:9464
+    /// (needs the kryon-runtime/metrics feature)
+    #[arg(long, value_name = "ADDR")]
+    metrics_listen: Option<String>,
+
+    /// Push the metrics to the Pushgateway at URL instead of, or as well
+    /// as, serving them; for devices Prometheus can't reach
+    #[arg(long, value_name = "URL")]
+    metrics_push: Option<String>,
+
+    /// Seconds between pushes to the Pushgateway
+    #[arg(long, value_name = "SECONDS", default_value_t = 15.0)]
+    metrics_push_interval: f64,
+
+    /// The `app_id` label on the metrics (defaults to the KRB file's name)
+    #[arg(long, value_name = "ID")]
+    app_id: Option<String>,
+
+    /// Record clicks and form field events (never typed text) and append
+    /// them to FILE as JSON Lines, for heatmaps and funnels
+    #[arg(long, value_name = "FILE")]
+    analytics_log: Option<String>,
 }
 
 fn main() -> Result<()> {
-    // Initialize logging - this will output to stderr which can be redirected
-    tracing_subscriber::fmt::init();
-    
     let args = Args::parse();
 
+    // Initialize logging - this will output to stderr which can be redirected
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(|| diagnostics::tee(io::stdout()))
+        .init();
+
+    if let Some(dir) = &args.diagnostics_dir {
+        diagnostics::install(dir).context("Failed to enable diagnostics")?;
+    }
+
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         let _ = cleanup_terminal();
@@ -55,7 +109,21 @@ fn main() -> Result<()> {
         return inspect_krb_file(&args.krb_file);
     }
 
-    let result = run(&args);
+    // Seed template variables from KRYON_VAR_* and --var before the app starts
+    let mut krb_file = load_krb_file(&args.krb_file)?;
+    let overrides = VariableOverrides::from_env_and_cli(&args.vars)?;
+    if args.info {
+        print!("{}", overrides.info_report(&krb_file));
+        return Ok(());
+    }
+    overrides.apply(&mut krb_file);
+    diagnostics::set_krb(&args.krb_file, &krb_file);
+
+    let stats_interval = args.stats_interval.map(std::time::Duration::from_secs_f64);
+    let push_interval = std::time::Duration::from_secs_f64(args.metrics_push_interval);
+    let metrics = MetricsExporter::from_args(&app_id(&args), args.metrics_listen.as_deref(), args.metrics_push.as_deref(), push_interval);
+    let analytics = args.analytics_log.clone().map(|path| AnalyticsRecorder::default().with_uploader(analytics::file_uploader(path)));
+    let result = run(krb_file, args.repl_listen.as_deref(), stats_interval, metrics, analytics);
 
     cleanup_terminal()?;
 
@@ -66,16 +134,40 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run(args: &Args) -> Result<()> {
+/// `--app-id`, or the KRB file's name without its extension
+fn app_id(args: &Args) -> String {
+    args.app_id.clone().unwrap_or_else(|| {
+        Path::new(&args.krb_file).file_stem().map_or_else(|| "kryon".to_string(), |stem| stem.to_string_lossy().into_owned())
+    })
+}
+
+fn run(
+    krb_file: kryon_core::KRBFile,
+    repl_listen: Option<&str>,
+    stats_interval: Option<std::time::Duration>,
+    metrics: Option<MetricsExporter>,
+    analytics: Option<AnalyticsRecorder>,
+) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
 
+    diagnostics::set_backend("ratatui (crossterm)");
     let backend = CrosstermBackend::new(stdout);
     let renderer = RatatuiRenderer::initialize(backend)?;
 
-    let mut app =
-        KryonApp::new(&args.krb_file, renderer).context("Failed to create Kryon application")?;
+    let mut app = KryonApp::new_with_krb(krb_file, renderer, None)
+        .context("Failed to create Kryon application")?;
+    if let Some(repl) = Repl::from_args(false, repl_listen) {
+        app.attach_repl(repl);
+    }
+    app.set_stats_interval(stats_interval);
+    if let Some(metrics) = metrics {
+        app.attach_metrics(metrics);
+    }
+    if let Some(recorder) = analytics {
+        app.enable_analytics(recorder);
+    }
 
     tracing::info!("Starting terminal render loop... (Press 'q' to quit, click on buttons to interact)");
 
@@ -100,6 +192,12 @@ fn run(args: &Args) -> Result<()> {
                             let event = InputEvent::MousePress {
                                 position: glam::vec2(mouse_event.column as f32, mouse_event.row as f32),
                                 button: kryon_render::MouseButton::Left,
+                                modifiers: kryon_render::KeyModifiers {
+                                    ctrl: mouse_event.modifiers.contains(crossterm::event::KeyModifiers::CONTROL),
+                                    shift: mouse_event.modifiers.contains(crossterm::event::KeyModifiers::SHIFT),
+                                    alt: mouse_event.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+                                    meta: mouse_event.modifiers.contains(crossterm::event::KeyModifiers::SUPER),
+                                },
                             };
                             if let Err(e) = app.handle_input(event) {
                                 tracing::error!("Failed to handle mouse click: {:?}", e);
@@ -6,15 +6,16 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use tracing::{error, info};
 
-use kryon_render::Renderer;
-use kryon_runtime::KryonApp;
+use kryon_render::{Renderer, WindowGeometry, WindowPlacement};
+use kryon_runtime::{analytics, diagnostics, live_compile::{self, DEFAULT_COMPILE_COMMAND}, AnalyticsRecorder, ColorScheme, gallery, GalleryPlan, GallerySize, GalleryTheme, CompileCommand, CompileOutcome, CompileWatcher, InputPolicy, KryonApp, MetricsExporter, OutputRotation, Repl, SafeAreaInsets, VariableOverrides};
 use kryon_raylib::RaylibRenderer;
 
 #[derive(Parser)]
 #[command(name = "kryon-renderer-raylib")]
 #[command(about = "Raylib-based renderer for Kryon .krb files")]
 struct Args {
-    /// Path to the .krb file to render
+    /// Path to the .krb file to render (with --gallery, a directory of .krb
+    /// files or a JSON gallery manifest). A .kry source is compiled first; see --watch.
     krb_file: String,
 
     /// Window width. Overrides the value in the KRB file.
@@ -29,6 +30,28 @@ struct Args {
     #[arg(long)]
     title: Option<String>,
 
+    /// Open on this monitor (0 is the first; see kryon.monitors())
+    #[arg(long, value_name = "INDEX")]
+    monitor: Option<usize>,
+
+    /// Window position, relative to --monitor if given
+    #[arg(long, value_name = "X,Y", value_parser = parse_position)]
+    position: Option<glam::Vec2>,
+
+    /// Start maximized
+    #[arg(long)]
+    maximized: bool,
+
+    /// Transparent, undecorated, always-on-top HUD window. Clicks pass
+    /// through to the windows below unless an element sets pointer_events: auto.
+    #[arg(long)]
+    overlay: bool,
+
+    /// Remember the window's position, size and maximized state in FILE,
+    /// restoring them on the next start
+    #[arg(long, value_name = "FILE")]
+    window_state: Option<String>,
+
     /// Enable debug logging
     #[arg(short, long)]
     debug: bool,
@@ -40,10 +63,128 @@ struct Args {
     /// Duration to wait before taking screenshot (in milliseconds)
     #[arg(long, default_value = "100")]
     screenshot_delay: u64,
+
+    /// Screenshot every KRB file under the input directory or manifest into
+    /// DIR, with an index.json and index.html gallery, then exit
+    #[arg(long, value_name = "DIR")]
+    gallery: Option<String>,
+
+    /// Gallery size as WIDTHxHEIGHT (repeatable; default: each KRB's own size)
+    #[arg(long = "gallery-size", value_name = "WxH", value_parser = GallerySize::parse)]
+    gallery_sizes: Vec<GallerySize>,
+
+    /// Gallery theme as NAME or NAME:VAR=VALUE,... template variable
+    /// overrides (repeatable)
+    #[arg(long = "gallery-theme", value_name = "THEME", value_parser = GalleryTheme::parse)]
+    gallery_themes: Vec<GalleryTheme>,
+
+    /// Number of gallery screenshots rendered at once (default: CPU count)
+    #[arg(long, value_name = "N")]
+    jobs: Option<usize>,
     
     /// Enable standalone rendering mode (auto-wrap non-App elements)
     #[arg(long)]
     standalone: bool,
+
+    /// Render static UI only, for files that aren't trusted: scripts, event
+    /// handlers and declared data sources are stripped, and processes,
+    /// network, serial ports and file dialogs stay off
+    #[arg(long)]
+    sandbox: bool,
+
+    /// Set a template variable, e.g. --var api_url=https://host (repeatable).
+    /// Takes precedence over KRYON_VAR_* environment variables and KRB defaults.
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    vars: Vec<String>,
+
+    /// Print template variable precedence and resolved values, then exit
+    #[arg(long)]
+    info: bool,
+
+    /// Evaluate Lua typed on stdin in the running app (see :help)
+    #[arg(long)]
+    repl: bool,
+
+    /// Serve the script console over WebSocket, e.g. 127.0.0.1:9230
+    /// (needs the kryon-runtime/websocket feature)
+    #[arg(long, value_name = "ADDR")]
+    repl_listen: Option<String>,
+    /// On panic, write a crash bundle (backtrace, recent frames, log, KRB and
+    /// backend info) into DIR. Also enables kryon.diagnostics.dump().
+    #[arg(long, value_name = "DIR")]
+    diagnostics_dir: Option<String>,
+
+    /// Show the profiler overlay (toggle at runtime with Ctrl+Shift+P)
+    #[arg(long)]
+    profiler: bool,
+
+    /// Print a summary of render statistics (commands by type, elements
+    /// visited/culled/clipped, style cache hit rate) every SECONDS
+    #[arg(long, value_name = "SECONDS")]
+    stats_interval: Option<f64>,
+
+    /// Serve Prometheus metrics (frame times, FPS, memory, script errors,
+    /// caches) at http://ADDR/metrics, e.g. 0.0.0.0:9464
+    /// (needs the kryon-runtime/metrics feature)
+    #[arg(long, value_name = "ADDR")]
+    metrics_listen: Option<String>,
+
+    /// Push the metrics to the Pushgateway at URL instead of, or as well
+    /// as, serving them; for devices Prometheus can't reach
+    #[arg(long, value_name = "URL")]
+    metrics_push: Option<String>,
+
+    /// Seconds between pushes to the Pushgateway
+    #[arg(long, value_name = "SECONDS", default_value_t = 15.0)]
+    metrics_push_interval: f64,
+
+    /// The `app_id` label on the metrics (defaults to the KRB file's name)
+    #[arg(long, value_name = "ID")]
+    app_id: Option<String>,
+
+    /// Record clicks and form field events (never typed text) and append
+    /// them to FILE as JSON Lines, for heatmaps and funnels
+    #[arg(long, value_name = "FILE")]
+    analytics_log: Option<String>,
+
+    /// Print the features this backend couldn't draw, by element, after the
+    /// first frame (also `:warnings` in the script console)
+    #[arg(long)]
+    warnings: bool,
+
+    /// Handle every mouse move and wheel event as it arrives instead of once
+    /// per frame, for latency-sensitive apps
+    #[arg(long)]
+    no_input_coalescing: bool,
+
+    /// Insets from the screen edges hidden by a notch, rounded corners or a
+    /// kiosk bezel: ALL, VERTICAL,HORIZONTAL or TOP,RIGHT,BOTTOM,LEFT pixels
+    #[arg(long, value_name = "INSETS", value_parser = SafeAreaInsets::parse)]
+    safe_area_insets: Option<SafeAreaInsets>,
+
+    /// Rotate the output clockwise by 0, 90, 180 or 270 degrees, for a
+    /// display mounted sideways or upside down
+    #[arg(long, value_name = "DEGREES", value_parser = parse_rotation)]
+    rotate: Option<OutputRotation>,
+
+    /// Light or dark default styles for buttons, inputs, links and
+    /// scrollbars
+    #[arg(long, value_name = "SCHEME", value_parser = parse_color_scheme)]
+    color_scheme: Option<ColorScheme>,
+
+    /// Default stylesheet to use in place of the built-in one
+    #[arg(long, value_name = "FILE")]
+    default_styles: Option<String>,
+
+    /// Recompile the .kry source whenever a .kry file beside it changes and
+    /// reload the result, showing compile errors over the UI
+    #[arg(long)]
+    watch: bool,
+
+    /// Compiler command line for .kry sources; {input} and {output} are
+    /// replaced with the source and KRB paths
+    #[arg(long, value_name = "COMMAND", default_value = DEFAULT_COMPILE_COMMAND, value_parser = CompileCommand::parse)]
+    compiler: CompileCommand,
 }
 
 fn main() -> Result<()> {
@@ -58,22 +199,41 @@ fn main() -> Result<()> {
         })
         .with_target(false)
         .compact()
+        .with_writer(|| diagnostics::tee(std::io::stdout()))
         .finish();
     
     tracing::subscriber::set_global_default(subscriber)
         .context("Failed to set tracing subscriber")?;
 
+    if let Some(dir) = &args.diagnostics_dir {
+        diagnostics::install(dir).context("Failed to enable diagnostics")?;
+    }
+
+    if let Some(out_dir) = &args.gallery {
+        return run_gallery(&args, out_dir);
+    }
+
     // Validate file path
     if !Path::new(&args.krb_file).exists() {
         anyhow::bail!("KRB file not found: {}", args.krb_file);
     }
+    let (krb_path, mut watcher) = compile_source(&args)?;
 
     info!("Loading KRB file: {}", args.krb_file);
     
     // Load the application definition first to get window properties
-    let krb_file = kryon_core::load_krb_file(&args.krb_file)
+    let mut krb_file = kryon_core::load_krb_file(&krb_path)
         .context("Failed to load KRB file to read window properties")?;
 
+    // Seed template variables from KRYON_VAR_* and --var before the app starts
+    let overrides = VariableOverrides::from_env_and_cli(&args.vars)?;
+    if args.info {
+        print!("{}", overrides.info_report(&krb_file));
+        return Ok(());
+    }
+    overrides.apply(&mut krb_file);
+    diagnostics::set_krb(&krb_path, &krb_file);
+
     // Set default values
     let mut width = 800;
     let mut height = 600;
@@ -105,15 +265,22 @@ fn main() -> Result<()> {
         false
     };
     
+    // A remembered window size wins over the KRB file but not the command line
+    let saved_geometry = args.window_state.as_deref().and_then(WindowGeometry::load);
+    if let Some(saved) = &saved_geometry {
+        width = saved.size.x as i32;
+        height = saved.size.y as i32;
+    }
+    
     // Allow CLI arguments to override KRB file properties
     // In standalone mode, prefer CLI arguments over KRB file properties
-    let final_width = if is_standalone {
+    let final_width = if is_standalone && saved_geometry.is_none() {
         args.width.unwrap_or(800) // Default for standalone
     } else {
         args.width.unwrap_or(width)
     };
     
-    let final_height = if is_standalone {
+    let final_height = if is_standalone && saved_geometry.is_none() {
         args.height.unwrap_or(600) // Default for standalone
     } else {
         args.height.unwrap_or(height)
@@ -128,15 +295,68 @@ fn main() -> Result<()> {
     info!("Initializing Raylib renderer with properties: {}x{} '{}'", final_width, final_height, &final_title);
     
     // Initialize renderer with the final, resolved properties
-    let mut renderer = RaylibRenderer::initialize((final_width, final_height, final_title))
-        .context("Failed to initialize Raylib renderer")?;
+    let surface = (final_width, final_height, final_title);
+    let mut renderer = if args.overlay {
+        RaylibRenderer::initialize_overlay(surface)
+    } else {
+        RaylibRenderer::initialize(surface)
+    }.context("Failed to initialize Raylib renderer")?;
+    diagnostics::set_backend(format!("raylib ({}x{})", final_width, final_height));
+
+    let monitors = renderer.monitors();
+    if let Some(index) = args.monitor.filter(|&index| index >= monitors.len()) {
+        tracing::warn!("Monitor {} not found ({} connected); using the default placement", index, monitors.len());
+    }
+    let placement = WindowPlacement { monitor: args.monitor, position: args.position, maximized: args.maximized }
+        .or_saved(saved_geometry.as_ref(), &monitors);
+    renderer.apply_placement(&placement);
 
     // Register fonts from the KRB file
     // Extract font mappings from KRB file strings
     register_fonts_from_krb(&mut renderer, &krb_file);
 
-    let mut app = KryonApp::new(&args.krb_file, renderer)
-        .context("Failed to create Kryon application")?;
+    let mut app = if args.sandbox {
+        KryonApp::new_sandboxed(krb_file, renderer, None)
+    } else {
+        KryonApp::new_with_krb(krb_file, renderer, None)
+    }
+    .context("Failed to create Kryon application")?;
+    app.set_monitors(&monitors);
+    app.set_overlay_mode(args.overlay);
+    if let Some(repl) = Repl::from_args(args.repl, args.repl_listen.as_deref()) {
+        app.attach_repl(repl);
+    }
+    if args.profiler {
+        app.set_profiler_overlay(true);
+    }
+    if let Some(seconds) = args.stats_interval {
+        app.set_stats_interval(Some(std::time::Duration::from_secs_f64(seconds)));
+    }
+    let push_interval = std::time::Duration::from_secs_f64(args.metrics_push_interval);
+    if let Some(metrics) = MetricsExporter::from_args(&app_id(&args), args.metrics_listen.as_deref(), args.metrics_push.as_deref(), push_interval) {
+        app.attach_metrics(metrics);
+    }
+    if let Some(path) = &args.analytics_log {
+        app.enable_analytics(AnalyticsRecorder::default().with_uploader(analytics::file_uploader(path.clone())));
+    }
+    if args.warnings {
+        app.set_warning_summary(true);
+    }
+    if args.no_input_coalescing {
+        app.set_input_policy(InputPolicy::immediate())?;
+    }
+    if let Some(insets) = args.safe_area_insets {
+        app.set_safe_area_insets(insets);
+    }
+    if let Some(rotation) = args.rotate {
+        app.set_output_rotation(rotation)?;
+    }
+    if let Some(path) = &args.default_styles {
+        app.load_default_styles(path)?;
+    }
+    if let Some(color_scheme) = args.color_scheme {
+        app.set_color_scheme(color_scheme);
+    }
 
     // Force initial mouse position update to establish initial hover state
     let initial_events = app.renderer_mut().backend_mut().poll_input_events();
@@ -156,6 +376,7 @@ fn main() -> Result<()> {
     let mut last_frame_time = Instant::now();
     let start_time = Instant::now();
     let screenshot_taken = false;
+    let mut click_through = false;
     
     'main_loop: loop {
         // Check if window should close
@@ -184,12 +405,23 @@ fn main() -> Result<()> {
             }
         }
         
+        if let Some(outcome) = watcher.as_mut().and_then(CompileWatcher::poll) {
+            apply_compile_outcome(&mut app, outcome, &overrides);
+        }
+        
         // Update application
         if let Err(e) = app.update(delta_time) {
             error!("Failed to update app: {}", e);
             break;
         }
         
+        // Raylib stops reporting the mouse once clicks pass through, so the
+        // overlay only passes them through while nothing opts in to input
+        if args.overlay && click_through == app.has_pointer_targets() {
+            click_through = !click_through;
+            app.renderer_mut().backend_mut().set_click_through(click_through);
+        }
+        
         // Render frame
         if let Err(e) = app.render() {
             error!("Failed to render frame: {}", e);
@@ -211,10 +443,132 @@ fn main() -> Result<()> {
         }
     }
     
+    if let Some(path) = &args.window_state {
+        if let Err(e) = app.renderer().backend().window_geometry().save(path) {
+            error!("Failed to save window state to {}: {}", path, e);
+        }
+    }
+    
     info!("Raylib renderer shutdown complete");
     Ok(())
 }
 
+/// `--app-id`, or the KRB file's name without its extension
+fn app_id(args: &Args) -> String {
+    args.app_id.clone().unwrap_or_else(|| {
+        Path::new(&args.krb_file).file_stem().map_or_else(|| "kryon".to_string(), |stem| stem.to_string_lossy().into_owned())
+    })
+}
+
+/// Renders each gallery job in a child process of this binary, since raylib
+/// owns one window per process.
+fn run_gallery(args: &Args, out_dir: &str) -> Result<()> {
+    let plan = GalleryPlan::from_path(Path::new(&args.krb_file), &args.gallery_sizes, &args.gallery_themes)?;
+    let out_dir = Path::new(out_dir);
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create gallery directory {}", out_dir.display()))?;
+    let exe = std::env::current_exe().context("Failed to locate the renderer executable")?;
+
+    let mut extra_args = vec!["--screenshot-delay".to_string(), args.screenshot_delay.to_string()];
+    if args.standalone {
+        extra_args.push("--standalone".to_string());
+    }
+    if args.sandbox {
+        extra_args.push("--sandbox".to_string());
+    }
+    for var in &args.vars {
+        extra_args.extend(["--var".to_string(), var.clone()]);
+    }
+
+    let jobs = args.jobs.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    info!("Rendering {} gallery screenshots into {} ({} at a time)", plan.jobs.len(), out_dir.display(), jobs);
+    let entries = gallery::run(plan.jobs, jobs, |job| {
+        let _ = std::fs::remove_file(out_dir.join(&job.output));
+        let output = gallery::screenshot_command(&exe, job, out_dir, &extra_args)
+            .output()
+            .map_err(|e| format!("failed to start renderer: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(stderr.lines().last().unwrap_or("renderer failed").to_string());
+        }
+        if !out_dir.join(&job.output).exists() {
+            return Err("no screenshot was written".to_string());
+        }
+        info!("Rendered {}", job.output);
+        Ok(())
+    });
+
+    gallery::write_index(out_dir, &entries).context("Failed to write the gallery index")?;
+    let failed: Vec<_> = entries.iter().filter(|entry| entry.result.is_err()).collect();
+    for entry in &failed {
+        error!("{}: {}", entry.job.output, entry.result.as_ref().unwrap_err());
+    }
+    info!("Gallery written to {}", out_dir.join("index.html").display());
+    if !failed.is_empty() {
+        anyhow::bail!("{} of {} gallery screenshots failed", failed.len(), entries.len());
+    }
+    Ok(())
+}
+
+/// Compiles a .kry input, returning the KRB to load and, with --watch, the
+/// watcher that rebuilds it
+fn compile_source(args: &Args) -> Result<(String, Option<CompileWatcher>)> {
+    if !args.krb_file.ends_with(".kry") {
+        if args.watch {
+            anyhow::bail!("--watch needs a .kry source file, got {}", args.krb_file);
+        }
+        return Ok((args.krb_file.clone(), None));
+    }
+    let output = live_compile::output_path(Path::new(&args.krb_file));
+    let watcher = CompileWatcher::new(&args.krb_file, output, args.compiler.clone());
+    info!("Compiling {}", args.krb_file);
+    if let CompileOutcome::Failed(error) = watcher.compile_now() {
+        anyhow::bail!("Failed to compile {}:\n{}", args.krb_file, error);
+    }
+    let krb_path = watcher.output().to_string_lossy().into_owned();
+    Ok((krb_path, args.watch.then_some(watcher)))
+}
+
+/// Loads a rebuilt KRB into the app, or shows why the build failed over the UI
+fn apply_compile_outcome(app: &mut KryonApp<RaylibRenderer>, outcome: CompileOutcome, overrides: &VariableOverrides) {
+    let result = match outcome {
+        CompileOutcome::Compiled(path) => {
+            let path = path.to_string_lossy();
+            kryon_core::load_krb_file(&path).map_err(anyhow::Error::from).and_then(|mut krb_file| {
+                overrides.apply(&mut krb_file);
+                diagnostics::set_krb(&path, &krb_file);
+                register_fonts_from_krb(app.renderer_mut().backend_mut(), &krb_file);
+                app.reload(krb_file)
+            })
+        }
+        CompileOutcome::Failed(error) => Err(anyhow::anyhow!(error)),
+    };
+    match result {
+        Ok(()) => {
+            info!("Reloaded the recompiled KRB");
+            app.set_build_error(None);
+        }
+        Err(e) => {
+            error!("Build failed: {:#}", e);
+            app.set_build_error(Some(format!("{:#}", e)));
+        }
+    }
+}
+
+fn parse_position(value: &str) -> Result<glam::Vec2, String> {
+    WindowPlacement::parse_position(value).ok_or_else(|| format!("expected X,Y, got '{}'", value))
+}
+
+fn parse_rotation(value: &str) -> Result<OutputRotation, String> {
+    value.parse().ok()
+        .and_then(OutputRotation::from_degrees)
+        .ok_or_else(|| format!("expected 0, 90, 180 or 270, got '{}'", value))
+}
+
+fn parse_color_scheme(value: &str) -> Result<ColorScheme, String> {
+    ColorScheme::parse(value).ok_or_else(|| format!("expected light or dark, got '{}'", value))
+}
+
 fn register_fonts_from_krb(renderer: &mut RaylibRenderer, krb_file: &kryon_core::KRBFile) {
     // Register fonts using the font mappings stored in the KRB file
     // The font mappings should be stored as key-value pairs in the fonts HashMap
@@ -7,34 +7,56 @@ use glam::Vec2;
 use tracing::{error, info};
 
 use winit::{
-    event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    event::{DeviceEvent, Event, WindowEvent},
+    event_loop::{ControlFlow, DeviceEvents, EventLoop},
+    window::{WindowBuilder, WindowLevel},
 };
 
-use kryon_render::Renderer;
-use kryon_runtime::KryonApp;
+use kryon_render::{MonitorInfo, Renderer, ThreadedRenderer, WindowGeometry, WindowPlacement};
+use kryon_runtime::{analytics, diagnostics, live_compile::{self, DEFAULT_COMPILE_COMMAND}, AnalyticsRecorder, ColorScheme, CompileCommand, CompileOutcome, CompileWatcher, InputPolicy, KryonApp, MetricsExporter, OutputRotation, Repl, SafeAreaInsets, VariableOverrides};
 use kryon_wgpu::WgpuRenderer;
 
 #[derive(Parser)]
 #[command(name = "kryon-renderer-wgpu")]
 #[command(about = "WGPU-based GPU renderer for Kryon .krb files")]
 struct Args {
-    /// Path to the .krb file to render
+    /// Path to the .krb file to render. A .kry source is compiled first; see --watch.
     krb_file: String,
 
-    /// Window width
-    #[arg(long, default_value = "800")]
-    width: u32,
+    /// Window width [default: 800, or the size saved in --window-state]
+    #[arg(long)]
+    width: Option<u32>,
 
-    /// Window height
-    #[arg(long, default_value = "600")]
-    height: u32,
+    /// Window height [default: 600, or the size saved in --window-state]
+    #[arg(long)]
+    height: Option<u32>,
 
     /// Window title
     #[arg(long, default_value = "Kryon WGPU Renderer")]
     title: String,
 
+    /// Open on this monitor (0 is the first; see kryon.monitors())
+    #[arg(long, value_name = "INDEX")]
+    monitor: Option<usize>,
+
+    /// Window position, relative to --monitor if given
+    #[arg(long, value_name = "X,Y", value_parser = parse_position)]
+    position: Option<Vec2>,
+
+    /// Start maximized
+    #[arg(long)]
+    maximized: bool,
+
+    /// Transparent, undecorated, always-on-top HUD window. Clicks pass
+    /// through to the windows below unless an element sets pointer_events: auto.
+    #[arg(long)]
+    overlay: bool,
+
+    /// Remember the window's position, size and maximized state in FILE,
+    /// restoring them on the next start
+    #[arg(long, value_name = "FILE")]
+    window_state: Option<String>,
+
     /// Enable debug logging
     #[arg(short, long)]
     debug: bool,
@@ -42,6 +64,111 @@ struct Args {
     /// Enable standalone rendering mode (auto-wrap non-App elements)
     #[arg(long)]
     standalone: bool,
+
+    /// Render static UI only, for files that aren't trusted: scripts, event
+    /// handlers and declared data sources are stripped, and processes,
+    /// network, serial ports and file dialogs stay off
+    #[arg(long)]
+    sandbox: bool,
+
+    /// Set a template variable, e.g. --var api_url=https://host (repeatable).
+    /// Takes precedence over KRYON_VAR_* environment variables and KRB defaults.
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    vars: Vec<String>,
+
+    /// Print template variable precedence and resolved values, then exit
+    #[arg(long)]
+    info: bool,
+
+    /// Evaluate Lua typed on stdin in the running app (see :help)
+    #[arg(long)]
+    repl: bool,
+
+    /// Serve the script console over WebSocket, e.g. 127.0.0.1:9230
+    /// (needs the kryon-runtime/websocket feature)
+    #[arg(long, value_name = "ADDR")]
+    repl_listen: Option<String>,
+    /// On panic, write a crash bundle (backtrace, recent frames, log, KRB and
+    /// backend info) into DIR. Also enables kryon.diagnostics.dump().
+    #[arg(long, value_name = "DIR")]
+    diagnostics_dir: Option<String>,
+
+    /// Show the profiler overlay (toggle at runtime with Ctrl+Shift+P)
+    #[arg(long)]
+    profiler: bool,
+
+    /// Print a summary of render statistics (commands by type, elements
+    /// visited/culled/clipped, style cache hit rate) every SECONDS
+    #[arg(long, value_name = "SECONDS")]
+    stats_interval: Option<f64>,
+
+    /// Serve Prometheus metrics (frame times, FPS, memory, script errors,
+    /// caches) at http://ADDR/metrics, e.g. 0.0.0.0:9464
+    /// (needs the kryon-runtime/metrics feature)
+    #[arg(long, value_name = "ADDR")]
+    metrics_listen: Option<String>,
+
+    /// Push the metrics to the Pushgateway at URL instead of, or as well
+    /// as, serving them; for devices Prometheus can't reach
+    #[arg(long, value_name = "URL")]
+    metrics_push: Option<String>,
+
+    /// Seconds between pushes to the Pushgateway
+    #[arg(long, value_name = "SECONDS", default_value_t = 15.0)]
+    metrics_push_interval: f64,
+
+    /// The `app_id` label on the metrics (defaults to the KRB file's name)
+    #[arg(long, value_name = "ID")]
+    app_id: Option<String>,
+
+    /// Record clicks and form field events (never typed text) and append
+    /// them to FILE as JSON Lines, for heatmaps and funnels
+    #[arg(long, value_name = "FILE")]
+    analytics_log: Option<String>,
+
+    /// Print the features this backend couldn't draw, by element, after the
+    /// first frame (also `:warnings` in the script console)
+    #[arg(long)]
+    warnings: bool,
+
+    /// Handle every mouse move and wheel event as it arrives instead of once
+    /// per frame, for latency-sensitive apps
+    #[arg(long)]
+    no_input_coalescing: bool,
+
+    /// Draw on a render thread of its own, so slow scripts or layout don't
+    /// hold up presenting frames
+    #[arg(long)]
+    render_thread: bool,
+
+    /// Insets from the screen edges hidden by a notch, rounded corners or a
+    /// kiosk bezel: ALL, VERTICAL,HORIZONTAL or TOP,RIGHT,BOTTOM,LEFT pixels
+    #[arg(long, value_name = "INSETS", value_parser = SafeAreaInsets::parse)]
+    safe_area_insets: Option<SafeAreaInsets>,
+
+    /// Rotate the output clockwise by 0, 90, 180 or 270 degrees, for a
+    /// display mounted sideways or upside down
+    #[arg(long, value_name = "DEGREES", value_parser = parse_rotation)]
+    rotate: Option<OutputRotation>,
+
+    /// Light or dark default styles for buttons, inputs, links and
+    /// scrollbars; follows the system setting if not given
+    #[arg(long, value_name = "SCHEME", value_parser = parse_color_scheme)]
+    color_scheme: Option<ColorScheme>,
+
+    /// Default stylesheet to use in place of the built-in one
+    #[arg(long, value_name = "FILE")]
+    default_styles: Option<String>,
+
+    /// Recompile the .kry source whenever a .kry file beside it changes and
+    /// reload the result, showing compile errors over the UI
+    #[arg(long)]
+    watch: bool,
+
+    /// Compiler command line for .kry sources; {input} and {output} are
+    /// replaced with the source and KRB paths
+    #[arg(long, value_name = "COMMAND", default_value = DEFAULT_COMPILE_COMMAND, value_parser = CompileCommand::parse)]
+    compiler: CompileCommand,
 }
 
 fn main() -> Result<()> {
@@ -56,31 +183,84 @@ fn main() -> Result<()> {
         })
         .with_target(false)
         .compact()
+        .with_writer(|| diagnostics::tee(std::io::stdout()))
         .finish();
     
     tracing::subscriber::set_global_default(subscriber)
         .context("Failed to set tracing subscriber")?;
 
+    if let Some(dir) = &args.diagnostics_dir {
+        diagnostics::install(dir).context("Failed to enable diagnostics")?;
+    }
+
     // Validate file path
     if !Path::new(&args.krb_file).exists() {
         anyhow::bail!("KRB file not found: {}", args.krb_file);
     }
+    let (krb_path, mut watcher) = compile_source(&args)?;
+
+    // Pre-load KRB file to check if we need to override window properties
+    let mut krb_file = kryon_core::load_krb_file(&krb_path)?;
+    let overrides = VariableOverrides::from_env_and_cli(&args.vars)?;
+    if args.info {
+        print!("{}", overrides.info_report(&krb_file));
+        return Ok(());
+    }
+    overrides.apply(&mut krb_file);
+    diagnostics::set_krb(&krb_path, &krb_file);
 
     info!("Initializing WGPU renderer for: {}", args.krb_file);
     
     let event_loop = EventLoop::new()?;
+    let monitors: Vec<MonitorInfo> = event_loop.available_monitors()
+        .enumerate()
+        .map(|(index, monitor)| MonitorInfo {
+            name: monitor.name().unwrap_or_else(|| format!("Monitor {}", index)),
+            position: Vec2::new(monitor.position().x as f32, monitor.position().y as f32),
+            size: Vec2::new(monitor.size().width as f32, monitor.size().height as f32),
+            scale_factor: monitor.scale_factor() as f32,
+        })
+        .collect();
+    if let Some(index) = args.monitor.filter(|&index| index >= monitors.len()) {
+        tracing::warn!("Monitor {} not found ({} connected); using the default placement", index, monitors.len());
+    }
+    
+    // A remembered size is in physical pixels; --width/--height are logical
+    let saved_geometry = args.window_state.as_deref().and_then(WindowGeometry::load);
+    let mut window_builder = WindowBuilder::new().with_title(&args.title);
+    if args.overlay {
+        window_builder = window_builder
+            .with_transparent(true)
+            .with_decorations(false)
+            .with_window_level(WindowLevel::AlwaysOnTop);
+    }
+    let physical_size = match (saved_geometry, args.width, args.height) {
+        (Some(saved), None, None) => {
+            window_builder = window_builder.with_inner_size(winit::dpi::PhysicalSize::new(saved.size.x as u32, saved.size.y as u32));
+            saved.size
+        }
+        (_, width, height) => {
+            let logical = Vec2::new(width.unwrap_or(800) as f32, height.unwrap_or(600) as f32);
+            window_builder = window_builder.with_inner_size(winit::dpi::LogicalSize::new(logical.x, logical.y));
+            let scale = args.monitor.and_then(|index| monitors.get(index)).or(monitors.first()).map_or(1.0, |m| m.scale_factor);
+            logical * scale
+        }
+    };
+    
+    let placement = WindowPlacement { monitor: args.monitor, position: args.position, maximized: args.maximized }
+        .or_saved(saved_geometry.as_ref(), &monitors);
+    if let Some(position) = placement.resolve(&monitors, physical_size) {
+        window_builder = window_builder.with_position(winit::dpi::PhysicalPosition::new(position.x as i32, position.y as i32));
+    }
     let window = std::sync::Arc::new(
-        WindowBuilder::new()
-            .with_title(&args.title)
-            .with_inner_size(winit::dpi::LogicalSize::new(args.width, args.height))
+        window_builder
+            .with_maximized(placement.maximized)
             .build(&event_loop)?
     );
 
     let size = window.inner_size();
     let viewport_size = Vec2::new(size.width as f32, size.height as f32);
     
-    // Pre-load KRB file to check if we need to override window properties
-    let krb_file = kryon_core::load_krb_file(&args.krb_file)?;
     let should_override_window = if let Some(root_id) = krb_file.root_element_id {
         if let Some(root_element) = krb_file.elements.get(&root_id) {
             args.standalone || root_element.id == "auto_generated_app"
@@ -111,6 +291,17 @@ fn main() -> Result<()> {
         eprintln!("  - {} ({:?})", info.name, info.backend);
     }
     
+    diagnostics::set_backend(format!(
+        "wgpu; adapters: {}",
+        adapters.iter()
+            .map(|adapter| {
+                let info = adapter.get_info();
+                format!("{} ({:?}, {} {})", info.name, info.backend, info.driver, info.driver_info)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    
     if adapters.is_empty() {
         anyhow::bail!("No WGPU adapters found! Check your graphics drivers.");
     }
@@ -120,17 +311,78 @@ fn main() -> Result<()> {
     
     // Initialize renderer
 
-    let renderer = WgpuRenderer::initialize((window.clone(), viewport_size))
+    let mut renderer = WgpuRenderer::initialize((window.clone(), viewport_size))
         .context("Failed to initialize WGPU renderer")?;
+    if args.overlay && !renderer.set_transparent(true) {
+        tracing::warn!("This surface can't be composited with transparency; the overlay will be opaque");
+    }
+    let renderer = if args.render_thread {
+        ThreadedRenderer::spawn_or_inline(renderer)
+    } else {
+        ThreadedRenderer::inline(renderer)
+    };
         
     // Create Kryon app
-    let mut app = KryonApp::new(&args.krb_file, renderer)
-        .context("Failed to create Kryon application")?;
+    let mut app = if args.sandbox {
+        KryonApp::new_sandboxed(krb_file, renderer, None)
+    } else {
+        KryonApp::new_with_krb(krb_file, renderer, None)
+    }
+    .context("Failed to create Kryon application")?;
+    app.set_monitors(&monitors);
+    app.set_overlay_mode(args.overlay);
+    if args.overlay {
+        // Raw mouse motion still arrives while clicks pass through, which is
+        // how the overlay notices the pointer coming back
+        event_loop.listen_device_events(DeviceEvents::Always);
+    }
+    if let Some(repl) = Repl::from_args(args.repl, args.repl_listen.as_deref()) {
+        app.attach_repl(repl);
+    }
+    if args.profiler {
+        app.set_profiler_overlay(true);
+    }
+    if let Some(seconds) = args.stats_interval {
+        app.set_stats_interval(Some(std::time::Duration::from_secs_f64(seconds)));
+    }
+    let push_interval = std::time::Duration::from_secs_f64(args.metrics_push_interval);
+    if let Some(metrics) = MetricsExporter::from_args(&app_id(&args), args.metrics_listen.as_deref(), args.metrics_push.as_deref(), push_interval) {
+        app.attach_metrics(metrics);
+    }
+    if let Some(path) = &args.analytics_log {
+        app.enable_analytics(AnalyticsRecorder::default().with_uploader(analytics::file_uploader(path.clone())));
+    }
+    if args.warnings {
+        app.set_warning_summary(true);
+    }
+    if args.no_input_coalescing {
+        app.set_input_policy(InputPolicy::immediate())?;
+    }
+    if let Some(insets) = args.safe_area_insets {
+        app.set_safe_area_insets(insets);
+    }
+    if let Some(rotation) = args.rotate {
+        app.set_output_rotation(rotation)?;
+    }
+    if let Some(path) = &args.default_styles {
+        app.load_default_styles(path)?;
+    }
+    if let Some(color_scheme) = args.color_scheme.or_else(|| window.theme().map(color_scheme_of)) {
+        app.set_color_scheme(color_scheme);
+    }
 
     info!("Starting WGPU render loop...");
     
     let mut last_frame_time = Instant::now();
     let window_for_event_loop = window.clone();
+    let window_state = args.window_state.clone();
+    let follow_system_theme = args.color_scheme.is_none();
+    let mut toggle_click_through = args.overlay;
+    let mut click_through = false;
+    let mut cursor_position = Vec2::ZERO;
+    let mut modifiers = kryon_render::KeyModifiers::none();
+    let mut ime_area = None;
+    window.set_ime_allowed(true);
     
     event_loop.run(move |event, control_flow| {
         control_flow.set_control_flow(ControlFlow::Poll);
@@ -147,32 +399,90 @@ fn main() -> Result<()> {
                         error!("Failed to handle resize: {}", e);
                     }
                 }
+                // Moved to a display with another scale; the renderer reads
+                // the new one as it resizes, even if the size stayed
+                WindowEvent::ScaleFactorChanged { .. } => {
+                    let size = window_for_event_loop.inner_size();
+                    let new_size = Vec2::new(size.width as f32, size.height as f32);
+                    if let Err(e) = app.handle_input(kryon_render::InputEvent::Resize { size: new_size }) {
+                        error!("Failed to handle scale change: {}", e);
+                    }
+                }
+                WindowEvent::ThemeChanged(theme) if follow_system_theme => {
+                    app.set_color_scheme(color_scheme_of(theme));
+                }
                 WindowEvent::CursorMoved { position, .. } => {
                     let pos = Vec2::new(position.x as f32, position.y as f32);
+                    cursor_position = pos;
                     if let Err(e) = app.handle_input(kryon_render::InputEvent::MouseMove { position: pos }) {
                         error!("Failed to handle mouse move: {}", e);
                     }
+                    if toggle_click_through && click_through == app.captures_pointer_at(pos) {
+                        click_through = !click_through;
+                        if let Err(e) = window_for_event_loop.set_cursor_hittest(!click_through) {
+                            error!("Click-through is not supported here: {}", e);
+                            toggle_click_through = false;
+                            click_through = false;
+                        }
+                    }
                 }
-                WindowEvent::KeyboardInput { event, .. } => {
-                    if event.state == winit::event::ElementState::Pressed {
-                        let key_code = match event.physical_key {
-                            winit::keyboard::PhysicalKey::Code(code) => match code {
-                                winit::keyboard::KeyCode::Escape => {
-                                    info!("Escape pressed, exiting");
-                                    control_flow.exit();
-                                    return;
-                                }
-                                _ => kryon_render::KeyCode::Space, // Default
-                            },
-                            _ => return,
-                        };
-                        
-                        if let Err(e) = app.handle_input(kryon_render::InputEvent::KeyPress { 
-                            key: key_code,
-                            modifiers: kryon_render::KeyModifiers::none()
-                        }) {
-                            error!("Failed to handle key press: {}", e);
+                WindowEvent::MouseWheel { delta, .. } => {
+                    // Wheel up reveals content above, so positive deltas become upward scrolls
+                    let delta = match delta {
+                        winit::event::MouseScrollDelta::LineDelta(x, y) => kryon_render::wheel_scroll_delta(Vec2::new(x, y), modifiers),
+                        winit::event::MouseScrollDelta::PixelDelta(position) => {
+                            -Vec2::new(position.x as f32, position.y as f32) / app.viewport_scale()
                         }
+                    };
+                    if let Err(e) = app.handle_input(kryon_render::InputEvent::Scroll { delta }) {
+                        error!("Failed to handle mouse wheel: {}", e);
+                    }
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    let button = match button {
+                        winit::event::MouseButton::Left => kryon_render::MouseButton::Left,
+                        winit::event::MouseButton::Right => kryon_render::MouseButton::Right,
+                        winit::event::MouseButton::Middle => kryon_render::MouseButton::Middle,
+                        winit::event::MouseButton::Back => kryon_render::MouseButton::Back,
+                        winit::event::MouseButton::Forward => kryon_render::MouseButton::Forward,
+                        _ => return,
+                    };
+                    let event = match state {
+                        winit::event::ElementState::Pressed => kryon_render::InputEvent::MousePress { position: cursor_position, button, modifiers },
+                        winit::event::ElementState::Released => kryon_render::InputEvent::MouseRelease { position: cursor_position, button, modifiers },
+                    };
+                    if let Err(e) = app.handle_input(event) {
+                        error!("Failed to handle mouse button: {}", e);
+                    }
+                }
+                WindowEvent::ModifiersChanged(new_modifiers) => {
+                    let state = new_modifiers.state();
+                    modifiers = kryon_render::KeyModifiers {
+                        ctrl: state.control_key(),
+                        shift: state.shift_key(),
+                        alt: state.alt_key(),
+                        meta: state.super_key(),
+                    };
+                }
+                WindowEvent::KeyboardInput { event, .. } if event.state == winit::event::ElementState::Pressed => {
+                    if event.logical_key == winit::keyboard::Key::Named(winit::keyboard::NamedKey::Escape) {
+                        info!("Escape pressed, exiting");
+                        control_flow.exit();
+                        return;
+                    }
+                    let Some(key) = key_code(&event, modifiers) else { return };
+                    if let Err(e) = app.handle_input(kryon_render::InputEvent::KeyPress { key, modifiers }) {
+                        error!("Failed to handle key press: {}", e);
+                    }
+                }
+                WindowEvent::Ime(ime) => {
+                    let event = match ime {
+                        winit::event::Ime::Preedit(text, _) => kryon_render::InputEvent::ImePreedit { text },
+                        winit::event::Ime::Commit(text) => kryon_render::InputEvent::TextInput { text },
+                        winit::event::Ime::Enabled | winit::event::Ime::Disabled => return,
+                    };
+                    if let Err(e) = app.handle_input(event) {
+                        error!("Failed to handle input method text: {}", e);
                     }
                 }
                 WindowEvent::RedrawRequested => {
@@ -180,6 +490,10 @@ fn main() -> Result<()> {
                     let delta_time = now.duration_since(last_frame_time);
                     last_frame_time = now;
                     
+                    if let Some(outcome) = watcher.as_mut().and_then(CompileWatcher::poll) {
+                        apply_compile_outcome(&mut app, outcome, &overrides);
+                    }
+                    
                     // Update application
                     if let Err(e) = app.update(delta_time) {
                         error!("Failed to update app: {}", e);
@@ -191,15 +505,146 @@ fn main() -> Result<()> {
                         error!("Failed to render frame: {}", e);
                         return;
                     }
+                    
+                    // Keep the input method's candidate window next to the caret
+                    let area = app.ime_cursor_area();
+                    if area != ime_area {
+                        if let Some((position, size)) = area {
+                            window_for_event_loop.set_ime_cursor_area(
+                                winit::dpi::PhysicalPosition::new(position.x, position.y),
+                                winit::dpi::PhysicalSize::new(size.x, size.y),
+                            );
+                        }
+                        ime_area = area;
+                    }
                 }
                 _ => {}
             },
+            Event::DeviceEvent { event: DeviceEvent::MouseMotion { .. }, .. } if click_through => {
+                // Take the pointer back for a moment; the next CursorMoved
+                // decides whether clicks keep passing through
+                click_through = false;
+                let _ = window_for_event_loop.set_cursor_hittest(true);
+            }
             Event::AboutToWait => {
                 window_for_event_loop.request_redraw();
             }
+            Event::LoopExiting => {
+                if let Some(path) = &window_state {
+                    let position = window_for_event_loop.outer_position().unwrap_or_default();
+                    let size = window_for_event_loop.inner_size();
+                    let geometry = WindowGeometry {
+                        position: Vec2::new(position.x as f32, position.y as f32),
+                        size: Vec2::new(size.width as f32, size.height as f32),
+                        maximized: window_for_event_loop.is_maximized(),
+                    };
+                    if let Err(e) = geometry.save(path) {
+                        error!("Failed to save window state to {}: {}", path, e);
+                    }
+                }
+            }
             _ => {}
         }
     })?;
     
     Ok(())
 }
+
+/// `--app-id`, or the KRB file's name without its extension
+fn app_id(args: &Args) -> String {
+    args.app_id.clone().unwrap_or_else(|| {
+        Path::new(&args.krb_file).file_stem().map_or_else(|| "kryon".to_string(), |stem| stem.to_string_lossy().into_owned())
+    })
+}
+
+/// The runtime's key for a key press; `None` for keys it has no use for.
+/// Characters come from the text the key typed, so Shift and the keyboard
+/// layout apply, except with Ctrl or Super held, where the key itself counts.
+fn key_code(event: &winit::event::KeyEvent, modifiers: kryon_render::KeyModifiers) -> Option<kryon_render::KeyCode> {
+    use kryon_render::KeyCode;
+    use winit::keyboard::{Key, NamedKey};
+    Some(match &event.logical_key {
+        Key::Named(NamedKey::Enter) => KeyCode::Enter,
+        Key::Named(NamedKey::Space) => KeyCode::Space,
+        Key::Named(NamedKey::Backspace) => KeyCode::Backspace,
+        Key::Named(NamedKey::Delete) => KeyCode::Delete,
+        Key::Named(NamedKey::Tab) => KeyCode::Tab,
+        Key::Named(NamedKey::ArrowLeft) => KeyCode::ArrowLeft,
+        Key::Named(NamedKey::ArrowRight) => KeyCode::ArrowRight,
+        Key::Named(NamedKey::ArrowUp) => KeyCode::ArrowUp,
+        Key::Named(NamedKey::ArrowDown) => KeyCode::ArrowDown,
+        Key::Named(NamedKey::Home) => KeyCode::Home,
+        Key::Named(NamedKey::End) => KeyCode::End,
+        Key::Named(NamedKey::PageUp) => KeyCode::PageUp,
+        Key::Named(NamedKey::PageDown) => KeyCode::PageDown,
+        Key::Character(key) if modifiers.ctrl || modifiers.meta => KeyCode::Character(key.chars().next()?),
+        // No text while an input method is composing; it commits through `WindowEvent::Ime`
+        Key::Character(_) => KeyCode::Character(event.text.as_ref()?.chars().next()?),
+        _ => return None,
+    })
+}
+
+/// Compiles a .kry input, returning the KRB to load and, with --watch, the
+/// watcher that rebuilds it
+fn compile_source(args: &Args) -> Result<(String, Option<CompileWatcher>)> {
+    if !args.krb_file.ends_with(".kry") {
+        if args.watch {
+            anyhow::bail!("--watch needs a .kry source file, got {}", args.krb_file);
+        }
+        return Ok((args.krb_file.clone(), None));
+    }
+    let output = live_compile::output_path(Path::new(&args.krb_file));
+    let watcher = CompileWatcher::new(&args.krb_file, output, args.compiler.clone());
+    info!("Compiling {}", args.krb_file);
+    if let CompileOutcome::Failed(error) = watcher.compile_now() {
+        anyhow::bail!("Failed to compile {}:\n{}", args.krb_file, error);
+    }
+    let krb_path = watcher.output().to_string_lossy().into_owned();
+    Ok((krb_path, args.watch.then_some(watcher)))
+}
+
+/// Loads a rebuilt KRB into the app, or shows why the build failed over the UI
+fn apply_compile_outcome(app: &mut KryonApp<ThreadedRenderer<WgpuRenderer>>, outcome: CompileOutcome, overrides: &VariableOverrides) {
+    let result = match outcome {
+        CompileOutcome::Compiled(path) => {
+            let path = path.to_string_lossy();
+            kryon_core::load_krb_file(&path).map_err(anyhow::Error::from).and_then(|mut krb_file| {
+                overrides.apply(&mut krb_file);
+                diagnostics::set_krb(&path, &krb_file);
+                app.reload(krb_file)
+            })
+        }
+        CompileOutcome::Failed(error) => Err(anyhow::anyhow!(error)),
+    };
+    match result {
+        Ok(()) => {
+            info!("Reloaded the recompiled KRB");
+            app.set_build_error(None);
+        }
+        Err(e) => {
+            error!("Build failed: {:#}", e);
+            app.set_build_error(Some(format!("{:#}", e)));
+        }
+    }
+}
+
+fn parse_position(value: &str) -> Result<Vec2, String> {
+    WindowPlacement::parse_position(value).ok_or_else(|| format!("expected X,Y, got '{}'", value))
+}
+
+fn parse_rotation(value: &str) -> Result<OutputRotation, String> {
+    value.parse().ok()
+        .and_then(OutputRotation::from_degrees)
+        .ok_or_else(|| format!("expected 0, 90, 180 or 270, got '{}'", value))
+}
+
+fn parse_color_scheme(value: &str) -> Result<ColorScheme, String> {
+    ColorScheme::parse(value).ok_or_else(|| format!("expected light or dark, got '{}'", value))
+}
+
+fn color_scheme_of(theme: winit::window::Theme) -> ColorScheme {
+    match theme {
+        winit::window::Theme::Light => ColorScheme::Light,
+        winit::window::Theme::Dark => ColorScheme::Dark,
+    }
+}
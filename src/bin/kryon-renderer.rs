@@ -3,13 +3,25 @@ use clap::{Parser, Subcommand};
 use std::path::Path;
 use std::process::Command;
 
+use kryon_runtime::validation::{self, Backend};
+
 #[derive(Parser)]
 #[command(name = "kryon-renderer")]
 #[command(about = "Kryon renderer with multiple backends")]
 #[command(version)]
+#[command(arg_required_else_help = true, args_conflicts_with_subcommands = true)]
 struct Args {
     #[command(subcommand)]
-    command: RenderCommand,
+    command: Option<RenderCommand>,
+
+    /// Lint a .krb file without rendering and print a JSON report.
+    /// Exits non-zero when the report contains errors.
+    #[arg(long, value_name = "KRB_FILE")]
+    validate: Option<String>,
+
+    /// Backend whose capabilities --validate checks against (wgpu, raylib, ratatui, web)
+    #[arg(long, default_value = "wgpu", requires = "validate")]
+    backend: String,
 }
 
 #[derive(Subcommand)]
@@ -33,6 +45,12 @@ enum RenderCommand {
         /// Enable standalone rendering mode (auto-wrap non-App elements)
         #[arg(long)]
         standalone: bool,
+        /// Set a template variable (NAME=VALUE, repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+        /// Print template variable precedence and resolved values, then exit
+        #[arg(long)]
+        info: bool,
     },
     /// Render with Ratatui backend (terminal UI)
     Ratatui {
@@ -44,6 +62,12 @@ enum RenderCommand {
         /// Enable standalone rendering mode (auto-wrap non-App elements)
         #[arg(long)]
         standalone: bool,
+        /// Set a template variable (NAME=VALUE, repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+        /// Print template variable precedence and resolved values, then exit
+        #[arg(long)]
+        info: bool,
     },
     /// Render with Raylib backend (simple graphics)
     Raylib {
@@ -64,6 +88,35 @@ enum RenderCommand {
         /// Enable standalone rendering mode (auto-wrap non-App elements)
         #[arg(long)]
         standalone: bool,
+        /// Set a template variable (NAME=VALUE, repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+        /// Print template variable precedence and resolved values, then exit
+        #[arg(long)]
+        info: bool,
+    },
+    /// Render offscreen to an image without opening a window (CI, servers)
+    Headless {
+        /// Path to the .krb file to render
+        krb_file: String,
+        /// Image to write: PNG path, `.rgba` for raw RGBA bytes, or `-` for stdout
+        #[arg(short, long)]
+        output: String,
+        /// Frame width
+        #[arg(long)]
+        width: Option<u32>,
+        /// Frame height
+        #[arg(long)]
+        height: Option<u32>,
+        /// Frames to run before capturing
+        #[arg(long)]
+        frames: Option<u32>,
+        /// Enable debug logging
+        #[arg(short, long)]
+        debug: bool,
+        /// Set a template variable (NAME=VALUE, repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
     },
     /// Debug renderer (text hierarchy output)
     Debug {
@@ -90,8 +143,19 @@ enum RenderCommand {
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    match args.command {
-        RenderCommand::Wgpu { krb_file, width, height, title, debug, standalone } => {
+    if let Some(krb_file) = args.validate {
+        let backend: Backend = args.backend.parse().map_err(anyhow::Error::msg)?;
+        let report = validation::validate_file(&krb_file, backend);
+        println!("{}", report.to_json());
+        std::process::exit(report.exit_code());
+    }
+
+    let Some(command) = args.command else {
+        anyhow::bail!("no backend selected; see --help");
+    };
+
+    match command {
+        RenderCommand::Wgpu { krb_file, width, height, title, debug, standalone, vars, info } => {
             validate_krb_file(&krb_file)?;
             
             let mut cmd_args = Vec::<String>::new();
@@ -114,12 +178,13 @@ fn main() -> Result<()> {
             if standalone {
                 cmd_args.push("--standalone".to_string());
             }
+            push_variable_args(&mut cmd_args, vars, info);
             cmd_args.push(krb_file);
             
             run_backend_binary("kryon-renderer-wgpu", &cmd_args)
         }
         
-        RenderCommand::Ratatui { krb_file, debug, standalone } => {
+        RenderCommand::Ratatui { krb_file, debug, standalone, vars, info } => {
             validate_krb_file(&krb_file)?;
             
             let mut cmd_args = Vec::<String>::new();
@@ -129,12 +194,13 @@ fn main() -> Result<()> {
             if standalone {
                 cmd_args.push("--standalone".to_string());
             }
+            push_variable_args(&mut cmd_args, vars, info);
             cmd_args.push(krb_file);
             
             run_backend_binary("kryon-renderer-ratatui", &cmd_args)
         }
         
-        RenderCommand::Raylib { krb_file, width, height, title, debug, standalone } => {
+        RenderCommand::Raylib { krb_file, width, height, title, debug, standalone, vars, info } => {
             validate_krb_file(&krb_file)?;
             
             let mut cmd_args = Vec::<String>::new();
@@ -157,11 +223,31 @@ fn main() -> Result<()> {
             if standalone {
                 cmd_args.push("--standalone".to_string());
             }
+            push_variable_args(&mut cmd_args, vars, info);
             cmd_args.push(krb_file);
             
             run_backend_binary("kryon-renderer-raylib", &cmd_args)
         }
         
+        RenderCommand::Headless { krb_file, output, width, height, frames, debug, vars } => {
+            validate_krb_file(&krb_file)?;
+            
+            let mut cmd_args = vec!["--output".to_string(), output];
+            for (flag, value) in [("--width", width), ("--height", height), ("--frames", frames)] {
+                if let Some(value) = value {
+                    cmd_args.push(flag.to_string());
+                    cmd_args.push(value.to_string());
+                }
+            }
+            if debug {
+                cmd_args.push("--debug".to_string());
+            }
+            push_variable_args(&mut cmd_args, vars, false);
+            cmd_args.push(krb_file);
+            
+            run_backend_binary("kryon-renderer-headless", &cmd_args)
+        }
+        
         RenderCommand::Debug { krb_file, format, output, show_properties, show_layout, show_colors } => {
             validate_krb_file(&krb_file)?;
             
@@ -193,6 +279,16 @@ fn main() -> Result<()> {
     }
 }
 
+fn push_variable_args(cmd_args: &mut Vec<String>, vars: Vec<String>, info: bool) {
+    for var in vars {
+        cmd_args.push("--var".to_string());
+        cmd_args.push(var);
+    }
+    if info {
+        cmd_args.push("--info".to_string());
+    }
+}
+
 fn validate_krb_file(path: &str) -> Result<()> {
     if !Path::new(path).exists() {
         anyhow::bail!("KRB file not found: {}", path);
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use glam::Vec2;
+use kryon_html::SsrOptions;
+use std::fs;
+use std::path::Path;
+
+#[derive(Parser)]
+#[command(name = "kryon-ssr")]
+#[command(about = "Pre-render a KRB file to a static HTML page")]
+struct Args {
+    /// Path to the .krb file to render
+    krb_file: String,
+
+    /// Viewport width in pixels
+    #[arg(long, default_value_t = 1280.0)]
+    width: f32,
+
+    /// Viewport height in pixels
+    #[arg(long, default_value_t = 800.0)]
+    height: f32,
+
+    /// Page title (defaults to the file name)
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Tag nodes with element ids so the wasm runtime can hydrate the page
+    #[arg(long)]
+    hydrate: bool,
+
+    /// Save output to file instead of stdout
+    #[arg(long, short)]
+    output: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let krb_file = kryon_core::load_krb_file(&args.krb_file)
+        .with_context(|| format!("Failed to load KRB file: {}", args.krb_file))?;
+
+    let title = args.title.unwrap_or_else(|| {
+        Path::new(&args.krb_file).file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+    });
+    let options = SsrOptions { viewport: Vec2::new(args.width, args.height), title, hydrate: args.hydrate };
+    let html = kryon_html::render_krb(&krb_file, &options)?;
+
+    if let Some(output_file) = args.output {
+        fs::write(&output_file, html)
+            .with_context(|| format!("Failed to write to file: {}", output_file))?;
+        eprintln!("Output written to: {}", output_file);
+    } else {
+        print!("{}", html);
+    }
+
+    Ok(())
+}
@@ -134,6 +134,15 @@ fn main() -> anyhow::Result<()> {
                             KeyCode::Backspace => RenderKeyCode::Backspace,
                             KeyCode::Esc => RenderKeyCode::Escape,
                             KeyCode::Tab => RenderKeyCode::Tab,
+                            KeyCode::Delete => RenderKeyCode::Delete,
+                            KeyCode::Left => RenderKeyCode::ArrowLeft,
+                            KeyCode::Right => RenderKeyCode::ArrowRight,
+                            KeyCode::Up => RenderKeyCode::ArrowUp,
+                            KeyCode::Down => RenderKeyCode::ArrowDown,
+                            KeyCode::Home => RenderKeyCode::Home,
+                            KeyCode::End => RenderKeyCode::End,
+                            KeyCode::PageUp => RenderKeyCode::PageUp,
+                            KeyCode::PageDown => RenderKeyCode::PageDown,
                             KeyCode::Char(' ') => RenderKeyCode::Space,
                             KeyCode::Char(c) => RenderKeyCode::Character(c),
                             _ => continue,